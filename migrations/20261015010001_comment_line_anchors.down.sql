@@ -0,0 +1,6 @@
+DROP INDEX IF EXISTS idx_comments_mr_anchored;
+ALTER TABLE comments DROP CONSTRAINT IF EXISTS comments_anchor_complete;
+ALTER TABLE comments DROP COLUMN outdated;
+ALTER TABLE comments DROP COLUMN commit_sha;
+ALTER TABLE comments DROP COLUMN line_number;
+ALTER TABLE comments DROP COLUMN file_path;
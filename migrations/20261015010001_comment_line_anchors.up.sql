@@ -0,0 +1,14 @@
+-- Line-anchored MR review comments: file path + line on the diff at a given commit.
+ALTER TABLE comments ADD COLUMN file_path TEXT;
+ALTER TABLE comments ADD COLUMN line_number INTEGER;
+ALTER TABLE comments ADD COLUMN commit_sha TEXT;
+ALTER TABLE comments ADD COLUMN outdated BOOLEAN NOT NULL DEFAULT false;
+
+ALTER TABLE comments ADD CONSTRAINT comments_anchor_complete CHECK (
+    (file_path IS NULL AND line_number IS NULL AND commit_sha IS NULL)
+    OR (file_path IS NOT NULL AND line_number > 0 AND commit_sha IS NOT NULL)
+);
+
+-- Outdated-marking on push scans the live anchored comments of one MR
+CREATE INDEX idx_comments_mr_anchored ON comments(mr_id)
+  WHERE file_path IS NOT NULL AND outdated = false;
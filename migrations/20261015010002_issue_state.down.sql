@@ -0,0 +1,6 @@
+DROP INDEX IF EXISTS idx_issues_project_assignee;
+
+UPDATE issues SET status = 'open' WHERE status = 'in_progress';
+ALTER TABLE issues DROP CONSTRAINT issues_status_check;
+ALTER TABLE issues ADD CONSTRAINT issues_status_check
+    CHECK (status IN ('open', 'closed'));
@@ -0,0 +1,8 @@
+-- Issue lifecycle: open -> in_progress -> closed (reopen allowed).
+ALTER TABLE issues DROP CONSTRAINT issues_status_check;
+ALTER TABLE issues ADD CONSTRAINT issues_status_check
+    CHECK (status IN ('open', 'in_progress', 'closed'));
+
+-- Issue list filters by assignee
+CREATE INDEX idx_issues_project_assignee ON issues(project_id, assignee_id)
+    WHERE assignee_id IS NOT NULL;
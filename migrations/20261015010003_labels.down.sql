@@ -0,0 +1,10 @@
+ALTER TABLE issues ADD COLUMN labels TEXT[] NOT NULL DEFAULT '{}';
+
+UPDATE issues i SET labels = ARRAY(
+    SELECT lb.name FROM issue_labels il JOIN labels lb ON lb.id = il.label_id
+    WHERE il.issue_id = i.id ORDER BY lb.name
+);
+
+DROP TABLE IF EXISTS mr_labels;
+DROP TABLE IF EXISTS issue_labels;
+DROP TABLE IF EXISTS labels;
@@ -0,0 +1,44 @@
+-- Project-scoped labels, attachable to issues and merge requests.
+CREATE TABLE labels (
+    id          UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    project_id  UUID NOT NULL REFERENCES projects(id) ON DELETE CASCADE,
+    name        TEXT NOT NULL,
+    color       TEXT NOT NULL DEFAULT '#6b7280',
+    description TEXT,
+    created_at  TIMESTAMPTZ NOT NULL DEFAULT now(),
+    updated_at  TIMESTAMPTZ NOT NULL DEFAULT now(),
+    UNIQUE (project_id, name)
+);
+
+CREATE TRIGGER trg_labels_updated_at
+    BEFORE UPDATE ON labels
+    FOR EACH ROW EXECUTE FUNCTION set_updated_at();
+
+CREATE TABLE issue_labels (
+    issue_id UUID NOT NULL REFERENCES issues(id) ON DELETE CASCADE,
+    label_id UUID NOT NULL REFERENCES labels(id) ON DELETE CASCADE,
+    PRIMARY KEY (issue_id, label_id)
+);
+CREATE INDEX idx_issue_labels_label ON issue_labels(label_id);
+
+CREATE TABLE mr_labels (
+    mr_id    UUID NOT NULL REFERENCES merge_requests(id) ON DELETE CASCADE,
+    label_id UUID NOT NULL REFERENCES labels(id) ON DELETE CASCADE,
+    PRIMARY KEY (mr_id, label_id)
+);
+CREATE INDEX idx_mr_labels_label ON mr_labels(label_id);
+
+-- Move free-form issue labels into the labels table
+INSERT INTO labels (project_id, name)
+SELECT DISTINCT i.project_id, l.name
+FROM issues i CROSS JOIN LATERAL unnest(i.labels) AS l(name)
+ON CONFLICT DO NOTHING;
+
+INSERT INTO issue_labels (issue_id, label_id)
+SELECT i.id, lb.id
+FROM issues i
+CROSS JOIN LATERAL unnest(i.labels) AS l(name)
+JOIN labels lb ON lb.project_id = i.project_id AND lb.name = l.name
+ON CONFLICT DO NOTHING;
+
+ALTER TABLE issues DROP COLUMN labels;
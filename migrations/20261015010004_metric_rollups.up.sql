@@ -0,0 +1,30 @@
+-- Per-series downsampled metric aggregates, maintained by observe::rollup.
+CREATE TABLE metric_rollups_1m (
+    series_id UUID NOT NULL REFERENCES metric_series(id) ON DELETE CASCADE,
+    bucket    TIMESTAMPTZ NOT NULL,
+    count     BIGINT NOT NULL,
+    sum       DOUBLE PRECISION NOT NULL,
+    min       DOUBLE PRECISION NOT NULL,
+    max       DOUBLE PRECISION NOT NULL,
+    PRIMARY KEY (series_id, bucket)
+);
+
+CREATE TABLE metric_rollups_1h (
+    series_id UUID NOT NULL REFERENCES metric_series(id) ON DELETE CASCADE,
+    bucket    TIMESTAMPTZ NOT NULL,
+    count     BIGINT NOT NULL,
+    sum       DOUBLE PRECISION NOT NULL,
+    min       DOUBLE PRECISION NOT NULL,
+    max       DOUBLE PRECISION NOT NULL,
+    PRIMARY KEY (series_id, bucket)
+);
+
+-- Retention purges by bucket across all series
+CREATE INDEX idx_metric_rollups_1m_bucket ON metric_rollups_1m(bucket);
+CREATE INDEX idx_metric_rollups_1h_bucket ON metric_rollups_1h(bucket);
+
+-- Everything before rolled_up_to has been aggregated for that resolution
+CREATE TABLE metric_rollup_state (
+    resolution   TEXT PRIMARY KEY,
+    rolled_up_to TIMESTAMPTZ NOT NULL
+);
@@ -0,0 +1,10 @@
+DROP INDEX IF EXISTS idx_alert_events_open;
+
+UPDATE alert_events SET status = 'resolved', resolved_at = COALESCE(resolved_at, now())
+WHERE status = 'inhibited';
+
+ALTER TABLE alert_events DROP CONSTRAINT alert_events_status_check;
+ALTER TABLE alert_events ADD CONSTRAINT alert_events_status_check
+    CHECK (status IN ('firing', 'resolved'));
+
+ALTER TABLE alert_rules DROP COLUMN IF EXISTS inhibit_if;
@@ -0,0 +1,8 @@
+-- Rules named in inhibit_if suppress this rule while they fire at a higher severity
+ALTER TABLE alert_rules ADD COLUMN inhibit_if TEXT[] NOT NULL DEFAULT '{}';
+
+ALTER TABLE alert_events DROP CONSTRAINT alert_events_status_check;
+ALTER TABLE alert_events ADD CONSTRAINT alert_events_status_check
+    CHECK (status IN ('firing', 'inhibited', 'resolved'));
+
+CREATE INDEX idx_alert_events_open ON alert_events(rule_id) WHERE resolved_at IS NULL;
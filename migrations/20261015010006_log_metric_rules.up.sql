@@ -0,0 +1,15 @@
+-- Log-based metrics: each enabled rule increments a counter series
+-- `<metric_name>{service="<log service>"}` for every matching log entry.
+CREATE TABLE log_metric_rules (
+    id               UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    metric_name      TEXT NOT NULL,
+    service          TEXT,
+    level            TEXT,
+    message_contains TEXT,
+    project_id       UUID REFERENCES projects(id) ON DELETE CASCADE,
+    enabled          BOOLEAN NOT NULL DEFAULT true,
+    created_by       UUID REFERENCES users(id) ON DELETE SET NULL,
+    created_at       TIMESTAMPTZ NOT NULL DEFAULT now()
+);
+
+CREATE INDEX idx_log_metric_rules_project ON log_metric_rules(project_id);
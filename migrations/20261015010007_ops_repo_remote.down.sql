@@ -0,0 +1,2 @@
+ALTER TABLE ops_repos DROP COLUMN IF EXISTS credential_secret_id;
+ALTER TABLE ops_repos DROP COLUMN IF EXISTS remote_url;
@@ -0,0 +1,4 @@
+-- Optional upstream for an ops repo: sync fetches `remote_url` into the local
+-- bare repo, authenticating with the referenced secret (token or SSH key).
+ALTER TABLE ops_repos ADD COLUMN remote_url TEXT;
+ALTER TABLE ops_repos ADD COLUMN credential_secret_id UUID REFERENCES secrets(id) ON DELETE SET NULL;
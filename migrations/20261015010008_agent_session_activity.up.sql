@@ -0,0 +1,3 @@
+-- Last time an agent session showed signs of life (API call, logs, spans).
+-- The reaper completes running sessions idle beyond the configured window.
+ALTER TABLE agent_sessions ADD COLUMN last_activity_at TIMESTAMPTZ;
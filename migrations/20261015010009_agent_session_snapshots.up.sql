@@ -0,0 +1 @@
+ALTER TABLE agent_sessions ADD COLUMN last_snapshot_at TIMESTAMPTZ;
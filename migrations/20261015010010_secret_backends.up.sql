@@ -0,0 +1,5 @@
+-- 'local': encrypted_value holds the secret value.
+-- 'vault': encrypted_value holds an encrypted vault://path#key reference,
+--          resolved against Vault at read time.
+ALTER TABLE secrets ADD COLUMN backend TEXT NOT NULL DEFAULT 'local'
+    CHECK (backend IN ('local', 'vault'));
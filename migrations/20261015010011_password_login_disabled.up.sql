@@ -0,0 +1,2 @@
+-- Admin-enforced passkey-only login: password attempts are rejected.
+ALTER TABLE users ADD COLUMN password_login_disabled BOOLEAN NOT NULL DEFAULT false;
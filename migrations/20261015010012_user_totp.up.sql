@@ -0,0 +1,12 @@
+CREATE TABLE user_totp (
+    id               UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    user_id          UUID NOT NULL UNIQUE REFERENCES users(id) ON DELETE CASCADE,
+    -- TOTP secret, encrypted with the master key
+    encrypted_secret BYTEA NOT NULL,
+    -- Set once the user confirms enrollment with a valid code; login
+    -- requires a code only while enabled
+    enabled          BOOLEAN NOT NULL DEFAULT false,
+    -- Last accepted time step, so a code cannot be replayed
+    last_used_step   BIGINT,
+    created_at       TIMESTAMPTZ NOT NULL DEFAULT now()
+);
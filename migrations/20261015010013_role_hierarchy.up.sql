@@ -0,0 +1,4 @@
+-- Role inheritance: a role also grants every permission of its parent chain.
+ALTER TABLE roles ADD COLUMN parent_role_id UUID REFERENCES roles(id) ON DELETE SET NULL
+    CHECK (parent_role_id <> id);
+CREATE INDEX idx_roles_parent ON roles(parent_role_id);
@@ -0,0 +1,4 @@
+DROP INDEX IF EXISTS idx_audit_break_glass;
+ALTER TABLE audit_log DROP COLUMN IF EXISTS break_glass_id;
+DROP INDEX IF EXISTS idx_delegations_break_glass_active;
+ALTER TABLE delegations DROP COLUMN IF EXISTS break_glass;
@@ -0,0 +1,11 @@
+-- Break-glass: self-granted, short-lived emergency delegations.
+ALTER TABLE delegations ADD COLUMN break_glass BOOLEAN NOT NULL DEFAULT false;
+
+CREATE INDEX idx_delegations_break_glass_active
+    ON delegations(delegate_id) WHERE break_glass AND revoked_at IS NULL;
+
+-- Audit entries written while the actor held an active break-glass grant.
+ALTER TABLE audit_log ADD COLUMN break_glass_id UUID;
+
+CREATE INDEX idx_audit_break_glass ON audit_log(break_glass_id, created_at DESC)
+    WHERE break_glass_id IS NOT NULL;
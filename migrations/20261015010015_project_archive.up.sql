@@ -0,0 +1,2 @@
+-- Archived projects stay readable but refuse pushes, pipelines and edits.
+ALTER TABLE projects ADD COLUMN archived_at TIMESTAMPTZ;
@@ -0,0 +1,2 @@
+ALTER TABLE webhooks DROP COLUMN IF EXISTS path_filter;
+ALTER TABLE webhooks DROP COLUMN IF EXISTS branch_filter;
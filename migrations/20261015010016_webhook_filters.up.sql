@@ -0,0 +1,3 @@
+-- Optional delivery filters for push events; an empty array means "no filter".
+ALTER TABLE webhooks ADD COLUMN branch_filter TEXT[] NOT NULL DEFAULT '{}';
+ALTER TABLE webhooks ADD COLUMN path_filter TEXT[] NOT NULL DEFAULT '{}';
@@ -0,0 +1,18 @@
+-- Per-project Slack/Discord incoming-webhook channels for event messages.
+CREATE TABLE chat_channels (
+    id          UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    project_id  UUID NOT NULL REFERENCES projects(id) ON DELETE CASCADE,
+    kind        TEXT NOT NULL CHECK (kind IN ('slack', 'discord')),
+    url         TEXT NOT NULL,
+    events      TEXT[] NOT NULL DEFAULT '{}',
+    active      BOOLEAN NOT NULL DEFAULT true,
+    created_by  UUID REFERENCES users(id) ON DELETE SET NULL,
+    created_at  TIMESTAMPTZ NOT NULL DEFAULT now(),
+    updated_at  TIMESTAMPTZ NOT NULL DEFAULT now()
+);
+
+CREATE INDEX idx_chat_channels_project ON chat_channels(project_id);
+
+CREATE TRIGGER trg_chat_channels_updated_at
+    BEFORE UPDATE ON chat_channels
+    FOR EACH ROW EXECUTE FUNCTION set_updated_at();
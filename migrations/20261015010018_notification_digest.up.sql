@@ -0,0 +1,23 @@
+-- Per-user, per-type email delivery preference. No row means immediate delivery.
+CREATE TABLE notification_preferences (
+    user_id           UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    notification_type TEXT NOT NULL,
+    digest            TEXT NOT NULL CHECK (digest IN ('hourly', 'daily')),
+    updated_at        TIMESTAMPTZ NOT NULL DEFAULT now(),
+    PRIMARY KEY (user_id, notification_type)
+);
+
+CREATE TRIGGER trg_notification_preferences_updated_at
+    BEFORE UPDATE ON notification_preferences
+    FOR EACH ROW EXECUTE FUNCTION set_updated_at();
+
+-- Email notifications held back for the next digest of their user.
+CREATE TABLE notification_digest_queue (
+    notification_id UUID PRIMARY KEY REFERENCES notifications(id) ON DELETE CASCADE,
+    user_id         UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    digest          TEXT NOT NULL CHECK (digest IN ('hourly', 'daily')),
+    created_at      TIMESTAMPTZ NOT NULL DEFAULT now()
+);
+
+CREATE INDEX idx_notification_digest_queue_user
+    ON notification_digest_queue(user_id, digest, created_at);
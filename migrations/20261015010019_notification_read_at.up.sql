@@ -0,0 +1,8 @@
+-- Read state for in-app notifications; NULL means unread.
+ALTER TABLE notifications ADD COLUMN read_at TIMESTAMPTZ;
+
+UPDATE notifications SET read_at = created_at WHERE status = 'read';
+
+CREATE INDEX idx_notifications_unread
+    ON notifications(user_id, created_at DESC)
+    WHERE read_at IS NULL AND channel = 'in_app';
@@ -0,0 +1,10 @@
+ALTER TABLE audit_log
+    ADD CONSTRAINT audit_log_project_id_fkey
+    FOREIGN KEY (project_id) REFERENCES projects(id) ON DELETE SET NULL NOT VALID;
+
+DROP INDEX IF EXISTS idx_audit_seq;
+
+ALTER TABLE audit_log
+    DROP COLUMN entry_hash,
+    DROP COLUMN prev_hash,
+    DROP COLUMN seq;
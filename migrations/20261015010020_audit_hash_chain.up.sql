@@ -0,0 +1,11 @@
+-- Tamper-evident audit log: each entry stores the previous entry's hash and
+-- its own SHA-256 over (prev_hash, content). Existing rows stay unchained.
+ALTER TABLE audit_log
+    ADD COLUMN seq        BIGINT GENERATED ALWAYS AS IDENTITY,
+    ADD COLUMN prev_hash  TEXT,
+    ADD COLUMN entry_hash TEXT;
+
+CREATE UNIQUE INDEX idx_audit_seq ON audit_log(seq);
+
+-- ON DELETE SET NULL would rewrite hashed rows when a project is removed.
+ALTER TABLE audit_log DROP CONSTRAINT audit_log_project_id_fkey;
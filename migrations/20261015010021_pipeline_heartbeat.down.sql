@@ -0,0 +1,2 @@
+DROP INDEX IF EXISTS idx_pipelines_running_heartbeat;
+ALTER TABLE pipelines DROP COLUMN IF EXISTS heartbeat_at;
@@ -0,0 +1,5 @@
+-- Liveness of the replica executing a running pipeline. A running pipeline
+-- whose heartbeat goes stale was orphaned (crash or restart) and is requeued.
+ALTER TABLE pipelines ADD COLUMN heartbeat_at TIMESTAMPTZ;
+CREATE INDEX idx_pipelines_running_heartbeat ON pipelines(heartbeat_at)
+    WHERE status = 'running';
@@ -0,0 +1,24 @@
+-- Cron-scheduled pipeline runs (e.g. nightly builds), fired by
+-- pipeline::schedule. next_run_at is advanced with a compare-and-set when a
+-- run is enqueued, so each slot fires once across replicas.
+CREATE TABLE pipeline_schedules (
+    id               UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    project_id       UUID NOT NULL REFERENCES projects(id) ON DELETE CASCADE,
+    cron             TEXT NOT NULL,
+    git_ref          TEXT NOT NULL,
+    enabled          BOOLEAN NOT NULL DEFAULT true,
+    created_by       UUID REFERENCES users(id) ON DELETE SET NULL,
+    next_run_at      TIMESTAMPTZ,
+    last_run_at      TIMESTAMPTZ,
+    last_pipeline_id UUID REFERENCES pipelines(id) ON DELETE SET NULL,
+    created_at       TIMESTAMPTZ NOT NULL DEFAULT now(),
+    updated_at       TIMESTAMPTZ NOT NULL DEFAULT now()
+);
+
+CREATE INDEX idx_pipeline_schedules_project ON pipeline_schedules(project_id);
+CREATE INDEX idx_pipeline_schedules_due ON pipeline_schedules(next_run_at)
+    WHERE enabled;
+
+CREATE TRIGGER trg_pipeline_schedules_updated_at
+    BEFORE UPDATE ON pipeline_schedules
+    FOR EACH ROW EXECUTE FUNCTION set_updated_at();
@@ -0,0 +1,3 @@
+-- Ad-hoc variables passed when triggering a pipeline manually; exposed to
+-- every step as environment variables.
+ALTER TABLE pipelines ADD COLUMN variables JSONB NOT NULL DEFAULT '{}';
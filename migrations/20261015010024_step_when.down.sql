@@ -0,0 +1,5 @@
+ALTER TABLE pipelines DROP COLUMN IF EXISTS changed_files;
+
+ALTER TABLE pipeline_steps
+    DROP COLUMN IF EXISTS skip_reason,
+    DROP COLUMN IF EXISTS condition_when;
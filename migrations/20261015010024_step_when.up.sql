@@ -0,0 +1,7 @@
+-- Conditional steps: the step's `when` rule, why a step was skipped, and the
+-- files a push changed (for `when.changes`; NULL when unknown).
+ALTER TABLE pipeline_steps
+    ADD COLUMN condition_when JSONB,
+    ADD COLUMN skip_reason TEXT;
+
+ALTER TABLE pipelines ADD COLUMN changed_files TEXT[];
@@ -0,0 +1,3 @@
+ALTER TABLE pipelines DROP COLUMN IF EXISTS variable_groups;
+DROP TABLE IF EXISTS variable_group_entries;
+DROP TABLE IF EXISTS variable_groups;
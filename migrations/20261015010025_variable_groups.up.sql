@@ -0,0 +1,37 @@
+-- Reusable pipeline variable groups, referenced from .platform.yaml via
+-- `pipeline.variable_groups`. A group belongs to one project, or is global
+-- when project_id is NULL; a project group shadows a global one of the same
+-- name. Masked values are encrypted with the secrets engine master key and
+-- redacted from step logs.
+CREATE TABLE variable_groups (
+    id          UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    project_id  UUID REFERENCES projects(id) ON DELETE CASCADE,
+    name        TEXT NOT NULL,
+    description TEXT,
+    created_by  UUID REFERENCES users(id) ON DELETE SET NULL,
+    created_at  TIMESTAMPTZ NOT NULL DEFAULT now(),
+    updated_at  TIMESTAMPTZ NOT NULL DEFAULT now()
+);
+
+CREATE UNIQUE INDEX idx_variable_groups_project_name ON variable_groups(project_id, name)
+    WHERE project_id IS NOT NULL;
+CREATE UNIQUE INDEX idx_variable_groups_global_name ON variable_groups(name)
+    WHERE project_id IS NULL;
+
+CREATE TRIGGER trg_variable_groups_updated_at
+    BEFORE UPDATE ON variable_groups
+    FOR EACH ROW EXECUTE FUNCTION set_updated_at();
+
+CREATE TABLE variable_group_entries (
+    id              UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    group_id        UUID NOT NULL REFERENCES variable_groups(id) ON DELETE CASCADE,
+    name            TEXT NOT NULL,
+    masked          BOOLEAN NOT NULL DEFAULT false,
+    value           TEXT,
+    encrypted_value BYTEA,
+    UNIQUE (group_id, name),
+    CHECK ((masked AND value IS NULL AND encrypted_value IS NOT NULL)
+        OR (NOT masked AND value IS NOT NULL AND encrypted_value IS NULL))
+);
+
+ALTER TABLE pipelines ADD COLUMN variable_groups TEXT[] NOT NULL DEFAULT '{}';
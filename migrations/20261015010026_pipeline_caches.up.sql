@@ -0,0 +1,17 @@
+-- Dependency caches saved by pipeline steps with a `cache:` block. Each row
+-- is one tar.gz of the step's cache paths in MinIO, keyed per project. A
+-- missed key falls back to the most recently used cache matching one of the
+-- step's restore_keys prefixes; least recently used caches are evicted when
+-- the total size exceeds PLATFORM_PIPELINE_CACHE_MAX_BYTES.
+CREATE TABLE pipeline_caches (
+    id           UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    project_id   UUID NOT NULL REFERENCES projects(id) ON DELETE CASCADE,
+    cache_key    TEXT NOT NULL,
+    minio_path   TEXT NOT NULL,
+    size_bytes   BIGINT NOT NULL,
+    created_at   TIMESTAMPTZ NOT NULL DEFAULT now(),
+    last_used_at TIMESTAMPTZ NOT NULL DEFAULT now(),
+    UNIQUE (project_id, cache_key)
+);
+
+CREATE INDEX idx_pipeline_caches_last_used ON pipeline_caches(last_used_at);
@@ -0,0 +1,9 @@
+DROP INDEX IF EXISTS idx_projects_mirror_due;
+ALTER TABLE projects
+    DROP COLUMN IF EXISTS mirror_url,
+    DROP COLUMN IF EXISTS mirror_interval_secs,
+    DROP COLUMN IF EXISTS mirror_credential,
+    DROP COLUMN IF EXISTS mirror_protected_policy,
+    DROP COLUMN IF EXISTS mirror_last_synced_at,
+    DROP COLUMN IF EXISTS mirror_last_error,
+    DROP COLUMN IF EXISTS mirror_diverged;
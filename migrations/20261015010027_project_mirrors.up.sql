@@ -0,0 +1,18 @@
+-- Pull mirrors: a background task periodically fetches mirror_url into the
+-- project's bare repo. mirror_credential is an encrypted HTTP token or SSH
+-- key. When upstream rewrites a protected branch (one whose protection rule
+-- blocks force pushes), mirror_protected_policy decides: 'keep' leaves the
+-- local branch and records it in mirror_diverged, 'overwrite' follows upstream.
+ALTER TABLE projects
+    ADD COLUMN mirror_url              TEXT,
+    ADD COLUMN mirror_interval_secs    INTEGER NOT NULL DEFAULT 3600
+                                       CHECK (mirror_interval_secs >= 60),
+    ADD COLUMN mirror_credential       BYTEA,
+    ADD COLUMN mirror_protected_policy TEXT NOT NULL DEFAULT 'keep'
+                                       CHECK (mirror_protected_policy IN ('keep', 'overwrite')),
+    ADD COLUMN mirror_last_synced_at   TIMESTAMPTZ,
+    ADD COLUMN mirror_last_error       TEXT,
+    ADD COLUMN mirror_diverged         TEXT[] NOT NULL DEFAULT '{}';
+
+CREATE INDEX idx_projects_mirror_due ON projects(mirror_last_synced_at)
+    WHERE mirror_url IS NOT NULL;
@@ -0,0 +1,9 @@
+DROP INDEX IF EXISTS idx_projects_push_mirror_pending;
+ALTER TABLE projects
+    DROP COLUMN IF EXISTS push_mirror_url,
+    DROP COLUMN IF EXISTS push_mirror_credential,
+    DROP COLUMN IF EXISTS push_mirror_pending_since,
+    DROP COLUMN IF EXISTS push_mirror_next_attempt_at,
+    DROP COLUMN IF EXISTS push_mirror_attempts,
+    DROP COLUMN IF EXISTS push_mirror_last_pushed_at,
+    DROP COLUMN IF EXISTS push_mirror_last_error;
@@ -0,0 +1,16 @@
+-- Push mirrors: after every push, branches and tags are pushed to
+-- push_mirror_url. push_mirror_credential is an encrypted HTTP token or SSH
+-- key. push_mirror_pending_since is set while refs are waiting to be pushed;
+-- failed pushes stay pending and are retried at push_mirror_next_attempt_at
+-- with exponential backoff.
+ALTER TABLE projects
+    ADD COLUMN push_mirror_url              TEXT,
+    ADD COLUMN push_mirror_credential       BYTEA,
+    ADD COLUMN push_mirror_pending_since    TIMESTAMPTZ,
+    ADD COLUMN push_mirror_next_attempt_at  TIMESTAMPTZ,
+    ADD COLUMN push_mirror_attempts         INTEGER NOT NULL DEFAULT 0,
+    ADD COLUMN push_mirror_last_pushed_at   TIMESTAMPTZ,
+    ADD COLUMN push_mirror_last_error       TEXT;
+
+CREATE INDEX idx_projects_push_mirror_pending ON projects(push_mirror_next_attempt_at)
+    WHERE push_mirror_pending_since IS NOT NULL;
@@ -0,0 +1,17 @@
+-- Commit statuses: external CI (or anything else) reports a state per
+-- (commit, context), e.g. `ci/build` = `pending`. Reporting the same context
+-- again replaces the previous state. Branch protection `required_checks` name
+-- the contexts that must be `success` on the source head before a merge.
+CREATE TABLE commit_statuses (
+    id          UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    project_id  UUID NOT NULL REFERENCES projects(id) ON DELETE CASCADE,
+    sha         TEXT NOT NULL,
+    context     TEXT NOT NULL,
+    state       TEXT NOT NULL CHECK (state IN ('pending', 'success', 'failure', 'error')),
+    description TEXT,
+    target_url  TEXT,
+    created_by  UUID REFERENCES users(id) ON DELETE SET NULL,
+    created_at  TIMESTAMPTZ NOT NULL DEFAULT now(),
+    updated_at  TIMESTAMPTZ NOT NULL DEFAULT now(),
+    UNIQUE (project_id, sha, context)
+);
@@ -0,0 +1,13 @@
+-- Single sign-on identities: the OIDC subject (`iss` + `sub`) a platform user
+-- signed in with. The first login links by email; later logins use the
+-- subject so a changed email at the provider still finds the same account.
+CREATE TABLE oidc_identities (
+    issuer        TEXT NOT NULL,
+    subject       TEXT NOT NULL,
+    user_id       UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    created_at    TIMESTAMPTZ NOT NULL DEFAULT now(),
+    last_login_at TIMESTAMPTZ NOT NULL DEFAULT now(),
+    PRIMARY KEY (issuer, subject)
+);
+
+CREATE INDEX idx_oidc_identities_user ON oidc_identities(user_id);
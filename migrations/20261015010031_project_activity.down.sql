@@ -0,0 +1,2 @@
+DROP INDEX IF EXISTS idx_deploy_releases_project;
+DROP INDEX IF EXISTS idx_audit_project;
@@ -0,0 +1,5 @@
+-- Project activity feed: per-project time-ordered scans of its sources.
+CREATE INDEX idx_audit_project ON audit_log(project_id, created_at DESC)
+    WHERE project_id IS NOT NULL;
+
+CREATE INDEX idx_deploy_releases_project ON deploy_releases(project_id, created_at DESC);
@@ -0,0 +1,4 @@
+DROP INDEX IF EXISTS idx_merge_requests_search;
+ALTER TABLE merge_requests DROP COLUMN IF EXISTS search_vector;
+DROP INDEX IF EXISTS idx_issues_search;
+ALTER TABLE issues DROP COLUMN IF EXISTS search_vector;
@@ -0,0 +1,14 @@
+-- Full-text search over issue and merge request titles (weight A) and bodies (weight B).
+ALTER TABLE issues ADD COLUMN search_vector tsvector GENERATED ALWAYS AS (
+    setweight(to_tsvector('english', title), 'A')
+    || setweight(to_tsvector('english', COALESCE(body, '')), 'B')
+) STORED;
+
+CREATE INDEX idx_issues_search ON issues USING GIN (search_vector);
+
+ALTER TABLE merge_requests ADD COLUMN search_vector tsvector GENERATED ALWAYS AS (
+    setweight(to_tsvector('english', title), 'A')
+    || setweight(to_tsvector('english', COALESCE(body, '')), 'B')
+) STORED;
+
+CREATE INDEX idx_merge_requests_search ON merge_requests USING GIN (search_vector);
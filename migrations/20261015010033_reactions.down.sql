@@ -0,0 +1,5 @@
+DROP TRIGGER IF EXISTS trg_comments_delete_reactions ON comments;
+DROP TRIGGER IF EXISTS trg_merge_requests_delete_reactions ON merge_requests;
+DROP TRIGGER IF EXISTS trg_issues_delete_reactions ON issues;
+DROP FUNCTION IF EXISTS delete_target_reactions();
+DROP TABLE IF EXISTS reactions;
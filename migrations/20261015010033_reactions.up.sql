@@ -0,0 +1,33 @@
+-- Emoji reactions on issues, merge requests and comments; one per emoji per user.
+CREATE TABLE reactions (
+    id          UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    project_id  UUID NOT NULL REFERENCES projects(id) ON DELETE CASCADE,
+    target_type TEXT NOT NULL CHECK (target_type IN ('issue', 'merge_request', 'comment')),
+    target_id   UUID NOT NULL,
+    user_id     UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    emoji       TEXT NOT NULL,
+    created_at  TIMESTAMPTZ NOT NULL DEFAULT now(),
+    UNIQUE (target_type, target_id, user_id, emoji)
+);
+
+CREATE INDEX idx_reactions_target ON reactions(target_type, target_id);
+
+-- target_id has no foreign key, so reactions are removed with their target here.
+CREATE FUNCTION delete_target_reactions() RETURNS trigger AS $$
+BEGIN
+    DELETE FROM reactions WHERE target_type = TG_ARGV[0] AND target_id = OLD.id;
+    RETURN OLD;
+END;
+$$ LANGUAGE plpgsql;
+
+CREATE TRIGGER trg_issues_delete_reactions
+    AFTER DELETE ON issues
+    FOR EACH ROW EXECUTE FUNCTION delete_target_reactions('issue');
+
+CREATE TRIGGER trg_merge_requests_delete_reactions
+    AFTER DELETE ON merge_requests
+    FOR EACH ROW EXECUTE FUNCTION delete_target_reactions('merge_request');
+
+CREATE TRIGGER trg_comments_delete_reactions
+    AFTER DELETE ON comments
+    FOR EACH ROW EXECUTE FUNCTION delete_target_reactions('comment');
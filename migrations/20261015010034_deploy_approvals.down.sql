@@ -0,0 +1,13 @@
+DELETE FROM release_history WHERE action = 'approved';
+ALTER TABLE release_history DROP CONSTRAINT release_history_action_check;
+ALTER TABLE release_history ADD CONSTRAINT release_history_action_check CHECK (action IN (
+    'created','step_advanced','analysis_started','analysis_completed',
+    'promoted','paused','resumed','rolled_back','cancelled','failed',
+    'health_changed','traffic_shifted'));
+
+ALTER TABLE deploy_releases
+    DROP COLUMN IF EXISTS approved_at,
+    DROP COLUMN IF EXISTS approved_by,
+    DROP COLUMN IF EXISTS requires_approval;
+
+ALTER TABLE deploy_targets DROP COLUMN IF EXISTS requires_approval;
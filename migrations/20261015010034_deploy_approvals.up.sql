@@ -0,0 +1,14 @@
+-- Per-environment approval policy: releases to a target that requires
+-- approval stay pending until a second user approves them.
+ALTER TABLE deploy_targets ADD COLUMN requires_approval BOOLEAN NOT NULL DEFAULT false;
+
+ALTER TABLE deploy_releases
+    ADD COLUMN requires_approval BOOLEAN NOT NULL DEFAULT false,
+    ADD COLUMN approved_by UUID REFERENCES users(id) ON DELETE SET NULL,
+    ADD COLUMN approved_at TIMESTAMPTZ;
+
+ALTER TABLE release_history DROP CONSTRAINT release_history_action_check;
+ALTER TABLE release_history ADD CONSTRAINT release_history_action_check CHECK (action IN (
+    'created','step_advanced','analysis_started','analysis_completed',
+    'promoted','paused','resumed','rolled_back','cancelled','failed',
+    'health_changed','traffic_shifted','approved'));
@@ -0,0 +1,22 @@
+-- Deployment freeze windows: while one is active, deploys and rollbacks to
+-- the environment are refused unless an admin forces them. A window either
+-- recurs (opens at each cron slot for duration_minutes) or is a one-off
+-- starts_at..ends_at range.
+CREATE TABLE deploy_freeze_windows (
+    id               UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    project_id       UUID NOT NULL REFERENCES projects(id) ON DELETE CASCADE,
+    environment      TEXT NOT NULL CHECK (environment IN ('preview', 'staging', 'production')),
+    name             TEXT NOT NULL,
+    cron             TEXT,
+    duration_minutes INT CHECK (duration_minutes > 0),
+    starts_at        TIMESTAMPTZ,
+    ends_at          TIMESTAMPTZ,
+    created_by       UUID REFERENCES users(id) ON DELETE SET NULL,
+    created_at       TIMESTAMPTZ NOT NULL DEFAULT now(),
+    CHECK (
+        (cron IS NOT NULL AND duration_minutes IS NOT NULL AND starts_at IS NULL AND ends_at IS NULL)
+        OR (cron IS NULL AND duration_minutes IS NULL AND starts_at IS NOT NULL AND ends_at > starts_at)
+    )
+);
+
+CREATE INDEX idx_deploy_freeze_windows_project ON deploy_freeze_windows(project_id, environment);
@@ -0,0 +1,3 @@
+DROP TABLE IF EXISTS release_logs;
+
+ALTER TABLE deploy_releases DROP COLUMN IF EXISTS attempts;
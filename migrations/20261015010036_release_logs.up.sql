@@ -0,0 +1,15 @@
+-- Reconciler log trail for each release, shown alongside cluster events so a
+-- stuck or failed deploy can be diagnosed. An attempt is one claim of the
+-- release while pending; later phases log against the latest attempt.
+ALTER TABLE deploy_releases ADD COLUMN attempts INT NOT NULL DEFAULT 0;
+
+CREATE TABLE release_logs (
+    id          UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    release_id  UUID NOT NULL REFERENCES deploy_releases(id) ON DELETE CASCADE,
+    attempt     INT NOT NULL,
+    level       TEXT NOT NULL CHECK (level IN ('info', 'warn', 'error')),
+    message     TEXT NOT NULL,
+    created_at  TIMESTAMPTZ NOT NULL DEFAULT now()
+);
+
+CREATE INDEX idx_release_logs_release ON release_logs(release_id, created_at);
@@ -0,0 +1,13 @@
+-- Digests of images pushed by pipeline build steps, parsed from kaniko's
+-- "Pushed <repo>@sha256:..." output. Deploys from the same pipeline pin the
+-- image to this digest, since the commit-SHA or version tag is mutable.
+-- `repository` omits the registry host (e.g. `shop/app`): builds push via the
+-- in-cluster registry URL while deploys pull via the node-facing one.
+CREATE TABLE pipeline_image_digests (
+    pipeline_id UUID NOT NULL REFERENCES pipelines(id) ON DELETE CASCADE,
+    repository  TEXT NOT NULL,
+    digest      TEXT NOT NULL CHECK (digest ~ '^sha256:[0-9a-f]{64}$'),
+    step_name   TEXT NOT NULL,
+    created_at  TIMESTAMPTZ NOT NULL DEFAULT now(),
+    PRIMARY KEY (pipeline_id, repository)
+);
@@ -0,0 +1,5 @@
+-- Claim order for pending pipelines. Set at trigger time: manual runs default
+-- to high, scheduled runs to low, everything else to normal.
+ALTER TABLE pipelines
+    ADD COLUMN priority TEXT NOT NULL DEFAULT 'normal'
+        CHECK (priority IN ('high', 'normal', 'low'));
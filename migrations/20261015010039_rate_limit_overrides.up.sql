@@ -0,0 +1,12 @@
+-- Admin-set API rate limits for a single token or a whole project, replacing
+-- PLATFORM_API_RATE_LIMIT (and its route group overrides) for matching requests.
+CREATE TABLE rate_limit_overrides (
+    id                  UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    project_id          UUID UNIQUE REFERENCES projects(id) ON DELETE CASCADE,
+    token_id            UUID UNIQUE REFERENCES api_tokens(id) ON DELETE CASCADE,
+    requests_per_minute INTEGER NOT NULL CHECK (requests_per_minute >= 0),
+    created_by          UUID REFERENCES users(id) ON DELETE SET NULL,
+    created_at          TIMESTAMPTZ NOT NULL DEFAULT now(),
+    updated_at          TIMESTAMPTZ NOT NULL DEFAULT now(),
+    CHECK (num_nonnulls(project_id, token_id) = 1)
+);
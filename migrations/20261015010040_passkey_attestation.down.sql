@@ -0,0 +1,5 @@
+ALTER TABLE users DROP COLUMN IF EXISTS passkey_policy;
+ALTER TABLE passkey_credentials
+    DROP COLUMN IF EXISTS user_verified,
+    DROP COLUMN IF EXISTS attestation_format,
+    DROP COLUMN IF EXISTS aaguid;
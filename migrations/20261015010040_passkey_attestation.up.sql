@@ -0,0 +1,10 @@
+-- Authenticator details captured at passkey registration, and a per-user
+-- policy restricting which authenticators may be enrolled and used.
+ALTER TABLE passkey_credentials
+    ADD COLUMN aaguid UUID,
+    ADD COLUMN attestation_format TEXT,
+    ADD COLUMN user_verified BOOLEAN NOT NULL DEFAULT false;
+
+ALTER TABLE users
+    ADD COLUMN passkey_policy TEXT NOT NULL DEFAULT 'any'
+        CHECK (passkey_policy IN ('any', 'hardware'));
@@ -0,0 +1,2 @@
+DROP TABLE IF EXISTS platform_feature_overrides;
+DROP TABLE IF EXISTS platform_features;
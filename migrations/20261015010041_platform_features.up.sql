@@ -0,0 +1,23 @@
+-- Runtime switches for platform features (see src/features.rs): a global
+-- state per feature plus per-project and per-user overrides. Features with no
+-- row keep their built-in default.
+CREATE TABLE platform_features (
+    key        TEXT PRIMARY KEY,
+    enabled    BOOLEAN NOT NULL,
+    updated_by UUID REFERENCES users(id) ON DELETE SET NULL,
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT now()
+);
+
+CREATE TABLE platform_feature_overrides (
+    id         UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    feature    TEXT NOT NULL,
+    project_id UUID REFERENCES projects(id) ON DELETE CASCADE,
+    user_id    UUID REFERENCES users(id) ON DELETE CASCADE,
+    enabled    BOOLEAN NOT NULL,
+    created_by UUID REFERENCES users(id) ON DELETE SET NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT now(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT now(),
+    UNIQUE (feature, project_id),
+    UNIQUE (feature, user_id),
+    CHECK (num_nonnulls(project_id, user_id) = 1)
+);
@@ -0,0 +1 @@
+ALTER TABLE passkey_credentials DROP COLUMN IF EXISTS attested_aaguid;
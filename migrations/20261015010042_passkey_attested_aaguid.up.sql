@@ -0,0 +1,4 @@
+-- Authenticator model vouched for by a trusted vendor attestation CA. NULL
+-- when the attestation was not verified; the client-reported `aaguid` is
+-- informational only.
+ALTER TABLE passkey_credentials ADD COLUMN attested_aaguid UUID;
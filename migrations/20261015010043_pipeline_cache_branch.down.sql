@@ -0,0 +1,7 @@
+DELETE FROM pipeline_caches a USING pipeline_caches b
+WHERE a.project_id = b.project_id AND a.cache_key = b.cache_key
+  AND (a.last_used_at, a.id) < (b.last_used_at, b.id);
+ALTER TABLE pipeline_caches DROP CONSTRAINT pipeline_caches_project_id_branch_cache_key_key;
+ALTER TABLE pipeline_caches ADD CONSTRAINT pipeline_caches_project_id_cache_key_key
+    UNIQUE (project_id, cache_key);
+ALTER TABLE pipeline_caches DROP COLUMN branch;
@@ -0,0 +1,13 @@
+-- Scope pipeline caches to the branch that saved them. A step restores from
+-- its own branch first and falls back to the project's default branch, so a
+-- feature branch can't overwrite or poison the default branch's caches.
+-- Existing caches are attributed to the default branch.
+ALTER TABLE pipeline_caches ADD COLUMN branch TEXT;
+
+UPDATE pipeline_caches c SET branch = p.default_branch
+FROM projects p WHERE p.id = c.project_id;
+
+ALTER TABLE pipeline_caches ALTER COLUMN branch SET NOT NULL;
+ALTER TABLE pipeline_caches DROP CONSTRAINT pipeline_caches_project_id_cache_key_key;
+ALTER TABLE pipeline_caches ADD CONSTRAINT pipeline_caches_project_id_branch_cache_key_key
+    UNIQUE (project_id, branch, cache_key);
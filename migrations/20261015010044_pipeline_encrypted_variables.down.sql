@@ -0,0 +1,2 @@
+ALTER TABLE pipelines ADD COLUMN variables JSONB NOT NULL DEFAULT '{}';
+ALTER TABLE pipelines DROP COLUMN IF EXISTS encrypted_variables;
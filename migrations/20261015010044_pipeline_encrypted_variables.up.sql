@@ -0,0 +1,6 @@
+-- Trigger variables may carry credentials, so they are stored encrypted with
+-- the master key like secrets. NULL means the run has none. Existing plaintext
+-- values cannot be encrypted in SQL and are dropped; only runs still pending
+-- lose their variables.
+ALTER TABLE pipelines ADD COLUMN encrypted_variables BYTEA;
+ALTER TABLE pipelines DROP COLUMN variables;
@@ -14,7 +14,7 @@ use super::error::AgentError;
 use super::pubsub_bridge;
 
 /// Timeout for a single CLI invocation (5 minutes).
-const CLI_INVOKE_TIMEOUT: Duration = Duration::from_secs(300);
+const CLI_INVOKE_TIMEOUT: Duration = Duration::from_mins(5);
 
 /// Timeout for the CLI to emit its first NDJSON message (system init).
 /// Detects startup hangs (auth, config) in 30s instead of 300s.
@@ -26,6 +26,9 @@ pub enum AgentError {
     #[error("too many concurrent manager sessions")]
     TooManySessions,
 
+    #[error("{0}")]
+    QuotaExceeded(String),
+
     #[error(transparent)]
     Db(#[from] sqlx::Error),
 
@@ -47,6 +50,7 @@ impl From<AgentError> for ApiError {
             AgentError::TooManySessions => {
                 Self::BadRequest("too many concurrent manager sessions (max 5)".into())
             }
+            AgentError::QuotaExceeded(msg) => Self::QuotaExceeded(msg),
             AgentError::PodCreationFailed(_)
             | AgentError::AttachFailed(_)
             | AgentError::Db(_)
@@ -90,6 +94,12 @@ mod tests {
         assert!(matches!(api, ApiError::BadRequest(msg) if msg.contains("too many")));
     }
 
+    #[test]
+    fn quota_exceeded_maps_to_quota_exceeded() {
+        let api: ApiError = AgentError::QuotaExceeded("limit reached".into()).into();
+        assert!(matches!(api, ApiError::QuotaExceeded(msg) if msg == "limit reached"));
+    }
+
     #[test]
     fn pod_creation_failed_maps_to_internal() {
         let api: ApiError = AgentError::PodCreationFailed("timeout".into()).into();
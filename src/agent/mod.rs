@@ -16,6 +16,7 @@ pub mod preview_watcher;
 pub mod provider;
 pub mod pubsub_bridge;
 pub mod service;
+pub mod snapshot;
 pub mod valkey_acl;
 
 use std::fmt;
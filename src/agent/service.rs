@@ -22,6 +22,10 @@ use super::provider::{
     AgentProvider, AgentSession, BuildPodParams, ProgressEvent, ProgressKind, ProviderConfig,
 };
 
+/// Advisory lock key serialising session creation, so the quota count and
+/// the insert that follows it act as one step across requests and replicas.
+const SESSION_QUOTA_LOCK: i64 = 0x6167_656e_745f_7174;
+
 // ---------------------------------------------------------------------------
 // Provider resolution
 // ---------------------------------------------------------------------------
@@ -79,6 +83,14 @@ pub async fn create_session(
         0
     };
 
+    // Enforce per-user and per-project concurrency quotas. The count and the
+    // insert share one transaction under a lock, so concurrent creates cannot
+    // all pass the count before any of them is inserted.
+    let mut tx = state.pool.begin().await?;
+    sqlx::query!("SELECT pg_advisory_xact_lock($1)", SESSION_QUOTA_LOCK)
+        .execute(&mut *tx)
+        .await?;
+    check_session_quota(&mut tx, state, user_id, project_id).await?;
     sqlx::query!(
         r#"
         INSERT INTO agent_sessions (id, project_id, user_id, prompt, provider, provider_config, branch, status, parent_session_id, spawn_depth)
@@ -94,8 +106,12 @@ pub async fn create_session(
         parent_session_id,
         spawn_depth,
     )
-    .execute(&state.pool)
+    .execute(&mut *tx)
     .await?;
+    tx.commit().await?;
+    // From here on, an error (or a dropped request) fails the pending row so
+    // it stops holding a quota slot.
+    let pending = PendingSessionGuard::new(state, session_id);
 
     // 2. Look up project's workspace_id and namespace_slug for scope boundaries
     let project_info = sqlx::query!(
@@ -165,7 +181,7 @@ pub async fn create_session(
         resolve_active_llm_provider(state, user_id).await;
 
     // 4b. Query project secrets scoped to agent/all + merge provider env
-    let mut extra_env_vars = resolve_agent_secrets(state, project_id).await;
+    let mut extra_env_vars = resolve_agent_secrets(state, project_id, session_id, user_id).await;
     extra_env_vars.extend(provider_extra_env);
 
     // 4c. Create registry pull secret if registry is configured
@@ -287,7 +303,7 @@ pub async fn create_session(
     };
     let claude_cli_path = std::env::var("CLAUDE_CLI_PATH").ok();
 
-    let pod = provider.build_pod(BuildPodParams {
+    let mut pod = provider.build_pod(BuildPodParams {
         session: &session_for_pod,
         config: &config,
         agent_api_token: &agent_identity.api_token,
@@ -319,6 +335,7 @@ pub async fn create_session(
             None
         },
     })?;
+    crate::deployer::scheduling::apply(&mut pod, &state.config);
 
     tracing::info!(
         ?node_registry_url,
@@ -346,7 +363,6 @@ pub async fn create_session(
     // 8. Create the pod in the session namespace (subscriber is already listening)
     let pods: Api<Pod> = Api::namespaced(state.kube.clone(), &session_ns);
     if let Err(e) = pods.create(&PostParams::default(), &pod).await {
-        let _ = super::valkey_acl::delete_session_acl(&state.valkey, session_id).await;
         return Err(AgentError::PodCreationFailed(e.to_string()));
     }
 
@@ -364,11 +380,55 @@ pub async fn create_session(
     )
     .execute(&state.pool)
     .await?;
+    pending.disarm();
 
     // 10. Return the complete session
     fetch_session(&state.pool, session_id).await
 }
 
+/// Drop guard for a session row inserted as `pending`: unless disarmed once
+/// the pod is running, it marks the row failed and drops the session's Valkey
+/// ACL.
+struct PendingSessionGuard {
+    state: Option<AppState>,
+    session_id: Uuid,
+}
+
+impl PendingSessionGuard {
+    fn new(state: &AppState, session_id: Uuid) -> Self {
+        Self {
+            state: Some(state.clone()),
+            session_id,
+        }
+    }
+
+    fn disarm(mut self) {
+        self.state = None;
+    }
+}
+
+impl Drop for PendingSessionGuard {
+    fn drop(&mut self) {
+        let Some(state) = self.state.take() else {
+            return;
+        };
+        let session_id = self.session_id;
+        tokio::spawn(async move {
+            let _ = super::valkey_acl::delete_session_acl(&state.valkey, session_id).await;
+            let result = sqlx::query!(
+                "UPDATE agent_sessions SET status = 'failed', finished_at = now()
+                 WHERE id = $1 AND status = 'pending'",
+                session_id,
+            )
+            .execute(&state.pool)
+            .await;
+            if let Err(e) = result {
+                tracing::warn!(error = %e, %session_id, "failed to mark unstarted session failed");
+            }
+        });
+    }
+}
+
 /// Send a message to a running agent session.
 ///
 /// Routes via Valkey pub/sub for `uses_pubsub` sessions, otherwise falls back
@@ -590,6 +650,15 @@ pub async fn run_reaper(state: AppState, cancel: tokio_util::sync::CancellationT
                             tracing::error!(error = %e, "error reaping agent sessions");
                         }
                     }
+                    if state.config.agent_snapshot_interval_secs > 0
+                        && let Err(e) = super::snapshot::snapshot_due_sessions(
+                            &state,
+                            state.config.agent_snapshot_interval_secs,
+                        )
+                        .await
+                    {
+                        tracing::error!(error = %e, "error taking periodic agent snapshots");
+                    }
                     match reap_idle_sessions(&state).await {
                         Ok(()) => state.task_registry.heartbeat("agent_reaper"),
                         Err(e) => {
@@ -605,6 +674,16 @@ pub async fn run_reaper(state: AppState, cancel: tokio_util::sync::CancellationT
 
 /// Find running sessions whose pods have terminated and finalize them.
 async fn reap_terminated_sessions(state: &AppState) -> Result<(), AgentError> {
+    // Sessions still pending long after creation never got a pod (the platform
+    // stopped mid-creation); fail them so they stop counting against the
+    // session quotas.
+    sqlx::query!(
+        "UPDATE agent_sessions SET status = 'failed', finished_at = now() \
+         WHERE status = 'pending' AND created_at < NOW() - INTERVAL '10 minutes'",
+    )
+    .execute(&state.pool)
+    .await?;
+
     let running = sqlx::query!(
         r#"
         SELECT s.id as "id!", s.pod_name, s.agent_user_id, s.project_id,
@@ -695,31 +774,44 @@ struct IdleSession {
     agent_user_id: Option<Uuid>,
     project_id: Option<Uuid>,
     session_namespace: Option<String>,
+    /// Past the absolute lifetime (as opposed to merely idle).
+    expired: bool,
 }
 
-/// Find running sessions that have been idle for longer than the configured timeout
-/// and finalize them.
+/// Find running sessions that have been idle for longer than the configured timeout,
+/// or have outlived the absolute session lifetime, and finalize them.
 async fn reap_idle_sessions(state: &AppState) -> Result<(), AgentError> {
     let timeout_interval = format!("{} seconds", state.config.session_idle_timeout_secs);
+    // A lifetime of 0 disables the cap rather than reaping every session
+    let lifetime_interval = (state.config.session_max_lifetime_secs > 0)
+        .then(|| format!("{} seconds", state.config.session_max_lifetime_secs));
 
-    // Find running sessions where the latest message (or session creation if no messages)
-    // is older than the idle timeout.
+    // Idle: no recorded activity and no message within the timeout (session
+    // creation counts as activity). Expired: created before the lifetime cutoff;
+    // manager sessions are long-lived by design and only reaped when idle.
     let idle_sessions: Vec<IdleSession> = sqlx::query_as(
-        "SELECT s.id, s.pod_name, s.execution_mode, s.agent_user_id, s.project_id, s.session_namespace \
+        "SELECT s.id, s.pod_name, s.execution_mode, s.agent_user_id, s.project_id, s.session_namespace, \
+                COALESCE(s.execution_mode <> 'manager' AND s.created_at < NOW() - $2::interval, false) AS expired \
          FROM agent_sessions s \
          WHERE s.status = 'running' \
-           AND NOT EXISTS ( \
-             SELECT 1 FROM agent_messages m \
-             WHERE m.session_id = s.id AND m.created_at > NOW() - $1::interval \
-           ) \
-           AND s.created_at < NOW() - $1::interval",
+           AND ( \
+             COALESCE(s.execution_mode <> 'manager' AND s.created_at < NOW() - $2::interval, false) \
+             OR ( \
+               COALESCE(s.last_activity_at, s.created_at) < NOW() - $1::interval \
+               AND NOT EXISTS ( \
+                 SELECT 1 FROM agent_messages m \
+                 WHERE m.session_id = s.id AND m.created_at > NOW() - $1::interval \
+               ) \
+             ) \
+           )",
     )
     .bind(&timeout_interval)
+    .bind(&lifetime_interval)
     .fetch_all(&state.pool)
     .await?;
 
     for s in idle_sessions {
-        tracing::info!(session_id = %s.id, execution_mode = %s.execution_mode, "reaping idle agent session");
+        tracing::info!(session_id = %s.id, execution_mode = %s.execution_mode, expired = s.expired, "reaping idle agent session");
 
         match s.execution_mode.as_str() {
             "cli_subprocess" => {
@@ -766,7 +858,11 @@ async fn reap_idle_sessions(state: &AppState) -> Result<(), AgentError> {
             s.id,
             &ProgressEvent {
                 kind: ProgressKind::Completed,
-                message: "Session closed due to inactivity".into(),
+                message: if s.expired {
+                    "Session closed after reaching its maximum lifetime".into()
+                } else {
+                    "Session closed due to inactivity".into()
+                },
                 metadata: None,
             },
         )
@@ -842,6 +938,62 @@ async fn finalize_reaped_session(
 // Helpers
 // ---------------------------------------------------------------------------
 
+/// Reject session creation when the user or project already has the configured
+/// maximum of active (pending or running) sessions. Manager sessions have their
+/// own per-user limit and are not counted.
+///
+/// Callers hold [`SESSION_QUOTA_LOCK`] in `conn`'s transaction until the new
+/// row is inserted.
+async fn check_session_quota(
+    conn: &mut sqlx::PgConnection,
+    state: &AppState,
+    user_id: Uuid,
+    project_id: Uuid,
+) -> Result<(), AgentError> {
+    let active = sqlx::query!(
+        r#"SELECT COUNT(*) FILTER (WHERE user_id = $1) as "user_active!",
+                  COUNT(*) FILTER (WHERE project_id = $2) as "project_active!"
+           FROM agent_sessions
+           WHERE status IN ('pending', 'running') AND execution_mode <> 'manager'
+             AND (user_id = $1 OR project_id = $2)"#,
+        user_id,
+        project_id,
+    )
+    .fetch_one(conn)
+    .await?;
+
+    match quota_violation(
+        active.user_active,
+        active.project_active,
+        state.config.agent_session_max_per_user,
+        state.config.agent_session_max_per_project,
+    ) {
+        Some(msg) => Err(AgentError::QuotaExceeded(msg)),
+        None => Ok(()),
+    }
+}
+
+/// Describe which session quota (if any) a new session would exceed. A limit of 0
+/// disables that quota.
+fn quota_violation(
+    user_active: i64,
+    project_active: i64,
+    max_per_user: i64,
+    max_per_project: i64,
+) -> Option<String> {
+    if max_per_user > 0 && user_active >= max_per_user {
+        return Some(format!(
+            "agent session limit reached: you have {user_active} active sessions (max {max_per_user} per user); stop a session before starting another"
+        ));
+    }
+    if max_per_project > 0 && project_active >= max_per_project {
+        return Some(format!(
+            "agent session limit reached: project has {project_active} active sessions (max {max_per_project} per project); stop a session before starting another"
+        ));
+    }
+    None
+}
+
 /// Resolve the K8s namespace for a session.
 ///
 /// Priority: `session.session_namespace` (per-session ns) > fallback agent namespace.
@@ -1309,7 +1461,12 @@ async fn notify_parent_of_completion(state: &AppState, child_session_id: Uuid, c
 
 /// Resolve project secrets scoped to agent/all for injection into agent pods.
 /// Returns an empty vec on error or if no secrets engine is configured.
-async fn resolve_agent_secrets(state: &AppState, project_id: Uuid) -> Vec<(String, String)> {
+async fn resolve_agent_secrets(
+    state: &AppState,
+    project_id: Uuid,
+    session_id: Uuid,
+    user_id: Uuid,
+) -> Vec<(String, String)> {
     let Some(master_key_hex) = state.config.master_key.as_deref() else {
         return Vec::new();
     };
@@ -1325,7 +1482,18 @@ async fn resolve_agent_secrets(state: &AppState, project_id: Uuid) -> Vec<(Strin
     )
     .await
     {
-        Ok(secrets) => secrets,
+        Ok(secrets) => {
+            crate::secrets::access::record_access(
+                &state.pool,
+                &state.audit_tx,
+                project_id,
+                Some(user_id),
+                secrets.iter().map(|(name, _)| name.as_str()),
+                crate::secrets::access::AccessPurpose::AgentSession { session_id },
+            )
+            .await;
+            secrets
+        }
         Err(e) => {
             tracing::warn!(error = %e, %project_id, "failed to resolve agent secrets");
             Vec::new()
@@ -1621,6 +1789,30 @@ mod tests {
         assert_eq!(branch_name, "feature/foo");
     }
 
+    #[test]
+    fn quota_violation_under_limits_is_none() {
+        assert_eq!(quota_violation(4, 19, 5, 20), None);
+        assert_eq!(quota_violation(0, 0, 5, 20), None);
+    }
+
+    #[test]
+    fn quota_violation_reports_user_limit_first() {
+        let msg = quota_violation(5, 20, 5, 20).unwrap();
+        assert!(msg.contains("max 5 per user"), "got: {msg}");
+    }
+
+    #[test]
+    fn quota_violation_reports_project_limit() {
+        let msg = quota_violation(1, 20, 5, 20).unwrap();
+        assert!(msg.contains("max 20 per project"), "got: {msg}");
+    }
+
+    #[test]
+    fn quota_violation_zero_disables_limit() {
+        assert_eq!(quota_violation(100, 100, 0, 0), None);
+        assert!(quota_violation(100, 100, 0, 20).is_some());
+    }
+
     #[test]
     fn build_manager_mcp_config_has_all_servers() {
         let sid = Uuid::new_v4();
@@ -0,0 +1,431 @@
+// Copyright (c) 2026 Steven Hooker. Exclusively licensed to and distributed by AgentSphere GmbH.
+// SPDX-License-Identifier: BUSL-1.1
+
+//! Agent workspace snapshots in `MinIO`.
+//!
+//! A snapshot is a tar of the session pod's `/workspace`, streamed out of the
+//! `claude` container with `tar` and stored at
+//! `agents/{session_id}/snapshots/{ts}.tar`. Restoring streams the archive back
+//! into a new session's pod. `.platform/` (tool installs, kubeconfig, git
+//! credentials helper) is excluded: the new pod provisions its own. Each
+//! session keeps its newest `agent_snapshot_keep` snapshots.
+
+use std::time::Duration;
+
+use chrono::{DateTime, NaiveDateTime, Utc};
+use futures_util::StreamExt;
+use k8s_openapi::api::core::v1::Pod;
+use kube::Api;
+use kube::api::AttachParams;
+use serde::Serialize;
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use ts_rs::TS;
+use uuid::Uuid;
+
+use crate::store::AppState;
+
+use super::error::AgentError;
+use super::provider::{AgentSession, ProgressEvent, ProgressKind};
+
+/// Container in the agent pod that owns the workspace volume.
+const WORKSPACE_CONTAINER: &str = "claude";
+
+/// Timestamp format of snapshot names (`20261015T093000Z`).
+const SNAPSHOT_TS_FORMAT: &str = "%Y%m%dT%H%M%SZ";
+
+/// How long a restore waits for the new session's pod to start running.
+const RESTORE_POD_TIMEOUT: Duration = Duration::from_mins(5);
+
+/// Read size when streaming the archive out of the pod.
+const CHUNK_SIZE: usize = 1024 * 1024;
+
+/// A stored workspace snapshot of an agent session.
+#[derive(Debug, Serialize, TS)]
+#[ts(export)]
+pub struct AgentSnapshot {
+    /// Snapshot name, used to restore from it.
+    pub name: String,
+    #[ts(type = "number")]
+    pub size_bytes: u64,
+    pub created_at: DateTime<Utc>,
+}
+
+fn snapshot_prefix(session_id: Uuid) -> String {
+    format!("agents/{session_id}/snapshots/")
+}
+
+fn snapshot_path(session_id: Uuid, name: &str) -> String {
+    format!("{}{name}.tar", snapshot_prefix(session_id))
+}
+
+/// Parse a snapshot name back into its creation time. Also serves as name
+/// validation, since names end up in object paths.
+pub fn parse_snapshot_name(name: &str) -> Option<DateTime<Utc>> {
+    NaiveDateTime::parse_from_str(name, SNAPSHOT_TS_FORMAT)
+        .ok()
+        .map(|t| t.and_utc())
+}
+
+/// Tar the session's workspace out of its pod into `MinIO`.
+#[tracing::instrument(skip(state, session), fields(session_id = %session.id), err)]
+pub async fn create_snapshot(
+    state: &AppState,
+    session: &AgentSession,
+) -> Result<AgentSnapshot, AgentError> {
+    let pods = session_pods(state, session)?;
+    let pod_name = session
+        .pod_name
+        .as_deref()
+        .ok_or(AgentError::SessionNotRunning)?;
+
+    let created_at = Utc::now();
+    let name = created_at.format(SNAPSHOT_TS_FORMAT).to_string();
+    let path = snapshot_path(session.id, &name);
+
+    let mut attached = pods
+        .exec(
+            pod_name,
+            [
+                "tar",
+                "-cf",
+                "-",
+                "-C",
+                "/workspace",
+                "--exclude=./.platform",
+                ".",
+            ],
+            &AttachParams {
+                container: Some(WORKSPACE_CONTAINER.into()),
+                stdout: true,
+                stderr: false,
+                ..Default::default()
+            },
+        )
+        .await
+        .map_err(|e| AgentError::AttachFailed(e.to_string()))?;
+    let mut stdout = attached
+        .stdout()
+        .ok_or_else(|| AgentError::AttachFailed("no stdout available".into()))?;
+
+    let mut writer = state.minio.writer(&path).await.map_err(other)?;
+    let mut size_bytes = 0u64;
+    let mut buf = vec![0u8; CHUNK_SIZE];
+    loop {
+        let n = stdout
+            .read(&mut buf)
+            .await
+            .map_err(|e| AgentError::AttachFailed(e.to_string()))?;
+        if n == 0 {
+            break;
+        }
+        size_bytes += n as u64;
+        writer.write(buf[..n].to_vec()).await.map_err(other)?;
+    }
+    writer.close().await.map_err(other)?;
+    attached
+        .join()
+        .await
+        .map_err(|e| AgentError::AttachFailed(e.to_string()))?;
+
+    if size_bytes == 0 {
+        // tar failed in the pod (e.g. no workspace yet): don't keep an empty archive
+        let _ = state.minio.delete(&path).await;
+        return Err(AgentError::Other(anyhow::anyhow!(
+            "snapshot produced no data"
+        )));
+    }
+
+    sqlx::query!(
+        "UPDATE agent_sessions SET last_snapshot_at = $2 WHERE id = $1",
+        session.id,
+        created_at,
+    )
+    .execute(&state.pool)
+    .await?;
+
+    tracing::info!(%path, size_bytes, "agent workspace snapshot stored");
+    if let Err(e) = prune_snapshots(state, session.id, state.config.agent_snapshot_keep).await {
+        tracing::warn!(error = %e, session_id = %session.id, "failed to prune old agent snapshots");
+    }
+    Ok(AgentSnapshot {
+        name,
+        size_bytes,
+        created_at,
+    })
+}
+
+/// List a session's snapshots, newest first.
+pub async fn list_snapshots(
+    state: &AppState,
+    session_id: Uuid,
+) -> Result<Vec<AgentSnapshot>, AgentError> {
+    let entries = match state.minio.list(&snapshot_prefix(session_id)).await {
+        Ok(entries) => entries,
+        Err(e) if e.kind() == opendal::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(e) => return Err(other(e)),
+    };
+
+    let mut snapshots: Vec<AgentSnapshot> = entries
+        .iter()
+        .filter_map(|entry| {
+            let name = entry.name().strip_suffix(".tar")?;
+            Some(AgentSnapshot {
+                name: name.to_owned(),
+                size_bytes: entry.metadata().content_length(),
+                created_at: parse_snapshot_name(name)?,
+            })
+        })
+        .collect();
+    snapshots.sort_by_key(|s| std::cmp::Reverse(s.created_at));
+    Ok(snapshots)
+}
+
+/// Snapshots beyond the newest `keep` (at least one is always kept), given
+/// snapshots sorted newest first.
+fn expired_snapshots(snapshots: &[AgentSnapshot], keep: usize) -> &[AgentSnapshot] {
+    &snapshots[keep.max(1).min(snapshots.len())..]
+}
+
+/// Delete all but the newest `keep` snapshots of a session.
+async fn prune_snapshots(
+    state: &AppState,
+    session_id: Uuid,
+    keep: usize,
+) -> Result<(), AgentError> {
+    let snapshots = list_snapshots(state, session_id).await?;
+    for expired in expired_snapshots(&snapshots, keep) {
+        state
+            .minio
+            .delete(&snapshot_path(session_id, &expired.name))
+            .await
+            .map_err(other)?;
+        tracing::debug!(%session_id, name = %expired.name, "pruned agent snapshot");
+    }
+    Ok(())
+}
+
+/// Whether a snapshot exists for the given session.
+pub async fn snapshot_exists(
+    state: &AppState,
+    session_id: Uuid,
+    name: &str,
+) -> Result<bool, AgentError> {
+    state
+        .minio
+        .exists(&snapshot_path(session_id, name))
+        .await
+        .map_err(other)
+}
+
+/// Restore a snapshot into a freshly created session once its pod is running.
+///
+/// Runs in the background after session creation; the outcome is published as
+/// a progress event on the target session.
+pub fn spawn_restore(
+    state: AppState,
+    target_session_id: Uuid,
+    source_session_id: Uuid,
+    name: String,
+) {
+    tokio::spawn(async move {
+        let result = restore_snapshot(&state, target_session_id, source_session_id, &name).await;
+        let (kind, message) = match result {
+            Ok(()) => (
+                ProgressKind::Milestone,
+                format!("Workspace restored from snapshot {name}"),
+            ),
+            Err(ref e) => {
+                tracing::error!(error = %e, %target_session_id, %name, "snapshot restore failed");
+                (
+                    ProgressKind::Error,
+                    format!("Failed to restore snapshot {name}: {e}"),
+                )
+            }
+        };
+        let _ = super::pubsub_bridge::publish_event(
+            &state.valkey,
+            target_session_id,
+            &ProgressEvent {
+                kind,
+                message,
+                metadata: None,
+            },
+        )
+        .await;
+    });
+}
+
+#[tracing::instrument(skip(state), err)]
+async fn restore_snapshot(
+    state: &AppState,
+    target_session_id: Uuid,
+    source_session_id: Uuid,
+    name: &str,
+) -> Result<(), AgentError> {
+    let session = super::service::fetch_session(&state.pool, target_session_id).await?;
+    let pods = session_pods(state, &session)?;
+    let pod_name = session
+        .pod_name
+        .as_deref()
+        .ok_or(AgentError::SessionNotRunning)?;
+    wait_for_running(&pods, pod_name).await?;
+
+    let reader = state
+        .minio
+        .reader(&snapshot_path(source_session_id, name))
+        .await
+        .map_err(other)?;
+    let mut stream = reader.into_bytes_stream(..).await.map_err(other)?;
+
+    let mut attached = pods
+        .exec(
+            pod_name,
+            ["tar", "-xf", "-", "-C", "/workspace"],
+            &AttachParams {
+                container: Some(WORKSPACE_CONTAINER.into()),
+                stdin: true,
+                stdout: false,
+                stderr: false,
+                ..Default::default()
+            },
+        )
+        .await
+        .map_err(|e| AgentError::AttachFailed(e.to_string()))?;
+    let mut stdin = attached
+        .stdin()
+        .ok_or_else(|| AgentError::AttachFailed("no stdin available".into()))?;
+
+    while let Some(chunk) = stream.next().await {
+        let chunk = chunk.map_err(|e| AgentError::Other(e.into()))?;
+        stdin
+            .write_all(&chunk)
+            .await
+            .map_err(|e| AgentError::AttachFailed(e.to_string()))?;
+    }
+    stdin
+        .shutdown()
+        .await
+        .map_err(|e| AgentError::AttachFailed(e.to_string()))?;
+    drop(stdin);
+    attached
+        .join()
+        .await
+        .map_err(|e| AgentError::AttachFailed(e.to_string()))?;
+
+    tracing::info!(%target_session_id, %source_session_id, name, "agent workspace restored");
+    Ok(())
+}
+
+/// Take a snapshot of every running pod session whose last snapshot is older
+/// than `interval_secs` (called from the reaper loop when periodic snapshots are on).
+pub async fn snapshot_due_sessions(state: &AppState, interval_secs: u64) -> Result<(), AgentError> {
+    #[allow(clippy::cast_precision_loss)]
+    let interval_secs = interval_secs as f64;
+    let due = sqlx::query_scalar!(
+        "SELECT id FROM agent_sessions \
+         WHERE status = 'running' AND execution_mode = 'pod' AND pod_name IS NOT NULL \
+           AND COALESCE(last_snapshot_at, created_at) < NOW() - make_interval(secs => $1)",
+        interval_secs
+    )
+    .fetch_all(&state.pool)
+    .await?;
+
+    for session_id in due {
+        let session = super::service::fetch_session(&state.pool, session_id).await?;
+        if let Err(e) = create_snapshot(state, &session).await {
+            tracing::warn!(error = %e, %session_id, "periodic agent snapshot failed");
+        }
+    }
+    Ok(())
+}
+
+fn session_pods(state: &AppState, session: &AgentSession) -> Result<Api<Pod>, AgentError> {
+    if session.execution_mode != "pod" {
+        return Err(AgentError::SessionNotRunning);
+    }
+    let namespace = session
+        .session_namespace
+        .as_deref()
+        .unwrap_or(&state.config.agent_namespace);
+    Ok(Api::namespaced(state.kube.clone(), namespace))
+}
+
+/// Wait until the pod's `claude` container is running (init containers done).
+async fn wait_for_running(pods: &Api<Pod>, pod_name: &str) -> Result<(), AgentError> {
+    let deadline = tokio::time::Instant::now() + RESTORE_POD_TIMEOUT;
+    loop {
+        let pod = pods.get(pod_name).await?;
+        let running = pod
+            .status
+            .as_ref()
+            .and_then(|s| s.container_statuses.as_ref())
+            .and_then(|cs| cs.iter().find(|c| c.name == WORKSPACE_CONTAINER))
+            .and_then(|c| c.state.as_ref())
+            .is_some_and(|s| s.running.is_some());
+        if running {
+            return Ok(());
+        }
+        if tokio::time::Instant::now() >= deadline {
+            return Err(AgentError::Other(anyhow::anyhow!(
+                "pod {pod_name} did not start within {}s",
+                RESTORE_POD_TIMEOUT.as_secs()
+            )));
+        }
+        tokio::time::sleep(Duration::from_secs(2)).await;
+    }
+}
+
+fn other(e: opendal::Error) -> AgentError {
+    AgentError::Other(e.into())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn snapshot_path_layout() {
+        let sid = Uuid::nil();
+        assert_eq!(
+            snapshot_path(sid, "20261015T093000Z"),
+            "agents/00000000-0000-0000-0000-000000000000/snapshots/20261015T093000Z.tar"
+        );
+    }
+
+    #[test]
+    fn snapshot_name_round_trips() {
+        let ts = "2026-10-15T09:30:00Z".parse::<DateTime<Utc>>().unwrap();
+        let name = ts.format(SNAPSHOT_TS_FORMAT).to_string();
+        assert_eq!(name, "20261015T093000Z");
+        assert_eq!(parse_snapshot_name(&name), Some(ts));
+    }
+
+    #[test]
+    fn expired_snapshots_beyond_keep() {
+        let snapshots: Vec<AgentSnapshot> = ["c", "b", "a"]
+            .into_iter()
+            .map(|name| AgentSnapshot {
+                name: name.into(),
+                size_bytes: 1,
+                created_at: Utc::now(),
+            })
+            .collect();
+        let names = |keep| -> Vec<&str> {
+            expired_snapshots(&snapshots, keep)
+                .iter()
+                .map(|s| s.name.as_str())
+                .collect()
+        };
+        assert_eq!(names(2), vec!["a"]);
+        assert_eq!(names(5), Vec::<&str>::new());
+        // Never prunes the snapshot just taken
+        assert_eq!(names(0), vec!["b", "a"]);
+    }
+
+    #[test]
+    fn parse_snapshot_name_rejects_paths() {
+        assert_eq!(parse_snapshot_name("../other/20261015T093000Z"), None);
+        assert_eq!(parse_snapshot_name("20261015T093000Z.tar"), None);
+        assert_eq!(parse_snapshot_name(""), None);
+    }
+}
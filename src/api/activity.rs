@@ -0,0 +1,160 @@
+// Copyright (c) 2026 Steven Hooker. Exclusively licensed to and distributed by AgentSphere GmbH.
+// SPDX-License-Identifier: BUSL-1.1
+
+//! Project activity feed: one timeline built from the audit log (merge
+//! requests, issues, comments, reviews, pushes), pipeline runs and
+//! deploy releases.
+
+use axum::extract::{Path, Query, State};
+use axum::routing::get;
+use axum::{Json, Router};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use ts_rs::TS;
+
+use crate::auth::middleware::AuthUser;
+use crate::error::ApiError;
+use crate::store::AppState;
+
+use super::helpers::{ListResponse, require_project_read};
+
+// ---------------------------------------------------------------------------
+// Types
+// ---------------------------------------------------------------------------
+
+#[derive(Debug, Serialize, TS)]
+#[ts(export)]
+pub struct ActivityEntry {
+    /// `merge_request`, `issue`, `comment`, `review`, `push`, `pipeline` or
+    /// `deployment`.
+    pub kind: String,
+    /// Audit action (`mr.merge`, `issue.close`, `git.push`, ...), pipeline
+    /// status (`success`, `failure`, ...) or release phase (`completed`, ...).
+    pub action: String,
+    pub actor_id: Option<Uuid>,
+    pub actor_name: Option<String>,
+    /// The merge request, issue, comment, review, pipeline or release.
+    pub resource_id: Option<Uuid>,
+    /// Kind-specific fields: `number`/`title` for merge requests and issues,
+    /// `git_ref`/`commit_sha` for pipelines, `environment`/`image_ref` for
+    /// deployments.
+    pub detail: serde_json::Value,
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ActivityParams {
+    pub limit: Option<i64>,
+    pub offset: Option<i64>,
+}
+
+// ---------------------------------------------------------------------------
+// Router
+// ---------------------------------------------------------------------------
+
+pub fn router() -> Router<AppState> {
+    Router::new().route("/api/projects/{id}/activity", get(list_activity))
+}
+
+// ---------------------------------------------------------------------------
+// Handlers
+// ---------------------------------------------------------------------------
+
+/// Newest first. Pipelines are placed at their latest transition (finish,
+/// start or creation), deployments at the time the release was created.
+async fn list_activity(
+    State(state): State<AppState>,
+    auth: AuthUser,
+    Path(id): Path<Uuid>,
+    Query(params): Query<ActivityParams>,
+) -> Result<Json<ListResponse<ActivityEntry>>, ApiError> {
+    require_project_read(&state, &auth, id).await?;
+
+    let limit = params.limit.unwrap_or(50).clamp(1, 100);
+    let offset = params.offset.unwrap_or(0).max(0);
+
+    // Audit rows are limited to collaboration events so settings and secret
+    // changes stay in the admin audit log.
+    let total = sqlx::query_scalar!(
+        r#"
+        SELECT (SELECT COUNT(*) FROM audit_log a
+                WHERE a.project_id = $1
+                  AND (a.action = 'git.push' OR a.action LIKE 'mr.%' OR a.action LIKE 'issue.%'
+                       OR a.action LIKE 'comment.%' OR a.action LIKE 'review.%'))
+             + (SELECT COUNT(*) FROM pipelines WHERE project_id = $1)
+             + (SELECT COUNT(*) FROM deploy_releases WHERE project_id = $1) AS "count!"
+        "#,
+        id
+    )
+    .fetch_one(&state.pool)
+    .await?;
+
+    let items = sqlx::query_as!(
+        ActivityEntry,
+        r#"
+        SELECT kind AS "kind!", action AS "action!", actor_id, actor_name, resource_id,
+               detail AS "detail!", created_at AS "created_at!"
+        FROM (
+            SELECT CASE
+                       WHEN a.action = 'git.push' THEN 'push'
+                       WHEN a.action LIKE 'mr.%' THEN 'merge_request'
+                       ELSE split_part(a.action, '.', 1)
+                   END AS kind,
+                   a.action,
+                   a.actor_id AS actor_id,
+                   a.actor_name AS actor_name,
+                   a.resource_id,
+                   COALESCE(a.detail, '{}'::jsonb) || jsonb_strip_nulls(jsonb_build_object(
+                       'number', COALESCE(mr.number, i.number),
+                       'title', COALESCE(mr.title, i.title)
+                   )) AS detail,
+                   a.created_at
+            FROM audit_log a
+            LEFT JOIN merge_requests mr ON a.resource = 'merge_request' AND mr.id = a.resource_id
+            LEFT JOIN issues i ON a.resource = 'issue' AND i.id = a.resource_id
+            WHERE a.project_id = $1
+              AND (a.action = 'git.push' OR a.action LIKE 'mr.%' OR a.action LIKE 'issue.%'
+                   OR a.action LIKE 'comment.%' OR a.action LIKE 'review.%')
+
+            UNION ALL
+
+            SELECT 'pipeline', p.status, p.triggered_by, u.name, p.id,
+                   jsonb_strip_nulls(jsonb_build_object(
+                       'git_ref', p.git_ref,
+                       'commit_sha', p.commit_sha,
+                       'trigger', p.trigger
+                   )),
+                   COALESCE(p.finished_at, p.started_at, p.created_at)
+            FROM pipelines p
+            LEFT JOIN users u ON u.id = p.triggered_by
+            WHERE p.project_id = $1
+
+            UNION ALL
+
+            SELECT 'deployment', r.phase, r.deployed_by, u.name, r.id,
+                   jsonb_strip_nulls(jsonb_build_object(
+                       'environment', t.environment,
+                       'target', t.name,
+                       'image_ref', r.image_ref,
+                       'strategy', r.strategy
+                   )),
+                   r.created_at
+            FROM deploy_releases r
+            JOIN deploy_targets t ON t.id = r.target_id
+            LEFT JOIN users u ON u.id = r.deployed_by
+            WHERE r.project_id = $1
+        ) feed
+        ORDER BY created_at DESC, resource_id
+        LIMIT $2 OFFSET $3
+        "#,
+        id,
+        limit,
+        offset,
+    )
+    .fetch_all(&state.pool)
+    .await?;
+
+    Ok(Json(ListResponse { items, total }))
+}
@@ -4,7 +4,7 @@
 use axum::extract::{Path, Query, State};
 use axum::http::StatusCode;
 use axum::response::IntoResponse;
-use axum::routing::{delete, get, post};
+use axum::routing::{delete, get, post, put};
 use axum::{Json, Router};
 use chrono::{DateTime, Duration, Utc};
 use serde::{Deserialize, Serialize};
@@ -17,8 +17,9 @@ use crate::api::helpers::{ListResponse, require_admin};
 use crate::api::users::{CreateTokenResponse, ListParams, TokenResponse, UserResponse};
 use crate::audit::{AuditEntry, send_audit};
 use crate::auth::middleware::AuthUser;
-use crate::auth::token;
+use crate::auth::passkey::PasskeyPolicy;
 use crate::auth::user_type::UserType;
+use crate::auth::{lockout, token};
 use crate::error::ApiError;
 use crate::rbac::{Permission, delegation, resolver};
 use crate::store::AppState;
@@ -35,6 +36,8 @@ pub struct RoleResponse {
     pub name: String,
     pub description: Option<String>,
     pub is_system: bool,
+    /// Role whose permissions this role inherits (transitively).
+    pub parent_role_id: Option<Uuid>,
     pub created_at: DateTime<Utc>,
 }
 
@@ -42,6 +45,13 @@ pub struct RoleResponse {
 pub struct CreateRoleRequest {
     pub name: String,
     pub description: Option<String>,
+    pub parent_role_id: Option<Uuid>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct SetRoleParentRequest {
+    /// `null` removes the parent.
+    pub parent_role_id: Option<Uuid>,
 }
 
 #[derive(Debug, Deserialize)]
@@ -97,6 +107,12 @@ pub struct CreateServiceAccountRequest {
     pub project_id: Option<Uuid>,
 }
 
+#[derive(Debug, Deserialize)]
+pub struct SetPasskeyPolicyRequest {
+    /// `any` or `hardware`.
+    pub policy: String,
+}
+
 #[derive(Debug, Serialize, TS)]
 #[ts(export)]
 pub struct ServiceAccountResponse {
@@ -120,12 +136,18 @@ pub fn router() -> Router<AppState> {
             "/api/admin/roles/{id}/permissions",
             get(list_role_permissions).put(set_role_permissions),
         )
+        .route("/api/admin/roles/{id}/parent", put(set_role_parent))
         // User role assignments
         .route("/api/admin/users/{user_id}/roles", post(assign_role))
         .route(
             "/api/admin/users/{user_id}/roles/{role_id}",
             delete(remove_role),
         )
+        .route("/api/admin/users/{user_id}/unlock", post(unlock_user))
+        .route(
+            "/api/admin/users/{user_id}/passkey-policy",
+            put(set_passkey_policy),
+        )
         // Delegations
         .route(
             "/api/admin/delegations",
@@ -182,7 +204,8 @@ async fn list_roles(
 
     let roles = sqlx::query_as!(
         RoleResponse,
-        "SELECT id, name, description, is_system, created_at FROM roles ORDER BY name LIMIT 200"
+        "SELECT id, name, description, is_system, parent_role_id, created_at
+         FROM roles ORDER BY name LIMIT 200",
     )
     .fetch_all(&state.pool)
     .await?;
@@ -207,15 +230,18 @@ async fn create_role(
         validation::check_length("description", desc, 0, 10_000)?;
     }
 
+    if let Some(parent_id) = body.parent_role_id {
+        require_role_exists(&state, parent_id, "parent role").await?;
+    }
+
     let role = sqlx::query_as!(
         RoleResponse,
-        r#"
-        INSERT INTO roles (name, description, is_system)
-        VALUES ($1, $2, false)
-        RETURNING id, name, description, is_system, created_at
-        "#,
+        "INSERT INTO roles (name, description, is_system, parent_role_id)
+         VALUES ($1, $2, false, $3)
+         RETURNING id, name, description, is_system, parent_role_id, created_at",
         body.name,
         body.description,
+        body.parent_role_id,
     )
     .fetch_one(&state.pool)
     .await?;
@@ -229,7 +255,10 @@ async fn create_role(
             resource: "role".into(),
             resource_id: Some(role.id),
             project_id: None,
-            detail: Some(serde_json::json!({"name": body.name})),
+            detail: Some(serde_json::json!({
+                "name": body.name,
+                "parent_role_id": body.parent_role_id,
+            })),
             ip_addr: auth.ip_addr.clone(),
         },
     );
@@ -244,20 +273,17 @@ async fn get_role(
 ) -> Result<Json<RoleResponse>, ApiError> {
     require_admin(&state, &auth).await?;
 
-    let row =
-        sqlx::query("SELECT id, name, description, is_system, created_at FROM roles WHERE id = $1")
-            .bind(id)
-            .fetch_optional(&state.pool)
-            .await?
-            .ok_or_else(|| ApiError::NotFound("role".into()))?;
+    let role = sqlx::query_as!(
+        RoleResponse,
+        "SELECT id, name, description, is_system, parent_role_id, created_at
+         FROM roles WHERE id = $1",
+        id,
+    )
+    .fetch_optional(&state.pool)
+    .await?
+    .ok_or_else(|| ApiError::NotFound("role".into()))?;
 
-    Ok(Json(RoleResponse {
-        id: row.get("id"),
-        name: row.get("name"),
-        description: row.get("description"),
-        is_system: row.get("is_system"),
-        created_at: row.get("created_at"),
-    }))
+    Ok(Json(role))
 }
 
 #[tracing::instrument(skip(state, body), fields(%id), err)]
@@ -288,16 +314,17 @@ async fn update_role(
         return Err(ApiError::Conflict("cannot modify system role".into()));
     }
 
-    let row = sqlx::query(
+    let role = sqlx::query_as!(
+        RoleResponse,
         "UPDATE roles SET \
              name = COALESCE($2, name), \
              description = COALESCE($3, description) \
          WHERE id = $1 \
-         RETURNING id, name, description, is_system, created_at",
+         RETURNING id, name, description, is_system, parent_role_id, created_at",
+        id,
+        body.name,
+        body.description,
     )
-    .bind(id)
-    .bind(&body.name)
-    .bind(&body.description)
     .fetch_one(&state.pool)
     .await?;
 
@@ -315,13 +342,7 @@ async fn update_role(
         },
     );
 
-    Ok(Json(RoleResponse {
-        id: row.get("id"),
-        name: row.get("name"),
-        description: row.get("description"),
-        is_system: row.get("is_system"),
-        created_at: row.get("created_at"),
-    }))
+    Ok(Json(role))
 }
 
 #[tracing::instrument(skip(state), fields(%id), err)]
@@ -455,16 +476,8 @@ async fn set_role_permissions(
 
     tx.commit().await?;
 
-    // A26: invalidate permission cache for all users with this role
-    let affected_users: Vec<Uuid> =
-        sqlx::query_scalar("SELECT user_id FROM user_roles WHERE role_id = $1")
-            .bind(id)
-            .fetch_all(&state.pool)
-            .await
-            .unwrap_or_default();
-    for uid in affected_users {
-        let _ = resolver::invalidate_permissions(&state.valkey, uid, None).await;
-    }
+    // A26: invalidate permission cache for all users with this role or a child role
+    invalidate_role_users(&state, id).await;
 
     send_audit(
         &state.audit_tx,
@@ -483,6 +496,88 @@ async fn set_role_permissions(
     Ok(Json(serde_json::json!({"ok": true})))
 }
 
+/// Set or clear the role this role inherits permissions from.
+#[tracing::instrument(skip(state, body), fields(%id), err)]
+async fn set_role_parent(
+    State(state): State<AppState>,
+    auth: AuthUser,
+    Path(id): Path<Uuid>,
+    Json(body): Json<SetRoleParentRequest>,
+) -> Result<Json<RoleResponse>, ApiError> {
+    require_admin(&state, &auth).await?;
+
+    let is_system: bool = sqlx::query_scalar!("SELECT is_system FROM roles WHERE id = $1", id,)
+        .fetch_optional(&state.pool)
+        .await?
+        .ok_or_else(|| ApiError::NotFound("role".into()))?;
+    if is_system {
+        return Err(ApiError::Conflict("cannot modify system role".into()));
+    }
+
+    if let Some(parent_id) = body.parent_role_id {
+        require_role_exists(&state, parent_id, "parent role").await?;
+        if resolver::role_parent_would_cycle(&state.pool, id, parent_id)
+            .await
+            .map_err(ApiError::Internal)?
+        {
+            return Err(ApiError::BadRequest(
+                "parent_role_id would create a cycle in the role hierarchy".into(),
+            ));
+        }
+    }
+
+    let role = sqlx::query_as!(
+        RoleResponse,
+        "UPDATE roles SET parent_role_id = $2 WHERE id = $1
+         RETURNING id, name, description, is_system, parent_role_id, created_at",
+        id,
+        body.parent_role_id,
+    )
+    .fetch_one(&state.pool)
+    .await?;
+
+    invalidate_role_users(&state, id).await;
+
+    send_audit(
+        &state.audit_tx,
+        AuditEntry {
+            actor_id: auth.user_id,
+            actor_name: auth.user_name.clone(),
+            action: "role.set_parent".into(),
+            resource: "role".into(),
+            resource_id: Some(id),
+            project_id: None,
+            detail: Some(serde_json::json!({"parent_role_id": body.parent_role_id})),
+            ip_addr: auth.ip_addr.clone(),
+        },
+    );
+
+    Ok(Json(role))
+}
+
+async fn require_role_exists(state: &AppState, id: Uuid, what: &str) -> Result<(), ApiError> {
+    let exists: bool = sqlx::query_scalar!(
+        r#"SELECT EXISTS(SELECT 1 FROM roles WHERE id = $1) as "exists!""#,
+        id,
+    )
+    .fetch_one(&state.pool)
+    .await?;
+    if !exists {
+        return Err(ApiError::NotFound(what.into()));
+    }
+    Ok(())
+}
+
+/// Drop cached permissions of everyone holding `role_id` or a role inheriting from it.
+async fn invalidate_role_users(state: &AppState, role_id: Uuid) {
+    let affected_users = resolver::users_inheriting_role(&state.pool, role_id)
+        .await
+        .unwrap_or_default();
+    for uid in affected_users {
+        let _ = resolver::invalidate_permissions(&state.valkey, uid, None).await;
+    }
+}
+
 // ---------------------------------------------------------------------------
 // User role assignment handlers
 // ---------------------------------------------------------------------------
@@ -567,6 +662,88 @@ async fn remove_role(
     Ok(StatusCode::NO_CONTENT)
 }
 
+/// Lift a failed-login lockout before it expires.
+#[tracing::instrument(skip(state), fields(%user_id), err)]
+async fn unlock_user(
+    State(state): State<AppState>,
+    auth: AuthUser,
+    Path(user_id): Path<Uuid>,
+) -> Result<Json<serde_json::Value>, ApiError> {
+    require_admin(&state, &auth).await?;
+
+    let exists = sqlx::query_scalar!(
+        r#"SELECT EXISTS(SELECT 1 FROM users WHERE id = $1) AS "exists!""#,
+        user_id
+    )
+    .fetch_one(&state.pool)
+    .await?;
+    if !exists {
+        return Err(ApiError::NotFound("user".into()));
+    }
+
+    let was_locked = lockout::unlock(&state.valkey, user_id).await?;
+
+    send_audit(
+        &state.audit_tx,
+        AuditEntry {
+            actor_id: auth.user_id,
+            actor_name: auth.user_name.clone(),
+            action: "user.unlock".into(),
+            resource: "user".into(),
+            resource_id: Some(user_id),
+            project_id: None,
+            detail: Some(serde_json::json!({"was_locked": was_locked})),
+            ip_addr: auth.ip_addr.clone(),
+        },
+    );
+
+    Ok(Json(
+        serde_json::json!({"ok": true, "was_locked": was_locked}),
+    ))
+}
+
+/// Set which authenticators a user may enrol and sign in with. Under
+/// `hardware`, only attested, user-verifying, device-bound keys whose AAGUID
+/// is in `PLATFORM_PASSKEY_HARDWARE_AAGUIDS` are accepted.
+#[tracing::instrument(skip(state, body), fields(%user_id), err)]
+async fn set_passkey_policy(
+    State(state): State<AppState>,
+    auth: AuthUser,
+    Path(user_id): Path<Uuid>,
+    Json(body): Json<SetPasskeyPolicyRequest>,
+) -> Result<Json<serde_json::Value>, ApiError> {
+    require_admin(&state, &auth).await?;
+    let policy = PasskeyPolicy::parse(&body.policy)
+        .ok_or_else(|| ApiError::BadRequest("policy must be any or hardware".into()))?;
+
+    let result = sqlx::query!(
+        "UPDATE users SET passkey_policy = $2 WHERE id = $1",
+        user_id,
+        policy.as_str(),
+    )
+    .execute(&state.pool)
+    .await?;
+    if result.rows_affected() == 0 {
+        return Err(ApiError::NotFound("user".into()));
+    }
+
+    send_audit(
+        &state.audit_tx,
+        AuditEntry {
+            actor_id: auth.user_id,
+            actor_name: auth.user_name.clone(),
+            action: "user.passkey_policy".into(),
+            resource: "user".into(),
+            resource_id: Some(user_id),
+            project_id: None,
+            detail: Some(serde_json::json!({"policy": policy.as_str()})),
+            ip_addr: auth.ip_addr.clone(),
+        },
+    );
+
+    Ok(Json(serde_json::json!({"policy": policy.as_str()})))
+}
+
 // ---------------------------------------------------------------------------
 // Delegation handlers
 // ---------------------------------------------------------------------------
@@ -629,7 +806,7 @@ async fn get_delegation(
     let row = sqlx::query(
         "SELECT d.id, d.delegator_id, d.delegate_id, d.permission_id, \
                 p.name as permission_name, d.project_id, \
-                d.expires_at, d.reason, d.revoked_at, d.created_at \
+                d.expires_at, d.reason, d.revoked_at, d.created_at, d.break_glass \
          FROM delegations d \
          JOIN permissions p ON p.id = d.permission_id \
          WHERE d.id = $1",
@@ -649,6 +826,7 @@ async fn get_delegation(
     let reason: Option<String> = row.get("reason");
     let revoked_at: Option<DateTime<Utc>> = row.get("revoked_at");
     let created_at: DateTime<Utc> = row.get("created_at");
+    let break_glass: bool = row.get("break_glass");
 
     Ok(Json(serde_json::json!({
         "id": delegation_id,
@@ -661,6 +839,7 @@ async fn get_delegation(
         "reason": reason,
         "revoked_at": revoked_at,
         "created_at": created_at,
+        "break_glass": break_glass,
     })))
 }
 
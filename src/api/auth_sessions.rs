@@ -0,0 +1,126 @@
+// Copyright (c) 2026 Steven Hooker. Exclusively licensed to and distributed by AgentSphere GmbH.
+// SPDX-License-Identifier: BUSL-1.1
+
+use axum::extract::{Path, State};
+use axum::http::StatusCode;
+use axum::routing::{delete, get};
+use axum::{Json, Router};
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use uuid::Uuid;
+
+use ts_rs::TS;
+
+use crate::audit::{AuditEntry, send_audit};
+use crate::auth::middleware::AuthUser;
+use crate::error::ApiError;
+use crate::store::AppState;
+
+use super::helpers::ListResponse;
+
+// ---------------------------------------------------------------------------
+// Types
+// ---------------------------------------------------------------------------
+
+#[derive(Debug, Serialize, TS)]
+#[ts(export, rename = "AuthSession")]
+pub struct SessionResponse {
+    pub id: Uuid,
+    pub ip_addr: Option<String>,
+    pub user_agent: Option<String>,
+    /// True for the session making this request.
+    pub current: bool,
+    pub created_at: DateTime<Utc>,
+    pub expires_at: DateTime<Utc>,
+}
+
+struct SessionRow {
+    id: Uuid,
+    token_hash: String,
+    ip_addr: Option<String>,
+    user_agent: Option<String>,
+    created_at: DateTime<Utc>,
+    expires_at: DateTime<Utc>,
+}
+
+// ---------------------------------------------------------------------------
+// Router
+// ---------------------------------------------------------------------------
+
+pub fn router() -> Router<AppState> {
+    Router::new()
+        .route("/api/auth/sessions", get(list_sessions))
+        .route("/api/auth/sessions/{id}", delete(revoke_session))
+}
+
+// ---------------------------------------------------------------------------
+// Handlers
+// ---------------------------------------------------------------------------
+
+/// Active (unexpired) login sessions of the current user, newest first.
+async fn list_sessions(
+    State(state): State<AppState>,
+    auth: AuthUser,
+) -> Result<Json<ListResponse<SessionResponse>>, ApiError> {
+    let rows = sqlx::query_as!(
+        SessionRow,
+        "SELECT id, token_hash, host(ip_addr) AS ip_addr, user_agent, created_at, expires_at
+         FROM auth_sessions
+         WHERE user_id = $1 AND expires_at > now()
+         ORDER BY created_at DESC",
+        auth.user_id
+    )
+    .fetch_all(&state.pool)
+    .await?;
+
+    let items: Vec<SessionResponse> = rows
+        .into_iter()
+        .map(|r| SessionResponse {
+            current: auth.session_token_hash.as_deref() == Some(r.token_hash.as_str()),
+            id: r.id,
+            ip_addr: r.ip_addr,
+            user_agent: r.user_agent,
+            created_at: r.created_at,
+            expires_at: r.expires_at,
+        })
+        .collect();
+
+    let total = i64::try_from(items.len()).unwrap_or(0);
+    Ok(Json(ListResponse { items, total }))
+}
+
+/// Revoke one of the current user's sessions, e.g. on a lost device.
+#[tracing::instrument(skip(state), fields(%id, user_id = %auth.user_id), err)]
+async fn revoke_session(
+    State(state): State<AppState>,
+    auth: AuthUser,
+    Path(id): Path<Uuid>,
+) -> Result<StatusCode, ApiError> {
+    let result = sqlx::query!(
+        "DELETE FROM auth_sessions WHERE id = $1 AND user_id = $2",
+        id,
+        auth.user_id,
+    )
+    .execute(&state.pool)
+    .await?;
+
+    if result.rows_affected() == 0 {
+        return Err(ApiError::NotFound("session".into()));
+    }
+
+    send_audit(
+        &state.audit_tx,
+        AuditEntry {
+            actor_id: auth.user_id,
+            actor_name: auth.user_name.clone(),
+            action: "auth.session_revoke".into(),
+            resource: "session".into(),
+            resource_id: Some(id),
+            project_id: None,
+            detail: None,
+            ip_addr: auth.ip_addr.clone(),
+        },
+    );
+
+    Ok(StatusCode::NO_CONTENT)
+}
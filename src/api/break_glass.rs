@@ -0,0 +1,194 @@
+// Copyright (c) 2026 Steven Hooker. Exclusively licensed to and distributed by AgentSphere GmbH.
+// SPDX-License-Identifier: BUSL-1.1
+
+//! Break-glass: self-service, time-boxed emergency access.
+//!
+//! A human member of a project can grant themselves one of the permissions in
+//! `break_glass_permissions` for `break_glass_duration_secs`. Admins are
+//! notified immediately, every audit entry written during the window carries
+//! the grant's ID (`audit_log.break_glass_id`), and the reaper in
+//! `rbac::delegation` revokes the grant when the window ends.
+
+use axum::extract::{Path, State};
+use axum::http::StatusCode;
+use axum::response::IntoResponse;
+use axum::routing::{delete, post};
+use axum::{Json, Router};
+use serde::Deserialize;
+use uuid::Uuid;
+
+use crate::audit::{AuditEntry, send_audit};
+use crate::auth::middleware::AuthUser;
+use crate::auth::rate_limit;
+use crate::auth::user_type::UserType;
+use crate::error::ApiError;
+use crate::notify::dispatch;
+use crate::rbac::{Permission, delegation, resolver};
+use crate::store::AppState;
+use crate::validation;
+
+// ---------------------------------------------------------------------------
+// Types
+// ---------------------------------------------------------------------------
+
+#[derive(Debug, Deserialize)]
+pub struct BreakGlassRequest {
+    pub permission: String,
+    pub project_id: Uuid,
+    pub reason: String,
+}
+
+// ---------------------------------------------------------------------------
+// Router
+// ---------------------------------------------------------------------------
+
+pub fn router() -> Router<AppState> {
+    Router::new()
+        .route("/api/break-glass", post(grant_break_glass))
+        .route("/api/break-glass/{id}", delete(end_break_glass))
+}
+
+// ---------------------------------------------------------------------------
+// Handlers
+// ---------------------------------------------------------------------------
+
+#[tracing::instrument(skip(state, body), fields(user_id = %auth.user_id, project_id = %body.project_id), err)]
+async fn grant_break_glass(
+    State(state): State<AppState>,
+    auth: AuthUser,
+    Json(body): Json<BreakGlassRequest>,
+) -> Result<impl IntoResponse, ApiError> {
+    if auth.user_type != UserType::Human {
+        return Err(ApiError::Forbidden);
+    }
+    auth.check_project_scope(body.project_id)?;
+
+    let reason = body.reason.trim();
+    validation::check_length("reason", reason, 1, 2000)?;
+
+    let perm: Permission = body
+        .permission
+        .parse()
+        .map_err(|e: anyhow::Error| ApiError::BadRequest(e.to_string()))?;
+    if !state
+        .config
+        .break_glass_permissions
+        .iter()
+        .any(|p| p == perm.as_str())
+    {
+        return Err(ApiError::BadRequest(format!(
+            "{perm} is not available via break-glass"
+        )));
+    }
+    if !resolver::scope_allows(auth.token_scopes.as_deref(), perm) {
+        return Err(ApiError::Forbidden);
+    }
+
+    rate_limit::check_rate(
+        &state.valkey,
+        "break_glass",
+        &auth.user_id.to_string(),
+        5,
+        3600,
+    )
+    .await?;
+
+    // Emergency access is for people already on the project, not a way in.
+    let perms = resolver::effective_permissions(
+        &state.pool,
+        &state.valkey,
+        auth.user_id,
+        Some(body.project_id),
+    )
+    .await
+    .map_err(ApiError::Internal)?;
+    if !perms.contains(&Permission::ProjectRead) {
+        return Err(ApiError::NotFound("project".into()));
+    }
+    if perms.contains(&perm) {
+        return Err(ApiError::Conflict(format!("you already hold {perm}")));
+    }
+
+    let grant = delegation::create_break_glass(
+        &state.pool,
+        &state.valkey,
+        &delegation::BreakGlassParams {
+            user_id: auth.user_id,
+            permission: perm,
+            project_id: body.project_id,
+            reason: reason.to_owned(),
+            duration_secs: state.config.break_glass_duration_secs,
+        },
+    )
+    .await?;
+
+    send_audit(
+        &state.audit_tx,
+        AuditEntry {
+            actor_id: auth.user_id,
+            actor_name: auth.user_name.clone(),
+            action: "break_glass.grant".into(),
+            resource: "delegation".into(),
+            resource_id: Some(grant.id),
+            project_id: Some(body.project_id),
+            detail: Some(serde_json::json!({
+                "permission": perm.as_str(),
+                "reason": reason,
+                "expires_at": grant.expires_at,
+            })),
+            ip_addr: auth.ip_addr.clone(),
+        },
+    );
+
+    let notify_state = state.clone();
+    let (grant_id, user_name, reason) = (grant.id, auth.user_name.clone(), reason.to_owned());
+    tokio::spawn(async move {
+        dispatch::on_break_glass_granted(
+            &notify_state,
+            grant_id,
+            &user_name,
+            perm.as_str(),
+            body.project_id,
+            &reason,
+        )
+        .await;
+    });
+
+    Ok((StatusCode::CREATED, Json(grant)))
+}
+
+/// End your own break-glass grant before it expires.
+#[tracing::instrument(skip(state), fields(%id, user_id = %auth.user_id), err)]
+async fn end_break_glass(
+    State(state): State<AppState>,
+    auth: AuthUser,
+    Path(id): Path<Uuid>,
+) -> Result<StatusCode, ApiError> {
+    let project_id = sqlx::query_scalar!(
+        "SELECT project_id FROM delegations
+         WHERE id = $1 AND delegate_id = $2 AND break_glass AND revoked_at IS NULL",
+        id,
+        auth.user_id,
+    )
+    .fetch_optional(&state.pool)
+    .await?;
+    let project_id = project_id.ok_or_else(|| ApiError::NotFound("break-glass grant".into()))?;
+
+    delegation::revoke_delegation(&state.pool, &state.valkey, id).await?;
+
+    send_audit(
+        &state.audit_tx,
+        AuditEntry {
+            actor_id: auth.user_id,
+            actor_name: auth.user_name.clone(),
+            action: "break_glass.end".into(),
+            resource: "delegation".into(),
+            resource_id: Some(id),
+            project_id,
+            detail: None,
+            ip_addr: auth.ip_addr.clone(),
+        },
+    );
+
+    Ok(StatusCode::NO_CONTENT)
+}
@@ -0,0 +1,300 @@
+// Copyright (c) 2026 Steven Hooker. Exclusively licensed to and distributed by AgentSphere GmbH.
+// SPDX-License-Identifier: BUSL-1.1
+
+use axum::extract::{Path, State};
+use axum::http::StatusCode;
+use axum::response::IntoResponse;
+use axum::routing::{get, patch};
+use axum::{Json, Router};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use ts_rs::TS;
+
+use crate::audit::{AuditEntry, send_audit};
+use crate::auth::middleware::AuthUser;
+use crate::error::ApiError;
+use crate::notify::chat::{CHAT_EVENTS, ChatKind};
+use crate::store::AppState;
+use crate::validation;
+
+use super::helpers::{ListResponse, require_project_write};
+
+// ---------------------------------------------------------------------------
+// Types
+// ---------------------------------------------------------------------------
+
+#[derive(Debug, Deserialize)]
+pub struct CreateChatChannelRequest {
+    /// `slack` or `discord`.
+    pub kind: String,
+    pub url: String,
+    pub events: Vec<String>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct UpdateChatChannelRequest {
+    pub url: Option<String>,
+    pub events: Option<Vec<String>>,
+    pub active: Option<bool>,
+}
+
+#[derive(Debug, Serialize, TS)]
+#[ts(export, rename = "ChatChannel")]
+pub struct ChatChannelResponse {
+    pub id: Uuid,
+    pub project_id: Uuid,
+    pub kind: String,
+    /// Host of the incoming-webhook URL; the full URL is a credential and is never returned.
+    pub url_host: String,
+    pub events: Vec<String>,
+    pub active: bool,
+    pub created_at: DateTime<Utc>,
+}
+
+struct ChatChannelRow {
+    id: Uuid,
+    project_id: Uuid,
+    kind: String,
+    url: String,
+    events: Vec<String>,
+    active: bool,
+    created_at: DateTime<Utc>,
+}
+
+impl From<ChatChannelRow> for ChatChannelResponse {
+    fn from(r: ChatChannelRow) -> Self {
+        let url_host = url::Url::parse(&r.url)
+            .ok()
+            .and_then(|u| u.host_str().map(str::to_owned))
+            .unwrap_or_default();
+        Self {
+            id: r.id,
+            project_id: r.project_id,
+            kind: r.kind,
+            url_host,
+            events: r.events,
+            active: r.active,
+            created_at: r.created_at,
+        }
+    }
+}
+
+fn validate_url(url: &str) -> Result<(), ApiError> {
+    validation::check_url(url)?;
+    validation::check_ssrf_url(url, &["https"])
+}
+
+fn validate_events(events: &[String]) -> Result<(), ApiError> {
+    if events.is_empty() {
+        return Err(ApiError::BadRequest("events must not be empty".into()));
+    }
+    for event in events {
+        if !CHAT_EVENTS.contains(&event.as_str()) {
+            return Err(ApiError::BadRequest(format!(
+                "invalid event '{event}'; valid events: {CHAT_EVENTS:?}"
+            )));
+        }
+    }
+    Ok(())
+}
+
+// ---------------------------------------------------------------------------
+// Router
+// ---------------------------------------------------------------------------
+
+pub fn router() -> Router<AppState> {
+    Router::new()
+        .route(
+            "/api/projects/{id}/chat-channels",
+            get(list_channels).post(create_channel),
+        )
+        .route(
+            "/api/projects/{id}/chat-channels/{channel_id}",
+            patch(update_channel).delete(delete_channel),
+        )
+}
+
+// ---------------------------------------------------------------------------
+// Handlers
+// ---------------------------------------------------------------------------
+
+async fn list_channels(
+    State(state): State<AppState>,
+    auth: AuthUser,
+    Path(id): Path<Uuid>,
+) -> Result<Json<ListResponse<ChatChannelResponse>>, ApiError> {
+    require_project_write(&state, &auth, id).await?;
+
+    let rows = sqlx::query_as!(
+        ChatChannelRow,
+        r#"
+        SELECT id, project_id, kind, url, events, active, created_at
+        FROM chat_channels
+        WHERE project_id = $1
+        ORDER BY created_at DESC
+        "#,
+        id,
+    )
+    .fetch_all(&state.pool)
+    .await?;
+
+    let total = i64::try_from(rows.len()).unwrap_or(i64::MAX);
+    let items = rows.into_iter().map(ChatChannelResponse::from).collect();
+
+    Ok(Json(ListResponse { items, total }))
+}
+
+#[tracing::instrument(skip(state, body), fields(%id), err)]
+async fn create_channel(
+    State(state): State<AppState>,
+    auth: AuthUser,
+    Path(id): Path<Uuid>,
+    Json(body): Json<CreateChatChannelRequest>,
+) -> Result<impl IntoResponse, ApiError> {
+    let kind = ChatKind::parse(&body.kind)
+        .ok_or_else(|| ApiError::BadRequest("kind must be 'slack' or 'discord'".into()))?;
+    validate_url(&body.url)?;
+    validate_events(&body.events)?;
+    require_project_write(&state, &auth, id).await?;
+
+    let row = sqlx::query_as!(
+        ChatChannelRow,
+        r#"
+        INSERT INTO chat_channels (project_id, kind, url, events, created_by)
+        VALUES ($1, $2, $3, $4, $5)
+        RETURNING id, project_id, kind, url, events, active, created_at
+        "#,
+        id,
+        kind.as_str(),
+        body.url,
+        &body.events,
+        auth.user_id,
+    )
+    .fetch_one(&state.pool)
+    .await?;
+
+    send_audit(
+        &state.audit_tx,
+        AuditEntry {
+            actor_id: auth.user_id,
+            actor_name: auth.user_name.clone(),
+            action: "chat_channel.create".into(),
+            resource: "chat_channel".into(),
+            resource_id: Some(row.id),
+            project_id: Some(id),
+            detail: Some(serde_json::json!({"kind": kind.as_str(), "events": body.events})),
+            ip_addr: auth.ip_addr.clone(),
+        },
+    );
+
+    Ok((StatusCode::CREATED, Json(ChatChannelResponse::from(row))))
+}
+
+#[tracing::instrument(skip(state, body), fields(%id, %channel_id), err)]
+async fn update_channel(
+    State(state): State<AppState>,
+    auth: AuthUser,
+    Path((id, channel_id)): Path<(Uuid, Uuid)>,
+    Json(body): Json<UpdateChatChannelRequest>,
+) -> Result<Json<ChatChannelResponse>, ApiError> {
+    if let Some(ref url) = body.url {
+        validate_url(url)?;
+    }
+    if let Some(ref events) = body.events {
+        validate_events(events)?;
+    }
+    require_project_write(&state, &auth, id).await?;
+
+    let row = sqlx::query_as!(
+        ChatChannelRow,
+        r#"
+        UPDATE chat_channels SET
+            url = COALESCE($3, url),
+            events = COALESCE($4, events),
+            active = COALESCE($5, active)
+        WHERE id = $1 AND project_id = $2
+        RETURNING id, project_id, kind, url, events, active, created_at
+        "#,
+        channel_id,
+        id,
+        body.url,
+        body.events.as_deref(),
+        body.active,
+    )
+    .fetch_optional(&state.pool)
+    .await?
+    .ok_or_else(|| ApiError::NotFound("chat channel".into()))?;
+
+    send_audit(
+        &state.audit_tx,
+        AuditEntry {
+            actor_id: auth.user_id,
+            actor_name: auth.user_name.clone(),
+            action: "chat_channel.update".into(),
+            resource: "chat_channel".into(),
+            resource_id: Some(channel_id),
+            project_id: Some(id),
+            detail: None,
+            ip_addr: auth.ip_addr.clone(),
+        },
+    );
+
+    Ok(Json(ChatChannelResponse::from(row)))
+}
+
+#[tracing::instrument(skip(state), fields(%id, %channel_id), err)]
+async fn delete_channel(
+    State(state): State<AppState>,
+    auth: AuthUser,
+    Path((id, channel_id)): Path<(Uuid, Uuid)>,
+) -> Result<StatusCode, ApiError> {
+    require_project_write(&state, &auth, id).await?;
+
+    let result = sqlx::query!(
+        "DELETE FROM chat_channels WHERE id = $1 AND project_id = $2",
+        channel_id,
+        id,
+    )
+    .execute(&state.pool)
+    .await?;
+    if result.rows_affected() == 0 {
+        return Err(ApiError::NotFound("chat channel".into()));
+    }
+
+    send_audit(
+        &state.audit_tx,
+        AuditEntry {
+            actor_id: auth.user_id,
+            actor_name: auth.user_name.clone(),
+            action: "chat_channel.delete".into(),
+            resource: "chat_channel".into(),
+            resource_id: Some(channel_id),
+            project_id: Some(id),
+            detail: None,
+            ip_addr: auth.ip_addr.clone(),
+        },
+    );
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn validate_events_accepts_known() {
+        assert!(validate_events(&["pipeline".into(), "alert".into()]).is_ok());
+        assert!(validate_events(&[]).is_err());
+        assert!(validate_events(&["push".into()]).is_err());
+    }
+
+    #[test]
+    fn validate_url_requires_public_https() {
+        assert!(validate_url("https://hooks.slack.com/services/T0/B0/x").is_ok());
+        assert!(validate_url("http://hooks.slack.com/services/T0/B0/x").is_err());
+        assert!(validate_url("https://169.254.169.254/latest").is_err());
+    }
+}
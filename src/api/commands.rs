@@ -17,7 +17,7 @@ use crate::error::ApiError;
 use crate::rbac::{Permission, resolver};
 use crate::store::AppState;
 
-use super::helpers::ListResponse;
+use super::helpers::{ListResponse, require_admin};
 
 // ---------------------------------------------------------------------------
 // Request / response types
@@ -70,8 +70,9 @@ pub struct ResolvedCommandsParams {
 // Permission helpers
 // ---------------------------------------------------------------------------
 
-/// Global commands require `admin:config`. Workspace-scoped commands require
-/// workspace admin. Project-scoped commands require `project:write`.
+/// Global commands require admin from an unbounded token. Workspace-scoped
+/// commands require workspace admin. Project-scoped commands require
+/// `project:write`.
 async fn require_command_write(
     state: &AppState,
     auth: &AuthUser,
@@ -95,19 +96,7 @@ async fn require_command_write(
     } else if let Some(wid) = workspace_id {
         require_workspace_admin(state, auth, wid).await?;
     } else {
-        let allowed = resolver::has_permission_scoped(
-            &state.pool,
-            &state.valkey,
-            auth.user_id,
-            None,
-            Permission::AdminConfig,
-            auth.token_scopes.as_deref(),
-        )
-        .await
-        .map_err(ApiError::Internal)?;
-        if !allowed {
-            return Err(ApiError::Forbidden);
-        }
+        require_admin(state, auth).await?;
     }
     Ok(())
 }
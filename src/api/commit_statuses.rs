@@ -0,0 +1,281 @@
+// Copyright (c) 2026 Steven Hooker. Exclusively licensed to and distributed by AgentSphere GmbH.
+// SPDX-License-Identifier: BUSL-1.1
+
+//! Commit statuses reported by external CI under
+//! `/api/projects/{id}/commits/{sha}/statuses`, and the required-checks gate
+//! that branch protection applies to merges.
+
+use std::collections::HashMap;
+use std::hash::BuildHasher;
+
+use axum::extract::{Path, State};
+use axum::http::StatusCode;
+use axum::routing::get;
+use axum::{Json, Router};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use ts_rs::TS;
+
+use crate::auth::middleware::AuthUser;
+use crate::error::ApiError;
+use crate::store::AppState;
+use crate::validation;
+
+use super::helpers::{ListResponse, require_project_read, require_project_write};
+
+const STATES: &[&str] = &["pending", "success", "failure", "error"];
+
+// ---------------------------------------------------------------------------
+// Types
+// ---------------------------------------------------------------------------
+
+#[derive(Debug, Deserialize)]
+pub struct CreateCommitStatusRequest {
+    /// `pending`, `success`, `failure` or `error`.
+    pub state: String,
+    /// Names the check, e.g. `ci/build`. Reporting a context again replaces
+    /// its previous state.
+    #[serde(default = "default_context")]
+    pub context: String,
+    pub description: Option<String>,
+    pub target_url: Option<String>,
+}
+
+fn default_context() -> String {
+    "default".into()
+}
+
+#[derive(Debug, Serialize, TS)]
+#[ts(export, rename = "CommitStatus")]
+pub struct CommitStatusResponse {
+    pub id: Uuid,
+    pub sha: String,
+    pub context: String,
+    pub state: String,
+    pub description: Option<String>,
+    pub target_url: Option<String>,
+    pub created_by: Option<Uuid>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+// ---------------------------------------------------------------------------
+// Router
+// ---------------------------------------------------------------------------
+
+pub fn router() -> Router<AppState> {
+    Router::new().route(
+        "/api/projects/{id}/commits/{sha}/statuses",
+        get(list_statuses).post(create_status),
+    )
+}
+
+// ---------------------------------------------------------------------------
+// Handlers
+// ---------------------------------------------------------------------------
+
+async fn list_statuses(
+    State(state): State<AppState>,
+    auth: AuthUser,
+    Path((id, sha)): Path<(Uuid, String)>,
+) -> Result<Json<ListResponse<CommitStatusResponse>>, ApiError> {
+    require_project_read(&state, &auth, id).await?;
+    let sha = normalize_sha(&sha)?;
+
+    let items = sqlx::query_as!(
+        CommitStatusResponse,
+        "SELECT id, sha, context, state, description, target_url, created_by, created_at, updated_at
+         FROM commit_statuses
+         WHERE project_id = $1 AND sha = $2
+         ORDER BY context",
+        id,
+        sha,
+    )
+    .fetch_all(&state.pool)
+    .await?;
+
+    let total = i64::try_from(items.len()).unwrap_or(i64::MAX);
+    Ok(Json(ListResponse { items, total }))
+}
+
+#[tracing::instrument(skip(state, body), fields(%id, %sha), err)]
+async fn create_status(
+    State(state): State<AppState>,
+    auth: AuthUser,
+    Path((id, sha)): Path<(Uuid, String)>,
+    Json(body): Json<CreateCommitStatusRequest>,
+) -> Result<(StatusCode, Json<CommitStatusResponse>), ApiError> {
+    require_project_write(&state, &auth, id).await?;
+    let sha = normalize_sha(&sha)?;
+
+    if !STATES.contains(&body.state.as_str()) {
+        return Err(ApiError::BadRequest(format!(
+            "state must be one of: {}",
+            STATES.join(", ")
+        )));
+    }
+    validation::check_length("context", &body.context, 1, 255)?;
+    if let Some(ref description) = body.description {
+        validation::check_length("description", description, 0, 1000)?;
+    }
+    if let Some(ref url) = body.target_url {
+        validation::check_url(url)?;
+    }
+
+    let status = sqlx::query_as!(
+        CommitStatusResponse,
+        "INSERT INTO commit_statuses
+             (project_id, sha, context, state, description, target_url, created_by)
+         VALUES ($1, $2, $3, $4, $5, $6, $7)
+         ON CONFLICT (project_id, sha, context) DO UPDATE SET
+             state = EXCLUDED.state,
+             description = EXCLUDED.description,
+             target_url = EXCLUDED.target_url,
+             created_by = EXCLUDED.created_by,
+             updated_at = now()
+         RETURNING id, sha, context, state, description, target_url, created_by,
+                   created_at, updated_at",
+        id,
+        sha,
+        body.context,
+        body.state,
+        body.description,
+        body.target_url,
+        auth.user_id,
+    )
+    .fetch_one(&state.pool)
+    .await?;
+
+    Ok((StatusCode::CREATED, Json(status)))
+}
+
+/// Full SHA-1 or SHA-256 object ids only, lowercased so lookups match.
+fn normalize_sha(sha: &str) -> Result<String, ApiError> {
+    if !matches!(sha.len(), 40 | 64) || !sha.chars().all(|c| c.is_ascii_hexdigit()) {
+        return Err(ApiError::BadRequest(
+            "sha must be a full 40 or 64 character commit id".into(),
+        ));
+    }
+    Ok(sha.to_ascii_lowercase())
+}
+
+// ---------------------------------------------------------------------------
+// Required checks
+// ---------------------------------------------------------------------------
+
+/// Latest state per context reported for `sha`.
+pub async fn states_for_commit(
+    pool: &PgPool,
+    project_id: Uuid,
+    sha: &str,
+) -> Result<HashMap<String, String>, sqlx::Error> {
+    let rows = sqlx::query!(
+        "SELECT context, state FROM commit_statuses WHERE project_id = $1 AND sha = $2",
+        project_id,
+        sha.to_ascii_lowercase(),
+    )
+    .fetch_all(pool)
+    .await?;
+    Ok(rows.into_iter().map(|r| (r.context, r.state)).collect())
+}
+
+/// Check every required context against the reported states. Contexts that
+/// nobody reported fall back to `pipeline_status`, the platform's own CI
+/// result for the branch. Returns a message listing the missing and failing
+/// checks when any is not `success`.
+pub fn evaluate_required_checks<S: BuildHasher>(
+    required: &[String],
+    reported: &HashMap<String, String, S>,
+    pipeline_status: Option<&str>,
+) -> Result<(), String> {
+    let mut missing = Vec::new();
+    let mut failing = Vec::new();
+    for context in required {
+        match reported
+            .get(context)
+            .map(String::as_str)
+            .or(pipeline_status)
+        {
+            Some("success") => {}
+            Some(state) => failing.push(format!("{context} ({state})")),
+            None => missing.push(context.as_str()),
+        }
+    }
+
+    let mut problems = Vec::new();
+    if !missing.is_empty() {
+        problems.push(format!("missing: {}", missing.join(", ")));
+    }
+    if !failing.is_empty() {
+        problems.push(format!("not successful: {}", failing.join(", ")));
+    }
+    if problems.is_empty() {
+        Ok(())
+    } else {
+        Err(format!(
+            "required status checks have not passed; {}",
+            problems.join("; ")
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn reported(pairs: &[(&str, &str)]) -> HashMap<String, String> {
+        pairs
+            .iter()
+            .map(|(c, s)| ((*c).to_string(), (*s).to_string()))
+            .collect()
+    }
+
+    fn required(contexts: &[&str]) -> Vec<String> {
+        contexts.iter().map(|c| (*c).to_string()).collect()
+    }
+
+    #[test]
+    fn all_success_passes() {
+        let states = reported(&[("ci/build", "success"), ("ci/test", "success")]);
+        assert!(
+            evaluate_required_checks(&required(&["ci/build", "ci/test"]), &states, None).is_ok()
+        );
+    }
+
+    #[test]
+    fn pending_and_missing_are_listed() {
+        let states = reported(&[("ci/build", "pending")]);
+        let err = evaluate_required_checks(&required(&["ci/build", "ci/test"]), &states, None)
+            .unwrap_err();
+        assert!(err.contains("missing: ci/test"), "{err}");
+        assert!(err.contains("not successful: ci/build (pending)"), "{err}");
+    }
+
+    #[test]
+    fn unreported_context_falls_back_to_pipeline() {
+        let states = reported(&[]);
+        let checks = required(&["ci"]);
+        assert!(evaluate_required_checks(&checks, &states, Some("success")).is_ok());
+        let err = evaluate_required_checks(&checks, &states, Some("failure")).unwrap_err();
+        assert!(err.contains("ci (failure)"), "{err}");
+    }
+
+    #[test]
+    fn reported_state_overrides_pipeline() {
+        let states = reported(&[("ci/build", "failure")]);
+        assert!(
+            evaluate_required_checks(&required(&["ci/build"]), &states, Some("success")).is_err()
+        );
+    }
+
+    #[test]
+    fn sha_is_validated_and_lowercased() {
+        let sha = "ABCDEF0123456789ABCDEF0123456789ABCDEF01";
+        assert_eq!(normalize_sha(sha).unwrap(), sha.to_ascii_lowercase());
+        assert!(normalize_sha("abc123").is_err());
+        assert!(normalize_sha(&"g".repeat(40)).is_err());
+    }
+}
@@ -14,7 +14,7 @@ use crate::auth::middleware::AuthUser;
 use crate::error::ApiError;
 use crate::store::AppState;
 
-use super::helpers::{ListResponse, require_admin};
+use super::helpers::{ListResponse, ReadAudit, audit_read, require_admin};
 
 // ---------------------------------------------------------------------------
 // Types
@@ -48,9 +48,34 @@ pub struct AuditLogEntry {
     pub resource_id: Option<Uuid>,
     pub project_id: Option<Uuid>,
     pub detail: Option<serde_json::Value>,
+    /// Break-glass grant the actor was using, if any.
+    pub break_glass_id: Option<Uuid>,
     pub created_at: DateTime<Utc>,
 }
 
+/// Result of recomputing the audit log hash chain.
+#[derive(Debug, Serialize, TS)]
+#[ts(export)]
+pub struct AuditChainReport {
+    pub intact: bool,
+    /// Chained entries verified before the first break (or in total when intact).
+    #[ts(type = "number")]
+    pub checked: i64,
+    pub first_break: Option<AuditChainBreak>,
+}
+
+#[derive(Debug, Serialize, TS)]
+#[ts(export)]
+pub struct AuditChainBreak {
+    pub id: Uuid,
+    #[ts(type = "number")]
+    pub seq: i64,
+    pub created_at: DateTime<Utc>,
+    /// `hash_mismatch` (row edited), `link_mismatch` (row removed or inserted)
+    /// or `unchained` (row without a hash after chaining started).
+    pub reason: String,
+}
+
 #[derive(Debug, Deserialize)]
 pub struct AuditLogParams {
     pub limit: Option<i64>,
@@ -75,6 +100,7 @@ pub fn router() -> Router<AppState> {
     Router::new()
         .route("/api/dashboard/stats", get(dashboard_stats))
         .route("/api/audit-log", get(list_audit_log))
+        .route("/api/audit-log/verify", get(verify_audit_log))
         .route("/api/onboarding/status", get(onboarding_status))
 }
 
@@ -157,13 +183,20 @@ async fn list_audit_log(
         .unwrap_or(Some(0))
         .unwrap_or(0);
 
-    let rows = sqlx::query_as::<_, (Uuid, Uuid, String, String, String, Option<Uuid>, Option<Uuid>, Option<serde_json::Value>, DateTime<Utc>)>(
-        "SELECT id, actor_id, actor_name, action, resource, resource_id, project_id, detail, created_at FROM audit_log ORDER BY created_at DESC LIMIT $1 OFFSET $2",
+    let rows = sqlx::query_as::<_, (Uuid, Uuid, String, String, String, Option<Uuid>, Option<Uuid>, Option<serde_json::Value>, Option<Uuid>, DateTime<Utc>)>(
+        "SELECT id, actor_id, actor_name, action, resource, resource_id, project_id, detail, break_glass_id, created_at FROM audit_log ORDER BY created_at DESC LIMIT $1 OFFSET $2",
     )
     .bind(limit)
     .bind(offset)
     .fetch_all(&state.pool)
     .await?;
+    audit_read(
+        &state,
+        &auth,
+        ReadAudit::AuditLog,
+        None,
+        serde_json::json!({"limit": limit, "offset": offset}),
+    );
 
     let items = rows
         .into_iter()
@@ -177,6 +210,7 @@ async fn list_audit_log(
                 resource_id,
                 project_id,
                 detail,
+                break_glass_id,
                 created_at,
             )| {
                 AuditLogEntry {
@@ -188,6 +222,7 @@ async fn list_audit_log(
                     resource_id,
                     project_id,
                     detail,
+                    break_glass_id,
                     created_at,
                 }
             },
@@ -197,6 +232,36 @@ async fn list_audit_log(
     Ok(Json(ListResponse { items, total }))
 }
 
+async fn verify_audit_log(
+    State(state): State<AppState>,
+    auth: AuthUser,
+) -> Result<Json<AuditChainReport>, ApiError> {
+    require_admin(&state, &auth).await?;
+
+    let result = crate::audit::verify_chain(&state.pool).await?;
+    audit_read(
+        &state,
+        &auth,
+        ReadAudit::AuditLog,
+        None,
+        serde_json::json!({"verify": true}),
+    );
+    if let Some(ref b) = result.first_break {
+        tracing::warn!(id = %b.id, seq = b.seq, reason = b.reason.as_str(), "audit log hash chain broken");
+    }
+
+    Ok(Json(AuditChainReport {
+        intact: result.first_break.is_none(),
+        checked: result.checked,
+        first_break: result.first_break.map(|b| AuditChainBreak {
+            id: b.id,
+            seq: b.seq,
+            created_at: b.created_at,
+            reason: b.reason.as_str().to_owned(),
+        }),
+    }))
+}
+
 async fn onboarding_status(
     State(state): State<AppState>,
     auth: AuthUser,
@@ -0,0 +1,341 @@
+// Copyright (c) 2026 Steven Hooker. Exclusively licensed to and distributed by AgentSphere GmbH.
+// SPDX-License-Identifier: BUSL-1.1
+
+//! Deployment freeze windows. While a window is open, deploys and rollbacks
+//! to its environment are refused unless an admin forces them (see
+//! `deployments::check_freeze`).
+
+use axum::extract::{Path, State};
+use axum::http::StatusCode;
+use axum::response::IntoResponse;
+use axum::routing::{delete, get};
+use axum::{Json, Router};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use ts_rs::TS;
+
+use crate::audit::{AuditEntry, send_audit};
+use crate::auth::middleware::AuthUser;
+use crate::deployer::freeze::Schedule;
+use crate::error::ApiError;
+use crate::pipeline::cron::CronExpr;
+use crate::store::AppState;
+use crate::validation;
+
+use super::deployments::{require_deploy_promote, require_deploy_read};
+use super::helpers::{ListResponse, require_admin};
+
+const MAX_WINDOWS_PER_PROJECT: i64 = 50;
+
+/// Longest recurring opening: one week.
+const MAX_DURATION_MINUTES: i32 = 7 * 24 * 60;
+
+// ---------------------------------------------------------------------------
+// Types
+// ---------------------------------------------------------------------------
+
+/// Either `cron` + `duration_minutes` (recurring) or `starts_at` + `ends_at`.
+#[derive(Debug, Deserialize)]
+pub struct CreateFreezeWindowRequest {
+    pub environment: String,
+    pub name: String,
+    /// Five-field cron expression (UTC) for when each freeze starts.
+    pub cron: Option<String>,
+    pub duration_minutes: Option<i32>,
+    pub starts_at: Option<DateTime<Utc>>,
+    pub ends_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Serialize, TS)]
+#[ts(export, rename = "DeployFreezeWindow")]
+pub struct FreezeWindowResponse {
+    pub id: Uuid,
+    pub project_id: Uuid,
+    pub environment: String,
+    pub name: String,
+    pub cron: Option<String>,
+    pub duration_minutes: Option<i32>,
+    pub starts_at: Option<DateTime<Utc>>,
+    pub ends_at: Option<DateTime<Utc>>,
+    pub created_by: Option<Uuid>,
+    pub created_at: DateTime<Utc>,
+    /// When this window's current opening closes; `None` if it is not open.
+    pub active_until: Option<DateTime<Utc>>,
+}
+
+struct WindowRow {
+    id: Uuid,
+    project_id: Uuid,
+    environment: String,
+    name: String,
+    cron: Option<String>,
+    duration_minutes: Option<i32>,
+    starts_at: Option<DateTime<Utc>>,
+    ends_at: Option<DateTime<Utc>>,
+    created_by: Option<Uuid>,
+    created_at: DateTime<Utc>,
+}
+
+impl WindowRow {
+    fn into_response(self, now: DateTime<Utc>) -> FreezeWindowResponse {
+        let active_until = Schedule::from_columns(
+            self.cron.as_deref(),
+            self.duration_minutes,
+            self.starts_at,
+            self.ends_at,
+        )
+        .ok()
+        .and_then(|s| s.open_until(now));
+        FreezeWindowResponse {
+            id: self.id,
+            project_id: self.project_id,
+            environment: self.environment,
+            name: self.name,
+            cron: self.cron,
+            duration_minutes: self.duration_minutes,
+            starts_at: self.starts_at,
+            ends_at: self.ends_at,
+            created_by: self.created_by,
+            created_at: self.created_at,
+            active_until,
+        }
+    }
+}
+
+/// Check the request describes exactly one kind of window.
+fn validate_window(body: &CreateFreezeWindowRequest) -> Result<(), ApiError> {
+    validation::check_length("name", &body.name, 1, 100)?;
+    if !matches!(
+        body.environment.as_str(),
+        "preview" | "staging" | "production"
+    ) {
+        return Err(ApiError::BadRequest(
+            "environment must be preview, staging, or production".into(),
+        ));
+    }
+    match (
+        &body.cron,
+        body.duration_minutes,
+        body.starts_at,
+        body.ends_at,
+    ) {
+        (Some(cron), Some(minutes), None, None) => {
+            validation::check_length("cron", cron, 1, 100)?;
+            CronExpr::parse(cron)
+                .map_err(|e| ApiError::BadRequest(format!("invalid cron expression: {e}")))?;
+            if !(1..=MAX_DURATION_MINUTES).contains(&minutes) {
+                return Err(ApiError::BadRequest(format!(
+                    "duration_minutes must be 1-{MAX_DURATION_MINUTES}"
+                )));
+            }
+            Ok(())
+        }
+        (None, None, Some(starts_at), Some(ends_at)) => {
+            if ends_at <= starts_at {
+                return Err(ApiError::BadRequest(
+                    "ends_at must be after starts_at".into(),
+                ));
+            }
+            Ok(())
+        }
+        _ => Err(ApiError::BadRequest(
+            "give either cron and duration_minutes, or starts_at and ends_at".into(),
+        )),
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Router
+// ---------------------------------------------------------------------------
+
+pub fn router() -> Router<AppState> {
+    Router::new()
+        .route(
+            "/api/projects/{id}/freeze-windows",
+            get(list_windows).post(create_window),
+        )
+        .route(
+            "/api/projects/{id}/freeze-windows/{window_id}",
+            delete(delete_window),
+        )
+}
+
+// ---------------------------------------------------------------------------
+// Handlers
+// ---------------------------------------------------------------------------
+
+async fn list_windows(
+    State(state): State<AppState>,
+    auth: AuthUser,
+    Path(id): Path<Uuid>,
+) -> Result<Json<ListResponse<FreezeWindowResponse>>, ApiError> {
+    require_deploy_read(&state, &auth, id).await?;
+
+    let rows = sqlx::query_as!(
+        WindowRow,
+        "SELECT id, project_id, environment, name, cron, duration_minutes,
+                starts_at, ends_at, created_by, created_at
+         FROM deploy_freeze_windows
+         WHERE project_id = $1 ORDER BY environment, created_at",
+        id,
+    )
+    .fetch_all(&state.pool)
+    .await?;
+
+    let now = Utc::now();
+    let total = i64::try_from(rows.len()).unwrap_or(i64::MAX);
+    let items = rows.into_iter().map(|r| r.into_response(now)).collect();
+    Ok(Json(ListResponse { items, total }))
+}
+
+#[tracing::instrument(skip(state, body), fields(%id), err)]
+async fn create_window(
+    State(state): State<AppState>,
+    auth: AuthUser,
+    Path(id): Path<Uuid>,
+    Json(body): Json<CreateFreezeWindowRequest>,
+) -> Result<impl IntoResponse, ApiError> {
+    validate_window(&body)?;
+    require_deploy_promote(&state, &auth, id).await?;
+
+    let count = sqlx::query_scalar!(
+        r#"SELECT COUNT(*) as "count!" FROM deploy_freeze_windows WHERE project_id = $1"#,
+        id,
+    )
+    .fetch_one(&state.pool)
+    .await?;
+    if count >= MAX_WINDOWS_PER_PROJECT {
+        return Err(ApiError::BadRequest(format!(
+            "max {MAX_WINDOWS_PER_PROJECT} freeze windows per project"
+        )));
+    }
+
+    let row = sqlx::query_as!(
+        WindowRow,
+        "INSERT INTO deploy_freeze_windows
+             (project_id, environment, name, cron, duration_minutes, starts_at, ends_at, created_by)
+         VALUES ($1, $2, $3, $4, $5, $6, $7, $8)
+         RETURNING id, project_id, environment, name, cron, duration_minutes,
+                   starts_at, ends_at, created_by, created_at",
+        id,
+        body.environment,
+        body.name,
+        body.cron.as_deref().map(str::trim),
+        body.duration_minutes,
+        body.starts_at,
+        body.ends_at,
+        auth.user_id,
+    )
+    .fetch_one(&state.pool)
+    .await?;
+
+    send_audit(
+        &state.audit_tx,
+        AuditEntry {
+            actor_id: auth.user_id,
+            actor_name: auth.user_name.clone(),
+            action: "deploy.freeze.create".into(),
+            resource: "deploy_freeze_window".into(),
+            resource_id: Some(row.id),
+            project_id: Some(id),
+            detail: Some(serde_json::json!({
+                "environment": row.environment,
+                "name": row.name,
+                "cron": row.cron,
+                "duration_minutes": row.duration_minutes,
+                "starts_at": row.starts_at,
+                "ends_at": row.ends_at,
+            })),
+            ip_addr: auth.ip_addr.clone(),
+        },
+    );
+
+    Ok((StatusCode::CREATED, Json(row.into_response(Utc::now()))))
+}
+
+/// Lifting a freeze is as strong as forcing a deploy through it, so only
+/// admins may delete windows.
+#[tracing::instrument(skip(state), fields(%id, %window_id), err)]
+async fn delete_window(
+    State(state): State<AppState>,
+    auth: AuthUser,
+    Path((id, window_id)): Path<(Uuid, Uuid)>,
+) -> Result<StatusCode, ApiError> {
+    require_deploy_read(&state, &auth, id).await?;
+    require_admin(&state, &auth).await?;
+
+    let result = sqlx::query!(
+        "DELETE FROM deploy_freeze_windows WHERE id = $1 AND project_id = $2",
+        window_id,
+        id,
+    )
+    .execute(&state.pool)
+    .await?;
+    if result.rows_affected() == 0 {
+        return Err(ApiError::NotFound("freeze window".into()));
+    }
+
+    send_audit(
+        &state.audit_tx,
+        AuditEntry {
+            actor_id: auth.user_id,
+            actor_name: auth.user_name.clone(),
+            action: "deploy.freeze.delete".into(),
+            resource: "deploy_freeze_window".into(),
+            resource_id: Some(window_id),
+            project_id: Some(id),
+            detail: None,
+            ip_addr: auth.ip_addr.clone(),
+        },
+    );
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn request(cron: Option<&str>, minutes: Option<i32>) -> CreateFreezeWindowRequest {
+        CreateFreezeWindowRequest {
+            environment: "production".into(),
+            name: "friday".into(),
+            cron: cron.map(Into::into),
+            duration_minutes: minutes,
+            starts_at: None,
+            ends_at: None,
+        }
+    }
+
+    #[test]
+    fn recurring_window_needs_valid_cron_and_duration() {
+        assert!(validate_window(&request(Some("0 9 * * FRI"), Some(480))).is_ok());
+        assert!(validate_window(&request(Some("0 25 * * *"), Some(480))).is_err());
+        assert!(validate_window(&request(Some("0 9 * * FRI"), Some(0))).is_err());
+        assert!(validate_window(&request(Some("0 9 * * FRI"), None)).is_err());
+    }
+
+    #[test]
+    fn one_off_window_must_end_after_start() {
+        let now = Utc::now();
+        let mut body = request(None, None);
+        assert!(validate_window(&body).is_err());
+        body.starts_at = Some(now);
+        body.ends_at = Some(now + chrono::Duration::hours(1));
+        assert!(validate_window(&body).is_ok());
+        body.ends_at = Some(now);
+        assert!(validate_window(&body).is_err());
+        body.cron = Some("0 9 * * *".into());
+        body.ends_at = Some(now + chrono::Duration::hours(1));
+        assert!(validate_window(&body).is_err());
+    }
+
+    #[test]
+    fn environment_is_checked() {
+        let mut body = request(Some("0 9 * * FRI"), Some(60));
+        body.environment = "prod".into();
+        assert!(validate_window(&body).is_err());
+    }
+}
@@ -42,6 +42,8 @@ pub struct TargetResponse {
     pub manifest_path: Option<String>,
     pub hostname: Option<String>,
     pub is_active: bool,
+    /// New releases wait for a second user's approval before rolling out.
+    pub requires_approval: bool,
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
 }
@@ -63,6 +65,10 @@ pub struct ReleaseResponse {
     pub values_override: Option<serde_json::Value>,
     pub deployed_by: Option<Uuid>,
     pub pipeline_id: Option<Uuid>,
+    /// Held in `pending` until approved; see `approved_by`.
+    pub requires_approval: bool,
+    pub approved_by: Option<Uuid>,
+    pub approved_at: Option<DateTime<Utc>>,
     pub started_at: Option<DateTime<Utc>>,
     pub completed_at: Option<DateTime<Utc>>,
     pub created_at: DateTime<Utc>,
@@ -92,6 +98,12 @@ pub struct CreateTargetRequest {
     pub ops_repo_id: Option<Uuid>,
     pub manifest_path: Option<String>,
     pub hostname: Option<String>,
+    pub requires_approval: Option<bool>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct UpdateTargetRequest {
+    pub requires_approval: Option<bool>,
 }
 
 #[derive(Debug, Deserialize)]
@@ -130,18 +142,75 @@ pub struct ListResponse<T: Serialize> {
     pub total: i64,
 }
 
+/// `?force=true` lets an admin deploy through an active freeze window.
+#[derive(Debug, Deserialize)]
+pub struct ForceParams {
+    pub force: Option<bool>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct DeployDiffParams {
+    pub image_ref: Option<String>,
+}
+
+#[derive(Debug, Serialize, TS)]
+#[ts(export, rename = "DeployDiff")]
+pub struct DeployDiffResponse {
+    pub environment: String,
+    /// Image the diff was rendered with.
+    pub image_ref: String,
+    /// Image of the target's latest applied release, if any.
+    pub current_image: Option<String>,
+    /// Ops repo branch the manifests were read from, when not the environment's own.
+    pub manifest_branch: Option<String>,
+    pub resources: Vec<crate::deployer::diff::ResourceDiff>,
+}
+
+/// One line of the reconciler's log trail for a release.
+#[derive(Debug, Serialize, TS)]
+#[ts(export, rename = "ReleaseLog")]
+pub struct ReleaseLogResponse {
+    /// Reconciler attempt the line belongs to, starting at 1.
+    pub attempt: i32,
+    /// `info`, `warn` or `error`.
+    pub level: String,
+    pub message: String,
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Serialize, TS)]
+#[ts(export, rename = "DeploymentEvents")]
+pub struct DeploymentEventsResponse {
+    pub environment: String,
+    /// The target's latest release; `None` if nothing was ever deployed.
+    pub release_id: Option<Uuid>,
+    pub phase: Option<String>,
+    pub attempts: i32,
+    pub conditions: Vec<crate::deployer::events::RolloutCondition>,
+    /// Cluster events for the release's workloads, newest first.
+    pub events: Vec<crate::deployer::events::WorkloadEvent>,
+    /// Why cluster state could not be read. Logs are returned regardless.
+    pub cluster_error: Option<String>,
+    /// Oldest first.
+    pub logs: Vec<ReleaseLogResponse>,
+}
+
 // Ops repo types (unchanged)
 #[derive(Debug, Deserialize)]
 pub struct CreateOpsRepoRequest {
     pub name: String,
     pub branch: Option<String>,
     pub path: Option<String>,
+    pub remote_url: Option<String>,
+    pub credential_secret_id: Option<Uuid>,
 }
 
 #[derive(Debug, Deserialize)]
 pub struct UpdateOpsRepoRequest {
     pub branch: Option<String>,
     pub path: Option<String>,
+    pub remote_url: Option<String>,
+    pub credential_secret_id: Option<Uuid>,
 }
 
 #[derive(Debug, Serialize, TS)]
@@ -152,6 +221,8 @@ pub struct OpsRepoResponse {
     pub repo_path: String,
     pub branch: String,
     pub path: String,
+    pub remote_url: Option<String>,
+    pub credential_secret_id: Option<Uuid>,
     pub created_at: DateTime<Utc>,
 }
 
@@ -166,7 +237,10 @@ pub fn router() -> Router<AppState> {
             "/api/projects/{id}/targets",
             get(list_targets).post(create_target),
         )
-        .route("/api/projects/{id}/targets/{target_id}", get(get_target))
+        .route(
+            "/api/projects/{id}/targets/{target_id}",
+            get(get_target).patch(update_target),
+        )
         // Releases
         .route(
             "/api/projects/{id}/deploy-releases",
@@ -181,6 +255,10 @@ pub fn router() -> Router<AppState> {
             "/api/projects/{id}/deploy-releases/{release_id}/traffic",
             axum::routing::patch(adjust_traffic),
         )
+        .route(
+            "/api/projects/{id}/deploy-releases/{release_id}/approve",
+            axum::routing::post(approve_release),
+        )
         .route(
             "/api/projects/{id}/deploy-releases/{release_id}/promote",
             axum::routing::post(promote_release),
@@ -208,6 +286,23 @@ pub fn router() -> Router<AppState> {
             axum::routing::post(promote_staging),
         )
         .route("/api/projects/{id}/staging-status", get(staging_status))
+        .route(
+            "/api/projects/{id}/deployments/{env}/diff",
+            get(deploy_diff),
+        )
+        .route(
+            "/api/projects/{id}/deployments/{env}/events",
+            get(deployment_events),
+        )
+        // Preview lifecycle
+        .route(
+            "/api/projects/{id}/deployments/preview/{branch_slug}/extend",
+            axum::routing::post(extend_preview),
+        )
+        .route(
+            "/api/projects/{id}/deployments/preview/{branch_slug}",
+            get(preview_deployment_events).delete(delete_preview),
+        )
         // Deploy preview iframes (unchanged)
         .route(
             "/api/projects/{id}/deploy-preview/iframes",
@@ -230,7 +325,7 @@ pub fn router() -> Router<AppState> {
 // Permission helpers
 // ---------------------------------------------------------------------------
 
-async fn require_deploy_read(
+pub(super) async fn require_deploy_read(
     state: &AppState,
     auth: &AuthUser,
     project_id: Uuid,
@@ -253,7 +348,7 @@ async fn require_deploy_read(
     Ok(())
 }
 
-async fn require_deploy_promote(
+pub(super) async fn require_deploy_promote(
     state: &AppState,
     auth: &AuthUser,
     project_id: Uuid,
@@ -276,6 +371,54 @@ async fn require_deploy_promote(
     Ok(())
 }
 
+/// Refuse `action` while a freeze window is open on `environment`, unless an
+/// admin forces it. Forced actions are audited against the window.
+async fn check_freeze(
+    state: &AppState,
+    auth: &AuthUser,
+    project_id: Uuid,
+    environment: &str,
+    action: &str,
+    force: bool,
+) -> Result<(), ApiError> {
+    let Some(freeze) =
+        crate::deployer::freeze::active_freeze(&state.pool, project_id, environment, Utc::now())
+            .await?
+    else {
+        return Ok(());
+    };
+    if !force {
+        return Err(ApiError::Locked(format!(
+            "{environment} is frozen ({}) until {}; an admin can override with force=true",
+            freeze.name,
+            freeze
+                .until
+                .to_rfc3339_opts(chrono::SecondsFormat::Secs, true)
+        )));
+    }
+    require_admin(state, auth).await?;
+
+    send_audit(
+        &state.audit_tx,
+        AuditEntry {
+            actor_id: auth.user_id,
+            actor_name: auth.user_name.clone(),
+            action: "deploy.freeze.override".into(),
+            resource: "deploy_freeze_window".into(),
+            resource_id: Some(freeze.window_id),
+            project_id: Some(project_id),
+            detail: Some(serde_json::json!({
+                "environment": environment,
+                "action": action,
+                "window": freeze.name,
+                "frozen_until": freeze.until,
+            })),
+            ip_addr: auth.ip_addr.clone(),
+        },
+    );
+    Ok(())
+}
+
 // ---------------------------------------------------------------------------
 // Target handlers
 // ---------------------------------------------------------------------------
@@ -300,7 +443,7 @@ async fn list_targets(
 
     let rows = sqlx::query(
         "SELECT id, project_id, name, environment, branch, branch_slug, ttl_hours, expires_at,
-                default_strategy, ops_repo_id, manifest_path, hostname, is_active, created_at, updated_at
+                default_strategy, ops_repo_id, manifest_path, hostname, is_active, requires_approval, created_at, updated_at
          FROM deploy_targets WHERE project_id = $1 AND is_active = true
          ORDER BY environment, name LIMIT $2 OFFSET $3",
     )
@@ -323,7 +466,7 @@ async fn get_target(
 
     let row = sqlx::query(
         "SELECT id, project_id, name, environment, branch, branch_slug, ttl_hours, expires_at,
-                default_strategy, ops_repo_id, manifest_path, hostname, is_active, created_at, updated_at
+                default_strategy, ops_repo_id, manifest_path, hostname, is_active, requires_approval, created_at, updated_at
          FROM deploy_targets WHERE id = $1 AND project_id = $2 AND is_active = true",
     )
     .bind(target_id)
@@ -363,10 +506,10 @@ async fn create_target(
     }
 
     let row = sqlx::query(
-        "INSERT INTO deploy_targets (project_id, name, environment, default_strategy, ops_repo_id, manifest_path, hostname, created_by)
-         VALUES ($1, $2, $3, $4, $5, $6, $7, $8)
+        "INSERT INTO deploy_targets (project_id, name, environment, default_strategy, ops_repo_id, manifest_path, hostname, created_by, requires_approval)
+         VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9)
          RETURNING id, project_id, name, environment, branch, branch_slug, ttl_hours, expires_at,
-                   default_strategy, ops_repo_id, manifest_path, hostname, is_active, created_at, updated_at",
+                   default_strategy, ops_repo_id, manifest_path, hostname, is_active, requires_approval, created_at, updated_at",
     )
     .bind(id)
     .bind(&body.name)
@@ -376,6 +519,7 @@ async fn create_target(
     .bind(&body.manifest_path)
     .bind(&body.hostname)
     .bind(auth.user_id)
+    .bind(body.requires_approval.unwrap_or(false))
     .fetch_one(&state.pool)
     .await
     .map_err(|e| match e {
@@ -403,6 +547,141 @@ async fn create_target(
     Ok((StatusCode::CREATED, Json(row_to_target(&row))))
 }
 
+async fn update_target(
+    State(state): State<AppState>,
+    auth: AuthUser,
+    Path((id, target_id)): Path<(Uuid, Uuid)>,
+    Json(body): Json<UpdateTargetRequest>,
+) -> Result<Json<TargetResponse>, ApiError> {
+    require_deploy_promote(&state, &auth, id).await?;
+
+    let target = sqlx::query_as!(
+        TargetResponse,
+        "UPDATE deploy_targets
+         SET requires_approval = COALESCE($3, requires_approval), updated_at = now()
+         WHERE id = $1 AND project_id = $2 AND is_active = true
+         RETURNING id, project_id, name, environment, branch, branch_slug, ttl_hours, expires_at,
+                   default_strategy, ops_repo_id, manifest_path, hostname, is_active, requires_approval, created_at, updated_at",
+        target_id,
+        id,
+        body.requires_approval,
+    )
+    .fetch_optional(&state.pool)
+    .await?
+    .ok_or_else(|| ApiError::NotFound("target".into()))?;
+
+    send_audit(
+        &state.audit_tx,
+        AuditEntry {
+            actor_id: auth.user_id,
+            actor_name: auth.user_name.clone(),
+            action: "deploy.target.update".into(),
+            resource: "deploy_target".into(),
+            resource_id: Some(target_id),
+            project_id: Some(id),
+            detail: Some(serde_json::json!({"requires_approval": body.requires_approval})),
+            ip_addr: auth.ip_addr.clone(),
+        },
+    );
+
+    Ok(Json(target))
+}
+
+/// TTL used when extending a preview target that has no `ttl_hours` of its own.
+const DEFAULT_PREVIEW_TTL_HOURS: i32 = 24;
+
+/// Push an active preview's expiry out to `now() + ttl_hours`.
+async fn extend_preview(
+    State(state): State<AppState>,
+    auth: AuthUser,
+    Path((id, branch_slug)): Path<(Uuid, String)>,
+) -> Result<Json<TargetResponse>, ApiError> {
+    require_deploy_promote(&state, &auth, id).await?;
+
+    let target = sqlx::query_as!(
+        TargetResponse,
+        "UPDATE deploy_targets
+         SET expires_at = now() + make_interval(hours => COALESCE(ttl_hours, $3)),
+             updated_at = now()
+         WHERE project_id = $1 AND environment = 'preview' AND branch_slug = $2 AND is_active = true
+         RETURNING id, project_id, name, environment, branch, branch_slug, ttl_hours, expires_at,
+                   default_strategy, ops_repo_id, manifest_path, hostname, is_active, requires_approval, created_at, updated_at",
+        id,
+        branch_slug,
+        DEFAULT_PREVIEW_TTL_HOURS,
+    )
+    .fetch_optional(&state.pool)
+    .await?
+    .ok_or_else(|| ApiError::NotFound("preview".into()))?;
+
+    send_audit(
+        &state.audit_tx,
+        AuditEntry {
+            actor_id: auth.user_id,
+            actor_name: auth.user_name.clone(),
+            action: "deploy.preview.extend".into(),
+            resource: "deploy_target".into(),
+            resource_id: Some(target.id),
+            project_id: Some(id),
+            detail: Some(serde_json::json!({
+                "branch_slug": branch_slug,
+                "expires_at": target.expires_at,
+            })),
+            ip_addr: auth.ip_addr.clone(),
+        },
+    );
+
+    Ok(Json(target))
+}
+
+/// Tear down an active preview now instead of waiting for it to expire.
+async fn delete_preview(
+    State(state): State<AppState>,
+    auth: AuthUser,
+    Path((id, branch_slug)): Path<(Uuid, String)>,
+) -> Result<StatusCode, ApiError> {
+    require_deploy_promote(&state, &auth, id).await?;
+
+    let row = sqlx::query!(
+        "SELECT dt.id, p.namespace_slug
+         FROM deploy_targets dt
+         JOIN projects p ON p.id = dt.project_id
+         WHERE dt.project_id = $1 AND dt.environment = 'preview' AND dt.branch_slug = $2
+           AND dt.is_active = true",
+        id,
+        branch_slug,
+    )
+    .fetch_optional(&state.pool)
+    .await?
+    .ok_or_else(|| ApiError::NotFound("preview".into()))?;
+
+    let target_id = row.id;
+    let namespace_slug = row.namespace_slug;
+    crate::deployer::preview::teardown_preview(
+        &state,
+        target_id,
+        &namespace_slug,
+        Some(&branch_slug),
+    )
+    .await;
+
+    send_audit(
+        &state.audit_tx,
+        AuditEntry {
+            actor_id: auth.user_id,
+            actor_name: auth.user_name.clone(),
+            action: "deploy.preview.delete".into(),
+            resource: "deploy_target".into(),
+            resource_id: Some(target_id),
+            project_id: Some(id),
+            detail: Some(serde_json::json!({"branch_slug": branch_slug})),
+            ip_addr: auth.ip_addr.clone(),
+        },
+    );
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
 // ---------------------------------------------------------------------------
 // Release handlers
 // ---------------------------------------------------------------------------
@@ -427,7 +706,8 @@ async fn list_releases(
     let rows = sqlx::query(
         "SELECT id, target_id, project_id, image_ref, commit_sha, strategy, phase,
                 traffic_weight, health, current_step, rollout_config, values_override,
-                deployed_by, pipeline_id, started_at, completed_at, created_at, updated_at
+                deployed_by, pipeline_id, requires_approval, approved_by, approved_at,
+                started_at, completed_at, created_at, updated_at
          FROM deploy_releases WHERE project_id = $1
          ORDER BY created_at DESC LIMIT $2 OFFSET $3",
     )
@@ -451,7 +731,8 @@ async fn get_release(
     let row = sqlx::query(
         "SELECT id, target_id, project_id, image_ref, commit_sha, strategy, phase,
                 traffic_weight, health, current_step, rollout_config, values_override,
-                deployed_by, pipeline_id, started_at, completed_at, created_at, updated_at
+                deployed_by, pipeline_id, requires_approval, approved_by, approved_at,
+                started_at, completed_at, created_at, updated_at
          FROM deploy_releases WHERE id = $1 AND project_id = $2",
     )
     .bind(release_id)
@@ -467,6 +748,7 @@ async fn create_release(
     State(state): State<AppState>,
     auth: AuthUser,
     Path(id): Path<Uuid>,
+    Query(force): Query<ForceParams>,
     Json(body): Json<CreateReleaseRequest>,
 ) -> Result<(StatusCode, Json<ReleaseResponse>), ApiError> {
     // Rate limit: 30 releases per hour per user
@@ -481,10 +763,19 @@ async fn create_release(
 
     require_deploy_promote(&state, &auth, id).await?;
     validation::check_length("image_ref", &body.image_ref, 1, 2048)?;
+    check_freeze(
+        &state,
+        &auth,
+        id,
+        "production",
+        "deploy",
+        force.force.unwrap_or(false),
+    )
+    .await?;
 
     // Find or require a target
     let target = sqlx::query(
-        "SELECT id, default_strategy FROM deploy_targets
+        "SELECT id, default_strategy, requires_approval FROM deploy_targets
          WHERE project_id = $1 AND environment = 'production' AND is_active = true
          LIMIT 1",
     )
@@ -495,6 +786,7 @@ async fn create_release(
 
     let target_id: Uuid = target.get("id");
     let default_strategy: String = target.get("default_strategy");
+    let requires_approval: bool = target.get("requires_approval");
     let strategy = body.strategy.as_deref().unwrap_or(&default_strategy);
 
     let rollout_config = body
@@ -503,11 +795,12 @@ async fn create_release(
         .unwrap_or_else(|| serde_json::json!({}));
 
     let row = sqlx::query(
-        "INSERT INTO deploy_releases (target_id, project_id, image_ref, commit_sha, strategy, rollout_config, values_override, deployed_by, pipeline_id)
-         VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9)
+        "INSERT INTO deploy_releases (target_id, project_id, image_ref, commit_sha, strategy, rollout_config, values_override, deployed_by, pipeline_id, requires_approval)
+         VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10)
          RETURNING id, target_id, project_id, image_ref, commit_sha, strategy, phase,
                    traffic_weight, health, current_step, rollout_config, values_override,
-                   deployed_by, pipeline_id, started_at, completed_at, created_at, updated_at",
+                   deployed_by, pipeline_id, requires_approval, approved_by, approved_at,
+                   started_at, completed_at, created_at, updated_at",
     )
     .bind(target_id)
     .bind(id)
@@ -518,6 +811,7 @@ async fn create_release(
     .bind(&body.values_override)
     .bind(auth.user_id)
     .bind(body.pipeline_id)
+    .bind(requires_approval)
     .fetch_one(&state.pool)
     .await?;
 
@@ -545,7 +839,11 @@ async fn create_release(
             resource: "deploy_release".into(),
             resource_id: Some(release_id),
             project_id: Some(id),
-            detail: Some(serde_json::json!({"image_ref": body.image_ref, "strategy": strategy})),
+            detail: Some(serde_json::json!({
+                "image_ref": body.image_ref,
+                "strategy": strategy,
+                "requires_approval": requires_approval,
+            })),
             ip_addr: auth.ip_addr.clone(),
         },
     );
@@ -590,7 +888,8 @@ async fn adjust_traffic(
          WHERE id = $1 AND project_id = $2 AND phase NOT IN ('completed','rolled_back','cancelled','failed')
          RETURNING id, target_id, project_id, image_ref, commit_sha, strategy, phase,
                    traffic_weight, health, current_step, rollout_config, values_override,
-                   deployed_by, pipeline_id, started_at, completed_at, created_at, updated_at",
+                   deployed_by, pipeline_id, requires_approval, approved_by, approved_at,
+                   started_at, completed_at, created_at, updated_at",
     )
     .bind(release_id)
     .bind(id)
@@ -629,6 +928,91 @@ async fn adjust_traffic(
     Ok(Json(row_to_release(&row)))
 }
 
+/// Release a rollout held by its target's approval policy. The approver needs
+/// `deploy:promote` and must not be the user who created the release.
+async fn approve_release(
+    State(state): State<AppState>,
+    auth: AuthUser,
+    Path((id, release_id)): Path<(Uuid, Uuid)>,
+    Query(force): Query<ForceParams>,
+) -> Result<Json<ReleaseResponse>, ApiError> {
+    require_deploy_promote(&state, &auth, id).await?;
+
+    // Releases from a pipeline count as deployed by whoever triggered it
+    let pending = sqlx::query!(
+        "SELECT COALESCE(r.deployed_by, p.triggered_by) AS deployed_by, dt.environment
+         FROM deploy_releases r
+         JOIN deploy_targets dt ON dt.id = r.target_id
+         LEFT JOIN pipelines p ON p.id = r.pipeline_id
+         WHERE r.id = $1 AND r.project_id = $2 AND r.phase = 'pending'
+           AND r.requires_approval AND r.approved_at IS NULL",
+        release_id,
+        id,
+    )
+    .fetch_optional(&state.pool)
+    .await?
+    .ok_or_else(|| ApiError::BadRequest("release not found or not awaiting approval".into()))?;
+    if pending.deployed_by == Some(auth.user_id) {
+        return Err(ApiError::BadRequest(
+            "a release must be approved by someone other than its deployer".into(),
+        ));
+    }
+    check_freeze(
+        &state,
+        &auth,
+        id,
+        &pending.environment,
+        "approve",
+        force.force.unwrap_or(false),
+    )
+    .await?;
+
+    let release = sqlx::query_as!(
+        ReleaseResponse,
+        "UPDATE deploy_releases SET approved_by = $3, approved_at = now()
+         WHERE id = $1 AND project_id = $2 AND phase = 'pending'
+           AND requires_approval AND approved_at IS NULL
+         RETURNING id, target_id, project_id, image_ref, commit_sha, strategy, phase,
+                   traffic_weight, health, current_step, rollout_config, values_override,
+                   deployed_by, pipeline_id, requires_approval, approved_by, approved_at,
+                   started_at, completed_at, created_at, updated_at",
+        release_id,
+        id,
+        auth.user_id,
+    )
+    .fetch_optional(&state.pool)
+    .await?
+    .ok_or_else(|| ApiError::BadRequest("release not found or not awaiting approval".into()))?;
+
+    record_release_history(
+        &state.pool,
+        release_id,
+        release.target_id,
+        "approved",
+        "pending",
+        None,
+        &release.image_ref,
+        Some(auth.user_id),
+    )
+    .await;
+    send_audit(
+        &state.audit_tx,
+        AuditEntry {
+            actor_id: auth.user_id,
+            actor_name: auth.user_name.clone(),
+            action: "deploy.release.approve".into(),
+            resource: "deploy_release".into(),
+            resource_id: Some(release_id),
+            project_id: Some(id),
+            detail: None,
+            ip_addr: auth.ip_addr.clone(),
+        },
+    );
+
+    state.deploy_notify.notify_one();
+    Ok(Json(release))
+}
+
 async fn promote_release(
     State(state): State<AppState>,
     auth: AuthUser,
@@ -641,7 +1025,8 @@ async fn promote_release(
          WHERE id = $1 AND project_id = $2 AND phase IN ('progressing','holding','paused')
          RETURNING id, target_id, project_id, image_ref, commit_sha, strategy, phase,
                    traffic_weight, health, current_step, rollout_config, values_override,
-                   deployed_by, pipeline_id, started_at, completed_at, created_at, updated_at",
+                   deployed_by, pipeline_id, requires_approval, approved_by, approved_at,
+                   started_at, completed_at, created_at, updated_at",
     )
     .bind(release_id)
     .bind(id)
@@ -683,15 +1068,39 @@ async fn rollback_release(
     State(state): State<AppState>,
     auth: AuthUser,
     Path((id, release_id)): Path<(Uuid, Uuid)>,
+    Query(force): Query<ForceParams>,
 ) -> Result<Json<ReleaseResponse>, ApiError> {
     require_deploy_promote(&state, &auth, id).await?;
 
+    let environment = sqlx::query_scalar!(
+        "SELECT dt.environment FROM deploy_releases r
+         JOIN deploy_targets dt ON dt.id = r.target_id
+         WHERE r.id = $1 AND r.project_id = $2",
+        release_id,
+        id,
+    )
+    .fetch_optional(&state.pool)
+    .await?
+    .ok_or_else(|| {
+        ApiError::BadRequest("release not found or not in a rollback-able phase".into())
+    })?;
+    check_freeze(
+        &state,
+        &auth,
+        id,
+        &environment,
+        "rollback",
+        force.force.unwrap_or(false),
+    )
+    .await?;
+
     let row = sqlx::query(
         "UPDATE deploy_releases SET phase = 'rolling_back'
          WHERE id = $1 AND project_id = $2 AND phase IN ('progressing','holding','paused')
          RETURNING id, target_id, project_id, image_ref, commit_sha, strategy, phase,
                    traffic_weight, health, current_step, rollout_config, values_override,
-                   deployed_by, pipeline_id, started_at, completed_at, created_at, updated_at",
+                   deployed_by, pipeline_id, requires_approval, approved_by, approved_at,
+                   started_at, completed_at, created_at, updated_at",
     )
     .bind(release_id)
     .bind(id)
@@ -743,7 +1152,8 @@ async fn pause_release(
          WHERE id = $1 AND project_id = $2 AND phase = 'progressing'
          RETURNING id, target_id, project_id, image_ref, commit_sha, strategy, phase,
                    traffic_weight, health, current_step, rollout_config, values_override,
-                   deployed_by, pipeline_id, started_at, completed_at, created_at, updated_at",
+                   deployed_by, pipeline_id, requires_approval, approved_by, approved_at,
+                   started_at, completed_at, created_at, updated_at",
     )
     .bind(release_id)
     .bind(id)
@@ -792,7 +1202,8 @@ async fn resume_release(
          WHERE id = $1 AND project_id = $2 AND phase = 'paused'
          RETURNING id, target_id, project_id, image_ref, commit_sha, strategy, phase,
                    traffic_weight, health, current_step, rollout_config, values_override,
-                   deployed_by, pipeline_id, started_at, completed_at, created_at, updated_at",
+                   deployed_by, pipeline_id, requires_approval, approved_by, approved_at,
+                   started_at, completed_at, created_at, updated_at",
     )
     .bind(release_id)
     .bind(id)
@@ -935,8 +1346,18 @@ async fn promote_staging(
     State(state): State<AppState>,
     auth: AuthUser,
     Path(id): Path<Uuid>,
+    Query(force): Query<ForceParams>,
 ) -> Result<Json<serde_json::Value>, ApiError> {
     require_deploy_promote(&state, &auth, id).await?;
+    check_freeze(
+        &state,
+        &auth,
+        id,
+        "production",
+        "promote_staging",
+        force.force.unwrap_or(false),
+    )
+    .await?;
 
     let ops_repo = fetch_ops_repo_for_project(&state, id).await?;
     let ops_path = std::path::PathBuf::from(&ops_repo.repo_path);
@@ -962,6 +1383,7 @@ async fn promote_staging(
             environment: "production".into(),
             commit_sha: new_sha.clone(),
             image_ref: image_ref.clone(),
+            triggered_by: Some(auth.user_id),
         },
     )
     .await;
@@ -990,6 +1412,178 @@ async fn promote_staging(
     })))
 }
 
+/// Preview what deploying to `env` would change in the cluster.
+///
+/// The image defaults to what the next deploy would ship: for production the
+/// staging image awaiting promotion, otherwise the target's current image.
+/// Production manifests come from the `staging` branch when it exists, since
+/// promotion merges it. Read-only: the ops repo is not fetched, and kustomize
+/// overlays are not built.
+async fn deploy_diff(
+    State(state): State<AppState>,
+    auth: AuthUser,
+    Path((id, env)): Path<(Uuid, String)>,
+    Query(params): Query<DeployDiffParams>,
+) -> Result<Json<DeployDiffResponse>, ApiError> {
+    require_deploy_read(&state, &auth, id).await?;
+    if !matches!(env.as_str(), "preview" | "staging" | "production") {
+        return Err(ApiError::BadRequest(
+            "environment must be preview, staging, or production".into(),
+        ));
+    }
+    if let Some(ref image) = params.image_ref {
+        validation::check_container_image(image)?;
+    }
+
+    let target = sqlx::query!(
+        r#"SELECT dt.id,
+                o.repo_path AS "repo_path?",
+                (SELECT image_ref FROM deploy_releases
+                 WHERE target_id = dt.id AND phase <> 'pending'
+                 ORDER BY created_at DESC LIMIT 1) AS current_image
+         FROM deploy_targets dt
+         LEFT JOIN ops_repos o ON o.id = dt.ops_repo_id
+         WHERE dt.project_id = $1 AND dt.environment = $2 AND dt.is_active = true
+         ORDER BY dt.created_at DESC LIMIT 1"#,
+        id,
+        env,
+    )
+    .fetch_optional(&state.pool)
+    .await?
+    .ok_or_else(|| ApiError::NotFound("deploy target".into()))?;
+    let target_id = target.id;
+    let current_image = target.current_image;
+    let ops_path = target.repo_path.map(std::path::PathBuf::from);
+
+    // Promotion merges staging into the production branch.
+    let mut manifest_branch = None;
+    let mut staging_image = None;
+    if env == "production"
+        && let Some(ref ops_path) = ops_path
+        && crate::deployer::ops_repo::get_branch_sha(ops_path, "staging")
+            .await
+            .is_ok()
+    {
+        manifest_branch = Some("staging".to_owned());
+        staging_image = crate::deployer::ops_repo::read_values(ops_path, "staging", "staging")
+            .await
+            .ok()
+            .and_then(|v| v["image_ref"].as_str().map(String::from));
+    }
+
+    let image_ref = params
+        .image_ref
+        .or(staging_image)
+        .or_else(|| current_image.clone())
+        .ok_or_else(|| ApiError::BadRequest("no image to diff; pass image_ref".into()))?;
+
+    let resources = crate::deployer::reconciler::diff_target(
+        &state,
+        target_id,
+        &image_ref,
+        manifest_branch.as_deref(),
+    )
+    .await?;
+
+    Ok(Json(DeployDiffResponse {
+        environment: env,
+        image_ref,
+        current_image,
+        manifest_branch,
+        resources,
+    }))
+}
+
+/// Why the latest release in `env` is where it is: rollout conditions and
+/// Kubernetes events for its workloads, plus the reconciler's log trail.
+async fn deployment_events(
+    State(state): State<AppState>,
+    auth: AuthUser,
+    Path((id, env)): Path<(Uuid, String)>,
+) -> Result<Json<DeploymentEventsResponse>, ApiError> {
+    require_deploy_read(&state, &auth, id).await?;
+    if !matches!(env.as_str(), "preview" | "staging" | "production") {
+        return Err(ApiError::BadRequest(
+            "environment must be preview, staging, or production".into(),
+        ));
+    }
+
+    let target_id = sqlx::query_scalar!(
+        "SELECT id FROM deploy_targets
+         WHERE project_id = $1 AND environment = $2 AND is_active = true
+         ORDER BY created_at DESC LIMIT 1",
+        id,
+        env,
+    )
+    .fetch_optional(&state.pool)
+    .await?
+    .ok_or_else(|| ApiError::NotFound("deploy target".into()))?;
+
+    let mut response = DeploymentEventsResponse {
+        environment: env,
+        release_id: None,
+        phase: None,
+        attempts: 0,
+        conditions: Vec::new(),
+        events: Vec::new(),
+        cluster_error: None,
+        logs: Vec::new(),
+    };
+
+    let Some(release) = sqlx::query!(
+        "SELECT id, phase, attempts, tracked_resources FROM deploy_releases
+         WHERE target_id = $1 ORDER BY created_at DESC LIMIT 1",
+        target_id
+    )
+    .fetch_optional(&state.pool)
+    .await?
+    else {
+        return Ok(Json(response));
+    };
+    let release_id = release.id;
+    response.release_id = Some(release_id);
+    response.phase = Some(release.phase);
+    response.attempts = release.attempts;
+
+    response.logs = sqlx::query_as!(
+        ReleaseLogResponse,
+        "SELECT attempt, level, message, created_at FROM release_logs
+         WHERE release_id = $1 ORDER BY created_at, id LIMIT 500",
+        release_id
+    )
+    .fetch_all(&state.pool)
+    .await?;
+
+    let tracked: Vec<crate::deployer::applier::TrackedResource> =
+        serde_json::from_value(release.tracked_resources).unwrap_or_default();
+    match crate::deployer::events::workload_status(&state.kube, &tracked).await {
+        Ok(status) => {
+            response.conditions = status.conditions;
+            response.events = status.events;
+        }
+        Err(e) => {
+            tracing::warn!(%release_id, error = %e, "failed to read workload status");
+            response.cluster_error = Some(e.to_string());
+        }
+    }
+
+    Ok(Json(response))
+}
+
+/// `GET …/deployments/preview/events`. The preview lifecycle routes own
+/// `deployments/preview/{branch_slug}`, so `deployment_events` can't be reached
+/// for `preview` through its own route.
+async fn preview_deployment_events(
+    State(state): State<AppState>,
+    auth: AuthUser,
+    Path((id, branch_slug)): Path<(Uuid, String)>,
+) -> Result<Json<DeploymentEventsResponse>, ApiError> {
+    if branch_slug != "events" {
+        return Err(ApiError::NotFound("route".into()));
+    }
+    deployment_events(State(state), auth, Path((id, "preview".into()))).await
+}
+
 /// Fetch the ops repo associated with a project, returning 404 if none exists.
 async fn fetch_ops_repo_for_project(
     state: &AppState,
@@ -1128,6 +1722,9 @@ async fn create_ops_repo(
 ) -> Result<impl IntoResponse, ApiError> {
     require_admin(&state, &auth).await?;
     validate_ops_repo_create(&body)?;
+    if let Some(secret_id) = body.credential_secret_id {
+        check_ops_repo_credential(&state, secret_id).await?;
+    }
 
     let branch = body.branch.as_deref().unwrap_or("main");
     let path = body.path.as_deref().unwrap_or("/");
@@ -1141,14 +1738,16 @@ async fn create_ops_repo(
 
     let r = sqlx::query!(
         r#"
-        INSERT INTO ops_repos (name, repo_path, branch, path)
-        VALUES ($1, $2, $3, $4)
-        RETURNING id, name, repo_path, branch, path, created_at
+        INSERT INTO ops_repos (name, repo_path, branch, path, remote_url, credential_secret_id)
+        VALUES ($1, $2, $3, $4, $5, $6)
+        RETURNING id, name, repo_path, branch, path, remote_url, credential_secret_id, created_at
         "#,
         body.name,
         repo_path_str,
         branch,
         path,
+        body.remote_url,
+        body.credential_secret_id,
     )
     .fetch_one(&state.pool)
     .await?;
@@ -1175,6 +1774,8 @@ async fn create_ops_repo(
             repo_path: r.repo_path,
             branch: r.branch,
             path: r.path,
+            remote_url: r.remote_url,
+            credential_secret_id: r.credential_secret_id,
             created_at: r.created_at,
         }),
     ))
@@ -1188,6 +1789,9 @@ fn validate_ops_repo_create(body: &CreateOpsRepoRequest) -> Result<(), ApiError>
     if let Some(ref path) = body.path {
         validation::check_length("path", path, 1, 500)?;
     }
+    if let Some(ref url) = body.remote_url {
+        check_ops_repo_remote_url(url)?;
+    }
     Ok(())
 }
 
@@ -1199,7 +1803,7 @@ async fn list_ops_repos(
 
     let rows = sqlx::query!(
         r#"
-        SELECT id, name, repo_path, branch, path, created_at
+        SELECT id, name, repo_path, branch, path, remote_url, credential_secret_id, created_at
         FROM ops_repos ORDER BY name
         "#,
     )
@@ -1214,6 +1818,8 @@ async fn list_ops_repos(
             repo_path: r.repo_path,
             branch: r.branch,
             path: r.path,
+            remote_url: r.remote_url,
+            credential_secret_id: r.credential_secret_id,
             created_at: r.created_at,
         })
         .collect();
@@ -1230,7 +1836,7 @@ async fn get_ops_repo(
 
     let r = sqlx::query!(
         r#"
-        SELECT id, name, repo_path, branch, path, created_at
+        SELECT id, name, repo_path, branch, path, remote_url, credential_secret_id, created_at
         FROM ops_repos WHERE id = $1
         "#,
         repo_id,
@@ -1245,6 +1851,8 @@ async fn get_ops_repo(
         repo_path: r.repo_path,
         branch: r.branch,
         path: r.path,
+        remote_url: r.remote_url,
+        credential_secret_id: r.credential_secret_id,
         created_at: r.created_at,
     }))
 }
@@ -1258,18 +1866,25 @@ async fn update_ops_repo(
 ) -> Result<Json<OpsRepoResponse>, ApiError> {
     require_admin(&state, &auth).await?;
     validate_ops_repo_update(&body)?;
+    if let Some(secret_id) = body.credential_secret_id {
+        check_ops_repo_credential(&state, secret_id).await?;
+    }
 
     let r = sqlx::query!(
         r#"
         UPDATE ops_repos SET
             branch = COALESCE($2, branch),
-            path = COALESCE($3, path)
+            path = COALESCE($3, path),
+            remote_url = COALESCE($4, remote_url),
+            credential_secret_id = COALESCE($5, credential_secret_id)
         WHERE id = $1
-        RETURNING id, name, repo_path, branch, path, created_at
+        RETURNING id, name, repo_path, branch, path, remote_url, credential_secret_id, created_at
         "#,
         repo_id,
         body.branch,
         body.path,
+        body.remote_url,
+        body.credential_secret_id,
     )
     .fetch_optional(&state.pool)
     .await?
@@ -1295,6 +1910,8 @@ async fn update_ops_repo(
         repo_path: r.repo_path,
         branch: r.branch,
         path: r.path,
+        remote_url: r.remote_url,
+        credential_secret_id: r.credential_secret_id,
         created_at: r.created_at,
     }))
 }
@@ -1306,9 +1923,45 @@ fn validate_ops_repo_update(body: &UpdateOpsRepoRequest) -> Result<(), ApiError>
     if let Some(ref path) = body.path {
         validation::check_length("path", path, 1, 500)?;
     }
+    if let Some(ref url) = body.remote_url {
+        check_ops_repo_remote_url(url)?;
+    }
+    Ok(())
+}
+
+/// Validate an ops repo remote: SSRF-safe, and without inline credentials
+/// (those belong in the secrets engine via `credential_secret_id`).
+fn check_ops_repo_remote_url(url: &str) -> Result<(), ApiError> {
+    validation::check_length("remote_url", url, 1, 2048)?;
+    validation::check_ssrf_url(url, &["https", "http", "ssh"])?;
+    let parsed =
+        url::Url::parse(url).map_err(|_| ApiError::BadRequest("invalid remote_url".into()))?;
+    if parsed.password().is_some() {
+        return Err(ApiError::BadRequest(
+            "remote_url must not embed credentials; use credential_secret_id".into(),
+        ));
+    }
     Ok(())
 }
 
+/// The credential must be a global secret the deployer is allowed to read.
+async fn check_ops_repo_credential(state: &AppState, secret_id: Uuid) -> Result<(), ApiError> {
+    let scope = sqlx::query_scalar!(
+        "SELECT scope FROM secrets
+         WHERE id = $1 AND project_id IS NULL AND workspace_id IS NULL AND environment IS NULL",
+        secret_id
+    )
+    .fetch_optional(&state.pool)
+    .await?;
+    match scope.as_deref() {
+        Some("deploy" | "all") => Ok(()),
+        Some(_) => Err(ApiError::BadRequest(
+            "credential secret must have scope 'deploy' or 'all'".into(),
+        )),
+        None => Err(ApiError::NotFound("secret".into())),
+    }
+}
+
 #[tracing::instrument(skip(state), fields(%repo_id), err)]
 async fn delete_ops_repo(
     State(state): State<AppState>,
@@ -1374,6 +2027,7 @@ fn row_to_target(row: &sqlx::postgres::PgRow) -> TargetResponse {
         manifest_path: row.get("manifest_path"),
         hostname: row.get("hostname"),
         is_active: row.get("is_active"),
+        requires_approval: row.get("requires_approval"),
         created_at: row.get("created_at"),
         updated_at: row.get("updated_at"),
     }
@@ -1395,6 +2049,9 @@ fn row_to_release(row: &sqlx::postgres::PgRow) -> ReleaseResponse {
         values_override: row.get("values_override"),
         deployed_by: row.get("deployed_by"),
         pipeline_id: row.get("pipeline_id"),
+        requires_approval: row.get("requires_approval"),
+        approved_by: row.get("approved_by"),
+        approved_at: row.get("approved_at"),
         started_at: row.get("started_at"),
         completed_at: row.get("completed_at"),
         created_at: row.get("created_at"),
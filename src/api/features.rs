@@ -0,0 +1,416 @@
+// Copyright (c) 2026 Steven Hooker. Exclusively licensed to and distributed by AgentSphere GmbH.
+// SPDX-License-Identifier: BUSL-1.1
+
+//! Admin switches for platform features (see `crate::features`): the global
+//! state of each feature and its per-project and per-user overrides.
+
+use axum::extract::{Path, State};
+use axum::http::StatusCode;
+use axum::routing::{get, put};
+use axum::{Json, Router};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use ts_rs::TS;
+
+use crate::audit::{AuditEntry, send_audit};
+use crate::auth::middleware::AuthUser;
+use crate::error::ApiError;
+use crate::features::{self, Feature};
+use crate::store::AppState;
+
+use super::helpers::{ListResponse, require_admin};
+
+// ---------------------------------------------------------------------------
+// Types
+// ---------------------------------------------------------------------------
+
+#[derive(Debug, Deserialize)]
+pub struct SetFeatureRequest {
+    pub enabled: bool,
+}
+
+#[derive(Debug, Serialize, TS)]
+#[ts(export)]
+pub struct PlatformFeature {
+    pub key: String,
+    pub description: String,
+    pub default_enabled: bool,
+    /// Global state set by an admin; `null` means the default applies.
+    pub enabled: Option<bool>,
+    pub overrides: Vec<PlatformFeatureOverride>,
+}
+
+/// Exactly one of `project_id` and `user_id` is set.
+#[derive(Debug, Serialize, TS)]
+#[ts(export)]
+pub struct PlatformFeatureOverride {
+    pub id: Uuid,
+    pub feature: String,
+    pub project_id: Option<Uuid>,
+    pub user_id: Option<Uuid>,
+    pub enabled: bool,
+    pub created_by: Option<Uuid>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+/// What an override is attached to, from the route.
+#[derive(Debug, Clone, Copy)]
+enum Subject {
+    Project(Uuid),
+    User(Uuid),
+}
+
+impl Subject {
+    fn column(self) -> &'static str {
+        match self {
+            Self::Project(_) => "project_id",
+            Self::User(_) => "user_id",
+        }
+    }
+
+    fn id(self) -> Uuid {
+        match self {
+            Self::Project(id) | Self::User(id) => id,
+        }
+    }
+
+    fn name(self) -> &'static str {
+        match self {
+            Self::Project(_) => "project",
+            Self::User(_) => "user",
+        }
+    }
+
+    fn project_id(self) -> Option<Uuid> {
+        match self {
+            Self::Project(id) => Some(id),
+            Self::User(_) => None,
+        }
+    }
+}
+
+fn parse_feature(key: &str) -> Result<Feature, ApiError> {
+    Feature::parse(key).ok_or_else(|| ApiError::NotFound("feature".into()))
+}
+
+// ---------------------------------------------------------------------------
+// Router
+// ---------------------------------------------------------------------------
+
+pub fn router() -> Router<AppState> {
+    Router::new()
+        .route("/api/admin/features", get(list_features))
+        .route(
+            "/api/admin/features/{key}",
+            put(set_feature).delete(reset_feature),
+        )
+        .route(
+            "/api/admin/features/{key}/projects/{project_id}",
+            put(set_project_override).delete(delete_project_override),
+        )
+        .route(
+            "/api/admin/features/{key}/users/{user_id}",
+            put(set_user_override).delete(delete_user_override),
+        )
+}
+
+// ---------------------------------------------------------------------------
+// Handlers
+// ---------------------------------------------------------------------------
+
+async fn list_features(
+    State(state): State<AppState>,
+    auth: AuthUser,
+) -> Result<Json<ListResponse<PlatformFeature>>, ApiError> {
+    require_admin(&state, &auth).await?;
+
+    let globals = sqlx::query!("SELECT key, enabled FROM platform_features")
+        .fetch_all(&state.pool)
+        .await?;
+    let mut overrides = sqlx::query_as!(
+        PlatformFeatureOverride,
+        "SELECT id, feature, project_id, user_id, enabled, created_by, created_at, updated_at
+         FROM platform_feature_overrides ORDER BY created_at",
+    )
+    .fetch_all(&state.pool)
+    .await?;
+
+    let items: Vec<PlatformFeature> = Feature::ALL
+        .into_iter()
+        .map(|feature| PlatformFeature {
+            key: feature.key().into(),
+            description: feature.description().into(),
+            default_enabled: feature.default_enabled(),
+            enabled: globals
+                .iter()
+                .find(|g| g.key == feature.key())
+                .map(|g| g.enabled),
+            overrides: overrides
+                .extract_if(.., |o| o.feature == feature.key())
+                .collect(),
+        })
+        .collect();
+
+    let total = i64::try_from(items.len()).unwrap_or(i64::MAX);
+    Ok(Json(ListResponse { items, total }))
+}
+
+/// Set the global state of a feature.
+#[tracing::instrument(skip(state, body), fields(%key), err)]
+async fn set_feature(
+    State(state): State<AppState>,
+    auth: AuthUser,
+    Path(key): Path<String>,
+    Json(body): Json<SetFeatureRequest>,
+) -> Result<Json<serde_json::Value>, ApiError> {
+    require_admin(&state, &auth).await?;
+    let feature = parse_feature(&key)?;
+
+    sqlx::query!(
+        "INSERT INTO platform_features (key, enabled, updated_by) VALUES ($1, $2, $3)
+         ON CONFLICT (key) DO UPDATE
+             SET enabled = EXCLUDED.enabled, updated_by = EXCLUDED.updated_by, updated_at = now()",
+        feature.key(),
+        body.enabled,
+        auth.user_id,
+    )
+    .execute(&state.pool)
+    .await?;
+    features::invalidate(&state, feature).await;
+
+    audit(
+        &state,
+        &auth,
+        "feature.set",
+        None,
+        serde_json::json!({"feature": feature.key(), "enabled": body.enabled}),
+    );
+
+    Ok(Json(
+        serde_json::json!({"key": feature.key(), "enabled": body.enabled}),
+    ))
+}
+
+/// Return a feature to its built-in default. Overrides are kept.
+#[tracing::instrument(skip(state), fields(%key), err)]
+async fn reset_feature(
+    State(state): State<AppState>,
+    auth: AuthUser,
+    Path(key): Path<String>,
+) -> Result<StatusCode, ApiError> {
+    require_admin(&state, &auth).await?;
+    let feature = parse_feature(&key)?;
+
+    let result = sqlx::query!(
+        "DELETE FROM platform_features WHERE key = $1",
+        feature.key(),
+    )
+    .execute(&state.pool)
+    .await?;
+    if result.rows_affected() == 0 {
+        return Err(ApiError::NotFound("feature setting".into()));
+    }
+    features::invalidate(&state, feature).await;
+
+    audit(
+        &state,
+        &auth,
+        "feature.reset",
+        None,
+        serde_json::json!({"feature": feature.key()}),
+    );
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+async fn set_project_override(
+    State(state): State<AppState>,
+    auth: AuthUser,
+    Path((key, project_id)): Path<(String, Uuid)>,
+    Json(body): Json<SetFeatureRequest>,
+) -> Result<Json<PlatformFeatureOverride>, ApiError> {
+    set_override(&state, &auth, &key, Subject::Project(project_id), &body).await
+}
+
+async fn set_user_override(
+    State(state): State<AppState>,
+    auth: AuthUser,
+    Path((key, user_id)): Path<(String, Uuid)>,
+    Json(body): Json<SetFeatureRequest>,
+) -> Result<Json<PlatformFeatureOverride>, ApiError> {
+    set_override(&state, &auth, &key, Subject::User(user_id), &body).await
+}
+
+async fn delete_project_override(
+    State(state): State<AppState>,
+    auth: AuthUser,
+    Path((key, project_id)): Path<(String, Uuid)>,
+) -> Result<StatusCode, ApiError> {
+    delete_override(&state, &auth, &key, Subject::Project(project_id)).await
+}
+
+async fn delete_user_override(
+    State(state): State<AppState>,
+    auth: AuthUser,
+    Path((key, user_id)): Path<(String, Uuid)>,
+) -> Result<StatusCode, ApiError> {
+    delete_override(&state, &auth, &key, Subject::User(user_id)).await
+}
+
+/// Create or replace the override of `key` for `subject`.
+#[tracing::instrument(skip(state, auth, body), err)]
+async fn set_override(
+    state: &AppState,
+    auth: &AuthUser,
+    key: &str,
+    subject: Subject,
+    body: &SetFeatureRequest,
+) -> Result<Json<PlatformFeatureOverride>, ApiError> {
+    require_admin(state, auth).await?;
+    let feature = parse_feature(key)?;
+
+    let exists = match subject {
+        Subject::Project(id) => {
+            sqlx::query_scalar!(
+                r#"SELECT EXISTS(SELECT 1 FROM projects WHERE id = $1) as "exists!""#,
+                id,
+            )
+            .fetch_one(&state.pool)
+            .await?
+        }
+        Subject::User(id) => {
+            sqlx::query_scalar!(
+                r#"SELECT EXISTS(SELECT 1 FROM users WHERE id = $1) as "exists!""#,
+                id,
+            )
+            .fetch_one(&state.pool)
+            .await?
+        }
+    };
+    if !exists {
+        return Err(ApiError::NotFound(subject.name().into()));
+    }
+
+    let row = match subject {
+        Subject::Project(id) => {
+            sqlx::query_as!(
+                PlatformFeatureOverride,
+                "INSERT INTO platform_feature_overrides (feature, project_id, enabled, created_by)
+                 VALUES ($1, $2, $3, $4)
+                 ON CONFLICT (feature, project_id) DO UPDATE
+                     SET enabled = EXCLUDED.enabled, updated_at = now()
+                 RETURNING id, feature, project_id, user_id, enabled, created_by,
+                           created_at, updated_at",
+                feature.key(),
+                id,
+                body.enabled,
+                auth.user_id,
+            )
+            .fetch_one(&state.pool)
+            .await?
+        }
+        Subject::User(id) => {
+            sqlx::query_as!(
+                PlatformFeatureOverride,
+                "INSERT INTO platform_feature_overrides (feature, user_id, enabled, created_by)
+                 VALUES ($1, $2, $3, $4)
+                 ON CONFLICT (feature, user_id) DO UPDATE
+                     SET enabled = EXCLUDED.enabled, updated_at = now()
+                 RETURNING id, feature, project_id, user_id, enabled, created_by,
+                           created_at, updated_at",
+                feature.key(),
+                id,
+                body.enabled,
+                auth.user_id,
+            )
+            .fetch_one(&state.pool)
+            .await?
+        }
+    };
+    features::invalidate(state, feature).await;
+
+    audit(
+        state,
+        auth,
+        "feature.override.set",
+        subject.project_id(),
+        serde_json::json!({
+            "feature": feature.key(),
+            subject.column(): subject.id(),
+            "enabled": body.enabled,
+        }),
+    );
+
+    Ok(Json(row))
+}
+
+#[tracing::instrument(skip(state, auth), err)]
+async fn delete_override(
+    state: &AppState,
+    auth: &AuthUser,
+    key: &str,
+    subject: Subject,
+) -> Result<StatusCode, ApiError> {
+    require_admin(state, auth).await?;
+    let feature = parse_feature(key)?;
+
+    let result = match subject {
+        Subject::Project(id) => {
+            sqlx::query!(
+                "DELETE FROM platform_feature_overrides WHERE feature = $1 AND project_id = $2",
+                feature.key(),
+                id,
+            )
+            .execute(&state.pool)
+            .await?
+        }
+        Subject::User(id) => {
+            sqlx::query!(
+                "DELETE FROM platform_feature_overrides WHERE feature = $1 AND user_id = $2",
+                feature.key(),
+                id,
+            )
+            .execute(&state.pool)
+            .await?
+        }
+    };
+    if result.rows_affected() == 0 {
+        return Err(ApiError::NotFound("feature override".into()));
+    }
+    features::invalidate(state, feature).await;
+
+    audit(
+        state,
+        auth,
+        "feature.override.delete",
+        subject.project_id(),
+        serde_json::json!({"feature": feature.key(), subject.column(): subject.id()}),
+    );
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+fn audit(
+    state: &AppState,
+    auth: &AuthUser,
+    action: &str,
+    project_id: Option<Uuid>,
+    detail: serde_json::Value,
+) {
+    send_audit(
+        &state.audit_tx,
+        AuditEntry {
+            actor_id: auth.user_id,
+            actor_name: auth.user_name.clone(),
+            action: action.into(),
+            resource: "platform_feature".into(),
+            resource_id: None,
+            project_id,
+            detail: Some(detail),
+            ip_addr: auth.ip_addr.clone(),
+        },
+    );
+}
@@ -18,6 +18,7 @@ use crate::auth::middleware::AuthUser;
 use crate::error::ApiError;
 use crate::health::{HealthSnapshot, SubsystemCheck, SubsystemStatus};
 use crate::store::AppState;
+use crate::store::pool::{self, DbPoolStats};
 
 // ---------------------------------------------------------------------------
 // Response types
@@ -30,6 +31,8 @@ pub struct HealthSummary {
     pub subsystems: Vec<SubsystemCheck>,
     #[ts(type = "number")]
     pub uptime_seconds: u64,
+    /// Live `PostgreSQL` pool utilization.
+    pub db_pool: DbPoolStats,
 }
 
 // ---------------------------------------------------------------------------
@@ -47,7 +50,7 @@ pub fn router() -> Router<AppState> {
 // Handlers
 // ---------------------------------------------------------------------------
 
-/// GET /api/health — admin-only summary (overall + subsystems).
+/// GET /api/health — admin-only summary (overall + subsystems + DB pool).
 #[tracing::instrument(skip(state), err)]
 async fn health_summary(
     State(state): State<AppState>,
@@ -65,6 +68,7 @@ async fn health_summary(
         overall: snap.overall,
         subsystems: snap.subsystems,
         uptime_seconds: snap.uptime_seconds,
+        db_pool: pool::stats(&state.pool),
     }))
 }
 
@@ -5,6 +5,7 @@ use serde::Serialize;
 use ts_rs::TS;
 use uuid::Uuid;
 
+use crate::audit::{AuditEntry, send_audit};
 use crate::auth::middleware::AuthUser;
 use crate::error::ApiError;
 use crate::rbac::{Permission, resolver};
@@ -68,7 +69,12 @@ pub async fn require_project_read(
 }
 
 /// Check the caller has admin:users permission (scope-aware), return Forbidden otherwise.
+/// Tokens bounded to a project or workspace never act as admin, whatever the
+/// owner's role.
 pub async fn require_admin(state: &AppState, auth: &AuthUser) -> Result<(), ApiError> {
+    if auth.boundary_project_id.is_some() || auth.boundary_workspace_id.is_some() {
+        return Err(ApiError::Forbidden);
+    }
     let allowed = resolver::has_permission_scoped(
         &state.pool,
         &state.valkey,
@@ -86,7 +92,8 @@ pub async fn require_admin(state: &AppState, auth: &AuthUser) -> Result<(), ApiE
     Ok(())
 }
 
-/// Check project-level write access via scope + RBAC.
+/// Check project-level write access via scope + RBAC. Archived projects are
+/// read-only, so this returns `Locked` for them even when RBAC allows.
 pub async fn require_project_write(
     state: &AppState,
     auth: &AuthUser,
@@ -122,5 +129,74 @@ pub async fn require_project_write(
     if !allowed {
         return Err(ApiError::Forbidden);
     }
+
+    require_not_archived(state, project_id).await
+}
+
+/// Refuse modifications to an archived project.
+pub async fn require_not_archived(state: &AppState, project_id: Uuid) -> Result<(), ApiError> {
+    let archived = sqlx::query_scalar!(
+        r#"SELECT archived_at IS NOT NULL AS "archived!: bool" FROM projects WHERE id = $1"#,
+        project_id,
+    )
+    .fetch_optional(&state.pool)
+    .await?;
+    if archived == Some(true) {
+        return Err(ApiError::Locked("project is archived".into()));
+    }
     Ok(())
 }
+
+/// Resource types whose GET handlers emit a `{name}.read` audit entry when
+/// listed in `PLATFORM_AUDIT_READS`. Reads are not audited by default; only
+/// low-volume, sensitive resources belong here.
+#[derive(Debug, Clone, Copy)]
+pub enum ReadAudit {
+    /// Secret listings (values are always audited as `secret.read`).
+    Secrets,
+    /// The audit log itself.
+    AuditLog,
+}
+
+impl ReadAudit {
+    /// Name used in `PLATFORM_AUDIT_READS` and as the action prefix.
+    pub const fn name(self) -> &'static str {
+        match self {
+            Self::Secrets => "secrets",
+            Self::AuditLog => "audit_log",
+        }
+    }
+
+    const fn resource(self) -> &'static str {
+        match self {
+            Self::Secrets => "secret",
+            Self::AuditLog => "audit_log",
+        }
+    }
+}
+
+/// Record a read of `kind` by the caller, if read auditing is enabled for it.
+pub fn audit_read(
+    state: &AppState,
+    auth: &AuthUser,
+    kind: ReadAudit,
+    project_id: Option<Uuid>,
+    detail: serde_json::Value,
+) {
+    if !state.config.audit_reads.iter().any(|r| r == kind.name()) {
+        return;
+    }
+    send_audit(
+        &state.audit_tx,
+        AuditEntry {
+            actor_id: auth.user_id,
+            actor_name: auth.user_name.clone(),
+            action: format!("{}.read", kind.name()),
+            resource: kind.resource().into(),
+            resource_id: None,
+            project_id,
+            detail: Some(detail),
+            ip_addr: auth.ip_addr.clone(),
+        },
+    );
+}
@@ -0,0 +1,351 @@
+// Copyright (c) 2026 Steven Hooker. Exclusively licensed to and distributed by AgentSphere GmbH.
+// SPDX-License-Identifier: BUSL-1.1
+
+//! `Idempotency-Key` support for create endpoints.
+//!
+//! A client retrying a create request after a network failure sends the same
+//! `Idempotency-Key` header. The first request's response is kept in Valkey for
+//! 24h and replayed for every retry, so a retry never creates a duplicate.
+//! Keys are scoped to the presented credential and the request path, and bound
+//! to a fingerprint of the request body.
+//!
+//! Token creation is covered too, but its success response holds the plaintext
+//! token, which is never stored. A retry of a completed token request gets 409
+//! instead of a replay, and the client lists its tokens to find the one created.
+
+use axum::body::{Body, Bytes};
+use axum::extract::{Request, State};
+use axum::http::{HeaderValue, Method, StatusCode, header};
+use axum::middleware::Next;
+use axum::response::{IntoResponse, Response};
+use fred::interfaces::KeysInterface;
+use fred::types::{Expiration, SetOptions};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+use crate::auth::token;
+use crate::error::ApiError;
+use crate::store::AppState;
+
+pub const IDEMPOTENCY_KEY_HEADER: &str = "idempotency-key";
+
+/// Set on responses replayed from the idempotency cache.
+pub const IDEMPOTENT_REPLAYED_HEADER: &str = "idempotent-replayed";
+
+/// How long a completed response is replayed.
+const RESPONSE_TTL_SECS: i64 = 24 * 3600;
+
+const MAX_KEY_LEN: usize = 255;
+
+/// Largest request body accepted with an `Idempotency-Key`, and largest response cached.
+const MAX_BODY_BYTES: usize = 1024 * 1024;
+
+/// Create endpoints that honour `Idempotency-Key`. `*` matches one path segment.
+const IDEMPOTENT_ROUTES: &[&str] = &[
+    "/api/projects",
+    "/api/projects/*/issues",
+    "/api/projects/*/merge-requests",
+    "/api/tokens",
+];
+
+/// Idempotent routes whose success response carries a secret. Only the fact
+/// that the request completed is stored; a retry gets 409, not the secret.
+const SECRET_ROUTES: &[&str] = &["/api/tokens"];
+
+#[derive(Debug, Serialize, Deserialize)]
+struct Record {
+    /// SHA-256 of the request body the key was first used with.
+    fingerprint: String,
+    /// `None` while the first request is still being handled.
+    response: Option<CachedResponse>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct CachedResponse {
+    status: u16,
+    content_type: Option<String>,
+    body: String,
+    /// The response held a secret and its body was not stored.
+    #[serde(default)]
+    withheld: bool,
+}
+
+fn route_matches(pattern: &str, path: &str) -> bool {
+    let mut pattern_segments = pattern.split('/');
+    let mut path_segments = path.trim_end_matches('/').split('/');
+    loop {
+        match (pattern_segments.next(), path_segments.next()) {
+            (None, None) => return true,
+            (Some(p), Some(s)) if p == "*" && !s.is_empty() => {}
+            (Some(p), Some(s)) if p == s => {}
+            _ => return false,
+        }
+    }
+}
+
+fn is_idempotent_route(method: &Method, path: &str) -> bool {
+    method == Method::POST && IDEMPOTENT_ROUTES.iter().any(|p| route_matches(p, path))
+}
+
+fn returns_secret(path: &str) -> bool {
+    SECRET_ROUTES.iter().any(|p| route_matches(p, path))
+}
+
+fn validate_key(key: &str) -> Result<(), ApiError> {
+    if key.is_empty() || key.len() > MAX_KEY_LEN {
+        return Err(ApiError::BadRequest(format!(
+            "Idempotency-Key must be 1-{MAX_KEY_LEN} characters"
+        )));
+    }
+    if !key.bytes().all(|b| b.is_ascii_graphic()) {
+        return Err(ApiError::BadRequest(
+            "Idempotency-Key must be printable ASCII".into(),
+        ));
+    }
+    Ok(())
+}
+
+/// Only settled outcomes are replayed. Server errors and rate limiting are
+/// transient, so the key is released and the client may retry for real.
+fn is_cacheable(status: StatusCode) -> bool {
+    !status.is_server_error() && status != StatusCode::TOO_MANY_REQUESTS
+}
+
+fn replay(cached: CachedResponse) -> Response {
+    if cached.withheld {
+        return ApiError::Conflict(
+            "a request with this Idempotency-Key was already processed; \
+             its response held a secret and is not replayed"
+                .into(),
+        )
+        .into_response();
+    }
+    let status = StatusCode::from_u16(cached.status).unwrap_or(StatusCode::OK);
+    let mut resp = (status, cached.body).into_response();
+    if let Some(ct) = cached
+        .content_type
+        .and_then(|ct| HeaderValue::from_str(&ct).ok())
+    {
+        resp.headers_mut().insert(header::CONTENT_TYPE, ct);
+    }
+    resp.headers_mut()
+        .insert(IDEMPOTENT_REPLAYED_HEADER, HeaderValue::from_static("true"));
+    resp
+}
+
+/// Middleware implementing `Idempotency-Key` for [`IDEMPOTENT_ROUTES`].
+///
+/// The first request with a key claims it (`SET NX`) with an in-flight marker;
+/// a concurrent retry gets 409 until the first one finishes. Reusing a key with
+/// a different body is rejected with 422. Requests without a key, without
+/// credentials, or to other routes pass through untouched.
+pub async fn idempotency(State(state): State<AppState>, req: Request, next: Next) -> Response {
+    if !is_idempotent_route(req.method(), req.uri().path()) {
+        return next.run(req).await;
+    }
+    let Some(key) = req
+        .headers()
+        .get(IDEMPOTENCY_KEY_HEADER)
+        .map(|v| v.to_str().unwrap_or_default().to_owned())
+    else {
+        return next.run(req).await;
+    };
+    if let Err(e) = validate_key(&key) {
+        return e.into_response();
+    }
+
+    let (parts, body) = req.into_parts();
+    let Some(credential) =
+        crate::auth::middleware::request_credential(&parts).map(token::hash_token)
+    else {
+        // Unauthenticated: let the handler reject it.
+        return next.run(Request::from_parts(parts, body)).await;
+    };
+    let cache_key = format!("idempotency:{credential}:{}:{key}", parts.uri.path());
+    let secret = returns_secret(parts.uri.path());
+
+    let Ok(bytes) = axum::body::to_bytes(body, MAX_BODY_BYTES).await else {
+        return ApiError::BadRequest("request body too large".into()).into_response();
+    };
+    let fingerprint = hex::encode(Sha256::digest(&bytes));
+    let req = Request::from_parts(parts, Body::from(bytes));
+
+    match claim(&state, &cache_key, &fingerprint).await {
+        Ok(Claim::Acquired) => {}
+        Ok(Claim::Existing(record)) => {
+            if record.fingerprint != fingerprint {
+                return ApiError::Validation(vec![
+                    "Idempotency-Key was already used with a different request body".into(),
+                ])
+                .into_response();
+            }
+            return match record.response {
+                Some(cached) => replay(cached),
+                None => ApiError::Conflict(
+                    "a request with this Idempotency-Key is still in progress".into(),
+                )
+                .into_response(),
+            };
+        }
+        Err(e) => {
+            tracing::warn!(error = %e, "idempotency check failed; handling request normally");
+            return next.run(req).await;
+        }
+    }
+
+    let resp = next.run(req).await;
+    let (parts, body) = resp.into_parts();
+    let body = match axum::body::to_bytes(body, usize::MAX).await {
+        Ok(b) => b,
+        Err(e) => {
+            release(&state, &cache_key).await;
+            return ApiError::Internal(anyhow::anyhow!("reading response body: {e}"))
+                .into_response();
+        }
+    };
+
+    if is_cacheable(parts.status) {
+        let withhold = secret && parts.status.is_success();
+        store(&state, &cache_key, fingerprint, &parts, &body, withhold).await;
+    } else {
+        release(&state, &cache_key).await;
+    }
+    Response::from_parts(parts, Body::from(body))
+}
+
+enum Claim {
+    Acquired,
+    Existing(Record),
+}
+
+/// Claim `cache_key` for this request, or return the record already stored under it.
+async fn claim(state: &AppState, cache_key: &str, fingerprint: &str) -> anyhow::Result<Claim> {
+    let pending = serde_json::to_string(&Record {
+        fingerprint: fingerprint.to_owned(),
+        response: None,
+    })?;
+    // The in-flight marker lives as long as a request may run, so a crashed
+    // request does not block the key for a whole day.
+    let ttl = i64::try_from(state.config.request_timeout_secs).unwrap_or(300);
+    let claimed: Option<String> = state
+        .valkey
+        .set(
+            cache_key,
+            pending,
+            Some(Expiration::EX(ttl)),
+            Some(SetOptions::NX),
+            false,
+        )
+        .await?;
+    if claimed.is_some() {
+        return Ok(Claim::Acquired);
+    }
+
+    match crate::store::valkey::get_cached::<Record>(&state.valkey, cache_key).await {
+        Some(record) => Ok(Claim::Existing(record)),
+        // Expired between SET and GET: treat as ours.
+        None => Ok(Claim::Acquired),
+    }
+}
+
+async fn store(
+    state: &AppState,
+    cache_key: &str,
+    fingerprint: String,
+    parts: &axum::http::response::Parts,
+    body: &Bytes,
+    withhold: bool,
+) {
+    let body: &[u8] = if withhold { b"" } else { body };
+    let Ok(body) = std::str::from_utf8(body) else {
+        release(state, cache_key).await;
+        return;
+    };
+    if body.len() > MAX_BODY_BYTES {
+        release(state, cache_key).await;
+        return;
+    }
+    let record = Record {
+        fingerprint,
+        response: Some(CachedResponse {
+            status: parts.status.as_u16(),
+            content_type: parts
+                .headers
+                .get(header::CONTENT_TYPE)
+                .and_then(|v| v.to_str().ok())
+                .map(str::to_owned),
+            body: body.to_owned(),
+            withheld: withhold,
+        }),
+    };
+    if let Err(e) =
+        crate::store::valkey::set_cached(&state.valkey, cache_key, &record, RESPONSE_TTL_SECS).await
+    {
+        tracing::warn!(error = %e, "failed to store idempotent response");
+    }
+}
+
+async fn release(state: &AppState, cache_key: &str) {
+    if let Err(e) = crate::store::valkey::invalidate(&state.valkey, cache_key).await {
+        tracing::warn!(error = %e, "failed to release idempotency key");
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn route_matching() {
+        assert!(route_matches("/api/projects", "/api/projects"));
+        assert!(route_matches("/api/projects", "/api/projects/"));
+        assert!(route_matches(
+            "/api/projects/*/issues",
+            "/api/projects/abc/issues"
+        ));
+        assert!(!route_matches(
+            "/api/projects/*/issues",
+            "/api/projects//issues"
+        ));
+        assert!(!route_matches(
+            "/api/projects/*/issues",
+            "/api/projects/abc/issues/1/comments"
+        ));
+        assert!(!route_matches("/api/projects", "/api/projects/abc"));
+    }
+
+    #[test]
+    fn only_post_to_create_routes() {
+        assert!(is_idempotent_route(&Method::POST, "/api/projects"));
+        assert!(is_idempotent_route(
+            &Method::POST,
+            "/api/projects/abc/merge-requests"
+        ));
+        assert!(is_idempotent_route(&Method::POST, "/api/tokens"));
+        assert!(!is_idempotent_route(&Method::GET, "/api/projects"));
+        assert!(!is_idempotent_route(&Method::POST, "/api/users"));
+    }
+
+    #[test]
+    fn only_token_creation_withholds_the_response() {
+        assert!(returns_secret("/api/tokens"));
+        assert!(!returns_secret("/api/tokens/abc"));
+        assert!(!returns_secret("/api/projects"));
+    }
+
+    #[test]
+    fn key_validation() {
+        assert!(validate_key("3f0c9a1e-retry").is_ok());
+        assert!(validate_key("").is_err());
+        assert!(validate_key("has space").is_err());
+        assert!(validate_key(&"a".repeat(MAX_KEY_LEN + 1)).is_err());
+    }
+
+    #[test]
+    fn transient_failures_are_not_cached() {
+        assert!(is_cacheable(StatusCode::CREATED));
+        assert!(is_cacheable(StatusCode::CONFLICT));
+        assert!(!is_cacheable(StatusCode::INTERNAL_SERVER_ERROR));
+        assert!(!is_cacheable(StatusCode::TOO_MANY_REQUESTS));
+    }
+}
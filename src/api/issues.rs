@@ -4,7 +4,7 @@
 use axum::extract::{Path, Query, State};
 use axum::http::StatusCode;
 use axum::response::IntoResponse;
-use axum::routing::get;
+use axum::routing::{get, put};
 use axum::{Json, Router};
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
@@ -47,6 +47,12 @@ pub struct ListIssuesParams {
     pub offset: Option<i64>,
     pub status: Option<String>,
     pub assignee_id: Option<Uuid>,
+    pub label: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct AssignIssueRequest {
+    pub assignee_id: Uuid,
 }
 
 #[derive(Debug, Deserialize)]
@@ -79,6 +85,7 @@ pub struct IssueResponse {
     pub assignee_id: Option<Uuid>,
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
+    pub reactions: Vec<ReactionCount>,
 }
 
 #[derive(Debug, Serialize, TS)]
@@ -89,9 +96,47 @@ pub struct CommentResponse {
     pub body: String,
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
+    pub reactions: Vec<ReactionCount>,
 }
 
-use super::helpers::{ListResponse, require_project_read, require_project_write};
+/// Issue lifecycle states.
+const ISSUE_STATES: &[&str] = &["open", "in_progress", "closed"];
+
+fn validate_issue_state(state: &str) -> Result<(), ApiError> {
+    if ISSUE_STATES.contains(&state) {
+        Ok(())
+    } else {
+        Err(ApiError::BadRequest(format!(
+            "status must be one of: {}",
+            ISSUE_STATES.join(", ")
+        )))
+    }
+}
+
+/// Allowed lifecycle transitions. Setting the current state again is a no-op;
+/// a closed issue must be reopened before work can resume on it.
+fn validate_issue_transition(from: &str, to: &str) -> Result<(), ApiError> {
+    validate_issue_state(to)?;
+    let allowed = from == to
+        || matches!(
+            (from, to),
+            ("open", "in_progress" | "closed")
+                | ("in_progress", "open" | "closed")
+                | ("closed", "open")
+        );
+    if allowed {
+        Ok(())
+    } else {
+        Err(ApiError::BadRequest(format!(
+            "cannot transition issue from {from} to {to}"
+        )))
+    }
+}
+
+use super::helpers::{
+    ListResponse, require_not_archived, require_project_read, require_project_write,
+};
+use super::reactions::{self, ReactionCount, ReactionTarget};
 
 // ---------------------------------------------------------------------------
 // Router
@@ -107,6 +152,10 @@ pub fn router() -> Router<AppState> {
             "/api/projects/{id}/issues/{number}",
             get(get_issue).patch(update_issue).delete(delete_issue),
         )
+        .route(
+            "/api/projects/{id}/issues/{number}/assignee",
+            put(assign_issue).delete(unassign_issue),
+        )
         .route(
             "/api/projects/{id}/issues/{number}/comments",
             get(list_comments).post(create_comment),
@@ -123,6 +172,7 @@ pub fn router() -> Router<AppState> {
 // Issue handlers
 // ---------------------------------------------------------------------------
 
+#[allow(clippy::too_many_lines)]
 #[tracing::instrument(skip(state, body), fields(%id), err)]
 async fn create_issue(
     State(state): State<AppState>,
@@ -154,6 +204,11 @@ async fn create_issue(
     if !allowed {
         return Err(ApiError::Forbidden);
     }
+    require_not_archived(&state, id).await?;
+
+    if let Some(assignee_id) = body.assignee_id {
+        check_assignee(&state, id, assignee_id).await?;
+    }
 
     // Atomic increment of issue number
     let number = sqlx::query_scalar!(
@@ -168,25 +223,29 @@ async fn create_issue(
     .await?
     .ok_or_else(|| ApiError::NotFound("project".into()))?;
 
-    let labels = body.labels.unwrap_or_default();
-
     let issue = sqlx::query!(
         r#"
-        INSERT INTO issues (project_id, number, author_id, title, body, labels, assignee_id)
-        VALUES ($1, $2, $3, $4, $5, $6, $7)
-        RETURNING id, project_id, number, author_id, title, body, status, labels, assignee_id, created_at, updated_at
+        INSERT INTO issues (project_id, number, author_id, title, body, assignee_id)
+        VALUES ($1, $2, $3, $4, $5, $6)
+        RETURNING id, project_id, number, author_id, title, body, status, assignee_id, created_at, updated_at
         "#,
         id,
         number,
         auth.user_id,
         body.title,
         body.body,
-        &labels,
         body.assignee_id,
     )
     .fetch_one(&state.pool)
     .await?;
 
+    let labels = match body.labels {
+        Some(ref labels) => {
+            crate::api::labels::set_issue_labels(&state.pool, id, issue.id, labels).await?
+        }
+        None => Vec::new(),
+    };
+
     send_audit(
         &state.audit_tx,
         AuditEntry {
@@ -214,6 +273,12 @@ async fn create_issue(
     )
     .await;
 
+    if let Some(assignee_id) = issue.assignee_id
+        && assignee_id != auth.user_id
+    {
+        crate::notify::dispatch::on_issue_assigned(&state, id, issue.id, number, assignee_id).await;
+    }
+
     Ok((
         StatusCode::CREATED,
         Json(IssueResponse {
@@ -224,10 +289,11 @@ async fn create_issue(
             title: issue.title,
             body: issue.body,
             status: issue.status,
-            labels: issue.labels,
+            labels,
             assignee_id: issue.assignee_id,
             created_at: issue.created_at,
             updated_at: issue.updated_at,
+            reactions: Vec::new(),
         }),
     ))
 }
@@ -239,6 +305,9 @@ async fn list_issues(
     Query(params): Query<ListIssuesParams>,
 ) -> Result<Json<ListResponse<IssueResponse>>, ApiError> {
     require_project_read(&state, &auth, id).await?;
+    if let Some(ref status) = params.status {
+        validate_issue_state(status)?;
+    }
 
     let limit = params.limit.unwrap_or(50).min(100);
     let offset = params.offset.unwrap_or(0);
@@ -250,33 +319,47 @@ async fn list_issues(
         WHERE project_id = $1
           AND ($2::text IS NULL OR status = $2)
           AND ($3::uuid IS NULL OR assignee_id = $3)
+          AND ($4::text IS NULL OR EXISTS (
+              SELECT 1 FROM issue_labels il JOIN labels l ON l.id = il.label_id
+              WHERE il.issue_id = issues.id AND l.name = $4))
         "#,
         id,
         params.status,
         params.assignee_id,
+        params.label,
     )
     .fetch_one(&state.pool)
     .await?;
 
     let rows = sqlx::query!(
         r#"
-        SELECT id, project_id, number, author_id, title, body, status, labels, assignee_id, created_at, updated_at
+        SELECT id, project_id, number, author_id, title, body, status,
+               ARRAY(SELECT l.name FROM issue_labels il JOIN labels l ON l.id = il.label_id
+                     WHERE il.issue_id = issues.id ORDER BY l.name) as "labels!",
+               assignee_id, created_at, updated_at
         FROM issues
         WHERE project_id = $1
           AND ($2::text IS NULL OR status = $2)
           AND ($3::uuid IS NULL OR assignee_id = $3)
+          AND ($4::text IS NULL OR EXISTS (
+              SELECT 1 FROM issue_labels il JOIN labels l ON l.id = il.label_id
+              WHERE il.issue_id = issues.id AND l.name = $4))
         ORDER BY number DESC
-        LIMIT $4 OFFSET $5
+        LIMIT $5 OFFSET $6
         "#,
         id,
         params.status,
         params.assignee_id,
+        params.label,
         limit,
         offset,
     )
     .fetch_all(&state.pool)
     .await?;
 
+    let ids: Vec<Uuid> = rows.iter().map(|i| i.id).collect();
+    let mut reactions =
+        reactions::summaries(&state.pool, ReactionTarget::Issue, &ids, auth.user_id).await?;
     let items = rows
         .into_iter()
         .map(|i| IssueResponse {
@@ -291,6 +374,7 @@ async fn list_issues(
             assignee_id: i.assignee_id,
             created_at: i.created_at,
             updated_at: i.updated_at,
+            reactions: reactions.remove(&i.id).unwrap_or_default(),
         })
         .collect();
 
@@ -306,7 +390,10 @@ async fn get_issue(
 
     let issue = sqlx::query!(
         r#"
-        SELECT id, project_id, number, author_id, title, body, status, labels, assignee_id, created_at, updated_at
+        SELECT id, project_id, number, author_id, title, body, status,
+               ARRAY(SELECT l.name FROM issue_labels il JOIN labels l ON l.id = il.label_id
+                     WHERE il.issue_id = issues.id ORDER BY l.name) as "labels!",
+               assignee_id, created_at, updated_at
         FROM issues WHERE project_id = $1 AND number = $2
         "#,
         id,
@@ -316,6 +403,8 @@ async fn get_issue(
     .await?
     .ok_or_else(|| ApiError::NotFound("issue".into()))?;
 
+    let reactions =
+        reactions::summary(&state.pool, ReactionTarget::Issue, issue.id, auth.user_id).await?;
     Ok(Json(IssueResponse {
         id: issue.id,
         project_id: issue.project_id,
@@ -328,9 +417,11 @@ async fn get_issue(
         assignee_id: issue.assignee_id,
         created_at: issue.created_at,
         updated_at: issue.updated_at,
+        reactions,
     }))
 }
 
+#[allow(clippy::too_many_lines)]
 #[tracing::instrument(skip(state, body), fields(%id, %number), err)]
 async fn update_issue(
     State(state): State<AppState>,
@@ -353,8 +444,8 @@ async fn update_issue(
     require_project_write(&state, &auth, id).await?;
 
     // Verify issue exists and check authorship (non-authors also need admin to edit)
-    let issue_author = sqlx::query_scalar!(
-        "SELECT author_id FROM issues WHERE project_id = $1 AND number = $2",
+    let current = sqlx::query!(
+        "SELECT id, author_id, status, assignee_id FROM issues WHERE project_id = $1 AND number = $2",
         id,
         number,
     )
@@ -362,7 +453,7 @@ async fn update_issue(
     .await?
     .ok_or_else(|| ApiError::NotFound("issue".into()))?;
 
-    if issue_author != auth.user_id {
+    if current.author_id != auth.user_id {
         let is_admin = crate::rbac::resolver::has_permission_scoped(
             &state.pool,
             &state.valkey,
@@ -379,10 +470,17 @@ async fn update_issue(
         }
     }
 
-    if let Some(ref status) = body.status
-        && !["open", "closed"].contains(&status.as_str())
+    if let Some(ref status) = body.status {
+        validate_issue_transition(&current.status, status)?;
+    }
+    if let Some(assignee_id) = body.assignee_id
+        && current.assignee_id != Some(assignee_id)
     {
-        return Err(ApiError::BadRequest("status must be open or closed".into()));
+        check_assignee(&state, id, assignee_id).await?;
+    }
+
+    if let Some(ref labels) = body.labels {
+        crate::api::labels::set_issue_labels(&state.pool, id, current.id, labels).await?;
     }
 
     let issue = sqlx::query!(
@@ -391,37 +489,53 @@ async fn update_issue(
             title = COALESCE($3, title),
             body = COALESCE($4, body),
             status = COALESCE($5, status),
-            labels = COALESCE($6, labels),
-            assignee_id = COALESCE($7, assignee_id)
+            assignee_id = COALESCE($6, assignee_id)
         WHERE project_id = $1 AND number = $2
-        RETURNING id, project_id, number, author_id, title, body, status, labels, assignee_id, created_at, updated_at
+        RETURNING id, project_id, number, author_id, title, body, status,
+                  ARRAY(SELECT l.name FROM issue_labels il JOIN labels l ON l.id = il.label_id
+                        WHERE il.issue_id = issues.id ORDER BY l.name) as "labels!",
+                  assignee_id, created_at, updated_at
         "#,
         id,
         number,
         body.title,
         body.body,
         body.status,
-        body.labels.as_deref(),
         body.assignee_id,
     )
     .fetch_optional(&state.pool)
     .await?
     .ok_or_else(|| ApiError::NotFound("issue".into()))?;
 
+    let closed = issue.status == "closed" && current.status != "closed";
     send_audit(
         &state.audit_tx,
         AuditEntry {
             actor_id: auth.user_id,
             actor_name: auth.user_name.clone(),
-            action: "issue.update".into(),
+            action: if closed {
+                "issue.close"
+            } else {
+                "issue.update"
+            }
+            .into(),
             resource: "issue".into(),
             resource_id: Some(issue.id),
             project_id: Some(id),
-            detail: None,
+            detail: closed.then(|| close_detail(number, &current.status, &auth, issue.updated_at)),
             ip_addr: auth.ip_addr.clone(),
         },
     );
 
+    if let Some(assignee_id) = issue.assignee_id
+        && current.assignee_id != Some(assignee_id)
+        && assignee_id != auth.user_id
+    {
+        crate::notify::dispatch::on_issue_assigned(&state, id, issue.id, number, assignee_id).await;
+    }
+
+    let reactions =
+        reactions::summary(&state.pool, ReactionTarget::Issue, issue.id, auth.user_id).await?;
     Ok(Json(IssueResponse {
         id: issue.id,
         project_id: issue.project_id,
@@ -434,6 +548,7 @@ async fn update_issue(
         assignee_id: issue.assignee_id,
         created_at: issue.created_at,
         updated_at: issue.updated_at,
+        reactions,
     }))
 }
 
@@ -448,7 +563,7 @@ async fn delete_issue(
     let row = sqlx::query(
         "UPDATE issues SET status = 'closed', updated_at = now() \
          WHERE project_id = $1 AND number = $2 AND status != 'closed' \
-         RETURNING id",
+         RETURNING id, updated_at",
     )
     .bind(id)
     .bind(number)
@@ -457,6 +572,7 @@ async fn delete_issue(
 
     if let Some(row) = row {
         let issue_id: Uuid = row.get("id");
+        let closed_at: DateTime<Utc> = row.get("updated_at");
 
         send_audit(
             &state.audit_tx,
@@ -467,7 +583,11 @@ async fn delete_issue(
                 resource: "issue".into(),
                 resource_id: Some(issue_id),
                 project_id: Some(id),
-                detail: Some(serde_json::json!({"number": number})),
+                detail: Some(serde_json::json!({
+                    "number": number,
+                    "closed_by": auth.user_id,
+                    "closed_at": closed_at,
+                })),
                 ip_addr: auth.ip_addr.clone(),
             },
         );
@@ -488,6 +608,191 @@ async fn delete_issue(
     Ok(StatusCode::NO_CONTENT)
 }
 
+#[tracing::instrument(skip(state, body), fields(%id, %number), err)]
+async fn assign_issue(
+    State(state): State<AppState>,
+    auth: AuthUser,
+    Path((id, number)): Path<(Uuid, i32)>,
+    Json(body): Json<AssignIssueRequest>,
+) -> Result<Json<IssueResponse>, ApiError> {
+    require_project_write(&state, &auth, id).await?;
+    check_assignee(&state, id, body.assignee_id).await?;
+
+    let previous = sqlx::query_scalar!(
+        "SELECT assignee_id FROM issues WHERE project_id = $1 AND number = $2",
+        id,
+        number,
+    )
+    .fetch_optional(&state.pool)
+    .await?
+    .ok_or_else(|| ApiError::NotFound("issue".into()))?;
+
+    let issue = sqlx::query!(
+        r#"
+        UPDATE issues SET assignee_id = $3
+        WHERE project_id = $1 AND number = $2
+        RETURNING id, project_id, number, author_id, title, body, status,
+                  ARRAY(SELECT l.name FROM issue_labels il JOIN labels l ON l.id = il.label_id
+                        WHERE il.issue_id = issues.id ORDER BY l.name) as "labels!",
+                  assignee_id, created_at, updated_at
+        "#,
+        id,
+        number,
+        body.assignee_id,
+    )
+    .fetch_optional(&state.pool)
+    .await?
+    .ok_or_else(|| ApiError::NotFound("issue".into()))?;
+
+    send_audit(
+        &state.audit_tx,
+        AuditEntry {
+            actor_id: auth.user_id,
+            actor_name: auth.user_name.clone(),
+            action: "issue.assign".into(),
+            resource: "issue".into(),
+            resource_id: Some(issue.id),
+            project_id: Some(id),
+            detail: Some(serde_json::json!({
+                "number": number,
+                "assignee_id": body.assignee_id,
+                "previous_assignee_id": previous,
+            })),
+            ip_addr: auth.ip_addr.clone(),
+        },
+    );
+
+    if previous != Some(body.assignee_id) && body.assignee_id != auth.user_id {
+        crate::notify::dispatch::on_issue_assigned(&state, id, issue.id, number, body.assignee_id)
+            .await;
+    }
+
+    let reactions =
+        reactions::summary(&state.pool, ReactionTarget::Issue, issue.id, auth.user_id).await?;
+    Ok(Json(IssueResponse {
+        id: issue.id,
+        project_id: issue.project_id,
+        number: issue.number,
+        author_id: issue.author_id,
+        title: issue.title,
+        body: issue.body,
+        status: issue.status,
+        labels: issue.labels,
+        assignee_id: issue.assignee_id,
+        created_at: issue.created_at,
+        updated_at: issue.updated_at,
+        reactions,
+    }))
+}
+
+#[tracing::instrument(skip(state), fields(%id, %number), err)]
+async fn unassign_issue(
+    State(state): State<AppState>,
+    auth: AuthUser,
+    Path((id, number)): Path<(Uuid, i32)>,
+) -> Result<Json<IssueResponse>, ApiError> {
+    require_project_write(&state, &auth, id).await?;
+
+    let issue = sqlx::query!(
+        r#"
+        UPDATE issues SET assignee_id = NULL
+        WHERE project_id = $1 AND number = $2
+        RETURNING id, project_id, number, author_id, title, body, status,
+                  ARRAY(SELECT l.name FROM issue_labels il JOIN labels l ON l.id = il.label_id
+                        WHERE il.issue_id = issues.id ORDER BY l.name) as "labels!",
+                  assignee_id, created_at, updated_at
+        "#,
+        id,
+        number,
+    )
+    .fetch_optional(&state.pool)
+    .await?
+    .ok_or_else(|| ApiError::NotFound("issue".into()))?;
+
+    send_audit(
+        &state.audit_tx,
+        AuditEntry {
+            actor_id: auth.user_id,
+            actor_name: auth.user_name.clone(),
+            action: "issue.unassign".into(),
+            resource: "issue".into(),
+            resource_id: Some(issue.id),
+            project_id: Some(id),
+            detail: Some(serde_json::json!({"number": number})),
+            ip_addr: auth.ip_addr.clone(),
+        },
+    );
+
+    let reactions =
+        reactions::summary(&state.pool, ReactionTarget::Issue, issue.id, auth.user_id).await?;
+    Ok(Json(IssueResponse {
+        id: issue.id,
+        project_id: issue.project_id,
+        number: issue.number,
+        author_id: issue.author_id,
+        title: issue.title,
+        body: issue.body,
+        status: issue.status,
+        labels: issue.labels,
+        assignee_id: issue.assignee_id,
+        created_at: issue.created_at,
+        updated_at: issue.updated_at,
+        reactions,
+    }))
+}
+
+/// An assignee must be an active user who can see the project.
+async fn check_assignee(state: &AppState, project_id: Uuid, user_id: Uuid) -> Result<(), ApiError> {
+    let row = sqlx::query!(
+        r#"
+        SELECT p.visibility, p.owner_id
+        FROM users u, projects p
+        WHERE u.id = $1 AND u.is_active = true AND p.id = $2 AND p.is_active = true
+        "#,
+        user_id,
+        project_id,
+    )
+    .fetch_optional(&state.pool)
+    .await?
+    .ok_or_else(|| ApiError::BadRequest("assignee not found".into()))?;
+
+    if row.visibility == "public" || row.visibility == "internal" || row.owner_id == user_id {
+        return Ok(());
+    }
+
+    let can_read = crate::rbac::resolver::has_permission(
+        &state.pool,
+        &state.valkey,
+        user_id,
+        Some(project_id),
+        Permission::ProjectRead,
+    )
+    .await
+    .map_err(ApiError::Internal)?;
+
+    if !can_read {
+        return Err(ApiError::BadRequest(
+            "assignee has no access to this project".into(),
+        ));
+    }
+    Ok(())
+}
+
+/// Audit detail recorded when an issue transitions to closed.
+fn close_detail(
+    number: i32,
+    from: &str,
+    auth: &AuthUser,
+    closed_at: DateTime<Utc>,
+) -> serde_json::Value {
+    serde_json::json!({
+        "number": number,
+        "from": from,
+        "closed_by": auth.user_id,
+        "closed_at": closed_at,
+    })
+}
+
 // ---------------------------------------------------------------------------
 // Comment handlers
 // ---------------------------------------------------------------------------
@@ -531,14 +836,21 @@ async fn list_comments(
     .fetch_all(&state.pool)
     .await?;
 
+    let ids: Vec<Uuid> = rows.iter().map(|c| c.get("id")).collect();
+    let mut reactions =
+        reactions::summaries(&state.pool, ReactionTarget::Comment, &ids, auth.user_id).await?;
     let items = rows
         .into_iter()
-        .map(|c| CommentResponse {
-            id: c.get("id"),
-            author_id: c.get("author_id"),
-            body: c.get("body"),
-            created_at: c.get("created_at"),
-            updated_at: c.get("updated_at"),
+        .map(|c| {
+            let comment_id: Uuid = c.get("id");
+            CommentResponse {
+                id: comment_id,
+                author_id: c.get("author_id"),
+                body: c.get("body"),
+                created_at: c.get("created_at"),
+                updated_at: c.get("updated_at"),
+                reactions: reactions.remove(&comment_id).unwrap_or_default(),
+            }
         })
         .collect();
 
@@ -592,6 +904,16 @@ async fn create_comment(
         },
     );
 
+    crate::notify::dispatch::on_issue_commented(
+        &state,
+        id,
+        issue_id,
+        number,
+        auth.user_id,
+        &auth.user_name,
+    )
+    .await;
+
     Ok((
         StatusCode::CREATED,
         Json(CommentResponse {
@@ -600,6 +922,7 @@ async fn create_comment(
             body: comment.body,
             created_at: comment.created_at,
             updated_at: comment.updated_at,
+            reactions: Vec::new(),
         }),
     ))
 }
@@ -676,12 +999,20 @@ async fn update_comment(
         },
     );
 
+    let reactions = reactions::summary(
+        &state.pool,
+        ReactionTarget::Comment,
+        comment.id,
+        auth.user_id,
+    )
+    .await?;
     Ok(Json(CommentResponse {
         id: comment.id,
         author_id: comment.author_id,
         body: comment.body,
         created_at: comment.created_at,
         updated_at: comment.updated_at,
+        reactions,
     }))
 }
 
@@ -712,12 +1043,20 @@ async fn get_comment(
     .await?
     .ok_or_else(|| ApiError::NotFound("comment".into()))?;
 
+    let reactions = reactions::summary(
+        &state.pool,
+        ReactionTarget::Comment,
+        comment_id,
+        auth.user_id,
+    )
+    .await?;
     Ok(Json(CommentResponse {
         id: row.get("id"),
         author_id: row.get("author_id"),
         body: row.get("body"),
         created_at: row.get("created_at"),
         updated_at: row.get("updated_at"),
+        reactions,
     }))
 }
 
@@ -789,3 +1128,32 @@ async fn delete_comment(
 
     Ok(StatusCode::NO_CONTENT)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn issue_transitions_allowed() {
+        for (from, to) in [
+            ("open", "in_progress"),
+            ("open", "closed"),
+            ("in_progress", "open"),
+            ("in_progress", "closed"),
+            ("closed", "open"),
+            ("closed", "closed"),
+        ] {
+            assert!(
+                validate_issue_transition(from, to).is_ok(),
+                "{from} -> {to}"
+            );
+        }
+    }
+
+    #[test]
+    fn issue_transitions_rejected() {
+        assert!(validate_issue_transition("closed", "in_progress").is_err());
+        assert!(validate_issue_transition("open", "done").is_err());
+        assert!(validate_issue_state("merged").is_err());
+    }
+}
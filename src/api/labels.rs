@@ -0,0 +1,385 @@
+// Copyright (c) 2026 Steven Hooker. Exclusively licensed to and distributed by AgentSphere GmbH.
+// SPDX-License-Identifier: BUSL-1.1
+
+use axum::extract::{Path, State};
+use axum::http::StatusCode;
+use axum::response::IntoResponse;
+use axum::routing::{get, patch};
+use axum::{Json, Router};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use ts_rs::TS;
+
+use crate::audit::{AuditEntry, send_audit};
+use crate::auth::middleware::AuthUser;
+use crate::error::ApiError;
+use crate::store::AppState;
+use crate::validation;
+
+use super::helpers::{ListResponse, require_project_read, require_project_write};
+
+/// Color given to labels created implicitly by tagging an issue or MR.
+const DEFAULT_LABEL_COLOR: &str = "#6b7280";
+
+// ---------------------------------------------------------------------------
+// Types
+// ---------------------------------------------------------------------------
+
+#[derive(Debug, Deserialize)]
+pub struct CreateLabelRequest {
+    pub name: String,
+    pub color: Option<String>,
+    pub description: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct UpdateLabelRequest {
+    pub name: Option<String>,
+    pub color: Option<String>,
+    pub description: Option<String>,
+}
+
+#[derive(Debug, Serialize, TS)]
+#[ts(export, rename = "Label")]
+pub struct LabelResponse {
+    pub id: Uuid,
+    pub project_id: Uuid,
+    pub name: String,
+    pub color: String,
+    pub description: Option<String>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+// ---------------------------------------------------------------------------
+// Router
+// ---------------------------------------------------------------------------
+
+pub fn router() -> Router<AppState> {
+    Router::new()
+        .route(
+            "/api/projects/{id}/labels",
+            get(list_labels).post(create_label),
+        )
+        .route(
+            "/api/projects/{id}/labels/{label_id}",
+            patch(update_label).delete(delete_label),
+        )
+}
+
+// ---------------------------------------------------------------------------
+// Handlers
+// ---------------------------------------------------------------------------
+
+async fn list_labels(
+    State(state): State<AppState>,
+    auth: AuthUser,
+    Path(id): Path<Uuid>,
+) -> Result<Json<ListResponse<LabelResponse>>, ApiError> {
+    require_project_read(&state, &auth, id).await?;
+
+    let rows = sqlx::query!(
+        r#"
+        SELECT id, project_id, name, color, description, created_at, updated_at
+        FROM labels
+        WHERE project_id = $1
+        ORDER BY name
+        "#,
+        id,
+    )
+    .fetch_all(&state.pool)
+    .await?;
+
+    let total = i64::try_from(rows.len()).unwrap_or(i64::MAX);
+    let items = rows
+        .into_iter()
+        .map(|r| LabelResponse {
+            id: r.id,
+            project_id: r.project_id,
+            name: r.name,
+            color: r.color,
+            description: r.description,
+            created_at: r.created_at,
+            updated_at: r.updated_at,
+        })
+        .collect();
+
+    Ok(Json(ListResponse { items, total }))
+}
+
+#[tracing::instrument(skip(state, body), fields(%id), err)]
+async fn create_label(
+    State(state): State<AppState>,
+    auth: AuthUser,
+    Path(id): Path<Uuid>,
+    Json(body): Json<CreateLabelRequest>,
+) -> Result<impl IntoResponse, ApiError> {
+    validation::check_labels(std::slice::from_ref(&body.name))?;
+    if let Some(ref c) = body.color {
+        validation::check_color(c)?;
+    }
+    if let Some(ref d) = body.description {
+        validation::check_length("description", d, 0, 1000)?;
+    }
+    require_project_write(&state, &auth, id).await?;
+
+    let color = body.color.as_deref().unwrap_or(DEFAULT_LABEL_COLOR);
+    let row = sqlx::query!(
+        r#"
+        INSERT INTO labels (project_id, name, color, description)
+        VALUES ($1, $2, $3, $4)
+        RETURNING id, project_id, name, color, description, created_at, updated_at
+        "#,
+        id,
+        body.name,
+        color,
+        body.description,
+    )
+    .fetch_one(&state.pool)
+    .await
+    .map_err(|e| match e {
+        sqlx::Error::Database(ref db) if db.is_unique_violation() => {
+            ApiError::Conflict("label with this name already exists".into())
+        }
+        _ => ApiError::from(e),
+    })?;
+
+    send_audit(
+        &state.audit_tx,
+        AuditEntry {
+            actor_id: auth.user_id,
+            actor_name: auth.user_name.clone(),
+            action: "label.create".into(),
+            resource: "label".into(),
+            resource_id: Some(row.id),
+            project_id: Some(id),
+            detail: Some(serde_json::json!({"name": body.name})),
+            ip_addr: auth.ip_addr.clone(),
+        },
+    );
+
+    Ok((
+        StatusCode::CREATED,
+        Json(LabelResponse {
+            id: row.id,
+            project_id: row.project_id,
+            name: row.name,
+            color: row.color,
+            description: row.description,
+            created_at: row.created_at,
+            updated_at: row.updated_at,
+        }),
+    ))
+}
+
+#[tracing::instrument(skip(state, body), fields(%id, %label_id), err)]
+async fn update_label(
+    State(state): State<AppState>,
+    auth: AuthUser,
+    Path((id, label_id)): Path<(Uuid, Uuid)>,
+    Json(body): Json<UpdateLabelRequest>,
+) -> Result<Json<LabelResponse>, ApiError> {
+    if let Some(ref n) = body.name {
+        validation::check_labels(std::slice::from_ref(n))?;
+    }
+    if let Some(ref c) = body.color {
+        validation::check_color(c)?;
+    }
+    if let Some(ref d) = body.description {
+        validation::check_length("description", d, 0, 1000)?;
+    }
+    require_project_write(&state, &auth, id).await?;
+
+    let row = sqlx::query!(
+        r#"
+        UPDATE labels SET
+            name = COALESCE($3, name),
+            color = COALESCE($4, color),
+            description = COALESCE($5, description)
+        WHERE id = $1 AND project_id = $2
+        RETURNING id, project_id, name, color, description, created_at, updated_at
+        "#,
+        label_id,
+        id,
+        body.name,
+        body.color,
+        body.description,
+    )
+    .fetch_optional(&state.pool)
+    .await
+    .map_err(|e| match e {
+        sqlx::Error::Database(ref db) if db.is_unique_violation() => {
+            ApiError::Conflict("label with this name already exists".into())
+        }
+        _ => ApiError::from(e),
+    })?
+    .ok_or_else(|| ApiError::NotFound("label".into()))?;
+
+    send_audit(
+        &state.audit_tx,
+        AuditEntry {
+            actor_id: auth.user_id,
+            actor_name: auth.user_name.clone(),
+            action: "label.update".into(),
+            resource: "label".into(),
+            resource_id: Some(label_id),
+            project_id: Some(id),
+            detail: None,
+            ip_addr: auth.ip_addr.clone(),
+        },
+    );
+
+    Ok(Json(LabelResponse {
+        id: row.id,
+        project_id: row.project_id,
+        name: row.name,
+        color: row.color,
+        description: row.description,
+        created_at: row.created_at,
+        updated_at: row.updated_at,
+    }))
+}
+
+#[tracing::instrument(skip(state), fields(%id, %label_id), err)]
+async fn delete_label(
+    State(state): State<AppState>,
+    auth: AuthUser,
+    Path((id, label_id)): Path<(Uuid, Uuid)>,
+) -> Result<StatusCode, ApiError> {
+    require_project_write(&state, &auth, id).await?;
+
+    // Cascades to issue_labels / mr_labels
+    let result = sqlx::query!(
+        "DELETE FROM labels WHERE id = $1 AND project_id = $2",
+        label_id,
+        id,
+    )
+    .execute(&state.pool)
+    .await?;
+
+    if result.rows_affected() == 0 {
+        return Err(ApiError::NotFound("label".into()));
+    }
+
+    send_audit(
+        &state.audit_tx,
+        AuditEntry {
+            actor_id: auth.user_id,
+            actor_name: auth.user_name.clone(),
+            action: "label.delete".into(),
+            resource: "label".into(),
+            resource_id: Some(label_id),
+            project_id: Some(id),
+            detail: None,
+            ip_addr: auth.ip_addr.clone(),
+        },
+    );
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+// ---------------------------------------------------------------------------
+// Issue / MR label assignment
+// ---------------------------------------------------------------------------
+
+/// Replace the labels on an issue. Unknown names are created in the project
+/// with the default color. Returns the label names, sorted.
+pub(crate) async fn set_issue_labels(
+    pool: &sqlx::PgPool,
+    project_id: Uuid,
+    issue_id: Uuid,
+    names: &[String],
+) -> Result<Vec<String>, ApiError> {
+    let names = normalize(names);
+    let mut tx = pool.begin().await?;
+    let label_ids = ensure_labels(&mut tx, project_id, &names).await?;
+
+    sqlx::query!("DELETE FROM issue_labels WHERE issue_id = $1", issue_id)
+        .execute(&mut *tx)
+        .await?;
+    sqlx::query!(
+        "INSERT INTO issue_labels (issue_id, label_id) SELECT $1, unnest($2::uuid[])",
+        issue_id,
+        &label_ids,
+    )
+    .execute(&mut *tx)
+    .await?;
+
+    tx.commit().await?;
+    Ok(names)
+}
+
+/// Replace the labels on a merge request; see [`set_issue_labels`].
+pub(crate) async fn set_mr_labels(
+    pool: &sqlx::PgPool,
+    project_id: Uuid,
+    mr_id: Uuid,
+    names: &[String],
+) -> Result<Vec<String>, ApiError> {
+    let names = normalize(names);
+    let mut tx = pool.begin().await?;
+    let label_ids = ensure_labels(&mut tx, project_id, &names).await?;
+
+    sqlx::query!("DELETE FROM mr_labels WHERE mr_id = $1", mr_id)
+        .execute(&mut *tx)
+        .await?;
+    sqlx::query!(
+        "INSERT INTO mr_labels (mr_id, label_id) SELECT $1, unnest($2::uuid[])",
+        mr_id,
+        &label_ids,
+    )
+    .execute(&mut *tx)
+    .await?;
+
+    tx.commit().await?;
+    Ok(names)
+}
+
+/// Look up (creating if missing) the project labels with the given names.
+async fn ensure_labels(
+    tx: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+    project_id: Uuid,
+    names: &[String],
+) -> Result<Vec<Uuid>, ApiError> {
+    sqlx::query!(
+        r#"
+        INSERT INTO labels (project_id, name, color)
+        SELECT $1, unnest($2::text[]), $3
+        ON CONFLICT (project_id, name) DO NOTHING
+        "#,
+        project_id,
+        names,
+        DEFAULT_LABEL_COLOR,
+    )
+    .execute(&mut **tx)
+    .await?;
+
+    let ids = sqlx::query_scalar!(
+        "SELECT id FROM labels WHERE project_id = $1 AND name = ANY($2)",
+        project_id,
+        names,
+    )
+    .fetch_all(&mut **tx)
+    .await?;
+    Ok(ids)
+}
+
+fn normalize(names: &[String]) -> Vec<String> {
+    let mut names = names.to_vec();
+    names.sort();
+    names.dedup();
+    names
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn normalize_sorts_and_dedups() {
+        let names = vec!["bug".to_string(), "api".into(), "bug".into()];
+        assert_eq!(normalize(&names), vec!["api", "bug"]);
+    }
+}
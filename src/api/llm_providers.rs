@@ -69,7 +69,7 @@ pub struct ActiveProviderResponse {
 // Helpers
 // ---------------------------------------------------------------------------
 
-fn get_master_key(state: &AppState) -> Result<[u8; 32], ApiError> {
+fn get_master_key(state: &AppState) -> Result<engine::MasterKey, ApiError> {
     let hex_str = state
         .config
         .master_key
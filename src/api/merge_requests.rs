@@ -34,6 +34,7 @@ pub struct CreateMrRequest {
     pub body: Option<String>,
     #[serde(default)]
     pub auto_merge: bool,
+    pub labels: Option<Vec<String>>,
 }
 
 #[derive(Debug, Deserialize)]
@@ -41,6 +42,7 @@ pub struct UpdateMrRequest {
     pub title: Option<String>,
     pub body: Option<String>,
     pub status: Option<String>,
+    pub labels: Option<Vec<String>>,
 }
 
 #[derive(Debug, Deserialize)]
@@ -49,6 +51,7 @@ pub struct ListMrParams {
     pub offset: Option<i64>,
     pub status: Option<String>,
     pub author_id: Option<Uuid>,
+    pub label: Option<String>,
 }
 
 #[derive(Debug, Deserialize)]
@@ -66,6 +69,8 @@ pub struct CreateReviewRequest {
 #[derive(Debug, Deserialize)]
 pub struct MergeMrRequest {
     pub merge_method: Option<String>,
+    /// Commit message for squash merges. Defaults to the MR title and body.
+    pub commit_message: Option<String>,
 }
 
 #[derive(Debug, Deserialize)]
@@ -76,6 +81,11 @@ pub struct AutoMergeRequest {
 #[derive(Debug, Deserialize)]
 pub struct CreateCommentRequest {
     pub body: String,
+    /// Diff anchor: file path and line on the new side of the diff.
+    pub file_path: Option<String>,
+    pub line: Option<i32>,
+    /// Commit the anchor refers to. Defaults to the MR's current head.
+    pub commit_sha: Option<String>,
 }
 
 #[derive(Debug, Deserialize)]
@@ -97,8 +107,10 @@ pub struct MrResponse {
     pub status: String,
     pub merged_by: Option<Uuid>,
     pub merged_at: Option<DateTime<Utc>>,
+    pub labels: Vec<String>,
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
+    pub reactions: Vec<ReactionCount>,
 }
 
 #[derive(Debug, Serialize, TS)]
@@ -118,11 +130,95 @@ pub struct CommentResponse {
     pub id: Uuid,
     pub author_id: Uuid,
     pub body: String,
+    pub file_path: Option<String>,
+    pub line: Option<i32>,
+    pub commit_sha: Option<String>,
+    pub outdated: bool,
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
+    pub reactions: Vec<ReactionCount>,
 }
 
-use super::helpers::{ListResponse, require_project_read, require_project_write};
+use super::helpers::{
+    ListResponse, require_not_archived, require_project_read, require_project_write,
+};
+use super::reactions::{self, ReactionCount, ReactionTarget};
+
+#[derive(Debug, Serialize, Deserialize, TS)]
+#[ts(export)]
+pub struct MergeabilityResponse {
+    pub can_merge: bool,
+    pub conflicts: Vec<String>,
+    pub behind_target: bool,
+    #[ts(type = "number")]
+    pub behind_by: i64,
+    pub source_sha: String,
+    pub target_sha: String,
+}
+
+/// Supported merge strategies for `merge_method`.
+const MERGE_METHODS: &[&str] = &["merge", "squash", "rebase"];
+
+fn validate_merge_method(method: &str) -> Result<(), ApiError> {
+    if MERGE_METHODS.contains(&method) {
+        Ok(())
+    } else {
+        Err(ApiError::BadRequest(format!(
+            "merge_method must be one of: {}",
+            MERGE_METHODS.join(", ")
+        )))
+    }
+}
+
+/// Author and message for the commit a squash merge creates on target.
+struct SquashCommit {
+    author_name: String,
+    author_email: String,
+    message: String,
+}
+
+/// Default squash commit message: MR title, blank line, MR body (if any).
+fn default_squash_message(title: &str, body: Option<&str>) -> String {
+    match body.map(str::trim) {
+        Some(b) if !b.is_empty() => format!("{title}\n\n{b}"),
+        _ => title.to_owned(),
+    }
+}
+
+/// Validate the optional diff anchor on a new comment: path and line must be
+/// given together, and the commit (if given) must look like a commit SHA.
+fn validate_comment_anchor(body: &CreateCommentRequest) -> Result<(), ApiError> {
+    match (&body.file_path, body.line) {
+        (None, None) => {
+            if body.commit_sha.is_some() {
+                return Err(ApiError::BadRequest(
+                    "commit_sha requires file_path and line".into(),
+                ));
+            }
+            return Ok(());
+        }
+        (Some(path), Some(line)) => {
+            validation::check_length("file_path", path, 1, 4096)?;
+            if path.contains("..") || path.contains('\0') || path.starts_with('/') {
+                return Err(ApiError::BadRequest("invalid file_path".into()));
+            }
+            if line < 1 {
+                return Err(ApiError::BadRequest("line must be >= 1".into()));
+            }
+        }
+        _ => {
+            return Err(ApiError::BadRequest(
+                "file_path and line must be given together".into(),
+            ));
+        }
+    }
+    if let Some(ref sha) = body.commit_sha
+        && !crate::git::signature::validate_commit_sha(sha)
+    {
+        return Err(ApiError::BadRequest("invalid commit_sha".into()));
+    }
+    Ok(())
+}
 
 // ---------------------------------------------------------------------------
 // Router
@@ -142,6 +238,10 @@ pub fn router() -> Router<AppState> {
             "/api/projects/{id}/merge-requests/{number}/merge",
             post(merge_mr),
         )
+        .route(
+            "/api/projects/{id}/merge-requests/{number}/mergeability",
+            get(get_mergeability),
+        )
         .route(
             "/api/projects/{id}/merge-requests/{number}/auto-merge",
             axum::routing::put(enable_auto_merge).delete(disable_auto_merge),
@@ -195,6 +295,9 @@ async fn create_mr(
     }
     validation::check_branch_name(&body.source_branch)?;
     validation::check_branch_name(&body.target_branch)?;
+    if let Some(ref labels) = body.labels {
+        validation::check_labels(labels)?;
+    }
 
     require_project_write(&state, &auth, id).await?;
 
@@ -248,6 +351,13 @@ async fn create_mr(
     .fetch_one(&state.pool)
     .await?;
 
+    let labels = match body.labels {
+        Some(ref labels) => {
+            crate::api::labels::set_mr_labels(&state.pool, id, mr.id, labels).await?
+        }
+        None => Vec::new(),
+    };
+
     run_mr_create_side_effects(&state, &auth, id, &mr.id, number, &body, &repo_path).await;
 
     // Enable auto-merge if requested in the create body
@@ -282,8 +392,10 @@ async fn create_mr(
             status: mr.status,
             merged_by: mr.merged_by,
             merged_at: mr.merged_at,
+            labels,
             created_at: mr.created_at,
             updated_at: mr.updated_at,
+            reactions: Vec::new(),
         }),
     ))
 }
@@ -306,10 +418,14 @@ async fn list_mrs(
         WHERE project_id = $1
           AND ($2::text IS NULL OR status = $2)
           AND ($3::uuid IS NULL OR author_id = $3)
+          AND ($4::text IS NULL OR EXISTS (
+              SELECT 1 FROM mr_labels ml JOIN labels l ON l.id = ml.label_id
+              WHERE ml.mr_id = merge_requests.id AND l.name = $4))
         "#,
         id,
         params.status,
         params.author_id,
+        params.label,
     )
     .fetch_one(&state.pool)
     .await?;
@@ -317,23 +433,38 @@ async fn list_mrs(
     let rows = sqlx::query!(
         r#"
         SELECT id, project_id, number, author_id, source_branch, target_branch, title, body,
-               status, merged_by, merged_at, created_at, updated_at
+               status, merged_by, merged_at,
+               ARRAY(SELECT l.name FROM mr_labels ml JOIN labels l ON l.id = ml.label_id
+                     WHERE ml.mr_id = merge_requests.id ORDER BY l.name) as "labels!",
+               created_at, updated_at
         FROM merge_requests
         WHERE project_id = $1
           AND ($2::text IS NULL OR status = $2)
           AND ($3::uuid IS NULL OR author_id = $3)
+          AND ($4::text IS NULL OR EXISTS (
+              SELECT 1 FROM mr_labels ml JOIN labels l ON l.id = ml.label_id
+              WHERE ml.mr_id = merge_requests.id AND l.name = $4))
         ORDER BY number DESC
-        LIMIT $4 OFFSET $5
+        LIMIT $5 OFFSET $6
         "#,
         id,
         params.status,
         params.author_id,
+        params.label,
         limit,
         offset,
     )
     .fetch_all(&state.pool)
     .await?;
 
+    let ids: Vec<Uuid> = rows.iter().map(|m| m.id).collect();
+    let mut reactions = reactions::summaries(
+        &state.pool,
+        ReactionTarget::MergeRequest,
+        &ids,
+        auth.user_id,
+    )
+    .await?;
     let items = rows
         .into_iter()
         .map(|m| MrResponse {
@@ -348,8 +479,10 @@ async fn list_mrs(
             status: m.status,
             merged_by: m.merged_by,
             merged_at: m.merged_at,
+            labels: m.labels,
             created_at: m.created_at,
             updated_at: m.updated_at,
+            reactions: reactions.remove(&m.id).unwrap_or_default(),
         })
         .collect();
 
@@ -366,7 +499,10 @@ async fn get_mr(
     let mr = sqlx::query!(
         r#"
         SELECT id, project_id, number, author_id, source_branch, target_branch, title, body,
-               status, merged_by, merged_at, created_at, updated_at
+               status, merged_by, merged_at,
+               ARRAY(SELECT l.name FROM mr_labels ml JOIN labels l ON l.id = ml.label_id
+                     WHERE ml.mr_id = merge_requests.id ORDER BY l.name) as "labels!",
+               created_at, updated_at
         FROM merge_requests WHERE project_id = $1 AND number = $2
         "#,
         id,
@@ -376,6 +512,13 @@ async fn get_mr(
     .await?
     .ok_or_else(|| ApiError::NotFound("merge request".into()))?;
 
+    let reactions = reactions::summary(
+        &state.pool,
+        ReactionTarget::MergeRequest,
+        mr.id,
+        auth.user_id,
+    )
+    .await?;
     Ok(Json(MrResponse {
         id: mr.id,
         project_id: mr.project_id,
@@ -388,8 +531,10 @@ async fn get_mr(
         status: mr.status,
         merged_by: mr.merged_by,
         merged_at: mr.merged_at,
+        labels: mr.labels,
         created_at: mr.created_at,
         updated_at: mr.updated_at,
+        reactions,
     }))
 }
 
@@ -407,13 +552,16 @@ async fn update_mr(
     if let Some(ref b) = body.body {
         validation::check_length("body", b, 0, 100_000)?;
     }
+    if let Some(ref labels) = body.labels {
+        validation::check_labels(labels)?;
+    }
 
     // A2: Even the author needs current project-write permission (may have been revoked)
     require_project_write(&state, &auth, id).await?;
 
     // Verify MR exists
-    let _mr_exists = sqlx::query_scalar!(
-        "SELECT author_id FROM merge_requests WHERE project_id = $1 AND number = $2",
+    let mr_id = sqlx::query_scalar!(
+        "SELECT id FROM merge_requests WHERE project_id = $1 AND number = $2",
         id,
         number,
     )
@@ -430,6 +578,10 @@ async fn update_mr(
         ));
     }
 
+    if let Some(ref labels) = body.labels {
+        crate::api::labels::set_mr_labels(&state.pool, id, mr_id, labels).await?;
+    }
+
     let mr = sqlx::query!(
         r#"
         UPDATE merge_requests SET
@@ -438,7 +590,10 @@ async fn update_mr(
             status = COALESCE($5, status)
         WHERE project_id = $1 AND number = $2
         RETURNING id, project_id, number, author_id, source_branch, target_branch, title, body,
-                  status, merged_by, merged_at, created_at, updated_at
+                  status, merged_by, merged_at,
+                  ARRAY(SELECT l.name FROM mr_labels ml JOIN labels l ON l.id = ml.label_id
+                        WHERE ml.mr_id = merge_requests.id ORDER BY l.name) as "labels!",
+                  created_at, updated_at
         "#,
         id,
         number,
@@ -464,6 +619,13 @@ async fn update_mr(
         },
     );
 
+    let reactions = reactions::summary(
+        &state.pool,
+        ReactionTarget::MergeRequest,
+        mr.id,
+        auth.user_id,
+    )
+    .await?;
     Ok(Json(MrResponse {
         id: mr.id,
         project_id: mr.project_id,
@@ -476,8 +638,10 @@ async fn update_mr(
         status: mr.status,
         merged_by: mr.merged_by,
         merged_at: mr.merged_at,
+        labels: mr.labels,
         created_at: mr.created_at,
         updated_at: mr.updated_at,
+        reactions,
     }))
 }
 
@@ -559,8 +723,85 @@ async fn merge_mr(
         .as_ref()
         .and_then(|b| b.merge_method.as_deref())
         .unwrap_or("merge");
+    validate_merge_method(merge_method)?;
+
+    let commit_message = body.as_ref().and_then(|b| b.commit_message.as_deref());
+    if let Some(msg) = commit_message {
+        validation::check_length("commit_message", msg, 1, 100_000)?;
+    }
+
+    do_merge(&state, &auth, id, number, merge_method, commit_message).await
+}
 
-    do_merge(&state, &auth, id, number, merge_method).await
+/// Mergeability results are cached per (source, target) head pair, so a push to
+/// either branch naturally invalidates the cached entry.
+const MERGEABILITY_CACHE_TTL: i64 = 3600;
+
+/// `GET /api/projects/:id/merge-requests/:number/mergeability`
+///
+/// Dry-run merge of source into target in a throwaway worktree.
+#[tracing::instrument(skip(state), fields(%id, %number), err)]
+async fn get_mergeability(
+    State(state): State<AppState>,
+    auth: AuthUser,
+    Path((id, number)): Path<(Uuid, i32)>,
+) -> Result<Json<MergeabilityResponse>, ApiError> {
+    use fred::interfaces::KeysInterface;
+
+    require_project_read(&state, &auth, id).await?;
+
+    let mr = sqlx::query!(
+        "SELECT source_branch, target_branch FROM merge_requests WHERE project_id = $1 AND number = $2",
+        id,
+        number,
+    )
+    .fetch_optional(&state.pool)
+    .await?
+    .ok_or_else(|| ApiError::NotFound("merge request".into()))?;
+
+    let repo_path = PathBuf::from(get_project_repo_path(&state.pool, id).await?);
+    let source_sha = get_branch_head_sha(&repo_path, &mr.source_branch)
+        .await
+        .ok_or_else(|| ApiError::NotFound("source branch".into()))?;
+    let target_sha = get_branch_head_sha(&repo_path, &mr.target_branch)
+        .await
+        .ok_or_else(|| ApiError::NotFound("target branch".into()))?;
+
+    let cache_key = format!("mr:mergeability:{id}:{number}:{source_sha}:{target_sha}");
+    if let Ok(Some(cached)) = state.valkey.get::<Option<String>, _>(&cache_key).await
+        && let Ok(resp) = serde_json::from_str::<MergeabilityResponse>(&cached)
+    {
+        return Ok(Json(resp));
+    }
+
+    let conflicts = git_dry_run_merge(&repo_path, &source_sha, &target_sha)
+        .await
+        .map_err(ApiError::Internal)?;
+    let behind_by = count_commits_behind(&repo_path, &source_sha, &target_sha).await;
+
+    let resp = MergeabilityResponse {
+        can_merge: conflicts.is_empty(),
+        conflicts,
+        behind_target: behind_by > 0,
+        behind_by,
+        source_sha,
+        target_sha,
+    };
+
+    if let Ok(json) = serde_json::to_string(&resp) {
+        let _: Result<(), _> = state
+            .valkey
+            .set(
+                &cache_key,
+                json,
+                Some(fred::types::Expiration::EX(MERGEABILITY_CACHE_TTL)),
+                None,
+                false,
+            )
+            .await;
+    }
+
+    Ok(Json(resp))
 }
 
 /// Check branch protection rules before allowing a merge.
@@ -627,34 +868,48 @@ async fn enforce_merge_gates(
         }
     }
 
-    // Check CI pipeline status
+    // Check required status checks on the source head
     if !rule.required_checks.is_empty() {
-        let latest_pipeline = sqlx::query!(
-            r#"
-            SELECT status FROM pipelines
-            WHERE project_id = $1 AND git_ref = $2 AND trigger IN ('push', 'mr')
-            ORDER BY created_at DESC LIMIT 1
-            "#,
-            project_id,
-            format!("refs/heads/{source_branch}"),
-        )
-        .fetch_optional(&state.pool)
-        .await?;
+        let repo_path = get_project_repo_path(&state.pool, project_id).await?;
+        let head_sha = get_branch_head_sha(&PathBuf::from(&repo_path), source_branch)
+            .await
+            .ok_or_else(|| {
+                ApiError::BadRequest(format!(
+                    "required status checks have not passed; source branch '{source_branch}' not found"
+                ))
+            })?;
+        let reported =
+            super::commit_statuses::states_for_commit(&state.pool, project_id, &head_sha).await?;
+
+        // Required contexts that external CI has not reported fall back to the
+        // latest platform pipeline for the source head; a pipeline for an
+        // earlier head does not count
+        let pipeline_status: Option<String> = if rule
+            .required_checks
+            .iter()
+            .all(|c| reported.contains_key(c))
+        {
+            None
+        } else {
+            sqlx::query_scalar!(
+                "SELECT status FROM pipelines
+                 WHERE project_id = $1 AND git_ref = $2 AND commit_sha = $3
+                   AND trigger IN ('push', 'mr')
+                 ORDER BY created_at DESC LIMIT 1",
+                project_id,
+                format!("refs/heads/{source_branch}"),
+                head_sha,
+            )
+            .fetch_optional(&state.pool)
+            .await?
+        };
 
-        match latest_pipeline {
-            Some(ref p) if p.status == "success" => {}
-            Some(ref p) => {
-                return Err(ApiError::BadRequest(format!(
-                    "CI pipeline status is '{}', must be 'success'",
-                    p.status
-                )));
-            }
-            None => {
-                return Err(ApiError::BadRequest(
-                    "no CI pipeline found for source branch".into(),
-                ));
-            }
-        }
+        super::commit_statuses::evaluate_required_checks(
+            &rule.required_checks,
+            &reported,
+            pipeline_status.as_deref(),
+        )
+        .map_err(ApiError::BadRequest)?;
     }
 
     // Check require_up_to_date
@@ -673,12 +928,14 @@ async fn enforce_merge_gates(
 }
 
 /// Core merge logic shared by manual merge and auto-merge.
+#[allow(clippy::too_many_lines)]
 async fn do_merge(
     state: &AppState,
     auth: &AuthUser,
     project_id: Uuid,
     number: i32,
     merge_method: &str,
+    commit_message: Option<&str>,
 ) -> Result<Json<MrResponse>, ApiError> {
     let allowed = crate::rbac::resolver::has_permission_scoped(
         &state.pool,
@@ -694,11 +951,15 @@ async fn do_merge(
     if !allowed {
         return Err(ApiError::Forbidden);
     }
+    require_not_archived(state, project_id).await?;
 
     let mr = sqlx::query!(
         r#"
-        SELECT id, source_branch, target_branch, status, head_sha
-        FROM merge_requests WHERE project_id = $1 AND number = $2
+        SELECT mr.id, mr.source_branch, mr.target_branch, mr.status, mr.head_sha,
+               mr.title, mr.body, u.name AS author_name, u.email AS author_email
+        FROM merge_requests mr
+        JOIN users u ON u.id = mr.author_id
+        WHERE mr.project_id = $1 AND mr.number = $2
         "#,
         project_id,
         number,
@@ -729,12 +990,21 @@ async fn do_merge(
     let repo_path = get_project_repo_path(&state.pool, project_id).await?;
     let repo_path_buf = PathBuf::from(&repo_path);
 
+    let squash = SquashCommit {
+        author_name: mr.author_name,
+        author_email: mr.author_email,
+        message: commit_message.map_or_else(
+            || default_squash_message(&mr.title, mr.body.as_deref()),
+            str::to_owned,
+        ),
+    };
+
     execute_git_merge(
         &repo_path_buf,
         &mr.source_branch,
         &mr.target_branch,
-        &mr.id.to_string(),
         merge_method,
+        &squash,
     )
     .await?;
 
@@ -745,7 +1015,10 @@ async fn do_merge(
         SET status = 'merged', merged_by = $3, merged_at = $4
         WHERE project_id = $1 AND number = $2
         RETURNING id, project_id, number, author_id, source_branch, target_branch, title, body,
-                  status, merged_by, merged_at, created_at, updated_at
+                  status, merged_by, merged_at,
+                  ARRAY(SELECT l.name FROM mr_labels ml JOIN labels l ON l.id = ml.label_id
+                        WHERE ml.mr_id = merge_requests.id ORDER BY l.name) as "labels!",
+                  created_at, updated_at
         "#,
         project_id,
         number,
@@ -769,6 +1042,13 @@ async fn do_merge(
     )
     .await;
 
+    let reactions = reactions::summary(
+        &state.pool,
+        ReactionTarget::MergeRequest,
+        merged.id,
+        auth.user_id,
+    )
+    .await?;
     Ok(Json(MrResponse {
         id: merged.id,
         project_id: merged.project_id,
@@ -781,8 +1061,10 @@ async fn do_merge(
         status: merged.status,
         merged_by: merged.merged_by,
         merged_at: merged.merged_at,
+        labels: merged.labels,
         created_at: merged.created_at,
         updated_at: merged.updated_at,
+        reactions,
     }))
 }
 
@@ -991,7 +1273,8 @@ async fn list_comments(
         .unwrap_or(0);
 
     let rows = sqlx::query(
-        "SELECT id, author_id, body, created_at, updated_at \
+        "SELECT id, author_id, body, file_path, line_number, commit_sha, outdated, \
+                created_at, updated_at \
          FROM comments WHERE mr_id = $1 \
          ORDER BY created_at ASC \
          LIMIT $2 OFFSET $3",
@@ -1002,14 +1285,25 @@ async fn list_comments(
     .fetch_all(&state.pool)
     .await?;
 
+    let ids: Vec<Uuid> = rows.iter().map(|c| c.get("id")).collect();
+    let mut reactions =
+        reactions::summaries(&state.pool, ReactionTarget::Comment, &ids, auth.user_id).await?;
     let items = rows
         .into_iter()
-        .map(|c| CommentResponse {
-            id: c.get("id"),
-            author_id: c.get("author_id"),
-            body: c.get("body"),
-            created_at: c.get("created_at"),
-            updated_at: c.get("updated_at"),
+        .map(|c| {
+            let comment_id: Uuid = c.get("id");
+            CommentResponse {
+                id: comment_id,
+                author_id: c.get("author_id"),
+                body: c.get("body"),
+                file_path: c.get("file_path"),
+                line: c.get("line_number"),
+                commit_sha: c.get("commit_sha"),
+                outdated: c.get("outdated"),
+                created_at: c.get("created_at"),
+                updated_at: c.get("updated_at"),
+                reactions: reactions.remove(&comment_id).unwrap_or_default(),
+            }
         })
         .collect();
 
@@ -1025,26 +1319,44 @@ async fn create_comment(
 ) -> Result<impl IntoResponse, ApiError> {
     require_project_read(&state, &auth, id).await?;
     validation::check_length("body", &body.body, 1, 100_000)?;
+    validate_comment_anchor(&body)?;
 
-    let mr_id = sqlx::query_scalar!(
-        "SELECT id FROM merge_requests WHERE project_id = $1 AND number = $2",
+    let mr = sqlx::query!(
+        "SELECT id, head_sha FROM merge_requests WHERE project_id = $1 AND number = $2",
         id,
         number,
     )
     .fetch_optional(&state.pool)
     .await?
     .ok_or_else(|| ApiError::NotFound("merge request".into()))?;
+    let mr_id = mr.id;
+
+    // Anchored comments pin to the given commit, or the MR head at comment time
+    let commit_sha = if body.file_path.is_some() {
+        Some(
+            body.commit_sha
+                .clone()
+                .or(mr.head_sha)
+                .ok_or_else(|| ApiError::BadRequest("commit_sha is required".into()))?,
+        )
+    } else {
+        None
+    };
 
     let comment = sqlx::query!(
         r#"
-        INSERT INTO comments (project_id, mr_id, author_id, body)
-        VALUES ($1, $2, $3, $4)
-        RETURNING id, author_id, body, created_at, updated_at
+        INSERT INTO comments (project_id, mr_id, author_id, body, file_path, line_number, commit_sha)
+        VALUES ($1, $2, $3, $4, $5, $6, $7)
+        RETURNING id, author_id, body, file_path, line_number, commit_sha, outdated,
+                  created_at, updated_at
         "#,
         id,
         mr_id,
         auth.user_id,
         body.body,
+        body.file_path,
+        body.line,
+        commit_sha,
     )
     .fetch_one(&state.pool)
     .await?;
@@ -1069,8 +1381,13 @@ async fn create_comment(
             id: comment.id,
             author_id: comment.author_id,
             body: comment.body,
+            file_path: comment.file_path,
+            line: comment.line_number,
+            commit_sha: comment.commit_sha,
+            outdated: comment.outdated,
             created_at: comment.created_at,
             updated_at: comment.updated_at,
+            reactions: Vec::new(),
         }),
     ))
 }
@@ -1124,7 +1441,8 @@ async fn update_comment(
         r#"
         UPDATE comments SET body = $2
         WHERE id = $1
-        RETURNING id, author_id, body, created_at, updated_at
+        RETURNING id, author_id, body, file_path, line_number, commit_sha, outdated,
+                  created_at, updated_at
         "#,
         comment_id,
         body.body,
@@ -1146,12 +1464,24 @@ async fn update_comment(
         },
     );
 
+    let reactions = reactions::summary(
+        &state.pool,
+        ReactionTarget::Comment,
+        comment.id,
+        auth.user_id,
+    )
+    .await?;
     Ok(Json(CommentResponse {
         id: comment.id,
         author_id: comment.author_id,
         body: comment.body,
+        file_path: comment.file_path,
+        line: comment.line_number,
+        commit_sha: comment.commit_sha,
+        outdated: comment.outdated,
         created_at: comment.created_at,
         updated_at: comment.updated_at,
+        reactions,
     }))
 }
 
@@ -1173,7 +1503,8 @@ async fn get_comment(
             .get("id");
 
     let row = sqlx::query(
-        "SELECT id, author_id, body, created_at, updated_at \
+        "SELECT id, author_id, body, file_path, line_number, commit_sha, outdated, \
+                created_at, updated_at \
          FROM comments WHERE id = $1 AND project_id = $2",
     )
     .bind(comment_id)
@@ -1182,12 +1513,24 @@ async fn get_comment(
     .await?
     .ok_or_else(|| ApiError::NotFound("comment".into()))?;
 
+    let reactions = reactions::summary(
+        &state.pool,
+        ReactionTarget::Comment,
+        comment_id,
+        auth.user_id,
+    )
+    .await?;
     Ok(Json(CommentResponse {
         id: row.get("id"),
         author_id: row.get("author_id"),
         body: row.get("body"),
+        file_path: row.get("file_path"),
+        line: row.get("line_number"),
+        commit_sha: row.get("commit_sha"),
+        outdated: row.get("outdated"),
         created_at: row.get("created_at"),
         updated_at: row.get("updated_at"),
+        reactions,
     }))
 }
 
@@ -1282,11 +1625,11 @@ async fn execute_git_merge(
     repo_path: &std::path::Path,
     source_branch: &str,
     target_branch: &str,
-    mr_id: &str,
     merge_method: &str,
+    squash: &SquashCommit,
 ) -> Result<(), ApiError> {
     match merge_method {
-        "squash" => git_squash_merge(repo_path, source_branch, target_branch, mr_id)
+        "squash" => git_squash_merge(repo_path, source_branch, target_branch, squash)
             .await
             .map_err(|e| {
                 tracing::warn!(error = %e, "squash merge failed");
@@ -1374,12 +1717,13 @@ async fn git_merge_no_ff(
     Ok(())
 }
 
-/// Squash merge: squash all source commits into a single commit on target.
+/// Squash merge: squash all source commits into a single commit on target,
+/// authored by the MR author.
 async fn git_squash_merge(
     repo_path: &std::path::Path,
     source_branch: &str,
     target_branch: &str,
-    mr_id: &str,
+    squash: &SquashCommit,
 ) -> anyhow::Result<()> {
     let worktree_dir = repo_path.join(format!("_squash_worktree_{}", uuid::Uuid::new_v4()));
 
@@ -1415,15 +1759,14 @@ async fn git_squash_merge(
         tokio::process::Command::new("git")
             .arg("-C")
             .arg(&worktree_dir)
-            .env("GIT_AUTHOR_NAME", "Platform")
-            .env("GIT_AUTHOR_EMAIL", "platform@localhost")
+            .env("GIT_AUTHOR_NAME", &squash.author_name)
+            .env("GIT_AUTHOR_EMAIL", &squash.author_email)
             .env("GIT_COMMITTER_NAME", "Platform")
             .env("GIT_COMMITTER_EMAIL", "platform@localhost")
             .arg("commit")
+            .arg("--cleanup=verbatim")
             .arg("-m")
-            .arg(format!(
-                "Squash merge branch '{source_branch}' into {target_branch} (MR {mr_id})"
-            ))
+            .arg(&squash.message)
             .output()
             .await?
     } else {
@@ -1440,12 +1783,17 @@ async fn git_squash_merge(
     Ok(())
 }
 
-/// Rebase merge: rebase source commits onto target.
+/// Rebase merge: replay source commits onto target in a detached worktree,
+/// then fast-forward target to the rebased head.
 async fn git_rebase_merge(
     repo_path: &std::path::Path,
     source_branch: &str,
     target_branch: &str,
 ) -> anyhow::Result<()> {
+    let target_sha = get_branch_head_sha(repo_path, target_branch)
+        .await
+        .ok_or_else(|| anyhow::anyhow!("target branch '{target_branch}' not found"))?;
+
     let worktree_dir = repo_path.join(format!("_rebase_worktree_{}", uuid::Uuid::new_v4()));
 
     let output = tokio::process::Command::new("git")
@@ -1453,8 +1801,9 @@ async fn git_rebase_merge(
         .arg(repo_path)
         .arg("worktree")
         .arg("add")
+        .arg("--detach")
         .arg(&worktree_dir)
-        .arg(target_branch)
+        .arg(format!("refs/heads/{source_branch}"))
         .output()
         .await?;
 
@@ -1463,25 +1812,60 @@ async fn git_rebase_merge(
         anyhow::bail!("failed to create worktree: {stderr}");
     }
 
-    // Get the source branch commits and cherry-pick/rebase onto target
+    // Original authors are preserved; only the committer is rewritten.
     let rebase_output = tokio::process::Command::new("git")
         .arg("-C")
         .arg(&worktree_dir)
-        .env("GIT_AUTHOR_NAME", "Platform")
-        .env("GIT_AUTHOR_EMAIL", "platform@localhost")
         .env("GIT_COMMITTER_NAME", "Platform")
         .env("GIT_COMMITTER_EMAIL", "platform@localhost")
-        .arg("merge")
-        .arg("--ff-only")
-        .arg(source_branch)
+        .arg("rebase")
+        .arg(&target_sha)
         .output()
         .await?;
 
+    let rebased_head = if rebase_output.status.success() {
+        tokio::process::Command::new("git")
+            .arg("-C")
+            .arg(&worktree_dir)
+            .arg("rev-parse")
+            .arg("HEAD")
+            .output()
+            .await
+            .ok()
+            .filter(|o| o.status.success())
+            .map(|o| String::from_utf8_lossy(&o.stdout).trim().to_owned())
+    } else {
+        let _ = tokio::process::Command::new("git")
+            .arg("-C")
+            .arg(&worktree_dir)
+            .arg("rebase")
+            .arg("--abort")
+            .output()
+            .await;
+        None
+    };
+
     cleanup_worktree(repo_path, &worktree_dir).await;
 
-    if !rebase_output.status.success() {
+    let Some(new_head) = rebased_head else {
         let stderr = String::from_utf8_lossy(&rebase_output.stderr);
-        anyhow::bail!("rebase merge failed (source not rebased on target): {stderr}");
+        anyhow::bail!("rebase merge failed: {stderr}");
+    };
+
+    // Fast-forward target; the old-value guard rejects a concurrent push to target.
+    let update_output = tokio::process::Command::new("git")
+        .arg("-C")
+        .arg(repo_path)
+        .arg("update-ref")
+        .arg(format!("refs/heads/{target_branch}"))
+        .arg(&new_head)
+        .arg(&target_sha)
+        .output()
+        .await?;
+
+    if !update_output.status.success() {
+        let stderr = String::from_utf8_lossy(&update_output.stderr);
+        anyhow::bail!("fast-forward of target failed: {stderr}");
     }
 
     Ok(())
@@ -1500,6 +1884,104 @@ async fn cleanup_worktree(repo_path: &std::path::Path, worktree_dir: &std::path:
     let _ = tokio::fs::remove_dir_all(worktree_dir).await;
 }
 
+/// Attempt a merge of `source_sha` into `target_sha` in a detached temporary
+/// worktree without committing. Returns the conflicting paths (empty = clean).
+async fn git_dry_run_merge(
+    repo_path: &std::path::Path,
+    source_sha: &str,
+    target_sha: &str,
+) -> anyhow::Result<Vec<String>> {
+    let worktree_dir = repo_path.join(format!("_dryrun_worktree_{}", uuid::Uuid::new_v4()));
+
+    let output = tokio::process::Command::new("git")
+        .arg("-C")
+        .arg(repo_path)
+        .arg("worktree")
+        .arg("add")
+        .arg("--detach")
+        .arg(&worktree_dir)
+        .arg(target_sha)
+        .output()
+        .await?;
+
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        anyhow::bail!("failed to create worktree: {stderr}");
+    }
+
+    let merge_output = tokio::process::Command::new("git")
+        .arg("-C")
+        .arg(&worktree_dir)
+        .env("GIT_AUTHOR_NAME", "Platform")
+        .env("GIT_AUTHOR_EMAIL", "platform@localhost")
+        .env("GIT_COMMITTER_NAME", "Platform")
+        .env("GIT_COMMITTER_EMAIL", "platform@localhost")
+        .arg("merge")
+        .arg("--no-commit")
+        .arg("--no-ff")
+        .arg(source_sha)
+        .output()
+        .await;
+
+    let conflicts = match merge_output {
+        Ok(o) if o.status.success() => Ok(Vec::new()),
+        Ok(_) => list_unmerged_paths(&worktree_dir).await,
+        Err(e) => Err(e.into()),
+    };
+
+    cleanup_worktree(repo_path, &worktree_dir).await;
+    conflicts
+}
+
+/// List paths left unmerged in a worktree after a failed merge.
+async fn list_unmerged_paths(worktree_dir: &std::path::Path) -> anyhow::Result<Vec<String>> {
+    let output = tokio::process::Command::new("git")
+        .arg("-C")
+        .arg(worktree_dir)
+        .arg("diff")
+        .arg("--name-only")
+        .arg("--diff-filter=U")
+        .arg("-z")
+        .output()
+        .await?;
+
+    let paths: Vec<String> = String::from_utf8_lossy(&output.stdout)
+        .split('\0')
+        .filter(|p| !p.is_empty())
+        .map(str::to_owned)
+        .collect();
+
+    if paths.is_empty() {
+        // Merge failed for a reason other than content conflicts
+        anyhow::bail!("dry-run merge failed without unmerged paths");
+    }
+    Ok(paths)
+}
+
+/// Number of commits on target that are not on source.
+async fn count_commits_behind(
+    repo_path: &std::path::Path,
+    source_sha: &str,
+    target_sha: &str,
+) -> i64 {
+    let output = tokio::process::Command::new("git")
+        .arg("-C")
+        .arg(repo_path)
+        .arg("rev-list")
+        .arg("--count")
+        .arg(format!("{source_sha}..{target_sha}"))
+        .output()
+        .await;
+
+    match output {
+        Ok(o) if o.status.success() => String::from_utf8_lossy(&o.stdout)
+            .trim()
+            .parse()
+            .unwrap_or(0),
+        _ => 0,
+    }
+}
+
 /// Get the HEAD SHA of a branch.
 async fn get_branch_head_sha(repo_path: &std::path::Path, branch: &str) -> Option<String> {
     let output = tokio::process::Command::new("git")
@@ -1554,6 +2036,7 @@ async fn enable_auto_merge(
         .and_then(|b| b.merge_method.as_deref())
         .unwrap_or("merge")
         .to_string();
+    validate_merge_method(&merge_method)?;
 
     let result = sqlx::query!(
         r#"
@@ -1675,7 +2158,7 @@ pub async fn try_auto_merge(state: &AppState, project_id: Uuid) {
             session_token_hash: None,
         };
 
-        match do_merge(state, &auth, project_id, mr.number, method).await {
+        match do_merge(state, &auth, project_id, mr.number, method, None).await {
             Ok(_) => {
                 tracing::info!(project_id = %project_id, mr_number = mr.number, "auto-merge succeeded");
             }
@@ -1807,6 +2290,7 @@ async fn run_post_merge_side_effects(
                 repo_path: trigger_repo,
                 branch: trigger_branch,
                 commit_sha: merge_sha,
+                before_sha: None,
             };
             match crate::pipeline::trigger::on_push(
                 &trigger_state.pool,
@@ -1887,6 +2371,7 @@ async fn post_merge_deploy(
     target_branch: &str,
     repo_path: &std::path::Path,
     source_head_sha: &str,
+    merged_by: Uuid,
 ) {
     if source_head_sha.is_empty() {
         return;
@@ -1944,7 +2429,13 @@ async fn post_merge_deploy(
     let ops_repo_id = ops_repo.as_ref().map(|o| o.id);
 
     // Upsert deploy target + create release so the reconciler picks it up
-    if let Err(e) = async {
+    let created = async {
+        if crate::deployer::freeze::refuses_automatic(&state.pool, project_id, "production")
+            .await?
+        {
+            return Ok(false);
+        }
+
         // Ensure deploy target exists
         let target_id = sqlx::query_scalar::<_, uuid::Uuid>(
             "INSERT INTO deploy_targets (project_id, name, environment, ops_repo_id)
@@ -1960,23 +2451,24 @@ async fn post_merge_deploy(
 
         // Create release
         sqlx::query(
-            "INSERT INTO deploy_releases (target_id, project_id, image_ref, strategy)
-             SELECT $1, $2, $3, dt.default_strategy
+            "INSERT INTO deploy_releases (target_id, project_id, image_ref, strategy, requires_approval, deployed_by)
+             SELECT $1, $2, $3, dt.default_strategy, dt.requires_approval, $4
              FROM deploy_targets dt WHERE dt.id = $1",
         )
         .bind(target_id)
         .bind(project_id)
         .bind(&image_ref)
+        .bind(merged_by)
         .execute(&state.pool)
         .await?;
 
-        Ok::<(), sqlx::Error>(())
+        Ok::<bool, sqlx::Error>(true)
     }
-    .await
-    {
-        tracing::warn!(error = %e, "post-merge release creation failed");
-    } else {
-        tracing::info!(%project_id, %image_ref, "release created (post-merge)");
+    .await;
+    match created {
+        Ok(true) => tracing::info!(%project_id, %image_ref, "release created (post-merge)"),
+        Ok(false) => {}
+        Err(e) => tracing::warn!(error = %e, "post-merge release creation failed"),
     }
 
     // Sync deploy/ to ops repo + commit values
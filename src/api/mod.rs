@@ -3,61 +3,134 @@
 
 //! HTTP API handlers and route definitions.
 
+pub mod activity;
 pub mod admin;
+pub mod auth_sessions;
 pub mod branch_protection;
+pub mod break_glass;
+pub mod chat_channels;
 pub mod cli_auth;
 pub mod commands;
+pub mod commit_statuses;
 pub mod dashboard;
+pub mod deploy_freezes;
 pub mod deployments;
 pub mod downloads;
+pub mod features;
 pub mod flags;
 pub mod gpg_keys;
 pub mod health;
 pub mod helpers;
+pub mod idempotency;
 pub mod issues;
+pub mod labels;
 pub mod llm_providers;
 pub mod merge_requests;
 pub mod mesh;
+pub mod mirrors;
 pub mod notifications;
+pub mod oidc;
 pub mod onboarding;
 pub mod passkeys;
+pub mod permissions;
+pub mod pipeline_schedules;
 pub mod pipelines;
 pub mod preview;
 pub mod projects;
+pub mod rate_limits;
+pub mod reactions;
 pub mod releases;
+pub mod search;
 pub mod secrets;
 pub mod sessions;
 pub mod setup;
 pub mod ssh_keys;
+pub mod totp;
+pub mod user_import;
 pub mod user_keys;
 pub mod users;
+pub mod variable_groups;
 pub mod webhooks;
 pub mod workspaces;
 
 use axum::Router;
+use axum::extract::DefaultBodyLimit;
+use tower_http::limit::RequestBodyLimitLayer;
 
+use crate::config::Config;
 use crate::store::AppState;
 
+/// Request body limits for route groups that differ from the API default.
+#[derive(Debug, Clone, Copy)]
+pub struct BodyLimits {
+    /// Issues and merge requests.
+    pub content: usize,
+    /// File uploads (release assets).
+    pub upload: usize,
+}
+
+impl BodyLimits {
+    pub fn from_config(cfg: &Config) -> Self {
+        Self {
+            content: cfg.content_body_limit_bytes,
+            upload: cfg.upload_body_limit_bytes,
+        }
+    }
+}
+
+impl Default for BodyLimits {
+    fn default() -> Self {
+        Self {
+            content: 1024 * 1024,
+            upload: 500 * 1024 * 1024,
+        }
+    }
+}
+
+/// The API router with the default body limits.
+#[allow(dead_code)] // public library entry point; the binary mounts `router_with_body_limits`
 pub fn router() -> Router<AppState> {
+    router_with_body_limits(BodyLimits::default())
+}
+
+/// The API router with each route group behind its own body limit. Routes
+/// outside a group get the app-wide `DefaultBodyLimit`.
+pub fn router_with_body_limits(limits: BodyLimits) -> Router<AppState> {
     Router::new()
         .merge(users::router())
         .merge(admin::router())
+        .merge(rate_limits::router())
+        .merge(features::router())
+        .merge(user_import::router())
         .merge(projects::router())
-        .merge(issues::router())
-        .merge(merge_requests::router())
+        .merge(activity::router())
+        .merge(search::router())
+        .merge(reactions::router())
+        .merge(labels::router())
         .merge(webhooks::router())
+        .merge(chat_channels::router())
         .merge(pipelines::router())
+        .merge(pipeline_schedules::router())
+        .merge(variable_groups::router())
         .merge(deployments::router())
+        .merge(deploy_freezes::router())
         .merge(flags::router())
         .merge(sessions::router())
         .merge(secrets::router())
         .merge(notifications::router())
         .merge(passkeys::router())
+        .merge(oidc::router())
+        .merge(auth_sessions::router())
+        .merge(permissions::router())
+        .merge(break_glass::router())
+        .merge(totp::router())
         .merge(user_keys::router())
         .merge(ssh_keys::router())
         .merge(gpg_keys::router())
         .merge(workspaces::router())
         .merge(branch_protection::router())
+        .merge(commit_statuses::router())
+        .merge(mirrors::router())
         .merge(releases::router())
         .merge(dashboard::router())
         .merge(onboarding::router())
@@ -69,4 +142,16 @@ pub fn router() -> Router<AppState> {
         .merge(llm_providers::router())
         .merge(mesh::router())
         .merge(crate::git::browser_router())
+        .merge(
+            issues::router()
+                .merge(merge_requests::router())
+                .layer(RequestBodyLimitLayer::new(limits.content)),
+        )
+        // DefaultBodyLimit::disable() lifts the extractor limit so only the
+        // upload limit applies (see the git routes in main.rs)
+        .merge(
+            releases::upload_router()
+                .layer(DefaultBodyLimit::disable())
+                .layer(RequestBodyLimitLayer::new(limits.upload)),
+        )
 }
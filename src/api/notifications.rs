@@ -12,7 +12,9 @@ use ts_rs::TS;
 
 use crate::auth::middleware::AuthUser;
 use crate::error::ApiError;
+use crate::notify::digest::DigestInterval;
 use crate::store::AppState;
+use crate::validation;
 
 // ---------------------------------------------------------------------------
 // Types
@@ -25,6 +27,14 @@ pub struct ListParams {
     pub status: Option<String>,
     #[serde(rename = "type")]
     pub notification_type: Option<String>,
+    /// Only unread in-app notifications.
+    #[serde(default)]
+    pub unread: bool,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct UpdateNotificationRequest {
+    pub read: bool,
 }
 
 use super::helpers::ListResponse;
@@ -40,9 +50,47 @@ pub struct NotificationResponse {
     pub status: String,
     pub ref_type: Option<String>,
     pub ref_id: Option<Uuid>,
+    pub read_at: Option<DateTime<Utc>>,
     pub created_at: DateTime<Utc>,
 }
 
+struct NotificationRow {
+    id: Uuid,
+    notification_type: String,
+    subject: String,
+    body: Option<String>,
+    channel: String,
+    status: String,
+    ref_type: Option<String>,
+    ref_id: Option<Uuid>,
+    read_at: Option<DateTime<Utc>>,
+    created_at: DateTime<Utc>,
+}
+
+impl From<NotificationRow> for NotificationResponse {
+    fn from(r: NotificationRow) -> Self {
+        Self {
+            id: r.id,
+            notification_type: r.notification_type,
+            subject: r.subject,
+            body: r.body,
+            channel: r.channel,
+            status: r.status,
+            ref_type: r.ref_type,
+            ref_id: r.ref_id,
+            read_at: r.read_at,
+            created_at: r.created_at,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, TS)]
+#[ts(export)]
+pub struct MarkAllReadResponse {
+    #[ts(type = "number")]
+    pub updated: u64,
+}
+
 #[derive(Debug, Serialize, TS)]
 #[ts(export)]
 pub struct UnreadCountResponse {
@@ -50,6 +98,14 @@ pub struct UnreadCountResponse {
     pub count: i64,
 }
 
+#[derive(Debug, Deserialize, Serialize, TS)]
+#[ts(export)]
+pub struct NotificationPreference {
+    pub notification_type: String,
+    /// `immediate` (default), `hourly`, or `daily` email digest.
+    pub digest: String,
+}
+
 // ---------------------------------------------------------------------------
 // Router
 // ---------------------------------------------------------------------------
@@ -58,10 +114,22 @@ pub fn router() -> Router<AppState> {
     Router::new()
         .route("/api/notifications", get(list_notifications))
         .route("/api/notifications/unread-count", get(unread_count))
+        .route(
+            "/api/notifications/read-all",
+            axum::routing::post(mark_all_read),
+        )
+        .route(
+            "/api/notifications/{id}",
+            axum::routing::patch(update_notification),
+        )
         .route(
             "/api/notifications/{id}/read",
             axum::routing::patch(mark_read),
         )
+        .route(
+            "/api/notifications/preferences",
+            get(list_preferences).put(set_preference),
+        )
 }
 
 // ---------------------------------------------------------------------------
@@ -83,52 +151,45 @@ async fn list_notifications(
         WHERE user_id = $1
           AND ($2::text IS NULL OR status = $2)
           AND ($3::text IS NULL OR notification_type = $3)
+          AND (NOT $4 OR (read_at IS NULL AND channel = 'in_app'))
         "#,
         auth.user_id,
         params.status,
         params.notification_type,
+        params.unread,
     )
     .fetch_one(&state.pool)
     .await?;
 
-    let rows = sqlx::query!(
+    let rows = sqlx::query_as!(
+        NotificationRow,
         r#"
         SELECT id, notification_type, subject, body, channel, status,
-               ref_type, ref_id, created_at
+               ref_type, ref_id, read_at, created_at
         FROM notifications
         WHERE user_id = $1
           AND ($2::text IS NULL OR status = $2)
           AND ($3::text IS NULL OR notification_type = $3)
+          AND (NOT $4 OR (read_at IS NULL AND channel = 'in_app'))
         ORDER BY created_at DESC
-        LIMIT $4 OFFSET $5
+        LIMIT $5 OFFSET $6
         "#,
         auth.user_id,
         params.status,
         params.notification_type,
+        params.unread,
         limit,
         offset,
     )
     .fetch_all(&state.pool)
     .await?;
 
-    let items = rows
-        .into_iter()
-        .map(|r| NotificationResponse {
-            id: r.id,
-            notification_type: r.notification_type,
-            subject: r.subject,
-            body: r.body,
-            channel: r.channel,
-            status: r.status,
-            ref_type: r.ref_type,
-            ref_id: r.ref_id,
-            created_at: r.created_at,
-        })
-        .collect();
+    let items = rows.into_iter().map(NotificationResponse::from).collect();
 
     Ok(Json(ListResponse { items, total }))
 }
 
+/// Unread in-app notifications, for the UI badge.
 async fn unread_count(
     State(state): State<AppState>,
     auth: AuthUser,
@@ -137,7 +198,7 @@ async fn unread_count(
         r#"
         SELECT COUNT(*) as "count!"
         FROM notifications
-        WHERE user_id = $1 AND status IN ('pending', 'sent')
+        WHERE user_id = $1 AND read_at IS NULL AND channel = 'in_app'
         "#,
         auth.user_id,
     )
@@ -154,8 +215,8 @@ async fn mark_read(
 ) -> Result<Json<serde_json::Value>, ApiError> {
     let result = sqlx::query!(
         r#"
-        UPDATE notifications SET status = 'read'
-        WHERE id = $1 AND user_id = $2 AND status != 'read'
+        UPDATE notifications SET status = 'read', read_at = now()
+        WHERE id = $1 AND user_id = $2 AND read_at IS NULL
         "#,
         id,
         auth.user_id,
@@ -170,3 +231,106 @@ async fn mark_read(
 
     Ok(Json(serde_json::json!({"ok": true})))
 }
+
+/// Mark one notification read or unread.
+async fn update_notification(
+    State(state): State<AppState>,
+    auth: AuthUser,
+    Path(id): Path<Uuid>,
+    Json(body): Json<UpdateNotificationRequest>,
+) -> Result<Json<NotificationResponse>, ApiError> {
+    // Marking unread restores 'sent', the status in-app notifications are delivered with
+    let row = sqlx::query_as!(
+        NotificationRow,
+        r#"
+        UPDATE notifications SET
+            read_at = CASE WHEN $3 THEN COALESCE(read_at, now()) END,
+            status = CASE WHEN $3 THEN 'read' WHEN status = 'read' THEN 'sent' ELSE status END
+        WHERE id = $1 AND user_id = $2
+        RETURNING id, notification_type, subject, body, channel, status,
+                  ref_type, ref_id, read_at, created_at
+        "#,
+        id,
+        auth.user_id,
+        body.read,
+    )
+    .fetch_optional(&state.pool)
+    .await?
+    .ok_or_else(|| ApiError::NotFound("notification".into()))?;
+
+    Ok(Json(NotificationResponse::from(row)))
+}
+
+/// Mark every unread in-app notification of the caller read.
+async fn mark_all_read(
+    State(state): State<AppState>,
+    auth: AuthUser,
+) -> Result<Json<MarkAllReadResponse>, ApiError> {
+    let result = sqlx::query!(
+        r#"
+        UPDATE notifications SET status = 'read', read_at = now()
+        WHERE user_id = $1 AND read_at IS NULL AND channel = 'in_app'
+        "#,
+        auth.user_id,
+    )
+    .execute(&state.pool)
+    .await?;
+
+    Ok(Json(MarkAllReadResponse {
+        updated: result.rows_affected(),
+    }))
+}
+
+async fn list_preferences(
+    State(state): State<AppState>,
+    auth: AuthUser,
+) -> Result<Json<Vec<NotificationPreference>>, ApiError> {
+    let rows = sqlx::query_as!(
+        NotificationPreference,
+        "SELECT notification_type, digest FROM notification_preferences
+         WHERE user_id = $1 ORDER BY notification_type",
+        auth.user_id,
+    )
+    .fetch_all(&state.pool)
+    .await?;
+
+    Ok(Json(rows))
+}
+
+/// Choose immediate delivery or an email digest for one notification type.
+async fn set_preference(
+    State(state): State<AppState>,
+    auth: AuthUser,
+    Json(body): Json<NotificationPreference>,
+) -> Result<Json<NotificationPreference>, ApiError> {
+    validation::check_length("notification_type", &body.notification_type, 1, 64)?;
+
+    if body.digest == "immediate" {
+        sqlx::query!(
+            "DELETE FROM notification_preferences WHERE user_id = $1 AND notification_type = $2",
+            auth.user_id,
+            body.notification_type,
+        )
+        .execute(&state.pool)
+        .await?;
+        return Ok(Json(body));
+    }
+
+    let interval = DigestInterval::parse(&body.digest).ok_or_else(|| {
+        ApiError::BadRequest("digest must be 'immediate', 'hourly' or 'daily'".into())
+    })?;
+    sqlx::query!(
+        r#"
+        INSERT INTO notification_preferences (user_id, notification_type, digest)
+        VALUES ($1, $2, $3)
+        ON CONFLICT (user_id, notification_type) DO UPDATE SET digest = EXCLUDED.digest
+        "#,
+        auth.user_id,
+        body.notification_type,
+        interval.as_str(),
+    )
+    .execute(&state.pool)
+    .await?;
+
+    Ok(Json(body))
+}
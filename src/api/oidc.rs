@@ -0,0 +1,616 @@
+// Copyright (c) 2026 Steven Hooker. Exclusively licensed to and distributed by AgentSphere GmbH.
+// SPDX-License-Identifier: BUSL-1.1
+
+//! OIDC single sign-on: `GET /api/auth/oidc/login` redirects to the
+//! configured provider, `GET /api/auth/oidc/callback` verifies the ID token,
+//! links or provisions the platform user and starts a normal session.
+//!
+//! A first login links an existing account with the same email when the
+//! provider asserts the email is verified. Accounts with TOTP or admin rights
+//! are never linked that way: their owner signs in normally and starts
+//! `GET /api/auth/oidc/link`. Accounts created here have no password. Groups listed in `PLATFORM_OIDC_ROLE_MAP`
+//! grant their global role on every login (roles are never revoked here).
+
+use axum::extract::{Query, State};
+use axum::http::{HeaderMap, header};
+use axum::response::{AppendHeaders, IntoResponse, Redirect, Response};
+use axum::routing::get;
+use axum::{Json, Router};
+use chrono::Utc;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::audit::{AuditEntry, send_audit};
+use crate::auth::middleware::{AuthUser, ClientInfo};
+use crate::auth::oidc::{self, IdTokenClaims, ProviderMetadata};
+use crate::auth::user_type::UserType;
+use crate::error::ApiError;
+use crate::rbac::{Permission, resolver};
+use crate::store::{AppState, valkey};
+use crate::validation;
+
+/// How long a started login may take to come back from the provider.
+const FLOW_TTL_SECS: i64 = 600;
+
+/// Binds the callback to the browser that started the login.
+const STATE_COOKIE: &str = "oidc_state";
+
+/// Placeholder hash for accounts that only sign in through the provider.
+const NO_PASSWORD: &str = "!disabled";
+
+/// Where the browser lands after a successful login.
+const LANDING_PATH: &str = "/";
+
+#[derive(Debug, Serialize, Deserialize)]
+struct PendingLogin {
+    nonce: String,
+    code_verifier: String,
+    /// Set when a signed-in user started the flow to link their account.
+    #[serde(default)]
+    link_user_id: Option<Uuid>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct CallbackQuery {
+    pub code: Option<String>,
+    pub state: Option<String>,
+    pub error: Option<String>,
+    pub error_description: Option<String>,
+}
+
+#[derive(Debug)]
+struct SsoUser {
+    id: Uuid,
+    name: String,
+    is_active: bool,
+    user_type: String,
+}
+
+// ---------------------------------------------------------------------------
+// Router
+// ---------------------------------------------------------------------------
+
+pub fn router() -> Router<AppState> {
+    Router::new()
+        .route("/api/auth/oidc/status", get(status))
+        .route("/api/auth/oidc/login", get(login))
+        .route("/api/auth/oidc/link", get(link))
+        .route("/api/auth/oidc/callback", get(callback))
+}
+
+// ---------------------------------------------------------------------------
+// Handlers
+// ---------------------------------------------------------------------------
+
+/// Whether single sign-on is configured, so the login page can offer it.
+async fn status(State(state): State<AppState>) -> Json<serde_json::Value> {
+    Json(serde_json::json!({"enabled": state.config.oidc_issuer.is_some()}))
+}
+
+#[tracing::instrument(skip_all, err)]
+async fn login(State(state): State<AppState>, client: ClientInfo) -> Result<Response, ApiError> {
+    start_flow(&state, &client, None).await
+}
+
+/// Link the provider identity to the signed-in account. Needed for accounts
+/// that a login never links by email (TOTP or admin rights).
+#[tracing::instrument(skip_all, fields(user_id = %auth.user_id), err)]
+async fn link(
+    State(state): State<AppState>,
+    auth: AuthUser,
+    client: ClientInfo,
+) -> Result<Response, ApiError> {
+    // A browser session, not an API token, proves the owner is present
+    if auth.session_token_hash.is_none() {
+        return Err(ApiError::Forbidden);
+    }
+    start_flow(&state, &client, Some(auth.user_id)).await
+}
+
+async fn start_flow(
+    state: &AppState,
+    client: &ClientInfo,
+    link_user_id: Option<Uuid>,
+) -> Result<Response, ApiError> {
+    let issuer = configured_issuer(state)?;
+    let rate_key = client.ip_addr.as_deref().unwrap_or("global");
+    crate::auth::rate_limit::check_rate(&state.valkey, "oidc_login", rate_key, 30, 60).await?;
+
+    let meta = discover(issuer).await?;
+    let flow_state = oidc::random_token();
+    let pending = PendingLogin {
+        nonce: oidc::random_token(),
+        code_verifier: oidc::random_token(),
+        link_user_id,
+    };
+    valkey::set_cached(
+        &state.valkey,
+        &flow_key(&flow_state),
+        &pending,
+        FLOW_TTL_SECS,
+    )
+    .await
+    .map_err(ApiError::Internal)?;
+
+    let url = oidc::authorization_url(
+        &meta,
+        &state.config,
+        &flow_state,
+        &pending.nonce,
+        &pending.code_verifier,
+    )
+    .map_err(|e| ApiError::BadGateway(format!("identity provider: {e}")))?;
+
+    // Lax, not Strict: the callback is a cross-site navigation from the provider
+    let cookie = format!(
+        "{STATE_COOKIE}={flow_state}; Path=/api/auth/oidc; HttpOnly; SameSite=Lax; \
+         Max-Age={FLOW_TTL_SECS}{}",
+        secure_flag(state)
+    );
+    Ok((
+        AppendHeaders([(header::SET_COOKIE, cookie)]),
+        Redirect::to(&url),
+    )
+        .into_response())
+}
+
+#[tracing::instrument(skip_all, err)]
+async fn callback(
+    State(state): State<AppState>,
+    client: ClientInfo,
+    headers: HeaderMap,
+    Query(query): Query<CallbackQuery>,
+) -> Result<Response, ApiError> {
+    let issuer = configured_issuer(&state)?;
+    if let Some(error) = query.error {
+        let detail = query.error_description.unwrap_or(error);
+        return Err(ApiError::BadRequest(format!(
+            "identity provider rejected the login: {detail}"
+        )));
+    }
+    let (Some(code), Some(flow_state)) = (query.code, query.state) else {
+        return Err(ApiError::BadRequest("missing code or state".into()));
+    };
+    if state_cookie(&headers) != Some(flow_state.as_str()) {
+        return Err(ApiError::BadRequest(
+            "login state does not match this browser — start the sign-in again".into(),
+        ));
+    }
+
+    let key = flow_key(&flow_state);
+    let pending: PendingLogin = valkey::get_cached(&state.valkey, &key)
+        .await
+        .ok_or_else(|| ApiError::BadRequest("login expired — start the sign-in again".into()))?;
+    let _ = valkey::invalidate(&state.valkey, &key).await;
+
+    let meta = discover(issuer).await?;
+    let id_token = oidc::exchange_code(&meta, &state.config, &code, &pending.code_verifier)
+        .await
+        .map_err(|e| ApiError::BadGateway(format!("identity provider: {e}")))?;
+    let jwks = oidc::fetch_jwks(&meta)
+        .await
+        .map_err(|e| ApiError::BadGateway(format!("identity provider: {e}")))?;
+    let claims = oidc::verify_id_token(
+        &id_token,
+        &jwks,
+        &meta.issuer,
+        &state.config.oidc_client_id,
+        &pending.nonce,
+        Utc::now().timestamp(),
+    )
+    .map_err(|e| {
+        tracing::warn!(error = %e, "rejected OIDC id token");
+        ApiError::Unauthorized
+    })?;
+
+    let user = match pending.link_user_id {
+        Some(user_id) => link_identity(&state, &meta, &claims, user_id, &client).await?,
+        None => resolve_user(&state, &meta, &claims, &client).await?,
+    };
+    let user_type = user
+        .user_type
+        .parse::<UserType>()
+        .map_err(ApiError::Internal)?;
+    if !user.is_active || !user_type.can_login() {
+        return Err(ApiError::Unauthorized);
+    }
+    grant_mapped_roles(&state, user.id, &user.name, &claims, &client).await?;
+
+    let session = super::users::create_login_session(&state, user.id, &user.name, &client).await?;
+    let clear_state = format!(
+        "{STATE_COOKIE}=; Path=/api/auth/oidc; HttpOnly; SameSite=Lax; Max-Age=0{}",
+        secure_flag(&state)
+    );
+    Ok((
+        AppendHeaders([
+            (
+                header::SET_COOKIE,
+                super::users::session_cookie(&state, &session.token),
+            ),
+            (header::SET_COOKIE, clear_state),
+        ]),
+        Redirect::to(LANDING_PATH),
+    )
+        .into_response())
+}
+
+// ---------------------------------------------------------------------------
+// Helpers
+// ---------------------------------------------------------------------------
+
+fn configured_issuer(state: &AppState) -> Result<&str, ApiError> {
+    state
+        .config
+        .oidc_issuer
+        .as_deref()
+        .ok_or_else(|| ApiError::NotFound("single sign-on".into()))
+}
+
+async fn discover(issuer: &str) -> Result<ProviderMetadata, ApiError> {
+    oidc::discover(issuer)
+        .await
+        .map_err(|e| ApiError::BadGateway(format!("identity provider discovery failed: {e}")))
+}
+
+fn flow_key(flow_state: &str) -> String {
+    format!("oidc:flow:{flow_state}")
+}
+
+fn secure_flag(state: &AppState) -> &'static str {
+    if state.config.secure_cookies {
+        "; Secure"
+    } else {
+        ""
+    }
+}
+
+fn state_cookie(headers: &HeaderMap) -> Option<&str> {
+    headers
+        .get_all(header::COOKIE)
+        .iter()
+        .filter_map(|v| v.to_str().ok())
+        .flat_map(|v| v.split(';'))
+        .find_map(|pair| pair.trim().strip_prefix(STATE_COOKIE)?.strip_prefix('='))
+}
+
+/// The platform user for `claims`: the linked identity, else the account
+/// with the same verified email (linking it), else a newly provisioned one.
+async fn resolve_user(
+    state: &AppState,
+    meta: &ProviderMetadata,
+    claims: &IdTokenClaims,
+    client: &ClientInfo,
+) -> Result<SsoUser, ApiError> {
+    let linked = sqlx::query_as!(
+        SsoUser,
+        "UPDATE oidc_identities i SET last_login_at = now()
+         FROM users u
+         WHERE u.id = i.user_id AND i.issuer = $1 AND i.subject = $2
+         RETURNING u.id, u.name, u.is_active, u.user_type",
+        meta.issuer,
+        claims.sub,
+    )
+    .fetch_optional(&state.pool)
+    .await?;
+    if let Some(user) = linked {
+        return Ok(user);
+    }
+
+    let email = claims
+        .email
+        .as_deref()
+        .filter(|e| validation::check_email(e).is_ok())
+        .ok_or_else(|| {
+            ApiError::BadRequest("the identity provider did not return an email address".into())
+        })?;
+    // A missing claim is not an assertion: without it anyone who can set an
+    // email at the provider could take over the matching account
+    if claims.email_verified() != Some(true) {
+        return Err(ApiError::BadRequest(
+            "the identity provider has not verified this email address".into(),
+        ));
+    }
+
+    let user = match find_by_email(&state.pool, email).await? {
+        Some(user) => {
+            if requires_explicit_link(state, user.id).await? {
+                return Err(ApiError::Conflict(
+                    "an account with this email exists — sign in to it and link single \
+                     sign-on from your account settings"
+                        .into(),
+                ));
+            }
+            user
+        }
+        None => provision(state, claims, email, client).await?,
+    };
+
+    sqlx::query!(
+        "INSERT INTO oidc_identities (issuer, subject, user_id) VALUES ($1, $2, $3)
+         ON CONFLICT (issuer, subject) DO NOTHING",
+        meta.issuer,
+        claims.sub,
+        user.id,
+    )
+    .execute(&state.pool)
+    .await?;
+
+    Ok(user)
+}
+
+/// Link the provider identity in `claims` to `user_id`, who started the flow
+/// from a signed-in session.
+async fn link_identity(
+    state: &AppState,
+    meta: &ProviderMetadata,
+    claims: &IdTokenClaims,
+    user_id: Uuid,
+    client: &ClientInfo,
+) -> Result<SsoUser, ApiError> {
+    let linked_to = sqlx::query_scalar!(
+        "INSERT INTO oidc_identities (issuer, subject, user_id) VALUES ($1, $2, $3)
+         ON CONFLICT (issuer, subject) DO UPDATE SET last_login_at = now()
+         RETURNING user_id",
+        meta.issuer,
+        claims.sub,
+        user_id,
+    )
+    .fetch_one(&state.pool)
+    .await?;
+    if linked_to != user_id {
+        return Err(ApiError::Conflict(
+            "this identity is already linked to another account".into(),
+        ));
+    }
+
+    let user = sqlx::query_as!(
+        SsoUser,
+        "SELECT id, name, is_active, user_type FROM users WHERE id = $1",
+        user_id,
+    )
+    .fetch_optional(&state.pool)
+    .await?
+    .ok_or(ApiError::Unauthorized)?;
+    send_audit(
+        &state.audit_tx,
+        AuditEntry {
+            actor_id: user.id,
+            actor_name: user.name.clone(),
+            action: "user.oidc_link".into(),
+            resource: "user".into(),
+            resource_id: Some(user.id),
+            project_id: None,
+            detail: Some(serde_json::json!({"issuer": meta.issuer, "subject": claims.sub})),
+            ip_addr: client.ip_addr.clone(),
+        },
+    );
+    Ok(user)
+}
+
+/// Accounts with a second factor or admin rights are only linked from a
+/// signed-in session; an SSO login would skip the TOTP check.
+async fn requires_explicit_link(state: &AppState, user_id: Uuid) -> Result<bool, ApiError> {
+    let has_totp = sqlx::query_scalar!(
+        r#"SELECT EXISTS(SELECT 1 FROM user_totp WHERE user_id = $1 AND enabled) as "exists!""#,
+        user_id,
+    )
+    .fetch_one(&state.pool)
+    .await?;
+    if has_totp {
+        return Ok(true);
+    }
+    resolver::has_permission(
+        &state.pool,
+        &state.valkey,
+        user_id,
+        None,
+        Permission::AdminUsers,
+    )
+    .await
+    .map_err(ApiError::Internal)
+}
+
+async fn find_by_email(pool: &sqlx::PgPool, email: &str) -> Result<Option<SsoUser>, ApiError> {
+    let user = sqlx::query_as!(
+        SsoUser,
+        "SELECT id, name, is_active, user_type FROM users
+         WHERE lower(email) = lower($1)
+         ORDER BY created_at
+         LIMIT 1",
+        email,
+    )
+    .fetch_optional(pool)
+    .await?;
+    Ok(user)
+}
+
+/// Create a password-less human account for a first-time SSO user.
+async fn provision(
+    state: &AppState,
+    claims: &IdTokenClaims,
+    email: &str,
+    client: &ClientInfo,
+) -> Result<SsoUser, ApiError> {
+    let base = username_from(claims.preferred_username.as_deref(), email);
+    let display_name = claims.name.as_deref().filter(|n| !n.trim().is_empty());
+
+    for attempt in 0..5 {
+        let name = if attempt == 0 {
+            base.clone()
+        } else {
+            format!("{base}-{}", &Uuid::new_v4().simple().to_string()[..6])
+        };
+        let id = sqlx::query_scalar!(
+            "INSERT INTO users (name, display_name, email, password_hash, user_type)
+             VALUES ($1, $2, $3, $4, 'human')
+             ON CONFLICT DO NOTHING
+             RETURNING id",
+            name,
+            display_name,
+            email,
+            NO_PASSWORD,
+        )
+        .fetch_optional(&state.pool)
+        .await?;
+
+        let Some(id) = id else {
+            // A concurrent login may have created the account already
+            if let Some(user) = find_by_email(&state.pool, email).await? {
+                return Ok(user);
+            }
+            continue;
+        };
+
+        let _ = crate::workspace::service::get_or_create_default_workspace(
+            &state.pool,
+            id,
+            &name,
+            display_name.unwrap_or(&name),
+        )
+        .await;
+        send_audit(
+            &state.audit_tx,
+            AuditEntry {
+                actor_id: id,
+                actor_name: name.clone(),
+                action: "user.create".into(),
+                resource: "user".into(),
+                resource_id: Some(id),
+                project_id: None,
+                detail: Some(serde_json::json!({
+                    "name": name,
+                    "user_type": "human",
+                    "sso": true,
+                })),
+                ip_addr: client.ip_addr.clone(),
+            },
+        );
+        return Ok(SsoUser {
+            id,
+            name,
+            is_active: true,
+            user_type: "human".into(),
+        });
+    }
+
+    Err(ApiError::Conflict(
+        "could not find a free user name for this account".into(),
+    ))
+}
+
+/// Grant the global roles mapped from the user's identity provider groups.
+async fn grant_mapped_roles(
+    state: &AppState,
+    user_id: Uuid,
+    user_name: &str,
+    claims: &IdTokenClaims,
+    client: &ClientInfo,
+) -> Result<(), ApiError> {
+    let groups = claims.groups(&state.config.oidc_groups_claim);
+    let roles = oidc::mapped_roles(&groups, &state.config.oidc_role_map);
+    if roles.is_empty() {
+        return Ok(());
+    }
+
+    let granted = sqlx::query!(
+        r#"WITH granted AS (
+             INSERT INTO user_roles (user_id, role_id)
+             SELECT $1, id FROM roles WHERE name = ANY($2)
+             ON CONFLICT DO NOTHING
+             RETURNING id, role_id
+         )
+         SELECT g.id AS "id!", r.name FROM granted g JOIN roles r ON r.id = g.role_id"#,
+        user_id,
+        &roles,
+    )
+    .fetch_all(&state.pool)
+    .await?;
+    if granted.is_empty() {
+        return Ok(());
+    }
+
+    let _ = resolver::invalidate_permissions(&state.valkey, user_id, None).await;
+    for row in granted {
+        send_audit(
+            &state.audit_tx,
+            AuditEntry {
+                actor_id: user_id,
+                actor_name: user_name.to_string(),
+                action: "role.assign".into(),
+                resource: "user_role".into(),
+                resource_id: Some(row.id),
+                project_id: None,
+                detail: Some(serde_json::json!({
+                    "user_id": user_id,
+                    "role": row.name,
+                    "sso": true,
+                })),
+                ip_addr: client.ip_addr.clone(),
+            },
+        );
+    }
+    Ok(())
+}
+
+/// Platform user name from `preferred_username`, else the email local part,
+/// reduced to the characters user names allow.
+fn username_from(preferred: Option<&str>, email: &str) -> String {
+    let raw = preferred.filter(|p| !p.trim().is_empty()).unwrap_or(email);
+    let raw = raw.split('@').next().unwrap_or(raw);
+    let name: String = raw
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.') {
+                c.to_ascii_lowercase()
+            } else {
+                '-'
+            }
+        })
+        .take(64)
+        .collect();
+    let name = name.trim_matches(|c| c == '.' || c == '-');
+    if name.is_empty() {
+        "user".into()
+    } else {
+        name.to_owned()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn username_prefers_preferred_username() {
+        assert_eq!(
+            username_from(Some("Ada.Lovelace"), "x@example.com"),
+            "ada.lovelace"
+        );
+        assert_eq!(
+            username_from(Some("ada@corp.example.com"), "x@example.com"),
+            "ada"
+        );
+    }
+
+    #[test]
+    fn username_falls_back_to_email_and_is_sanitized() {
+        assert_eq!(
+            username_from(None, "grace hopper@example.com"),
+            "grace-hopper"
+        );
+        assert_eq!(username_from(Some(" "), ".dot.@example.com"), "dot");
+        assert_eq!(username_from(None, "äö@example.com"), "user");
+        assert!(validation::check_name(&username_from(None, "a+b@example.com")).is_ok());
+    }
+
+    #[test]
+    fn state_cookie_is_found_among_others() {
+        let mut headers = HeaderMap::new();
+        headers.insert(
+            header::COOKIE,
+            "session=abc; oidc_state=xyz; theme=dark".parse().unwrap(),
+        );
+        assert_eq!(state_cookie(&headers), Some("xyz"));
+        assert_eq!(state_cookie(&HeaderMap::new()), None);
+    }
+}
@@ -350,7 +350,8 @@ async fn save_provider_key(state: &AppState, user_id: Uuid, api_key: &str) -> Re
     let master_key =
         crate::secrets::engine::parse_master_key(hex_str).map_err(ApiError::Internal)?;
 
-    let key_bytes = crate::secrets::engine::encrypt(api_key.as_bytes(), &master_key)
+    let key_bytes = master_key
+        .encrypt(api_key.as_bytes())
         .map_err(ApiError::Internal)?;
 
     let suffix = if api_key.len() >= 4 {
@@ -13,10 +13,9 @@ use webauthn_rs::prelude::*;
 
 use ts_rs::TS;
 
-use sqlx::Row;
-
 use crate::audit::{AuditEntry, send_audit};
-use crate::auth::middleware::AuthUser;
+use crate::auth::middleware::{AuthUser, ClientInfo};
+use crate::auth::passkey::PasskeyPolicy;
 use crate::auth::{passkey, token};
 use crate::error::ApiError;
 use crate::store::AppState;
@@ -43,6 +42,11 @@ pub struct PasskeyResponse {
     pub backup_eligible: bool,
     pub backup_state: bool,
     pub transports: Vec<String>,
+    /// Authenticator model, when it disclosed one at registration.
+    pub aaguid: Option<Uuid>,
+    /// Attestation statement format given at registration (`none`, `packed`, ...).
+    pub attestation_format: Option<String>,
+    pub user_verified: bool,
 }
 
 #[derive(Debug, Serialize, TS)]
@@ -146,13 +150,33 @@ async fn begin_register(
     .fetch_one(&state.pool)
     .await?;
 
+    // Under the hardware policy the browser must return an attestation that
+    // chains to a trusted vendor CA
+    let attestation_cas = if passkey_policy(&state, auth.user_id).await? == PasskeyPolicy::Hardware
+    {
+        let cas = passkey::hardware_attestation_cas(&state.config)
+            .await
+            .map_err(ApiError::Internal)?;
+        Some(cas.ok_or_else(|| {
+            ApiError::BadRequest(
+                "this account requires a hardware security key, but none are approved on this \
+                 platform"
+                    .into(),
+            )
+        })?)
+    } else {
+        None
+    };
+
     let ccr = passkey::begin_registration(
         &state.webauthn,
         &state.valkey,
         auth.user_id,
         &auth.user_name,
         &display_name,
+        &body.name,
         exclude_creds,
+        attestation_cas,
     )
     .await
     .map_err(ApiError::Internal)?;
@@ -160,37 +184,81 @@ async fn begin_register(
     Ok(Json(ccr))
 }
 
+async fn passkey_policy(state: &AppState, user_id: Uuid) -> Result<PasskeyPolicy, ApiError> {
+    let policy = sqlx::query_scalar!("SELECT passkey_policy FROM users WHERE id = $1", user_id)
+        .fetch_one(&state.pool)
+        .await?;
+    Ok(PasskeyPolicy::parse(&policy).unwrap_or(PasskeyPolicy::Hardware))
+}
+
 #[tracing::instrument(skip(state, body), fields(user_id = %auth.user_id), err)]
 async fn complete_register(
     State(state): State<AppState>,
     auth: AuthUser,
     Json(body): Json<RegisterPublicKeyCredential>,
 ) -> Result<impl IntoResponse, ApiError> {
-    let pk = passkey::finish_registration(&state.webauthn, &state.valkey, auth.user_id, &body)
+    let passkey::Registered {
+        passkey: pk,
+        name,
+        attested_aaguid,
+    } = passkey::finish_registration(&state.webauthn, &state.valkey, auth.user_id, &body)
         .await
         .map_err(|e| ApiError::BadRequest(format!("registration failed: {e}")))?;
+    let attestation: &[u8] = body.response.attestation_object.as_ref();
+    let info = passkey::parse_attestation(attestation)
+        .map_err(|e| ApiError::BadRequest(format!("registration failed: {e}")))?;
+
+    let policy = passkey_policy(&state, auth.user_id).await?;
+    if let Some(reason) = policy.violation(
+        &info,
+        attested_aaguid,
+        &state.config.passkey_hardware_aaguids,
+    ) {
+        tracing::warn!(
+            user_id = %auth.user_id,
+            aaguid = ?info.aaguid,
+            attested_aaguid = ?attested_aaguid,
+            format = %info.format,
+            %reason,
+            "passkey rejected by hardware policy"
+        );
+        return Err(ApiError::BadRequest(format!(
+            "this account requires a hardware security key: {reason}"
+        )));
+    }
 
     // Store credential in DB
     let cred_id_bytes: Vec<u8> = pk.cred_id().to_vec();
     let public_key_bytes = serde_json::to_vec(&pk).map_err(|e| ApiError::Internal(e.into()))?;
+    let transports: Vec<String> = body
+        .response
+        .transports
+        .iter()
+        .flatten()
+        .filter_map(|t| serde_json::to_value(t).ok()?.as_str().map(str::to_owned))
+        .collect();
 
-    // Get the passkey name from the Valkey-stored registration context
-    // (We don't have it here directly, so we'll use a default and let the user
-    // rename via the PATCH endpoint. For now, query the begin_register name
-    // from an earlier context). Since we can't carry the name through the
-    // WebAuthn ceremony, use a sensible default.
-    let name = format!("Passkey {}", &hex::encode(&cred_id_bytes[..4]));
-
-    let row = sqlx::query!(
-        r#"
-        INSERT INTO passkey_credentials (user_id, credential_id, public_key, name)
-        VALUES ($1, $2, $3, $4)
-        RETURNING id, created_at
-        "#,
+    let passkey = sqlx::query_as!(
+        PasskeyResponse,
+        "INSERT INTO passkey_credentials
+             (user_id, credential_id, public_key, name, transports, attestation,
+              aaguid, attested_aaguid, attestation_format, user_verified, backup_eligible,
+              backup_state)
+         VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12)
+         RETURNING id, name, created_at, last_used_at, backup_eligible, backup_state,
+                   transports, aaguid, attestation_format, user_verified",
         auth.user_id,
         cred_id_bytes,
         public_key_bytes,
         name,
+        &transports,
+        attestation,
+        info.aaguid,
+        attested_aaguid,
+        info.format,
+        info.user_verified,
+        info.backup_eligible,
+        info.backup_state,
     )
     .fetch_one(&state.pool)
     .await?;
@@ -202,25 +270,20 @@ async fn complete_register(
             actor_name: auth.user_name.clone(),
             action: "auth.passkey_register".into(),
             resource: "passkey_credential".into(),
-            resource_id: Some(row.id),
+            resource_id: Some(passkey.id),
             project_id: None,
-            detail: Some(serde_json::json!({"name": name})),
+            detail: Some(serde_json::json!({
+                "name": name,
+                "aaguid": info.aaguid,
+                "attested_aaguid": attested_aaguid,
+                "attestation_format": info.format,
+                "policy": policy.as_str(),
+            })),
             ip_addr: auth.ip_addr.clone(),
         },
     );
 
-    Ok((
-        StatusCode::CREATED,
-        Json(PasskeyResponse {
-            id: row.id,
-            name,
-            created_at: row.created_at,
-            last_used_at: None,
-            backup_eligible: false,
-            backup_state: false,
-            transports: vec![],
-        }),
-    ))
+    Ok((StatusCode::CREATED, Json(passkey)))
 }
 
 // ---------------------------------------------------------------------------
@@ -231,30 +294,17 @@ async fn list_passkeys(
     State(state): State<AppState>,
     auth: AuthUser,
 ) -> Result<Json<ListResponse<PasskeyResponse>>, ApiError> {
-    let rows = sqlx::query!(
-        r#"
-        SELECT id, name, created_at, last_used_at, backup_eligible, backup_state, transports
-        FROM passkey_credentials WHERE user_id = $1
-        ORDER BY created_at DESC
-        "#,
+    let items = sqlx::query_as!(
+        PasskeyResponse,
+        "SELECT id, name, created_at, last_used_at, backup_eligible, backup_state,
+                transports, aaguid, attestation_format, user_verified
+         FROM passkey_credentials WHERE user_id = $1
+         ORDER BY created_at DESC",
         auth.user_id,
     )
     .fetch_all(&state.pool)
     .await?;
 
-    let items: Vec<PasskeyResponse> = rows
-        .into_iter()
-        .map(|r| PasskeyResponse {
-            id: r.id,
-            name: r.name,
-            created_at: r.created_at,
-            last_used_at: r.last_used_at,
-            backup_eligible: r.backup_eligible,
-            backup_state: r.backup_state,
-            transports: r.transports,
-        })
-        .collect();
-
     let total = i64::try_from(items.len()).unwrap_or(0);
     Ok(Json(ListResponse { items, total }))
 }
@@ -264,25 +314,19 @@ async fn get_passkey(
     auth: AuthUser,
     Path(id): Path<Uuid>,
 ) -> Result<Json<PasskeyResponse>, ApiError> {
-    let row = sqlx::query(
-        "SELECT id, name, created_at, last_used_at, backup_eligible, backup_state, transports
+    let passkey = sqlx::query_as!(
+        PasskeyResponse,
+        "SELECT id, name, created_at, last_used_at, backup_eligible, backup_state,
+                transports, aaguid, attestation_format, user_verified
          FROM passkey_credentials WHERE id = $1 AND user_id = $2",
+        id,
+        auth.user_id,
     )
-    .bind(id)
-    .bind(auth.user_id)
     .fetch_optional(&state.pool)
     .await?
     .ok_or_else(|| ApiError::NotFound("passkey".into()))?;
 
-    Ok(Json(PasskeyResponse {
-        id: row.get("id"),
-        name: row.get("name"),
-        created_at: row.get("created_at"),
-        last_used_at: row.get("last_used_at"),
-        backup_eligible: row.get("backup_eligible"),
-        backup_state: row.get("backup_state"),
-        transports: row.get("transports"),
-    }))
+    Ok(Json(passkey))
 }
 
 #[tracing::instrument(skip(state), fields(%id, user_id = %auth.user_id), err)]
@@ -330,17 +374,41 @@ async fn delete_passkey(
     auth: AuthUser,
     Path(id): Path<Uuid>,
 ) -> Result<StatusCode, ApiError> {
-    let result = sqlx::query!(
-        "DELETE FROM passkey_credentials WHERE id = $1 AND user_id = $2",
-        id,
+    // Accounts with password login disabled must keep at least one passkey.
+    // The user row is locked so two concurrent deletes cannot both pass the
+    // check and remove the last two keys.
+    let mut tx = state.pool.begin().await?;
+    let password_login_disabled = sqlx::query_scalar!(
+        "SELECT password_login_disabled FROM users WHERE id = $1 FOR UPDATE",
         auth.user_id,
     )
-    .execute(&state.pool)
+    .fetch_one(&mut *tx)
     .await?;
-
-    if result.rows_affected() == 0 {
+    let keys = sqlx::query!(
+        r#"SELECT COALESCE(bool_or(id = $2), false) AS "owned!", COUNT(*) AS "total!"
+           FROM passkey_credentials WHERE user_id = $1"#,
+        auth.user_id,
+        id,
+    )
+    .fetch_one(&mut *tx)
+    .await?;
+    if !keys.owned {
         return Err(ApiError::NotFound("passkey".into()));
     }
+    if password_login_disabled && keys.total == 1 {
+        return Err(ApiError::Conflict(
+            "password login is disabled for this account — cannot delete its last passkey".into(),
+        ));
+    }
+
+    sqlx::query!(
+        "DELETE FROM passkey_credentials WHERE id = $1 AND user_id = $2",
+        id,
+        auth.user_id,
+    )
+    .execute(&mut *tx)
+    .await?;
+    tx.commit().await?;
 
     send_audit(
         &state.audit_tx,
@@ -378,16 +446,30 @@ async fn begin_login(State(state): State<AppState>) -> Result<Json<BeginLoginRes
     }))
 }
 
+/// Why the credential in `row` may not sign its user in, if it may not. A key
+/// enrolled before the user was put under a stricter policy no longer signs
+/// them in.
+fn login_policy_violation(
+    row: &sqlx::postgres::PgRow,
+    hardware_aaguids: &[Uuid],
+) -> Option<String> {
+    use sqlx::Row;
+    let policy: String = row.get("passkey_policy");
+    let policy = PasskeyPolicy::parse(&policy).unwrap_or(PasskeyPolicy::Hardware);
+    let attestation: Option<Vec<u8>> = row.get("attestation");
+    let attested_aaguid: Option<Uuid> = row.get("attested_aaguid");
+    match attestation.as_deref().map(passkey::parse_attestation) {
+        Some(Ok(info)) => policy.violation(&info, attested_aaguid, hardware_aaguids),
+        _ => (policy != PasskeyPolicy::Any).then(|| "no attestation on record".into()),
+    }
+}
+
 #[tracing::instrument(skip(state, body), err)]
 async fn complete_login(
     State(state): State<AppState>,
+    client: ClientInfo,
     Json(body): Json<CompleteLoginRequest>,
 ) -> Result<impl IntoResponse, ApiError> {
-    // A62: No AuthUser in this unauthenticated flow; IP is not available without
-    // ConnectInfo (requires into_make_service_with_connect_info on the router).
-    // Passkey login audit entries use ip_addr: None for now.
-    let ip_addr: Option<String> = None;
-
     // Rate-limit passkey login attempts (global key — challenge_id is per-ceremony
     // and would allow unlimited attempts across ceremonies)
     crate::auth::rate_limit::check_rate(&state.valkey, "passkey_login", "global", 50, 300).await?;
@@ -398,6 +480,7 @@ async fn complete_login(
 
     let cred_rows = sqlx::query(
         "SELECT pc.id, pc.user_id, pc.credential_id, pc.public_key, pc.sign_count, \
+               pc.attestation, pc.attested_aaguid, u.passkey_policy, \
                u.is_active, u.name as user_name, u.user_type, \
                u.display_name, u.email, u.created_at as user_created_at, \
                u.updated_at as user_updated_at \
@@ -455,6 +538,13 @@ async fn complete_login(
     let row_created_at: chrono::DateTime<chrono::Utc> = matched_row.get("user_created_at");
     let row_updated_at: chrono::DateTime<chrono::Utc> = matched_row.get("user_updated_at");
 
+    if let Some(reason) =
+        login_policy_violation(matched_row, &state.config.passkey_hardware_aaguids)
+    {
+        tracing::warn!(credential_id = %row_id, %reason, "passkey login refused by policy");
+        return Err(ApiError::Unauthorized);
+    }
+
     // Clone detection: verify sign count
     let new_counter = auth_result.counter();
     if new_counter > 0 && row_sign_count > 0 && i64::from(new_counter) <= row_sign_count {
@@ -496,13 +586,13 @@ async fn complete_login(
         credential_id: row_id,
     };
 
-    build_passkey_session(&state, &login_user, ip_addr).await
+    build_passkey_session(&state, &login_user, &client).await
 }
 
 async fn build_passkey_session(
     state: &AppState,
     u: &PasskeyLoginUser,
-    ip_addr: Option<String>,
+    client: &ClientInfo,
 ) -> Result<
     (
         StatusCode,
@@ -514,14 +604,7 @@ async fn build_passkey_session(
     let (raw_token, token_hash) = token::generate_session_token();
     let expires_at = chrono::Utc::now() + chrono::Duration::hours(24);
 
-    sqlx::query!(
-        "INSERT INTO auth_sessions (user_id, token_hash, expires_at) VALUES ($1, $2, $3)",
-        u.user_id,
-        token_hash,
-        expires_at,
-    )
-    .execute(&state.pool)
-    .await?;
+    super::users::insert_session(&state.pool, u.user_id, &token_hash, expires_at, client).await?;
 
     send_audit(
         &state.audit_tx,
@@ -533,7 +616,7 @@ async fn build_passkey_session(
             resource_id: None,
             project_id: None,
             detail: Some(serde_json::json!({"credential_id": u.credential_id})),
-            ip_addr,
+            ip_addr: client.ip_addr.clone(),
         },
     );
 
@@ -0,0 +1,141 @@
+// Copyright (c) 2026 Steven Hooker. Exclusively licensed to and distributed by AgentSphere GmbH.
+// SPDX-License-Identifier: BUSL-1.1
+
+use std::collections::{BTreeMap, HashSet};
+use std::str::FromStr;
+
+use axum::extract::State;
+use axum::routing::post;
+use axum::{Json, Router};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use ts_rs::TS;
+
+use crate::auth::middleware::AuthUser;
+use crate::error::ApiError;
+use crate::rbac::{Permission, resolver};
+use crate::store::AppState;
+
+/// Upper bound on checks per request — a page's worth, not a bulk export.
+const MAX_CHECKS: usize = 100;
+
+/// Result key for checks without a project.
+const GLOBAL_KEY: &str = "global";
+
+// ---------------------------------------------------------------------------
+// Types
+// ---------------------------------------------------------------------------
+
+#[derive(Debug, Deserialize)]
+pub struct PermissionCheck {
+    pub permission: String,
+    /// Omit for a global (non-project) check.
+    pub project_id: Option<Uuid>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct PermissionCheckRequest {
+    pub checks: Vec<PermissionCheck>,
+}
+
+#[derive(Debug, Serialize, TS)]
+#[ts(export)]
+pub struct PermissionCheckResponse {
+    /// Keyed by project ID (or `"global"`), then by permission name.
+    pub results: BTreeMap<String, BTreeMap<String, bool>>,
+}
+
+// ---------------------------------------------------------------------------
+// Router
+// ---------------------------------------------------------------------------
+
+pub fn router() -> Router<AppState> {
+    Router::new().route("/api/auth/permissions/check", post(check_permissions))
+}
+
+// ---------------------------------------------------------------------------
+// Handlers
+// ---------------------------------------------------------------------------
+
+/// Resolve many permission checks for the caller in one round trip.
+///
+/// Token scopes and project/workspace boundaries apply exactly as they do for
+/// individual endpoints: anything outside them resolves to `false`.
+#[tracing::instrument(skip(state, body), fields(user_id = %auth.user_id, checks = body.checks.len()), err)]
+async fn check_permissions(
+    State(state): State<AppState>,
+    auth: AuthUser,
+    Json(body): Json<PermissionCheckRequest>,
+) -> Result<Json<PermissionCheckResponse>, ApiError> {
+    if body.checks.is_empty() || body.checks.len() > MAX_CHECKS {
+        return Err(ApiError::BadRequest(format!(
+            "checks must contain between 1 and {MAX_CHECKS} entries"
+        )));
+    }
+
+    let mut checks = Vec::with_capacity(body.checks.len());
+    for check in &body.checks {
+        let perm = Permission::from_str(&check.permission).map_err(|_| {
+            ApiError::BadRequest(format!("unknown permission: {}", check.permission))
+        })?;
+        checks.push((check.project_id, perm));
+    }
+
+    let scopes: Vec<Option<Uuid>> = checks.iter().map(|(pid, _)| *pid).collect();
+    let outside = outside_boundary(&state, &auth, &scopes).await?;
+    let reachable: Vec<Option<Uuid>> = scopes
+        .iter()
+        .filter(|pid| !pid.is_some_and(|p| outside.contains(&p)))
+        .copied()
+        .collect();
+
+    let granted =
+        resolver::effective_permissions_batch(&state.pool, &state.valkey, auth.user_id, &reachable)
+            .await
+            .map_err(ApiError::Internal)?;
+
+    let mut results: BTreeMap<String, BTreeMap<String, bool>> = BTreeMap::new();
+    for (pid, perm) in checks {
+        let allowed = resolver::scope_allows(auth.token_scopes.as_deref(), perm)
+            && granted.get(&pid).is_some_and(|perms| perms.contains(&perm));
+        let key = pid.map_or_else(|| GLOBAL_KEY.to_owned(), |p| p.to_string());
+        results
+            .entry(key)
+            .or_default()
+            .insert(perm.as_str().to_owned(), allowed);
+    }
+
+    Ok(Json(PermissionCheckResponse { results }))
+}
+
+/// Projects among `scopes` that the caller's token boundary excludes.
+async fn outside_boundary(
+    state: &AppState,
+    auth: &AuthUser,
+    scopes: &[Option<Uuid>],
+) -> Result<HashSet<Uuid>, ApiError> {
+    let projects: Vec<Uuid> = scopes.iter().flatten().copied().collect();
+    let mut outside: HashSet<Uuid> = projects
+        .iter()
+        .filter(|pid| auth.check_project_scope(**pid).is_err())
+        .copied()
+        .collect();
+
+    if let Some(scope_wid) = auth.boundary_workspace_id
+        && !projects.is_empty()
+    {
+        let inside: HashSet<Uuid> = sqlx::query_scalar!(
+            "SELECT id FROM projects WHERE id = ANY($1) AND workspace_id = $2 AND is_active = true",
+            &projects,
+            scope_wid,
+        )
+        .fetch_all(&state.pool)
+        .await?
+        .into_iter()
+        .collect();
+        outside.extend(projects.iter().filter(|pid| !inside.contains(pid)));
+    }
+
+    Ok(outside)
+}
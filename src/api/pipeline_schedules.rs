@@ -0,0 +1,341 @@
+// Copyright (c) 2026 Steven Hooker. Exclusively licensed to and distributed by AgentSphere GmbH.
+// SPDX-License-Identifier: BUSL-1.1
+
+use axum::extract::{Path, State};
+use axum::http::StatusCode;
+use axum::response::IntoResponse;
+use axum::routing::get;
+use axum::{Json, Router};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use ts_rs::TS;
+
+use crate::audit::{AuditEntry, send_audit};
+use crate::auth::middleware::AuthUser;
+use crate::error::ApiError;
+use crate::pipeline::cron::CronExpr;
+use crate::store::AppState;
+use crate::validation;
+
+use super::helpers::{ListResponse, require_project_read, require_project_write};
+
+const MAX_SCHEDULES_PER_PROJECT: i64 = 20;
+
+// ---------------------------------------------------------------------------
+// Types
+// ---------------------------------------------------------------------------
+
+#[derive(Debug, Deserialize)]
+pub struct CreateScheduleRequest {
+    /// Five-field cron expression, evaluated in UTC.
+    pub cron: String,
+    /// Branch to build. Defaults to the project's default branch.
+    pub git_ref: Option<String>,
+    pub enabled: Option<bool>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct UpdateScheduleRequest {
+    pub cron: Option<String>,
+    pub git_ref: Option<String>,
+    pub enabled: Option<bool>,
+}
+
+#[derive(Debug, Serialize, TS)]
+#[ts(export, rename = "PipelineSchedule")]
+pub struct ScheduleResponse {
+    pub id: Uuid,
+    pub project_id: Uuid,
+    pub cron: String,
+    pub git_ref: String,
+    pub enabled: bool,
+    pub created_by: Option<Uuid>,
+    /// `None` while the schedule is disabled.
+    pub next_run_at: Option<DateTime<Utc>>,
+    pub last_run_at: Option<DateTime<Utc>>,
+    pub last_pipeline_id: Option<Uuid>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+fn parse_cron(cron: &str) -> Result<CronExpr, ApiError> {
+    validation::check_length("cron", cron, 1, 100)?;
+    CronExpr::parse(cron).map_err(|e| ApiError::BadRequest(format!("invalid cron expression: {e}")))
+}
+
+/// Accept `main` or `refs/heads/main`; store the bare branch name.
+fn normalize_git_ref(git_ref: &str) -> Result<String, ApiError> {
+    let branch = git_ref.strip_prefix("refs/heads/").unwrap_or(git_ref);
+    validation::check_branch_name(branch)?;
+    Ok(branch.to_owned())
+}
+
+/// When an enabled schedule next fires.
+fn next_run_at(cron: &CronExpr, enabled: bool) -> Option<DateTime<Utc>> {
+    if enabled {
+        cron.next_after(Utc::now())
+    } else {
+        None
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Router
+// ---------------------------------------------------------------------------
+
+pub fn router() -> Router<AppState> {
+    Router::new()
+        .route(
+            "/api/projects/{id}/pipeline-schedules",
+            get(list_schedules).post(create_schedule),
+        )
+        .route(
+            "/api/projects/{id}/pipeline-schedules/{schedule_id}",
+            get(get_schedule)
+                .patch(update_schedule)
+                .delete(delete_schedule),
+        )
+}
+
+// ---------------------------------------------------------------------------
+// Handlers
+// ---------------------------------------------------------------------------
+
+async fn list_schedules(
+    State(state): State<AppState>,
+    auth: AuthUser,
+    Path(id): Path<Uuid>,
+) -> Result<Json<ListResponse<ScheduleResponse>>, ApiError> {
+    require_project_read(&state, &auth, id).await?;
+
+    let items = sqlx::query_as!(
+        ScheduleResponse,
+        "SELECT id, project_id, cron, git_ref, enabled, created_by, next_run_at,
+                last_run_at, last_pipeline_id, created_at, updated_at
+         FROM pipeline_schedules WHERE project_id = $1 ORDER BY created_at",
+        id,
+    )
+    .fetch_all(&state.pool)
+    .await?;
+
+    let total = i64::try_from(items.len()).unwrap_or(i64::MAX);
+    Ok(Json(ListResponse { items, total }))
+}
+
+async fn get_schedule(
+    State(state): State<AppState>,
+    auth: AuthUser,
+    Path((id, schedule_id)): Path<(Uuid, Uuid)>,
+) -> Result<Json<ScheduleResponse>, ApiError> {
+    require_project_read(&state, &auth, id).await?;
+
+    let schedule = sqlx::query_as!(
+        ScheduleResponse,
+        "SELECT id, project_id, cron, git_ref, enabled, created_by, next_run_at,
+                last_run_at, last_pipeline_id, created_at, updated_at
+         FROM pipeline_schedules WHERE id = $1 AND project_id = $2",
+        schedule_id,
+        id,
+    )
+    .fetch_optional(&state.pool)
+    .await?
+    .ok_or_else(|| ApiError::NotFound("pipeline schedule".into()))?;
+
+    Ok(Json(schedule))
+}
+
+#[tracing::instrument(skip(state, body), fields(%id), err)]
+async fn create_schedule(
+    State(state): State<AppState>,
+    auth: AuthUser,
+    Path(id): Path<Uuid>,
+    Json(body): Json<CreateScheduleRequest>,
+) -> Result<impl IntoResponse, ApiError> {
+    let cron = parse_cron(&body.cron)?;
+    let git_ref = body.git_ref.as_deref().map(normalize_git_ref).transpose()?;
+    require_project_write(&state, &auth, id).await?;
+
+    let count = sqlx::query_scalar!(
+        r#"SELECT COUNT(*) as "count!" FROM pipeline_schedules WHERE project_id = $1"#,
+        id,
+    )
+    .fetch_one(&state.pool)
+    .await?;
+    if count >= MAX_SCHEDULES_PER_PROJECT {
+        return Err(ApiError::BadRequest(format!(
+            "max {MAX_SCHEDULES_PER_PROJECT} pipeline schedules per project"
+        )));
+    }
+
+    let enabled = body.enabled.unwrap_or(true);
+    let row = sqlx::query_as!(
+        ScheduleResponse,
+        "INSERT INTO pipeline_schedules (project_id, cron, git_ref, enabled, created_by, next_run_at)
+         SELECT p.id, $2, COALESCE($3, p.default_branch), $4, $5, $6
+         FROM projects p WHERE p.id = $1
+         RETURNING id, project_id, cron, git_ref, enabled, created_by, next_run_at,
+                   last_run_at, last_pipeline_id, created_at, updated_at",
+        id,
+        body.cron.trim(),
+        git_ref,
+        enabled,
+        auth.user_id,
+        next_run_at(&cron, enabled),
+    )
+    .fetch_optional(&state.pool)
+    .await?
+    .ok_or_else(|| ApiError::NotFound("project".into()))?;
+
+    send_audit(
+        &state.audit_tx,
+        AuditEntry {
+            actor_id: auth.user_id,
+            actor_name: auth.user_name.clone(),
+            action: "pipeline_schedule.create".into(),
+            resource: "pipeline_schedule".into(),
+            resource_id: Some(row.id),
+            project_id: Some(id),
+            detail: Some(serde_json::json!({"cron": row.cron, "git_ref": row.git_ref})),
+            ip_addr: auth.ip_addr.clone(),
+        },
+    );
+
+    Ok((StatusCode::CREATED, Json(row)))
+}
+
+#[tracing::instrument(skip(state, body), fields(%id, %schedule_id), err)]
+async fn update_schedule(
+    State(state): State<AppState>,
+    auth: AuthUser,
+    Path((id, schedule_id)): Path<(Uuid, Uuid)>,
+    Json(body): Json<UpdateScheduleRequest>,
+) -> Result<Json<ScheduleResponse>, ApiError> {
+    if let Some(ref c) = body.cron {
+        parse_cron(c)?;
+    }
+    let git_ref = body.git_ref.as_deref().map(normalize_git_ref).transpose()?;
+    require_project_write(&state, &auth, id).await?;
+
+    let mut tx = state.pool.begin().await?;
+    let current = sqlx::query!(
+        "SELECT cron, enabled FROM pipeline_schedules
+         WHERE id = $1 AND project_id = $2 FOR UPDATE",
+        schedule_id,
+        id,
+    )
+    .fetch_optional(&mut *tx)
+    .await?
+    .ok_or_else(|| ApiError::NotFound("pipeline schedule".into()))?;
+
+    let cron_str = body
+        .cron
+        .as_deref()
+        .map_or(current.cron, |c| c.trim().to_owned());
+    let enabled = body.enabled.unwrap_or(current.enabled);
+    let next = next_run_at(&parse_cron(&cron_str)?, enabled);
+
+    let row = sqlx::query_as!(
+        ScheduleResponse,
+        "UPDATE pipeline_schedules SET
+             cron = $2,
+             git_ref = COALESCE($3, git_ref),
+             enabled = $4,
+             next_run_at = $5
+         WHERE id = $1
+         RETURNING id, project_id, cron, git_ref, enabled, created_by, next_run_at,
+                   last_run_at, last_pipeline_id, created_at, updated_at",
+        schedule_id,
+        cron_str,
+        git_ref,
+        enabled,
+        next,
+    )
+    .fetch_one(&mut *tx)
+    .await?;
+    tx.commit().await?;
+
+    send_audit(
+        &state.audit_tx,
+        AuditEntry {
+            actor_id: auth.user_id,
+            actor_name: auth.user_name.clone(),
+            action: "pipeline_schedule.update".into(),
+            resource: "pipeline_schedule".into(),
+            resource_id: Some(schedule_id),
+            project_id: Some(id),
+            detail: Some(
+                serde_json::json!({"cron": row.cron, "git_ref": row.git_ref, "enabled": row.enabled}),
+            ),
+            ip_addr: auth.ip_addr.clone(),
+        },
+    );
+
+    Ok(Json(row))
+}
+
+#[tracing::instrument(skip(state), fields(%id, %schedule_id), err)]
+async fn delete_schedule(
+    State(state): State<AppState>,
+    auth: AuthUser,
+    Path((id, schedule_id)): Path<(Uuid, Uuid)>,
+) -> Result<StatusCode, ApiError> {
+    require_project_write(&state, &auth, id).await?;
+
+    let result = sqlx::query!(
+        "DELETE FROM pipeline_schedules WHERE id = $1 AND project_id = $2",
+        schedule_id,
+        id,
+    )
+    .execute(&state.pool)
+    .await?;
+    if result.rows_affected() == 0 {
+        return Err(ApiError::NotFound("pipeline schedule".into()));
+    }
+
+    send_audit(
+        &state.audit_tx,
+        AuditEntry {
+            actor_id: auth.user_id,
+            actor_name: auth.user_name.clone(),
+            action: "pipeline_schedule.delete".into(),
+            resource: "pipeline_schedule".into(),
+            resource_id: Some(schedule_id),
+            project_id: Some(id),
+            detail: None,
+            ip_addr: auth.ip_addr.clone(),
+        },
+    );
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn git_ref_normalized_to_branch() {
+        assert_eq!(normalize_git_ref("refs/heads/main").unwrap(), "main");
+        assert_eq!(normalize_git_ref("release/1.x").unwrap(), "release/1.x");
+        assert!(normalize_git_ref("main..evil").is_err());
+        assert!(normalize_git_ref("").is_err());
+    }
+
+    #[test]
+    fn invalid_cron_is_bad_request() {
+        assert!(parse_cron("0 2 * * *").is_ok());
+        assert!(matches!(
+            parse_cron("0 25 * * *"),
+            Err(ApiError::BadRequest(msg)) if msg.contains("hour")
+        ));
+    }
+
+    #[test]
+    fn disabled_schedule_has_no_next_run() {
+        let cron = CronExpr::parse("0 2 * * *").unwrap();
+        assert!(next_run_at(&cron, true).is_some());
+        assert!(next_run_at(&cron, false).is_none());
+    }
+}
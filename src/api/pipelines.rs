@@ -1,6 +1,9 @@
 // Copyright (c) 2026 Steven Hooker. Exclusively licensed to and distributed by AgentSphere GmbH.
 // SPDX-License-Identifier: BUSL-1.1
 
+use std::collections::BTreeMap;
+use std::fmt::Write as _;
+
 use axum::body::Body;
 use axum::extract::{Path, Query, State};
 use axum::http::StatusCode;
@@ -16,6 +19,9 @@ use ts_rs::TS;
 use crate::audit::{AuditEntry, send_audit};
 use crate::auth::middleware::AuthUser;
 use crate::error::ApiError;
+use crate::pipeline::PipelinePriority;
+use crate::pipeline::logs;
+use crate::secrets::engine;
 use crate::store::AppState;
 use crate::validation;
 
@@ -26,6 +32,34 @@ use crate::validation;
 #[derive(Debug, Deserialize)]
 pub struct TriggerRequest {
     pub git_ref: String,
+    /// Extra environment variables exposed to every step of this run. Names the
+    /// platform already sets are exposed with a `TRIGGER_` prefix.
+    #[serde(default)]
+    pub variables: BTreeMap<String, String>,
+    /// `high`, `normal` or `low`; manual runs default to `high`.
+    pub priority: Option<String>,
+}
+
+/// Most variables accepted on a manual trigger.
+const MAX_TRIGGER_VARIABLES: usize = 50;
+
+/// Prefix for trigger variables that would clobber a platform variable.
+const TRIGGER_VARIABLE_PREFIX: &str = "TRIGGER_";
+
+/// Largest `.platform.yaml` accepted by the validate endpoint.
+const MAX_DEFINITION_BYTES: usize = 256 * 1024;
+
+#[derive(Debug, Deserialize)]
+pub struct ValidateRequest {
+    /// `.platform.yaml` contents.
+    pub content: String,
+}
+
+#[derive(Debug, Serialize, TS)]
+#[ts(export, rename = "PipelineValidation")]
+pub struct ValidateResponse {
+    pub valid: bool,
+    pub errors: Vec<crate::pipeline::definition::LintIssue>,
 }
 
 #[derive(Debug, Deserialize)]
@@ -37,6 +71,12 @@ pub struct ListPipelinesParams {
     pub trigger: Option<String>,
 }
 
+#[derive(Debug, Deserialize)]
+pub struct PipelineLogsQuery {
+    /// Only lines logged after this instant, for incremental polling.
+    pub since: Option<DateTime<Utc>>,
+}
+
 #[derive(Debug, Serialize, TS)]
 #[ts(export, rename = "Pipeline")]
 pub struct PipelineResponse {
@@ -73,9 +113,20 @@ pub struct StepResponse {
     pub log_ref: Option<String>,
     pub gate: bool,
     pub depends_on: Vec<String>,
+    /// Why the step was skipped, when `status` is `skipped`.
+    pub skip_reason: Option<String>,
     pub created_at: DateTime<Utc>,
 }
 
+struct StepLogRow {
+    name: String,
+    status: String,
+    log_ref: Option<String>,
+    exit_code: Option<i32>,
+    duration_ms: Option<i32>,
+    finished_at: Option<DateTime<Utc>>,
+}
+
 #[derive(Debug, Serialize, TS)]
 #[ts(export, rename = "Artifact")]
 pub struct ArtifactResponse {
@@ -148,6 +199,10 @@ pub fn router() -> Router<AppState> {
             "/api/projects/{id}/pipelines",
             get(list_pipelines).post(trigger_pipeline),
         )
+        .route(
+            "/api/projects/{id}/pipelines/validate",
+            axum::routing::post(validate_definition),
+        )
         .route(
             "/api/projects/{id}/pipelines/{pipeline_id}",
             get(get_pipeline),
@@ -156,6 +211,10 @@ pub fn router() -> Router<AppState> {
             "/api/projects/{id}/pipelines/{pipeline_id}/cancel",
             axum::routing::post(cancel_pipeline),
         )
+        .route(
+            "/api/projects/{id}/pipelines/{pipeline_id}/logs",
+            get(get_pipeline_logs),
+        )
         .route(
             "/api/projects/{id}/pipelines/{pipeline_id}/steps/{step_id}/logs",
             get(get_step_logs),
@@ -183,6 +242,76 @@ pub fn router() -> Router<AppState> {
 // Handlers
 // ---------------------------------------------------------------------------
 
+/// A shell-style variable name that doesn't shadow the platform's own
+/// pipeline variables.
+pub(crate) fn check_variable_name(name: &str) -> Result<(), ApiError> {
+    check_variable_syntax(name)?;
+    if is_platform_variable(name) {
+        return Err(ApiError::BadRequest(format!(
+            "variable '{name}' is reserved by the platform"
+        )));
+    }
+    Ok(())
+}
+
+fn check_variable_syntax(name: &str) -> Result<(), ApiError> {
+    validation::check_length("variable name", name, 1, 255)?;
+    let valid = name.starts_with(|c: char| c.is_ascii_alphabetic() || c == '_')
+        && name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_');
+    if !valid {
+        return Err(ApiError::BadRequest(format!(
+            "invalid variable name '{name}'"
+        )));
+    }
+    Ok(())
+}
+
+fn is_platform_variable(name: &str) -> bool {
+    name.starts_with("PLATFORM_") || crate::pipeline::executor::is_reserved_pipeline_env_var(name)
+}
+
+/// Validate trigger variables and rename the ones that would clobber a
+/// platform variable to `TRIGGER_<name>`.
+fn prepare_trigger_variables(
+    variables: &BTreeMap<String, String>,
+) -> Result<BTreeMap<String, String>, ApiError> {
+    if variables.len() > MAX_TRIGGER_VARIABLES {
+        return Err(ApiError::BadRequest(format!(
+            "at most {MAX_TRIGGER_VARIABLES} variables allowed"
+        )));
+    }
+    let mut prepared = BTreeMap::new();
+    for (name, value) in variables {
+        check_variable_syntax(name)?;
+        validation::check_length("variable value", value, 0, 10_000)?;
+        let exposed = if is_platform_variable(name) {
+            let prefixed = format!("{TRIGGER_VARIABLE_PREFIX}{name}");
+            if variables.contains_key(&prefixed) {
+                return Err(ApiError::BadRequest(format!(
+                    "variable '{name}' is exposed as '{prefixed}', which is also given"
+                )));
+            }
+            prefixed
+        } else {
+            name.clone()
+        };
+        prepared.insert(exposed, value.clone());
+    }
+    Ok(prepared)
+}
+
+fn get_master_key(state: &AppState) -> Result<engine::MasterKey, ApiError> {
+    let hex_str = state
+        .config
+        .master_key
+        .as_deref()
+        .ok_or_else(|| ApiError::ServiceUnavailable("secrets engine not configured".into()))?;
+    engine::parse_master_key(hex_str).map_err(|e| {
+        tracing::error!(error = %e, "invalid master key configuration");
+        ApiError::ServiceUnavailable("secrets engine misconfigured".into())
+    })
+}
+
 #[tracing::instrument(skip(state, body), fields(%id), err)]
 async fn trigger_pipeline(
     State(state): State<AppState>,
@@ -202,6 +331,12 @@ async fn trigger_pipeline(
 
     require_project_write(&state, &auth, id).await?;
     validation::check_branch_name(&body.git_ref)?;
+    let variables = prepare_trigger_variables(&body.variables)?;
+    let priority = match body.priority.as_deref() {
+        None => PipelinePriority::High,
+        Some(p) => PipelinePriority::parse(p)
+            .ok_or_else(|| ApiError::BadRequest("priority must be high, normal, or low".into()))?,
+    };
 
     let project = sqlx::query!(
         "SELECT repo_path FROM projects WHERE id = $1 AND is_active = true",
@@ -216,16 +351,28 @@ async fn trigger_pipeline(
             .repo_path
             .ok_or_else(|| ApiError::BadRequest("project has no repo path".into()))?,
     );
-    let pipeline_id = crate::pipeline::trigger::on_api(
-        &state.pool,
-        &repo_path,
-        id,
-        &body.git_ref,
-        auth.user_id,
-        &state.config.kaniko_image,
-    )
-    .await
-    .map_err(ApiError::from)?;
+    // Trigger variables may carry credentials: stored encrypted like secrets
+    let encrypted_variables = if variables.is_empty() {
+        None
+    } else {
+        let master_key = get_master_key(&state)?;
+        Some(
+            crate::pipeline::trigger::encrypt_variables(&master_key, &variables)
+                .map_err(ApiError::Internal)?,
+        )
+    };
+    let params = crate::pipeline::trigger::ApiTriggerParams {
+        project_id: id,
+        user_id: auth.user_id,
+        repo_path,
+        git_ref: body.git_ref.clone(),
+        encrypted_variables,
+        priority,
+    };
+    let pipeline_id =
+        crate::pipeline::trigger::on_api(&state.pool, &params, &state.config.kaniko_image)
+            .await
+            .map_err(ApiError::from)?;
 
     // Notify executor
     crate::pipeline::trigger::notify_executor(&state, pipeline_id).await;
@@ -239,7 +386,12 @@ async fn trigger_pipeline(
             resource: "pipeline".into(),
             resource_id: Some(pipeline_id),
             project_id: Some(id),
-            detail: Some(serde_json::json!({"git_ref": body.git_ref, "trigger": "api"})),
+            detail: Some(serde_json::json!({
+                "git_ref": body.git_ref,
+                "trigger": "api",
+                "priority": priority.as_str(),
+                "variables": variables.keys().collect::<Vec<_>>(),
+            })),
             ip_addr: auth.ip_addr.clone(),
         },
     );
@@ -249,6 +401,24 @@ async fn trigger_pipeline(
     Ok((StatusCode::CREATED, Json(pipeline)))
 }
 
+/// Lint a pipeline definition without creating a pipeline.
+#[tracing::instrument(skip(state, body), fields(%id), err)]
+async fn validate_definition(
+    State(state): State<AppState>,
+    auth: AuthUser,
+    Path(id): Path<Uuid>,
+    Json(body): Json<ValidateRequest>,
+) -> Result<Json<ValidateResponse>, ApiError> {
+    require_project_read(&state, &auth, id).await?;
+    validation::check_length("content", &body.content, 1, MAX_DEFINITION_BYTES)?;
+
+    let errors = crate::pipeline::definition::lint(&body.content);
+    Ok(Json(ValidateResponse {
+        valid: errors.is_empty(),
+        errors,
+    }))
+}
+
 async fn list_pipelines(
     State(state): State<AppState>,
     auth: AuthUser,
@@ -334,7 +504,7 @@ async fn get_pipeline(
     let steps = sqlx::query!(
         r#"
         SELECT id, step_order, name, image, status, exit_code, duration_ms, log_ref,
-               gate, depends_on, created_at
+               gate, depends_on, skip_reason, created_at
         FROM pipeline_steps
         WHERE pipeline_id = $1
         ORDER BY step_order ASC
@@ -357,6 +527,7 @@ async fn get_pipeline(
             log_ref: s.log_ref,
             gate: s.gate,
             depends_on: s.depends_on,
+            skip_reason: s.skip_reason,
             created_at: s.created_at,
         })
         .collect();
@@ -467,6 +638,154 @@ async fn get_step_logs(
     }
 }
 
+/// All steps' logs in step order, each under a `==> [n/total] name (status)`
+/// header, with kubelet timestamps. With `since`, only newer lines are
+/// returned and steps without any are left out.
+async fn get_pipeline_logs(
+    State(state): State<AppState>,
+    auth: AuthUser,
+    Path((id, pipeline_id)): Path<(Uuid, Uuid)>,
+    Query(params): Query<PipelineLogsQuery>,
+) -> Result<Response, ApiError> {
+    require_project_read(&state, &auth, id).await?;
+
+    let belongs = sqlx::query_scalar!(
+        r#"SELECT EXISTS (SELECT 1 FROM pipelines WHERE id = $1 AND project_id = $2) AS "exists!""#,
+        pipeline_id,
+        id,
+    )
+    .fetch_one(&state.pool)
+    .await?;
+    if !belongs {
+        return Err(ApiError::NotFound("pipeline".into()));
+    }
+
+    let steps = sqlx::query_as!(
+        StepLogRow,
+        r"
+        SELECT name, status, log_ref, exit_code, duration_ms, finished_at
+        FROM pipeline_steps
+        WHERE pipeline_id = $1
+        ORDER BY step_order, created_at
+        ",
+        pipeline_id
+    )
+    .fetch_all(&state.pool)
+    .await?;
+
+    let since = params.since;
+    let mut out = String::new();
+    for (i, step) in steps.iter().enumerate() {
+        let text = if step.status == "running" {
+            Some(live_step_logs(&state, pipeline_id, &step.name, since).await?)
+        } else if let Some(log_ref) = &step.log_ref {
+            stored_step_logs(&state, log_ref, step.finished_at).await?
+        } else {
+            None
+        };
+        let lines = text
+            .as_deref()
+            .map(|t| logs::lines_since(t, since))
+            .unwrap_or_default();
+        if since.is_some() && lines.is_empty() {
+            continue;
+        }
+
+        out.push_str(&logs::step_header(
+            i + 1,
+            steps.len(),
+            &step.name,
+            &step.status,
+            step.exit_code,
+            step.duration_ms,
+        ));
+        out.push('\n');
+        for line in lines {
+            out.push_str(line);
+            out.push('\n');
+        }
+    }
+
+    Ok(Response::builder()
+        .header("content-type", "text/plain; charset=utf-8")
+        .body(Body::from(out))
+        .expect("infallible: valid status and header"))
+}
+
+/// A finished step's timestamped logs. Logs stored without timestamps get
+/// the step's finish time on every line.
+async fn stored_step_logs(
+    state: &AppState,
+    log_ref: &str,
+    finished_at: Option<DateTime<Utc>>,
+) -> Result<Option<String>, ApiError> {
+    let read = |path: String| async move {
+        match state.minio.read(&path).await {
+            Ok(data) => Ok(Some(String::from_utf8_lossy(&data.to_vec()).into_owned())),
+            Err(e) if e.kind() == opendal::ErrorKind::NotFound => Ok(None),
+            Err(e) => Err(ApiError::from(e)),
+        }
+    };
+
+    if let Some(text) = read(logs::timestamped_path(log_ref)).await? {
+        return Ok(Some(text));
+    }
+    let Some(text) = read(log_ref.to_owned()).await? else {
+        return Ok(None);
+    };
+    Ok(Some(match finished_at {
+        Some(finished_at) => {
+            let ts = finished_at.to_rfc3339_opts(chrono::SecondsFormat::Nanos, true);
+            text.lines().fold(String::new(), |mut out, l| {
+                let _ = writeln!(out, "{ts} {l}");
+                out
+            })
+        }
+        None => text,
+    }))
+}
+
+/// A running step's logs so far, timestamped and redacted.
+async fn live_step_logs(
+    state: &AppState,
+    pipeline_id: Uuid,
+    step_name: &str,
+    since: Option<DateTime<Utc>>,
+) -> Result<String, ApiError> {
+    let pods: kube::Api<k8s_openapi::api::core::v1::Pod> =
+        kube::Api::namespaced(state.kube.clone(), &state.config.pipeline_namespace);
+    let pod_name = format!("pl-{}-{}", &pipeline_id.to_string()[..8], slug(step_name));
+
+    // The kubelet only filters to whole seconds; `lines_since` does the rest
+    let log_params = kube::api::LogParams {
+        container: Some("step".into()),
+        timestamps: true,
+        since_seconds: since.map(|s| (Utc::now() - s).num_seconds().max(0) + 1),
+        ..Default::default()
+    };
+    let logs = match pods.logs(&pod_name, &log_params).await {
+        Ok(logs) => logs,
+        Err(kube::Error::Api(err_resp)) if err_resp.code == 404 => return Ok(String::new()),
+        Err(e) => {
+            tracing::warn!(error = %e, %pipeline_id, step = step_name, "failed to read pod logs");
+            return Ok(String::new());
+        }
+    };
+
+    let master_key = state
+        .config
+        .master_key
+        .as_deref()
+        .and_then(|k| crate::secrets::engine::parse_master_key(k).ok());
+    let masked = crate::pipeline::variable_groups::masked_values_for_pipeline(
+        &state.pool,
+        master_key.as_ref(),
+        pipeline_id,
+    )
+    .await?;
+    Ok(crate::pipeline::variable_groups::redact(&logs, &masked))
+}
+
 async fn stream_live_logs(
     state: &AppState,
     pipeline_id: Uuid,
@@ -485,10 +804,24 @@ async fn stream_live_logs(
     };
 
     match pods.logs(&pod_name, &log_params).await {
-        Ok(logs) => Ok(Response::builder()
-            .header("content-type", "text/plain; charset=utf-8")
-            .body(Body::from(logs))
-            .expect("infallible: valid status and header")),
+        Ok(logs) => {
+            let master_key = state
+                .config
+                .master_key
+                .as_deref()
+                .and_then(|k| crate::secrets::engine::parse_master_key(k).ok());
+            let masked = crate::pipeline::variable_groups::masked_values_for_pipeline(
+                &state.pool,
+                master_key.as_ref(),
+                pipeline_id,
+            )
+            .await?;
+            let logs = crate::pipeline::variable_groups::redact(&logs, &masked);
+            Ok(Response::builder()
+                .header("content-type", "text/plain; charset=utf-8")
+                .body(Body::from(logs))
+                .expect("infallible: valid status and header"))
+        }
         Err(kube::Error::Api(err_resp)) if err_resp.code == 404 => Ok(Response::builder()
             .header("content-type", "text/plain; charset=utf-8")
             .body(Body::from("Logs not yet available — pod not started"))
@@ -815,7 +1148,7 @@ use crate::pipeline::slug;
 
 /// Sanitize a filename for use in Content-Disposition headers.
 /// Only allows alphanumeric characters, hyphens, underscores, and dots.
-fn sanitize_filename(name: &str) -> String {
+pub(crate) fn sanitize_filename(name: &str) -> String {
     let sanitized: String = name
         .chars()
         .filter(|c| c.is_ascii_alphanumeric() || *c == '-' || *c == '_' || *c == '.')
@@ -831,6 +1164,71 @@ fn sanitize_filename(name: &str) -> String {
 mod tests {
     use super::*;
 
+    fn vars(pairs: &[(&str, &str)]) -> BTreeMap<String, String> {
+        pairs
+            .iter()
+            .map(|(k, v)| ((*k).to_owned(), (*v).to_owned()))
+            .collect()
+    }
+
+    #[test]
+    fn trigger_variables_accepts_valid() {
+        assert!(
+            prepare_trigger_variables(&BTreeMap::new())
+                .unwrap()
+                .is_empty()
+        );
+        let vars_in = vars(&[("DEPLOY_ENV", "staging"), ("_x1", "")]);
+        assert_eq!(prepare_trigger_variables(&vars_in).unwrap(), vars_in);
+    }
+
+    #[test]
+    fn trigger_variables_rejects_bad_names() {
+        for name in ["", "1ABC", "DEPLOY-ENV", "A B", "DÉPLOY"] {
+            assert!(
+                prepare_trigger_variables(&vars(&[(name, "x")])).is_err(),
+                "'{name}' should be rejected"
+            );
+        }
+    }
+
+    #[test]
+    fn trigger_variables_prefixes_platform_names() {
+        let prepared = prepare_trigger_variables(&vars(&[
+            ("COMMIT_SHA", "a"),
+            ("PATH", "b"),
+            ("PLATFORM_PROJECT_ID", "c"),
+            ("DEPLOY_ENV", "d"),
+        ]))
+        .unwrap();
+        assert_eq!(
+            prepared,
+            vars(&[
+                ("TRIGGER_COMMIT_SHA", "a"),
+                ("TRIGGER_PATH", "b"),
+                ("TRIGGER_PLATFORM_PROJECT_ID", "c"),
+                ("DEPLOY_ENV", "d"),
+            ])
+        );
+    }
+
+    #[test]
+    fn trigger_variables_rejects_prefix_collision() {
+        let clash = vars(&[("COMMIT_SHA", "a"), ("TRIGGER_COMMIT_SHA", "b")]);
+        assert!(prepare_trigger_variables(&clash).is_err());
+    }
+
+    #[test]
+    fn trigger_variables_limits() {
+        let too_many: BTreeMap<String, String> = (0..=MAX_TRIGGER_VARIABLES)
+            .map(|i| (format!("V{i}"), String::new()))
+            .collect();
+        assert!(prepare_trigger_variables(&too_many).is_err());
+        assert!(prepare_trigger_variables(&vars(&[("V", "a\0b")])).is_err());
+        let long = "x".repeat(10_001);
+        assert!(prepare_trigger_variables(&vars(&[("V", &long)])).is_err());
+    }
+
     #[test]
     fn sanitize_filename_preserves_normal() {
         assert_eq!(sanitize_filename("report.tar.gz"), "report.tar.gz");
@@ -28,7 +28,7 @@ use crate::store::AppState;
 static PREVIEW_CLIENT: LazyLock<reqwest::Client> = LazyLock::new(|| {
     reqwest::Client::builder()
         .connect_timeout(std::time::Duration::from_secs(5))
-        .timeout(std::time::Duration::from_secs(120))
+        .timeout(std::time::Duration::from_mins(2))
         .redirect(reqwest::redirect::Policy::none())
         .no_proxy()
         .build()
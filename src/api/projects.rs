@@ -4,7 +4,7 @@
 use axum::extract::{Path, Query, State};
 use axum::http::StatusCode;
 use axum::response::IntoResponse;
-use axum::routing::get;
+use axum::routing::{get, post};
 use axum::{Json, Router};
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
@@ -51,6 +51,11 @@ pub struct UpdateProjectRequest {
     pub agent_image: Option<String>,
 }
 
+#[derive(Debug, Deserialize)]
+pub struct TransferProjectRequest {
+    pub new_owner_id: Uuid,
+}
+
 #[derive(Debug, Deserialize)]
 pub struct ListProjectsParams {
     pub limit: Option<i64>,
@@ -58,6 +63,8 @@ pub struct ListProjectsParams {
     pub owner_id: Option<Uuid>,
     pub visibility: Option<String>,
     pub search: Option<String>,
+    /// Include archived projects (excluded by default).
+    pub include_archived: Option<bool>,
 }
 
 #[derive(Debug, Serialize, TS)]
@@ -74,6 +81,8 @@ pub struct ProjectResponse {
     pub namespace_slug: String,
     pub agent_image: Option<String>,
     pub is_active: bool,
+    /// Set while the project is archived (read-only).
+    pub archived_at: Option<DateTime<Utc>>,
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
 }
@@ -140,6 +149,7 @@ struct ProjectRow {
     namespace_slug: String,
     agent_image: Option<String>,
     is_active: bool,
+    archived_at: Option<DateTime<Utc>>,
     created_at: DateTime<Utc>,
     updated_at: DateTime<Utc>,
 }
@@ -161,7 +171,7 @@ async fn try_insert_project(
         INSERT INTO projects (owner_id, name, display_name, description, visibility, default_branch, repo_path, workspace_id, namespace_slug)
         VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9)
         RETURNING id, owner_id, workspace_id, name, display_name, description, visibility, default_branch,
-                  namespace_slug, agent_image, is_active, created_at, updated_at
+                  namespace_slug, agent_image, is_active, archived_at, created_at, updated_at
         "#,
         auth.user_id,
         body.name,
@@ -277,6 +287,7 @@ fn project_row_to_response(p: ProjectRow) -> ProjectResponse {
         namespace_slug: p.namespace_slug,
         agent_image: p.agent_image,
         is_active: p.is_active,
+        archived_at: p.archived_at,
         created_at: p.created_at,
         updated_at: p.updated_at,
     }
@@ -295,12 +306,46 @@ pub fn router() -> Router<AppState> {
                 .patch(update_project)
                 .delete(delete_project),
         )
+        .route("/api/projects/{id}/transfer", post(transfer_project))
+        .route("/api/projects/{id}/archive", post(archive_project))
+        .route("/api/projects/{id}/unarchive", post(unarchive_project))
 }
 
 // ---------------------------------------------------------------------------
 // Helpers
 // ---------------------------------------------------------------------------
 
+/// Gate for ownership-level actions (transfer, archive): the caller must own
+/// the project or be an admin. Returns the current owner's ID.
+async fn require_owner_or_admin(
+    state: &AppState,
+    auth: &AuthUser,
+    id: Uuid,
+) -> Result<Uuid, ApiError> {
+    // Enforce hard project scope from API token
+    auth.check_project_scope(id)?;
+
+    let current = sqlx::query!(
+        "SELECT owner_id, workspace_id FROM projects WHERE id = $1 AND is_active = true",
+        id,
+    )
+    .fetch_optional(&state.pool)
+    .await?
+    .ok_or_else(|| ApiError::NotFound("project".into()))?;
+
+    // Enforce hard workspace scope from API token
+    if let Some(scope_wid) = auth.boundary_workspace_id
+        && current.workspace_id != scope_wid
+    {
+        return Err(ApiError::NotFound("project".into()));
+    }
+
+    if current.owner_id != auth.user_id {
+        require_admin(state, auth).await?;
+    }
+    Ok(current.owner_id)
+}
+
 /// Resolve workspace: use explicit ID (validating membership) or auto-assign the user's default.
 async fn resolve_workspace(
     pool: &PgPool,
@@ -495,6 +540,7 @@ async fn list_projects(
 ) -> Result<Json<ListResponse<ProjectResponse>>, ApiError> {
     let limit = params.limit.unwrap_or(50).clamp(0, 100);
     let offset = params.offset.unwrap_or(0).max(0);
+    let include_archived = params.include_archived.unwrap_or(false);
     let search_pattern = params.search.as_deref().map(|s| {
         let escaped = s.replace('%', "\\%").replace('_', "\\_");
         format!("%{escaped}%")
@@ -513,6 +559,7 @@ async fn list_projects(
           AND ($3::text IS NULL OR name ILIKE $3)
           AND ($5::uuid IS NULL OR id = $5)
           AND ($6::uuid IS NULL OR workspace_id = $6)
+          AND ($7::bool OR archived_at IS NULL)
           AND (
               visibility = 'public'
               OR visibility = 'internal'
@@ -537,6 +584,7 @@ async fn list_projects(
         auth.user_id,
         auth.boundary_project_id,
         auth.boundary_workspace_id,
+        include_archived,
     )
     .fetch_one(&state.pool)
     .await?;
@@ -545,7 +593,7 @@ async fn list_projects(
         ProjectRow,
         r#"
         SELECT id, owner_id, workspace_id, name, display_name, description, visibility, default_branch,
-               namespace_slug, agent_image, is_active, created_at, updated_at
+               namespace_slug, agent_image, is_active, archived_at, created_at, updated_at
         FROM projects
         WHERE is_active = true
           AND ($1::uuid IS NULL OR owner_id = $1)
@@ -553,6 +601,7 @@ async fn list_projects(
           AND ($3::text IS NULL OR name ILIKE $3)
           AND ($5::uuid IS NULL OR id = $5)
           AND ($6::uuid IS NULL OR workspace_id = $6)
+          AND ($7::bool OR archived_at IS NULL)
           AND (
               visibility = 'public'
               OR visibility = 'internal'
@@ -571,7 +620,7 @@ async fn list_projects(
               )
           )
         ORDER BY created_at DESC
-        LIMIT $7 OFFSET $8
+        LIMIT $8 OFFSET $9
         "#,
         params.owner_id,
         params.visibility,
@@ -579,6 +628,7 @@ async fn list_projects(
         auth.user_id,
         auth.boundary_project_id,
         auth.boundary_workspace_id,
+        include_archived,
         limit,
         offset,
     )
@@ -602,7 +652,7 @@ async fn get_project(
         ProjectRow,
         r#"
         SELECT id, owner_id, workspace_id, name, display_name, description, visibility, default_branch,
-               namespace_slug, agent_image, is_active, created_at, updated_at
+               namespace_slug, agent_image, is_active, archived_at, created_at, updated_at
         FROM projects WHERE id = $1 AND is_active = true
         "#,
         id,
@@ -673,6 +723,9 @@ async fn update_project(
     if let Some(ref image) = body.agent_image {
         validation::check_container_image(image)?;
     }
+    if let Some(ref branch) = body.default_branch {
+        set_repo_head(&state, id, branch).await?;
+    }
 
     let project = sqlx::query_as!(
         ProjectRow,
@@ -686,7 +739,7 @@ async fn update_project(
             updated_at = now()
         WHERE id = $1 AND is_active = true
         RETURNING id, owner_id, workspace_id, name, display_name, description, visibility, default_branch,
-                  namespace_slug, agent_image, is_active, created_at, updated_at
+                  namespace_slug, agent_image, is_active, archived_at, created_at, updated_at
         "#,
         id,
         body.display_name,
@@ -716,6 +769,181 @@ async fn update_project(
     Ok(Json(project_row_to_response(project)))
 }
 
+/// Point the project repo's `HEAD` at a new default branch, which must exist
+/// unless the repo is still empty.
+async fn set_repo_head(state: &AppState, id: Uuid, branch: &str) -> Result<(), ApiError> {
+    let repo_path = sqlx::query_scalar!(
+        "SELECT repo_path FROM projects WHERE id = $1 AND is_active = true",
+        id,
+    )
+    .fetch_optional(&state.pool)
+    .await?
+    .flatten();
+    let Some(repo_path) = repo_path else {
+        return Ok(());
+    };
+    let updated = crate::git::repo::set_default_branch(std::path::Path::new(&repo_path), branch)
+        .await
+        .map_err(ApiError::Internal)?;
+    if !updated {
+        return Err(ApiError::BadRequest(format!(
+            "branch '{branch}' does not exist"
+        )));
+    }
+    Ok(())
+}
+
+/// Hand a project to another user. Only the current owner or an admin may
+/// transfer; the new owner must be an active user. Ownership carries implicit
+/// read/write access, so both users' cached permissions are invalidated.
+/// Git URLs (`/{owner}/{repo}`) follow the new owner's name; the repository
+/// stays where it is on disk (`repo_path`).
+#[tracing::instrument(skip(state, body), fields(%id, new_owner_id = %body.new_owner_id), err)]
+async fn transfer_project(
+    State(state): State<AppState>,
+    auth: AuthUser,
+    Path(id): Path<Uuid>,
+    Json(body): Json<TransferProjectRequest>,
+) -> Result<Json<ProjectResponse>, ApiError> {
+    let old_owner_id = require_owner_or_admin(&state, &auth, id).await?;
+
+    if body.new_owner_id == old_owner_id {
+        return Err(ApiError::BadRequest(
+            "user already owns this project".into(),
+        ));
+    }
+
+    let new_owner_active = sqlx::query_scalar!(
+        "SELECT is_active FROM users WHERE id = $1",
+        body.new_owner_id,
+    )
+    .fetch_optional(&state.pool)
+    .await?;
+    if new_owner_active != Some(true) {
+        return Err(ApiError::BadRequest(
+            "new_owner_id must refer to an active user".into(),
+        ));
+    }
+
+    let project = sqlx::query_as!(
+        ProjectRow,
+        r#"
+        UPDATE projects SET owner_id = $2, updated_at = now()
+        WHERE id = $1 AND is_active = true
+        RETURNING id, owner_id, workspace_id, name, display_name, description, visibility, default_branch,
+                  namespace_slug, agent_image, is_active, archived_at, created_at, updated_at
+        "#,
+        id,
+        body.new_owner_id,
+    )
+    .fetch_optional(&state.pool)
+    .await
+    .map_err(|e| match &e {
+        // Project names are unique per owner
+        sqlx::Error::Database(db_err) if db_err.code().as_deref() == Some("23505") => {
+            ApiError::Conflict("new owner already has a project with this name".into())
+        }
+        _ => ApiError::from(e),
+    })?
+    .ok_or_else(|| ApiError::NotFound("project".into()))?;
+
+    for user_id in [old_owner_id, body.new_owner_id] {
+        let _ = resolver::invalidate_permissions(&state.valkey, user_id, Some(id)).await;
+    }
+
+    send_audit(
+        &state.audit_tx,
+        AuditEntry {
+            actor_id: auth.user_id,
+            actor_name: auth.user_name.clone(),
+            action: "project.transfer".into(),
+            resource: "project".into(),
+            resource_id: Some(id),
+            project_id: Some(id),
+            detail: Some(serde_json::json!({
+                "from_owner_id": old_owner_id,
+                "to_owner_id": body.new_owner_id,
+            })),
+            ip_addr: auth.ip_addr.clone(),
+        },
+    );
+
+    Ok(Json(project_row_to_response(project)))
+}
+
+/// Archive a project: it stays readable and cloneable, but pushes, pipelines
+/// and modifications are refused until it is unarchived.
+#[tracing::instrument(skip(state), fields(%id), err)]
+async fn archive_project(
+    State(state): State<AppState>,
+    auth: AuthUser,
+    Path(id): Path<Uuid>,
+) -> Result<Json<ProjectResponse>, ApiError> {
+    set_archived(&state, &auth, id, true).await
+}
+
+#[tracing::instrument(skip(state), fields(%id), err)]
+async fn unarchive_project(
+    State(state): State<AppState>,
+    auth: AuthUser,
+    Path(id): Path<Uuid>,
+) -> Result<Json<ProjectResponse>, ApiError> {
+    set_archived(&state, &auth, id, false).await
+}
+
+async fn set_archived(
+    state: &AppState,
+    auth: &AuthUser,
+    id: Uuid,
+    archive: bool,
+) -> Result<Json<ProjectResponse>, ApiError> {
+    require_owner_or_admin(state, auth, id).await?;
+
+    let project = sqlx::query_as!(
+        ProjectRow,
+        r#"
+        UPDATE projects SET
+            archived_at = CASE WHEN $2 THEN now() END,
+            updated_at = now()
+        WHERE id = $1 AND is_active = true AND (archived_at IS NULL) = $2
+        RETURNING id, owner_id, workspace_id, name, display_name, description, visibility, default_branch,
+                  namespace_slug, agent_image, is_active, archived_at, created_at, updated_at
+        "#,
+        id,
+        archive,
+    )
+    .fetch_optional(&state.pool)
+    .await?
+    .ok_or_else(|| {
+        ApiError::Conflict(if archive {
+            "project is already archived".into()
+        } else {
+            "project is not archived".into()
+        })
+    })?;
+
+    send_audit(
+        &state.audit_tx,
+        AuditEntry {
+            actor_id: auth.user_id,
+            actor_name: auth.user_name.clone(),
+            action: if archive {
+                "project.archive"
+            } else {
+                "project.unarchive"
+            }
+            .into(),
+            resource: "project".into(),
+            resource_id: Some(id),
+            project_id: Some(id),
+            detail: None,
+            ip_addr: auth.ip_addr.clone(),
+        },
+    );
+
+    Ok(Json(project_row_to_response(project)))
+}
+
 #[tracing::instrument(skip(state), fields(%id), err)]
 async fn delete_project(
     State(state): State<AppState>,
@@ -0,0 +1,316 @@
+// Copyright (c) 2026 Steven Hooker. Exclusively licensed to and distributed by AgentSphere GmbH.
+// SPDX-License-Identifier: BUSL-1.1
+
+//! Admin-managed API rate limit overrides for a single API token or a whole
+//! project. The `api_rate_limit` middleware consults them in place of
+//! `PLATFORM_API_RATE_LIMIT` (see `auth::rate_limit`).
+
+use axum::extract::{Path, State};
+use axum::http::StatusCode;
+use axum::routing::{get, put};
+use axum::{Json, Router};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use ts_rs::TS;
+
+use crate::audit::{AuditEntry, send_audit};
+use crate::auth::middleware::AuthUser;
+use crate::error::ApiError;
+use crate::store::AppState;
+
+use super::helpers::{ListResponse, require_admin};
+
+/// Highest limit an override may set, in requests per minute.
+const MAX_REQUESTS_PER_MINUTE: i32 = 1_000_000;
+
+// ---------------------------------------------------------------------------
+// Types
+// ---------------------------------------------------------------------------
+
+#[derive(Debug, Deserialize)]
+pub struct SetRateLimitRequest {
+    /// Requests per minute; 0 exempts the token or project from limiting.
+    pub requests_per_minute: i32,
+}
+
+/// Exactly one of `project_id` and `token_id` is set.
+#[derive(Debug, Serialize, TS)]
+#[ts(export, rename = "RateLimitOverride")]
+pub struct RateLimitOverrideResponse {
+    pub id: Uuid,
+    pub project_id: Option<Uuid>,
+    pub token_id: Option<Uuid>,
+    pub requests_per_minute: i32,
+    pub created_by: Option<Uuid>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+/// What an override is attached to, from the route.
+#[derive(Debug, Clone, Copy)]
+enum Subject {
+    Project(Uuid),
+    Token(Uuid),
+}
+
+impl Subject {
+    fn column(self) -> &'static str {
+        match self {
+            Self::Project(_) => "project_id",
+            Self::Token(_) => "token_id",
+        }
+    }
+
+    fn id(self) -> Uuid {
+        match self {
+            Self::Project(id) | Self::Token(id) => id,
+        }
+    }
+
+    fn name(self) -> &'static str {
+        match self {
+            Self::Project(_) => "project",
+            Self::Token(_) => "api token",
+        }
+    }
+
+    fn project_id(self) -> Option<Uuid> {
+        match self {
+            Self::Project(id) => Some(id),
+            Self::Token(_) => None,
+        }
+    }
+}
+
+fn validate_limit(requests_per_minute: i32) -> Result<(), ApiError> {
+    if !(0..=MAX_REQUESTS_PER_MINUTE).contains(&requests_per_minute) {
+        return Err(ApiError::BadRequest(format!(
+            "requests_per_minute must be 0-{MAX_REQUESTS_PER_MINUTE}"
+        )));
+    }
+    Ok(())
+}
+
+// ---------------------------------------------------------------------------
+// Router
+// ---------------------------------------------------------------------------
+
+pub fn router() -> Router<AppState> {
+    Router::new()
+        .route("/api/admin/rate-limits", get(list_overrides))
+        .route(
+            "/api/admin/rate-limits/projects/{project_id}",
+            put(set_project_override).delete(delete_project_override),
+        )
+        .route(
+            "/api/admin/rate-limits/tokens/{token_id}",
+            put(set_token_override).delete(delete_token_override),
+        )
+}
+
+// ---------------------------------------------------------------------------
+// Handlers
+// ---------------------------------------------------------------------------
+
+async fn list_overrides(
+    State(state): State<AppState>,
+    auth: AuthUser,
+) -> Result<Json<ListResponse<RateLimitOverrideResponse>>, ApiError> {
+    require_admin(&state, &auth).await?;
+
+    let items = sqlx::query_as!(
+        RateLimitOverrideResponse,
+        "SELECT id, project_id, token_id, requests_per_minute, created_by, created_at, updated_at
+         FROM rate_limit_overrides ORDER BY created_at"
+    )
+    .fetch_all(&state.pool)
+    .await?;
+
+    let total = i64::try_from(items.len()).unwrap_or(i64::MAX);
+    Ok(Json(ListResponse { items, total }))
+}
+
+async fn set_project_override(
+    State(state): State<AppState>,
+    auth: AuthUser,
+    Path(project_id): Path<Uuid>,
+    Json(body): Json<SetRateLimitRequest>,
+) -> Result<Json<RateLimitOverrideResponse>, ApiError> {
+    set_override(&state, &auth, Subject::Project(project_id), &body).await
+}
+
+async fn set_token_override(
+    State(state): State<AppState>,
+    auth: AuthUser,
+    Path(token_id): Path<Uuid>,
+    Json(body): Json<SetRateLimitRequest>,
+) -> Result<Json<RateLimitOverrideResponse>, ApiError> {
+    set_override(&state, &auth, Subject::Token(token_id), &body).await
+}
+
+async fn delete_project_override(
+    State(state): State<AppState>,
+    auth: AuthUser,
+    Path(project_id): Path<Uuid>,
+) -> Result<StatusCode, ApiError> {
+    delete_override(&state, &auth, Subject::Project(project_id)).await
+}
+
+async fn delete_token_override(
+    State(state): State<AppState>,
+    auth: AuthUser,
+    Path(token_id): Path<Uuid>,
+) -> Result<StatusCode, ApiError> {
+    delete_override(&state, &auth, Subject::Token(token_id)).await
+}
+
+/// Create or replace the override for `subject`.
+#[tracing::instrument(skip(state, auth, body), err)]
+async fn set_override(
+    state: &AppState,
+    auth: &AuthUser,
+    subject: Subject,
+    body: &SetRateLimitRequest,
+) -> Result<Json<RateLimitOverrideResponse>, ApiError> {
+    require_admin(state, auth).await?;
+    validate_limit(body.requests_per_minute)?;
+
+    let exists = match subject {
+        Subject::Project(id) => {
+            sqlx::query_scalar!(
+                r#"SELECT EXISTS(SELECT 1 FROM projects WHERE id = $1) as "exists!""#,
+                id,
+            )
+            .fetch_one(&state.pool)
+            .await?
+        }
+        Subject::Token(id) => {
+            sqlx::query_scalar!(
+                r#"SELECT EXISTS(SELECT 1 FROM api_tokens WHERE id = $1) as "exists!""#,
+                id,
+            )
+            .fetch_one(&state.pool)
+            .await?
+        }
+    };
+    if !exists {
+        return Err(ApiError::NotFound(subject.name().into()));
+    }
+
+    let row = match subject {
+        Subject::Project(id) => {
+            sqlx::query_as!(
+                RateLimitOverrideResponse,
+                "INSERT INTO rate_limit_overrides (project_id, requests_per_minute, created_by)
+                 VALUES ($1, $2, $3)
+                 ON CONFLICT (project_id) DO UPDATE
+                     SET requests_per_minute = EXCLUDED.requests_per_minute, updated_at = now()
+                 RETURNING id, project_id, token_id, requests_per_minute, created_by,
+                           created_at, updated_at",
+                id,
+                body.requests_per_minute,
+                auth.user_id,
+            )
+            .fetch_one(&state.pool)
+            .await?
+        }
+        Subject::Token(id) => {
+            sqlx::query_as!(
+                RateLimitOverrideResponse,
+                "INSERT INTO rate_limit_overrides (token_id, requests_per_minute, created_by)
+                 VALUES ($1, $2, $3)
+                 ON CONFLICT (token_id) DO UPDATE
+                     SET requests_per_minute = EXCLUDED.requests_per_minute, updated_at = now()
+                 RETURNING id, project_id, token_id, requests_per_minute, created_by,
+                           created_at, updated_at",
+                id,
+                body.requests_per_minute,
+                auth.user_id,
+            )
+            .fetch_one(&state.pool)
+            .await?
+        }
+    };
+
+    send_audit(
+        &state.audit_tx,
+        AuditEntry {
+            actor_id: auth.user_id,
+            actor_name: auth.user_name.clone(),
+            action: "rate_limit.set".into(),
+            resource: "api_rate_limit_override".into(),
+            resource_id: Some(row.id),
+            project_id: subject.project_id(),
+            detail: Some(serde_json::json!({
+                subject.column(): subject.id(),
+                "requests_per_minute": row.requests_per_minute,
+            })),
+            ip_addr: auth.ip_addr.clone(),
+        },
+    );
+
+    Ok(Json(row))
+}
+
+#[tracing::instrument(skip(state, auth), err)]
+async fn delete_override(
+    state: &AppState,
+    auth: &AuthUser,
+    subject: Subject,
+) -> Result<StatusCode, ApiError> {
+    require_admin(state, auth).await?;
+
+    let id = match subject {
+        Subject::Project(id) => {
+            sqlx::query_scalar!(
+                "DELETE FROM rate_limit_overrides WHERE project_id = $1 RETURNING id",
+                id,
+            )
+            .fetch_optional(&state.pool)
+            .await?
+        }
+        Subject::Token(id) => {
+            sqlx::query_scalar!(
+                "DELETE FROM rate_limit_overrides WHERE token_id = $1 RETURNING id",
+                id,
+            )
+            .fetch_optional(&state.pool)
+            .await?
+        }
+    };
+    let Some(id) = id else {
+        return Err(ApiError::NotFound("rate limit override".into()));
+    };
+
+    send_audit(
+        &state.audit_tx,
+        AuditEntry {
+            actor_id: auth.user_id,
+            actor_name: auth.user_name.clone(),
+            action: "rate_limit.delete".into(),
+            resource: "api_rate_limit_override".into(),
+            resource_id: Some(id),
+            project_id: subject.project_id(),
+            detail: Some(serde_json::json!({ subject.column(): subject.id() })),
+            ip_addr: auth.ip_addr.clone(),
+        },
+    );
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn limit_bounds() {
+        assert!(validate_limit(0).is_ok());
+        assert!(validate_limit(6000).is_ok());
+        assert!(validate_limit(MAX_REQUESTS_PER_MINUTE).is_ok());
+        assert!(validate_limit(-1).is_err());
+        assert!(validate_limit(MAX_REQUESTS_PER_MINUTE + 1).is_err());
+    }
+}
@@ -0,0 +1,445 @@
+// Copyright (c) 2026 Steven Hooker. Exclusively licensed to and distributed by AgentSphere GmbH.
+// SPDX-License-Identifier: BUSL-1.1
+
+//! Emoji reactions on issues, merge requests and their comments. Each user
+//! can add a given emoji to a target once; responses for those targets carry
+//! the aggregated counts.
+
+use std::collections::HashMap;
+
+use axum::extract::{Path, State};
+use axum::http::StatusCode;
+use axum::routing::{delete, post};
+use axum::{Json, Router};
+use serde::{Deserialize, Serialize};
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use ts_rs::TS;
+
+use crate::auth::middleware::AuthUser;
+use crate::error::ApiError;
+use crate::store::AppState;
+
+use super::helpers::require_project_read;
+
+// ---------------------------------------------------------------------------
+// Types
+// ---------------------------------------------------------------------------
+
+#[derive(Debug, Deserialize)]
+pub struct AddReactionRequest {
+    pub emoji: String,
+}
+
+/// Users who reacted to a target with one emoji.
+#[derive(Debug, Clone, Serialize, TS)]
+#[ts(export, rename = "Reaction")]
+pub struct ReactionCount {
+    pub emoji: String,
+    #[ts(type = "number")]
+    pub count: i64,
+    /// Whether the requesting user is one of them.
+    pub reacted: bool,
+}
+
+/// What a reaction is attached to (`reactions.target_type`).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum ReactionTarget {
+    Issue,
+    MergeRequest,
+    Comment,
+}
+
+impl ReactionTarget {
+    fn as_str(self) -> &'static str {
+        match self {
+            Self::Issue => "issue",
+            Self::MergeRequest => "merge_request",
+            Self::Comment => "comment",
+        }
+    }
+}
+
+/// Longest accepted emoji, in code points (ZWJ sequences with skin tones).
+const MAX_EMOJI_CHARS: usize = 10;
+
+/// A single emoji: a short run of non-ASCII, printable code points.
+fn check_emoji(emoji: &str) -> Result<(), ApiError> {
+    let chars = emoji.chars().count();
+    if chars == 0
+        || chars > MAX_EMOJI_CHARS
+        || emoji
+            .chars()
+            .any(|c| c.is_ascii() || c.is_whitespace() || c.is_control())
+    {
+        return Err(ApiError::BadRequest("emoji must be a single emoji".into()));
+    }
+    Ok(())
+}
+
+// ---------------------------------------------------------------------------
+// Router
+// ---------------------------------------------------------------------------
+
+pub fn router() -> Router<AppState> {
+    Router::new()
+        .route(
+            "/api/projects/{id}/issues/{number}/reactions",
+            post(add_issue_reaction),
+        )
+        .route(
+            "/api/projects/{id}/issues/{number}/reactions/{emoji}",
+            delete(remove_issue_reaction),
+        )
+        .route(
+            "/api/projects/{id}/issues/{number}/comments/{comment_id}/reactions",
+            post(add_issue_comment_reaction),
+        )
+        .route(
+            "/api/projects/{id}/issues/{number}/comments/{comment_id}/reactions/{emoji}",
+            delete(remove_issue_comment_reaction),
+        )
+        .route(
+            "/api/projects/{id}/merge-requests/{number}/reactions",
+            post(add_mr_reaction),
+        )
+        .route(
+            "/api/projects/{id}/merge-requests/{number}/reactions/{emoji}",
+            delete(remove_mr_reaction),
+        )
+        .route(
+            "/api/projects/{id}/merge-requests/{number}/comments/{comment_id}/reactions",
+            post(add_mr_comment_reaction),
+        )
+        .route(
+            "/api/projects/{id}/merge-requests/{number}/comments/{comment_id}/reactions/{emoji}",
+            delete(remove_mr_comment_reaction),
+        )
+}
+
+// ---------------------------------------------------------------------------
+// Handlers
+// ---------------------------------------------------------------------------
+
+async fn add_issue_reaction(
+    State(state): State<AppState>,
+    auth: AuthUser,
+    Path((id, number)): Path<(Uuid, i32)>,
+    Json(body): Json<AddReactionRequest>,
+) -> Result<(StatusCode, Json<Vec<ReactionCount>>), ApiError> {
+    require_project_read(&state, &auth, id).await?;
+    let target_id = find_issue(&state.pool, id, number).await?;
+    add(
+        &state.pool,
+        &auth,
+        id,
+        ReactionTarget::Issue,
+        target_id,
+        &body.emoji,
+    )
+    .await
+}
+
+async fn remove_issue_reaction(
+    State(state): State<AppState>,
+    auth: AuthUser,
+    Path((id, number, emoji)): Path<(Uuid, i32, String)>,
+) -> Result<Json<Vec<ReactionCount>>, ApiError> {
+    require_project_read(&state, &auth, id).await?;
+    let target_id = find_issue(&state.pool, id, number).await?;
+    remove(&state.pool, &auth, ReactionTarget::Issue, target_id, &emoji).await
+}
+
+async fn add_issue_comment_reaction(
+    State(state): State<AppState>,
+    auth: AuthUser,
+    Path((id, number, comment_id)): Path<(Uuid, i32, Uuid)>,
+    Json(body): Json<AddReactionRequest>,
+) -> Result<(StatusCode, Json<Vec<ReactionCount>>), ApiError> {
+    require_project_read(&state, &auth, id).await?;
+    let issue_id = find_issue(&state.pool, id, number).await?;
+    find_comment(&state.pool, issue_id, comment_id).await?;
+    add(
+        &state.pool,
+        &auth,
+        id,
+        ReactionTarget::Comment,
+        comment_id,
+        &body.emoji,
+    )
+    .await
+}
+
+async fn remove_issue_comment_reaction(
+    State(state): State<AppState>,
+    auth: AuthUser,
+    Path((id, number, comment_id, emoji)): Path<(Uuid, i32, Uuid, String)>,
+) -> Result<Json<Vec<ReactionCount>>, ApiError> {
+    require_project_read(&state, &auth, id).await?;
+    let issue_id = find_issue(&state.pool, id, number).await?;
+    find_comment(&state.pool, issue_id, comment_id).await?;
+    remove(
+        &state.pool,
+        &auth,
+        ReactionTarget::Comment,
+        comment_id,
+        &emoji,
+    )
+    .await
+}
+
+async fn add_mr_reaction(
+    State(state): State<AppState>,
+    auth: AuthUser,
+    Path((id, number)): Path<(Uuid, i32)>,
+    Json(body): Json<AddReactionRequest>,
+) -> Result<(StatusCode, Json<Vec<ReactionCount>>), ApiError> {
+    require_project_read(&state, &auth, id).await?;
+    let target_id = find_mr(&state.pool, id, number).await?;
+    add(
+        &state.pool,
+        &auth,
+        id,
+        ReactionTarget::MergeRequest,
+        target_id,
+        &body.emoji,
+    )
+    .await
+}
+
+async fn remove_mr_reaction(
+    State(state): State<AppState>,
+    auth: AuthUser,
+    Path((id, number, emoji)): Path<(Uuid, i32, String)>,
+) -> Result<Json<Vec<ReactionCount>>, ApiError> {
+    require_project_read(&state, &auth, id).await?;
+    let target_id = find_mr(&state.pool, id, number).await?;
+    remove(
+        &state.pool,
+        &auth,
+        ReactionTarget::MergeRequest,
+        target_id,
+        &emoji,
+    )
+    .await
+}
+
+async fn add_mr_comment_reaction(
+    State(state): State<AppState>,
+    auth: AuthUser,
+    Path((id, number, comment_id)): Path<(Uuid, i32, Uuid)>,
+    Json(body): Json<AddReactionRequest>,
+) -> Result<(StatusCode, Json<Vec<ReactionCount>>), ApiError> {
+    require_project_read(&state, &auth, id).await?;
+    let mr_id = find_mr(&state.pool, id, number).await?;
+    find_comment(&state.pool, mr_id, comment_id).await?;
+    add(
+        &state.pool,
+        &auth,
+        id,
+        ReactionTarget::Comment,
+        comment_id,
+        &body.emoji,
+    )
+    .await
+}
+
+async fn remove_mr_comment_reaction(
+    State(state): State<AppState>,
+    auth: AuthUser,
+    Path((id, number, comment_id, emoji)): Path<(Uuid, i32, Uuid, String)>,
+) -> Result<Json<Vec<ReactionCount>>, ApiError> {
+    require_project_read(&state, &auth, id).await?;
+    let mr_id = find_mr(&state.pool, id, number).await?;
+    find_comment(&state.pool, mr_id, comment_id).await?;
+    remove(
+        &state.pool,
+        &auth,
+        ReactionTarget::Comment,
+        comment_id,
+        &emoji,
+    )
+    .await
+}
+
+// ---------------------------------------------------------------------------
+// Helpers
+// ---------------------------------------------------------------------------
+
+async fn find_issue(pool: &PgPool, project_id: Uuid, number: i32) -> Result<Uuid, ApiError> {
+    sqlx::query_scalar!(
+        "SELECT id FROM issues WHERE project_id = $1 AND number = $2",
+        project_id,
+        number,
+    )
+    .fetch_optional(pool)
+    .await?
+    .ok_or_else(|| ApiError::NotFound("issue".into()))
+}
+
+async fn find_mr(pool: &PgPool, project_id: Uuid, number: i32) -> Result<Uuid, ApiError> {
+    sqlx::query_scalar!(
+        "SELECT id FROM merge_requests WHERE project_id = $1 AND number = $2",
+        project_id,
+        number,
+    )
+    .fetch_optional(pool)
+    .await?
+    .ok_or_else(|| ApiError::NotFound("merge request".into()))
+}
+
+/// Check that `comment_id` belongs to the issue or MR `parent_id`.
+async fn find_comment(pool: &PgPool, parent_id: Uuid, comment_id: Uuid) -> Result<(), ApiError> {
+    let exists = sqlx::query_scalar!(
+        r#"SELECT EXISTS(
+               SELECT 1 FROM comments WHERE id = $1 AND (issue_id = $2 OR mr_id = $2)
+           ) as "exists!""#,
+        comment_id,
+        parent_id,
+    )
+    .fetch_one(pool)
+    .await?;
+    if exists {
+        Ok(())
+    } else {
+        Err(ApiError::NotFound("comment".into()))
+    }
+}
+
+/// Add `emoji` from the caller; adding it again is a no-op (200 instead of 201).
+async fn add(
+    pool: &PgPool,
+    auth: &AuthUser,
+    project_id: Uuid,
+    target: ReactionTarget,
+    target_id: Uuid,
+    emoji: &str,
+) -> Result<(StatusCode, Json<Vec<ReactionCount>>), ApiError> {
+    check_emoji(emoji)?;
+    let inserted = sqlx::query!(
+        "INSERT INTO reactions (project_id, target_type, target_id, user_id, emoji)
+         VALUES ($1, $2, $3, $4, $5)
+         ON CONFLICT (target_type, target_id, user_id, emoji) DO NOTHING",
+        project_id,
+        target.as_str(),
+        target_id,
+        auth.user_id,
+        emoji,
+    )
+    .execute(pool)
+    .await?
+    .rows_affected();
+
+    let status = if inserted > 0 {
+        StatusCode::CREATED
+    } else {
+        StatusCode::OK
+    };
+    Ok((
+        status,
+        Json(summary(pool, target, target_id, auth.user_id).await?),
+    ))
+}
+
+async fn remove(
+    pool: &PgPool,
+    auth: &AuthUser,
+    target: ReactionTarget,
+    target_id: Uuid,
+    emoji: &str,
+) -> Result<Json<Vec<ReactionCount>>, ApiError> {
+    let deleted = sqlx::query!(
+        "DELETE FROM reactions
+         WHERE target_type = $1 AND target_id = $2 AND user_id = $3 AND emoji = $4",
+        target.as_str(),
+        target_id,
+        auth.user_id,
+        emoji,
+    )
+    .execute(pool)
+    .await?
+    .rows_affected();
+    if deleted == 0 {
+        return Err(ApiError::NotFound("reaction".into()));
+    }
+    Ok(Json(summary(pool, target, target_id, auth.user_id).await?))
+}
+
+/// Aggregated reactions on one target, in the order each emoji was first used.
+pub(crate) async fn summary(
+    pool: &PgPool,
+    target: ReactionTarget,
+    target_id: Uuid,
+    viewer: Uuid,
+) -> Result<Vec<ReactionCount>, ApiError> {
+    Ok(summaries(pool, target, &[target_id], viewer)
+        .await?
+        .remove(&target_id)
+        .unwrap_or_default())
+}
+
+/// Aggregated reactions for many targets of one type, keyed by target id.
+/// Targets without reactions are absent.
+pub(crate) async fn summaries(
+    pool: &PgPool,
+    target: ReactionTarget,
+    target_ids: &[Uuid],
+    viewer: Uuid,
+) -> Result<HashMap<Uuid, Vec<ReactionCount>>, ApiError> {
+    if target_ids.is_empty() {
+        return Ok(HashMap::new());
+    }
+    let rows = sqlx::query!(
+        r#"SELECT target_id, emoji, COUNT(*) AS "count!", bool_or(user_id = $3) AS "reacted!"
+           FROM reactions
+           WHERE target_type = $1 AND target_id = ANY($2)
+           GROUP BY target_id, emoji
+           ORDER BY MIN(created_at), emoji"#,
+        target.as_str(),
+        target_ids,
+        viewer,
+    )
+    .fetch_all(pool)
+    .await?;
+
+    let mut by_target: HashMap<Uuid, Vec<ReactionCount>> = HashMap::new();
+    for row in rows {
+        by_target
+            .entry(row.target_id)
+            .or_default()
+            .push(ReactionCount {
+                emoji: row.emoji,
+                count: row.count,
+                reacted: row.reacted,
+            });
+    }
+    Ok(by_target)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn emoji_accepts_single_emoji_and_sequences() {
+        for emoji in ["👍", "❤️", "🎉", "👍🏽", "👩‍💻", "👨‍👩‍👧‍👦"]
+        {
+            assert!(check_emoji(emoji).is_ok(), "{emoji} should be accepted");
+        }
+    }
+
+    #[test]
+    fn emoji_rejects_text() {
+        for emoji in [
+            "",
+            "+1",
+            ":thumbsup:",
+            "👍 ",
+            "👍\n",
+            "👍👍👍👍👍👍👍👍👍👍👍",
+        ] {
+            assert!(check_emoji(emoji).is_err(), "{emoji:?} should be rejected");
+        }
+    }
+}
@@ -93,16 +93,20 @@ pub fn router() -> Router<AppState> {
                 .patch(update_release)
                 .delete(delete_release),
         )
-        .route(
-            "/api/projects/{id}/releases/{tag_name}/assets",
-            axum::routing::post(upload_asset),
-        )
         .route(
             "/api/projects/{id}/releases/{tag_name}/assets/{asset_id}/download",
             get(download_asset),
         )
 }
 
+/// Asset uploads, mounted separately so they get the upload body limit.
+pub fn upload_router() -> Router<AppState> {
+    Router::new().route(
+        "/api/projects/{id}/releases/{tag_name}/assets",
+        axum::routing::post(upload_asset),
+    )
+}
+
 // ---------------------------------------------------------------------------
 // Handlers
 // ---------------------------------------------------------------------------
@@ -396,7 +400,7 @@ async fn upload_asset(
     .await?
     .ok_or_else(|| ApiError::NotFound("release".into()))?;
 
-    let field = multipart
+    let mut field = multipart
         .next_field()
         .await
         .map_err(|e| ApiError::BadRequest(format!("multipart error: {e}")))?
@@ -407,17 +411,31 @@ async fn upload_asset(
 
     validation::check_length("name", &file_name, 1, 255)?;
 
-    let data = field
-        .bytes()
-        .await
-        .map_err(|e| ApiError::BadRequest(format!("failed to read file: {e}")))?;
-
-    let size_bytes = i64::try_from(data.len()).unwrap_or(i64::MAX);
+    // Stream to MinIO — assets may be as large as the upload body limit
     let minio_path = format!("releases/{release_id}/{file_name}");
-
-    state
+    let mut writer = state
         .minio
-        .write(&minio_path, data)
+        .writer(&minio_path)
+        .await
+        .map_err(|e| ApiError::Internal(anyhow::anyhow!("storage write: {e}")))?;
+    let mut size_bytes: i64 = 0;
+    loop {
+        let chunk = match field.chunk().await {
+            Ok(Some(chunk)) => chunk,
+            Ok(None) => break,
+            Err(e) => {
+                let _ = writer.abort().await;
+                return Err(ApiError::BadRequest(format!("failed to read file: {e}")));
+            }
+        };
+        size_bytes = size_bytes.saturating_add(i64::try_from(chunk.len()).unwrap_or(i64::MAX));
+        if let Err(e) = writer.write(chunk).await {
+            let _ = writer.abort().await;
+            return Err(ApiError::Internal(anyhow::anyhow!("storage write: {e}")));
+        }
+    }
+    writer
+        .close()
         .await
         .map_err(|e| ApiError::Internal(anyhow::anyhow!("storage write: {e}")))?;
 
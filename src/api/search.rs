@@ -0,0 +1,309 @@
+// Copyright (c) 2026 Steven Hooker. Exclusively licensed to and distributed by AgentSphere GmbH.
+// SPDX-License-Identifier: BUSL-1.1
+
+//! Full-text search over issue and merge request titles and bodies, within
+//! one project or across every project the caller can read.
+
+use axum::extract::{Path, Query, State};
+use axum::routing::get;
+use axum::{Json, Router};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use ts_rs::TS;
+
+use crate::auth::middleware::AuthUser;
+use crate::error::ApiError;
+use crate::store::AppState;
+use crate::validation;
+
+use super::helpers::{ListResponse, require_project_read};
+
+// ---------------------------------------------------------------------------
+// Types
+// ---------------------------------------------------------------------------
+
+#[derive(Debug, Serialize, TS)]
+#[ts(export)]
+pub struct SearchResult {
+    /// `issue` or `mr`.
+    pub kind: String,
+    pub id: Uuid,
+    pub project_id: Uuid,
+    pub project_name: String,
+    pub number: i32,
+    pub title: String,
+    pub status: String,
+    /// Best-matching body fragment with hits wrapped in `**`.
+    pub snippet: Option<String>,
+    /// Relevance; results are ordered by it, highest first.
+    pub rank: f32,
+    pub updated_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct SearchParams {
+    /// Web-search syntax: words, `"quoted phrases"`, `or`, `-excluded`.
+    pub q: String,
+    /// Comma-separated subset of `issue,mr` (default: both).
+    #[serde(rename = "type")]
+    pub kinds: Option<String>,
+    pub limit: Option<i64>,
+    pub offset: Option<i64>,
+}
+
+/// Which result kinds a search covers.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct Kinds {
+    issues: bool,
+    mrs: bool,
+}
+
+impl Kinds {
+    fn parse(value: Option<&str>) -> Result<Self, ApiError> {
+        let Some(value) = value.filter(|v| !v.trim().is_empty()) else {
+            return Ok(Self {
+                issues: true,
+                mrs: true,
+            });
+        };
+        let mut kinds = Self {
+            issues: false,
+            mrs: false,
+        };
+        for kind in value.split(',').map(str::trim) {
+            match kind {
+                "issue" => kinds.issues = true,
+                "mr" => kinds.mrs = true,
+                other => {
+                    return Err(ApiError::BadRequest(format!(
+                        "unknown search type '{other}' (expected issue or mr)"
+                    )));
+                }
+            }
+        }
+        Ok(kinds)
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Router
+// ---------------------------------------------------------------------------
+
+pub fn router() -> Router<AppState> {
+    Router::new()
+        .route("/api/search", get(search_all))
+        .route("/api/projects/{id}/search", get(search_project))
+}
+
+// ---------------------------------------------------------------------------
+// Handlers
+// ---------------------------------------------------------------------------
+
+async fn search_project(
+    State(state): State<AppState>,
+    auth: AuthUser,
+    Path(id): Path<Uuid>,
+    Query(params): Query<SearchParams>,
+) -> Result<Json<ListResponse<SearchResult>>, ApiError> {
+    require_project_read(&state, &auth, id).await?;
+    let kinds = check_params(&params)?;
+    search(&state, &params, kinds, Scope::Project(id)).await
+}
+
+async fn search_all(
+    State(state): State<AppState>,
+    auth: AuthUser,
+    Query(params): Query<SearchParams>,
+) -> Result<Json<ListResponse<SearchResult>>, ApiError> {
+    let kinds = check_params(&params)?;
+    search(&state, &params, kinds, Scope::Readable(&auth)).await
+}
+
+// ---------------------------------------------------------------------------
+// Helpers
+// ---------------------------------------------------------------------------
+
+fn check_params(params: &SearchParams) -> Result<Kinds, ApiError> {
+    validation::check_length("q", params.q.trim(), 1, 200)?;
+    Kinds::parse(params.kinds.as_deref())
+}
+
+fn page(params: &SearchParams) -> (i64, i64) {
+    (
+        params.limit.unwrap_or(20).clamp(1, 100),
+        params.offset.unwrap_or(0).max(0),
+    )
+}
+
+/// Which projects a search covers.
+#[derive(Clone, Copy)]
+enum Scope<'a> {
+    /// Only this project; the caller has already checked read access.
+    Project(Uuid),
+    /// Every project the user can read, within their token boundaries.
+    Readable(&'a AuthUser),
+}
+
+/// Matches for `params.q`, ranked, with the total count. `scoped` mirrors the
+/// visibility rules of the project list when searching readable projects, and
+/// snippets are only computed for the rows returned.
+async fn search(
+    state: &AppState,
+    params: &SearchParams,
+    kinds: Kinds,
+    scope: Scope<'_>,
+) -> Result<Json<ListResponse<SearchResult>>, ApiError> {
+    let q = params.q.trim();
+    let (project_id, reader) = match scope {
+        Scope::Project(id) => (Some(id), None),
+        Scope::Readable(auth) => (None, Some(auth)),
+    };
+    let user_id = reader.map(|a| a.user_id);
+    let boundary_project_id = reader.and_then(|a| a.boundary_project_id);
+    let boundary_workspace_id = reader.and_then(|a| a.boundary_workspace_id);
+
+    let total = sqlx::query_scalar!(
+        r#"
+        WITH scoped AS (
+            SELECT p.id, p.name FROM projects p
+            WHERE ($4::uuid IS NULL OR p.id = $4)
+              AND ($5::uuid IS NULL OR (
+                  p.is_active = true
+                  AND ($6::uuid IS NULL OR p.id = $6)
+                  AND ($7::uuid IS NULL OR p.workspace_id = $7)
+                  AND (
+                      p.visibility IN ('public', 'internal')
+                      OR p.owner_id = $5
+                      OR EXISTS(
+                          SELECT 1 FROM user_roles ur
+                          JOIN role_permissions rp ON rp.role_id = ur.role_id
+                          JOIN permissions perm ON perm.id = rp.permission_id
+                          WHERE ur.user_id = $5 AND perm.name = 'project:read'
+                          AND (ur.project_id = p.id OR ur.project_id IS NULL)
+                      )
+                      OR EXISTS(
+                          SELECT 1 FROM workspace_members wm
+                          WHERE wm.workspace_id = p.workspace_id AND wm.user_id = $5
+                      )
+                  )
+              ))
+        )
+        SELECT
+            (SELECT COUNT(*) FROM issues i JOIN scoped s ON s.id = i.project_id
+             WHERE $2 AND i.search_vector @@ websearch_to_tsquery('english', $1))
+          + (SELECT COUNT(*) FROM merge_requests m JOIN scoped s ON s.id = m.project_id
+             WHERE $3 AND m.search_vector @@ websearch_to_tsquery('english', $1))
+            AS "count!"
+        "#,
+        q,
+        kinds.issues,
+        kinds.mrs,
+        project_id,
+        user_id,
+        boundary_project_id,
+        boundary_workspace_id,
+    )
+    .fetch_one(&state.pool)
+    .await?;
+
+    let (limit, offset) = page(params);
+    let items = sqlx::query_as!(
+        SearchResult,
+        r#"
+        WITH scoped AS (
+            SELECT p.id, p.name FROM projects p
+            WHERE ($4::uuid IS NULL OR p.id = $4)
+              AND ($5::uuid IS NULL OR (
+                  p.is_active = true
+                  AND ($6::uuid IS NULL OR p.id = $6)
+                  AND ($7::uuid IS NULL OR p.workspace_id = $7)
+                  AND (
+                      p.visibility IN ('public', 'internal')
+                      OR p.owner_id = $5
+                      OR EXISTS(
+                          SELECT 1 FROM user_roles ur
+                          JOIN role_permissions rp ON rp.role_id = ur.role_id
+                          JOIN permissions perm ON perm.id = rp.permission_id
+                          WHERE ur.user_id = $5 AND perm.name = 'project:read'
+                          AND (ur.project_id = p.id OR ur.project_id IS NULL)
+                      )
+                      OR EXISTS(
+                          SELECT 1 FROM workspace_members wm
+                          WHERE wm.workspace_id = p.workspace_id AND wm.user_id = $5
+                      )
+                  )
+              ))
+        ),
+        hits AS (
+            SELECT 'issue' AS kind, i.id, i.project_id, s.name AS project_name, i.number,
+                   i.title, i.body, i.status, i.updated_at,
+                   ts_rank_cd(i.search_vector, websearch_to_tsquery('english', $1)) AS rank
+            FROM issues i
+            JOIN scoped s ON s.id = i.project_id
+            WHERE $2 AND i.search_vector @@ websearch_to_tsquery('english', $1)
+
+            UNION ALL
+
+            SELECT 'mr', m.id, m.project_id, s.name, m.number,
+                   m.title, m.body, m.status, m.updated_at,
+                   ts_rank_cd(m.search_vector, websearch_to_tsquery('english', $1))
+            FROM merge_requests m
+            JOIN scoped s ON s.id = m.project_id
+            WHERE $3 AND m.search_vector @@ websearch_to_tsquery('english', $1)
+        )
+        SELECT kind AS "kind!", id AS "id!", project_id AS "project_id!",
+               project_name AS "project_name!", number AS "number!", title AS "title!",
+               status AS "status!", updated_at AS "updated_at!", rank AS "rank!",
+               CASE WHEN body IS NULL OR body = '' THEN NULL
+                    ELSE ts_headline('english', body, websearch_to_tsquery('english', $1),
+                                     'StartSel=**, StopSel=**, MaxWords=30, MinWords=10, MaxFragments=1')
+               END AS snippet
+        FROM hits
+        ORDER BY rank DESC, updated_at DESC, id
+        LIMIT $8 OFFSET $9
+        "#,
+        q,
+        kinds.issues,
+        kinds.mrs,
+        project_id,
+        user_id,
+        boundary_project_id,
+        boundary_workspace_id,
+        limit,
+        offset,
+    )
+    .fetch_all(&state.pool)
+    .await?;
+
+    Ok(Json(ListResponse { items, total }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn kinds_default_to_both() {
+        let both = Kinds {
+            issues: true,
+            mrs: true,
+        };
+        assert_eq!(Kinds::parse(None).unwrap(), both);
+        assert_eq!(Kinds::parse(Some(" ")).unwrap(), both);
+        assert_eq!(Kinds::parse(Some("mr, issue")).unwrap(), both);
+    }
+
+    #[test]
+    fn kinds_select_one_or_reject_unknown() {
+        assert_eq!(
+            Kinds::parse(Some("mr")).unwrap(),
+            Kinds {
+                issues: false,
+                mrs: true
+            }
+        );
+        assert!(Kinds::parse(Some("issue,wiki")).is_err());
+    }
+}
@@ -17,12 +17,13 @@ use crate::audit::{AuditEntry, send_audit};
 use crate::auth::middleware::AuthUser;
 use crate::error::ApiError;
 use crate::rbac::{Permission, resolver};
-use crate::secrets::engine;
 use crate::secrets::request::{MAX_PENDING_PER_SESSION, SecretRequest, SecretRequestStatus};
+use crate::secrets::{access, engine, vault};
 use crate::store::AppState;
 use crate::validation;
 
-use super::helpers::{ListResponse, require_admin};
+use super::dashboard::AuditLogEntry;
+use super::helpers::{ListResponse, ReadAudit, audit_read, require_admin};
 
 // ---------------------------------------------------------------------------
 // Types
@@ -34,6 +35,8 @@ pub struct CreateSecretRequest {
     pub value: String,
     pub scope: String,
     pub environment: Option<String>,
+    /// `local` (default) or `vault`, in which case `value` is a `vault://path#key` reference.
+    pub backend: Option<String>,
 }
 
 const VALID_SCOPES: &[&str] = &["all", "pipeline", "agent", "test", "staging", "prod"];
@@ -74,7 +77,7 @@ struct SecretRequestResponse {
 // Helpers
 // ---------------------------------------------------------------------------
 
-fn get_master_key(state: &AppState) -> Result<[u8; 32], ApiError> {
+fn get_master_key(state: &AppState) -> Result<engine::MasterKey, ApiError> {
     let hex_str = state
         .config
         .master_key
@@ -95,6 +98,33 @@ fn validate_scope(scope: &str) -> Result<(), ApiError> {
     Ok(())
 }
 
+/// A `vault` secret's value must be a `vault://path#key` reference.
+fn validate_backend(backend: Option<&str>, value: &str) -> Result<&'static str, ApiError> {
+    match backend {
+        None | Some(engine::BACKEND_LOCAL) => Ok(engine::BACKEND_LOCAL),
+        Some(engine::BACKEND_VAULT) => {
+            vault::parse_reference(value).map_err(ApiError::BadRequest)?;
+            Ok(engine::BACKEND_VAULT)
+        }
+        Some(_) => Err(ApiError::BadRequest(
+            "backend must be one of [\"local\", \"vault\"]".into(),
+        )),
+    }
+}
+
+/// Vault references are resolved with the platform's own Vault token, so only
+/// platform admins may choose what a secret points at.
+async fn require_vault_admin(
+    state: &AppState,
+    auth: &AuthUser,
+    backend: &str,
+) -> Result<(), ApiError> {
+    if backend == engine::BACKEND_VAULT {
+        require_admin(state, auth).await?;
+    }
+    Ok(())
+}
+
 const VALID_ENVIRONMENTS: &[&str] = &["preview", "staging", "production"];
 
 fn validate_environment(env: &str) -> Result<(), ApiError> {
@@ -166,6 +196,10 @@ pub fn router() -> Router<AppState> {
             "/api/projects/{id}/secrets/{name}",
             get(read_project_secret).delete(delete_project_secret),
         )
+        .route(
+            "/api/projects/{id}/secrets/{name}/access-log",
+            get(secret_access_log),
+        )
         .route(
             "/api/projects/{id}/secret-requests",
             get(list_secret_requests).post(create_secret_request),
@@ -190,6 +224,10 @@ pub fn router() -> Router<AppState> {
             "/api/admin/secrets/{name}",
             axum::routing::delete(delete_global_secret),
         )
+        .route(
+            "/api/admin/secrets/rewrap",
+            axum::routing::post(rewrap_secrets),
+        )
 }
 
 // ---------------------------------------------------------------------------
@@ -218,6 +256,8 @@ async fn create_project_secret(
     validation::check_name(&body.name)?;
     validation::check_length("value", &body.value, 1, 65_536)?;
     validate_scope(&body.scope)?;
+    let backend = validate_backend(body.backend.as_deref(), &body.value)?;
+    require_vault_admin(&state, &auth, backend).await?;
 
     let master_key = get_master_key(&state)?;
 
@@ -235,6 +275,7 @@ async fn create_project_secret(
             name: &body.name,
             value: body.value.as_bytes(),
             scope: &body.scope,
+            backend,
             created_by: auth.user_id,
         },
     )
@@ -253,6 +294,7 @@ async fn create_project_secret(
             detail: Some(serde_json::json!({
                 "name": body.name,
                 "scope": body.scope,
+                "backend": backend,
                 "environment": body.environment,
             })),
             ip_addr: auth.ip_addr.clone(),
@@ -278,6 +320,17 @@ async fn list_project_secrets(
     let secrets = engine::list_secrets(&state.pool, Some(id), params.environment.as_deref())
         .await
         .map_err(ApiError::Internal)?;
+    audit_read(
+        &state,
+        &auth,
+        ReadAudit::Secrets,
+        Some(id),
+        serde_json::json!({
+            "scope": "project",
+            "environment": params.environment,
+            "count": secrets.len(),
+        }),
+    );
 
     #[allow(clippy::cast_possible_wrap)]
     let total = secrets.len() as i64;
@@ -287,6 +340,12 @@ async fn list_project_secrets(
     }))
 }
 
+#[derive(Debug, Deserialize)]
+struct AccessLogParams {
+    limit: Option<i64>,
+    offset: Option<i64>,
+}
+
 #[derive(Debug, Deserialize)]
 #[allow(dead_code)] // environment reserved for future env-aware resolution
 struct ReadSecretParams {
@@ -321,11 +380,16 @@ async fn read_project_secret(
         AuditEntry {
             actor_id: auth.user_id,
             actor_name: auth.user_name.clone(),
-            action: "secret.read".into(),
+            action: access::READ_ACTION.into(),
             resource: "secret".into(),
             resource_id: None,
             project_id: Some(id),
-            detail: Some(serde_json::json!({ "name": &name, "scope": requested_scope })),
+            detail: Some(serde_json::json!({
+                "name": &name,
+                "scope": requested_scope,
+                "purpose": "api",
+                "session_id": auth.session_id,
+            })),
             ip_addr: auth.ip_addr.clone(),
         },
     );
@@ -336,6 +400,58 @@ async fn read_project_secret(
     })))
 }
 
+/// Who decrypted a project secret, when, and why (pipeline step, agent session,
+/// deployment, or API read), newest first.
+#[tracing::instrument(skip(state), fields(%id, %name), err)]
+async fn secret_access_log(
+    State(state): State<AppState>,
+    auth: AuthUser,
+    Path((id, name)): Path<(Uuid, String)>,
+    Query(params): Query<AccessLogParams>,
+) -> Result<Json<ListResponse<AuditLogEntry>>, ApiError> {
+    require_secret_write(&state, &auth, id).await?;
+
+    let limit = params.limit.unwrap_or(50).min(100);
+    let offset = params.offset.unwrap_or(0);
+    let actions = [access::READ_ACTION, access::ACCESS_ACTION];
+
+    let total = sqlx::query_scalar!(
+        r#"SELECT COUNT(*) AS "count!" FROM audit_log
+         WHERE project_id = $1 AND resource = 'secret' AND action = ANY($2)
+           AND detail->>'name' = $3"#,
+        id,
+        &actions as &[&str],
+        name,
+    )
+    .fetch_one(&state.pool)
+    .await?;
+
+    let items = sqlx::query_as!(
+        AuditLogEntry,
+        "SELECT id, actor_id, actor_name, action, resource, resource_id, project_id, detail, break_glass_id, created_at \
+         FROM audit_log \
+         WHERE project_id = $1 AND resource = 'secret' AND action = ANY($2) \
+           AND detail->>'name' = $3 \
+         ORDER BY created_at DESC LIMIT $4 OFFSET $5",
+        id,
+        &actions as &[&str],
+        name,
+        limit,
+        offset,
+    )
+    .fetch_all(&state.pool)
+    .await?;
+    audit_read(
+        &state,
+        &auth,
+        ReadAudit::AuditLog,
+        Some(id),
+        serde_json::json!({"secret": &name, "limit": limit, "offset": offset}),
+    );
+
+    Ok(Json(ListResponse { items, total }))
+}
+
 #[tracing::instrument(skip(state), fields(%id, %name), err)]
 async fn delete_project_secret(
     State(state): State<AppState>,
@@ -647,6 +763,7 @@ async fn complete_secret_request(
                 name: &req_name,
                 value: body.value.as_bytes(),
                 scope: "agent",
+                backend: engine::BACKEND_LOCAL,
                 created_by: auth.user_id,
             },
         )
@@ -664,6 +781,7 @@ async fn complete_secret_request(
                     name: &req_name,
                     value: body.value.as_bytes(),
                     scope: "agent",
+                    backend: engine::BACKEND_LOCAL,
                     created_by: auth.user_id,
                 },
             )
@@ -712,6 +830,7 @@ async fn create_global_secret(
     validation::check_name(&body.name)?;
     validation::check_length("value", &body.value, 1, 65_536)?;
     validate_scope(&body.scope)?;
+    let backend = validate_backend(body.backend.as_deref(), &body.value)?;
 
     let master_key = get_master_key(&state)?;
 
@@ -721,6 +840,7 @@ async fn create_global_secret(
         &body.name,
         body.value.as_bytes(),
         &body.scope,
+        backend,
         auth.user_id,
     )
     .await
@@ -736,7 +856,7 @@ async fn create_global_secret(
             resource_id: Some(meta.id),
             project_id: None,
             detail: Some(
-                serde_json::json!({"name": body.name, "scope": body.scope, "global": true}),
+                serde_json::json!({"name": body.name, "scope": body.scope, "backend": backend, "global": true}),
             ),
             ip_addr: auth.ip_addr.clone(),
         },
@@ -745,6 +865,40 @@ async fn create_global_secret(
     Ok((StatusCode::CREATED, Json(meta)))
 }
 
+/// Re-encrypt all stored secrets and credentials with the current master key
+/// version, so older versions can be removed from `PLATFORM_MASTER_KEY`.
+async fn rewrap_secrets(
+    State(state): State<AppState>,
+    auth: AuthUser,
+) -> Result<Json<engine::RewrapReport>, ApiError> {
+    require_admin(&state, &auth).await?;
+
+    let master_key = get_master_key(&state)?;
+    let report = engine::rewrap_all(&state.pool, &master_key)
+        .await
+        .map_err(ApiError::Internal)?;
+
+    send_audit(
+        &state.audit_tx,
+        AuditEntry {
+            actor_id: auth.user_id,
+            actor_name: auth.user_name.clone(),
+            action: "secret.rewrap".into(),
+            resource: "secret".into(),
+            resource_id: None,
+            project_id: None,
+            detail: Some(serde_json::json!({
+                "key_version": report.key_version,
+                "rewrapped": report.rewrapped,
+                "failed": report.failed,
+            })),
+            ip_addr: auth.ip_addr.clone(),
+        },
+    );
+
+    Ok(Json(report))
+}
+
 async fn list_global_secrets(
     State(state): State<AppState>,
     auth: AuthUser,
@@ -754,6 +908,13 @@ async fn list_global_secrets(
     let secrets = engine::list_secrets(&state.pool, None, None)
         .await
         .map_err(ApiError::Internal)?;
+    audit_read(
+        &state,
+        &auth,
+        ReadAudit::Secrets,
+        None,
+        serde_json::json!({"scope": "global", "count": secrets.len()}),
+    );
 
     #[allow(clippy::cast_possible_wrap)]
     let total = secrets.len() as i64;
@@ -834,6 +995,8 @@ async fn create_workspace_secret(
     validation::check_name(&body.name)?;
     validation::check_length("value", &body.value, 1, 65_536)?;
     validate_scope(&body.scope)?;
+    let backend = validate_backend(body.backend.as_deref(), &body.value)?;
+    require_vault_admin(&state, &auth, backend).await?;
 
     let master_key = get_master_key(&state)?;
 
@@ -847,6 +1010,7 @@ async fn create_workspace_secret(
             name: &body.name,
             value: body.value.as_bytes(),
             scope: &body.scope,
+            backend,
             created_by: auth.user_id,
         },
     )
@@ -865,6 +1029,7 @@ async fn create_workspace_secret(
             detail: Some(serde_json::json!({
                 "name": body.name,
                 "scope": body.scope,
+                "backend": backend,
                 "workspace_id": id,
             })),
             ip_addr: auth.ip_addr.clone(),
@@ -884,6 +1049,13 @@ async fn list_workspace_secrets(
     let secrets = engine::list_workspace_secrets(&state.pool, id)
         .await
         .map_err(ApiError::Internal)?;
+    audit_read(
+        &state,
+        &auth,
+        ReadAudit::Secrets,
+        None,
+        serde_json::json!({"scope": "workspace", "workspace_id": id, "count": secrets.len()}),
+    );
 
     #[allow(clippy::cast_possible_wrap)]
     let total = secrets.len() as i64;
@@ -17,6 +17,7 @@ use ts_rs::TS;
 
 use crate::agent::AgentRoleName;
 use crate::agent::service;
+use crate::agent::snapshot::{self, AgentSnapshot};
 use crate::audit::{AuditEntry, send_audit};
 use crate::auth::middleware::AuthUser;
 use crate::error::ApiError;
@@ -37,6 +38,14 @@ pub struct CreateSessionRequest {
     pub config: Option<serde_json::Value>,
     /// Agent role: "dev" (default), "ops", "test", "review", "manager".
     pub role: Option<String>,
+    /// Seed the new session's workspace from a snapshot of an earlier session.
+    pub restore_from: Option<RestoreFrom>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct RestoreFrom {
+    pub session_id: Uuid,
+    pub snapshot: String,
 }
 
 #[derive(Debug, Deserialize)]
@@ -170,6 +179,10 @@ pub fn router() -> Router<AppState> {
             "/api/projects/{id}/sessions/{session_id}/events",
             get(sse_session_events),
         )
+        .route(
+            "/api/projects/{id}/sessions/{session_id}/snapshots",
+            get(list_snapshots).post(create_snapshot),
+        )
         // Global (project-less) endpoints
         .route(
             "/api/sessions/{session_id}",
@@ -246,7 +259,7 @@ pub fn router() -> Router<AppState> {
 // Permission helpers
 // ---------------------------------------------------------------------------
 
-use super::helpers::require_project_read;
+use super::helpers::{require_not_archived, require_project_read};
 
 async fn require_agent_run(
     state: &AppState,
@@ -271,7 +284,8 @@ async fn require_agent_run(
     Ok(())
 }
 
-/// Session owner or project:write can mutate sessions.
+/// Session owner or project:write can mutate sessions, unless the project
+/// is archived.
 async fn require_session_write(
     state: &AppState,
     auth: &AuthUser,
@@ -279,6 +293,7 @@ async fn require_session_write(
     session_user_id: Uuid,
 ) -> Result<(), ApiError> {
     auth.check_project_scope(project_id)?;
+    require_not_archived(state, project_id).await?;
     if auth.user_id == session_user_id {
         return Ok(());
     }
@@ -388,6 +403,10 @@ async fn create_session(
     .await?
     .ok_or_else(|| ApiError::NotFound("project".into()))?;
 
+    if let Some(ref restore) = body.restore_from {
+        validate_restore_from(&state, id, restore).await?;
+    }
+
     // Create session (identity + pod)
     let session = service::create_session(
         &state,
@@ -403,6 +422,15 @@ async fn create_session(
     .await
     .map_err(ApiError::from)?;
 
+    if let Some(restore) = body.restore_from.as_ref() {
+        snapshot::spawn_restore(
+            state.clone(),
+            session.id,
+            restore.session_id,
+            restore.snapshot.clone(),
+        );
+    }
+
     // Audit log (never log prompt content)
     send_audit(
         &state.audit_tx,
@@ -417,6 +445,9 @@ async fn create_session(
                 "provider": provider,
                 "branch": session.branch,
                 "role": role_str,
+                "restore_from": body.restore_from.as_ref().map(|r| {
+                    serde_json::json!({"session_id": r.session_id, "snapshot": r.snapshot})
+                }),
             })),
             ip_addr: auth.ip_addr.clone(),
         },
@@ -438,6 +469,30 @@ async fn create_session(
     ))
 }
 
+/// Restore source must be an existing snapshot of a session in the same project.
+async fn validate_restore_from(
+    state: &AppState,
+    project_id: Uuid,
+    restore: &RestoreFrom,
+) -> Result<(), ApiError> {
+    if snapshot::parse_snapshot_name(&restore.snapshot).is_none() {
+        return Err(ApiError::BadRequest("invalid snapshot name".into()));
+    }
+    let source = service::fetch_session(&state.pool, restore.session_id)
+        .await
+        .map_err(ApiError::from)?;
+    if source.project_id != Some(project_id) {
+        return Err(ApiError::NotFound("session".into()));
+    }
+    if !snapshot::snapshot_exists(state, restore.session_id, &restore.snapshot)
+        .await
+        .map_err(ApiError::from)?
+    {
+        return Err(ApiError::NotFound("snapshot".into()));
+    }
+    Ok(())
+}
+
 async fn list_sessions(
     State(state): State<AppState>,
     auth: AuthUser,
@@ -890,6 +945,74 @@ fn session_to_response(
     }
 }
 
+// ---------------------------------------------------------------------------
+// Snapshots
+// ---------------------------------------------------------------------------
+
+/// Snapshot a running session's workspace to object storage.
+#[tracing::instrument(skip(state, auth), fields(%id, %session_id), err)]
+async fn create_snapshot(
+    State(state): State<AppState>,
+    auth: AuthUser,
+    Path((id, session_id)): Path<(Uuid, Uuid)>,
+) -> Result<impl IntoResponse, ApiError> {
+    let session = service::fetch_session(&state.pool, session_id)
+        .await
+        .map_err(ApiError::from)?;
+
+    if session.project_id != Some(id) {
+        return Err(ApiError::NotFound("session".into()));
+    }
+
+    require_session_write(&state, &auth, id, session.user_id).await?;
+
+    if session.status != "running" {
+        return Err(ApiError::Conflict("session is not running".into()));
+    }
+
+    let snapshot = snapshot::create_snapshot(&state, &session)
+        .await
+        .map_err(ApiError::from)?;
+
+    send_audit(
+        &state.audit_tx,
+        AuditEntry {
+            actor_id: auth.user_id,
+            actor_name: auth.user_name.clone(),
+            action: "agent_session.snapshot".into(),
+            resource: "agent_session".into(),
+            resource_id: Some(session_id),
+            project_id: Some(id),
+            detail: Some(serde_json::json!({"snapshot": snapshot.name})),
+            ip_addr: auth.ip_addr.clone(),
+        },
+    );
+
+    Ok((StatusCode::CREATED, Json(snapshot)))
+}
+
+async fn list_snapshots(
+    State(state): State<AppState>,
+    auth: AuthUser,
+    Path((id, session_id)): Path<(Uuid, Uuid)>,
+) -> Result<Json<ListResponse<AgentSnapshot>>, ApiError> {
+    require_project_read(&state, &auth, id).await?;
+
+    let session = service::fetch_session(&state.pool, session_id)
+        .await
+        .map_err(ApiError::from)?;
+
+    if session.project_id != Some(id) {
+        return Err(ApiError::NotFound("session".into()));
+    }
+
+    let items = snapshot::list_snapshots(&state, session_id)
+        .await
+        .map_err(ApiError::from)?;
+    let total = i64::try_from(items.len()).unwrap_or(i64::MAX);
+    Ok(Json(ListResponse { items, total }))
+}
+
 // ---------------------------------------------------------------------------
 // Iframe panels
 // ---------------------------------------------------------------------------
@@ -1614,8 +1737,7 @@ async fn check_action_rejection(
         .next()
         .exists::<i64, _>(&key)
         .await
-        .map(|n| n > 0)
-        .unwrap_or(false);
+        .is_ok_and(|n| n > 0);
     if exists {
         let _: () = state.valkey.next().del(&key).await.unwrap_or(());
     }
@@ -1634,8 +1756,7 @@ async fn check_action_approval(
         .next()
         .exists::<i64, _>(&key)
         .await
-        .map(|n| n > 0)
-        .unwrap_or(false);
+        .is_ok_and(|n| n > 0);
 
     // If approved, consume it (single-use)
     if exists {
@@ -89,7 +89,7 @@ async fn setup(
     crate::auth::rate_limit::check_rate(&state.valkey, "setup", "global", 3, 300).await?;
 
     // Validate inputs
-    validate_setup_request(&body)?;
+    validate_setup_request(&body, &state.config.password_policy())?;
 
     // Atomically consume the token (prevents race condition with concurrent requests)
     let token_hash = bootstrap::hash_setup_token(&body.token);
@@ -183,10 +183,13 @@ async fn setup(
     ))
 }
 
-fn validate_setup_request(body: &SetupRequest) -> Result<(), ApiError> {
+fn validate_setup_request(
+    body: &SetupRequest,
+    policy: &validation::PasswordPolicy,
+) -> Result<(), ApiError> {
     validation::check_name(&body.name)?;
     validation::check_email(&body.email)?;
-    validation::check_length("password", &body.password, 8, 1024)?;
+    validation::check_password(&body.password, policy)?;
     if let Some(ref dn) = body.display_name {
         validation::check_length("display_name", dn, 1, 255)?;
     }
@@ -209,40 +212,91 @@ mod tests {
 
     #[test]
     fn setup_request_validation_empty_name() {
-        assert!(validate_setup_request(&make_body("", "a@b.c", "password123")).is_err());
+        assert!(
+            validate_setup_request(
+                &make_body("", "a@b.c", "password123"),
+                &validation::PasswordPolicy::default()
+            )
+            .is_err()
+        );
     }
 
     #[test]
     fn setup_request_validation_empty_email() {
-        assert!(validate_setup_request(&make_body("admin", "", "password123")).is_err());
+        assert!(
+            validate_setup_request(
+                &make_body("admin", "", "password123"),
+                &validation::PasswordPolicy::default()
+            )
+            .is_err()
+        );
     }
 
     #[test]
     fn setup_request_validation_short_password() {
-        assert!(validate_setup_request(&make_body("admin", "a@b.c", "short")).is_err());
+        assert!(
+            validate_setup_request(
+                &make_body("admin", "a@b.c", "short"),
+                &validation::PasswordPolicy::default()
+            )
+            .is_err()
+        );
     }
 
     #[test]
     fn setup_request_validation_email_format() {
-        assert!(validate_setup_request(&make_body("admin", "notanemail", "password123")).is_err());
+        assert!(
+            validate_setup_request(
+                &make_body("admin", "notanemail", "password123"),
+                &validation::PasswordPolicy::default()
+            )
+            .is_err()
+        );
     }
 
     #[test]
     fn setup_request_validation_name_too_long() {
         let long_name = "a".repeat(256);
-        assert!(validate_setup_request(&make_body(&long_name, "a@b.c", "password123")).is_err());
+        assert!(
+            validate_setup_request(
+                &make_body(&long_name, "a@b.c", "password123"),
+                &validation::PasswordPolicy::default()
+            )
+            .is_err()
+        );
     }
 
     #[test]
     fn setup_request_validation_password_too_long() {
         let long_pw = "a".repeat(1025);
-        assert!(validate_setup_request(&make_body("admin", "a@b.c", &long_pw)).is_err());
+        assert!(
+            validate_setup_request(
+                &make_body("admin", "a@b.c", &long_pw),
+                &validation::PasswordPolicy::default()
+            )
+            .is_err()
+        );
+    }
+
+    #[test]
+    fn setup_request_validation_common_password() {
+        assert!(
+            validate_setup_request(
+                &make_body("admin", "a@b.c", "password123"),
+                &validation::PasswordPolicy::default()
+            )
+            .is_err()
+        );
     }
 
     #[test]
     fn setup_request_validation_valid() {
         assert!(
-            validate_setup_request(&make_body("admin", "admin@example.com", "password123")).is_ok()
+            validate_setup_request(
+                &make_body("admin", "admin@example.com", "correct-horse-42"),
+                &validation::PasswordPolicy::default()
+            )
+            .is_ok()
         );
     }
 }
@@ -0,0 +1,258 @@
+// Copyright (c) 2026 Steven Hooker. Exclusively licensed to and distributed by AgentSphere GmbH.
+// SPDX-License-Identifier: BUSL-1.1
+
+use axum::extract::State;
+use axum::http::StatusCode;
+use axum::routing::{get, post};
+use axum::{Json, Router};
+use chrono::Utc;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use ts_rs::TS;
+
+use crate::audit::{AuditEntry, send_audit};
+use crate::auth::middleware::AuthUser;
+use crate::auth::totp;
+use crate::error::ApiError;
+use crate::secrets::engine;
+use crate::store::AppState;
+
+// ---------------------------------------------------------------------------
+// Types
+// ---------------------------------------------------------------------------
+
+#[derive(Debug, Serialize, TS)]
+#[ts(export)]
+pub struct TotpEnrollResponse {
+    /// Base32 secret for manual entry into an authenticator app.
+    pub secret: String,
+    /// `otpauth://` URI to render as a QR code.
+    pub provisioning_uri: String,
+}
+
+#[derive(Debug, Serialize, TS)]
+#[ts(export)]
+pub struct TotpStatusResponse {
+    pub enabled: bool,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct TotpCodeRequest {
+    pub code: String,
+}
+
+// ---------------------------------------------------------------------------
+// Router
+// ---------------------------------------------------------------------------
+
+pub fn router() -> Router<AppState> {
+    Router::new()
+        .route("/api/auth/totp", get(status))
+        .route("/api/auth/totp/enroll", post(enroll))
+        .route("/api/auth/totp/verify", post(confirm))
+        .route("/api/auth/totp/disable", post(disable))
+}
+
+// ---------------------------------------------------------------------------
+// Helpers
+// ---------------------------------------------------------------------------
+
+fn get_master_key(state: &AppState) -> Result<engine::MasterKey, ApiError> {
+    let hex_str = state
+        .config
+        .master_key
+        .as_deref()
+        .ok_or_else(|| ApiError::ServiceUnavailable("secrets engine not configured".into()))?;
+    engine::parse_master_key(hex_str).map_err(|e| {
+        tracing::error!(error = %e, "invalid master key configuration");
+        ApiError::ServiceUnavailable("secrets engine misconfigured".into())
+    })
+}
+
+/// `(encrypted_secret, last_used_step, enabled)` for a user's TOTP enrollment.
+async fn load_enrollment(
+    state: &AppState,
+    user_id: Uuid,
+) -> Result<Option<(Vec<u8>, Option<i64>, bool)>, ApiError> {
+    let row = sqlx::query!(
+        "SELECT encrypted_secret, last_used_step, enabled FROM user_totp WHERE user_id = $1",
+        user_id
+    )
+    .fetch_optional(&state.pool)
+    .await?;
+    Ok(row.map(|r| (r.encrypted_secret, r.last_used_step, r.enabled)))
+}
+
+/// Verify `code` and consume its time step. Attempts are rate-limited per user
+/// (5 per 5 minutes).
+async fn accept_code(
+    state: &AppState,
+    user_id: Uuid,
+    encrypted_secret: &[u8],
+    last_used_step: Option<i64>,
+    code: &str,
+) -> Result<(), ApiError> {
+    crate::auth::rate_limit::check_rate(&state.valkey, "totp", &user_id.to_string(), 5, 300)
+        .await?;
+
+    let secret = get_master_key(state)?
+        .decrypt(encrypted_secret)
+        .map_err(ApiError::Internal)?;
+    let now_step = totp::step_at(Utc::now().timestamp());
+    let step = totp::verify(&secret, code.trim(), now_step, last_used_step)
+        .ok_or(ApiError::Unauthorized)?;
+
+    // Compare-and-swap so two concurrent requests cannot both use one code.
+    let claimed = sqlx::query!(
+        "UPDATE user_totp SET last_used_step = $2
+         WHERE user_id = $1 AND (last_used_step IS NULL OR last_used_step < $2)",
+        user_id,
+        step,
+    )
+    .execute(&state.pool)
+    .await?;
+    if claimed.rows_affected() == 0 {
+        return Err(ApiError::Unauthorized);
+    }
+    Ok(())
+}
+
+/// Login gate: when TOTP is enabled for `user_id`, `code` must be a valid,
+/// unused code. A missing code is a 400 so clients know to prompt for one.
+pub(super) async fn check_login_code(
+    state: &AppState,
+    user_id: Uuid,
+    code: Option<&str>,
+) -> Result<(), ApiError> {
+    let Some((encrypted, last_used, true)) = load_enrollment(state, user_id).await? else {
+        return Ok(());
+    };
+    let code = code.ok_or_else(|| ApiError::BadRequest("totp_code required".into()))?;
+    accept_code(state, user_id, &encrypted, last_used, code).await
+}
+
+fn audit(state: &AppState, auth: &AuthUser, action: &str) {
+    send_audit(
+        &state.audit_tx,
+        AuditEntry {
+            actor_id: auth.user_id,
+            actor_name: auth.user_name.clone(),
+            action: action.into(),
+            resource: "user".into(),
+            resource_id: Some(auth.user_id),
+            project_id: None,
+            detail: None,
+            ip_addr: auth.ip_addr.clone(),
+        },
+    );
+}
+
+// ---------------------------------------------------------------------------
+// Handlers
+// ---------------------------------------------------------------------------
+
+async fn status(
+    State(state): State<AppState>,
+    auth: AuthUser,
+) -> Result<Json<TotpStatusResponse>, ApiError> {
+    let enabled = load_enrollment(&state, auth.user_id)
+        .await?
+        .is_some_and(|(_, _, enabled)| enabled);
+    Ok(Json(TotpStatusResponse { enabled }))
+}
+
+/// Start (or restart) enrollment with a fresh secret. TOTP is not enforced
+/// until the user confirms a code via `/api/auth/totp/verify`.
+#[tracing::instrument(skip(state), fields(user_id = %auth.user_id), err)]
+async fn enroll(
+    State(state): State<AppState>,
+    auth: AuthUser,
+) -> Result<(StatusCode, Json<TotpEnrollResponse>), ApiError> {
+    let master_key = get_master_key(&state)?;
+    let secret = totp::generate_secret();
+    let encrypted = master_key.encrypt(&secret).map_err(ApiError::Internal)?;
+
+    let result = sqlx::query!(
+        "INSERT INTO user_totp (user_id, encrypted_secret)
+         VALUES ($1, $2)
+         ON CONFLICT (user_id) DO UPDATE SET
+             encrypted_secret = EXCLUDED.encrypted_secret,
+             last_used_step = NULL,
+             created_at = now()
+         WHERE user_totp.enabled = false",
+        auth.user_id,
+        encrypted,
+    )
+    .execute(&state.pool)
+    .await?;
+    if result.rows_affected() == 0 {
+        return Err(ApiError::Conflict("TOTP is already enabled".into()));
+    }
+
+    audit(&state, &auth, "auth.totp_enroll");
+
+    Ok((
+        StatusCode::CREATED,
+        Json(TotpEnrollResponse {
+            secret: totp::encode_secret(&secret),
+            provisioning_uri: totp::provisioning_uri(
+                &state.config.webauthn_rp_name,
+                &auth.user_name,
+                &secret,
+            ),
+        }),
+    ))
+}
+
+/// Confirm enrollment with a code from the authenticator app; from then on
+/// login requires a code.
+#[tracing::instrument(skip(state, body), fields(user_id = %auth.user_id), err)]
+async fn confirm(
+    State(state): State<AppState>,
+    auth: AuthUser,
+    Json(body): Json<TotpCodeRequest>,
+) -> Result<Json<TotpStatusResponse>, ApiError> {
+    let (encrypted, last_used, enabled) = load_enrollment(&state, auth.user_id)
+        .await?
+        .ok_or_else(|| ApiError::NotFound("totp enrollment".into()))?;
+    if enabled {
+        return Err(ApiError::Conflict("TOTP is already enabled".into()));
+    }
+
+    accept_code(&state, auth.user_id, &encrypted, last_used, &body.code).await?;
+
+    sqlx::query!(
+        "UPDATE user_totp SET enabled = true WHERE user_id = $1",
+        auth.user_id
+    )
+    .execute(&state.pool)
+    .await?;
+
+    audit(&state, &auth, "auth.totp_enable");
+
+    Ok(Json(TotpStatusResponse { enabled: true }))
+}
+
+/// Turn TOTP off. Requires a current code so a hijacked session alone
+/// cannot remove the second factor.
+#[tracing::instrument(skip(state, body), fields(user_id = %auth.user_id), err)]
+async fn disable(
+    State(state): State<AppState>,
+    auth: AuthUser,
+    Json(body): Json<TotpCodeRequest>,
+) -> Result<StatusCode, ApiError> {
+    let Some((encrypted, last_used, true)) = load_enrollment(&state, auth.user_id).await? else {
+        return Err(ApiError::NotFound("totp enrollment".into()));
+    };
+
+    accept_code(&state, auth.user_id, &encrypted, last_used, &body.code).await?;
+
+    sqlx::query!("DELETE FROM user_totp WHERE user_id = $1", auth.user_id)
+        .execute(&state.pool)
+        .await?;
+
+    audit(&state, &auth, "auth.totp_disable");
+
+    Ok(StatusCode::NO_CONTENT)
+}
@@ -0,0 +1,532 @@
+// Copyright (c) 2026 Steven Hooker. Exclusively licensed to and distributed by AgentSphere GmbH.
+// SPDX-License-Identifier: BUSL-1.1
+
+//! Bulk user import: `POST /api/admin/users/import`.
+//!
+//! Takes JSON (`{"users": [...]}`) or CSV (`Content-Type: text/csv`, header
+//! row `name,email[,display_name][,roles]`, roles separated by `;`). Every
+//! user gets a generated temporary password, returned once in the response.
+//! Bad rows are reported individually; the rest are created in a single
+//! transaction.
+
+use std::collections::{HashMap, HashSet};
+
+use axum::body::Bytes;
+use axum::extract::State;
+use axum::http::{HeaderMap, header};
+use axum::routing::post;
+use axum::{Json, Router};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use ts_rs::TS;
+
+use crate::api::helpers::require_admin;
+use crate::audit::{AuditEntry, send_audit};
+use crate::auth::middleware::AuthUser;
+use crate::auth::password;
+use crate::error::ApiError;
+use crate::rbac::resolver;
+use crate::store::AppState;
+use crate::validation::{self, PasswordPolicy};
+
+/// Largest batch accepted in one request.
+const MAX_IMPORT_ROWS: usize = 500;
+
+/// Temporary passwords are at least this long, or the policy minimum.
+const TEMP_PASSWORD_LEN: usize = 20;
+
+/// No look-alike characters (`0`/`O`, `1`/`l`/`I`).
+const TEMP_PASSWORD_CHARS: &[u8] =
+    b"ABCDEFGHJKLMNPQRSTUVWXYZabcdefghijkmnopqrstuvwxyz23456789!@#$%*-_=+";
+
+// ---------------------------------------------------------------------------
+// Types
+// ---------------------------------------------------------------------------
+
+#[derive(Debug, Deserialize)]
+pub struct ImportUsersRequest {
+    pub users: Vec<ImportUserRow>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ImportUserRow {
+    pub name: String,
+    pub email: String,
+    pub display_name: Option<String>,
+    /// Names of global roles to assign, e.g. `developer`.
+    #[serde(default)]
+    pub roles: Vec<String>,
+}
+
+#[derive(Debug, Serialize, TS)]
+#[ts(export)]
+pub struct ImportUsersResponse {
+    pub created: Vec<ImportedUser>,
+    pub failed: Vec<ImportFailure>,
+}
+
+#[derive(Debug, Serialize, TS)]
+#[ts(export)]
+pub struct ImportedUser {
+    /// 1-based position in the request (CSV: data row, header excluded).
+    pub row: u32,
+    pub id: Uuid,
+    pub name: String,
+    pub email: String,
+    /// Shown only in this response; the user should change it after login.
+    pub temporary_password: String,
+}
+
+#[derive(Debug, Serialize, TS)]
+#[ts(export)]
+pub struct ImportFailure {
+    pub row: u32,
+    pub name: Option<String>,
+    pub error: String,
+}
+
+/// A validated row waiting to be inserted.
+struct PendingUser {
+    row: u32,
+    name: String,
+    email: String,
+    display_name: Option<String>,
+    role_ids: Vec<Uuid>,
+    role_names: Vec<String>,
+    password: String,
+}
+
+// ---------------------------------------------------------------------------
+// Router
+// ---------------------------------------------------------------------------
+
+pub fn router() -> Router<AppState> {
+    Router::new().route("/api/admin/users/import", post(import_users))
+}
+
+// ---------------------------------------------------------------------------
+// Handler
+// ---------------------------------------------------------------------------
+
+#[tracing::instrument(skip(state, headers, body), err)]
+async fn import_users(
+    State(state): State<AppState>,
+    auth: AuthUser,
+    headers: HeaderMap,
+    body: Bytes,
+) -> Result<Json<ImportUsersResponse>, ApiError> {
+    require_admin(&state, &auth).await?;
+
+    let rows = parse_body(&headers, &body)?;
+    if rows.is_empty() {
+        return Err(ApiError::BadRequest("no users to import".into()));
+    }
+    if rows.len() > MAX_IMPORT_ROWS {
+        return Err(ApiError::BadRequest(format!(
+            "at most {MAX_IMPORT_ROWS} users per import"
+        )));
+    }
+
+    let roles: HashMap<String, Uuid> = sqlx::query!("SELECT name, id FROM roles")
+        .fetch_all(&state.pool)
+        .await?
+        .into_iter()
+        .map(|r| (r.name, r.id))
+        .collect();
+    let policy = state.config.password_policy();
+
+    let (pending, mut failed) = prepare_rows(rows, &roles, &policy);
+
+    let hashes = hash_passwords(&pending).await?;
+
+    let mut created = Vec::new();
+    let mut created_pending = Vec::new();
+    let mut tx = state.pool.begin().await?;
+    for (user, hash) in pending.into_iter().zip(hashes) {
+        let id = sqlx::query_scalar!(
+            "INSERT INTO users (name, display_name, email, password_hash, user_type)
+             VALUES ($1, $2, $3, $4, 'human')
+             ON CONFLICT DO NOTHING
+             RETURNING id",
+            user.name,
+            user.display_name,
+            user.email,
+            hash,
+        )
+        .fetch_optional(&mut *tx)
+        .await?;
+        let Some(id) = id else {
+            failed.push(ImportFailure {
+                row: user.row,
+                name: Some(user.name),
+                error: "a user with this name or email already exists".into(),
+            });
+            continue;
+        };
+
+        for role_id in &user.role_ids {
+            sqlx::query!(
+                "INSERT INTO user_roles (user_id, role_id, granted_by) VALUES ($1, $2, $3)",
+                id,
+                role_id,
+                auth.user_id,
+            )
+            .execute(&mut *tx)
+            .await?;
+        }
+        created.push(ImportedUser {
+            row: user.row,
+            id,
+            name: user.name.clone(),
+            email: user.email.clone(),
+            temporary_password: user.password.clone(),
+        });
+        created_pending.push((id, user));
+    }
+    tx.commit().await?;
+
+    for (id, user) in &created_pending {
+        let display = user.display_name.as_deref().unwrap_or(&user.name);
+        let _ = crate::workspace::service::get_or_create_default_workspace(
+            &state.pool,
+            *id,
+            &user.name,
+            display,
+        )
+        .await;
+        if !user.role_ids.is_empty() {
+            let _ = resolver::invalidate_permissions(&state.valkey, *id, None).await;
+        }
+        send_audit(
+            &state.audit_tx,
+            AuditEntry {
+                actor_id: auth.user_id,
+                actor_name: auth.user_name.clone(),
+                action: "user.create".into(),
+                resource: "user".into(),
+                resource_id: Some(*id),
+                project_id: None,
+                detail: Some(serde_json::json!({
+                    "name": user.name,
+                    "user_type": "human",
+                    "import": true,
+                    "roles": user.role_names,
+                })),
+                ip_addr: auth.ip_addr.clone(),
+            },
+        );
+    }
+
+    failed.sort_by_key(|f| f.row);
+    Ok(Json(ImportUsersResponse { created, failed }))
+}
+
+// ---------------------------------------------------------------------------
+// Rows
+// ---------------------------------------------------------------------------
+
+#[derive(Debug)]
+struct RowError {
+    name: Option<String>,
+    error: String,
+}
+
+impl RowError {
+    fn new(name: Option<&str>, error: impl Into<String>) -> Self {
+        Self {
+            name: name.map(str::to_owned),
+            error: error.into(),
+        }
+    }
+}
+
+/// Hash the temporary passwords of `pending`, in order. Argon2 is deliberately
+/// slow, so this runs off the async workers.
+async fn hash_passwords(pending: &[PendingUser]) -> Result<Vec<String>, ApiError> {
+    let passwords: Vec<String> = pending.iter().map(|u| u.password.clone()).collect();
+    tokio::task::spawn_blocking(move || {
+        passwords
+            .iter()
+            .map(|pw| password::hash_password(pw))
+            .collect::<anyhow::Result<Vec<_>>>()
+    })
+    .await
+    .map_err(|e| ApiError::Internal(e.into()))?
+    .map_err(ApiError::Internal)
+}
+
+/// Validate every row, rejecting names and emails repeated within the import.
+fn prepare_rows(
+    rows: Vec<Result<ImportUserRow, RowError>>,
+    roles: &HashMap<String, Uuid>,
+    policy: &PasswordPolicy,
+) -> (Vec<PendingUser>, Vec<ImportFailure>) {
+    let mut failed = Vec::new();
+    let mut pending = Vec::new();
+    let mut seen_names = HashSet::new();
+    let mut seen_emails = HashSet::new();
+    for (i, row) in rows.into_iter().enumerate() {
+        let row_no = u32::try_from(i + 1).unwrap_or(u32::MAX);
+        let user = row.and_then(|r| {
+            let user = prepare_row(row_no, r, roles, policy)?;
+            if !seen_names.insert(user.name.clone()) || !seen_emails.insert(user.email.clone()) {
+                return Err(RowError::new(
+                    Some(&user.name),
+                    "duplicate name or email in this import",
+                ));
+            }
+            Ok(user)
+        });
+        match user {
+            Ok(user) => pending.push(user),
+            Err(e) => failed.push(ImportFailure {
+                row: row_no,
+                name: e.name,
+                error: e.error,
+            }),
+        }
+    }
+    (pending, failed)
+}
+
+fn prepare_row(
+    row: u32,
+    r: ImportUserRow,
+    roles: &HashMap<String, Uuid>,
+    policy: &PasswordPolicy,
+) -> Result<PendingUser, RowError> {
+    let invalid = |e: ApiError| {
+        let error = match e {
+            ApiError::BadRequest(msg) => msg,
+            other => other.to_string(),
+        };
+        RowError::new(Some(&r.name), error)
+    };
+    validation::check_name(&r.name).map_err(invalid)?;
+    validation::check_email(&r.email).map_err(invalid)?;
+    if let Some(ref dn) = r.display_name {
+        validation::check_length("display_name", dn, 1, 255).map_err(invalid)?;
+    }
+
+    let mut role_names: Vec<String> = Vec::new();
+    for role in &r.roles {
+        if !role_names.contains(role) {
+            role_names.push(role.clone());
+        }
+    }
+    let unknown: Vec<&str> = role_names
+        .iter()
+        .filter(|n| !roles.contains_key(*n))
+        .map(String::as_str)
+        .collect();
+    if !unknown.is_empty() {
+        return Err(RowError::new(
+            Some(&r.name),
+            format!("unknown role(s): {}", unknown.join(", ")),
+        ));
+    }
+    let role_ids = role_names.iter().map(|n| roles[n]).collect();
+
+    Ok(PendingUser {
+        row,
+        name: r.name,
+        email: r.email,
+        display_name: r.display_name,
+        role_ids,
+        role_names,
+        password: temporary_password(policy),
+    })
+}
+
+/// Random password that satisfies `policy`.
+fn temporary_password(policy: &PasswordPolicy) -> String {
+    let len = TEMP_PASSWORD_LEN.max(policy.min_length);
+    loop {
+        let mut bytes = vec![0u8; len];
+        rand::fill(&mut bytes[..]);
+        let candidate: String = bytes
+            .iter()
+            .map(|b| char::from(TEMP_PASSWORD_CHARS[usize::from(*b) % TEMP_PASSWORD_CHARS.len()]))
+            .collect();
+        if validation::check_password(&candidate, policy).is_ok() {
+            return candidate;
+        }
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Parsing
+// ---------------------------------------------------------------------------
+
+/// Rows from a JSON or CSV body. Malformed CSV rows become row errors; a
+/// malformed body as a whole is a `BadRequest`.
+fn parse_body(
+    headers: &HeaderMap,
+    body: &[u8],
+) -> Result<Vec<Result<ImportUserRow, RowError>>, ApiError> {
+    let is_csv = headers
+        .get(header::CONTENT_TYPE)
+        .and_then(|v| v.to_str().ok())
+        .is_some_and(|ct| ct.starts_with("text/csv"));
+    if is_csv {
+        let text = std::str::from_utf8(body)
+            .map_err(|_| ApiError::BadRequest("CSV body must be UTF-8".into()))?;
+        parse_csv(text)
+    } else {
+        let req: ImportUsersRequest = serde_json::from_slice(body)
+            .map_err(|e| ApiError::BadRequest(format!("invalid JSON: {e}")))?;
+        Ok(req.users.into_iter().map(Ok).collect())
+    }
+}
+
+fn parse_csv(text: &str) -> Result<Vec<Result<ImportUserRow, RowError>>, ApiError> {
+    let mut lines = text.lines().filter(|l| !l.trim().is_empty());
+    let header = lines
+        .next()
+        .ok_or_else(|| ApiError::BadRequest("CSV is empty".into()))?;
+    let columns: Vec<String> = split_csv_line(header)
+        .map_err(|e| ApiError::BadRequest(format!("CSV header: {e}")))?
+        .into_iter()
+        .map(|c| c.trim().to_lowercase())
+        .collect();
+    let index = |name: &str| columns.iter().position(|c| c == name);
+    let (Some(name_col), Some(email_col)) = (index("name"), index("email")) else {
+        return Err(ApiError::BadRequest(
+            "CSV header must include name and email columns".into(),
+        ));
+    };
+    let display_col = index("display_name");
+    let roles_col = index("roles");
+
+    Ok(lines
+        .map(|line| {
+            let fields = split_csv_line(line).map_err(|e| RowError::new(None, e))?;
+            if fields.len() != columns.len() {
+                return Err(RowError::new(
+                    fields.get(name_col).map(String::as_str),
+                    format!("expected {} fields, got {}", columns.len(), fields.len()),
+                ));
+            }
+            let field = |i: Option<usize>| {
+                i.map(|i| fields[i].trim())
+                    .filter(|v| !v.is_empty())
+                    .map(str::to_owned)
+            };
+            Ok(ImportUserRow {
+                name: fields[name_col].trim().to_owned(),
+                email: fields[email_col].trim().to_owned(),
+                display_name: field(display_col),
+                roles: field(roles_col)
+                    .map(|r| {
+                        r.split(';')
+                            .map(str::trim)
+                            .filter(|r| !r.is_empty())
+                            .map(str::to_owned)
+                            .collect()
+                    })
+                    .unwrap_or_default(),
+            })
+        })
+        .collect())
+}
+
+/// Split one CSV line, honouring `"quoted, fields"` and `""` escapes.
+fn split_csv_line(line: &str) -> Result<Vec<String>, String> {
+    let mut fields = Vec::new();
+    let mut field = String::new();
+    let mut in_quotes = false;
+    let mut chars = line.chars().peekable();
+    while let Some(c) = chars.next() {
+        match (c, in_quotes) {
+            ('"', true) if chars.peek() == Some(&'"') => {
+                field.push('"');
+                chars.next();
+            }
+            ('"', true) => in_quotes = false,
+            ('"', false) if field.trim().is_empty() => {
+                field.clear();
+                in_quotes = true;
+            }
+            (',', false) => fields.push(std::mem::take(&mut field)),
+            _ => field.push(c),
+        }
+    }
+    if in_quotes {
+        return Err("unterminated quoted field".into());
+    }
+    fields.push(field);
+    Ok(fields)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn split_plain_and_quoted_fields() {
+        assert_eq!(split_csv_line("a,b,,c").unwrap(), vec!["a", "b", "", "c"]);
+        assert_eq!(
+            split_csv_line(r#"alice,"Smith, Alice","say ""hi""""#).unwrap(),
+            vec!["alice", "Smith, Alice", r#"say "hi""#]
+        );
+        assert!(split_csv_line(r#"alice,"open"#).is_err());
+    }
+
+    #[test]
+    fn csv_rows_with_optional_columns() {
+        let rows = parse_csv(
+            "Name,Email,Display_Name,Roles\n\
+             alice,alice@example.com,Alice,developer; viewer\n\
+             bob,bob@example.com,,\n\
+             \n\
+             carol,carol@example.com\n",
+        )
+        .unwrap();
+        assert_eq!(rows.len(), 3);
+        let alice = rows[0].as_ref().unwrap();
+        assert_eq!(alice.display_name.as_deref(), Some("Alice"));
+        assert_eq!(alice.roles, vec!["developer", "viewer"]);
+        let bob = rows[1].as_ref().unwrap();
+        assert!(bob.display_name.is_none() && bob.roles.is_empty());
+        let err = rows[2].as_ref().unwrap_err();
+        assert_eq!(err.name.as_deref(), Some("carol"));
+    }
+
+    #[test]
+    fn csv_requires_name_and_email() {
+        assert!(parse_csv("name,display_name\nalice,Alice\n").is_err());
+        assert!(parse_csv("").is_err());
+    }
+
+    #[test]
+    fn unknown_roles_are_row_errors() {
+        let roles = HashMap::from([("developer".to_owned(), Uuid::new_v4())]);
+        let row = ImportUserRow {
+            name: "alice".into(),
+            email: "alice@example.com".into(),
+            display_name: None,
+            roles: vec!["developer".into(), "wizard".into()],
+        };
+        let err = prepare_row(1, row, &roles, &PasswordPolicy::default())
+            .err()
+            .unwrap();
+        assert_eq!(err.error, "unknown role(s): wizard");
+    }
+
+    #[test]
+    fn temporary_password_meets_strict_policy() {
+        let policy = PasswordPolicy {
+            min_length: 24,
+            require_mixed_case: true,
+            require_digit: true,
+            require_symbol: true,
+            denylist: Vec::new(),
+        };
+        for _ in 0..20 {
+            let pw = temporary_password(&policy);
+            assert_eq!(pw.len(), 24);
+            assert!(validation::check_password(&pw, &policy).is_ok());
+        }
+    }
+}
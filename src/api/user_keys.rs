@@ -46,7 +46,7 @@ pub struct ValidateKeyResponse {
 // Helpers
 // ---------------------------------------------------------------------------
 
-fn get_master_key(state: &AppState) -> Result<[u8; 32], ApiError> {
+fn get_master_key(state: &AppState) -> Result<engine::MasterKey, ApiError> {
     let hex_str = state
         .config
         .master_key
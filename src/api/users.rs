@@ -14,9 +14,9 @@ use uuid::Uuid;
 use ts_rs::TS;
 
 use crate::audit::{AuditEntry, send_audit};
-use crate::auth::middleware::AuthUser;
+use crate::auth::middleware::{AuthUser, ClientInfo};
 use crate::auth::user_type::UserType;
-use crate::auth::{password, token};
+use crate::auth::{lockout, password, token};
 use crate::error::ApiError;
 use crate::rbac::Permission;
 use crate::store::AppState;
@@ -43,12 +43,16 @@ pub struct UpdateUserRequest {
     pub email: Option<String>,
     pub password: Option<String>,
     pub current_password: Option<String>,
+    /// Admin-only: require passkey login and reject password attempts.
+    pub password_login_disabled: Option<bool>,
 }
 
 #[derive(Debug, Deserialize)]
 pub struct LoginRequest {
     pub name: String,
     pub password: String,
+    /// Required when the account has TOTP enabled.
+    pub totp_code: Option<String>,
 }
 
 #[derive(Debug, Deserialize)]
@@ -117,6 +121,7 @@ pub fn router() -> Router<AppState> {
         // Auth routes (no auth required for login)
         .route("/api/auth/login", post(login))
         .route("/api/auth/logout", post(logout))
+        .route("/api/auth/logout-all", post(logout_all))
         .route("/api/auth/me", get(me))
         // User management (admin checks done inline per handler)
         .route("/api/users", get(list_users).post(create_user))
@@ -149,6 +154,7 @@ fn parse_user_type(s: &str) -> Result<UserType, ApiError> {
 #[tracing::instrument(skip(state, body), fields(username = %body.name), err)]
 async fn login(
     State(state): State<AppState>,
+    client: ClientInfo,
     Json(body): Json<LoginRequest>,
 ) -> Result<impl IntoResponse, ApiError> {
     // Rate-limit login attempts (10 per 5 minutes per username)
@@ -158,7 +164,7 @@ async fn login(
     let user = sqlx::query!(
         r#"
         SELECT id, name, display_name, email, password_hash, is_active,
-               user_type, created_at, updated_at
+               user_type, password_login_disabled, created_at, updated_at
         FROM users
         WHERE name = $1
         "#,
@@ -175,9 +181,26 @@ async fn login(
 
     let password_valid = password::verify_password(&body.password, &hash_to_verify);
 
+    // Locked accounts are refused even when the password is correct; unknown
+    // names lock the same way so the answer does not reveal which exist
+    let subject = user
+        .as_ref()
+        .map_or(lockout::Subject::UnknownName(&body.name), |u| {
+            lockout::Subject::User(u.id)
+        });
+    lockout::check(&state.valkey, subject).await?;
+
     let user = match user {
         Some(u) if password_valid && u.is_active => u,
-        _ => return Err(ApiError::Unauthorized),
+        Some(u) if !password_valid => {
+            record_login_failure(&state, client.ip_addr.as_deref(), u.id, &u.name).await?;
+            return Err(ApiError::Unauthorized);
+        }
+        Some(_) => return Err(ApiError::Unauthorized),
+        None => {
+            lockout::record_failure(&state.valkey, &state.config, subject).await?;
+            return Err(ApiError::Unauthorized);
+        }
     };
 
     // Reject non-human users (timing-safe: check after password verify)
@@ -186,15 +209,20 @@ async fn login(
         return Err(ApiError::Unauthorized);
     }
 
-    // Check for disabled password (passkey-only accounts)
-    if user.password_hash == "!disabled" {
+    // Check for disabled password (passkey-only accounts, or enforced by an admin)
+    if user.password_hash == "!disabled" || user.password_login_disabled {
         return Err(ApiError::BadRequest(
             "this account uses passkey authentication — use the passkey login flow".into(),
         ));
     }
 
+    // Second factor: accounts with TOTP enabled must present a valid code
+    super::totp::check_login_code(&state, user.id, body.totp_code.as_deref()).await?;
+
+    lockout::clear_failures(&state.valkey, user.id).await?;
+
     // Create session
-    let session = create_login_session(&state, user.id, &user.name).await?;
+    let session = create_login_session(&state, user.id, &user.name, &client).await?;
 
     let response = LoginResponse {
         token: session.token,
@@ -212,15 +240,7 @@ async fn login(
     };
 
     // Set session cookie + return JSON
-    let secure_flag = if state.config.secure_cookies {
-        "; Secure"
-    } else {
-        ""
-    };
-    let cookie = format!(
-        "session={}; Path=/; HttpOnly; SameSite=Strict; Max-Age=86400{secure_flag}",
-        response.token
-    );
+    let cookie = session_cookie(&state, &response.token);
     Ok((
         StatusCode::OK,
         [(axum::http::header::SET_COOKIE, cookie)],
@@ -228,30 +248,65 @@ async fn login(
     ))
 }
 
-struct SessionInfo {
-    token: String,
-    expires_at: DateTime<Utc>,
+/// Count a wrong password for an existing account, auditing when it locks.
+pub(crate) async fn record_login_failure(
+    state: &AppState,
+    ip_addr: Option<&str>,
+    user_id: Uuid,
+    user_name: &str,
+) -> Result<(), ApiError> {
+    if lockout::record_failure(
+        &state.valkey,
+        &state.config,
+        lockout::Subject::User(user_id),
+    )
+    .await?
+    {
+        tracing::warn!(%user_id, "account locked after repeated failed logins");
+        send_audit(
+            &state.audit_tx,
+            AuditEntry {
+                actor_id: user_id,
+                actor_name: user_name.to_string(),
+                action: "auth.account_locked".into(),
+                resource: "user".into(),
+                resource_id: Some(user_id),
+                project_id: None,
+                detail: Some(serde_json::json!({
+                    "duration_secs": state.config.login_lockout_duration_secs,
+                })),
+                ip_addr: ip_addr.map(str::to_owned),
+            },
+        );
+    }
+    Ok(())
+}
+
+pub(super) struct SessionInfo {
+    pub token: String,
+    pub expires_at: DateTime<Utc>,
 }
 
-async fn create_login_session(
+/// `Set-Cookie` value for a new login session.
+pub(super) fn session_cookie(state: &AppState, token: &str) -> String {
+    let secure_flag = if state.config.secure_cookies {
+        "; Secure"
+    } else {
+        ""
+    };
+    format!("session={token}; Path=/; HttpOnly; SameSite=Strict; Max-Age=86400{secure_flag}")
+}
+
+pub(super) async fn create_login_session(
     state: &AppState,
     user_id: Uuid,
     user_name: &str,
+    client: &ClientInfo,
 ) -> Result<SessionInfo, ApiError> {
     let (raw_token, token_hash) = token::generate_session_token();
     let expires_at = Utc::now() + Duration::hours(24);
 
-    sqlx::query!(
-        r#"
-        INSERT INTO auth_sessions (user_id, token_hash, expires_at)
-        VALUES ($1, $2, $3)
-        "#,
-        user_id,
-        token_hash,
-        expires_at,
-    )
-    .execute(&state.pool)
-    .await?;
+    insert_session(&state.pool, user_id, &token_hash, expires_at, client).await?;
 
     send_audit(
         &state.audit_tx,
@@ -263,7 +318,7 @@ async fn create_login_session(
             resource_id: None,
             project_id: None,
             detail: None,
-            ip_addr: None,
+            ip_addr: client.ip_addr.clone(),
         },
     );
 
@@ -273,23 +328,42 @@ async fn create_login_session(
     })
 }
 
+/// Store a new login session along with the client it was created from.
+pub(super) async fn insert_session(
+    pool: &sqlx::PgPool,
+    user_id: Uuid,
+    token_hash: &str,
+    expires_at: DateTime<Utc>,
+    client: &ClientInfo,
+) -> Result<(), ApiError> {
+    let ip: Option<ipnetwork::IpNetwork> = client.ip_addr.as_deref().and_then(|s| s.parse().ok());
+    sqlx::query!(
+        "INSERT INTO auth_sessions (user_id, token_hash, expires_at, ip_addr, user_agent)
+         VALUES ($1, $2, $3, $4, $5)",
+        user_id,
+        token_hash,
+        expires_at,
+        ip,
+        client.user_agent.as_deref(),
+    )
+    .execute(pool)
+    .await?;
+    Ok(())
+}
+
 #[tracing::instrument(skip(state), fields(user_id = %auth.user_id), err)]
 async fn logout(
     State(state): State<AppState>,
     auth: AuthUser,
 ) -> Result<impl IntoResponse, ApiError> {
-    // If we have a session token hash (session-based auth), delete only this session.
-    // If authenticated via API token, fall back to deleting all sessions for the user.
+    // Only the presented session is revoked; other devices stay signed in.
+    // API-token callers have no session to end (see `logout_all`).
     if let Some(ref hash) = auth.session_token_hash {
         sqlx::query("DELETE FROM auth_sessions WHERE user_id = $1 AND token_hash = $2")
             .bind(auth.user_id)
             .bind(hash)
             .execute(&state.pool)
             .await?;
-    } else {
-        sqlx::query!("DELETE FROM auth_sessions WHERE user_id = $1", auth.user_id)
-            .execute(&state.pool)
-            .await?;
     }
 
     send_audit(
@@ -314,6 +388,38 @@ async fn logout(
     ))
 }
 
+/// Revoke every session of the current user, on all devices.
+#[tracing::instrument(skip(state), fields(user_id = %auth.user_id), err)]
+async fn logout_all(
+    State(state): State<AppState>,
+    auth: AuthUser,
+) -> Result<impl IntoResponse, ApiError> {
+    let result = sqlx::query!("DELETE FROM auth_sessions WHERE user_id = $1", auth.user_id)
+        .execute(&state.pool)
+        .await?;
+
+    send_audit(
+        &state.audit_tx,
+        AuditEntry {
+            actor_id: auth.user_id,
+            actor_name: auth.user_name.clone(),
+            action: "auth.logout_all".into(),
+            resource: "session".into(),
+            resource_id: None,
+            project_id: None,
+            detail: Some(serde_json::json!({"revoked": result.rows_affected()})),
+            ip_addr: auth.ip_addr.clone(),
+        },
+    );
+
+    let cookie = "session=; Path=/; HttpOnly; SameSite=Strict; Max-Age=0";
+    Ok((
+        StatusCode::OK,
+        [(axum::http::header::SET_COOKIE, cookie.to_owned())],
+        Json(serde_json::json!({"ok": true, "revoked": result.rows_affected()})),
+    ))
+}
+
 async fn me(State(state): State<AppState>, auth: AuthUser) -> Result<Json<UserResponse>, ApiError> {
     let user = sqlx::query!(
         r#"
@@ -364,7 +470,7 @@ async fn create_user(
             .password
             .as_deref()
             .ok_or_else(|| ApiError::BadRequest("password is required for human users".into()))?;
-        validation::check_length("password", pw, 8, 1024)?;
+        validation::check_password(pw, &state.config.password_policy())?;
         password::hash_password(pw).map_err(ApiError::Internal)?
     } else {
         if body.password.is_some() {
@@ -536,7 +642,7 @@ async fn update_user(
         validation::check_email(email)?;
     }
     if let Some(ref pw) = body.password {
-        validation::check_length("password", pw, 8, 1024)?;
+        validation::check_password(pw, &state.config.password_policy())?;
         // Non-admin users changing their own password must verify current password
         if auth.user_id == id {
             let cp = body
@@ -559,12 +665,18 @@ async fn update_user(
         None => None,
     };
 
+    let mut tx = state.pool.begin().await?;
+    if let Some(disabled) = body.password_login_disabled {
+        check_password_login_toggle(&state, &auth, &mut tx, id, disabled).await?;
+    }
+
     let user = sqlx::query!(
         r#"
         UPDATE users SET
             display_name = COALESCE($2, display_name),
             email = COALESCE($3, email),
-            password_hash = COALESCE($4, password_hash)
+            password_hash = COALESCE($4, password_hash),
+            password_login_disabled = COALESCE($5, password_login_disabled)
         WHERE id = $1
         RETURNING id, name, display_name, email, user_type, is_active, created_at, updated_at
         "#,
@@ -572,10 +684,12 @@ async fn update_user(
         body.display_name,
         body.email,
         password_hash,
+        body.password_login_disabled,
     )
-    .fetch_optional(&state.pool)
+    .fetch_optional(&mut *tx)
     .await?
     .ok_or_else(|| ApiError::NotFound("user".into()))?;
+    tx.commit().await?;
 
     send_audit(
         &state.audit_tx,
@@ -586,7 +700,9 @@ async fn update_user(
             resource: "user".into(),
             resource_id: Some(id),
             project_id: None,
-            detail: None,
+            detail: body
+                .password_login_disabled
+                .map(|disabled| serde_json::json!({ "password_login_disabled": disabled })),
             ip_addr: auth.ip_addr.clone(),
         },
     );
@@ -603,6 +719,38 @@ async fn update_user(
     }))
 }
 
+/// Admins only — even for their own account — may toggle passkey-only login,
+/// and only for users who already have a passkey to log in with. The user row
+/// is locked as in `delete_passkey`, so the last passkey cannot be deleted
+/// between this check and the caller's update.
+async fn check_password_login_toggle(
+    state: &AppState,
+    auth: &AuthUser,
+    tx: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+    user_id: Uuid,
+    disabled: bool,
+) -> Result<(), ApiError> {
+    require_admin(state, auth).await?;
+    if disabled {
+        sqlx::query_scalar!("SELECT id FROM users WHERE id = $1 FOR UPDATE", user_id)
+            .fetch_optional(&mut **tx)
+            .await?
+            .ok_or_else(|| ApiError::NotFound("user".into()))?;
+        let passkeys: i64 = sqlx::query_scalar!(
+            r#"SELECT COUNT(*) as "count!: i64" FROM passkey_credentials WHERE user_id = $1"#,
+            user_id,
+        )
+        .fetch_one(&mut **tx)
+        .await?;
+        if passkeys == 0 {
+            return Err(ApiError::BadRequest(
+                "user has no registered passkey — password login cannot be disabled".into(),
+            ));
+        }
+    }
+    Ok(())
+}
+
 #[tracing::instrument(skip(state), fields(%id), err)]
 async fn deactivate_user(
     State(state): State<AppState>,
@@ -670,10 +818,26 @@ async fn create_api_token(
 
     validation::check_length("name", &body.name, 1, 255)?;
 
+    // A project-bounded token can only mint tokens bounded to the same project
+    if let Some(boundary_pid) = auth.boundary_project_id
+        && body.project_id != Some(boundary_pid)
+    {
+        return Err(ApiError::Forbidden);
+    }
+
     let (raw_token, token_hash) = token::generate_api_token();
 
     let scopes = body.scopes.unwrap_or_default();
 
+    // ...and a token with restricted scopes can't mint one with more
+    if let Some(held) = auth.token_scopes.as_deref()
+        && !held.is_empty()
+        && !held.iter().any(|s| s == "*")
+        && (scopes.is_empty() || scopes.iter().any(|s| !held.contains(s)))
+    {
+        return Err(ApiError::Forbidden);
+    }
+
     // Validate that requested scopes are real permissions and subset of user's
     if !scopes.is_empty() && !scopes.contains(&"*".to_string()) {
         validate_token_scopes(&state, &auth, &scopes, body.project_id).await?;
@@ -762,12 +926,15 @@ async fn validate_token_scopes(
         if scope == "*" {
             continue;
         }
-        // Validate it's a known permission
-        if scope.parse::<Permission>().is_err() {
-            return Err(ApiError::BadRequest(format!("unknown scope '{scope}'")));
-        }
+        // Validate it's a known permission or git scope
+        let required = match crate::rbac::resolver::repo_scope_permission(scope) {
+            Some(perm) => perm,
+            None => scope
+                .parse::<Permission>()
+                .map_err(|_| ApiError::BadRequest(format!("unknown scope '{scope}'")))?,
+        };
         // Validate user has this permission
-        if !user_perm_strings.contains(scope.as_str()) {
+        if !user_perm_strings.contains(required.as_str()) {
             return Err(ApiError::BadRequest(format!(
                 "scope '{scope}' exceeds your permissions"
             )));
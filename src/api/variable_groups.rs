@@ -0,0 +1,718 @@
+// Copyright (c) 2026 Steven Hooker. Exclusively licensed to and distributed by AgentSphere GmbH.
+// SPDX-License-Identifier: BUSL-1.1
+
+//! Variable groups shared between pipelines (see [`crate::pipeline::variable_groups`]).
+//! Project groups live under `/api/projects/{id}/variable-groups`; global groups,
+//! visible to every project, under `/api/admin/variable-groups`.
+
+use std::collections::{HashMap, HashSet};
+
+use axum::extract::{Path, State};
+use axum::http::StatusCode;
+use axum::response::IntoResponse;
+use axum::routing::get;
+use axum::{Json, Router};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use ts_rs::TS;
+
+use crate::audit::{AuditEntry, send_audit};
+use crate::auth::middleware::AuthUser;
+use crate::error::ApiError;
+use crate::secrets::engine;
+use crate::store::AppState;
+use crate::validation;
+
+use super::helpers::{ListResponse, require_admin, require_project_read, require_project_write};
+use super::pipelines::check_variable_name;
+
+const MAX_VARIABLES_PER_GROUP: usize = 100;
+const MAX_GROUPS_PER_SCOPE: i64 = 50;
+/// Shorter masked values would redact unrelated log text.
+const MIN_MASKED_VALUE_LEN: usize = 8;
+
+// ---------------------------------------------------------------------------
+// Types
+// ---------------------------------------------------------------------------
+
+#[derive(Debug, Deserialize)]
+pub struct VariableInput {
+    pub name: String,
+    /// Required, except for an existing masked variable on update, where
+    /// omitting it keeps the stored value.
+    pub value: Option<String>,
+    #[serde(default)]
+    pub masked: bool,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct CreateVariableGroupRequest {
+    pub name: String,
+    pub description: Option<String>,
+    #[serde(default)]
+    pub variables: Vec<VariableInput>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct UpdateVariableGroupRequest {
+    pub description: Option<String>,
+    /// Replaces the group's variables.
+    pub variables: Option<Vec<VariableInput>>,
+}
+
+#[derive(Debug, Serialize, TS)]
+#[ts(export, rename = "VariableGroupVariable")]
+pub struct VariableResponse {
+    pub name: String,
+    /// `None` for masked variables.
+    pub value: Option<String>,
+    pub masked: bool,
+}
+
+#[derive(Debug, Serialize, TS)]
+#[ts(export, rename = "VariableGroup")]
+pub struct VariableGroupResponse {
+    pub id: Uuid,
+    /// `None` for global groups.
+    pub project_id: Option<Uuid>,
+    pub name: String,
+    pub description: Option<String>,
+    pub variables: Vec<VariableResponse>,
+    pub created_by: Option<Uuid>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+struct GroupRow {
+    id: Uuid,
+    project_id: Option<Uuid>,
+    name: String,
+    description: Option<String>,
+    created_by: Option<Uuid>,
+    created_at: DateTime<Utc>,
+    updated_at: DateTime<Utc>,
+}
+
+struct EntryRow {
+    group_id: Uuid,
+    name: String,
+    masked: bool,
+    value: Option<String>,
+}
+
+/// A variable ready to store: plain text, or encrypted when masked.
+enum StoredValue {
+    Plain(String),
+    Encrypted(Vec<u8>),
+}
+
+fn get_master_key(state: &AppState) -> Result<engine::MasterKey, ApiError> {
+    let hex_str = state
+        .config
+        .master_key
+        .as_deref()
+        .ok_or_else(|| ApiError::ServiceUnavailable("secrets engine not configured".into()))?;
+    engine::parse_master_key(hex_str).map_err(|e| {
+        tracing::error!(error = %e, "invalid master key configuration");
+        ApiError::ServiceUnavailable("secrets engine misconfigured".into())
+    })
+}
+
+fn check_description(description: Option<&str>) -> Result<(), ApiError> {
+    if let Some(d) = description {
+        validation::check_length("description", d, 0, 1000)?;
+    }
+    Ok(())
+}
+
+/// Validate variables without looking at stored state.
+fn check_variables(variables: &[VariableInput], creating: bool) -> Result<(), ApiError> {
+    if variables.len() > MAX_VARIABLES_PER_GROUP {
+        return Err(ApiError::BadRequest(format!(
+            "at most {MAX_VARIABLES_PER_GROUP} variables per group"
+        )));
+    }
+    let mut seen = HashSet::new();
+    for var in variables {
+        check_variable_name(&var.name)?;
+        if !seen.insert(var.name.as_str()) {
+            return Err(ApiError::BadRequest(format!(
+                "variable '{}' is listed more than once",
+                var.name
+            )));
+        }
+        match &var.value {
+            Some(value) => {
+                validation::check_length("variable value", value, 0, 10_000)?;
+                if var.masked
+                    && (value.chars().count() < MIN_MASKED_VALUE_LEN || value.contains('\n'))
+                {
+                    return Err(ApiError::BadRequest(format!(
+                        "masked variable '{}' must be a single line of at least {MIN_MASKED_VALUE_LEN} characters",
+                        var.name
+                    )));
+                }
+            }
+            None if creating || !var.masked => {
+                return Err(ApiError::BadRequest(format!(
+                    "variable '{}' needs a value",
+                    var.name
+                )));
+            }
+            None => {}
+        }
+    }
+    Ok(())
+}
+
+/// Turn validated input into stored values. A masked variable without a value
+/// keeps its current ciphertext from `existing`.
+fn prepare_values(
+    state: &AppState,
+    variables: Vec<VariableInput>,
+    existing: &HashMap<String, Vec<u8>>,
+) -> Result<Vec<(String, StoredValue)>, ApiError> {
+    let master_key = if variables.iter().any(|v| v.masked && v.value.is_some()) {
+        Some(get_master_key(state)?)
+    } else {
+        None
+    };
+    variables
+        .into_iter()
+        .map(|var| {
+            let stored = match (var.masked, var.value) {
+                (true, Some(value)) => {
+                    let key = master_key.as_ref().ok_or_else(|| {
+                        ApiError::ServiceUnavailable("secrets engine not configured".into())
+                    })?;
+                    StoredValue::Encrypted(
+                        key.encrypt(value.as_bytes()).map_err(ApiError::Internal)?,
+                    )
+                }
+                (true, None) => {
+                    StoredValue::Encrypted(existing.get(&var.name).cloned().ok_or_else(|| {
+                        ApiError::BadRequest(format!(
+                            "variable '{}' needs a value: it is not an existing masked variable",
+                            var.name
+                        ))
+                    })?)
+                }
+                (false, value) => StoredValue::Plain(value.unwrap_or_default()),
+            };
+            Ok((var.name, stored))
+        })
+        .collect()
+}
+
+async fn insert_entries(
+    tx: &mut sqlx::PgConnection,
+    group_id: Uuid,
+    values: &[(String, StoredValue)],
+) -> Result<(), ApiError> {
+    for (name, stored) in values {
+        let (masked, value, encrypted) = match stored {
+            StoredValue::Plain(v) => (false, Some(v.as_str()), None),
+            StoredValue::Encrypted(e) => (true, None, Some(e.as_slice())),
+        };
+        sqlx::query!(
+            "INSERT INTO variable_group_entries (group_id, name, masked, value, encrypted_value)
+             VALUES ($1, $2, $3, $4, $5)",
+            group_id,
+            name,
+            masked,
+            value,
+            encrypted,
+        )
+        .execute(&mut *tx)
+        .await?;
+    }
+    Ok(())
+}
+
+/// Attach the variables (masked values hidden) to each group.
+async fn with_variables(
+    state: &AppState,
+    groups: Vec<GroupRow>,
+) -> Result<Vec<VariableGroupResponse>, ApiError> {
+    let ids: Vec<Uuid> = groups.iter().map(|g| g.id).collect();
+    let entries = sqlx::query_as!(
+        EntryRow,
+        r#"SELECT group_id, name, masked, CASE WHEN masked THEN NULL ELSE value END AS "value?"
+           FROM variable_group_entries WHERE group_id = ANY($1) ORDER BY name"#,
+        &ids,
+    )
+    .fetch_all(&state.pool)
+    .await?;
+
+    let mut by_group: HashMap<Uuid, Vec<VariableResponse>> = HashMap::new();
+    for e in entries {
+        by_group
+            .entry(e.group_id)
+            .or_default()
+            .push(VariableResponse {
+                name: e.name,
+                value: e.value,
+                masked: e.masked,
+            });
+    }
+    Ok(groups
+        .into_iter()
+        .map(|g| VariableGroupResponse {
+            variables: by_group.remove(&g.id).unwrap_or_default(),
+            id: g.id,
+            project_id: g.project_id,
+            name: g.name,
+            description: g.description,
+            created_by: g.created_by,
+            created_at: g.created_at,
+            updated_at: g.updated_at,
+        })
+        .collect())
+}
+
+fn audit(
+    state: &AppState,
+    auth: &AuthUser,
+    action: &str,
+    group_id: Uuid,
+    project_id: Option<Uuid>,
+    detail: serde_json::Value,
+) {
+    send_audit(
+        &state.audit_tx,
+        AuditEntry {
+            actor_id: auth.user_id,
+            actor_name: auth.user_name.clone(),
+            action: action.into(),
+            resource: "variable_group".into(),
+            resource_id: Some(group_id),
+            project_id,
+            detail: Some(detail),
+            ip_addr: auth.ip_addr.clone(),
+        },
+    );
+}
+
+// ---------------------------------------------------------------------------
+// Router
+// ---------------------------------------------------------------------------
+
+pub fn router() -> Router<AppState> {
+    Router::new()
+        .route(
+            "/api/projects/{id}/variable-groups",
+            get(list_project_groups).post(create_project_group),
+        )
+        .route(
+            "/api/projects/{id}/variable-groups/{name}",
+            get(get_project_group)
+                .put(update_project_group)
+                .delete(delete_project_group),
+        )
+        .route(
+            "/api/admin/variable-groups",
+            get(list_global_groups).post(create_global_group),
+        )
+        .route(
+            "/api/admin/variable-groups/{name}",
+            get(get_global_group)
+                .put(update_global_group)
+                .delete(delete_global_group),
+        )
+}
+
+// ---------------------------------------------------------------------------
+// Project handlers
+// ---------------------------------------------------------------------------
+
+async fn list_project_groups(
+    State(state): State<AppState>,
+    auth: AuthUser,
+    Path(id): Path<Uuid>,
+) -> Result<Json<ListResponse<VariableGroupResponse>>, ApiError> {
+    require_project_read(&state, &auth, id).await?;
+    list_groups(&state, Some(id)).await
+}
+
+async fn get_project_group(
+    State(state): State<AppState>,
+    auth: AuthUser,
+    Path((id, name)): Path<(Uuid, String)>,
+) -> Result<Json<VariableGroupResponse>, ApiError> {
+    require_project_read(&state, &auth, id).await?;
+    get_group(&state, Some(id), &name).await
+}
+
+#[tracing::instrument(skip(state, body), fields(%id), err)]
+async fn create_project_group(
+    State(state): State<AppState>,
+    auth: AuthUser,
+    Path(id): Path<Uuid>,
+    Json(body): Json<CreateVariableGroupRequest>,
+) -> Result<impl IntoResponse, ApiError> {
+    require_project_write(&state, &auth, id).await?;
+    create_group(&state, &auth, Some(id), body).await
+}
+
+#[tracing::instrument(skip(state, body), fields(%id, %name), err)]
+async fn update_project_group(
+    State(state): State<AppState>,
+    auth: AuthUser,
+    Path((id, name)): Path<(Uuid, String)>,
+    Json(body): Json<UpdateVariableGroupRequest>,
+) -> Result<Json<VariableGroupResponse>, ApiError> {
+    require_project_write(&state, &auth, id).await?;
+    update_group(&state, &auth, Some(id), &name, body).await
+}
+
+#[tracing::instrument(skip(state), fields(%id, %name), err)]
+async fn delete_project_group(
+    State(state): State<AppState>,
+    auth: AuthUser,
+    Path((id, name)): Path<(Uuid, String)>,
+) -> Result<StatusCode, ApiError> {
+    require_project_write(&state, &auth, id).await?;
+    delete_group(&state, &auth, Some(id), &name).await
+}
+
+// ---------------------------------------------------------------------------
+// Global (admin) handlers
+// ---------------------------------------------------------------------------
+
+async fn list_global_groups(
+    State(state): State<AppState>,
+    auth: AuthUser,
+) -> Result<Json<ListResponse<VariableGroupResponse>>, ApiError> {
+    require_admin(&state, &auth).await?;
+    list_groups(&state, None).await
+}
+
+async fn get_global_group(
+    State(state): State<AppState>,
+    auth: AuthUser,
+    Path(name): Path<String>,
+) -> Result<Json<VariableGroupResponse>, ApiError> {
+    require_admin(&state, &auth).await?;
+    get_group(&state, None, &name).await
+}
+
+#[tracing::instrument(skip(state, body), err)]
+async fn create_global_group(
+    State(state): State<AppState>,
+    auth: AuthUser,
+    Json(body): Json<CreateVariableGroupRequest>,
+) -> Result<impl IntoResponse, ApiError> {
+    require_admin(&state, &auth).await?;
+    create_group(&state, &auth, None, body).await
+}
+
+#[tracing::instrument(skip(state, body), fields(%name), err)]
+async fn update_global_group(
+    State(state): State<AppState>,
+    auth: AuthUser,
+    Path(name): Path<String>,
+    Json(body): Json<UpdateVariableGroupRequest>,
+) -> Result<Json<VariableGroupResponse>, ApiError> {
+    require_admin(&state, &auth).await?;
+    update_group(&state, &auth, None, &name, body).await
+}
+
+#[tracing::instrument(skip(state), fields(%name), err)]
+async fn delete_global_group(
+    State(state): State<AppState>,
+    auth: AuthUser,
+    Path(name): Path<String>,
+) -> Result<StatusCode, ApiError> {
+    require_admin(&state, &auth).await?;
+    delete_group(&state, &auth, None, &name).await
+}
+
+// ---------------------------------------------------------------------------
+// Shared implementation (`project_id: None` = global)
+// ---------------------------------------------------------------------------
+
+async fn list_groups(
+    state: &AppState,
+    project_id: Option<Uuid>,
+) -> Result<Json<ListResponse<VariableGroupResponse>>, ApiError> {
+    let rows = sqlx::query_as!(
+        GroupRow,
+        "SELECT id, project_id, name, description, created_by, created_at, updated_at
+         FROM variable_groups
+         WHERE project_id IS NOT DISTINCT FROM $1 ORDER BY name",
+        project_id,
+    )
+    .fetch_all(&state.pool)
+    .await?;
+
+    let items = with_variables(state, rows).await?;
+    let total = i64::try_from(items.len()).unwrap_or(i64::MAX);
+    Ok(Json(ListResponse { items, total }))
+}
+
+async fn fetch_group<'e>(
+    executor: impl sqlx::PgExecutor<'e>,
+    project_id: Option<Uuid>,
+    name: &str,
+    for_update: bool,
+) -> Result<GroupRow, ApiError> {
+    let row = if for_update {
+        sqlx::query_as!(
+            GroupRow,
+            "SELECT id, project_id, name, description, created_by, created_at, updated_at
+             FROM variable_groups
+             WHERE project_id IS NOT DISTINCT FROM $1 AND name = $2
+             FOR UPDATE",
+            project_id,
+            name,
+        )
+        .fetch_optional(executor)
+        .await?
+    } else {
+        sqlx::query_as!(
+            GroupRow,
+            "SELECT id, project_id, name, description, created_by, created_at, updated_at
+             FROM variable_groups
+             WHERE project_id IS NOT DISTINCT FROM $1 AND name = $2",
+            project_id,
+            name,
+        )
+        .fetch_optional(executor)
+        .await?
+    };
+    row.ok_or_else(|| ApiError::NotFound("variable group".into()))
+}
+
+async fn get_group(
+    state: &AppState,
+    project_id: Option<Uuid>,
+    name: &str,
+) -> Result<Json<VariableGroupResponse>, ApiError> {
+    let row = fetch_group(&state.pool, project_id, name, false).await?;
+    let mut groups = with_variables(state, vec![row]).await?;
+    Ok(Json(groups.remove(0)))
+}
+
+async fn create_group(
+    state: &AppState,
+    auth: &AuthUser,
+    project_id: Option<Uuid>,
+    body: CreateVariableGroupRequest,
+) -> Result<(StatusCode, Json<VariableGroupResponse>), ApiError> {
+    validation::check_name(&body.name)?;
+    check_description(body.description.as_deref())?;
+    check_variables(&body.variables, true)?;
+    let variable_names: Vec<String> = body.variables.iter().map(|v| v.name.clone()).collect();
+    let values = prepare_values(state, body.variables, &HashMap::new())?;
+
+    let mut tx = state.pool.begin().await?;
+    let count = sqlx::query_scalar!(
+        r#"SELECT COUNT(*) as "count!" FROM variable_groups WHERE project_id IS NOT DISTINCT FROM $1"#,
+        project_id,
+    )
+    .fetch_one(&mut *tx)
+    .await?;
+    if count >= MAX_GROUPS_PER_SCOPE {
+        return Err(ApiError::BadRequest(format!(
+            "max {MAX_GROUPS_PER_SCOPE} variable groups"
+        )));
+    }
+
+    let row = sqlx::query_as!(
+        GroupRow,
+        "INSERT INTO variable_groups (project_id, name, description, created_by)
+         VALUES ($1, $2, $3, $4)
+         RETURNING id, project_id, name, description, created_by, created_at, updated_at",
+        project_id,
+        body.name,
+        body.description,
+        auth.user_id,
+    )
+    .fetch_one(&mut *tx)
+    .await
+    .map_err(|e| match ApiError::from(e) {
+        ApiError::Conflict(_) => {
+            ApiError::Conflict(format!("variable group '{}' already exists", body.name))
+        }
+        other => other,
+    })?;
+    insert_entries(&mut tx, row.id, &values).await?;
+    tx.commit().await?;
+
+    audit(
+        state,
+        auth,
+        "variable_group.create",
+        row.id,
+        project_id,
+        serde_json::json!({
+            "name": row.name,
+            "variables": variable_names,
+            "global": project_id.is_none(),
+        }),
+    );
+
+    let mut groups = with_variables(state, vec![row]).await?;
+    Ok((StatusCode::CREATED, Json(groups.remove(0))))
+}
+
+async fn update_group(
+    state: &AppState,
+    auth: &AuthUser,
+    project_id: Option<Uuid>,
+    name: &str,
+    body: UpdateVariableGroupRequest,
+) -> Result<Json<VariableGroupResponse>, ApiError> {
+    check_description(body.description.as_deref())?;
+    if let Some(ref variables) = body.variables {
+        check_variables(variables, false)?;
+    }
+
+    let mut tx = state.pool.begin().await?;
+    let current = fetch_group(&mut *tx, project_id, name, true).await?;
+
+    let variable_names = body
+        .variables
+        .as_ref()
+        .map(|vars| vars.iter().map(|v| v.name.clone()).collect::<Vec<_>>());
+    if let Some(variables) = body.variables {
+        let existing: HashMap<String, Vec<u8>> = sqlx::query!(
+            r#"SELECT name, encrypted_value as "encrypted_value!" FROM variable_group_entries
+               WHERE group_id = $1 AND masked"#,
+            current.id,
+        )
+        .fetch_all(&mut *tx)
+        .await?
+        .into_iter()
+        .map(|r| (r.name, r.encrypted_value))
+        .collect();
+        let values = prepare_values(state, variables, &existing)?;
+        sqlx::query!(
+            "DELETE FROM variable_group_entries WHERE group_id = $1",
+            current.id,
+        )
+        .execute(&mut *tx)
+        .await?;
+        insert_entries(&mut tx, current.id, &values).await?;
+    }
+
+    let row = sqlx::query_as!(
+        GroupRow,
+        "UPDATE variable_groups SET description = COALESCE($2, description), updated_at = now()
+         WHERE id = $1
+         RETURNING id, project_id, name, description, created_by, created_at, updated_at",
+        current.id,
+        body.description,
+    )
+    .fetch_one(&mut *tx)
+    .await?;
+    tx.commit().await?;
+
+    audit(
+        state,
+        auth,
+        "variable_group.update",
+        row.id,
+        project_id,
+        serde_json::json!({
+            "name": row.name,
+            "variables": variable_names,
+            "global": project_id.is_none(),
+        }),
+    );
+
+    let mut groups = with_variables(state, vec![row]).await?;
+    Ok(Json(groups.remove(0)))
+}
+
+async fn delete_group(
+    state: &AppState,
+    auth: &AuthUser,
+    project_id: Option<Uuid>,
+    name: &str,
+) -> Result<StatusCode, ApiError> {
+    let group_id = sqlx::query_scalar!(
+        "DELETE FROM variable_groups
+         WHERE project_id IS NOT DISTINCT FROM $1 AND name = $2
+         RETURNING id",
+        project_id,
+        name,
+    )
+    .fetch_optional(&state.pool)
+    .await?
+    .ok_or_else(|| ApiError::NotFound("variable group".into()))?;
+
+    audit(
+        state,
+        auth,
+        "variable_group.delete",
+        group_id,
+        project_id,
+        serde_json::json!({"name": name, "global": project_id.is_none()}),
+    );
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn var(name: &str, value: Option<&str>, masked: bool) -> VariableInput {
+        VariableInput {
+            name: name.into(),
+            value: value.map(Into::into),
+            masked,
+        }
+    }
+
+    #[test]
+    fn variables_accept_plain_and_masked() {
+        check_variables(
+            &[
+                var("REGION", Some("eu-west-1"), false),
+                var("EMPTY", Some(""), false),
+                var("DB_PASSWORD", Some("correct-horse"), true),
+            ],
+            true,
+        )
+        .unwrap();
+    }
+
+    #[test]
+    fn variables_reject_bad_names_and_duplicates() {
+        for vars in [
+            vec![var("1ABC", Some("x"), false)],
+            vec![var("COMMIT_SHA", Some("x"), false)],
+            vec![var("PLATFORM_X", Some("x"), false)],
+            vec![var("A", Some("x"), false), var("A", Some("y"), false)],
+        ] {
+            assert!(check_variables(&vars, true).is_err());
+        }
+    }
+
+    #[test]
+    fn masked_values_must_be_long_single_lines() {
+        assert!(check_variables(&[var("TOKEN", Some("short"), true)], true).is_err());
+        assert!(check_variables(&[var("TOKEN", Some("two\nlines!"), true)], true).is_err());
+        // Short values are fine unmasked
+        check_variables(&[var("TOKEN", Some("short"), false)], true).unwrap();
+    }
+
+    #[test]
+    fn value_may_be_omitted_only_for_masked_updates() {
+        assert!(check_variables(&[var("TOKEN", None, true)], true).is_err());
+        assert!(check_variables(&[var("REGION", None, false)], false).is_err());
+        check_variables(&[var("TOKEN", None, true)], false).unwrap();
+    }
+
+    #[test]
+    fn too_many_variables_rejected() {
+        let vars: Vec<VariableInput> = (0..=MAX_VARIABLES_PER_GROUP)
+            .map(|i| var(&format!("V{i}"), Some("x"), false))
+            .collect();
+        assert!(check_variables(&vars, true).is_err());
+    }
+}
@@ -1,8 +1,9 @@
 // Copyright (c) 2026 Steven Hooker. Exclusively licensed to and distributed by AgentSphere GmbH.
 // SPDX-License-Identifier: BUSL-1.1
 
-use std::net::IpAddr;
-use std::sync::LazyLock;
+use std::collections::HashMap;
+use std::net::{IpAddr, SocketAddr};
+use std::sync::{Arc, LazyLock, Mutex, OnceLock};
 
 use axum::extract::{Path, State};
 use axum::http::StatusCode;
@@ -23,23 +24,69 @@ use crate::audit::{AuditEntry, send_audit};
 use crate::auth::middleware::AuthUser;
 use crate::error::ApiError;
 use crate::store::AppState;
-use crate::validation;
+use crate::validation::{self, EgressAllowlist};
 
 use super::helpers::require_project_write;
 
 /// Shared HTTP client for webhook dispatch (with timeouts).
 pub(crate) static WEBHOOK_CLIENT: LazyLock<reqwest::Client> = LazyLock::new(|| {
+    webhook_client_builder()
+        .build()
+        .expect("failed to build webhook HTTP client")
+});
+
+fn webhook_client_builder() -> reqwest::ClientBuilder {
     reqwest::Client::builder()
         .connect_timeout(std::time::Duration::from_secs(5))
         .timeout(std::time::Duration::from_secs(10))
         .redirect(reqwest::redirect::Policy::none())
-        .build()
-        .expect("failed to build webhook HTTP client")
+}
+
+/// Dev mode allows deliveries to localhost (e.g., test wiremock servers).
+static DEV_MODE: LazyLock<bool> = LazyLock::new(|| {
+    std::env::var("PLATFORM_DEV")
+        .ok()
+        .is_some_and(|v| v == "true")
 });
 
+static EGRESS_ALLOWLIST: OnceLock<EgressAllowlist> = OnceLock::new();
+
 /// Concurrency limiter for webhook dispatch (max 50 concurrent deliveries).
 pub(crate) static WEBHOOK_SEMAPHORE: LazyLock<Semaphore> = LazyLock::new(|| Semaphore::new(50));
 
+static PROJECT_CONCURRENCY: OnceLock<usize> = OnceLock::new();
+
+/// Delivery slots per project with deliveries in flight or queued.
+static PROJECT_SLOTS: LazyLock<Mutex<HashMap<Uuid, Arc<Semaphore>>>> =
+    LazyLock::new(|| Mutex::new(HashMap::new()));
+
+/// Set the per-project delivery concurrency. Call once at startup.
+pub fn set_project_concurrency(limit: usize) {
+    PROJECT_CONCURRENCY.set(limit.max(1)).ok();
+}
+
+/// Restrict deliveries to the configured destinations. Call once at startup;
+/// entries are validated by `Config::validate` before this runs.
+pub fn set_egress_allowlist(entries: &[String]) {
+    if let Ok(list) = EgressAllowlist::parse(entries) {
+        EGRESS_ALLOWLIST.set(list).ok();
+    }
+}
+
+/// Shared slots for `project_id`, created on first use.
+fn project_slots(project_id: Uuid) -> Arc<Semaphore> {
+    let limit = *PROJECT_CONCURRENCY.get().unwrap_or(&10);
+    let mut slots = PROJECT_SLOTS
+        .lock()
+        .unwrap_or_else(std::sync::PoisonError::into_inner);
+    // Forget projects with nothing in flight so the map stays small
+    slots.retain(|_, s| Arc::strong_count(s) > 1);
+    slots
+        .entry(project_id)
+        .or_insert_with(|| Arc::new(Semaphore::new(limit)))
+        .clone()
+}
+
 // ---------------------------------------------------------------------------
 // SSRF protection
 // ---------------------------------------------------------------------------
@@ -89,15 +136,28 @@ pub(crate) fn validate_webhook_url(url_str: &str) -> Result<(), ApiError> {
 
     Ok(())
 }
+
+/// Egress allowlist for validating webhook URLs on create and update.
+fn configured_allowlist(state: &AppState) -> Result<EgressAllowlist, ApiError> {
+    EgressAllowlist::parse(&state.config.webhook_allowlist)
+        .map_err(|e| ApiError::Internal(anyhow::anyhow!(e)))
+}
 // ---------------------------------------------------------------------------
 // Types
 // ---------------------------------------------------------------------------
 
+/// Max glob patterns per webhook filter.
+const MAX_FILTER_PATTERNS: usize = 20;
+
 #[derive(Debug, Deserialize)]
 pub struct CreateWebhookRequest {
     pub url: String,
     pub events: Vec<String>,
     pub secret: Option<String>,
+    #[serde(default)]
+    pub branch_filter: Vec<String>,
+    #[serde(default)]
+    pub path_filter: Vec<String>,
 }
 
 #[derive(Debug, Deserialize)]
@@ -106,6 +166,10 @@ pub struct UpdateWebhookRequest {
     pub events: Option<Vec<String>>,
     pub secret: Option<String>,
     pub active: Option<bool>,
+    /// Replaces the branch filter; an empty list removes it.
+    pub branch_filter: Option<Vec<String>>,
+    /// Replaces the path filter; an empty list removes it.
+    pub path_filter: Option<Vec<String>>,
 }
 
 #[derive(Debug, Serialize, TS)]
@@ -116,9 +180,111 @@ pub struct WebhookResponse {
     pub url: String,
     pub events: Vec<String>,
     pub active: bool,
+    /// Branch globs a push must match to be delivered (empty = all branches).
+    pub branch_filter: Vec<String>,
+    /// Path globs at least one changed file must match (empty = all paths).
+    pub path_filter: Vec<String>,
     pub created_at: DateTime<Utc>,
 }
 
+/// Result of a test delivery (`POST .../webhooks/{wh_id}/test`).
+#[derive(Debug, Serialize, TS)]
+#[ts(export, rename = "WebhookTestResult")]
+pub struct WebhookTestResponse {
+    /// The receiver answered with a 2xx status.
+    pub ok: bool,
+    /// The receiver's HTTP status; `None` if it never answered.
+    pub status_code: Option<u16>,
+    /// Start of the receiver's response body (at most 4 KiB).
+    pub response_body: Option<String>,
+    /// Why no response was received.
+    pub error: Option<String>,
+    pub duration_ms: u32,
+}
+
+/// Most response body returned by a test delivery.
+const MAX_TEST_RESPONSE_BYTES: usize = 4096;
+
+struct WebhookRow {
+    id: Uuid,
+    project_id: Uuid,
+    url: String,
+    events: Vec<String>,
+    active: bool,
+    branch_filter: Vec<String>,
+    path_filter: Vec<String>,
+    created_at: DateTime<Utc>,
+}
+
+impl From<WebhookRow> for WebhookResponse {
+    fn from(w: WebhookRow) -> Self {
+        Self {
+            id: w.id,
+            project_id: w.project_id,
+            url: w.url,
+            events: w.events,
+            active: w.active,
+            branch_filter: w.branch_filter,
+            path_filter: w.path_filter,
+            created_at: w.created_at,
+        }
+    }
+}
+
+/// What a push touched, used to evaluate webhook branch/path filters.
+pub struct PushScope<'a> {
+    /// Pushed branch, or `None` for tag pushes.
+    pub branch: Option<&'a str>,
+    /// Files changed by the push, or `None` when they could not be determined.
+    pub changed_paths: Option<&'a [String]>,
+}
+
+fn validate_filter(field: &str, patterns: &[String]) -> Result<(), ApiError> {
+    if patterns.len() > MAX_FILTER_PATTERNS {
+        return Err(ApiError::BadRequest(format!(
+            "max {MAX_FILTER_PATTERNS} {field} patterns"
+        )));
+    }
+    for pattern in patterns {
+        validation::check_length(field, pattern, 1, 255)?;
+    }
+    Ok(())
+}
+
+/// Whether a push passes a webhook's filters.
+///
+/// A branch filter only admits branch pushes whose name matches one of its
+/// globs, so tag pushes are skipped. A path filter admits the push if any
+/// changed file matches; when the changed files are unknown the push is
+/// delivered rather than silently dropped.
+fn push_matches_filters(
+    branch_filter: &[String],
+    path_filter: &[String],
+    scope: &PushScope,
+) -> bool {
+    if !branch_filter.is_empty() {
+        let Some(branch) = scope.branch else {
+            return false;
+        };
+        if !branch_filter
+            .iter()
+            .any(|p| validation::match_glob_pattern(p, branch))
+        {
+            return false;
+        }
+    }
+    if !path_filter.is_empty()
+        && let Some(paths) = scope.changed_paths
+    {
+        return paths.iter().any(|path| {
+            path_filter
+                .iter()
+                .any(|p| validation::match_glob_pattern(p, path))
+        });
+    }
+    true
+}
+
 // ---------------------------------------------------------------------------
 // Router
 // ---------------------------------------------------------------------------
@@ -167,6 +333,7 @@ async fn create_webhook(
     // Validate URL (format + SSRF protection)
     validation::check_url(&body.url)?;
     validation::check_ssrf_url(&body.url, &["http", "https"])?;
+    validation::check_egress_url(&body.url, &configured_allowlist(&state)?)?;
 
     // Validate secret length
     if let Some(ref secret) = body.secret {
@@ -188,17 +355,22 @@ async fn create_webhook(
             )));
         }
     }
+    validate_filter("branch_filter", &body.branch_filter)?;
+    validate_filter("path_filter", &body.path_filter)?;
 
-    let wh = sqlx::query!(
+    let wh = sqlx::query_as!(
+        WebhookRow,
         r#"
-        INSERT INTO webhooks (project_id, url, events, secret)
-        VALUES ($1, $2, $3, $4)
-        RETURNING id, project_id, url, events, active, created_at
+        INSERT INTO webhooks (project_id, url, events, secret, branch_filter, path_filter)
+        VALUES ($1, $2, $3, $4, $5, $6)
+        RETURNING id, project_id, url, events, active, branch_filter, path_filter, created_at
         "#,
         id,
         body.url,
         &body.events,
         body.secret,
+        &body.branch_filter,
+        &body.path_filter,
     )
     .fetch_one(&state.pool)
     .await?;
@@ -217,17 +389,7 @@ async fn create_webhook(
         },
     );
 
-    Ok((
-        StatusCode::CREATED,
-        Json(WebhookResponse {
-            id: wh.id,
-            project_id: wh.project_id,
-            url: wh.url,
-            events: wh.events,
-            active: wh.active,
-            created_at: wh.created_at,
-        }),
-    ))
+    Ok((StatusCode::CREATED, Json(WebhookResponse::from(wh))))
 }
 
 async fn list_webhooks(
@@ -244,9 +406,10 @@ async fn list_webhooks(
     .fetch_one(&state.pool)
     .await?;
 
-    let rows = sqlx::query!(
+    let rows = sqlx::query_as!(
+        WebhookRow,
         r#"
-        SELECT id, project_id, url, events, active, created_at
+        SELECT id, project_id, url, events, active, branch_filter, path_filter, created_at
         FROM webhooks WHERE project_id = $1
         ORDER BY created_at DESC
         "#,
@@ -255,17 +418,7 @@ async fn list_webhooks(
     .fetch_all(&state.pool)
     .await?;
 
-    let items = rows
-        .into_iter()
-        .map(|w| WebhookResponse {
-            id: w.id,
-            project_id: w.project_id,
-            url: w.url,
-            events: w.events,
-            active: w.active,
-            created_at: w.created_at,
-        })
-        .collect();
+    let items = rows.into_iter().map(WebhookResponse::from).collect();
 
     Ok(Json(super::helpers::ListResponse { items, total }))
 }
@@ -277,9 +430,10 @@ async fn get_webhook(
 ) -> Result<Json<WebhookResponse>, ApiError> {
     require_project_write(&state, &auth, id).await?;
 
-    let wh = sqlx::query!(
+    let wh = sqlx::query_as!(
+        WebhookRow,
         r#"
-        SELECT id, project_id, url, events, active, created_at
+        SELECT id, project_id, url, events, active, branch_filter, path_filter, created_at
         FROM webhooks WHERE id = $1 AND project_id = $2
         "#,
         wh_id,
@@ -289,14 +443,7 @@ async fn get_webhook(
     .await?
     .ok_or_else(|| ApiError::NotFound("webhook".into()))?;
 
-    Ok(Json(WebhookResponse {
-        id: wh.id,
-        project_id: wh.project_id,
-        url: wh.url,
-        events: wh.events,
-        active: wh.active,
-        created_at: wh.created_at,
-    }))
+    Ok(Json(WebhookResponse::from(wh)))
 }
 
 #[tracing::instrument(skip(state, body), fields(%id, %wh_id), err)]
@@ -312,6 +459,7 @@ async fn update_webhook(
     if let Some(ref url) = body.url {
         validation::check_url(url)?;
         validation::check_ssrf_url(url, &["http", "https"])?;
+        validation::check_egress_url(url, &configured_allowlist(&state)?)?;
     }
     if let Some(ref secret) = body.secret {
         validation::check_length("secret", secret, 0, 1024)?;
@@ -332,16 +480,25 @@ async fn update_webhook(
             }
         }
     }
+    if let Some(ref branch_filter) = body.branch_filter {
+        validate_filter("branch_filter", branch_filter)?;
+    }
+    if let Some(ref path_filter) = body.path_filter {
+        validate_filter("path_filter", path_filter)?;
+    }
 
-    let wh = sqlx::query!(
+    let wh = sqlx::query_as!(
+        WebhookRow,
         r#"
         UPDATE webhooks SET
             url = COALESCE($3, url),
             events = COALESCE($4, events),
             secret = COALESCE($5, secret),
-            active = COALESCE($6, active)
+            active = COALESCE($6, active),
+            branch_filter = COALESCE($7, branch_filter),
+            path_filter = COALESCE($8, path_filter)
         WHERE id = $1 AND project_id = $2
-        RETURNING id, project_id, url, events, active, created_at
+        RETURNING id, project_id, url, events, active, branch_filter, path_filter, created_at
         "#,
         wh_id,
         id,
@@ -349,6 +506,8 @@ async fn update_webhook(
         body.events.as_deref(),
         body.secret,
         body.active,
+        body.branch_filter.as_deref(),
+        body.path_filter.as_deref(),
     )
     .fetch_optional(&state.pool)
     .await?
@@ -368,14 +527,7 @@ async fn update_webhook(
         },
     );
 
-    Ok(Json(WebhookResponse {
-        id: wh.id,
-        project_id: wh.project_id,
-        url: wh.url,
-        events: wh.events,
-        active: wh.active,
-        created_at: wh.created_at,
-    }))
+    Ok(Json(WebhookResponse::from(wh)))
 }
 
 #[tracing::instrument(skip(state), fields(%id, %wh_id), err)]
@@ -415,12 +567,13 @@ async fn delete_webhook(
     Ok(StatusCode::NO_CONTENT)
 }
 
+/// Send a `ping` event right away and report how the receiver answered.
 #[tracing::instrument(skip(state), fields(%id, %wh_id), err)]
 async fn test_webhook(
     State(state): State<AppState>,
     auth: AuthUser,
     Path((id, wh_id)): Path<(Uuid, Uuid)>,
-) -> Result<Json<serde_json::Value>, ApiError> {
+) -> Result<Json<WebhookTestResponse>, ApiError> {
     require_project_write(&state, &auth, id).await?;
 
     let wh = sqlx::query!(
@@ -433,21 +586,47 @@ async fn test_webhook(
     .ok_or_else(|| ApiError::NotFound("webhook".into()))?;
 
     let payload = serde_json::json!({
-        "event": "test",
+        "event": "ping",
         "project_id": id,
+        "webhook_id": wh_id,
         "message": "webhook test delivery",
     });
 
-    dispatch_single(
-        wh_id,
-        &wh.url,
-        wh.secret.as_deref(),
-        &payload,
-        &state.webhook_semaphore,
-    )
-    .await;
+    let _permit = state
+        .webhook_semaphore
+        .acquire()
+        .await
+        .map_err(|e| ApiError::Internal(e.into()))?;
+    let started = std::time::Instant::now();
+    let result = deliver(&wh.url, wh.secret.as_deref(), &payload).await;
+    let (status_code, response_body, error) = match result {
+        Ok(mut resp) => {
+            let status = resp.status().as_u16();
+            let mut body = Vec::new();
+            while body.len() < MAX_TEST_RESPONSE_BYTES {
+                match resp.chunk().await {
+                    Ok(Some(chunk)) => body.extend_from_slice(&chunk),
+                    Ok(None) => break,
+                    Err(e) => {
+                        tracing::debug!(error = %e, "failed to read webhook test response body");
+                        break;
+                    }
+                }
+            }
+            body.truncate(MAX_TEST_RESPONSE_BYTES);
+            let body = String::from_utf8_lossy(&body).into_owned();
+            (Some(status), Some(body), None)
+        }
+        Err(e) => (None, None, Some(e)),
+    };
 
-    Ok(Json(serde_json::json!({"ok": true})))
+    Ok(Json(WebhookTestResponse {
+        ok: status_code.is_some_and(|s| (200..300).contains(&s)),
+        status_code,
+        response_body,
+        error,
+        duration_ms: u32::try_from(started.elapsed().as_millis()).unwrap_or(u32::MAX),
+    }))
 }
 
 // ---------------------------------------------------------------------------
@@ -455,7 +634,10 @@ async fn test_webhook(
 // ---------------------------------------------------------------------------
 
 /// Fire all active webhooks for a project + event.
-/// Spawns background tasks for each delivery.
+/// Spawns background tasks for each delivery. At most
+/// `PLATFORM_WEBHOOK_MAX_CONCURRENT_PER_PROJECT` deliveries per project (and
+/// `PLATFORM_WEBHOOK_MAX_CONCURRENT` overall) are in flight; the rest wait
+/// their turn.
 pub async fn fire_webhooks(
     pool: &PgPool,
     project_id: Uuid,
@@ -463,9 +645,41 @@ pub async fn fire_webhooks(
     payload: &serde_json::Value,
     semaphore: &std::sync::Arc<tokio::sync::Semaphore>,
 ) {
-    let webhooks = match sqlx::query!(
+    fire_filtered(pool, project_id, event, payload, None, semaphore).await;
+}
+
+/// Fire `push` webhooks, skipping those whose branch/path filters reject the push.
+pub async fn fire_push_webhooks(
+    pool: &PgPool,
+    project_id: Uuid,
+    payload: &serde_json::Value,
+    scope: &PushScope<'_>,
+    semaphore: &std::sync::Arc<tokio::sync::Semaphore>,
+) {
+    fire_filtered(pool, project_id, "push", payload, Some(scope), semaphore).await;
+}
+
+/// An active webhook subscribed to the event being fired.
+struct FireTarget {
+    id: Uuid,
+    url: String,
+    secret: Option<String>,
+    branch_filter: Vec<String>,
+    path_filter: Vec<String>,
+}
+
+async fn fire_filtered(
+    pool: &PgPool,
+    project_id: Uuid,
+    event: &str,
+    payload: &serde_json::Value,
+    scope: Option<&PushScope<'_>>,
+    semaphore: &std::sync::Arc<tokio::sync::Semaphore>,
+) {
+    let result = sqlx::query_as!(
+        FireTarget,
         r#"
-        SELECT id, url, secret
+        SELECT id, url, secret, branch_filter, path_filter
         FROM webhooks
         WHERE project_id = $1 AND active = true AND $2 = ANY(events)
         "#,
@@ -473,8 +687,8 @@ pub async fn fire_webhooks(
         event,
     )
     .fetch_all(pool)
-    .await
-    {
+    .await;
+    let webhooks = match result {
         Ok(rows) => rows,
         Err(e) => {
             tracing::error!(error = %e, project_id = %project_id, event, "failed to query webhooks");
@@ -482,14 +696,30 @@ pub async fn fire_webhooks(
         }
     };
 
-    for wh in webhooks {
-        let webhook_id = wh.id;
-        let url = wh.url.clone();
-        let secret = wh.secret.clone();
+    for FireTarget {
+        id: webhook_id,
+        url,
+        secret,
+        branch_filter,
+        path_filter,
+    } in webhooks
+    {
+        if let Some(scope) = scope
+            && !push_matches_filters(&branch_filter, &path_filter, scope)
+        {
+            tracing::debug!(webhook_id = %webhook_id, "push filtered out by webhook filters");
+            continue;
+        }
         let payload = payload.clone();
         let sem = semaphore.clone();
+        let slots = project_slots(project_id);
 
         tokio::spawn(async move {
+            // Take the project slot first so a busy project queues without
+            // holding global permits other projects could use
+            let Ok(_slot) = slots.acquire().await else {
+                return;
+            };
             dispatch_single(webhook_id, &url, secret.as_deref(), &payload, &sem).await;
         });
     }
@@ -502,33 +732,41 @@ pub(crate) async fn dispatch_single(
     payload: &serde_json::Value,
     semaphore: &tokio::sync::Semaphore,
 ) {
-    // S63: Re-validate SSRF before dispatch — URL may have been modified in DB
-    // Skip in dev mode to allow localhost URLs (e.g., test wiremock servers)
-    static DEV_MODE: LazyLock<bool> = LazyLock::new(|| {
-        std::env::var("PLATFORM_DEV")
-            .ok()
-            .is_some_and(|v| v == "true")
-    });
-    if !*DEV_MODE && crate::validation::check_ssrf_url(url, &["http", "https"]).is_err() {
-        tracing::warn!(webhook_id = %webhook_id, "webhook URL failed SSRF re-validation, skipping dispatch");
-        return;
-    }
-
-    // Acquire semaphore permit (concurrency limit)
-    let Ok(_permit) = semaphore.try_acquire() else {
-        tracing::warn!(webhook_id = %webhook_id, "webhook dispatch dropped: concurrency limit reached");
+    // Wait for a delivery permit (global concurrency limit)
+    let Ok(_permit) = semaphore.acquire().await else {
         return;
     };
 
-    let body = match serde_json::to_string(payload) {
-        Ok(b) => b,
+    match deliver(url, secret, payload).await {
+        Ok(resp) => {
+            tracing::info!(webhook_id = %webhook_id, status = resp.status().as_u16(), "webhook delivered");
+        }
         Err(e) => {
-            tracing::error!(error = %e, webhook_id = %webhook_id, "failed to serialize webhook payload");
-            return;
+            tracing::warn!(webhook_id = %webhook_id, error = %e, "webhook delivery failed");
         }
-    };
+    }
+}
+
+/// POST `payload` to `url`, signed with `secret` if there is one. Returns
+/// the receiver's response, or why the delivery could not be made.
+async fn deliver(
+    url: &str,
+    secret: Option<&str>,
+    payload: &serde_json::Value,
+) -> Result<reqwest::Response, String> {
+    // S63: Re-validate SSRF before dispatch — URL may have been modified in DB
+    if !*DEV_MODE && crate::validation::check_ssrf_url(url, &["http", "https"]).is_err() {
+        return Err("webhook URL failed SSRF re-validation".into());
+    }
 
-    let mut request = WEBHOOK_CLIENT
+    let client = destination_client(url, *DEV_MODE)
+        .await
+        .map_err(|e| format!("webhook destination rejected: {e}"))?;
+
+    let body = serde_json::to_string(payload)
+        .map_err(|e| format!("failed to serialize webhook payload: {e}"))?;
+
+    let mut request = client
         .post(url)
         .header("Content-Type", "application/json")
         .header("User-Agent", "Platform-Webhook/1.0");
@@ -542,13 +780,36 @@ pub(crate) async fn dispatch_single(
         request = request.header("X-Platform-Signature", format!("sha256={signature}"));
     }
 
-    match request.body(body).send().await {
-        Ok(resp) => {
-            tracing::info!(webhook_id = %webhook_id, status = resp.status().as_u16(), "webhook delivered");
-        }
-        Err(e) => {
-            tracing::warn!(webhook_id = %webhook_id, error = %e, "webhook delivery failed");
-        }
+    request.body(body).send().await.map_err(|e| e.to_string())
+}
+
+/// Resolve the destination host, check every address against the egress
+/// allowlist and (unless `allow_private`) the private ranges, and return a
+/// client pinned to exactly those addresses so DNS rebinding between the
+/// check and the connect cannot redirect the delivery. The client does not
+/// follow redirects.
+pub(crate) async fn destination_client(
+    url: &str,
+    allow_private: bool,
+) -> Result<reqwest::Client, ApiError> {
+    let parsed = url::Url::parse(url).map_err(|_| ApiError::BadRequest("invalid URL".into()))?;
+    let host = parsed
+        .host()
+        .ok_or_else(|| ApiError::BadRequest("URL must have a host".into()))?;
+    let port = parsed.port_or_known_default().unwrap_or(443);
+    let addrs = validation::resolve_for_connect(host.clone(), port, allow_private).await?;
+
+    if let Some(allowlist) = EGRESS_ALLOWLIST.get() {
+        let ips: Vec<IpAddr> = addrs.iter().map(SocketAddr::ip).collect();
+        allowlist.check(parsed.host_str().unwrap_or_default(), &ips)?;
+    }
+
+    match host {
+        url::Host::Domain(name) => webhook_client_builder()
+            .resolve_to_addrs(name, &addrs)
+            .build()
+            .map_err(|e| ApiError::Internal(e.into())),
+        url::Host::Ipv4(_) | url::Host::Ipv6(_) => Ok(WEBHOOK_CLIENT.clone()),
     }
 }
 
@@ -715,9 +976,112 @@ mod tests {
         // Drop permit to release it for other tests
     }
 
+    #[test]
+    fn project_slots_are_shared_while_in_use() {
+        let project_id = Uuid::new_v4();
+        let first = project_slots(project_id);
+        let second = project_slots(project_id);
+        assert!(Arc::ptr_eq(&first, &second));
+
+        drop((first, second));
+        let other = project_slots(Uuid::new_v4());
+        let map = PROJECT_SLOTS.lock().unwrap();
+        assert!(
+            !map.contains_key(&project_id),
+            "idle project should be pruned"
+        );
+        drop(map);
+        drop(other);
+    }
+
     #[test]
     fn webhook_client_is_initialized() {
         // Verify the client LazyLock initializes without panic
         let _client = &*WEBHOOK_CLIENT;
     }
+
+    // -- push filters --
+
+    fn strings(items: &[&str]) -> Vec<String> {
+        items.iter().map(|s| (*s).to_owned()).collect()
+    }
+
+    #[test]
+    fn push_filters_empty_match_everything() {
+        let scope = PushScope {
+            branch: None,
+            changed_paths: None,
+        };
+        assert!(push_matches_filters(&[], &[], &scope));
+    }
+
+    #[test]
+    fn push_branch_filter_matches_globs() {
+        let filter = strings(&["main", "release/*"]);
+        let on = |branch| PushScope {
+            branch: Some(branch),
+            changed_paths: None,
+        };
+        assert!(push_matches_filters(&filter, &[], &on("main")));
+        assert!(push_matches_filters(&filter, &[], &on("release/1.2")));
+        assert!(!push_matches_filters(&filter, &[], &on("feature/login")));
+    }
+
+    #[test]
+    fn push_branch_filter_skips_tag_pushes() {
+        let scope = PushScope {
+            branch: None,
+            changed_paths: None,
+        };
+        assert!(!push_matches_filters(&strings(&["main"]), &[], &scope));
+    }
+
+    #[test]
+    fn push_path_filter_needs_one_matching_file() {
+        let filter = strings(&["docs/*", "*.md"]);
+        let docs = strings(&["src/main.rs", "docs/guide/intro.txt"]);
+        let code = strings(&["src/main.rs", "Cargo.toml"]);
+        let readme = strings(&["README.md"]);
+        let matches = |paths: &[String]| -> bool {
+            let scope = PushScope {
+                branch: Some("main"),
+                changed_paths: Some(paths),
+            };
+            push_matches_filters(&[], &filter, &scope)
+        };
+        assert!(matches(&docs));
+        assert!(matches(&readme));
+        assert!(!matches(&code));
+    }
+
+    #[test]
+    fn push_path_filter_delivers_when_paths_unknown() {
+        let scope = PushScope {
+            branch: Some("main"),
+            changed_paths: None,
+        };
+        assert!(push_matches_filters(&[], &strings(&["docs/*"]), &scope));
+    }
+
+    #[test]
+    fn push_filters_require_both_branch_and_path() {
+        let paths = strings(&["docs/intro.md"]);
+        let scope = PushScope {
+            branch: Some("feature/x"),
+            changed_paths: Some(&paths),
+        };
+        assert!(!push_matches_filters(
+            &strings(&["main"]),
+            &strings(&["docs/*"]),
+            &scope
+        ));
+    }
+
+    #[test]
+    fn validate_filter_limits() {
+        assert!(validate_filter("branch_filter", &strings(&["main"])).is_ok());
+        assert!(validate_filter("branch_filter", &strings(&[""])).is_err());
+        let many: Vec<String> = (0..=MAX_FILTER_PATTERNS).map(|i| format!("b{i}")).collect();
+        assert!(validate_filter("branch_filter", &many).is_err());
+    }
 }
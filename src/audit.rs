@@ -1,6 +1,8 @@
 // Copyright (c) 2026 Steven Hooker. Exclusively licensed to and distributed by AgentSphere GmbH.
 // SPDX-License-Identifier: BUSL-1.1
 
+use chrono::{DateTime, Utc};
+use sha2::{Digest, Sha256};
 use sqlx::PgPool;
 use uuid::Uuid;
 
@@ -40,25 +42,7 @@ pub fn send_audit(log: &AuditLog, entry: AuditEntry) {
 }
 
 async fn write_audit_inner(pool: &PgPool, entry: &AuditEntry) {
-    let ip: Option<ipnetwork::IpNetwork> = entry.ip_addr.as_deref().and_then(|s| s.parse().ok());
-
-    if let Err(e) = sqlx::query!(
-        r#"
-        INSERT INTO audit_log (actor_id, actor_name, action, resource, resource_id, project_id, detail, ip_addr)
-        VALUES ($1, $2, $3, $4, $5, $6, $7, $8)
-        "#,
-        entry.actor_id,
-        entry.actor_name,
-        entry.action,
-        entry.resource,
-        entry.resource_id,
-        entry.project_id,
-        entry.detail,
-        ip as Option<ipnetwork::IpNetwork>,
-    )
-    .execute(pool)
-    .await
-    {
+    if let Err(e) = append_chained(pool, entry).await {
         tracing::warn!(
             error = %e,
             action = %entry.action,
@@ -67,3 +51,313 @@ async fn write_audit_inner(pool: &PgPool, entry: &AuditEntry) {
         );
     }
 }
+
+// ---------------------------------------------------------------------------
+// Hash chain
+// ---------------------------------------------------------------------------
+
+/// Advisory lock key serialising chain appends, so every entry links to the
+/// one committed right before it.
+const AUDIT_CHAIN_LOCK: i64 = 0x6175_6469_745f_6368;
+
+/// Rows fetched per round trip while verifying the chain.
+const VERIFY_BATCH: i64 = 1000;
+
+/// The hashed content of one audit row.
+struct ChainedRow {
+    seq: i64,
+    id: Uuid,
+    actor_id: Uuid,
+    actor_name: String,
+    action: String,
+    resource: String,
+    resource_id: Option<Uuid>,
+    project_id: Option<Uuid>,
+    detail: Option<serde_json::Value>,
+    ip_addr: Option<ipnetwork::IpNetwork>,
+    break_glass_id: Option<Uuid>,
+    created_at: DateTime<Utc>,
+    prev_hash: Option<String>,
+    entry_hash: Option<String>,
+}
+
+/// SHA-256 over the previous entry's hash and this entry's content, as lowercase hex.
+///
+/// Fields are encoded as a JSON array so values cannot bleed into each other;
+/// `created_at` is hashed at microsecond precision, which is what Postgres stores.
+fn chain_hash(prev_hash: &str, row: &ChainedRow) -> String {
+    let content = serde_json::json!([
+        prev_hash,
+        row.id,
+        row.actor_id,
+        row.actor_name,
+        row.action,
+        row.resource,
+        row.resource_id,
+        row.project_id,
+        row.detail,
+        row.ip_addr.map(|ip| ip.to_string()),
+        row.break_glass_id,
+        row.created_at.timestamp_micros(),
+    ]);
+    hex::encode(Sha256::digest(content.to_string().as_bytes()))
+}
+
+async fn append_chained(pool: &PgPool, entry: &AuditEntry) -> Result<(), sqlx::Error> {
+    let mut tx = pool.begin().await?;
+    sqlx::query!("SELECT pg_advisory_xact_lock($1)", AUDIT_CHAIN_LOCK)
+        .execute(&mut *tx)
+        .await?;
+
+    let prev_hash = sqlx::query_scalar!(
+        "SELECT entry_hash FROM audit_log WHERE entry_hash IS NOT NULL ORDER BY seq DESC LIMIT 1",
+    )
+    .fetch_optional(&mut *tx)
+    .await?
+    .flatten()
+    .unwrap_or_default();
+
+    // Tag the entry with the actor's active break-glass grant, if any, so
+    // everything done under emergency access can be reviewed afterwards.
+    let break_glass_id = sqlx::query_scalar!(
+        r#"
+        SELECT id FROM delegations
+        WHERE delegate_id = $1 AND break_glass AND revoked_at IS NULL AND expires_at > now()
+        ORDER BY created_at DESC
+        LIMIT 1
+        "#,
+        entry.actor_id,
+    )
+    .fetch_optional(&mut *tx)
+    .await?;
+
+    let row = ChainedRow {
+        seq: 0,
+        id: Uuid::new_v4(),
+        actor_id: entry.actor_id,
+        actor_name: entry.actor_name.clone(),
+        action: entry.action.clone(),
+        resource: entry.resource.clone(),
+        resource_id: entry.resource_id,
+        project_id: entry.project_id,
+        detail: entry.detail.clone(),
+        ip_addr: entry.ip_addr.as_deref().and_then(|s| s.parse().ok()),
+        break_glass_id,
+        created_at: Utc::now(),
+        prev_hash: None,
+        entry_hash: None,
+    };
+    let entry_hash = chain_hash(&prev_hash, &row);
+
+    sqlx::query!(
+        r#"
+        INSERT INTO audit_log (id, actor_id, actor_name, action, resource, resource_id, project_id,
+                               detail, ip_addr, break_glass_id, created_at, prev_hash, entry_hash)
+        VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13)
+        "#,
+        row.id,
+        row.actor_id,
+        row.actor_name,
+        row.action,
+        row.resource,
+        row.resource_id,
+        row.project_id,
+        row.detail,
+        row.ip_addr as Option<ipnetwork::IpNetwork>,
+        row.break_glass_id,
+        row.created_at,
+        prev_hash,
+        entry_hash,
+    )
+    .execute(&mut *tx)
+    .await?;
+
+    tx.commit().await
+}
+
+/// Why verification stopped at an entry.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BreakReason {
+    /// The row's content no longer matches its stored hash.
+    HashMismatch,
+    /// The row does not link to the entry before it (a row was removed or inserted).
+    LinkMismatch,
+    /// A row without a hash appears after chaining started.
+    Unchained,
+}
+
+impl BreakReason {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::HashMismatch => "hash_mismatch",
+            Self::LinkMismatch => "link_mismatch",
+            Self::Unchained => "unchained",
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct ChainBreak {
+    pub id: Uuid,
+    pub seq: i64,
+    pub created_at: DateTime<Utc>,
+    pub reason: BreakReason,
+}
+
+#[derive(Debug)]
+pub struct ChainVerification {
+    /// Number of chained entries checked before the first break (or in total).
+    pub checked: i64,
+    pub first_break: Option<ChainBreak>,
+}
+
+/// Check one row against the hash of the entry before it.
+fn check_link(prev_hash: &str, row: &ChainedRow) -> Option<BreakReason> {
+    let Some(stored) = row.entry_hash.as_deref() else {
+        return Some(BreakReason::Unchained);
+    };
+    if row.prev_hash.as_deref() != Some(prev_hash) {
+        return Some(BreakReason::LinkMismatch);
+    }
+    if chain_hash(prev_hash, row) != stored {
+        return Some(BreakReason::HashMismatch);
+    }
+    None
+}
+
+/// Recompute the audit hash chain from its first entry and report the first
+/// broken link. Rows written before chaining was introduced are skipped.
+pub async fn verify_chain(pool: &PgPool) -> Result<ChainVerification, sqlx::Error> {
+    let start = sqlx::query_scalar!("SELECT MIN(seq) FROM audit_log WHERE entry_hash IS NOT NULL")
+        .fetch_one(pool)
+        .await?;
+    let Some(start) = start else {
+        return Ok(ChainVerification {
+            checked: 0,
+            first_break: None,
+        });
+    };
+
+    let mut prev_hash = String::new();
+    let mut after = start - 1;
+    let mut checked = 0;
+    loop {
+        let rows = sqlx::query_as!(
+            ChainedRow,
+            r#"
+            SELECT seq, id, actor_id, actor_name, action, resource, resource_id, project_id,
+                   detail, ip_addr, break_glass_id, created_at, prev_hash, entry_hash
+            FROM audit_log
+            WHERE seq > $1
+            ORDER BY seq
+            LIMIT $2
+            "#,
+            after,
+            VERIFY_BATCH,
+        )
+        .fetch_all(pool)
+        .await?;
+        let Some(last) = rows.last() else {
+            break;
+        };
+        after = last.seq;
+
+        for row in rows {
+            if let Some(reason) = check_link(&prev_hash, &row) {
+                return Ok(ChainVerification {
+                    checked,
+                    first_break: Some(ChainBreak {
+                        id: row.id,
+                        seq: row.seq,
+                        created_at: row.created_at,
+                        reason,
+                    }),
+                });
+            }
+            checked += 1;
+            prev_hash = row.entry_hash.unwrap_or_default();
+        }
+    }
+
+    Ok(ChainVerification {
+        checked,
+        first_break: None,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn row(action: &str) -> ChainedRow {
+        ChainedRow {
+            seq: 1,
+            id: Uuid::nil(),
+            actor_id: Uuid::nil(),
+            actor_name: "admin".into(),
+            action: action.into(),
+            resource: "project".into(),
+            resource_id: None,
+            project_id: None,
+            detail: Some(serde_json::json!({"name": "web"})),
+            ip_addr: "10.0.0.1".parse().ok(),
+            break_glass_id: None,
+            created_at: DateTime::from_timestamp_micros(1_760_000_000_000_001).unwrap(),
+            prev_hash: None,
+            entry_hash: None,
+        }
+    }
+
+    fn sealed(prev_hash: &str, mut r: ChainedRow) -> ChainedRow {
+        r.entry_hash = Some(chain_hash(prev_hash, &r));
+        r.prev_hash = Some(prev_hash.to_owned());
+        r
+    }
+
+    #[test]
+    fn chain_hash_is_deterministic_hex() {
+        let h = chain_hash("", &row("project.create"));
+        assert_eq!(h.len(), 64);
+        assert_eq!(h, chain_hash("", &row("project.create")));
+    }
+
+    #[test]
+    fn chain_hash_covers_prev_hash_and_content() {
+        let base = chain_hash("", &row("project.create"));
+        assert_ne!(base, chain_hash("abc", &row("project.create")));
+        assert_ne!(base, chain_hash("", &row("project.delete")));
+
+        let mut changed = row("project.create");
+        changed.detail = Some(serde_json::json!({"name": "api"}));
+        assert_ne!(base, chain_hash("", &changed));
+    }
+
+    #[test]
+    fn chain_hash_ignores_sub_microsecond_precision() {
+        let a = row("project.create");
+        let mut b = row("project.create");
+        b.created_at += chrono::Duration::nanoseconds(500);
+        assert_eq!(chain_hash("", &a), chain_hash("", &b));
+    }
+
+    #[test]
+    fn check_link_accepts_intact_row() {
+        let r = sealed("prev", row("project.create"));
+        assert_eq!(check_link("prev", &r), None);
+    }
+
+    #[test]
+    fn check_link_detects_edits_and_gaps() {
+        let mut edited = sealed("prev", row("project.create"));
+        edited.actor_name = "someone-else".into();
+        assert_eq!(check_link("prev", &edited), Some(BreakReason::HashMismatch));
+
+        let r = sealed("prev", row("project.create"));
+        assert_eq!(check_link("other", &r), Some(BreakReason::LinkMismatch));
+
+        assert_eq!(
+            check_link("prev", &row("project.create")),
+            Some(BreakReason::Unchained)
+        );
+    }
+}
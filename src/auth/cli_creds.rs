@@ -59,13 +59,13 @@ pub fn validate_auth_type(auth_type: &str) -> Result<(), String> {
 #[tracing::instrument(skip(pool, master_key, credential_value), fields(%user_id, %auth_type), err)]
 pub async fn store_credentials(
     pool: &PgPool,
-    master_key: &[u8; 32],
+    master_key: &engine::MasterKey,
     user_id: Uuid,
     auth_type: &str,
     credential_value: &str,
     token_expires_at: Option<DateTime<Utc>>,
 ) -> anyhow::Result<CliCredentialInfo> {
-    let encrypted = engine::encrypt(credential_value.as_bytes(), master_key)?;
+    let encrypted = master_key.encrypt(credential_value.as_bytes())?;
 
     let row = sqlx::query!(
         r#"
@@ -131,7 +131,7 @@ pub async fn get_credential_info(
 #[tracing::instrument(skip(pool, master_key), fields(%user_id), err)]
 pub async fn get_decrypted_credential(
     pool: &PgPool,
-    master_key: &[u8; 32],
+    master_key: &engine::MasterKey,
     user_id: Uuid,
 ) -> anyhow::Result<Option<DecryptedCredential>> {
     let row = sqlx::query!(
@@ -149,7 +149,7 @@ pub async fn get_decrypted_credential(
 
     match row {
         Some(r) => {
-            let plaintext = engine::decrypt(&r.encrypted_data, master_key, None)?;
+            let plaintext = master_key.decrypt(&r.encrypted_data)?;
             let value = String::from_utf8(plaintext)
                 .map_err(|e| anyhow::anyhow!("credential is not valid UTF-8: {e}"))?;
             Ok(Some(DecryptedCredential {
@@ -182,7 +182,7 @@ pub async fn delete_credentials(pool: &PgPool, user_id: Uuid) -> anyhow::Result<
 #[tracing::instrument(skip(pool, master_key), fields(%user_id), err)]
 pub async fn resolve_cli_auth(
     pool: &PgPool,
-    master_key: &[u8; 32],
+    master_key: &engine::MasterKey,
     user_id: Uuid,
 ) -> anyhow::Result<Option<String>> {
     match get_decrypted_credential(pool, master_key, user_id).await? {
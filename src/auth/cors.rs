@@ -0,0 +1,172 @@
+// Copyright (c) 2026 Steven Hooker. Exclusively licensed to and distributed by AgentSphere GmbH.
+// SPDX-License-Identifier: BUSL-1.1
+
+//! CORS origin matching for `PLATFORM_CORS_ORIGINS`.
+//!
+//! Each entry is one of:
+//! - an exact origin: `https://app.example.com`
+//! - a wildcard host: `https://*.preview.example.com` (`*` covers one or more
+//!   subdomain labels; without a scheme only `https` matches)
+//! - an anchored regex: `regex:https://pr-[0-9]+\.example\.com`
+//!
+//! Responses allow credentials, so a pattern that would accept any origin is
+//! rejected rather than reflected.
+
+use regex::Regex;
+
+const REGEX_PREFIX: &str = "regex:";
+
+/// Origins no sane pattern should match; used to reject catch-all entries.
+const PROBE_ORIGINS: &[&str] = &["https://cors-probe.invalid", "http://cors-probe.invalid"];
+
+/// One DNS label, lowercase as browsers send it.
+const LABEL: &str = "[a-z0-9](?:[a-z0-9-]*[a-z0-9])?";
+
+#[derive(Debug)]
+enum OriginPattern {
+    Exact(String),
+    Pattern(Regex),
+}
+
+/// Allowed origins compiled from the configured entries.
+#[derive(Debug, Default)]
+pub struct OriginMatcher {
+    patterns: Vec<OriginPattern>,
+}
+
+impl OriginMatcher {
+    /// Compile `entries`; invalid entries are logged and skipped.
+    pub fn new(entries: &[String]) -> Self {
+        let patterns = entries
+            .iter()
+            .filter_map(|entry| match parse_entry(entry) {
+                Ok(pattern) => Some(pattern),
+                Err(e) => {
+                    tracing::warn!(entry = %entry, error = %e, "ignoring CORS origin");
+                    None
+                }
+            })
+            .collect();
+        Self { patterns }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.patterns.is_empty()
+    }
+
+    pub fn matches(&self, origin: &str) -> bool {
+        self.patterns.iter().any(|p| match p {
+            OriginPattern::Exact(exact) => exact == origin,
+            OriginPattern::Pattern(re) => re.is_match(origin),
+        })
+    }
+}
+
+fn parse_entry(entry: &str) -> Result<OriginPattern, String> {
+    let pattern = if let Some(re) = entry.strip_prefix(REGEX_PREFIX) {
+        Regex::new(&format!("^(?:{re})$")).map_err(|e| format!("invalid regex: {e}"))?
+    } else if entry.contains('*') {
+        wildcard_regex(entry)?
+    } else {
+        return Ok(OriginPattern::Exact(entry.to_owned()));
+    };
+
+    if PROBE_ORIGINS.iter().any(|o| pattern.is_match(o)) {
+        return Err("pattern matches arbitrary origins".into());
+    }
+    Ok(OriginPattern::Pattern(pattern))
+}
+
+/// `[scheme://]*.suffix[:port]` → anchored regex.
+fn wildcard_regex(entry: &str) -> Result<Regex, String> {
+    let (scheme, rest) = match entry.split_once("://") {
+        Some((scheme @ ("http" | "https"), rest)) => (scheme, rest),
+        Some(_) => return Err("scheme must be http or https".into()),
+        None => ("https", entry),
+    };
+    let suffix = rest
+        .strip_prefix("*.")
+        .ok_or("wildcard must be a leading `*.` subdomain")?;
+    let (host, port) = match suffix.split_once(':') {
+        Some((host, port)) if !port.is_empty() && port.bytes().all(|b| b.is_ascii_digit()) => {
+            (host, Some(port))
+        }
+        Some(_) => return Err("invalid port".into()),
+        None => (suffix, None),
+    };
+    let labels: Vec<&str> = host.split('.').collect();
+    let label_re = Regex::new(&format!("^{LABEL}$")).expect("valid label regex");
+    if labels.len() < 2 || !labels.iter().all(|l| label_re.is_match(l)) {
+        return Err("wildcard suffix must be a domain with at least two labels".into());
+    }
+
+    let port = port.map(|p| format!(":{p}")).unwrap_or_default();
+    Regex::new(&format!(
+        "^{scheme}://(?:{LABEL}\\.)+{}{}$",
+        regex::escape(host),
+        regex::escape(&port)
+    ))
+    .map_err(|e| e.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn matcher(entries: &[&str]) -> OriginMatcher {
+        OriginMatcher::new(&entries.iter().map(|e| (*e).to_owned()).collect::<Vec<_>>())
+    }
+
+    #[test]
+    fn exact_origin() {
+        let m = matcher(&["http://localhost:3000"]);
+        assert!(m.matches("http://localhost:3000"));
+        assert!(!m.matches("http://localhost:3001"));
+    }
+
+    #[test]
+    fn wildcard_subdomains() {
+        let m = matcher(&["*.preview.example.com"]);
+        assert!(m.matches("https://abc.preview.example.com"));
+        assert!(m.matches("https://a.b.preview.example.com"));
+        assert!(!m.matches("https://preview.example.com"));
+        assert!(!m.matches("http://abc.preview.example.com"));
+        assert!(!m.matches("https://evil.com"));
+        assert!(!m.matches("https://abcpreview.example.com"));
+        assert!(!m.matches("https://abc.preview.example.com.evil.com"));
+        assert!(!m.matches("https://abc.preview.example.com:8443"));
+    }
+
+    #[test]
+    fn wildcard_with_scheme_and_port() {
+        let m = matcher(&["http://*.dev.example.com:8080"]);
+        assert!(m.matches("http://x.dev.example.com:8080"));
+        assert!(!m.matches("http://x.dev.example.com"));
+        assert!(!m.matches("https://x.dev.example.com:8080"));
+    }
+
+    #[test]
+    fn regex_is_anchored() {
+        let m = matcher(&[r"regex:https://pr-[0-9]+\.example\.com"]);
+        assert!(m.matches("https://pr-42.example.com"));
+        assert!(!m.matches("https://pr-42.example.com.evil.com"));
+        assert!(!m.matches("https://x.https://pr-42.example.com"));
+    }
+
+    #[test]
+    fn catch_all_patterns_are_rejected() {
+        for entry in [
+            "*",
+            "https://*",
+            "*.com",
+            "https://foo.*.example.com",
+            "ftp://*.example.com",
+            "regex:.*",
+            "regex:https?://.+",
+            "regex:(",
+        ] {
+            assert!(parse_entry(entry).is_err(), "{entry} should be rejected");
+        }
+        assert!(matcher(&["*", "regex:.*"]).is_empty());
+    }
+}
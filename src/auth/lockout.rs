@@ -0,0 +1,143 @@
+// Copyright (c) 2026 Steven Hooker. Exclusively licensed to and distributed by AgentSphere GmbH.
+// SPDX-License-Identifier: BUSL-1.1
+
+//! Account lockout after repeated failed logins.
+//!
+//! Failures are counted per user in `lockout:fail:{user_id}` over a fixed
+//! window (anchored like `rate_limit::check_rate`). Reaching the threshold
+//! sets `lockout:locked:{user_id}` for the lockout duration; while it exists
+//! login is refused even with the correct password. Login names that match
+//! no account are counted the same way, so a 423 does not reveal which
+//! accounts exist.
+
+use std::fmt;
+
+use fred::interfaces::KeysInterface;
+use fred::types::{Expiration, ExpireOptions};
+use uuid::Uuid;
+
+use crate::config::Config;
+use crate::error::ApiError;
+
+/// What failed logins are counted against.
+#[derive(Debug, Clone, Copy)]
+pub enum Subject<'a> {
+    User(Uuid),
+    /// A login name without an account.
+    UnknownName(&'a str),
+}
+
+impl fmt::Display for Subject<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::User(id) => write!(f, "{id}"),
+            Self::UnknownName(name) => write!(f, "name:{name}"),
+        }
+    }
+}
+
+fn fail_key(subject: Subject<'_>) -> String {
+    format!("lockout:fail:{subject}")
+}
+
+fn locked_key(subject: Subject<'_>) -> String {
+    format!("lockout:locked:{subject}")
+}
+
+/// Return `ApiError::Locked` if the subject is currently locked.
+pub async fn check(valkey: &fred::clients::Pool, subject: Subject<'_>) -> Result<(), ApiError> {
+    let ttl: i64 = valkey.ttl(locked_key(subject)).await?;
+    // -2: no key, -1: key without expiry (never set by us, treat as expired)
+    if ttl > 0 {
+        return Err(ApiError::Locked(format!(
+            "account locked, try again in {}",
+            format_retry(ttl)
+        )));
+    }
+    Ok(())
+}
+
+/// Count a failed login. Returns `true` when this failure locked the subject.
+pub async fn record_failure(
+    valkey: &fred::clients::Pool,
+    config: &Config,
+    subject: Subject<'_>,
+) -> Result<bool, ApiError> {
+    if config.login_lockout_threshold == 0 {
+        return Ok(false);
+    }
+    let key = fail_key(subject);
+    let count: u64 = valkey.incr(&key).await?;
+    let _: () = valkey
+        .expire(
+            &key,
+            config.login_lockout_window_secs,
+            Some(ExpireOptions::NX),
+        )
+        .await?;
+
+    if count < config.login_lockout_threshold {
+        return Ok(false);
+    }
+    let _: () = valkey
+        .set(
+            locked_key(subject),
+            1,
+            Some(Expiration::EX(config.login_lockout_duration_secs)),
+            None,
+            false,
+        )
+        .await?;
+    let _: () = valkey.del(&key).await?;
+    Ok(true)
+}
+
+/// Forget failed attempts after a successful login.
+pub async fn clear_failures(valkey: &fred::clients::Pool, user_id: Uuid) -> Result<(), ApiError> {
+    let _: () = valkey.del(fail_key(Subject::User(user_id))).await?;
+    Ok(())
+}
+
+/// Lift a lock early. Returns `true` if the account was locked.
+pub async fn unlock(valkey: &fred::clients::Pool, user_id: Uuid) -> Result<bool, ApiError> {
+    let removed: i64 = valkey.del(locked_key(Subject::User(user_id))).await?;
+    clear_failures(valkey, user_id).await?;
+    Ok(removed > 0)
+}
+
+/// Human-readable remaining lock time, rounded up to whole minutes past 60s.
+fn format_retry(secs: i64) -> String {
+    if secs < 60 {
+        let unit = if secs == 1 { "second" } else { "seconds" };
+        return format!("{secs} {unit}");
+    }
+    let mins = (secs + 59) / 60;
+    let unit = if mins == 1 { "minute" } else { "minutes" };
+    format!("{mins} {unit}")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn format_retry_seconds_and_minutes() {
+        assert_eq!(format_retry(1), "1 second");
+        assert_eq!(format_retry(45), "45 seconds");
+        assert_eq!(format_retry(60), "1 minute");
+        assert_eq!(format_retry(61), "2 minutes");
+        assert_eq!(format_retry(900), "15 minutes");
+    }
+
+    #[test]
+    fn keys_are_per_user() {
+        let a = Subject::User(Uuid::new_v4());
+        let b = Subject::User(Uuid::new_v4());
+        assert_ne!(locked_key(a), locked_key(b));
+        assert_ne!(fail_key(a), locked_key(a));
+        assert_eq!(
+            locked_key(Subject::UnknownName("ghost")),
+            "lockout:locked:name:ghost"
+        );
+    }
+}
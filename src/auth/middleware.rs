@@ -1,6 +1,10 @@
 // Copyright (c) 2026 Steven Hooker. Exclusively licensed to and distributed by AgentSphere GmbH.
 // SPDX-License-Identifier: BUSL-1.1
 
+use std::collections::HashMap;
+use std::sync::{LazyLock, Mutex};
+use std::time::{Duration, Instant};
+
 use axum::extract::FromRequestParts;
 use axum::http::header::AUTHORIZATION;
 use axum::http::request::Parts;
@@ -64,6 +68,24 @@ impl AuthUser {
         Ok(())
     }
 
+    /// Gate for project-bounded API tokens, applied to every request before
+    /// its handler runs: `/api/projects/{id}/…` paths for other projects and
+    /// the `/api/admin/…` endpoints are refused outright, whatever the
+    /// token owner's role. The project is refused by path alone, so the 403
+    /// says nothing about whether it exists.
+    fn check_path_boundary(&self, path: &str) -> Result<(), ApiError> {
+        let Some(boundary_pid) = self.boundary_project_id else {
+            return Ok(());
+        };
+        if path.starts_with("/api/admin/") {
+            return Err(ApiError::Forbidden);
+        }
+        match path_project_id(path) {
+            Some(pid) if pid != boundary_pid => Err(ApiError::Forbidden),
+            _ => Ok(()),
+        }
+    }
+
     /// Verify this request is allowed to access resources in the given workspace.
     /// Returns 404 for scope violations (don't leak resource existence).
     #[allow(dead_code)] // symmetric with check_project_scope; used by workspace-aware handlers
@@ -77,6 +99,48 @@ impl AuthUser {
     }
 }
 
+/// Client details recorded on new login sessions. Unlike `AuthUser` this
+/// never rejects, so it works on unauthenticated routes such as login.
+#[derive(Debug, Clone, Default)]
+pub struct ClientInfo {
+    pub ip_addr: Option<String>,
+    /// `User-Agent` header, truncated to `MAX_USER_AGENT_LEN` characters.
+    pub user_agent: Option<String>,
+}
+
+/// Longest `User-Agent` value stored on a session.
+const MAX_USER_AGENT_LEN: usize = 512;
+
+impl FromRequestParts<AppState> for ClientInfo {
+    type Rejection = std::convert::Infallible;
+
+    async fn from_request_parts(
+        parts: &mut Parts,
+        state: &AppState,
+    ) -> Result<Self, Self::Rejection> {
+        let ip_addr = extract_ip(
+            parts,
+            state.config.trust_proxy_headers,
+            &state.config.trust_proxy_cidrs,
+        );
+        let user_agent = parts
+            .headers
+            .get(axum::http::header::USER_AGENT)
+            .and_then(|v| v.to_str().ok())
+            .map(|ua| {
+                ua.trim()
+                    .chars()
+                    .take(MAX_USER_AGENT_LEN)
+                    .collect::<String>()
+            })
+            .filter(|ua| !ua.is_empty());
+        Ok(Self {
+            ip_addr,
+            user_agent,
+        })
+    }
+}
+
 /// Parse `user_type` string from DB into the `UserType` enum.
 fn parse_user_type(s: &str) -> Result<UserType, ApiError> {
     s.parse().map_err(|e: anyhow::Error| ApiError::Internal(e))
@@ -114,6 +178,11 @@ impl FromRequestParts<AppState> for AuthUser {
         parts: &mut Parts,
         state: &AppState,
     ) -> Result<Self, Self::Rejection> {
+        // Already authenticated by the API rate limiter for this request
+        if let Some(user) = parts.extensions.get::<Self>() {
+            return Ok(user.clone());
+        }
+
         let trust_proxy = state.config.trust_proxy_headers;
         let ip_addr = extract_ip(parts, trust_proxy, &state.config.trust_proxy_cidrs);
 
@@ -133,6 +202,9 @@ impl FromRequestParts<AppState> for AuthUser {
                 } else {
                     None
                 };
+                if let Some(sid) = session_id {
+                    record_agent_activity(&state.pool, sid);
+                }
                 let auth_user = Self {
                     user_id: user.user_id,
                     user_name: user.user_name,
@@ -144,6 +216,7 @@ impl FromRequestParts<AppState> for AuthUser {
                     session_id,
                     session_token_hash: None,
                 };
+                auth_user.check_path_boundary(parts.uri.path())?;
                 auth_user.record_to_span();
                 return Ok(auth_user);
             }
@@ -223,6 +296,18 @@ impl FromRequestParts<AppState> for OptionalAuthUser {
     }
 }
 
+/// Raw credential presented by the request (bearer token, else session cookie),
+/// without validating it. Keys per-token rate limits.
+pub(crate) fn request_credential(parts: &Parts) -> Option<&str> {
+    extract_bearer_token(parts).or_else(|| extract_session_cookie(parts))
+}
+
+/// The project a `/api/projects/{id}/…` path targets.
+pub(crate) fn path_project_id(path: &str) -> Option<Uuid> {
+    let rest = path.strip_prefix("/api/projects/")?;
+    rest.split(['/', '?']).next()?.parse().ok()
+}
+
 fn extract_bearer_token(parts: &Parts) -> Option<&str> {
     let value = parts.headers.get(AUTHORIZATION)?.to_str().ok()?;
     let token = value.strip_prefix("Bearer ")?;
@@ -250,35 +335,54 @@ fn extract_session_cookie(parts: &Parts) -> Option<&str> {
 }
 
 fn extract_ip(parts: &Parts, trust_proxy: bool, trust_proxy_cidrs: &[String]) -> Option<String> {
-    // Only trust X-Forwarded-For when behind a configured reverse proxy
+    let peer = parts
+        .extensions
+        .get::<axum::extract::ConnectInfo<std::net::SocketAddr>>()
+        .map(|ci| ci.0.ip());
+
+    // Only trust forwarding headers when behind a configured reverse proxy.
+    // S59: when CIDRs are configured the connecting IP must be one of them.
+    // Without ConnectInfo the CIDRs can't be enforced and the header is trusted.
     if trust_proxy
-        && let Some(forwarded) = parts.headers.get("x-forwarded-for")
-        && let Ok(val) = forwarded.to_str()
-        && let Some(first_ip) = val.split(',').next()
+        && peer.is_none_or(|ip| trust_proxy_cidrs.is_empty() || cidr_matches(ip, trust_proxy_cidrs))
+        && let Some(client) = forwarded_client_ip(&parts.headers, trust_proxy_cidrs)
     {
-        let ip_str = first_ip.trim();
-        // S59: When CIDRs are configured, only trust if the connecting IP matches
-        if !trust_proxy_cidrs.is_empty() {
-            let connecting_ip = parts
-                .extensions
-                .get::<axum::extract::ConnectInfo<std::net::SocketAddr>>()
-                .map(|ci| ci.0.ip());
-            if let Some(conn_ip) = connecting_ip
-                && !cidr_matches(conn_ip, trust_proxy_cidrs)
-            {
-                // Connecting IP not in trusted CIDRs — ignore X-Forwarded-For
-                return Some(conn_ip.to_string());
-            }
-            // No ConnectInfo available — fall through to trust the header
-            // (server not using ConnectInfo layer; CIDRs can't be enforced)
-        }
-        return Some(ip_str.to_owned());
+        return Some(client.to_string());
     }
-    // Fall back to ConnectInfo if available
-    parts
-        .extensions
-        .get::<axum::extract::ConnectInfo<std::net::SocketAddr>>()
-        .map(|ci| ci.0.ip().to_string())
+    peer.map(|ip| ip.to_string())
+}
+
+/// Client IP from `X-Forwarded-For`, else `X-Real-IP`. Hops are read right
+/// to left skipping trusted proxies, so the leftmost untrusted hop before our
+/// proxies is the client and spoofed entries further left are ignored. With no
+/// CIDRs configured every proxy is trusted and the leftmost hop wins.
+fn forwarded_client_ip(
+    headers: &axum::http::HeaderMap,
+    trust_proxy_cidrs: &[String],
+) -> Option<std::net::IpAddr> {
+    let hops: Vec<std::net::IpAddr> = headers
+        .get_all("x-forwarded-for")
+        .iter()
+        .filter_map(|v| v.to_str().ok())
+        .flat_map(|v| v.split(','))
+        .filter_map(|hop| hop.trim().parse().ok())
+        .collect();
+
+    let client = if trust_proxy_cidrs.is_empty() {
+        hops.first().copied()
+    } else {
+        hops.iter()
+            .rev()
+            .find(|ip| !cidr_matches(**ip, trust_proxy_cidrs))
+            .or(hops.first())
+            .copied()
+    };
+    client.or_else(|| {
+        headers
+            .get("x-real-ip")
+            .and_then(|v| v.to_str().ok())
+            .and_then(|v| v.trim().parse().ok())
+    })
 }
 
 /// Check whether an IP address matches any of the configured trusted CIDRs.
@@ -290,6 +394,53 @@ fn cidr_matches(ip: std::net::IpAddr, cidrs: &[String]) -> bool {
     })
 }
 
+/// Minimum gap between `last_activity_at` writes for one agent session.
+const ACTIVITY_WRITE_INTERVAL: Duration = Duration::from_secs(10);
+
+/// When this replica last wrote each agent session's activity.
+static ACTIVITY_WRITTEN: LazyLock<Mutex<HashMap<Uuid, Instant>>> =
+    LazyLock::new(|| Mutex::new(HashMap::new()));
+
+/// Whether `session_id`'s activity is due to be written again, claiming the
+/// write if so.
+fn activity_write_due(session_id: Uuid, now: Instant) -> bool {
+    let mut written = ACTIVITY_WRITTEN
+        .lock()
+        .unwrap_or_else(std::sync::PoisonError::into_inner);
+    if written
+        .get(&session_id)
+        .is_some_and(|at| now.duration_since(*at) < ACTIVITY_WRITE_INTERVAL)
+    {
+        return false;
+    }
+    // Forget sessions that have gone quiet so the map stays small
+    if written.len() >= 1024 {
+        written.retain(|_, at| now.duration_since(*at) < ACTIVITY_WRITE_INTERVAL);
+    }
+    written.insert(session_id, now);
+    true
+}
+
+/// Bump `last_activity_at` on the agent session behind an agent token (fire-and-forget).
+/// Writes at most every 10 seconds per session so chatty agents don't hammer the row:
+/// requests in between skip the database entirely.
+fn record_agent_activity(pool: &PgPool, session_id: Uuid) {
+    if !activity_write_due(session_id, Instant::now()) {
+        return;
+    }
+    let pool = pool.clone();
+    tokio::spawn(async move {
+        let _ = sqlx::query!(
+            "UPDATE agent_sessions SET last_activity_at = now() \
+             WHERE id = $1 AND status = 'running' \
+               AND (last_activity_at IS NULL OR last_activity_at < now() - INTERVAL '10 seconds')",
+            session_id
+        )
+        .execute(&pool)
+        .await;
+    });
+}
+
 /// Look up an API token by its raw value. Updates `last_used_at` on success.
 async fn lookup_api_token(
     pool: &PgPool,
@@ -642,6 +793,40 @@ mod tests {
         assert_eq!(extract_ip(&parts, true, &cidrs), Some("8.8.8.8".into()));
     }
 
+    #[test]
+    fn ip_cidr_takes_rightmost_untrusted_hop() {
+        let cidrs = vec!["10.0.0.0/8".to_string()];
+        // Client spoofs 6.6.6.6; our proxies at 10.x appended the real hops
+        let mut parts = make_parts(&[("x-forwarded-for", "6.6.6.6, 1.2.3.4, 10.0.0.2")]);
+        let addr: std::net::SocketAddr = "10.0.0.1:9000".parse().unwrap();
+        parts.extensions.insert(axum::extract::ConnectInfo(addr));
+        assert_eq!(extract_ip(&parts, true, &cidrs), Some("1.2.3.4".into()));
+    }
+
+    #[test]
+    fn ip_cidr_all_hops_trusted_returns_leftmost() {
+        let cidrs = vec!["10.0.0.0/8".to_string()];
+        let parts = make_parts(&[("x-forwarded-for", "10.1.1.1, 10.0.0.2")]);
+        assert_eq!(extract_ip(&parts, true, &cidrs), Some("10.1.1.1".into()));
+    }
+
+    #[test]
+    fn ip_from_real_ip_header() {
+        let mut parts = make_parts(&[("x-real-ip", " 1.2.3.4 ")]);
+        let addr: std::net::SocketAddr = "10.0.0.1:9000".parse().unwrap();
+        parts.extensions.insert(axum::extract::ConnectInfo(addr));
+        assert_eq!(extract_ip(&parts, true, &[]), Some("1.2.3.4".into()));
+        assert_eq!(extract_ip(&parts, false, &[]), Some("10.0.0.1".into()));
+    }
+
+    #[test]
+    fn ip_invalid_forwarded_for_falls_back_to_peer() {
+        let mut parts = make_parts(&[("x-forwarded-for", "not-an-ip")]);
+        let addr: std::net::SocketAddr = "10.0.0.1:9000".parse().unwrap();
+        parts.extensions.insert(axum::extract::ConnectInfo(addr));
+        assert_eq!(extract_ip(&parts, true, &[]), Some("10.0.0.1".into()));
+    }
+
     #[test]
     fn cidr_matches_valid_cidr() {
         let ip: std::net::IpAddr = "10.1.2.3".parse().unwrap();
@@ -905,4 +1090,55 @@ mod tests {
         assert_eq!(cloned.session_token_hash, Some("hash123".into()));
         assert_eq!(cloned.token_scopes, Some(vec!["project:read".to_string()]));
     }
+
+    #[test]
+    fn project_id_from_path() {
+        let id = Uuid::new_v4();
+        assert_eq!(
+            path_project_id(&format!("/api/projects/{id}/pipelines")),
+            Some(id)
+        );
+        assert_eq!(path_project_id(&format!("/api/projects/{id}")), Some(id));
+        assert_eq!(path_project_id("/api/projects"), None);
+        assert_eq!(path_project_id("/api/projects/search"), None);
+        assert_eq!(path_project_id(&format!("/api/workspaces/{id}")), None);
+    }
+
+    #[test]
+    fn project_boundary_refuses_other_projects_and_admin() {
+        let (a, b) = (Uuid::new_v4(), Uuid::new_v4());
+        let auth = AuthUser::test_with_project_scope(Uuid::new_v4(), a);
+        assert!(
+            auth.check_path_boundary(&format!("/api/projects/{a}/issues"))
+                .is_ok()
+        );
+        assert!(auth.check_path_boundary("/api/projects").is_ok());
+        assert!(auth.check_path_boundary("/api/auth/me").is_ok());
+        assert!(matches!(
+            auth.check_path_boundary(&format!("/api/projects/{b}/issues")),
+            Err(ApiError::Forbidden)
+        ));
+        assert!(matches!(
+            auth.check_path_boundary("/api/admin/roles"),
+            Err(ApiError::Forbidden)
+        ));
+
+        let unbounded = AuthUser::test_human(Uuid::new_v4());
+        assert!(
+            unbounded
+                .check_path_boundary(&format!("/api/projects/{b}"))
+                .is_ok()
+        );
+        assert!(unbounded.check_path_boundary("/api/admin/roles").is_ok());
+    }
+
+    #[test]
+    fn activity_write_throttled_per_session() {
+        let session = Uuid::new_v4();
+        let now = Instant::now();
+        assert!(activity_write_due(session, now));
+        assert!(!activity_write_due(session, now + Duration::from_secs(1)));
+        assert!(activity_write_due(Uuid::new_v4(), now));
+        assert!(activity_write_due(session, now + ACTIVITY_WRITE_INTERVAL));
+    }
 }
@@ -4,9 +4,13 @@
 //! Authentication, sessions, tokens, and passkeys.
 
 pub mod cli_creds;
+pub mod cors;
+pub mod lockout;
 pub mod middleware;
+pub mod oidc;
 pub mod passkey;
 pub mod password;
 pub mod rate_limit;
 pub mod token;
+pub mod totp;
 pub mod user_type;
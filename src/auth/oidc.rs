@@ -0,0 +1,597 @@
+// Copyright (c) 2026 Steven Hooker. Exclusively licensed to and distributed by AgentSphere GmbH.
+// SPDX-License-Identifier: BUSL-1.1
+
+//! `OpenID` Connect single sign-on against the provider in `PLATFORM_OIDC_*`.
+//!
+//! Authorization code flow with PKCE: [`discover`] the provider, send the
+//! browser to [`authorization_url`], then [`exchange_code`] and
+//! [`verify_id_token`] on the callback. ID tokens must be signed with
+//! RS256/384/512 or ES256/384 by a key from the provider's JWKS.
+
+use std::collections::HashMap;
+use std::sync::LazyLock;
+use std::time::Duration;
+
+use base64::Engine;
+use base64::engine::general_purpose::URL_SAFE_NO_PAD;
+use ring::signature;
+use serde::Deserialize;
+use serde::de::DeserializeOwned;
+use sha2::{Digest, Sha256};
+
+use crate::config::Config;
+
+/// Clock skew tolerated on `exp` and `iat`.
+const LEEWAY_SECS: i64 = 60;
+
+static OIDC_CLIENT: LazyLock<reqwest::Client> = LazyLock::new(|| {
+    reqwest::Client::builder()
+        .connect_timeout(Duration::from_secs(5))
+        .timeout(Duration::from_secs(10))
+        .redirect(reqwest::redirect::Policy::none())
+        .build()
+        .expect("failed to build OIDC HTTP client")
+});
+
+// ---------------------------------------------------------------------------
+// Provider
+// ---------------------------------------------------------------------------
+
+/// The parts of the discovery document the login flow needs.
+#[derive(Debug, Clone, Deserialize)]
+pub struct ProviderMetadata {
+    pub issuer: String,
+    pub authorization_endpoint: String,
+    pub token_endpoint: String,
+    pub jwks_uri: String,
+}
+
+/// Fetch `{issuer}/.well-known/openid-configuration`.
+#[tracing::instrument(err)]
+pub async fn discover(issuer: &str) -> anyhow::Result<ProviderMetadata> {
+    let issuer = issuer.trim_end_matches('/');
+    let meta: ProviderMetadata =
+        get_json(&format!("{issuer}/.well-known/openid-configuration")).await?;
+    if meta.issuer.trim_end_matches('/') != issuer {
+        anyhow::bail!(
+            "discovery document names issuer {} instead of {issuer}",
+            meta.issuer
+        );
+    }
+    Ok(meta)
+}
+
+#[derive(Debug, Deserialize)]
+pub struct Jwks {
+    pub keys: Vec<Jwk>,
+}
+
+/// A public key from the provider's JWKS. Only RSA and EC signing keys are used.
+#[derive(Debug, Deserialize)]
+pub struct Jwk {
+    pub kty: String,
+    pub kid: Option<String>,
+    pub alg: Option<String>,
+    #[serde(rename = "use")]
+    pub key_use: Option<String>,
+    pub n: Option<String>,
+    pub e: Option<String>,
+    pub crv: Option<String>,
+    pub x: Option<String>,
+    pub y: Option<String>,
+}
+
+/// Fetch the provider's signing keys. Not cached: logins are rare and this
+/// picks up key rotation immediately.
+pub async fn fetch_jwks(meta: &ProviderMetadata) -> anyhow::Result<Jwks> {
+    get_json(&meta.jwks_uri).await
+}
+
+async fn get_json<T: DeserializeOwned>(url: &str) -> anyhow::Result<T> {
+    let resp = OIDC_CLIENT.get(url).send().await?.error_for_status()?;
+    Ok(resp.json().await?)
+}
+
+// ---------------------------------------------------------------------------
+// Authorization code flow
+// ---------------------------------------------------------------------------
+
+/// Random URL-safe value for `state`, `nonce` and the PKCE verifier.
+pub fn random_token() -> String {
+    let mut bytes = [0u8; 32];
+    rand::fill(&mut bytes);
+    URL_SAFE_NO_PAD.encode(bytes)
+}
+
+/// PKCE `S256` challenge for `verifier`.
+pub fn pkce_challenge(verifier: &str) -> String {
+    URL_SAFE_NO_PAD.encode(Sha256::digest(verifier.as_bytes()))
+}
+
+/// Provider URL the browser is sent to for login.
+pub fn authorization_url(
+    meta: &ProviderMetadata,
+    config: &Config,
+    state: &str,
+    nonce: &str,
+    code_verifier: &str,
+) -> anyhow::Result<String> {
+    let scopes = if config.oidc_scopes.split_whitespace().any(|s| s == "openid") {
+        config.oidc_scopes.clone()
+    } else {
+        format!("openid {}", config.oidc_scopes)
+    };
+    let mut url = url::Url::parse(&meta.authorization_endpoint)?;
+    url.query_pairs_mut()
+        .append_pair("response_type", "code")
+        .append_pair("client_id", &config.oidc_client_id)
+        .append_pair("redirect_uri", &config.oidc_redirect_url)
+        .append_pair("scope", &scopes)
+        .append_pair("state", state)
+        .append_pair("nonce", nonce)
+        .append_pair("code_challenge", &pkce_challenge(code_verifier))
+        .append_pair("code_challenge_method", "S256");
+    Ok(url.into())
+}
+
+#[derive(Debug, Deserialize)]
+struct TokenResponse {
+    id_token: Option<String>,
+}
+
+/// Redeem the authorization `code` at the token endpoint; returns the raw ID token.
+#[tracing::instrument(skip_all, err)]
+pub async fn exchange_code(
+    meta: &ProviderMetadata,
+    config: &Config,
+    code: &str,
+    code_verifier: &str,
+) -> anyhow::Result<String> {
+    // The serializer is not `Send`; finish it before the first await.
+    let body = {
+        let mut form = url::form_urlencoded::Serializer::new(String::new());
+        form.append_pair("grant_type", "authorization_code")
+            .append_pair("code", code)
+            .append_pair("redirect_uri", &config.oidc_redirect_url)
+            .append_pair("client_id", &config.oidc_client_id)
+            .append_pair("code_verifier", code_verifier);
+        if let Some(ref secret) = config.oidc_client_secret {
+            form.append_pair("client_secret", secret);
+        }
+        form.finish()
+    };
+
+    let resp = OIDC_CLIENT
+        .post(&meta.token_endpoint)
+        .header(
+            reqwest::header::CONTENT_TYPE,
+            "application/x-www-form-urlencoded",
+        )
+        .header(reqwest::header::ACCEPT, "application/json")
+        .body(body)
+        .send()
+        .await?;
+    let status = resp.status();
+    if !status.is_success() {
+        let body = resp.text().await.unwrap_or_default();
+        let body: String = body.chars().take(200).collect();
+        anyhow::bail!("token endpoint returned {status}: {body}");
+    }
+    let token: TokenResponse = resp.json().await?;
+    token
+        .id_token
+        .ok_or_else(|| anyhow::anyhow!("token response has no id_token"))
+}
+
+// ---------------------------------------------------------------------------
+// ID token
+// ---------------------------------------------------------------------------
+
+#[derive(Debug, thiserror::Error)]
+pub enum IdTokenError {
+    #[error("malformed id token")]
+    Malformed,
+    #[error("unsupported signing algorithm {0}")]
+    UnsupportedAlgorithm(String),
+    #[error("no matching signing key")]
+    UnknownKey,
+    #[error("invalid signature")]
+    BadSignature,
+    #[error("{0}")]
+    InvalidClaim(&'static str),
+}
+
+#[derive(Debug, Deserialize)]
+struct JwsHeader {
+    alg: String,
+    kid: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(untagged)]
+enum Audience {
+    One(String),
+    Many(Vec<String>),
+}
+
+impl Audience {
+    fn as_slice(&self) -> &[String] {
+        match self {
+            Self::One(aud) => std::slice::from_ref(aud),
+            Self::Many(auds) => auds,
+        }
+    }
+}
+
+/// Verified ID token claims. Claims not named here (such as the groups
+/// claim) are kept in `extra`.
+#[derive(Debug, Deserialize)]
+pub struct IdTokenClaims {
+    pub iss: String,
+    pub sub: String,
+    aud: Audience,
+    pub exp: i64,
+    pub iat: Option<i64>,
+    pub nonce: Option<String>,
+    pub azp: Option<String>,
+    pub email: Option<String>,
+    email_verified: Option<serde_json::Value>,
+    pub name: Option<String>,
+    pub preferred_username: Option<String>,
+    #[serde(flatten)]
+    pub extra: HashMap<String, serde_json::Value>,
+}
+
+impl IdTokenClaims {
+    /// `email_verified`, accepting the string form some providers send.
+    /// `None` when the provider does not say.
+    pub fn email_verified(&self) -> Option<bool> {
+        match self.email_verified.as_ref()? {
+            serde_json::Value::Bool(b) => Some(*b),
+            serde_json::Value::String(s) => Some(s == "true"),
+            _ => None,
+        }
+    }
+
+    /// Group names in `claim`, either a list or a single string.
+    pub fn groups(&self, claim: &str) -> Vec<String> {
+        match self.extra.get(claim) {
+            Some(serde_json::Value::Array(items)) => items
+                .iter()
+                .filter_map(|v| v.as_str().map(str::to_owned))
+                .collect(),
+            Some(serde_json::Value::String(group)) => vec![group.clone()],
+            _ => Vec::new(),
+        }
+    }
+}
+
+/// Verify the signature of `token` against `jwks` and check issuer, audience,
+/// expiry and nonce. `now` is a Unix timestamp.
+pub fn verify_id_token(
+    token: &str,
+    jwks: &Jwks,
+    issuer: &str,
+    client_id: &str,
+    nonce: &str,
+    now: i64,
+) -> Result<IdTokenClaims, IdTokenError> {
+    let mut parts = token.split('.');
+    let (Some(header_b64), Some(payload_b64), Some(sig_b64), None) =
+        (parts.next(), parts.next(), parts.next(), parts.next())
+    else {
+        return Err(IdTokenError::Malformed);
+    };
+    let header: JwsHeader = decode_segment(header_b64)?;
+    let sig = URL_SAFE_NO_PAD
+        .decode(sig_b64)
+        .map_err(|_| IdTokenError::Malformed)?;
+
+    let key = select_key(jwks, &header)?;
+    let signed = format!("{header_b64}.{payload_b64}");
+    verify_signature(&header.alg, key, signed.as_bytes(), &sig)?;
+
+    let claims: IdTokenClaims = decode_segment(payload_b64)?;
+    check_claims(&claims, issuer, client_id, nonce, now)?;
+    Ok(claims)
+}
+
+fn decode_segment<T: DeserializeOwned>(segment: &str) -> Result<T, IdTokenError> {
+    let bytes = URL_SAFE_NO_PAD
+        .decode(segment)
+        .map_err(|_| IdTokenError::Malformed)?;
+    serde_json::from_slice(&bytes).map_err(|_| IdTokenError::Malformed)
+}
+
+fn select_key<'a>(jwks: &'a Jwks, header: &JwsHeader) -> Result<&'a Jwk, IdTokenError> {
+    let kty = match header.alg.as_str() {
+        "RS256" | "RS384" | "RS512" => "RSA",
+        "ES256" | "ES384" => "EC",
+        other => return Err(IdTokenError::UnsupportedAlgorithm(other.to_owned())),
+    };
+    let mut candidates = jwks.keys.iter().filter(|k| {
+        k.kty == kty
+            && k.key_use.as_deref().is_none_or(|u| u == "sig")
+            && k.alg.as_deref().is_none_or(|a| a == header.alg)
+    });
+    match header.kid {
+        Some(ref kid) => candidates
+            .find(|k| k.kid.as_deref() == Some(kid))
+            .ok_or(IdTokenError::UnknownKey),
+        // Without a `kid` the choice must be unambiguous
+        None => match (candidates.next(), candidates.next()) {
+            (Some(key), None) => Ok(key),
+            _ => Err(IdTokenError::UnknownKey),
+        },
+    }
+}
+
+fn verify_signature(alg: &str, key: &Jwk, message: &[u8], sig: &[u8]) -> Result<(), IdTokenError> {
+    let component = |value: &Option<String>| {
+        value
+            .as_deref()
+            .and_then(|v| URL_SAFE_NO_PAD.decode(v).ok())
+            .ok_or(IdTokenError::UnknownKey)
+    };
+
+    let verified = match alg {
+        "RS256" | "RS384" | "RS512" => {
+            let params = match alg {
+                "RS256" => &signature::RSA_PKCS1_2048_8192_SHA256,
+                "RS384" => &signature::RSA_PKCS1_2048_8192_SHA384,
+                _ => &signature::RSA_PKCS1_2048_8192_SHA512,
+            };
+            signature::RsaPublicKeyComponents {
+                n: component(&key.n)?,
+                e: component(&key.e)?,
+            }
+            .verify(params, message, sig)
+        }
+        "ES256" | "ES384" => {
+            let (params, crv) = if alg == "ES256" {
+                (&signature::ECDSA_P256_SHA256_FIXED, "P-256")
+            } else {
+                (&signature::ECDSA_P384_SHA384_FIXED, "P-384")
+            };
+            if key.crv.as_deref() != Some(crv) {
+                return Err(IdTokenError::UnknownKey);
+            }
+            // Uncompressed SEC1 point: 0x04 || x || y
+            let mut point = vec![0x04];
+            point.extend(component(&key.x)?);
+            point.extend(component(&key.y)?);
+            signature::UnparsedPublicKey::new(params, point).verify(message, sig)
+        }
+        other => return Err(IdTokenError::UnsupportedAlgorithm(other.to_owned())),
+    };
+    verified.map_err(|_| IdTokenError::BadSignature)
+}
+
+fn check_claims(
+    claims: &IdTokenClaims,
+    issuer: &str,
+    client_id: &str,
+    nonce: &str,
+    now: i64,
+) -> Result<(), IdTokenError> {
+    if claims.iss != issuer {
+        return Err(IdTokenError::InvalidClaim("issuer does not match"));
+    }
+    let audiences = claims.aud.as_slice();
+    if !audiences.iter().any(|a| a == client_id)
+        || (audiences.len() > 1 && claims.azp.as_deref() != Some(client_id))
+    {
+        return Err(IdTokenError::InvalidClaim(
+            "token was issued for another client",
+        ));
+    }
+    if claims.exp + LEEWAY_SECS < now {
+        return Err(IdTokenError::InvalidClaim("token has expired"));
+    }
+    if claims.iat.is_some_and(|iat| iat - LEEWAY_SECS > now) {
+        return Err(IdTokenError::InvalidClaim("token was issued in the future"));
+    }
+    if claims.nonce.as_deref() != Some(nonce) {
+        return Err(IdTokenError::InvalidClaim("nonce does not match"));
+    }
+    Ok(())
+}
+
+/// Global roles granted by `role_map` for `groups`, without duplicates.
+pub fn mapped_roles(groups: &[String], role_map: &[(String, String)]) -> Vec<String> {
+    let mut roles: Vec<String> = Vec::new();
+    for (group, role) in role_map {
+        if groups.contains(group) && !roles.contains(role) {
+            roles.push(role.clone());
+        }
+    }
+    roles
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ring::rand::SystemRandom;
+    use ring::signature::{ECDSA_P256_SHA256_FIXED_SIGNING, EcdsaKeyPair, KeyPair};
+
+    const ISSUER: &str = "https://idp.example.com";
+    const CLIENT: &str = "platform";
+    const NOW: i64 = 1_800_000_000;
+
+    struct TestKey {
+        pair: EcdsaKeyPair,
+        rng: SystemRandom,
+    }
+
+    impl TestKey {
+        fn new() -> Self {
+            let rng = SystemRandom::new();
+            let pkcs8 = EcdsaKeyPair::generate_pkcs8(&ECDSA_P256_SHA256_FIXED_SIGNING, &rng)
+                .expect("generate key");
+            let pair =
+                EcdsaKeyPair::from_pkcs8(&ECDSA_P256_SHA256_FIXED_SIGNING, pkcs8.as_ref(), &rng)
+                    .expect("parse key");
+            Self { pair, rng }
+        }
+
+        fn jwks(&self, kid: &str) -> Jwks {
+            let point = self.pair.public_key().as_ref();
+            serde_json::from_value(serde_json::json!({"keys": [{
+                "kty": "EC",
+                "kid": kid,
+                "crv": "P-256",
+                "x": URL_SAFE_NO_PAD.encode(&point[1..33]),
+                "y": URL_SAFE_NO_PAD.encode(&point[33..65]),
+            }]}))
+            .unwrap()
+        }
+
+        fn sign(&self, kid: &str, claims: &serde_json::Value) -> String {
+            let header = serde_json::json!({"alg": "ES256", "kid": kid, "typ": "JWT"});
+            let signed = format!(
+                "{}.{}",
+                URL_SAFE_NO_PAD.encode(header.to_string()),
+                URL_SAFE_NO_PAD.encode(claims.to_string())
+            );
+            let sig = self.pair.sign(&self.rng, signed.as_bytes()).unwrap();
+            format!("{signed}.{}", URL_SAFE_NO_PAD.encode(sig.as_ref()))
+        }
+    }
+
+    fn claims() -> serde_json::Value {
+        serde_json::json!({
+            "iss": ISSUER,
+            "sub": "00u1",
+            "aud": CLIENT,
+            "exp": NOW + 300,
+            "iat": NOW,
+            "nonce": "n-1",
+            "email": "ada@example.com",
+            "email_verified": true,
+            "groups": ["eng", "platform-admins"],
+        })
+    }
+
+    fn verify(token: &str, jwks: &Jwks) -> Result<IdTokenClaims, IdTokenError> {
+        verify_id_token(token, jwks, ISSUER, CLIENT, "n-1", NOW)
+    }
+
+    #[test]
+    fn valid_token_is_accepted() {
+        let key = TestKey::new();
+        let token = key.sign("k1", &claims());
+        let claims = verify(&token, &key.jwks("k1")).unwrap();
+        assert_eq!(claims.sub, "00u1");
+        assert_eq!(claims.email.as_deref(), Some("ada@example.com"));
+        assert_eq!(claims.email_verified(), Some(true));
+        assert_eq!(claims.groups("groups"), vec!["eng", "platform-admins"]);
+    }
+
+    #[test]
+    fn signature_from_another_key_is_rejected() {
+        let token = TestKey::new().sign("k1", &claims());
+        let err = verify(&token, &TestKey::new().jwks("k1")).unwrap_err();
+        assert!(matches!(err, IdTokenError::BadSignature), "{err}");
+    }
+
+    #[test]
+    fn tampered_payload_is_rejected() {
+        let key = TestKey::new();
+        let token = key.sign("k1", &claims());
+        let mut forged = claims();
+        forged["email"] = "admin@example.com".into();
+        let parts: Vec<&str> = token.split('.').collect();
+        let tampered = format!(
+            "{}.{}.{}",
+            parts[0],
+            URL_SAFE_NO_PAD.encode(forged.to_string()),
+            parts[2]
+        );
+        assert!(matches!(
+            verify(&tampered, &key.jwks("k1")),
+            Err(IdTokenError::BadSignature)
+        ));
+    }
+
+    #[test]
+    fn unknown_kid_and_alg_none_are_rejected() {
+        let key = TestKey::new();
+        let token = key.sign("k2", &claims());
+        assert!(matches!(
+            verify(&token, &key.jwks("k1")),
+            Err(IdTokenError::UnknownKey)
+        ));
+
+        let header = URL_SAFE_NO_PAD.encode(r#"{"alg":"none"}"#);
+        let payload = URL_SAFE_NO_PAD.encode(claims().to_string());
+        assert!(matches!(
+            verify(&format!("{header}.{payload}."), &key.jwks("k1")),
+            Err(IdTokenError::UnsupportedAlgorithm(_))
+        ));
+    }
+
+    #[test]
+    fn claims_are_checked() {
+        let key = TestKey::new();
+        let jwks = key.jwks("k1");
+        for (field, value) in [
+            ("iss", serde_json::json!("https://evil.example.com")),
+            ("aud", serde_json::json!("other-client")),
+            ("aud", serde_json::json!([CLIENT, "other-client"])),
+            ("exp", serde_json::json!(NOW - 3600)),
+            ("iat", serde_json::json!(NOW + 3600)),
+            ("nonce", serde_json::json!("n-2")),
+        ] {
+            let mut c = claims();
+            c[field] = value;
+            let err = verify(&key.sign("k1", &c), &jwks).unwrap_err();
+            assert!(
+                matches!(err, IdTokenError::InvalidClaim(_)),
+                "{field}: {err}"
+            );
+        }
+
+        // Several audiences are fine when we are the authorized party
+        let mut c = claims();
+        c["aud"] = serde_json::json!([CLIENT, "other-client"]);
+        c["azp"] = CLIENT.into();
+        assert!(verify(&key.sign("k1", &c), &jwks).is_ok());
+    }
+
+    #[test]
+    fn malformed_tokens_are_rejected() {
+        let jwks = TestKey::new().jwks("k1");
+        for token in ["", "a.b", "a.b.c.d", "!!.??.**"] {
+            assert!(
+                matches!(verify(token, &jwks), Err(IdTokenError::Malformed)),
+                "{token}"
+            );
+        }
+    }
+
+    #[test]
+    fn pkce_challenge_matches_rfc_example() {
+        // RFC 7636, appendix B
+        assert_eq!(
+            pkce_challenge("dBjftJeZ4CVP-mB92K27uhbUJU1p1r_wW1gFWFOEjXk"),
+            "E9Melhoa2OwvFrEMTJguCHaoeK1t8URWbuGJSstw-cM"
+        );
+    }
+
+    #[test]
+    fn roles_are_mapped_from_groups() {
+        let map = vec![
+            ("platform-admins".to_owned(), "admin".to_owned()),
+            ("eng".to_owned(), "developer".to_owned()),
+            ("contractors".to_owned(), "viewer".to_owned()),
+            ("sre".to_owned(), "admin".to_owned()),
+        ];
+        let groups = vec![
+            "eng".to_owned(),
+            "platform-admins".to_owned(),
+            "sre".to_owned(),
+        ];
+        assert_eq!(mapped_roles(&groups, &map), vec!["admin", "developer"]);
+        assert!(mapped_roles(&[], &map).is_empty());
+    }
+}
@@ -1,6 +1,7 @@
 // Copyright (c) 2026 Steven Hooker. Exclusively licensed to and distributed by AgentSphere GmbH.
 // SPDX-License-Identifier: BUSL-1.1
 
+use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 use webauthn_rs::prelude::*;
 
@@ -22,24 +23,74 @@ pub fn build_webauthn(config: &Config) -> anyhow::Result<Webauthn> {
 // Registration ceremony
 // ---------------------------------------------------------------------------
 
+/// Registration state kept in Valkey between begin and complete, with the
+/// name the user chose for the new credential.
+#[derive(Serialize, Deserialize)]
+struct PendingRegistration {
+    state: RegistrationCeremony,
+    name: String,
+}
+
+#[derive(Serialize, Deserialize)]
+enum RegistrationCeremony {
+    Passkey(PasskeyRegistration),
+    /// The attestation must chain to one of the CAs kept in the state.
+    Attested(AttestedPasskeyRegistration),
+}
+
+/// A verified registration, ready to store.
+pub struct Registered {
+    pub passkey: Passkey,
+    /// Name given at `begin_registration`.
+    pub name: String,
+    /// Model vouched for by a trusted attestation CA; `None` when the
+    /// ceremony did not require attestation.
+    pub attested_aaguid: Option<Uuid>,
+}
+
 /// Begin passkey registration. Returns the challenge JSON for the browser and
-/// stores the registration state in Valkey (120s TTL).
-#[tracing::instrument(skip(webauthn, valkey_pool, existing_credentials), fields(%user_id), err)]
+/// stores the registration state and `credential_name` in Valkey (120s TTL).
+/// With `attestation_cas`, only a user-verifying, device-bound authenticator
+/// whose attestation chains to one of them can complete it.
+#[tracing::instrument(
+    skip(webauthn, valkey_pool, existing_credentials, attestation_cas),
+    fields(%user_id),
+    err
+)]
+#[allow(clippy::too_many_arguments)]
 pub async fn begin_registration(
     webauthn: &Webauthn,
     valkey_pool: &fred::clients::Pool,
     user_id: Uuid,
     user_name: &str,
     display_name: &str,
+    credential_name: &str,
     existing_credentials: Vec<CredentialID>,
+    attestation_cas: Option<AttestationCaList>,
 ) -> anyhow::Result<CreationChallengeResponse> {
     let exclude = existing_credentials;
 
-    let (ccr, reg_state) =
-        webauthn.start_passkey_registration(user_id, user_name, display_name, Some(exclude))?;
+    let (ccr, state) = if let Some(cas) = attestation_cas {
+        let (ccr, reg_state) = webauthn.start_attested_passkey_registration(
+            user_id,
+            user_name,
+            display_name,
+            Some(exclude),
+            cas,
+            Some(AuthenticatorAttachment::CrossPlatform),
+        )?;
+        (ccr, RegistrationCeremony::Attested(reg_state))
+    } else {
+        let (ccr, reg_state) =
+            webauthn.start_passkey_registration(user_id, user_name, display_name, Some(exclude))?;
+        (ccr, RegistrationCeremony::Passkey(reg_state))
+    };
 
     // Store registration state in Valkey
-    let state_json = serde_json::to_string(&reg_state)?;
+    let state_json = serde_json::to_string(&PendingRegistration {
+        state,
+        name: credential_name.to_owned(),
+    })?;
     let key = format!("webauthn:reg:{user_id}");
     valkey::set_cached(valkey_pool, &key, &state_json, CHALLENGE_TTL_SECS).await?;
 
@@ -47,14 +98,15 @@ pub async fn begin_registration(
 }
 
 /// Complete passkey registration. Verifies the browser's response against
-/// the stored challenge state. Returns the credential to store in DB.
+/// the stored challenge state, including the attestation chain when the
+/// ceremony required one.
 #[tracing::instrument(skip(webauthn, valkey_pool, response), fields(%user_id), err)]
 pub async fn finish_registration(
     webauthn: &Webauthn,
     valkey_pool: &fred::clients::Pool,
     user_id: Uuid,
     response: &RegisterPublicKeyCredential,
-) -> anyhow::Result<Passkey> {
+) -> anyhow::Result<Registered> {
     let key = format!("webauthn:reg:{user_id}");
     let state_json: String = valkey::get_cached(valkey_pool, &key)
         .await
@@ -63,10 +115,185 @@ pub async fn finish_registration(
     // Clean up state after retrieval
     let _ = valkey::invalidate(valkey_pool, &key).await;
 
-    let reg_state: PasskeyRegistration = serde_json::from_str(&state_json)?;
-    let passkey = webauthn.finish_passkey_registration(response, &reg_state)?;
+    let pending: PendingRegistration = serde_json::from_str(&state_json)?;
+    let (passkey, attested_aaguid) = match pending.state {
+        RegistrationCeremony::Passkey(state) => (
+            webauthn.finish_passkey_registration(response, &state)?,
+            None,
+        ),
+        RegistrationCeremony::Attested(state) => {
+            let attested = webauthn.finish_attested_passkey_registration(response, &state)?;
+            let aaguid = match attested.attestation().metadata {
+                AttestationMetadata::Packed { aaguid }
+                | AttestationMetadata::Tpm { aaguid, .. } => Some(aaguid),
+                _ => None,
+            };
+            (Passkey::from(attested), aaguid)
+        }
+    };
 
-    Ok(passkey)
+    Ok(Registered {
+        passkey,
+        name: pending.name,
+        attested_aaguid,
+    })
+}
+
+// ---------------------------------------------------------------------------
+// Attestation and authenticator policy
+// ---------------------------------------------------------------------------
+
+/// Authenticator data flag bits (`WebAuthn` §6.1).
+const FLAG_UV: u8 = 0x04;
+const FLAG_BE: u8 = 0x08;
+const FLAG_BS: u8 = 0x10;
+const FLAG_AT: u8 = 0x40;
+
+/// Offset of the AAGUID in authenticator data: rpIdHash (32), flags (1),
+/// signCount (4).
+const AAGUID_OFFSET: usize = 37;
+
+/// What a registration's attestation object says about the authenticator.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AuthenticatorInfo {
+    /// Attestation statement format: `none`, `packed`, `tpm`, `fido-u2f`, ...
+    pub format: String,
+    /// Authenticator model; `None` when absent or zeroed by the client.
+    pub aaguid: Option<Uuid>,
+    pub user_verified: bool,
+    /// Set by synced (cloud-backed) passkeys, which live outside any one device.
+    pub backup_eligible: bool,
+    pub backup_state: bool,
+}
+
+/// Read the authenticator details from a CBOR attestation object. The
+/// signature and any certificate chain are verified by `finish_registration`;
+/// the AAGUID here is only what the authenticator claims.
+pub fn parse_attestation(attestation_object: &[u8]) -> anyhow::Result<AuthenticatorInfo> {
+    use serde_cbor_2::Value;
+
+    let Value::Map(map) = serde_cbor_2::from_slice::<Value>(attestation_object)? else {
+        anyhow::bail!("attestation object is not a map");
+    };
+    let field = |name: &str| map.get(&Value::Text(name.into()));
+    let Some(Value::Text(format)) = field("fmt") else {
+        anyhow::bail!("attestation object has no fmt");
+    };
+    let Some(Value::Bytes(auth_data)) = field("authData") else {
+        anyhow::bail!("attestation object has no authData");
+    };
+    let flags = *auth_data
+        .get(AAGUID_OFFSET - 5)
+        .ok_or_else(|| anyhow::anyhow!("authenticator data too short"))?;
+    let aaguid = if flags & FLAG_AT == 0 {
+        None
+    } else {
+        auth_data
+            .get(AAGUID_OFFSET..AAGUID_OFFSET + 16)
+            .and_then(|b| Uuid::from_slice(b).ok())
+            .filter(|id| !id.is_nil())
+    };
+
+    Ok(AuthenticatorInfo {
+        format: format.clone(),
+        aaguid,
+        user_verified: flags & FLAG_UV != 0,
+        backup_eligible: flags & FLAG_BE != 0,
+        backup_state: flags & FLAG_BS != 0,
+    })
+}
+
+/// Which authenticators a user may enroll and sign in with (`users.passkey_policy`).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PasskeyPolicy {
+    Any,
+    /// Device-bound, user-verifying security keys whose attested model is in
+    /// `PLATFORM_PASSKEY_HARDWARE_AAGUIDS`.
+    Hardware,
+}
+
+impl PasskeyPolicy {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Any => "any",
+            Self::Hardware => "hardware",
+        }
+    }
+
+    pub fn parse(s: &str) -> Option<Self> {
+        match s {
+            "any" => Some(Self::Any),
+            "hardware" => Some(Self::Hardware),
+            _ => None,
+        }
+    }
+
+    /// Why the key does not satisfy this policy, if it doesn't.
+    /// `attested_aaguid` is the model verified against the attestation CAs at
+    /// registration; what the authenticator merely claims never counts.
+    pub fn violation(
+        self,
+        info: &AuthenticatorInfo,
+        attested_aaguid: Option<Uuid>,
+        approved_aaguids: &[Uuid],
+    ) -> Option<String> {
+        if self == Self::Any {
+            return None;
+        }
+        if !info.user_verified {
+            return Some("authenticator did not verify the user".into());
+        }
+        if info.backup_eligible {
+            return Some("synced passkeys are not hardware-bound".into());
+        }
+        match attested_aaguid {
+            Some(id) if approved_aaguids.contains(&id) => None,
+            Some(id) => Some(format!("authenticator model {id} is not approved")),
+            None => Some(format!(
+                "authenticator attestation is not signed by a trusted vendor (format {})",
+                info.format
+            )),
+        }
+    }
+}
+
+/// Attestation roots for [`PasskeyPolicy::Hardware`] registrations: every CA
+/// in `PLATFORM_PASSKEY_ATTESTATION_CA`, trusted for the approved models only.
+/// `None` when either setting is empty, in which case no key qualifies.
+pub async fn hardware_attestation_cas(
+    config: &Config,
+) -> anyhow::Result<Option<AttestationCaList>> {
+    use anyhow::Context;
+
+    let Some(path) = config.passkey_attestation_ca.as_deref() else {
+        return Ok(None);
+    };
+    if config.passkey_hardware_aaguids.is_empty() {
+        return Ok(None);
+    }
+    let pem = tokio::fs::read_to_string(path)
+        .await
+        .with_context(|| format!("reading {path}"))?;
+
+    let mut builder = AttestationCaListBuilder::new();
+    for cert in pem
+        .split_inclusive("-----END CERTIFICATE-----")
+        .filter(|block| block.contains("-----BEGIN CERTIFICATE-----"))
+    {
+        for aaguid in &config.passkey_hardware_aaguids {
+            builder
+                .insert_device_pem(
+                    cert.as_bytes(),
+                    *aaguid,
+                    aaguid.to_string(),
+                    std::collections::BTreeMap::new(),
+                )
+                .with_context(|| format!("invalid attestation CA in {path}"))?;
+        }
+    }
+    let cas = builder.build();
+    anyhow::ensure!(!cas.is_empty(), "no certificates in {path}");
+    Ok(Some(cas))
 }
 
 // ---------------------------------------------------------------------------
@@ -144,4 +371,69 @@ mod tests {
     fn test_config() -> Config {
         Config::test_default()
     }
+
+    const YUBIKEY: Uuid = Uuid::from_u128(0xfa2b_99dc_9e39_4257_8f92_4a30_d23c_4118);
+
+    fn attestation_object(format: &str, flags: u8, aaguid: Uuid) -> Vec<u8> {
+        use serde_cbor_2::Value;
+
+        let mut auth_data = vec![0u8; 32];
+        auth_data.push(flags);
+        auth_data.extend_from_slice(&[0, 0, 0, 1]);
+        auth_data.extend_from_slice(aaguid.as_bytes());
+        auth_data.extend_from_slice(&[0, 0]);
+        let map = std::collections::BTreeMap::from([
+            (Value::Text("fmt".into()), Value::Text(format.into())),
+            (
+                Value::Text("attStmt".into()),
+                Value::Map(std::collections::BTreeMap::new()),
+            ),
+            (Value::Text("authData".into()), Value::Bytes(auth_data)),
+        ]);
+        serde_cbor_2::to_vec(&Value::Map(map)).unwrap()
+    }
+
+    #[test]
+    fn parse_attestation_reads_flags_and_aaguid() {
+        let obj = attestation_object("packed", 0x45, YUBIKEY);
+        let info = parse_attestation(&obj).unwrap();
+        assert_eq!(info.format, "packed");
+        assert_eq!(info.aaguid, Some(YUBIKEY));
+        assert!(info.user_verified);
+        assert!(!info.backup_eligible);
+
+        // Zeroed AAGUID, synced passkey
+        let obj = attestation_object("none", 0x5d, Uuid::nil());
+        let info = parse_attestation(&obj).unwrap();
+        assert_eq!(info.aaguid, None);
+        assert!(info.backup_eligible && info.backup_state);
+
+        assert!(parse_attestation(b"not cbor").is_err());
+    }
+
+    #[test]
+    fn hardware_policy_needs_approved_bound_verified_key() {
+        let approved = [YUBIKEY];
+        let check = |flags, attested| {
+            let info = parse_attestation(&attestation_object("packed", flags, YUBIKEY)).unwrap();
+            PasskeyPolicy::Hardware.violation(&info, attested, &approved)
+        };
+
+        assert_eq!(check(0x45, Some(YUBIKEY)), None);
+        assert!(check(0x41, Some(YUBIKEY)).is_some()); // no UV
+        assert!(check(0x4d, Some(YUBIKEY)).is_some()); // synced
+        assert!(check(0x45, None).is_some()); // claimed, not attested
+        assert!(check(0x45, Some(Uuid::from_u128(7))).is_some());
+
+        let software = parse_attestation(&attestation_object("none", 0x5d, Uuid::nil())).unwrap();
+        assert_eq!(PasskeyPolicy::Any.violation(&software, None, &[]), None);
+    }
+
+    #[test]
+    fn policy_parse_roundtrip() {
+        for p in [PasskeyPolicy::Any, PasskeyPolicy::Hardware] {
+            assert_eq!(PasskeyPolicy::parse(p.as_str()), Some(p));
+        }
+        assert_eq!(PasskeyPolicy::parse("strict"), None);
+    }
 }
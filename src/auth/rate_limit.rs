@@ -1,10 +1,27 @@
 // Copyright (c) 2026 Steven Hooker. Exclusively licensed to and distributed by AgentSphere GmbH.
 // SPDX-License-Identifier: BUSL-1.1
 
+use axum::extract::{FromRequestParts, Request, State};
+use axum::http::HeaderValue;
+use axum::http::header::RETRY_AFTER;
+use axum::middleware::Next;
+use axum::response::{IntoResponse, Response};
 use fred::interfaces::KeysInterface;
-use fred::types::ExpireOptions;
+use fred::types::{Expiration, ExpireOptions};
+use uuid::Uuid;
 
+use crate::auth::middleware::{AuthUser, ClientInfo};
+use crate::auth::token;
+use crate::config::Config;
 use crate::error::ApiError;
+use crate::store::AppState;
+
+/// Window of the general API rate limit, in seconds.
+const API_WINDOW_SECS: i64 = 60;
+
+/// How long a resolved token/project override is cached in Valkey. Admin
+/// changes take effect within this many seconds.
+const OVERRIDE_CACHE_SECS: i64 = 10;
 
 /// Fixed-window rate limiter backed by Valkey.
 ///
@@ -20,19 +37,50 @@ pub async fn check_rate(
     window_secs: i64,
 ) -> Result<(), ApiError> {
     let key = format!("rate:{prefix}:{identifier}");
+    let count = hit(valkey, &key, window_secs).await?;
+    check_rate_result(count, max_attempts)
+}
+
+/// Same fixed window as [`check_rate`], but on rejection returns the number
+/// of seconds until the window resets (for a `Retry-After` header) instead
+/// of an error. `Ok(None)` means the request is allowed.
+pub async fn check_rate_retry_after(
+    valkey: &fred::clients::Pool,
+    prefix: &str,
+    identifier: &str,
+    max_attempts: u64,
+    window_secs: i64,
+) -> Result<Option<u64>, ApiError> {
+    let key = format!("rate:{prefix}:{identifier}");
+    let count = hit(valkey, &key, window_secs).await?;
+    if check_rate_result(count, max_attempts).is_ok() {
+        return Ok(None);
+    }
+    let ttl: i64 = valkey.ttl(&key).await.map_err(ApiError::from)?;
+    Ok(Some(retry_after_secs(ttl, window_secs)))
+}
 
-    let count: u64 = valkey.incr(&key).await.map_err(ApiError::from)?;
+/// Count one request against `key` and return the new count.
+async fn hit(valkey: &fred::clients::Pool, key: &str, window_secs: i64) -> Result<u64, ApiError> {
+    let count: u64 = valkey.incr(key).await.map_err(ApiError::from)?;
 
     // NX: only set the TTL if none exists yet. This anchors the window to the
     // first request instead of sliding it forward on every hit. Worst case on
     // crash between INCR and EXPIRE: a single orphaned counter (harmless,
     // evicted by Valkey memory policy).
     let _: () = valkey
-        .expire(&key, window_secs, Some(ExpireOptions::NX))
+        .expire(key, window_secs, Some(ExpireOptions::NX))
         .await
         .map_err(ApiError::from)?;
 
-    check_rate_result(count, max_attempts)
+    Ok(count)
+}
+
+/// Seconds a rejected client should wait. `TTL` returns -1/-2 when the key has
+/// no expiry or is already gone; fall back to the full window then.
+fn retry_after_secs(ttl: i64, window_secs: i64) -> u64 {
+    let secs = if ttl > 0 { ttl } else { window_secs };
+    u64::try_from(secs.max(1)).unwrap_or(1)
 }
 
 /// Pure threshold check: returns `Err(ApiError::TooManyRequests)` when
@@ -44,6 +92,221 @@ fn check_rate_result(count: u64, max_attempts: u64) -> Result<(), ApiError> {
     Ok(())
 }
 
+// ---------------------------------------------------------------------------
+// API-wide limit
+// ---------------------------------------------------------------------------
+
+/// Requests per minute allowed for `path`, and the route group the counter is
+/// kept under. The longest matching override prefix wins; otherwise the global
+/// default applies. A limit of 0 disables limiting.
+fn api_limit_for<'a>(config: &'a Config, path: &str) -> (&'a str, u64) {
+    config
+        .api_rate_limit_overrides
+        .iter()
+        .filter(|(prefix, _)| in_route_group(path, prefix))
+        .max_by_key(|(prefix, _)| prefix.len())
+        .map_or(
+            ("default", config.api_rate_limit_per_minute),
+            |(prefix, max)| (prefix.as_str(), *max),
+        )
+}
+
+/// Whether `path` falls under the route group `prefix`, matching whole path
+/// segments only: `/api/projects` covers `/api/projects/1` but not
+/// `/api/projectsearch`.
+fn in_route_group(path: &str, prefix: &str) -> bool {
+    path.strip_prefix(prefix).is_some_and(|rest| {
+        rest.is_empty() || prefix.ends_with('/') || rest.starts_with(['/', '?'])
+    })
+}
+
+/// An admin-set limit (the `rate_limit_overrides` table) that replaces the
+/// configured one for a request.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum LimitOverride {
+    /// Set on the presented API token.
+    Token(u64),
+    /// Set on the project the request targets or the token is scoped to.
+    Project(Uuid, u64),
+    None,
+}
+
+impl LimitOverride {
+    /// The route group and limit to count the request under: a token override
+    /// beats a project override, which beats the configured limit.
+    fn apply(self, group: &str, max: u64) -> (String, u64) {
+        match self {
+            Self::Token(max) => ("token".into(), max),
+            Self::Project(id, max) => (format!("project:{id}"), max),
+            Self::None => (group.to_owned(), max),
+        }
+    }
+}
+
+/// Cache value for an override lookup: the limit, or `none` when no override
+/// is set.
+fn encode_cached(max: Option<u64>) -> String {
+    max.map_or_else(|| "none".into(), |max| max.to_string())
+}
+
+fn decode_cached(s: &str) -> Result<Option<u64>, std::num::ParseIntError> {
+    if s == "none" {
+        return Ok(None);
+    }
+    s.parse().map(Some)
+}
+
+/// Run `lookup` unless its result for `cache_key` is still cached in Valkey.
+async fn cached_override(
+    state: &AppState,
+    cache_key: &str,
+    lookup: impl Future<Output = Result<Option<i32>, sqlx::Error>>,
+) -> Result<Option<u64>, ApiError> {
+    if let Ok(Some(cached)) = state.valkey.get::<Option<String>, _>(cache_key).await
+        && let Ok(found) = decode_cached(&cached)
+    {
+        return Ok(found);
+    }
+    let found = lookup.await?.map(|max| u64::try_from(max).unwrap_or(0));
+    let _: Result<(), _> = state
+        .valkey
+        .set(
+            cache_key,
+            encode_cached(found),
+            Some(Expiration::EX(OVERRIDE_CACHE_SECS)),
+            None,
+            false,
+        )
+        .await;
+    Ok(found)
+}
+
+/// Find the override for an authenticated request for `project_id`.
+/// `credential` is the hash of the presented credential. Lookups are cached
+/// per token and per project, so they cost one query per window at most.
+async fn limit_override(
+    state: &AppState,
+    user: &AuthUser,
+    credential: &str,
+    project_id: Option<Uuid>,
+) -> Result<LimitOverride, ApiError> {
+    // Only API tokens carry scopes; session credentials never have a token override
+    if user.token_scopes.is_some() {
+        let token_max = cached_override(
+            state,
+            &format!("rate:override:token:{credential}"),
+            sqlx::query_scalar!(
+                "SELECT o.requests_per_minute
+                 FROM rate_limit_overrides o
+                 JOIN api_tokens t ON t.id = o.token_id
+                 WHERE t.token_hash = $1",
+                credential,
+            )
+            .fetch_optional(&state.pool),
+        )
+        .await?;
+        if let Some(max) = token_max {
+            return Ok(LimitOverride::Token(max));
+        }
+    }
+
+    // A project override matches the project in the path or the token's
+    // project scope; the higher of the two wins.
+    let mut found = LimitOverride::None;
+    let mut projects = vec![project_id, user.boundary_project_id];
+    projects.dedup();
+    for id in projects.into_iter().flatten() {
+        let max = cached_override(
+            state,
+            &format!("rate:override:project:{id}"),
+            sqlx::query_scalar!(
+                "SELECT requests_per_minute FROM rate_limit_overrides WHERE project_id = $1",
+                id,
+            )
+            .fetch_optional(&state.pool),
+        )
+        .await?;
+        if let Some(max) = max
+            && !matches!(found, LimitOverride::Project(_, current) if current >= max)
+        {
+            found = LimitOverride::Project(id, max);
+        }
+    }
+    Ok(found)
+}
+
+/// Per-token request rate limit for the API router.
+///
+/// Requests are counted per credential (API token, session token or session
+/// cookie, hashed) and route group, in a one-minute fixed window. The
+/// credential is authenticated here and the result handed on to the
+/// [`AuthUser`] extractor; requests whose credential does not authenticate
+/// share one counter per client IP, so random tokens cannot mint fresh
+/// buckets. Requests without credentials are not counted here — endpoints reachable
+/// without them (login, setup) carry their own [`check_rate`] calls.
+///
+/// The limit has two sources. The configured limit is `PLATFORM_API_RATE_LIMIT`,
+/// replaced per route group by `PLATFORM_API_RATE_LIMIT_OVERRIDES`
+/// (`Config::api_rate_limit_overrides`). Admin-set limits for one token or one
+/// project are kept in the `rate_limit_overrides` table (see
+/// `api::rate_limits`). When both are set, the admin-set limit wins: a token
+/// override beats a project override, which beats the configured route group
+/// limit. The exception is a route whose configured limit is 0: limiting is
+/// off there and admin-set limits do not apply.
+///
+/// Fails open when Valkey is unavailable: a cache outage must not take the
+/// whole API down with it.
+pub async fn api_rate_limit(State(state): State<AppState>, req: Request, next: Next) -> Response {
+    let (group, max) = api_limit_for(&state.config, req.uri().path());
+    if max == 0 {
+        return next.run(req).await;
+    }
+    let project_id = crate::auth::middleware::path_project_id(req.uri().path());
+
+    let (mut parts, body) = req.into_parts();
+    let Some(credential) =
+        crate::auth::middleware::request_credential(&parts).map(token::hash_token)
+    else {
+        return next.run(Request::from_parts(parts, body)).await;
+    };
+    let user = AuthUser::from_request_parts(&mut parts, &state).await.ok();
+    let (identifier, (group, max)) = if let Some(user) = user {
+        let limit = match limit_override(&state, &user, &credential, project_id).await {
+            Ok(found) => found.apply(group, max),
+            Err(e) => {
+                tracing::warn!(error = %e, "api rate limit override lookup failed; using default");
+                (group.to_owned(), max)
+            }
+        };
+        parts.extensions.insert(user);
+        (credential, limit)
+    } else {
+        let Ok(client) = ClientInfo::from_request_parts(&mut parts, &state).await;
+        let ip = client.ip_addr.unwrap_or_else(|| "unknown".into());
+        (format!("ip:{ip}"), (group.to_owned(), max))
+    };
+    let req = Request::from_parts(parts, body);
+    if max == 0 {
+        return next.run(req).await;
+    }
+
+    let prefix = format!("api:{group}");
+    match check_rate_retry_after(&state.valkey, &prefix, &identifier, max, API_WINDOW_SECS).await {
+        Ok(None) => next.run(req).await,
+        Ok(Some(retry_after)) => {
+            tracing::debug!(%group, retry_after, "api rate limit exceeded");
+            let mut resp = ApiError::TooManyRequests.into_response();
+            resp.headers_mut()
+                .insert(RETRY_AFTER, HeaderValue::from(retry_after));
+            resp
+        }
+        Err(e) => {
+            tracing::warn!(error = %e, "api rate limit check failed; allowing request");
+            next.run(req).await
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -82,4 +345,93 @@ mod tests {
         assert!(check_rate_result(u64::MAX, u64::MAX).is_ok());
         assert!(check_rate_result(0, 0).is_ok());
     }
+
+    #[test]
+    fn retry_after_uses_remaining_ttl() {
+        assert_eq!(retry_after_secs(42, 60), 42);
+    }
+
+    #[test]
+    fn retry_after_falls_back_to_window() {
+        assert_eq!(retry_after_secs(-1, 60), 60);
+        assert_eq!(retry_after_secs(-2, 60), 60);
+        assert_eq!(retry_after_secs(0, 60), 60);
+    }
+
+    #[test]
+    fn api_limit_defaults_to_global() {
+        let mut config = Config::test_default();
+        config.api_rate_limit_per_minute = 600;
+        assert_eq!(api_limit_for(&config, "/api/projects"), ("default", 600));
+    }
+
+    #[test]
+    fn api_limit_longest_override_wins() {
+        let mut config = Config::test_default();
+        config.api_rate_limit_per_minute = 600;
+        config.api_rate_limit_overrides = vec![
+            ("/api/projects".into(), 300),
+            ("/api/projects/search".into(), 30),
+            ("/api/sessions".into(), 0),
+        ];
+        assert_eq!(
+            api_limit_for(&config, "/api/projects/123"),
+            ("/api/projects", 300)
+        );
+        assert_eq!(
+            api_limit_for(&config, "/api/projects/search?q=x"),
+            ("/api/projects/search", 30)
+        );
+        assert_eq!(
+            api_limit_for(&config, "/api/sessions/1"),
+            ("/api/sessions", 0)
+        );
+        assert_eq!(api_limit_for(&config, "/api/users"), ("default", 600));
+    }
+
+    #[test]
+    fn api_limit_matches_whole_segments() {
+        let mut config = Config::test_default();
+        config.api_rate_limit_per_minute = 600;
+        config.api_rate_limit_overrides =
+            vec![("/api/projects".into(), 300), ("/api/admin/".into(), 10)];
+        assert_eq!(
+            api_limit_for(&config, "/api/projects"),
+            ("/api/projects", 300)
+        );
+        assert_eq!(
+            api_limit_for(&config, "/api/projectsearch"),
+            ("default", 600)
+        );
+        assert_eq!(
+            api_limit_for(&config, "/api/admin/users"),
+            ("/api/admin/", 10)
+        );
+    }
+
+    #[test]
+    fn override_precedence() {
+        let project = Uuid::nil();
+        assert_eq!(
+            LimitOverride::Token(6000).apply("/api/projects", 300),
+            ("token".into(), 6000)
+        );
+        assert_eq!(
+            LimitOverride::Project(project, 0).apply("default", 600),
+            (format!("project:{project}"), 0)
+        );
+        assert_eq!(
+            LimitOverride::None.apply("/api/projects", 300),
+            ("/api/projects".into(), 300)
+        );
+    }
+
+    #[test]
+    fn override_cache_roundtrip() {
+        for max in [Some(6000), Some(0), None] {
+            assert_eq!(decode_cached(&encode_cached(max)), Ok(max));
+        }
+        assert!(decode_cached("token:x").is_err());
+        assert!(decode_cached("-1").is_err());
+    }
 }
@@ -0,0 +1,140 @@
+// Copyright (c) 2026 Steven Hooker. Exclusively licensed to and distributed by AgentSphere GmbH.
+// SPDX-License-Identifier: BUSL-1.1
+
+//! TOTP second factor (RFC 6238): HMAC-SHA1, 6 digits, 30-second steps —
+//! the defaults every authenticator app supports.
+
+use hmac::{Hmac, Mac};
+use sha1::Sha1;
+
+/// Seconds per time step.
+pub const STEP_SECS: i64 = 30;
+
+/// Secret length in bytes (RFC 4226 recommends 160 bits).
+const SECRET_LEN: usize = 20;
+
+/// Steps of clock drift accepted on either side of the current step.
+const DRIFT_STEPS: i64 = 1;
+
+/// Generate a fresh random secret.
+pub fn generate_secret() -> Vec<u8> {
+    let mut bytes = vec![0u8; SECRET_LEN];
+    rand::fill(&mut bytes[..]);
+    bytes
+}
+
+/// Base32 (unpadded) form of a secret, as typed into authenticator apps.
+pub fn encode_secret(secret: &[u8]) -> String {
+    data_encoding::BASE32_NOPAD.encode(secret)
+}
+
+/// `otpauth://` URI for QR-code enrollment.
+pub fn provisioning_uri(issuer: &str, account: &str, secret: &[u8]) -> String {
+    // Form encoding writes spaces as `+`; authenticator apps expect `%20`.
+    let enc = |s: &str| {
+        url::form_urlencoded::byte_serialize(s.as_bytes())
+            .collect::<String>()
+            .replace('+', "%20")
+    };
+    format!(
+        "otpauth://totp/{}:{}?secret={}&issuer={}&algorithm=SHA1&digits=6&period={STEP_SECS}",
+        enc(issuer),
+        enc(account),
+        encode_secret(secret),
+        enc(issuer),
+    )
+}
+
+/// Time step for a Unix timestamp.
+pub fn step_at(unix_secs: i64) -> i64 {
+    unix_secs.div_euclid(STEP_SECS)
+}
+
+/// The 6-digit code for `step` (RFC 4226 HOTP with dynamic truncation).
+pub fn code_at(secret: &[u8], step: i64) -> u32 {
+    let mut mac = Hmac::<Sha1>::new_from_slice(secret).expect("HMAC accepts any key length");
+    mac.update(&step.to_be_bytes());
+    let digest = mac.finalize().into_bytes();
+    let offset = usize::from(digest[19] & 0x0f);
+    let binary = u32::from_be_bytes([
+        digest[offset] & 0x7f,
+        digest[offset + 1],
+        digest[offset + 2],
+        digest[offset + 3],
+    ]);
+    binary % 1_000_000
+}
+
+/// Check `code` against the steps around `now_step`. Returns the matched step,
+/// which the caller stores so the same code cannot be replayed: steps at or
+/// before `last_used_step` never match.
+pub fn verify(
+    secret: &[u8],
+    code: &str,
+    now_step: i64,
+    last_used_step: Option<i64>,
+) -> Option<i64> {
+    if code.len() != 6 || !code.bytes().all(|b| b.is_ascii_digit()) {
+        return None;
+    }
+    let code: u32 = code.parse().ok()?;
+    (now_step - DRIFT_STEPS..=now_step + DRIFT_STEPS)
+        .filter(|step| last_used_step.is_none_or(|last| *step > last))
+        .find(|step| code_at(secret, *step) == code)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// RFC 6238 Appendix B SHA1 seed.
+    const RFC_SECRET: &[u8] = b"12345678901234567890";
+
+    #[test]
+    fn rfc6238_vectors() {
+        // Low 6 digits of the RFC's 8-digit codes.
+        for (time, expected) in [
+            (59, 287_082),
+            (1_111_111_109, 81_804),
+            (1_111_111_111, 50_471),
+            (1_234_567_890, 5_924),
+            (2_000_000_000, 279_037),
+        ] {
+            assert_eq!(code_at(RFC_SECRET, step_at(time)), expected, "t={time}");
+        }
+    }
+
+    #[test]
+    fn verify_accepts_adjacent_steps_only() {
+        let step = step_at(1_111_111_109);
+        let code = format!("{:06}", code_at(RFC_SECRET, step));
+        assert_eq!(verify(RFC_SECRET, &code, step, None), Some(step));
+        assert_eq!(verify(RFC_SECRET, &code, step + 1, None), Some(step));
+        assert_eq!(verify(RFC_SECRET, &code, step - 1, None), Some(step));
+        assert_eq!(verify(RFC_SECRET, &code, step + 2, None), None);
+    }
+
+    #[test]
+    fn verify_rejects_replay_and_malformed_codes() {
+        let step = step_at(59);
+        assert_eq!(verify(RFC_SECRET, "287082", step, Some(step)), None);
+        assert_eq!(verify(RFC_SECRET, "28708", step, None), None);
+        assert_eq!(verify(RFC_SECRET, "28708a", step, None), None);
+        assert_eq!(verify(RFC_SECRET, "+87082", step, None), None);
+    }
+
+    #[test]
+    fn provisioning_uri_encodes_label() {
+        let uri = provisioning_uri("Agent Sphere", "alice@example.com", RFC_SECRET);
+        assert!(uri.starts_with("otpauth://totp/Agent%20Sphere:alice%40example.com?secret="));
+        assert!(uri.contains(&format!("secret={}", encode_secret(RFC_SECRET))));
+        assert!(uri.contains("&digits=6&period=30"));
+    }
+
+    #[test]
+    fn generated_secrets_are_random() {
+        let a = generate_secret();
+        assert_eq!(a.len(), 20);
+        assert_ne!(a, generate_secret());
+    }
+}
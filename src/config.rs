@@ -1,9 +1,12 @@
 // Copyright (c) 2026 Steven Hooker. Exclusively licensed to and distributed by AgentSphere GmbH.
 // SPDX-License-Identifier: BUSL-1.1
 
+use std::collections::BTreeMap;
 use std::env;
 use std::path::PathBuf;
 
+use k8s_openapi::api::core::v1::Toleration;
+
 #[derive(Clone)]
 #[allow(clippy::struct_excessive_bools, dead_code)]
 pub struct Config {
@@ -15,6 +18,14 @@ pub struct Config {
     pub minio_secret_key: String,
     /// Accept self-signed TLS certificates for `MinIO` (dev/test only). S55.
     pub minio_insecure: bool,
+    /// Server-side encryption for objects written to `MinIO`: `AES256`
+    /// (SSE-S3) or `aws:kms` (SSE-KMS). `None` leaves objects unencrypted.
+    pub minio_sse: Option<String>,
+    /// KMS key for `aws:kms` encryption; the server default key when unset.
+    pub minio_sse_kms_key_id: Option<String>,
+    /// Days before build logs, artifacts and telemetry archives in `MinIO`
+    /// expire and are pruned (default 0 = keep forever).
+    pub object_retention_days: u32,
     pub master_key: Option<String>,
     pub git_repos_path: PathBuf,
     pub ops_repos_path: PathBuf,
@@ -39,6 +50,14 @@ pub struct Config {
     pub webauthn_rp_origin: String,
     /// `WebAuthn` Relying Party display name.
     pub webauthn_rp_name: String,
+    /// Authenticator models (AAGUIDs) accepted for users under the `hardware`
+    /// passkey policy (`PLATFORM_PASSKEY_HARDWARE_AAGUIDS`). Empty means no
+    /// authenticator qualifies.
+    pub passkey_hardware_aaguids: Vec<uuid::Uuid>,
+    /// PEM bundle of vendor attestation roots that must sign the attestation
+    /// of a `hardware` policy key (`PLATFORM_PASSKEY_ATTESTATION_CA`). Unset
+    /// means no authenticator qualifies.
+    pub passkey_attestation_ca: Option<String>,
     /// Platform API URL for agent/pipeline pods to reach the platform.
     pub platform_api_url: String,
     /// K8s namespace where the platform itself runs (for `NetworkPolicy` egress).
@@ -47,6 +66,8 @@ pub struct Config {
     pub ssh_listen: Option<String>,
     /// Path to ED25519 host key (auto-generated if absent).
     pub ssh_host_key_path: String,
+    /// OTLP/gRPC ingest listen address (e.g. "0.0.0.0:4317"). `None` disables it.
+    pub otlp_grpc_listen: Option<String>,
     /// Maximum concurrent CLI subprocess sessions per platform pod.
     pub max_cli_subprocesses: usize,
     /// Valkey host:port as seen from inside agent pods.
@@ -84,30 +105,84 @@ pub struct Config {
     /// Minimum tracing level for platform self-observability (default "warn").
     pub self_observe_level: String,
     /// Idle timeout for agent sessions in seconds (default 1800 = 30 min).
-    /// Sessions with no messages for this duration are auto-completed by the reaper.
+    /// Sessions with no activity (API calls, logs/spans, messages) for this
+    /// duration are auto-completed by the reaper.
     pub session_idle_timeout_secs: u64,
+    /// Absolute lifetime for agent sessions in seconds (default 7200 = 2h, the agent
+    /// token TTL). Running sessions older than this are reaped regardless of activity;
+    /// manager sessions are exempt. 0 disables the cap.
+    pub session_max_lifetime_secs: u64,
+    /// Interval in seconds for periodic agent workspace snapshots to `MinIO`
+    /// (default 0 = disabled; on-demand snapshots are always available).
+    pub agent_snapshot_interval_secs: u64,
+    /// Workspace snapshots kept per agent session; older ones are deleted after
+    /// each new snapshot (default 5, minimum 1).
+    pub agent_snapshot_keep: usize,
+    /// Vault server address for `vault://` secret references (e.g. `https://vault:8200`).
+    /// Subject to the same SSRF checks as webhooks outside dev mode.
+    pub vault_addr: Option<String>,
+    /// Token sent as `X-Vault-Token` when resolving `vault://` secret references.
+    pub vault_token: Option<String>,
+    /// Seconds a resolved Vault value is cached in memory (default 60, 0 disables).
+    pub vault_cache_ttl_secs: u64,
     /// External URL for preview proxy (dev only).
     /// When set, preview requests route through this proxy instead of direct K8s DNS.
     /// Example: `http://172.18.0.2:31500`
     pub preview_proxy_url: Option<String>,
     /// Maximum concurrent pipeline step pods per pipeline (default 4).
     pub pipeline_max_parallel: usize,
+    /// Maximum running pipelines per project across all executors (default 3).
+    /// Further pipelines stay `pending` in FIFO order until a slot frees up.
+    pub pipeline_max_concurrent_per_project: usize,
     /// Name of the shared Gateway resource for traffic splitting (default "platform-gateway").
     pub gateway_name: String,
     /// Namespace where the shared Gateway lives (default: same as `platform_namespace`).
     pub gateway_namespace: String,
     /// Maximum pipeline run duration in seconds (default 3600 = 1 hour).
     pub pipeline_timeout_secs: u64,
+    /// Seconds shutdown waits for in-flight pipelines before requeueing them
+    /// as `pending` (default 20).
+    pub pipeline_drain_timeout_secs: u64,
+    /// Seconds a deployment has to become available before the release is
+    /// rolled back (default 300).
+    pub deploy_health_timeout_secs: u64,
     /// Maximum allowed LFS object size in bytes (default 5 GB).
     pub max_lfs_object_bytes: u64,
     /// Maximum API token expiry in days (default 365). S71.
     pub token_max_expiry_days: u32,
-    /// Observability data retention in days (default 30). S94.
+    /// Metric rollup retention in days (default 30). S94.
     pub observe_retention_days: u32,
+    /// Days log entries stay in Postgres before Parquet archival to `MinIO` (default 7).
+    pub observe_log_retention_days: u32,
+    /// Days spans stay in Postgres before Parquet archival to `MinIO` (default 7).
+    pub observe_span_retention_days: u32,
+    /// Days metric samples stay in Postgres before Parquet archival to `MinIO` (default 7).
+    pub observe_metric_retention_days: u32,
     /// Previous master key for key rotation (S44). Optional — only during rotation.
     pub master_key_previous: Option<String>,
     /// Trusted proxy CIDRs (S59). When non-empty, X-Forwarded-For only trusted from these IPs.
     pub trust_proxy_cidrs: Vec<String>,
+    /// Minimum password length (default 8).
+    pub password_min_length: usize,
+    /// Require both upper- and lowercase letters in passwords.
+    pub password_require_mixed_case: bool,
+    /// Require at least one digit in passwords.
+    pub password_require_digit: bool,
+    /// Require at least one non-alphanumeric character in passwords.
+    pub password_require_symbol: bool,
+    /// Extra passwords to reject on top of the built-in common-password list.
+    pub password_denylist: Vec<String>,
+    /// Failed logins within `login_lockout_window_secs` that lock an account (0 disables).
+    pub login_lockout_threshold: u64,
+    /// Window in seconds over which failed logins are counted (default 900).
+    pub login_lockout_window_secs: i64,
+    /// Seconds an account stays locked after too many failed logins (default 900).
+    pub login_lockout_duration_secs: i64,
+    /// Lifetime in seconds of a self-granted break-glass delegation (default 1800).
+    pub break_glass_duration_secs: i64,
+    /// Permissions users may grant themselves via break-glass. Empty (the
+    /// default) disables break-glass until an operator lists them explicitly.
+    pub break_glass_permissions: Vec<String>,
     /// Default runner image for agent pods (A4). Pinned to avoid `:latest`.
     pub runner_image: String,
     /// Git clone init container image (A4). Pinned to avoid `:latest`.
@@ -123,6 +198,9 @@ pub struct Config {
     pub max_artifact_file_bytes: u64,
     /// Maximum total artifact size per step in bytes (default 500 MB).
     pub max_artifact_total_bytes: u64,
+    /// Total size of pipeline dependency caches before least recently used
+    /// caches are evicted, in bytes (default 10 GB).
+    pub pipeline_cache_max_bytes: u64,
     /// Maximum HTTP body size for registry blob uploads in bytes (default 2 GB).
     pub registry_http_body_limit_bytes: usize,
     /// Maximum individual registry blob size in bytes (default 5 GB).
@@ -159,6 +237,14 @@ pub struct Config {
     pub db_max_connections: u32,
     /// `PostgreSQL` connection acquire timeout in seconds (default 10).
     pub db_acquire_timeout_secs: u64,
+    /// `PostgreSQL` connections kept open even when idle (default 0, capped at the max).
+    pub db_min_connections: u32,
+    /// Seconds before an idle `PostgreSQL` connection above the minimum is
+    /// closed (default 300; 0 = never).
+    pub db_idle_timeout_secs: u64,
+    /// Statements slower than this many milliseconds are logged at WARN
+    /// (default 1000; 0 = off).
+    pub db_slow_query_ms: u64,
     /// Maximum Valkey connections (default 6).
     pub valkey_pool_size: usize,
     /// Git smart HTTP operation timeout in seconds (default 600 = 10 min).
@@ -167,10 +253,93 @@ pub struct Config {
     pub request_timeout_secs: u64,
     /// Maximum concurrent webhook deliveries (default 50).
     pub webhook_max_concurrent: usize,
+    /// Maximum concurrent webhook deliveries for a single project (default 10).
+    /// Further deliveries queue until a slot frees up.
+    pub webhook_max_concurrent_per_project: usize,
+    /// Webhook destinations allowed for outbound delivery: hostnames,
+    /// `*.domain` wildcards, or CIDR networks. Empty allows any public host.
+    pub webhook_allowlist: Vec<String>,
     /// Maximum running manager sessions per user (default 10).
     pub manager_session_max_per_user: i64,
+    /// Maximum active (pending or running) agent sessions per user (default 5).
+    /// 0 disables the cap.
+    pub agent_session_max_per_user: i64,
+    /// Maximum active agent sessions per project (default 20). 0 disables the cap.
+    pub agent_session_max_per_project: i64,
     /// Observe ingest buffer capacity per signal type (default 10,000).
     pub observe_buffer_capacity: usize,
+    /// Maximum metric series per project (default 10,000). Samples that would
+    /// create a series beyond the cap are dropped. 0 disables the cap.
+    pub observe_max_series_per_project: u64,
+    /// Requests per minute per API token / session across the API (default 600).
+    /// 0 disables the limit.
+    pub api_rate_limit_per_minute: u64,
+    /// Per route group overrides of `api_rate_limit_per_minute`, as
+    /// `(path prefix, requests per minute)`. The longest matching prefix wins.
+    /// Admin-set token and project limits (the `rate_limit_overrides` table)
+    /// take precedence over these; see `auth::rate_limit::api_rate_limit`.
+    pub api_rate_limit_overrides: Vec<(String, u64)>,
+    /// Resource types whose reads are audited (`PLATFORM_AUDIT_READS`, e.g.
+    /// `secrets,audit_log`; see `api::helpers::ReadAudit`). Empty by default.
+    pub audit_reads: Vec<String>,
+    /// Request body limit for API routes without a more specific group
+    /// (`PLATFORM_API_BODY_LIMIT`, default 10 MB).
+    pub api_body_limit_bytes: usize,
+    /// Request body limit for issue and merge request routes: bodies, comments
+    /// and reviews (`PLATFORM_CONTENT_BODY_LIMIT`, default 1 MB).
+    pub content_body_limit_bytes: usize,
+    /// Request body limit for file uploads such as release assets
+    /// (`PLATFORM_UPLOAD_BODY_LIMIT`, default 500 MB).
+    pub upload_body_limit_bytes: usize,
+    /// Node selector applied to pipeline and agent pods (`PLATFORM_POD_NODE_SELECTOR`).
+    pub pod_node_selector: BTreeMap<String, String>,
+    /// Tolerations applied to pipeline and agent pods (`PLATFORM_POD_TOLERATIONS`).
+    pub pod_tolerations: Vec<Toleration>,
+    /// OIDC provider issuer URL (`PLATFORM_OIDC_ISSUER`). `None` disables
+    /// single sign-on.
+    pub oidc_issuer: Option<String>,
+    pub oidc_client_id: String,
+    pub oidc_client_secret: Option<String>,
+    /// Callback registered with the provider. Defaults to
+    /// `{webauthn_rp_origin}/api/auth/oidc/callback`.
+    pub oidc_redirect_url: String,
+    /// Space-separated scopes requested at login (default `openid email profile`).
+    pub oidc_scopes: String,
+    /// ID token claim holding the user's groups (default `groups`).
+    pub oidc_groups_claim: String,
+    /// Global roles granted for identity provider groups, as `(group, role)` pairs
+    /// (`PLATFORM_OIDC_ROLE_MAP=platform-admins=admin,eng=developer`).
+    pub oidc_role_map: Vec<(String, String)>,
+}
+
+/// Parse `PLATFORM_API_RATE_LIMIT_OVERRIDES`: comma-separated `prefix=limit`
+/// pairs, e.g. `/api/sessions=60,/api/projects=300`. Malformed entries are skipped.
+fn parse_rate_limit_overrides(s: &str) -> Vec<(String, u64)> {
+    s.split(',')
+        .filter_map(|entry| {
+            let (prefix, limit) = entry.trim().split_once('=')?;
+            let prefix = prefix.trim();
+            if !prefix.starts_with('/') {
+                return None;
+            }
+            Some((prefix.to_owned(), limit.trim().parse().ok()?))
+        })
+        .collect()
+}
+
+/// Parse `PLATFORM_OIDC_ROLE_MAP`: comma-separated `group=role` pairs.
+/// Malformed entries are skipped.
+fn parse_oidc_role_map(s: &str) -> Vec<(String, String)> {
+    s.split(',')
+        .filter_map(|entry| {
+            let (group, role) = entry.trim().split_once('=')?;
+            let (group, role) = (group.trim(), role.trim());
+            if group.is_empty() || role.is_empty() {
+                return None;
+            }
+            Some((group.to_owned(), role.to_owned()))
+        })
+        .collect()
 }
 
 fn parse_cors_origins(s: &str) -> Vec<String> {
@@ -212,6 +381,11 @@ impl std::fmt::Debug for Config {
             .field("runner_image", &self.runner_image)
             .field("git_clone_image", &self.git_clone_image)
             .field("kaniko_image", &self.kaniko_image)
+            .field("oidc_issuer", &self.oidc_issuer)
+            .field(
+                "oidc_client_secret",
+                &self.oidc_client_secret.as_ref().map(|_| "[REDACTED]"),
+            )
             .finish_non_exhaustive()
     }
 }
@@ -222,6 +396,8 @@ impl Config {
         let valkey_url = env::var("VALKEY_URL").unwrap_or_else(|_| "redis://localhost:6379".into());
         let valkey_agent_host = env::var("PLATFORM_VALKEY_AGENT_HOST")
             .unwrap_or_else(|_| derive_valkey_host_port(&valkey_url));
+        let webauthn_rp_origin =
+            env::var("WEBAUTHN_RP_ORIGIN").unwrap_or_else(|_| "http://localhost:8080".into());
         Self {
             listen: env::var("PLATFORM_LISTEN").unwrap_or_else(|_| "0.0.0.0:8080".into()),
             database_url: env::var("DATABASE_URL")
@@ -232,6 +408,14 @@ impl Config {
             minio_access_key: env::var("MINIO_ACCESS_KEY").unwrap_or_else(|_| "platform".into()),
             minio_secret_key: env::var("MINIO_SECRET_KEY").unwrap_or_else(|_| "devdevdev".into()),
             minio_insecure: env::var("MINIO_INSECURE").ok().is_some_and(|v| v == "true"),
+            minio_sse: env::var("MINIO_SSE").ok().filter(|v| !v.is_empty()),
+            minio_sse_kms_key_id: env::var("MINIO_SSE_KMS_KEY_ID")
+                .ok()
+                .filter(|v| !v.is_empty()),
+            object_retention_days: env::var("PLATFORM_OBJECT_RETENTION_DAYS")
+                .ok()
+                .and_then(|v| v.parse().ok())
+                .unwrap_or(0),
             master_key: env::var("PLATFORM_MASTER_KEY").ok(),
             git_repos_path: env::var("PLATFORM_GIT_REPOS_PATH")
                 .map_or_else(|_| PathBuf::from("/data/repos"), PathBuf::from),
@@ -267,9 +451,12 @@ impl Config {
                 .and_then(|v| v.parse().ok())
                 .unwrap_or(300),
             webauthn_rp_id: env::var("WEBAUTHN_RP_ID").unwrap_or_else(|_| "localhost".into()),
-            webauthn_rp_origin: env::var("WEBAUTHN_RP_ORIGIN")
-                .unwrap_or_else(|_| "http://localhost:8080".into()),
+            webauthn_rp_origin: webauthn_rp_origin.clone(),
             webauthn_rp_name: env::var("WEBAUTHN_RP_NAME").unwrap_or_else(|_| "Platform".into()),
+            passkey_hardware_aaguids: env::var("PLATFORM_PASSKEY_HARDWARE_AAGUIDS")
+                .map(|v| v.split(',').filter_map(|s| s.trim().parse().ok()).collect())
+                .unwrap_or_default(),
+            passkey_attestation_ca: env::var("PLATFORM_PASSKEY_ATTESTATION_CA").ok(),
             platform_api_url: env::var("PLATFORM_API_URL")
                 .unwrap_or_else(|_| "http://platform.platform.svc.cluster.local:8080".into()),
             platform_namespace: env::var("PLATFORM_NAMESPACE")
@@ -277,6 +464,7 @@ impl Config {
             ssh_listen: env::var("PLATFORM_SSH_LISTEN").ok(),
             ssh_host_key_path: env::var("PLATFORM_SSH_HOST_KEY_PATH")
                 .unwrap_or_else(|_| "/data/ssh_host_ed25519_key".into()),
+            otlp_grpc_listen: env::var("PLATFORM_OTLP_GRPC_LISTEN").ok(),
             max_cli_subprocesses: env::var("PLATFORM_MAX_CLI_SUBPROCESSES")
                 .ok()
                 .and_then(|v| v.parse().ok())
@@ -308,11 +496,44 @@ impl Config {
                 .ok()
                 .and_then(|v| v.parse().ok())
                 .unwrap_or(1800),
+            break_glass_permissions: env::var("PLATFORM_BREAK_GLASS_PERMISSIONS")
+                .map(|v| {
+                    v.split(',')
+                        .map(|s| s.trim().to_owned())
+                        .filter(|s| !s.is_empty())
+                        .collect()
+                })
+                .unwrap_or_default(),
+            session_max_lifetime_secs: env::var("PLATFORM_SESSION_MAX_LIFETIME")
+                .ok()
+                .and_then(|v| v.parse().ok())
+                .unwrap_or(7200),
+            agent_snapshot_interval_secs: env::var("PLATFORM_AGENT_SNAPSHOT_INTERVAL")
+                .ok()
+                .and_then(|v| v.parse().ok())
+                .unwrap_or(0),
+            agent_snapshot_keep: env::var("PLATFORM_AGENT_SNAPSHOT_KEEP")
+                .ok()
+                .and_then(|v| v.parse().ok())
+                .unwrap_or(5),
+            vault_addr: env::var("PLATFORM_VAULT_ADDR").ok(),
+            vault_token: env::var("PLATFORM_VAULT_TOKEN").ok(),
+            vault_cache_ttl_secs: env::var("PLATFORM_VAULT_CACHE_TTL")
+                .ok()
+                .and_then(|v| v.parse().ok())
+                .unwrap_or(60),
             preview_proxy_url: env::var("PLATFORM_PREVIEW_PROXY_URL").ok(),
             pipeline_max_parallel: env::var("PLATFORM_PIPELINE_MAX_PARALLEL")
                 .ok()
                 .and_then(|v| v.parse().ok())
                 .unwrap_or(4),
+            pipeline_max_concurrent_per_project: env::var(
+                "PLATFORM_PIPELINE_MAX_CONCURRENT_PER_PROJECT",
+            )
+            .ok()
+            .and_then(|v| v.parse().ok())
+            .filter(|&n: &usize| n > 0)
+            .unwrap_or(3),
             gateway_name: env::var("PLATFORM_GATEWAY_NAME")
                 .unwrap_or_else(|_| "platform-gateway".into()),
             gateway_namespace: env::var("PLATFORM_GATEWAY_NAMESPACE").unwrap_or_else(|_| {
@@ -322,6 +543,15 @@ impl Config {
                 .ok()
                 .and_then(|v| v.parse().ok())
                 .unwrap_or(3600),
+            pipeline_drain_timeout_secs: env::var("PLATFORM_PIPELINE_DRAIN_TIMEOUT")
+                .ok()
+                .and_then(|v| v.parse().ok())
+                .unwrap_or(20),
+            deploy_health_timeout_secs: env::var("PLATFORM_DEPLOY_HEALTH_TIMEOUT")
+                .ok()
+                .and_then(|v| v.parse().ok())
+                .filter(|&v| v > 0)
+                .unwrap_or(300),
             max_lfs_object_bytes: env::var("PLATFORM_MAX_LFS_OBJECT_BYTES")
                 .ok()
                 .and_then(|v| v.parse().ok())
@@ -334,11 +564,64 @@ impl Config {
                 .ok()
                 .and_then(|v| v.parse().ok())
                 .unwrap_or(30),
+            observe_log_retention_days: env::var("PLATFORM_OBSERVE_LOG_RETENTION_DAYS")
+                .ok()
+                .and_then(|v| v.parse().ok())
+                .filter(|&d: &u32| d > 0)
+                .unwrap_or(7),
+            observe_span_retention_days: env::var("PLATFORM_OBSERVE_SPAN_RETENTION_DAYS")
+                .ok()
+                .and_then(|v| v.parse().ok())
+                .filter(|&d: &u32| d > 0)
+                .unwrap_or(7),
+            observe_metric_retention_days: env::var("PLATFORM_OBSERVE_METRIC_RETENTION_DAYS")
+                .ok()
+                .and_then(|v| v.parse().ok())
+                .filter(|&d: &u32| d > 0)
+                .unwrap_or(7),
             master_key_previous: env::var("PLATFORM_MASTER_KEY_PREVIOUS").ok(),
             trust_proxy_cidrs: env::var("PLATFORM_TRUST_PROXY_CIDR")
                 .ok()
                 .map(|v| v.split(',').map(|s| s.trim().to_owned()).collect())
                 .unwrap_or_default(),
+            password_min_length: env::var("PLATFORM_PASSWORD_MIN_LENGTH")
+                .ok()
+                .and_then(|v| v.parse().ok())
+                .unwrap_or(8),
+            password_require_mixed_case: env::var("PLATFORM_PASSWORD_REQUIRE_MIXED_CASE")
+                .ok()
+                .is_some_and(|v| v == "true"),
+            password_require_digit: env::var("PLATFORM_PASSWORD_REQUIRE_DIGIT")
+                .ok()
+                .is_some_and(|v| v == "true"),
+            password_require_symbol: env::var("PLATFORM_PASSWORD_REQUIRE_SYMBOL")
+                .ok()
+                .is_some_and(|v| v == "true"),
+            password_denylist: env::var("PLATFORM_PASSWORD_DENYLIST")
+                .ok()
+                .map(|v| {
+                    v.split(',')
+                        .map(|s| s.trim().to_owned())
+                        .filter(|s| !s.is_empty())
+                        .collect()
+                })
+                .unwrap_or_default(),
+            login_lockout_threshold: env::var("PLATFORM_LOGIN_LOCKOUT_THRESHOLD")
+                .ok()
+                .and_then(|v| v.parse().ok())
+                .unwrap_or(5),
+            login_lockout_window_secs: env::var("PLATFORM_LOGIN_LOCKOUT_WINDOW")
+                .ok()
+                .and_then(|v| v.parse().ok())
+                .unwrap_or(900),
+            login_lockout_duration_secs: env::var("PLATFORM_LOGIN_LOCKOUT_DURATION")
+                .ok()
+                .and_then(|v| v.parse().ok())
+                .unwrap_or(900),
+            break_glass_duration_secs: env::var("PLATFORM_BREAK_GLASS_DURATION")
+                .ok()
+                .and_then(|v| v.parse().ok())
+                .unwrap_or(1800),
             runner_image: env::var("PLATFORM_RUNNER_IMAGE")
                 .unwrap_or_else(|_| "platform-runner:v1".into()),
             git_clone_image: env::var("PLATFORM_GIT_CLONE_IMAGE")
@@ -369,6 +652,10 @@ impl Config {
                 .ok()
                 .and_then(|v| v.parse().ok())
                 .unwrap_or(500 * 1024 * 1024), // 500 MB
+            pipeline_cache_max_bytes: env::var("PLATFORM_PIPELINE_CACHE_MAX_BYTES")
+                .ok()
+                .and_then(|v| v.parse().ok())
+                .unwrap_or(10 * 1024 * 1024 * 1024), // 10 GB
             registry_http_body_limit_bytes: env::var("PLATFORM_REGISTRY_HTTP_BODY_LIMIT")
                 .ok()
                 .and_then(|v| v.parse().ok())
@@ -434,6 +721,18 @@ impl Config {
                 .ok()
                 .and_then(|v| v.parse().ok())
                 .unwrap_or(10),
+            db_min_connections: env::var("PLATFORM_DB_MIN_CONNECTIONS")
+                .ok()
+                .and_then(|v| v.parse().ok())
+                .unwrap_or(0),
+            db_idle_timeout_secs: env::var("PLATFORM_DB_IDLE_TIMEOUT")
+                .ok()
+                .and_then(|v| v.parse().ok())
+                .unwrap_or(300),
+            db_slow_query_ms: env::var("PLATFORM_DB_SLOW_QUERY_MS")
+                .ok()
+                .and_then(|v| v.parse().ok())
+                .unwrap_or(1000),
             valkey_pool_size: env::var("PLATFORM_VALKEY_POOL_SIZE")
                 .ok()
                 .and_then(|v| v.parse().ok())
@@ -450,14 +749,95 @@ impl Config {
                 .ok()
                 .and_then(|v| v.parse().ok())
                 .unwrap_or(50),
+            webhook_max_concurrent_per_project: env::var(
+                "PLATFORM_WEBHOOK_MAX_CONCURRENT_PER_PROJECT",
+            )
+            .ok()
+            .and_then(|v| v.parse().ok())
+            .filter(|&n: &usize| n > 0)
+            .unwrap_or(10),
+            webhook_allowlist: env::var("PLATFORM_WEBHOOK_ALLOWLIST").map_or_else(
+                |_| Vec::new(),
+                |v| {
+                    v.split(',')
+                        .map(|s| s.trim().to_owned())
+                        .filter(|s| !s.is_empty())
+                        .collect()
+                },
+            ),
             manager_session_max_per_user: env::var("PLATFORM_MANAGER_SESSION_MAX")
                 .ok()
                 .and_then(|v| v.parse().ok())
                 .unwrap_or(10),
+            agent_session_max_per_user: env::var("PLATFORM_AGENT_SESSION_MAX_PER_USER")
+                .ok()
+                .and_then(|v| v.parse().ok())
+                .unwrap_or(5),
+            agent_session_max_per_project: env::var("PLATFORM_AGENT_SESSION_MAX_PER_PROJECT")
+                .ok()
+                .and_then(|v| v.parse().ok())
+                .unwrap_or(20),
             observe_buffer_capacity: env::var("PLATFORM_OBSERVE_BUFFER_CAPACITY")
                 .ok()
                 .and_then(|v| v.parse().ok())
                 .unwrap_or(10_000),
+            observe_max_series_per_project: env::var("PLATFORM_OBSERVE_MAX_SERIES_PER_PROJECT")
+                .ok()
+                .and_then(|v| v.parse().ok())
+                .unwrap_or(10_000),
+            api_rate_limit_per_minute: env::var("PLATFORM_API_RATE_LIMIT")
+                .ok()
+                .and_then(|v| v.parse().ok())
+                .unwrap_or(600),
+            api_rate_limit_overrides: env::var("PLATFORM_API_RATE_LIMIT_OVERRIDES")
+                .map(|v| parse_rate_limit_overrides(&v))
+                .unwrap_or_default(),
+            audit_reads: env::var("PLATFORM_AUDIT_READS")
+                .map(|v| {
+                    v.split(',')
+                        .map(|s| s.trim().to_ascii_lowercase())
+                        .filter(|s| !s.is_empty())
+                        .collect()
+                })
+                .unwrap_or_default(),
+            api_body_limit_bytes: env::var("PLATFORM_API_BODY_LIMIT")
+                .ok()
+                .and_then(|v| v.parse().ok())
+                .unwrap_or(10 * 1024 * 1024), // 10 MB
+            content_body_limit_bytes: env::var("PLATFORM_CONTENT_BODY_LIMIT")
+                .ok()
+                .and_then(|v| v.parse().ok())
+                .unwrap_or(1024 * 1024), // 1 MB
+            upload_body_limit_bytes: env::var("PLATFORM_UPLOAD_BODY_LIMIT")
+                .ok()
+                .and_then(|v| v.parse().ok())
+                .unwrap_or(500 * 1024 * 1024), // 500 MB
+            pod_node_selector: env::var("PLATFORM_POD_NODE_SELECTOR")
+                .map(|v| crate::deployer::scheduling::parse_node_selector(&v))
+                .unwrap_or_default(),
+            pod_tolerations: env::var("PLATFORM_POD_TOLERATIONS")
+                .map(|v| crate::deployer::scheduling::parse_tolerations(&v))
+                .unwrap_or_default(),
+            oidc_issuer: env::var("PLATFORM_OIDC_ISSUER")
+                .ok()
+                .filter(|v| !v.is_empty()),
+            oidc_client_id: env::var("PLATFORM_OIDC_CLIENT_ID").unwrap_or_default(),
+            oidc_client_secret: env::var("PLATFORM_OIDC_CLIENT_SECRET")
+                .ok()
+                .filter(|v| !v.is_empty()),
+            oidc_redirect_url: env::var("PLATFORM_OIDC_REDIRECT_URL").unwrap_or_else(|_| {
+                format!(
+                    "{}/api/auth/oidc/callback",
+                    webauthn_rp_origin.trim_end_matches('/')
+                )
+            }),
+            oidc_scopes: env::var("PLATFORM_OIDC_SCOPES")
+                .unwrap_or_else(|_| "openid email profile".into()),
+            oidc_groups_claim: env::var("PLATFORM_OIDC_GROUPS_CLAIM")
+                .unwrap_or_else(|_| "groups".into()),
+            oidc_role_map: env::var("PLATFORM_OIDC_ROLE_MAP")
+                .map(|v| parse_oidc_role_map(&v))
+                .unwrap_or_default(),
         }
     }
 
@@ -509,9 +889,80 @@ impl Config {
             errors.push(format!("PLATFORM_MASTER_KEY is invalid: {e}"));
         }
 
+        self.validate_minio_sse(&mut errors);
+        self.validate_vault(&mut warnings, &mut errors);
+        self.validate_oidc(&mut errors);
+        if let Err(e) = crate::validation::EgressAllowlist::parse(&self.webhook_allowlist) {
+            errors.push(format!("PLATFORM_WEBHOOK_ALLOWLIST is invalid: {e}"));
+        }
+        errors.extend(crate::deployer::scheduling::validate(self));
+
         (warnings, errors)
     }
 
+    /// `MINIO_SSE` must name an encryption mode opendal can request.
+    fn validate_minio_sse(&self, errors: &mut Vec<String>) {
+        match self.minio_sse.as_deref() {
+            None | Some("AES256") => {
+                if self.minio_sse_kms_key_id.is_some() {
+                    errors.push("MINIO_SSE_KMS_KEY_ID requires MINIO_SSE=aws:kms.".into());
+                }
+            }
+            Some("aws:kms") => {}
+            Some(other) => errors.push(format!(
+                "MINIO_SSE is invalid: '{other}' (expected AES256 or aws:kms)"
+            )),
+        }
+    }
+
+    /// `PLATFORM_VAULT_ADDR` gets the same SSRF checks as webhook URLs.
+    fn validate_vault(&self, warnings: &mut Vec<String>, errors: &mut Vec<String>) {
+        if let Some(ref addr) = self.vault_addr {
+            if !self.dev_mode
+                && let Err(e) = crate::validation::check_ssrf_url(addr, &["http", "https"])
+            {
+                errors.push(format!("PLATFORM_VAULT_ADDR is invalid: {e}"));
+            }
+            if self.vault_token.is_none() {
+                warnings.push(
+                    "PLATFORM_VAULT_ADDR is set without PLATFORM_VAULT_TOKEN — \
+                     vault:// secrets will fail to resolve."
+                        .into(),
+                );
+            }
+        }
+    }
+
+    /// Single sign-on needs a client id and absolute issuer and callback URLs.
+    fn validate_oidc(&self, errors: &mut Vec<String>) {
+        let Some(ref issuer) = self.oidc_issuer else {
+            return;
+        };
+        if url::Url::parse(issuer).is_err() {
+            errors.push(format!("PLATFORM_OIDC_ISSUER is not a valid URL: {issuer}"));
+        }
+        if self.oidc_client_id.is_empty() {
+            errors.push("PLATFORM_OIDC_ISSUER is set without PLATFORM_OIDC_CLIENT_ID.".into());
+        }
+        if url::Url::parse(&self.oidc_redirect_url).is_err() {
+            errors.push(format!(
+                "PLATFORM_OIDC_REDIRECT_URL is not a valid URL: {}",
+                self.oidc_redirect_url
+            ));
+        }
+    }
+
+    /// Password rules from `PLATFORM_PASSWORD_*`.
+    pub fn password_policy(&self) -> crate::validation::PasswordPolicy {
+        crate::validation::PasswordPolicy {
+            min_length: self.password_min_length,
+            require_mixed_case: self.password_require_mixed_case,
+            require_digit: self.password_require_digit,
+            require_symbol: self.password_require_symbol,
+            denylist: self.password_denylist.clone(),
+        }
+    }
+
     /// Derive a project's K8s namespace: `{ns_prefix}-{slug}-{env}` or `{slug}-{env}`.
     pub fn project_namespace(&self, slug: &str, env: &str) -> String {
         match &self.ns_prefix {
@@ -537,6 +988,7 @@ fn derive_valkey_host_port(url: &str) -> String {
 impl Config {
     /// Sensible defaults for unit tests. Override specific fields with struct update syntax:
     /// `Config { smtp_host: Some("localhost".into()), ..Config::test_default() }`
+    #[allow(clippy::too_many_lines)]
     pub fn test_default() -> Self {
         Self {
             listen: "127.0.0.1:0".into(),
@@ -546,6 +998,9 @@ impl Config {
             minio_access_key: "test".into(),
             minio_secret_key: "test".into(),
             minio_insecure: false,
+            minio_sse: None,
+            minio_sse_kms_key_id: None,
+            object_retention_days: 0,
             master_key: None,
             git_repos_path: "/tmp/repos".into(),
             ops_repos_path: "/tmp/ops-repos".into(),
@@ -566,10 +1021,13 @@ impl Config {
             webauthn_rp_id: "localhost".into(),
             webauthn_rp_origin: "http://localhost:8080".into(),
             webauthn_rp_name: "Test Platform".into(),
+            passkey_hardware_aaguids: Vec::new(),
+            passkey_attestation_ca: None,
             platform_api_url: "http://platform.test-agents.svc.cluster.local:8080".into(),
             platform_namespace: "test-platform".into(),
             ssh_listen: None,
             ssh_host_key_path: "/tmp/test_ssh_host_key".into(),
+            otlp_grpc_listen: None,
             max_cli_subprocesses: 10,
             valkey_agent_host: "localhost:6379".into(),
             agent_runner_dir: "/tmp/test-agent-runner".into(),
@@ -584,16 +1042,38 @@ impl Config {
             health_check_interval_secs: 15,
             self_observe_level: "warn".into(),
             session_idle_timeout_secs: 1800,
+            session_max_lifetime_secs: 7200,
+            agent_snapshot_interval_secs: 0,
+            agent_snapshot_keep: 5,
+            vault_addr: None,
+            vault_token: None,
+            vault_cache_ttl_secs: 60,
             preview_proxy_url: None,
             pipeline_max_parallel: 4,
+            pipeline_max_concurrent_per_project: 3,
             gateway_name: "platform-gateway".into(),
             gateway_namespace: "test-platform".into(),
             pipeline_timeout_secs: 3600,
+            pipeline_drain_timeout_secs: 20,
+            deploy_health_timeout_secs: 300,
             max_lfs_object_bytes: 5_368_709_120,
             token_max_expiry_days: 365,
             observe_retention_days: 30,
+            observe_log_retention_days: 7,
+            observe_span_retention_days: 7,
+            observe_metric_retention_days: 7,
             master_key_previous: None,
             trust_proxy_cidrs: vec![],
+            password_min_length: 8,
+            password_require_mixed_case: false,
+            password_require_digit: false,
+            password_require_symbol: false,
+            password_denylist: vec![],
+            login_lockout_threshold: 5,
+            login_lockout_window_secs: 900,
+            login_lockout_duration_secs: 900,
+            break_glass_duration_secs: 1800,
+            break_glass_permissions: vec!["deploy:promote".into()],
             runner_image: "platform-runner:v1".into(),
             git_clone_image: "alpine/git:2.47.2".into(),
             kaniko_image: "gcr.io/kaniko-project/executor:v1.23.2-debug".into(),
@@ -601,6 +1081,7 @@ impl Config {
             mcp_servers_path: "mcp/servers".into(),
             max_artifact_file_bytes: 50 * 1024 * 1024,
             max_artifact_total_bytes: 500 * 1024 * 1024,
+            pipeline_cache_max_bytes: 10 * 1024 * 1024 * 1024,
             registry_http_body_limit_bytes: 2 * 1024 * 1024 * 1024, // 2 GB
             registry_max_blob_size_bytes: 5_368_709_120,            // 5 GB
             mesh_enabled: false,
@@ -619,12 +1100,35 @@ impl Config {
             acme_contact_email: None,
             db_max_connections: 20,
             db_acquire_timeout_secs: 10,
+            db_min_connections: 0,
+            db_idle_timeout_secs: 300,
+            db_slow_query_ms: 1000,
             valkey_pool_size: 6,
             git_http_timeout_secs: 600,
             request_timeout_secs: 300,
             webhook_max_concurrent: 50,
+            webhook_max_concurrent_per_project: 10,
+            webhook_allowlist: Vec::new(),
             manager_session_max_per_user: 10,
+            agent_session_max_per_user: 5,
+            agent_session_max_per_project: 20,
             observe_buffer_capacity: 10_000,
+            observe_max_series_per_project: 10_000,
+            api_rate_limit_per_minute: 0,
+            api_rate_limit_overrides: Vec::new(),
+            audit_reads: Vec::new(),
+            api_body_limit_bytes: 10 * 1024 * 1024,
+            content_body_limit_bytes: 1024 * 1024,
+            upload_body_limit_bytes: 500 * 1024 * 1024,
+            pod_node_selector: BTreeMap::new(),
+            pod_tolerations: Vec::new(),
+            oidc_issuer: None,
+            oidc_client_id: String::new(),
+            oidc_client_secret: None,
+            oidc_redirect_url: "http://localhost:8080/api/auth/oidc/callback".into(),
+            oidc_scopes: "openid email profile".into(),
+            oidc_groups_claim: "groups".into(),
+            oidc_role_map: Vec::new(),
         }
     }
 }
@@ -651,6 +1155,65 @@ mod tests {
         assert!(result.is_empty());
     }
 
+    #[test]
+    fn parse_rate_limit_overrides_pairs() {
+        assert_eq!(
+            parse_rate_limit_overrides("/api/sessions=60, /api/projects = 300"),
+            vec![
+                ("/api/sessions".to_owned(), 60),
+                ("/api/projects".to_owned(), 300)
+            ]
+        );
+    }
+
+    #[test]
+    fn parse_rate_limit_overrides_skips_malformed() {
+        assert!(parse_rate_limit_overrides("").is_empty());
+        assert_eq!(
+            parse_rate_limit_overrides("api/x=1,/api/y=abc,/api/z,/api/ok=5"),
+            vec![("/api/ok".to_owned(), 5)]
+        );
+    }
+
+    #[test]
+    fn parse_oidc_role_map_pairs() {
+        assert_eq!(
+            parse_oidc_role_map(" platform-admins = admin ,eng=developer,broken,=x"),
+            vec![
+                ("platform-admins".to_owned(), "admin".to_owned()),
+                ("eng".to_owned(), "developer".to_owned()),
+            ]
+        );
+    }
+
+    #[test]
+    fn validate_rejects_invalid_webhook_allowlist() {
+        let config = Config {
+            webhook_allowlist: vec!["hooks.example.com".into(), "https://*".into()],
+            ..Config::test_default()
+        };
+        let (_, errors) = config.validate();
+        assert!(
+            errors
+                .iter()
+                .any(|e| e.contains("PLATFORM_WEBHOOK_ALLOWLIST")),
+            "a malformed allowlist entry should abort startup"
+        );
+    }
+
+    #[test]
+    fn validate_oidc_requires_client_id() {
+        let config = Config {
+            oidc_issuer: Some("https://idp.example.com".into()),
+            ..Config::test_default()
+        };
+        let (_, errors) = config.validate();
+        assert!(
+            errors.iter().any(|e| e.contains("PLATFORM_OIDC_CLIENT_ID")),
+            "issuer without a client id should abort startup"
+        );
+    }
+
     #[test]
     fn test_default_smtp_port() {
         let config = Config::test_default();
@@ -768,6 +1331,10 @@ mod tests {
         assert!(!config.webauthn_rp_id.is_empty());
         assert!(!config.webauthn_rp_origin.is_empty());
         assert!(!config.webauthn_rp_name.is_empty());
+        // Break-glass is off unless PLATFORM_BREAK_GLASS_PERMISSIONS lists permissions
+        if std::env::var("PLATFORM_BREAK_GLASS_PERMISSIONS").is_err() {
+            assert!(config.break_glass_permissions.is_empty());
+        }
     }
 
     #[test]
@@ -1153,4 +1720,59 @@ mod tests {
             "should reject invalid master key format"
         );
     }
+
+    #[test]
+    fn validate_prod_rejects_private_vault_addr() {
+        let config = Config {
+            dev_mode: false,
+            master_key: Some("a".repeat(64)),
+            minio_access_key: "real".into(),
+            minio_secret_key: "real".into(),
+            vault_addr: Some("http://10.0.0.5:8200".into()),
+            vault_token: Some("s.token".into()),
+            ..Config::test_default()
+        };
+        let (_, errors) = config.validate();
+        assert!(
+            errors.iter().any(|e| e.contains("PLATFORM_VAULT_ADDR")),
+            "should apply SSRF checks to the Vault address"
+        );
+    }
+
+    #[test]
+    fn validate_minio_sse() {
+        for (sse, kms_key, ok) in [
+            (None, None, true),
+            (Some("AES256"), None, true),
+            (Some("aws:kms"), None, true),
+            (Some("aws:kms"), Some("key-1"), true),
+            (Some("aes256"), None, false),
+            (None, Some("key-1"), false),
+            (Some("AES256"), Some("key-1"), false),
+        ] {
+            let config = Config {
+                minio_sse: sse.map(Into::into),
+                minio_sse_kms_key_id: kms_key.map(Into::into),
+                ..Config::test_default()
+            };
+            let (_, errors) = config.validate();
+            let sse_error = errors.iter().any(|e| e.contains("MINIO_SSE"));
+            assert_eq!(!sse_error, ok, "{sse:?} / {kms_key:?}: {errors:?}");
+        }
+    }
+
+    #[test]
+    fn validate_rejects_invalid_pod_scheduling() {
+        let config = Config {
+            pod_node_selector: crate::deployer::scheduling::parse_node_selector("pool:ci"),
+            ..Config::test_default()
+        };
+        let (_, errors) = config.validate();
+        assert!(
+            errors
+                .iter()
+                .any(|e| e.contains("PLATFORM_POD_NODE_SELECTOR")),
+            "malformed node selector should abort startup"
+        );
+    }
 }
@@ -70,39 +70,9 @@ pub async fn apply_with_tracking(
     let mut applied = Vec::new();
 
     for doc_str in &docs {
-        let mut doc: serde_json::Value = serde_yaml::from_str(doc_str)
-            .map_err(|e| DeployerError::InvalidManifest(e.to_string()))?;
-
-        // Skip non-manifest YAML docs (e.g. variables files without apiVersion/kind)
-        if doc.get("apiVersion").and_then(|v| v.as_str()).is_none() {
-            tracing::debug!(doc = %doc_str.chars().take(100).collect::<String>(), "skipping non-manifest YAML doc");
+        let Some((ar, obj, name)) = prepare_manifest(doc_str, deployment_id)? else {
             continue;
-        }
-
-        // Inject managed-by labels when tracking is enabled
-        if let Some(did) = deployment_id {
-            inject_managed_labels(&mut doc, did);
-        }
-
-        let (ar, obj) = api_resource_from_yaml(&doc)?;
-
-        // R2: Reject cluster-scoped resource types
-        if !ALLOWED_KINDS.contains(&ar.kind.as_str()) {
-            return Err(DeployerError::InvalidManifest(format!(
-                "resource kind '{}' is not allowed in deploy manifests",
-                ar.kind
-            )));
-        }
-
-        // R3: Reject manifests with dangerous pod specs (S19 security hardening)
-        validate_pod_spec(&doc)?;
-
-        let name = obj
-            .metadata
-            .name
-            .as_deref()
-            .ok_or_else(|| DeployerError::InvalidManifest("missing metadata.name".into()))?
-            .to_owned();
+        };
 
         // R1: Always use the deployment namespace — ignore per-resource namespace
         // to prevent cross-tenant resource injection.
@@ -133,6 +103,50 @@ pub async fn apply_with_tracking(
     Ok(applied)
 }
 
+/// Parse and vet one manifest document for apply: injects managed-by labels when
+/// `deployment_id` is set and enforces the allowed kinds and pod spec rules.
+/// Returns `None` for non-manifest documents.
+pub(super) fn prepare_manifest(
+    doc_str: &str,
+    deployment_id: Option<Uuid>,
+) -> Result<Option<(ApiResource, DynamicObject, String)>, DeployerError> {
+    let mut doc: serde_json::Value =
+        serde_yaml::from_str(doc_str).map_err(|e| DeployerError::InvalidManifest(e.to_string()))?;
+
+    // Skip non-manifest YAML docs (e.g. variables files without apiVersion/kind)
+    if doc.get("apiVersion").and_then(|v| v.as_str()).is_none() {
+        tracing::debug!(doc = %doc_str.chars().take(100).collect::<String>(), "skipping non-manifest YAML doc");
+        return Ok(None);
+    }
+
+    // Inject managed-by labels when tracking is enabled
+    if let Some(did) = deployment_id {
+        inject_managed_labels(&mut doc, did);
+    }
+
+    let (ar, obj) = api_resource_from_yaml(&doc)?;
+
+    // R2: Reject cluster-scoped resource types
+    if !ALLOWED_KINDS.contains(&ar.kind.as_str()) {
+        return Err(DeployerError::InvalidManifest(format!(
+            "resource kind '{}' is not allowed in deploy manifests",
+            ar.kind
+        )));
+    }
+
+    // R3: Reject manifests with dangerous pod specs (S19 security hardening)
+    validate_pod_spec(&doc)?;
+
+    let name = obj
+        .metadata
+        .name
+        .as_deref()
+        .ok_or_else(|| DeployerError::InvalidManifest("missing metadata.name".into()))?
+        .to_owned();
+
+    Ok(Some((ar, obj, name)))
+}
+
 /// Extract the pod spec from a workload manifest (`Deployment`, `StatefulSet`, `DaemonSet`,
 /// `Job`, `CronJob`). Returns `None` for non-workload kinds (`Service`, `ConfigMap`, etc.).
 fn extract_pod_spec(manifest: &serde_json::Value) -> Option<&serde_json::Value> {
@@ -249,17 +263,7 @@ pub async fn prune_orphans(
     let mut deleted = 0;
 
     for res in orphans {
-        let (group, version) = parse_api_version(&res.api_version);
-        let plural = kind_to_plural(&res.kind);
-
-        let ar = ApiResource {
-            group: group.to_owned(),
-            version: version.to_owned(),
-            api_version: res.api_version.clone(),
-            kind: res.kind.clone(),
-            plural,
-        };
-
+        let ar = api_resource_for(&res.api_version, &res.kind);
         let api: Api<DynamicObject> =
             Api::namespaced_with(kube_client.clone(), &res.namespace, &ar);
 
@@ -352,7 +356,7 @@ fn inject_managed_labels(doc: &mut serde_json::Value, deployment_id: Uuid) {
 }
 
 /// Check if a resource has the `platform.io/prune: disabled` annotation.
-fn has_prune_disabled(obj: &DynamicObject) -> bool {
+pub(super) fn has_prune_disabled(obj: &DynamicObject) -> bool {
     obj.metadata
         .annotations
         .as_ref()
@@ -432,6 +436,62 @@ pub async fn scale(
     Ok(())
 }
 
+/// Point every container of a Deployment running `from_image` at `to_image`.
+/// Returns the number of containers changed.
+#[tracing::instrument(skip(kube_client), fields(%namespace, %deployment_name, %to_image), err)]
+pub async fn replace_image(
+    kube_client: &kube::Client,
+    namespace: &str,
+    deployment_name: &str,
+    from_image: &str,
+    to_image: &str,
+) -> Result<usize, DeployerError> {
+    let deployments: Api<Deployment> = Api::namespaced(kube_client.clone(), namespace);
+    let deploy = deployments.get(deployment_name).await?;
+
+    let Some((patch, changed)) = image_patch(&deploy, from_image, to_image) else {
+        return Ok(0);
+    };
+    deployments
+        .patch(
+            deployment_name,
+            &PatchParams::default(),
+            &Patch::Strategic(&patch),
+        )
+        .await?;
+
+    tracing::info!(%deployment_name, changed, "deployment image replaced");
+    Ok(changed)
+}
+
+/// Strategic merge patch (containers merge by name) swapping `from_image` for
+/// `to_image`, with the number of containers it changes. `None` if no
+/// container runs `from_image`.
+fn image_patch(
+    deploy: &Deployment,
+    from_image: &str,
+    to_image: &str,
+) -> Option<(serde_json::Value, usize)> {
+    let containers: Vec<serde_json::Value> = deploy
+        .spec
+        .as_ref()
+        .and_then(|s| s.template.spec.as_ref())
+        .map(|pod| pod.containers.as_slice())
+        .unwrap_or_default()
+        .iter()
+        .filter(|c| c.image.as_deref() == Some(from_image))
+        .map(|c| serde_json::json!({ "name": c.name, "image": to_image }))
+        .collect();
+    if containers.is_empty() {
+        return None;
+    }
+    let changed = containers.len();
+    let patch = serde_json::json!({
+        "spec": { "template": { "spec": { "containers": containers } } }
+    });
+    Some((patch, changed))
+}
+
 /// Inject `envFrom: [{secretRef: {name: ...}}]` into all workload containers
 /// in the rendered YAML, so that deployed pods automatically receive env vars
 /// from a K8s Secret (e.g. OTEL config, deploy-scoped secrets).
@@ -819,16 +879,7 @@ fn api_resource_from_yaml(
         .as_str()
         .ok_or_else(|| DeployerError::InvalidManifest("missing kind".into()))?;
 
-    let (group, version) = parse_api_version(api_version);
-    let plural = kind_to_plural(kind);
-
-    let ar = ApiResource {
-        group: group.to_owned(),
-        version: version.to_owned(),
-        api_version: api_version.to_owned(),
-        kind: kind.to_owned(),
-        plural: plural.clone(),
-    };
+    let ar = api_resource_for(api_version, kind);
 
     let obj: DynamicObject = serde_json::from_value(doc.clone())
         .map_err(|e| DeployerError::InvalidManifest(e.to_string()))?;
@@ -836,6 +887,18 @@ fn api_resource_from_yaml(
     Ok((ar, obj))
 }
 
+/// Build the `ApiResource` for a namespaced kind.
+pub(super) fn api_resource_for(api_version: &str, kind: &str) -> ApiResource {
+    let (group, version) = parse_api_version(api_version);
+    ApiResource {
+        group: group.to_owned(),
+        version: version.to_owned(),
+        api_version: api_version.to_owned(),
+        kind: kind.to_owned(),
+        plural: kind_to_plural(kind),
+    }
+}
+
 /// Parse "apps/v1" → ("apps", "v1"), "v1" → ("", "v1")
 fn parse_api_version(api_version: &str) -> (&str, &str) {
     match api_version.rsplit_once('/') {
@@ -876,6 +939,38 @@ fn kind_to_plural(kind: &str) -> String {
 mod tests {
     use super::*;
 
+    fn deployment_with_images(images: &[(&str, &str)]) -> Deployment {
+        let containers: Vec<serde_json::Value> = images
+            .iter()
+            .map(|(name, image)| serde_json::json!({ "name": name, "image": image }))
+            .collect();
+        serde_json::from_value(serde_json::json!({
+            "metadata": { "name": "api" },
+            "spec": {
+                "selector": {},
+                "template": { "spec": { "containers": containers } }
+            }
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn image_patch_swaps_matching_containers() {
+        let deploy = deployment_with_images(&[("app", "shop/api:bad"), ("sidecar", "envoy:1")]);
+        let (patch, changed) = image_patch(&deploy, "shop/api:bad", "shop/api:good").unwrap();
+        assert_eq!(changed, 1);
+        assert_eq!(
+            patch["spec"]["template"]["spec"]["containers"],
+            serde_json::json!([{ "name": "app", "image": "shop/api:good" }])
+        );
+    }
+
+    #[test]
+    fn image_patch_none_when_image_absent() {
+        let deploy = deployment_with_images(&[("app", "shop/api:other")]);
+        assert!(image_patch(&deploy, "shop/api:bad", "shop/api:good").is_none());
+    }
+
     #[test]
     fn parse_core_api_version() {
         let (group, version) = parse_api_version("v1");
@@ -0,0 +1,354 @@
+// Copyright (c) 2026 Steven Hooker. Exclusively licensed to and distributed by AgentSphere GmbH.
+// SPDX-License-Identifier: BUSL-1.1
+
+//! Deploy diff: compare rendered manifests against the live cluster.
+//!
+//! Every rendered resource is server-side applied with `dryRun=All`, so the
+//! API server runs defaulting and admission exactly as for a real apply, and
+//! the result is compared with the live object. Resources the current release
+//! tracks but the new manifests no longer contain are reported as removed,
+//! because the next apply prunes them.
+
+use std::fmt::Write as _;
+
+use kube::Api;
+use kube::api::{DynamicObject, Patch, PatchParams};
+use serde::Serialize;
+use ts_rs::TS;
+use uuid::Uuid;
+
+use super::applier::{self, TrackedResource};
+use super::error::DeployerError;
+use super::renderer;
+
+/// Unchanged lines shown around each hunk.
+const CONTEXT_LINES: usize = 3;
+
+/// Largest line-matrix the LCS diff builds; bigger objects are shown as a full replace.
+const MAX_DIFF_CELLS: usize = 1_000_000;
+
+/// Metadata the API server maintains itself; never part of a meaningful diff.
+const SERVER_METADATA: &[&str] = &[
+    "managedFields",
+    "resourceVersion",
+    "uid",
+    "generation",
+    "creationTimestamp",
+];
+
+/// Annotations written by controllers rather than by the manifests.
+const SERVER_ANNOTATIONS: &[&str] = &[
+    "deployment.kubernetes.io/revision",
+    "kubectl.kubernetes.io/last-applied-configuration",
+];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, TS)]
+#[serde(rename_all = "snake_case")]
+#[ts(export)]
+pub enum ResourceChange {
+    Added,
+    Changed,
+    Removed,
+    Unchanged,
+}
+
+/// How one resource would change if the manifests were applied.
+#[derive(Debug, Serialize, TS)]
+#[ts(export)]
+pub struct ResourceDiff {
+    pub kind: String,
+    pub name: String,
+    pub change: ResourceChange,
+    /// Unified diff of the live object (`-`) against the dry-run result (`+`), as YAML.
+    /// Empty when unchanged.
+    pub diff: String,
+}
+
+/// Dry-run `manifests_yaml` into `namespace` and diff each resource against the cluster.
+///
+/// `deployment_id` is the release currently live on the target; its managed-by
+/// labels are applied to the dry run so they do not show up as changes.
+/// `previous` is that release's tracked inventory, used to report the
+/// resources the next apply would prune.
+#[tracing::instrument(skip(kube_client, manifests_yaml, previous), fields(%namespace), err)]
+pub async fn diff_manifests(
+    kube_client: &kube::Client,
+    manifests_yaml: &str,
+    namespace: &str,
+    deployment_id: Option<Uuid>,
+    previous: &[TrackedResource],
+) -> Result<Vec<ResourceDiff>, DeployerError> {
+    let mut diffs = Vec::new();
+
+    for doc_str in &renderer::split_yaml_documents(manifests_yaml) {
+        let Some((ar, obj, name)) = applier::prepare_manifest(doc_str, deployment_id)? else {
+            continue;
+        };
+        let api: Api<DynamicObject> = Api::namespaced_with(kube_client.clone(), namespace, &ar);
+
+        let live = api.get_opt(&name).await?;
+        let params = PatchParams::apply("platform-deployer").force().dry_run();
+        let desired = match api.patch(&name, &params, &Patch::Apply(&obj)).await {
+            Ok(result) => result,
+            // The namespace does not exist yet: everything is new.
+            Err(kube::Error::Api(resp)) if resp.code == 404 && live.is_none() => obj,
+            Err(e) => return Err(e.into()),
+        };
+
+        let desired_yaml = to_yaml(&desired)?;
+        let (change, diff) = match live {
+            None => (ResourceChange::Added, unified_diff("", &desired_yaml)),
+            Some(live) => {
+                let diff = unified_diff(&to_yaml(&live)?, &desired_yaml);
+                if diff.is_empty() {
+                    (ResourceChange::Unchanged, diff)
+                } else {
+                    (ResourceChange::Changed, diff)
+                }
+            }
+        };
+        diffs.push(ResourceDiff {
+            kind: ar.kind,
+            name,
+            change,
+            diff,
+        });
+    }
+
+    let rendered = applier::build_tracked_inventory(manifests_yaml, namespace);
+    for orphan in applier::find_orphans(previous, &rendered) {
+        let ar = applier::api_resource_for(&orphan.api_version, &orphan.kind);
+        let api: Api<DynamicObject> =
+            Api::namespaced_with(kube_client.clone(), &orphan.namespace, &ar);
+        // Prune skips resources that opt out, so they are not removals.
+        if let Some(live) = api.get_opt(&orphan.name).await?
+            && !applier::has_prune_disabled(&live)
+        {
+            diffs.push(ResourceDiff {
+                kind: orphan.kind,
+                name: orphan.name,
+                change: ResourceChange::Removed,
+                diff: unified_diff(&to_yaml(&live)?, ""),
+            });
+        }
+    }
+
+    Ok(diffs)
+}
+
+/// Serialize an object as YAML without server-maintained fields and status.
+fn to_yaml(obj: &DynamicObject) -> Result<String, DeployerError> {
+    let mut value =
+        serde_json::to_value(obj).map_err(|e| DeployerError::RenderFailed(e.to_string()))?;
+    strip_server_fields(&mut value);
+    serde_yaml::to_string(&value).map_err(|e| DeployerError::RenderFailed(e.to_string()))
+}
+
+fn strip_server_fields(value: &mut serde_json::Value) {
+    let Some(obj) = value.as_object_mut() else {
+        return;
+    };
+    obj.remove("status");
+    let Some(metadata) = obj.get_mut("metadata").and_then(|m| m.as_object_mut()) else {
+        return;
+    };
+    for field in SERVER_METADATA {
+        metadata.remove(*field);
+    }
+    if let Some(annotations) = metadata
+        .get_mut("annotations")
+        .and_then(|a| a.as_object_mut())
+    {
+        for key in SERVER_ANNOTATIONS {
+            annotations.remove(*key);
+        }
+        if annotations.is_empty() {
+            metadata.remove("annotations");
+        }
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Line diff
+// ---------------------------------------------------------------------------
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Op {
+    Equal,
+    Delete,
+    Insert,
+}
+
+/// Line-level edit script from `old` to `new` (longest common subsequence).
+fn diff_ops<'a>(old: &[&'a str], new: &[&'a str]) -> Vec<(Op, &'a str)> {
+    let prefix = old.iter().zip(new).take_while(|(x, y)| x == y).count();
+    let suffix = old[prefix..]
+        .iter()
+        .rev()
+        .zip(new[prefix..].iter().rev())
+        .take_while(|(x, y)| x == y)
+        .count();
+    let mid_old = &old[prefix..old.len() - suffix];
+    let mid_new = &new[prefix..new.len() - suffix];
+
+    let mut ops: Vec<(Op, &str)> = old[..prefix].iter().map(|l| (Op::Equal, *l)).collect();
+
+    let (rows, cols) = (mid_old.len(), mid_new.len());
+    if rows.saturating_mul(cols) > MAX_DIFF_CELLS {
+        ops.extend(mid_old.iter().map(|l| (Op::Delete, *l)));
+        ops.extend(mid_new.iter().map(|l| (Op::Insert, *l)));
+    } else {
+        // lcs[i * (cols + 1) + j] = LCS length of mid_old[i..] and mid_new[j..]
+        let width = cols + 1;
+        let mut lcs = vec![0u32; (rows + 1) * width];
+        for i in (0..rows).rev() {
+            for j in (0..cols).rev() {
+                lcs[i * width + j] = if mid_old[i] == mid_new[j] {
+                    lcs[(i + 1) * width + j + 1] + 1
+                } else {
+                    lcs[(i + 1) * width + j].max(lcs[i * width + j + 1])
+                };
+            }
+        }
+        let (mut i, mut j) = (0, 0);
+        while i < rows || j < cols {
+            if i < rows && j < cols && mid_old[i] == mid_new[j] {
+                ops.push((Op::Equal, mid_old[i]));
+                i += 1;
+                j += 1;
+            } else if i < rows && (j == cols || lcs[(i + 1) * width + j] >= lcs[i * width + j + 1])
+            {
+                ops.push((Op::Delete, mid_old[i]));
+                i += 1;
+            } else {
+                ops.push((Op::Insert, mid_new[j]));
+                j += 1;
+            }
+        }
+    }
+
+    ops.extend(old[old.len() - suffix..].iter().map(|l| (Op::Equal, *l)));
+    ops
+}
+
+/// Unified diff of `old` against `new` with [`CONTEXT_LINES`] of context.
+/// Returns an empty string when the texts are equal.
+fn unified_diff(old: &str, new: &str) -> String {
+    let old: Vec<&str> = old.lines().collect();
+    let new: Vec<&str> = new.lines().collect();
+    let ops = diff_ops(&old, &new);
+    let changed: Vec<usize> = ops
+        .iter()
+        .enumerate()
+        .filter(|(_, (op, _))| *op != Op::Equal)
+        .map(|(i, _)| i)
+        .collect();
+
+    let mut out = String::new();
+    let mut k = 0;
+    while k < changed.len() {
+        let start = changed[k].saturating_sub(CONTEXT_LINES);
+        let mut last = changed[k];
+        // Merge changes whose context would overlap into one hunk.
+        while k + 1 < changed.len() && changed[k + 1] <= last + 2 * CONTEXT_LINES + 1 {
+            k += 1;
+            last = changed[k];
+        }
+        k += 1;
+        let end = (last + CONTEXT_LINES + 1).min(ops.len());
+
+        let count = |ops: &[(Op, &str)], skip: Op| ops.iter().filter(|(op, _)| *op != skip).count();
+        let (old_start, new_start) = (
+            count(&ops[..start], Op::Insert),
+            count(&ops[..start], Op::Delete),
+        );
+        let hunk = &ops[start..end];
+        let (old_len, new_len) = (count(hunk, Op::Insert), count(hunk, Op::Delete));
+        // Unified diff numbers an empty range by the line before it.
+        let line_no = |start: usize, len: usize| if len == 0 { start } else { start + 1 };
+        let _ = writeln!(
+            out,
+            "@@ -{},{old_len} +{},{new_len} @@",
+            line_no(old_start, old_len),
+            line_no(new_start, new_len),
+        );
+        for (op, line) in hunk {
+            let prefix = match op {
+                Op::Equal => ' ',
+                Op::Delete => '-',
+                Op::Insert => '+',
+            };
+            let _ = writeln!(out, "{prefix}{line}");
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn unified_diff_equal_is_empty() {
+        assert_eq!(unified_diff("a\nb\n", "a\nb\n"), "");
+    }
+
+    #[test]
+    fn unified_diff_shows_image_change_with_context() {
+        let old = "kind: Deployment\nspec:\n  replicas: 2\n  image: app:v1\n  port: 80\n";
+        let new = "kind: Deployment\nspec:\n  replicas: 2\n  image: app:v2\n  port: 80\n";
+        assert_eq!(
+            unified_diff(old, new),
+            "@@ -1,5 +1,5 @@\n kind: Deployment\n spec:\n   replicas: 2\n-  image: app:v1\n+  image: app:v2\n   port: 80\n"
+        );
+    }
+
+    #[test]
+    fn unified_diff_splits_distant_changes_into_hunks() {
+        let old = (1..=20).fold(String::new(), |mut s, i| {
+            let _ = writeln!(s, "line{i}");
+            s
+        });
+        let new = old.replace("line2\n", "LINE2\n").replace("line18\n", "");
+        let diff = unified_diff(&old, &new);
+        let hunks: Vec<&str> = diff.lines().filter(|l| l.starts_with("@@")).collect();
+        assert_eq!(hunks, ["@@ -1,5 +1,5 @@", "@@ -15,6 +15,5 @@"]);
+        assert!(diff.contains("-line2\n+LINE2\n"));
+        assert!(diff.contains("-line18\n"));
+    }
+
+    #[test]
+    fn unified_diff_added_and_removed_objects() {
+        assert_eq!(unified_diff("", "a\nb\n"), "@@ -0,0 +1,2 @@\n+a\n+b\n");
+        assert_eq!(unified_diff("a\n", ""), "@@ -1,1 +0,0 @@\n-a\n");
+    }
+
+    #[test]
+    fn strip_server_fields_keeps_spec_and_user_annotations() {
+        let mut value = serde_json::json!({
+            "metadata": {
+                "name": "api",
+                "uid": "123",
+                "resourceVersion": "42",
+                "managedFields": [{}],
+                "annotations": {"deployment.kubernetes.io/revision": "3", "team": "shop"},
+            },
+            "spec": {"replicas": 2},
+            "status": {"readyReplicas": 2},
+        });
+        strip_server_fields(&mut value);
+        assert_eq!(
+            value,
+            serde_json::json!({
+                "metadata": {"name": "api", "annotations": {"team": "shop"}},
+                "spec": {"replicas": 2},
+            })
+        );
+
+        let mut value = serde_json::json!({
+            "metadata": {"name": "api", "annotations": {"deployment.kubernetes.io/revision": "3"}},
+        });
+        strip_server_fields(&mut value);
+        assert_eq!(value, serde_json::json!({"metadata": {"name": "api"}}));
+    }
+}
@@ -0,0 +1,284 @@
+// Copyright (c) 2026 Steven Hooker. Exclusively licensed to and distributed by AgentSphere GmbH.
+// SPDX-License-Identifier: BUSL-1.1
+
+//! Deployment diagnostics: Kubernetes events and rollout conditions for the
+//! workloads a release applied.
+//!
+//! Events are matched by ownership — the release's Deployments, the
+//! `ReplicaSet`s they own and those `ReplicaSet`s' Pods — so pod-level failures
+//! such as `ImagePullBackOff` or `CrashLoopBackOff` show up next to the
+//! Deployment's own `Progressing`/`Available` conditions.
+
+use std::collections::{BTreeMap, HashSet};
+
+use chrono::{DateTime, Utc};
+use k8s_openapi::api::apps::v1::{Deployment, ReplicaSet};
+use k8s_openapi::api::core::v1::{Event, Pod};
+use k8s_openapi::apimachinery::pkg::apis::meta::v1::ObjectMeta;
+use kube::Api;
+use kube::api::ListParams;
+use serde::Serialize;
+use ts_rs::TS;
+
+use super::applier::TrackedResource;
+use super::error::DeployerError;
+
+/// Most recent events returned; older ones are dropped.
+const MAX_EVENTS: usize = 100;
+
+/// A Kubernetes event about one of a release's workloads.
+#[derive(Debug, Serialize, TS)]
+#[ts(export)]
+pub struct WorkloadEvent {
+    /// Kind of the object the event is about: `Deployment`, `ReplicaSet` or `Pod`.
+    pub kind: String,
+    pub name: String,
+    /// `Normal` or `Warning`.
+    pub event_type: String,
+    pub reason: Option<String>,
+    pub message: Option<String>,
+    /// How many times the event has been seen.
+    pub count: i32,
+    pub last_seen: Option<DateTime<Utc>>,
+}
+
+/// A Deployment status condition (`Available`, `Progressing`, `ReplicaFailure`).
+#[derive(Debug, Serialize, TS)]
+#[ts(export)]
+pub struct RolloutCondition {
+    pub deployment: String,
+    pub condition_type: String,
+    pub status: String,
+    pub reason: Option<String>,
+    pub message: Option<String>,
+    pub last_update: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Default)]
+pub struct WorkloadStatus {
+    pub conditions: Vec<RolloutCondition>,
+    /// Newest first.
+    pub events: Vec<WorkloadEvent>,
+}
+
+/// Read rollout conditions and events for the Deployments in `tracked`.
+#[tracing::instrument(skip(kube_client, tracked), err)]
+pub async fn workload_status(
+    kube_client: &kube::Client,
+    tracked: &[TrackedResource],
+) -> Result<WorkloadStatus, DeployerError> {
+    let mut by_namespace: BTreeMap<&str, Vec<&str>> = BTreeMap::new();
+    for r in tracked.iter().filter(|r| r.kind == "Deployment") {
+        by_namespace
+            .entry(r.namespace.as_str())
+            .or_default()
+            .push(r.name.as_str());
+    }
+
+    let mut status = WorkloadStatus::default();
+    for (namespace, deployments) in by_namespace {
+        let deploy_api: Api<Deployment> = Api::namespaced(kube_client.clone(), namespace);
+        for name in &deployments {
+            if let Some(deploy) = deploy_api.get_opt(name).await? {
+                status.conditions.extend(rollout_conditions(name, &deploy));
+            }
+        }
+
+        let lp = ListParams::default();
+        let replicasets = Api::<ReplicaSet>::namespaced(kube_client.clone(), namespace)
+            .list(&lp)
+            .await?
+            .items;
+        let pods = Api::<Pod>::namespaced(kube_client.clone(), namespace)
+            .list(&lp)
+            .await?
+            .items;
+        let objects = workload_objects(&deployments, &replicasets, &pods);
+
+        let events = Api::<Event>::namespaced(kube_client.clone(), namespace)
+            .list(&lp)
+            .await?
+            .items;
+        status.events.extend(events.iter().filter_map(|e| {
+            let kind = e.involved_object.kind.as_deref()?;
+            let name = e.involved_object.name.as_deref()?;
+            objects
+                .contains(&(kind.to_owned(), name.to_owned()))
+                .then(|| to_workload_event(e))
+        }));
+    }
+
+    status
+        .events
+        .sort_by_key(|e| std::cmp::Reverse(e.last_seen));
+    status.events.truncate(MAX_EVENTS);
+    Ok(status)
+}
+
+fn rollout_conditions(name: &str, deploy: &Deployment) -> Vec<RolloutCondition> {
+    deploy
+        .status
+        .as_ref()
+        .and_then(|s| s.conditions.as_ref())
+        .into_iter()
+        .flatten()
+        .map(|c| RolloutCondition {
+            deployment: name.to_owned(),
+            condition_type: c.type_.clone(),
+            status: c.status.clone(),
+            reason: c.reason.clone(),
+            message: c.message.clone(),
+            last_update: c
+                .last_update_time
+                .as_ref()
+                .and_then(timestamp)
+                .or_else(|| c.last_transition_time.as_ref().and_then(timestamp)),
+        })
+        .collect()
+}
+
+/// `(kind, name)` of the Deployments plus the `ReplicaSet`s and Pods they own.
+fn workload_objects(
+    deployments: &[&str],
+    replicasets: &[ReplicaSet],
+    pods: &[Pod],
+) -> HashSet<(String, String)> {
+    fn owned_by(meta: &ObjectMeta, kind: &str, names: &HashSet<&str>) -> bool {
+        meta.owner_references
+            .iter()
+            .flatten()
+            .any(|o| o.kind == kind && names.contains(o.name.as_str()))
+    }
+
+    let deploy_names: HashSet<&str> = deployments.iter().copied().collect();
+    let rs_names: HashSet<&str> = replicasets
+        .iter()
+        .filter(|rs| owned_by(&rs.metadata, "Deployment", &deploy_names))
+        .filter_map(|rs| rs.metadata.name.as_deref())
+        .collect();
+    let pod_names = pods
+        .iter()
+        .filter(|p| owned_by(&p.metadata, "ReplicaSet", &rs_names))
+        .filter_map(|p| p.metadata.name.as_deref());
+
+    deploy_names
+        .iter()
+        .map(|n| ("Deployment", *n))
+        .chain(rs_names.iter().map(|n| ("ReplicaSet", *n)))
+        .chain(pod_names.map(|n| ("Pod", n)))
+        .map(|(kind, name)| (kind.to_owned(), name.to_owned()))
+        .collect()
+}
+
+fn to_workload_event(e: &Event) -> WorkloadEvent {
+    WorkloadEvent {
+        kind: e.involved_object.kind.clone().unwrap_or_default(),
+        name: e.involved_object.name.clone().unwrap_or_default(),
+        event_type: e.type_.clone().unwrap_or_else(|| "Normal".into()),
+        reason: e.reason.clone(),
+        message: e.message.clone(),
+        count: e.count.unwrap_or(1),
+        last_seen: e
+            .last_timestamp
+            .as_ref()
+            .and_then(timestamp)
+            .or_else(|| e.event_time.as_ref().and_then(timestamp))
+            .or_else(|| e.first_timestamp.as_ref().and_then(timestamp)),
+    }
+}
+
+/// Convert a k8s `Time`/`MicroTime` through its RFC 3339 wire form.
+fn timestamp<T: Serialize>(t: &T) -> Option<DateTime<Utc>> {
+    serde_json::to_value(t)
+        .ok()?
+        .as_str()?
+        .parse::<DateTime<Utc>>()
+        .ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use k8s_openapi::apimachinery::pkg::apis::meta::v1::OwnerReference;
+
+    fn meta(name: &str, owner: Option<(&str, &str)>) -> ObjectMeta {
+        ObjectMeta {
+            name: Some(name.into()),
+            owner_references: owner.map(|(kind, name)| {
+                vec![OwnerReference {
+                    kind: kind.into(),
+                    name: name.into(),
+                    ..Default::default()
+                }]
+            }),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn objects_follow_ownership_not_name_prefixes() {
+        let replicasets = [
+            ReplicaSet {
+                metadata: meta("app-5d8f", Some(("Deployment", "app"))),
+                ..Default::default()
+            },
+            ReplicaSet {
+                metadata: meta("app-worker-7c9b", Some(("Deployment", "app-worker"))),
+                ..Default::default()
+            },
+        ];
+        let pods = [
+            Pod {
+                metadata: meta("app-5d8f-x2k4q", Some(("ReplicaSet", "app-5d8f"))),
+                ..Default::default()
+            },
+            Pod {
+                metadata: meta(
+                    "app-worker-7c9b-abcde",
+                    Some(("ReplicaSet", "app-worker-7c9b")),
+                ),
+                ..Default::default()
+            },
+            Pod {
+                metadata: meta("app-debug", None),
+                ..Default::default()
+            },
+        ];
+
+        let objects = workload_objects(&["app"], &replicasets, &pods);
+        let mut got: Vec<_> = objects.into_iter().collect();
+        got.sort();
+        assert_eq!(
+            got,
+            vec![
+                ("Deployment".to_owned(), "app".to_owned()),
+                ("Pod".to_owned(), "app-5d8f-x2k4q".to_owned()),
+                ("ReplicaSet".to_owned(), "app-5d8f".to_owned()),
+            ]
+        );
+    }
+
+    #[test]
+    fn event_conversion_reads_wire_timestamps() {
+        let event: Event = serde_json::from_value(serde_json::json!({
+            "metadata": {"name": "app-5d8f-x2k4q.1"},
+            "involvedObject": {"kind": "Pod", "name": "app-5d8f-x2k4q"},
+            "type": "Warning",
+            "reason": "Failed",
+            "message": "Error: ImagePullBackOff",
+            "count": 4,
+            "lastTimestamp": "2026-10-15T09:30:00Z",
+        }))
+        .unwrap();
+
+        let e = to_workload_event(&event);
+        assert_eq!(e.kind, "Pod");
+        assert_eq!(e.event_type, "Warning");
+        assert_eq!(e.message.as_deref(), Some("Error: ImagePullBackOff"));
+        assert_eq!(e.count, 4);
+        assert_eq!(
+            e.last_seen,
+            Some("2026-10-15T09:30:00Z".parse::<DateTime<Utc>>().unwrap())
+        );
+    }
+}
@@ -0,0 +1,291 @@
+// Copyright (c) 2026 Steven Hooker. Exclusively licensed to and distributed by AgentSphere GmbH.
+// SPDX-License-Identifier: BUSL-1.1
+
+//! Deployment freeze windows (`deploy_freeze_windows`).
+//!
+//! A recurring window opens at every slot of its cron expression (UTC) and
+//! stays open for `duration_minutes`, e.g. `0 9 * * FRI` for 480 minutes
+//! freezes Fridays 09:00–17:00. A one-off window covers `starts_at..ends_at`.
+//! Overlapping and back-to-back windows merge, so the reported lift time is
+//! when the environment actually opens again.
+
+use chrono::{DateTime, Duration, Utc};
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use crate::pipeline::cron::CronExpr;
+
+/// Upper bound on windows chained together when computing a lift time, so a
+/// window that never closes (`* * * * *`) still yields an answer.
+const MAX_CHAINED: usize = 100;
+
+#[derive(Debug, Clone)]
+pub enum Schedule {
+    Recurring {
+        cron: CronExpr,
+        duration: Duration,
+    },
+    Once {
+        starts_at: DateTime<Utc>,
+        ends_at: DateTime<Utc>,
+    },
+}
+
+impl Schedule {
+    /// Build from a `deploy_freeze_windows` row's schedule columns.
+    pub fn from_columns(
+        cron: Option<&str>,
+        duration_minutes: Option<i32>,
+        starts_at: Option<DateTime<Utc>>,
+        ends_at: Option<DateTime<Utc>>,
+    ) -> Result<Self, String> {
+        match (cron, duration_minutes, starts_at, ends_at) {
+            (Some(cron), Some(minutes), _, _) => Ok(Self::Recurring {
+                cron: CronExpr::parse(cron)?,
+                duration: Duration::minutes(i64::from(minutes)),
+            }),
+            (_, _, Some(starts_at), Some(ends_at)) => Ok(Self::Once { starts_at, ends_at }),
+            _ => Err("window has neither a cron schedule nor a time range".into()),
+        }
+    }
+
+    /// If the window is open at `now`, when that opening closes (exclusive).
+    pub fn open_until(&self, now: DateTime<Utc>) -> Option<DateTime<Utc>> {
+        match self {
+            Self::Once { starts_at, ends_at } => {
+                (*starts_at <= now && now < *ends_at).then_some(*ends_at)
+            }
+            Self::Recurring { cron, duration } => {
+                // The earliest slot whose opening still covers `now`.
+                let start = cron.next_after(now - *duration)?;
+                (start <= now).then(|| {
+                    let mut end = start + *duration;
+                    let mut slot = start;
+                    for _ in 0..MAX_CHAINED {
+                        match cron.next_after(slot) {
+                            Some(next) if next <= end => {
+                                end = end.max(next + *duration);
+                                slot = next;
+                            }
+                            _ => break,
+                        }
+                    }
+                    end
+                })
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct FreezeWindow {
+    pub id: Uuid,
+    pub name: String,
+    pub schedule: Schedule,
+}
+
+/// The window freezing an environment and when the freeze lifts.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ActiveFreeze {
+    pub window_id: Uuid,
+    pub name: String,
+    pub until: DateTime<Utc>,
+}
+
+/// Whether any of `windows` is open at `now`, and until when the environment
+/// stays frozen once windows that open as another closes are merged.
+pub fn frozen_until(windows: &[FreezeWindow], now: DateTime<Utc>) -> Option<ActiveFreeze> {
+    let (window, mut until) = windows
+        .iter()
+        .filter_map(|w| w.schedule.open_until(now).map(|until| (w, until)))
+        .max_by_key(|(_, until)| *until)?;
+    for _ in 0..MAX_CHAINED {
+        match windows
+            .iter()
+            .filter_map(|w| w.schedule.open_until(until))
+            .max()
+        {
+            Some(next) if next > until => until = next,
+            _ => break,
+        }
+    }
+    Some(ActiveFreeze {
+        window_id: window.id,
+        name: window.name.clone(),
+        until,
+    })
+}
+
+/// The active freeze for a project's environment at `now`, if any.
+pub async fn active_freeze(
+    pool: &PgPool,
+    project_id: Uuid,
+    environment: &str,
+    now: DateTime<Utc>,
+) -> Result<Option<ActiveFreeze>, sqlx::Error> {
+    let windows = load_windows(pool, project_id, environment).await?;
+    Ok(frozen_until(&windows, now))
+}
+
+/// Whether a release created without a person behind it (pipeline, merge)
+/// must be refused. Only an admin can deploy through a freeze, via the API.
+pub async fn refuses_automatic(
+    pool: &PgPool,
+    project_id: Uuid,
+    environment: &str,
+) -> Result<bool, sqlx::Error> {
+    let Some(freeze) = active_freeze(pool, project_id, environment, Utc::now()).await? else {
+        return Ok(false);
+    };
+    tracing::warn!(
+        %project_id,
+        %environment,
+        window = %freeze.name,
+        until = %freeze.until,
+        "environment is frozen, automatic release not created"
+    );
+    Ok(true)
+}
+
+struct WindowRow {
+    id: Uuid,
+    name: String,
+    cron: Option<String>,
+    duration_minutes: Option<i32>,
+    starts_at: Option<DateTime<Utc>>,
+    ends_at: Option<DateTime<Utc>>,
+}
+
+impl WindowRow {
+    fn into_window(self) -> Option<FreezeWindow> {
+        let schedule = Schedule::from_columns(
+            self.cron.as_deref(),
+            self.duration_minutes,
+            self.starts_at,
+            self.ends_at,
+        );
+        match schedule {
+            Ok(schedule) => Some(FreezeWindow {
+                id: self.id,
+                name: self.name,
+                schedule,
+            }),
+            Err(e) => {
+                tracing::warn!(window_id = %self.id, error = %e, "ignoring invalid freeze window");
+                None
+            }
+        }
+    }
+}
+
+async fn load_windows(
+    pool: &PgPool,
+    project_id: Uuid,
+    environment: &str,
+) -> Result<Vec<FreezeWindow>, sqlx::Error> {
+    let rows = sqlx::query_as!(
+        WindowRow,
+        "SELECT id, name, cron, duration_minutes, starts_at, ends_at
+         FROM deploy_freeze_windows WHERE project_id = $1 AND environment = $2",
+        project_id,
+        environment,
+    )
+    .fetch_all(pool)
+    .await?;
+    Ok(rows
+        .into_iter()
+        .filter_map(WindowRow::into_window)
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn at(s: &str) -> DateTime<Utc> {
+        DateTime::parse_from_rfc3339(s).unwrap().with_timezone(&Utc)
+    }
+
+    fn recurring(name: &str, cron: &str, minutes: i64) -> FreezeWindow {
+        FreezeWindow {
+            id: Uuid::new_v4(),
+            name: name.into(),
+            schedule: Schedule::Recurring {
+                cron: CronExpr::parse(cron).unwrap(),
+                duration: Duration::minutes(minutes),
+            },
+        }
+    }
+
+    fn once(name: &str, starts_at: &str, ends_at: &str) -> FreezeWindow {
+        FreezeWindow {
+            id: Uuid::new_v4(),
+            name: name.into(),
+            schedule: Schedule::Once {
+                starts_at: at(starts_at),
+                ends_at: at(ends_at),
+            },
+        }
+    }
+
+    // 2026-10-16 is a Friday.
+    #[test]
+    fn friday_business_hours() {
+        let w = [recurring("friday", "0 9 * * FRI", 8 * 60)];
+        assert_eq!(frozen_until(&w, at("2026-10-16T08:59:00Z")), None);
+        let freeze = frozen_until(&w, at("2026-10-16T09:00:00Z")).unwrap();
+        assert_eq!(freeze.until, at("2026-10-16T17:00:00Z"));
+        assert_eq!(freeze.name, "friday");
+        assert!(frozen_until(&w, at("2026-10-16T16:59:59Z")).is_some());
+        assert_eq!(frozen_until(&w, at("2026-10-16T17:00:00Z")), None);
+        assert_eq!(frozen_until(&w, at("2026-10-15T12:00:00Z")), None);
+    }
+
+    #[test]
+    fn window_spanning_midnight() {
+        let w = [recurring("overnight", "0 22 * * *", 4 * 60)];
+        let freeze = frozen_until(&w, at("2026-10-16T01:30:00Z")).unwrap();
+        assert_eq!(freeze.until, at("2026-10-16T02:00:00Z"));
+    }
+
+    #[test]
+    fn one_off_range() {
+        let w = [once(
+            "launch",
+            "2026-12-20T00:00:00Z",
+            "2026-12-27T00:00:00Z",
+        )];
+        assert_eq!(frozen_until(&w, at("2026-12-19T23:59:59Z")), None);
+        assert_eq!(
+            frozen_until(&w, at("2026-12-24T12:00:00Z")).unwrap().until,
+            at("2026-12-27T00:00:00Z")
+        );
+        assert_eq!(frozen_until(&w, at("2026-12-27T00:00:00Z")), None);
+    }
+
+    #[test]
+    fn adjacent_windows_merge() {
+        // Hourly slots lasting an hour never open up between them
+        let w = [recurring("hourly", "0 9-11 * * *", 60)];
+        assert_eq!(
+            frozen_until(&w, at("2026-10-16T09:30:00Z")).unwrap().until,
+            at("2026-10-16T12:00:00Z")
+        );
+
+        // A one-off window picking up where a recurring one ends
+        let w = [
+            recurring("friday", "0 9 * * FRI", 8 * 60),
+            once("weekend", "2026-10-16T17:00:00Z", "2026-10-19T06:00:00Z"),
+        ];
+        let freeze = frozen_until(&w, at("2026-10-16T10:00:00Z")).unwrap();
+        assert_eq!(freeze.until, at("2026-10-19T06:00:00Z"));
+        assert_eq!(freeze.name, "friday");
+    }
+
+    #[test]
+    fn never_closing_window_is_bounded() {
+        let w = [recurring("always", "* * * * *", 5)];
+        let now = at("2026-10-16T10:00:00Z");
+        assert!(frozen_until(&w, now).unwrap().until > now);
+    }
+}
@@ -50,7 +50,7 @@ struct CacheEntry {
     inserted_at: Instant,
 }
 
-const CACHE_TTL: Duration = Duration::from_secs(3600); // 1 hour
+const CACHE_TTL: Duration = Duration::from_hours(1);
 
 impl Default for EntrypointCache {
     fn default() -> Self {
@@ -5,12 +5,17 @@
 
 pub mod analysis;
 pub mod applier;
+pub mod diff;
 pub mod error;
+pub mod events;
+pub mod freeze;
 pub mod gateway;
 pub mod image_inspect;
 pub mod namespace;
 pub mod ops_repo;
+pub mod pod_gc;
 pub mod preview;
 pub mod reconciler;
 pub mod renderer;
+pub mod scheduling;
 pub mod types;
@@ -0,0 +1,305 @@
+// Copyright (c) 2026 Steven Hooker. Exclusively licensed to and distributed by AgentSphere GmbH.
+// SPDX-License-Identifier: BUSL-1.1
+
+//! Garbage collection of pipeline and agent pods leaked by crashes.
+//!
+//! The executor and the agent reaper delete their pods when a run finishes, but
+//! a crash between creating a pod and recording it leaves the pod behind. This
+//! task deletes labelled pods whose owning pipeline or agent session is
+//! terminal, or no longer exists.
+
+use std::collections::HashSet;
+use std::time::Duration;
+
+use k8s_openapi::api::core::v1::Pod;
+use kube::Api;
+use kube::api::{DeleteParams, ListParams};
+use tracing::Instrument;
+use uuid::Uuid;
+
+use crate::store::AppState;
+
+const GC_INTERVAL: Duration = Duration::from_mins(10);
+
+/// Pods the collector looks after, and how to find out whether their owner is alive.
+#[derive(Debug, Clone, Copy)]
+enum PodKind {
+    Pipeline,
+    AgentSession,
+}
+
+impl PodKind {
+    fn selector(self) -> &'static str {
+        match self {
+            Self::Pipeline => "platform.io/pipeline",
+            Self::AgentSession => "platform.io/component=agent-session,platform.io/session",
+        }
+    }
+
+    /// Label holding the owner's ID.
+    fn owner_label(self) -> &'static str {
+        match self {
+            Self::Pipeline => "platform.io/pipeline",
+            Self::AgentSession => "platform.io/session",
+        }
+    }
+
+    /// Existing owners among `ids`, and whether each still needs its pods.
+    async fn owners(
+        self,
+        pool: &sqlx::PgPool,
+        ids: &[Uuid],
+    ) -> Result<Vec<(Uuid, bool)>, sqlx::Error> {
+        let owners = match self {
+            Self::Pipeline => sqlx::query!(
+                r#"SELECT id, status IN ('pending', 'running') AS "live!"
+                   FROM pipelines WHERE id = ANY($1)"#,
+                ids
+            )
+            .fetch_all(pool)
+            .await?
+            .into_iter()
+            .map(|r| (r.id, r.live))
+            .collect(),
+            Self::AgentSession => sqlx::query!(
+                r#"SELECT id, status IN ('pending', 'running') AS "live!"
+                   FROM agent_sessions WHERE id = ANY($1)"#,
+                ids
+            )
+            .fetch_all(pool)
+            .await?
+            .into_iter()
+            .map(|r| (r.id, r.live))
+            .collect(),
+        };
+        Ok(owners)
+    }
+}
+
+/// A labelled pod, reduced to what the collector decides on.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct LabelledPod {
+    namespace: String,
+    name: String,
+    owner: Uuid,
+    project: Option<Uuid>,
+}
+
+impl LabelledPod {
+    fn from_pod(pod: &Pod, kind: PodKind) -> Option<Self> {
+        let labels = pod.metadata.labels.as_ref()?;
+        let label_uuid = |key: &str| labels.get(key).and_then(|v| Uuid::parse_str(v).ok());
+        Some(Self {
+            namespace: pod.metadata.namespace.clone()?,
+            name: pod.metadata.name.clone()?,
+            owner: label_uuid(kind.owner_label())?,
+            project: label_uuid("platform.io/project"),
+        })
+    }
+}
+
+/// Pods whose owner is terminal, or missing while its project is known here.
+///
+/// A missing owner under an unknown project most likely belongs to another
+/// platform instance sharing the cluster, so it is left alone.
+fn orphaned(
+    pods: Vec<LabelledPod>,
+    live_owners: &HashSet<Uuid>,
+    known_owners: &HashSet<Uuid>,
+    known_projects: &HashSet<Uuid>,
+) -> Vec<LabelledPod> {
+    pods.into_iter()
+        .filter(|p| !live_owners.contains(&p.owner))
+        .filter(|p| {
+            known_owners.contains(&p.owner)
+                || p.project.is_some_and(|id| known_projects.contains(&id))
+        })
+        .collect()
+}
+
+/// Background task that periodically deletes orphaned pipeline and agent pods.
+pub async fn run(state: AppState, cancel: tokio_util::sync::CancellationToken) {
+    let mut interval = tokio::time::interval(GC_INTERVAL);
+    state.task_registry.register("pod_gc", 1200);
+    loop {
+        tokio::select! {
+            () = cancel.cancelled() => {
+                tracing::info!("pod GC shutting down");
+                break;
+            }
+            _ = interval.tick() => {
+                let iter_trace_id = uuid::Uuid::new_v4().to_string().replace('-', "");
+                let span = tracing::info_span!(
+                    "task_iteration",
+                    task_name = "pod_gc",
+                    trace_id = %iter_trace_id,
+                    source = "system",
+                );
+                async {
+                    match collect_orphaned_pods(&state).await {
+                        Ok(_) => state.task_registry.heartbeat("pod_gc"),
+                        Err(e) => {
+                            state.task_registry.report_error("pod_gc", &e.to_string());
+                            tracing::error!(error = %e, "pod GC failed");
+                        }
+                    }
+                }.instrument(span).await;
+            }
+        }
+    }
+}
+
+/// Delete orphaned pipeline and agent pods. Returns how many were deleted.
+pub async fn collect_orphaned_pods(state: &AppState) -> anyhow::Result<usize> {
+    let mut deleted = 0;
+    for kind in [PodKind::Pipeline, PodKind::AgentSession] {
+        deleted += collect_kind(state, kind).await?;
+    }
+    Ok(deleted)
+}
+
+async fn collect_kind(state: &AppState, kind: PodKind) -> anyhow::Result<usize> {
+    let pods: Api<Pod> = Api::all(state.kube.clone());
+    let listed = pods
+        .list(&ListParams::default().labels(kind.selector()))
+        .await?;
+    let labelled: Vec<LabelledPod> = listed
+        .items
+        .iter()
+        .filter(|p| p.metadata.deletion_timestamp.is_none())
+        .filter_map(|p| LabelledPod::from_pod(p, kind))
+        .collect();
+    if labelled.is_empty() {
+        return Ok(0);
+    }
+
+    let owner_ids: Vec<Uuid> = labelled.iter().map(|p| p.owner).collect();
+    let project_ids: Vec<Uuid> = labelled.iter().filter_map(|p| p.project).collect();
+    let owners = kind.owners(&state.pool, &owner_ids).await?;
+    let known_owners: HashSet<Uuid> = owners.iter().map(|(id, _)| *id).collect();
+    let live_owners: HashSet<Uuid> = owners
+        .iter()
+        .filter(|(_, live)| *live)
+        .map(|(id, _)| *id)
+        .collect();
+    let known_projects: HashSet<Uuid> =
+        sqlx::query_scalar!("SELECT id FROM projects WHERE id = ANY($1)", &project_ids)
+            .fetch_all(&state.pool)
+            .await?
+            .into_iter()
+            .collect();
+
+    let orphans = orphaned(labelled, &live_owners, &known_owners, &known_projects);
+    if !orphans.is_empty() {
+        tracing::info!(
+            count = orphans.len(),
+            ?kind,
+            "pod GC: deleting orphaned pods"
+        );
+    }
+
+    let mut deleted = 0;
+    for pod in &orphans {
+        let api: Api<Pod> = Api::namespaced(state.kube.clone(), &pod.namespace);
+        match api.delete(&pod.name, &DeleteParams::default()).await {
+            Ok(_) => deleted += 1,
+            Err(kube::Error::Api(err)) if err.code == 404 => {}
+            Err(e) => {
+                tracing::warn!(error = %e, namespace = %pod.namespace, pod = %pod.name, "pod GC: failed to delete pod");
+            }
+        }
+    }
+    Ok(deleted)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeMap;
+
+    use k8s_openapi::apimachinery::pkg::apis::meta::v1::ObjectMeta;
+
+    use super::*;
+
+    fn pod(labels: &[(&str, String)]) -> Pod {
+        Pod {
+            metadata: ObjectMeta {
+                name: Some("pl-abc-build".into()),
+                namespace: Some("demo-p-abc".into()),
+                labels: Some(
+                    labels
+                        .iter()
+                        .map(|(k, v)| ((*k).to_owned(), v.clone()))
+                        .collect::<BTreeMap<_, _>>(),
+                ),
+                ..Default::default()
+            },
+            ..Default::default()
+        }
+    }
+
+    fn labelled(owner: Uuid, project: Option<Uuid>) -> LabelledPod {
+        LabelledPod {
+            namespace: "ns".into(),
+            name: format!("pod-{owner}"),
+            owner,
+            project,
+        }
+    }
+
+    #[test]
+    fn from_pod_reads_owner_and_project() {
+        let owner = Uuid::new_v4();
+        let project = Uuid::new_v4();
+        let p = pod(&[
+            ("platform.io/pipeline", owner.to_string()),
+            ("platform.io/project", project.to_string()),
+        ]);
+        let parsed = LabelledPod::from_pod(&p, PodKind::Pipeline).unwrap();
+        assert_eq!(parsed.owner, owner);
+        assert_eq!(parsed.project, Some(project));
+        assert_eq!(parsed.namespace, "demo-p-abc");
+    }
+
+    #[test]
+    fn from_pod_skips_unparseable_owner() {
+        let p = pod(&[("platform.io/session", "not-a-uuid".into())]);
+        assert!(LabelledPod::from_pod(&p, PodKind::AgentSession).is_none());
+    }
+
+    #[test]
+    fn live_owner_is_kept() {
+        let owner = Uuid::new_v4();
+        let known = HashSet::from([owner]);
+        let result = orphaned(vec![labelled(owner, None)], &known, &known, &HashSet::new());
+        assert!(result.is_empty());
+    }
+
+    #[test]
+    fn terminal_owner_is_collected() {
+        let owner = Uuid::new_v4();
+        let result = orphaned(
+            vec![labelled(owner, None)],
+            &HashSet::new(),
+            &HashSet::from([owner]),
+            &HashSet::new(),
+        );
+        assert_eq!(result.len(), 1);
+    }
+
+    #[test]
+    fn missing_owner_collected_only_for_known_project() {
+        let ours = Uuid::new_v4();
+        let pods = vec![
+            labelled(Uuid::new_v4(), Some(ours)),
+            labelled(Uuid::new_v4(), Some(Uuid::new_v4())),
+            labelled(Uuid::new_v4(), None),
+        ];
+        let result = orphaned(
+            pods.clone(),
+            &HashSet::new(),
+            &HashSet::new(),
+            &HashSet::from([ours]),
+        );
+        assert_eq!(result, vec![pods[0].clone()]);
+    }
+}
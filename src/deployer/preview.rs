@@ -6,10 +6,13 @@
 //! Preview reconciliation is handled by the unified reconciler
 //! (`reconciler::cleanup_expired_previews`). This module retains:
 //! - `stop_preview_for_branch()` — called from MR merge to deactivate previews
+//! - `teardown_preview()` — used by expiry cleanup and the preview delete API
 //! - Builder functions — used by the unified reconciler for preview K8s resources
 
 use uuid::Uuid;
 
+use crate::store::AppState;
+
 /// Stop a preview deployment for a given project and branch slug.
 /// Called when an MR is merged to clean up the preview for the source branch.
 /// Uses `deploy_targets` table (`preview_deployments` was dropped).
@@ -46,8 +49,49 @@ pub async fn stop_preview_for_branch(pool: &sqlx::PgPool, project_id: Uuid, bran
     );
 }
 
+/// Tear down a preview target: deactivate it, cancel its in-flight releases,
+/// and delete its namespace, which removes every K8s resource deployed to it.
+pub async fn teardown_preview(
+    state: &AppState,
+    target_id: Uuid,
+    namespace_slug: &str,
+    branch_slug: Option<&str>,
+) {
+    let _ = sqlx::query!(
+        "UPDATE deploy_targets SET is_active = false WHERE id = $1",
+        target_id
+    )
+    .execute(&state.pool)
+    .await;
+
+    let _ = sqlx::query!(
+        "UPDATE deploy_releases SET phase = 'cancelled'
+         WHERE target_id = $1 AND phase NOT IN ('completed','rolled_back','cancelled','failed')",
+        target_id
+    )
+    .execute(&state.pool)
+    .await;
+
+    let ns = namespace_for(&state.config, namespace_slug, branch_slug);
+    if let Err(e) = crate::deployer::namespace::delete_namespace(&state.kube, &ns).await {
+        tracing::warn!(error = %e, %target_id, namespace = %ns, "failed to delete preview namespace");
+    }
+}
+
+/// Namespace a preview target deploys into: one per branch, or the plain
+/// `preview` environment namespace for a target without a branch.
+pub fn namespace_for(
+    config: &crate::config::Config,
+    namespace_slug: &str,
+    branch_slug: Option<&str>,
+) -> String {
+    match branch_slug {
+        Some(slug) => build_namespace_name(config, namespace_slug, slug),
+        None => config.project_namespace(namespace_slug, "preview"),
+    }
+}
+
 /// Build the K8s namespace name for a preview, respecting the 63-char DNS label limit.
-#[allow(dead_code)]
 pub fn build_namespace_name(
     config: &crate::config::Config,
     project_slug: &str,
@@ -70,6 +114,16 @@ mod tests {
         crate::config::Config::test_default()
     }
 
+    #[test]
+    fn namespace_for_branch_and_branchless_targets() {
+        let config = test_config();
+        assert_eq!(
+            namespace_for(&config, "my-app", Some("feat-1")),
+            "my-app-preview-feat-1"
+        );
+        assert_eq!(namespace_for(&config, "my-app", None), "my-app-preview");
+    }
+
     #[test]
     fn build_namespace_name_basic() {
         let config = test_config();
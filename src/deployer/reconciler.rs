@@ -16,7 +16,7 @@ use crate::store::AppState;
 
 use super::error::DeployerError;
 use super::image_inspect::{self, EntrypointCache};
-use super::{applier, ops_repo, renderer};
+use super::{applier, diff, ops_repo, renderer};
 
 /// Global entrypoint cache (1-hour TTL per entry, used across reconciler runs).
 static ENTRYPOINT_CACHE: LazyLock<EntrypointCache> = LazyLock::new(EntrypointCache::new);
@@ -78,7 +78,8 @@ pub async fn run(state: AppState, cancel: tokio_util::sync::CancellationToken) {
     }
 }
 
-/// Find releases needing reconciliation and spawn tasks for each.
+/// Find releases needing reconciliation and spawn tasks for each. Pending
+/// releases awaiting approval are left alone until approved.
 ///
 /// Uses `FOR UPDATE OF r SKIP LOCKED` so multiple replicas don't race on the same rows.
 async fn reconcile(state: &AppState) -> Result<(), DeployerError> {
@@ -93,6 +94,7 @@ async fn reconcile(state: &AppState) -> Result<(), DeployerError> {
          JOIN deploy_targets dt ON dt.id = r.target_id
          JOIN projects p ON p.id = r.project_id AND p.is_active = true
          WHERE r.phase IN ('pending','progressing','holding','promoting','rolling_back')
+           AND NOT (r.phase = 'pending' AND r.requires_approval AND r.approved_at IS NULL)
          ORDER BY r.created_at ASC
          LIMIT 10
          FOR UPDATE OF r SKIP LOCKED",
@@ -199,15 +201,30 @@ pub fn target_namespace(
     config.project_namespace(namespace_slug, env_suffix(environment))
 }
 
+/// Namespace a release deploys into. Preview targets get one per branch.
+fn release_namespace(config: &crate::config::Config, release: &PendingRelease) -> String {
+    if release.environment == "preview" {
+        super::preview::namespace_for(
+            config,
+            &release.namespace_slug,
+            release.branch_slug.as_deref(),
+        )
+    } else {
+        target_namespace(config, &release.namespace_slug, &release.environment)
+    }
+}
+
 // ---------------------------------------------------------------------------
 // Single release reconciliation
 // ---------------------------------------------------------------------------
 
 /// Claim and reconcile a single release. Uses optimistic locking.
 async fn reconcile_one(state: &AppState, release: &PendingRelease) -> Result<(), DeployerError> {
-    // Claim with optimistic lock — only process if still in expected phase
+    // Claim with optimistic lock — only process if still in expected phase.
+    // Each claim of a pending release is a new attempt for the log trail.
     let claimed = sqlx::query_scalar::<_, Uuid>(
-        "UPDATE deploy_releases SET started_at = COALESCE(started_at, now())
+        "UPDATE deploy_releases SET started_at = COALESCE(started_at, now()),
+                attempts = attempts + CASE WHEN phase = 'pending' THEN 1 ELSE 0 END
          WHERE id = $1 AND phase = $2
          RETURNING id",
     )
@@ -245,7 +262,14 @@ async fn reconcile_one(state: &AppState, release: &PendingRelease) -> Result<(),
 /// Pending release — apply manifests, transition to progressing.
 #[allow(clippy::too_many_lines)]
 async fn handle_pending(state: &AppState, release: &PendingRelease) -> Result<(), DeployerError> {
-    let ns = target_namespace(&state.config, &release.namespace_slug, &release.environment);
+    let ns = release_namespace(&state.config, release);
+    release_log(
+        state,
+        release,
+        "info",
+        &format!("applying {} to namespace {ns}", release.image_ref),
+    )
+    .await;
 
     // Ensure namespace, secrets, registry pull secret
     crate::deployer::namespace::ensure_namespace(
@@ -263,46 +287,8 @@ async fn handle_pending(state: &AppState, release: &PendingRelease) -> Result<()
     ensure_registry_pull_secret_for(state, release.project_id, release.id, &ns).await;
 
     // Render + apply manifests
-    let (rendered, _sha) = render_manifests(state, release).await?;
-    let rendered = if let Some(ref sn) = secrets_name {
-        applier::inject_env_from_secret(&rendered, sn)?
-    } else {
-        rendered
-    };
-
-    // Inject proxy wrapper if mesh is enabled
-    let rendered = if state.config.mesh_enabled {
-        // Resolve entrypoints for containers missing explicit `command` fields
-        // so the proxy wrapper can wrap them.
-        let rendered = resolve_manifest_entrypoints(
-            &rendered,
-            &state.pool,
-            &state.minio,
-            state.config.registry_node_url.as_deref(),
-        )
-        .await;
-
-        // Distroless init image: contains proxy binary + iptables, no shell
-        let init_image = match state
-            .config
-            .registry_node_url
-            .as_deref()
-            .or(state.config.registry_url.as_deref())
-        {
-            Some(reg) => format!("{reg}/platform-proxy-init:v1"),
-            None => "platform-proxy-init:v1".into(),
-        };
-        applier::inject_proxy_wrapper(
-            &rendered,
-            &applier::ProxyInjectionConfig {
-                platform_api_url: state.config.platform_api_url.clone(),
-                init_image,
-                mesh_strict_mtls: state.config.mesh_strict_mtls,
-            },
-        )?
-    } else {
-        rendered
-    };
+    let (rendered, _sha) = render_manifests(state, release, &RenderOverrides::default()).await?;
+    let rendered = inject_runtime(state, rendered, secrets_name.as_deref()).await?;
 
     // Prune orphans
     let new_tracked = applier::build_tracked_inventory(&rendered, &ns);
@@ -317,14 +303,47 @@ async fn handle_pending(state: &AppState, release: &PendingRelease) -> Result<()
     let applied =
         applier::apply_with_tracking(&state.kube, &rendered, &ns, Some(release.id)).await?;
     store_tracked_resources(state, release.id, &new_tracked).await?;
+    let applied_names: Vec<String> = applied
+        .iter()
+        .map(|r| format!("{}/{}", r.kind, r.name))
+        .collect();
+    release_log(
+        state,
+        release,
+        "info",
+        &format!("applied {}", applied_names.join(", ")),
+    )
+    .await;
+
+    let health_timeout = Duration::from_secs(state.config.deploy_health_timeout_secs);
+    if let Some(deploy_name) = applier::find_deployment_name(&applied) {
+        release_log(
+            state,
+            release,
+            "info",
+            &format!(
+                "waiting up to {}s for deployment {deploy_name} to become available",
+                health_timeout.as_secs()
+            ),
+        )
+        .await;
+    }
 
     // For rolling strategy: wait for health and complete immediately
     if release.strategy == "rolling" {
         if let Some(deploy_name) = applier::find_deployment_name(&applied) {
-            applier::wait_healthy(&state.kube, &ns, deploy_name, Duration::from_secs(300)).await?;
+            match applier::wait_healthy(&state.kube, &ns, deploy_name, health_timeout).await {
+                Err(DeployerError::HealthTimeout(secs)) => {
+                    return start_auto_rollback(state, release, secs).await;
+                }
+                result => {
+                    result?;
+                }
+            }
         }
         transition_phase(state, release, "completed", Some(100), Some("healthy")).await?;
-        record_history(state, release, "promoted", "completed", Some(100)).await;
+        record_history(state, release, "promoted", "completed", Some(100), None).await;
+        release_log(state, release, "info", "release completed").await;
         fire_webhook(state, release, "deployed").await;
 
         let _ = crate::store::eventbus::publish(
@@ -339,7 +358,7 @@ async fn handle_pending(state: &AppState, release: &PendingRelease) -> Result<()
     } else {
         // Canary/AB: wait for canary deployment health before traffic switch
         if let Some(deploy_name) = applier::find_deployment_name(&applied) {
-            applier::wait_healthy(&state.kube, &ns, deploy_name, Duration::from_secs(300)).await?;
+            applier::wait_healthy(&state.kube, &ns, deploy_name, health_timeout).await?;
         }
 
         // Re-check phase — release may have been cancelled while waiting for health
@@ -350,6 +369,13 @@ async fn handle_pending(state: &AppState, release: &PendingRelease) -> Result<()
                 .await?;
         if current_phase.as_deref() != Some("pending") {
             tracing::info!(release_id = %release.id, phase = ?current_phase, "release phase changed during health wait, aborting");
+            release_log(
+                state,
+                release,
+                "info",
+                "release phase changed while waiting for health; stopping",
+            )
+            .await;
             return Ok(());
         }
 
@@ -379,12 +405,20 @@ async fn handle_pending(state: &AppState, release: &PendingRelease) -> Result<()
         );
 
         transition_phase(state, release, "progressing", Some(initial_weight), None).await?;
+        release_log(
+            state,
+            release,
+            "info",
+            &format!("canary healthy; routing {initial_weight}% of traffic to it"),
+        )
+        .await;
         record_history(
             state,
             release,
             "step_advanced",
             "progressing",
             Some(initial_weight),
+            None,
         )
         .await;
     }
@@ -436,7 +470,7 @@ async fn handle_canary_progress(
                     "canary all steps passed — promoting to stable"
                 );
                 transition_phase(state, release, "promoting", Some(100), None).await?;
-                record_history(state, release, "promoted", "promoting", Some(100)).await;
+                record_history(state, release, "promoted", "promoting", Some(100), None).await;
             } else {
                 let weight = steps.get(next_step as usize).copied().unwrap_or(100);
                 sqlx::query(
@@ -449,15 +483,22 @@ async fn handle_canary_progress(
                 .await?;
 
                 // Update HTTPRoute with new weight
-                let ns =
-                    target_namespace(&state.config, &release.namespace_slug, &release.environment);
+                let ns = release_namespace(&state.config, release);
                 apply_gateway_resources(state, release, &ns, weight).await;
                 tracing::info!(
                     release_id = %release.id, step = next_step, %weight,
                     "canary step advanced — traffic weight updated"
                 );
 
-                record_history(state, release, "step_advanced", "progressing", Some(weight)).await;
+                record_history(
+                    state,
+                    release,
+                    "step_advanced",
+                    "progressing",
+                    Some(weight),
+                    None,
+                )
+                .await;
             }
         }
         Some("fail") => {
@@ -479,10 +520,10 @@ async fn handle_canary_progress(
             if fail_count >= max_failures {
                 transition_phase(state, release, "rolling_back", Some(0), Some("unhealthy"))
                     .await?;
-                record_history(state, release, "rolled_back", "rolling_back", Some(0)).await;
+                record_history(state, release, "rolled_back", "rolling_back", Some(0), None).await;
             } else if release.phase == "progressing" {
                 transition_phase(state, release, "holding", None, Some("degraded")).await?;
-                record_history(state, release, "health_changed", "holding", None).await;
+                record_history(state, release, "health_changed", "holding", None, None).await;
             }
         }
         Some("inconclusive") => {
@@ -520,7 +561,7 @@ async fn handle_ab_test_progress(
     if elapsed == Some(true) {
         // Duration complete — move to promoting for manual review
         transition_phase(state, release, "promoting", Some(100), None).await?;
-        record_history(state, release, "promoted", "promoting", Some(100)).await;
+        record_history(state, release, "promoted", "promoting", Some(100), None).await;
     }
 
     Ok(())
@@ -528,7 +569,7 @@ async fn handle_ab_test_progress(
 
 /// Promoting — re-render manifests with canary as new stable, apply, finalize.
 async fn handle_promoting(state: &AppState, release: &PendingRelease) -> Result<(), DeployerError> {
-    let ns = target_namespace(&state.config, &release.namespace_slug, &release.environment);
+    let ns = release_namespace(&state.config, release);
 
     // For canary/AB: route 100% to stable (which now uses the canary image)
     if release.strategy != "rolling" {
@@ -537,42 +578,9 @@ async fn handle_promoting(state: &AppState, release: &PendingRelease) -> Result<
         // Re-render manifests with stable_image = canary_image (promotion)
         // Re-inject secrets (envFrom) since render_manifests produces raw templates
         let secrets_name = inject_project_secrets(state, release, &ns).await;
-        let (rendered, _sha) = render_manifests(state, release).await?;
-        let rendered = if let Some(ref sn) = secrets_name {
-            applier::inject_env_from_secret(&rendered, sn)?
-        } else {
-            rendered
-        };
-
-        // Re-inject proxy wrapper (same as handle_pending)
-        let rendered = if state.config.mesh_enabled {
-            let rendered = resolve_manifest_entrypoints(
-                &rendered,
-                &state.pool,
-                &state.minio,
-                state.config.registry_node_url.as_deref(),
-            )
-            .await;
-            let init_image = match state
-                .config
-                .registry_node_url
-                .as_deref()
-                .or(state.config.registry_url.as_deref())
-            {
-                Some(reg) => format!("{reg}/platform-proxy-init:v1"),
-                None => "platform-proxy-init:v1".into(),
-            };
-            applier::inject_proxy_wrapper(
-                &rendered,
-                &applier::ProxyInjectionConfig {
-                    platform_api_url: state.config.platform_api_url.clone(),
-                    init_image,
-                    mesh_strict_mtls: state.config.mesh_strict_mtls,
-                },
-            )?
-        } else {
-            rendered
-        };
+        let (rendered, _sha) =
+            render_manifests(state, release, &RenderOverrides::default()).await?;
+        let rendered = inject_runtime(state, rendered, secrets_name.as_deref()).await?;
 
         let _ = applier::apply_with_tracking(&state.kube, &rendered, &ns, Some(release.id)).await;
     }
@@ -595,7 +603,7 @@ async fn handle_promoting(state: &AppState, release: &PendingRelease) -> Result<
     );
 
     transition_phase(state, release, "completed", Some(100), Some("healthy")).await?;
-    record_history(state, release, "promoted", "completed", Some(100)).await;
+    record_history(state, release, "promoted", "completed", Some(100), None).await;
     fire_webhook(state, release, "deployed").await;
 
     let _ = crate::store::eventbus::publish(
@@ -616,23 +624,41 @@ async fn handle_rolling_back(
     state: &AppState,
     release: &PendingRelease,
 ) -> Result<(), DeployerError> {
-    let ns = target_namespace(&state.config, &release.namespace_slug, &release.environment);
+    let ns = release_namespace(&state.config, release);
 
-    // Route 100% traffic to stable (0% canary)
-    if release.strategy != "rolling" {
+    // Route 100% traffic to stable (0% canary). Rolling releases have no
+    // stable track, so put the last completed release's image back instead.
+    let detail = if release.strategy == "rolling" {
+        let stable = restore_stable_image(state, release).await?;
+        Some(serde_json::json!({ "rolled_back_to": stable }))
+    } else {
         apply_gateway_resources(state, release, &ns, 0).await;
-    }
+        None
+    };
 
     transition_phase(state, release, "rolled_back", Some(0), Some("unhealthy")).await?;
-    record_history(state, release, "rolled_back", "rolled_back", Some(0)).await;
+    record_history(
+        state,
+        release,
+        "rolled_back",
+        "rolled_back",
+        Some(0),
+        detail,
+    )
+    .await;
     fire_webhook(state, release, "rolled_back").await;
 
+    let reason = if release.strategy == "rolling" {
+        "deployment did not become healthy"
+    } else {
+        "rollback requested"
+    };
     let _ = crate::store::eventbus::publish(
         &state.valkey,
         &crate::store::eventbus::PlatformEvent::ReleaseRolledBack {
             release_id: release.id,
             project_id: release.project_id,
-            reason: "rollback requested".into(),
+            reason: reason.into(),
         },
     )
     .await;
@@ -640,6 +666,64 @@ async fn handle_rolling_back(
     Ok(())
 }
 
+/// A rolling release whose Deployment never became available: hand it to the
+/// `rolling_back` phase, which restores the previous known-good image.
+async fn start_auto_rollback(
+    state: &AppState,
+    release: &PendingRelease,
+    timeout_secs: u64,
+) -> Result<(), DeployerError> {
+    let reason = format!("deployment not available after {timeout_secs}s");
+    tracing::warn!(release_id = %release.id, %reason, "rolling release unhealthy, rolling back");
+    release_log(state, release, "warn", &format!("{reason}; rolling back")).await;
+
+    transition_phase(state, release, "rolling_back", None, Some("unhealthy")).await?;
+    record_history(
+        state,
+        release,
+        "health_changed",
+        "rolling_back",
+        None,
+        Some(serde_json::json!({ "reason": reason, "auto_rollback": true })),
+    )
+    .await;
+    state.deploy_notify.notify_one();
+    Ok(())
+}
+
+/// Point the release's Deployments back at the image of the target's last
+/// completed release. Returns that image, or `None` if there is none to restore.
+async fn restore_stable_image(
+    state: &AppState,
+    release: &PendingRelease,
+) -> Result<Option<String>, DeployerError> {
+    let Some(stable) = lookup_stable_image(&state.pool, release.target_id)
+        .await
+        .filter(|image| *image != release.image_ref)
+    else {
+        tracing::warn!(release_id = %release.id, "no previous release image to restore");
+        return Ok(None);
+    };
+
+    for deployment in release
+        .tracked_resources
+        .iter()
+        .filter(|r| r.kind == "Deployment")
+    {
+        applier::replace_image(
+            &state.kube,
+            &deployment.namespace,
+            &deployment.name,
+            &release.image_ref,
+            &stable,
+        )
+        .await?;
+    }
+
+    tracing::info!(release_id = %release.id, %stable, "restored previous image");
+    Ok(Some(stable))
+}
+
 // ---------------------------------------------------------------------------
 // Gateway API helpers
 // ---------------------------------------------------------------------------
@@ -793,28 +877,9 @@ async fn cleanup_expired_previews(state: &AppState) {
         let branch_slug: Option<String> = row.get("branch_slug");
         let namespace_slug: String = row.get("namespace_slug");
 
-        // Mark target inactive
-        let _ = sqlx::query("UPDATE deploy_targets SET is_active = false WHERE id = $1")
-            .bind(target_id)
-            .execute(&state.pool)
+        super::preview::teardown_preview(state, target_id, &namespace_slug, branch_slug.as_deref())
             .await;
 
-        // Cancel any active releases
-        let _ = sqlx::query(
-            "UPDATE deploy_releases SET phase = 'cancelled'
-             WHERE target_id = $1 AND phase NOT IN ('completed','rolled_back','cancelled','failed')",
-        )
-        .bind(target_id)
-        .execute(&state.pool)
-        .await;
-
-        // Delete K8s namespace
-        let slug = branch_slug.as_deref().unwrap_or("unknown");
-        let ns = target_namespace(&state.config, &namespace_slug, &format!("preview-{slug}"));
-        if let Err(e) = crate::deployer::namespace::delete_namespace(&state.kube, &ns).await {
-            tracing::warn!(error = %e, %target_id, "failed to delete preview namespace");
-        }
-
         tracing::info!(%project_id, %target_id, "expired preview target cleaned up");
     }
 }
@@ -871,10 +936,11 @@ async fn record_history(
     action: &str,
     phase: &str,
     traffic_weight: Option<i32>,
+    detail: Option<serde_json::Value>,
 ) {
     let _ = sqlx::query(
-        "INSERT INTO release_history (release_id, target_id, action, phase, traffic_weight, image_ref, actor_id)
-         VALUES ($1, $2, $3, $4, $5, $6, $7)",
+        "INSERT INTO release_history (release_id, target_id, action, phase, traffic_weight, image_ref, actor_id, detail)
+         VALUES ($1, $2, $3, $4, $5, $6, $7, $8)",
     )
     .bind(release.id)
     .bind(release.target_id)
@@ -883,12 +949,27 @@ async fn record_history(
     .bind(traffic_weight)
     .bind(&release.image_ref)
     .bind(release.deployed_by)
+    .bind(detail)
+    .execute(&state.pool)
+    .await;
+}
+
+/// Append to the release's log trail under its current attempt.
+async fn release_log(state: &AppState, release: &PendingRelease, level: &str, message: &str) {
+    let _ = sqlx::query!(
+        "INSERT INTO release_logs (release_id, attempt, level, message)
+         SELECT id, attempts, $2, $3 FROM deploy_releases WHERE id = $1",
+        release.id,
+        level,
+        message,
+    )
     .execute(&state.pool)
     .await;
 }
 
 /// Mark a release as failed.
 pub async fn mark_failed(state: &AppState, release: &PendingRelease, message: &str) {
+    release_log(state, release, "error", message).await;
     let _ = sqlx::query(
         "UPDATE deploy_releases SET phase = 'failed', health = 'unhealthy', completed_at = now() WHERE id = $1",
     )
@@ -932,7 +1013,7 @@ async fn create_user_secrets(
         && let Ok(mk) = crate::secrets::engine::parse_master_key(master_key_str)
     {
         let rows = sqlx::query(
-            "SELECT name, encrypted_value FROM secrets
+            "SELECT name, encrypted_value, backend FROM secrets
                  WHERE project_id = $1 AND scope IN ($3, 'all')
                    AND (environment IS NULL OR environment = $2)",
         )
@@ -943,24 +1024,37 @@ async fn create_user_secrets(
         .await
         .unwrap_or_default();
 
+        let mut accessed: Vec<String> = Vec::new();
         for row in &rows {
             let name: String = row.get("name");
             let encrypted: Vec<u8> = row.get("encrypted_value");
-            match crate::secrets::engine::decrypt(&encrypted, &mk, None) {
-                Ok(val) => {
-                    if let Ok(s) = String::from_utf8(val) {
-                        let k8s_name = secret_name_from_key(&name);
-                        let data = BTreeMap::from([("value".to_string(), s)]);
-                        apply_k8s_secret(state, namespace, &k8s_name, &release.project_id, &data)
-                            .await;
-                        created.push(k8s_name);
-                    }
+            let backend: String = row.get("backend");
+            match crate::secrets::engine::reveal(&mk, &backend, &encrypted).await {
+                Ok(s) => {
+                    let k8s_name = secret_name_from_key(&name);
+                    let data = BTreeMap::from([("value".to_string(), s)]);
+                    apply_k8s_secret(state, namespace, &k8s_name, &release.project_id, &data).await;
+                    created.push(k8s_name);
+                    accessed.push(name);
                 }
                 Err(e) => {
-                    tracing::warn!(error = %e, "failed to decrypt secret");
+                    tracing::warn!(error = %e, "failed to resolve secret");
                 }
             }
         }
+
+        crate::secrets::access::record_access(
+            &state.pool,
+            &state.audit_tx,
+            release.project_id,
+            release.deployed_by,
+            accessed.iter().map(String::as_str),
+            crate::secrets::access::AccessPurpose::Deploy {
+                release_id: release.id,
+                environment: &release.environment,
+            },
+        )
+        .await;
     }
 
     created
@@ -974,7 +1068,7 @@ async fn inject_platform_secret(
     namespace: &str,
 ) -> Option<String> {
     let mut env_data: BTreeMap<String, String> = BTreeMap::new();
-    let secret_name = format!("{namespace}-{}-platform", env_suffix(&release.environment));
+    let secret_name = platform_secret_name(namespace, &release.environment);
 
     inject_otel_env_vars(state, release, &mut env_data, namespace, &secret_name).await;
 
@@ -992,6 +1086,11 @@ async fn inject_platform_secret(
     Some(secret_name)
 }
 
+/// Name of the per-environment platform secret (OTEL + platform vars).
+fn platform_secret_name(namespace: &str, environment: &str) -> String {
+    format!("{namespace}-{}-platform", env_suffix(environment))
+}
+
 /// Query deploy-scoped secrets, decrypt, inject OTEL config, create K8s Secrets.
 ///
 /// Creates individual K8s Secrets per user secret, plus one platform secret
@@ -1456,13 +1555,51 @@ async fn apply_or_replace_secret(
     }
 }
 
+/// Render-time overrides for a release that has not been created (deploy diff).
+#[derive(Default)]
+struct RenderOverrides<'a> {
+    /// Read manifests from this ops repo branch instead of the environment's.
+    manifest_branch: Option<&'a str>,
+    /// Image to deploy, taking precedence over `image_ref` in the ops repo values.
+    image_ref: Option<&'a str>,
+    /// Render from the ops repo as it is on disk: no remote fetch, and no
+    /// kustomize build. Used by previews, which must not change anything.
+    read_only: bool,
+}
+
+/// Path and branch of the release's ops repo, fetched from its remote first
+/// unless `read_only`.
+async fn open_ops_repo(
+    state: &AppState,
+    ops_repo_id: Uuid,
+    read_only: bool,
+) -> Result<(std::path::PathBuf, String), DeployerError> {
+    if read_only {
+        return ops_repo::local_repo(&state.pool, ops_repo_id).await;
+    }
+    let master_key = state
+        .config
+        .master_key
+        .as_deref()
+        .and_then(|k| crate::secrets::engine::parse_master_key(k).ok());
+    let (repo_path, _sha, branch) = ops_repo::sync_repo(
+        &state.pool,
+        master_key.as_ref(),
+        ops_repo_id,
+        state.config.dev_mode,
+    )
+    .await?;
+    Ok((repo_path, branch))
+}
+
 /// Render manifests from ops repo or generate a basic one.
 async fn render_manifests(
     state: &AppState,
     release: &PendingRelease,
+    overrides: &RenderOverrides<'_>,
 ) -> Result<(String, Option<String>), DeployerError> {
     if let Some(ops_repo_id) = release.ops_repo_id {
-        let (repo_path, _sha, branch) = ops_repo::sync_repo(&state.pool, ops_repo_id).await?;
+        let (repo_path, branch) = open_ops_repo(state, ops_repo_id, overrides.read_only).await?;
 
         let ops = sqlx::query("SELECT name, path FROM ops_repos WHERE id = $1")
             .bind(ops_repo_id)
@@ -1492,18 +1629,6 @@ async fn render_manifests(
             sha
         };
 
-        // If manifest_path ends with '/', read all YAML files from the directory;
-        // otherwise read a single file.
-        let template_content = if manifest_path.ends_with('/') {
-            ops_repo::read_dir_yaml_at_ref(&repo_path, &sha, manifest_path)
-                .await
-                .map_err(|e| DeployerError::RenderFailed(e.to_string()))?
-        } else {
-            ops_repo::read_file_at_ref(&repo_path, &sha, manifest_path)
-                .await
-                .map_err(|e| DeployerError::RenderFailed(e.to_string()))?
-        };
-
         // Read values from the environment-specific branch (matches eventbus logic)
         let ops_values = ops_repo::read_values(&repo_path, values_branch, &release.environment)
             .await
@@ -1519,10 +1644,44 @@ async fn render_manifests(
             }
         }
 
+        if let (Some(image), Some(base)) = (overrides.image_ref, base_values.as_object_mut()) {
+            base.insert("image_ref".into(), serde_json::json!(image));
+        }
+
         let image_ref = base_values
             .get("image_ref")
             .and_then(|v| v.as_str())
             .map_or_else(|| release.image_ref.clone(), String::from);
+        let sha = match overrides.manifest_branch {
+            Some(branch) => ops_repo::get_branch_sha(&repo_path, branch).await?,
+            None => sha,
+        };
+
+        // Kustomize overlays are built as-is with the image injected by
+        // kustomize's image transformer; they are not templates.
+        if ops_repo::is_kustomization_at_ref(&repo_path, &sha, manifest_path).await {
+            if overrides.read_only {
+                return Err(DeployerError::RenderFailed(
+                    "kustomize overlays are built at deploy time and can't be previewed".into(),
+                ));
+            }
+            let built =
+                ops_repo::build_kustomization_at_ref(&repo_path, &sha, manifest_path, &image_ref)
+                    .await?;
+            return Ok((built, Some(sha)));
+        }
+
+        // If manifest_path ends with '/', read all YAML files from the directory;
+        // otherwise read a single file.
+        let template_content = if manifest_path.ends_with('/') {
+            ops_repo::read_dir_yaml_at_ref(&repo_path, &sha, manifest_path)
+                .await
+                .map_err(|e| DeployerError::RenderFailed(e.to_string()))?
+        } else {
+            ops_repo::read_file_at_ref(&repo_path, &sha, manifest_path)
+                .await
+                .map_err(|e| DeployerError::RenderFailed(e.to_string()))?
+        };
 
         let stable_image = Some(
             lookup_stable_image(&state.pool, release.target_id)
@@ -1585,6 +1744,137 @@ fn generate_basic_manifest(release: &PendingRelease) -> Result<String, DeployerE
     ))
 }
 
+/// Apply the platform's runtime injections to rendered manifests: `envFrom` for
+/// the platform secret and, with the mesh enabled, the proxy wrapper.
+async fn inject_runtime(
+    state: &AppState,
+    rendered: String,
+    secrets_name: Option<&str>,
+) -> Result<String, DeployerError> {
+    let rendered = if let Some(sn) = secrets_name {
+        applier::inject_env_from_secret(&rendered, sn)?
+    } else {
+        rendered
+    };
+
+    if !state.config.mesh_enabled {
+        return Ok(rendered);
+    }
+
+    // Resolve entrypoints for containers missing explicit `command` fields
+    // so the proxy wrapper can wrap them.
+    let rendered = resolve_manifest_entrypoints(
+        &rendered,
+        &state.pool,
+        &state.minio,
+        state.config.registry_node_url.as_deref(),
+    )
+    .await;
+
+    // Distroless init image: contains proxy binary + iptables, no shell
+    let init_image = match state
+        .config
+        .registry_node_url
+        .as_deref()
+        .or(state.config.registry_url.as_deref())
+    {
+        Some(reg) => format!("{reg}/platform-proxy-init:v1"),
+        None => "platform-proxy-init:v1".into(),
+    };
+    applier::inject_proxy_wrapper(
+        &rendered,
+        &applier::ProxyInjectionConfig {
+            platform_api_url: state.config.platform_api_url.clone(),
+            init_image,
+            mesh_strict_mtls: state.config.mesh_strict_mtls,
+        },
+    )
+}
+
+/// Diff what releasing `image_ref` to `target_id` would apply against the cluster.
+///
+/// Renders like a new pending release built from the target's latest applied
+/// release (values override, commit), then server-side dry-runs it. Nothing is created
+/// or applied, and no secrets are written.
+#[tracing::instrument(skip(state), fields(%target_id, %image_ref), err)]
+pub async fn diff_target(
+    state: &AppState,
+    target_id: Uuid,
+    image_ref: &str,
+    manifest_branch: Option<&str>,
+) -> Result<Vec<diff::ResourceDiff>, DeployerError> {
+    let row = sqlx::query!(
+        r#"SELECT dt.project_id, dt.default_strategy, dt.environment, dt.ops_repo_id,
+                dt.manifest_path, dt.branch_slug, dt.hostname as target_hostname,
+                p.name as project_name, p.namespace_slug,
+                r.id as "release_id?", r.commit_sha as "commit_sha?",
+                r.values_override as "values_override?",
+                r.tracked_resources as "tracked_resources?"
+         FROM deploy_targets dt
+         JOIN projects p ON p.id = dt.project_id
+         LEFT JOIN LATERAL (
+             SELECT id, commit_sha, values_override, tracked_resources
+             FROM deploy_releases WHERE target_id = dt.id AND phase <> 'pending'
+             ORDER BY created_at DESC LIMIT 1
+         ) r ON true
+         WHERE dt.id = $1"#,
+        target_id
+    )
+    .fetch_optional(&state.pool)
+    .await?
+    .ok_or(DeployerError::NotFound)?;
+
+    let current_release = row.release_id;
+    let tracked = row
+        .tracked_resources
+        .and_then(|v| serde_json::from_value(v).ok())
+        .unwrap_or_default();
+
+    let release = PendingRelease {
+        id: current_release.unwrap_or_else(Uuid::new_v4),
+        target_id,
+        project_id: row.project_id,
+        image_ref: image_ref.to_owned(),
+        commit_sha: row.commit_sha,
+        strategy: row.default_strategy,
+        phase: "pending".into(),
+        traffic_weight: 0,
+        current_step: 0,
+        rollout_config: serde_json::json!({}),
+        values_override: row.values_override,
+        deployed_by: None,
+        pipeline_id: None,
+        environment: row.environment,
+        ops_repo_id: row.ops_repo_id,
+        manifest_path: row.manifest_path,
+        branch_slug: row.branch_slug,
+        target_hostname: row.target_hostname,
+        project_name: row.project_name,
+        namespace_slug: row.namespace_slug,
+        tracked_resources: tracked,
+        skip_prune: false,
+    };
+
+    let ns = release_namespace(&state.config, &release);
+    let overrides = RenderOverrides {
+        manifest_branch,
+        image_ref: Some(image_ref),
+        read_only: true,
+    };
+    let (rendered, _sha) = render_manifests(state, &release, &overrides).await?;
+    let secret_name = platform_secret_name(&ns, &release.environment);
+    let rendered = inject_runtime(state, rendered, Some(&secret_name)).await?;
+
+    diff::diff_manifests(
+        &state.kube,
+        &rendered,
+        &ns,
+        current_release,
+        &release.tracked_resources,
+    )
+    .await
+}
+
 /// Store tracked resource inventory.
 async fn store_tracked_resources(
     state: &AppState,
@@ -1619,6 +1909,14 @@ async fn fire_webhook(state: &AppState, release: &PendingRelease, action: &str)
         &state.webhook_semaphore,
     )
     .await;
+
+    let message = crate::notify::chat::deploy_message(
+        &release.project_name,
+        &release.environment,
+        &release.image_ref,
+        action,
+    );
+    crate::notify::chat::post_event(state, release.project_id, "deploy", message).await;
 }
 
 // ---------------------------------------------------------------------------
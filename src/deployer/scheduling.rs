@@ -0,0 +1,225 @@
+// Copyright (c) 2026 Steven Hooker. Exclusively licensed to and distributed by AgentSphere GmbH.
+// SPDX-License-Identifier: BUSL-1.1
+
+//! Node placement (node selector + tolerations) for pipeline and agent pods.
+//!
+//! Configured with `PLATFORM_POD_NODE_SELECTOR` (`key=value,...`) and
+//! `PLATFORM_POD_TOLERATIONS` (`kubectl taint` syntax: `key[=value][:Effect],...`).
+
+use std::collections::BTreeMap;
+
+use k8s_openapi::api::core::v1::{Pod, Toleration};
+
+use crate::config::Config;
+
+const TAINT_EFFECTS: &[&str] = &["NoSchedule", "PreferNoSchedule", "NoExecute"];
+
+/// Parse `key=value,...`. Entries without `=` get an empty value and are
+/// caught by [`validate`] if the key is malformed.
+pub fn parse_node_selector(s: &str) -> BTreeMap<String, String> {
+    s.split(',')
+        .map(str::trim)
+        .filter(|e| !e.is_empty())
+        .map(|entry| match entry.split_once('=') {
+            Some((k, v)) => (k.trim().to_owned(), v.trim().to_owned()),
+            None => (entry.to_owned(), String::new()),
+        })
+        .collect()
+}
+
+/// Parse `key[=value][:Effect],...`. A toleration without a value uses the
+/// `Exists` operator; one without an effect tolerates every effect.
+pub fn parse_tolerations(s: &str) -> Vec<Toleration> {
+    s.split(',')
+        .map(str::trim)
+        .filter(|e| !e.is_empty())
+        .map(|entry| {
+            let (key_value, effect) = match entry.rsplit_once(':') {
+                Some((kv, effect)) => (kv, Some(effect.trim().to_owned())),
+                None => (entry, None),
+            };
+            let (key, operator, value) = match key_value.split_once('=') {
+                Some((k, v)) => (k.trim(), "Equal", Some(v.trim().to_owned())),
+                None => (key_value.trim(), "Exists", None),
+            };
+            Toleration {
+                key: Some(key.to_owned()),
+                operator: Some(operator.into()),
+                value,
+                effect,
+                ..Default::default()
+            }
+        })
+        .collect()
+}
+
+/// Check a label key: optional DNS subdomain prefix + `/` + name of at most 63 characters.
+fn check_label_key(key: &str) -> Result<(), String> {
+    let (prefix, name) = match key.split_once('/') {
+        Some((p, n)) => (Some(p), n),
+        None => (None, key),
+    };
+    if let Some(prefix) = prefix {
+        let valid_prefix = !prefix.is_empty()
+            && prefix.len() <= 253
+            && prefix.split('.').all(|part| {
+                !part.is_empty()
+                    && part
+                        .bytes()
+                        .all(|b| b.is_ascii_lowercase() || b.is_ascii_digit() || b == b'-')
+                    && !part.starts_with('-')
+                    && !part.ends_with('-')
+            });
+        if !valid_prefix {
+            return Err(format!("invalid label key prefix in '{key}'"));
+        }
+    }
+    if name.is_empty() {
+        return Err(format!("label key '{key}' has an empty name"));
+    }
+    check_label_value(name).map_err(|_| format!("invalid label key '{key}'"))
+}
+
+/// Check a label value: at most 63 alphanumerics, `-`, `_` or `.`, starting
+/// and ending with an alphanumeric. May be empty.
+fn check_label_value(value: &str) -> Result<(), String> {
+    let valid = value.is_empty()
+        || (value.len() <= 63
+            && value
+                .bytes()
+                .all(|b| b.is_ascii_alphanumeric() || matches!(b, b'-' | b'_' | b'.'))
+            && value.starts_with(|c: char| c.is_ascii_alphanumeric())
+            && value.ends_with(|c: char| c.is_ascii_alphanumeric()));
+    if valid {
+        Ok(())
+    } else {
+        Err(format!("invalid label value '{value}'"))
+    }
+}
+
+/// Validate the configured node selector and tolerations. Returns one error per bad entry.
+pub fn validate(config: &Config) -> Vec<String> {
+    let mut errors = Vec::new();
+    for (key, value) in &config.pod_node_selector {
+        if let Err(e) = check_label_key(key).and_then(|()| check_label_value(value)) {
+            errors.push(format!("PLATFORM_POD_NODE_SELECTOR: {e}"));
+        }
+    }
+    for t in &config.pod_tolerations {
+        let key = t.key.as_deref().unwrap_or_default();
+        let result = check_label_key(key)
+            .and_then(|()| check_label_value(t.value.as_deref().unwrap_or_default()))
+            .and_then(|()| match t.effect.as_deref() {
+                Some(effect) if !TAINT_EFFECTS.contains(&effect) => Err(format!(
+                    "invalid effect '{effect}' for '{key}' (expected one of {})",
+                    TAINT_EFFECTS.join(", ")
+                )),
+                _ => Ok(()),
+            });
+        if let Err(e) = result {
+            errors.push(format!("PLATFORM_POD_TOLERATIONS: {e}"));
+        }
+    }
+    errors
+}
+
+/// Apply the configured node selector and tolerations to a pod spec.
+pub fn apply(pod: &mut Pod, config: &Config) {
+    if config.pod_node_selector.is_empty() && config.pod_tolerations.is_empty() {
+        return;
+    }
+    let spec = pod.spec.get_or_insert_with(Default::default);
+    if !config.pod_node_selector.is_empty() {
+        spec.node_selector
+            .get_or_insert_with(BTreeMap::new)
+            .extend(config.pod_node_selector.clone());
+    }
+    if !config.pod_tolerations.is_empty() {
+        spec.tolerations
+            .get_or_insert_with(Vec::new)
+            .extend(config.pod_tolerations.iter().cloned());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_node_selector_pairs() {
+        let sel = parse_node_selector("pool=ci, kubernetes.io/arch = amd64,");
+        assert_eq!(sel.get("pool").map(String::as_str), Some("ci"));
+        assert_eq!(
+            sel.get("kubernetes.io/arch").map(String::as_str),
+            Some("amd64")
+        );
+        assert_eq!(sel.len(), 2);
+    }
+
+    #[test]
+    fn parse_tolerations_kubectl_syntax() {
+        let t = parse_tolerations("dedicated=ci:NoSchedule,gpu:NoExecute,spot");
+        assert_eq!(t.len(), 3);
+        assert_eq!(t[0].key.as_deref(), Some("dedicated"));
+        assert_eq!(t[0].operator.as_deref(), Some("Equal"));
+        assert_eq!(t[0].value.as_deref(), Some("ci"));
+        assert_eq!(t[0].effect.as_deref(), Some("NoSchedule"));
+        assert_eq!(t[1].operator.as_deref(), Some("Exists"));
+        assert!(t[1].value.is_none());
+        assert_eq!(t[1].effect.as_deref(), Some("NoExecute"));
+        assert!(t[2].effect.is_none());
+    }
+
+    #[test]
+    fn label_key_validation() {
+        assert!(check_label_key("pool").is_ok());
+        assert!(check_label_key("node.kubernetes.io/pool").is_ok());
+        assert!(check_label_key("").is_err());
+        assert!(check_label_key("pool:ci").is_err());
+        assert!(check_label_key("Bad_Prefix/pool").is_err());
+        assert!(check_label_key("example.com/").is_err());
+        assert!(check_label_key(&"a".repeat(64)).is_err());
+    }
+
+    #[test]
+    fn label_value_validation() {
+        assert!(check_label_value("").is_ok());
+        assert!(check_label_value("ci-pool_1.a").is_ok());
+        assert!(check_label_value("-ci").is_err());
+        assert!(check_label_value("has space").is_err());
+    }
+
+    #[test]
+    fn validate_reports_bad_entries() {
+        let mut config = Config::test_default();
+        config.pod_node_selector = parse_node_selector("pool=ci,bad key=x");
+        config.pod_tolerations = parse_tolerations("dedicated=ci:NoSchedule,x=y:Sometimes");
+        let errors = validate(&config);
+        assert_eq!(errors.len(), 2, "{errors:?}");
+        assert!(errors[0].starts_with("PLATFORM_POD_NODE_SELECTOR"));
+        assert!(errors[1].contains("Sometimes"));
+    }
+
+    #[test]
+    fn apply_sets_selector_and_tolerations() {
+        let mut config = Config::test_default();
+        config.pod_node_selector = parse_node_selector("pool=ci");
+        config.pod_tolerations = parse_tolerations("dedicated=ci:NoSchedule");
+        let mut pod = Pod::default();
+        apply(&mut pod, &config);
+        let spec = pod.spec.unwrap();
+        assert_eq!(
+            spec.node_selector.unwrap().get("pool").map(String::as_str),
+            Some("ci")
+        );
+        assert_eq!(spec.tolerations.unwrap().len(), 1);
+    }
+
+    #[test]
+    fn apply_without_config_is_noop() {
+        let config = Config::test_default();
+        let mut pod = Pod::default();
+        apply(&mut pod, &config);
+        assert!(pod.spec.is_none());
+    }
+}
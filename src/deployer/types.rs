@@ -24,6 +24,8 @@ use std::fmt;
 ///   ▼              ▼            ▼
 /// cancelled    rolling_back  rolling_back ──► rolled_back
 ///
+/// pending ──► rolling_back (rolling deploy not healthy within the health timeout)
+///
 /// Any non-terminal ──► failed (unrecoverable error)
 /// ```
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, serde::Serialize, serde::Deserialize)]
@@ -91,8 +93,9 @@ impl ReleasePhase {
         }
         matches!(
             (self, next),
-            // Start (Completed is fast-path for rolling deploys)
-            (Self::Pending, Self::Progressing | Self::Completed | Self::Cancelled)
+            // Start (Completed is fast-path for rolling deploys; RollingBack when
+            // a rolling deploy never becomes healthy)
+            (Self::Pending, Self::Progressing | Self::Completed | Self::Cancelled | Self::RollingBack)
             // Normal flow
             | (Self::Progressing, Self::Holding | Self::Paused | Self::Promoting | Self::RollingBack)
             // Hold → retry or escalate
@@ -380,6 +383,11 @@ mod tests {
         assert!(ReleasePhase::Pending.can_transition_to(ReleasePhase::Completed));
     }
 
+    #[test]
+    fn pending_can_rollback_on_failed_health() {
+        assert!(ReleasePhase::Pending.can_transition_to(ReleasePhase::RollingBack));
+    }
+
     #[test]
     fn pending_cannot_promote() {
         assert!(!ReleasePhase::Pending.can_transition_to(ReleasePhase::Promoting));
@@ -768,11 +776,6 @@ mod tests {
         assert!(!ReleasePhase::Pending.can_transition_to(ReleasePhase::Holding));
     }
 
-    #[test]
-    fn pending_cannot_rollback() {
-        assert!(!ReleasePhase::Pending.can_transition_to(ReleasePhase::RollingBack));
-    }
-
     #[test]
     fn pending_cannot_pause() {
         assert!(!ReleasePhase::Pending.can_transition_to(ReleasePhase::Paused));
@@ -28,6 +28,12 @@ pub enum ApiError {
     #[error("too many requests")]
     TooManyRequests,
 
+    #[error("locked: {0}")]
+    Locked(String),
+
+    #[error("quota exceeded: {0}")]
+    QuotaExceeded(String),
+
     #[error("bad gateway: {0}")]
     BadGateway(String),
 
@@ -38,42 +44,59 @@ pub enum ApiError {
     Internal(#[from] anyhow::Error),
 }
 
+impl ApiError {
+    /// Stable machine-readable error code, returned as `code` in the JSON body.
+    ///
+    /// Clients branch on this rather than on the human-readable message, so
+    /// existing codes must never change meaning.
+    pub fn code(&self) -> &'static str {
+        match self {
+            Self::NotFound(_) => "not_found",
+            Self::Unauthorized => "unauthorized",
+            Self::Forbidden => "forbidden",
+            Self::BadRequest(_) => "bad_request",
+            Self::Conflict(_) => "conflict",
+            Self::Validation(_) => "validation_failed",
+            Self::TooManyRequests => "rate_limited",
+            Self::Locked(_) => "locked",
+            Self::QuotaExceeded(_) => "quota_exceeded",
+            Self::BadGateway(_) => "bad_gateway",
+            Self::ServiceUnavailable(_) => "service_unavailable",
+            Self::Internal(_) => "internal",
+        }
+    }
+}
+
 impl IntoResponse for ApiError {
     fn into_response(self) -> Response {
-        let (status, body) = match &self {
-            Self::NotFound(msg) => (StatusCode::NOT_FOUND, serde_json::json!({ "error": msg })),
-            Self::Unauthorized => (
-                StatusCode::UNAUTHORIZED,
-                serde_json::json!({ "error": "unauthorized" }),
-            ),
-            Self::Forbidden => (
-                StatusCode::FORBIDDEN,
-                serde_json::json!({ "error": "forbidden" }),
-            ),
-            Self::BadRequest(msg) => (StatusCode::BAD_REQUEST, serde_json::json!({ "error": msg })),
-            Self::Conflict(msg) => (StatusCode::CONFLICT, serde_json::json!({ "error": msg })),
-            Self::TooManyRequests => (
-                StatusCode::TOO_MANY_REQUESTS,
-                serde_json::json!({ "error": "too many requests" }),
-            ),
-            Self::Validation(errors) => (
-                StatusCode::UNPROCESSABLE_ENTITY,
-                serde_json::json!({ "error": "validation error", "fields": errors }),
-            ),
-            Self::BadGateway(msg) => (StatusCode::BAD_GATEWAY, serde_json::json!({ "error": msg })),
-            Self::ServiceUnavailable(msg) => (
-                StatusCode::SERVICE_UNAVAILABLE,
-                serde_json::json!({ "error": msg }),
-            ),
+        let (status, message) = match &self {
+            Self::NotFound(msg) => (StatusCode::NOT_FOUND, msg.as_str()),
+            Self::Unauthorized => (StatusCode::UNAUTHORIZED, "unauthorized"),
+            Self::Forbidden => (StatusCode::FORBIDDEN, "forbidden"),
+            Self::BadRequest(msg) => (StatusCode::BAD_REQUEST, msg.as_str()),
+            Self::Conflict(msg) => (StatusCode::CONFLICT, msg.as_str()),
+            Self::TooManyRequests => (StatusCode::TOO_MANY_REQUESTS, "too many requests"),
+            Self::Locked(msg) => (StatusCode::LOCKED, msg.as_str()),
+            Self::QuotaExceeded(msg) => (StatusCode::TOO_MANY_REQUESTS, msg.as_str()),
+            Self::Validation(_) => (StatusCode::UNPROCESSABLE_ENTITY, "validation error"),
+            Self::BadGateway(msg) => (StatusCode::BAD_GATEWAY, msg.as_str()),
+            Self::ServiceUnavailable(msg) => (StatusCode::SERVICE_UNAVAILABLE, msg.as_str()),
             Self::Internal(err) => {
                 tracing::error!(error = %err, "internal server error");
-                (
-                    StatusCode::INTERNAL_SERVER_ERROR,
-                    serde_json::json!({ "error": "internal server error" }),
-                )
+                (StatusCode::INTERNAL_SERVER_ERROR, "internal server error")
             }
         };
 
+        // `error` predates `code`/`message` and is kept for existing clients.
+        let mut body = serde_json::json!({
+            "error": message,
+            "code": self.code(),
+            "message": message,
+        });
+        if let Self::Validation(errors) = &self {
+            body["fields"] = serde_json::json!(errors);
+        }
+
         (status, axum::Json(body)).into_response()
     }
 }
@@ -164,6 +187,29 @@ mod tests {
         assert_eq!(resp.status(), StatusCode::TOO_MANY_REQUESTS);
     }
 
+    #[tokio::test]
+    async fn quota_exceeded_returns_429_with_message() {
+        let resp = ApiError::QuotaExceeded("limit of 5 reached".into()).into_response();
+        assert_eq!(resp.status(), StatusCode::TOO_MANY_REQUESTS);
+        let body = axum::body::to_bytes(resp.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let json: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        assert_eq!(json["error"], "limit of 5 reached");
+    }
+
+    #[tokio::test]
+    async fn locked_returns_423_with_message() {
+        let resp =
+            ApiError::Locked("account locked, try again in 15 minutes".into()).into_response();
+        assert_eq!(resp.status(), StatusCode::LOCKED);
+        let body = axum::body::to_bytes(resp.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let json: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        assert_eq!(json["error"], "account locked, try again in 15 minutes");
+    }
+
     #[test]
     fn validation_returns_422() {
         let resp = ApiError::Validation(vec!["field".into()]).into_response();
@@ -223,6 +269,62 @@ mod tests {
         );
     }
 
+    #[tokio::test]
+    async fn not_found_body_has_code_and_message() {
+        let resp = ApiError::NotFound("project".into()).into_response();
+        let body = axum::body::to_bytes(resp.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let json: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        assert_eq!(json["code"], "not_found");
+        assert_eq!(json["message"], "project");
+        assert_eq!(json["error"], "project");
+    }
+
+    #[tokio::test]
+    async fn validation_body_has_code_and_fields() {
+        let resp = ApiError::Validation(vec!["name required".into()]).into_response();
+        let body = axum::body::to_bytes(resp.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let json: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        assert_eq!(json["code"], "validation_failed");
+        assert_eq!(json["message"], "validation error");
+        assert_eq!(json["fields"][0], "name required");
+    }
+
+    #[tokio::test]
+    async fn internal_body_has_code_without_details() {
+        let resp = ApiError::Internal(anyhow::anyhow!("secret info")).into_response();
+        let body = axum::body::to_bytes(resp.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let json: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        assert_eq!(json["code"], "internal");
+        assert_eq!(json["message"], "internal server error");
+    }
+
+    #[test]
+    fn error_codes_are_distinct() {
+        let errors = [
+            ApiError::NotFound(String::new()),
+            ApiError::Unauthorized,
+            ApiError::Forbidden,
+            ApiError::BadRequest(String::new()),
+            ApiError::Conflict(String::new()),
+            ApiError::Validation(vec![]),
+            ApiError::TooManyRequests,
+            ApiError::Locked(String::new()),
+            ApiError::QuotaExceeded(String::new()),
+            ApiError::BadGateway(String::new()),
+            ApiError::ServiceUnavailable(String::new()),
+            ApiError::Internal(anyhow::anyhow!("x")),
+        ];
+        let codes: std::collections::HashSet<&str> = errors.iter().map(ApiError::code).collect();
+        assert_eq!(codes.len(), errors.len());
+        assert_eq!(ApiError::TooManyRequests.code(), "rate_limited");
+    }
+
     // -- sqlx error conversion tests --
 
     #[test]
@@ -0,0 +1,148 @@
+// Copyright (c) 2026 Steven Hooker. Exclusively licensed to and distributed by AgentSphere GmbH.
+// SPDX-License-Identifier: BUSL-1.1
+
+//! Platform feature switches for gradual rollout of endpoints.
+//!
+//! Each [`Feature`] has a built-in default. Admins can replace it globally
+//! (`platform_features`) and per project or user (`platform_feature_overrides`)
+//! through `/api/admin/features`. The resolved state is cached in Valkey per
+//! project and user, and a feature's entries are dropped on every admin change.
+//!
+//! These gate the platform's own endpoints; the project flags that apps
+//! evaluate live in `api::flags`.
+
+use uuid::Uuid;
+
+use crate::error::ApiError;
+use crate::store::{AppState, valkey};
+
+/// How long a resolved state is cached when nothing invalidates it.
+const CACHE_TTL_SECS: i64 = 60;
+
+/// A feature that can be switched at runtime.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Feature {
+    /// `GET /api/projects/{id}/search/code`.
+    CodeSearch,
+}
+
+impl Feature {
+    pub const ALL: [Self; 1] = [Self::CodeSearch];
+
+    pub fn key(self) -> &'static str {
+        match self {
+            Self::CodeSearch => "code_search",
+        }
+    }
+
+    pub fn parse(key: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|f| f.key() == key)
+    }
+
+    pub fn description(self) -> &'static str {
+        match self {
+            Self::CodeSearch => "Full-text code search over a project's repository",
+        }
+    }
+
+    /// State when no admin has set one.
+    pub fn default_enabled(self) -> bool {
+        match self {
+            Self::CodeSearch => true,
+        }
+    }
+
+    /// Cached states for this feature, one per project and user.
+    fn cache_pattern(self) -> String {
+        format!("feature:{}:*", self.key())
+    }
+}
+
+/// A user override wins over a project override, which wins over the global
+/// state, which wins over the feature's default.
+fn resolve(
+    feature: Feature,
+    user: Option<bool>,
+    project: Option<bool>,
+    global: Option<bool>,
+) -> bool {
+    user.or(project)
+        .or(global)
+        .unwrap_or_else(|| feature.default_enabled())
+}
+
+/// Whether `feature` is on for `user_id`, within `project_id` if given.
+pub async fn is_enabled(
+    app: &AppState,
+    feature: Feature,
+    project_id: Option<Uuid>,
+    user_id: Uuid,
+) -> Result<bool, ApiError> {
+    let scope = project_id.map_or_else(|| "-".to_owned(), |id| id.to_string());
+    let key = format!("feature:{}:{scope}:{user_id}", feature.key());
+    if let Some(cached) = valkey::get_cached::<bool>(&app.valkey, &key).await {
+        return Ok(cached);
+    }
+
+    let row = sqlx::query!(
+        r#"SELECT
+             (SELECT enabled FROM platform_feature_overrides
+              WHERE feature = $1 AND user_id = $3) AS "user?",
+             (SELECT enabled FROM platform_feature_overrides
+              WHERE feature = $1 AND project_id = $2) AS "project?",
+             (SELECT enabled FROM platform_features WHERE key = $1) AS "global?""#,
+        feature.key(),
+        project_id,
+        user_id,
+    )
+    .fetch_one(&app.pool)
+    .await?;
+    let enabled = resolve(feature, row.user, row.project, row.global);
+
+    let _ = valkey::set_cached(&app.valkey, &key, &enabled, CACHE_TTL_SECS).await;
+    Ok(enabled)
+}
+
+/// Refuse with 404 when `feature` is off, so gated endpoints look absent.
+pub async fn require(
+    app: &AppState,
+    feature: Feature,
+    project_id: Option<Uuid>,
+    user_id: Uuid,
+) -> Result<(), ApiError> {
+    if is_enabled(app, feature, project_id, user_id).await? {
+        Ok(())
+    } else {
+        Err(ApiError::NotFound(feature.key().replace('_', " ")))
+    }
+}
+
+/// Drop the cached states after an admin change.
+pub async fn invalidate(app: &AppState, feature: Feature) {
+    if let Err(e) = valkey::invalidate_pattern(&app.valkey, &feature.cache_pattern()).await {
+        tracing::warn!(error = %e, feature = feature.key(), "failed to invalidate feature cache");
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn keys_roundtrip() {
+        for feature in Feature::ALL {
+            assert_eq!(Feature::parse(feature.key()), Some(feature));
+        }
+        assert_eq!(Feature::parse("nope"), None);
+    }
+
+    #[test]
+    fn narrower_setting_wins() {
+        let f = Feature::CodeSearch;
+        assert!(resolve(f, None, None, None));
+        assert!(!resolve(f, None, None, Some(false)));
+        assert!(resolve(f, None, Some(true), Some(false)));
+        assert!(!resolve(f, Some(false), Some(true), Some(true)));
+        assert!(resolve(f, Some(true), None, Some(false)));
+    }
+}
@@ -4,19 +4,40 @@
 use std::path::PathBuf;
 use std::time::Duration;
 
+use axum::body::Body;
 use axum::extract::{Path, Query, State};
+use axum::http::{HeaderMap, HeaderValue, StatusCode, header};
+use axum::response::{IntoResponse, Response};
 use axum::routing::get;
 use axum::{Json, Router};
 use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
 use sqlx::PgPool;
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio_util::io::ReaderStream;
 use uuid::Uuid;
 
 const GIT_TIMEOUT: Duration = Duration::from_secs(30);
 
+/// Largest blob returned inline by the JSON blob endpoint; bigger files are
+/// downloaded through the streaming raw endpoint.
+const MAX_BLOB_SIZE: u64 = 50 * 1024 * 1024;
+
+/// Maximum matches returned by a code search.
+const MAX_SEARCH_RESULTS: usize = 100;
+/// Most `git grep` output read per search.
+const MAX_GREP_OUTPUT: u64 = 1024 * 1024;
+/// Longest snippet (in chars) returned per search match.
+const MAX_SNIPPET_CHARS: usize = 300;
+/// Code search results are keyed by commit SHA, so they never go stale.
+const SEARCH_CACHE_TTL_SECS: i64 = 600;
+
 use ts_rs::TS;
 
+use crate::api::pipelines::sanitize_filename;
 use crate::auth::middleware::AuthUser;
 use crate::error::ApiError;
+use crate::features::{self, Feature};
 use crate::git::signature::{self, SignatureInfo, SignatureStatus};
 use crate::rbac::{Permission, resolver};
 use crate::store::AppState;
@@ -70,6 +91,63 @@ pub struct CommitInfo {
     pub signature: Option<SignatureInfo>,
 }
 
+#[derive(Debug, Serialize, TS)]
+#[ts(export)]
+pub struct DiffHunk {
+    pub header: String,
+    pub old_start: u32,
+    pub old_lines: u32,
+    pub new_start: u32,
+    pub new_lines: u32,
+    pub lines: Vec<String>,
+}
+
+#[derive(Debug, Serialize, TS)]
+#[ts(export)]
+pub struct DiffFile {
+    pub path: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[ts(optional)]
+    pub old_path: Option<String>,
+    pub status: String, // "added", "modified", "deleted", "renamed"
+    #[ts(type = "number")]
+    pub additions: i64,
+    #[ts(type = "number")]
+    pub deletions: i64,
+    pub binary: bool,
+    pub hunks: Vec<DiffHunk>,
+}
+
+#[derive(Debug, Serialize, TS)]
+#[ts(export)]
+pub struct CompareResponse {
+    pub base: String,
+    pub head: String,
+    pub files: Vec<DiffFile>,
+    #[ts(type = "number")]
+    pub total_additions: i64,
+    #[ts(type = "number")]
+    pub total_deletions: i64,
+}
+
+#[derive(Debug, Serialize, Deserialize, TS)]
+#[ts(export)]
+pub struct CodeSearchMatch {
+    pub path: String,
+    pub line: u32,
+    pub snippet: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, TS)]
+#[ts(export)]
+pub struct CodeSearchResponse {
+    /// Commit the search ran against.
+    pub commit_sha: String,
+    pub matches: Vec<CodeSearchMatch>,
+    /// More matches exist beyond the result cap.
+    pub truncated: bool,
+}
+
 #[derive(Debug, Deserialize)]
 pub struct TreeQuery {
     #[serde(rename = "ref", default = "default_ref")]
@@ -85,6 +163,12 @@ pub struct BlobQuery {
     pub path: String,
 }
 
+#[derive(Debug, Deserialize)]
+pub struct RawQuery {
+    /// `1` or `true` to serve the file as an attachment instead of inline.
+    pub download: Option<String>,
+}
+
 #[derive(Debug, Deserialize)]
 pub struct CommitsQuery {
     #[serde(rename = "ref", default = "default_ref")]
@@ -94,6 +178,19 @@ pub struct CommitsQuery {
     pub verify_signatures: bool,
 }
 
+#[derive(Debug, Deserialize)]
+pub struct CompareQuery {
+    pub base: String,
+    pub head: String,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct CodeSearchQuery {
+    pub q: String,
+    #[serde(rename = "ref", default = "default_ref")]
+    pub git_ref: String,
+}
+
 fn default_ref() -> String {
     "HEAD".to_owned()
 }
@@ -106,12 +203,17 @@ pub fn router() -> Router<AppState> {
     Router::new()
         .route("/api/projects/{id}/tree", get(tree))
         .route("/api/projects/{id}/blob", get(blob))
+        .route("/api/projects/{id}/raw", get(raw))
+        .route("/api/projects/{id}/raw/{ref}/{*path}", get(raw_at_ref))
         .route("/api/projects/{id}/branches", get(branches))
         .route("/api/projects/{id}/commits", get(commits))
         .route("/api/projects/{id}/commits/{sha}", get(commit_detail))
+        .route("/api/projects/{id}/compare", get(compare))
+        .route("/api/projects/{id}/search/code", get(search_code))
         // Ops repo browsing (same interface, different backing repo)
         .route("/api/projects/{id}/ops-repo/tree", get(ops_tree))
         .route("/api/projects/{id}/ops-repo/blob", get(ops_blob))
+        .route("/api/projects/{id}/ops-repo/raw", get(ops_raw))
         .route("/api/projects/{id}/ops-repo/branches", get(ops_branches))
 }
 
@@ -129,6 +231,7 @@ fn validate_git_ref(git_ref: &str) -> Result<(), ApiError> {
         || git_ref.contains('\n')
         || git_ref.contains('\0')
         || git_ref.contains(' ')
+        || git_ref.starts_with('-')
     {
         return Err(ApiError::BadRequest("invalid git ref".into()));
     }
@@ -142,6 +245,24 @@ fn validate_path(path: &str) -> Result<(), ApiError> {
     Ok(())
 }
 
+/// Code search terms are passed to `git grep` as a fixed string after `-e`, so
+/// they cannot be read as options; this only bounds size and rejects control
+/// characters git cannot match across.
+fn validate_search_query(q: &str) -> Result<(), ApiError> {
+    if q.trim().is_empty() {
+        return Err(ApiError::BadRequest("q is required".into()));
+    }
+    if q.len() > 200 {
+        return Err(ApiError::BadRequest("q must be at most 200 bytes".into()));
+    }
+    if q.chars().any(char::is_control) {
+        return Err(ApiError::BadRequest(
+            "q must not contain control characters".into(),
+        ));
+    }
+    Ok(())
+}
+
 // ---------------------------------------------------------------------------
 // Shared helpers
 // ---------------------------------------------------------------------------
@@ -261,51 +382,40 @@ async fn git_ls_tree(
     Ok(parse_ls_tree(&String::from_utf8_lossy(&output.stdout)))
 }
 
+/// Read blob `sha` in full for the JSON blob endpoint; callers check its size
+/// first (see [`git_blob_info`]).
 async fn git_show_blob(
     repo_path: &std::path::Path,
-    git_ref: &str,
+    sha: &str,
     path: &str,
 ) -> Result<BlobResponse, ApiError> {
-    const MAX_BLOB_SIZE: usize = 50 * 1024 * 1024;
-    let clean_path = path.trim_start_matches('/');
-    let object_spec = format!("{git_ref}:{clean_path}");
-
     let output = tokio::time::timeout(GIT_TIMEOUT, {
         tokio::process::Command::new("git")
             .arg("-C")
             .arg(repo_path)
-            .arg("show")
-            .arg(&object_spec)
+            .arg("cat-file")
+            .arg("blob")
+            .arg(sha)
             .output()
     })
     .await
-    .map_err(|_| ApiError::Internal(anyhow::anyhow!("git show timed out after 30s")))?
-    .map_err(|e| ApiError::Internal(anyhow::anyhow!("failed to run git show: {e}")))?;
+    .map_err(|_| ApiError::Internal(anyhow::anyhow!("git cat-file timed out after 30s")))?
+    .map_err(|e| ApiError::Internal(anyhow::anyhow!("failed to run git cat-file: {e}")))?;
 
     if !output.status.success() {
         let stderr = String::from_utf8_lossy(&output.stderr);
-        if stderr.contains("does not exist") || stderr.contains("not a valid object") {
-            return Err(ApiError::NotFound("blob".into()));
-        }
         return Err(ApiError::Internal(anyhow::anyhow!(
-            "git show failed: {stderr}"
-        )));
-    }
-
-    if output.stdout.len() > MAX_BLOB_SIZE {
-        return Err(ApiError::BadRequest(format!(
-            "file too large: {} bytes (max {MAX_BLOB_SIZE})",
-            output.stdout.len()
+            "git cat-file failed: {stderr}"
         )));
     }
 
     #[allow(clippy::cast_possible_wrap)]
     let size = output.stdout.len() as i64;
 
-    let (content, encoding) = match String::from_utf8(output.stdout.clone()) {
+    let (content, encoding) = match String::from_utf8(output.stdout) {
         Ok(text) => (text, "utf-8".to_owned()),
-        Err(_) => (
-            base64::Engine::encode(&base64::engine::general_purpose::STANDARD, &output.stdout),
+        Err(e) => (
+            base64::Engine::encode(&base64::engine::general_purpose::STANDARD, e.as_bytes()),
             "base64".to_owned(),
         ),
     };
@@ -318,6 +428,262 @@ async fn git_show_blob(
     })
 }
 
+/// Resolve `spec` (`ref:path`) to a blob, returning its SHA and size in bytes.
+/// Missing paths and non-blob objects (directories) are `NotFound`.
+async fn git_blob_info(repo_path: &std::path::Path, spec: &str) -> Result<(String, u64), ApiError> {
+    let mut child = tokio::process::Command::new("git")
+        .arg("-C")
+        .arg(repo_path)
+        .arg("cat-file")
+        .arg("--batch-check")
+        .stdin(std::process::Stdio::piped())
+        .stdout(std::process::Stdio::piped())
+        .stderr(std::process::Stdio::piped())
+        .spawn()
+        .map_err(|e| ApiError::Internal(anyhow::anyhow!("failed to spawn git cat-file: {e}")))?;
+
+    // batch-check reads object names from stdin, so the spec can't be
+    // mistaken for an option
+    let mut stdin = child.stdin.take().expect("stdin piped");
+    stdin
+        .write_all(format!("{spec}\n").as_bytes())
+        .await
+        .map_err(|e| ApiError::Internal(anyhow::anyhow!("failed to write to git cat-file: {e}")))?;
+    drop(stdin);
+
+    let output = tokio::time::timeout(GIT_TIMEOUT, child.wait_with_output())
+        .await
+        .map_err(|_| ApiError::Internal(anyhow::anyhow!("git cat-file timed out after 30s")))?
+        .map_err(|e| ApiError::Internal(anyhow::anyhow!("failed to run git cat-file: {e}")))?;
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(ApiError::Internal(anyhow::anyhow!(
+            "git cat-file failed: {stderr}"
+        )));
+    }
+
+    parse_batch_check(&String::from_utf8_lossy(&output.stdout))
+        .ok_or_else(|| ApiError::NotFound("blob".into()))
+}
+
+/// Resolve `spec` (e.g. `main^{tree}` or `main:src/lib.rs`) to its object SHA.
+///
+/// Git objects are content-addressed, so the SHA is a strong `ETag` for tree and
+/// blob responses. Returns `None` if the spec does not resolve; the caller's
+/// regular git command then reports the proper error.
+async fn git_object_sha(repo_path: &std::path::Path, spec: &str) -> Option<String> {
+    let output = tokio::time::timeout(GIT_TIMEOUT, {
+        tokio::process::Command::new("git")
+            .arg("-C")
+            .arg(repo_path)
+            .arg("rev-parse")
+            .arg("--verify")
+            .arg("--quiet")
+            .arg(spec)
+            .output()
+    })
+    .await
+    .ok()?
+    .ok()?;
+
+    if !output.status.success() {
+        return None;
+    }
+    let sha = String::from_utf8_lossy(&output.stdout).trim().to_owned();
+    (!sha.is_empty()).then_some(sha)
+}
+
+/// Object spec for a tree listing: the root tree of `git_ref`, or `git_ref:path`.
+fn tree_spec(git_ref: &str, path: &str) -> String {
+    if path.is_empty() || path == "/" {
+        format!("{git_ref}^{{tree}}")
+    } else {
+        format!("{git_ref}:{}", path.trim_start_matches('/'))
+    }
+}
+
+/// Whether an `If-None-Match` header matches `etag` (weak comparison, per RFC 9110).
+fn etag_matches(headers: &HeaderMap, etag: &str) -> bool {
+    let Some(value) = headers
+        .get(header::IF_NONE_MATCH)
+        .and_then(|v| v.to_str().ok())
+    else {
+        return false;
+    };
+    value.split(',').map(str::trim).any(|candidate| {
+        candidate == "*" || candidate.strip_prefix("W/").unwrap_or(candidate) == etag
+    })
+}
+
+/// `304 Not Modified` (empty body) if the client already holds object `sha`.
+fn not_modified(headers: &HeaderMap, sha: Option<&str>) -> Option<Response> {
+    let etag = format!("\"{}\"", sha?);
+    etag_matches(headers, &etag).then(|| with_etag(StatusCode::NOT_MODIFIED, sha))
+}
+
+/// Attach an `ETag` for object `sha`, with `Cache-Control: private, no-cache` so
+/// browsers revalidate instead of re-downloading.
+fn with_etag(response: impl IntoResponse, sha: Option<&str>) -> Response {
+    let mut response = response.into_response();
+    if let Some(Ok(etag)) = sha.map(|sha| HeaderValue::from_str(&format!("\"{sha}\""))) {
+        let headers = response.headers_mut();
+        headers.insert(header::ETAG, etag);
+        headers.insert(
+            header::CACHE_CONTROL,
+            HeaderValue::from_static("private, no-cache"),
+        );
+    }
+    response
+}
+
+/// Whether a `Range` request may be honoured: true without `If-Range`, or when
+/// `If-Range` carries the blob's current `ETag`. Date validators never match, so
+/// the client gets the full blob.
+fn if_range_matches(headers: &HeaderMap, sha: &str) -> bool {
+    headers
+        .get(header::IF_RANGE)
+        .and_then(|v| v.to_str().ok())
+        .is_none_or(|value| value.trim() == format!("\"{sha}\""))
+}
+
+/// `Content-Type` for a raw file, from its extension or else its first bytes
+/// (`head`). Formats a browser could execute (HTML, JS) are served as plain
+/// text; SVG keeps its type so `<img>` renders it, with scripts blocked by the
+/// response's CSP.
+fn raw_content_type(path: &str, head: &[u8]) -> &'static str {
+    let name = path.rsplit('/').next().unwrap_or_default();
+    let ext = name
+        .rsplit_once('.')
+        .map(|(_, ext)| ext.to_ascii_lowercase())
+        .unwrap_or_default();
+    match ext.as_str() {
+        "png" => return "image/png",
+        "jpg" | "jpeg" => return "image/jpeg",
+        "gif" => return "image/gif",
+        "webp" => return "image/webp",
+        "svg" => return "image/svg+xml",
+        "ico" => return "image/x-icon",
+        "pdf" => return "application/pdf",
+        "json" => return "application/json",
+        "zip" => return "application/zip",
+        "tar" => return "application/x-tar",
+        "gz" | "tgz" => return "application/gzip",
+        "txt" | "md" | "log" | "csv" | "yaml" | "yml" | "toml" | "rs" | "py" | "go" | "ts"
+        | "js" | "html" | "htm" | "css" | "xml" | "sh" => return "text/plain; charset=utf-8",
+        _ => {}
+    }
+    sniff_content_type(head)
+}
+
+/// Bytes read from the start of a blob to sniff its type.
+const SNIFF_LEN: usize = 512;
+
+/// Guess a content type from a file's magic bytes; UTF-8 without NULs is text.
+fn sniff_content_type(head: &[u8]) -> &'static str {
+    const MAGIC: &[(&[u8], &str)] = &[
+        (b"\x89PNG\r\n\x1a\n", "image/png"),
+        (b"\xff\xd8\xff", "image/jpeg"),
+        (b"GIF87a", "image/gif"),
+        (b"GIF89a", "image/gif"),
+        (b"%PDF-", "application/pdf"),
+        (b"PK\x03\x04", "application/zip"),
+        (b"\x1f\x8b", "application/gzip"),
+    ];
+    if let Some(&(_, content_type)) = MAGIC.iter().find(|(magic, _)| head.starts_with(magic)) {
+        return content_type;
+    }
+    if head.len() >= 12 && head.starts_with(b"RIFF") && &head[8..12] == b"WEBP" {
+        return "image/webp";
+    }
+    // The head may end mid-character; only reject invalid sequences
+    let is_text = match std::str::from_utf8(head) {
+        Ok(_) => true,
+        Err(e) => e.error_len().is_none(),
+    };
+    if is_text && !head.contains(&0) {
+        "text/plain; charset=utf-8"
+    } else {
+        "application/octet-stream"
+    }
+}
+
+/// Run a fixed-string `git grep` for `q` at `commit_sha` (case-insensitive,
+/// binary files skipped), returning at most `MAX_SEARCH_RESULTS` matches.
+/// Reads at most `MAX_GREP_OUTPUT` bytes and stops git once it has them, so a
+/// query matching most of a large repo can't exhaust memory.
+async fn git_grep(
+    repo_path: &std::path::Path,
+    commit_sha: &str,
+    q: &str,
+) -> Result<CodeSearchResponse, ApiError> {
+    let mut child = tokio::process::Command::new("git")
+        .arg("-C")
+        .arg(repo_path)
+        .arg("grep")
+        .arg("-z")
+        .arg("-n")
+        .arg("-I")
+        .arg("-i")
+        .arg("--fixed-strings")
+        .arg("--no-color")
+        .arg(format!("--max-count={}", MAX_SEARCH_RESULTS + 1))
+        .arg("-e")
+        .arg(q)
+        .arg(commit_sha)
+        .arg("--")
+        .stdout(std::process::Stdio::piped())
+        .stderr(std::process::Stdio::piped())
+        .kill_on_drop(true)
+        .spawn()
+        .map_err(|e| ApiError::Internal(anyhow::anyhow!("failed to run git grep: {e}")))?;
+    let mut stdout = child.stdout.take().expect("stdout piped");
+
+    let mut output = Vec::new();
+    tokio::time::timeout(
+        GIT_TIMEOUT,
+        (&mut stdout).take(MAX_GREP_OUTPUT).read_to_end(&mut output),
+    )
+    .await
+    .map_err(|_| ApiError::Internal(anyhow::anyhow!("git grep timed out after 30s")))?
+    .map_err(|e| ApiError::Internal(anyhow::anyhow!("failed to read git grep output: {e}")))?;
+
+    let capped = output.len() as u64 >= MAX_GREP_OUTPUT;
+    if capped {
+        // Enough output; drop the partial last line and stop git
+        let end = output
+            .iter()
+            .rposition(|&b| b == b'\n')
+            .map_or(0, |i| i + 1);
+        output.truncate(end);
+        let _ = child.kill().await;
+    } else {
+        let status = tokio::time::timeout(GIT_TIMEOUT, child.wait())
+            .await
+            .map_err(|_| ApiError::Internal(anyhow::anyhow!("git grep timed out after 30s")))?
+            .map_err(|e| ApiError::Internal(anyhow::anyhow!("failed to run git grep: {e}")))?;
+        // Exit code 1 means "no matches"
+        if !status.success() && status.code() != Some(1) {
+            let mut stderr = Vec::new();
+            if let Some(mut pipe) = child.stderr.take() {
+                let _ = pipe.read_to_end(&mut stderr).await;
+            }
+            let stderr = String::from_utf8_lossy(&stderr);
+            return Err(ApiError::Internal(anyhow::anyhow!(
+                "git grep failed: {stderr}"
+            )));
+        }
+    }
+
+    let mut matches = parse_grep(&String::from_utf8_lossy(&output), commit_sha);
+    let truncated = capped || matches.len() > MAX_SEARCH_RESULTS;
+    matches.truncate(MAX_SEARCH_RESULTS);
+    Ok(CodeSearchResponse {
+        commit_sha: commit_sha.to_owned(),
+        matches,
+        truncated,
+    })
+}
+
 async fn git_list_branches(repo_path: &std::path::Path) -> Result<Vec<BranchInfo>, ApiError> {
     let output = tokio::time::timeout(GIT_TIMEOUT, {
         tokio::process::Command::new("git")
@@ -339,6 +705,211 @@ async fn git_list_branches(repo_path: &std::path::Path) -> Result<Vec<BranchInfo
     Ok(parse_branches(&String::from_utf8_lossy(&output.stdout)))
 }
 
+/// Run `git diff` between the merge base of `base` and `head`, returning the
+/// structured per-file diff. Line counts come from `--numstat`, status and
+/// hunks from the unified patch; both list files in the same order.
+async fn git_compare(
+    repo_path: &std::path::Path,
+    base: &str,
+    head: &str,
+) -> Result<Vec<DiffFile>, ApiError> {
+    const MAX_DIFF_SIZE: usize = 10 * 1024 * 1024;
+    let range = format!("{base}...{head}");
+
+    let numstat = run_git_diff(repo_path, &["--numstat", "-z"], &range).await?;
+    let patch = run_git_diff(repo_path, &["--no-color", "--no-ext-diff"], &range).await?;
+
+    if patch.len() > MAX_DIFF_SIZE {
+        return Err(ApiError::BadRequest(format!(
+            "diff too large: {} bytes (max {MAX_DIFF_SIZE})",
+            patch.len()
+        )));
+    }
+
+    let stats = parse_numstat(&String::from_utf8_lossy(&numstat));
+    let mut files = parse_unified_diff(&String::from_utf8_lossy(&patch));
+    for (file, stat) in files.iter_mut().zip(stats) {
+        file.additions = stat.additions;
+        file.deletions = stat.deletions;
+        file.binary = file.binary || stat.binary;
+    }
+    Ok(files)
+}
+
+async fn run_git_diff(
+    repo_path: &std::path::Path,
+    flags: &[&str],
+    range: &str,
+) -> Result<Vec<u8>, ApiError> {
+    let output = tokio::time::timeout(GIT_TIMEOUT, {
+        tokio::process::Command::new("git")
+            .arg("-C")
+            .arg(repo_path)
+            .arg("-c")
+            .arg("core.quotepath=false")
+            .arg("diff")
+            .arg("-M")
+            .args(flags)
+            .arg(range)
+            .arg("--")
+            .output()
+    })
+    .await
+    .map_err(|_| ApiError::Internal(anyhow::anyhow!("git diff timed out after 30s")))?
+    .map_err(|e| ApiError::Internal(anyhow::anyhow!("failed to run git diff: {e}")))?;
+
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        if stderr.contains("unknown revision")
+            || stderr.contains("bad revision")
+            || stderr.contains("no merge base")
+        {
+            return Err(ApiError::NotFound("ref".into()));
+        }
+        return Err(ApiError::Internal(anyhow::anyhow!(
+            "git diff failed: {stderr}"
+        )));
+    }
+
+    Ok(output.stdout)
+}
+
+/// Tree listing with `ETag` / `If-None-Match` support (shared by project and ops repos).
+async fn serve_tree(
+    repo_path: &std::path::Path,
+    query: &TreeQuery,
+    headers: &HeaderMap,
+) -> Result<Response, ApiError> {
+    let sha = git_object_sha(repo_path, &tree_spec(&query.git_ref, &query.path)).await;
+    if let Some(response) = not_modified(headers, sha.as_deref()) {
+        return Ok(response);
+    }
+    let entries = git_ls_tree(repo_path, &query.git_ref, &query.path).await?;
+    Ok(with_etag(Json(entries), sha.as_deref()))
+}
+
+/// Blob contents with `ETag` / `If-None-Match` support (shared by project and ops repos).
+async fn serve_blob(
+    repo_path: &std::path::Path,
+    query: &BlobQuery,
+    headers: &HeaderMap,
+) -> Result<Response, ApiError> {
+    let spec = format!("{}:{}", query.git_ref, query.path.trim_start_matches('/'));
+    let (sha, size) = git_blob_info(repo_path, &spec).await?;
+    if let Some(response) = not_modified(headers, Some(&sha)) {
+        return Ok(response);
+    }
+    if size > MAX_BLOB_SIZE {
+        return Err(ApiError::BadRequest(format!(
+            "file too large: {size} bytes (max {MAX_BLOB_SIZE}); download it from the raw endpoint"
+        )));
+    }
+    let blob = git_show_blob(repo_path, &sha, &query.path).await?;
+    Ok(with_etag(Json(blob), Some(&sha)))
+}
+
+/// Raw blob contents, streamed from `git cat-file` so large files never sit in
+/// memory. Served inline (for images and text) unless `download` is set.
+/// Supports `ETag`s and single-range `Range` requests (with `If-Range`) so
+/// interrupted downloads can resume.
+async fn serve_raw(
+    repo_path: &std::path::Path,
+    git_ref: &str,
+    path: &str,
+    download: bool,
+    headers: &HeaderMap,
+) -> Result<Response, ApiError> {
+    let spec = format!("{git_ref}:{}", path.trim_start_matches('/'));
+    let (sha, size) = git_blob_info(repo_path, &spec).await?;
+    if let Some(response) = not_modified(headers, Some(&sha)) {
+        return Ok(response);
+    }
+
+    let range = headers
+        .get(header::RANGE)
+        .and_then(|v| v.to_str().ok())
+        .filter(|_| if_range_matches(headers, &sha))
+        .map_or(ByteRange::Full, |value| parse_range(value, size));
+    let (status, start, len) = match range {
+        ByteRange::Full => (StatusCode::OK, 0, size),
+        ByteRange::Partial { start, end } => (StatusCode::PARTIAL_CONTENT, start, end - start + 1),
+        ByteRange::Unsatisfiable => {
+            return Ok((
+                StatusCode::RANGE_NOT_SATISFIABLE,
+                [(header::CONTENT_RANGE, format!("bytes */{size}"))],
+            )
+                .into_response());
+        }
+    };
+
+    let mut child = tokio::process::Command::new("git")
+        .arg("-C")
+        .arg(repo_path)
+        .arg("cat-file")
+        .arg("blob")
+        .arg(&sha)
+        .stdout(std::process::Stdio::piped())
+        .stderr(std::process::Stdio::null())
+        .spawn()
+        .map_err(|e| ApiError::Internal(anyhow::anyhow!("failed to spawn git cat-file: {e}")))?;
+    let mut stdout = child.stdout.take().expect("stdout piped");
+    // Reap the process once it exits (or hits EPIPE after a client disconnect)
+    tokio::spawn(async move {
+        let _ = child.wait().await;
+    });
+
+    let read_err =
+        |e: std::io::Error| ApiError::Internal(anyhow::anyhow!("failed to read blob: {e}"));
+    // The first bytes identify files without a known extension
+    let mut head = Vec::with_capacity(SNIFF_LEN);
+    (&mut stdout)
+        .take(SNIFF_LEN as u64)
+        .read_to_end(&mut head)
+        .await
+        .map_err(read_err)?;
+    let content_type = raw_content_type(path, &head);
+
+    // cat-file can't seek, so skip to the range start without buffering
+    let head_len = head.len() as u64;
+    if start > head_len {
+        tokio::io::copy(
+            &mut (&mut stdout).take(start - head_len),
+            &mut tokio::io::sink(),
+        )
+        .await
+        .map_err(read_err)?;
+    }
+    let head = head.split_off(usize::try_from(start.min(head_len)).unwrap_or_default());
+    let reader = std::io::Cursor::new(head).chain(stdout).take(len);
+    let body = Body::from_stream(ReaderStream::new(reader));
+
+    let filename = sanitize_filename(path.rsplit('/').next().unwrap_or_default());
+    let disposition = if download { "attachment" } else { "inline" };
+    let mut response = Response::builder()
+        .status(status)
+        .header(header::CONTENT_TYPE, content_type)
+        .header(header::CONTENT_LENGTH, len)
+        .header(header::ACCEPT_RANGES, "bytes")
+        .header(
+            header::CONTENT_DISPOSITION,
+            format!("{disposition}; filename=\"{filename}\""),
+        )
+        .header(header::X_CONTENT_TYPE_OPTIONS, "nosniff")
+        // Inline files come from user repos: never let them run scripts
+        .header(
+            header::CONTENT_SECURITY_POLICY,
+            "default-src 'none'; style-src 'unsafe-inline'; sandbox",
+        );
+    if status == StatusCode::PARTIAL_CONTENT {
+        response = response.header(
+            header::CONTENT_RANGE,
+            format!("bytes {start}-{}/{size}", start + len - 1),
+        );
+    }
+    let response = response.body(body).expect("valid status and headers");
+    Ok(with_etag(response, Some(&sha)))
+}
+
 // ---------------------------------------------------------------------------
 // Project repo handlers
 // ---------------------------------------------------------------------------
@@ -350,15 +921,14 @@ async fn tree(
     auth: AuthUser,
     Path(id): Path<Uuid>,
     Query(query): Query<TreeQuery>,
-) -> Result<Json<Vec<TreeEntry>>, ApiError> {
+    headers: HeaderMap,
+) -> Result<Response, ApiError> {
     check_project_read(&state, &auth, id).await?;
     validate_git_ref(&query.git_ref)?;
     validate_path(&query.path)?;
 
     let (repo_path, _) = get_repo_path(&state.pool, &state.config, id).await?;
-    Ok(Json(
-        git_ls_tree(&repo_path, &query.git_ref, &query.path).await?,
-    ))
+    serve_tree(&repo_path, &query, &headers).await
 }
 
 /// `GET /api/projects/:id/blob?ref=main&path=src/main.rs`
@@ -368,7 +938,8 @@ async fn blob(
     auth: AuthUser,
     Path(id): Path<Uuid>,
     Query(query): Query<BlobQuery>,
-) -> Result<Json<BlobResponse>, ApiError> {
+    headers: HeaderMap,
+) -> Result<Response, ApiError> {
     check_project_read(&state, &auth, id).await?;
     validate_git_ref(&query.git_ref)?;
     validate_path(&query.path)?;
@@ -376,9 +947,46 @@ async fn blob(
         return Err(ApiError::BadRequest("path is required".into()));
     }
     let (repo_path, _) = get_repo_path(&state.pool, &state.config, id).await?;
-    Ok(Json(
-        git_show_blob(&repo_path, &query.git_ref, &query.path).await?,
-    ))
+    serve_blob(&repo_path, &query, &headers).await
+}
+
+/// `GET /api/projects/:id/raw?ref=main&path=assets/model.bin`
+#[tracing::instrument(skip(state), fields(%id), err)]
+async fn raw(
+    State(state): State<AppState>,
+    auth: AuthUser,
+    Path(id): Path<Uuid>,
+    Query(query): Query<BlobQuery>,
+    headers: HeaderMap,
+) -> Result<Response, ApiError> {
+    check_project_read(&state, &auth, id).await?;
+    validate_git_ref(&query.git_ref)?;
+    validate_path(&query.path)?;
+    if query.path.is_empty() {
+        return Err(ApiError::BadRequest("path is required".into()));
+    }
+    let (repo_path, _) = get_repo_path(&state.pool, &state.config, id).await?;
+    serve_raw(&repo_path, &query.git_ref, &query.path, true, &headers).await
+}
+
+/// `GET /api/projects/:id/raw/:ref/*path[?download=1]`
+///
+/// Path-addressed raw file for `<img src>` and direct links. A ref containing
+/// `/` must be percent-encoded (`feature%2Flogin`).
+#[tracing::instrument(skip(state), fields(%id), err)]
+async fn raw_at_ref(
+    State(state): State<AppState>,
+    auth: AuthUser,
+    Path((id, git_ref, path)): Path<(Uuid, String, String)>,
+    Query(query): Query<RawQuery>,
+    headers: HeaderMap,
+) -> Result<Response, ApiError> {
+    check_project_read(&state, &auth, id).await?;
+    validate_git_ref(&git_ref)?;
+    validate_path(&path)?;
+    let download = matches!(query.download.as_deref(), Some("1" | "true"));
+    let (repo_path, _) = get_repo_path(&state.pool, &state.config, id).await?;
+    serve_raw(&repo_path, &git_ref, &path, download, &headers).await
 }
 
 /// `GET /api/projects/:id/branches`
@@ -404,14 +1012,13 @@ async fn ops_tree(
     auth: AuthUser,
     Path(id): Path<Uuid>,
     Query(query): Query<TreeQuery>,
-) -> Result<Json<Vec<TreeEntry>>, ApiError> {
+    headers: HeaderMap,
+) -> Result<Response, ApiError> {
     check_project_read(&state, &auth, id).await?;
     validate_git_ref(&query.git_ref)?;
     validate_path(&query.path)?;
     let (repo_path, _) = get_ops_repo_path(&state.pool, id).await?;
-    Ok(Json(
-        git_ls_tree(&repo_path, &query.git_ref, &query.path).await?,
-    ))
+    serve_tree(&repo_path, &query, &headers).await
 }
 
 /// `GET /api/projects/:id/ops-repo/blob?ref=main&path=...`
@@ -421,7 +1028,8 @@ async fn ops_blob(
     auth: AuthUser,
     Path(id): Path<Uuid>,
     Query(query): Query<BlobQuery>,
-) -> Result<Json<BlobResponse>, ApiError> {
+    headers: HeaderMap,
+) -> Result<Response, ApiError> {
     check_project_read(&state, &auth, id).await?;
     validate_git_ref(&query.git_ref)?;
     validate_path(&query.path)?;
@@ -429,9 +1037,26 @@ async fn ops_blob(
         return Err(ApiError::BadRequest("path is required".into()));
     }
     let (repo_path, _) = get_ops_repo_path(&state.pool, id).await?;
-    Ok(Json(
-        git_show_blob(&repo_path, &query.git_ref, &query.path).await?,
-    ))
+    serve_blob(&repo_path, &query, &headers).await
+}
+
+/// `GET /api/projects/:id/ops-repo/raw?ref=main&path=values.yaml`
+#[tracing::instrument(skip(state), fields(%id), err)]
+async fn ops_raw(
+    State(state): State<AppState>,
+    auth: AuthUser,
+    Path(id): Path<Uuid>,
+    Query(query): Query<BlobQuery>,
+    headers: HeaderMap,
+) -> Result<Response, ApiError> {
+    check_project_read(&state, &auth, id).await?;
+    validate_git_ref(&query.git_ref)?;
+    validate_path(&query.path)?;
+    if query.path.is_empty() {
+        return Err(ApiError::BadRequest("path is required".into()));
+    }
+    let (repo_path, _) = get_ops_repo_path(&state.pool, id).await?;
+    serve_raw(&repo_path, &query.git_ref, &query.path, true, &headers).await
 }
 
 /// `GET /api/projects/:id/ops-repo/branches`
@@ -569,6 +1194,85 @@ async fn commit_detail(
     Ok(Json(commit))
 }
 
+/// `GET /api/projects/:id/compare?base=main&head=feature`
+///
+/// Structured diff of `head` against its merge base with `base`.
+#[tracing::instrument(skip(state), fields(%id), err)]
+async fn compare(
+    State(state): State<AppState>,
+    auth: AuthUser,
+    Path(id): Path<Uuid>,
+    Query(query): Query<CompareQuery>,
+) -> Result<Json<CompareResponse>, ApiError> {
+    check_project_read(&state, &auth, id).await?;
+    validate_git_ref(&query.base)?;
+    validate_git_ref(&query.head)?;
+
+    let (repo_path, _) = get_repo_path(&state.pool, &state.config, id).await?;
+    let files = git_compare(&repo_path, &query.base, &query.head).await?;
+
+    let total_additions = files.iter().map(|f| f.additions).sum();
+    let total_deletions = files.iter().map(|f| f.deletions).sum();
+
+    Ok(Json(CompareResponse {
+        base: query.base,
+        head: query.head,
+        files,
+        total_additions,
+        total_deletions,
+    }))
+}
+
+/// `GET /api/projects/:id/search/code?q=parse_config&ref=main`
+///
+/// Case-insensitive fixed-string search over the files at `ref`. Results are
+/// cached in Valkey per resolved commit and query. Gated by the `code_search`
+/// platform feature.
+#[tracing::instrument(skip(state, query), fields(%id), err)]
+async fn search_code(
+    State(state): State<AppState>,
+    auth: AuthUser,
+    Path(id): Path<Uuid>,
+    Query(query): Query<CodeSearchQuery>,
+) -> Result<Json<CodeSearchResponse>, ApiError> {
+    check_project_read(&state, &auth, id).await?;
+    features::require(&state, Feature::CodeSearch, Some(id), auth.user_id).await?;
+    validate_git_ref(&query.git_ref)?;
+    validate_search_query(&query.q)?;
+
+    crate::auth::rate_limit::check_rate(
+        &state.valkey,
+        "code_search",
+        &auth.user_id.to_string(),
+        60,
+        60,
+    )
+    .await?;
+
+    let (repo_path, _) = get_repo_path(&state.pool, &state.config, id).await?;
+    let commit_sha = git_object_sha(&repo_path, &format!("{}^{{commit}}", query.git_ref))
+        .await
+        .ok_or_else(|| ApiError::NotFound("ref".into()))?;
+
+    let query_hash = hex::encode(Sha256::digest(query.q.as_bytes()));
+    let cache_key = format!("code_search:{id}:{commit_sha}:{query_hash}");
+    if let Some(cached) =
+        crate::store::valkey::get_cached::<CodeSearchResponse>(&state.valkey, &cache_key).await
+    {
+        return Ok(Json(cached));
+    }
+
+    let results = git_grep(&repo_path, &commit_sha, &query.q).await?;
+    let _ = crate::store::valkey::set_cached(
+        &state.valkey,
+        &cache_key,
+        &results,
+        SEARCH_CACHE_TTL_SECS,
+    )
+    .await;
+    Ok(Json(results))
+}
+
 // ---------------------------------------------------------------------------
 // Signature verification
 // ---------------------------------------------------------------------------
@@ -780,6 +1484,76 @@ async fn verify_commits_batch(
 // Parsers
 // ---------------------------------------------------------------------------
 
+/// Parse a `git cat-file --batch-check` line (`<sha> <type> <size>`) into the
+/// SHA and size of a blob. `None` for missing or non-blob objects.
+fn parse_batch_check(output: &str) -> Option<(String, u64)> {
+    let mut parts = output.split_whitespace();
+    let (sha, object_type, size) = (parts.next()?, parts.next()?, parts.next()?);
+    if object_type != "blob" {
+        return None;
+    }
+    Some((sha.to_owned(), size.parse().ok()?))
+}
+
+/// The part of a blob a `Range` request asks for.
+#[derive(Debug, PartialEq, Eq)]
+enum ByteRange {
+    /// No usable range: serve the whole blob.
+    Full,
+    /// Inclusive byte offsets, clamped to the blob.
+    Partial { start: u64, end: u64 },
+    /// The range starts past the end of the blob (416).
+    Unsatisfiable,
+}
+
+/// Parse a `Range` header against a blob of `size` bytes. Only a single
+/// `bytes=` range is supported; malformed and multi-range headers are ignored
+/// and the full blob is served, as RFC 9110 allows.
+fn parse_range(value: &str, size: u64) -> ByteRange {
+    let Some(spec) = value.trim().strip_prefix("bytes=") else {
+        return ByteRange::Full;
+    };
+    if spec.contains(',') {
+        return ByteRange::Full;
+    }
+    let Some((first, last)) = spec.split_once('-') else {
+        return ByteRange::Full;
+    };
+    let (first, last) = (first.trim(), last.trim());
+
+    // Suffix range: the last N bytes
+    if first.is_empty() {
+        return match last.parse::<u64>() {
+            Ok(0) => ByteRange::Unsatisfiable,
+            Ok(_) if size == 0 => ByteRange::Unsatisfiable,
+            Ok(n) => ByteRange::Partial {
+                start: size.saturating_sub(n),
+                end: size - 1,
+            },
+            Err(_) => ByteRange::Full,
+        };
+    }
+
+    let Ok(start) = first.parse::<u64>() else {
+        return ByteRange::Full;
+    };
+    let end = if last.is_empty() {
+        u64::MAX
+    } else {
+        match last.parse::<u64>() {
+            Ok(end) if end >= start => end,
+            _ => return ByteRange::Full,
+        }
+    };
+    if start >= size {
+        return ByteRange::Unsatisfiable;
+    }
+    ByteRange::Partial {
+        start,
+        end: end.min(size - 1),
+    }
+}
+
 /// Parse `git ls-tree -l` output.
 ///
 /// Format: `<mode> <type> <sha> <size>\t<name>`
@@ -805,6 +1579,27 @@ fn parse_ls_tree(output: &str) -> Vec<TreeEntry> {
         .collect()
 }
 
+/// Parse `git grep -z -n` output run against a tree: each line is
+/// `<sha>:<path>\0<line>\0<text>`.
+fn parse_grep(output: &str, commit_sha: &str) -> Vec<CodeSearchMatch> {
+    let prefix = format!("{commit_sha}:");
+    output
+        .lines()
+        .filter_map(|line| {
+            let mut parts = line.splitn(3, '\0');
+            let name = parts.next()?;
+            let line_no = parts.next()?.parse().ok()?;
+            let text = parts.next()?;
+            Some(CodeSearchMatch {
+                path: name.strip_prefix(&prefix).unwrap_or(name).to_owned(),
+                line: line_no,
+                snippet: text.trim_end().chars().take(MAX_SNIPPET_CHARS).collect(),
+            })
+        })
+        .take(MAX_SEARCH_RESULTS + 1)
+        .collect()
+}
+
 /// Parse `git for-each-ref` output for branches.
 ///
 /// Format: `<name>\t<sha>\t<date>`
@@ -851,6 +1646,125 @@ fn parse_log(output: &str) -> Vec<CommitInfo> {
         .collect()
 }
 
+#[derive(Debug, PartialEq, Eq)]
+struct NumStat {
+    additions: i64,
+    deletions: i64,
+    binary: bool,
+}
+
+/// Parse `git diff --numstat -z` output.
+///
+/// Format: `<added>\t<deleted>\t<path>\0`, or for renames
+/// `<added>\t<deleted>\t\0<old>\0<new>\0`. Binary files report `-` counts.
+fn parse_numstat(output: &str) -> Vec<NumStat> {
+    let mut stats = Vec::new();
+    let mut fields = output.split('\0');
+    while let Some(record) = fields.next() {
+        let mut parts = record.splitn(3, '\t');
+        let (Some(added), Some(deleted), Some(path)) = (parts.next(), parts.next(), parts.next())
+        else {
+            continue;
+        };
+        if path.is_empty() {
+            // Rename: old and new paths follow as separate NUL-terminated fields
+            fields.next();
+            fields.next();
+        }
+        let binary = added == "-" || deleted == "-";
+        stats.push(NumStat {
+            additions: added.parse().unwrap_or(0),
+            deletions: deleted.parse().unwrap_or(0),
+            binary,
+        });
+    }
+    stats
+}
+
+/// Parse a hunk header such as `@@ -1,3 +1,4 @@ fn main()`.
+///
+/// Returns `(old_start, old_lines, new_start, new_lines)`; omitted counts default to 1.
+pub(crate) fn parse_hunk_header(line: &str) -> Option<(u32, u32, u32, u32)> {
+    let rest = line.strip_prefix("@@ -")?;
+    let (ranges, _) = rest.split_once(" @@")?;
+    let (old, new) = ranges.split_once(" +")?;
+    let parse_range = |r: &str| -> Option<(u32, u32)> {
+        match r.split_once(',') {
+            Some((start, len)) => Some((start.parse().ok()?, len.parse().ok()?)),
+            None => Some((r.parse().ok()?, 1)),
+        }
+    };
+    let (old_start, old_lines) = parse_range(old)?;
+    let (new_start, new_lines) = parse_range(new)?;
+    Some((old_start, old_lines, new_start, new_lines))
+}
+
+/// Parse unified `git diff` output into per-file entries with hunks.
+///
+/// Line counts are left at zero; they are filled from `--numstat`.
+fn parse_unified_diff(output: &str) -> Vec<DiffFile> {
+    let mut files: Vec<DiffFile> = Vec::new();
+
+    for line in output.lines() {
+        if let Some(rest) = line.strip_prefix("diff --git ") {
+            // Fallback path from the header; refined by ---/+++/rename lines below
+            let path = rest.rsplit_once(" b/").map_or(rest, |(_, b)| b).to_owned();
+            files.push(DiffFile {
+                path,
+                old_path: None,
+                status: "modified".into(),
+                additions: 0,
+                deletions: 0,
+                binary: false,
+                hunks: Vec::new(),
+            });
+            continue;
+        }
+        let Some(file) = files.last_mut() else {
+            continue;
+        };
+
+        if let Some(hunk) = file.hunks.last_mut()
+            && (line.starts_with(' ')
+                || line.starts_with('+')
+                || line.starts_with('-')
+                || line.starts_with('\\'))
+        {
+            hunk.lines.push(line.to_owned());
+        } else if line.starts_with("@@ ") {
+            if let Some((old_start, old_lines, new_start, new_lines)) = parse_hunk_header(line) {
+                file.hunks.push(DiffHunk {
+                    header: line.to_owned(),
+                    old_start,
+                    old_lines,
+                    new_start,
+                    new_lines,
+                    lines: Vec::new(),
+                });
+            }
+        } else if line.starts_with("new file mode") {
+            file.status = "added".into();
+        } else if line.starts_with("deleted file mode") {
+            file.status = "deleted".into();
+        } else if let Some(from) = line.strip_prefix("rename from ") {
+            file.status = "renamed".into();
+            file.old_path = Some(from.to_owned());
+        } else if let Some(to) = line.strip_prefix("rename to ") {
+            to.clone_into(&mut file.path);
+        } else if let Some(path) = line.strip_prefix("+++ b/") {
+            path.clone_into(&mut file.path);
+        } else if let Some(path) = line.strip_prefix("--- a/")
+            && file.status == "deleted"
+        {
+            path.clone_into(&mut file.path);
+        } else if line.starts_with("Binary files ") {
+            file.binary = true;
+        }
+    }
+
+    files
+}
+
 // ---------------------------------------------------------------------------
 // Tests
 // ---------------------------------------------------------------------------
@@ -889,6 +1803,57 @@ mod tests {
         assert!(validate_path("foo/../../bar").is_err());
     }
 
+    #[test]
+    fn tree_spec_root_and_subdir() {
+        assert_eq!(tree_spec("main", ""), "main^{tree}");
+        assert_eq!(tree_spec("main", "/"), "main^{tree}");
+        assert_eq!(tree_spec("main", "/src"), "main:src");
+    }
+
+    #[test]
+    fn etag_matches_if_none_match() {
+        let mut headers = HeaderMap::new();
+        assert!(!etag_matches(&headers, "\"abc\""));
+
+        headers.insert(header::IF_NONE_MATCH, HeaderValue::from_static("\"abc\""));
+        assert!(etag_matches(&headers, "\"abc\""));
+        assert!(!etag_matches(&headers, "\"def\""));
+
+        headers.insert(
+            header::IF_NONE_MATCH,
+            HeaderValue::from_static("\"xyz\", W/\"abc\""),
+        );
+        assert!(etag_matches(&headers, "\"abc\""));
+
+        headers.insert(header::IF_NONE_MATCH, HeaderValue::from_static("*"));
+        assert!(etag_matches(&headers, "\"anything\""));
+    }
+
+    #[test]
+    fn parse_grep_strips_tree_prefix() {
+        let sha = "0123456789abcdef0123456789abcdef01234567";
+        let output = format!(
+            "{sha}:src/main.rs\x0012\x00fn parse_config() {{\n{sha}:a:b.rs\x003\x00    parse_config();\n"
+        );
+        let matches = parse_grep(&output, sha);
+        assert_eq!(matches.len(), 2);
+        assert_eq!(matches[0].path, "src/main.rs");
+        assert_eq!(matches[0].line, 12);
+        assert_eq!(matches[0].snippet, "fn parse_config() {");
+        assert_eq!(matches[1].path, "a:b.rs");
+        assert_eq!(matches[1].line, 3);
+    }
+
+    #[test]
+    fn validate_search_query_bounds() {
+        assert!(validate_search_query("parse_config").is_ok());
+        assert!(validate_search_query("--output=/tmp/x").is_ok());
+        assert!(validate_search_query("").is_err());
+        assert!(validate_search_query("   ").is_err());
+        assert!(validate_search_query("a\nb").is_err());
+        assert!(validate_search_query(&"x".repeat(201)).is_err());
+    }
+
     #[test]
     fn parse_ls_tree_normal() {
         let output = "100644 blob abc1234 1234\tREADME.md\n040000 tree def5678      -\tsrc\n";
@@ -1082,6 +2047,12 @@ mod tests {
         assert!(validate_git_ref("foo bar").is_err());
     }
 
+    #[test]
+    fn validate_ref_rejects_leading_dash() {
+        assert!(validate_git_ref("--output=/tmp/x").is_err());
+        assert!(validate_git_ref("-n1").is_err());
+    }
+
     // -- validate_path --
 
     #[test]
@@ -1512,4 +2483,262 @@ mod tests {
         let debug = format!("{info:?}");
         assert!(debug.contains("BranchInfo"));
     }
+
+    // -- compare parsers --
+
+    #[test]
+    fn parse_numstat_plain_and_binary() {
+        let output = "3\t1\tsrc/main.rs\0-\t-\tlogo.png\0";
+        let stats = parse_numstat(output);
+        assert_eq!(stats.len(), 2);
+        assert_eq!(
+            stats[0],
+            NumStat {
+                additions: 3,
+                deletions: 1,
+                binary: false
+            }
+        );
+        assert!(stats[1].binary);
+        assert_eq!(stats[1].additions, 0);
+    }
+
+    #[test]
+    fn parse_numstat_rename() {
+        let output = concat!("1\t0\t\0old.txt\0new.txt\0", "2\t2\tother.rs\0");
+        let stats = parse_numstat(output);
+        assert_eq!(stats.len(), 2);
+        assert_eq!(stats[0].additions, 1);
+        assert_eq!(stats[1].additions, 2);
+        assert_eq!(stats[1].deletions, 2);
+    }
+
+    #[test]
+    fn parse_numstat_empty() {
+        assert!(parse_numstat("").is_empty());
+    }
+
+    #[test]
+    fn parse_hunk_header_full() {
+        assert_eq!(
+            parse_hunk_header("@@ -1,3 +1,4 @@ fn main()"),
+            Some((1, 3, 1, 4))
+        );
+    }
+
+    #[test]
+    fn parse_hunk_header_omitted_counts() {
+        assert_eq!(parse_hunk_header("@@ -5 +5 @@"), Some((5, 1, 5, 1)));
+        assert_eq!(parse_hunk_header("@@ -0,0 +1 @@"), Some((0, 0, 1, 1)));
+    }
+
+    #[test]
+    fn parse_hunk_header_invalid() {
+        assert!(parse_hunk_header("not a hunk").is_none());
+        assert!(parse_hunk_header("@@ -x,1 +1 @@").is_none());
+    }
+
+    #[test]
+    fn parse_unified_diff_statuses() {
+        let output = "\
+diff --git a/src/lib.rs b/src/lib.rs
+index 1111111..2222222 100644
+--- a/src/lib.rs
++++ b/src/lib.rs
+@@ -1,2 +1,2 @@
+ fn a() {}
+-fn b() {}
++fn c() {}
+diff --git a/new.txt b/new.txt
+new file mode 100644
+index 0000000..3333333
+--- /dev/null
++++ b/new.txt
+@@ -0,0 +1 @@
++hello
+diff --git a/gone.txt b/gone.txt
+deleted file mode 100644
+index 4444444..0000000
+--- a/gone.txt
++++ /dev/null
+@@ -1 +0,0 @@
+-bye
+diff --git a/old name.md b/new name.md
+similarity index 100%
+rename from old name.md
+rename to new name.md
+diff --git a/logo.png b/logo.png
+index 5555555..6666666 100644
+Binary files a/logo.png and b/logo.png differ
+";
+        let files = parse_unified_diff(output);
+        assert_eq!(files.len(), 5);
+
+        assert_eq!(files[0].path, "src/lib.rs");
+        assert_eq!(files[0].status, "modified");
+        assert_eq!(files[0].hunks.len(), 1);
+        assert_eq!(files[0].hunks[0].lines.len(), 3);
+        assert_eq!(files[0].hunks[0].old_start, 1);
+
+        assert_eq!(files[1].path, "new.txt");
+        assert_eq!(files[1].status, "added");
+        assert_eq!(files[1].hunks[0].lines, vec!["+hello"]);
+
+        assert_eq!(files[2].path, "gone.txt");
+        assert_eq!(files[2].status, "deleted");
+
+        assert_eq!(files[3].path, "new name.md");
+        assert_eq!(files[3].old_path.as_deref(), Some("old name.md"));
+        assert_eq!(files[3].status, "renamed");
+        assert!(files[3].hunks.is_empty());
+
+        assert_eq!(files[4].path, "logo.png");
+        assert!(files[4].binary);
+    }
+
+    #[test]
+    fn parse_unified_diff_empty() {
+        assert!(parse_unified_diff("").is_empty());
+    }
+
+    #[test]
+    fn parse_unified_diff_no_newline_marker_kept_in_hunk() {
+        let output = "\
+diff --git a/a.txt b/a.txt
+--- a/a.txt
++++ b/a.txt
+@@ -1 +1 @@
+-old
+\\ No newline at end of file
++new
+";
+        let files = parse_unified_diff(output);
+        assert_eq!(files[0].hunks[0].lines.len(), 3);
+    }
+
+    #[test]
+    fn parse_batch_check_blob_only() {
+        assert_eq!(
+            parse_batch_check("abc123 blob 1048576\n"),
+            Some(("abc123".into(), 1_048_576))
+        );
+        assert_eq!(parse_batch_check("def456 tree 96\n"), None);
+        assert_eq!(parse_batch_check("main:nope missing\n"), None);
+    }
+
+    #[test]
+    fn parse_range_variants() {
+        assert_eq!(
+            parse_range("bytes=0-99", 1000),
+            ByteRange::Partial { start: 0, end: 99 }
+        );
+        assert_eq!(
+            parse_range("bytes=500-", 1000),
+            ByteRange::Partial {
+                start: 500,
+                end: 999
+            }
+        );
+        assert_eq!(
+            parse_range("bytes=-100", 1000),
+            ByteRange::Partial {
+                start: 900,
+                end: 999
+            }
+        );
+        // Clamped to the blob
+        assert_eq!(
+            parse_range("bytes=900-5000", 1000),
+            ByteRange::Partial {
+                start: 900,
+                end: 999
+            }
+        );
+        assert_eq!(
+            parse_range("bytes=-5000", 1000),
+            ByteRange::Partial { start: 0, end: 999 }
+        );
+    }
+
+    #[test]
+    fn parse_range_unsatisfiable() {
+        assert_eq!(parse_range("bytes=1000-", 1000), ByteRange::Unsatisfiable);
+        assert_eq!(parse_range("bytes=-0", 1000), ByteRange::Unsatisfiable);
+        assert_eq!(parse_range("bytes=0-", 0), ByteRange::Unsatisfiable);
+    }
+
+    #[test]
+    fn parse_range_ignores_unsupported() {
+        for value in [
+            "items=0-10",
+            "bytes=0-10,20-30",
+            "bytes=abc-",
+            "bytes=10-5",
+            "bytes=5",
+        ] {
+            assert_eq!(parse_range(value, 1000), ByteRange::Full, "{value}");
+        }
+    }
+
+    #[test]
+    fn if_range_requires_current_etag() {
+        let mut headers = HeaderMap::new();
+        assert!(if_range_matches(&headers, "abc"));
+        headers.insert(header::IF_RANGE, HeaderValue::from_static("\"abc\""));
+        assert!(if_range_matches(&headers, "abc"));
+        assert!(!if_range_matches(&headers, "def"));
+        headers.insert(
+            header::IF_RANGE,
+            HeaderValue::from_static("Wed, 21 Oct 2015 07:28:00 GMT"),
+        );
+        assert!(!if_range_matches(&headers, "abc"));
+    }
+
+    #[test]
+    fn raw_content_type_from_extension() {
+        assert_eq!(raw_content_type("logo.PNG", b""), "image/png");
+        assert_eq!(raw_content_type("docs/icon.svg", b""), "image/svg+xml");
+        assert_eq!(
+            raw_content_type("index.html", b"<html>"),
+            "text/plain; charset=utf-8"
+        );
+        // The extension wins over the content
+        assert_eq!(
+            raw_content_type("notes.txt", b"\x89PNG\r\n\x1a\n"),
+            "text/plain; charset=utf-8"
+        );
+    }
+
+    #[test]
+    fn raw_content_type_sniffs_magic_bytes() {
+        assert_eq!(
+            raw_content_type("assets/logo", b"\x89PNG\r\n\x1a\n\0\0"),
+            "image/png"
+        );
+        assert_eq!(
+            raw_content_type("photo.raw", b"\xff\xd8\xff\xe0"),
+            "image/jpeg"
+        );
+        assert_eq!(raw_content_type("a.v1/anim", b"GIF89a"), "image/gif");
+        assert_eq!(
+            raw_content_type("img", b"RIFF\x10\0\0\0WEBPVP8 "),
+            "image/webp"
+        );
+        assert_eq!(raw_content_type("report", b"%PDF-1.7"), "application/pdf");
+        assert_eq!(
+            raw_content_type("Makefile", b"all:\n\tcargo build\n"),
+            "text/plain; charset=utf-8"
+        );
+        assert_eq!(
+            raw_content_type("model.bin", b"\0\x01\x02\x03"),
+            "application/octet-stream"
+        );
+    }
+
+    #[test]
+    fn sniff_tolerates_truncated_utf8() {
+        // "é" cut after its first byte at the end of the sniffed head
+        assert_eq!(sniff_content_type(b"caf\xc3"), "text/plain; charset=utf-8");
+        assert_eq!(sniff_content_type(b"\xc3("), "application/octet-stream");
+    }
 }
@@ -2,7 +2,10 @@
 // SPDX-License-Identifier: BUSL-1.1
 
 use std::path::Path;
+use std::sync::LazyLock;
 
+use regex::Regex;
+use tracing::Instrument;
 use uuid::Uuid;
 
 use crate::error::ApiError;
@@ -32,6 +35,8 @@ pub struct PostReceiveParams {
     pub pushed_branches: Vec<String>,
     /// Tag names that were pushed (stripped of `refs/tags/` prefix).
     pub pushed_tags: Vec<String>,
+    /// Raw ref updates of the push; used to find the commits it introduced.
+    pub ref_updates: Vec<RefUpdate>,
 }
 
 // ---------------------------------------------------------------------------
@@ -182,6 +187,8 @@ pub fn parse_ref_updates(input: &str) -> Vec<RefUpdate> {
 /// 1. Delegate to `pipeline::trigger::on_push()` to parse `.platform.yaml` and create pipeline + steps
 /// 2. If a pipeline was created, notify the executor via Valkey
 /// 3. Fire push webhooks
+///
+/// Finally a push to the project's push mirror, if any, is started in the background.
 #[tracing::instrument(skip(state, params), fields(project_id = %params.project_id, user = %params.user_name), err)]
 pub async fn post_receive(state: &AppState, params: &PostReceiveParams) -> Result<(), ApiError> {
     // Use pushed branches if available, otherwise fall back to default branch
@@ -194,7 +201,10 @@ pub async fn post_receive(state: &AppState, params: &PostReceiveParams) -> Resul
     let mut pipelines_triggered = 0u32;
     for branch in &branches {
         tracing::info!(branch, "post-receive: processing push");
-        let commit_sha = get_branch_sha(&params.repo_path, branch).await;
+        let commit_sha = match pushed_sha(&params.ref_updates, branch) {
+            Some(sha) => Some(sha.to_owned()),
+            None => get_branch_sha(&params.repo_path, branch).await,
+        };
 
         let trigger_params = crate::pipeline::trigger::PushTriggerParams {
             project_id: params.project_id,
@@ -202,6 +212,7 @@ pub async fn post_receive(state: &AppState, params: &PostReceiveParams) -> Resul
             repo_path: params.repo_path.clone(),
             branch: (*branch).to_string(),
             commit_sha,
+            before_sha: previous_sha(&params.ref_updates, branch).map(str::to_owned),
         };
 
         match crate::pipeline::trigger::on_push(
@@ -237,11 +248,16 @@ pub async fn post_receive(state: &AppState, params: &PostReceiveParams) -> Resul
             "project_id": params.project_id,
             "pusher": params.user_name,
         });
-        crate::api::webhooks::fire_webhooks(
+        let changed_paths = branch_changed_paths(params, branch).await;
+        let scope = crate::api::webhooks::PushScope {
+            branch: Some(branch),
+            changed_paths: changed_paths.as_deref(),
+        };
+        crate::api::webhooks::fire_push_webhooks(
             &state.pool,
             params.project_id,
-            "push",
             &payload,
+            &scope,
             &state.webhook_semaphore,
         )
         .await;
@@ -252,44 +268,25 @@ pub async fn post_receive(state: &AppState, params: &PostReceiveParams) -> Resul
         handle_mr_sync_on_push(state, params, branch).await;
     }
 
+    // Close issues referenced by "Fixes #N" in commits landing on the default branch
+    if branches.contains(&params.default_branch.as_str()) {
+        close_referenced_issues(state, params).await;
+    }
+
     // Handle tag pushes
     for tag_name in &params.pushed_tags {
-        let commit_sha = get_tag_sha(&params.repo_path, tag_name).await;
-        let tag_params = crate::pipeline::trigger::TagTriggerParams {
-            project_id: params.project_id,
-            user_id: params.user_id,
-            repo_path: params.repo_path.clone(),
-            tag_name: tag_name.clone(),
-            commit_sha: commit_sha.clone(),
-        };
-
-        match crate::pipeline::trigger::on_tag(&state.pool, &tag_params, &state.config.kaniko_image)
-            .await
-        {
-            Ok(Some(pipeline_id)) => {
-                crate::pipeline::trigger::notify_executor(state, pipeline_id).await;
-            }
-            Ok(None) => {}
-            Err(e) => {
-                tracing::error!(error = %e, %tag_name, "tag pipeline trigger failed");
-            }
-        }
-
-        let payload = serde_json::json!({
-            "ref": format!("refs/tags/{tag_name}"),
-            "project_id": params.project_id,
-            "pusher": params.user_name,
-        });
-        crate::api::webhooks::fire_webhooks(
-            &state.pool,
-            params.project_id,
-            "push",
-            &payload,
-            &state.webhook_semaphore,
-        )
-        .await;
+        handle_tag_push(state, params, tag_name).await;
     }
 
+    // Pushing to the mirror can take minutes; the push that triggered it
+    // does not wait. Failures are retried by `push_mirror::run`.
+    let mirror_state = state.clone();
+    let project_id = params.project_id;
+    tokio::spawn(
+        async move { super::push_mirror::on_push(&mirror_state, project_id).await }
+            .in_current_span(),
+    );
+
     Ok(())
 }
 
@@ -297,6 +294,48 @@ pub async fn post_receive(state: &AppState, params: &PostReceiveParams) -> Resul
 // Git helpers
 // ---------------------------------------------------------------------------
 
+/// Trigger the tag pipeline and fire tag push webhooks.
+async fn handle_tag_push(state: &AppState, params: &PostReceiveParams, tag_name: &str) {
+    let commit_sha = get_tag_sha(&params.repo_path, tag_name).await;
+    let tag_params = crate::pipeline::trigger::TagTriggerParams {
+        project_id: params.project_id,
+        user_id: params.user_id,
+        repo_path: params.repo_path.clone(),
+        tag_name: tag_name.to_owned(),
+        commit_sha,
+    };
+
+    match crate::pipeline::trigger::on_tag(&state.pool, &tag_params, &state.config.kaniko_image)
+        .await
+    {
+        Ok(Some(pipeline_id)) => {
+            crate::pipeline::trigger::notify_executor(state, pipeline_id).await;
+        }
+        Ok(None) => {}
+        Err(e) => {
+            tracing::error!(error = %e, %tag_name, "tag pipeline trigger failed");
+        }
+    }
+
+    let payload = serde_json::json!({
+        "ref": format!("refs/tags/{tag_name}"),
+        "project_id": params.project_id,
+        "pusher": params.user_name,
+    });
+    let scope = crate::api::webhooks::PushScope {
+        branch: None,
+        changed_paths: None,
+    };
+    crate::api::webhooks::fire_push_webhooks(
+        &state.pool,
+        params.project_id,
+        &payload,
+        &scope,
+        &state.webhook_semaphore,
+    )
+    .await;
+}
+
 /// Handle MR sync when a branch is pushed: update `head_sha`, dismiss stale reviews, trigger MR pipeline.
 async fn handle_mr_sync_on_push(state: &AppState, params: &PostReceiveParams, branch: &str) {
     let commit_sha = get_branch_sha(&params.repo_path, branch).await;
@@ -328,6 +367,10 @@ async fn handle_mr_sync_on_push(state: &AppState, params: &PostReceiveParams, br
         .execute(&state.pool)
         .await;
 
+        if let Some(ref new_sha) = commit_sha {
+            mark_outdated_comments(&state.pool, &params.repo_path, mr.id, new_sha).await;
+        }
+
         // Dismiss stale reviews if protection rule says so
         if let Ok(Some(rule)) =
             crate::git::protection::get_protection(&state.pool, params.project_id, branch).await
@@ -366,6 +409,282 @@ async fn handle_mr_sync_on_push(state: &AppState, params: &PostReceiveParams, br
     }
 }
 
+/// Mark line-anchored MR comments as outdated when the pushed head no longer
+/// contains the anchored line (the line was changed/removed, or the file deleted).
+async fn mark_outdated_comments(pool: &sqlx::PgPool, repo_path: &Path, mr_id: Uuid, new_sha: &str) {
+    let anchored = sqlx::query!(
+        r#"SELECT id, file_path as "file_path!", line_number as "line_number!",
+                  commit_sha as "commit_sha!"
+           FROM comments
+           WHERE mr_id = $1 AND file_path IS NOT NULL AND outdated = false"#,
+        mr_id,
+    )
+    .fetch_all(pool)
+    .await;
+
+    let Ok(anchored) = anchored else {
+        return;
+    };
+
+    let mut outdated_ids = Vec::new();
+    for c in &anchored {
+        if c.commit_sha == new_sha {
+            continue;
+        }
+        let Some(diff) = diff_file_between(repo_path, &c.commit_sha, new_sha, &c.file_path).await
+        else {
+            continue;
+        };
+        if anchor_is_outdated(&diff, c.line_number) {
+            outdated_ids.push(c.id);
+        }
+    }
+
+    if outdated_ids.is_empty() {
+        return;
+    }
+    if let Err(e) = sqlx::query!(
+        "UPDATE comments SET outdated = true WHERE id = ANY($1)",
+        &outdated_ids,
+    )
+    .execute(pool)
+    .await
+    {
+        tracing::warn!(error = %e, %mr_id, "failed to mark outdated comments");
+    }
+}
+
+/// `git diff -U0 <from> <to> -- <path>`; `None` if git fails (e.g. unknown commit).
+async fn diff_file_between(repo_path: &Path, from: &str, to: &str, path: &str) -> Option<String> {
+    let output = tokio::process::Command::new("git")
+        .arg("-C")
+        .arg(repo_path)
+        .arg("diff")
+        .arg("-U0")
+        .arg("--no-color")
+        .arg(from)
+        .arg(to)
+        .arg("--")
+        .arg(path)
+        .output()
+        .await
+        .ok()?;
+
+    output
+        .status
+        .success()
+        .then(|| String::from_utf8_lossy(&output.stdout).into_owned())
+}
+
+/// Whether a zero-context diff of the anchored file removes or rewrites `line`
+/// (a line number on the old side of the diff).
+fn anchor_is_outdated(diff: &str, line: i32) -> bool {
+    let Ok(line) = u32::try_from(line) else {
+        return false;
+    };
+    diff.lines().any(|l| {
+        if l.starts_with("deleted file mode") {
+            return true;
+        }
+        super::browser::parse_hunk_header(l).is_some_and(|(old_start, old_lines, _, _)| {
+            old_lines > 0 && line >= old_start && line < old_start + old_lines
+        })
+    })
+}
+
+/// Max commits scanned for issue references per push.
+const MAX_ISSUE_REF_COMMITS: usize = 500;
+
+static ISSUE_REF_RE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"(?i)\b(?:closes|fixes|resolves)\s+#(\d+)\b").expect("valid issue ref regex")
+});
+
+/// Issue numbers referenced by closing keywords (`Fixes #42`) in a commit message.
+fn parse_closing_issue_refs(message: &str) -> Vec<i32> {
+    let mut numbers: Vec<i32> = ISSUE_REF_RE
+        .captures_iter(message)
+        .filter_map(|c| c[1].parse().ok())
+        .collect();
+    numbers.sort_unstable();
+    numbers.dedup();
+    numbers
+}
+
+/// Close issues referenced by commits pushed to the default branch and post a
+/// comment on each linking the closing commit.
+async fn close_referenced_issues(state: &AppState, params: &PostReceiveParams) {
+    let default_ref = format!("refs/heads/{}", params.default_branch);
+    let Some(update) = params.ref_updates.iter().find(|u| u.refname == default_ref) else {
+        return;
+    };
+
+    let commits = list_pushed_commits(&params.repo_path, &update.old_sha, &update.new_sha).await;
+    for (sha, message) in commits {
+        for number in parse_closing_issue_refs(&message) {
+            close_issue_from_commit(state, params, number, &sha).await;
+        }
+    }
+}
+
+async fn close_issue_from_commit(
+    state: &AppState,
+    params: &PostReceiveParams,
+    number: i32,
+    sha: &str,
+) {
+    let closed = sqlx::query!(
+        r#"UPDATE issues SET status = 'closed'
+           WHERE project_id = $1 AND number = $2 AND status != 'closed'
+           RETURNING id, updated_at"#,
+        params.project_id,
+        number,
+    )
+    .fetch_optional(&state.pool)
+    .await;
+
+    let issue = match closed {
+        Ok(Some(issue)) => issue,
+        Ok(None) => return,
+        Err(e) => {
+            tracing::warn!(error = %e, number, "failed to close referenced issue");
+            return;
+        }
+    };
+
+    let short_sha = &sha[..sha.len().min(12)];
+    let body = format!(
+        "Closed by commit {short_sha} pushed to `{}`.",
+        params.default_branch
+    );
+    if let Err(e) = sqlx::query!(
+        "INSERT INTO comments (project_id, issue_id, author_id, body) VALUES ($1, $2, $3, $4)",
+        params.project_id,
+        issue.id,
+        params.user_id,
+        body,
+    )
+    .execute(&state.pool)
+    .await
+    {
+        tracing::warn!(error = %e, number, "failed to post issue close comment");
+    }
+
+    crate::audit::send_audit(
+        &state.audit_tx,
+        crate::audit::AuditEntry {
+            actor_id: params.user_id,
+            actor_name: params.user_name.clone(),
+            action: "issue.close".into(),
+            resource: "issue".into(),
+            resource_id: Some(issue.id),
+            project_id: Some(params.project_id),
+            detail: Some(serde_json::json!({
+                "number": number,
+                "closed_by": params.user_id,
+                "closed_at": issue.updated_at,
+                "commit_sha": sha,
+            })),
+            ip_addr: None,
+        },
+    );
+
+    crate::api::webhooks::fire_webhooks(
+        &state.pool,
+        params.project_id,
+        "issue",
+        &serde_json::json!({
+            "action": "closed",
+            "issue": {"id": issue.id, "number": number},
+            "commit_sha": sha,
+        }),
+        &state.webhook_semaphore,
+    )
+    .await;
+}
+
+/// `(sha, message)` of commits in `old..new`, or of `new`'s history when the
+/// branch was just created. Capped at [`MAX_ISSUE_REF_COMMITS`].
+async fn list_pushed_commits(
+    repo_path: &Path,
+    old_sha: &str,
+    new_sha: &str,
+) -> Vec<(String, String)> {
+    let range = if old_sha.bytes().all(|b| b == b'0') {
+        new_sha.to_owned()
+    } else {
+        format!("{old_sha}..{new_sha}")
+    };
+    let output = tokio::process::Command::new("git")
+        .arg("-C")
+        .arg(repo_path)
+        .arg("log")
+        .arg(format!("--max-count={MAX_ISSUE_REF_COMMITS}"))
+        .arg("--format=%H%x1f%B%x1e")
+        .arg(&range)
+        .arg("--")
+        .output()
+        .await;
+
+    let Ok(output) = output else {
+        return Vec::new();
+    };
+    if !output.status.success() {
+        return Vec::new();
+    }
+    String::from_utf8_lossy(&output.stdout)
+        .split('\x1e')
+        .filter_map(|record| {
+            let (sha, message) = record.trim_start_matches('\n').split_once('\x1f')?;
+            Some((sha.to_owned(), message.to_owned()))
+        })
+        .collect()
+}
+
+/// Git's well-known empty tree, the diff base for a repository's first push.
+const EMPTY_TREE_SHA: &str = "4b825dc642cb6eb9a060e54bf8d69288fbee4904";
+
+/// Files changed by the push to `branch`, for webhook path filters.
+///
+/// Diffs the old and new tips; a newly created branch is diffed against its
+/// merge base with the default branch, and a first push against the empty tree.
+/// Returns `None` when the ref update is unknown or git fails.
+async fn branch_changed_paths(params: &PostReceiveParams, branch: &str) -> Option<Vec<String>> {
+    let refname = format!("refs/heads/{branch}");
+    let update = params.ref_updates.iter().find(|u| u.refname == refname)?;
+
+    let mut cmd = tokio::process::Command::new("git");
+    cmd.arg("-C")
+        .arg(&params.repo_path)
+        .arg("diff")
+        .arg("--name-only")
+        .arg("-z");
+    if !update.old_sha.bytes().all(|b| b == b'0') {
+        cmd.arg(&update.old_sha).arg(&update.new_sha);
+    } else if branch != params.default_branch
+        && get_branch_sha(&params.repo_path, &params.default_branch)
+            .await
+            .is_some()
+    {
+        cmd.arg(format!(
+            "refs/heads/{}...{}",
+            params.default_branch, update.new_sha
+        ));
+    } else {
+        cmd.arg(EMPTY_TREE_SHA).arg(&update.new_sha);
+    }
+    let output = cmd.arg("--").output().await.ok()?;
+    if !output.status.success() {
+        return None;
+    }
+    Some(
+        String::from_utf8_lossy(&output.stdout)
+            .split('\0')
+            .filter(|p| !p.is_empty())
+            .map(str::to_owned)
+            .collect(),
+    )
+}
+
 /// Get the SHA of a tag.
 async fn get_tag_sha(repo_path: &Path, tag_name: &str) -> Option<String> {
     let output = tokio::process::Command::new("git")
@@ -399,6 +718,31 @@ async fn check_file_exists(repo_path: &Path, git_ref: &str, file_path: &str) ->
     matches!(result, Ok(output) if output.status.success())
 }
 
+/// The commit a push moved `branch` to, taken from the push's own ref updates.
+fn pushed_sha<'a>(updates: &'a [RefUpdate], branch: &str) -> Option<&'a str> {
+    branch_update(updates, branch)
+        .map(|u| u.new_sha.as_str())
+        .filter(|sha| is_object_id(sha))
+}
+
+/// The commit `branch` pointed at before the push; `None` for a new branch.
+fn previous_sha<'a>(updates: &'a [RefUpdate], branch: &str) -> Option<&'a str> {
+    branch_update(updates, branch)
+        .map(|u| u.old_sha.as_str())
+        .filter(|sha| is_object_id(sha))
+}
+
+fn branch_update<'a>(updates: &'a [RefUpdate], branch: &str) -> Option<&'a RefUpdate> {
+    updates
+        .iter()
+        .find(|u| u.refname.strip_prefix("refs/heads/") == Some(branch))
+}
+
+/// A hex object id other than the all-zero id git uses for created/deleted refs.
+fn is_object_id(sha: &str) -> bool {
+    sha.bytes().all(|b| b.is_ascii_hexdigit()) && sha.bytes().any(|b| b != b'0')
+}
+
 /// Get the SHA of a branch tip.
 async fn get_branch_sha(repo_path: &Path, branch: &str) -> Option<String> {
     let output = tokio::process::Command::new("git")
@@ -425,6 +769,37 @@ async fn get_branch_sha(repo_path: &Path, branch: &str) -> Option<String> {
 mod tests {
     use super::*;
 
+    #[test]
+    fn pushed_sha_from_ref_updates() {
+        let sha = "d".repeat(40);
+        let updates = vec![
+            RefUpdate {
+                old_sha: "0".repeat(40),
+                new_sha: sha.clone(),
+                refname: "refs/heads/feature/x".into(),
+            },
+            RefUpdate {
+                old_sha: "a".repeat(40),
+                new_sha: "0".repeat(40),
+                refname: "refs/heads/old".into(),
+            },
+            RefUpdate {
+                old_sha: "a".repeat(40),
+                new_sha: format!("--output={}", "a".repeat(31)),
+                refname: "refs/heads/evil".into(),
+            },
+        ];
+        assert_eq!(pushed_sha(&updates, "feature/x"), Some(sha.as_str()));
+        assert_eq!(pushed_sha(&updates, "old"), None, "deletion");
+        assert_eq!(pushed_sha(&updates, "evil"), None, "not a hex SHA");
+        assert_eq!(pushed_sha(&updates, "main"), None);
+        assert_eq!(previous_sha(&updates, "feature/x"), None, "new branch");
+        assert_eq!(
+            previous_sha(&updates, "evil"),
+            Some("a".repeat(40).as_str())
+        );
+    }
+
     #[test]
     fn parse_normal_push() {
         let input = "abc123abc123abc123abc123abc123abc123abc12a def456def456def456def456def456def456def456d refs/heads/main\n";
@@ -1036,6 +1411,7 @@ cccccccccccccccccccccccccccccccccccccccc ddddddddddddddddddddddddddddddddddddddd
             default_branch: "main".into(),
             pushed_branches: vec!["main".into()],
             pushed_tags: vec!["v1.0.0".into()],
+            ref_updates: vec![],
         };
         assert_eq!(params.pushed_branches.len(), 1);
         assert_eq!(params.pushed_tags.len(), 1);
@@ -1172,6 +1548,7 @@ cccccccccccccccccccccccccccccccccccccccc ddddddddddddddddddddddddddddddddddddddd
             default_branch: "main".into(),
             pushed_branches: vec![],
             pushed_tags: vec![],
+            ref_updates: vec![],
         };
         assert!(params.pushed_branches.is_empty());
         assert!(params.pushed_tags.is_empty());
@@ -1187,8 +1564,57 @@ cccccccccccccccccccccccccccccccccccccccc ddddddddddddddddddddddddddddddddddddddd
             default_branch: "main".into(),
             pushed_branches: vec!["main".into(), "develop".into(), "feature/x".into()],
             pushed_tags: vec!["v1.0.0".into(), "v2.0.0".into()],
+            ref_updates: vec![],
         };
         assert_eq!(params.pushed_branches.len(), 3);
         assert_eq!(params.pushed_tags.len(), 2);
     }
+
+    // -- anchor_is_outdated --
+
+    #[test]
+    fn anchor_outdated_when_line_rewritten() {
+        let diff = "diff --git a/f b/f\n--- a/f\n+++ b/f\n@@ -3,2 +3,2 @@\n-a\n-b\n+c\n+d\n";
+        assert!(anchor_is_outdated(diff, 3));
+        assert!(anchor_is_outdated(diff, 4));
+        assert!(!anchor_is_outdated(diff, 2));
+        assert!(!anchor_is_outdated(diff, 5));
+    }
+
+    #[test]
+    fn anchor_not_outdated_by_pure_insertion() {
+        let diff = "@@ -4,0 +5,2 @@\n+new\n+lines\n";
+        assert!(!anchor_is_outdated(diff, 4));
+        assert!(!anchor_is_outdated(diff, 5));
+    }
+
+    #[test]
+    fn anchor_outdated_when_file_deleted() {
+        let diff = "diff --git a/f b/f\ndeleted file mode 100644\n--- a/f\n+++ /dev/null\n@@ -1 +0,0 @@\n-x\n";
+        assert!(anchor_is_outdated(diff, 1));
+    }
+
+    #[test]
+    fn anchor_not_outdated_for_empty_diff() {
+        assert!(!anchor_is_outdated("", 10));
+    }
+
+    // -- parse_closing_issue_refs --
+
+    #[test]
+    fn closing_refs_keywords_case_insensitive() {
+        assert_eq!(parse_closing_issue_refs("Fixes #3"), vec![3]);
+        assert_eq!(
+            parse_closing_issue_refs("fix parser\n\ncloses #12, RESOLVES #4"),
+            vec![4, 12]
+        );
+    }
+
+    #[test]
+    fn closing_refs_dedup_and_ignore_plain_mentions() {
+        assert_eq!(parse_closing_issue_refs("Fixes #7\nfixes #7"), vec![7]);
+        assert!(parse_closing_issue_refs("see #7, related to #8").is_empty());
+        assert!(parse_closing_issue_refs("prefixes #7").is_empty());
+        assert!(parse_closing_issue_refs("Fixes #").is_empty());
+    }
 }
@@ -83,7 +83,7 @@ async fn batch(
     Json(body): Json<LfsBatchRequest>,
 ) -> Result<Json<LfsBatchResponse>, ApiError> {
     // Authenticate via Basic Auth (same as smart HTTP)
-    let git_user = super::smart_http::authenticate_basic(&headers, &state.pool).await?;
+    let git_user = super::smart_http::authenticate_basic(&headers, &state).await?;
 
     // Resolve project
     let project =
@@ -116,14 +116,20 @@ async fn batch(
         _ => return Err(ApiError::BadRequest("invalid operation".into())),
     };
 
-    // A8: Use has_permission_scoped to enforce API token scopes
-    let allowed = resolver::has_permission_scoped(
+    // Token scopes gate the operation the same way as smart HTTP
+    if !resolver::git_scope_allows(
+        git_user.token_scopes.as_deref(),
+        required_perm == Permission::ProjectWrite,
+    ) {
+        return Err(ApiError::Forbidden);
+    }
+
+    let allowed = resolver::has_permission(
         &state.pool,
         &state.valkey,
         git_user.user_id,
         Some(project.project_id),
         required_perm,
-        git_user.token_scopes.as_deref(),
     )
     .await
     .map_err(ApiError::Internal)?;
@@ -134,7 +140,7 @@ async fn batch(
 
     // Generate presigned URLs for each object
     const EXPIRES_SECS: i64 = 3600;
-    let expire_duration = Duration::from_secs(3600);
+    let expire_duration = Duration::from_hours(1);
     let mut objects = Vec::with_capacity(body.objects.len());
 
     for obj in &body.objects {
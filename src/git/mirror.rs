@@ -0,0 +1,565 @@
+// Copyright (c) 2026 Steven Hooker. Exclusively licensed to and distributed by AgentSphere GmbH.
+// SPDX-License-Identifier: BUSL-1.1
+
+//! Pull mirrors: keep a project's repo in sync with an external git remote.
+//!
+//! [`run`] syncs each mirror once its `mirror_interval_secs` has elapsed.
+//! Upstream branches and tags are fetched into `refs/mirror/*`, then applied
+//! to `refs/heads/*` and `refs/tags/*` in a single `git update-ref`
+//! transaction, so a push landing mid-sync fails the update instead of being
+//! silently overwritten. Refs deleted upstream are deleted locally.
+//!
+//! A protected branch (one whose protection rule blocks force pushes) that
+//! upstream rewound, force-pushed or deleted follows the project's
+//! `mirror_protected_policy`: `keep` leaves it untouched and reports it as
+//! diverged, `overwrite` follows upstream like any other branch.
+
+use std::collections::{BTreeMap, HashSet};
+use std::fmt::Write as _;
+use std::path::{Path, PathBuf};
+use std::sync::{LazyLock, Mutex};
+use std::time::Duration;
+
+use serde::Serialize;
+use tokio::io::AsyncWriteExt;
+use tracing::Instrument;
+use ts_rs::TS;
+use uuid::Uuid;
+
+use crate::deployer::ops_repo::{
+    PinnedRemote, RemoteCredential, configure_remote_auth, pin_remote,
+};
+use crate::error::ApiError;
+use crate::secrets::engine;
+use crate::store::AppState;
+
+/// Leave diverged protected branches alone.
+pub const POLICY_KEEP: &str = "keep";
+/// Force protected branches to match upstream.
+pub const POLICY_OVERWRITE: &str = "overwrite";
+
+/// Shortest allowed sync interval.
+pub const MIN_INTERVAL_SECS: i32 = 60;
+
+/// Where upstream refs are fetched before being applied.
+const MIRROR_NAMESPACE: &str = "refs/mirror/";
+
+/// Upper bound for a single `git fetch` of the upstream.
+const FETCH_TIMEOUT: Duration = Duration::from_mins(10);
+
+/// How often [`run`] looks for due mirrors.
+const POLL_INTERVAL: Duration = Duration::from_mins(1);
+
+/// Mirrors synced per poll.
+const SYNC_BATCH_SIZE: i64 = 20;
+
+/// Projects with a mirror sync or push in progress (shared by manual and
+/// background runs, and by [`super::push_mirror`]).
+static IN_FLIGHT: LazyLock<Mutex<HashSet<Uuid>>> = LazyLock::new(|| Mutex::new(HashSet::new()));
+
+// ---------------------------------------------------------------------------
+// Types
+// ---------------------------------------------------------------------------
+
+#[derive(Debug, thiserror::Error)]
+pub enum MirrorError {
+    #[error("project has no mirror configured")]
+    NotConfigured,
+    #[error("a mirror sync is already running for this project")]
+    InProgress,
+    #[error("mirror sync failed: {0}")]
+    SyncFailed(String),
+    #[error(transparent)]
+    Db(#[from] sqlx::Error),
+}
+
+impl From<MirrorError> for ApiError {
+    fn from(err: MirrorError) -> Self {
+        match err {
+            MirrorError::NotConfigured => ApiError::NotFound("mirror".into()),
+            MirrorError::InProgress => ApiError::Conflict(err.to_string()),
+            MirrorError::SyncFailed(_) => ApiError::BadGateway(err.to_string()),
+            MirrorError::Db(e) => ApiError::from(e),
+        }
+    }
+}
+
+/// Outcome of a mirror sync.
+#[derive(Debug, Default, Serialize, TS)]
+#[ts(export, rename = "MirrorSyncResult")]
+pub struct SyncResult {
+    /// Refs created or moved to match upstream.
+    pub updated: Vec<String>,
+    /// Refs deleted because upstream no longer has them.
+    pub deleted: Vec<String>,
+    /// Protected branches kept at their local commit although upstream diverged.
+    pub diverged: Vec<String>,
+}
+
+/// A ref that differs between the local repo and upstream.
+#[derive(Debug, PartialEq, Eq)]
+struct RefChange {
+    refname: String,
+    old: Option<String>,
+    new: Option<String>,
+}
+
+/// Removes a project from [`IN_FLIGHT`] when its sync ends.
+pub(super) struct SyncGuard(Uuid);
+
+impl SyncGuard {
+    pub(super) fn acquire(project_id: Uuid) -> Option<Self> {
+        let mut in_flight = IN_FLIGHT
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner);
+        // Build the guard only on insert: dropping a spare one here would
+        // re-lock `IN_FLIGHT` and release the current holder's entry.
+        if in_flight.insert(project_id) {
+            Some(Self(project_id))
+        } else {
+            None
+        }
+    }
+}
+
+impl Drop for SyncGuard {
+    fn drop(&mut self) {
+        IN_FLIGHT
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner)
+            .remove(&self.0);
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Background task
+// ---------------------------------------------------------------------------
+
+/// Background task: sync mirrors whose interval has elapsed.
+pub async fn run(state: AppState, cancel: tokio_util::sync::CancellationToken) {
+    let mut interval = tokio::time::interval(POLL_INTERVAL);
+    state.task_registry.register("repo_mirror", 1800);
+    loop {
+        tokio::select! {
+            () = cancel.cancelled() => {
+                tracing::info!("repo mirror shutting down");
+                break;
+            }
+            _ = interval.tick() => {
+                let iter_trace_id = Uuid::new_v4().to_string().replace('-', "");
+                let span = tracing::info_span!(
+                    "task_iteration",
+                    task_name = "repo_mirror",
+                    trace_id = %iter_trace_id,
+                    source = "system",
+                );
+                async {
+                    match sync_due(&state).await {
+                        Ok(()) => state.task_registry.heartbeat("repo_mirror"),
+                        Err(e) => {
+                            state.task_registry.report_error("repo_mirror", &e.to_string());
+                            tracing::error!(error = %e, "repo mirror failed");
+                        }
+                    }
+                }.instrument(span).await;
+            }
+        }
+    }
+}
+
+async fn sync_due(state: &AppState) -> Result<(), sqlx::Error> {
+    let due = sqlx::query_scalar!(
+        "SELECT id FROM projects
+         WHERE mirror_url IS NOT NULL AND is_active AND archived_at IS NULL
+           AND (mirror_last_synced_at IS NULL
+                OR mirror_last_synced_at + make_interval(secs => mirror_interval_secs) <= now())
+         ORDER BY mirror_last_synced_at NULLS FIRST
+         LIMIT $1",
+        SYNC_BATCH_SIZE,
+    )
+    .fetch_all(&state.pool)
+    .await?;
+
+    for project_id in due {
+        match sync_project(state, project_id).await {
+            Ok(result) => {
+                if !result.updated.is_empty() || !result.deleted.is_empty() {
+                    tracing::info!(
+                        %project_id,
+                        updated = result.updated.len(),
+                        deleted = result.deleted.len(),
+                        "mirror synced"
+                    );
+                }
+            }
+            Err(MirrorError::InProgress | MirrorError::NotConfigured) => {}
+            Err(MirrorError::Db(e)) => return Err(e),
+            Err(e) => tracing::warn!(%project_id, error = %e, "mirror sync failed"),
+        }
+    }
+    Ok(())
+}
+
+// ---------------------------------------------------------------------------
+// Sync
+// ---------------------------------------------------------------------------
+
+/// Fetch the project's mirror upstream and update its refs. The outcome is
+/// recorded on the project (`mirror_last_synced_at`, `mirror_last_error`,
+/// `mirror_diverged`).
+#[tracing::instrument(skip(state), fields(%project_id), err)]
+pub async fn sync_project(state: &AppState, project_id: Uuid) -> Result<SyncResult, MirrorError> {
+    let Some(project) = sqlx::query!(
+        "SELECT repo_path, mirror_url, mirror_credential, mirror_protected_policy
+         FROM projects WHERE id = $1 AND is_active AND archived_at IS NULL",
+        project_id,
+    )
+    .fetch_optional(&state.pool)
+    .await?
+    else {
+        return Err(MirrorError::NotConfigured);
+    };
+    let (Some(repo_path), Some(mirror_url)) = (project.repo_path, project.mirror_url) else {
+        return Err(MirrorError::NotConfigured);
+    };
+    let (credential, policy) = (project.mirror_credential, project.mirror_protected_policy);
+    let _guard = SyncGuard::acquire(project_id).ok_or(MirrorError::InProgress)?;
+
+    let outcome = async {
+        let credential = decrypt_credential(state, credential.as_deref())?;
+        let repo_path = PathBuf::from(repo_path);
+        let pin = pin_upstream(state, &mirror_url).await?;
+        fetch_upstream(&repo_path, &mirror_url, credential.as_ref(), pin.as_ref()).await?;
+        apply_upstream(state, project_id, &repo_path, &policy).await
+    }
+    .await;
+
+    match &outcome {
+        Ok(result) => {
+            sqlx::query!(
+                "UPDATE projects SET mirror_last_synced_at = now(), mirror_last_error = NULL,
+                                     mirror_diverged = $2
+                 WHERE id = $1",
+                project_id,
+                &result.diverged,
+            )
+            .execute(&state.pool)
+            .await?;
+        }
+        Err(e) => {
+            // Failures also wait out the interval instead of retrying every poll
+            sqlx::query!(
+                "UPDATE projects SET mirror_last_synced_at = now(), mirror_last_error = $2
+                 WHERE id = $1",
+                project_id,
+                e.to_string(),
+            )
+            .execute(&state.pool)
+            .await?;
+        }
+    }
+    outcome
+}
+
+/// Resolve and check the upstream host right before connecting. The URL was
+/// checked when it was saved, but its name may since point somewhere internal.
+pub(super) async fn pin_upstream(
+    state: &AppState,
+    url: &str,
+) -> Result<Option<PinnedRemote>, MirrorError> {
+    pin_remote(url, state.config.dev_mode)
+        .await
+        .map_err(|e| MirrorError::SyncFailed(e.to_string()))
+}
+
+pub(super) fn decrypt_credential(
+    state: &AppState,
+    encrypted: Option<&[u8]>,
+) -> Result<Option<RemoteCredential>, MirrorError> {
+    let Some(encrypted) = encrypted else {
+        return Ok(None);
+    };
+    let master_key = state
+        .config
+        .master_key
+        .as_deref()
+        .ok_or_else(|| {
+            MirrorError::SyncFailed("mirror credential requires PLATFORM_MASTER_KEY".into())
+        })
+        .and_then(|hex| {
+            engine::parse_master_key(hex)
+                .map_err(|e| MirrorError::SyncFailed(format!("invalid master key: {e}")))
+        })?;
+    let plaintext = master_key
+        .decrypt(encrypted)
+        .ok()
+        .and_then(|bytes| String::from_utf8(bytes).ok())
+        .ok_or_else(|| {
+            MirrorError::SyncFailed("mirror credential could not be decrypted".into())
+        })?;
+    Ok(Some(RemoteCredential::from_secret(plaintext)))
+}
+
+/// Fetch all upstream branches and tags into [`MIRROR_NAMESPACE`], pruning
+/// refs upstream deleted. The credential never appears in argv or the URL.
+async fn fetch_upstream(
+    repo_path: &Path,
+    mirror_url: &str,
+    credential: Option<&RemoteCredential>,
+    pin: Option<&PinnedRemote>,
+) -> Result<(), MirrorError> {
+    let mut cmd = tokio::process::Command::new("git");
+    cmd.arg("-C")
+        .arg(repo_path)
+        .args(["-c", "http.followRedirects=false"]);
+    if let Some(pin) = pin {
+        pin.apply_http(&mut cmd);
+    }
+    cmd.args(["fetch", "--prune", "--no-tags", "--", mirror_url])
+        .arg(format!("+refs/heads/*:{MIRROR_NAMESPACE}heads/*"))
+        .arg(format!("+refs/tags/*:{MIRROR_NAMESPACE}tags/*"))
+        .kill_on_drop(true);
+    let key_dir = configure_remote_auth(&mut cmd, repo_path, credential)
+        .await
+        .map_err(|e| MirrorError::SyncFailed(e.to_string()))?;
+    if let Some(pin) = pin {
+        pin.apply_ssh(&mut cmd);
+    }
+
+    let output = tokio::time::timeout(FETCH_TIMEOUT, cmd.output()).await;
+    if let Some(dir) = key_dir {
+        let _ = tokio::fs::remove_dir_all(dir).await;
+    }
+    let output = output
+        .map_err(|_| MirrorError::SyncFailed("git fetch timed out".into()))?
+        .map_err(|e| MirrorError::SyncFailed(format!("failed to run git fetch: {e}")))?;
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(MirrorError::SyncFailed(format!(
+            "git fetch failed: {}",
+            stderr.trim()
+        )));
+    }
+    Ok(())
+}
+
+/// Bring local branches and tags in line with the fetched upstream refs.
+async fn apply_upstream(
+    state: &AppState,
+    project_id: Uuid,
+    repo_path: &Path,
+    policy: &str,
+) -> Result<SyncResult, MirrorError> {
+    let refs = list_refs(repo_path).await?;
+    let mut result = SyncResult::default();
+    let mut transaction = String::new();
+    let zero = "0".repeat(40);
+
+    for change in plan_changes(&refs) {
+        if let (Some(branch), Some(old)) = (change.refname.strip_prefix("refs/heads/"), &change.old)
+        {
+            let rewritten = match &change.new {
+                Some(new) => super::protection::is_force_push(repo_path, old, new).await,
+                None => true,
+            };
+            if rewritten
+                && policy != POLICY_OVERWRITE
+                && super::protection::get_protection(&state.pool, project_id, branch)
+                    .await?
+                    .is_some_and(|rule| rule.block_force_push)
+            {
+                result.diverged.push(branch.to_owned());
+                continue;
+            }
+        }
+
+        let _ = writeln!(
+            transaction,
+            "update {} {} {}",
+            change.refname,
+            change.new.as_deref().unwrap_or(&zero),
+            change.old.as_deref().unwrap_or(&zero),
+        );
+        if change.new.is_some() {
+            result.updated.push(change.refname);
+        } else {
+            result.deleted.push(change.refname);
+        }
+    }
+
+    if !transaction.is_empty() {
+        update_refs(repo_path, &transaction).await?;
+    }
+    Ok(result)
+}
+
+/// All branch, tag and mirror refs, by name.
+async fn list_refs(repo_path: &Path) -> Result<BTreeMap<String, String>, MirrorError> {
+    let output = tokio::process::Command::new("git")
+        .arg("-C")
+        .arg(repo_path)
+        .args(["for-each-ref", "--format=%(objectname) %(refname)"])
+        .args([
+            "refs/heads",
+            "refs/tags",
+            MIRROR_NAMESPACE.trim_end_matches('/'),
+        ])
+        .output()
+        .await
+        .map_err(|e| MirrorError::SyncFailed(format!("failed to run git for-each-ref: {e}")))?;
+    if !output.status.success() {
+        return Err(MirrorError::SyncFailed(format!(
+            "git for-each-ref failed: {}",
+            String::from_utf8_lossy(&output.stderr).trim()
+        )));
+    }
+    Ok(String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .filter_map(|line| {
+            let (sha, name) = line.split_once(' ')?;
+            Some((name.to_owned(), sha.to_owned()))
+        })
+        .collect())
+}
+
+/// Compare local branches and tags with their `refs/mirror/` counterparts.
+fn plan_changes(refs: &BTreeMap<String, String>) -> Vec<RefChange> {
+    let upstream: BTreeMap<String, &String> = refs
+        .iter()
+        .filter_map(|(name, sha)| {
+            let rest = name.strip_prefix(MIRROR_NAMESPACE)?;
+            Some((format!("refs/{rest}"), sha))
+        })
+        .collect();
+    let local = refs
+        .iter()
+        .filter(|(name, _)| name.starts_with("refs/heads/") || name.starts_with("refs/tags/"));
+
+    let mut changes: Vec<RefChange> = upstream
+        .iter()
+        .filter(|(name, sha)| refs.get(*name) != Some(**sha))
+        .map(|(name, sha)| RefChange {
+            refname: name.clone(),
+            old: refs.get(name).cloned(),
+            new: Some((*sha).clone()),
+        })
+        .collect();
+    changes.extend(
+        local
+            .filter(|(name, _)| !upstream.contains_key(*name))
+            .map(|(name, sha)| RefChange {
+                refname: name.clone(),
+                old: Some(sha.clone()),
+                new: None,
+            }),
+    );
+    changes
+}
+
+/// Apply `update <ref> <new> <old>` lines atomically. Fails if any ref moved
+/// since it was listed.
+async fn update_refs(repo_path: &Path, transaction: &str) -> Result<(), MirrorError> {
+    let mut child = tokio::process::Command::new("git")
+        .arg("-C")
+        .arg(repo_path)
+        .args(["update-ref", "--stdin"])
+        .stdin(std::process::Stdio::piped())
+        .stdout(std::process::Stdio::null())
+        .stderr(std::process::Stdio::piped())
+        .spawn()
+        .map_err(|e| MirrorError::SyncFailed(format!("failed to run git update-ref: {e}")))?;
+    let mut stdin = child.stdin.take().expect("stdin piped");
+    stdin
+        .write_all(transaction.as_bytes())
+        .await
+        .map_err(|e| MirrorError::SyncFailed(format!("failed to write to git update-ref: {e}")))?;
+    drop(stdin);
+
+    let output = child
+        .wait_with_output()
+        .await
+        .map_err(|e| MirrorError::SyncFailed(format!("failed to run git update-ref: {e}")))?;
+    if !output.status.success() {
+        return Err(MirrorError::SyncFailed(format!(
+            "git update-ref failed (refs changed during sync?): {}",
+            String::from_utf8_lossy(&output.stderr).trim()
+        )));
+    }
+    Ok(())
+}
+
+// ---------------------------------------------------------------------------
+// Tests
+// ---------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn refs(entries: &[(&str, &str)]) -> BTreeMap<String, String> {
+        entries
+            .iter()
+            .map(|(name, sha)| ((*name).to_owned(), (*sha).to_owned()))
+            .collect()
+    }
+
+    #[test]
+    fn plan_creates_moves_and_deletes() {
+        let refs = refs(&[
+            ("refs/heads/main", "aaa"),
+            ("refs/heads/stale", "bbb"),
+            ("refs/heads/same", "ccc"),
+            ("refs/mirror/heads/main", "ddd"),
+            ("refs/mirror/heads/new", "eee"),
+            ("refs/mirror/heads/same", "ccc"),
+            ("refs/mirror/tags/v1", "fff"),
+        ]);
+        let changes = plan_changes(&refs);
+        assert_eq!(
+            changes,
+            vec![
+                RefChange {
+                    refname: "refs/heads/main".into(),
+                    old: Some("aaa".into()),
+                    new: Some("ddd".into()),
+                },
+                RefChange {
+                    refname: "refs/heads/new".into(),
+                    old: None,
+                    new: Some("eee".into()),
+                },
+                RefChange {
+                    refname: "refs/tags/v1".into(),
+                    old: None,
+                    new: Some("fff".into()),
+                },
+                RefChange {
+                    refname: "refs/heads/stale".into(),
+                    old: Some("bbb".into()),
+                    new: None,
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn plan_in_sync_is_empty() {
+        let refs = refs(&[
+            ("refs/heads/main", "aaa"),
+            ("refs/tags/v1", "bbb"),
+            ("refs/mirror/heads/main", "aaa"),
+            ("refs/mirror/tags/v1", "bbb"),
+        ]);
+        assert!(plan_changes(&refs).is_empty());
+    }
+
+    #[test]
+    fn sync_guard_is_exclusive_per_project() {
+        let project_id = Uuid::new_v4();
+        let guard = SyncGuard::acquire(project_id).unwrap();
+        assert!(SyncGuard::acquire(project_id).is_none());
+        assert!(SyncGuard::acquire(Uuid::new_v4()).is_some());
+        drop(guard);
+        assert!(SyncGuard::acquire(project_id).is_some());
+    }
+}
@@ -7,7 +7,9 @@ pub mod browser;
 pub mod gpg_keys;
 pub mod hooks;
 pub mod lfs;
+pub mod mirror;
 pub mod protection;
+pub mod push_mirror;
 pub mod repo;
 pub mod signature;
 pub mod smart_http;
@@ -28,7 +30,7 @@ pub fn git_protocol_router() -> Router<AppState> {
 }
 
 /// Repository browser API routes. Mounted via `api::router()`.
-/// Matches `/api/projects/:id/{tree,blob,branches,commits}`.
+/// Matches `/api/projects/:id/{tree,blob,branches,commits,compare}`.
 pub fn browser_router() -> Router<AppState> {
     browser::router()
 }
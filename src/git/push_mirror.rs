@@ -0,0 +1,242 @@
+// Copyright (c) 2026 Steven Hooker. Exclusively licensed to and distributed by AgentSphere GmbH.
+// SPDX-License-Identifier: BUSL-1.1
+
+//! Push mirrors: copy a project's branches and tags to an external remote
+//! (e.g. a backup repo on GitHub) after every push.
+//!
+//! [`on_push`], spawned from post-receive, marks the project pending and
+//! pushes right away. A failed push stays pending and [`run`] retries it with
+//! exponential backoff. Branches and tags are force-pushed with `--prune`, so
+//! the remote ends up matching this repo; unlike `git push --mirror`, internal
+//! refs such as `refs/mirror/*` are left out.
+
+use std::path::Path;
+use std::time::Duration;
+
+use tracing::Instrument;
+use uuid::Uuid;
+
+use super::mirror::{MirrorError, SyncGuard, decrypt_credential, pin_upstream};
+use crate::deployer::ops_repo::{PinnedRemote, RemoteCredential, configure_remote_auth};
+use crate::store::AppState;
+
+/// Upper bound for a single `git push` to the remote.
+const PUSH_TIMEOUT: Duration = Duration::from_mins(10);
+
+/// How often [`run`] looks for pushes due a retry.
+const RETRY_POLL_INTERVAL: Duration = Duration::from_secs(30);
+
+/// Pushes retried per poll.
+const RETRY_BATCH_SIZE: i64 = 20;
+
+/// Delay before the first retry; doubles with every failed attempt.
+const BASE_BACKOFF_SECS: i32 = 30;
+
+/// Longest delay between retries.
+const MAX_BACKOFF_SECS: i32 = 3600;
+
+// ---------------------------------------------------------------------------
+// Background task
+// ---------------------------------------------------------------------------
+
+/// Background task: retry pending pushes whose backoff has elapsed.
+pub async fn run(state: AppState, cancel: tokio_util::sync::CancellationToken) {
+    let mut interval = tokio::time::interval(RETRY_POLL_INTERVAL);
+    state.task_registry.register("push_mirror", 600);
+    loop {
+        tokio::select! {
+            () = cancel.cancelled() => {
+                tracing::info!("push mirror shutting down");
+                break;
+            }
+            _ = interval.tick() => {
+                let iter_trace_id = Uuid::new_v4().to_string().replace('-', "");
+                let span = tracing::info_span!(
+                    "task_iteration",
+                    task_name = "push_mirror",
+                    trace_id = %iter_trace_id,
+                    source = "system",
+                );
+                async {
+                    match retry_due(&state).await {
+                        Ok(()) => state.task_registry.heartbeat("push_mirror"),
+                        Err(e) => {
+                            state.task_registry.report_error("push_mirror", &e.to_string());
+                            tracing::error!(error = %e, "push mirror retry failed");
+                        }
+                    }
+                }.instrument(span).await;
+            }
+        }
+    }
+}
+
+async fn retry_due(state: &AppState) -> Result<(), sqlx::Error> {
+    let due = sqlx::query_scalar!(
+        "SELECT id FROM projects
+         WHERE push_mirror_pending_since IS NOT NULL AND push_mirror_url IS NOT NULL
+           AND is_active AND push_mirror_next_attempt_at <= now()
+         ORDER BY push_mirror_next_attempt_at
+         LIMIT $1",
+        RETRY_BATCH_SIZE,
+    )
+    .fetch_all(&state.pool)
+    .await?;
+
+    for project_id in due {
+        match push_project(state, project_id).await {
+            Ok(()) | Err(MirrorError::InProgress | MirrorError::NotConfigured) => {}
+            Err(MirrorError::Db(e)) => return Err(e),
+            Err(e) => tracing::warn!(%project_id, error = %e, "push mirror failed"),
+        }
+    }
+    Ok(())
+}
+
+// ---------------------------------------------------------------------------
+// Push
+// ---------------------------------------------------------------------------
+
+/// Post-receive: queue a push to the project's push mirror, if it has one,
+/// and attempt it now. Failures are left for [`run`] to retry.
+pub async fn on_push(state: &AppState, project_id: Uuid) {
+    let queued = sqlx::query!(
+        "UPDATE projects SET push_mirror_pending_since = now(),
+                             push_mirror_next_attempt_at = now()
+         WHERE id = $1 AND push_mirror_url IS NOT NULL",
+        project_id,
+    )
+    .execute(&state.pool)
+    .await;
+    match queued {
+        Ok(result) if result.rows_affected() == 0 => return,
+        Ok(_) => {}
+        Err(e) => {
+            tracing::error!(error = %e, %project_id, "failed to queue push mirror");
+            return;
+        }
+    }
+
+    match push_project(state, project_id).await {
+        // The running push leaves this one pending for the retry task
+        Ok(()) | Err(MirrorError::InProgress | MirrorError::NotConfigured) => {}
+        Err(e) => tracing::warn!(%project_id, error = %e, "push mirror failed, will retry"),
+    }
+}
+
+/// Push branches and tags to the project's push mirror. The outcome is
+/// recorded on the project; the pending flag is only cleared if no newer push
+/// arrived while this one ran.
+#[tracing::instrument(skip(state), fields(%project_id), err)]
+pub async fn push_project(state: &AppState, project_id: Uuid) -> Result<(), MirrorError> {
+    let Some(project) = sqlx::query!(
+        "SELECT repo_path, push_mirror_url, push_mirror_credential, push_mirror_pending_since
+         FROM projects WHERE id = $1 AND is_active",
+        project_id,
+    )
+    .fetch_optional(&state.pool)
+    .await?
+    else {
+        return Err(MirrorError::NotConfigured);
+    };
+    let (Some(repo_path), Some(push_url)) = (project.repo_path, project.push_mirror_url) else {
+        return Err(MirrorError::NotConfigured);
+    };
+    let (credential, pending_since) = (
+        project.push_mirror_credential,
+        project.push_mirror_pending_since,
+    );
+    let _guard = SyncGuard::acquire(project_id).ok_or(MirrorError::InProgress)?;
+
+    let outcome = async {
+        let credential = decrypt_credential(state, credential.as_deref())?;
+        let pin = pin_upstream(state, &push_url).await?;
+        push_refs(
+            Path::new(&repo_path),
+            &push_url,
+            credential.as_ref(),
+            pin.as_ref(),
+        )
+        .await
+    }
+    .await;
+
+    match &outcome {
+        Ok(()) => {
+            sqlx::query!(
+                "UPDATE projects SET
+                    push_mirror_last_pushed_at = now(),
+                    push_mirror_last_error = NULL,
+                    push_mirror_attempts = 0,
+                    push_mirror_pending_since = CASE
+                        WHEN push_mirror_pending_since IS NOT DISTINCT FROM $2 THEN NULL
+                        ELSE push_mirror_pending_since END
+                 WHERE id = $1",
+                project_id,
+                pending_since,
+            )
+            .execute(&state.pool)
+            .await?;
+        }
+        Err(e) => {
+            sqlx::query!(
+                "UPDATE projects SET
+                    push_mirror_last_error = $2,
+                    push_mirror_attempts = push_mirror_attempts + 1,
+                    push_mirror_pending_since = COALESCE(push_mirror_pending_since, now()),
+                    push_mirror_next_attempt_at = now() + make_interval(secs =>
+                        LEAST($3::int4 * power(2, LEAST(push_mirror_attempts, 16)), $4::int4))
+                 WHERE id = $1",
+                project_id,
+                e.to_string(),
+                BASE_BACKOFF_SECS,
+                MAX_BACKOFF_SECS,
+            )
+            .execute(&state.pool)
+            .await?;
+        }
+    }
+    outcome
+}
+
+/// Force-push all branches and tags, deleting remote branches and tags that
+/// no longer exist here. The credential never appears in argv or the URL.
+async fn push_refs(
+    repo_path: &Path,
+    push_url: &str,
+    credential: Option<&RemoteCredential>,
+    pin: Option<&PinnedRemote>,
+) -> Result<(), MirrorError> {
+    let mut cmd = tokio::process::Command::new("git");
+    cmd.arg("-C")
+        .arg(repo_path)
+        .args(["-c", "http.followRedirects=false"]);
+    if let Some(pin) = pin {
+        pin.apply_http(&mut cmd);
+    }
+    cmd.args(["push", "--force", "--prune", "--", push_url])
+        .args(["refs/heads/*:refs/heads/*", "refs/tags/*:refs/tags/*"])
+        .kill_on_drop(true);
+    let key_dir = configure_remote_auth(&mut cmd, repo_path, credential)
+        .await
+        .map_err(|e| MirrorError::SyncFailed(e.to_string()))?;
+    if let Some(pin) = pin {
+        pin.apply_ssh(&mut cmd);
+    }
+
+    let output = tokio::time::timeout(PUSH_TIMEOUT, cmd.output()).await;
+    if let Some(dir) = key_dir {
+        let _ = tokio::fs::remove_dir_all(dir).await;
+    }
+    let output = output
+        .map_err(|_| MirrorError::SyncFailed("git push timed out".into()))?
+        .map_err(|e| MirrorError::SyncFailed(format!("failed to run git push: {e}")))?;
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(MirrorError::SyncFailed(format!(
+            "git push failed: {}",
+            stderr.trim()
+        )));
+    }
+    Ok(())
+}
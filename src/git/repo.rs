@@ -48,7 +48,7 @@ pub async fn init_bare_repo(
         .await
         .context("failed to set HEAD")?;
 
-    let files = templates::project_template_files(name);
+    let files = templates::project_template_files(name, default_branch);
     create_initial_commit(&repo_dir, default_branch, &files)
         .await
         .context("failed to create initial commit")?;
@@ -99,6 +99,44 @@ pub async fn init_bare_repo_with_files(
     Ok(repo_dir)
 }
 
+/// Point the bare repo's `HEAD` at `default_branch`, so clones check it out
+/// and browsing without a ref shows it.
+///
+/// Returns `false`, leaving `HEAD` alone, if the repo has branches but not
+/// this one; an empty repo may name a branch its first push will create.
+#[tracing::instrument(fields(repo = %repo_dir.display()), err)]
+pub async fn set_default_branch(repo_dir: &Path, default_branch: &str) -> anyhow::Result<bool> {
+    let output = tokio::process::Command::new("git")
+        .arg("-C")
+        .arg(repo_dir)
+        .args(["for-each-ref", "--format=%(refname)", "refs/heads/"])
+        .output()
+        .await
+        .context("failed to run git for-each-ref")?;
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        anyhow::bail!("git for-each-ref failed: {stderr}");
+    }
+    let target = format!("refs/heads/{default_branch}");
+    let branches = String::from_utf8_lossy(&output.stdout);
+    if !branches.trim().is_empty() && !branches.lines().any(|b| b == target) {
+        return Ok(false);
+    }
+
+    let output = tokio::process::Command::new("git")
+        .arg("-C")
+        .arg(repo_dir)
+        .args(["symbolic-ref", "HEAD", &target])
+        .output()
+        .await
+        .context("failed to run git symbolic-ref")?;
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        anyhow::bail!("git symbolic-ref failed: {stderr}");
+    }
+    Ok(true)
+}
+
 /// Create the initial commit with template files in a bare repo using git plumbing.
 ///
 /// Supports arbitrarily nested paths (e.g. `.claude/commands/dev.md`) by
@@ -19,7 +19,7 @@ use tokio_util::io::ReaderStream;
 use uuid::Uuid;
 
 use crate::audit::{AuditEntry, send_audit};
-use crate::auth::{password, token};
+use crate::auth::{lockout, password, token};
 use crate::error::ApiError;
 use crate::rbac::{Permission, resolver};
 use crate::store::AppState;
@@ -116,14 +116,19 @@ fn validate_ref(git_ref: &str) -> Result<(), ApiError> {
 /// Authenticate a git client via HTTP Basic Auth.
 ///
 /// Tries the password as an API token first, then falls back to password verification.
+/// Accounts with password login disabled or TOTP enabled must use a token.
 /// This is NOT an axum extractor — called explicitly by smart HTTP handlers.
-pub async fn authenticate_basic(headers: &HeaderMap, pool: &PgPool) -> Result<GitUser, ApiError> {
+pub async fn authenticate_basic(
+    headers: &HeaderMap,
+    state: &AppState,
+) -> Result<GitUser, ApiError> {
+    let pool = &state.pool;
     let (username, password_raw) = extract_basic_credentials(headers)?;
 
     // Look up user by name
     let user_row = sqlx::query!(
         r#"
-        SELECT id, name, password_hash, is_active
+        SELECT id, name, password_hash, is_active, password_login_disabled
         FROM users WHERE name = $1
         "#,
         username,
@@ -207,14 +212,19 @@ pub async fn authenticate_basic(headers: &HeaderMap, pool: &PgPool) -> Result<Gi
         .map_or_else(|| password::dummy_hash(), |u| u.password_hash.as_str());
 
     let valid = password::verify_password(&password_raw, hash_to_verify);
+    let account = user_row.as_ref().map(|u| (u.id, u.name.as_str()));
+    check_lockout(state, &username, account, valid).await?;
 
     let Some(user) = user_row else {
         return Err(ApiError::Unauthorized);
     };
-    if !user.is_active || !valid {
+    if !user.is_active {
         return Err(ApiError::Unauthorized);
     }
 
+    check_password_allowed(pool, user.id, user.password_login_disabled).await?;
+    lockout::clear_failures(&state.valkey, user.id).await?;
+
     Ok(GitUser {
         user_id: user.id,
         user_name: user.name,
@@ -225,6 +235,52 @@ pub async fn authenticate_basic(headers: &HeaderMap, pool: &PgPool) -> Result<Gi
     })
 }
 
+/// Same lockout as the web login. Names without an account are counted too,
+/// so a 423 does not reveal which accounts exist.
+async fn check_lockout(
+    state: &AppState,
+    username: &str,
+    account: Option<(Uuid, &str)>,
+    valid: bool,
+) -> Result<(), ApiError> {
+    let subject = account.map_or(lockout::Subject::UnknownName(username), |(id, _)| {
+        lockout::Subject::User(id)
+    });
+    lockout::check(&state.valkey, subject).await?;
+    match account {
+        Some(_) if valid => Ok(()),
+        Some((id, name)) => {
+            crate::api::users::record_login_failure(state, None, id, name).await?;
+            Err(ApiError::Unauthorized)
+        }
+        None => {
+            lockout::record_failure(&state.valkey, &state.config, subject).await?;
+            Err(ApiError::Unauthorized)
+        }
+    }
+}
+
+/// A password alone must not bypass what the web login enforces: accounts
+/// with password login disabled or a second factor use API tokens for git.
+async fn check_password_allowed(
+    pool: &PgPool,
+    user_id: Uuid,
+    password_login_disabled: bool,
+) -> Result<(), ApiError> {
+    let second_factor = sqlx::query_scalar!(
+        r#"SELECT EXISTS(SELECT 1 FROM user_totp WHERE user_id = $1 AND enabled) as "exists!""#,
+        user_id,
+    )
+    .fetch_one(pool)
+    .await?;
+    if password_login_disabled || second_factor {
+        return Err(ApiError::BadRequest(
+            "this account cannot use its password for git — authenticate with an API token".into(),
+        ));
+    }
+    Ok(())
+}
+
 /// Extract username and password from HTTP Basic Auth header.
 fn extract_basic_credentials(headers: &HeaderMap) -> Result<(String, String), ApiError> {
     let auth_value = headers
@@ -577,6 +633,7 @@ async fn receive_pack(
             default_branch: project.default_branch.clone(),
             pushed_branches,
             pushed_tags,
+            ref_updates,
         };
         tokio::spawn(async move {
             if let Err(e) = super::hooks::post_receive(&hook_state, &params).await {
@@ -614,7 +671,8 @@ async fn receive_pack(
 /// Check RBAC access for an already-authenticated git user.
 ///
 /// Enforces token scope (project + workspace), visibility rules, and permission checks.
-/// Returns `Ok(())` if allowed, `Err(NotFound)` if denied (to avoid leaking repo existence).
+/// Returns `Ok(())` if allowed, `Err(NotFound)` if denied (to avoid leaking repo existence),
+/// and `Err(Locked)` for pushes to an archived project.
 pub async fn check_access_for_user(
     state: &AppState,
     git_user: &GitUser,
@@ -642,6 +700,11 @@ pub async fn check_access_for_user(
         }
     }
 
+    // Token scopes gate the git operation itself, even on public repos
+    if !resolver::git_scope_allows(git_user.token_scopes.as_deref(), !is_read) {
+        return Err(ApiError::Forbidden);
+    }
+
     // Public or internal repos: any authenticated user can read
     if is_read && (project.visibility == "public" || project.visibility == "internal") {
         return Ok(());
@@ -654,14 +717,13 @@ pub async fn check_access_for_user(
         Permission::ProjectWrite
     };
 
-    // A8: Use has_permission_scoped to enforce API token scopes
-    let allowed = resolver::has_permission_scoped(
+    // Token scopes were checked above (`repo:*` or `project:*`)
+    let allowed = resolver::has_permission(
         &state.pool,
         &state.valkey,
         git_user.user_id,
         Some(project.project_id),
         perm,
-        git_user.token_scopes.as_deref(),
     )
     .await
     .map_err(ApiError::Internal)?;
@@ -670,6 +732,19 @@ pub async fn check_access_for_user(
         return Err(ApiError::NotFound("repository".into()));
     }
 
+    // Archived projects stay cloneable but refuse pushes
+    if !is_read {
+        let archived = sqlx::query_scalar!(
+            r#"SELECT archived_at IS NOT NULL as "archived!" FROM projects WHERE id = $1"#,
+            project.project_id,
+        )
+        .fetch_optional(&state.pool)
+        .await?;
+        if archived == Some(true) {
+            return Err(ApiError::Locked("project is archived".into()));
+        }
+    }
+
     Ok(())
 }
 
@@ -688,7 +763,7 @@ async fn check_access(
         return Ok(None);
     }
 
-    let git_user = authenticate_basic(headers, &state.pool).await?;
+    let git_user = authenticate_basic(headers, state).await?;
     // S52: rate-limit git basic auth — high enough for concurrent pipeline
     // clones (3 parallel steps × 2 calls each × multiple pipelines).
     crate::auth::rate_limit::check_rate(&state.valkey, "git_auth", &git_user.user_name, 200, 300)
@@ -504,6 +504,7 @@ async fn handle_post_push(
         default_branch: project.default_branch.clone(),
         pushed_branches,
         pushed_tags,
+        ref_updates: ref_updates.to_vec(),
     };
     if let Err(e) = hooks::post_receive(state, &params).await {
         tracing::error!(error = %e, "SSH post-receive hook failed");
@@ -23,12 +23,13 @@ pub struct TemplateFile {
 
 /// Generate the full set of template files for a new project.
 ///
-/// The `project_name` is substituted into the README.md template.
-pub fn project_template_files(project_name: &str) -> Vec<TemplateFile> {
+/// The `project_name` is substituted into the README.md template, and the
+/// `default_branch` into the `.platform.yaml` push trigger.
+pub fn project_template_files(project_name: &str, default_branch: &str) -> Vec<TemplateFile> {
     vec![
         TemplateFile {
             path: ".platform.yaml",
-            content: PLATFORM_YAML.to_owned(),
+            content: PLATFORM_YAML.replace("{{default_branch}}", default_branch),
         },
         TemplateFile {
             path: "Dockerfile",
@@ -83,13 +84,13 @@ mod tests {
 
     #[test]
     fn template_files_count() {
-        let files = project_template_files("test-project");
+        let files = project_template_files("test-project", "main");
         assert_eq!(files.len(), 12);
     }
 
     #[test]
     fn template_readme_contains_project_name() {
-        let files = project_template_files("my-awesome-app");
+        let files = project_template_files("my-awesome-app", "main");
         let readme = files.iter().find(|f| f.path == "README.md").unwrap();
         assert!(readme.content.contains("my-awesome-app"));
         assert!(!readme.content.contains("{{project_name}}"));
@@ -97,7 +98,7 @@ mod tests {
 
     #[test]
     fn template_paths_are_correct() {
-        let files = project_template_files("test");
+        let files = project_template_files("test", "main");
         let paths: Vec<&str> = files.iter().map(|f| f.path).collect();
         assert!(paths.contains(&".platform.yaml"));
         assert!(paths.contains(&"Dockerfile"));
@@ -113,16 +114,24 @@ mod tests {
         assert!(paths.contains(&"tests-e2e/test_api.py"));
     }
 
+    #[test]
+    fn template_push_trigger_uses_default_branch() {
+        let files = project_template_files("test", "develop");
+        let f = files.iter().find(|f| f.path == ".platform.yaml").unwrap();
+        assert!(f.content.contains(r#"branches: ["develop"]"#));
+        assert!(!f.content.contains("{{default_branch}}"));
+    }
+
     #[test]
     fn template_platform_yaml_has_kaniko() {
-        let files = project_template_files("test");
+        let files = project_template_files("test", "main");
         let f = files.iter().find(|f| f.path == ".platform.yaml").unwrap();
         assert!(f.content.contains("kaniko"));
     }
 
     #[test]
     fn template_claude_md_has_build_verification() {
-        let files = project_template_files("test");
+        let files = project_template_files("test", "main");
         let f = files.iter().find(|f| f.path == "CLAUDE.md").unwrap();
         assert!(f.content.contains("Build Verification"));
         assert!(f.content.contains("platform-build-status"));
@@ -130,14 +139,14 @@ mod tests {
 
     #[test]
     fn template_dev_dockerfile_extends_runner() {
-        let files = project_template_files("test");
+        let files = project_template_files("test", "main");
         let f = files.iter().find(|f| f.path == "Dockerfile.dev").unwrap();
         assert!(f.content.contains("platform-runner"));
     }
 
     #[test]
     fn template_platform_yaml_has_dev_image() {
-        let files = project_template_files("test");
+        let files = project_template_files("test", "main");
         let f = files.iter().find(|f| f.path == ".platform.yaml").unwrap();
         assert!(f.content.contains("dev_image"));
         assert!(f.content.contains("Dockerfile.dev"));
@@ -145,7 +154,7 @@ mod tests {
 
     #[test]
     fn template_claude_md_has_dev_image_docs() {
-        let files = project_template_files("test");
+        let files = project_template_files("test", "main");
         let f = files.iter().find(|f| f.path == "CLAUDE.md").unwrap();
         assert!(f.content.contains("Dev Image"));
         assert!(f.content.contains("dev_image"));
@@ -153,7 +162,7 @@ mod tests {
 
     #[test]
     fn template_dockerfile_test_has_pytest() {
-        let files = project_template_files("test");
+        let files = project_template_files("test", "main");
         let f = files.iter().find(|f| f.path == "Dockerfile.test").unwrap();
         assert!(f.content.contains("pytest"));
         assert!(f.content.contains("APP_HOST"));
@@ -161,7 +170,7 @@ mod tests {
 
     #[test]
     fn template_pipeline_has_build_test() {
-        let files = project_template_files("test");
+        let files = project_template_files("test", "main");
         let f = files.iter().find(|f| f.path == ".platform.yaml").unwrap();
         assert!(f.content.contains("build-test"));
         assert!(f.content.contains("Dockerfile.test"));
@@ -169,7 +178,7 @@ mod tests {
 
     #[test]
     fn template_deploy_has_postgres() {
-        let files = project_template_files("test");
+        let files = project_template_files("test", "main");
         let f = files
             .iter()
             .find(|f| f.path == "deploy/production.yaml")
@@ -181,7 +190,7 @@ mod tests {
 
     #[test]
     fn template_claude_md_has_dev_workflow() {
-        let files = project_template_files("test");
+        let files = project_template_files("test", "main");
         let f = files.iter().find(|f| f.path == "CLAUDE.md").unwrap();
         assert!(f.content.contains("Development Workflow"));
         assert!(f.content.contains("Create Tests First"));
@@ -190,7 +199,7 @@ mod tests {
 
     #[test]
     fn template_claude_md_has_deploy_test_docs() {
-        let files = project_template_files("test");
+        let files = project_template_files("test", "main");
         let f = files.iter().find(|f| f.path == "CLAUDE.md").unwrap();
         assert!(f.content.contains("Deploy-Test Steps"));
         assert!(f.content.contains("deploy_test"));
@@ -198,7 +207,7 @@ mod tests {
 
     #[test]
     fn template_dev_command_has_steps() {
-        let files = project_template_files("test");
+        let files = project_template_files("test", "main");
         let f = files
             .iter()
             .find(|f| f.path == ".claude/commands/dev.md")
@@ -212,7 +221,7 @@ mod tests {
 
     #[test]
     fn template_dockerfile_is_python_app() {
-        let files = project_template_files("test");
+        let files = project_template_files("test", "main");
         let f = files.iter().find(|f| f.path == "Dockerfile").unwrap();
         assert!(f.content.contains("python"));
         assert!(f.content.contains("uvicorn"));
@@ -221,7 +230,7 @@ mod tests {
 
     #[test]
     fn template_claude_md_has_visual_preview_section() {
-        let files = project_template_files("test");
+        let files = project_template_files("test", "main");
         let f = files.iter().find(|f| f.path == "CLAUDE.md").unwrap();
         assert!(f.content.contains("Visual Preview"));
         assert!(f.content.contains("port 8000"));
@@ -230,7 +239,7 @@ mod tests {
 
     #[test]
     fn template_claude_md_has_vite_instructions() {
-        let files = project_template_files("test");
+        let files = project_template_files("test", "main");
         let f = files.iter().find(|f| f.path == "CLAUDE.md").unwrap();
         assert!(f.content.contains("--host 0.0.0.0"));
         assert!(f.content.contains("--port 8000"));
@@ -238,14 +247,14 @@ mod tests {
 
     #[test]
     fn template_claude_md_has_relative_base() {
-        let files = project_template_files("test");
+        let files = project_template_files("test", "main");
         let f = files.iter().find(|f| f.path == "CLAUDE.md").unwrap();
         assert!(f.content.contains("base: './'"));
     }
 
     #[test]
     fn template_has_conftest_with_timeout() {
-        let files = project_template_files("test");
+        let files = project_template_files("test", "main");
         let f = files
             .iter()
             .find(|f| f.path == "tests-e2e/conftest.py")
@@ -257,7 +266,7 @@ mod tests {
 
     #[test]
     fn template_has_healthz_test() {
-        let files = project_template_files("test");
+        let files = project_template_files("test", "main");
         let f = files
             .iter()
             .find(|f| f.path == "tests-e2e/test_healthz.py")
@@ -268,7 +277,7 @@ mod tests {
 
     #[test]
     fn template_has_requirements_test() {
-        let files = project_template_files("test");
+        let files = project_template_files("test", "main");
         let f = files
             .iter()
             .find(|f| f.path == "requirements-test.txt")
@@ -279,7 +288,7 @@ mod tests {
 
     #[test]
     fn template_deploy_uses_app_suffix() {
-        let files = project_template_files("test");
+        let files = project_template_files("test", "main");
         let f = files
             .iter()
             .find(|f| f.path == "deploy/production.yaml")
@@ -291,7 +300,7 @@ mod tests {
 
     #[test]
     fn template_dockerfile_test_uses_tests_e2e() {
-        let files = project_template_files("test");
+        let files = project_template_files("test", "main");
         let f = files.iter().find(|f| f.path == "Dockerfile.test").unwrap();
         assert!(f.content.contains("tests-e2e/"));
         assert!(f.content.contains("--timeout=10"));
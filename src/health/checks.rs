@@ -8,9 +8,13 @@ use fred::interfaces::ClientLike;
 use sqlx::Row;
 use tracing::Instrument;
 
+use crate::observe::store::{MetricRecord, write_metrics};
 use crate::store::AppState;
 
-use super::{HealthSnapshot, PodFailureSummary, RecentPodFailure, SubsystemCheck, SubsystemStatus};
+use super::{
+    HealthSnapshot, PodFailureSummary, ReadinessReport, RecentPodFailure, SubsystemCheck,
+    SubsystemStatus,
+};
 
 /// Measure latency in ms, capped at `u64::MAX`.
 fn elapsed_ms(start: Instant) -> u64 {
@@ -305,6 +309,7 @@ pub async fn run(state: AppState, cancel: tokio_util::sync::CancellationToken) {
                 );
                 async {
                     let snapshot = build_snapshot(&state, start_time).await;
+                    record_pool_metrics(&state).await;
 
                     // Publish to Valkey for SSE subscribers
                     if let Ok(json) = serde_json::to_string(&snapshot) {
@@ -325,6 +330,35 @@ pub async fn run(state: AppState, cancel: tokio_util::sync::CancellationToken) {
     }
 }
 
+/// Record `PostgreSQL` pool utilization as `platform.db.pool.*` gauges, so it
+/// can be charted through the metrics query API. There is no pending gauge:
+/// sqlx does not expose how many callers wait for a connection.
+async fn record_pool_metrics(state: &AppState) {
+    let pool_stats = crate::store::pool::stats(&state.pool);
+    let labels = serde_json::json!({"service": "platform"});
+    let now = Utc::now();
+    let metrics: Vec<MetricRecord> = [
+        ("platform.db.pool.size", pool_stats.size),
+        ("platform.db.pool.idle", pool_stats.idle),
+        ("platform.db.pool.in_use", pool_stats.in_use),
+        ("platform.db.pool.max", pool_stats.max),
+    ]
+    .into_iter()
+    .map(|(name, value)| MetricRecord {
+        name: name.into(),
+        labels: labels.clone(),
+        metric_type: "gauge".into(),
+        unit: Some("connections".into()),
+        project_id: None,
+        timestamp: now,
+        value: f64::from(value),
+    })
+    .collect();
+    if let Err(e) = write_metrics(&state.pool, &metrics).await {
+        tracing::warn!(error = %e, "failed to record db pool metrics");
+    }
+}
+
 /// Run all probes and build a complete health snapshot.
 async fn build_snapshot(state: &AppState, start_time: Instant) -> HealthSnapshot {
     // Run async probes concurrently
@@ -358,37 +392,68 @@ async fn build_snapshot(state: &AppState, start_time: Instant) -> HealthSnapshot
     }
 }
 
-/// Quick readiness check: `Postgres` + `Valkey` + `MinIO`.
-pub async fn is_ready(state: &AppState) -> bool {
-    if let Ok(snap) = state.health.read() {
-        let age = Utc::now() - snap.checked_at;
-        // 15s cache — must be ≤ K8s probe period
-        if age.num_seconds() < 15 {
-            let required = ["postgres", "valkey", "minio"];
-            return required.iter().all(|name| {
-                snap.subsystems.iter().any(|s| {
-                    s.name == *name
-                        && matches!(
-                            s.status,
-                            SubsystemStatus::Healthy | SubsystemStatus::Degraded
-                        )
-                })
-            });
-        }
-    }
-    // Stale or missing snapshot — run live probes
-    let (pg, vk, minio) = tokio::join!(
-        check_postgres(&state.pool),
-        check_valkey(&state.valkey),
-        check_minio(&state.minio),
+/// Dependencies that must be reachable for this instance to serve traffic.
+const READINESS_DEPENDENCIES: [&str; 4] = ["postgres", "valkey", "minio", "kubernetes"];
+
+/// Readiness of every required dependency.
+///
+/// Results from the background health loop are reused while they are fresh
+/// (15s — must be ≤ K8s probe period); dependencies missing from a fresh
+/// snapshot, or all of them when it is stale, are probed live.
+pub async fn readiness(state: &AppState) -> ReadinessReport {
+    let cached: Vec<SubsystemCheck> = state
+        .health
+        .read()
+        .ok()
+        .filter(|snap| (Utc::now() - snap.checked_at).num_seconds() < 15)
+        .map(|snap| {
+            snap.subsystems
+                .iter()
+                .filter(|s| READINESS_DEPENDENCIES.contains(&s.name.as_str()))
+                .cloned()
+                .collect()
+        })
+        .unwrap_or_default();
+    let cached_check = |name: &str| cached.iter().find(|s| s.name == name).cloned();
+
+    let (pg, vk, minio, k8s) = tokio::join!(
+        async {
+            match cached_check("postgres") {
+                Some(c) => c,
+                None => check_postgres(&state.pool).await,
+            }
+        },
+        async {
+            match cached_check("valkey") {
+                Some(c) => c,
+                None => check_valkey(&state.valkey).await,
+            }
+        },
+        async {
+            match cached_check("minio") {
+                Some(c) => c,
+                None => check_minio(&state.minio).await,
+            }
+        },
+        async {
+            match cached_check("kubernetes") {
+                Some(c) => c,
+                None => check_kubernetes(&state.kube).await,
+            }
+        },
     );
-    let ok = |s: &SubsystemCheck| {
-        matches!(
-            s.status,
-            SubsystemStatus::Healthy | SubsystemStatus::Degraded
-        )
-    };
-    ok(&pg) && ok(&vk) && ok(&minio)
+
+    let report = ReadinessReport::from_checks(vec![pg, vk, minio, k8s]);
+    if !report.ready {
+        tracing::warn!(failing = ?report.failing, "readiness check failed");
+    }
+    report
+}
+
+/// Quick readiness check: `Postgres` + `Valkey` + `MinIO` + Kubernetes.
+#[allow(dead_code)] // public library API; the binary serves the full `readiness` report
+pub async fn is_ready(state: &AppState) -> bool {
+    readiness(state).await.ready
 }
 
 #[cfg(test)]
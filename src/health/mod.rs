@@ -5,6 +5,6 @@ pub mod checks;
 pub mod types;
 
 pub use types::{
-    HealthSnapshot, PodFailureSummary, RecentPodFailure, SubsystemCheck, SubsystemStatus,
-    TaskRegistry,
+    HealthSnapshot, PodFailureSummary, ReadinessReport, RecentPodFailure, SubsystemCheck,
+    SubsystemStatus, TaskRegistry,
 };
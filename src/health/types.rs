@@ -57,6 +57,37 @@ pub struct SubsystemCheck {
     pub checked_at: DateTime<Utc>,
 }
 
+// ---------------------------------------------------------------------------
+// Readiness
+// ---------------------------------------------------------------------------
+
+/// `/readyz` body: overall readiness plus the check of every required dependency.
+#[derive(Debug, Clone, Serialize, TS)]
+#[ts(export)]
+pub struct ReadinessReport {
+    pub ready: bool,
+    /// Names of the dependencies that are not healthy.
+    pub failing: Vec<String>,
+    pub dependencies: Vec<SubsystemCheck>,
+}
+
+impl ReadinessReport {
+    /// Readiness is strict: any dependency that is not healthy, including a
+    /// degraded one, takes the instance out of rotation.
+    pub fn from_checks(dependencies: Vec<SubsystemCheck>) -> Self {
+        let failing: Vec<String> = dependencies
+            .iter()
+            .filter(|d| d.status != SubsystemStatus::Healthy)
+            .map(|d| d.name.clone())
+            .collect();
+        Self {
+            ready: failing.is_empty(),
+            failing,
+            dependencies,
+        }
+    }
+}
+
 // ---------------------------------------------------------------------------
 // Background task health
 // ---------------------------------------------------------------------------
@@ -283,6 +314,39 @@ impl Default for TaskRegistry {
 mod tests {
     use super::*;
 
+    fn check(name: &str, status: SubsystemStatus) -> SubsystemCheck {
+        SubsystemCheck {
+            name: name.into(),
+            status,
+            latency_ms: 1,
+            message: None,
+            checked_at: Utc::now(),
+        }
+    }
+
+    #[test]
+    fn readiness_report_names_failing_dependencies() {
+        let report = ReadinessReport::from_checks(vec![
+            check("postgres", SubsystemStatus::Healthy),
+            check("valkey", SubsystemStatus::Unhealthy),
+            check("minio", SubsystemStatus::Degraded),
+            check("kubernetes", SubsystemStatus::Unknown),
+        ]);
+        assert!(!report.ready);
+        assert_eq!(report.failing, vec!["valkey", "minio", "kubernetes"]);
+        assert_eq!(report.dependencies.len(), 4);
+    }
+
+    #[test]
+    fn readiness_report_degraded_is_not_ready() {
+        let report = ReadinessReport::from_checks(vec![
+            check("postgres", SubsystemStatus::Degraded),
+            check("valkey", SubsystemStatus::Healthy),
+        ]);
+        assert!(!report.ready);
+        assert_eq!(report.failing, vec!["postgres"]);
+    }
+
     #[test]
     fn subsystem_status_worst_of() {
         assert_eq!(
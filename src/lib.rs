@@ -1,9 +1,13 @@
 // Copyright (c) 2026 Steven Hooker. Exclusively licensed to and distributed by AgentSphere GmbH.
 // SPDX-License-Identifier: BUSL-1.1
 
+// The git SSH exec handler's future nests deeply enough to exceed the default layout depth.
+#![recursion_limit = "256"]
+
 pub mod audit;
 pub mod config;
 pub mod error;
+pub mod features;
 pub mod health;
 pub mod store;
 pub mod validation;
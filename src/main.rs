@@ -1,6 +1,9 @@
 // Copyright (c) 2026 Steven Hooker. Exclusively licensed to and distributed by AgentSphere GmbH.
 // SPDX-License-Identifier: BUSL-1.1
 
+// The git SSH exec handler's future nests deeply enough to exceed the default layout depth.
+#![recursion_limit = "256"]
+
 use std::net::SocketAddr;
 use std::sync::Arc;
 
@@ -20,6 +23,7 @@ use tracing_subscriber::{EnvFilter, fmt, layer::SubscriberExt, util::SubscriberI
 mod audit;
 mod config;
 mod error;
+mod features;
 mod health;
 mod store;
 mod validation;
@@ -109,9 +113,13 @@ async fn main() -> anyhow::Result<()> {
 
     // Validate master key for secrets engine
     if let Some(ref mk) = cfg.master_key {
-        secrets::engine::parse_master_key(mk)
+        let ring = secrets::engine::parse_master_key(mk)
             .context("PLATFORM_MASTER_KEY is invalid (passed validation but failed parse)")?;
-        tracing::info!("secrets engine master key loaded");
+        tracing::info!(
+            key_version = ring.current_version(),
+            versions = ?ring.versions(),
+            "secrets engine master key loaded"
+        );
     } else if cfg.dev_mode {
         // Random dev key — secrets won't survive restart
         let mut key_bytes = [0u8; 32];
@@ -150,8 +158,7 @@ async fn main() -> anyhow::Result<()> {
         startup_timeout,
         store::pool::connect(
             &cfg.database_url,
-            cfg.db_max_connections,
-            cfg.db_acquire_timeout_secs,
+            &store::pool::PoolSettings::from_config(&cfg),
         ),
     )
     .await
@@ -174,6 +181,13 @@ async fn main() -> anyhow::Result<()> {
             .secret_access_key(&cfg.minio_secret_key)
             .bucket("platform")
             .region("us-east-1");
+        // Server-side encryption at rest (config validated at startup)
+        if let Some(ref sse) = cfg.minio_sse {
+            builder = builder.server_side_encryption(sse);
+            if let Some(ref key_id) = cfg.minio_sse_kms_key_id {
+                builder = builder.server_side_encryption_aws_kms_key_id(key_id);
+            }
+        }
         let op = opendal::Operator::new(builder)?.finish();
         // S55: Accept self-signed TLS certificates for MinIO in dev/test.
         // Uses reqwest 0.12 (matching opendal's internal dep) to build a
@@ -193,6 +207,7 @@ async fn main() -> anyhow::Result<()> {
     tracing::info!(
         endpoint = %cfg.minio_endpoint,
         insecure = cfg.minio_insecure,
+        sse = cfg.minio_sse.as_deref().unwrap_or("none"),
         "minio operator created"
     );
 
@@ -242,6 +257,9 @@ async fn main() -> anyhow::Result<()> {
 
     // Set configurable permission cache TTL
     rbac::resolver::set_cache_ttl(cfg.permission_cache_ttl_secs);
+    secrets::vault::configure(&cfg);
+    api::webhooks::set_project_concurrency(cfg.webhook_max_concurrent_per_project);
+    api::webhooks::set_egress_allowlist(&cfg.webhook_allowlist);
 
     // Bootstrap system roles, permissions, and create admin (dev) or setup token (prod)
     match store::bootstrap::run(&pool, cfg.admin_password.as_deref(), cfg.dev_mode).await? {
@@ -317,15 +335,27 @@ async fn main() -> anyhow::Result<()> {
             axum::routing::get(move || {
                 let s = ready_state.clone();
                 async move {
-                    if health::checks::is_ready(&s).await {
-                        (axum::http::StatusCode::OK, "ok")
+                    let report = health::checks::readiness(&s).await;
+                    let status = if report.ready {
+                        axum::http::StatusCode::OK
                     } else {
-                        (axum::http::StatusCode::SERVICE_UNAVAILABLE, "not ready")
-                    }
+                        axum::http::StatusCode::SERVICE_UNAVAILABLE
+                    };
+                    (status, axum::Json(report))
                 }
             }),
         )
-        .merge(api::router())
+        .merge(
+            api::router_with_body_limits(api::BodyLimits::from_config(&cfg))
+                .layer(axum::middleware::from_fn_with_state(
+                    state.clone(),
+                    api::idempotency::idempotency,
+                ))
+                .layer(axum::middleware::from_fn_with_state(
+                    state.clone(),
+                    auth::rate_limit::api_rate_limit,
+                )),
+        )
         .merge(api::preview::router())
         .merge(observe::router(observe_channels))
         // Git + registry routes need a higher body limit for push/LFS/blob uploads.
@@ -361,8 +391,9 @@ async fn main() -> anyhow::Result<()> {
         .layer(axum::middleware::from_fn(request_tracing_middleware))
         .with_state(state)
         .fallback(ui::static_handler)
-        // Default body limit: 10 MB for API endpoints.
-        .layer(DefaultBodyLimit::max(10 * 1024 * 1024))
+        // Default body limit for API endpoints; route groups in
+        // api::router_with_body_limits() override it.
+        .layer(DefaultBodyLimit::max(cfg.api_body_limit_bytes))
         // Compress responses (gzip) when client sends Accept-Encoding: gzip
         .layer(CompressionLayer::new())
         // Global request timeout
@@ -408,22 +439,30 @@ async fn main() -> anyhow::Result<()> {
     tracing::info!(%addr, "starting platform");
 
     let listener = tokio::net::TcpListener::bind(addr).await?;
-    axum::serve(listener, app)
-        .with_graceful_shutdown(shutdown_signal())
-        .await?;
+    axum::serve(
+        listener,
+        app.into_make_service_with_connect_info::<SocketAddr>(),
+    )
+    .with_graceful_shutdown(shutdown_signal())
+    .await?;
 
     // Signal all background tasks to stop
     tracing::info!("http server stopped, draining background tasks...");
     cancel_token.cancel();
     task_tracker.close();
 
-    // Wait for all tracked tasks to finish, with a hard deadline
-    let drain_timeout = std::time::Duration::from_secs(30);
+    // Wait for all tracked tasks to finish, with a hard deadline. The pipeline
+    // executor needs its own drain window plus time to requeue stragglers.
+    let drain_secs = cfg.pipeline_drain_timeout_secs.saturating_add(10).max(30);
+    let drain_timeout = std::time::Duration::from_secs(drain_secs);
     if tokio::time::timeout(drain_timeout, task_tracker.wait())
         .await
         .is_err()
     {
-        tracing::warn!("background tasks did not drain within 30s, forcing exit");
+        tracing::warn!(
+            drain_secs,
+            "background tasks did not drain in time, forcing exit"
+        );
     }
 
     tracing::info!("platform stopped");
@@ -476,9 +515,29 @@ fn spawn_background_tasks(
     tracker.spawn(agent::preview_watcher::run(state.clone(), token.clone()));
     let observe_channels = observe::spawn_background_tasks(state.clone(), token.clone(), &tracker);
     tracker.spawn(registry::gc::run(state.clone(), token.clone()));
+    tracker.spawn(store::lifecycle::run(state.clone(), token.clone()));
+    tracker.spawn(deployer::pod_gc::run(state.clone(), token.clone()));
+    tracker.spawn(pipeline::schedule::run(state.clone(), token.clone()));
+    tracker.spawn(git::mirror::run(state.clone(), token.clone()));
+    tracker.spawn(git::push_mirror::run(state.clone(), token.clone()));
+    tracker.spawn(rbac::delegation::run_break_glass_reaper(
+        state.clone(),
+        token.clone(),
+    ));
+    tracker.spawn(notify::digest::run_digest_flusher(
+        state.clone(),
+        token.clone(),
+    ));
     if state.config.ssh_listen.is_some() {
         tracker.spawn(git::ssh_server::run(state.clone(), token.clone()));
     }
+    if state.config.otlp_grpc_listen.is_some() {
+        tracker.spawn(observe::grpc::run(
+            state.clone(),
+            observe_channels.clone(),
+            token.clone(),
+        ));
+    }
     tracker.spawn(run_session_cleanup(
         pool.clone(),
         state.minio.clone(),
@@ -680,15 +739,15 @@ fn build_cors_layer(cfg: &config::Config) -> CorsLayer {
         ])
         .allow_credentials(true);
 
-    if cfg.cors_origins.is_empty() {
+    let matcher = auth::cors::OriginMatcher::new(&cfg.cors_origins);
+    if matcher.is_empty() {
         // No origins configured — deny cross-origin requests
         cors.allow_origin(AllowOrigin::exact(HeaderValue::from_static("null")))
     } else {
-        let origins: Vec<HeaderValue> = cfg
-            .cors_origins
-            .iter()
-            .filter_map(|o| o.parse().ok())
-            .collect();
-        cors.allow_origin(origins)
+        // Resolved per request so wildcard and regex entries work; only
+        // matching origins are reflected
+        cors.allow_origin(AllowOrigin::predicate(move |origin: &HeaderValue, _| {
+            origin.to_str().is_ok_and(|o| matcher.matches(o))
+        }))
     }
 }
@@ -172,7 +172,7 @@ impl MeshCa {
 // Helpers
 // ---------------------------------------------------------------------------
 
-fn resolve_master_key(config: &Config) -> Result<[u8; 32], MeshError> {
+fn resolve_master_key(config: &Config) -> Result<engine::MasterKey, MeshError> {
     let key_hex = config
         .master_key
         .as_deref()
@@ -183,7 +183,7 @@ fn resolve_master_key(config: &Config) -> Result<[u8; 32], MeshError> {
 
 async fn load_root_key(
     pool: &PgPool,
-    master_key: &[u8; 32],
+    master_key: &engine::MasterKey,
     secret_name: &str,
 ) -> Result<String, MeshError> {
     let row = sqlx::query!(
@@ -194,7 +194,8 @@ async fn load_root_key(
     .await?
     .ok_or_else(|| MeshError::CaInit(format!("root key secret {secret_name} not found")))?;
 
-    let plaintext = engine::decrypt(&row.encrypted_value, master_key, None)
+    let plaintext = master_key
+        .decrypt(&row.encrypted_value)
         .map_err(|e| MeshError::CaInit(format!("decrypt root key: {e}")))?;
 
     String::from_utf8(plaintext)
@@ -203,11 +204,12 @@ async fn load_root_key(
 
 async fn store_root_key(
     pool: &PgPool,
-    master_key: &[u8; 32],
+    master_key: &engine::MasterKey,
     secret_name: &str,
     root_key_pem: &str,
 ) -> Result<(), MeshError> {
-    let encrypted = engine::encrypt(root_key_pem.as_bytes(), master_key)
+    let encrypted = master_key
+        .encrypt(root_key_pem.as_bytes())
         .map_err(|e| MeshError::CaInit(format!("encrypt root key: {e}")))?;
 
     sqlx::query!(
@@ -29,7 +29,7 @@ pub async fn sync_trust_bundles(state: AppState, cancel: tokio_util::sync::Cance
     };
 
     let ca_pem = mesh_ca.trust_bundle().to_owned();
-    let mut interval = tokio::time::interval(Duration::from_secs(300));
+    let mut interval = tokio::time::interval(Duration::from_mins(5));
 
     state.task_registry.register("mesh_trust_bundle_sync", 10);
 
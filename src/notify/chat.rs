@@ -0,0 +1,338 @@
+// Copyright (c) 2026 Steven Hooker. Exclusively licensed to and distributed by AgentSphere GmbH.
+// SPDX-License-Identifier: BUSL-1.1
+
+//! Slack / Discord incoming-webhook channels.
+//!
+//! Projects register chat channels (`chat_channels` table) subscribed to a set
+//! of events; `post_event` renders a colored message in the target's native
+//! format and delivers it to every matching channel in the background.
+
+use std::fmt::Write as _;
+
+use uuid::Uuid;
+
+use crate::api::webhooks::{WEBHOOK_CLIENT, WEBHOOK_SEMAPHORE};
+use crate::store::AppState;
+
+/// Events a chat channel can subscribe to.
+pub const CHAT_EVENTS: [&str; 3] = ["pipeline", "deploy", "alert"];
+
+/// Incoming-webhook flavour; decides the payload format.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ChatKind {
+    Slack,
+    Discord,
+}
+
+impl ChatKind {
+    pub fn parse(s: &str) -> Option<Self> {
+        match s {
+            "slack" => Some(Self::Slack),
+            "discord" => Some(Self::Discord),
+            _ => None,
+        }
+    }
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Slack => "slack",
+            Self::Discord => "discord",
+        }
+    }
+}
+
+/// Message color, mapped to a sidebar/embed color by each chat service.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Tone {
+    Success,
+    Failure,
+    Warning,
+    Info,
+}
+
+impl Tone {
+    fn hex(self) -> &'static str {
+        match self {
+            Self::Success => "#2ea043",
+            Self::Failure => "#d73a49",
+            Self::Warning => "#dbab09",
+            Self::Info => "#0366d6",
+        }
+    }
+
+    fn rgb(self) -> u32 {
+        u32::from_str_radix(&self.hex()[1..], 16).unwrap_or(0)
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct ChatMessage {
+    pub title: String,
+    pub text: String,
+    pub tone: Tone,
+}
+
+/// Render a message as the JSON body the chat service expects.
+fn render(kind: ChatKind, message: &ChatMessage) -> serde_json::Value {
+    match kind {
+        ChatKind::Slack => serde_json::json!({
+            "attachments": [{
+                "color": message.tone.hex(),
+                "fallback": message.title,
+                "title": message.title,
+                "text": message.text,
+            }],
+        }),
+        ChatKind::Discord => serde_json::json!({
+            "embeds": [{
+                "title": message.title,
+                "description": message.text,
+                "color": message.tone.rgb(),
+            }],
+        }),
+    }
+}
+
+/// Post one message to a chat channel.
+///
+/// The URL is re-checked against SSRF before sending (it may have been
+/// changed in the DB since creation) unless `dev_mode` is set.
+pub async fn deliver(
+    kind: ChatKind,
+    url: &str,
+    message: &ChatMessage,
+    dev_mode: bool,
+) -> anyhow::Result<()> {
+    if !dev_mode {
+        crate::validation::check_ssrf_url(url, &["https"])
+            .map_err(|e| anyhow::anyhow!("chat channel URL rejected: {e}"))?;
+    }
+
+    let _permit = WEBHOOK_SEMAPHORE
+        .try_acquire()
+        .map_err(|_| anyhow::anyhow!("chat delivery dropped: concurrency limit reached"))?;
+
+    let resp = WEBHOOK_CLIENT
+        .post(url)
+        .header("User-Agent", "Platform-Notification/1.0")
+        .json(&render(kind, message))
+        .send()
+        .await?;
+    if !resp.status().is_success() {
+        anyhow::bail!("chat service returned {}", resp.status());
+    }
+    Ok(())
+}
+
+/// Deliver `message` to every active channel of the project subscribed to `event`.
+/// Deliveries run in background tasks; failures are logged.
+pub async fn post_event(state: &AppState, project_id: Uuid, event: &str, message: ChatMessage) {
+    let channels = match sqlx::query!(
+        "SELECT id, kind, url FROM chat_channels
+         WHERE project_id = $1 AND active = true AND $2 = ANY(events)",
+        project_id,
+        event,
+    )
+    .fetch_all(&state.pool)
+    .await
+    {
+        Ok(rows) => rows,
+        Err(e) => {
+            tracing::error!(error = %e, %project_id, event, "failed to query chat channels");
+            return;
+        }
+    };
+
+    let dev_mode = state.config.dev_mode;
+    for channel in channels {
+        let Some(kind) = ChatKind::parse(&channel.kind) else {
+            continue;
+        };
+        let (channel_id, url) = (channel.id, channel.url);
+        let message = message.clone();
+        tokio::spawn(async move {
+            if let Err(e) = deliver(kind, &url, &message, dev_mode).await {
+                tracing::warn!(error = %e, %channel_id, "chat notification failed");
+            }
+        });
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Event messages
+// ---------------------------------------------------------------------------
+
+fn short_sha(sha: &str) -> &str {
+    sha.get(..7).unwrap_or(sha)
+}
+
+fn pipeline_message(
+    project_name: &str,
+    pipeline_id: Uuid,
+    git_ref: &str,
+    commit_sha: Option<&str>,
+    status: &str,
+) -> ChatMessage {
+    let (verb, tone) = match status {
+        "success" => ("succeeded", Tone::Success),
+        "failure" => ("failed", Tone::Failure),
+        "cancelled" => ("was cancelled", Tone::Warning),
+        other => (other, Tone::Info),
+    };
+    let git_ref = git_ref.strip_prefix("refs/heads/").unwrap_or(git_ref);
+    let mut text = format!("Ref `{git_ref}`");
+    if let Some(sha) = commit_sha {
+        let _ = write!(text, " at `{}`", short_sha(sha));
+    }
+    let _ = write!(text, "\nPipeline {pipeline_id}");
+    ChatMessage {
+        title: format!("Pipeline {verb}: {project_name}"),
+        text,
+        tone,
+    }
+}
+
+/// Post a finished pipeline's result to the project's chat channels.
+pub async fn on_pipeline_finished(
+    state: &AppState,
+    project_id: Uuid,
+    pipeline_id: Uuid,
+    status: &str,
+) {
+    let row = match sqlx::query!(
+        "SELECT p.name, pl.git_ref, pl.commit_sha
+         FROM pipelines pl JOIN projects p ON p.id = pl.project_id
+         WHERE pl.id = $1",
+        pipeline_id,
+    )
+    .fetch_optional(&state.pool)
+    .await
+    {
+        Ok(row) => row,
+        Err(e) => {
+            tracing::error!(error = %e, %pipeline_id, "failed to load pipeline for chat message");
+            return;
+        }
+    };
+    let Some(row) = row else {
+        return;
+    };
+
+    let message = pipeline_message(
+        &row.name,
+        pipeline_id,
+        &row.git_ref,
+        row.commit_sha.as_deref(),
+        status,
+    );
+    post_event(state, project_id, "pipeline", message).await;
+}
+
+/// Build the message for a release reaching `action` (`deployed` / `rolled_back`).
+pub fn deploy_message(
+    project_name: &str,
+    environment: &str,
+    image_ref: &str,
+    action: &str,
+) -> ChatMessage {
+    let (verb, tone) = match action {
+        "deployed" => ("Deployed", Tone::Success),
+        "rolled_back" => ("Rolled back", Tone::Warning),
+        other => (other, Tone::Info),
+    };
+    ChatMessage {
+        title: format!("{verb} {project_name} to {environment}"),
+        text: format!("Image `{image_ref}`"),
+        tone,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn chat_kind_round_trip() {
+        for kind in [ChatKind::Slack, ChatKind::Discord] {
+            assert_eq!(ChatKind::parse(kind.as_str()), Some(kind));
+        }
+        assert_eq!(ChatKind::parse("teams"), None);
+    }
+
+    #[test]
+    fn tone_rgb_matches_hex() {
+        assert_eq!(Tone::Failure.rgb(), 0x00d7_3a49);
+        assert_eq!(Tone::Success.rgb(), 0x002e_a043);
+    }
+
+    #[test]
+    fn slack_payload_uses_colored_attachment() {
+        let msg = ChatMessage {
+            title: "Pipeline failed: web".into(),
+            text: "Ref `main`".into(),
+            tone: Tone::Failure,
+        };
+        let body = render(ChatKind::Slack, &msg);
+        assert_eq!(body["attachments"][0]["color"], "#d73a49");
+        assert_eq!(body["attachments"][0]["title"], "Pipeline failed: web");
+        assert_eq!(body["attachments"][0]["text"], "Ref `main`");
+    }
+
+    #[test]
+    fn discord_payload_uses_colored_embed() {
+        let msg = ChatMessage {
+            title: "Deployed web to production".into(),
+            text: "Image `web:1`".into(),
+            tone: Tone::Success,
+        };
+        let body = render(ChatKind::Discord, &msg);
+        assert_eq!(body["embeds"][0]["color"], 0x002e_a043);
+        assert_eq!(body["embeds"][0]["description"], "Image `web:1`");
+    }
+
+    #[test]
+    fn pipeline_message_failure_is_red() {
+        let id = Uuid::nil();
+        let msg = pipeline_message(
+            "web",
+            id,
+            "refs/heads/main",
+            Some("0123456789abcdef"),
+            "failure",
+        );
+        assert_eq!(msg.tone, Tone::Failure);
+        assert_eq!(msg.title, "Pipeline failed: web");
+        assert!(msg.text.starts_with("Ref `main` at `0123456`"));
+    }
+
+    #[test]
+    fn pipeline_message_tones() {
+        let id = Uuid::nil();
+        assert_eq!(
+            pipeline_message("web", id, "main", None, "success").tone,
+            Tone::Success
+        );
+        assert_eq!(
+            pipeline_message("web", id, "main", None, "cancelled").tone,
+            Tone::Warning
+        );
+    }
+
+    #[test]
+    fn deploy_message_rollback_is_warning() {
+        let msg = deploy_message("web", "production", "web:2", "rolled_back");
+        assert_eq!(msg.tone, Tone::Warning);
+        assert_eq!(msg.title, "Rolled back web to production");
+    }
+
+    #[tokio::test]
+    async fn deliver_rejects_private_url_outside_dev_mode() {
+        let msg = ChatMessage {
+            title: "t".into(),
+            text: String::new(),
+            tone: Tone::Info,
+        };
+        let result = deliver(ChatKind::Slack, "https://10.0.0.1/hook", &msg, false).await;
+        assert!(result.is_err());
+    }
+}
@@ -0,0 +1,295 @@
+// Copyright (c) 2026 Steven Hooker. Exclusively licensed to and distributed by AgentSphere GmbH.
+// SPDX-License-Identifier: BUSL-1.1
+
+//! Email digests.
+//!
+//! A user can ask for email notifications of a given type to be batched
+//! (`notification_preferences`). `dispatch::notify` then parks those emails in
+//! `notification_digest_queue` instead of sending them, and the digest flusher
+//! sends one summary email per user and interval once the oldest queued entry
+//! is an interval old.
+
+use std::fmt::Write as _;
+
+use tracing::Instrument;
+use uuid::Uuid;
+
+use crate::store::AppState;
+
+/// Max notifications listed individually in one digest email.
+const MAX_DIGEST_ITEMS: usize = 50;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DigestInterval {
+    Hourly,
+    Daily,
+}
+
+impl DigestInterval {
+    pub fn parse(s: &str) -> Option<Self> {
+        match s {
+            "hourly" => Some(Self::Hourly),
+            "daily" => Some(Self::Daily),
+            _ => None,
+        }
+    }
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Hourly => "hourly",
+            Self::Daily => "daily",
+        }
+    }
+
+    fn label(self) -> &'static str {
+        match self {
+            Self::Hourly => "Hourly",
+            Self::Daily => "Daily",
+        }
+    }
+}
+
+/// The user's digest interval for `notification_type`, or `None` for immediate delivery.
+pub async fn preference(
+    pool: &sqlx::PgPool,
+    user_id: Uuid,
+    notification_type: &str,
+) -> Result<Option<DigestInterval>, sqlx::Error> {
+    let digest = sqlx::query_scalar!(
+        "SELECT digest FROM notification_preferences WHERE user_id = $1 AND notification_type = $2",
+        user_id,
+        notification_type,
+    )
+    .fetch_optional(pool)
+    .await?;
+    Ok(digest.as_deref().and_then(DigestInterval::parse))
+}
+
+/// Hold a (pending) email notification for the user's next digest.
+pub async fn enqueue(
+    pool: &sqlx::PgPool,
+    notification_id: Uuid,
+    user_id: Uuid,
+    interval: DigestInterval,
+) -> Result<(), sqlx::Error> {
+    sqlx::query!(
+        "INSERT INTO notification_digest_queue (notification_id, user_id, digest) VALUES ($1, $2, $3)",
+        notification_id,
+        user_id,
+        interval.as_str(),
+    )
+    .execute(pool)
+    .await?;
+    Ok(())
+}
+
+/// Build the subject and body of a digest email from `(subject, body)` pairs.
+fn format_digest(interval: DigestInterval, items: &[(String, Option<String>)]) -> (String, String) {
+    let count = items.len();
+    let noun = if count == 1 {
+        "notification"
+    } else {
+        "notifications"
+    };
+    let subject = format!("{} digest: {count} {noun}", interval.label());
+
+    let mut body = format!("You have {count} new {noun}.\n\n");
+    for (item_subject, item_body) in items.iter().take(MAX_DIGEST_ITEMS) {
+        body.push_str("- ");
+        body.push_str(item_subject);
+        body.push('\n');
+        if let Some(first_line) = item_body
+            .as_deref()
+            .and_then(|b| b.lines().find(|l| !l.trim().is_empty()))
+        {
+            body.push_str("  ");
+            body.push_str(first_line.trim());
+            body.push('\n');
+        }
+    }
+    if count > MAX_DIGEST_ITEMS {
+        let _ = write!(body, "\n...and {} more.\n", count - MAX_DIGEST_ITEMS);
+    }
+    (subject, body)
+}
+
+/// Send every digest that is due. Returns the number of digest emails attempted.
+pub async fn flush_due(state: &AppState) -> anyhow::Result<usize> {
+    let due = sqlx::query!(
+        r#"
+        SELECT DISTINCT user_id, digest FROM notification_digest_queue
+        WHERE created_at <= now() - CASE digest
+            WHEN 'daily' THEN interval '1 day'
+            ELSE interval '1 hour'
+        END
+        "#,
+    )
+    .fetch_all(&state.pool)
+    .await?;
+
+    let mut sent = 0;
+    for row in due {
+        let Some(interval) = DigestInterval::parse(&row.digest) else {
+            continue;
+        };
+        let user_id = row.user_id;
+        if let Err(e) = send_digest(state, user_id, interval).await {
+            tracing::warn!(error = %e, %user_id, digest = row.digest, "notification digest failed");
+        }
+        sent += 1;
+    }
+    Ok(sent)
+}
+
+/// Send the user's queue for `interval` as one email. The queued rows stay
+/// locked until the send finishes and are only removed once the email went
+/// out, so a failed send is retried on the next flush.
+async fn send_digest(
+    state: &AppState,
+    user_id: Uuid,
+    interval: DigestInterval,
+) -> anyhow::Result<()> {
+    let mut tx = state.pool.begin().await?;
+    // SKIP LOCKED: another replica already sending this digest keeps it
+    let rows = sqlx::query!(
+        r#"
+        SELECT n.id, n.subject, n.body
+        FROM notification_digest_queue q
+        JOIN notifications n ON n.id = q.notification_id
+        WHERE q.user_id = $1 AND q.digest = $2
+        ORDER BY n.created_at
+        FOR UPDATE OF q SKIP LOCKED
+        "#,
+        user_id,
+        interval.as_str(),
+    )
+    .fetch_all(&mut *tx)
+    .await?;
+    if rows.is_empty() {
+        return Ok(());
+    }
+
+    let ids: Vec<Uuid> = rows.iter().map(|r| r.id).collect();
+    let items: Vec<(String, Option<String>)> =
+        rows.into_iter().map(|r| (r.subject, r.body)).collect();
+    let (subject, body) = format_digest(interval, &items);
+
+    let email = sqlx::query_scalar!(
+        "SELECT email FROM users WHERE id = $1 AND is_active = true",
+        user_id,
+    )
+    .fetch_optional(&mut *tx)
+    .await?;
+    // A failed send returns early: dropping `tx` rolls back and keeps the queue.
+    // A user who can no longer receive email is not retried.
+    let sent = match email {
+        Some(to) => {
+            crate::notify::email::send(&state.config, &to, &subject, &body).await?;
+            true
+        }
+        None => false,
+    };
+
+    sqlx::query!(
+        "DELETE FROM notification_digest_queue WHERE notification_id = ANY($1)",
+        &ids,
+    )
+    .execute(&mut *tx)
+    .await?;
+    sqlx::query!(
+        "UPDATE notifications SET status = $2 WHERE id = ANY($1) AND status = 'pending'",
+        &ids,
+        if sent { "sent" } else { "failed" },
+    )
+    .execute(&mut *tx)
+    .await?;
+    tx.commit().await?;
+
+    anyhow::ensure!(sent, "user not found for digest email");
+    Ok(())
+}
+
+/// Background task: send due digests once a minute.
+pub async fn run_digest_flusher(state: AppState, cancel: tokio_util::sync::CancellationToken) {
+    let mut interval = tokio::time::interval(std::time::Duration::from_mins(1));
+    state.task_registry.register("notification_digest", 180);
+    loop {
+        tokio::select! {
+            () = cancel.cancelled() => {
+                tracing::info!("notification digest flusher shutting down");
+                break;
+            }
+            _ = interval.tick() => {
+                let iter_trace_id = Uuid::new_v4().to_string().replace('-', "");
+                let span = tracing::info_span!(
+                    "task_iteration",
+                    task_name = "notification_digest",
+                    trace_id = %iter_trace_id,
+                    source = "system",
+                );
+                async {
+                    match flush_due(&state).await {
+                        Ok(sent) => {
+                            if sent > 0 {
+                                tracing::info!(sent, "notification digests sent");
+                            }
+                            state.task_registry.heartbeat("notification_digest");
+                        }
+                        Err(e) => {
+                            state.task_registry.report_error("notification_digest", &e.to_string());
+                            tracing::error!(error = %e, "notification digest flush failed");
+                        }
+                    }
+                }.instrument(span).await;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn interval_round_trip() {
+        for i in [DigestInterval::Hourly, DigestInterval::Daily] {
+            assert_eq!(DigestInterval::parse(i.as_str()), Some(i));
+        }
+        assert_eq!(DigestInterval::parse("immediate"), None);
+        assert_eq!(DigestInterval::parse("weekly"), None);
+    }
+
+    #[test]
+    fn format_digest_lists_each_item() {
+        let items = vec![
+            (
+                "New comment on #1".to_owned(),
+                Some("\nalice: looks good\nmore".to_owned()),
+            ),
+            ("New comment on #2".to_owned(), None),
+        ];
+        let (subject, body) = format_digest(DigestInterval::Hourly, &items);
+        assert_eq!(subject, "Hourly digest: 2 notifications");
+        assert!(body.starts_with("You have 2 new notifications."));
+        assert!(body.contains("- New comment on #1\n  alice: looks good\n"));
+        assert!(body.contains("- New comment on #2\n"));
+        assert!(!body.contains("more"));
+    }
+
+    #[test]
+    fn format_digest_singular() {
+        let items = vec![("Build failed".to_owned(), None)];
+        let (subject, _) = format_digest(DigestInterval::Daily, &items);
+        assert_eq!(subject, "Daily digest: 1 notification");
+    }
+
+    #[test]
+    fn format_digest_truncates_long_batches() {
+        let items: Vec<(String, Option<String>)> = (0..MAX_DIGEST_ITEMS + 5)
+            .map(|i| (format!("item {i}"), None))
+            .collect();
+        let (_, body) = format_digest(DigestInterval::Hourly, &items);
+        assert!(body.contains("...and 5 more."));
+        assert!(!body.contains(&format!("item {MAX_DIGEST_ITEMS}\n")));
+    }
+}
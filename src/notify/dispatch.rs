@@ -80,6 +80,20 @@ pub async fn notify(state: &AppState, notification: NewNotification) -> Result<(
 
     let notif_id = row.id;
 
+    // Emails the user batches into a digest stay pending until the flusher sends them
+    if matches!(notification.channel, NotifyChannel::Email)
+        && let Some(interval) = crate::notify::digest::preference(
+            &state.pool,
+            notification.user_id,
+            &notification.notification_type,
+        )
+        .await?
+    {
+        crate::notify::digest::enqueue(&state.pool, notif_id, notification.user_id, interval)
+            .await?;
+        return Ok(());
+    }
+
     // Route to channel
     let new_status = match notification.channel {
         NotifyChannel::Email => match send_email_notification(state, &notification).await {
@@ -189,6 +203,81 @@ pub async fn on_mr_created(state: &AppState, project_id: Uuid, mr_number: i64) {
     .await;
 }
 
+/// Notify a user that an issue was assigned to them.
+pub async fn on_issue_assigned(
+    state: &AppState,
+    project_id: Uuid,
+    issue_id: Uuid,
+    issue_number: i32,
+    assignee_id: Uuid,
+) {
+    let _ = notify(
+        state,
+        NewNotification {
+            user_id: assignee_id,
+            notification_type: "issue_assigned".into(),
+            subject: format!("Issue #{issue_number} assigned to you"),
+            body: Some(format!(
+                "You were assigned issue #{issue_number} in project {project_id}."
+            )),
+            channel: NotifyChannel::InApp,
+            ref_type: Some("issue".into()),
+            ref_id: Some(issue_id),
+        },
+    )
+    .await;
+}
+
+/// Notify an issue's author and assignee (except the commenter) of a new
+/// comment, in-app and by email.
+pub async fn on_issue_commented(
+    state: &AppState,
+    project_id: Uuid,
+    issue_id: Uuid,
+    issue_number: i32,
+    commenter_id: Uuid,
+    commenter_name: &str,
+) {
+    let recipients = match sqlx::query_scalar!(
+        r#"SELECT DISTINCT u AS "u!" FROM issues i, unnest(ARRAY[i.author_id, i.assignee_id]) AS u
+         WHERE i.id = $1 AND u IS NOT NULL AND u <> $2"#,
+        issue_id,
+        commenter_id,
+    )
+    .fetch_all(&state.pool)
+    .await
+    {
+        Ok(ids) => ids,
+        Err(e) => {
+            tracing::error!(error = %e, %issue_id, "failed to look up issue comment recipients");
+            return;
+        }
+    };
+
+    for user_id in recipients {
+        for channel in [NotifyChannel::InApp, NotifyChannel::Email] {
+            if let Err(e) = notify(
+                state,
+                NewNotification {
+                    user_id,
+                    notification_type: "issue_comment".into(),
+                    subject: format!("New comment on issue #{issue_number}"),
+                    body: Some(format!(
+                        "{commenter_name} commented on issue #{issue_number} in project {project_id}."
+                    )),
+                    channel,
+                    ref_type: Some("issue".into()),
+                    ref_id: Some(issue_id),
+                },
+            )
+            .await
+            {
+                tracing::warn!(error = %e, %user_id, "issue comment notification failed");
+            }
+        }
+    }
+}
+
 /// Notify when a deploy completes.
 pub async fn on_deploy_status(state: &AppState, project_id: Uuid, status: &str) {
     let owner = match sqlx::query!(
@@ -253,6 +342,60 @@ pub async fn on_alert_firing(state: &AppState, user_id: Uuid, alert_id: Uuid) {
     .await;
 }
 
+/// Alert every platform admin that someone granted themselves break-glass
+/// access. Sent in-app and by email so it reaches people outside the UI.
+pub async fn on_break_glass_granted(
+    state: &AppState,
+    delegation_id: Uuid,
+    user_name: &str,
+    permission: &str,
+    project_id: Uuid,
+    reason: &str,
+) {
+    let admins = match sqlx::query_scalar!(
+        "SELECT DISTINCT ur.user_id
+         FROM user_roles ur
+         JOIN role_permissions rp ON rp.role_id = ur.role_id
+         JOIN permissions p ON p.id = rp.permission_id
+         JOIN users u ON u.id = ur.user_id
+         WHERE p.name = 'admin:users' AND ur.project_id IS NULL AND u.is_active = true",
+    )
+    .fetch_all(&state.pool)
+    .await
+    {
+        Ok(ids) => ids,
+        Err(e) => {
+            tracing::error!(error = %e, %delegation_id, "failed to look up admins for break-glass alert");
+            return;
+        }
+    };
+
+    let subject = format!("Break-glass access: {user_name} granted {permission}");
+    let body = format!(
+        "{user_name} granted themselves {permission} on project {project_id}.\nReason: {reason}"
+    );
+    for admin_id in admins {
+        for channel in [NotifyChannel::InApp, NotifyChannel::Email] {
+            if let Err(e) = notify(
+                state,
+                NewNotification {
+                    user_id: admin_id,
+                    notification_type: "break_glass".into(),
+                    subject: subject.clone(),
+                    body: Some(body.clone()),
+                    channel,
+                    ref_type: Some("delegation".into()),
+                    ref_id: Some(delegation_id),
+                },
+            )
+            .await
+            {
+                tracing::warn!(error = %e, %admin_id, "break-glass notification failed");
+            }
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
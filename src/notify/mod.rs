@@ -1,8 +1,10 @@
 // Copyright (c) 2026 Steven Hooker. Exclusively licensed to and distributed by AgentSphere GmbH.
 // SPDX-License-Identifier: BUSL-1.1
 
-//! Notification dispatch: email, webhook, and in-app delivery.
+//! Notification dispatch: email, webhook, chat (Slack/Discord), and in-app delivery.
 
+pub mod chat;
+pub mod digest;
 #[allow(dead_code)]
 pub mod dispatch;
 #[allow(dead_code)]
@@ -4,7 +4,7 @@
 use hmac::{Hmac, Mac};
 use sha2::Sha256;
 
-use crate::api::webhooks::{WEBHOOK_CLIENT, WEBHOOK_SEMAPHORE, validate_webhook_url};
+use crate::api::webhooks::{WEBHOOK_SEMAPHORE, destination_client, validate_webhook_url};
 use crate::error::ApiError;
 
 /// Result of a webhook delivery attempt.
@@ -25,15 +25,19 @@ fn compute_signature(payload: &[u8], secret: &str) -> Option<String> {
 
 /// Deliver a JSON payload to a webhook URL with optional HMAC-SHA256 signing.
 ///
-/// Reuses the shared webhook HTTP client and SSRF protection from `api::webhooks`.
+/// Reuses the SSRF protection from `api::webhooks`: the host is resolved
+/// right before sending and the connection is pinned to the checked
+/// addresses, which must be public unless `dev_mode` is set.
 #[tracing::instrument(skip(url, payload, secret), err)]
 pub async fn deliver(
     url: &str,
     payload: &serde_json::Value,
     secret: Option<&str>,
+    dev_mode: bool,
 ) -> Result<DeliveryResult, ApiError> {
     // SSRF protection
     validate_webhook_url(url)?;
+    let client = destination_client(url, dev_mode).await?;
 
     // Acquire concurrency permit
     let _permit = WEBHOOK_SEMAPHORE.try_acquire().map_err(|_| {
@@ -44,7 +48,7 @@ pub async fn deliver(
     let body = serde_json::to_string(payload)
         .map_err(|e| ApiError::Internal(anyhow::anyhow!("failed to serialize payload: {e}")))?;
 
-    let mut request = WEBHOOK_CLIENT
+    let mut request = client
         .post(url)
         .header("Content-Type", "application/json")
         .header("User-Agent", "Platform-Notification/1.0");
@@ -169,7 +173,7 @@ mod tests {
     #[tokio::test]
     async fn deliver_ssrf_localhost_rejected() {
         let payload = serde_json::json!({"test": true});
-        let result = deliver("http://localhost:8080/hook", &payload, None).await;
+        let result = deliver("http://localhost:8080/hook", &payload, None, false).await;
         assert!(
             result.is_err(),
             "localhost should be rejected by SSRF check"
@@ -179,7 +183,7 @@ mod tests {
     #[tokio::test]
     async fn deliver_ssrf_private_ip_rejected() {
         let payload = serde_json::json!({"test": true});
-        let result = deliver("http://192.168.1.1:8080/hook", &payload, None).await;
+        let result = deliver("http://192.168.1.1:8080/hook", &payload, None, false).await;
         assert!(
             result.is_err(),
             "private IP should be rejected by SSRF check"
@@ -189,28 +193,34 @@ mod tests {
     #[tokio::test]
     async fn deliver_ssrf_metadata_rejected() {
         let payload = serde_json::json!({"test": true});
-        let result = deliver("http://169.254.169.254/latest/meta-data/", &payload, None).await;
+        let result = deliver(
+            "http://169.254.169.254/latest/meta-data/",
+            &payload,
+            None,
+            false,
+        )
+        .await;
         assert!(result.is_err(), "metadata endpoint should be rejected");
     }
 
     #[tokio::test]
     async fn deliver_invalid_url_rejected() {
         let payload = serde_json::json!({"test": true});
-        let result = deliver("not-a-url", &payload, None).await;
+        let result = deliver("not-a-url", &payload, None, false).await;
         assert!(result.is_err(), "invalid URL should be rejected");
     }
 
     #[tokio::test]
     async fn deliver_ftp_scheme_rejected() {
         let payload = serde_json::json!({"test": true});
-        let result = deliver("ftp://example.com/file", &payload, None).await;
+        let result = deliver("ftp://example.com/file", &payload, None, false).await;
         assert!(result.is_err(), "ftp:// scheme should be rejected");
     }
 
     #[tokio::test]
     async fn deliver_file_scheme_rejected() {
         let payload = serde_json::json!({"test": true});
-        let result = deliver("file:///etc/passwd", &payload, None).await;
+        let result = deliver("file:///etc/passwd", &payload, None, false).await;
         assert!(result.is_err(), "file:// scheme should be rejected");
     }
 }
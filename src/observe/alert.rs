@@ -40,6 +40,8 @@ pub struct CreateAlertRequest {
     pub severity: Option<String>,
     #[serde(alias = "channels")]
     pub notify_channels: Option<Vec<String>>,
+    /// Names of rules that suppress this one while firing at a higher severity.
+    pub inhibit_if: Option<Vec<String>>,
     pub project_id: Option<Uuid>,
 }
 
@@ -55,6 +57,7 @@ pub struct UpdateAlertRequest {
     pub severity: Option<String>,
     #[serde(alias = "channels")]
     pub notify_channels: Option<Vec<String>>,
+    pub inhibit_if: Option<Vec<String>>,
     pub enabled: Option<bool>,
 }
 
@@ -80,11 +83,23 @@ pub struct AlertRuleResponse {
     pub severity: String,
     #[serde(rename = "channels")]
     pub notify_channels: Vec<String>,
+    pub inhibit_if: Vec<String>,
     pub project_id: Option<Uuid>,
     pub enabled: bool,
     pub created_at: DateTime<Utc>,
 }
 
+#[derive(Debug, Deserialize)]
+pub struct EvaluateQueryParams {
+    pub query: String,
+}
+
+#[derive(Debug, Serialize, TS)]
+#[ts(export, rename = "AlertQueryValue")]
+pub struct EvaluateQueryResponse {
+    pub value: Option<f64>,
+}
+
 #[derive(Debug, Serialize, TS)]
 #[ts(export, rename = "AlertEvent")]
 pub struct AlertEventResponse {
@@ -155,6 +170,459 @@ fn parse_alert_query(query: &str) -> Result<AlertQuery, ApiError> {
     })
 }
 
+// ---------------------------------------------------------------------------
+// Alert expressions (PromQL subset)
+// ---------------------------------------------------------------------------
+//
+// Grammar:
+//   expr     := term (('+' | '-') term)*
+//   term     := factor (('*' | '/') factor)*
+//   factor   := number | '(' expr ')' | '-' factor
+//             | func '(' selector '[' duration ']' ')'
+//             | selector
+//   selector := name ('{' label '=' "value" (',' label '=' "value")* '}')?
+//   duration := integer ('s' | 'm' | 'h' | 'd')
+//
+// Every selector is reduced to a scalar by summing across matching series:
+// an instant selector takes each series' latest sample in the last
+// `INSTANT_LOOKBACK_SECS`, a range function aggregates within its window.
+
+/// How far back an instant selector looks for a series' latest sample.
+const INSTANT_LOOKBACK_SECS: i32 = 300;
+
+/// Maximum nesting depth of an expression.
+const MAX_EXPR_DEPTH: usize = 32;
+
+/// A rule query: the legacy `metric:` DSL or an expression.
+enum AlertSource {
+    Dsl(AlertQuery),
+    Expr(AlertExpr),
+}
+
+/// Range functions over a `[window]` of samples.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum RangeFunc {
+    /// Per-second increase of a counter, accounting for resets.
+    Rate,
+    /// Total increase of a counter, accounting for resets.
+    Increase,
+    /// Plain aggregation of the samples (`avg_over_time` etc.).
+    OverTime(&'static str),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum AlertExpr {
+    Number(f64),
+    Instant {
+        name: String,
+        labels: Option<serde_json::Value>,
+    },
+    Range {
+        func: RangeFunc,
+        name: String,
+        labels: Option<serde_json::Value>,
+        window_secs: i32,
+    },
+    Binary {
+        op: char,
+        lhs: Box<AlertExpr>,
+        rhs: Box<AlertExpr>,
+    },
+}
+
+/// Parse a rule query. Queries containing a `metric:` term use the legacy DSL;
+/// anything else is parsed as an expression.
+fn parse_alert_source(query: &str) -> Result<AlertSource, ApiError> {
+    if query.split_whitespace().any(|p| p.starts_with("metric:")) {
+        return parse_alert_query(query).map(AlertSource::Dsl);
+    }
+    validation::check_length("query", query, 1, 1000)?;
+    let tokens = tokenize_expr(query)?;
+    let mut parser = ExprParser { tokens, pos: 0 };
+    let expr = parser.expr(0)?;
+    if parser.pos != parser.tokens.len() {
+        return Err(ApiError::BadRequest(format!(
+            "unexpected token in query: {}",
+            parser.tokens[parser.pos]
+        )));
+    }
+    Ok(AlertSource::Expr(expr))
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+    Ident(String),
+    Number(f64),
+    Str(String),
+    Punct(char),
+}
+
+impl std::fmt::Display for Token {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Ident(s) => write!(f, "{s}"),
+            Self::Number(n) => write!(f, "{n}"),
+            Self::Str(s) => write!(f, "\"{s}\""),
+            Self::Punct(c) => write!(f, "{c}"),
+        }
+    }
+}
+
+fn tokenize_expr(input: &str) -> Result<Vec<Token>, ApiError> {
+    let mut tokens = Vec::new();
+    let mut chars = input.char_indices().peekable();
+    while let Some(&(start, c)) = chars.peek() {
+        if c.is_whitespace() {
+            chars.next();
+        } else if c.is_ascii_digit() || c == '.' {
+            let mut end = start;
+            while let Some(&(i, d)) = chars.peek() {
+                if !(d.is_ascii_digit() || d == '.') {
+                    break;
+                }
+                end = i + d.len_utf8();
+                chars.next();
+            }
+            let n = input[start..end].parse().map_err(|_| {
+                ApiError::BadRequest(format!("invalid number: {}", &input[start..end]))
+            })?;
+            tokens.push(Token::Number(n));
+        } else if c.is_ascii_alphabetic() || c == '_' || c == ':' {
+            let mut end = start;
+            while let Some(&(i, d)) = chars.peek() {
+                if !(d.is_ascii_alphanumeric() || matches!(d, '_' | ':' | '.')) {
+                    break;
+                }
+                end = i + d.len_utf8();
+                chars.next();
+            }
+            tokens.push(Token::Ident(input[start..end].to_string()));
+        } else if c == '"' {
+            chars.next();
+            let mut value = String::new();
+            loop {
+                match chars.next() {
+                    Some((_, '"')) => break,
+                    Some((_, '\\')) => match chars.next() {
+                        Some((_, e)) => value.push(e),
+                        None => return Err(ApiError::BadRequest("unterminated string".into())),
+                    },
+                    Some((_, ch)) => value.push(ch),
+                    None => return Err(ApiError::BadRequest("unterminated string".into())),
+                }
+            }
+            tokens.push(Token::Str(value));
+        } else if "()[]{},=+-*/".contains(c) {
+            chars.next();
+            tokens.push(Token::Punct(c));
+        } else {
+            return Err(ApiError::BadRequest(format!(
+                "unexpected character in query: {c}"
+            )));
+        }
+    }
+    Ok(tokens)
+}
+
+struct ExprParser {
+    tokens: Vec<Token>,
+    pos: usize,
+}
+
+impl ExprParser {
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.pos)
+    }
+
+    fn next(&mut self) -> Option<Token> {
+        let t = self.tokens.get(self.pos).cloned();
+        self.pos += 1;
+        t
+    }
+
+    fn eat(&mut self, c: char) -> bool {
+        if self.peek() == Some(&Token::Punct(c)) {
+            self.pos += 1;
+            true
+        } else {
+            false
+        }
+    }
+
+    fn expect(&mut self, c: char) -> Result<(), ApiError> {
+        if self.eat(c) {
+            Ok(())
+        } else {
+            Err(ApiError::BadRequest(format!("expected '{c}' in query")))
+        }
+    }
+
+    fn expr(&mut self, depth: usize) -> Result<AlertExpr, ApiError> {
+        if depth > MAX_EXPR_DEPTH {
+            return Err(ApiError::BadRequest("query is nested too deeply".into()));
+        }
+        let mut lhs = self.term(depth)?;
+        while let Some(Token::Punct(op @ ('+' | '-'))) = self.peek().cloned() {
+            self.pos += 1;
+            let rhs = self.term(depth)?;
+            lhs = AlertExpr::Binary {
+                op,
+                lhs: Box::new(lhs),
+                rhs: Box::new(rhs),
+            };
+        }
+        Ok(lhs)
+    }
+
+    fn term(&mut self, depth: usize) -> Result<AlertExpr, ApiError> {
+        let mut lhs = self.factor(depth)?;
+        while let Some(Token::Punct(op @ ('*' | '/'))) = self.peek().cloned() {
+            self.pos += 1;
+            let rhs = self.factor(depth)?;
+            lhs = AlertExpr::Binary {
+                op,
+                lhs: Box::new(lhs),
+                rhs: Box::new(rhs),
+            };
+        }
+        Ok(lhs)
+    }
+
+    fn factor(&mut self, depth: usize) -> Result<AlertExpr, ApiError> {
+        match self.next() {
+            Some(Token::Number(n)) => Ok(AlertExpr::Number(n)),
+            Some(Token::Punct('(')) => {
+                let e = self.expr(depth + 1)?;
+                self.expect(')')?;
+                Ok(e)
+            }
+            Some(Token::Punct('-')) => Ok(AlertExpr::Binary {
+                op: '-',
+                lhs: Box::new(AlertExpr::Number(0.0)),
+                rhs: Box::new(self.factor(depth + 1)?),
+            }),
+            Some(Token::Ident(ident)) => {
+                if self.peek() == Some(&Token::Punct('(')) {
+                    self.range_call(&ident)
+                } else {
+                    let labels = self.labels()?;
+                    validation::check_length("metric_name", &ident, 1, 255)?;
+                    Ok(AlertExpr::Instant {
+                        name: ident,
+                        labels,
+                    })
+                }
+            }
+            Some(t) => Err(ApiError::BadRequest(format!(
+                "unexpected token in query: {t}"
+            ))),
+            None => Err(ApiError::BadRequest("unexpected end of query".into())),
+        }
+    }
+
+    /// `func(selector[duration])`
+    fn range_call(&mut self, func: &str) -> Result<AlertExpr, ApiError> {
+        let func = match func {
+            "rate" => RangeFunc::Rate,
+            "increase" => RangeFunc::Increase,
+            "avg_over_time" => RangeFunc::OverTime("avg"),
+            "sum_over_time" => RangeFunc::OverTime("sum"),
+            "min_over_time" => RangeFunc::OverTime("min"),
+            "max_over_time" => RangeFunc::OverTime("max"),
+            "count_over_time" => RangeFunc::OverTime("count"),
+            other => return Err(ApiError::BadRequest(format!("unknown function: {other}"))),
+        };
+        self.expect('(')?;
+        let Some(Token::Ident(name)) = self.next() else {
+            return Err(ApiError::BadRequest("expected metric name".into()));
+        };
+        validation::check_length("metric_name", &name, 1, 255)?;
+        let labels = self.labels()?;
+        self.expect('[')?;
+        let window_secs = self.duration()?;
+        self.expect(']')?;
+        self.expect(')')?;
+        Ok(AlertExpr::Range {
+            func,
+            name,
+            labels,
+            window_secs,
+        })
+    }
+
+    /// Optional `{k="v", ...}` matcher, as a JSON object for `labels @>`.
+    fn labels(&mut self) -> Result<Option<serde_json::Value>, ApiError> {
+        if !self.eat('{') {
+            return Ok(None);
+        }
+        let mut map = serde_json::Map::new();
+        if !self.eat('}') {
+            loop {
+                let Some(Token::Ident(key)) = self.next() else {
+                    return Err(ApiError::BadRequest("expected label name".into()));
+                };
+                self.expect('=')?;
+                let Some(Token::Str(value)) = self.next() else {
+                    return Err(ApiError::BadRequest("expected quoted label value".into()));
+                };
+                map.insert(key, serde_json::Value::String(value));
+                if self.eat('}') {
+                    break;
+                }
+                self.expect(',')?;
+            }
+        }
+        Ok(Some(serde_json::Value::Object(map)))
+    }
+
+    fn duration(&mut self) -> Result<i32, ApiError> {
+        let n = match self.next() {
+            Some(Token::Number(n)) if n.fract() == 0.0 && n > 0.0 => n,
+            _ => return Err(ApiError::BadRequest("expected duration like 5m".into())),
+        };
+        let Some(Token::Ident(unit)) = self.next() else {
+            return Err(ApiError::BadRequest(
+                "duration needs a unit (s, m, h, d)".into(),
+            ));
+        };
+        let mult = match unit.as_str() {
+            "s" => 1.0,
+            "m" => 60.0,
+            "h" => 3600.0,
+            "d" => 86400.0,
+            _ => {
+                return Err(ApiError::BadRequest(format!(
+                    "unknown duration unit: {unit}"
+                )));
+            }
+        };
+        let secs = n * mult;
+        if !(10.0..=86400.0).contains(&secs) {
+            return Err(ApiError::BadRequest(
+                "range must be between 10 seconds and 1 day".into(),
+            ));
+        }
+        #[allow(clippy::cast_possible_truncation)]
+        Ok(secs as i32)
+    }
+}
+
+/// Evaluate a rule query to a single value (`None` when there is no data).
+async fn evaluate_source(
+    pool: &sqlx::PgPool,
+    source: &AlertSource,
+) -> Result<Option<f64>, sqlx::Error> {
+    match source {
+        AlertSource::Dsl(aq) => {
+            evaluate_metric(
+                pool,
+                &aq.metric_name,
+                aq.labels.as_ref(),
+                &aq.aggregation,
+                aq.window_secs,
+            )
+            .await
+        }
+        AlertSource::Expr(expr) => evaluate_expr(pool, expr).await,
+    }
+}
+
+fn evaluate_expr<'a>(
+    pool: &'a sqlx::PgPool,
+    expr: &'a AlertExpr,
+) -> std::pin::Pin<Box<dyn Future<Output = Result<Option<f64>, sqlx::Error>> + Send + 'a>> {
+    Box::pin(async move {
+        match expr {
+            AlertExpr::Number(n) => Ok(Some(*n)),
+            AlertExpr::Instant { name, labels } => {
+                sqlx::query_scalar::<_, Option<f64>>(
+                    r"SELECT SUM(latest.value) FROM (
+                        SELECT DISTINCT ON (ms.series_id) ms.value
+                        FROM metric_samples ms
+                        JOIN metric_series ser ON ser.id = ms.series_id
+                        WHERE ser.name = $1 AND ($2::jsonb IS NULL OR ser.labels @> $2)
+                          AND ms.timestamp > now() - $3::interval
+                        ORDER BY ms.series_id, ms.timestamp DESC
+                      ) latest",
+                )
+                .bind(name)
+                .bind(labels)
+                .bind(format!("{INSTANT_LOOKBACK_SECS} seconds"))
+                .fetch_one(pool)
+                .await
+            }
+            AlertExpr::Range {
+                func: RangeFunc::OverTime(agg),
+                name,
+                labels,
+                window_secs,
+            } => evaluate_metric(pool, name, labels.as_ref(), agg, *window_secs).await,
+            AlertExpr::Range {
+                func,
+                name,
+                labels,
+                window_secs,
+            } => {
+                let increase = evaluate_increase(pool, name, labels.as_ref(), *window_secs).await?;
+                Ok(match func {
+                    RangeFunc::Rate => increase.map(|v| v / f64::from(*window_secs)),
+                    _ => increase,
+                })
+            }
+            AlertExpr::Binary { op, lhs, rhs } => {
+                let (Some(l), Some(r)) = (
+                    evaluate_expr(pool, lhs).await?,
+                    evaluate_expr(pool, rhs).await?,
+                ) else {
+                    return Ok(None);
+                };
+                Ok(apply_binary(*op, l, r))
+            }
+        }
+    })
+}
+
+/// Counter increase over the window, summed across series. A sample lower
+/// than its predecessor is treated as a reset, contributing its full value.
+async fn evaluate_increase(
+    pool: &sqlx::PgPool,
+    name: &str,
+    labels: Option<&serde_json::Value>,
+    window_secs: i32,
+) -> Result<Option<f64>, sqlx::Error> {
+    sqlx::query_scalar::<_, Option<f64>>(
+        r"SELECT SUM(CASE
+                     WHEN prev IS NULL THEN 0
+                     WHEN value >= prev THEN value - prev
+                     ELSE value
+                 END)
+          FROM (
+            SELECT ms.value,
+                   lag(ms.value) OVER (PARTITION BY ms.series_id ORDER BY ms.timestamp) AS prev
+            FROM metric_samples ms
+            JOIN metric_series ser ON ser.id = ms.series_id
+            WHERE ser.name = $1 AND ($2::jsonb IS NULL OR ser.labels @> $2)
+              AND ms.timestamp > now() - $3::interval
+          ) deltas",
+    )
+    .bind(name)
+    .bind(labels)
+    .bind(format!("{window_secs} seconds"))
+    .fetch_one(pool)
+    .await
+}
+
+/// Apply an arithmetic operator. Division by zero yields no value.
+fn apply_binary(op: char, l: f64, r: f64) -> Option<f64> {
+    match op {
+        '+' => Some(l + r),
+        '-' => Some(l - r),
+        '*' => Some(l * r),
+        '/' if r != 0.0 => Some(l / r),
+        _ => None,
+    }
+}
+
 // ---------------------------------------------------------------------------
 // Router
 // ---------------------------------------------------------------------------
@@ -163,6 +631,7 @@ pub fn router() -> Router<AppState> {
     Router::new()
         .route("/api/observe/alerts", get(list_alerts).post(create_alert))
         .route("/api/observe/alerts/events", get(list_all_alert_events))
+        .route("/api/observe/alerts/evaluate", get(evaluate_query))
         .route(
             "/api/observe/alerts/{id}",
             get(get_alert).patch(update_alert).delete(delete_alert),
@@ -243,7 +712,7 @@ async fn list_alerts(
     let rows = sqlx::query(
         r"
         SELECT id, name, description, query, condition, threshold,
-               for_seconds, severity, notify_channels, project_id,
+               for_seconds, severity, notify_channels, inhibit_if, project_id,
                enabled, created_at
         FROM alert_rules
         WHERE ($1::uuid IS NULL OR project_id = $1)
@@ -271,6 +740,7 @@ async fn list_alerts(
             for_seconds: r.get("for_seconds"),
             severity: r.get("severity"),
             notify_channels: r.get("notify_channels"),
+            inhibit_if: r.get("inhibit_if"),
             project_id: r.get("project_id"),
             enabled: r.get("enabled"),
             created_at: r.get("created_at"),
@@ -293,8 +763,8 @@ async fn create_alert(
         validation::check_length("description", desc, 0, 10_000)?;
     }
 
-    // Validate query DSL
-    parse_alert_query(&body.query)?;
+    // Validate query DSL / expression
+    parse_alert_source(&body.query)?;
 
     validate_condition(&body.condition)?;
 
@@ -313,14 +783,18 @@ async fn create_alert(
     }
 
     let channels = body.notify_channels.as_deref().unwrap_or(&[]);
+    validate_channels(channels)?;
+    let inhibit_if = body.inhibit_if.as_deref().unwrap_or(&[]);
+    validate_inhibit_if(inhibit_if)?;
 
     let row = sqlx::query(
         r"
         INSERT INTO alert_rules (name, description, query, condition, threshold,
-                                 for_seconds, severity, notify_channels, project_id)
-        VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9)
+                                 for_seconds, severity, notify_channels, project_id,
+                                 inhibit_if)
+        VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10)
         RETURNING id, name, description, query, condition, threshold,
-                  for_seconds, severity, notify_channels, project_id,
+                  for_seconds, severity, notify_channels, inhibit_if, project_id,
                   enabled, created_at
         ",
     )
@@ -333,6 +807,7 @@ async fn create_alert(
     .bind(severity)
     .bind(channels)
     .bind(body.project_id)
+    .bind(inhibit_if)
     .fetch_one(&state.pool)
     .await?;
 
@@ -364,6 +839,7 @@ async fn create_alert(
             for_seconds: row.get("for_seconds"),
             severity: row.get("severity"),
             notify_channels: row.get("notify_channels"),
+            inhibit_if: row.get("inhibit_if"),
             project_id: row.get("project_id"),
             enabled: row.get("enabled"),
             created_at: row.get("created_at"),
@@ -382,7 +858,7 @@ async fn get_alert(
     let row = sqlx::query(
         r"
         SELECT id, name, description, query, condition, threshold,
-               for_seconds, severity, notify_channels, project_id,
+               for_seconds, severity, notify_channels, inhibit_if, project_id,
                enabled, created_at
         FROM alert_rules WHERE id = $1
         ",
@@ -402,6 +878,7 @@ async fn get_alert(
         for_seconds: row.get("for_seconds"),
         severity: row.get("severity"),
         notify_channels: row.get("notify_channels"),
+        inhibit_if: row.get("inhibit_if"),
         project_id: row.get("project_id"),
         enabled: row.get("enabled"),
         created_at: row.get("created_at"),
@@ -424,7 +901,7 @@ async fn update_alert(
         validation::check_length("description", desc, 0, 10_000)?;
     }
     if let Some(ref query) = body.query {
-        parse_alert_query(query)?;
+        parse_alert_source(query)?;
     }
     if let Some(ref condition) = body.condition {
         validate_condition(condition)?;
@@ -443,6 +920,12 @@ async fn update_alert(
             "severity must be info, warning, or critical".into(),
         ));
     }
+    if let Some(ref channels) = body.notify_channels {
+        validate_channels(channels)?;
+    }
+    if let Some(ref inhibit_if) = body.inhibit_if {
+        validate_inhibit_if(inhibit_if)?;
+    }
 
     let row = sqlx::query(
         r"
@@ -455,10 +938,11 @@ async fn update_alert(
             for_seconds = COALESCE($7, for_seconds),
             severity = COALESCE($8, severity),
             notify_channels = COALESCE($9, notify_channels),
-            enabled = COALESCE($10, enabled)
+            enabled = COALESCE($10, enabled),
+            inhibit_if = COALESCE($11, inhibit_if)
         WHERE id = $1
         RETURNING id, name, description, query, condition, threshold,
-                  for_seconds, severity, notify_channels, project_id,
+                  for_seconds, severity, notify_channels, inhibit_if, project_id,
                   enabled, created_at
         ",
     )
@@ -472,6 +956,7 @@ async fn update_alert(
     .bind(&body.severity)
     .bind(body.notify_channels.as_deref())
     .bind(body.enabled)
+    .bind(body.inhibit_if.as_deref())
     .fetch_optional(&state.pool)
     .await?
     .ok_or_else(|| ApiError::NotFound("alert rule".into()))?;
@@ -502,6 +987,7 @@ async fn update_alert(
         for_seconds: row.get("for_seconds"),
         severity: row.get("severity"),
         notify_channels: row.get("notify_channels"),
+        inhibit_if: row.get("inhibit_if"),
         project_id: row_project_id,
         enabled: row.get("enabled"),
         created_at: row.get("created_at"),
@@ -637,6 +1123,32 @@ async fn list_all_alert_events(
 // Validation helpers
 // ---------------------------------------------------------------------------
 
+/// Evaluate an alert query (DSL or expression) now, e.g. to preview a rule
+/// or drive a dashboard panel.
+#[tracing::instrument(skip(state), err)]
+async fn evaluate_query(
+    State(state): State<AppState>,
+    auth: AuthUser,
+    Query(params): Query<EvaluateQueryParams>,
+) -> Result<Json<EvaluateQueryResponse>, ApiError> {
+    require_observe_read(&state, &auth).await?;
+    let source = parse_alert_source(&params.query)?;
+    let value = evaluate_source(&state.pool, &source).await?;
+    Ok(Json(EvaluateQueryResponse { value }))
+}
+
+fn validate_inhibit_if(names: &[String]) -> Result<(), ApiError> {
+    if names.len() > 20 {
+        return Err(ApiError::BadRequest(
+            "at most 20 inhibit_if rules allowed".into(),
+        ));
+    }
+    for name in names {
+        validation::check_length("inhibit_if", name, 1, 255)?;
+    }
+    Ok(())
+}
+
 fn validate_condition(condition: &str) -> Result<(), ApiError> {
     if !["gt", "lt", "eq", "absent"].contains(&condition) {
         return Err(ApiError::BadRequest(
@@ -653,6 +1165,8 @@ fn validate_condition(condition: &str) -> Result<(), ApiError> {
 pub struct AlertState {
     pub first_triggered: Option<DateTime<Utc>>,
     pub firing: bool,
+    /// Firing, but suppressed by a higher-severity rule in `inhibit_if`.
+    pub inhibited: bool,
 }
 
 /// Background task that evaluates alert rules every 30 seconds.
@@ -695,7 +1209,8 @@ pub async fn evaluate_all(
     alert_states: &mut HashMap<Uuid, AlertState>,
 ) -> Result<(), anyhow::Error> {
     let rules = sqlx::query(
-        "SELECT id, name, query, condition, threshold, for_seconds, severity, project_id \
+        "SELECT id, name, query, condition, threshold, for_seconds, severity, project_id, \
+                notify_channels, inhibit_if \
          FROM alert_rules WHERE enabled = true ORDER BY id LIMIT 500",
     )
     .fetch_all(&state.pool)
@@ -705,14 +1220,16 @@ pub async fn evaluate_all(
         tracing::warn!("alert rule limit reached (500) — some rules may not be evaluated");
     }
 
+    // Phase 1: evaluate every rule and advance its state machine.
     let rule_timeout = std::time::Duration::from_secs(10);
+    let mut evaluated = Vec::with_capacity(rules.len());
     for rule in &rules {
         let rule_id: Uuid = rule.get("id");
         let rule_name: String = rule.get("name");
 
         match tokio::time::timeout(rule_timeout, evaluate_one_rule(state, alert_states, rule)).await
         {
-            Ok(Ok(())) => {}
+            Ok(Ok(ev)) => evaluated.push(ev),
             Ok(Err(e)) => {
                 tracing::warn!(
                     rule_id = %rule_id, rule_name = %rule_name,
@@ -728,53 +1245,70 @@ pub async fn evaluate_all(
         }
     }
 
+    // Phase 2: with the full set of firing rules known, apply inhibition
+    // before recording events and notifying.
+    let firing: Vec<FiringRule<'_>> = rules
+        .iter()
+        .filter(|r| {
+            alert_states
+                .get(&r.get::<Uuid, _>("id"))
+                .is_some_and(|s| s.firing)
+        })
+        .map(|r| FiringRule {
+            id: r.get("id"),
+            name: r.get("name"),
+            severity: r.get("severity"),
+            project_id: r.get("project_id"),
+        })
+        .collect();
+
+    for ev in &evaluated {
+        let inhibited = is_inhibited(&ev.info, &firing);
+        if let Some(alert_state) = alert_states.get_mut(&ev.info.id) {
+            handle_alert_state(state, ev, inhibited, alert_state).await;
+        }
+    }
+
     Ok(())
 }
 
-/// Evaluate a single alert rule: parse query, fetch metric, check condition, handle state.
-async fn evaluate_one_rule(
+/// Evaluate a single alert rule: parse query, fetch metric, check condition,
+/// and advance its state. Events and notifications are handled afterwards.
+async fn evaluate_one_rule<'r>(
     state: &AppState,
     alert_states: &mut HashMap<Uuid, AlertState>,
-    rule: &sqlx::postgres::PgRow,
-) -> Result<(), anyhow::Error> {
-    let rule_id: Uuid = rule.get("id");
-    let rule_name: String = rule.get("name");
-    let rule_query: String = rule.get("query");
-    let rule_condition: String = rule.get("condition");
-    let rule_threshold: Option<f64> = rule.get("threshold");
-    let rule_for_seconds: i32 = rule.get("for_seconds");
-    let rule_severity: String = rule.get("severity");
-    let rule_project_id: Option<Uuid> = rule.get("project_id");
-
-    let aq = parse_alert_query(&rule_query)?;
-
-    let value = evaluate_metric(
-        &state.pool,
-        &aq.metric_name,
-        aq.labels.as_ref(),
-        &aq.aggregation,
-        aq.window_secs,
-    )
-    .await?;
+    rule: &'r sqlx::postgres::PgRow,
+) -> Result<EvaluatedRule<'r>, anyhow::Error> {
+    let info = AlertRuleInfo {
+        id: rule.get("id"),
+        name: rule.get("name"),
+        severity: rule.get("severity"),
+        project_id: rule.get("project_id"),
+        for_seconds: rule.get("for_seconds"),
+        condition: rule.get("condition"),
+        threshold: rule.get("threshold"),
+        channels: rule.get("notify_channels"),
+        inhibit_if: rule.get("inhibit_if"),
+    };
+    let rule_query: &str = rule.get("query");
 
-    let condition_met = check_condition(&rule_condition, rule_threshold, value);
+    let source = parse_alert_source(rule_query)?;
+    let value = evaluate_source(&state.pool, &source).await?;
 
-    let now = Utc::now();
-    let as_entry = alert_states.entry(rule_id).or_insert(AlertState {
+    let condition_met = check_condition(info.condition, info.threshold, value);
+
+    let as_entry = alert_states.entry(info.id).or_insert(AlertState {
         first_triggered: None,
         firing: false,
+        inhibited: false,
     });
+    let transition = next_alert_state(as_entry, condition_met, Utc::now(), info.for_seconds);
 
-    let rule_info = AlertRuleInfo {
-        id: rule_id,
-        name: &rule_name,
-        severity: &rule_severity,
-        project_id: rule_project_id,
-        for_seconds: rule_for_seconds,
-    };
-    handle_alert_state(state, condition_met, value, now, as_entry, &rule_info).await;
-
-    Ok(())
+    Ok(EvaluatedRule {
+        info,
+        value,
+        transition,
+    })
 }
 
 /// Metadata about an alert rule, passed to `handle_alert_state`.
@@ -784,6 +1318,44 @@ struct AlertRuleInfo<'a> {
     severity: &'a str,
     project_id: Option<Uuid>,
     for_seconds: i32,
+    condition: &'a str,
+    threshold: Option<f64>,
+    channels: Vec<String>,
+    inhibit_if: Vec<String>,
+}
+
+/// Outcome of phase 1 for one rule.
+struct EvaluatedRule<'a> {
+    info: AlertRuleInfo<'a>,
+    value: Option<f64>,
+    transition: AlertTransition,
+}
+
+/// A rule whose condition currently holds past its `for_seconds`.
+struct FiringRule<'a> {
+    id: Uuid,
+    name: &'a str,
+    severity: &'a str,
+    project_id: Option<Uuid>,
+}
+
+fn severity_rank(severity: &str) -> u8 {
+    match severity {
+        "critical" => 2,
+        "warning" => 1,
+        _ => 0,
+    }
+}
+
+/// A rule is inhibited while a rule it names in `inhibit_if`, in the same
+/// project, fires at a strictly higher severity.
+fn is_inhibited(rule: &AlertRuleInfo<'_>, firing: &[FiringRule<'_>]) -> bool {
+    firing.iter().any(|f| {
+        f.id != rule.id
+            && f.project_id == rule.project_id
+            && rule.inhibit_if.iter().any(|n| n == f.name)
+            && severity_rank(f.severity) > severity_rank(rule.severity)
+    })
 }
 
 /// Result of evaluating the alert state transition.
@@ -831,39 +1403,203 @@ fn next_alert_state(
     }
 }
 
+/// Record events and send notifications for one evaluated rule.
 async fn handle_alert_state(
     app_state: &AppState,
-    condition_met: bool,
-    value: Option<f64>,
-    now: DateTime<Utc>,
+    ev: &EvaluatedRule<'_>,
+    inhibited: bool,
     alert_state: &mut AlertState,
-    rule_info: &AlertRuleInfo<'_>,
 ) {
-    let transition = next_alert_state(alert_state, condition_met, now, rule_info.for_seconds);
-    if transition.should_fire {
-        if let Err(e) = fire_alert(&app_state.pool, rule_info.id, value).await {
-            tracing::error!(error = %e, rule_id = %rule_info.id, "failed to persist alert firing");
+    let rule_info = &ev.info;
+    let value = ev.value;
+
+    // Fire on the transition, or when an inhibited alert's inhibitor clears
+    let lifted = alert_state.firing && alert_state.inhibited && !inhibited;
+    if ev.transition.should_fire || lifted {
+        alert_state.inhibited = inhibited;
+        match open_alert_event(&app_state.pool, rule_info.id, value, inhibited).await {
+            Ok(true) => {
+                notify_alert_channels(app_state, rule_info, true, value);
+                // Publish event for downstream handlers (ops agent spawn)
+                let event = crate::store::eventbus::PlatformEvent::AlertFired {
+                    rule_id: rule_info.id,
+                    project_id: rule_info.project_id,
+                    severity: rule_info.severity.to_string(),
+                    value,
+                    message: "Alert condition met".into(),
+                    alert_name: rule_info.name.to_string(),
+                };
+                if let Err(e) = crate::store::eventbus::publish(&app_state.valkey, &event).await {
+                    tracing::error!(error = %e, rule_id = %rule_info.id, "failed to publish AlertFired event");
+                }
+            }
+            // Inhibited, or an open firing event already exists (e.g. evaluator restarted)
+            Ok(false) => {}
+            Err(e) => {
+                tracing::error!(error = %e, rule_id = %rule_info.id, "failed to persist alert firing");
+            }
         }
-        // Publish event for downstream handlers (ops agent spawn, notifications)
-        let event = crate::store::eventbus::PlatformEvent::AlertFired {
-            rule_id: rule_info.id,
-            project_id: rule_info.project_id,
-            severity: rule_info.severity.to_string(),
-            value,
-            message: "Alert condition met".into(),
-            alert_name: rule_info.name.to_string(),
-        };
-        if let Err(e) = crate::store::eventbus::publish(&app_state.valkey, &event).await {
-            tracing::error!(error = %e, rule_id = %rule_info.id, "failed to publish AlertFired event");
+    }
+    if ev.transition.should_resolve {
+        alert_state.inhibited = false;
+        match resolve_alert(&app_state.pool, rule_info.id).await {
+            Ok(true) => notify_alert_channels(app_state, rule_info, false, value),
+            Ok(false) => {}
+            Err(e) => {
+                tracing::error!(error = %e, rule_id = %rule_info.id, "failed to resolve alert");
+            }
         }
     }
-    if transition.should_resolve
-        && let Err(e) = resolve_alert(&app_state.pool, rule_info.id).await
-    {
-        tracing::error!(error = %e, rule_id = %rule_info.id, "failed to resolve alert");
+}
+
+// ---------------------------------------------------------------------------
+// Alert notifications
+// ---------------------------------------------------------------------------
+
+/// Maximum number of `notify_channels` on a rule.
+const MAX_ALERT_CHANNELS: usize = 20;
+
+/// A `notify_channels` entry: `email:<address>` or `webhook:<url>`.
+#[derive(Debug, PartialEq, Eq)]
+enum AlertChannel {
+    Email(String),
+    Webhook(String),
+}
+
+fn parse_alert_channel(channel: &str) -> Result<AlertChannel, ApiError> {
+    if let Some(addr) = channel.strip_prefix("email:") {
+        validation::check_email(addr)?;
+        Ok(AlertChannel::Email(addr.to_string()))
+    } else if let Some(url) = channel.strip_prefix("webhook:") {
+        crate::api::webhooks::validate_webhook_url(url)?;
+        Ok(AlertChannel::Webhook(url.to_string()))
+    } else {
+        Err(ApiError::BadRequest(format!(
+            "notify channel must be email:<address> or webhook:<url>, got: {channel}"
+        )))
     }
 }
 
+fn validate_channels(channels: &[String]) -> Result<(), ApiError> {
+    if channels.len() > MAX_ALERT_CHANNELS {
+        return Err(ApiError::BadRequest(format!(
+            "at most {MAX_ALERT_CHANNELS} notify channels allowed"
+        )));
+    }
+    for c in channels {
+        parse_alert_channel(c)?;
+    }
+    Ok(())
+}
+
+/// Subject and body of a firing/resolved notification.
+fn format_alert_message(
+    rule_info: &AlertRuleInfo<'_>,
+    firing: bool,
+    value: Option<f64>,
+) -> (String, String) {
+    let status = if firing { "FIRING" } else { "RESOLVED" };
+    let subject = format!("[{status}] {} ({})", rule_info.name, rule_info.severity);
+    let value = value.map_or_else(|| "no data".to_string(), |v| format!("{v}"));
+    let threshold = rule_info
+        .threshold
+        .map_or_else(|| "none".to_string(), |t| format!("{t}"));
+    let body = format!(
+        "Alert {name} is {status_lc}.\n\n\
+         Value: {value}\n\
+         Condition: {condition} {threshold}\n\
+         Severity: {severity}\n\
+         Rule: {id}\n",
+        name = rule_info.name,
+        status_lc = status.to_lowercase(),
+        condition = rule_info.condition,
+        severity = rule_info.severity,
+        id = rule_info.id,
+    );
+    (subject, body)
+}
+
+/// Send a firing/resolved message to every channel on the rule. Delivery runs
+/// in the background so a slow SMTP server or webhook can't stall evaluation.
+fn notify_alert_channels(
+    app_state: &AppState,
+    rule_info: &AlertRuleInfo<'_>,
+    firing: bool,
+    value: Option<f64>,
+) {
+    let (subject, body) = format_alert_message(rule_info, firing, value);
+
+    if let Some(project_id) = rule_info.project_id {
+        let message = crate::notify::chat::ChatMessage {
+            title: subject.clone(),
+            text: body.clone(),
+            tone: if firing {
+                crate::notify::chat::Tone::Failure
+            } else {
+                crate::notify::chat::Tone::Success
+            },
+        };
+        let state = app_state.clone();
+        tokio::spawn(
+            async move {
+                crate::notify::chat::post_event(&state, project_id, "alert", message).await;
+            }
+            .in_current_span(),
+        );
+    }
+
+    let channels: Vec<AlertChannel> = rule_info
+        .channels
+        .iter()
+        .filter_map(|c| {
+            parse_alert_channel(c)
+                .inspect_err(|_| {
+                    tracing::warn!(rule_id = %rule_info.id, channel = %c, "skipping invalid alert channel");
+                })
+                .ok()
+        })
+        .collect();
+    if channels.is_empty() {
+        return;
+    }
+
+    let payload = serde_json::json!({
+        "event": "alert",
+        "status": if firing { "firing" } else { "resolved" },
+        "rule_id": rule_info.id,
+        "name": rule_info.name,
+        "severity": rule_info.severity,
+        "project_id": rule_info.project_id,
+        "condition": rule_info.condition,
+        "threshold": rule_info.threshold,
+        "value": value,
+    });
+    let config = app_state.config.clone();
+    let rule_id = rule_info.id;
+
+    tokio::spawn(
+        async move {
+            for channel in channels {
+                let result = match &channel {
+                    AlertChannel::Email(to) => {
+                        crate::notify::email::send(&config, to, &subject, &body).await
+                    }
+                    AlertChannel::Webhook(url) => {
+                        crate::notify::webhook::deliver(url, &payload, None, config.dev_mode)
+                            .await
+                            .map(|_| ())
+                            .map_err(|e| anyhow::anyhow!("{e}"))
+                    }
+                };
+                if let Err(e) = result {
+                    tracing::warn!(error = %e, %rule_id, ?channel, "alert notification failed");
+                }
+            }
+        }
+        .in_current_span(),
+    );
+}
+
 pub fn check_condition(condition: &str, threshold: Option<f64>, value: Option<f64>) -> bool {
     match condition {
         "absent" => value.is_none(),
@@ -954,40 +1690,94 @@ pub async fn evaluate_metric(
     }
 }
 
-pub async fn fire_alert(
+/// Open (or update) the rule's current incident as `firing` or `inhibited`.
+/// Returns `true` when the incident newly became `firing` and should be
+/// notified: a fresh firing event, or an inhibited one whose inhibitor cleared.
+pub async fn open_alert_event(
     pool: &sqlx::PgPool,
     rule_id: Uuid,
     value: Option<f64>,
-) -> Result<(), sqlx::Error> {
-    sqlx::query(
+    inhibited: bool,
+) -> Result<bool, sqlx::Error> {
+    let status = if inhibited { "inhibited" } else { "firing" };
+    let mut tx = pool.begin().await?;
+
+    let open = sqlx::query!(
         r"
-        INSERT INTO alert_events (rule_id, status, value, message)
-        VALUES ($1, 'firing', $2, 'Alert condition met')
+        SELECT id, status FROM alert_events
+        WHERE rule_id = $1 AND status IN ('firing', 'inhibited') AND resolved_at IS NULL
+        ORDER BY created_at DESC
+        LIMIT 1
+        FOR UPDATE
         ",
+        rule_id
     )
-    .bind(rule_id)
-    .bind(value)
-    .execute(pool)
+    .fetch_optional(&mut *tx)
     .await?;
 
-    tracing::warn!(rule_id = %rule_id, ?value, "alert firing");
-    Ok(())
+    let notify = match open {
+        None => {
+            let message = if inhibited {
+                "Alert condition met (inhibited)"
+            } else {
+                "Alert condition met"
+            };
+            sqlx::query!(
+                "INSERT INTO alert_events (rule_id, status, value, message) VALUES ($1, $2, $3, $4)",
+                rule_id,
+                status,
+                value,
+                message,
+            )
+            .execute(&mut *tx)
+            .await?;
+            !inhibited
+        }
+        Some(event) if event.status == "inhibited" && !inhibited => {
+            sqlx::query!(
+                "UPDATE alert_events SET status = 'firing', value = $2, \
+                 message = 'Alert condition met' WHERE id = $1",
+                event.id,
+                value,
+            )
+            .execute(&mut *tx)
+            .await?;
+            true
+        }
+        // Already firing (never downgraded once notified) or still inhibited
+        Some(_) => false,
+    };
+    tx.commit().await?;
+
+    if notify {
+        tracing::warn!(rule_id = %rule_id, ?value, "alert firing");
+    } else if inhibited {
+        tracing::info!(rule_id = %rule_id, ?value, "alert inhibited");
+    }
+    Ok(notify)
 }
 
-pub async fn resolve_alert(pool: &sqlx::PgPool, rule_id: Uuid) -> Result<(), sqlx::Error> {
-    // Resolve the most recent firing event for this rule
-    sqlx::query(
+/// Resolve the rule's open incident. Returns `true` if a firing (notified)
+/// event was resolved; inhibited events are closed but keep their status.
+pub async fn resolve_alert(pool: &sqlx::PgPool, rule_id: Uuid) -> Result<bool, sqlx::Error> {
+    let statuses = sqlx::query_scalar!(
         r"
-        UPDATE alert_events SET status = 'resolved', resolved_at = now()
-        WHERE rule_id = $1 AND status = 'firing' AND resolved_at IS NULL
+        UPDATE alert_events
+        SET status = CASE WHEN status = 'firing' THEN 'resolved' ELSE status END,
+            resolved_at = now()
+        WHERE rule_id = $1 AND status IN ('firing', 'inhibited') AND resolved_at IS NULL
+        RETURNING status
         ",
+        rule_id
     )
-    .bind(rule_id)
-    .execute(pool)
+    .fetch_all(pool)
     .await?;
 
+    if !statuses.iter().any(|s| s == "resolved") {
+        return Ok(false);
+    }
     tracing::info!(rule_id = %rule_id, "alert resolved");
-    Ok(())
+    Ok(true)
 }
 
 // ---------------------------------------------------------------------------
@@ -1175,6 +1965,7 @@ mod tests {
         let mut state = AlertState {
             first_triggered: None,
             firing: false,
+            inhibited: false,
         };
         let t = next_alert_state(&mut state, true, now, 60);
         // Should set first_triggered but not fire yet (hold period not met)
@@ -1190,6 +1981,7 @@ mod tests {
         let mut state = AlertState {
             first_triggered: Some(now - chrono::Duration::seconds(120)),
             firing: false,
+            inhibited: false,
         };
         let t = next_alert_state(&mut state, true, now, 60);
         assert!(state.firing);
@@ -1203,6 +1995,7 @@ mod tests {
         let mut state = AlertState {
             first_triggered: Some(now - chrono::Duration::seconds(30)),
             firing: false,
+            inhibited: false,
         };
         let t = next_alert_state(&mut state, false, now, 60);
         assert!(state.first_triggered.is_none());
@@ -1217,6 +2010,7 @@ mod tests {
         let mut state = AlertState {
             first_triggered: Some(now - chrono::Duration::seconds(300)),
             firing: true,
+            inhibited: false,
         };
         let t = next_alert_state(&mut state, false, now, 60);
         assert!(!state.firing);
@@ -1231,6 +2025,7 @@ mod tests {
         let mut state = AlertState {
             first_triggered: Some(now - chrono::Duration::seconds(300)),
             firing: true,
+            inhibited: false,
         };
         let t = next_alert_state(&mut state, true, now, 60);
         // Already firing — no duplicate fire
@@ -1245,6 +2040,7 @@ mod tests {
         let mut state = AlertState {
             first_triggered: Some(now - chrono::Duration::seconds(600)),
             firing: true,
+            inhibited: false,
         };
         // Call multiple times — should never return should_fire again
         for _ in 0..5 {
@@ -1343,6 +2139,7 @@ mod tests {
         let mut state = AlertState {
             first_triggered: Some(now - chrono::Duration::seconds(60)),
             firing: false,
+            inhibited: false,
         };
         let t = next_alert_state(&mut state, true, now, 60);
         // Exactly at the boundary — should fire (>=)
@@ -1356,6 +2153,7 @@ mod tests {
         let mut state = AlertState {
             first_triggered: None,
             firing: false,
+            inhibited: false,
         };
         // With for_seconds=0, immediately transitions
         let t = next_alert_state(&mut state, true, now, 0);
@@ -1369,4 +2167,189 @@ mod tests {
     fn validate_condition_whitespace_rejected() {
         assert!(validate_condition(" gt ").is_err());
     }
+
+    // -- alert expressions --
+
+    fn parse_expr(query: &str) -> AlertExpr {
+        match parse_alert_source(query).unwrap() {
+            AlertSource::Expr(e) => e,
+            AlertSource::Dsl(_) => panic!("parsed as DSL: {query}"),
+        }
+    }
+
+    #[test]
+    fn legacy_dsl_still_parsed() {
+        assert!(matches!(
+            parse_alert_source("metric:cpu agg:max window:60").unwrap(),
+            AlertSource::Dsl(_)
+        ));
+    }
+
+    #[test]
+    fn parse_rate_with_window() {
+        assert_eq!(
+            parse_expr("rate(http_requests_total[5m])"),
+            AlertExpr::Range {
+                func: RangeFunc::Rate,
+                name: "http_requests_total".into(),
+                labels: None,
+                window_secs: 300,
+            }
+        );
+    }
+
+    #[test]
+    fn parse_selector_labels() {
+        assert_eq!(
+            parse_expr(r#"errors{service="api", code="500"}"#),
+            AlertExpr::Instant {
+                name: "errors".into(),
+                labels: Some(serde_json::json!({"service": "api", "code": "500"})),
+            }
+        );
+    }
+
+    #[test]
+    fn parse_binary_precedence() {
+        // a + b * 2 parses as a + (b * 2)
+        let AlertExpr::Binary { op, rhs, .. } = parse_expr("a + b * 2") else {
+            panic!("expected binary");
+        };
+        assert_eq!(op, '+');
+        assert!(matches!(*rhs, AlertExpr::Binary { op: '*', .. }));
+    }
+
+    #[test]
+    fn parse_ratio_of_rates() {
+        let e = parse_expr(r#"rate(errors_total{code="500"}[1m]) / rate(requests_total[1m])"#);
+        assert!(matches!(e, AlertExpr::Binary { op: '/', .. }));
+    }
+
+    #[test]
+    fn parse_expr_errors() {
+        for q in [
+            "rate(foo)",
+            "rate(foo[5])",
+            "rate(foo[5w])",
+            "rate(foo[5s])",
+            "rate(foo[2d])",
+            "median(foo[5m])",
+            "foo +",
+            "(foo",
+            "foo bar",
+            "foo{a=1}",
+            "foo $ 2",
+        ] {
+            assert!(parse_alert_source(q).is_err(), "{q} should be rejected");
+        }
+    }
+
+    #[test]
+    fn parse_expr_depth_limited() {
+        let q = format!("{}1{}", "(".repeat(40), ")".repeat(40));
+        assert!(parse_alert_source(&q).is_err());
+        let q = format!("{}1{}", "(".repeat(10), ")".repeat(10));
+        assert_eq!(parse_expr(&q), AlertExpr::Number(1.0));
+    }
+
+    #[test]
+    fn binary_division_by_zero_is_none() {
+        assert_eq!(apply_binary('/', 1.0, 0.0), None);
+        assert_eq!(apply_binary('/', 6.0, 3.0), Some(2.0));
+        assert_eq!(apply_binary('-', 1.0, 3.0), Some(-2.0));
+    }
+
+    // -- alert notifications --
+
+    #[test]
+    fn parse_alert_channels() {
+        assert_eq!(
+            parse_alert_channel("email:oncall@example.com").unwrap(),
+            AlertChannel::Email("oncall@example.com".into())
+        );
+        assert_eq!(
+            parse_alert_channel("webhook:https://hooks.example.com/x").unwrap(),
+            AlertChannel::Webhook("https://hooks.example.com/x".into())
+        );
+        assert!(parse_alert_channel("oncall@example.com").is_err());
+        assert!(parse_alert_channel("webhook:http://169.254.169.254/").is_err());
+        let many: Vec<String> = (0..21).map(|i| format!("email:u{i}@example.com")).collect();
+        assert!(validate_channels(&many).is_err());
+    }
+
+    fn rule_info(
+        name: &'static str,
+        severity: &'static str,
+        inhibit_if: &[&str],
+    ) -> AlertRuleInfo<'static> {
+        AlertRuleInfo {
+            id: Uuid::new_v4(),
+            name,
+            severity,
+            project_id: None,
+            for_seconds: 60,
+            condition: "gt",
+            threshold: Some(80.0),
+            channels: vec![],
+            inhibit_if: inhibit_if.iter().map(ToString::to_string).collect(),
+        }
+    }
+
+    #[test]
+    fn alert_message_includes_value_and_threshold() {
+        let info = rule_info("High CPU", "critical", &[]);
+        let (subject, body) = format_alert_message(&info, true, Some(93.5));
+        assert_eq!(subject, "[FIRING] High CPU (critical)");
+        assert!(body.contains("Value: 93.5"));
+        assert!(body.contains("Condition: gt 80"));
+
+        let (subject, body) = format_alert_message(&info, false, None);
+        assert!(subject.starts_with("[RESOLVED]"));
+        assert!(body.contains("Value: no data"));
+    }
+
+    // -- inhibition --
+
+    fn firing<'a>(info: &'a AlertRuleInfo<'a>) -> FiringRule<'a> {
+        FiringRule {
+            id: info.id,
+            name: info.name,
+            severity: info.severity,
+            project_id: info.project_id,
+        }
+    }
+
+    #[test]
+    fn higher_severity_named_rule_inhibits() {
+        let node_down = rule_info("node down", "critical", &[]);
+        let latency = rule_info("high latency", "warning", &["node down"]);
+        assert!(is_inhibited(&latency, &[firing(&node_down)]));
+        // Nothing firing, nothing inhibited
+        assert!(!is_inhibited(&latency, &[]));
+    }
+
+    #[test]
+    fn inhibition_requires_name_match_and_higher_severity() {
+        let node_down = rule_info("node down", "critical", &[]);
+        let other = rule_info("disk full", "critical", &[]);
+        let latency = rule_info("high latency", "warning", &["node down"]);
+        assert!(!is_inhibited(&latency, &[firing(&other)]));
+
+        let peer = rule_info("node down", "warning", &[]);
+        assert!(
+            !is_inhibited(&latency, &[firing(&peer)]),
+            "same severity does not inhibit"
+        );
+
+        let critical = rule_info("high latency", "critical", &["node down"]);
+        assert!(!is_inhibited(&critical, &[firing(&node_down)]));
+    }
+
+    #[test]
+    fn inhibition_scoped_to_project() {
+        let mut node_down = rule_info("node down", "critical", &[]);
+        node_down.project_id = Some(Uuid::new_v4());
+        let latency = rule_info("high latency", "warning", &["node down"]);
+        assert!(!is_inhibited(&latency, &[firing(&node_down)]));
+    }
 }
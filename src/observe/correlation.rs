@@ -83,6 +83,28 @@ pub async fn resolve_session(
     Ok(())
 }
 
+/// Mark the agent sessions that produced a batch of telemetry as active, so the
+/// reaper's idle timeout only fires for sessions that have gone quiet.
+pub async fn record_session_activity(
+    pool: &sqlx::PgPool,
+    session_ids: impl IntoIterator<Item = Uuid>,
+) -> Result<(), sqlx::Error> {
+    let mut ids: Vec<Uuid> = session_ids.into_iter().collect();
+    ids.sort_unstable();
+    ids.dedup();
+    if ids.is_empty() {
+        return Ok(());
+    }
+    sqlx::query!(
+        "UPDATE agent_sessions SET last_activity_at = now() \
+         WHERE id = ANY($1) AND status = 'running'",
+        &ids
+    )
+    .execute(pool)
+    .await?;
+    Ok(())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
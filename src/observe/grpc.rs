@@ -0,0 +1,316 @@
+// Copyright (c) 2026 Steven Hooker. Exclusively licensed to and distributed by AgentSphere GmbH.
+// SPDX-License-Identifier: BUSL-1.1
+
+//! OTLP/gRPC ingest server.
+//!
+//! Serves the OpenTelemetry collector `Export` RPCs for traces, logs, and
+//! metrics on a dedicated port (`PLATFORM_OTLP_GRPC_LISTEN`). Requests are
+//! authenticated from the `authorization` metadata exactly like the HTTP
+//! `/v1/*` endpoints and feed the same [`IngestChannels`].
+//!
+//! The services are written by hand against `tonic::server::Grpc` rather than
+//! generated, because the message types already live in [`super::proto`].
+
+use std::convert::Infallible;
+use std::marker::PhantomData;
+
+use axum::extract::FromRequestParts;
+use tonic::codec::CompressionEncoding;
+use tonic::codegen::{Body, BoxFuture, Context, Poll, Service, StdError, http};
+use tonic::server::{Grpc, NamedService};
+use tonic::{Request, Response, Status};
+
+use super::ingest::{self, IngestChannels};
+use super::proto;
+use crate::auth::middleware::AuthUser;
+use crate::error::ApiError;
+use crate::store::AppState;
+
+/// Largest accepted decoded export message (matches the HTTP body limit for `/v1/*`).
+const MAX_MESSAGE_SIZE: usize = 10 * 1024 * 1024;
+
+/// Run the OTLP/gRPC server if `otlp_grpc_listen` is configured.
+pub async fn run(
+    state: AppState,
+    channels: IngestChannels,
+    cancel: tokio_util::sync::CancellationToken,
+) -> Result<(), anyhow::Error> {
+    let listen_addr = match &state.config.otlp_grpc_listen {
+        Some(addr) => addr.clone(),
+        None => return Ok(()),
+    };
+
+    let listener = tokio::net::TcpListener::bind(&listen_addr).await?;
+    tracing::info!(addr = %listen_addr, "OTLP/gRPC server listening");
+
+    run_with_listener(state, channels, listener, &cancel).await
+}
+
+/// Run the OTLP/gRPC server on a pre-bound listener. Returns when shutdown is signalled.
+pub async fn run_with_listener(
+    state: AppState,
+    channels: IngestChannels,
+    listener: tokio::net::TcpListener,
+    cancel: &tokio_util::sync::CancellationToken,
+) -> Result<(), anyhow::Error> {
+    tonic::transport::Server::builder()
+        .add_routes(routes(&state, &channels))
+        .serve_with_incoming_shutdown(
+            tokio_stream::wrappers::TcpListenerStream::new(listener),
+            cancel.cancelled(),
+        )
+        .await?;
+    tracing::info!("OTLP/gRPC server stopped");
+    Ok(())
+}
+
+/// The trace, logs, and metrics collector services.
+pub fn routes(state: &AppState, channels: &IngestChannels) -> tonic::service::Routes {
+    tonic::service::Routes::new(OtlpService::<Traces>::new(state, channels))
+        .add_service(OtlpService::<Logs>::new(state, channels))
+        .add_service(OtlpService::<Metrics>::new(state, channels))
+}
+
+// ---------------------------------------------------------------------------
+// Signals
+// ---------------------------------------------------------------------------
+
+/// One OTLP collector service: its gRPC name, message types, and ingest function.
+trait Signal: Send + Sync + 'static {
+    const SERVICE: &'static str;
+    type Request: prost::Message + Default + Send + 'static;
+    type Response: prost::Message + Default + Send + 'static;
+
+    fn export<'a>(
+        state: &'a AppState,
+        auth: &'a AuthUser,
+        channels: &'a IngestChannels,
+        request: Self::Request,
+    ) -> futures_util::future::BoxFuture<'a, Result<(), ApiError>>;
+}
+
+struct Traces;
+
+impl Signal for Traces {
+    const SERVICE: &'static str = "opentelemetry.proto.collector.trace.v1.TraceService";
+    type Request = proto::ExportTraceServiceRequest;
+    type Response = proto::ExportTraceServiceResponse;
+
+    fn export<'a>(
+        state: &'a AppState,
+        auth: &'a AuthUser,
+        channels: &'a IngestChannels,
+        request: Self::Request,
+    ) -> futures_util::future::BoxFuture<'a, Result<(), ApiError>> {
+        Box::pin(ingest::export_traces(state, auth, channels, request))
+    }
+}
+
+struct Logs;
+
+impl Signal for Logs {
+    const SERVICE: &'static str = "opentelemetry.proto.collector.logs.v1.LogsService";
+    type Request = proto::ExportLogsServiceRequest;
+    type Response = proto::ExportLogsServiceResponse;
+
+    fn export<'a>(
+        state: &'a AppState,
+        auth: &'a AuthUser,
+        channels: &'a IngestChannels,
+        request: Self::Request,
+    ) -> futures_util::future::BoxFuture<'a, Result<(), ApiError>> {
+        Box::pin(ingest::export_logs(state, auth, channels, request))
+    }
+}
+
+struct Metrics;
+
+impl Signal for Metrics {
+    const SERVICE: &'static str = "opentelemetry.proto.collector.metrics.v1.MetricsService";
+    type Request = proto::ExportMetricsServiceRequest;
+    type Response = proto::ExportMetricsServiceResponse;
+
+    fn export<'a>(
+        state: &'a AppState,
+        auth: &'a AuthUser,
+        channels: &'a IngestChannels,
+        request: Self::Request,
+    ) -> futures_util::future::BoxFuture<'a, Result<(), ApiError>> {
+        Box::pin(ingest::export_metrics(state, auth, channels, request))
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Service
+// ---------------------------------------------------------------------------
+
+/// gRPC service exposing the single unary `Export` method of signal `S`.
+struct OtlpService<S> {
+    state: AppState,
+    channels: IngestChannels,
+    _signal: PhantomData<fn() -> S>,
+}
+
+impl<S> OtlpService<S> {
+    fn new(state: &AppState, channels: &IngestChannels) -> Self {
+        Self {
+            state: state.clone(),
+            channels: channels.clone(),
+            _signal: PhantomData,
+        }
+    }
+}
+
+impl<S> Clone for OtlpService<S> {
+    fn clone(&self) -> Self {
+        Self::new(&self.state, &self.channels)
+    }
+}
+
+impl<S: Signal> NamedService for OtlpService<S> {
+    const NAME: &'static str = S::SERVICE;
+}
+
+impl<S, B> Service<http::Request<B>> for OtlpService<S>
+where
+    S: Signal,
+    B: Body + Send + 'static,
+    B::Error: Into<StdError> + Send + 'static,
+{
+    type Response = http::Response<tonic::body::Body>;
+    type Error = Infallible;
+    type Future = BoxFuture<Self::Response, Self::Error>;
+
+    fn poll_ready(&mut self, _cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        Poll::Ready(Ok(()))
+    }
+
+    fn call(&mut self, req: http::Request<B>) -> Self::Future {
+        if !is_export_path::<S>(req.uri().path()) {
+            return Box::pin(async { Ok(Status::unimplemented("unknown method").into_http()) });
+        }
+
+        let state = self.state.clone();
+        let channels = self.channels.clone();
+        let export = tower::service_fn(move |request: Request<S::Request>| {
+            let state = state.clone();
+            let channels = channels.clone();
+            async move {
+                let mut parts = request_parts(&request);
+                let auth = AuthUser::from_request_parts(&mut parts, &state)
+                    .await
+                    .map_err(to_status)?;
+                S::export(&state, &auth, &channels, request.into_inner())
+                    .await
+                    .map_err(to_status)?;
+                Ok::<_, Status>(Response::new(S::Response::default()))
+            }
+        });
+
+        Box::pin(async move {
+            let mut grpc = Grpc::new(tonic_prost::ProstCodec::<S::Response, S::Request>::default())
+                .accept_compressed(CompressionEncoding::Gzip)
+                .send_compressed(CompressionEncoding::Gzip)
+                .max_decoding_message_size(MAX_MESSAGE_SIZE);
+            Ok(grpc.unary(export, req).await)
+        })
+    }
+}
+
+/// Whether `path` is `/<package>.<Service>/Export` for signal `S`.
+fn is_export_path<S: Signal>(path: &str) -> bool {
+    path.strip_prefix('/')
+        .and_then(|p| p.strip_prefix(S::SERVICE))
+        .is_some_and(|method| method == "/Export")
+}
+
+/// Rebuild HTTP request parts from gRPC metadata so the regular `AuthUser`
+/// extractor (bearer token, client IP) can run unchanged.
+fn request_parts<T>(request: &Request<T>) -> http::request::Parts {
+    let (mut parts, ()) = http::Request::new(()).into_parts();
+    parts.headers = request.metadata().clone().into_headers();
+    if let Some(addr) = request.remote_addr() {
+        parts.extensions.insert(axum::extract::ConnectInfo(addr));
+    }
+    parts
+}
+
+/// Map an API error onto the closest gRPC status code.
+fn to_status(err: ApiError) -> Status {
+    match err {
+        ApiError::Unauthorized => Status::unauthenticated("unauthorized"),
+        ApiError::Forbidden => Status::permission_denied("forbidden"),
+        ApiError::NotFound(msg) => Status::not_found(msg),
+        ApiError::BadRequest(msg) => Status::invalid_argument(msg),
+        ApiError::Validation(errors) => Status::invalid_argument(errors.join("; ")),
+        ApiError::Conflict(msg) => Status::already_exists(msg),
+        ApiError::TooManyRequests => Status::resource_exhausted("too many requests"),
+        ApiError::QuotaExceeded(msg) => Status::resource_exhausted(msg),
+        ApiError::Locked(msg) => Status::failed_precondition(msg),
+        ApiError::BadGateway(msg) | ApiError::ServiceUnavailable(msg) => Status::unavailable(msg),
+        ApiError::Internal(e) => {
+            tracing::error!(error = %e, "OTLP/gRPC export failed");
+            Status::internal("internal error")
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn export_path_matches_only_own_service() {
+        assert!(is_export_path::<Traces>(
+            "/opentelemetry.proto.collector.trace.v1.TraceService/Export"
+        ));
+        assert!(is_export_path::<Logs>(
+            "/opentelemetry.proto.collector.logs.v1.LogsService/Export"
+        ));
+        assert!(is_export_path::<Metrics>(
+            "/opentelemetry.proto.collector.metrics.v1.MetricsService/Export"
+        ));
+        assert!(!is_export_path::<Traces>(
+            "/opentelemetry.proto.collector.logs.v1.LogsService/Export"
+        ));
+        assert!(!is_export_path::<Traces>(
+            "/opentelemetry.proto.collector.trace.v1.TraceService/Other"
+        ));
+        assert!(!is_export_path::<Traces>(
+            "/opentelemetry.proto.collector.trace.v1.TraceServiceX/Export"
+        ));
+    }
+
+    #[test]
+    fn api_errors_map_to_grpc_codes() {
+        use tonic::Code;
+
+        assert_eq!(
+            to_status(ApiError::Unauthorized).code(),
+            Code::Unauthenticated
+        );
+        assert_eq!(
+            to_status(ApiError::Forbidden).code(),
+            Code::PermissionDenied
+        );
+        assert_eq!(
+            to_status(ApiError::BadRequest("bad".into())).code(),
+            Code::InvalidArgument
+        );
+        assert_eq!(
+            to_status(ApiError::Validation(vec!["a".into(), "b".into()])).message(),
+            "a; b"
+        );
+        assert_eq!(
+            to_status(ApiError::TooManyRequests).code(),
+            Code::ResourceExhausted
+        );
+        assert_eq!(
+            to_status(ApiError::ServiceUnavailable("down".into())).code(),
+            Code::Unavailable
+        );
+        let internal = to_status(ApiError::Internal(anyhow::anyhow!("db password leaked")));
+        assert_eq!(internal.code(), Code::Internal);
+        assert_eq!(internal.message(), "internal error");
+    }
+}
@@ -1,7 +1,7 @@
 // Copyright (c) 2026 Steven Hooker. Exclusively licensed to and distributed by AgentSphere GmbH.
 // SPDX-License-Identifier: BUSL-1.1
 
-use std::collections::HashSet;
+use std::collections::{HashMap, HashSet};
 use std::io::Read;
 use std::sync::atomic::{AtomicU64, Ordering};
 
@@ -31,6 +31,16 @@ static BUFFER_FULL_DROPS: AtomicU64 = AtomicU64::new(0);
 /// Epoch-second of the last buffer-full warning log.
 static BUFFER_FULL_LAST_LOG: AtomicU64 = AtomicU64::new(0);
 
+/// Samples dropped because they would exceed a project's series cap.
+static METRICS_DROPPED: AtomicU64 = AtomicU64::new(0);
+/// Epoch-second of the last series-cap warning log.
+static SERIES_CAP_LAST_LOG: AtomicU64 = AtomicU64::new(0);
+
+/// Total metric samples dropped by the per-project series cap since startup.
+pub fn metrics_dropped() -> u64 {
+    METRICS_DROPPED.load(Ordering::Relaxed)
+}
+
 /// Log a buffer-full warning at most once per 30 seconds to avoid log spam.
 fn warn_buffer_full(signal: &str) {
     let dropped = BUFFER_FULL_DROPS.fetch_add(1, Ordering::Relaxed) + 1;
@@ -92,7 +102,7 @@ pub fn create_channels_with_capacity(
 }
 
 /// Create ingest channels with default buffer capacity (used in tests).
-#[cfg(test)]
+#[allow(dead_code)] // Only called from integration tests
 pub fn create_channels() -> (
     IngestChannels,
     mpsc::Receiver<SpanRecord>,
@@ -164,14 +174,19 @@ async fn check_otlp_project_auth(
         }
     }
 
-    // System-level metrics (no project_id) require admin permission
+    // System-level metrics (no project_id) require admin permission, which
+    // tokens bounded to a project or workspace never carry
     if has_system_metrics {
-        let is_admin = resolver::has_permission(
+        if auth.boundary_project_id.is_some() || auth.boundary_workspace_id.is_some() {
+            return Err(ApiError::Forbidden);
+        }
+        let is_admin = resolver::has_permission_scoped(
             &state.pool,
             &state.valkey,
             auth.user_id,
             None,
             Permission::AdminConfig,
+            auth.token_scopes.as_deref(),
         )
         .await
         .map_err(|e| ApiError::Internal(e.context("OTLP system auth check")))?;
@@ -217,14 +232,27 @@ pub async fn ingest_traces(
     headers: HeaderMap,
     body: Bytes,
 ) -> Result<impl IntoResponse, ApiError> {
-    let rate_id = auth
-        .boundary_project_id
-        .map_or_else(|| auth.user_id.to_string(), |pid| pid.to_string());
-    crate::auth::rate_limit::check_rate(&state.valkey, "otlp", &rate_id, 10_000, 60).await?;
-
     let body = maybe_decompress(&headers, body)?;
     let request = proto::ExportTraceServiceRequest::decode(body)
         .map_err(|e| ApiError::BadRequest(format!("invalid protobuf: {e}")))?;
+    export_traces(&state, &auth, &channels, request).await?;
+
+    let response_bytes = proto::ExportTraceServiceResponse {}.encode_to_vec();
+    Ok((
+        StatusCode::OK,
+        [("content-type", "application/x-protobuf")],
+        response_bytes,
+    ))
+}
+
+/// Rate-limit, authorize, and enqueue an OTLP trace export (HTTP or gRPC).
+pub(crate) async fn export_traces(
+    state: &AppState,
+    auth: &AuthUser,
+    channels: &IngestChannels,
+    request: proto::ExportTraceServiceRequest,
+) -> Result<(), ApiError> {
+    check_otlp_rate(state, auth).await?;
 
     // Collect resource attrs for project auth check
     let resource_attrs_refs: Vec<&[proto::KeyValue]> = request
@@ -232,13 +260,13 @@ pub async fn ingest_traces(
         .iter()
         .map(|rs| rs.resource.as_ref().map_or(&[][..], |r| &r.attributes[..]))
         .collect();
-    check_otlp_project_auth(&state, &auth, &resource_attrs_refs).await?;
+    check_otlp_project_auth(state, auth, &resource_attrs_refs).await?;
 
     for rs in &request.resource_spans {
         let resource_attrs = rs.resource.as_ref().map_or(&[][..], |r| &r.attributes);
         for ss in &rs.scope_spans {
             for span in &ss.spans {
-                let record = build_span_record(span, resource_attrs, &state).await;
+                let record = build_span_record(span, resource_attrs, state).await;
                 if channels.spans_tx.try_send(record).is_err() {
                     warn_buffer_full("traces");
                     return Err(ApiError::ServiceUnavailable("ingest buffer full".into()));
@@ -246,13 +274,7 @@ pub async fn ingest_traces(
             }
         }
     }
-
-    let response_bytes = proto::ExportTraceServiceResponse {}.encode_to_vec();
-    Ok((
-        StatusCode::OK,
-        [("content-type", "application/x-protobuf")],
-        response_bytes,
-    ))
+    Ok(())
 }
 
 /// `POST /v1/logs` — receive OTLP log protobuf.
@@ -264,27 +286,40 @@ pub async fn ingest_logs(
     headers: HeaderMap,
     body: Bytes,
 ) -> Result<impl IntoResponse, ApiError> {
-    let rate_id = auth
-        .boundary_project_id
-        .map_or_else(|| auth.user_id.to_string(), |pid| pid.to_string());
-    crate::auth::rate_limit::check_rate(&state.valkey, "otlp", &rate_id, 10_000, 60).await?;
-
     let body = maybe_decompress(&headers, body)?;
     let request = proto::ExportLogsServiceRequest::decode(body)
         .map_err(|e| ApiError::BadRequest(format!("invalid protobuf: {e}")))?;
+    export_logs(&state, &auth, &channels, request).await?;
+
+    let response_bytes = proto::ExportLogsServiceResponse {}.encode_to_vec();
+    Ok((
+        StatusCode::OK,
+        [("content-type", "application/x-protobuf")],
+        response_bytes,
+    ))
+}
+
+/// Rate-limit, authorize, and enqueue an OTLP log export (HTTP or gRPC).
+pub(crate) async fn export_logs(
+    state: &AppState,
+    auth: &AuthUser,
+    channels: &IngestChannels,
+    request: proto::ExportLogsServiceRequest,
+) -> Result<(), ApiError> {
+    check_otlp_rate(state, auth).await?;
 
     let resource_attrs_refs: Vec<&[proto::KeyValue]> = request
         .resource_logs
         .iter()
         .map(|rl| rl.resource.as_ref().map_or(&[][..], |r| &r.attributes[..]))
         .collect();
-    check_otlp_project_auth(&state, &auth, &resource_attrs_refs).await?;
+    check_otlp_project_auth(state, auth, &resource_attrs_refs).await?;
 
     for rl in &request.resource_logs {
         let resource_attrs = rl.resource.as_ref().map_or(&[][..], |r| &r.attributes);
         for sl in &rl.scope_logs {
             for log in &sl.log_records {
-                let record = build_log_record(log, resource_attrs, &state).await;
+                let record = build_log_record(log, resource_attrs, state).await;
                 if channels.logs_tx.try_send(record).is_err() {
                     warn_buffer_full("logs");
                     return Err(ApiError::ServiceUnavailable("ingest buffer full".into()));
@@ -293,12 +328,7 @@ pub async fn ingest_logs(
         }
     }
 
-    let response_bytes = proto::ExportLogsServiceResponse {}.encode_to_vec();
-    Ok((
-        StatusCode::OK,
-        [("content-type", "application/x-protobuf")],
-        response_bytes,
-    ))
+    Ok(())
 }
 
 /// `POST /v1/metrics` — receive OTLP metric protobuf.
@@ -310,27 +340,40 @@ pub async fn ingest_metrics(
     headers: HeaderMap,
     body: Bytes,
 ) -> Result<impl IntoResponse, ApiError> {
-    let rate_id = auth
-        .boundary_project_id
-        .map_or_else(|| auth.user_id.to_string(), |pid| pid.to_string());
-    crate::auth::rate_limit::check_rate(&state.valkey, "otlp", &rate_id, 10_000, 60).await?;
-
     let body = maybe_decompress(&headers, body)?;
     let request = proto::ExportMetricsServiceRequest::decode(body)
         .map_err(|e| ApiError::BadRequest(format!("invalid protobuf: {e}")))?;
+    export_metrics(&state, &auth, &channels, request).await?;
+
+    let response_bytes = proto::ExportMetricsServiceResponse {}.encode_to_vec();
+    Ok((
+        StatusCode::OK,
+        [("content-type", "application/x-protobuf")],
+        response_bytes,
+    ))
+}
+
+/// Rate-limit, authorize, and enqueue an OTLP metric export (HTTP or gRPC).
+pub(crate) async fn export_metrics(
+    state: &AppState,
+    auth: &AuthUser,
+    channels: &IngestChannels,
+    request: proto::ExportMetricsServiceRequest,
+) -> Result<(), ApiError> {
+    check_otlp_rate(state, auth).await?;
 
     let resource_attrs_refs: Vec<&[proto::KeyValue]> = request
         .resource_metrics
         .iter()
         .map(|rm| rm.resource.as_ref().map_or(&[][..], |r| &r.attributes[..]))
         .collect();
-    check_otlp_project_auth(&state, &auth, &resource_attrs_refs).await?;
+    check_otlp_project_auth(state, auth, &resource_attrs_refs).await?;
 
     for rm in &request.resource_metrics {
         let resource_attrs = rm.resource.as_ref().map_or(&[][..], |r| &r.attributes);
         for sm in &rm.scope_metrics {
             for metric in &sm.metrics {
-                let records = build_metric_records(metric, resource_attrs, &state).await;
+                let records = build_metric_records(metric, resource_attrs, state).await;
                 for record in records {
                     if channels.metrics_tx.try_send(record).is_err() {
                         warn_buffer_full("metrics");
@@ -341,12 +384,15 @@ pub async fn ingest_metrics(
         }
     }
 
-    let response_bytes = proto::ExportMetricsServiceResponse {}.encode_to_vec();
-    Ok((
-        StatusCode::OK,
-        [("content-type", "application/x-protobuf")],
-        response_bytes,
-    ))
+    Ok(())
+}
+
+/// Per-token (or per-project for scoped tokens) OTLP rate limit.
+async fn check_otlp_rate(state: &AppState, auth: &AuthUser) -> Result<(), ApiError> {
+    let rate_id = auth
+        .boundary_project_id
+        .map_or_else(|| auth.user_id.to_string(), |pid| pid.to_string());
+    crate::auth::rate_limit::check_rate(&state.valkey, "otlp", &rate_id, 10_000, 60).await
 }
 
 // ---------------------------------------------------------------------------
@@ -615,6 +661,13 @@ async fn drain_spans(
     if !buffer.is_empty() {
         if let Err(e) = super::store::write_spans(pool, buffer).await {
             tracing::error!(error = %e, count = buffer.len(), "failed to flush spans");
+        } else if let Err(e) = super::correlation::record_session_activity(
+            pool,
+            buffer.iter().filter_map(|s| s.session_id),
+        )
+        .await
+        {
+            tracing::warn!(error = %e, "failed to record agent session activity");
         }
         buffer.clear();
     }
@@ -628,6 +681,7 @@ pub async fn flush_logs(
     cancel: tokio_util::sync::CancellationToken,
 ) {
     let mut buffer = Vec::with_capacity(128);
+    let mut rules = super::log_metrics::RuleCache::default();
     let mut interval = tokio::time::interval(std::time::Duration::from_secs(1));
 
     loop {
@@ -635,12 +689,12 @@ pub async fn flush_logs(
             () = cancel.cancelled() => {
                 let _ = tokio::time::timeout(
                     std::time::Duration::from_secs(5),
-                    drain_and_publish_logs(&pool, &valkey, &mut rx, &mut buffer),
+                    drain_and_publish_logs(&pool, &valkey, &mut rx, &mut buffer, &mut rules),
                 ).await;
                 break;
             }
             _ = interval.tick() => {
-                drain_and_publish_logs(&pool, &valkey, &mut rx, &mut buffer).await;
+                drain_and_publish_logs(&pool, &valkey, &mut rx, &mut buffer, &mut rules).await;
             }
         }
     }
@@ -651,6 +705,7 @@ async fn drain_and_publish_logs(
     valkey: &fred::clients::Pool,
     rx: &mut mpsc::Receiver<LogEntryRecord>,
     buffer: &mut Vec<LogEntryRecord>,
+    rules: &mut super::log_metrics::RuleCache,
 ) {
     while buffer.len() < 500 {
         match rx.try_recv() {
@@ -682,6 +737,26 @@ async fn drain_and_publish_logs(
 
     if let Err(e) = super::store::write_logs(pool, buffer).await {
         tracing::error!(error = %e, count = buffer.len(), "failed to flush logs");
+        buffer.clear();
+        return;
+    }
+
+    if let Err(e) = super::correlation::record_session_activity(
+        pool,
+        buffer.iter().filter_map(|l| l.session_id),
+    )
+    .await
+    {
+        tracing::warn!(error = %e, "failed to record agent session activity");
+    }
+
+    // Log-based metrics: count matching entries into counter series
+    let increments = super::log_metrics::count_matches(rules.get(pool).await, buffer);
+    if !increments.is_empty()
+        && let Err(e) =
+            super::store::increment_counters(pool, &increments, chrono::Utc::now()).await
+    {
+        tracing::warn!(error = %e, "failed to record log-based metrics");
     }
     buffer.clear();
 }
@@ -689,6 +764,7 @@ async fn drain_and_publish_logs(
 /// Drain the metrics channel and batch-write to Postgres.
 pub async fn flush_metrics(
     pool: sqlx::PgPool,
+    max_series_per_project: u64,
     mut rx: mpsc::Receiver<MetricRecord>,
     cancel: tokio_util::sync::CancellationToken,
 ) {
@@ -700,12 +776,12 @@ pub async fn flush_metrics(
             () = cancel.cancelled() => {
                 let _ = tokio::time::timeout(
                     std::time::Duration::from_secs(5),
-                    drain_metrics(&pool, &mut rx, &mut buffer),
+                    drain_metrics(&pool, max_series_per_project, &mut rx, &mut buffer),
                 ).await;
                 break;
             }
             _ = interval.tick() => {
-                drain_metrics(&pool, &mut rx, &mut buffer).await;
+                drain_metrics(&pool, max_series_per_project, &mut rx, &mut buffer).await;
             }
         }
     }
@@ -713,6 +789,7 @@ pub async fn flush_metrics(
 
 async fn drain_metrics(
     pool: &sqlx::PgPool,
+    max_series_per_project: u64,
     rx: &mut mpsc::Receiver<MetricRecord>,
     buffer: &mut Vec<MetricRecord>,
 ) {
@@ -723,6 +800,10 @@ async fn drain_metrics(
         }
     }
     if !buffer.is_empty() {
+        if let Err(e) = enforce_series_cap(pool, buffer, max_series_per_project).await {
+            // Fail open: a failed cap check must not lose metrics
+            tracing::warn!(error = %e, "metric series cap check failed");
+        }
         if let Err(e) = super::store::write_metrics(pool, buffer).await {
             tracing::error!(error = %e, count = buffer.len(), "failed to flush metrics");
         }
@@ -730,12 +811,205 @@ async fn drain_metrics(
     }
 }
 
+// ---------------------------------------------------------------------------
+// Series cardinality cap
+// ---------------------------------------------------------------------------
+
+/// Drop project-scoped records that would create a new `metric_series` row in
+/// a project already holding `max_series` series. Samples for existing series
+/// are always kept; system metrics (no project) are exempt. Returns the number
+/// of records dropped.
+async fn enforce_series_cap(
+    pool: &sqlx::PgPool,
+    buffer: &mut Vec<MetricRecord>,
+    max_series: u64,
+) -> Result<u64, sqlx::Error> {
+    if max_series == 0 {
+        return Ok(0);
+    }
+
+    let scoped: Vec<usize> = (0..buffer.len())
+        .filter(|&i| buffer[i].project_id.is_some())
+        .collect();
+    if scoped.is_empty() {
+        return Ok(0);
+    }
+
+    let names: Vec<&str> = scoped.iter().map(|&i| buffer[i].name.as_str()).collect();
+    let labels: Vec<&serde_json::Value> = scoped.iter().map(|&i| &buffer[i].labels).collect();
+    let projects: Vec<Uuid> = scoped
+        .iter()
+        .filter_map(|&i| buffer[i].project_id)
+        .collect();
+
+    // 1-based positions (into `scoped`) of records whose series already exists
+    let existing = sqlx::query_scalar!(
+        r#"
+        SELECT k.ord AS "ord!"
+        FROM UNNEST($1::text[], $2::jsonb[], $3::uuid[]) WITH ORDINALITY
+             AS k(name, labels, project_id, ord)
+        JOIN metric_series s
+          ON s.name = k.name AND s.labels = k.labels AND s.project_id = k.project_id
+        "#,
+        &names as &[&str],
+        &labels as &[&serde_json::Value],
+        &projects,
+    )
+    .fetch_all(pool)
+    .await?;
+    let existing: HashSet<usize> = existing
+        .into_iter()
+        .filter_map(|ord| usize::try_from(ord - 1).ok())
+        .collect();
+
+    let new_records: Vec<usize> = (0..scoped.len())
+        .filter(|pos| !existing.contains(pos))
+        .map(|pos| scoped[pos])
+        .collect();
+    if new_records.is_empty() {
+        return Ok(0);
+    }
+
+    let mut new_projects: Vec<Uuid> = new_records
+        .iter()
+        .filter_map(|&i| buffer[i].project_id)
+        .collect();
+    new_projects.sort_unstable();
+    new_projects.dedup();
+    let counts = sqlx::query!(
+        r#"
+        SELECT project_id AS "project_id!", COUNT(*) AS "count!"
+        FROM metric_series
+        WHERE project_id = ANY($1)
+        GROUP BY project_id
+        "#,
+        &new_projects,
+    )
+    .fetch_all(pool)
+    .await?;
+    let mut remaining: HashMap<Uuid, u64> = new_projects.iter().map(|&p| (p, max_series)).collect();
+    for row in counts {
+        let count = u64::try_from(row.count).unwrap_or(0);
+        remaining.insert(row.project_id, max_series.saturating_sub(count));
+    }
+
+    let rejected = admit_new_series(buffer, &new_records, &mut remaining);
+    if rejected.is_empty() {
+        return Ok(0);
+    }
+
+    let before = buffer.len();
+    let mut index = 0;
+    buffer.retain(|_| {
+        let keep = !rejected.contains(&index);
+        index += 1;
+        keep
+    });
+    let dropped = (before - buffer.len()) as u64;
+    warn_series_cap(dropped, max_series);
+    Ok(dropped)
+}
+
+/// Decide which new-series records fit in each project's remaining allowance.
+/// Several records for the same new series consume one slot. Returns the
+/// buffer indices to drop.
+fn admit_new_series(
+    buffer: &[MetricRecord],
+    new_records: &[usize],
+    remaining: &mut HashMap<Uuid, u64>,
+) -> HashSet<usize> {
+    let mut admitted: HashSet<(Uuid, &str, String)> = HashSet::new();
+    let mut rejected = HashSet::new();
+    for &i in new_records {
+        let record = &buffer[i];
+        let Some(project_id) = record.project_id else {
+            continue;
+        };
+        let key = (project_id, record.name.as_str(), record.labels.to_string());
+        if admitted.contains(&key) {
+            continue;
+        }
+        let slots = remaining.entry(project_id).or_insert(0);
+        if *slots > 0 {
+            *slots -= 1;
+            admitted.insert(key);
+        } else {
+            rejected.insert(i);
+        }
+    }
+    rejected
+}
+
+/// Count dropped samples and warn at most once per 30 seconds.
+fn warn_series_cap(dropped: u64, max_series: u64) {
+    let total = METRICS_DROPPED.fetch_add(dropped, Ordering::Relaxed) + dropped;
+    let now = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs();
+    let last = SERIES_CAP_LAST_LOG.load(Ordering::Relaxed);
+    if now.saturating_sub(last) >= 30
+        && SERIES_CAP_LAST_LOG
+            .compare_exchange(last, now, Ordering::Relaxed, Ordering::Relaxed)
+            .is_ok()
+    {
+        tracing::warn!(
+            dropped,
+            total_dropped = total,
+            max_series,
+            "metric series cap reached, dropping samples for new series"
+        );
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
     use crate::observe::proto;
     use uuid::Uuid;
 
+    // -- admit_new_series ----------------------------------------------
+
+    fn metric(project_id: Uuid, name: &str, host: &str) -> MetricRecord {
+        MetricRecord {
+            name: name.into(),
+            labels: serde_json::json!({"host": host}),
+            metric_type: "gauge".into(),
+            unit: None,
+            project_id: Some(project_id),
+            timestamp: chrono::Utc::now(),
+            value: 1.0,
+        }
+    }
+
+    #[test]
+    fn admit_new_series_respects_per_project_allowance() {
+        let (a, b) = (Uuid::new_v4(), Uuid::new_v4());
+        let buffer = vec![
+            metric(a, "m", "h1"),
+            metric(a, "m", "h2"),
+            metric(b, "m", "h1"),
+            metric(a, "m", "h3"),
+        ];
+        let mut remaining = HashMap::from([(a, 1), (b, 5)]);
+        let rejected = admit_new_series(&buffer, &[0, 1, 2, 3], &mut remaining);
+        assert_eq!(rejected, HashSet::from([1, 3]));
+        assert_eq!(remaining[&b], 4);
+    }
+
+    #[test]
+    fn admit_new_series_counts_repeated_series_once() {
+        let a = Uuid::new_v4();
+        let buffer = vec![
+            metric(a, "m", "h1"),
+            metric(a, "m", "h1"),
+            metric(a, "m", "h2"),
+        ];
+        let mut remaining = HashMap::from([(a, 1)]);
+        let rejected = admit_new_series(&buffer, &[0, 1, 2], &mut remaining);
+        assert_eq!(rejected, HashSet::from([2]));
+    }
+
     // -- number_point_to_record ----------------------------------------
 
     fn empty_envelope() -> correlation::CorrelationEnvelope {
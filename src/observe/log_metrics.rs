@@ -0,0 +1,397 @@
+// Copyright (c) 2026 Steven Hooker. Exclusively licensed to and distributed by AgentSphere GmbH.
+// SPDX-License-Identifier: BUSL-1.1
+
+//! Log-based metrics: admin-defined rules that turn matching log entries into
+//! counter series, e.g. `log_errors_total{service="payments"}` for every
+//! `level=error` log from `payments`. Counters are incremented in the log
+//! flush path and land in `metric_samples`, so the alert engine can use them
+//! like any other counter (`rate(log_errors_total{service="payments"}[5m])`).
+
+use std::collections::HashMap;
+use std::time::{Duration, Instant};
+
+use axum::extract::{Path, State};
+use axum::http::StatusCode;
+use axum::response::IntoResponse;
+use axum::routing::{delete, get};
+use axum::{Json, Router};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use ts_rs::TS;
+
+use crate::api::helpers::ListResponse;
+use crate::audit::{AuditEntry, send_audit};
+use crate::auth::middleware::AuthUser;
+use crate::error::ApiError;
+use crate::store::AppState;
+use crate::validation;
+
+use super::store::{CounterIncrement, LogEntryRecord};
+
+/// How long the flush loop reuses loaded rules before re-reading them.
+const RULE_REFRESH: Duration = Duration::from_secs(30);
+
+/// Maximum number of log metric rules.
+const MAX_RULES: i64 = 200;
+
+const LEVELS: &[&str] = &["trace", "debug", "info", "warn", "error", "fatal"];
+
+// ---------------------------------------------------------------------------
+// Types
+// ---------------------------------------------------------------------------
+
+#[derive(Debug, Deserialize)]
+pub struct CreateLogMetricRuleRequest {
+    pub metric_name: String,
+    pub service: Option<String>,
+    pub level: Option<String>,
+    pub message_contains: Option<String>,
+    pub project_id: Option<Uuid>,
+}
+
+#[derive(Debug, Serialize, TS)]
+#[ts(export, rename = "LogMetricRule")]
+pub struct LogMetricRuleResponse {
+    pub id: Uuid,
+    pub metric_name: String,
+    pub service: Option<String>,
+    pub level: Option<String>,
+    pub message_contains: Option<String>,
+    pub project_id: Option<Uuid>,
+    pub enabled: bool,
+    pub created_at: DateTime<Utc>,
+}
+
+/// An enabled rule as used by the flush path.
+#[derive(Debug, Clone)]
+pub struct LogMetricRule {
+    pub metric_name: String,
+    pub service: Option<String>,
+    pub level: Option<String>,
+    /// Lowercased; matched case-insensitively against the message.
+    pub message_contains: Option<String>,
+    pub project_id: Option<Uuid>,
+}
+
+impl LogMetricRule {
+    fn matches(&self, log: &LogEntryRecord) -> bool {
+        self.project_id.is_none_or(|p| log.project_id == Some(p))
+            && self.service.as_deref().is_none_or(|s| log.service == s)
+            && self.level.as_deref().is_none_or(|l| log.level == l)
+            && self
+                .message_contains
+                .as_deref()
+                .is_none_or(|needle| log.message.to_lowercase().contains(needle))
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Router
+// ---------------------------------------------------------------------------
+
+pub fn router() -> Router<AppState> {
+    Router::new()
+        .route(
+            "/api/observe/log-metrics",
+            get(list_rules).post(create_rule),
+        )
+        .route("/api/observe/log-metrics/{id}", delete(delete_rule))
+}
+
+#[tracing::instrument(skip(state), err)]
+async fn list_rules(
+    State(state): State<AppState>,
+    auth: AuthUser,
+) -> Result<Json<ListResponse<LogMetricRuleResponse>>, ApiError> {
+    crate::api::helpers::require_admin(&state, &auth).await?;
+
+    let items = sqlx::query_as!(
+        LogMetricRuleResponse,
+        r"
+        SELECT id, metric_name, service, level, message_contains, project_id,
+               enabled, created_at
+        FROM log_metric_rules
+        ORDER BY created_at ASC
+        ",
+    )
+    .fetch_all(&state.pool)
+    .await?;
+
+    let total = i64::try_from(items.len()).unwrap_or(i64::MAX);
+    Ok(Json(ListResponse { items, total }))
+}
+
+#[tracing::instrument(skip(state, body), err)]
+async fn create_rule(
+    State(state): State<AppState>,
+    auth: AuthUser,
+    Json(body): Json<CreateLogMetricRuleRequest>,
+) -> Result<impl IntoResponse, ApiError> {
+    crate::api::helpers::require_admin(&state, &auth).await?;
+
+    check_metric_name(&body.metric_name)?;
+    if let Some(ref service) = body.service {
+        validation::check_length("service", service, 1, 255)?;
+    }
+    if let Some(ref level) = body.level
+        && !LEVELS.contains(&level.as_str())
+    {
+        return Err(ApiError::BadRequest(format!(
+            "level must be one of: {}",
+            LEVELS.join(", ")
+        )));
+    }
+    if let Some(ref needle) = body.message_contains {
+        validation::check_length("message_contains", needle, 1, 500)?;
+    }
+    if body.service.is_none() && body.level.is_none() && body.message_contains.is_none() {
+        return Err(ApiError::BadRequest(
+            "at least one of service, level, or message_contains is required".into(),
+        ));
+    }
+
+    let count = sqlx::query_scalar!(r#"SELECT COUNT(*) AS "count!" FROM log_metric_rules"#)
+        .fetch_one(&state.pool)
+        .await?;
+    if count >= MAX_RULES {
+        return Err(ApiError::BadRequest(format!(
+            "at most {MAX_RULES} log metric rules allowed"
+        )));
+    }
+
+    let rule = sqlx::query_as!(
+        LogMetricRuleResponse,
+        r"
+        INSERT INTO log_metric_rules (metric_name, service, level, message_contains,
+                                      project_id, created_by)
+        VALUES ($1, $2, $3, $4, $5, $6)
+        RETURNING id, metric_name, service, level, message_contains, project_id,
+                  enabled, created_at
+        ",
+        body.metric_name,
+        body.service,
+        body.level,
+        body.message_contains,
+        body.project_id,
+        auth.user_id,
+    )
+    .fetch_one(&state.pool)
+    .await?;
+
+    send_audit(
+        &state.audit_tx,
+        AuditEntry {
+            actor_id: auth.user_id,
+            actor_name: auth.user_name.clone(),
+            action: "log_metric.create".into(),
+            resource: "log_metric_rule".into(),
+            resource_id: Some(rule.id),
+            project_id: rule.project_id,
+            detail: Some(serde_json::json!({"metric_name": rule.metric_name})),
+            ip_addr: auth.ip_addr.clone(),
+        },
+    );
+
+    Ok((StatusCode::CREATED, Json(rule)))
+}
+
+#[tracing::instrument(skip(state), fields(%id), err)]
+async fn delete_rule(
+    State(state): State<AppState>,
+    auth: AuthUser,
+    Path(id): Path<Uuid>,
+) -> Result<StatusCode, ApiError> {
+    crate::api::helpers::require_admin(&state, &auth).await?;
+
+    let project_id = sqlx::query_scalar!(
+        "DELETE FROM log_metric_rules WHERE id = $1 RETURNING project_id",
+        id
+    )
+    .fetch_optional(&state.pool)
+    .await?
+    .ok_or_else(|| ApiError::NotFound("log metric rule".into()))?;
+
+    send_audit(
+        &state.audit_tx,
+        AuditEntry {
+            actor_id: auth.user_id,
+            actor_name: auth.user_name.clone(),
+            action: "log_metric.delete".into(),
+            resource: "log_metric_rule".into(),
+            resource_id: Some(id),
+            project_id,
+            detail: None,
+            ip_addr: auth.ip_addr.clone(),
+        },
+    );
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+/// Metric names follow the Prometheus convention: `[a-zA-Z_:][a-zA-Z0-9_:]*`.
+fn check_metric_name(name: &str) -> Result<(), ApiError> {
+    validation::check_length("metric_name", name, 1, 200)?;
+    let mut chars = name.chars();
+    let valid_first = chars
+        .next()
+        .is_some_and(|c| c.is_ascii_alphabetic() || c == '_' || c == ':');
+    if !valid_first || !chars.all(|c| c.is_ascii_alphanumeric() || c == '_' || c == ':') {
+        return Err(ApiError::BadRequest(
+            "metric_name must match [a-zA-Z_:][a-zA-Z0-9_:]*".into(),
+        ));
+    }
+    Ok(())
+}
+
+// ---------------------------------------------------------------------------
+// Flush path
+// ---------------------------------------------------------------------------
+
+/// Enabled rules, reloaded from the database at most every [`RULE_REFRESH`].
+#[derive(Default)]
+pub struct RuleCache {
+    rules: Vec<LogMetricRule>,
+    loaded_at: Option<Instant>,
+}
+
+impl RuleCache {
+    pub async fn get(&mut self, pool: &sqlx::PgPool) -> &[LogMetricRule] {
+        if self.loaded_at.is_none_or(|t| t.elapsed() >= RULE_REFRESH) {
+            match load_rules(pool).await {
+                Ok(rules) => self.rules = rules,
+                // Keep the previous rules; retry on the next refresh
+                Err(e) => tracing::warn!(error = %e, "failed to load log metric rules"),
+            }
+            self.loaded_at = Some(Instant::now());
+        }
+        &self.rules
+    }
+}
+
+async fn load_rules(pool: &sqlx::PgPool) -> Result<Vec<LogMetricRule>, sqlx::Error> {
+    let rows = sqlx::query!(
+        r"
+        SELECT metric_name, service, level, message_contains, project_id
+        FROM log_metric_rules
+        WHERE enabled = true
+        ",
+    )
+    .fetch_all(pool)
+    .await?;
+
+    Ok(rows
+        .into_iter()
+        .map(|r| LogMetricRule {
+            metric_name: r.metric_name,
+            service: r.service,
+            level: r.level,
+            message_contains: r.message_contains.map(|m| m.to_lowercase()),
+            project_id: r.project_id,
+        })
+        .collect())
+}
+
+/// Count matching logs per counter series. Each series is
+/// `<metric_name>{service="<log service>"}` in the log's project.
+pub fn count_matches(rules: &[LogMetricRule], logs: &[LogEntryRecord]) -> Vec<CounterIncrement> {
+    let mut counts: HashMap<(&str, &str, Option<Uuid>), u32> = HashMap::new();
+    for log in logs {
+        for rule in rules.iter().filter(|r| r.matches(log)) {
+            *counts
+                .entry((&rule.metric_name, &log.service, log.project_id))
+                .or_default() += 1;
+        }
+    }
+    counts
+        .into_iter()
+        .map(|((name, service, project_id), n)| CounterIncrement {
+            name: name.to_owned(),
+            labels: serde_json::json!({ "service": service }),
+            project_id,
+            delta: f64::from(n),
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn log(service: &str, level: &str, message: &str) -> LogEntryRecord {
+        LogEntryRecord {
+            timestamp: Utc::now(),
+            trace_id: None,
+            span_id: None,
+            project_id: None,
+            session_id: None,
+            user_id: None,
+            service: service.into(),
+            level: level.into(),
+            source: "external".into(),
+            message: message.into(),
+            attributes: None,
+        }
+    }
+
+    fn rule(service: Option<&str>, level: Option<&str>, contains: Option<&str>) -> LogMetricRule {
+        LogMetricRule {
+            metric_name: "log_errors_total".into(),
+            service: service.map(Into::into),
+            level: level.map(Into::into),
+            message_contains: contains.map(str::to_lowercase),
+            project_id: None,
+        }
+    }
+
+    #[test]
+    fn count_matches_groups_by_service() {
+        let rules = [rule(None, Some("error"), None)];
+        let logs = [
+            log("payments", "error", "card declined"),
+            log("payments", "error", "timeout"),
+            log("payments", "info", "ok"),
+            log("checkout", "error", "boom"),
+        ];
+        let mut got: Vec<(String, f64)> = count_matches(&rules, &logs)
+            .into_iter()
+            .map(|c| (c.labels["service"].as_str().unwrap().to_owned(), c.delta))
+            .collect();
+        got.sort_by(|a, b| a.0.cmp(&b.0));
+        assert_eq!(
+            got,
+            vec![("checkout".to_owned(), 1.0), ("payments".to_owned(), 2.0)]
+        );
+    }
+
+    #[test]
+    fn rule_matches_all_conditions() {
+        let r = rule(Some("payments"), Some("error"), Some("Declined"));
+        assert!(r.matches(&log("payments", "error", "Card DECLINED by issuer")));
+        assert!(!r.matches(&log("payments", "warn", "card declined")));
+        assert!(!r.matches(&log("checkout", "error", "card declined")));
+        assert!(!r.matches(&log("payments", "error", "timeout")));
+    }
+
+    #[test]
+    fn rule_scoped_to_project() {
+        let mut r = rule(None, Some("error"), None);
+        let project = Uuid::new_v4();
+        r.project_id = Some(project);
+        let mut l = log("payments", "error", "x");
+        assert!(!r.matches(&l));
+        l.project_id = Some(project);
+        assert!(r.matches(&l));
+    }
+
+    #[test]
+    fn check_metric_name_prometheus_rules() {
+        assert!(check_metric_name("log_errors_total").is_ok());
+        assert!(check_metric_name("ns:errors").is_ok());
+        assert!(check_metric_name("_x1").is_ok());
+        assert!(check_metric_name("1abc").is_err());
+        assert!(check_metric_name("bad-name").is_err());
+        assert!(check_metric_name("").is_err());
+    }
+}
@@ -6,12 +6,15 @@
 pub mod alert;
 pub mod correlation;
 pub mod error;
+pub mod grpc;
 pub mod ingest;
 pub mod k8s_watcher;
+pub mod log_metrics;
 pub mod parquet;
 pub mod partitions;
 pub mod proto;
 pub mod query;
+pub mod rollup;
 pub mod store;
 pub mod tracing_layer;
 
@@ -28,6 +31,7 @@ pub fn router(channels: ingest::IngestChannels) -> Router<AppState> {
         .layer(axum::Extension(channels))
         .merge(query::router())
         .merge(alert::router())
+        .merge(log_metrics::router())
 }
 
 /// Spawn all observe background tasks. Returns `IngestChannels` for the router.
@@ -52,27 +56,28 @@ pub fn spawn_background_tasks(
     ));
     tracker.spawn(ingest::flush_metrics(
         state.pool.clone(),
+        state.config.observe_max_series_per_project,
         metrics_rx,
         cancel.clone(),
     ));
+    // Raw logs, spans, and samples leave Postgres only through Parquet
+    // rotation, which archives each batch to MinIO before deleting it.
     tracker.spawn(parquet::rotation_loop(state.clone(), cancel.clone()));
-    // S94: Observability data retention — purge old data hourly
+    // S94: Metric rollup retention — purge old rollups hourly
     {
         let pool = state.pool.clone();
         let retention_days = state.config.observe_retention_days;
         let cancel = cancel.clone();
         tracker.spawn(async move {
-            let mut interval = tokio::time::interval(std::time::Duration::from_secs(3600));
+            let mut interval = tokio::time::interval(std::time::Duration::from_hours(1));
             loop {
                 tokio::select! {
                     _ = interval.tick() => {
                         let cutoff = chrono::Utc::now()
                             - chrono::Duration::days(i64::from(retention_days));
-                        // spans uses `started_at`; log_entries and metric_samples use `timestamp`
                         for (table, col) in &[
-                            ("spans", "started_at"),
-                            ("log_entries", "timestamp"),
-                            ("metric_samples", "timestamp"),
+                            ("metric_rollups_1m", "bucket"),
+                            ("metric_rollups_1h", "bucket"),
                         ] {
                             // Batched deletion to avoid long table locks
                             let batch_size: i64 = 50_000;
@@ -130,6 +135,11 @@ pub fn spawn_background_tasks(
 
     tracker.spawn(alert::evaluate_alerts_loop(state.clone(), cancel.clone()));
     tracker.spawn(partitions::run(state.pool.clone(), cancel.clone()));
+    tracker.spawn(rollup::run(
+        state.pool.clone(),
+        state.config.observe_retention_days,
+        cancel.clone(),
+    ));
 
     // K8s watcher: stream pod/deployment state into metric_samples
     let ns = state.config.platform_namespace.clone();
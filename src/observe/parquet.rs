@@ -3,9 +3,10 @@
 
 use std::sync::Arc;
 
-use arrow::array::{Float64Array, Int32Array, StringArray, TimestampMicrosecondArray};
+use arrow::array::{Array, Float64Array, Int32Array, StringArray, TimestampMicrosecondArray};
 use arrow::datatypes::{DataType, Field, Schema, TimeUnit};
 use arrow::record_batch::RecordBatch;
+use chrono::{DateTime, NaiveDate, Utc};
 use parquet::arrow::ArrowWriter;
 use sqlx::Row;
 use uuid::Uuid;
@@ -16,11 +17,20 @@ use crate::store::AppState;
 
 use super::error::ObserveError;
 
+/// Rows archived per Parquet rotation batch.
+const ROTATION_BATCH_SIZE: i64 = 10_000;
+
+/// Upper bound on batches per signal per rotation tick, so a large backlog
+/// (e.g. after lowering a retention setting) drains over several ticks.
+const MAX_BATCHES_PER_TICK: u32 = 50;
+
 // ---------------------------------------------------------------------------
 // Rotation loop
 // ---------------------------------------------------------------------------
 
-/// Background task: rotate old data to Parquet every 15 minutes.
+/// Background task: every 15 minutes, archive logs, spans, and metric samples
+/// older than their hot-retention window to Parquet in `MinIO` and delete them
+/// from Postgres.
 pub async fn rotation_loop(state: AppState, cancel: tokio_util::sync::CancellationToken) {
     tracing::info!("parquet rotation started");
     state.task_registry.register("parquet_rotation", 1800);
@@ -31,7 +41,7 @@ pub async fn rotation_loop(state: AppState, cancel: tokio_util::sync::Cancellati
                 tracing::info!("parquet rotation shutting down");
                 break;
             }
-            () = tokio::time::sleep(std::time::Duration::from_secs(900)) => {
+            () = tokio::time::sleep(std::time::Duration::from_mins(15)) => {
                 let iter_trace_id = uuid::Uuid::new_v4().to_string().replace('-', "");
                 let span = tracing::info_span!(
                     "task_iteration",
@@ -41,17 +51,17 @@ pub async fn rotation_loop(state: AppState, cancel: tokio_util::sync::Cancellati
                 );
                 async {
                     let mut had_error = false;
-                    if let Err(e) = rotate_logs(&state).await {
+                    if let Err(e) = drain(|| rotate_logs(&state)).await {
                         state.task_registry.report_error("parquet_rotation", &e.to_string());
                         tracing::error!(error = %e, "log rotation failed");
                         had_error = true;
                     }
-                    if let Err(e) = rotate_spans(&state).await {
+                    if let Err(e) = drain(|| rotate_spans(&state)).await {
                         state.task_registry.report_error("parquet_rotation", &e.to_string());
                         tracing::error!(error = %e, "span rotation failed");
                         had_error = true;
                     }
-                    if let Err(e) = rotate_metrics(&state).await {
+                    if let Err(e) = drain(|| rotate_metrics(&state)).await {
                         state.task_registry.report_error("parquet_rotation", &e.to_string());
                         tracing::error!(error = %e, "metric rotation failed");
                         had_error = true;
@@ -65,26 +75,78 @@ pub async fn rotation_loop(state: AppState, cancel: tokio_util::sync::Cancellati
     }
 }
 
+/// Run `rotate` until it archives a partial batch (caught up) or the per-tick
+/// cap is reached. Returns the total number of rows archived.
+async fn drain<F, Fut>(mut rotate: F) -> Result<u64, ObserveError>
+where
+    F: FnMut() -> Fut,
+    Fut: std::future::Future<Output = Result<u64, ObserveError>>,
+{
+    let mut total = 0;
+    for _ in 0..MAX_BATCHES_PER_TICK {
+        let count = rotate().await?;
+        total += count;
+        #[allow(clippy::cast_sign_loss)]
+        if count < ROTATION_BATCH_SIZE as u64 {
+            break;
+        }
+    }
+    Ok(total)
+}
+
+/// Rows newer than `retention_days` stay in Postgres.
+fn hot_cutoff(retention_days: u32) -> DateTime<Utc> {
+    Utc::now() - chrono::Duration::days(i64::from(retention_days))
+}
+
+/// Split time-ordered rows into per-UTC-day groups, so each Parquet file is
+/// stored under the date its data belongs to (`otel/<signal>/<date>/...`).
+fn split_by_day<T>(rows: Vec<T>, ts: impl Fn(&T) -> DateTime<Utc>) -> Vec<(NaiveDate, Vec<T>)> {
+    let mut days: Vec<(NaiveDate, Vec<T>)> = Vec::new();
+    for row in rows {
+        let day = ts(&row).date_naive();
+        match days.last_mut() {
+            Some((d, group)) if *d == day => group.push(row),
+            _ => days.push((day, vec![row])),
+        }
+    }
+    days
+}
+
+/// Upload a Parquet file and confirm it is readable before the caller deletes
+/// the source rows.
+async fn upload_verified(state: &AppState, path: &str, bytes: Vec<u8>) -> Result<(), ObserveError> {
+    crate::store::lifecycle::write_expiring(state, path, bytes).await?;
+
+    // A36: Verify upload succeeded before deleting source data
+    state.minio.stat(path).await.map_err(|e| {
+        tracing::error!(error = %e, path = %path, "parquet upload verification failed, skipping delete");
+        e
+    })?;
+    Ok(())
+}
+
 // ---------------------------------------------------------------------------
 // Log rotation
 // ---------------------------------------------------------------------------
 
-/// Rotate log entries older than 48h to `MinIO` Parquet.
+/// Rotate log entries older than `observe_log_retention_days` to `MinIO` Parquet.
 #[tracing::instrument(skip(state), err)]
 pub async fn rotate_logs(state: &AppState) -> Result<u64, ObserveError> {
-    let cutoff = chrono::Utc::now() - chrono::Duration::hours(48);
+    let cutoff = hot_cutoff(state.config.observe_log_retention_days);
 
     let rows = sqlx::query(
         r"
         SELECT id, timestamp, trace_id, span_id, project_id, session_id,
-               service, level, message, attributes
+               service, level, source, message, attributes
         FROM log_entries
         WHERE timestamp < $1
         ORDER BY timestamp ASC
-        LIMIT 10000
+        LIMIT $2
         ",
     )
     .bind(cutoff)
+    .bind(ROTATION_BATCH_SIZE)
     .fetch_all(&state.pool)
     .await?;
 
@@ -93,7 +155,6 @@ pub async fn rotate_logs(state: &AppState) -> Result<u64, ObserveError> {
     }
 
     let count = rows.len() as u64;
-    let ids: Vec<Uuid> = rows.iter().map(|r| r.get::<Uuid, _>("id")).collect();
 
     let typed: Vec<LogQueryRow> = rows
         .iter()
@@ -106,14 +167,18 @@ pub async fn rotate_logs(state: &AppState) -> Result<u64, ObserveError> {
             session_id: r.get("session_id"),
             service: r.get("service"),
             level: r.get("level"),
+            source: r.get("source"),
             message: r.get("message"),
             attributes: r.get("attributes"),
         })
         .collect();
 
-    let batch = build_log_batch(&typed)?;
-    let parquet_bytes = write_parquet_buffer(&batch)?;
-    upload_and_delete_logs(state, &ids, parquet_bytes, cutoff).await?;
+    for (day, day_rows) in split_by_day(typed, |r| r.timestamp) {
+        let ids: Vec<Uuid> = day_rows.iter().map(|r| r.id).collect();
+        let batch = build_log_batch(&day_rows)?;
+        let parquet_bytes = write_parquet_buffer(&batch)?;
+        upload_and_delete_logs(state, &ids, parquet_bytes, day).await?;
+    }
 
     tracing::info!(count, "rotated logs to parquet");
     Ok(count)
@@ -123,18 +188,12 @@ async fn upload_and_delete_logs(
     state: &AppState,
     ids: &[Uuid],
     parquet_bytes: Vec<u8>,
-    cutoff: chrono::DateTime<chrono::Utc>,
+    day: NaiveDate,
 ) -> Result<(), ObserveError> {
-    let date = cutoff.format("%Y-%m-%d");
+    let date = day.format("%Y-%m-%d");
     let batch_id = Uuid::new_v4();
     let path = format!("otel/logs/{date}/logs_{batch_id}.parquet");
-    state.minio.write(&path, parquet_bytes).await?;
-
-    // A36: Verify upload succeeded before deleting source data
-    state.minio.stat(&path).await.map_err(|e| {
-        tracing::error!(error = %e, path = %path, "parquet upload verification failed, skipping delete");
-        e
-    })?;
+    upload_verified(state, &path, parquet_bytes).await?;
 
     sqlx::query("DELETE FROM log_entries WHERE id = ANY($1)")
         .bind(ids)
@@ -159,6 +218,8 @@ fn log_schema() -> Arc<Schema> {
         Field::new("level", DataType::Utf8, false),
         Field::new("message", DataType::Utf8, false),
         Field::new("attributes", DataType::Utf8, true),
+        // Added after the first archives were written; absent in older files.
+        Field::new("source", DataType::Utf8, true),
     ]))
 }
 
@@ -174,6 +235,7 @@ fn build_log_batch(rows: &[LogQueryRow]) -> Result<RecordBatch, ObserveError> {
     let mut levels = Vec::with_capacity(len);
     let mut messages = Vec::with_capacity(len);
     let mut attributes: Vec<Option<String>> = Vec::with_capacity(len);
+    let mut sources = Vec::with_capacity(len);
 
     for row in rows {
         ids.push(row.id.to_string());
@@ -186,6 +248,7 @@ fn build_log_batch(rows: &[LogQueryRow]) -> Result<RecordBatch, ObserveError> {
         levels.push(row.level.clone());
         messages.push(row.message.clone());
         attributes.push(row.attributes.as_ref().map(ToString::to_string));
+        sources.push(Some(row.source.clone()));
     }
 
     let schema = log_schema();
@@ -200,32 +263,124 @@ fn build_log_batch(rows: &[LogQueryRow]) -> Result<RecordBatch, ObserveError> {
         Arc::new(StringArray::from(levels)),
         Arc::new(StringArray::from(messages)),
         Arc::new(StringArray::from(attributes)),
+        Arc::new(StringArray::from(sources)),
     ];
 
     Ok(RecordBatch::try_new(schema, columns)?)
 }
 
-struct LogQueryRow {
-    id: Uuid,
-    timestamp: chrono::DateTime<chrono::Utc>,
-    trace_id: Option<String>,
-    span_id: Option<String>,
-    project_id: Option<Uuid>,
-    session_id: Option<Uuid>,
-    service: String,
-    level: String,
-    message: String,
-    attributes: Option<serde_json::Value>,
+/// A log entry as stored in `log_entries` and in the Parquet archive.
+pub(crate) struct LogQueryRow {
+    pub(crate) id: Uuid,
+    pub(crate) timestamp: chrono::DateTime<chrono::Utc>,
+    pub(crate) trace_id: Option<String>,
+    pub(crate) span_id: Option<String>,
+    pub(crate) project_id: Option<Uuid>,
+    pub(crate) session_id: Option<Uuid>,
+    pub(crate) service: String,
+    pub(crate) level: String,
+    pub(crate) source: String,
+    pub(crate) message: String,
+    pub(crate) attributes: Option<serde_json::Value>,
+}
+
+// ---------------------------------------------------------------------------
+// Archived log reads
+// ---------------------------------------------------------------------------
+
+/// Read every archived log entry for one UTC day from `otel/logs/<date>/`.
+pub(crate) async fn read_archived_logs(
+    state: &AppState,
+    day: NaiveDate,
+) -> Result<Vec<LogQueryRow>, ObserveError> {
+    let prefix = format!("otel/logs/{}/", day.format("%Y-%m-%d"));
+    let entries = match state.minio.list(&prefix).await {
+        Ok(entries) => entries,
+        Err(e) if e.kind() == opendal::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(e) => return Err(e.into()),
+    };
+
+    let mut rows = Vec::new();
+    for entry in entries {
+        if !entry.path().ends_with(".parquet") {
+            continue;
+        }
+        let data = state.minio.read(entry.path()).await?;
+        let reader = parquet::arrow::arrow_reader::ParquetRecordBatchReaderBuilder::try_new(
+            data.to_bytes(),
+        )?
+        .build()?;
+        for batch in reader {
+            rows.extend(parse_log_batch(&batch?)?);
+        }
+    }
+    Ok(rows)
+}
+
+/// Inverse of [`build_log_batch`]. Files written before the `source` column
+/// existed default to `external`, matching the column default in Postgres.
+fn parse_log_batch(batch: &RecordBatch) -> Result<Vec<LogQueryRow>, ObserveError> {
+    fn column<'a, T: 'static>(batch: &'a RecordBatch, name: &str) -> Option<&'a T> {
+        batch
+            .column_by_name(name)
+            .and_then(|c| c.as_any().downcast_ref::<T>())
+    }
+    fn required<'a, T: 'static>(batch: &'a RecordBatch, name: &str) -> Result<&'a T, ObserveError> {
+        column(batch, name).ok_or_else(|| {
+            ObserveError::Other(anyhow::anyhow!("archived log batch missing column {name}"))
+        })
+    }
+    fn opt_str(col: &StringArray, i: usize) -> Option<String> {
+        col.is_valid(i).then(|| col.value(i).to_owned())
+    }
+    fn opt_uuid(col: &StringArray, i: usize) -> Option<Uuid> {
+        col.is_valid(i).then(|| col.value(i).parse().ok()).flatten()
+    }
+
+    let ids = required::<StringArray>(batch, "id")?;
+    let timestamps = required::<TimestampMicrosecondArray>(batch, "timestamp")?;
+    let trace_ids = required::<StringArray>(batch, "trace_id")?;
+    let span_ids = required::<StringArray>(batch, "span_id")?;
+    let project_ids = required::<StringArray>(batch, "project_id")?;
+    let session_ids = required::<StringArray>(batch, "session_id")?;
+    let services = required::<StringArray>(batch, "service")?;
+    let levels = required::<StringArray>(batch, "level")?;
+    let messages = required::<StringArray>(batch, "message")?;
+    let attributes = required::<StringArray>(batch, "attributes")?;
+    let sources = column::<StringArray>(batch, "source");
+
+    let mut rows = Vec::with_capacity(batch.num_rows());
+    for i in 0..batch.num_rows() {
+        let Some(timestamp) = DateTime::from_timestamp_micros(timestamps.value(i)) else {
+            continue;
+        };
+        rows.push(LogQueryRow {
+            id: ids.value(i).parse().unwrap_or_else(|_| Uuid::nil()),
+            timestamp,
+            trace_id: opt_str(trace_ids, i),
+            span_id: opt_str(span_ids, i),
+            project_id: opt_uuid(project_ids, i),
+            session_id: opt_uuid(session_ids, i),
+            service: services.value(i).to_owned(),
+            level: levels.value(i).to_owned(),
+            source: sources
+                .and_then(|c| opt_str(c, i))
+                .unwrap_or_else(|| "external".into()),
+            message: messages.value(i).to_owned(),
+            attributes: opt_str(attributes, i).and_then(|a| serde_json::from_str(&a).ok()),
+        });
+    }
+    Ok(rows)
 }
 
 // ---------------------------------------------------------------------------
 // Span rotation
 // ---------------------------------------------------------------------------
 
-/// Rotate spans older than 48h to `MinIO` Parquet.
+/// Rotate spans older than `observe_span_retention_days` to `MinIO` Parquet.
 #[tracing::instrument(skip(state), err)]
 pub async fn rotate_spans(state: &AppState) -> Result<u64, ObserveError> {
-    let cutoff = chrono::Utc::now() - chrono::Duration::hours(48);
+    let cutoff = hot_cutoff(state.config.observe_span_retention_days);
 
     let rows = sqlx::query(
         r"
@@ -234,10 +389,11 @@ pub async fn rotate_spans(state: &AppState) -> Result<u64, ObserveError> {
         FROM spans
         WHERE started_at < $1
         ORDER BY started_at ASC
-        LIMIT 10000
+        LIMIT $2
         ",
     )
     .bind(cutoff)
+    .bind(ROTATION_BATCH_SIZE)
     .fetch_all(&state.pool)
     .await?;
 
@@ -246,48 +402,48 @@ pub async fn rotate_spans(state: &AppState) -> Result<u64, ObserveError> {
     }
 
     let count = rows.len() as u64;
-    let ids: Vec<Uuid> = rows.iter().map(|r| r.get::<Uuid, _>("id")).collect();
 
     // Convert to typed rows for batch building
-    let typed: Vec<SpanQueryRow> = rows
+    let typed: Vec<(Uuid, SpanQueryRow)> = rows
         .iter()
-        .map(|r| SpanQueryRow {
-            trace_id: r.get("trace_id"),
-            span_id: r.get("span_id"),
-            parent_span_id: r.get("parent_span_id"),
-            name: r.get("name"),
-            service: r.get("service"),
-            kind: r.get("kind"),
-            status: r.get("status"),
-            duration_ms: r.get("duration_ms"),
-            started_at: r.get("started_at"),
-            attributes: r.get("attributes"),
-            project_id: r.get("project_id"),
-            session_id: r.get("session_id"),
-            user_id: r.get("user_id"),
+        .map(|r| {
+            (
+                r.get("id"),
+                SpanQueryRow {
+                    trace_id: r.get("trace_id"),
+                    span_id: r.get("span_id"),
+                    parent_span_id: r.get("parent_span_id"),
+                    name: r.get("name"),
+                    service: r.get("service"),
+                    kind: r.get("kind"),
+                    status: r.get("status"),
+                    duration_ms: r.get("duration_ms"),
+                    started_at: r.get("started_at"),
+                    attributes: r.get("attributes"),
+                    project_id: r.get("project_id"),
+                    session_id: r.get("session_id"),
+                    user_id: r.get("user_id"),
+                },
+            )
         })
         .collect();
 
-    let batch = build_span_batch(&typed)?;
-    let parquet_bytes = write_parquet_buffer(&batch)?;
-
-    let date = cutoff.format("%Y-%m-%d");
-    let batch_id = Uuid::new_v4();
-    let path = format!("otel/traces/{date}/spans_{batch_id}.parquet");
-    state.minio.write(&path, parquet_bytes).await?;
+    for (day, day_rows) in split_by_day(typed, |(_, r)| r.started_at) {
+        let (ids, spans): (Vec<Uuid>, Vec<SpanQueryRow>) = day_rows.into_iter().unzip();
+        let batch = build_span_batch(&spans)?;
+        let parquet_bytes = write_parquet_buffer(&batch)?;
 
-    // A36: Verify upload succeeded before deleting source data
-    state.minio.stat(&path).await.map_err(|e| {
-        tracing::error!(error = %e, path = %path, "parquet upload verification failed, skipping delete");
-        e
-    })?;
+        let date = day.format("%Y-%m-%d");
+        let batch_id = Uuid::new_v4();
+        let path = format!("otel/traces/{date}/spans_{batch_id}.parquet");
+        upload_verified(state, &path, parquet_bytes).await?;
 
-    sqlx::query("DELETE FROM spans WHERE id = ANY($1)")
-        .bind(&ids)
-        .execute(&state.pool)
-        .await?;
+        sqlx::query!("DELETE FROM spans WHERE id = ANY($1)", &ids)
+            .execute(&state.pool)
+            .await?;
+    }
 
-    tracing::info!(count, path = %path, "rotated spans to parquet");
+    tracing::info!(count, "rotated spans to parquet");
     Ok(count)
 }
 
@@ -386,10 +542,10 @@ fn build_span_batch(rows: &[SpanQueryRow]) -> Result<RecordBatch, ObserveError>
 // Metric rotation
 // ---------------------------------------------------------------------------
 
-/// Rotate metric samples older than 1h to `MinIO` Parquet.
+/// Rotate metric samples older than `observe_metric_retention_days` to `MinIO` Parquet.
 #[tracing::instrument(skip(state), err)]
 pub async fn rotate_metrics(state: &AppState) -> Result<u64, ObserveError> {
-    let cutoff = chrono::Utc::now() - chrono::Duration::hours(1);
+    let cutoff = hot_cutoff(state.config.observe_metric_retention_days);
 
     let rows = sqlx::query(
         r"
@@ -399,10 +555,11 @@ pub async fn rotate_metrics(state: &AppState) -> Result<u64, ObserveError> {
         JOIN metric_series ser ON ser.id = ms.series_id
         WHERE ms.timestamp < $1
         ORDER BY ms.timestamp ASC
-        LIMIT 10000
+        LIMIT $2
         ",
     )
     .bind(cutoff)
+    .bind(ROTATION_BATCH_SIZE)
     .fetch_all(&state.pool)
     .await?;
 
@@ -423,38 +580,33 @@ pub async fn rotate_metrics(state: &AppState) -> Result<u64, ObserveError> {
         })
         .collect();
 
-    let batch = build_metric_batch(&typed)?;
-    let parquet_bytes = write_parquet_buffer(&batch)?;
-
-    let date = cutoff.format("%Y-%m-%d");
-    let batch_id = Uuid::new_v4();
-    let path = format!("otel/metrics/{date}/metrics_{batch_id}.parquet");
-    state.minio.write(&path, parquet_bytes).await?;
-
-    // A36: Verify upload succeeded before deleting source data
-    state.minio.stat(&path).await.map_err(|e| {
-        tracing::error!(error = %e, path = %path, "parquet upload verification failed, skipping delete");
-        e
-    })?;
-
-    // Delete rotated samples
-    let series_ids: Vec<Uuid> = typed.iter().map(|r| r.series_id).collect();
-    let timestamps: Vec<chrono::DateTime<chrono::Utc>> =
-        typed.iter().map(|r| r.timestamp).collect();
-
-    sqlx::query(
-        r"
-        DELETE FROM metric_samples ms
-        USING (SELECT * FROM UNNEST($1::uuid[], $2::timestamptz[]) AS t(s, ts)) v
-        WHERE ms.series_id = v.s AND ms.timestamp = v.ts
-        ",
-    )
-    .bind(&series_ids)
-    .bind(&timestamps)
-    .execute(&state.pool)
-    .await?;
+    for (day, day_rows) in split_by_day(typed, |r| r.timestamp) {
+        let batch = build_metric_batch(&day_rows)?;
+        let parquet_bytes = write_parquet_buffer(&batch)?;
+
+        let date = day.format("%Y-%m-%d");
+        let batch_id = Uuid::new_v4();
+        let path = format!("otel/metrics/{date}/metrics_{batch_id}.parquet");
+        upload_verified(state, &path, parquet_bytes).await?;
+
+        // Delete rotated samples
+        let series_ids: Vec<Uuid> = day_rows.iter().map(|r| r.series_id).collect();
+        let timestamps: Vec<DateTime<Utc>> = day_rows.iter().map(|r| r.timestamp).collect();
+
+        sqlx::query!(
+            r"
+            DELETE FROM metric_samples ms
+            USING (SELECT * FROM UNNEST($1::uuid[], $2::timestamptz[]) AS t(s, ts)) v
+            WHERE ms.series_id = v.s AND ms.timestamp = v.ts
+            ",
+            &series_ids,
+            &timestamps,
+        )
+        .execute(&state.pool)
+        .await?;
+    }
 
-    tracing::info!(count, path = %path, "rotated metrics to parquet");
+    tracing::info!(count, "rotated metrics to parquet");
     Ok(count)
 }
 
@@ -525,12 +677,41 @@ mod tests {
     use arrow::datatypes::{DataType, TimeUnit};
     use chrono::Utc;
 
+    // ── Day split tests ─────────────────────────────────────────────
+
+    #[test]
+    fn split_by_day_groups_consecutive_days() {
+        let ts = |s: &str| s.parse::<DateTime<Utc>>().unwrap();
+        let rows = vec![
+            ts("2026-10-01T23:59:59Z"),
+            ts("2026-10-02T00:00:00Z"),
+            ts("2026-10-02T12:00:00Z"),
+            ts("2026-10-04T08:00:00Z"),
+        ];
+        let days = split_by_day(rows, |t| *t);
+        let summary: Vec<(String, usize)> =
+            days.iter().map(|(d, g)| (d.to_string(), g.len())).collect();
+        assert_eq!(
+            summary,
+            vec![
+                ("2026-10-01".to_owned(), 1),
+                ("2026-10-02".to_owned(), 2),
+                ("2026-10-04".to_owned(), 1),
+            ]
+        );
+    }
+
+    #[test]
+    fn split_by_day_empty() {
+        assert!(split_by_day(Vec::<DateTime<Utc>>::new(), |t| *t).is_empty());
+    }
+
     // ── Schema tests ────────────────────────────────────────────────
 
     #[test]
-    fn log_schema_has_10_fields() {
+    fn log_schema_has_11_fields() {
         let schema = log_schema();
-        assert_eq!(schema.fields().len(), 10);
+        assert_eq!(schema.fields().len(), 11);
         assert_eq!(schema.field(0).name(), "id");
         assert_eq!(
             *schema.field(1).data_type(),
@@ -573,6 +754,7 @@ mod tests {
             session_id: None,
             service: "my-svc".into(),
             level: "info".into(),
+            source: "external".into(),
             message: "hello".into(),
             attributes: Some(serde_json::json!({"key": "val"})),
         }
@@ -583,7 +765,7 @@ mod tests {
         let rows = vec![sample_log_row()];
         let batch = build_log_batch(&rows).unwrap();
         assert_eq!(batch.num_rows(), 1);
-        assert_eq!(batch.num_columns(), 10);
+        assert_eq!(batch.num_columns(), 11);
     }
 
     #[test]
@@ -604,6 +786,7 @@ mod tests {
             session_id: None,
             service: "svc".into(),
             level: "error".into(),
+            source: "external".into(),
             message: "fail".into(),
             attributes: None,
         };
@@ -729,12 +912,13 @@ mod tests {
             session_id: Some(Uuid::new_v4()),
             service: "full-svc".into(),
             level: "debug".into(),
+            source: "external".into(),
             message: "all fields".into(),
             attributes: Some(serde_json::json!({"nested": {"key": "val"}})),
         };
         let batch = build_log_batch(&[row]).unwrap();
         assert_eq!(batch.num_rows(), 1);
-        assert_eq!(batch.num_columns(), 10);
+        assert_eq!(batch.num_columns(), 11);
         // Verify non-nullable columns have no nulls
         assert_eq!(batch.column(0).null_count(), 0); // id
         assert_eq!(batch.column(6).null_count(), 0); // service
@@ -793,7 +977,7 @@ mod tests {
     fn build_log_batch_empty_input() {
         let batch = build_log_batch(&[]).unwrap();
         assert_eq!(batch.num_rows(), 0);
-        assert_eq!(batch.num_columns(), 10);
+        assert_eq!(batch.num_columns(), 11);
     }
 
     #[test]
@@ -832,6 +1016,7 @@ mod tests {
                 session_id: None,
                 service: format!("svc-{}", i % 5),
                 level: if i % 2 == 0 { "info" } else { "error" }.into(),
+                source: "external".into(),
                 message: format!("msg {i}"),
                 attributes: None,
             })
@@ -861,7 +1046,8 @@ mod tests {
                 "service",
                 "level",
                 "message",
-                "attributes"
+                "attributes",
+                "source"
             ]
         );
     }
@@ -911,6 +1097,7 @@ mod tests {
             session_id: None,
             service: "ts-svc".into(),
             level: "info".into(),
+            source: "external".into(),
             message: "ts-msg".into(),
             attributes: None,
         };
@@ -937,6 +1124,43 @@ mod tests {
         assert_eq!(col.value(0), ts.timestamp_micros());
     }
 
+    // ── Archived log parsing ────────────────────────────────────────
+
+    #[test]
+    fn parse_log_batch_round_trips() {
+        let mut row = sample_log_row();
+        row.source = "system".into();
+        let batch = build_log_batch(&[row]).unwrap();
+        let parsed = parse_log_batch(&batch).unwrap();
+        assert_eq!(parsed.len(), 1);
+        let original = sample_log_row();
+        let p = &parsed[0];
+        assert_eq!(p.trace_id.as_deref(), Some("trace-abc"));
+        assert_eq!(p.span_id.as_deref(), Some("span-def"));
+        assert!(p.project_id.is_some());
+        assert!(p.session_id.is_none());
+        assert_eq!(p.service, original.service);
+        assert_eq!(p.level, original.level);
+        assert_eq!(p.source, "system");
+        assert_eq!(p.message, original.message);
+        assert_eq!(p.attributes, original.attributes);
+    }
+
+    #[test]
+    fn parse_log_batch_defaults_missing_source() {
+        let batch = build_log_batch(&[sample_log_row()]).unwrap();
+        let legacy = batch.project(&(0..10).collect::<Vec<_>>()).unwrap();
+        assert!(legacy.column_by_name("source").is_none());
+        let parsed = parse_log_batch(&legacy).unwrap();
+        assert_eq!(parsed[0].source, "external");
+    }
+
+    #[test]
+    fn parse_log_batch_rejects_unrelated_schema() {
+        let batch = build_metric_batch(&[sample_metric_row()]).unwrap();
+        assert!(parse_log_batch(&batch).is_err());
+    }
+
     // ── write_parquet_buffer round-trip read ────────────────────────
 
     #[test]
@@ -955,7 +1179,7 @@ mod tests {
         let batches: Vec<_> = reader.collect::<Result<Vec<_>, _>>().unwrap();
         assert_eq!(batches.len(), 1);
         assert_eq!(batches[0].num_rows(), 1);
-        assert_eq!(batches[0].num_columns(), 10);
+        assert_eq!(batches[0].num_columns(), 11);
     }
 
     #[test]
@@ -20,7 +20,7 @@ const MONTHS_AHEAD: u32 = 3;
 /// Run the partition manager loop until shutdown.
 pub async fn run(pool: PgPool, cancel: tokio_util::sync::CancellationToken) {
     // Run once immediately at startup, then daily.
-    let mut interval = tokio::time::interval(Duration::from_secs(86_400));
+    let mut interval = tokio::time::interval(Duration::from_hours(24));
     loop {
         tokio::select! {
             _ = interval.tick() => {
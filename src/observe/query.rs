@@ -1,6 +1,9 @@
 // Copyright (c) 2026 Steven Hooker. Exclusively licensed to and distributed by AgentSphere GmbH.
 // SPDX-License-Identifier: BUSL-1.1
 
+use std::sync::Arc;
+use std::sync::atomic::{AtomicU64, Ordering};
+
 use axum::extract::{Path, Query, State};
 use axum::response::IntoResponse;
 use axum::response::sse::{Event, KeepAlive, Sse};
@@ -9,14 +12,34 @@ use axum::{Json, Router};
 use chrono::{DateTime, Utc};
 use fred::interfaces::EventInterface;
 use fred::interfaces::PubsubInterface;
+use futures_util::StreamExt;
 use serde::{Deserialize, Serialize};
 use sqlx::Row;
-use tokio::time::{Duration, timeout};
-use tokio_stream::StreamExt;
+use tokio::sync::broadcast::{self, error::RecvError};
+use tokio::time::{Duration, Instant, timeout};
 use uuid::Uuid;
 
 const QUERY_TIMEOUT: Duration = Duration::from_secs(10);
 
+/// Archived log reads fetch Parquet files from `MinIO`, so they get more time.
+const ARCHIVE_QUERY_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Most days of Parquet-archived logs a single search reads.
+const MAX_ARCHIVE_DAYS: i64 = 31;
+
+/// Most archived log entries a single search collects (newest first).
+const MAX_ARCHIVE_MATCHES: usize = 10_000;
+
+/// Live tail messages buffered per connection; the oldest are shed when a
+/// slow client falls further behind.
+const LIVE_TAIL_BUFFER: usize = 256;
+
+/// Most live tail messages forwarded per connection per second.
+const LIVE_TAIL_MAX_PER_SEC: u32 = 200;
+
+/// How often a `dropped` control event reports shed messages.
+const LIVE_TAIL_DROP_REPORT: Duration = Duration::from_secs(1);
+
 use ts_rs::TS;
 
 use crate::api::helpers::ListResponse;
@@ -64,6 +87,9 @@ pub struct LogEntryResponse {
     pub source: String,
     pub message: String,
     pub attributes: Option<serde_json::Value>,
+    /// True when the entry was read from the Parquet archive in `MinIO`
+    /// rather than from Postgres.
+    pub archived: bool,
 }
 
 // --- Trace types ---
@@ -76,6 +102,11 @@ pub struct TraceListParams {
     pub status: Option<String>,
     pub from: Option<DateTime<Utc>>,
     pub to: Option<DateTime<Utc>>,
+    /// Only traces whose total duration is at least this many milliseconds.
+    pub min_duration_ms: Option<i64>,
+    /// Span attribute filter `key:value` (or `key=value`). Matches traces
+    /// containing at least one span with that attribute.
+    pub attr: Option<String>,
     pub limit: Option<i64>,
     pub offset: Option<i64>,
 }
@@ -135,10 +166,10 @@ pub struct MetricQueryParams {
     pub to: Option<DateTime<Utc>>,
     /// Relative time range like "1h", "6h", "24h", "7d". Converted to `from`.
     pub range: Option<String>,
-    #[serde(rename = "step")]
-    _step: Option<i64>,
-    #[serde(rename = "agg")]
-    _agg: Option<String>,
+    /// Bucket width in seconds (1..=86400). Points are aggregated per bucket.
+    pub step: Option<i64>,
+    /// Per-bucket aggregation: `avg` (default), `sum`, `min`, `max`, `count`.
+    pub agg: Option<String>,
     pub limit: Option<i64>,
     #[serde(rename = "offset")]
     _offset: Option<i64>,
@@ -168,6 +199,32 @@ pub struct MetricNameResponse {
     pub unit: Option<String>,
 }
 
+#[derive(Debug, Deserialize)]
+pub struct CardinalityParams {
+    pub project_id: Option<Uuid>,
+    pub limit: Option<i64>,
+}
+
+#[derive(Debug, Serialize, TS)]
+#[ts(export)]
+pub struct MetricCardinality {
+    pub name: String,
+    pub project_id: Option<Uuid>,
+    pub series_count: i64,
+}
+
+#[derive(Debug, Serialize, TS)]
+#[ts(export)]
+pub struct MetricCardinalityResponse {
+    /// Highest-cardinality metric names, most series first.
+    pub items: Vec<MetricCardinality>,
+    /// Samples dropped by the per-project series cap since this replica started.
+    #[ts(type = "number")]
+    pub metrics_dropped: u64,
+    #[ts(type = "number")]
+    pub max_series_per_project: u64,
+}
+
 // --- Session types ---
 
 #[derive(Debug, Serialize)]
@@ -203,9 +260,14 @@ pub fn router() -> Router<AppState> {
         // Static path MUST come before parameterized {trace_id}
         .route("/api/observe/traces/aggregated", get(get_trace_aggregation))
         .route("/api/observe/traces/{trace_id}", get(get_trace))
+        .route("/api/observe/traces/{trace_id}/logs", get(get_trace_logs))
         .route("/api/observe/metrics", get(query_metrics))
         .route("/api/observe/metrics/query", get(query_metrics))
         .route("/api/observe/metrics/names", get(list_metric_names))
+        .route(
+            "/api/observe/metrics/cardinality",
+            get(get_metric_cardinality),
+        )
         .route(
             "/api/observe/sessions/{session_id}/timeline",
             get(session_timeline),
@@ -345,6 +407,7 @@ fn resolve_range(
     })
 }
 
+#[allow(clippy::too_many_lines)]
 async fn search_logs_inner(
     state: &AppState,
     auth: &AuthUser,
@@ -436,7 +499,7 @@ async fn search_logs_inner(
     .await
     .map_err(|_| ApiError::BadRequest("query timed out".into()))??;
 
-    let items = rows
+    let mut items: Vec<LogEntryResponse> = rows
         .into_iter()
         .map(|r| LogEntryResponse {
             id: r.get("id"),
@@ -450,12 +513,128 @@ async fn search_logs_inner(
             source: r.get("source"),
             message: r.get("message"),
             attributes: r.get("attributes"),
+            archived: false,
         })
         .collect();
 
+    // Older entries have been rotated to Parquet. Rotation archives oldest
+    // first, so every archived row is older than every row still in Postgres
+    // and the archive simply continues the newest-first listing.
+    let hot_boundary =
+        Utc::now() - chrono::Duration::days(i64::from(state.config.observe_log_retention_days));
+    let mut total = total;
+    if let Some(from) = from
+        && from < hot_boundary
+    {
+        let filter = ArchivedLogFilter {
+            params: &params,
+            needle: params.q.as_deref().map(str::to_lowercase),
+            from,
+            to: params.to.unwrap_or_else(Utc::now),
+        };
+        let archived = timeout(ARCHIVE_QUERY_TIMEOUT, search_archived_logs(state, &filter))
+            .await
+            .map_err(|_| ApiError::BadRequest("archive query timed out".into()))??;
+
+        let archived_total = i64::try_from(archived.len()).unwrap_or(i64::MAX);
+        let skip = usize::try_from((offset - total).max(0)).unwrap_or(usize::MAX);
+        let room = usize::try_from(limit)
+            .unwrap_or(0)
+            .saturating_sub(items.len());
+        items.extend(
+            archived
+                .into_iter()
+                .skip(skip)
+                .take(room)
+                .map(archived_log_entry),
+        );
+        total += archived_total;
+    }
+
     Ok(Json(ListResponse { items, total }))
 }
 
+/// Log search filters applied in memory to Parquet-archived entries; mirrors
+/// the SQL predicates in [`search_logs_inner`].
+struct ArchivedLogFilter<'a> {
+    params: &'a LogSearchParams,
+    /// Lowercased `q` for case-insensitive substring matching (like `ILIKE`).
+    needle: Option<String>,
+    from: DateTime<Utc>,
+    to: DateTime<Utc>,
+}
+
+impl ArchivedLogFilter<'_> {
+    fn matches(&self, row: &super::parquet::LogQueryRow) -> bool {
+        let p = self.params;
+        row.timestamp >= self.from
+            && row.timestamp <= self.to
+            && p.project_id.is_none_or(|id| row.project_id == Some(id))
+            && p.session_id.is_none_or(|id| row.session_id == Some(id))
+            && p.trace_id
+                .as_deref()
+                .is_none_or(|t| row.trace_id.as_deref() == Some(t))
+            && p.level.as_deref().is_none_or(|l| row.level == l)
+            && p.service.as_deref().is_none_or(|s| row.service == s)
+            && p.source.as_deref().is_none_or(|s| row.source == s)
+            && p.task_name.as_deref().is_none_or(|t| {
+                row.attributes
+                    .as_ref()
+                    .and_then(|a| a.get("task_name"))
+                    .and_then(serde_json::Value::as_str)
+                    == Some(t)
+            })
+            && self
+                .needle
+                .as_deref()
+                .is_none_or(|n| row.message.to_lowercase().contains(n))
+    }
+}
+
+/// Matching archived log entries within the filter's time range, newest first.
+/// Reads day by day from the newest day backwards and stops once
+/// `MAX_ARCHIVE_MATCHES` entries have been collected.
+async fn search_archived_logs(
+    state: &AppState,
+    filter: &ArchivedLogFilter<'_>,
+) -> Result<Vec<super::parquet::LogQueryRow>, ApiError> {
+    let last_day = filter.to.date_naive();
+    let first_day = filter
+        .from
+        .date_naive()
+        .max(last_day - chrono::Duration::days(MAX_ARCHIVE_DAYS - 1));
+
+    let mut matched = Vec::new();
+    let mut day = last_day;
+    while day >= first_day && matched.len() < MAX_ARCHIVE_MATCHES {
+        let rows = super::parquet::read_archived_logs(state, day).await?;
+        matched.extend(rows.into_iter().filter(|r| filter.matches(r)));
+        let Some(prev) = day.pred_opt() else { break };
+        day = prev;
+    }
+
+    matched.sort_by_key(|m| std::cmp::Reverse(m.timestamp));
+    matched.truncate(MAX_ARCHIVE_MATCHES);
+    Ok(matched)
+}
+
+fn archived_log_entry(row: super::parquet::LogQueryRow) -> LogEntryResponse {
+    LogEntryResponse {
+        id: row.id,
+        timestamp: row.timestamp,
+        trace_id: row.trace_id,
+        span_id: row.span_id,
+        project_id: row.project_id,
+        session_id: row.session_id,
+        service: row.service,
+        level: row.level,
+        source: row.source,
+        message: row.message,
+        attributes: row.attributes,
+        archived: true,
+    }
+}
+
 // ---------------------------------------------------------------------------
 // Trace list / detail
 // ---------------------------------------------------------------------------
@@ -470,6 +649,14 @@ async fn list_traces(
 
     let limit = params.limit.unwrap_or(50).min(100);
     let offset = params.offset.unwrap_or(0);
+    if let Some(min) = params.min_duration_ms
+        && min < 0
+    {
+        return Err(ApiError::BadRequest(
+            "min_duration_ms must be non-negative".into(),
+        ));
+    }
+    let attr_filter = params.attr.as_deref().map(parse_attr_filter).transpose()?;
 
     let total: i64 = timeout(
         QUERY_TIMEOUT,
@@ -483,6 +670,11 @@ async fn list_traces(
           AND ($4::text IS NULL OR status = $4)
           AND ($5::timestamptz IS NULL OR started_at >= $5)
           AND ($6::timestamptz IS NULL OR started_at <= $6)
+          AND ($7::bigint IS NULL OR duration_ms >= $7)
+          AND ($8::jsonb[] IS NULL OR EXISTS (
+                SELECT 1 FROM spans s
+                WHERE s.trace_id = traces.trace_id AND s.attributes @> ANY($8)
+              ))
         ",
         )
         .bind(params.project_id)
@@ -491,6 +683,8 @@ async fn list_traces(
         .bind(params.status.as_deref())
         .bind(params.from)
         .bind(params.to)
+        .bind(params.min_duration_ms)
+        .bind(attr_filter.as_deref())
         .fetch_one(&state.pool),
     )
     .await
@@ -508,8 +702,13 @@ async fn list_traces(
           AND ($4::text IS NULL OR status = $4)
           AND ($5::timestamptz IS NULL OR started_at >= $5)
           AND ($6::timestamptz IS NULL OR started_at <= $6)
+          AND ($7::bigint IS NULL OR duration_ms >= $7)
+          AND ($8::jsonb[] IS NULL OR EXISTS (
+                SELECT 1 FROM spans s
+                WHERE s.trace_id = traces.trace_id AND s.attributes @> ANY($8)
+              ))
         ORDER BY started_at DESC
-        LIMIT $7 OFFSET $8
+        LIMIT $9 OFFSET $10
         ",
         )
         .bind(params.project_id)
@@ -518,6 +717,8 @@ async fn list_traces(
         .bind(params.status.as_deref())
         .bind(params.from)
         .bind(params.to)
+        .bind(params.min_duration_ms)
+        .bind(attr_filter.as_deref())
         .bind(limit)
         .bind(offset)
         .fetch_all(&state.pool),
@@ -541,6 +742,31 @@ async fn list_traces(
     Ok(Json(ListResponse { items, total }))
 }
 
+/// Parse a `key:value` / `key=value` span attribute filter into JSONB
+/// containment candidates. Attributes keep their OTLP type on ingest, so a
+/// numeric or boolean value also matches the typed form (`500` matches both
+/// `"500"` and `500`).
+fn parse_attr_filter(raw: &str) -> Result<Vec<serde_json::Value>, ApiError> {
+    validation::check_length("attr", raw, 3, 500)?;
+    let (key, value) = raw
+        .split_once([':', '='])
+        .filter(|(k, _)| !k.trim().is_empty())
+        .ok_or_else(|| ApiError::BadRequest("attr must be key:value".into()))?;
+    let key = key.trim();
+
+    let mut candidates = vec![serde_json::json!({ key: value })];
+    if let Ok(i) = value.parse::<i64>() {
+        candidates.push(serde_json::json!({ key: i }));
+    } else if let Ok(f) = value.parse::<f64>()
+        && f.is_finite()
+    {
+        candidates.push(serde_json::json!({ key: f }));
+    } else if let Ok(b) = value.parse::<bool>() {
+        candidates.push(serde_json::json!({ key: b }));
+    }
+    Ok(candidates)
+}
+
 #[tracing::instrument(skip(state), err)]
 async fn get_trace(
     State(state): State<AppState>,
@@ -611,6 +837,71 @@ async fn get_trace(
     }))
 }
 
+/// All logs emitted within a trace, oldest first. Each entry carries its
+/// `span_id` so the UI can nest logs under the span that produced them.
+#[tracing::instrument(skip(state), err)]
+async fn get_trace_logs(
+    State(state): State<AppState>,
+    auth: AuthUser,
+    Path(trace_id): Path<String>,
+) -> Result<Json<ListResponse<LogEntryResponse>>, ApiError> {
+    let trace_project_id = timeout(
+        QUERY_TIMEOUT,
+        sqlx::query_scalar!(
+            "SELECT project_id FROM traces WHERE trace_id = $1",
+            trace_id
+        )
+        .fetch_optional(&state.pool),
+    )
+    .await
+    .map_err(|_| ApiError::BadRequest("query timed out".into()))??
+    .ok_or_else(|| ApiError::NotFound("trace".into()))?;
+
+    require_observe_read(&state, &auth, trace_project_id).await?;
+
+    // Only logs from the trace's own project: trace IDs are client-supplied,
+    // so another project could emit logs under a colliding ID.
+    let rows = timeout(
+        QUERY_TIMEOUT,
+        sqlx::query!(
+            r"
+        SELECT id, timestamp, trace_id, span_id, project_id, session_id,
+               service, level, source, message, attributes
+        FROM log_entries
+        WHERE trace_id = $1 AND project_id IS NOT DISTINCT FROM $2
+        ORDER BY timestamp ASC
+        LIMIT 10000
+        ",
+            trace_id,
+            trace_project_id,
+        )
+        .fetch_all(&state.pool),
+    )
+    .await
+    .map_err(|_| ApiError::BadRequest("query timed out".into()))??;
+
+    let items: Vec<LogEntryResponse> = rows
+        .into_iter()
+        .map(|r| LogEntryResponse {
+            id: r.id,
+            timestamp: r.timestamp,
+            trace_id: r.trace_id,
+            span_id: r.span_id,
+            project_id: r.project_id,
+            session_id: r.session_id,
+            service: r.service,
+            level: r.level,
+            source: r.source,
+            message: r.message,
+            attributes: r.attributes,
+            archived: false,
+        })
+        .collect();
+
+    let total = i64::try_from(items.len()).unwrap_or(i64::MAX);
+    Ok(Json(ListResponse { items, total }))
+}
+
 // ---------------------------------------------------------------------------
 // Metric query
 // ---------------------------------------------------------------------------
@@ -636,9 +927,43 @@ async fn query_metrics(
         .transpose()
         .map_err(|_| ApiError::BadRequest("invalid labels JSON".into()))?;
 
+    let agg = params.agg.as_deref().unwrap_or("avg");
+    if !METRIC_AGGS.contains(&agg) {
+        return Err(ApiError::BadRequest(format!(
+            "agg must be one of: {}",
+            METRIC_AGGS.join(", ")
+        )));
+    }
+
+    if let Some(step) = params.step
+        && !(1..=MAX_METRIC_STEP).contains(&step)
+    {
+        return Err(ApiError::BadRequest(format!(
+            "step must be between 1 and {MAX_METRIC_STEP} seconds"
+        )));
+    }
+
     let limit = params.limit.unwrap_or(1000).min(10_000);
 
     let from = resolve_range(params.from, params.range.as_deref());
+    let to = params.to.unwrap_or_else(Utc::now);
+
+    let source = super::rollup::pick_resolution(from, to, params.step);
+    if source.is_some() || params.step.is_some() {
+        let selector = MetricSelector {
+            name,
+            labels: labels_filter.as_ref(),
+            project_id: params.project_id,
+            from,
+            to: params.to,
+        };
+        let step = params
+            .step
+            .or(source.map(super::rollup::Resolution::seconds))
+            .unwrap_or(60);
+        let points = query_metric_buckets(&state.pool, &selector, source, step, agg, limit).await?;
+        return Ok(Json(group_metric_series(name, points)));
+    }
 
     let rows = timeout(
         QUERY_TIMEOUT,
@@ -667,22 +992,172 @@ async fn query_metrics(
     .await
     .map_err(|_| ApiError::BadRequest("query timed out".into()))??;
 
-    // Group by series_id
+    let points = rows
+        .iter()
+        .map(|r| {
+            (
+                r.get("series_id"),
+                r.get("labels"),
+                MetricDataPoint {
+                    timestamp: r.get("timestamp"),
+                    value: r.get("value"),
+                },
+            )
+        })
+        .collect();
+
+    Ok(Json(group_metric_series(name, points)))
+}
+
+/// Aggregations supported for metric queries.
+const METRIC_AGGS: &[&str] = &["avg", "sum", "max", "min", "count"];
+
+/// Largest accepted `step` (one day).
+const MAX_METRIC_STEP: i64 = 86_400;
+
+/// A data point tagged with its series id and labels, before grouping.
+type SeriesPoint = (Uuid, serde_json::Value, MetricDataPoint);
+
+/// Which series and time window a metric query covers.
+struct MetricSelector<'a> {
+    name: &'a str,
+    labels: Option<&'a serde_json::Value>,
+    project_id: Option<Uuid>,
+    from: Option<DateTime<Utc>>,
+    to: Option<DateTime<Utc>>,
+}
+
+/// Aggregate a metric query into `step`-second buckets aligned to the epoch.
+///
+/// With a rollup `source`, buckets before the rollup watermark are built from
+/// the rollup table and newer ones from raw samples, so the result is complete
+/// up to `to`. Without one, everything is aggregated from raw samples.
+async fn query_metric_buckets(
+    pool: &sqlx::PgPool,
+    sel: &MetricSelector<'_>,
+    source: Option<super::rollup::Resolution>,
+    step: i64,
+    agg: &str,
+    limit: i64,
+) -> Result<Vec<SeriesPoint>, ApiError> {
+    // `parts` yields partial aggregates (count/sum/min/max) that the outer
+    // query merges per step bucket. Only the rollup table matching `$7` is
+    // read; without a source the watermark is `-infinity` and everything
+    // comes from raw samples. A rollup bucket is only used when it lies
+    // entirely inside the requested range.
+    let rows = timeout(
+        QUERY_TIMEOUT,
+        sqlx::query!(
+            r#"
+            WITH series AS (
+                SELECT id, labels FROM metric_series
+                WHERE name = $1
+                  AND ($2::jsonb IS NULL OR labels @> $2)
+                  AND ($3::uuid IS NULL OR project_id = $3)
+            ),
+            wm AS (
+                SELECT COALESCE(
+                    (SELECT rolled_up_to FROM metric_rollup_state WHERE resolution = $7),
+                    '-infinity'::timestamptz
+                ) AS t
+            ),
+            parts (series_id, ts, count, sum, min, max) AS (
+                SELECT r.series_id, r.bucket, r.count, r.sum, r.min, r.max
+                FROM metric_rollups_1m r
+                JOIN series s ON s.id = r.series_id
+                CROSS JOIN wm
+                WHERE $7 = '1m'
+                  AND r.bucket < wm.t
+                  AND ($4::timestamptz IS NULL OR r.bucket >= $4)
+                  AND ($5::timestamptz IS NULL OR r.bucket + ('1 ' || $9::text)::interval <= $5)
+                UNION ALL
+                SELECT r.series_id, r.bucket, r.count, r.sum, r.min, r.max
+                FROM metric_rollups_1h r
+                JOIN series s ON s.id = r.series_id
+                CROSS JOIN wm
+                WHERE $7 = '1h'
+                  AND r.bucket < wm.t
+                  AND ($4::timestamptz IS NULL OR r.bucket >= $4)
+                  AND ($5::timestamptz IS NULL OR r.bucket + ('1 ' || $9::text)::interval <= $5)
+                UNION ALL
+                SELECT ms.series_id, ms.timestamp, 1, ms.value, ms.value, ms.value
+                FROM metric_samples ms
+                JOIN series s ON s.id = ms.series_id
+                CROSS JOIN wm
+                WHERE ms.timestamp >= wm.t
+                  AND ($4::timestamptz IS NULL OR ms.timestamp >= $4)
+                  AND ($5::timestamptz IS NULL OR ms.timestamp <= $5)
+            )
+            SELECT p.series_id AS "series_id!",
+                   s.labels AS "labels!",
+                   date_bin($8::bigint * interval '1 second', p.ts, timestamptz 'epoch') AS "bucket!",
+                   sum(p.count)::bigint AS "count!",
+                   sum(p.sum) AS "sum!",
+                   min(p.min) AS "min!",
+                   max(p.max) AS "max!"
+            FROM parts p
+            JOIN series s ON s.id = p.series_id
+            GROUP BY p.series_id, s.labels, 3
+            ORDER BY 1, 3 ASC
+            LIMIT $6
+            "#,
+            sel.name,
+            sel.labels,
+            sel.project_id,
+            sel.from,
+            sel.to,
+            limit,
+            source.map(super::rollup::Resolution::key),
+            step,
+            source.map(super::rollup::Resolution::trunc_unit),
+        )
+        .fetch_all(pool),
+    )
+    .await
+    .map_err(|_| ApiError::BadRequest("query timed out".into()))??;
+
+    Ok(rows
+        .into_iter()
+        .map(|r| {
+            let value = rollup_value(agg, r.count, r.sum, r.min, r.max);
+            (
+                r.series_id,
+                r.labels,
+                MetricDataPoint {
+                    timestamp: r.bucket,
+                    value,
+                },
+            )
+        })
+        .collect())
+}
+
+/// Value of an aggregated bucket under the requested aggregation.
+#[allow(clippy::cast_precision_loss)]
+fn rollup_value(agg: &str, count: i64, sum: f64, min: f64, max: f64) -> f64 {
+    match agg {
+        "sum" => sum,
+        "min" => min,
+        "max" => max,
+        "count" => count as f64,
+        _ if count > 0 => sum / count as f64,
+        _ => 0.0,
+    }
+}
+
+/// Group points (ordered by series, then time) into one `MetricSeries` per series.
+fn group_metric_series(name: &str, points: Vec<SeriesPoint>) -> Vec<MetricSeries> {
     let mut series_map: std::collections::HashMap<Uuid, (serde_json::Value, Vec<MetricDataPoint>)> =
         std::collections::HashMap::new();
-    for r in &rows {
-        let series_id: Uuid = r.get("series_id");
-        let entry = series_map.entry(series_id).or_insert_with(|| {
-            let labels: serde_json::Value = r.get("labels");
-            (labels, Vec::new())
-        });
-        entry.1.push(MetricDataPoint {
-            timestamp: r.get("timestamp"),
-            value: r.get("value"),
-        });
+    for (series_id, labels, point) in points {
+        series_map
+            .entry(series_id)
+            .or_insert_with(|| (labels, Vec::new()))
+            .1
+            .push(point);
     }
 
-    let items: Vec<MetricSeries> = series_map
+    series_map
         .into_values()
         .map(|(labels_json, points)| {
             let labels = match labels_json {
@@ -698,9 +1173,45 @@ async fn query_metrics(
                 points,
             }
         })
-        .collect();
+        .collect()
+}
 
-    Ok(Json(items))
+/// Admin: metric names with the most series, to spot cardinality explosions.
+#[tracing::instrument(skip(state), err)]
+async fn get_metric_cardinality(
+    State(state): State<AppState>,
+    auth: AuthUser,
+    Query(params): Query<CardinalityParams>,
+) -> Result<Json<MetricCardinalityResponse>, ApiError> {
+    crate::api::helpers::require_admin(&state, &auth).await?;
+
+    let limit = params.limit.unwrap_or(20).clamp(1, 100);
+
+    let items = timeout(
+        QUERY_TIMEOUT,
+        sqlx::query_as!(
+            MetricCardinality,
+            r#"
+        SELECT name, project_id, COUNT(*) AS "series_count!"
+        FROM metric_series
+        WHERE ($1::uuid IS NULL OR project_id = $1)
+        GROUP BY name, project_id
+        ORDER BY 3 DESC, name ASC
+        LIMIT $2
+        "#,
+            params.project_id,
+            limit,
+        )
+        .fetch_all(&state.pool),
+    )
+    .await
+    .map_err(|_| ApiError::BadRequest("query timed out".into()))??;
+
+    Ok(Json(MetricCardinalityResponse {
+        items,
+        metrics_dropped: super::ingest::metrics_dropped(),
+        max_series_per_project: state.config.observe_max_series_per_project,
+    }))
 }
 
 #[tracing::instrument(skip(state), err)]
@@ -838,27 +1349,156 @@ async fn live_tail_sse(
         .subscribe(&channel)
         .await
         .map_err(|e| ApiError::Internal(e.into()))?;
-    let mut msg_rx = subscriber.message_rx();
-
-    let (tx, rx) = tokio::sync::mpsc::channel::<String>(256);
+    let messages = futures_util::stream::unfold(subscriber.message_rx(), |mut rx| async move {
+        while let Ok(msg) = rx.recv().await {
+            if let Ok(text) = msg.value.convert::<String>() {
+                return Some((text, rx));
+            }
+        }
+        None
+    });
 
+    let (items, pump) = live_tail(messages, params);
     tokio::spawn(async move {
-        while let Ok(msg) = msg_rx.recv().await {
-            let text: String = match msg.value.convert() {
-                Ok(s) => s,
-                Err(_) => continue,
-            };
-            if should_forward(&text, &params) && tx.send(text).await.is_err() {
-                break;
+        let _ = pump.await;
+        let _ = subscriber.unsubscribe(&channel).await;
+    });
+    let stream = items.map(|item| Ok::<_, std::convert::Infallible>(item.into_event()));
+
+    // Keep-alive comments let quiet streams stay open without forwarding
+    // anything; a client that went away drops the stream, which stops the pump.
+    Ok(Sse::new(stream).keep_alive(KeepAlive::default()))
+}
+
+/// What a live tail stream sends the client.
+#[derive(Debug, PartialEq, Eq)]
+enum TailItem {
+    /// A log message published for the project.
+    Log(String),
+    /// How many messages were shed since the last report, by the rate limit
+    /// or because the client fell behind.
+    Dropped(u64),
+}
+
+impl TailItem {
+    fn into_event(self) -> Event {
+        match self {
+            Self::Log(text) => Event::default().event("log").data(text),
+            Self::Dropped(dropped) => Event::default()
+                .event("dropped")
+                .data(serde_json::json!({ "dropped": dropped }).to_string()),
+        }
+    }
+}
+
+/// Forward the messages matching `params` to a client stream, at most
+/// `LIVE_TAIL_MAX_PER_SEC` per second and through a bounded, drop-oldest
+/// buffer: a lagging client skips ahead and learns how many messages it
+/// missed. Returns the client stream and the pump task, which ends when
+/// `messages` ends or the client stream is dropped.
+fn live_tail<S>(
+    messages: S,
+    params: LiveTailParams,
+) -> (
+    impl futures_util::Stream<Item = TailItem>,
+    tokio::task::JoinHandle<()>,
+)
+where
+    S: futures_util::Stream<Item = String> + Send + 'static,
+{
+    let (tx, rx) = broadcast::channel::<String>(LIVE_TAIL_BUFFER);
+    let shed = Arc::new(AtomicU64::new(0));
+
+    let pump_shed = shed.clone();
+    let pump = tokio::spawn(async move {
+        let mut messages = std::pin::pin!(messages);
+        let mut limiter = RateLimiter::new(LIVE_TAIL_MAX_PER_SEC, Instant::now());
+        let mut check = tokio::time::interval(Duration::from_secs(1));
+        loop {
+            tokio::select! {
+                text = messages.next() => {
+                    let Some(text) = text else { break };
+                    if !should_forward(&text, &params) {
+                        continue;
+                    }
+                    if !limiter.allow(Instant::now()) {
+                        pump_shed.fetch_add(1, Ordering::Relaxed);
+                        continue;
+                    }
+                    // Err means the client stream is gone
+                    if tx.send(text).is_err() {
+                        break;
+                    }
+                }
+                _ = check.tick() => {
+                    if tx.receiver_count() == 0 {
+                        break;
+                    }
+                }
             }
         }
-        let _ = subscriber.unsubscribe(&channel).await;
     });
 
-    let stream = tokio_stream::wrappers::ReceiverStream::new(rx)
-        .map(|text| Ok::<_, std::convert::Infallible>(Event::default().event("log").data(text)));
+    let mut report = tokio::time::interval(LIVE_TAIL_DROP_REPORT);
+    report.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+    let items = futures_util::stream::unfold(
+        (rx, shed, report),
+        |(mut rx, shed, mut report)| async move {
+            loop {
+                tokio::select! {
+                    msg = rx.recv() => match msg {
+                        Ok(text) => return Some((TailItem::Log(text), (rx, shed, report))),
+                        Err(RecvError::Lagged(n)) => {
+                            shed.fetch_add(n, Ordering::Relaxed);
+                        }
+                        // Report what was shed before the stream ends
+                        Err(RecvError::Closed) => {
+                            let dropped = shed.swap(0, Ordering::Relaxed);
+                            return (dropped > 0)
+                                .then_some((TailItem::Dropped(dropped), (rx, shed, report)));
+                        }
+                    },
+                    _ = report.tick() => {
+                        let dropped = shed.swap(0, Ordering::Relaxed);
+                        if dropped > 0 {
+                            return Some((TailItem::Dropped(dropped), (rx, shed, report)));
+                        }
+                    }
+                }
+            }
+        },
+    );
+    (items, pump)
+}
 
-    Ok(Sse::new(stream).keep_alive(KeepAlive::default()))
+/// Fixed one-second window limiter for live tail messages.
+struct RateLimiter {
+    max_per_sec: u32,
+    window_start: Instant,
+    count: u32,
+}
+
+impl RateLimiter {
+    fn new(max_per_sec: u32, now: Instant) -> Self {
+        Self {
+            max_per_sec,
+            window_start: now,
+            count: 0,
+        }
+    }
+
+    fn allow(&mut self, now: Instant) -> bool {
+        if now.duration_since(self.window_start) >= Duration::from_secs(1) {
+            self.window_start = now;
+            self.count = 0;
+        }
+        if self.count < self.max_per_sec {
+            self.count += 1;
+            true
+        } else {
+            false
+        }
+    }
 }
 
 /// Check if a live tail message matches optional level/service/source filters.
@@ -1417,6 +2057,82 @@ mod tests {
         }
     }
 
+    #[test]
+    fn rate_limiter_caps_each_window() {
+        let start = Instant::now();
+        let mut limiter = RateLimiter::new(3, start);
+        let allowed = (0..10).filter(|_| limiter.allow(start)).count();
+        assert_eq!(allowed, 3);
+
+        let later = start + Duration::from_millis(999);
+        assert!(!limiter.allow(later), "same window");
+
+        let next = start + Duration::from_secs(1);
+        assert!(limiter.allow(next), "new window resets the count");
+    }
+
+    /// Matches the filters of `params(None, None)`.
+    fn tail_message(i: usize) -> String {
+        serde_json::json!({"level": "info", "message": i.to_string()}).to_string()
+    }
+
+    #[tokio::test]
+    async fn live_tail_burst_is_bounded_and_reported() {
+        let burst: Vec<String> = (0..10_000).map(tail_message).collect();
+        let (items, pump) = live_tail(futures_util::stream::iter(burst), params(None, None));
+        let items: Vec<TailItem> = timeout(Duration::from_secs(5), items.collect())
+            .await
+            .expect("stream ends with its source");
+        pump.await.unwrap();
+
+        let logs = items
+            .iter()
+            .filter(|i| matches!(i, TailItem::Log(_)))
+            .count();
+        let dropped: u64 = items
+            .iter()
+            .map(|i| match i {
+                TailItem::Dropped(n) => *n,
+                TailItem::Log(_) => 0,
+            })
+            .sum();
+        assert!(
+            logs <= LIVE_TAIL_BUFFER,
+            "forwarded {logs}, more than the buffer holds"
+        );
+        assert_eq!(
+            u64::try_from(logs).unwrap() + dropped,
+            10_000,
+            "every message is forwarded or reported dropped"
+        );
+        assert_eq!(items.first(), Some(&TailItem::Log(tail_message(0))));
+    }
+
+    #[tokio::test]
+    async fn live_tail_stays_open_while_quiet_and_stops_on_disconnect() {
+        let (tx, rx) = tokio::sync::mpsc::channel::<String>(1);
+        let (items, pump) = live_tail(
+            tokio_stream::wrappers::ReceiverStream::new(rx),
+            params(None, None),
+        );
+        let mut items = Box::pin(items);
+
+        tokio::time::sleep(Duration::from_millis(1500)).await;
+        assert!(
+            !pump.is_finished(),
+            "a quiet subscriber is not disconnected"
+        );
+        tx.send(tail_message(1)).await.unwrap();
+        assert_eq!(items.next().await, Some(TailItem::Log(tail_message(1))));
+
+        drop(items);
+        timeout(Duration::from_secs(3), pump)
+            .await
+            .expect("pump stops once the client is gone")
+            .unwrap();
+        drop(tx);
+    }
+
     #[test]
     fn should_forward_no_filters() {
         let p = params(None, None);
@@ -1497,6 +2213,107 @@ mod tests {
 
     // -- resolve_range --
 
+    fn archived_row(
+        message: &str,
+        attributes: Option<serde_json::Value>,
+    ) -> super::super::parquet::LogQueryRow {
+        super::super::parquet::LogQueryRow {
+            id: Uuid::nil(),
+            timestamp: "2026-10-01T12:00:00Z".parse().unwrap(),
+            trace_id: Some("t1".into()),
+            span_id: None,
+            project_id: None,
+            session_id: None,
+            service: "api".into(),
+            level: "error".into(),
+            source: "external".into(),
+            message: message.into(),
+            attributes,
+        }
+    }
+
+    fn archive_filter(params: &LogSearchParams) -> ArchivedLogFilter<'_> {
+        ArchivedLogFilter {
+            params,
+            needle: params.q.as_deref().map(str::to_lowercase),
+            from: "2026-09-30T00:00:00Z".parse().unwrap(),
+            to: "2026-10-02T00:00:00Z".parse().unwrap(),
+        }
+    }
+
+    #[test]
+    fn archived_filter_matches_like_sql() {
+        let params: LogSearchParams = serde_json::from_value(serde_json::json!({
+            "service": "api",
+            "level": "error",
+            "trace_id": "t1",
+            "q": "TIMEOUT",
+            "task_name": "sync",
+        }))
+        .unwrap();
+        let filter = archive_filter(&params);
+        let row = archived_row(
+            "upstream timeout after 5s",
+            Some(serde_json::json!({"task_name": "sync"})),
+        );
+        assert!(filter.matches(&row));
+        assert!(!filter.matches(&archived_row("upstream timeout after 5s", None)));
+        assert!(!filter.matches(&archived_row(
+            "all good",
+            Some(serde_json::json!({"task_name": "sync"}))
+        )));
+    }
+
+    #[test]
+    fn archived_filter_respects_time_range_and_project() {
+        let params: LogSearchParams = serde_json::from_value(serde_json::json!({
+            "project_id": "00000000-0000-0000-0000-000000000001",
+        }))
+        .unwrap();
+        let filter = archive_filter(&params);
+        let mut row = archived_row("x", None);
+        assert!(!filter.matches(&row), "project mismatch");
+        row.project_id = params.project_id;
+        assert!(filter.matches(&row));
+        row.timestamp = "2026-10-03T00:00:00Z".parse().unwrap();
+        assert!(!filter.matches(&row), "outside range");
+    }
+
+    #[test]
+    fn parse_attr_filter_string_value() {
+        let c = parse_attr_filter("http.method:POST").unwrap();
+        assert_eq!(c, vec![serde_json::json!({"http.method": "POST"})]);
+    }
+
+    #[test]
+    fn parse_attr_filter_typed_values() {
+        let c = parse_attr_filter("http.status_code=500").unwrap();
+        assert_eq!(
+            c,
+            vec![
+                serde_json::json!({"http.status_code": "500"}),
+                serde_json::json!({"http.status_code": 500}),
+            ]
+        );
+        let c = parse_attr_filter("cache.hit:true").unwrap();
+        assert_eq!(c[1], serde_json::json!({"cache.hit": true}));
+        let c = parse_attr_filter("ratio:0.5").unwrap();
+        assert_eq!(c[1], serde_json::json!({"ratio": 0.5}));
+    }
+
+    #[test]
+    fn parse_attr_filter_value_keeps_separators() {
+        let c = parse_attr_filter("http.url:http://x/?a=b").unwrap();
+        assert_eq!(c, vec![serde_json::json!({"http.url": "http://x/?a=b"})]);
+    }
+
+    #[test]
+    fn parse_attr_filter_rejects_missing_key_or_separator() {
+        assert!(parse_attr_filter("no-separator").is_err());
+        assert!(parse_attr_filter(":value").is_err());
+        assert!(parse_attr_filter("").is_err());
+    }
+
     #[test]
     fn resolve_range_explicit_from_takes_precedence() {
         let explicit = Utc::now();
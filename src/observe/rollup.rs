@@ -0,0 +1,251 @@
+// Copyright (c) 2026 Steven Hooker. Exclusively licensed to and distributed by AgentSphere GmbH.
+// SPDX-License-Identifier: BUSL-1.1
+
+//! Background task that downsamples `metric_samples` into per-series
+//! 1-minute and 1-hour rollups (`metric_rollups_1m` / `metric_rollups_1h`).
+//!
+//! Each resolution keeps a watermark in `metric_rollup_state`: every bucket
+//! before it has been aggregated. A tick recomputes from a little before the
+//! watermark (to pick up late samples) up to the last complete bucket, at most
+//! `max_chunk` at a time so a fresh install backfills history gradually.
+
+use chrono::{DateTime, Duration, Utc};
+use sqlx::PgPool;
+
+/// Rollup resolutions, finest first.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Resolution {
+    Minute,
+    Hour,
+}
+
+impl Resolution {
+    pub const ALL: [Self; 2] = [Self::Minute, Self::Hour];
+
+    /// Key in `metric_rollup_state`.
+    pub fn key(self) -> &'static str {
+        match self {
+            Self::Minute => "1m",
+            Self::Hour => "1h",
+        }
+    }
+
+    /// `date_trunc` unit matching the bucket width.
+    pub fn trunc_unit(self) -> &'static str {
+        match self {
+            Self::Minute => "minute",
+            Self::Hour => "hour",
+        }
+    }
+
+    pub fn seconds(self) -> i64 {
+        match self {
+            Self::Minute => 60,
+            Self::Hour => 3600,
+        }
+    }
+
+    /// How far before the watermark to recompute, to absorb late samples.
+    fn grace(self) -> Duration {
+        match self {
+            Self::Minute => Duration::minutes(5),
+            Self::Hour => Duration::hours(1),
+        }
+    }
+
+    /// Max range of raw samples aggregated per tick.
+    fn max_chunk(self) -> Duration {
+        match self {
+            Self::Minute => Duration::hours(6),
+            Self::Hour => Duration::days(2),
+        }
+    }
+}
+
+/// Pick the coarsest rollup that still gives at least the requested detail.
+///
+/// With an explicit `step` (seconds) the bucket width must divide it, so each
+/// step bucket is made of whole rollup buckets.
+/// Without one, long windows use rollups and short ones stay on raw samples.
+/// `None` means query raw samples.
+pub fn pick_resolution(
+    from: Option<DateTime<Utc>>,
+    to: DateTime<Utc>,
+    step: Option<i64>,
+) -> Option<Resolution> {
+    if let Some(step) = step {
+        return Resolution::ALL
+            .into_iter()
+            .rev()
+            .find(|r| r.seconds() <= step && step % r.seconds() == 0);
+    }
+    let span = to - from?;
+    if span > Duration::days(2) {
+        Some(Resolution::Hour)
+    } else if span > Duration::hours(6) {
+        Some(Resolution::Minute)
+    } else {
+        None
+    }
+}
+
+/// Start of the bucket containing `ts`.
+fn floor_to(ts: DateTime<Utc>, res: Resolution) -> DateTime<Utc> {
+    let secs = res.seconds();
+    DateTime::from_timestamp(ts.timestamp().div_euclid(secs) * secs, 0).unwrap_or(ts)
+}
+
+/// Run the rollup loop until shutdown.
+pub async fn run(pool: PgPool, retention_days: u32, cancel: tokio_util::sync::CancellationToken) {
+    let mut interval = tokio::time::interval(std::time::Duration::from_mins(1));
+    loop {
+        tokio::select! {
+            _ = interval.tick() => {
+                for res in Resolution::ALL {
+                    if let Err(e) = rollup_once(&pool, res, retention_days).await {
+                        tracing::warn!(error = %e, resolution = res.key(), "metric rollup failed");
+                    }
+                }
+            }
+            () = cancel.cancelled() => break,
+        }
+    }
+}
+
+/// Aggregate the next chunk of complete buckets for one resolution.
+/// Returns `false` once the rollup is caught up with the last complete bucket.
+pub async fn rollup_once(
+    pool: &PgPool,
+    res: Resolution,
+    retention_days: u32,
+) -> Result<bool, sqlx::Error> {
+    let now = Utc::now();
+    let complete_until = floor_to(now, res);
+
+    let watermark = sqlx::query_scalar!(
+        "SELECT rolled_up_to FROM metric_rollup_state WHERE resolution = $1",
+        res.key()
+    )
+    .fetch_optional(pool)
+    .await?;
+
+    let (start, end) = match watermark {
+        Some(w) if w >= complete_until => return Ok(false),
+        Some(w) => (w - res.grace(), (w + res.max_chunk()).min(complete_until)),
+        None => {
+            let start = floor_to(now - Duration::days(i64::from(retention_days)), res);
+            (start, (start + res.max_chunk()).min(complete_until))
+        }
+    };
+
+    let mut tx = pool.begin().await?;
+    let result = match res {
+        Resolution::Minute => {
+            sqlx::query!(
+                "INSERT INTO metric_rollups_1m (series_id, bucket, count, sum, min, max) \
+                 SELECT series_id, date_trunc($3, timestamp), count(*), sum(value), min(value), max(value) \
+                 FROM metric_samples WHERE timestamp >= $1 AND timestamp < $2 \
+                 GROUP BY 1, 2 \
+                 ON CONFLICT (series_id, bucket) DO UPDATE SET \
+                     count = EXCLUDED.count, sum = EXCLUDED.sum, min = EXCLUDED.min, max = EXCLUDED.max",
+                start,
+                end,
+                res.trunc_unit(),
+            )
+            .execute(&mut *tx)
+            .await?
+        }
+        Resolution::Hour => {
+            sqlx::query!(
+                "INSERT INTO metric_rollups_1h (series_id, bucket, count, sum, min, max) \
+                 SELECT series_id, date_trunc($3, timestamp), count(*), sum(value), min(value), max(value) \
+                 FROM metric_samples WHERE timestamp >= $1 AND timestamp < $2 \
+                 GROUP BY 1, 2 \
+                 ON CONFLICT (series_id, bucket) DO UPDATE SET \
+                     count = EXCLUDED.count, sum = EXCLUDED.sum, min = EXCLUDED.min, max = EXCLUDED.max",
+                start,
+                end,
+                res.trunc_unit(),
+            )
+            .execute(&mut *tx)
+            .await?
+        }
+    };
+    sqlx::query!(
+        "INSERT INTO metric_rollup_state (resolution, rolled_up_to) VALUES ($1, $2) \
+         ON CONFLICT (resolution) DO UPDATE SET rolled_up_to = EXCLUDED.rolled_up_to",
+        res.key(),
+        end,
+    )
+    .execute(&mut *tx)
+    .await?;
+    tx.commit().await?;
+
+    tracing::debug!(
+        resolution = res.key(),
+        %start,
+        %end,
+        buckets = result.rows_affected(),
+        "metric rollup advanced"
+    );
+    Ok(true)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ts(s: &str) -> DateTime<Utc> {
+        s.parse().unwrap()
+    }
+
+    #[test]
+    fn floor_to_bucket_start() {
+        let t = ts("2026-10-15T12:34:56Z");
+        assert_eq!(floor_to(t, Resolution::Minute), ts("2026-10-15T12:34:00Z"));
+        assert_eq!(floor_to(t, Resolution::Hour), ts("2026-10-15T12:00:00Z"));
+    }
+
+    #[test]
+    fn pick_resolution_from_step() {
+        let to = Utc::now();
+        assert_eq!(pick_resolution(None, to, Some(15)), None);
+        assert_eq!(
+            pick_resolution(None, to, Some(60)),
+            Some(Resolution::Minute)
+        );
+        assert_eq!(
+            pick_resolution(None, to, Some(900)),
+            Some(Resolution::Minute)
+        );
+        assert_eq!(
+            pick_resolution(None, to, Some(7200)),
+            Some(Resolution::Hour)
+        );
+        // Steps that don't align to a rollup bucket fall back to raw samples
+        assert_eq!(pick_resolution(None, to, Some(90)), None);
+        assert_eq!(
+            pick_resolution(None, to, Some(5400)),
+            Some(Resolution::Minute)
+        );
+    }
+
+    #[test]
+    fn pick_resolution_from_range() {
+        let to = Utc::now();
+        assert_eq!(
+            pick_resolution(Some(to - Duration::hours(1)), to, None),
+            None
+        );
+        assert_eq!(
+            pick_resolution(Some(to - Duration::hours(24)), to, None),
+            Some(Resolution::Minute)
+        );
+        assert_eq!(
+            pick_resolution(Some(to - Duration::days(30)), to, None),
+            Some(Resolution::Hour)
+        );
+        // Unbounded queries stay on raw samples
+        assert_eq!(pick_resolution(None, to, None), None);
+    }
+}
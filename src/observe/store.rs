@@ -58,6 +58,14 @@ pub struct MetricRecord {
     pub value: f64,
 }
 
+/// Increment of a cumulative counter series (log-based metrics).
+pub struct CounterIncrement {
+    pub name: String,
+    pub labels: JsonValue,
+    pub project_id: Option<Uuid>,
+    pub delta: f64,
+}
+
 /// Lightweight log message for live tail pub/sub.
 #[derive(Debug, Serialize)]
 pub struct LogTailMessage {
@@ -240,6 +248,52 @@ pub async fn write_logs(pool: &PgPool, logs: &[LogEntryRecord]) -> Result<(), Ob
     Ok(())
 }
 
+/// Add each increment to its counter series' running total and record the new
+/// total as a sample at `timestamp`. Increments must have unique series keys.
+#[tracing::instrument(skip(pool, increments), fields(count = increments.len()), err)]
+pub async fn increment_counters(
+    pool: &PgPool,
+    increments: &[CounterIncrement],
+    timestamp: DateTime<Utc>,
+) -> Result<(), ObserveError> {
+    if increments.is_empty() {
+        return Ok(());
+    }
+
+    let names: Vec<&str> = increments.iter().map(|c| c.name.as_str()).collect();
+    let labels: Vec<&JsonValue> = increments.iter().map(|c| &c.labels).collect();
+    let project_ids: Vec<Option<Uuid>> = increments.iter().map(|c| c.project_id).collect();
+    let deltas: Vec<f64> = increments.iter().map(|c| c.delta).collect();
+
+    sqlx::query!(
+        r"
+        WITH upserted AS (
+            INSERT INTO metric_series (name, labels, metric_type, project_id, last_value)
+            SELECT name, labels, 'counter', project_id, delta
+            FROM UNNEST($1::text[], $2::jsonb[], $3::uuid[], $4::double precision[])
+                 AS t(name, labels, project_id, delta)
+            ON CONFLICT (name, labels, project_id)
+            DO UPDATE SET
+                last_value = COALESCE(metric_series.last_value, 0) + EXCLUDED.last_value,
+                updated_at = now()
+            RETURNING id, last_value
+        )
+        INSERT INTO metric_samples (series_id, timestamp, value)
+        SELECT id, $5, last_value FROM upserted
+        ON CONFLICT (series_id, timestamp) DO UPDATE SET value = EXCLUDED.value
+        ",
+        &names as &[&str],
+        &labels as &[&JsonValue],
+        &project_ids as &[Option<Uuid>],
+        &deltas,
+        timestamp,
+    )
+    .execute(pool)
+    .await?;
+
+    Ok(())
+}
+
 /// Batch upsert metric series and insert samples using UNNEST.
 ///
 /// Uses 2 queries total regardless of batch size (was 2N with sequential approach).
@@ -156,7 +156,7 @@ impl CliAuthManager {
         code: &str,
         claude_cli_path: &str,
         pool: &sqlx::PgPool,
-        master_key: &[u8; 32],
+        master_key: &crate::secrets::engine::MasterKey,
     ) -> Result<(), anyhow::Error> {
         let (user_id, token_rx) = {
             let mut sessions = self.sessions.lock().await;
@@ -263,7 +263,7 @@ impl CliAuthManager {
     #[allow(dead_code)]
     pub async fn evict_stale(&self) {
         let mut sessions = self.sessions.lock().await;
-        let threshold = std::time::Duration::from_secs(300);
+        let threshold = std::time::Duration::from_mins(5);
         let stale: Vec<Uuid> = sessions
             .iter()
             .filter(|(_, s)| s.created_at.elapsed() > threshold)
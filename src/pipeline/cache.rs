@@ -0,0 +1,357 @@
+// Copyright (c) 2026 Steven Hooker. Exclusively licensed to and distributed by AgentSphere GmbH.
+// SPDX-License-Identifier: BUSL-1.1
+
+//! Dependency caches shared between pipeline runs of a project.
+//!
+//! A step's `cache:` names workspace paths and a key. The key may embed
+//! `{{ hashFiles('Cargo.lock', ...) }}`, which expands to a hash of those
+//! files at the pipeline's commit, so the cache changes with the lockfile.
+//! When the exact key misses, the most recently used cache whose key starts
+//! with one of `restore_keys` is restored instead, and the step saves a new
+//! cache under the exact key once it succeeds. Caches belong to the branch
+//! that saved them; a branch without a match restores from the project's
+//! default branch. Archives are streamed to and from object storage, and a
+//! save is abandoned once it exceeds `pipeline_cache_max_bytes`. Least
+//! recently used caches are evicted once the total size exceeds that limit.
+
+use std::path::Path;
+
+use sha2::{Digest, Sha256};
+use sqlx::PgPool;
+use tokio::io::{AsyncRead, AsyncReadExt};
+use uuid::Uuid;
+
+/// Maximum length of a resolved cache key.
+pub const MAX_KEY_LEN: usize = 512;
+
+/// Read size when streaming an archive to object storage.
+const CHUNK_SIZE: usize = 1024 * 1024;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Segment {
+    Literal(String),
+    HashFiles(Vec<String>),
+}
+
+/// Split a key template into literal text and `hashFiles(...)` expressions.
+fn parse_key(key: &str) -> Result<Vec<Segment>, String> {
+    let mut segments = Vec::new();
+    let mut rest = key;
+    while let Some(start) = rest.find("{{") {
+        if start > 0 {
+            segments.push(Segment::Literal(rest[..start].to_owned()));
+        }
+        let Some(len) = rest[start..].find("}}") else {
+            return Err("unterminated '{{'".into());
+        };
+        let expr = rest[start + 2..start + len].trim();
+        segments.push(Segment::HashFiles(parse_hash_files(expr)?));
+        rest = &rest[start + len + 2..];
+    }
+    if rest.contains("}}") {
+        return Err("unexpected '}}'".into());
+    }
+    if !rest.is_empty() {
+        segments.push(Segment::Literal(rest.to_owned()));
+    }
+    Ok(segments)
+}
+
+/// Parse `hashFiles('a', "b")` into its file paths.
+fn parse_hash_files(expr: &str) -> Result<Vec<String>, String> {
+    let args = expr
+        .strip_prefix("hashFiles(")
+        .and_then(|s| s.strip_suffix(')'))
+        .ok_or_else(|| format!("unsupported expression '{expr}' (expected hashFiles(...))"))?;
+    let mut files = Vec::new();
+    for arg in args.split(',') {
+        let arg = arg.trim();
+        let path = arg
+            .strip_prefix('\'')
+            .and_then(|s| s.strip_suffix('\''))
+            .or_else(|| arg.strip_prefix('"').and_then(|s| s.strip_suffix('"')))
+            .ok_or_else(|| format!("hashFiles arguments must be quoted paths, got '{arg}'"))?;
+        if path.is_empty() || path.starts_with('/') || path.contains("..") {
+            return Err(format!(
+                "hashFiles path '{path}' must be relative to the repository"
+            ));
+        }
+        files.push(path.to_owned());
+    }
+    Ok(files)
+}
+
+/// Check a key template without resolving it.
+pub fn validate_key(key: &str) -> Result<(), String> {
+    if key.trim().is_empty() {
+        return Err("key must not be empty".into());
+    }
+    if key.len() > MAX_KEY_LEN {
+        return Err(format!("key must be {MAX_KEY_LEN} characters or fewer"));
+    }
+    parse_key(key).map(|_| ())
+}
+
+/// Expand the `hashFiles(...)` expressions in `key` against the repo at
+/// `git_ref`. Files that don't exist are skipped; if none exist the
+/// expression expands to an empty string.
+pub async fn resolve_key(repo_path: &Path, git_ref: &str, key: &str) -> Result<String, String> {
+    let mut resolved = String::new();
+    for segment in parse_key(key)? {
+        match segment {
+            Segment::Literal(text) => resolved.push_str(&text),
+            Segment::HashFiles(files) => {
+                let mut hasher = Sha256::new();
+                let mut found = false;
+                for file in &files {
+                    if let Some(contents) =
+                        super::trigger::read_file_at_ref(repo_path, git_ref, file).await
+                    {
+                        hasher.update(contents.as_bytes());
+                        found = true;
+                    }
+                }
+                if found {
+                    resolved.push_str(&hex::encode(hasher.finalize()));
+                }
+            }
+        }
+    }
+    if resolved.len() > MAX_KEY_LEN {
+        return Err(format!("key must be {MAX_KEY_LEN} characters or fewer"));
+    }
+    Ok(resolved)
+}
+
+/// A stored cache.
+#[derive(Debug)]
+pub struct CacheEntry {
+    pub cache_key: String,
+    pub minio_path: String,
+    pub size_bytes: i64,
+}
+
+/// Find the cache to restore for `key` on `branch`, falling back to
+/// `default_branch` when the branch has none. Within a branch: an exact
+/// match, otherwise the most recently used cache matching the first
+/// `restore_keys` prefix that has one. Returns the entry and whether it was
+/// an exact hit. The entry's `last_used_at` is bumped so eviction keeps it.
+pub async fn lookup(
+    pool: &PgPool,
+    project_id: Uuid,
+    branch: &str,
+    default_branch: &str,
+    key: &str,
+    restore_keys: &[String],
+) -> Result<Option<(CacheEntry, bool)>, sqlx::Error> {
+    let mut branches = vec![branch];
+    if default_branch != branch {
+        branches.push(default_branch);
+    }
+    for branch in branches {
+        let found = lookup_in_branch(pool, project_id, branch, key, restore_keys).await?;
+        if found.is_some() {
+            return Ok(found);
+        }
+    }
+    Ok(None)
+}
+
+async fn lookup_in_branch(
+    pool: &PgPool,
+    project_id: Uuid,
+    branch: &str,
+    key: &str,
+    restore_keys: &[String],
+) -> Result<Option<(CacheEntry, bool)>, sqlx::Error> {
+    let exact = sqlx::query_as!(
+        CacheEntry,
+        "UPDATE pipeline_caches SET last_used_at = now()
+         WHERE project_id = $1 AND branch = $2 AND cache_key = $3
+         RETURNING cache_key, minio_path, size_bytes",
+        project_id,
+        branch,
+        key,
+    )
+    .fetch_optional(pool)
+    .await?;
+    if let Some(entry) = exact {
+        return Ok(Some((entry, true)));
+    }
+
+    for prefix in restore_keys {
+        let partial = sqlx::query_as!(
+            CacheEntry,
+            "UPDATE pipeline_caches SET last_used_at = now()
+             WHERE id = (
+                 SELECT id FROM pipeline_caches
+                 WHERE project_id = $1 AND branch = $2 AND starts_with(cache_key, $3)
+                 ORDER BY last_used_at DESC
+                 LIMIT 1
+             )
+             RETURNING cache_key, minio_path, size_bytes",
+            project_id,
+            branch,
+            prefix,
+        )
+        .fetch_optional(pool)
+        .await?;
+        if let Some(entry) = partial {
+            return Ok(Some((entry, false)));
+        }
+    }
+    Ok(None)
+}
+
+/// Result of [`save`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Saved {
+    /// Stored, with the archive size in bytes.
+    Stored(u64),
+    /// Another run saved the key on this branch first.
+    Exists,
+    /// The archive was empty, e.g. because tar failed in the pod.
+    Empty,
+    /// The archive grew past the size limit and the upload was abandoned.
+    TooLarge,
+}
+
+/// Stream `archive` (a tar.gz of the cache paths) to object storage and
+/// record it under `key` on `branch`. The upload is abandoned as soon as it
+/// exceeds `max_bytes`. Keys are immutable: if another run saved the key
+/// first, the upload is discarded.
+pub async fn save<R: AsyncRead + Unpin>(
+    pool: &PgPool,
+    minio: &opendal::Operator,
+    project_id: Uuid,
+    branch: &str,
+    key: &str,
+    archive: &mut R,
+    max_bytes: u64,
+) -> anyhow::Result<Saved> {
+    let id = Uuid::new_v4();
+    let minio_path = format!("caches/{project_id}/{id}.tar.gz");
+
+    let mut writer = minio.writer(&minio_path).await?;
+    let mut size = 0u64;
+    let mut buf = vec![0u8; CHUNK_SIZE];
+    loop {
+        let n = match archive.read(&mut buf).await {
+            Ok(n) => n,
+            Err(e) => {
+                let _ = writer.abort().await;
+                return Err(e.into());
+            }
+        };
+        if n == 0 {
+            break;
+        }
+        size += n as u64;
+        if size > max_bytes {
+            let _ = writer.abort().await;
+            return Ok(Saved::TooLarge);
+        }
+        writer.write(buf[..n].to_vec()).await?;
+    }
+    if size == 0 {
+        let _ = writer.abort().await;
+        return Ok(Saved::Empty);
+    }
+    writer.close().await?;
+
+    let inserted = sqlx::query!(
+        "INSERT INTO pipeline_caches (id, project_id, branch, cache_key, minio_path, size_bytes)
+         VALUES ($1, $2, $3, $4, $5, $6)
+         ON CONFLICT (project_id, branch, cache_key) DO NOTHING",
+        id,
+        project_id,
+        branch,
+        key,
+        minio_path,
+        i64::try_from(size).unwrap_or(i64::MAX),
+    )
+    .execute(pool)
+    .await?
+    .rows_affected()
+        > 0;
+    if !inserted {
+        let _ = minio.delete(&minio_path).await;
+        return Ok(Saved::Exists);
+    }
+    Ok(Saved::Stored(size))
+}
+
+/// Delete least recently used caches until the total size is at most
+/// `max_bytes`. Returns the number of caches evicted.
+pub async fn evict(
+    pool: &PgPool,
+    minio: &opendal::Operator,
+    max_bytes: u64,
+) -> Result<usize, sqlx::Error> {
+    let max_bytes = i64::try_from(max_bytes).unwrap_or(i64::MAX);
+    let evicted = sqlx::query_scalar!(
+        "DELETE FROM pipeline_caches WHERE id IN (
+             SELECT id FROM (
+                 SELECT id, (sum(size_bytes) OVER (ORDER BY last_used_at DESC, id))::BIGINT AS total
+                 FROM pipeline_caches
+             ) t
+             WHERE total > $1
+         )
+         RETURNING minio_path",
+        max_bytes,
+    )
+    .fetch_all(pool)
+    .await?;
+    for path in &evicted {
+        if let Err(e) = minio.delete(path).await {
+            tracing::warn!(error = %e, path, "failed to delete evicted pipeline cache");
+        }
+    }
+    if !evicted.is_empty() {
+        tracing::info!(count = evicted.len(), "evicted pipeline caches");
+    }
+    Ok(evicted.len())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_literal_key() {
+        assert_eq!(
+            parse_key("deps-v1").unwrap(),
+            vec![Segment::Literal("deps-v1".into())]
+        );
+    }
+
+    #[test]
+    fn parse_hash_files_key() {
+        assert_eq!(
+            parse_key("cargo-{{ hashFiles('Cargo.lock', \"sub/Cargo.lock\") }}-x").unwrap(),
+            vec![
+                Segment::Literal("cargo-".into()),
+                Segment::HashFiles(vec!["Cargo.lock".into(), "sub/Cargo.lock".into()]),
+                Segment::Literal("-x".into()),
+            ]
+        );
+    }
+
+    #[test]
+    fn rejects_invalid_keys() {
+        for bad in [
+            "",
+            "  ",
+            "deps-{{ hashFiles('Cargo.lock') ",
+            "deps-}}",
+            "deps-{{ branch }}",
+            "deps-{{ hashFiles(Cargo.lock) }}",
+            "deps-{{ hashFiles('/etc/passwd') }}",
+            "deps-{{ hashFiles('../x') }}",
+            "deps-{{ hashFiles('') }}",
+        ] {
+            assert!(validate_key(bad).is_err(), "'{bad}' should be rejected");
+        }
+        assert!(validate_key(&"k".repeat(MAX_KEY_LEN + 1)).is_err());
+    }
+}
@@ -0,0 +1,324 @@
+// Copyright (c) 2026 Steven Hooker. Exclusively licensed to and distributed by AgentSphere GmbH.
+// SPDX-License-Identifier: BUSL-1.1
+
+//! Five-field cron expressions (`minute hour day-of-month month day-of-week`),
+//! evaluated in UTC.
+//!
+//! Supports `*`, lists (`1,15`), ranges (`1-5`), steps (`*/15`, `0-30/10`),
+//! month and weekday names (`JAN`, `MON`), and the `@hourly`, `@daily`,
+//! `@weekly`, `@monthly` and `@yearly` shorthands. As in Vixie cron, when both
+//! day-of-month and day-of-week are restricted a day matching either fires.
+
+use chrono::{DateTime, Datelike, Days, Duration, TimeZone, Timelike, Utc};
+
+const MONTH_NAMES: &[&str] = &[
+    "JAN", "FEB", "MAR", "APR", "MAY", "JUN", "JUL", "AUG", "SEP", "OCT", "NOV", "DEC",
+];
+const WEEKDAY_NAMES: &[&str] = &["SUN", "MON", "TUE", "WED", "THU", "FRI", "SAT"];
+
+/// How far ahead [`CronExpr::next_after`] searches before giving up.
+const SEARCH_YEARS: i32 = 5;
+
+/// A parsed cron expression. Each field is a bitmask of the values it matches.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CronExpr {
+    minutes: u64,
+    hours: u64,
+    days_of_month: u64,
+    months: u64,
+    days_of_week: u64,
+    dom_restricted: bool,
+    dow_restricted: bool,
+}
+
+struct Field {
+    name: &'static str,
+    min: u32,
+    max: u32,
+    /// Names for the values, starting at `min`.
+    names: &'static [&'static str],
+}
+
+const MINUTE: Field = Field {
+    name: "minute",
+    min: 0,
+    max: 59,
+    names: &[],
+};
+const HOUR: Field = Field {
+    name: "hour",
+    min: 0,
+    max: 23,
+    names: &[],
+};
+const DAY_OF_MONTH: Field = Field {
+    name: "day-of-month",
+    min: 1,
+    max: 31,
+    names: &[],
+};
+const MONTH: Field = Field {
+    name: "month",
+    min: 1,
+    max: 12,
+    names: MONTH_NAMES,
+};
+// 7 is accepted as Sunday and folded onto 0.
+const DAY_OF_WEEK: Field = Field {
+    name: "day-of-week",
+    min: 0,
+    max: 7,
+    names: WEEKDAY_NAMES,
+};
+
+impl Field {
+    fn value(&self, s: &str) -> Result<u32, String> {
+        let upper = s.to_ascii_uppercase();
+        let value = match self.names.iter().position(|n| *n == upper) {
+            // Month names start at 1, weekday names at 0
+            Some(i) => self.min + u32::try_from(i).unwrap_or_default(),
+            None => s
+                .parse()
+                .map_err(|_| format!("invalid {} value '{s}'", self.name))?,
+        };
+        if value < self.min || value > self.max {
+            return Err(format!(
+                "{} value {value} out of range {}-{}",
+                self.name, self.min, self.max
+            ));
+        }
+        Ok(value)
+    }
+
+    /// Parse one comma-separated field into a bitmask.
+    fn parse(&self, s: &str) -> Result<u64, String> {
+        let mut mask = 0u64;
+        for part in s.split(',') {
+            let (range, step) = match part.split_once('/') {
+                Some((r, st)) => {
+                    let step: u32 = st
+                        .parse()
+                        .map_err(|_| format!("invalid {} step '{st}'", self.name))?;
+                    if step == 0 {
+                        return Err(format!("{} step must be positive", self.name));
+                    }
+                    (r, step)
+                }
+                None => (part, 1),
+            };
+            let (start, end) = if range == "*" {
+                (self.min, self.max)
+            } else if let Some((a, b)) = range.split_once('-') {
+                (self.value(a)?, self.value(b)?)
+            } else {
+                let start = self.value(range)?;
+                // `5/10` means "from 5 to the end, every 10"
+                let end = if part.contains('/') { self.max } else { start };
+                (start, end)
+            };
+            if start > end {
+                return Err(format!("invalid {} range '{range}'", self.name));
+            }
+            for v in (start..=end).step_by(usize::try_from(step).unwrap_or(usize::MAX)) {
+                mask |= 1 << v;
+            }
+        }
+        Ok(mask)
+    }
+}
+
+fn bit(mask: u64, value: u32) -> bool {
+    mask & (1 << value) != 0
+}
+
+impl CronExpr {
+    /// Parse a cron expression.
+    pub fn parse(expr: &str) -> Result<Self, String> {
+        let expr = expr.trim();
+        let expanded = match expr.to_ascii_lowercase().as_str() {
+            "@yearly" | "@annually" => "0 0 1 1 *",
+            "@monthly" => "0 0 1 * *",
+            "@weekly" => "0 0 * * 0",
+            "@daily" | "@midnight" => "0 0 * * *",
+            "@hourly" => "0 * * * *",
+            _ if expr.starts_with('@') => return Err(format!("unknown shorthand '{expr}'")),
+            _ => expr,
+        };
+        let fields: Vec<&str> = expanded.split_whitespace().collect();
+        let [minute, hour, dom, month, dow] = fields[..] else {
+            return Err(format!(
+                "expected 5 fields (minute hour day-of-month month day-of-week), got {}",
+                fields.len()
+            ));
+        };
+
+        let mut days_of_week = DAY_OF_WEEK.parse(dow)?;
+        if bit(days_of_week, 7) {
+            days_of_week = (days_of_week | 1) & 0x7f;
+        }
+        let cron = Self {
+            minutes: MINUTE.parse(minute)?,
+            hours: HOUR.parse(hour)?,
+            days_of_month: DAY_OF_MONTH.parse(dom)?,
+            months: MONTH.parse(month)?,
+            days_of_week,
+            dom_restricted: !dom.starts_with('*'),
+            dow_restricted: !dow.starts_with('*'),
+        };
+        if cron.next_after(Utc::now()).is_none() {
+            return Err(format!("'{expr}' never fires"));
+        }
+        Ok(cron)
+    }
+
+    fn day_matches(&self, day: u32, weekday: u32) -> bool {
+        let dom = bit(self.days_of_month, day);
+        let dow = bit(self.days_of_week, weekday);
+        if self.dom_restricted && self.dow_restricted {
+            dom || dow
+        } else {
+            dom && dow
+        }
+    }
+
+    /// The first time strictly after `after` that the expression fires, or
+    /// `None` if it does not fire within the next few years.
+    pub fn next_after(&self, after: DateTime<Utc>) -> Option<DateTime<Utc>> {
+        let mut t = after.with_second(0)?.with_nanosecond(0)? + Duration::minutes(1);
+        let last_year = t.year() + SEARCH_YEARS;
+        while t.year() <= last_year {
+            if !bit(self.months, t.month()) {
+                let (year, month) = if t.month() == 12 {
+                    (t.year() + 1, 1)
+                } else {
+                    (t.year(), t.month() + 1)
+                };
+                t = Utc.with_ymd_and_hms(year, month, 1, 0, 0, 0).single()?;
+            } else if !self.day_matches(t.day(), t.weekday().num_days_from_sunday()) {
+                t = (t.date_naive() + Days::new(1))
+                    .and_hms_opt(0, 0, 0)?
+                    .and_utc();
+            } else if !bit(self.hours, t.hour()) {
+                t = t.with_minute(0)? + Duration::hours(1);
+            } else if !bit(self.minutes, t.minute()) {
+                t += Duration::minutes(1);
+            } else {
+                return Some(t);
+            }
+        }
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn at(s: &str) -> DateTime<Utc> {
+        DateTime::parse_from_rfc3339(s).unwrap().with_timezone(&Utc)
+    }
+
+    fn next(expr: &str, after: &str) -> String {
+        CronExpr::parse(expr)
+            .unwrap()
+            .next_after(at(after))
+            .unwrap()
+            .to_rfc3339()
+    }
+
+    #[test]
+    fn nightly_at_two() {
+        assert_eq!(
+            next("0 2 * * *", "2026-10-15T01:59:30Z"),
+            "2026-10-15T02:00:00+00:00"
+        );
+        // Strictly after: the 02:00 slot itself schedules the next night
+        assert_eq!(
+            next("0 2 * * *", "2026-10-15T02:00:00Z"),
+            "2026-10-16T02:00:00+00:00"
+        );
+    }
+
+    #[test]
+    fn steps_lists_and_ranges() {
+        assert_eq!(
+            next("*/15 * * * *", "2026-10-15T10:16:00Z"),
+            "2026-10-15T10:30:00+00:00"
+        );
+        assert_eq!(
+            next("0 9-17/4 * * *", "2026-10-15T14:00:00Z"),
+            "2026-10-15T17:00:00+00:00"
+        );
+        assert_eq!(
+            next("30 6 1,15 * *", "2026-10-02T00:00:00Z"),
+            "2026-10-15T06:30:00+00:00"
+        );
+    }
+
+    #[test]
+    fn names_and_sunday_as_seven() {
+        // 2026-10-15 is a Thursday
+        assert_eq!(
+            next("0 0 * * SAT", "2026-10-15T00:00:00Z"),
+            "2026-10-17T00:00:00+00:00"
+        );
+        assert_eq!(
+            next("0 0 * * 7", "2026-10-15T00:00:00Z"),
+            "2026-10-18T00:00:00+00:00"
+        );
+        assert_eq!(
+            next("0 0 1 jan *", "2026-10-15T00:00:00Z"),
+            "2027-01-01T00:00:00+00:00"
+        );
+    }
+
+    #[test]
+    fn restricted_dom_and_dow_match_either() {
+        // The 20th, or any Monday: Monday 2026-10-19 comes first
+        assert_eq!(
+            next("0 0 20 * MON", "2026-10-15T00:00:00Z"),
+            "2026-10-19T00:00:00+00:00"
+        );
+    }
+
+    #[test]
+    fn leap_day() {
+        assert_eq!(
+            next("0 0 29 2 *", "2026-10-15T00:00:00Z"),
+            "2028-02-29T00:00:00+00:00"
+        );
+    }
+
+    #[test]
+    fn shorthands() {
+        assert_eq!(
+            CronExpr::parse("@daily").unwrap(),
+            CronExpr::parse("0 0 * * *").unwrap()
+        );
+        assert_eq!(
+            next("@hourly", "2026-10-15T10:16:00Z"),
+            "2026-10-15T11:00:00+00:00"
+        );
+    }
+
+    #[test]
+    fn rejects_invalid_expressions() {
+        for bad in [
+            "",
+            "* * * *",
+            "* * * * * *",
+            "60 * * * *",
+            "* 24 * * *",
+            "* * 0 * *",
+            "* * * 13 *",
+            "* * * * 8",
+            "*/0 * * * *",
+            "5-1 * * * *",
+            "a * * * *",
+            "0 0 30 2 *",
+            "@often",
+        ] {
+            assert!(CronExpr::parse(bad).is_err(), "'{bad}' should be rejected");
+        }
+    }
+}
@@ -1,9 +1,10 @@
 // Copyright (c) 2026 Steven Hooker. Exclusively licensed to and distributed by AgentSphere GmbH.
 // SPDX-License-Identifier: BUSL-1.1
 
-use std::collections::{HashMap, HashSet, VecDeque};
+use std::collections::{BTreeMap, HashMap, HashSet, VecDeque};
 
 use serde::{Deserialize, Serialize};
+use ts_rs::TS;
 
 use super::error::PipelineError;
 
@@ -37,8 +38,15 @@ pub struct PipelineDefinition {
     pub trigger: Option<TriggerConfig>,
     #[serde(default)]
     pub dev_image: Option<DevImageConfig>,
+    /// Variable groups whose variables are exposed to every step, in order
+    /// (later groups override earlier ones).
+    #[serde(default)]
+    pub variable_groups: Vec<String>,
 }
 
+/// Most variable groups one pipeline can reference.
+pub const MAX_VARIABLE_GROUPS: usize = 10;
+
 /// Configuration for building a custom dev/agent image from the project repo.
 #[derive(Debug, Deserialize)]
 pub struct DevImageConfig {
@@ -220,6 +228,95 @@ pub struct StepDef {
     /// Artifacts to collect after this step succeeds.
     #[serde(default)]
     pub artifacts: Vec<ArtifactDef>,
+    /// Service containers (databases, caches, ...) started alongside the step.
+    #[serde(default)]
+    pub services: Vec<ServiceDef>,
+    /// Run the step only when this holds; otherwise it is skipped with a reason.
+    #[serde(default)]
+    pub when: Option<WhenDef>,
+    /// Workspace paths restored before the step and saved after it succeeds.
+    #[serde(default)]
+    pub cache: Option<CacheDef>,
+    /// Commits of history to clone; `0` clones the full history. Default 1.
+    #[serde(default)]
+    pub clone_depth: Option<u32>,
+    /// Also fetch the repository's tags, e.g. for `git describe --tags`.
+    #[serde(default)]
+    pub fetch_tags: bool,
+}
+
+/// Maximum number of `when.changes` globs per step.
+pub const MAX_WHEN_CHANGES: usize = 50;
+
+/// A step's `when:` — either an expression (see [`super::when`]) or rules
+/// that can also require a changed file.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(untagged)]
+pub enum WhenDef {
+    Expr(String),
+    Rules(WhenRules),
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct WhenRules {
+    /// Expression, as in the short form.
+    #[serde(default, rename = "if")]
+    pub expr: Option<String>,
+    /// Globs matched against the files changed by the push. Pipelines not
+    /// started by a push (or whose changes are unknown) always match.
+    #[serde(default)]
+    pub changes: Vec<String>,
+}
+
+impl WhenDef {
+    pub fn expr(&self) -> Option<&str> {
+        match self {
+            Self::Expr(e) => Some(e),
+            Self::Rules(r) => r.expr.as_deref(),
+        }
+    }
+
+    pub fn changes(&self) -> &[String] {
+        match self {
+            Self::Expr(_) => &[],
+            Self::Rules(r) => &r.changes,
+        }
+    }
+}
+
+/// Maximum number of `cache.paths` and of `cache.restore_keys` per step.
+pub const MAX_CACHE_ENTRIES: usize = 10;
+
+/// A step's `cache:` (see [`super::cache`]).
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct CacheDef {
+    /// Cache key; `{{ hashFiles('Cargo.lock') }}` expands to a hash of the
+    /// files at the pipeline's commit.
+    pub key: String,
+    /// Key prefixes tried in order when `key` misses.
+    #[serde(default)]
+    pub restore_keys: Vec<String>,
+    /// Paths relative to the workspace.
+    pub paths: Vec<String>,
+}
+
+/// Deepest `clone_depth` a step may ask for short of the full history (`0`).
+pub const MAX_CLONE_DEPTH: u32 = 10_000;
+
+/// Maximum number of service containers per step.
+pub const MAX_STEP_SERVICES: usize = 5;
+
+/// A service container running in the step's pod. It shares the pod network,
+/// so the step reaches it on `localhost`.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct ServiceDef {
+    pub image: String,
+    #[serde(default)]
+    pub env: BTreeMap<String, String>,
+    /// Port the service listens on. When set, the step script starts only
+    /// once the service accepts TCP connections on it.
+    #[serde(default)]
+    pub port: Option<u16>,
 }
 
 /// Configuration for a `gitops_sync` step.
@@ -340,7 +437,12 @@ pub struct TriggerConfig {
 
 #[derive(Debug, Deserialize)]
 pub struct PushTrigger {
+    /// Branch patterns to build. Empty = every branch.
+    #[serde(default)]
     pub branches: Vec<String>,
+    /// Branch patterns never built, even when matched by `branches`.
+    #[serde(default)]
+    pub branches_ignore: Vec<String>,
 }
 
 #[derive(Debug, Deserialize)]
@@ -376,125 +478,510 @@ pub fn parse_platform_file(yaml: &str) -> Result<PlatformFile, PipelineError> {
     Ok(file)
 }
 
-#[allow(clippy::too_many_lines)]
+// ---------------------------------------------------------------------------
+// Linting
+// ---------------------------------------------------------------------------
+
+/// A problem found by [`lint`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, TS)]
+#[ts(export, rename = "PipelineLintIssue")]
+pub struct LintIssue {
+    /// Path to the offending node, e.g. `pipeline.steps[1].image`. `None` when
+    /// the YAML parser reports a position instead.
+    pub path: Option<String>,
+    /// 1-based line, for syntax and type errors.
+    pub line: Option<usize>,
+    /// 1-based column, for syntax and type errors.
+    pub column: Option<usize>,
+    pub message: String,
+}
+
+impl LintIssue {
+    fn at(path: impl Into<String>, err: PipelineError) -> Self {
+        let message = match err {
+            PipelineError::InvalidDefinition(msg) => msg,
+            other => other.to_string(),
+        };
+        Self {
+            path: Some(path.into()),
+            line: None,
+            column: None,
+            message,
+        }
+    }
+
+    fn from_yaml(err: &serde_yaml::Error) -> Self {
+        let location = err.location();
+        Self {
+            path: None,
+            line: location.as_ref().map(serde_yaml::Location::line),
+            column: location.as_ref().map(serde_yaml::Location::column),
+            message: err.to_string(),
+        }
+    }
+}
+
+/// Check a `.platform.yaml` without creating anything, collecting every
+/// problem instead of stopping at the first.
+///
+/// Stricter than [`parse_platform_file`]: keys the platform doesn't know are
+/// reported (they are silently ignored at run time, usually a typo). An empty
+/// result means [`parse_platform_file`] accepts the file.
+pub fn lint(yaml: &str) -> Vec<LintIssue> {
+    let value: serde_yaml::Value = match serde_yaml::from_str(yaml) {
+        Ok(v) => v,
+        Err(e) => return vec![LintIssue::from_yaml(&e)],
+    };
+    let mut issues = Vec::new();
+    lint_unknown_keys(&value, &mut issues);
+
+    let file: PlatformFile = match serde_yaml::from_str(yaml) {
+        Ok(f) => f,
+        Err(e) => {
+            issues.push(LintIssue::from_yaml(&e));
+            return issues;
+        }
+    };
+    let def = &file.pipeline;
+    if def.steps.is_empty() {
+        issues.push(LintIssue::at(
+            "pipeline.steps",
+            PipelineError::InvalidDefinition("pipeline must have at least one step".into()),
+        ));
+    }
+    for (i, step) in def.steps.iter().enumerate() {
+        if let Err(e) = validate_step(i, step) {
+            issues.push(LintIssue::at(format!("pipeline.steps[{i}]"), e));
+        }
+    }
+    if let Err(e) = validate_dag(&def.steps) {
+        issues.push(LintIssue::at("pipeline.steps", e));
+    }
+    if let Some(dev) = &def.dev_image
+        && let Err(e) = validate_dev_image(dev)
+    {
+        issues.push(LintIssue::at("pipeline.dev_image", e));
+    }
+    if let Err(e) = validate_flags(&file.flags) {
+        issues.push(LintIssue::at("flags", e));
+    }
+    if let Some(deploy) = &file.deploy
+        && let Err(e) = validate_deploy_config(deploy)
+    {
+        issues.push(LintIssue::at("deploy", e));
+    }
+    issues
+}
+
+/// Report mapping keys that the matching definition struct doesn't declare.
+fn lint_unknown_keys(root: &serde_yaml::Value, issues: &mut Vec<LintIssue>) {
+    check_keys::<PlatformFile>(root, "", issues);
+
+    let pipeline = &root["pipeline"];
+    check_keys::<PipelineDefinition>(pipeline, "pipeline", issues);
+    check_keys::<DevImageConfig>(&pipeline["dev_image"], "pipeline.dev_image", issues);
+    let on = &pipeline["on"];
+    check_keys::<TriggerConfig>(on, "pipeline.on", issues);
+    check_keys::<PushTrigger>(&on["push"], "pipeline.on.push", issues);
+    check_keys::<MrTrigger>(&on["mr"], "pipeline.on.mr", issues);
+    check_keys::<TagTrigger>(&on["tag"], "pipeline.on.tag", issues);
+
+    for (i, step) in items(&pipeline["steps"]) {
+        let path = format!("pipeline.steps[{i}]");
+        check_keys::<StepDef>(step, &path, issues);
+        check_keys::<StepCondition>(&step["only"], &format!("{path}.only"), issues);
+        check_keys::<DeployTestDef>(&step["deploy_test"], &format!("{path}.deploy_test"), issues);
+        check_keys::<GitopsSyncDef>(&step["gitops"], &format!("{path}.gitops"), issues);
+        check_keys::<DeployWatchDef>(
+            &step["deploy_watch"],
+            &format!("{path}.deploy_watch"),
+            issues,
+        );
+        for (j, artifact) in items(&step["artifacts"]) {
+            check_keys::<ArtifactDef>(artifact, &format!("{path}.artifacts[{j}]"), issues);
+        }
+        for (j, service) in items(&step["services"]) {
+            check_keys::<ServiceDef>(service, &format!("{path}.services[{j}]"), issues);
+        }
+        check_keys::<WhenRules>(&step["when"], &format!("{path}.when"), issues);
+        check_keys::<CacheDef>(&step["cache"], &format!("{path}.cache"), issues);
+    }
+
+    for (i, flag) in items(&root["flags"]) {
+        check_keys::<FlagDef>(flag, &format!("flags[{i}]"), issues);
+    }
+
+    let deploy = &root["deploy"];
+    check_keys::<DeployConfig>(deploy, "deploy", issues);
+    for (i, spec) in items(&deploy["specs"]) {
+        let path = format!("deploy.specs[{i}]");
+        check_keys::<DeploySpec>(spec, &path, issues);
+        let canary = &spec["canary"];
+        check_keys::<CanaryConfig>(canary, &format!("{path}.canary"), issues);
+        for key in ["progress_gates", "rollback_triggers"] {
+            for (j, gate) in items(&canary[key]) {
+                check_keys::<MetricGateConfig>(gate, &format!("{path}.canary.{key}[{j}]"), issues);
+            }
+        }
+        let ab = &spec["ab_test"];
+        check_keys::<AbTestConfig>(ab, &format!("{path}.ab_test"), issues);
+        check_keys::<AbTestMatchConfig>(&ab["match"], &format!("{path}.ab_test.match"), issues);
+    }
+}
+
+fn items(value: &serde_yaml::Value) -> impl Iterator<Item = (usize, &serde_yaml::Value)> {
+    value.as_sequence().into_iter().flatten().enumerate()
+}
+
+fn check_keys<T: serde::de::DeserializeOwned>(
+    value: &serde_yaml::Value,
+    path: &str,
+    issues: &mut Vec<LintIssue>,
+) {
+    let Some(map) = value.as_mapping() else {
+        return;
+    };
+    let fields = struct_fields::<T>();
+    for key in map.keys().filter_map(serde_yaml::Value::as_str) {
+        if !fields.contains(&key) {
+            let key_path = if path.is_empty() {
+                key.to_owned()
+            } else {
+                format!("{path}.{key}")
+            };
+            issues.push(LintIssue {
+                path: Some(key_path),
+                line: None,
+                column: None,
+                message: format!("unknown key '{key}'"),
+            });
+        }
+    }
+}
+
+/// The field names (after `rename`) a derived `Deserialize` impl accepts.
+///
+/// Derived impls pass their field list to `Deserializer::deserialize_struct`;
+/// this feeds them a deserializer that records it and bails out.
+fn struct_fields<T: serde::de::DeserializeOwned>() -> &'static [&'static str] {
+    struct FieldCapture(&'static [&'static str]);
+
+    impl<'de> serde::Deserializer<'de> for &mut FieldCapture {
+        type Error = serde::de::value::Error;
+
+        fn deserialize_any<V: serde::de::Visitor<'de>>(
+            self,
+            _visitor: V,
+        ) -> Result<V::Value, Self::Error> {
+            Err(serde::de::Error::custom("not a struct"))
+        }
+
+        fn deserialize_struct<V: serde::de::Visitor<'de>>(
+            self,
+            _name: &'static str,
+            fields: &'static [&'static str],
+            _visitor: V,
+        ) -> Result<V::Value, Self::Error> {
+            self.0 = fields;
+            Err(serde::de::Error::custom("fields captured"))
+        }
+
+        serde::forward_to_deserialize_any! {
+            bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char str string
+            bytes byte_buf option unit unit_struct newtype_struct seq tuple
+            tuple_struct map enum identifier ignored_any
+        }
+    }
+
+    let mut capture = FieldCapture(&[]);
+    let _ = T::deserialize(&mut capture);
+    capture.0
+}
+
+// ---------------------------------------------------------------------------
+// Validation
+// ---------------------------------------------------------------------------
+
 fn validate(def: &PipelineDefinition) -> Result<(), PipelineError> {
     if def.steps.is_empty() {
         return Err(PipelineError::InvalidDefinition(
             "pipeline must have at least one step".into(),
         ));
     }
-
     for (i, step) in def.steps.iter().enumerate() {
-        if step.name.is_empty() {
+        validate_step(i, step)?;
+    }
+    validate_dag(&def.steps)?;
+    if let Some(dev) = &def.dev_image {
+        validate_dev_image(dev)?;
+    }
+    validate_variable_groups(&def.variable_groups)?;
+    Ok(())
+}
+
+fn validate_variable_groups(groups: &[String]) -> Result<(), PipelineError> {
+    if groups.len() > MAX_VARIABLE_GROUPS {
+        return Err(PipelineError::InvalidDefinition(format!(
+            "variable_groups allows at most {MAX_VARIABLE_GROUPS} groups"
+        )));
+    }
+    for (i, name) in groups.iter().enumerate() {
+        crate::validation::check_name(name).map_err(|_| {
+            PipelineError::InvalidDefinition(format!("invalid variable group name '{name}'"))
+        })?;
+        if groups[..i].contains(name) {
             return Err(PipelineError::InvalidDefinition(format!(
-                "step {i} is missing a name"
+                "variable group '{name}' is listed more than once"
             )));
         }
+    }
+    Ok(())
+}
 
-        match step.kind() {
-            StepKind::ImageBuild => {
-                if step.image_name.as_ref().is_none_or(String::is_empty) {
-                    return Err(PipelineError::InvalidDefinition(format!(
-                        "step '{}': type=imagebuild requires imageName",
-                        step.name,
-                    )));
-                }
-                if let Some(ref img) = step.image_name {
-                    crate::validation::check_pipeline_image(img).map_err(|e| {
-                        PipelineError::InvalidDefinition(format!(
-                            "step '{}': imageName: {e}",
-                            step.name,
-                        ))
-                    })?;
-                }
+fn validate_step(i: usize, step: &StepDef) -> Result<(), PipelineError> {
+    if step.name.is_empty() {
+        return Err(PipelineError::InvalidDefinition(format!(
+            "step {i} is missing a name"
+        )));
+    }
+
+    match step.kind() {
+        StepKind::ImageBuild => {
+            if step.image_name.as_ref().is_none_or(String::is_empty) {
+                return Err(PipelineError::InvalidDefinition(format!(
+                    "step '{}': type=imagebuild requires imageName",
+                    step.name,
+                )));
             }
-            StepKind::DeployTest => {
-                let dt = step.deploy_test.as_ref().ok_or_else(|| {
+            if let Some(ref img) = step.image_name {
+                crate::validation::check_pipeline_image(img).map_err(|e| {
                     PipelineError::InvalidDefinition(format!(
-                        "step '{}': deploy_test kind but no deploy_test config",
+                        "step '{}': imageName: {e}",
                         step.name,
                     ))
                 })?;
-                if !step.commands.is_empty() {
-                    return Err(PipelineError::InvalidDefinition(format!(
-                        "step '{}': deploy_test and commands are mutually exclusive",
-                        step.name,
-                    )));
-                }
-                validate_deploy_test(&step.name, dt)?;
-            }
-            StepKind::GitopsSync => {
-                if step.gitops.as_ref().is_none_or(|g| g.copy.is_empty()) {
-                    return Err(PipelineError::InvalidDefinition(format!(
-                        "step '{}': type=gitops_sync requires gitops.copy with at least one entry",
-                        step.name,
-                    )));
-                }
             }
-            StepKind::DeployWatch => {
-                if step.deploy_watch.is_none() {
-                    return Err(PipelineError::InvalidDefinition(format!(
-                        "step '{}': type=deploy_watch requires deploy_watch config",
-                        step.name,
-                    )));
-                }
-            }
-            StepKind::Command => {
-                // Legacy: raw command step needs an image
-                if step.image.is_empty() {
-                    return Err(PipelineError::InvalidDefinition(format!(
-                        "step '{}' is missing an image",
-                        step.name
-                    )));
-                }
-                crate::validation::check_pipeline_image(&step.image).map_err(|e| {
-                    PipelineError::InvalidDefinition(format!("step '{}': image: {e}", step.name,))
-                })?;
+        }
+        StepKind::DeployTest => {
+            let dt = step.deploy_test.as_ref().ok_or_else(|| {
+                PipelineError::InvalidDefinition(format!(
+                    "step '{}': deploy_test kind but no deploy_test config",
+                    step.name,
+                ))
+            })?;
+            if !step.commands.is_empty() {
+                return Err(PipelineError::InvalidDefinition(format!(
+                    "step '{}': deploy_test and commands are mutually exclusive",
+                    step.name,
+                )));
             }
+            validate_deploy_test(&step.name, dt)?;
         }
-
-        if let Some(ref cond) = step.only {
-            validate_step_condition(&step.name, cond)?;
+        StepKind::GitopsSync => {
+            if step.gitops.as_ref().is_none_or(|g| g.copy.is_empty()) {
+                return Err(PipelineError::InvalidDefinition(format!(
+                    "step '{}': type=gitops_sync requires gitops.copy with at least one entry",
+                    step.name,
+                )));
+            }
         }
-    }
-
-    // Validate step-level artifact paths — reject path traversal
-    for step in &def.steps {
-        for artifact in &step.artifacts {
-            if artifact.path.contains("..") {
+        StepKind::DeployWatch => {
+            if step.deploy_watch.is_none() {
                 return Err(PipelineError::InvalidDefinition(format!(
-                    "step '{}': artifact path must not contain '..'",
+                    "step '{}': type=deploy_watch requires deploy_watch config",
                     step.name,
                 )));
             }
         }
+        StepKind::Command => {
+            // Legacy: raw command step needs an image
+            if step.image.is_empty() {
+                return Err(PipelineError::InvalidDefinition(format!(
+                    "step '{}' is missing an image",
+                    step.name
+                )));
+            }
+            crate::validation::check_pipeline_image(&step.image).map_err(|e| {
+                PipelineError::InvalidDefinition(format!("step '{}': image: {e}", step.name))
+            })?;
+        }
     }
 
-    validate_dag(&def.steps)?;
+    if let Some(ref cond) = step.only {
+        validate_step_condition(&step.name, cond)?;
+    }
+    validate_services(step)?;
+    if let Some(ref when) = step.when {
+        validate_when(&step.name, when)?;
+    }
+    if let Some(ref cache) = step.cache {
+        validate_cache(step, cache)?;
+    }
+    validate_clone(step)?;
 
-    if let Some(dev) = &def.dev_image {
-        if dev.dockerfile.is_empty() {
-            return Err(PipelineError::InvalidDefinition(
-                "dev_image.dockerfile must not be empty".into(),
-            ));
+    // Reject path traversal in artifact paths
+    for artifact in &step.artifacts {
+        if artifact.path.contains("..") {
+            return Err(PipelineError::InvalidDefinition(format!(
+                "step '{}': artifact path must not contain '..'",
+                step.name,
+            )));
         }
-        if dev.dockerfile.len() > 255 {
-            return Err(PipelineError::InvalidDefinition(
-                "dev_image.dockerfile must be 255 characters or fewer".into(),
-            ));
+    }
+    Ok(())
+}
+
+fn validate_when(step_name: &str, when: &WhenDef) -> Result<(), PipelineError> {
+    if when.expr().is_none() && when.changes().is_empty() {
+        return Err(PipelineError::InvalidDefinition(format!(
+            "step '{step_name}': when needs an 'if' expression or 'changes'"
+        )));
+    }
+    if let Some(expr) = when.expr() {
+        super::when::WhenExpr::parse(expr).map_err(|e| {
+            PipelineError::InvalidDefinition(format!("step '{step_name}': when: {e}"))
+        })?;
+    }
+    if when.changes().len() > MAX_WHEN_CHANGES {
+        return Err(PipelineError::InvalidDefinition(format!(
+            "step '{step_name}': when.changes allows at most {MAX_WHEN_CHANGES} patterns"
+        )));
+    }
+    if when.changes().iter().any(|p| p.is_empty() || p.len() > 255) {
+        return Err(PipelineError::InvalidDefinition(format!(
+            "step '{step_name}': when.changes patterns must be 1-255 characters"
+        )));
+    }
+    Ok(())
+}
+
+fn validate_cache(step: &StepDef, cache: &CacheDef) -> Result<(), PipelineError> {
+    if step.kind() != StepKind::Command {
+        return Err(PipelineError::InvalidDefinition(format!(
+            "step '{}': cache is only supported on command steps",
+            step.name,
+        )));
+    }
+    super::cache::validate_key(&cache.key).map_err(|e| {
+        PipelineError::InvalidDefinition(format!("step '{}': cache.key: {e}", step.name))
+    })?;
+    if cache.paths.is_empty() || cache.paths.len() > MAX_CACHE_ENTRIES {
+        return Err(PipelineError::InvalidDefinition(format!(
+            "step '{}': cache.paths needs 1-{MAX_CACHE_ENTRIES} paths",
+            step.name,
+        )));
+    }
+    for path in &cache.paths {
+        if path.is_empty() || path.starts_with('/') || path.contains("..") || path.len() > 255 {
+            return Err(PipelineError::InvalidDefinition(format!(
+                "step '{}': cache path '{path}' must be relative to the workspace",
+                step.name,
+            )));
         }
-        if dev.dockerfile.contains("..") {
-            return Err(PipelineError::InvalidDefinition(
-                "dev_image.dockerfile must not contain path traversal (..)".into(),
-            ));
+    }
+    if cache.restore_keys.len() > MAX_CACHE_ENTRIES {
+        return Err(PipelineError::InvalidDefinition(format!(
+            "step '{}': cache.restore_keys allows at most {MAX_CACHE_ENTRIES} prefixes",
+            step.name,
+        )));
+    }
+    if cache
+        .restore_keys
+        .iter()
+        .any(|k| k.is_empty() || k.len() > super::cache::MAX_KEY_LEN)
+    {
+        return Err(PipelineError::InvalidDefinition(format!(
+            "step '{}': cache.restore_keys must be 1-{} characters",
+            step.name,
+            super::cache::MAX_KEY_LEN,
+        )));
+    }
+    Ok(())
+}
+
+fn validate_dev_image(dev: &DevImageConfig) -> Result<(), PipelineError> {
+    if dev.dockerfile.is_empty() {
+        return Err(PipelineError::InvalidDefinition(
+            "dev_image.dockerfile must not be empty".into(),
+        ));
+    }
+    if dev.dockerfile.len() > 255 {
+        return Err(PipelineError::InvalidDefinition(
+            "dev_image.dockerfile must be 255 characters or fewer".into(),
+        ));
+    }
+    if dev.dockerfile.contains("..") {
+        return Err(PipelineError::InvalidDefinition(
+            "dev_image.dockerfile must not contain path traversal (..)".into(),
+        ));
+    }
+    if dev.dockerfile.starts_with('/') {
+        return Err(PipelineError::InvalidDefinition(
+            "dev_image.dockerfile must be a relative path".into(),
+        ));
+    }
+    Ok(())
+}
+
+fn validate_clone(step: &StepDef) -> Result<(), PipelineError> {
+    if step.clone_depth.is_none() && !step.fetch_tags {
+        return Ok(());
+    }
+    if !matches!(step.kind(), StepKind::Command | StepKind::ImageBuild) {
+        return Err(PipelineError::InvalidDefinition(format!(
+            "step '{}': clone_depth and fetch_tags are only supported on command and imagebuild steps",
+            step.name,
+        )));
+    }
+    if step.clone_depth.is_some_and(|d| d > MAX_CLONE_DEPTH) {
+        return Err(PipelineError::InvalidDefinition(format!(
+            "step '{}': clone_depth must be 0 (full history) to {MAX_CLONE_DEPTH}",
+            step.name,
+        )));
+    }
+    Ok(())
+}
+
+fn validate_services(step: &StepDef) -> Result<(), PipelineError> {
+    if step.services.is_empty() {
+        return Ok(());
+    }
+    if step.kind() != StepKind::Command {
+        return Err(PipelineError::InvalidDefinition(format!(
+            "step '{}': services are only supported on command steps",
+            step.name,
+        )));
+    }
+    if step.services.len() > MAX_STEP_SERVICES {
+        return Err(PipelineError::InvalidDefinition(format!(
+            "step '{}': at most {MAX_STEP_SERVICES} services are allowed",
+            step.name,
+        )));
+    }
+    for (i, svc) in step.services.iter().enumerate() {
+        crate::validation::check_container_image(&svc.image).map_err(|e| {
+            PipelineError::InvalidDefinition(format!(
+                "step '{}': services[{i}].image: {e}",
+                step.name
+            ))
+        })?;
+        for key in svc.env.keys() {
+            let valid = key.starts_with(|c: char| c.is_ascii_alphabetic() || c == '_')
+                && key.chars().all(|c| c.is_ascii_alphanumeric() || c == '_');
+            if !valid {
+                return Err(PipelineError::InvalidDefinition(format!(
+                    "step '{}': services[{i}].env: invalid variable name '{key}'",
+                    step.name
+                )));
+            }
         }
-        if dev.dockerfile.starts_with('/') {
-            return Err(PipelineError::InvalidDefinition(
-                "dev_image.dockerfile must be a relative path".into(),
-            ));
+        if svc.port == Some(0) {
+            return Err(PipelineError::InvalidDefinition(format!(
+                "step '{}': services[{i}].port must be between 1 and 65535",
+                step.name
+            )));
         }
     }
-
     Ok(())
 }
 
@@ -524,14 +1011,52 @@ fn validate_dag(steps: &[StepDef]) -> Result<(), PipelineError> {
 
     // Cycle detection via Kahn's algorithm
     if topological_layers(steps).is_none() {
-        return Err(PipelineError::InvalidDefinition(
-            "pipeline dependency graph contains a cycle".into(),
-        ));
+        let members = cycle_members(steps)
+            .iter()
+            .map(|name| format!("'{name}'"))
+            .collect::<Vec<_>>()
+            .join(", ");
+        return Err(PipelineError::InvalidDefinition(format!(
+            "pipeline dependency graph contains a cycle between steps {members}"
+        )));
     }
 
     Ok(())
 }
 
+/// Names of the steps that sit on a dependency cycle, in definition order.
+///
+/// Repeatedly drops steps that have no remaining dependencies or no remaining
+/// dependents; whatever survives is part of (or wedged between) cycles.
+fn cycle_members(steps: &[StepDef]) -> Vec<&str> {
+    let mut remaining: HashSet<&str> = steps.iter().map(|s| s.name.as_str()).collect();
+    loop {
+        let removable: Vec<&str> = steps
+            .iter()
+            .filter(|s| remaining.contains(s.name.as_str()))
+            .filter(|s| {
+                let has_deps = s.depends_on.iter().any(|d| remaining.contains(d.as_str()));
+                let has_dependents = steps.iter().any(|other| {
+                    remaining.contains(other.name.as_str()) && other.depends_on.contains(&s.name)
+                });
+                !has_deps || !has_dependents
+            })
+            .map(|s| s.name.as_str())
+            .collect();
+        if removable.is_empty() {
+            break;
+        }
+        for name in removable {
+            remaining.remove(name);
+        }
+    }
+    steps
+        .iter()
+        .map(|s| s.name.as_str())
+        .filter(|name| remaining.contains(name))
+        .collect()
+}
+
 /// Compute parallel execution layers via topological sort.
 ///
 /// Returns `None` if the graph has a cycle. Otherwise returns groups of step
@@ -595,6 +1120,7 @@ pub fn topological_layers(steps: &[StepDef]) -> Option<Vec<Vec<usize>>> {
 /// Check if a push to `branch` matches the trigger configuration.
 ///
 /// If no trigger config or no push trigger is defined, all branches match.
+/// `branches_ignore` takes precedence over `branches`.
 pub fn matches_push(trigger: Option<&TriggerConfig>, branch: &str) -> bool {
     let Some(config) = trigger else {
         return true;
@@ -602,6 +1128,13 @@ pub fn matches_push(trigger: Option<&TriggerConfig>, branch: &str) -> bool {
     let Some(push) = &config.push else {
         return true;
     };
+    if push
+        .branches_ignore
+        .iter()
+        .any(|pattern| crate::validation::match_glob_pattern(pattern, branch))
+    {
+        return false;
+    }
     if push.branches.is_empty() {
         return true;
     }
@@ -649,7 +1182,7 @@ pub fn matches_tag(trigger: Option<&TriggerConfig>, tag_name: &str) -> bool {
 // Per-step condition matching
 // ---------------------------------------------------------------------------
 
-const VALID_EVENTS: &[&str] = &["push", "mr", "tag", "api"];
+const VALID_EVENTS: &[&str] = &["push", "mr", "tag", "api", "schedule"];
 
 /// Check if a step should run given the trigger type and branch.
 ///
@@ -677,7 +1210,7 @@ fn validate_step_condition(step_name: &str, cond: &StepCondition) -> Result<(),
     for event in &cond.events {
         if !VALID_EVENTS.contains(&event.as_str()) {
             return Err(PipelineError::InvalidDefinition(format!(
-                "step '{step_name}': invalid event '{event}' (allowed: push, mr, tag, api)"
+                "step '{step_name}': invalid event '{event}' (allowed: push, mr, tag, api, schedule)"
             )));
         }
     }
@@ -699,9 +1232,7 @@ fn validate_deploy_test(step_name: &str, dt: &DeployTestDef) -> Result<(), Pipel
         )));
     }
     crate::validation::check_pipeline_image(&dt.test_image).map_err(|e| {
-        PipelineError::InvalidDefinition(
-            format!("step '{step_name}': deploy_test.test_image: {e}",),
-        )
+        PipelineError::InvalidDefinition(format!("step '{step_name}': deploy_test.test_image: {e}"))
     })?;
     if let Some(ref manifests) = dt.manifests
         && manifests.contains("..")
@@ -1095,7 +1626,24 @@ pipeline:
     }
 
     #[test]
-    fn matches_push_wildcard() {
+    fn matches_push_wildcard() {
+        let yaml = r#"
+pipeline:
+  steps:
+    - name: test
+      image: alpine
+  on:
+    push:
+      branches: ["feature/*"]
+"#;
+        let def = parse(yaml).unwrap();
+        assert!(matches_push(def.trigger.as_ref(), "feature/foo"));
+        assert!(matches_push(def.trigger.as_ref(), "feature/bar"));
+        assert!(!matches_push(def.trigger.as_ref(), "main"));
+    }
+
+    #[test]
+    fn matches_push_star_only() {
         let yaml = r#"
 pipeline:
   steps:
@@ -1103,16 +1651,15 @@ pipeline:
       image: alpine
   on:
     push:
-      branches: ["feature/*"]
+      branches: ["*"]
 "#;
         let def = parse(yaml).unwrap();
-        assert!(matches_push(def.trigger.as_ref(), "feature/foo"));
-        assert!(matches_push(def.trigger.as_ref(), "feature/bar"));
-        assert!(!matches_push(def.trigger.as_ref(), "main"));
+        assert!(matches_push(def.trigger.as_ref(), "main"));
+        assert!(matches_push(def.trigger.as_ref(), "anything"));
     }
 
     #[test]
-    fn matches_push_star_only() {
+    fn matches_push_branches_ignore() {
         let yaml = r#"
 pipeline:
   steps:
@@ -1121,10 +1668,31 @@ pipeline:
   on:
     push:
       branches: ["*"]
+      branches_ignore: ["dependabot/*", "wip"]
 "#;
         let def = parse(yaml).unwrap();
         assert!(matches_push(def.trigger.as_ref(), "main"));
-        assert!(matches_push(def.trigger.as_ref(), "anything"));
+        assert!(!matches_push(
+            def.trigger.as_ref(),
+            "dependabot/cargo-serde"
+        ));
+        assert!(!matches_push(def.trigger.as_ref(), "wip"));
+    }
+
+    #[test]
+    fn matches_push_branches_ignore_only() {
+        let yaml = r#"
+pipeline:
+  steps:
+    - name: test
+      image: alpine
+  on:
+    push:
+      branches_ignore: ["docs/*"]
+"#;
+        let def = parse(yaml).unwrap();
+        assert!(matches_push(def.trigger.as_ref(), "feature/x"));
+        assert!(!matches_push(def.trigger.as_ref(), "docs/readme"));
     }
 
     #[test]
@@ -1918,6 +2486,45 @@ pipeline:
         parse(yaml).unwrap(); // should not error
     }
 
+    // -- variable_groups --
+
+    #[test]
+    fn parse_variable_groups() {
+        let yaml = r"
+pipeline:
+  variable_groups: [shared, prod-vars]
+  steps:
+    - name: deploy
+      image: alpine
+";
+        let def = parse(yaml).unwrap();
+        assert_eq!(def.variable_groups, vec!["shared", "prod-vars"]);
+        assert!(
+            parse("pipeline:\n  steps:\n    - name: a\n      image: alpine\n")
+                .unwrap()
+                .variable_groups
+                .is_empty()
+        );
+    }
+
+    #[test]
+    fn validate_variable_groups_rejects_bad_names_and_duplicates() {
+        for (groups, expected) in [
+            ("[prod vars]", "invalid variable group name"),
+            ("[shared, shared]", "more than once"),
+            ("[a, b, c, d, e, f, g, h, i, j, k]", "at most 10 groups"),
+        ] {
+            let yaml = format!(
+                "pipeline:\n  variable_groups: {groups}\n  steps:\n    - name: a\n      image: alpine\n"
+            );
+            let err = parse(&yaml).unwrap_err();
+            assert!(
+                matches!(err, PipelineError::InvalidDefinition(ref msg) if msg.contains(expected)),
+                "{groups}: got {err:?}"
+            );
+        }
+    }
+
     // -- topological_layers --
 
     #[test]
@@ -2024,6 +2631,11 @@ pipeline:
                 gitops: None,
                 deploy_watch: None,
                 artifacts: vec![],
+                services: vec![],
+                when: None,
+                cache: None,
+                clone_depth: None,
+                fetch_tags: false,
             },
             StepDef {
                 name: "b".into(),
@@ -2041,6 +2653,11 @@ pipeline:
                 gitops: None,
                 deploy_watch: None,
                 artifacts: vec![],
+                services: vec![],
+                when: None,
+                cache: None,
+                clone_depth: None,
+                fetch_tags: false,
             },
         ];
         assert!(topological_layers(&steps).is_none());
@@ -4000,4 +4617,375 @@ pipeline:
             "got: {err:?}"
         );
     }
+
+    #[test]
+    fn parse_step_with_services() {
+        let yaml = r"
+pipeline:
+  steps:
+    - name: test
+      image: rust:1.85
+      commands:
+        - cargo test
+      services:
+        - image: postgres:16
+          port: 5432
+          env:
+            POSTGRES_PASSWORD: test
+        - image: redis:7
+";
+        let def = parse(yaml).unwrap();
+        let services = &def.steps[0].services;
+        assert_eq!(services.len(), 2);
+        assert_eq!(services[0].image, "postgres:16");
+        assert_eq!(services[0].port, Some(5432));
+        assert_eq!(services[0].env.get("POSTGRES_PASSWORD").unwrap(), "test");
+        assert!(services[1].port.is_none());
+    }
+
+    #[test]
+    fn validate_service_image_rejects_shell_chars() {
+        let yaml = r"
+pipeline:
+  steps:
+    - name: test
+      image: alpine
+      services:
+        - image: postgres:16;rm
+";
+        let err = parse(yaml).unwrap_err();
+        assert!(
+            matches!(err, PipelineError::InvalidDefinition(ref msg) if msg.contains("services[0].image")),
+            "got: {err:?}"
+        );
+    }
+
+    #[test]
+    fn validate_services_only_on_command_steps() {
+        let yaml = r"
+pipeline:
+  steps:
+    - name: build
+      type: imagebuild
+      imageName: app
+      services:
+        - image: postgres:16
+";
+        let err = parse(yaml).unwrap_err();
+        assert!(
+            matches!(err, PipelineError::InvalidDefinition(ref msg) if msg.contains("only supported on command steps")),
+            "got: {err:?}"
+        );
+    }
+
+    #[test]
+    fn validate_service_env_name() {
+        let yaml = r"
+pipeline:
+  steps:
+    - name: test
+      image: alpine
+      services:
+        - image: postgres:16
+          env:
+            BAD-NAME: x
+";
+        assert!(parse(yaml).is_err());
+    }
+
+    #[test]
+    fn validate_too_many_services() {
+        let services = "        - image: redis:7\n".repeat(MAX_STEP_SERVICES + 1);
+        let yaml = format!(
+            "pipeline:\n  steps:\n    - name: test\n      image: alpine\n      services:\n{services}"
+        );
+        assert!(parse(&yaml).is_err());
+    }
+
+    // -- lint --
+
+    #[test]
+    fn lint_valid_definition_has_no_issues() {
+        let yaml = r"
+pipeline:
+  on:
+    push:
+      branches: [main]
+  steps:
+    - name: test
+      image: rust:1.85
+      commands: [cargo test]
+      services:
+        - image: postgres:16
+          port: 5432
+    - name: build
+      type: imagebuild
+      imageName: app
+      depends_on: [test]
+flags:
+  - key: new_ui
+    default_value: false
+";
+        assert_eq!(lint(yaml), Vec::new());
+        assert!(parse(yaml).is_ok());
+    }
+
+    #[test]
+    fn lint_reports_syntax_error_with_location() {
+        let issues = lint("pipeline:\n  steps: [\n    - name: a\n");
+        assert_eq!(issues.len(), 1);
+        assert!(issues[0].line.is_some(), "{issues:?}");
+        assert!(issues[0].path.is_none());
+    }
+
+    #[test]
+    fn lint_reports_unknown_keys_with_paths() {
+        let yaml = r"
+pipeline:
+  steps:
+    - name: test
+      image: alpine
+      comands: [echo hi]
+      services:
+        - image: redis:7
+          ports: 6379
+  on:
+    push:
+      branch: [main]
+colour: blue
+";
+        let paths: Vec<_> = lint(yaml).into_iter().filter_map(|i| i.path).collect();
+        assert_eq!(
+            paths,
+            [
+                "colour",
+                "pipeline.on.push.branch",
+                "pipeline.steps[0].comands",
+                "pipeline.steps[0].services[0].ports",
+            ]
+        );
+    }
+
+    #[test]
+    fn lint_reports_every_invalid_step() {
+        let yaml = r"
+pipeline:
+  steps:
+    - name: a
+      image: 'alpine; rm -rf /'
+    - name: b
+      image: alpine
+    - name: c
+";
+        let issues = lint(yaml);
+        assert_eq!(issues.len(), 2, "{issues:?}");
+        assert_eq!(issues[0].path.as_deref(), Some("pipeline.steps[0]"));
+        assert!(issues[0].message.contains("image"));
+        assert_eq!(issues[1].path.as_deref(), Some("pipeline.steps[2]"));
+        assert!(issues[1].message.contains("missing an image"));
+    }
+
+    #[test]
+    fn lint_names_steps_in_cycle() {
+        let yaml = r"
+pipeline:
+  steps:
+    - name: setup
+      image: alpine
+    - name: a
+      image: alpine
+      depends_on: [setup, c]
+    - name: b
+      image: alpine
+      depends_on: [a]
+    - name: c
+      image: alpine
+      depends_on: [b]
+    - name: report
+      image: alpine
+      depends_on: [c]
+";
+        let issues = lint(yaml);
+        assert_eq!(issues.len(), 1, "{issues:?}");
+        assert_eq!(issues[0].path.as_deref(), Some("pipeline.steps"));
+        assert!(
+            issues[0].message.ends_with("between steps 'a', 'b', 'c'"),
+            "{}",
+            issues[0].message
+        );
+    }
+
+    #[test]
+    fn lint_reports_type_errors() {
+        let issues = lint("pipeline:\n  steps:\n    - image: alpine\n");
+        assert_eq!(issues.len(), 1, "{issues:?}");
+        assert!(issues[0].message.contains("name"), "{issues:?}");
+        assert!(issues[0].line.is_some());
+    }
+
+    #[test]
+    fn struct_fields_uses_serde_names() {
+        let fields = struct_fields::<StepDef>();
+        assert!(fields.contains(&"type"));
+        assert!(fields.contains(&"imageName"));
+        assert!(!fields.contains(&"step_type"));
+    }
+
+    // -- when --
+
+    #[test]
+    fn parse_when_expression_and_rules() {
+        let yaml = r#"
+pipeline:
+  steps:
+    - name: deploy
+      image: alpine
+      when: branch == "main"
+    - name: notify
+      image: alpine
+      when: on_failure
+    - name: docs
+      image: alpine
+      when:
+        if: event == "push"
+        changes: ["docs/**"]
+"#;
+        let def = parse(yaml).unwrap();
+        assert_eq!(
+            def.steps[0].when.as_ref().unwrap().expr(),
+            Some(r#"branch == "main""#)
+        );
+        assert!(def.steps[0].when.as_ref().unwrap().changes().is_empty());
+        assert_eq!(
+            def.steps[1].when.as_ref().unwrap().expr(),
+            Some("on_failure")
+        );
+        let docs = def.steps[2].when.as_ref().unwrap();
+        assert_eq!(docs.expr(), Some(r#"event == "push""#));
+        assert_eq!(docs.changes(), ["docs/**"]);
+    }
+
+    #[test]
+    fn validate_when_rejects_bad_expression() {
+        let yaml = r"
+pipeline:
+  steps:
+    - name: deploy
+      image: alpine
+      when: branch = main
+";
+        let err = parse(yaml).unwrap_err();
+        assert!(
+            matches!(err, PipelineError::InvalidDefinition(ref msg) if msg.contains("step 'deploy': when")),
+            "got: {err:?}"
+        );
+    }
+
+    #[test]
+    fn validate_when_rules_need_a_condition() {
+        let yaml = r"
+pipeline:
+  steps:
+    - name: deploy
+      image: alpine
+      when:
+        changes: []
+";
+        assert!(parse(yaml).is_err());
+    }
+
+    #[test]
+    fn lint_checks_when_keys() {
+        let yaml = r"
+pipeline:
+  steps:
+    - name: deploy
+      image: alpine
+      when:
+        change: [src/**]
+";
+        let paths: Vec<_> = lint(yaml).into_iter().filter_map(|i| i.path).collect();
+        assert_eq!(
+            paths,
+            ["pipeline.steps[0].when.change", "pipeline.steps[0]"]
+        );
+    }
+
+    #[test]
+    fn parse_step_cache() {
+        let yaml = r"
+pipeline:
+  steps:
+    - name: build
+      image: rust:1.85
+      commands:
+        - cargo build
+      cache:
+        key: cargo-{{ hashFiles('Cargo.lock') }}
+        restore_keys: [cargo-]
+        paths: [target, .cargo]
+";
+        let def = parse(yaml).unwrap();
+        let cache = def.steps[0].cache.as_ref().unwrap();
+        assert_eq!(cache.key, "cargo-{{ hashFiles('Cargo.lock') }}");
+        assert_eq!(cache.restore_keys, vec!["cargo-"]);
+        assert_eq!(cache.paths, vec!["target", ".cargo"]);
+    }
+
+    #[test]
+    fn validate_step_cache() {
+        for cache in [
+            "{key: deps, paths: []}",
+            "{key: deps, paths: [/root/.cargo]}",
+            "{key: deps, paths: [../outside]}",
+            "{key: '', paths: [target]}",
+            "{key: 'deps-{{ branch }}', paths: [target]}",
+            "{key: deps, restore_keys: [''], paths: [target]}",
+        ] {
+            let yaml = format!(
+                "pipeline:\n  steps:\n    - name: build\n      image: alpine\n      cache: {cache}\n"
+            );
+            assert!(parse(&yaml).is_err(), "cache {cache} should be rejected");
+        }
+        let yaml = r"
+pipeline:
+  steps:
+    - name: build
+      type: imagebuild
+      imageName: app
+      cache:
+        key: deps
+        paths: [target]
+";
+        assert!(parse(yaml).is_err(), "cache is only for command steps");
+    }
+
+    #[test]
+    fn validate_step_clone_options() {
+        let step = |extra: &str| {
+            format!("pipeline:\n  steps:\n    - name: version\n      image: alpine/git\n{extra}")
+        };
+        let def = parse(&step("      clone_depth: 0\n      fetch_tags: true\n")).unwrap();
+        assert_eq!(def.steps[0].clone_depth, Some(0));
+        assert!(def.steps[0].fetch_tags);
+        let def = parse(&step("")).unwrap();
+        assert_eq!(def.steps[0].clone_depth, None);
+        assert!(!def.steps[0].fetch_tags);
+
+        assert!(parse(&step(&format!("      clone_depth: {MAX_CLONE_DEPTH}\n"))).is_ok());
+        let too_deep = MAX_CLONE_DEPTH + 1;
+        assert!(parse(&step(&format!("      clone_depth: {too_deep}\n"))).is_err());
+        assert!(parse(&step("      clone_depth: -1\n")).is_err());
+
+        let yaml = r"
+pipeline:
+  steps:
+    - name: sync
+      type: gitops_sync
+      gitops:
+        copy: [deploy/]
+      fetch_tags: true
+";
+        assert!(parse(yaml).is_err(), "no clone on gitops_sync steps");
+    }
 }
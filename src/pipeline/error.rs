@@ -12,6 +12,9 @@ pub enum PipelineError {
     #[error("pipeline not found")]
     NotFound,
 
+    #[error("project is archived")]
+    ProjectArchived,
+
     #[error("step failed: {name} (exit code {exit_code})")]
     StepFailed { name: String, exit_code: i32 },
 
@@ -33,6 +36,7 @@ impl From<PipelineError> for ApiError {
         match err {
             PipelineError::InvalidDefinition(msg) => Self::BadRequest(msg),
             PipelineError::NotFound => Self::NotFound("pipeline".into()),
+            PipelineError::ProjectArchived => Self::Locked("project is archived".into()),
             PipelineError::StepFailed { .. } => Self::Internal(err.into()),
             PipelineError::Db(e) => Self::from(e),
             PipelineError::Kube(e) => Self::from(e),
@@ -58,6 +62,12 @@ mod tests {
         assert!(matches!(api, ApiError::NotFound(msg) if msg == "pipeline"));
     }
 
+    #[test]
+    fn project_archived_maps_to_locked() {
+        let api: ApiError = PipelineError::ProjectArchived.into();
+        assert!(matches!(api, ApiError::Locked(_)));
+    }
+
     #[test]
     fn step_failed_maps_to_internal() {
         let api: ApiError = PipelineError::StepFailed {
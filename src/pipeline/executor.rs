@@ -18,7 +18,7 @@ use uuid::Uuid;
 
 use crate::auth::token;
 use crate::pipeline::PipelineStatus;
-use crate::store::AppState;
+use crate::store::{AppState, lifecycle};
 
 use super::error::PipelineError;
 
@@ -26,11 +26,31 @@ use super::error::PipelineError;
 // Background executor loop
 // ---------------------------------------------------------------------------
 
+/// How often the executor refreshes `heartbeat_at` on the pipelines it runs.
+const HEARTBEAT_INTERVAL: std::time::Duration = std::time::Duration::from_secs(5);
+
+/// A running pipeline whose heartbeat is older than this has lost its executor.
+/// Far longer than `HEARTBEAT_INTERVAL`, so a replica stalled by a slow
+/// database or a long GC pause keeps its pods rather than having them deleted
+/// under a live step.
+const ORPHAN_AFTER_SECS: f64 = 600.0;
+
+/// Pipelines claimed per poll.
+const CLAIM_BATCH: i64 = 5;
+
+/// Advisory lock key serialising claims across replicas, so the per-project
+/// limit counts every running pipeline.
+const CLAIM_LOCK: i64 = 0x7069_7065_5f63_6c6d;
+
 /// Background task that polls for pending pipelines and executes them.
+///
+/// On cancellation, in-flight pipelines get `pipeline_drain_timeout_secs` to
+/// finish; whatever is still running after that is aborted and requeued.
 pub async fn run(state: AppState, cancel: tokio_util::sync::CancellationToken) {
     tracing::info!("pipeline executor started");
 
-    let mut interval = tokio::time::interval(std::time::Duration::from_secs(5));
+    let mut interval = tokio::time::interval(HEARTBEAT_INTERVAL);
+    let mut in_flight = InFlight::default();
 
     state.task_registry.register("pipeline_executor", 10);
 
@@ -38,8 +58,12 @@ pub async fn run(state: AppState, cancel: tokio_util::sync::CancellationToken) {
         tokio::select! {
             () = cancel.cancelled() => {
                 tracing::info!("pipeline executor shutting down");
+                drain_in_flight(&state, in_flight).await;
                 break;
             }
+            Some(done) = in_flight.tasks.join_next_with_id() => {
+                in_flight.finished(&done);
+            }
             _ = interval.tick() => {
                 let iter_trace_id = uuid::Uuid::new_v4().to_string().replace('-', "");
                 let span = tracing::info_span!(
@@ -49,7 +73,11 @@ pub async fn run(state: AppState, cancel: tokio_util::sync::CancellationToken) {
                     source = "system",
                 );
                 async {
-                    match poll_pending(&state).await {
+                    touch_heartbeats(&state.pool, &in_flight.pipeline_ids()).await;
+                    if let Err(e) = recover_orphaned_pipelines(&state).await {
+                        tracing::error!(error = %e, "error recovering orphaned pipelines");
+                    }
+                    match poll_pending(&state, &mut in_flight).await {
                         Ok(()) => state.task_registry.heartbeat("pipeline_executor"),
                         Err(e) => {
                             state.task_registry.report_error("pipeline_executor", &e.to_string());
@@ -68,7 +96,7 @@ pub async fn run(state: AppState, cancel: tokio_util::sync::CancellationToken) {
                 );
                 async {
                     // Immediate poll on notification
-                    if let Err(e) = poll_pending(&state).await {
+                    if let Err(e) = poll_pending(&state, &mut in_flight).await {
                         tracing::error!(error = %e, "error polling pending pipelines (notified)");
                     }
                 }.instrument(span).await;
@@ -79,35 +107,250 @@ pub async fn run(state: AppState, cancel: tokio_util::sync::CancellationToken) {
     }
 }
 
+/// Pipelines this replica is currently executing.
+#[derive(Default)]
+struct InFlight {
+    tasks: tokio::task::JoinSet<()>,
+    pipelines: std::collections::HashMap<tokio::task::Id, Uuid>,
+}
+
+impl InFlight {
+    fn spawn(&mut self, state: AppState, pipeline_id: Uuid) {
+        let handle = self.tasks.spawn(async move {
+            if let Err(e) = execute_pipeline(&state, pipeline_id).await {
+                tracing::error!(error = %e, %pipeline_id, "pipeline execution failed");
+                let _ = mark_pipeline_failed(&state.pool, pipeline_id).await;
+            }
+        });
+        self.pipelines.insert(handle.id(), pipeline_id);
+    }
+
+    fn finished(&mut self, done: &Result<(tokio::task::Id, ()), tokio::task::JoinError>) {
+        let id = match done {
+            Ok((id, ())) => *id,
+            Err(e) => e.id(),
+        };
+        self.pipelines.remove(&id);
+    }
+
+    fn pipeline_ids(&self) -> Vec<Uuid> {
+        self.pipelines.values().copied().collect()
+    }
+}
+
+/// Wait (bounded) for in-flight pipelines to finish, then abort and requeue the rest.
+async fn drain_in_flight(state: &AppState, mut in_flight: InFlight) {
+    if in_flight.pipelines.is_empty() {
+        return;
+    }
+    let drain_secs = state.config.pipeline_drain_timeout_secs;
+    tracing::info!(
+        count = in_flight.pipelines.len(),
+        drain_secs,
+        "waiting for in-flight pipelines to finish"
+    );
+
+    let deadline = tokio::time::sleep(std::time::Duration::from_secs(drain_secs));
+    tokio::pin!(deadline);
+    let mut heartbeat = tokio::time::interval(HEARTBEAT_INTERVAL);
+    while !in_flight.pipelines.is_empty() {
+        tokio::select! {
+            () = &mut deadline => break,
+            Some(done) = in_flight.tasks.join_next_with_id() => in_flight.finished(&done),
+            _ = heartbeat.tick() => touch_heartbeats(&state.pool, &in_flight.pipeline_ids()).await,
+        }
+    }
+
+    let stranded = in_flight.pipeline_ids();
+    if stranded.is_empty() {
+        tracing::info!("in-flight pipelines drained");
+        return;
+    }
+    in_flight.tasks.shutdown().await;
+    match requeue_pipelines(state, &stranded).await {
+        Ok(requeued) => tracing::warn!(
+            count = requeued,
+            "in-flight pipelines did not finish before shutdown; requeued as pending"
+        ),
+        Err(e) => tracing::error!(error = %e, "failed to requeue in-flight pipelines"),
+    }
+}
+
+/// Mark the given running pipelines as alive.
+async fn touch_heartbeats(pool: &PgPool, pipeline_ids: &[Uuid]) {
+    if pipeline_ids.is_empty() {
+        return;
+    }
+    if let Err(e) = sqlx::query!(
+        "UPDATE pipelines SET heartbeat_at = now() WHERE id = ANY($1) AND status = 'running'",
+        pipeline_ids,
+    )
+    .execute(pool)
+    .await
+    {
+        tracing::warn!(error = %e, "failed to update pipeline heartbeats");
+    }
+}
+
+/// Requeue running pipelines whose executor stopped heartbeating — e.g. the
+/// replica crashed, or was killed before it could drain. Returns how many
+/// pipelines were requeued.
+pub async fn recover_orphaned_pipelines(state: &AppState) -> Result<usize, PipelineError> {
+    // Pipelines claimed before heartbeats existed fall back to `started_at`.
+    let orphaned: Vec<Uuid> = sqlx::query_scalar!(
+        "SELECT id FROM pipelines
+         WHERE status = 'running'
+           AND COALESCE(heartbeat_at, started_at, created_at)
+               < now() - make_interval(secs => $1)",
+        ORPHAN_AFTER_SECS,
+    )
+    .fetch_all(&state.pool)
+    .await?;
+    if orphaned.is_empty() {
+        return Ok(0);
+    }
+    let requeued = requeue_pipelines(state, &orphaned).await?;
+    tracing::warn!(count = requeued, "requeued orphaned running pipelines");
+    Ok(requeued)
+}
+
+/// Put running pipelines back to `pending` so they run again from the first
+/// step, and remove what the interrupted run left in its namespace.
+///
+/// Returns how many pipelines were requeued; pipelines that already left
+/// `running` are skipped.
+async fn requeue_pipelines(
+    state: &AppState,
+    pipeline_ids: &[Uuid],
+) -> Result<usize, PipelineError> {
+    let mut tx = state.pool.begin().await?;
+    // Running -> Pending is the requeue transition of the state machine.
+    let requeued = sqlx::query!(
+        "UPDATE pipelines pl
+         SET status = $2, started_at = NULL, heartbeat_at = NULL
+         FROM projects p
+         WHERE p.id = pl.project_id AND pl.id = ANY($1) AND pl.status = 'running'
+         RETURNING pl.id, p.namespace_slug",
+        pipeline_ids,
+        PipelineStatus::Pending.as_str(),
+    )
+    .fetch_all(&mut *tx)
+    .await?;
+    let ids: Vec<Uuid> = requeued.iter().map(|row| row.id).collect();
+    sqlx::query!(
+        "UPDATE pipeline_steps
+         SET status = 'pending', exit_code = NULL, duration_ms = NULL, log_ref = NULL,
+             started_at = NULL, finished_at = NULL
+         WHERE pipeline_id = ANY($1)",
+        &ids,
+    )
+    .execute(&mut *tx)
+    .await?;
+    tx.commit().await?;
+
+    for row in &requeued {
+        release_interrupted_run(state, row.id, &row.namespace_slug).await;
+    }
+    Ok(requeued.len())
+}
+
+/// Best-effort cleanup of an interrupted run so the retry can recreate its
+/// pods and secrets under the same names.
+async fn release_interrupted_run(state: &AppState, pipeline_id: Uuid, namespace_slug: &str) {
+    let short_id = &pipeline_id.to_string()[..8];
+    let namespace = crate::deployer::namespace::pipeline_namespace_name(
+        &state.config,
+        namespace_slug,
+        short_id,
+    );
+    let selector = ListParams::default().labels(&format!("platform.io/pipeline={pipeline_id}"));
+    let force = DeleteParams {
+        grace_period_seconds: Some(0),
+        ..Default::default()
+    };
+
+    let pods: Api<Pod> = Api::namespaced(state.kube.clone(), &namespace);
+    if let Err(e) = pods.delete_collection(&force, &selector).await {
+        tracing::warn!(error = %e, %pipeline_id, %namespace, "failed to delete interrupted pipeline pods");
+    }
+    let secrets: Api<Secret> = Api::namespaced(state.kube.clone(), &namespace);
+    if let Err(e) = secrets.delete_collection(&force, &selector).await {
+        tracing::warn!(error = %e, %pipeline_id, %namespace, "failed to delete interrupted pipeline secrets");
+    }
+
+    // Short-lived git and registry tokens minted for the interrupted run.
+    if let Err(e) = sqlx::query!(
+        "DELETE FROM api_tokens WHERE name = ANY($1)",
+        &[
+            format!("pipeline-git-{pipeline_id}"),
+            format!("pipeline-{pipeline_id}"),
+        ][..],
+    )
+    .execute(&state.pool)
+    .await
+    {
+        tracing::warn!(error = %e, %pipeline_id, "failed to delete interrupted pipeline tokens");
+    }
+}
+
 /// Find pending pipelines, atomically claim them, and spawn execution tasks.
 ///
-/// Uses `FOR UPDATE SKIP LOCKED` so multiple replicas don't race on the same rows.
-async fn poll_pending(state: &AppState) -> Result<(), PipelineError> {
-    let claimed = sqlx::query_scalar!(
-        r#"
+/// Claims are serialised across replicas with an advisory lock, so two
+/// replicas never claim the same row or together exceed a project's limit.
+async fn poll_pending(state: &AppState, in_flight: &mut InFlight) -> Result<(), PipelineError> {
+    let per_project = i64::try_from(state.config.pipeline_max_concurrent_per_project)
+        .unwrap_or(i64::MAX)
+        .max(1);
+
+    let mut tx = state.pool.begin().await?;
+    sqlx::query!("SELECT pg_advisory_xact_lock($1)", CLAIM_LOCK)
+        .execute(&mut *tx)
+        .await?;
+
+    // Highest priority first. Within a priority, round-robin across projects:
+    // every project's oldest pending pipeline comes before any project's
+    // second, and a project already running `per_project` pipelines gets none.
+    // Within a project, by priority then FIFO.
+    let claimed: Vec<Uuid> = sqlx::query_scalar!(
+        r"
+        WITH running AS (
+            SELECT project_id, COUNT(*) AS n FROM pipelines
+            WHERE status = 'running'
+            GROUP BY project_id
+        ),
+        queued AS (
+            SELECT p.id, p.created_at, q.rank,
+                   COALESCE(r.n, 0)
+                   + ROW_NUMBER() OVER (
+                       PARTITION BY p.project_id ORDER BY q.rank, p.created_at, p.id
+                   ) AS slot
+            FROM pipelines p
+            CROSS JOIN LATERAL (
+                SELECT CASE p.priority WHEN 'high' THEN 0 WHEN 'normal' THEN 1 ELSE 2 END AS rank
+            ) q
+            LEFT JOIN running r ON r.project_id = p.project_id
+            WHERE p.status = 'pending'
+        )
         UPDATE pipelines
-        SET status = 'running', started_at = now()
+        SET status = 'running', started_at = now(), heartbeat_at = now()
         WHERE id IN (
-            SELECT id FROM pipelines
-            WHERE status = 'pending'
-            ORDER BY created_at ASC
-            LIMIT 5
-            FOR UPDATE SKIP LOCKED
+            SELECT id FROM queued
+            WHERE slot <= $1
+            ORDER BY rank, slot, created_at
+            LIMIT $2
         )
+        AND status = 'pending'
         RETURNING id
-        "#,
+        ",
+        per_project,
+        CLAIM_BATCH,
     )
-    .fetch_all(&state.pool)
+    .fetch_all(&mut *tx)
     .await?;
+    tx.commit().await?;
 
     for pipeline_id in claimed {
-        let state = state.clone();
-        tokio::spawn(async move {
-            if let Err(e) = execute_pipeline(&state, pipeline_id).await {
-                tracing::error!(error = %e, %pipeline_id, "pipeline execution failed");
-                let _ = mark_pipeline_failed(&state.pool, pipeline_id).await;
-            }
-        });
+        in_flight.spawn(state.clone(), pipeline_id);
     }
 
     Ok(())
@@ -178,6 +421,19 @@ async fn execute_pipeline(state: &AppState, pipeline_id: Uuid) -> Result<(), Pip
         short_id,
     );
     let git_secret_name = format!("pl-git-{short_id}");
+    let master_key = state
+        .config
+        .master_key
+        .as_deref()
+        .and_then(|k| crate::secrets::engine::parse_master_key(k).ok());
+    let inputs = load_trigger_inputs(&state.pool, master_key.as_ref(), pipeline_id).await?;
+    let group_variables = super::variable_groups::resolve(
+        &state.pool,
+        master_key.as_ref(),
+        project_id,
+        &inputs.variable_groups,
+    )
+    .await?;
 
     let meta = PipelineMeta {
         git_ref: pipeline.git_ref,
@@ -191,6 +447,10 @@ async fn execute_pipeline(state: &AppState, pipeline_id: Uuid) -> Result<(), Pip
         namespace_slug: pipeline.namespace_slug,
         otlp_token,
         git_secret_name,
+        triggered_by: pipeline.triggered_by,
+        variables: inputs.variables,
+        changed_files: inputs.changed_files,
+        group_variables,
     };
 
     // Ensure pipeline namespace exists (unique per pipeline run)
@@ -369,6 +629,8 @@ async fn finalize_pipeline(
         &state.webhook_semaphore,
     )
     .await;
+    crate::notify::chat::on_pipeline_finished(state, project_id, pipeline_id, final_status_str)
+        .await;
 
     let log_level = if all_succeeded { "info" } else { "error" };
     emit_pipeline_log(
@@ -387,6 +649,44 @@ async fn finalize_pipeline(
     Ok(())
 }
 
+/// What a pipeline was created with besides its steps.
+struct TriggerInputs {
+    variables: Vec<(String, String)>,
+    changed_files: Option<Vec<String>>,
+    variable_groups: Vec<String>,
+}
+
+/// Load the variables a pipeline was triggered with, the files its push
+/// changed and the variable groups its definition references.
+async fn load_trigger_inputs(
+    pool: &PgPool,
+    master_key: Option<&crate::secrets::engine::MasterKey>,
+    pipeline_id: Uuid,
+) -> Result<TriggerInputs, PipelineError> {
+    let row = sqlx::query!(
+        "SELECT encrypted_variables, changed_files, variable_groups FROM pipelines WHERE id = $1",
+        pipeline_id,
+    )
+    .fetch_one(pool)
+    .await?;
+    let variables = match row.encrypted_variables {
+        Some(encrypted) => {
+            let master_key = master_key.ok_or_else(|| {
+                anyhow::anyhow!("secrets engine not configured: cannot decrypt trigger variables")
+            })?;
+            super::trigger::decrypt_variables(master_key, &encrypted)?
+                .into_iter()
+                .collect()
+        }
+        None => Vec::new(),
+    };
+    Ok(TriggerInputs {
+        variables,
+        changed_files: row.changed_files,
+        variable_groups: row.variable_groups,
+    })
+}
+
 /// Parameters extracted from pipeline + project join query.
 #[derive(Debug)]
 struct PipelineMeta {
@@ -407,6 +707,14 @@ struct PipelineMeta {
     otlp_token: Option<String>,
     /// K8s Secret name for git auth token (S31).
     git_secret_name: String,
+    /// User who triggered the pipeline; actor for secret access audit entries.
+    triggered_by: Option<Uuid>,
+    /// Variables passed with a manual trigger, exposed to every step.
+    variables: Vec<(String, String)>,
+    /// Files changed by the triggering push; `None` when unknown.
+    changed_files: Option<Vec<String>>,
+    /// Variables from the definition's `variable_groups`.
+    group_variables: super::variable_groups::GroupVariables,
 }
 
 /// A pipeline step row loaded from the database.
@@ -420,6 +728,8 @@ struct StepRow {
     commands: Vec<String>,
     condition_events: Vec<String>,
     condition_branches: Vec<String>,
+    /// Serialized [`super::definition::WhenDef`], if the step has a `when`.
+    condition_when: Option<serde_json::Value>,
     deploy_test: Option<serde_json::Value>,
     depends_on: Vec<String>,
     environment: Option<serde_json::Value>,
@@ -467,7 +777,7 @@ async fn run_all_steps(
 ) -> Result<bool, PipelineError> {
     let steps = sqlx::query_as::<_, StepRow>(
         "SELECT id, step_order, name, image, commands,
-               condition_events, condition_branches,
+               condition_events, condition_branches, condition_when,
                deploy_test, depends_on, environment, gate,
                step_type, step_config
         FROM pipeline_steps
@@ -522,6 +832,7 @@ async fn run_steps_sequential(
 ) -> Result<bool, PipelineError> {
     let pods: Api<Pod> = Api::namespaced(state.kube.clone(), &pipeline.namespace);
     let branch = extract_branch(&pipeline.git_ref);
+    let mut failed = false;
 
     for step in steps {
         if is_cancelled(&state.pool, pipeline_id).await? {
@@ -529,20 +840,15 @@ async fn run_steps_sequential(
             return Ok(false);
         }
 
-        let condition = step_condition_from_row(step);
-        if !super::definition::step_matches(condition.as_ref(), &pipeline.trigger_type, branch) {
+        if let Some(reason) = step_skip_reason(step, pipeline, branch, failed) {
             tracing::info!(
                 step = %step.name,
                 trigger = %pipeline.trigger_type,
                 %branch,
-                "step skipped (condition not matched)"
+                %reason,
+                "step skipped"
             );
-            sqlx::query!(
-                "UPDATE pipeline_steps SET status = 'skipped', finished_at = now() WHERE id = $1",
-                step.id
-            )
-            .execute(&state.pool)
-            .await?;
+            skip_step(&state.pool, step.id, &reason).await?;
             continue;
         }
 
@@ -557,14 +863,10 @@ async fn run_steps_sequential(
             secrets,
         )
         .await?;
-
-        if !succeeded {
-            skip_remaining_after(&state.pool, pipeline_id, step.step_order).await?;
-            return Ok(false);
-        }
+        failed |= !succeeded;
     }
 
-    Ok(true)
+    Ok(!failed)
 }
 
 /// DAG-based parallel execution.
@@ -614,11 +916,6 @@ async fn run_steps_dag(
     loop {
         // Spawn ready steps
         while let Some(idx) = ready.pop() {
-            if skipped.contains(&idx) {
-                completed.insert(idx);
-                continue;
-            }
-
             if is_cancelled(&state.pool, pipeline_id).await? {
                 skip_remaining_steps(&state.pool, pipeline_id).await?;
                 return Ok(false);
@@ -626,22 +923,16 @@ async fn run_steps_dag(
 
             let step = &steps[idx];
 
-            // Check per-step condition
-            let condition = step_condition_from_row(step);
-            if !super::definition::step_matches(condition.as_ref(), &pipeline.trigger_type, branch)
-            {
+            // `skipped` holds the steps downstream of a failure
+            if let Some(reason) = step_skip_reason(step, pipeline, branch, skipped.contains(&idx)) {
                 tracing::info!(
                     step = %step.name,
                     trigger = %pipeline.trigger_type,
                     %branch,
-                    "step skipped (condition not matched)"
+                    %reason,
+                    "step skipped"
                 );
-                sqlx::query!(
-                    "UPDATE pipeline_steps SET status = 'skipped', finished_at = now() WHERE id = $1",
-                    step.id
-                )
-                .execute(&state.pool)
-                .await?;
+                skip_step(&state.pool, step.id, &reason).await?;
                 completed.insert(idx);
                 // Release dependents even for skipped steps (they still "completed")
                 for &dep_idx in &dependents[idx] {
@@ -677,6 +968,10 @@ async fn run_steps_dag(
                 namespace_slug: pipeline.namespace_slug.clone(),
                 otlp_token: pipeline.otlp_token.clone(),
                 git_secret_name: pipeline.git_secret_name.clone(),
+                triggered_by: pipeline.triggered_by,
+                variables: pipeline.variables.clone(),
+                changed_files: pipeline.changed_files.clone(),
+                group_variables: pipeline.group_variables.clone(),
             };
             let secrets = secrets.to_vec();
             let registry_secret = registry_secret.map(String::from);
@@ -694,6 +989,7 @@ async fn run_steps_dag(
                     commands: step_commands,
                     condition_events: vec![],
                     condition_branches: vec![],
+                    condition_when: None,
                     deploy_test: step_deploy_test,
                     depends_on: vec![],
                     environment: step_env,
@@ -741,28 +1037,16 @@ async fn run_steps_dag(
             }
         };
 
-        if succeeded {
-            // Release dependents
-            for &dep_idx in &dependents[idx] {
-                in_degree[dep_idx] -= 1;
-                if in_degree[dep_idx] == 0 && !skipped.contains(&dep_idx) {
-                    ready.push(dep_idx);
-                }
-            }
-        } else {
+        if !succeeded {
             any_failure = true;
-            // Mark all transitive dependents as skipped
+            // Dependents still get scheduled so `on_failure` / `always` steps can run;
+            // the rest are skipped when they come up.
             mark_transitive_dependents_skipped(idx, &dependents, &mut skipped, &completed);
-            // Skip those steps in DB
-            for &s_idx in &skipped {
-                if !completed.contains(&s_idx) {
-                    let _ = sqlx::query!(
-                        "UPDATE pipeline_steps SET status = 'skipped', finished_at = now() WHERE id = $1 AND status = 'pending'",
-                        steps[s_idx].id
-                    )
-                    .execute(&state.pool)
-                    .await;
-                }
+        }
+        for &dep_idx in &dependents[idx] {
+            in_degree[dep_idx] -= 1;
+            if in_degree[dep_idx] == 0 {
+                ready.push(dep_idx);
             }
         }
     }
@@ -770,7 +1054,7 @@ async fn run_steps_dag(
     Ok(!any_failure)
 }
 
-/// Mark all transitive dependents of a failed step as skipped.
+/// Mark all transitive dependents of a failed step as downstream of a failure.
 fn mark_transitive_dependents_skipped(
     failed_idx: usize,
     dependents: &[Vec<usize>],
@@ -807,6 +1091,21 @@ async fn execute_step_dispatch(
     registry_secret: Option<&str>,
     secrets: &[(String, String)],
 ) -> Result<bool, PipelineError> {
+    if !matches!(step.step_type.as_str(), "gitops_sync" | "deploy_watch") {
+        crate::secrets::access::record_access(
+            &state.pool,
+            &state.audit_tx,
+            project_id,
+            pipeline.triggered_by,
+            secrets.iter().map(|(name, _)| name.as_str()),
+            crate::secrets::access::AccessPurpose::PipelineStep {
+                pipeline_id,
+                step: &step.name,
+            },
+        )
+        .await;
+    }
+
     match step.step_type.as_str() {
         "deploy_test" => {
             execute_deploy_test_step(
@@ -892,6 +1191,60 @@ fn step_condition_from_row(step: &StepRow) -> Option<super::definition::StepCond
     })
 }
 
+/// Why a step should be skipped, or `None` if it should run.
+///
+/// `failed` is whether an earlier step (in DAG mode, an upstream step) failed.
+/// Steps without a `when` expression only run while nothing has failed. A
+/// `changes` filter is ignored when the changed files are unknown.
+fn step_skip_reason(
+    step: &StepRow,
+    pipeline: &PipelineMeta,
+    branch: &str,
+    failed: bool,
+) -> Option<String> {
+    let condition = step_condition_from_row(step);
+    if !super::definition::step_matches(condition.as_ref(), &pipeline.trigger_type, branch) {
+        return Some("condition not matched".into());
+    }
+    let when: Option<super::definition::WhenDef> = step
+        .condition_when
+        .clone()
+        .and_then(|v| serde_json::from_value(v).ok());
+    let Some(when) = when else {
+        return failed.then(|| "an earlier step failed".into());
+    };
+
+    match when.expr() {
+        Some(expr) => {
+            // Validated when the pipeline was created
+            let parsed = super::when::WhenExpr::parse(expr).ok()?;
+            let ctx = |failed| super::when::WhenContext {
+                branch,
+                event: &pipeline.trigger_type,
+                failed,
+            };
+            if !parsed.matches(&ctx(failed)) {
+                return Some(if failed && parsed.matches(&ctx(false)) {
+                    "an earlier step failed".into()
+                } else {
+                    format!("when condition not met: {expr}")
+                });
+            }
+        }
+        None if failed => return Some("an earlier step failed".into()),
+        None => {}
+    }
+
+    let changes = when.changes();
+    if let Some(files) = &pipeline.changed_files
+        && !changes.is_empty()
+        && !super::when::changes_match(changes, files)
+    {
+        return Some(format!("no changed files match {}", changes.join(", ")));
+    }
+    None
+}
+
 /// Execute one pipeline step as a K8s pod. Returns true on success.
 #[allow(clippy::too_many_arguments, clippy::too_many_lines)]
 async fn execute_single_step(
@@ -929,7 +1282,9 @@ async fn execute_single_step(
 
     let pod_name = format!("pl-{}-{}", &pipeline_id.to_string()[..8], slug(&step.name));
     let step_artifacts = extract_artifact_defs(step.step_config.as_ref());
-    let pod_spec = build_pod_spec(&PodSpecParams {
+    let step_services = extract_service_defs(step.step_config.as_ref());
+    let step_cache = resolve_step_cache(state, project_id, pipeline, step).await;
+    let mut pod_spec = build_pod_spec(&PodSpecParams {
         pod_name: &pod_name,
         pipeline_id,
         project_id,
@@ -939,17 +1294,22 @@ async fn execute_single_step(
         env_vars: &env_vars,
         repo_clone_url: &pipeline.repo_clone_url,
         git_ref: &pipeline.git_ref,
+        commit_sha: pipeline.commit_sha.as_deref(),
         registry_secret,
         git_secret_name: Some(&pipeline.git_secret_name),
         step_type: &step.step_type,
         git_clone_image: &state.config.git_clone_image,
         has_artifacts: !step_artifacts.is_empty(),
+        has_cache: step_cache.is_some(),
         proxy_binary_path: if state.config.dev_mode {
             state.config.proxy_binary_path.as_deref()
         } else {
             None
         },
+        services: &step_services,
+        clone: extract_clone_options(step.step_config.as_ref()),
     });
+    crate::deployer::scheduling::apply(&mut pod_spec, &state.config);
 
     let step_svc = format!("pipeline/{}/{}", pipeline.project_name, step.name);
 
@@ -980,6 +1340,8 @@ async fn execute_single_step(
         step.id,
         &step.name,
         &step_artifacts,
+        step_cache.as_ref(),
+        &pipeline.group_variables.masked,
     )
     .await;
     let duration_ms = i32::try_from(start.elapsed().as_millis()).unwrap_or(i32::MAX);
@@ -1057,20 +1419,48 @@ async fn run_step(
     step_id: Uuid,
     step_name: &str,
     artifact_defs: &[super::definition::ArtifactDef],
+    cache: Option<&StepCache>,
+    masked: &[String],
 ) -> Result<i32, PipelineError> {
     // Create the pod
     pods.create(&PostParams::default(), pod_spec).await?;
 
-    if artifact_defs.is_empty() {
+    if artifact_defs.is_empty() && cache.is_none() {
         // No artifacts: original flow
         let exit_code = wait_for_pod(pods, pod_name).await?;
-        capture_logs(pods, pod_name, state, pipeline_id, step_name, exit_code).await;
+        capture_logs(
+            pods,
+            pod_name,
+            state,
+            pipeline_id,
+            step_name,
+            exit_code,
+            masked,
+        )
+        .await;
+        if exit_code == 0 {
+            record_pushed_digests(pods, pod_name, state, pipeline_id, step_name).await;
+        }
         let _ = pods.delete(pod_name, &DeleteParams::default()).await;
         Ok(exit_code)
     } else {
-        // Artifacts: wait for exit-code marker, then collect before signaling done
+        // Artifacts/cache: restore the cache, wait for exit-code marker, then
+        // collect before signaling done
+        let exact_hit = match cache {
+            Some(cache) => restore_step_cache(pods, pod_name, state, cache).await,
+            None => false,
+        };
         let exit_code = wait_for_step_completion(pods, pod_name).await?;
-        capture_logs(pods, pod_name, state, pipeline_id, step_name, exit_code).await;
+        capture_logs(
+            pods,
+            pod_name,
+            state,
+            pipeline_id,
+            step_name,
+            exit_code,
+            masked,
+        )
+        .await;
 
         if exit_code == 0
             && let Err(e) =
@@ -1084,14 +1474,240 @@ async fn run_step(
             let _ = pods.delete(pod_name, &DeleteParams::default()).await;
             return Err(e);
         }
+        if exit_code == 0
+            && !exact_hit
+            && let Some(cache) = cache
+        {
+            save_step_cache(pods, pod_name, state, cache).await;
+        }
 
         signal_pod_done(pods, pod_name).await;
         let _ = wait_for_pod(pods, pod_name).await;
+        if exit_code == 0 {
+            record_pushed_digests(pods, pod_name, state, pipeline_id, step_name).await;
+        }
         let _ = pods.delete(pod_name, &DeleteParams::default()).await;
         Ok(exit_code)
     }
 }
 
+// ---------------------------------------------------------------------------
+// Step cache
+// ---------------------------------------------------------------------------
+
+/// A step's `cache:` with its key resolved for this pipeline.
+struct StepCache {
+    project_id: Uuid,
+    /// Branch the pipeline runs on; caches are saved here.
+    branch: String,
+    /// Restored from when `branch` has no matching cache.
+    default_branch: String,
+    key: String,
+    restore_keys: Vec<String>,
+    paths: Vec<String>,
+}
+
+/// Resolve the key of the step's `cache:` against the pipeline's commit. A
+/// key that can't be resolved disables the cache rather than failing the step.
+async fn resolve_step_cache(
+    state: &AppState,
+    project_id: Uuid,
+    pipeline: &PipelineMeta,
+    step: &StepRow,
+) -> Option<StepCache> {
+    let def: super::definition::CacheDef = step
+        .step_config
+        .as_ref()
+        .and_then(|c| c.get("cache"))
+        .and_then(|v| serde_json::from_value(v.clone()).ok())?;
+
+    let project = match sqlx::query!(
+        "SELECT repo_path, default_branch FROM projects WHERE id = $1",
+        project_id,
+    )
+    .fetch_one(&state.pool)
+    .await
+    {
+        Ok(project) => project,
+        Err(e) => {
+            tracing::warn!(error = %e, step = %step.name, "failed to load repo path, cache disabled");
+            return None;
+        }
+    };
+    let Some(repo_path) = project.repo_path else {
+        tracing::warn!(step = %step.name, "project has no repository, cache disabled");
+        return None;
+    };
+    let git_ref = pipeline.commit_sha.as_deref().unwrap_or(&pipeline.git_ref);
+    match super::cache::resolve_key(std::path::Path::new(&repo_path), git_ref, &def.key).await {
+        Ok(key) => Some(StepCache {
+            project_id,
+            branch: pipeline
+                .git_ref
+                .strip_prefix("refs/heads/")
+                .unwrap_or(&pipeline.git_ref)
+                .to_owned(),
+            default_branch: project.default_branch,
+            key,
+            restore_keys: def.restore_keys,
+            paths: def.paths,
+        }),
+        Err(e) => {
+            tracing::warn!(error = %e, step = %step.name, "cannot resolve cache key, cache disabled");
+            None
+        }
+    }
+}
+
+/// Restore the best matching cache into the step's workspace, then let the
+/// step script start. Returns whether the exact key was restored; on any
+/// failure the step runs without a cache.
+async fn restore_step_cache(
+    pods: &Api<Pod>,
+    pod_name: &str,
+    state: &AppState,
+    cache: &StepCache,
+) -> bool {
+    let exact_hit = match try_restore_step_cache(pods, pod_name, state, cache).await {
+        Ok(hit) => hit,
+        Err(e) => {
+            tracing::warn!(error = %e, key = %cache.key, "cache restore failed, running without cache");
+            false
+        }
+    };
+    if let Err(e) = exec_in_pod(pods, pod_name, "step", &["touch", "/tmp/.cache-restored"]).await {
+        tracing::warn!(error = %e, pod = pod_name, "failed to signal cache restored");
+    }
+    exact_hit
+}
+
+async fn try_restore_step_cache(
+    pods: &Api<Pod>,
+    pod_name: &str,
+    state: &AppState,
+    cache: &StepCache,
+) -> Result<bool, PipelineError> {
+    let deadline =
+        tokio::time::Instant::now() + std::time::Duration::from_secs(DEFAULT_STEP_TIMEOUT_SECS);
+    if wait_for_step_running(pods, pod_name, deadline)
+        .await?
+        .is_some()
+    {
+        return Ok(false);
+    }
+
+    let Some((entry, exact)) = super::cache::lookup(
+        &state.pool,
+        cache.project_id,
+        &cache.branch,
+        &cache.default_branch,
+        &cache.key,
+        &cache.restore_keys,
+    )
+    .await?
+    else {
+        tracing::info!(key = %cache.key, "pipeline cache miss");
+        return Ok(false);
+    };
+    let archive = state
+        .minio
+        .reader(&entry.minio_path)
+        .await
+        .map_err(|e| PipelineError::Other(anyhow::anyhow!("failed to read cache: {e}")))?
+        .into_bytes_stream(..)
+        .await
+        .map_err(|e| PipelineError::Other(anyhow::anyhow!("failed to read cache: {e}")))?;
+    exec_with_stdin(
+        pods,
+        pod_name,
+        "step",
+        &["tar", "xzf", "-", "-C", "/workspace"],
+        archive,
+    )
+    .await?;
+    tracing::info!(
+        key = %cache.key,
+        restored = %entry.cache_key,
+        exact,
+        size_bytes = entry.size_bytes,
+        "pipeline cache restored"
+    );
+    Ok(exact)
+}
+
+/// Stream the step's cache paths to object storage under the resolved key,
+/// then evict least recently used caches over the size limit. Failures are
+/// logged only.
+async fn save_step_cache(pods: &Api<Pod>, pod_name: &str, state: &AppState, cache: &StepCache) {
+    let mut cmd = vec!["tar", "czf", "-", "-C", "/workspace"];
+    cmd.extend(cache.paths.iter().map(String::as_str));
+    let mut ap = match pods
+        .exec(
+            pod_name,
+            cmd,
+            &kube::api::AttachParams {
+                container: Some("step".into()),
+                stdout: true,
+                stderr: false,
+                ..Default::default()
+            },
+        )
+        .await
+    {
+        Ok(ap) => ap,
+        Err(e) => {
+            tracing::warn!(error = %e, key = %cache.key, "failed to archive cache paths");
+            return;
+        }
+    };
+    let Some(mut archive) = ap.stdout() else {
+        tracing::warn!(key = %cache.key, "cache archive has no output");
+        return;
+    };
+
+    let max_bytes = state.config.pipeline_cache_max_bytes;
+    let saved = super::cache::save(
+        &state.pool,
+        &state.minio,
+        cache.project_id,
+        &cache.branch,
+        &cache.key,
+        &mut archive,
+        max_bytes,
+    )
+    .await;
+    // Closing stdout first stops a tar that is still writing past the limit.
+    drop(archive);
+    if let Err(e) = ap.join().await {
+        tracing::debug!(error = %e, key = %cache.key, "cache archive exec ended with an error");
+    }
+
+    match saved {
+        Ok(super::cache::Saved::Stored(size)) => {
+            tracing::info!(key = %cache.key, branch = %cache.branch, size, "pipeline cache saved");
+        }
+        Ok(super::cache::Saved::Exists) => {
+            tracing::debug!(key = %cache.key, "pipeline cache already saved");
+            return;
+        }
+        Ok(super::cache::Saved::Empty) => {
+            tracing::warn!(key = %cache.key, "cache paths produced no archive");
+            return;
+        }
+        Ok(super::cache::Saved::TooLarge) => {
+            tracing::warn!(key = %cache.key, max_bytes, "cache exceeds the cache size limit, not saved");
+            return;
+        }
+        Err(e) => {
+            tracing::warn!(error = %e, key = %cache.key, "failed to save pipeline cache");
+            return;
+        }
+    }
+    if let Err(e) = super::cache::evict(&state.pool, &state.minio, max_bytes).await {
+        tracing::warn!(error = %e, "pipeline cache eviction failed");
+    }
+}
+
 // ---------------------------------------------------------------------------
 // Artifact collection
 // ---------------------------------------------------------------------------
@@ -1106,13 +1722,38 @@ fn extract_artifact_defs(
         .unwrap_or_default()
 }
 
-/// Wait for the step's user commands to finish by polling `/tmp/.exit-code`.
-/// The container stays alive (marker file pattern) until we signal `/tmp/.done`.
-async fn wait_for_step_completion(pods: &Api<Pod>, pod_name: &str) -> Result<i32, PipelineError> {
-    let deadline =
-        tokio::time::Instant::now() + std::time::Duration::from_secs(DEFAULT_STEP_TIMEOUT_SECS);
+/// Extract service container definitions from a step's `step_config` JSON.
+fn extract_service_defs(
+    step_config: Option<&serde_json::Value>,
+) -> Vec<super::definition::ServiceDef> {
+    step_config
+        .and_then(|c| c.get("services"))
+        .and_then(|v| serde_json::from_value::<Vec<super::definition::ServiceDef>>(v.clone()).ok())
+        .unwrap_or_default()
+}
 
-    // Wait for pod to be running first
+fn extract_clone_options(step_config: Option<&serde_json::Value>) -> CloneOptions {
+    let defaults = CloneOptions::default();
+    CloneOptions {
+        depth: step_config
+            .and_then(|c| c.get("clone_depth"))
+            .and_then(serde_json::Value::as_u64)
+            .and_then(|d| u32::try_from(d).ok())
+            .unwrap_or(defaults.depth),
+        tags: step_config
+            .and_then(|c| c.get("fetch_tags"))
+            .and_then(serde_json::Value::as_bool)
+            .unwrap_or(defaults.tags),
+    }
+}
+
+/// Wait until the step pod is running. Returns the exit code instead if the
+/// pod already finished.
+async fn wait_for_step_running(
+    pods: &Api<Pod>,
+    pod_name: &str,
+    deadline: tokio::time::Instant,
+) -> Result<Option<i32>, PipelineError> {
     loop {
         if tokio::time::Instant::now() >= deadline {
             return Err(PipelineError::Other(anyhow::anyhow!(
@@ -1128,12 +1769,12 @@ async fn wait_for_step_completion(pods: &Api<Pod>, pod_name: &str) -> Result<i32
                     .and_then(|s| s.phase.as_deref())
                     .unwrap_or("Unknown");
                 match phase {
-                    "Running" => break,
+                    "Running" => return Ok(None),
                     "Failed" => {
                         let code = pod.status.as_ref().and_then(extract_exit_code).unwrap_or(1);
-                        return Ok(code);
+                        return Ok(Some(code));
                     }
-                    "Succeeded" => return Ok(0),
+                    "Succeeded" => return Ok(Some(0)),
                     _ => {
                         if let Some(ref status) = pod.status
                             && let Some(reason) = detect_unrecoverable_container(status)
@@ -1153,6 +1794,18 @@ async fn wait_for_step_completion(pods: &Api<Pod>, pod_name: &str) -> Result<i32
             Err(e) => return Err(e.into()),
         }
     }
+}
+
+/// Wait for the step's user commands to finish by polling `/tmp/.exit-code`.
+/// The container stays alive (marker file pattern) until we signal `/tmp/.done`.
+async fn wait_for_step_completion(pods: &Api<Pod>, pod_name: &str) -> Result<i32, PipelineError> {
+    let deadline =
+        tokio::time::Instant::now() + std::time::Duration::from_secs(DEFAULT_STEP_TIMEOUT_SECS);
+
+    // Wait for pod to be running first
+    if let Some(code) = wait_for_step_running(pods, pod_name, deadline).await? {
+        return Ok(code);
+    }
 
     // Poll for /tmp/.exit-code marker file
     loop {
@@ -1240,8 +1893,8 @@ async fn collect_step_artifacts(
         // Will be converted to sqlx::query!() after `just db-prepare`.
         sqlx::query(
             "INSERT INTO artifacts (id, pipeline_id, step_id, name, minio_path, content_type,
-                                    size_bytes, artifact_type, config, is_directory)
-             VALUES ($1, $2, $3, $4, $5, NULL, 0, $6, $7, true)",
+                                    size_bytes, artifact_type, config, is_directory, expires_at)
+             VALUES ($1, $2, $3, $4, $5, NULL, 0, $6, $7, true, $8)",
         )
         .bind(parent_id)
         .bind(pipeline_id)
@@ -1250,6 +1903,7 @@ async fn collect_step_artifacts(
         .bind(&parent_minio_path)
         .bind(&artifact_def.artifact_type)
         .bind(&config_json)
+        .bind(lifecycle::expires_at(&state.config))
         .execute(&state.pool)
         .await?;
 
@@ -1335,9 +1989,7 @@ async fn unpack_and_store(
         );
 
         // Upload to MinIO
-        state
-            .minio
-            .write(&minio_path, file.contents.clone())
+        lifecycle::write_expiring(state, &minio_path, file.contents.clone())
             .await
             .map_err(|e| {
                 PipelineError::Other(anyhow::anyhow!("failed to upload artifact to MinIO: {e}"))
@@ -1348,8 +2000,9 @@ async fn unpack_and_store(
         // Will be converted to sqlx::query!() after `just db-prepare`.
         sqlx::query(
             "INSERT INTO artifacts (pipeline_id, step_id, name, minio_path, content_type,
-                                    size_bytes, artifact_type, is_directory, parent_id, relative_path)
-             VALUES ($1, $2, $3, $4, $5, $6, $7, false, $8, $9)",
+                                    size_bytes, artifact_type, is_directory, parent_id, relative_path,
+                                    expires_at)
+             VALUES ($1, $2, $3, $4, $5, $6, $7, false, $8, $9, $10)",
         )
         .bind(pipeline_id)
         .bind(step_id)
@@ -1360,6 +2013,7 @@ async fn unpack_and_store(
         .bind("file")
         .bind(parent_id)
         .bind(&file.sanitized_path)
+        .bind(lifecycle::expires_at(&state.config))
         .execute(&state.pool)
         .await?;
     }
@@ -1659,6 +2313,58 @@ async fn exec_bytes(
     Ok(stdout_buf)
 }
 
+/// Execute a command in a pod container, streaming `input` to its stdin.
+async fn exec_with_stdin<S>(
+    pods: &Api<Pod>,
+    pod_name: &str,
+    container: &str,
+    cmd: &[&str],
+    mut input: S,
+) -> Result<(), PipelineError>
+where
+    S: futures_util::Stream<Item = std::io::Result<bytes::Bytes>> + Unpin,
+{
+    use futures_util::StreamExt;
+    use tokio::io::AsyncWriteExt;
+
+    let cmd_owned: Vec<String> = cmd.iter().map(|s| (*s).to_string()).collect();
+    let mut ap = pods
+        .exec(
+            pod_name,
+            cmd_owned,
+            &kube::api::AttachParams {
+                container: Some(container.to_string()),
+                stdin: true,
+                stdout: false,
+                stderr: false,
+                ..Default::default()
+            },
+        )
+        .await
+        .map_err(|e| PipelineError::Other(anyhow::anyhow!("exec in pod failed: {e}")))?;
+
+    if let Some(mut stdin) = ap.stdin() {
+        while let Some(chunk) = input.next().await {
+            let chunk = chunk.map_err(|e| {
+                PipelineError::Other(anyhow::anyhow!("exec read input failed: {e}"))
+            })?;
+            stdin.write_all(&chunk).await.map_err(|e| {
+                PipelineError::Other(anyhow::anyhow!("exec write stdin failed: {e}"))
+            })?;
+        }
+        stdin
+            .shutdown()
+            .await
+            .map_err(|e| PipelineError::Other(anyhow::anyhow!("exec close stdin failed: {e}")))?;
+    }
+
+    ap.join()
+        .await
+        .map_err(|e| PipelineError::Other(anyhow::anyhow!("exec join failed: {e}")))?;
+
+    Ok(())
+}
+
 /// Poll pod status until it reaches a terminal phase.
 /// Default step timeout: 15 minutes.
 const DEFAULT_STEP_TIMEOUT_SECS: u64 = 900;
@@ -1788,7 +2494,7 @@ fn extract_exit_code(status: &k8s_openapi::api::core::v1::PodStatus) -> Option<i
         .map(|t| t.exit_code)
 }
 
-/// Capture pod logs and write them to `MinIO`.
+/// Capture pod logs, with masked variable values redacted, and write them to `MinIO`.
 async fn capture_logs(
     pods: &Api<Pod>,
     pod_name: &str,
@@ -1796,6 +2502,7 @@ async fn capture_logs(
     pipeline_id: Uuid,
     step_name: &str,
     exit_code: i32,
+    masked: &[String],
 ) {
     let failed = exit_code != 0;
 
@@ -1806,6 +2513,7 @@ async fn capture_logs(
     };
     match pods.logs(pod_name, &init_log_params).await {
         Ok(logs) => {
+            let logs = super::variable_groups::redact(&logs, masked);
             if failed {
                 let truncated: String = logs.chars().take(2000).collect();
                 tracing::warn!(
@@ -1815,7 +2523,7 @@ async fn capture_logs(
                 );
             }
             let path = format!("logs/pipelines/{pipeline_id}/{step_name}-clone.log");
-            if let Err(e) = state.minio.write(&path, logs.into_bytes()).await {
+            if let Err(e) = lifecycle::write_expiring(state, &path, logs.into_bytes()).await {
                 tracing::error!(error = %e, %path, "failed to write clone logs to MinIO");
             }
         }
@@ -1824,14 +2532,17 @@ async fn capture_logs(
         }
     }
 
-    // Capture main step container logs
+    // Capture main step container logs, timestamped for the combined view
     let log_params = LogParams {
         container: Some("step".into()),
+        timestamps: true,
         ..Default::default()
     };
 
     match pods.logs(pod_name, &log_params).await {
-        Ok(logs) => {
+        Ok(timestamped) => {
+            let timestamped = super::variable_groups::redact(&timestamped, masked);
+            let logs = super::logs::strip_timestamps(&timestamped);
             if failed {
                 let truncated: String = logs.chars().take(2000).collect();
                 tracing::error!(
@@ -1842,9 +2553,14 @@ async fn capture_logs(
                 );
             }
             let path = format!("logs/pipelines/{pipeline_id}/{step_name}.log");
-            if let Err(e) = state.minio.write(&path, logs.into_bytes()).await {
+            if let Err(e) = lifecycle::write_expiring(state, &path, logs.into_bytes()).await {
                 tracing::error!(error = %e, %path, "failed to write logs to MinIO");
             }
+            let path = super::logs::timestamped_path(&path);
+            if let Err(e) = lifecycle::write_expiring(state, &path, timestamped.into_bytes()).await
+            {
+                tracing::error!(error = %e, %path, "failed to write timestamped logs to MinIO");
+            }
         }
         Err(e) => {
             tracing::warn!(error = %e, pod = pod_name, "failed to read pod logs");
@@ -1852,6 +2568,129 @@ async fn capture_logs(
     }
 }
 
+// ---------------------------------------------------------------------------
+// Image digest pinning
+// ---------------------------------------------------------------------------
+
+/// `(repository, digest)` for each image in kaniko's
+/// `--image-name-with-digest-file`, one `image@digest` per destination, e.g.
+/// `localhost:5000/shop/app:abc123@sha256:…` gives `("shop/app", "sha256:…")`.
+fn parse_pushed_digests(digest_file: &str) -> Vec<(String, String)> {
+    let mut found: Vec<(String, String)> = Vec::new();
+    for line in digest_file.lines() {
+        let Some((name, digest)) = line.trim().split_once('@') else {
+            continue;
+        };
+        let valid = digest.strip_prefix("sha256:").is_some_and(|hex| {
+            hex.len() == 64 && hex.bytes().all(|b| matches!(b, b'0'..=b'9' | b'a'..=b'f'))
+        });
+        let repository = repository_path(name);
+        if valid && !found.iter().any(|(r, _)| *r == repository) {
+            found.push((repository, digest.to_owned()));
+        }
+    }
+    found
+}
+
+/// An image reference without its registry host, tag or digest
+/// (`localhost:5000/shop/app:abc123` → `shop/app`).
+fn repository_path(image_ref: &str) -> String {
+    let name = image_ref
+        .split_once('@')
+        .map_or(image_ref, |(name, _)| name);
+    let name = match name.split_once('/') {
+        Some((host, rest)) if host.contains(['.', ':']) || host == "localhost" => rest,
+        _ => name,
+    };
+    let tag_start = name.rfind('/').map_or(0, |i| i + 1);
+    match name[tag_start..].rfind(':') {
+        Some(i) => name[..tag_start + i].to_owned(),
+        None => name.to_owned(),
+    }
+}
+
+/// Termination message of the step container. Image build steps point
+/// kaniko's `--image-name-with-digest-file` at `/dev/termination-log`, so this
+/// holds the digests they pushed.
+fn step_termination_message(status: &k8s_openapi::api::core::v1::PodStatus) -> Option<&str> {
+    status
+        .container_statuses
+        .as_ref()?
+        .iter()
+        .find(|cs| cs.name == "step")?
+        .state
+        .as_ref()?
+        .terminated
+        .as_ref()?
+        .message
+        .as_deref()
+}
+
+/// Remember the digests a build step pushed, for deploys later in the pipeline.
+async fn record_pushed_digests(
+    pods: &Api<Pod>,
+    pod_name: &str,
+    state: &AppState,
+    pipeline_id: Uuid,
+    step_name: &str,
+) {
+    let pod = match pods.get(pod_name).await {
+        Ok(pod) => pod,
+        Err(e) => {
+            tracing::warn!(error = %e, pod = pod_name, "failed to read step termination message");
+            return;
+        }
+    };
+    let Some(message) = pod.status.as_ref().and_then(step_termination_message) else {
+        return;
+    };
+    for (repository, digest) in parse_pushed_digests(message) {
+        let result = sqlx::query!(
+            "INSERT INTO pipeline_image_digests (pipeline_id, repository, digest, step_name)
+             VALUES ($1, $2, $3, $4)
+             ON CONFLICT (pipeline_id, repository)
+             DO UPDATE SET digest = EXCLUDED.digest, step_name = EXCLUDED.step_name",
+            pipeline_id,
+            repository,
+            digest,
+            step_name,
+        )
+        .execute(&state.pool)
+        .await;
+        match result {
+            Ok(_) => {
+                tracing::info!(%pipeline_id, %repository, %digest, "recorded pushed image digest");
+            }
+            Err(e) => tracing::warn!(error = %e, %repository, "failed to record image digest"),
+        }
+    }
+}
+
+/// Pin `image_ref` to the digest this pipeline pushed for its repository
+/// (`registry/shop/app:abc123@sha256:…`), so a retagged image can't change
+/// what is deployed. Falls back to the tag when no push was recorded.
+async fn pin_image_digest(state: &AppState, pipeline_id: Uuid, image_ref: &str) -> String {
+    let repository = repository_path(image_ref);
+    let digest = sqlx::query_scalar!(
+        "SELECT digest FROM pipeline_image_digests WHERE pipeline_id = $1 AND repository = $2",
+        pipeline_id,
+        repository,
+    )
+    .fetch_optional(&state.pool)
+    .await;
+    match digest {
+        Ok(Some(digest)) => format!("{image_ref}@{digest}"),
+        Ok(None) => {
+            tracing::info!(%pipeline_id, %image_ref, "no pushed digest recorded, deploying by tag");
+            image_ref.to_owned()
+        }
+        Err(e) => {
+            tracing::warn!(error = %e, %image_ref, "failed to look up image digest, deploying by tag");
+            image_ref.to_owned()
+        }
+    }
+}
+
 // ---------------------------------------------------------------------------
 // Git auth token for HTTP clone
 // ---------------------------------------------------------------------------
@@ -2043,6 +2882,9 @@ struct PodSpecParams<'a> {
     /// HTTP clone URL (e.g. `http://platform:8080/owner/repo.git`).
     repo_clone_url: &'a str,
     git_ref: &'a str,
+    /// Triggering commit, checked out after the clone so a branch that has
+    /// moved on since the trigger doesn't change what gets built.
+    commit_sha: Option<&'a str>,
     /// K8s Secret name containing Docker config JSON for registry auth.
     registry_secret: Option<&'a str>,
     /// K8s Secret name containing git auth token (mounted as volume instead of env var).
@@ -2053,8 +2895,60 @@ struct PodSpecParams<'a> {
     git_clone_image: &'a str,
     /// Whether this step has artifacts to collect (wraps script with marker file pattern).
     has_artifacts: bool,
+    /// Whether this step has a cache: the script waits for `/tmp/.cache-restored`
+    /// before running and is kept alive afterwards so the cache can be saved.
+    has_cache: bool,
     /// Host path to the platform-proxy binary (mesh wrapping). Only used in dev mode.
     proxy_binary_path: Option<&'a str>,
+    /// Service containers started before the step and reachable on `localhost`.
+    services: &'a [super::definition::ServiceDef],
+    /// History and tags fetched by the clone init container.
+    clone: CloneOptions,
+}
+
+/// How much of the repository the clone init container fetches.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct CloneOptions {
+    /// Commits of history; `0` for the full history.
+    depth: u32,
+    /// Fetch all tags after cloning.
+    tags: bool,
+}
+
+impl Default for CloneOptions {
+    fn default() -> Self {
+        Self {
+            depth: 1,
+            tags: false,
+        }
+    }
+}
+
+impl CloneOptions {
+    /// The `sh -c` script run by the clone init container.
+    fn script(self) -> String {
+        let depth = match self.depth {
+            0 => String::new(),
+            n => format!("--depth {n} "),
+        };
+        let tags = if self.tags {
+            " && git -C /workspace fetch --tags --force origin 2>&1"
+        } else {
+            ""
+        };
+        // `GIT_COMMIT_SHA` is set when the triggering commit is known; fetch
+        // it if the branch has moved past a shallow clone's history.
+        format!(
+            "printf '#!/bin/sh\\ncat /git-auth/token\\n' > /tmp/git-askpass.sh && \
+             chmod +x /tmp/git-askpass.sh && \
+             export GIT_ASKPASS=/tmp/git-askpass.sh && \
+             git clone {depth}--branch \"$GIT_BRANCH\" \"$GIT_CLONE_URL\" /workspace 2>&1{tags} && \
+             if [ -n \"${{GIT_COMMIT_SHA:-}}\" ]; then \
+             {{ git -C /workspace cat-file -e \"$GIT_COMMIT_SHA^{{commit}}\" 2>/dev/null || \
+             git -C /workspace fetch {depth}origin \"$GIT_COMMIT_SHA\" 2>&1; }} && \
+             git -C /workspace checkout -q \"$GIT_COMMIT_SHA\" 2>&1; fi"
+        )
+    }
 }
 
 /// Build the volumes and step container mounts for a pipeline pod.
@@ -2128,11 +3022,16 @@ fn build_volumes_and_mounts(
 
 #[allow(clippy::too_many_lines)]
 fn build_pod_spec(p: &PodSpecParams<'_>) -> Pod {
-    let script = if p.has_artifacts {
+    let script = if p.has_artifacts || p.has_cache {
         // Wrap user commands with marker file pattern to keep container alive for artifact collection
         let user_cmds = p.commands.join(" && ");
+        let wait_for_cache = if p.has_cache {
+            "while [ ! -f /tmp/.cache-restored ]; do sleep 1; done; "
+        } else {
+            ""
+        };
         format!(
-            "({user_cmds}); EC=$?; echo $EC > /tmp/.exit-code.tmp && mv /tmp/.exit-code.tmp /tmp/.exit-code; while [ ! -f /tmp/.done ]; do sleep 1; done; exit $EC"
+            "{wait_for_cache}({user_cmds}); EC=$?; echo $EC > /tmp/.exit-code.tmp && mv /tmp/.exit-code.tmp /tmp/.exit-code; while [ ! -f /tmp/.done ]; do sleep 1; done; exit $EC"
         )
     } else {
         p.commands.join(" && ")
@@ -2169,6 +3068,36 @@ fn build_pod_spec(p: &PodSpecParams<'_>) -> Pod {
         });
     }
 
+    let mut init_containers = vec![Container {
+        name: "clone".into(),
+        image: Some(p.git_clone_image.to_string()),
+        command: Some(vec!["sh".into(), "-c".into()]),
+        // S31: Read git token from mounted secret file instead of env var
+        // A17: Pass repo_clone_url as env var to avoid shell interpolation
+        args: Some(vec![p.clone.script()]),
+        env: Some(
+            [
+                Some(env_var("GIT_BRANCH", branch)),
+                Some(env_var("GIT_CLONE_URL", p.repo_clone_url)),
+                p.commit_sha.map(|sha| env_var("GIT_COMMIT_SHA", sha)),
+            ]
+            .into_iter()
+            .flatten()
+            .collect(),
+        ),
+        volume_mounts: Some(init_mounts),
+        security_context: Some(container_security()),
+        ..Default::default()
+    }];
+    // Services run as native sidecars: they start after the clone, before the
+    // step, and are stopped once the step container exits.
+    init_containers.extend(
+        p.services
+            .iter()
+            .enumerate()
+            .map(|(i, svc)| service_container(i, svc)),
+    );
+
     Pod {
         metadata: k8s_openapi::apimachinery::pkg::apis::meta::v1::ObjectMeta {
             name: Some(p.pod_name.into()),
@@ -2188,27 +3117,7 @@ fn build_pod_spec(p: &PodSpecParams<'_>) -> Pod {
                     name: name.to_string(),
                 }]
             }),
-            init_containers: Some(vec![Container {
-                name: "clone".into(),
-                image: Some(p.git_clone_image.to_string()),
-                command: Some(vec!["sh".into(), "-c".into()]),
-                // S31: Read git token from mounted secret file instead of env var
-                // A17: Pass repo_clone_url as env var to avoid shell interpolation
-                args: Some(vec![
-                    "printf '#!/bin/sh\\ncat /git-auth/token\\n' > /tmp/git-askpass.sh && \
-                     chmod +x /tmp/git-askpass.sh && \
-                     GIT_ASKPASS=/tmp/git-askpass.sh \
-                     git clone --depth 1 --branch \"$GIT_BRANCH\" \"$GIT_CLONE_URL\" /workspace 2>&1"
-                        .into(),
-                ]),
-                env: Some(vec![
-                    env_var("GIT_BRANCH", branch),
-                    env_var("GIT_CLONE_URL", p.repo_clone_url),
-                ]),
-                volume_mounts: Some(init_mounts),
-                security_context: Some(container_security()),
-                ..Default::default()
-            }]),
+            init_containers: Some(init_containers),
             containers: vec![Container {
                 name: "step".into(),
                 image: Some(p.image.into()),
@@ -2258,6 +3167,47 @@ fn build_pod_spec(p: &PodSpecParams<'_>) -> Pod {
     }
 }
 
+/// Build the sidecar container for a step service.
+fn service_container(index: usize, svc: &super::definition::ServiceDef) -> Container {
+    let env: Vec<EnvVar> = svc.env.iter().map(|(k, v)| env_var(k, v)).collect();
+    Container {
+        name: format!("service-{index}"),
+        image: Some(svc.image.clone()),
+        env: (!env.is_empty()).then_some(env),
+        restart_policy: Some("Always".into()),
+        // Hold the step back until the service accepts connections.
+        startup_probe: svc.port.map(|port| k8s_openapi::api::core::v1::Probe {
+            tcp_socket: Some(k8s_openapi::api::core::v1::TCPSocketAction {
+                port: k8s_openapi::apimachinery::pkg::util::intstr::IntOrString::Int(i32::from(
+                    port,
+                )),
+                ..Default::default()
+            }),
+            period_seconds: Some(2),
+            failure_threshold: Some(90),
+            ..Default::default()
+        }),
+        // Database images start as root and drop to their own user, so they
+        // keep the default capabilities.
+        security_context: Some(SecurityContext {
+            allow_privilege_escalation: Some(false),
+            ..Default::default()
+        }),
+        resources: Some(k8s_openapi::api::core::v1::ResourceRequirements {
+            limits: Some(BTreeMap::from([
+                ("cpu".into(), Quantity("1".into())),
+                ("memory".into(), Quantity("1Gi".into())),
+            ])),
+            requests: Some(BTreeMap::from([
+                ("cpu".into(), Quantity("100m".into())),
+                ("memory".into(), Quantity("128Mi".into())),
+            ])),
+            ..Default::default()
+        }),
+        ..Default::default()
+    }
+}
+
 /// Registry URL as seen from K8s nodes (for image refs in pod specs).
 /// Prefers `registry_node_url` (`DaemonSet` proxy), falls back to `registry_url`.
 fn node_registry_url(config: &crate::config::Config) -> Option<&str> {
@@ -2267,7 +3217,8 @@ fn node_registry_url(config: &crate::config::Config) -> Option<&str> {
         .or(config.registry_url.as_deref())
 }
 
-/// Env var names that must not be overridden by project secrets in pipeline pods.
+/// Env var names that must not be overridden by project secrets or trigger
+/// variables in pipeline pods.
 const RESERVED_PIPELINE_ENV_VARS: &[&str] = &[
     "PLATFORM_PROJECT_ID",
     "PLATFORM_PROJECT_NAME",
@@ -2292,7 +3243,7 @@ const RESERVED_PIPELINE_ENV_VARS: &[&str] = &[
     "OTEL_EXPORTER_OTLP_HEADERS",
 ];
 
-fn is_reserved_pipeline_env_var(name: &str) -> bool {
+pub(crate) fn is_reserved_pipeline_env_var(name: &str) -> bool {
     RESERVED_PIPELINE_ENV_VARS.contains(&name)
 }
 
@@ -2432,7 +3383,14 @@ fn build_env_vars_full(
         ));
     }
 
-    // 3. Project secrets (skip reserved names)
+    // 3. Variable groups (skip reserved names)
+    for (key, val) in &meta.group_variables.vars {
+        if !is_reserved_pipeline_env_var(key) {
+            vars.push(env_var(key, val));
+        }
+    }
+
+    // 4. Project secrets (skip reserved names)
     let mut secret_names = Vec::new();
     for (key, val) in secrets {
         if !is_reserved_pipeline_env_var(key) {
@@ -2444,7 +3402,15 @@ fn build_env_vars_full(
         vars.push(env_var("PLATFORM_SECRET_NAMES", &secret_names.join(",")));
     }
 
-    // 4. Step-level environment (highest priority — can override secrets)
+    // 5. Trigger variables (skip reserved names — the API renames them to
+    // `TRIGGER_<name>`, but rows predating that are not trusted)
+    for (key, val) in &meta.variables {
+        if !is_reserved_pipeline_env_var(key) {
+            vars.push(env_var(key, val));
+        }
+    }
+
+    // 6. Step-level environment (highest priority — can override secrets)
     if let Some(env_json) = step_environment
         && let Some(map) = env_json.as_object()
     {
@@ -2639,7 +3605,15 @@ async fn execute_deploy_test_step(
     .await;
 
     // 2b. Inject project secrets (scope: test/all) + OTEL tokens into test namespace
-    inject_test_namespace_secrets(state, project_id, &pipeline.project_name, &ns_name).await;
+    inject_test_namespace_secrets(
+        state,
+        project_id,
+        pipeline_id,
+        pipeline.triggered_by,
+        &pipeline.project_name,
+        &ns_name,
+    )
+    .await;
 
     // 3. Read + render deploy manifests from project repo
     let manifests_path = dt.manifests.as_deref().unwrap_or("deploy/");
@@ -2689,7 +3663,12 @@ async fn execute_deploy_test_step(
     // Determine app image ref (use node registry URL for containerd pulls)
     let registry = node_registry_url(&state.config).unwrap_or("localhost:5000");
     let commit_sha = pipeline.commit_sha.as_deref().unwrap_or("latest");
-    let app_image_ref = format!("{registry}/{}/app:{commit_sha}", pipeline.project_name);
+    let app_image_ref = pin_image_digest(
+        state,
+        pipeline_id,
+        &format!("{registry}/{}/app:{commit_sha}", pipeline.project_name),
+    )
+    .await;
 
     // Render manifests with test environment
     let vars = crate::deployer::renderer::RenderVars {
@@ -2788,7 +3767,7 @@ async fn execute_deploy_test_step(
         Some(vec!["sh".into(), "-c".into(), script])
     };
 
-    let test_pod = Pod {
+    let mut test_pod = Pod {
         metadata: k8s_openapi::apimachinery::pkg::apis::meta::v1::ObjectMeta {
             name: Some(test_pod_name.clone()),
             labels: Some(std::collections::BTreeMap::from([
@@ -2825,6 +3804,7 @@ async fn execute_deploy_test_step(
         ..Default::default()
     };
 
+    crate::deployer::scheduling::apply(&mut test_pod, &state.config);
     test_pods.create(&PostParams::default(), &test_pod).await?;
     tracing::info!(%test_pod_name, namespace = %ns_name, "deploy_test: test pod created");
 
@@ -2842,7 +3822,7 @@ async fn execute_deploy_test_step(
             tracing::error!(%test_pod_name, %logs, "deploy_test: test pod failed");
         }
         let path = format!("logs/pipelines/{pipeline_id}/{}-test.log", step.name);
-        if let Err(e) = state.minio.write(&path, logs.into_bytes()).await {
+        if let Err(e) = lifecycle::write_expiring(state, &path, logs.into_bytes()).await {
             tracing::error!(error = %e, %path, "failed to write test logs to MinIO");
         }
     }
@@ -3098,7 +4078,7 @@ async fn capture_deployment_logs(
         let log_params = LogParams::default();
         if let Ok(logs) = pods.logs(pod_name, &log_params).await {
             let path = format!("logs/pipelines/{pipeline_id}/{step_name}-app-{pod_name}.log");
-            if let Err(e) = state.minio.write(&path, logs.into_bytes()).await {
+            if let Err(e) = lifecycle::write_expiring(state, &path, logs.into_bytes()).await {
                 tracing::warn!(error = %e, %path, "failed to write app logs");
             }
         }
@@ -3151,7 +4131,7 @@ async fn execute_gitops_sync_step(
 #[allow(clippy::too_many_lines)]
 async fn execute_gitops_sync_inner(
     state: &AppState,
-    _pipeline_id: Uuid,
+    pipeline_id: Uuid,
     project_id: Uuid,
     pipeline: &PipelineMeta,
     _step: &StepRow,
@@ -3160,7 +4140,8 @@ async fn execute_gitops_sync_inner(
 
     // Read project info (use dynamic query — include_staging is a new column)
     let project = sqlx::query(
-        "SELECT name, repo_path, include_staging FROM projects WHERE id = $1 AND is_active = true",
+        "SELECT name, repo_path, include_staging, default_branch
+         FROM projects WHERE id = $1 AND is_active = true",
     )
     .bind(project_id)
     .fetch_optional(&state.pool)
@@ -3169,6 +4150,15 @@ async fn execute_gitops_sync_inner(
     let project_name: String = project.get("name");
     let project_repo_path: Option<String> = project.get("repo_path");
     let include_staging: bool = project.get("include_staging");
+    let default_branch: String = project.get("default_branch");
+
+    if !deploys_from_ref(&pipeline.git_ref, &default_branch) {
+        tracing::info!(
+            %project_id, git_ref = %pipeline.git_ref, %default_branch,
+            "gitops_sync: not the default branch, skipping deploy"
+        );
+        return Ok(true);
+    }
 
     // Look up ops repo
     let ops_repo = sqlx::query("SELECT id, repo_path, branch FROM ops_repos WHERE project_id = $1")
@@ -3304,7 +4294,12 @@ async fn execute_gitops_sync_inner(
         .and_then(|vi| vi.images.get("app"))
         .cloned();
     let tag = app_version.as_deref().unwrap_or(sha);
-    let image_ref = format!("{registry}/{project_name}/app:{tag}");
+    let image_ref = pin_image_digest(
+        state,
+        pipeline_id,
+        &format!("{registry}/{project_name}/app:{tag}"),
+    )
+    .await;
 
     let mut values = serde_json::json!({
         "image_ref": image_ref,
@@ -3361,6 +4356,7 @@ async fn execute_gitops_sync_inner(
         environment: environment.into(),
         commit_sha: ops_commit_sha,
         image_ref: image_ref.clone(),
+        triggered_by: pipeline.triggered_by,
     };
     if let Err(e) = crate::store::eventbus::publish(&state.valkey, &event).await {
         tracing::error!(error = %e, %project_id, "failed to publish OpsRepoUpdated event");
@@ -3488,6 +4484,8 @@ async fn execute_deploy_watch_step(
 async fn inject_test_namespace_secrets(
     state: &AppState,
     project_id: Uuid,
+    pipeline_id: Uuid,
+    triggered_by: Option<Uuid>,
     project_name: &str,
     namespace: &str,
 ) {
@@ -3500,7 +4498,7 @@ async fn inject_test_namespace_secrets(
         && let Ok(mk) = crate::secrets::engine::parse_master_key(master_key_str)
     {
         let rows = sqlx::query(
-            "SELECT name, encrypted_value FROM secrets
+            "SELECT name, encrypted_value, backend FROM secrets
                  WHERE project_id = $1 AND scope IN ('test', 'all')
                    AND environment IS NULL",
         )
@@ -3512,17 +4510,26 @@ async fn inject_test_namespace_secrets(
         for row in &rows {
             let name: String = row.get("name");
             let encrypted: Vec<u8> = row.get("encrypted_value");
-            match crate::secrets::engine::decrypt(&encrypted, &mk, None) {
-                Ok(val) => {
-                    if let Ok(s) = String::from_utf8(val) {
-                        env_data.insert(name, s);
-                    }
+            let backend: String = row.get("backend");
+            match crate::secrets::engine::reveal(&mk, &backend, &encrypted).await {
+                Ok(s) => {
+                    env_data.insert(name, s);
                 }
                 Err(e) => {
-                    tracing::warn!(error = %e, "failed to decrypt test secret");
+                    tracing::warn!(error = %e, "failed to resolve test secret");
                 }
             }
         }
+
+        crate::secrets::access::record_access(
+            &state.pool,
+            &state.audit_tx,
+            project_id,
+            triggered_by,
+            env_data.keys().map(String::as_str),
+            crate::secrets::access::AccessPurpose::DeployTest { pipeline_id },
+        )
+        .await;
     }
 
     // Inject OTEL env vars
@@ -3610,18 +4617,12 @@ async fn skip_remaining_steps(pool: &PgPool, pipeline_id: Uuid) -> Result<(), Pi
     Ok(())
 }
 
-async fn skip_remaining_after(
-    pool: &PgPool,
-    pipeline_id: Uuid,
-    after_order: i32,
-) -> Result<(), PipelineError> {
+async fn skip_step(pool: &PgPool, step_id: Uuid, reason: &str) -> Result<(), PipelineError> {
     sqlx::query!(
-        r#"
-        UPDATE pipeline_steps SET status = 'skipped', finished_at = now()
-        WHERE pipeline_id = $1 AND step_order > $2 AND status = 'pending'
-        "#,
-        pipeline_id,
-        after_order,
+        "UPDATE pipeline_steps SET status = 'skipped', skip_reason = $2, finished_at = now()
+         WHERE id = $1 AND status = 'pending'",
+        step_id,
+        reason,
     )
     .execute(pool)
     .await?;
@@ -3779,6 +4780,15 @@ pub async fn cancel_pipeline(state: &AppState, pipeline_id: Uuid) -> Result<(),
 // Utilities
 // ---------------------------------------------------------------------------
 
+/// Whether a pipeline on `git_ref` may hand off to the ops repo: branch
+/// pipelines only from the project's default branch. Tags and other refs
+/// (e.g. a commit SHA given to the API) are not restricted.
+fn deploys_from_ref(git_ref: &str, default_branch: &str) -> bool {
+    git_ref
+        .strip_prefix("refs/heads/")
+        .is_none_or(|branch| branch == default_branch)
+}
+
 use super::slug;
 
 #[cfg(test)]
@@ -3789,20 +4799,12 @@ mod tests {
         ContainerStatus, PodStatus,
     };
 
-    // -- test-only helpers for kaniko detection / branch classification --
+    // -- test-only helpers for kaniko detection --
 
     fn is_kaniko_image(image: &str) -> bool {
         image.to_ascii_lowercase().contains("kaniko")
     }
 
-    fn classify_branch(branch: &str) -> &'static str {
-        if matches!(branch, "main" | "master") {
-            "production"
-        } else {
-            "preview"
-        }
-    }
-
     fn build_image_ref(registry: &str, project_name: &str, tag: &str) -> String {
         format!("{registry}/{project_name}/app:{tag}")
     }
@@ -3936,12 +4938,16 @@ mod tests {
             env_vars: &[env_var("FOO", "bar")],
             repo_clone_url: "http://platform:8080/owner/repo.git",
             git_ref: "refs/heads/main",
+            commit_sha: None,
             registry_secret: None,
             git_secret_name: None,
             step_type: "command",
             git_clone_image: "alpine/git:2.47.2",
             has_artifacts: false,
+            has_cache: false,
             proxy_binary_path: None,
+            services: &[],
+            clone: CloneOptions::default(),
         });
 
         assert_eq!(pod.metadata.name.as_deref(), Some("pl-test-build"));
@@ -3989,12 +4995,16 @@ mod tests {
             env_vars: &[],
             repo_clone_url: "http://platform:8080/owner/test.git",
             git_ref: "refs/heads/feature-branch",
+            commit_sha: None,
             registry_secret: None,
             git_secret_name: None,
             step_type: "command",
             git_clone_image: "alpine/git:2.47.2",
             has_artifacts: false,
+            has_cache: false,
             proxy_binary_path: None,
+            services: &[],
+            clone: CloneOptions::default(),
         });
 
         let spec = pod.spec.unwrap();
@@ -4027,12 +5037,16 @@ mod tests {
             env_vars: &[],
             repo_clone_url: "http://platform:8080/owner/test.git",
             git_ref: "refs/tags/v1.0",
+            commit_sha: None,
             registry_secret: None,
             git_secret_name: None,
             step_type: "command",
             git_clone_image: "alpine/git:2.47.2",
             has_artifacts: false,
+            has_cache: false,
             proxy_binary_path: None,
+            services: &[],
+            clone: CloneOptions::default(),
         });
 
         let spec = pod.spec.unwrap();
@@ -4058,12 +5072,16 @@ mod tests {
             env_vars: &[],
             repo_clone_url: "http://platform:8080/owner/test.git",
             git_ref: "main",
+            commit_sha: None,
             registry_secret: None,
             git_secret_name: None,
             step_type: "command",
             git_clone_image: "alpine/git:2.47.2",
             has_artifacts: false,
+            has_cache: false,
             proxy_binary_path: None,
+            services: &[],
+            clone: CloneOptions::default(),
         });
 
         let spec = pod.spec.unwrap();
@@ -4089,12 +5107,16 @@ mod tests {
             env_vars: &[],
             repo_clone_url: "http://platform:8080/owner/test.git",
             git_ref: "main",
+            commit_sha: None,
             registry_secret: None,
             git_secret_name: None,
             step_type: "command",
             git_clone_image: "alpine/git:2.47.2",
             has_artifacts: false,
+            has_cache: false,
             proxy_binary_path: None,
+            services: &[],
+            clone: CloneOptions::default(),
         });
 
         let container = &pod.spec.unwrap().containers[0];
@@ -4117,12 +5139,16 @@ mod tests {
             env_vars: &[],
             repo_clone_url: "http://platform:8080/owner/test.git",
             git_ref: "main",
+            commit_sha: None,
             registry_secret: None,
             git_secret_name: None,
             step_type: "command",
             git_clone_image: "alpine/git:2.47.2",
             has_artifacts: false,
+            has_cache: false,
             proxy_binary_path: None,
+            services: &[],
+            clone: CloneOptions::default(),
         });
 
         let container = &pod.spec.unwrap().containers[0];
@@ -4149,12 +5175,16 @@ mod tests {
             env_vars: &[],
             repo_clone_url: "http://platform:8080/owner/test.git",
             git_ref: "main",
+            commit_sha: None,
             registry_secret: None,
             git_secret_name: None,
             step_type: "command",
             git_clone_image: "alpine/git:2.47.2",
             has_artifacts: false,
+            has_cache: false,
             proxy_binary_path: None,
+            services: &[],
+            clone: CloneOptions::default(),
         });
 
         let container = &pod.spec.unwrap().containers[0];
@@ -4175,12 +5205,16 @@ mod tests {
             env_vars: &[],
             repo_clone_url: "http://platform:8080/owner/test.git",
             git_ref: "main",
+            commit_sha: None,
             registry_secret: None,
             git_secret_name: None,
             step_type: "command",
             git_clone_image: "alpine/git:2.47.2",
             has_artifacts: false,
+            has_cache: false,
             proxy_binary_path: None,
+            services: &[],
+            clone: CloneOptions::default(),
         });
 
         let labels = pod.metadata.labels.as_ref().unwrap();
@@ -4386,6 +5420,7 @@ mod tests {
             commands: vec![],
             condition_events: vec![],
             condition_branches: vec![],
+            condition_when: None,
             deploy_test: None,
             depends_on: vec![],
             environment: None,
@@ -4406,6 +5441,7 @@ mod tests {
             commands: vec![],
             condition_events: vec!["mr".into()],
             condition_branches: vec![],
+            condition_when: None,
             deploy_test: None,
             depends_on: vec![],
             environment: None,
@@ -4444,26 +5480,24 @@ mod tests {
         assert!(!is_kaniko_image("rust:1.85-slim"));
     }
 
-    // -- classify_branch --
-
-    #[test]
-    fn branch_main_classified_as_production() {
-        assert_eq!(classify_branch("main"), "production");
-    }
+    // -- deploys_from_ref --
 
     #[test]
-    fn branch_master_classified_as_production() {
-        assert_eq!(classify_branch("master"), "production");
+    fn default_branch_deploys() {
+        assert!(deploys_from_ref("refs/heads/main", "main"));
+        assert!(deploys_from_ref("refs/heads/develop", "develop"));
     }
 
     #[test]
-    fn branch_feature_classified_as_preview() {
-        assert_eq!(classify_branch("feature/login"), "preview");
+    fn other_branches_do_not_deploy() {
+        assert!(!deploys_from_ref("refs/heads/main", "develop"));
+        assert!(!deploys_from_ref("refs/heads/feature/login", "main"));
     }
 
     #[test]
-    fn branch_develop_classified_as_preview() {
-        assert_eq!(classify_branch("develop"), "preview");
+    fn tags_and_other_refs_deploy() {
+        assert!(deploys_from_ref("refs/tags/v1.0.0", "develop"));
+        assert!(deploys_from_ref("0a1b2c3d", "main"));
     }
 
     // -- build_image_ref --
@@ -4480,6 +5514,47 @@ mod tests {
         assert_eq!(r, "localhost:5000/proj/app:latest");
     }
 
+    // -- image digest pinning --
+
+    const DIGEST: &str = "sha256:2c26b46b68ffc68ff99b453c1d30413413422d706483bfa0f98a5e886266e7ae";
+
+    #[test]
+    fn pushed_digests_from_kaniko_digest_file() {
+        let digest_file = format!(
+            "localhost:5000/shop/app:abc123@{DIGEST}\n\
+             localhost:5000/shop/app:1.2.0@{DIGEST}\n\
+             platform-registry.svc:5000/shop/test:abc123@{DIGEST}\n"
+        );
+        assert_eq!(
+            parse_pushed_digests(&digest_file),
+            vec![
+                ("shop/app".to_owned(), DIGEST.to_owned()),
+                ("shop/test".to_owned(), DIGEST.to_owned()),
+            ]
+        );
+    }
+
+    #[test]
+    fn pushed_digests_ignore_malformed_lines() {
+        assert!(parse_pushed_digests("localhost:5000/shop/app:abc123").is_empty());
+        assert!(parse_pushed_digests("localhost:5000/shop/app@sha256:abc").is_empty());
+        assert!(parse_pushed_digests("exit status 1").is_empty());
+    }
+
+    #[test]
+    fn repository_path_strips_host_tag_and_digest() {
+        assert_eq!(
+            repository_path("localhost:5000/shop/app:abc123"),
+            "shop/app"
+        );
+        assert_eq!(repository_path("registry.example.com/shop/app"), "shop/app");
+        assert_eq!(
+            repository_path(&format!("10.0.0.1:5000/shop/app:v1@{DIGEST}")),
+            "shop/app"
+        );
+        assert_eq!(repository_path("shop/app:v1"), "shop/app");
+    }
+
     // -- registry secret mount --
 
     #[test]
@@ -4494,12 +5569,16 @@ mod tests {
             env_vars: &[],
             repo_clone_url: "http://platform:8080/owner/test.git",
             git_ref: "main",
+            commit_sha: None,
             registry_secret: Some("pl-registry-00000000"),
             git_secret_name: None,
             step_type: "command",
             git_clone_image: "alpine/git:2.47.2",
             has_artifacts: false,
+            has_cache: false,
             proxy_binary_path: None,
+            services: &[],
+            clone: CloneOptions::default(),
         });
 
         let spec = pod.spec.unwrap();
@@ -4520,12 +5599,16 @@ mod tests {
             env_vars: &[],
             repo_clone_url: "http://platform:8080/owner/test.git",
             git_ref: "main",
+            commit_sha: None,
             registry_secret: None,
             git_secret_name: None,
             step_type: "command",
             git_clone_image: "alpine/git:2.47.2",
             has_artifacts: false,
+            has_cache: false,
             proxy_binary_path: None,
+            services: &[],
+            clone: CloneOptions::default(),
         });
 
         let spec = pod.spec.unwrap();
@@ -4547,12 +5630,16 @@ mod tests {
             env_vars: &[],
             repo_clone_url: "http://platform:8080/owner/test.git",
             git_ref: "main",
+            commit_sha: None,
             registry_secret: None,
             git_secret_name: None,
             step_type: "command",
             git_clone_image: "alpine/git:2.47.2",
             has_artifacts: false,
+            has_cache: false,
             proxy_binary_path: None,
+            services: &[],
+            clone: CloneOptions::default(),
         });
 
         let spec = pod.spec.unwrap();
@@ -4574,12 +5661,16 @@ mod tests {
             env_vars: &[],
             repo_clone_url: "http://platform:8080/owner/test.git",
             git_ref: "main",
+            commit_sha: None,
             registry_secret: Some("pl-registry-00000000"),
             git_secret_name: None,
             step_type: "command",
             git_clone_image: "alpine/git:2.47.2",
             has_artifacts: false,
+            has_cache: false,
             proxy_binary_path: None,
+            services: &[],
+            clone: CloneOptions::default(),
         });
 
         let spec = pod.spec.unwrap();
@@ -4800,12 +5891,16 @@ mod tests {
             env_vars: &[],
             repo_clone_url: "http://platform:8080/owner/test.git",
             git_ref: "main",
+            commit_sha: None,
             registry_secret: None,
             git_secret_name: None,
             step_type: "command",
             git_clone_image: "alpine/git:2.47.2",
             has_artifacts: false,
+            has_cache: false,
             proxy_binary_path: None,
+            services: &[],
+            clone: CloneOptions::default(),
         });
 
         let container = &pod.spec.unwrap().containers[0];
@@ -4825,12 +5920,16 @@ mod tests {
             env_vars: &[],
             repo_clone_url: "http://platform:8080/owner/test.git",
             git_ref: "main",
+            commit_sha: None,
             registry_secret: None,
             git_secret_name: None,
             step_type: "command",
             git_clone_image: "alpine/git:2.47.2",
             has_artifacts: false,
+            has_cache: false,
             proxy_binary_path: None,
+            services: &[],
+            clone: CloneOptions::default(),
         });
 
         let container = &pod.spec.unwrap().containers[0];
@@ -4850,12 +5949,16 @@ mod tests {
             env_vars: &[],
             repo_clone_url: "http://platform:8080/owner/repo.git",
             git_ref: "main",
+            commit_sha: None,
             registry_secret: None,
             git_secret_name: None,
             step_type: "command",
             git_clone_image: "alpine/git:2.47.2",
             has_artifacts: false,
+            has_cache: false,
             proxy_binary_path: None,
+            services: &[],
+            clone: CloneOptions::default(),
         });
 
         let init = &pod.spec.unwrap().init_containers.unwrap()[0];
@@ -4906,6 +6009,97 @@ mod tests {
         );
     }
 
+    #[test]
+    fn build_pod_spec_checks_out_triggering_commit() {
+        let sha = "4b825dc642cb6eb9a060e54bf8d69288fbee4904";
+        let pod = build_pod_spec(&PodSpecParams {
+            pod_name: "pl-test",
+            pipeline_id: Uuid::nil(),
+            project_id: Uuid::nil(),
+            step_name: "test",
+            image: "alpine:3.19",
+            commands: &["true".into()],
+            env_vars: &[],
+            repo_clone_url: "http://platform:8080/owner/repo.git",
+            git_ref: "refs/heads/main",
+            commit_sha: Some(sha),
+            registry_secret: None,
+            git_secret_name: None,
+            step_type: "command",
+            git_clone_image: "alpine/git:2.47.2",
+            has_artifacts: false,
+            has_cache: false,
+            proxy_binary_path: None,
+            services: &[],
+            clone: CloneOptions::default(),
+        });
+
+        let init = &pod.spec.unwrap().init_containers.unwrap()[0];
+        let env = init.env.as_ref().unwrap();
+        let sha_env = env.iter().find(|e| e.name == "GIT_COMMIT_SHA").unwrap();
+        assert_eq!(sha_env.value.as_deref(), Some(sha));
+        // The SHA reaches the script through the env var, never interpolated
+        let script = &init.args.as_ref().unwrap()[0];
+        assert!(!script.contains(sha));
+        assert!(
+            script.contains("git -C /workspace fetch --depth 1 origin \"$GIT_COMMIT_SHA\""),
+            "{script}"
+        );
+        assert!(
+            script.contains("git -C /workspace checkout -q \"$GIT_COMMIT_SHA\""),
+            "{script}"
+        );
+    }
+
+    #[test]
+    fn clone_script_depth_and_tags() {
+        let shallow = CloneOptions::default().script();
+        assert!(
+            shallow.contains("git clone --depth 1 --branch"),
+            "{shallow}"
+        );
+        assert!(!shallow.contains("fetch --tags"), "{shallow}");
+
+        let full = CloneOptions {
+            depth: 0,
+            tags: true,
+        }
+        .script();
+        assert!(!full.contains("--depth"), "{full}");
+        assert!(
+            full.contains("/workspace 2>&1 && git -C /workspace fetch --tags --force origin 2>&1"),
+            "{full}"
+        );
+        assert!(full.contains("fetch origin \"$GIT_COMMIT_SHA\""), "{full}");
+        // The tag fetch authenticates the same way as the clone
+        assert!(full.contains("export GIT_ASKPASS="), "{full}");
+
+        let deeper = CloneOptions {
+            depth: 50,
+            tags: false,
+        }
+        .script();
+        assert!(deeper.contains("git clone --depth 50 --branch"), "{deeper}");
+    }
+
+    #[test]
+    fn extract_clone_options_from_step_config() {
+        assert_eq!(extract_clone_options(None), CloneOptions::default());
+        let config = serde_json::json!({"clone_depth": 0, "fetch_tags": true});
+        assert_eq!(
+            extract_clone_options(Some(&config)),
+            CloneOptions {
+                depth: 0,
+                tags: true
+            }
+        );
+        let config = serde_json::json!({"services": []});
+        assert_eq!(
+            extract_clone_options(Some(&config)),
+            CloneOptions::default()
+        );
+    }
+
     #[test]
     fn build_pod_spec_with_env_vars() {
         let pod = build_pod_spec(&PodSpecParams {
@@ -4918,12 +6112,16 @@ mod tests {
             env_vars: &[env_var("FOO", "bar"), env_var("BAZ", "qux")],
             repo_clone_url: "http://platform:8080/owner/test.git",
             git_ref: "main",
+            commit_sha: None,
             registry_secret: None,
             git_secret_name: None,
             step_type: "command",
             git_clone_image: "alpine/git:2.47.2",
             has_artifacts: false,
+            has_cache: false,
             proxy_binary_path: None,
+            services: &[],
+            clone: CloneOptions::default(),
         });
 
         let container = &pod.spec.unwrap().containers[0];
@@ -5009,12 +6207,16 @@ mod tests {
             env_vars: &[],
             repo_clone_url: "http://platform:8080/owner/test.git",
             git_ref: "main",
+            commit_sha: None,
             registry_secret: None,
             git_secret_name: None,
             step_type: "command",
             git_clone_image: "alpine/git:2.47.2",
             has_artifacts: false,
+            has_cache: false,
             proxy_binary_path: None,
+            services: &[],
+            clone: CloneOptions::default(),
         });
 
         let init = &pod.spec.unwrap().init_containers.unwrap()[0];
@@ -5037,12 +6239,16 @@ mod tests {
             env_vars: &[],
             repo_clone_url: "http://platform:8080/owner/test.git",
             git_ref: "feat/$(malicious-cmd)",
+            commit_sha: None,
             registry_secret: None,
             git_secret_name: None,
             step_type: "command",
             git_clone_image: "alpine/git:2.47.2",
             has_artifacts: false,
+            has_cache: false,
             proxy_binary_path: None,
+            services: &[],
+            clone: CloneOptions::default(),
         });
 
         let init = &pod.spec.unwrap().init_containers.unwrap()[0];
@@ -5077,12 +6283,16 @@ mod tests {
             env_vars: &[],
             repo_clone_url: "http://platform:8080/owner/test.git",
             git_ref: "main",
+            commit_sha: None,
             registry_secret: None,
             git_secret_name: None,
             step_type: "command",
             git_clone_image: "alpine/git:2.47.2",
             has_artifacts: false,
+            has_cache: false,
             proxy_binary_path: None,
+            services: &[],
+            clone: CloneOptions::default(),
         });
 
         let spec = pod.spec.unwrap();
@@ -5107,12 +6317,16 @@ mod tests {
             env_vars: &[],
             repo_clone_url: "http://platform:8080/owner/test.git",
             git_ref: "main",
+            commit_sha: None,
             registry_secret: None,
             git_secret_name: None,
             step_type: "imagebuild",
             git_clone_image: "alpine/git:2.47.2",
             has_artifacts: false,
+            has_cache: false,
             proxy_binary_path: None,
+            services: &[],
+            clone: CloneOptions::default(),
         });
 
         let spec = pod.spec.unwrap();
@@ -5135,12 +6349,16 @@ mod tests {
             env_vars: &[],
             repo_clone_url: "http://platform:8080/owner/test.git",
             git_ref: "main",
+            commit_sha: None,
             registry_secret: None,
             git_secret_name: None,
             step_type: "command",
             git_clone_image: "alpine/git:2.47.2",
             has_artifacts: false,
+            has_cache: false,
             proxy_binary_path: None,
+            services: &[],
+            clone: CloneOptions::default(),
         });
 
         let spec = pod.spec.unwrap();
@@ -5163,12 +6381,16 @@ mod tests {
             env_vars: &[],
             repo_clone_url: "http://platform:8080/owner/test.git",
             git_ref: "main",
+            commit_sha: None,
             registry_secret: None,
             git_secret_name: None,
             step_type: "command",
             git_clone_image: "alpine/git:2.47.2",
             has_artifacts: false,
+            has_cache: false,
             proxy_binary_path: None,
+            services: &[],
+            clone: CloneOptions::default(),
         });
 
         let spec = pod.spec.unwrap();
@@ -5597,12 +6819,16 @@ mod tests {
             env_vars: &[],
             repo_clone_url: "http://platform:8080/owner/test.git",
             git_ref: "main",
+            commit_sha: None,
             registry_secret: None,
             git_secret_name: Some("pl-git-12345678"),
             step_type: "command",
             git_clone_image: "alpine/git:2.47.2",
             has_artifacts: false,
+            has_cache: false,
             proxy_binary_path: None,
+            services: &[],
+            clone: CloneOptions::default(),
         });
 
         let spec = pod.spec.unwrap();
@@ -5640,12 +6866,16 @@ mod tests {
             env_vars: &[],
             repo_clone_url: "http://platform:8080/owner/test.git",
             git_ref: "main",
+            commit_sha: None,
             registry_secret: Some("pl-registry-12345678"),
             git_secret_name: Some("pl-git-12345678"),
             step_type: "imagebuild",
             git_clone_image: "alpine/git:2.47.2",
             has_artifacts: false,
+            has_cache: false,
             proxy_binary_path: None,
+            services: &[],
+            clone: CloneOptions::default(),
         });
 
         let spec = pod.spec.unwrap();
@@ -5809,6 +7039,7 @@ mod tests {
             commands: vec![],
             condition_events: vec![],
             condition_branches: vec!["main".into(), "release/*".into()],
+            condition_when: None,
             deploy_test: None,
             depends_on: vec![],
             environment: None,
@@ -5831,6 +7062,7 @@ mod tests {
             commands: vec![],
             condition_events: vec!["push".into()],
             condition_branches: vec!["main".into()],
+            condition_when: None,
             deploy_test: None,
             depends_on: vec![],
             environment: None,
@@ -6319,12 +7551,16 @@ mod tests {
             env_vars: &[],
             repo_clone_url: "http://platform:8080/owner/test.git",
             git_ref: "main",
+            commit_sha: None,
             registry_secret: None,
             git_secret_name: None,
             step_type: "command",
             git_clone_image: "alpine/git:2.47.2",
             has_artifacts: false,
+            has_cache: false,
             proxy_binary_path: None,
+            services: &[],
+            clone: CloneOptions::default(),
         });
 
         let spec = pod.spec.unwrap();
@@ -6353,12 +7589,16 @@ mod tests {
             env_vars: &[],
             repo_clone_url: "http://platform:8080/owner/test.git",
             git_ref: "main",
+            commit_sha: None,
             registry_secret: None,
             git_secret_name: None,
             step_type: "deploy_test",
             git_clone_image: "alpine/git:2.47.2",
             has_artifacts: false,
+            has_cache: false,
             proxy_binary_path: None,
+            services: &[],
+            clone: CloneOptions::default(),
         });
 
         let spec = pod.spec.unwrap();
@@ -6433,6 +7673,7 @@ mod tests {
             commands: vec![],
             condition_events: vec![],
             condition_branches: vec![],
+            condition_when: None,
             deploy_test: Some(serde_json::json!({"test_image": "test:latest"})),
             depends_on: vec![],
             environment: None,
@@ -6454,6 +7695,7 @@ mod tests {
             commands: vec![],
             condition_events: vec!["push".into()],
             condition_branches: vec!["main".into()],
+            condition_when: None,
             deploy_test: Some(serde_json::json!({"test_image": "test:latest"})),
             depends_on: vec![],
             environment: None,
@@ -6824,6 +8066,7 @@ mod tests {
             commands: vec![],
             condition_events: vec!["push".into()],
             condition_branches: vec![],
+            condition_when: None,
             deploy_test: None,
             depends_on: vec!["build".into()],
             environment: None,
@@ -6864,12 +8107,16 @@ mod tests {
             env_vars: &[],
             repo_clone_url: "http://platform:8080/owner/test.git",
             git_ref: "main",
+            commit_sha: None,
             registry_secret: None,
             git_secret_name: None,
             step_type: "gitops_sync",
             git_clone_image: "alpine/git:2.47.2",
             has_artifacts: false,
+            has_cache: false,
             proxy_binary_path: None,
+            services: &[],
+            clone: CloneOptions::default(),
         });
 
         let spec = pod.spec.unwrap();
@@ -6892,12 +8139,16 @@ mod tests {
             env_vars: &[],
             repo_clone_url: "http://platform:8080/owner/test.git",
             git_ref: "main",
+            commit_sha: None,
             registry_secret: None,
             git_secret_name: None,
             step_type: "deploy_watch",
             git_clone_image: "alpine/git:2.47.2",
             has_artifacts: false,
+            has_cache: false,
             proxy_binary_path: None,
+            services: &[],
+            clone: CloneOptions::default(),
         });
 
         let spec = pod.spec.unwrap();
@@ -6922,12 +8173,16 @@ mod tests {
             env_vars: &[],
             repo_clone_url: "http://platform:8080/owner/test.git",
             git_ref: "main",
+            commit_sha: None,
             registry_secret: None,
             git_secret_name: None,
             step_type: "command",
             git_clone_image: "custom-registry/git-clone:v3.0",
             has_artifacts: false,
+            has_cache: false,
             proxy_binary_path: None,
+            services: &[],
+            clone: CloneOptions::default(),
         });
 
         let spec = pod.spec.unwrap();
@@ -7010,12 +8265,16 @@ mod tests {
             env_vars: &[],
             repo_clone_url: "http://platform:8080/owner/test.git",
             git_ref: "main",
+            commit_sha: None,
             registry_secret: None,
             git_secret_name: None,
             step_type: "command",
             git_clone_image: "alpine/git:2.47.2",
             has_artifacts: false,
+            has_cache: false,
             proxy_binary_path: None,
+            services: &[],
+            clone: CloneOptions::default(),
         });
 
         let spec = pod.spec.unwrap();
@@ -7037,12 +8296,16 @@ mod tests {
             env_vars: &[],
             repo_clone_url: "http://platform:8080/owner/test.git",
             git_ref: "main",
+            commit_sha: None,
             registry_secret: None,
             git_secret_name: None,
             step_type: "command",
             git_clone_image: "alpine/git:2.47.2",
             has_artifacts: false,
+            has_cache: false,
             proxy_binary_path: None,
+            services: &[],
+            clone: CloneOptions::default(),
         });
 
         let spec = pod.spec.unwrap();
@@ -7063,12 +8326,16 @@ mod tests {
             env_vars: &[],
             repo_clone_url: "http://platform:8080/owner/test.git",
             git_ref: "main",
+            commit_sha: None,
             registry_secret: None,
             git_secret_name: None,
             step_type: "command",
             git_clone_image: "alpine/git:2.47.2",
             has_artifacts: false,
+            has_cache: false,
             proxy_binary_path: None,
+            services: &[],
+            clone: CloneOptions::default(),
         });
 
         let spec = pod.spec.unwrap();
@@ -7093,12 +8360,16 @@ mod tests {
             env_vars: &[],
             repo_clone_url: "http://platform:8080/owner/test.git",
             git_ref: "main",
+            commit_sha: None,
             registry_secret: None,
             git_secret_name: None,
             step_type: "command",
             git_clone_image: "alpine/git:2.47.2",
             has_artifacts: false,
+            has_cache: false,
             proxy_binary_path: None,
+            services: &[],
+            clone: CloneOptions::default(),
         });
 
         let spec = pod.spec.unwrap();
@@ -7123,12 +8394,16 @@ mod tests {
             env_vars: &[],
             repo_clone_url: "http://platform:8080/owner/test.git",
             git_ref: "main",
+            commit_sha: None,
             registry_secret: None,
             git_secret_name: Some("pl-git-abc123"),
             step_type: "command",
             git_clone_image: "alpine/git:2.47.2",
             has_artifacts: false,
+            has_cache: false,
             proxy_binary_path: None,
+            services: &[],
+            clone: CloneOptions::default(),
         });
 
         let spec = pod.spec.unwrap();
@@ -7164,6 +8439,7 @@ mod tests {
             commands: vec![],
             condition_events: vec!["push".into(), "tag".into()],
             condition_branches: vec![],
+            condition_when: None,
             deploy_test: None,
             depends_on: vec![],
             environment: None,
@@ -7232,10 +8508,21 @@ mod tests {
             namespace_slug: "test-ns".into(),
             otlp_token: Some("otlp-token".into()),
             git_secret_name: "pl-git-12345678".into(),
+            triggered_by: None,
+            variables: vec![("DEPLOY_ENV".into(), "staging".into())],
+            changed_files: Some(vec!["src/main.rs".into()]),
+            group_variables: crate::pipeline::variable_groups::GroupVariables {
+                vars: vec![("DB_PASSWORD".into(), "hunter2-hunter2".into())],
+                masked: vec!["hunter2-hunter2".into()],
+            },
         };
         let debug = format!("{meta:?}");
         assert!(debug.contains("test-project"));
         assert!(debug.contains("refs/heads/main"));
+        assert!(
+            !debug.contains("hunter2"),
+            "masked values must not be printed"
+        );
     }
 
     #[test]
@@ -7252,6 +8539,10 @@ mod tests {
             namespace_slug: "app".into(),
             otlp_token: None,
             git_secret_name: "pl-git-00000000".into(),
+            triggered_by: None,
+            variables: Vec::new(),
+            changed_files: None,
+            group_variables: crate::pipeline::variable_groups::GroupVariables::default(),
         };
         assert!(meta.commit_sha.is_none());
         assert!(meta.version.is_none());
@@ -7270,6 +8561,7 @@ mod tests {
             commands: vec!["deploy.sh".into(), "verify.sh".into()],
             condition_events: vec!["push".into()],
             condition_branches: vec!["main".into(), "release/*".into()],
+            condition_when: None,
             deploy_test: Some(serde_json::json!({"test_image": "test:v1"})),
             depends_on: vec!["build".into(), "test".into()],
             environment: Some(serde_json::json!({"ENV": "production"})),
@@ -7287,6 +8579,111 @@ mod tests {
         assert!(row.step_config.is_some());
     }
 
+    // -- step_skip_reason --
+
+    fn when_meta(trigger: &str, changed_files: Option<Vec<String>>) -> PipelineMeta {
+        PipelineMeta {
+            git_ref: String::new(),
+            commit_sha: None,
+            version: None,
+            project_name: "app".into(),
+            repo_clone_url: String::new(),
+            git_auth_token: String::new(),
+            namespace: "app-dev".into(),
+            trigger_type: trigger.into(),
+            namespace_slug: "app".into(),
+            otlp_token: None,
+            git_secret_name: String::new(),
+            triggered_by: None,
+            variables: Vec::new(),
+            changed_files,
+            group_variables: crate::pipeline::variable_groups::GroupVariables::default(),
+        }
+    }
+
+    fn when_step(when: Option<serde_json::Value>) -> StepRow {
+        StepRow {
+            id: Uuid::nil(),
+            step_order: 0,
+            name: "deploy".into(),
+            image: "alpine".into(),
+            commands: vec![],
+            condition_events: vec![],
+            condition_branches: vec![],
+            condition_when: when,
+            deploy_test: None,
+            depends_on: vec![],
+            environment: None,
+            gate: false,
+            step_type: "command".into(),
+            step_config: None,
+        }
+    }
+
+    #[test]
+    fn skip_reason_branch_expression() {
+        let step = when_step(Some(serde_json::json!(r#"branch == "main""#)));
+        let meta = when_meta("push", None);
+        assert_eq!(step_skip_reason(&step, &meta, "main", false), None);
+        assert_eq!(
+            step_skip_reason(&step, &meta, "feature/login", false).as_deref(),
+            Some(r#"when condition not met: branch == "main""#)
+        );
+        assert_eq!(
+            step_skip_reason(&step, &meta, "main", true).as_deref(),
+            Some("an earlier step failed")
+        );
+    }
+
+    #[test]
+    fn skip_reason_status_keywords() {
+        let meta = when_meta("push", None);
+        let plain = when_step(None);
+        assert_eq!(step_skip_reason(&plain, &meta, "main", false), None);
+        assert_eq!(
+            step_skip_reason(&plain, &meta, "main", true).as_deref(),
+            Some("an earlier step failed")
+        );
+
+        let on_failure = when_step(Some(serde_json::json!("on_failure")));
+        assert_eq!(step_skip_reason(&on_failure, &meta, "main", true), None);
+        assert_eq!(
+            step_skip_reason(&on_failure, &meta, "main", false).as_deref(),
+            Some("when condition not met: on_failure")
+        );
+
+        let always = when_step(Some(serde_json::json!("always")));
+        assert_eq!(step_skip_reason(&always, &meta, "main", true), None);
+        assert_eq!(step_skip_reason(&always, &meta, "main", false), None);
+    }
+
+    #[test]
+    fn skip_reason_changes() {
+        let step = when_step(Some(serde_json::json!({"changes": ["src/**"]})));
+        let touched = when_meta("push", Some(vec!["src/lib.rs".into()]));
+        assert_eq!(step_skip_reason(&step, &touched, "main", false), None);
+
+        let docs_only = when_meta("push", Some(vec!["docs/index.md".into()]));
+        assert_eq!(
+            step_skip_reason(&step, &docs_only, "main", false).as_deref(),
+            Some("no changed files match src/**")
+        );
+
+        // Unknown changes (e.g. a manual trigger) run the step
+        let unknown = when_meta("api", None);
+        assert_eq!(step_skip_reason(&step, &unknown, "main", false), None);
+    }
+
+    #[test]
+    fn skip_reason_only_condition_comes_first() {
+        let mut step = when_step(Some(serde_json::json!("always")));
+        step.condition_events = vec!["mr".into()];
+        assert_eq!(
+            step_skip_reason(&step, &when_meta("push", None), "main", true).as_deref(),
+            Some("condition not matched")
+        );
+    }
+
     // -- TestNamespaceGuard: verify field semantics --
 
     #[test]
@@ -7376,12 +8773,16 @@ mod tests {
             env_vars: &[],
             repo_clone_url: "http://platform:8080/owner/repo.git",
             git_ref: "main",
+            commit_sha: None,
             registry_secret: None,
             git_secret_name: None,
             step_type: "command",
             git_clone_image: "alpine/git:2.47.2",
             has_artifacts: false,
+            has_cache: false,
             proxy_binary_path: Some("/tmp/proxy"),
+            services: &[],
+            clone: CloneOptions::default(),
         });
         let spec = pod.spec.as_ref().unwrap();
         let container = &spec.containers[0];
@@ -7409,12 +8810,16 @@ mod tests {
             env_vars: &[],
             repo_clone_url: "http://platform:8080/owner/repo.git",
             git_ref: "main",
+            commit_sha: None,
             registry_secret: None,
             git_secret_name: None,
             step_type: "command",
             git_clone_image: "alpine/git:2.47.2",
             has_artifacts: false,
+            has_cache: false,
             proxy_binary_path: Some("/tmp/proxy"),
+            services: &[],
+            clone: CloneOptions::default(),
         });
         let spec = pod.spec.as_ref().unwrap();
         let volumes = spec.volumes.as_ref().unwrap();
@@ -7443,12 +8848,16 @@ mod tests {
             env_vars: &[],
             repo_clone_url: "http://platform:8080/owner/repo.git",
             git_ref: "main",
+            commit_sha: None,
             registry_secret: None,
             git_secret_name: None,
             step_type: "command",
             git_clone_image: "alpine/git:2.47.2",
             has_artifacts: false,
+            has_cache: false,
             proxy_binary_path: None,
+            services: &[],
+            clone: CloneOptions::default(),
         });
         let spec = pod.spec.as_ref().unwrap();
         let container = &spec.containers[0];
@@ -7489,12 +8898,16 @@ mod tests {
             env_vars: &[],
             repo_clone_url: "http://platform:8080/owner/test.git",
             git_ref: "main",
+            commit_sha: None,
             registry_secret: None,
             git_secret_name: None,
             step_type: "command",
             git_clone_image: "alpine/git:2.47.2",
             has_artifacts: false,
+            has_cache: false,
             proxy_binary_path: None,
+            services: &[],
+            clone: CloneOptions::default(),
         });
 
         let labels = pod.metadata.labels.unwrap();
@@ -7520,12 +8933,16 @@ mod tests {
             env_vars: &[],
             repo_clone_url: "http://platform:8080/owner/test.git",
             git_ref: "main",
+            commit_sha: None,
             registry_secret: None,
             git_secret_name: None,
             step_type: "command",
             git_clone_image: "alpine/git:2.47.2",
             has_artifacts: false,
+            has_cache: false,
             proxy_binary_path: None,
+            services: &[],
+            clone: CloneOptions::default(),
         });
 
         let spec = pod.spec.unwrap();
@@ -7547,12 +8964,16 @@ mod tests {
             env_vars: &[],
             repo_clone_url: "http://platform:8080/owner/test.git",
             git_ref: "main",
+            commit_sha: None,
             registry_secret: None,
             git_secret_name: None,
             step_type: "command",
             git_clone_image: "alpine/git:2.47.2",
             has_artifacts: false,
+            has_cache: false,
             proxy_binary_path: None,
+            services: &[],
+            clone: CloneOptions::default(),
         });
 
         let spec = pod.spec.unwrap();
@@ -7935,12 +9356,16 @@ mod tests {
             env_vars: &[],
             repo_clone_url: "http://platform:8080/owner/test.git",
             git_ref: "main",
+            commit_sha: None,
             registry_secret: None,
             git_secret_name: None,
             step_type: "command",
             git_clone_image: "alpine/git:2.47.2",
             has_artifacts: true,
+            has_cache: false,
             proxy_binary_path: None,
+            services: &[],
+            clone: CloneOptions::default(),
         });
 
         let container = &pod.spec.unwrap().containers[0];
@@ -7959,6 +9384,42 @@ mod tests {
         );
     }
 
+    #[test]
+    fn build_pod_spec_cache_waits_for_restore() {
+        let pod = build_pod_spec(&PodSpecParams {
+            pod_name: "pl-test",
+            pipeline_id: Uuid::nil(),
+            project_id: Uuid::nil(),
+            step_name: "test",
+            image: "rust:1.85",
+            commands: &["cargo build".into()],
+            env_vars: &[],
+            repo_clone_url: "http://platform:8080/owner/test.git",
+            git_ref: "main",
+            commit_sha: None,
+            registry_secret: None,
+            git_secret_name: None,
+            step_type: "command",
+            git_clone_image: "alpine/git:2.47.2",
+            has_artifacts: false,
+            has_cache: true,
+            proxy_binary_path: None,
+            services: &[],
+            clone: CloneOptions::default(),
+        });
+
+        let container = &pod.spec.unwrap().containers[0];
+        let script = &container.args.as_ref().unwrap()[0];
+        assert!(
+            script.starts_with("while [ ! -f /tmp/.cache-restored ]"),
+            "script should wait for the cache restore: {script}"
+        );
+        assert!(
+            script.contains("/tmp/.done"),
+            "container should stay alive to save the cache: {script}"
+        );
+    }
+
     #[test]
     fn build_pod_spec_no_artifact_script_unchanged() {
         let pod = build_pod_spec(&PodSpecParams {
@@ -7971,12 +9432,16 @@ mod tests {
             env_vars: &[],
             repo_clone_url: "http://platform:8080/owner/test.git",
             git_ref: "main",
+            commit_sha: None,
             registry_secret: None,
             git_secret_name: None,
             step_type: "command",
             git_clone_image: "alpine/git:2.47.2",
             has_artifacts: false,
+            has_cache: false,
             proxy_binary_path: None,
+            services: &[],
+            clone: CloneOptions::default(),
         });
 
         let container = &pod.spec.unwrap().containers[0];
@@ -8017,4 +9482,95 @@ mod tests {
         let defs = extract_artifact_defs(Some(&config));
         assert!(defs.is_empty());
     }
+
+    // -- step services --
+
+    #[test]
+    fn build_pod_spec_adds_service_sidecars() {
+        let services = vec![super::super::definition::ServiceDef {
+            image: "postgres:16".into(),
+            env: BTreeMap::from([("POSTGRES_PASSWORD".into(), "test".into())]),
+            port: Some(5432),
+        }];
+        let pod = build_pod_spec(&PodSpecParams {
+            pod_name: "pl-test",
+            pipeline_id: Uuid::nil(),
+            project_id: Uuid::nil(),
+            step_name: "test",
+            image: "alpine:3.19",
+            commands: &["pg_isready -h localhost".into()],
+            env_vars: &[],
+            repo_clone_url: "http://platform:8080/owner/test.git",
+            git_ref: "main",
+            commit_sha: None,
+            registry_secret: None,
+            git_secret_name: None,
+            step_type: "command",
+            git_clone_image: "alpine/git:2.47.2",
+            has_artifacts: false,
+            has_cache: false,
+            proxy_binary_path: None,
+            services: &services,
+            clone: CloneOptions::default(),
+        });
+
+        let spec = pod.spec.unwrap();
+        let init = spec.init_containers.unwrap();
+        assert_eq!(init.len(), 2);
+        assert_eq!(init[0].name, "clone", "clone must run before services");
+        let svc = &init[1];
+        assert_eq!(svc.name, "service-0");
+        assert_eq!(svc.image.as_deref(), Some("postgres:16"));
+        assert_eq!(svc.restart_policy.as_deref(), Some("Always"));
+        assert_eq!(svc.env.as_ref().unwrap()[0].name, "POSTGRES_PASSWORD");
+        let probe = svc.startup_probe.as_ref().unwrap();
+        assert_eq!(
+            probe.tcp_socket.as_ref().unwrap().port,
+            k8s_openapi::apimachinery::pkg::util::intstr::IntOrString::Int(5432)
+        );
+        assert_eq!(
+            spec.containers.len(),
+            1,
+            "step stays the only main container"
+        );
+        assert_eq!(spec.containers[0].name, "step");
+    }
+
+    #[test]
+    fn service_without_port_has_no_startup_probe() {
+        let svc = service_container(
+            1,
+            &super::super::definition::ServiceDef {
+                image: "redis:7".into(),
+                env: BTreeMap::new(),
+                port: None,
+            },
+        );
+        assert_eq!(svc.name, "service-1");
+        assert!(svc.startup_probe.is_none());
+        assert!(svc.env.is_none());
+        assert_eq!(
+            svc.security_context.unwrap().allow_privilege_escalation,
+            Some(false)
+        );
+    }
+
+    #[test]
+    fn extract_service_defs_from_step_config() {
+        let config = serde_json::json!({
+            "services": [
+                {"image": "postgres:16", "env": {"POSTGRES_PASSWORD": "test"}, "port": 5432},
+                {"image": "redis:7"}
+            ]
+        });
+        let defs = extract_service_defs(Some(&config));
+        assert_eq!(defs.len(), 2);
+        assert_eq!(defs[0].port, Some(5432));
+        assert_eq!(
+            defs[0].env.get("POSTGRES_PASSWORD").map(String::as_str),
+            Some("test")
+        );
+        assert!(defs[1].env.is_empty());
+        assert!(extract_service_defs(None).is_empty());
+    }
 }
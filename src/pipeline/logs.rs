@@ -0,0 +1,126 @@
+// Copyright (c) 2026 Steven Hooker. Exclusively licensed to and distributed by AgentSphere GmbH.
+// SPDX-License-Identifier: BUSL-1.1
+
+//! Timestamped step logs and the combined pipeline log view.
+//!
+//! Step containers' logs are read with kubelet timestamps, one RFC 3339
+//! timestamp and a space before every line. The executor stores them twice:
+//! stripped at the step's `log_ref` (what the step logs endpoint serves) and
+//! verbatim next to it (see [`timestamped_path`]) for the combined view and its
+//! `since` filter.
+
+use std::fmt::Write as _;
+
+use chrono::{DateTime, Utc};
+
+/// Where the timestamped copy of the log at `log_ref` is stored.
+pub fn timestamped_path(log_ref: &str) -> String {
+    let stem = log_ref.strip_suffix(".log").unwrap_or(log_ref);
+    format!("{stem}.ts.log")
+}
+
+/// Split the kubelet timestamp off a log line, if it has one.
+pub fn split_timestamp(line: &str) -> (Option<DateTime<Utc>>, &str) {
+    line.split_once(' ')
+        .and_then(|(ts, rest)| {
+            DateTime::parse_from_rfc3339(ts)
+                .ok()
+                .map(|t| (Some(t.with_timezone(&Utc)), rest))
+        })
+        .unwrap_or((None, line))
+}
+
+/// Drop the kubelet timestamps from timestamped logs.
+pub fn strip_timestamps(logs: &str) -> String {
+    let mut out = String::with_capacity(logs.len());
+    for line in logs.lines() {
+        out.push_str(split_timestamp(line).1);
+        out.push('\n');
+    }
+    if !logs.ends_with('\n') {
+        out.pop();
+    }
+    out
+}
+
+/// The lines of timestamped logs written after `since` (all lines if `None`).
+/// Lines without a timestamp are kept only when there is no `since`.
+pub fn lines_since(logs: &str, since: Option<DateTime<Utc>>) -> Vec<&str> {
+    logs.lines()
+        .filter(|line| match since {
+            None => true,
+            Some(since) => split_timestamp(line).0.is_some_and(|t| t > since),
+        })
+        .collect()
+}
+
+/// Delimiter line opening a step's section of the combined log, e.g.
+/// `==> [2/4] test (failure, exit 1, 12.3s)`.
+pub fn step_header(
+    position: usize,
+    total: usize,
+    name: &str,
+    status: &str,
+    exit_code: Option<i32>,
+    duration_ms: Option<i32>,
+) -> String {
+    let mut detail = status.to_owned();
+    if let Some(code) = exit_code.filter(|c| *c != 0) {
+        let _ = write!(detail, ", exit {code}");
+    }
+    if let Some(ms) = duration_ms {
+        let _ = write!(detail, ", {:.1}s", f64::from(ms) / 1000.0);
+    }
+    format!("==> [{position}/{total}] {name} ({detail})")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const LOGS: &str = "2026-10-15T09:30:00.100000000Z Step 1/4 : FROM alpine\n\
+                        2026-10-15T09:30:01.200000000Z  ---> abc123\n\
+                        2026-10-15T09:30:02.300000000Z done\n";
+
+    fn at(s: &str) -> DateTime<Utc> {
+        s.parse().unwrap()
+    }
+
+    #[test]
+    fn timestamped_path_sits_next_to_log_ref() {
+        assert_eq!(
+            timestamped_path("logs/pipelines/p/build.log"),
+            "logs/pipelines/p/build.ts.log"
+        );
+    }
+
+    #[test]
+    fn strip_keeps_content_and_indentation() {
+        assert_eq!(
+            strip_timestamps(LOGS),
+            "Step 1/4 : FROM alpine\n ---> abc123\ndone\n"
+        );
+        assert_eq!(strip_timestamps("no timestamp here"), "no timestamp here");
+        assert_eq!(strip_timestamps(""), "");
+    }
+
+    #[test]
+    fn since_filters_by_line_timestamp() {
+        assert_eq!(lines_since(LOGS, None).len(), 3);
+        let newer = lines_since(LOGS, Some(at("2026-10-15T09:30:01.200000000Z")));
+        assert_eq!(newer, vec!["2026-10-15T09:30:02.300000000Z done"]);
+        assert!(lines_since("untimed line\n", Some(at("2026-10-15T00:00:00Z"))).is_empty());
+    }
+
+    #[test]
+    fn header_shows_outcome() {
+        assert_eq!(
+            step_header(2, 4, "test", "failure", Some(1), Some(12_345)),
+            "==> [2/4] test (failure, exit 1, 12.3s)"
+        );
+        assert_eq!(
+            step_header(1, 1, "build", "running", None, None),
+            "==> [1/1] build (running)"
+        );
+    }
+}
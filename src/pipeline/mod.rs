@@ -3,10 +3,16 @@
 
 //! CI/CD pipeline definition, execution, and status management.
 
+pub mod cache;
+pub mod cron;
 pub mod definition;
 pub mod error;
 pub mod executor;
+pub mod logs;
+pub mod schedule;
 pub mod trigger;
+pub mod variable_groups;
+pub mod when;
 
 /// Create a K8s-safe slug from a name.
 pub fn slug(name: &str) -> String {
@@ -120,12 +126,42 @@ impl PipelineStatus {
                 Self::Running | Self::Cancelled | Self::Failure
             ) | (
                 Self::Running,
-                Self::Success | Self::Failure | Self::Cancelled
+                // Pending: requeued after the executing replica shut down or died.
+                Self::Pending | Self::Success | Self::Failure | Self::Cancelled
             )
         )
     }
 }
 
+/// Claim order for pending pipelines: higher priorities are claimed first,
+/// still subject to the per-project concurrency limit. Manual runs default to
+/// `High` and scheduled runs to `Low`; everything else is `Normal`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PipelinePriority {
+    High,
+    Normal,
+    Low,
+}
+
+impl PipelinePriority {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::High => "high",
+            Self::Normal => "normal",
+            Self::Low => "low",
+        }
+    }
+
+    pub fn parse(s: &str) -> Option<Self> {
+        match s {
+            "high" => Some(Self::High),
+            "normal" => Some(Self::Normal),
+            "low" => Some(Self::Low),
+            _ => None,
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -138,12 +174,13 @@ mod tests {
         assert!(PipelineStatus::Running.can_transition_to(PipelineStatus::Success));
         assert!(PipelineStatus::Running.can_transition_to(PipelineStatus::Failure));
         assert!(PipelineStatus::Running.can_transition_to(PipelineStatus::Cancelled));
+        assert!(PipelineStatus::Running.can_transition_to(PipelineStatus::Pending));
     }
 
     #[test]
     fn pipeline_status_invalid_transitions() {
         assert!(!PipelineStatus::Pending.can_transition_to(PipelineStatus::Success));
-        assert!(!PipelineStatus::Running.can_transition_to(PipelineStatus::Pending));
+        assert!(!PipelineStatus::Pending.can_transition_to(PipelineStatus::Pending));
         assert!(!PipelineStatus::Running.can_transition_to(PipelineStatus::Running));
     }
 
@@ -174,6 +211,18 @@ mod tests {
         assert_eq!(PipelineStatus::parse("unknown"), None);
     }
 
+    #[test]
+    fn pipeline_priority_parse_roundtrip() {
+        for priority in [
+            PipelinePriority::High,
+            PipelinePriority::Normal,
+            PipelinePriority::Low,
+        ] {
+            assert_eq!(PipelinePriority::parse(priority.as_str()), Some(priority));
+        }
+        assert_eq!(PipelinePriority::parse("urgent"), None);
+    }
+
     #[test]
     fn slugify_simple_branch() {
         assert_eq!(slugify_branch("feature/add-login"), "feature-add-login");
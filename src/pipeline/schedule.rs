@@ -0,0 +1,157 @@
+// Copyright (c) 2026 Steven Hooker. Exclusively licensed to and distributed by AgentSphere GmbH.
+// SPDX-License-Identifier: BUSL-1.1
+
+//! Cron-scheduled pipelines.
+//!
+//! Every enabled row in `pipeline_schedules` carries the next time it is due.
+//! This task enqueues a pipeline for each due schedule and advances
+//! `next_run_at` with a compare-and-set, so a slot fires once even with
+//! several replicas running. Slots missed while the platform was down collapse
+//! into a single run, and a slot is skipped while the schedule's previous
+//! pipeline is still pending or running.
+
+use std::time::Duration;
+
+use chrono::{DateTime, Utc};
+use tracing::Instrument;
+use uuid::Uuid;
+
+use crate::pipeline::cron::CronExpr;
+use crate::store::AppState;
+
+const POLL_INTERVAL: Duration = Duration::from_secs(30);
+
+/// Most schedules fired per iteration; the rest are picked up on the next tick.
+const BATCH_SIZE: i64 = 100;
+
+struct DueSchedule {
+    id: Uuid,
+    project_id: Uuid,
+    cron: String,
+    git_ref: String,
+    next_run_at: DateTime<Utc>,
+    user_id: Uuid,
+    repo_path: Option<String>,
+    last_status: Option<String>,
+}
+
+/// Background task that enqueues pipelines for due schedules.
+pub async fn run(state: AppState, cancel: tokio_util::sync::CancellationToken) {
+    let mut interval = tokio::time::interval(POLL_INTERVAL);
+    state.task_registry.register("pipeline_schedules", 120);
+    loop {
+        tokio::select! {
+            () = cancel.cancelled() => {
+                tracing::info!("pipeline scheduler shutting down");
+                break;
+            }
+            _ = interval.tick() => {
+                let iter_trace_id = uuid::Uuid::new_v4().to_string().replace('-', "");
+                let span = tracing::info_span!(
+                    "task_iteration",
+                    task_name = "pipeline_schedules",
+                    trace_id = %iter_trace_id,
+                    source = "system",
+                );
+                async {
+                    match fire_due_schedules(&state).await {
+                        Ok(_) => state.task_registry.heartbeat("pipeline_schedules"),
+                        Err(e) => {
+                            state.task_registry.report_error("pipeline_schedules", &e.to_string());
+                            tracing::error!(error = %e, "pipeline scheduler failed");
+                        }
+                    }
+                }.instrument(span).await;
+            }
+        }
+    }
+}
+
+/// Enqueue a pipeline for every due schedule. Returns how many were created.
+pub async fn fire_due_schedules(state: &AppState) -> anyhow::Result<usize> {
+    let due = sqlx::query_as!(
+        DueSchedule,
+        r#"
+        SELECT s.id, s.project_id, s.cron, s.git_ref, s.next_run_at AS "next_run_at!",
+               COALESCE(s.created_by, p.owner_id) AS "user_id!",
+               p.repo_path, lp.status AS "last_status?"
+        FROM pipeline_schedules s
+        JOIN projects p ON p.id = s.project_id AND p.is_active = true
+        LEFT JOIN pipelines lp ON lp.id = s.last_pipeline_id
+        WHERE s.enabled AND s.next_run_at <= now()
+        ORDER BY s.next_run_at
+        LIMIT $1
+        "#,
+        BATCH_SIZE,
+    )
+    .fetch_all(&state.pool)
+    .await?;
+
+    let mut fired = 0;
+    for schedule in &due {
+        // Validated on write; an expression that no longer parses pauses the schedule.
+        let cron = CronExpr::parse(&schedule.cron)
+            .inspect_err(|e| tracing::warn!(schedule_id = %schedule.id, error = %e, "invalid cron expression, pausing schedule"))
+            .ok();
+        let next = cron.as_ref().and_then(|c| c.next_after(Utc::now()));
+        if !claim(state, schedule, next).await? || cron.is_none() {
+            continue;
+        }
+        if matches!(schedule.last_status.as_deref(), Some("pending" | "running")) {
+            tracing::info!(schedule_id = %schedule.id, "previous scheduled pipeline still in progress, skipping");
+            continue;
+        }
+        let Some(repo_path) = schedule.repo_path.as_deref() else {
+            continue;
+        };
+
+        match crate::pipeline::trigger::on_schedule(
+            &state.pool,
+            std::path::Path::new(repo_path),
+            schedule.project_id,
+            &schedule.git_ref,
+            schedule.user_id,
+            &state.config.kaniko_image,
+        )
+        .await
+        {
+            Ok(Some(pipeline_id)) => {
+                sqlx::query!(
+                    "UPDATE pipeline_schedules SET last_pipeline_id = $2 WHERE id = $1",
+                    schedule.id,
+                    pipeline_id,
+                )
+                .execute(&state.pool)
+                .await?;
+                crate::pipeline::trigger::notify_executor(state, pipeline_id).await;
+                tracing::info!(schedule_id = %schedule.id, %pipeline_id, "scheduled pipeline created");
+                fired += 1;
+            }
+            Ok(None) => {
+                tracing::info!(schedule_id = %schedule.id, git_ref = %schedule.git_ref, "no pipeline definition for scheduled ref");
+            }
+            Err(e) => {
+                tracing::warn!(error = %e, schedule_id = %schedule.id, "scheduled pipeline trigger failed");
+            }
+        }
+    }
+    Ok(fired)
+}
+
+/// Move the schedule on to `next`. Returns false if another replica got there first.
+async fn claim(
+    state: &AppState,
+    schedule: &DueSchedule,
+    next: Option<DateTime<Utc>>,
+) -> anyhow::Result<bool> {
+    let result = sqlx::query!(
+        "UPDATE pipeline_schedules SET next_run_at = $3, last_run_at = now()
+         WHERE id = $1 AND next_run_at = $2",
+        schedule.id,
+        schedule.next_run_at,
+        next,
+    )
+    .execute(&state.pool)
+    .await?;
+    Ok(result.rows_affected() == 1)
+}
@@ -7,8 +7,10 @@ use std::path::Path;
 use sqlx::PgPool;
 use uuid::Uuid;
 
+use super::PipelinePriority;
 use super::definition::{self, PipelineDefinition};
 use super::error::PipelineError;
+use crate::secrets::engine::MasterKey;
 
 // ---------------------------------------------------------------------------
 // Types
@@ -20,6 +22,18 @@ pub struct PushTriggerParams {
     pub repo_path: std::path::PathBuf,
     pub branch: String,
     pub commit_sha: Option<String>,
+    /// Branch tip before the push; `None` for new branches or when unknown.
+    pub before_sha: Option<String>,
+}
+
+pub struct ApiTriggerParams {
+    pub project_id: Uuid,
+    pub user_id: Uuid,
+    pub repo_path: std::path::PathBuf,
+    pub git_ref: String,
+    /// Trigger variables from [`encrypt_variables`], exposed to every step.
+    pub encrypted_variables: Option<Vec<u8>>,
+    pub priority: PipelinePriority,
 }
 
 pub struct MrTriggerParams {
@@ -109,8 +123,10 @@ pub async fn on_push(
     crate::validation::check_branch_name(&params.branch)
         .map_err(|e| PipelineError::InvalidDefinition(e.to_string()))?;
 
-    let Some(yaml) = read_file_at_ref(&params.repo_path, &params.branch, ".platform.yaml").await
-    else {
+    // Read from the pushed commit rather than the branch tip, which a
+    // concurrent push may already have moved.
+    let tree_ref = params.commit_sha.as_deref().unwrap_or(&params.branch);
+    let Some(yaml) = read_file_at_ref(&params.repo_path, tree_ref, ".platform.yaml").await else {
         tracing::debug!("no .platform.yaml at ref, skipping pipeline trigger");
         return Ok(None);
     };
@@ -125,14 +141,18 @@ pub async fn on_push(
     // Determine dev image dockerfile: explicit YAML config takes priority, auto-detect as fallback
     let dev_dockerfile = if let Some(dev) = &def.dev_image {
         Some(dev.dockerfile.as_str())
-    } else if has_dockerfile_dev(&params.repo_path, &params.branch).await {
+    } else if has_dockerfile_dev(&params.repo_path, tree_ref).await {
         Some("Dockerfile.dev")
     } else {
         None
     };
 
     let git_ref = format!("refs/heads/{}", params.branch);
-    let version = read_version_at_ref(&params.repo_path, &params.branch).await;
+    let version = read_version_at_ref(&params.repo_path, tree_ref).await;
+    let changed = match (params.before_sha.as_deref(), params.commit_sha.as_deref()) {
+        (Some(before), Some(after)) => changed_files(&params.repo_path, before, after).await,
+        _ => None,
+    };
     let pipeline_id = create_pipeline_with_steps(
         pool,
         params.project_id,
@@ -140,16 +160,19 @@ pub async fn on_push(
         params.commit_sha.as_deref(),
         params.user_id,
         "push",
+        PipelinePriority::Normal,
         &def,
         dev_dockerfile,
         version.as_ref(),
+        None,
+        changed.as_deref(),
         kaniko_image,
     )
     .await?;
 
-    // Create annotated git tags for versioned pushes to main
-    if (params.branch == "main" || params.branch == "master")
-        && let Some(ref vi) = version
+    // Create annotated git tags for versioned pushes to the default branch
+    if let Some(ref vi) = version
+        && params.branch == project_default_branch(pool, params.project_id).await?
     {
         for ver in vi.images.values() {
             let tag_name = format!("v{ver}");
@@ -198,17 +221,19 @@ pub async fn on_mr(
         return Ok(None);
     }
 
-    // Safety-net auto-bump: compare VERSION on source vs target (main)
+    // Safety-net auto-bump: compare VERSION on source vs the default branch
     let mut version = read_version_at_ref(&params.repo_path, &params.source_branch).await;
     let mut commit_sha = params.commit_sha.clone();
     if let Some(ref source_vi) = version {
-        let target_vi = read_version_at_ref(&params.repo_path, "main").await;
+        let default_branch = project_default_branch(pool, params.project_id).await?;
+        let target_vi = read_version_at_ref(&params.repo_path, &default_branch).await;
         if let Some(target_vi) = target_vi {
             // Check if all versions are identical — agent forgot to bump
             if source_vi.raw == target_vi.raw {
                 tracing::info!(
                     source_branch = %params.source_branch,
-                    "VERSION identical to main, auto-bumping patch"
+                    %default_branch,
+                    "VERSION identical to default branch, auto-bumping patch"
                 );
                 match auto_bump_version(&params.repo_path, &params.source_branch, &source_vi.images)
                     .await
@@ -243,9 +268,12 @@ pub async fn on_mr(
         commit_sha.as_deref(),
         params.user_id,
         "mr",
+        PipelinePriority::Normal,
         &def,
         None,
         version.as_ref(),
+        None,
+        None,
         kaniko_image,
     )
     .await?;
@@ -299,9 +327,12 @@ pub async fn on_tag(
         params.commit_sha.as_deref(),
         params.user_id,
         "tag",
+        PipelinePriority::Normal,
         &def,
         None,
         version.as_ref(),
+        None,
+        None,
         kaniko_image,
     )
     .await?;
@@ -315,15 +346,18 @@ pub async fn on_tag(
 // ---------------------------------------------------------------------------
 
 /// Manually trigger a pipeline for a given git ref.
-#[tracing::instrument(skip(pool, kaniko_image), fields(%project_id, %git_ref), err)]
+#[tracing::instrument(
+    skip(pool, params, kaniko_image),
+    fields(project_id = %params.project_id, git_ref = %params.git_ref),
+    err
+)]
 pub async fn on_api(
     pool: &PgPool,
-    repo_path: &Path,
-    project_id: Uuid,
-    git_ref: &str,
-    user_id: Uuid,
+    params: &ApiTriggerParams,
     kaniko_image: &str,
 ) -> Result<Uuid, PipelineError> {
+    let repo_path = &params.repo_path;
+    let git_ref = params.git_ref.as_str();
     // Resolve branch name from ref
     let branch = git_ref.strip_prefix("refs/heads/").unwrap_or(git_ref);
 
@@ -340,17 +374,80 @@ pub async fn on_api(
 
     create_pipeline_with_steps(
         pool,
-        project_id,
+        params.project_id,
         git_ref,
         commit_sha.as_deref(),
-        user_id,
+        params.user_id,
         "api",
+        params.priority,
+        &def,
+        None,
+        version.as_ref(),
+        params.encrypted_variables.as_deref(),
+        None,
+        kaniko_image,
+    )
+    .await
+}
+
+/// Create a pipeline for a cron schedule firing on `branch`.
+///
+/// Returns `None` if the branch has no `.platform.yaml`.
+#[tracing::instrument(skip(pool, repo_path, kaniko_image), fields(%project_id, branch), err)]
+pub async fn on_schedule(
+    pool: &PgPool,
+    repo_path: &Path,
+    project_id: Uuid,
+    branch: &str,
+    user_id: Uuid,
+    kaniko_image: &str,
+) -> Result<Option<Uuid>, PipelineError> {
+    crate::validation::check_branch_name(branch)
+        .map_err(|e| PipelineError::InvalidDefinition(e.to_string()))?;
+
+    let Some(yaml) = read_file_at_ref(repo_path, branch, ".platform.yaml").await else {
+        tracing::debug!("no .platform.yaml at ref, skipping scheduled pipeline");
+        return Ok(None);
+    };
+    let def = definition::parse(&yaml)?;
+
+    let git_ref = format!("refs/heads/{branch}");
+    let commit_sha = get_ref_sha(repo_path, &git_ref).await;
+    let version = read_version_at_ref(repo_path, branch).await;
+
+    create_pipeline_with_steps(
+        pool,
+        project_id,
+        &git_ref,
+        commit_sha.as_deref(),
+        user_id,
+        "schedule",
+        PipelinePriority::Low,
         &def,
         None,
         version.as_ref(),
+        None,
+        None,
         kaniko_image,
     )
     .await
+    .map(Some)
+}
+
+/// Encrypt trigger variables for `pipelines.encrypted_variables`.
+pub fn encrypt_variables(
+    master_key: &MasterKey,
+    variables: &BTreeMap<String, String>,
+) -> anyhow::Result<Vec<u8>> {
+    master_key.encrypt(&serde_json::to_vec(variables)?)
+}
+
+/// Decrypt trigger variables stored by [`encrypt_variables`].
+pub fn decrypt_variables(
+    master_key: &MasterKey,
+    encrypted: &[u8],
+) -> anyhow::Result<BTreeMap<String, String>> {
+    Ok(serde_json::from_slice(&master_key.decrypt(encrypted)?)?)
 }
 
 // ---------------------------------------------------------------------------
@@ -369,17 +466,40 @@ async fn create_pipeline_with_steps(
     commit_sha: Option<&str>,
     triggered_by: Uuid,
     trigger_type: &str,
+    priority: PipelinePriority,
     def: &PipelineDefinition,
     dev_image_dockerfile: Option<&str>,
     version: Option<&VersionInfo>,
+    encrypted_variables: Option<&[u8]>,
+    changed_files: Option<&[String]>,
     kaniko_image: &str,
 ) -> Result<Uuid, PipelineError> {
     let mut tx = pool.begin().await?;
 
+    // Archived projects are read-only: no new pipelines from any trigger
+    let archived: Option<bool> = sqlx::query_scalar!(
+        r#"SELECT archived_at IS NOT NULL AS "archived!" FROM projects WHERE id = $1"#,
+        project_id,
+    )
+    .fetch_optional(&mut *tx)
+    .await?;
+    if archived == Some(true) {
+        return Err(PipelineError::ProjectArchived);
+    }
+
+    let missing =
+        super::variable_groups::missing_groups(&mut *tx, project_id, &def.variable_groups).await?;
+    if let Some(name) = missing.first() {
+        return Err(PipelineError::InvalidDefinition(format!(
+            "unknown variable group '{name}'"
+        )));
+    }
+
     let pipeline_id = sqlx::query_scalar!(
         r#"
-        INSERT INTO pipelines (project_id, trigger, git_ref, commit_sha, status, triggered_by, version)
-        VALUES ($1, $2, $3, $4, 'pending', $5, $6)
+        INSERT INTO pipelines (project_id, trigger, git_ref, commit_sha, status, triggered_by, version,
+                               encrypted_variables, changed_files, variable_groups, priority)
+        VALUES ($1, $2, $3, $4, 'pending', $5, $6, $7, $8, $9, $10)
         RETURNING id
         "#,
         project_id,
@@ -388,6 +508,10 @@ async fn create_pipeline_with_steps(
         commit_sha,
         triggered_by,
         version.map(|v| v.raw.as_str()),
+        encrypted_variables,
+        changed_files,
+        &def.variable_groups,
+        priority.as_str(),
     )
     .fetch_one(&mut *tx)
     .await?;
@@ -444,7 +568,8 @@ async fn create_pipeline_with_steps(
                      --build-arg=PLATFORM_RUNNER_IMAGE=${{REGISTRY}}/platform-runner:v1 \
                      --insecure --insecure-registry=${{REGISTRY}} \
                      --insecure-pull \
-                     --cache=true --cache-repo=${{REGISTRY}}/${{PLATFORM_PROJECT_NAME}}/cache"
+                     --cache=true --cache-repo=${{REGISTRY}}/${{PLATFORM_PROJECT_NAME}}/cache \
+                     --image-name-with-digest-file=/dev/termination-log"
                 );
                 let mut config = serde_json::json!({
                     "image_name": image_name,
@@ -454,6 +579,12 @@ async fn create_pipeline_with_steps(
                 if !step.artifacts.is_empty() {
                     config["artifacts"] = serde_json::to_value(&step.artifacts).unwrap_or_default();
                 }
+                if let Some(depth) = step.clone_depth {
+                    config["clone_depth"] = depth.into();
+                }
+                if step.fetch_tags {
+                    config["fetch_tags"] = true.into();
+                }
                 (
                     "imagebuild",
                     kaniko_image.to_string(),
@@ -490,16 +621,32 @@ async fn create_pipeline_with_steps(
                 ("deploy_watch", String::new(), vec![], None, config)
             }
             super::definition::StepKind::Command => {
-                let config = if step.artifacts.is_empty() {
-                    None
-                } else {
-                    let mut c = serde_json::Map::new();
+                let mut c = serde_json::Map::new();
+                if !step.artifacts.is_empty() {
                     c.insert(
                         "artifacts".into(),
                         serde_json::to_value(&step.artifacts).unwrap_or_default(),
                     );
-                    Some(serde_json::Value::Object(c))
-                };
+                }
+                if !step.services.is_empty() {
+                    c.insert(
+                        "services".into(),
+                        serde_json::to_value(&step.services).unwrap_or_default(),
+                    );
+                }
+                if let Some(ref cache) = step.cache {
+                    c.insert(
+                        "cache".into(),
+                        serde_json::to_value(cache).unwrap_or_default(),
+                    );
+                }
+                if let Some(depth) = step.clone_depth {
+                    c.insert("clone_depth".into(), depth.into());
+                }
+                if step.fetch_tags {
+                    c.insert("fetch_tags".into(), true.into());
+                }
+                let config = (!c.is_empty()).then_some(serde_json::Value::Object(c));
                 (
                     "command",
                     step.image.clone(),
@@ -511,26 +658,33 @@ async fn create_pipeline_with_steps(
         };
         let commands_refs: Vec<&str> = commands.iter().map(String::as_str).collect();
 
-        sqlx::query(
+        let condition_when = step
+            .when
+            .as_ref()
+            .map(|w| serde_json::to_value(w).unwrap_or_default());
+
+        sqlx::query!(
             "INSERT INTO pipeline_steps (pipeline_id, project_id, step_order, name, image, commands,
                                         condition_events, condition_branches, deploy_test,
-                                        depends_on, environment, gate, step_type, step_config)
-            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13, $14)",
+                                        depends_on, environment, gate, step_type, step_config,
+                                        condition_when)
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13, $14, $15)",
+            pipeline_id,
+            project_id,
+            step_order,
+            &step.name,
+            &image,
+            &commands_refs as &[&str],
+            &condition_events as &[&str],
+            &condition_branches as &[&str],
+            deploy_test_json,
+            &depends_on as &[&str],
+            environment_json,
+            step.gate,
+            step_type_str,
+            step_config,
+            condition_when,
         )
-        .bind(pipeline_id)
-        .bind(project_id)
-        .bind(step_order)
-        .bind(&step.name)
-        .bind(&image)
-        .bind(&commands_refs as &[&str])
-        .bind(&condition_events as &[&str])
-        .bind(&condition_branches as &[&str])
-        .bind(&deploy_test_json)
-        .bind(&depends_on as &[&str])
-        .bind(&environment_json)
-        .bind(step.gate)
-        .bind(step_type_str)
-        .bind(&step_config)
         .execute(&mut *tx)
         .await?;
     }
@@ -546,6 +700,17 @@ async fn create_pipeline_with_steps(
 
 // insert_dev_image_step removed — dev images are now explicit `type: imagebuild` steps.
 
+/// The project's configured default branch.
+async fn project_default_branch(pool: &PgPool, project_id: Uuid) -> Result<String, PipelineError> {
+    let branch = sqlx::query_scalar!(
+        "SELECT default_branch FROM projects WHERE id = $1",
+        project_id,
+    )
+    .fetch_optional(pool)
+    .await?;
+    Ok(branch.unwrap_or_else(|| "main".to_owned()))
+}
+
 /// Check if `Dockerfile.dev` exists at the given git ref.
 async fn has_dockerfile_dev(repo_path: &Path, git_ref: &str) -> bool {
     read_file_at_ref(repo_path, git_ref, "Dockerfile.dev")
@@ -649,6 +814,35 @@ pub async fn read_version_at_ref(repo_path: &Path, git_ref: &str) -> Option<Vers
 }
 
 /// Get the SHA of a ref (branch, tag, or full ref path).
+/// Most changed paths recorded for a push; larger pushes are treated as
+/// "changes unknown" so every `when.changes` rule matches.
+const MAX_CHANGED_FILES: usize = 1000;
+
+/// Paths changed between two commits, or `None` if git can't tell (e.g. the
+/// old commit is gone after a force push).
+async fn changed_files(repo_path: &Path, from: &str, to: &str) -> Option<Vec<String>> {
+    let output = tokio::process::Command::new("git")
+        .arg("-C")
+        .arg(repo_path)
+        .arg("diff")
+        .arg("--name-only")
+        .arg("--no-renames")
+        .arg(format!("{from}..{to}"))
+        .arg("--")
+        .output()
+        .await
+        .ok()?;
+    if !output.status.success() {
+        return None;
+    }
+    let files: Vec<String> = String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .filter(|l| !l.is_empty())
+        .map(str::to_owned)
+        .collect();
+    (files.len() <= MAX_CHANGED_FILES).then_some(files)
+}
+
 async fn get_ref_sha(repo_path: &Path, git_ref: &str) -> Option<String> {
     let output = tokio::process::Command::new("git")
         .arg("-C")
@@ -1217,6 +1411,7 @@ mod tests {
             repo_path: std::path::PathBuf::from("/tmp/test"),
             branch: "main".to_string(),
             commit_sha: Some("abc123".to_string()),
+            before_sha: None,
         };
         assert_eq!(params.branch, "main");
         assert_eq!(params.commit_sha, Some("abc123".to_string()));
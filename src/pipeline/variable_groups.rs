@@ -0,0 +1,213 @@
+// Copyright (c) 2026 Steven Hooker. Exclusively licensed to and distributed by AgentSphere GmbH.
+// SPDX-License-Identifier: BUSL-1.1
+
+//! Variable groups: named sets of environment variables shared between
+//! pipelines, referenced from `.platform.yaml` as `pipeline.variable_groups`.
+//!
+//! A group belongs to a project or is global (`project_id IS NULL`); a
+//! project group shadows a global group of the same name. Masked values are
+//! stored encrypted with the secrets engine master key and are replaced by
+//! [`MASK`] in step logs.
+
+use std::collections::HashMap;
+
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use crate::secrets::engine::MasterKey;
+
+/// Replacement for masked values in logs.
+pub const MASK: &str = "[MASKED]";
+
+/// Variables resolved from a pipeline's groups, in reference order (later
+/// groups override earlier ones).
+#[derive(Clone, Default)]
+pub struct GroupVariables {
+    pub vars: Vec<(String, String)>,
+    /// Plaintext of every masked value, for [`redact`].
+    pub masked: Vec<String>,
+}
+
+impl std::fmt::Debug for GroupVariables {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let names: Vec<&str> = self.vars.iter().map(|(k, _)| k.as_str()).collect();
+        f.debug_struct("GroupVariables")
+            .field("names", &names)
+            .field("masked", &self.masked.len())
+            .finish()
+    }
+}
+
+struct EntryRow {
+    group_name: String,
+    name: String,
+    masked: bool,
+    value: Option<String>,
+    encrypted_value: Option<Vec<u8>>,
+}
+
+/// Names in `names` with no group visible to the project.
+pub async fn missing_groups<'e>(
+    executor: impl sqlx::PgExecutor<'e>,
+    project_id: Uuid,
+    names: &[String],
+) -> Result<Vec<String>, sqlx::Error> {
+    if names.is_empty() {
+        return Ok(Vec::new());
+    }
+    let found: Vec<String> = sqlx::query_scalar!(
+        "SELECT DISTINCT name FROM variable_groups
+         WHERE name = ANY($2) AND (project_id = $1 OR project_id IS NULL)",
+        project_id,
+        names,
+    )
+    .fetch_all(executor)
+    .await?;
+    Ok(names
+        .iter()
+        .filter(|n| !found.contains(n))
+        .cloned()
+        .collect())
+}
+
+/// Load the variables of the named groups. Groups deleted since the pipeline
+/// was created are skipped, as are masked values when no master key is
+/// configured or a value can't be decrypted.
+pub async fn resolve(
+    pool: &PgPool,
+    master_key: Option<&MasterKey>,
+    project_id: Uuid,
+    names: &[String],
+) -> Result<GroupVariables, sqlx::Error> {
+    if names.is_empty() {
+        return Ok(GroupVariables::default());
+    }
+    let rows: Vec<EntryRow> = sqlx::query_as!(
+        EntryRow,
+        "SELECT g.name AS group_name, e.name, e.masked, e.value, e.encrypted_value
+         FROM (
+             SELECT DISTINCT ON (name) id, name FROM variable_groups
+             WHERE name = ANY($2) AND (project_id = $1 OR project_id IS NULL)
+             ORDER BY name, project_id NULLS LAST
+         ) g
+         JOIN variable_group_entries e ON e.group_id = g.id
+         ORDER BY e.name",
+        project_id,
+        names,
+    )
+    .fetch_all(pool)
+    .await?;
+
+    let mut by_group: HashMap<&str, Vec<&EntryRow>> = HashMap::new();
+    for row in &rows {
+        by_group.entry(&row.group_name).or_default().push(row);
+    }
+
+    let mut resolved = GroupVariables::default();
+    for name in names {
+        for row in by_group.get(name.as_str()).into_iter().flatten() {
+            let value = if row.masked {
+                match decrypt_value(master_key, row.encrypted_value.as_deref()) {
+                    Ok(v) => {
+                        resolved.masked.push(v.clone());
+                        v
+                    }
+                    Err(e) => {
+                        tracing::warn!(error = %e, group = %name, variable = %row.name, "cannot decrypt masked variable, skipping");
+                        continue;
+                    }
+                }
+            } else {
+                row.value.clone().unwrap_or_default()
+            };
+            resolved.vars.push((row.name.clone(), value));
+        }
+    }
+    Ok(resolved)
+}
+
+fn decrypt_value(
+    master_key: Option<&MasterKey>,
+    encrypted: Option<&[u8]>,
+) -> anyhow::Result<String> {
+    let key = master_key.ok_or_else(|| anyhow::anyhow!("secrets engine not configured"))?;
+    let encrypted = encrypted.ok_or_else(|| anyhow::anyhow!("masked value missing"))?;
+    Ok(String::from_utf8(key.decrypt(encrypted)?)?)
+}
+
+/// Masked values of the groups a pipeline references, for redacting logs.
+pub async fn masked_values_for_pipeline(
+    pool: &PgPool,
+    master_key: Option<&MasterKey>,
+    pipeline_id: Uuid,
+) -> Result<Vec<String>, sqlx::Error> {
+    let row = sqlx::query!(
+        "SELECT project_id, variable_groups FROM pipelines WHERE id = $1",
+        pipeline_id,
+    )
+    .fetch_optional(pool)
+    .await?;
+    let Some(row) = row else {
+        return Ok(Vec::new());
+    };
+    Ok(
+        resolve(pool, master_key, row.project_id, &row.variable_groups)
+            .await?
+            .masked,
+    )
+}
+
+/// Replace every occurrence of a masked value with [`MASK`].
+pub fn redact(text: &str, masked: &[String]) -> String {
+    // Longest first, so a value containing another is masked whole
+    let mut values: Vec<&str> = masked
+        .iter()
+        .map(String::as_str)
+        .filter(|v| !v.is_empty())
+        .collect();
+    values.sort_by_key(|v| std::cmp::Reverse(v.len()));
+    let mut out = text.to_owned();
+    for value in values {
+        if out.contains(value) {
+            out = out.replace(value, MASK);
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn redact_replaces_every_occurrence() {
+        let masked = vec!["s3cr3t-token".to_owned()];
+        assert_eq!(
+            redact("token=s3cr3t-token\nagain: s3cr3t-token", &masked),
+            "token=[MASKED]\nagain: [MASKED]"
+        );
+        assert_eq!(redact("nothing here", &masked), "nothing here");
+    }
+
+    #[test]
+    fn redact_longest_value_first() {
+        let masked = vec!["password".to_owned(), "password-prod-1".to_owned()];
+        assert_eq!(redact("pw: password-prod-1", &masked), "pw: [MASKED]");
+    }
+
+    #[test]
+    fn redact_ignores_empty_values() {
+        assert_eq!(redact("abc", &[String::new()]), "abc");
+    }
+
+    #[test]
+    fn debug_hides_values() {
+        let vars = GroupVariables {
+            vars: vec![("DB_PASSWORD".into(), "hunter2-hunter2".into())],
+            masked: vec!["hunter2-hunter2".into()],
+        };
+        let debug = format!("{vars:?}");
+        assert!(debug.contains("DB_PASSWORD"));
+        assert!(!debug.contains("hunter2"));
+    }
+}
@@ -0,0 +1,366 @@
+// Copyright (c) 2026 Steven Hooker. Exclusively licensed to and distributed by AgentSphere GmbH.
+// SPDX-License-Identifier: BUSL-1.1
+
+//! `when` expressions on pipeline steps.
+//!
+//! ```text
+//! expr       := or
+//! or         := and ("||" and)*
+//! and        := unary ("&&" unary)*
+//! unary      := "!" unary | "(" expr ")" | comparison | status
+//! comparison := ("branch" | "event") ("==" | "!=" | "=~" | "!~") string
+//! status     := "on_success" | "on_failure" | "always"
+//! ```
+//!
+//! `=~` matches a glob where `*` matches any run of characters. Strings are
+//! single- or double-quoted. An expression that doesn't mention a status
+//! keyword only runs while no earlier step has failed, i.e. it is implicitly
+//! `on_success && (...)`.
+
+/// Pipeline state a `when` expression is evaluated against.
+#[derive(Debug, Clone, Copy)]
+pub struct WhenContext<'a> {
+    /// Branch (or tag) name, without the `refs/heads/` prefix.
+    pub branch: &'a str,
+    /// Trigger type: push, mr, tag, api, schedule.
+    pub event: &'a str,
+    /// Whether an earlier step (or, in DAG mode, an upstream step) failed.
+    pub failed: bool,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Field {
+    Branch,
+    Event,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Op {
+    Eq,
+    Ne,
+    Glob,
+    NotGlob,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Status {
+    OnSuccess,
+    OnFailure,
+    Always,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Node {
+    Compare(Field, Op, String),
+    Status(Status),
+    Not(Box<Node>),
+    And(Box<Node>, Box<Node>),
+    Or(Box<Node>, Box<Node>),
+}
+
+/// A parsed `when` expression.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WhenExpr {
+    root: Node,
+    mentions_status: bool,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Token {
+    Ident(String),
+    Str(String),
+    Eq,
+    Ne,
+    Glob,
+    NotGlob,
+    And,
+    Or,
+    Not,
+    LParen,
+    RParen,
+}
+
+fn tokenize(input: &str) -> Result<Vec<Token>, String> {
+    let mut tokens = Vec::new();
+    let mut chars = input.char_indices().peekable();
+    while let Some((i, c)) = chars.next() {
+        if c.is_whitespace() {
+            continue;
+        }
+        let pair = match (c, chars.peek().map(|&(_, n)| n)) {
+            ('=', Some('=')) => Some(Token::Eq),
+            ('!', Some('=')) => Some(Token::Ne),
+            ('=', Some('~')) => Some(Token::Glob),
+            ('!', Some('~')) => Some(Token::NotGlob),
+            ('&', Some('&')) => Some(Token::And),
+            ('|', Some('|')) => Some(Token::Or),
+            _ => None,
+        };
+        if let Some(token) = pair {
+            chars.next();
+            tokens.push(token);
+            continue;
+        }
+        match c {
+            '!' => tokens.push(Token::Not),
+            '(' => tokens.push(Token::LParen),
+            ')' => tokens.push(Token::RParen),
+            '"' | '\'' => {
+                let mut s = String::new();
+                loop {
+                    match chars.next() {
+                        Some((_, ch)) if ch == c => break,
+                        Some((_, ch)) => s.push(ch),
+                        None => return Err(format!("unterminated string at offset {i}")),
+                    }
+                }
+                tokens.push(Token::Str(s));
+            }
+            c if c.is_ascii_alphabetic() || c == '_' => {
+                let mut ident = String::from(c);
+                while let Some(&(_, ch)) = chars.peek() {
+                    if !(ch.is_ascii_alphanumeric() || ch == '_') {
+                        break;
+                    }
+                    ident.push(ch);
+                    chars.next();
+                }
+                tokens.push(Token::Ident(ident));
+            }
+            _ => return Err(format!("unexpected character '{c}' at offset {i}")),
+        }
+    }
+    Ok(tokens)
+}
+
+struct Parser {
+    tokens: Vec<Token>,
+    pos: usize,
+}
+
+impl Parser {
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.pos)
+    }
+
+    fn next(&mut self) -> Option<Token> {
+        let token = self.tokens.get(self.pos).cloned();
+        self.pos += 1;
+        token
+    }
+
+    fn or(&mut self) -> Result<Node, String> {
+        let mut node = self.and()?;
+        while self.peek() == Some(&Token::Or) {
+            self.pos += 1;
+            node = Node::Or(Box::new(node), Box::new(self.and()?));
+        }
+        Ok(node)
+    }
+
+    fn and(&mut self) -> Result<Node, String> {
+        let mut node = self.unary()?;
+        while self.peek() == Some(&Token::And) {
+            self.pos += 1;
+            node = Node::And(Box::new(node), Box::new(self.unary()?));
+        }
+        Ok(node)
+    }
+
+    fn unary(&mut self) -> Result<Node, String> {
+        match self.next() {
+            Some(Token::Not) => Ok(Node::Not(Box::new(self.unary()?))),
+            Some(Token::LParen) => {
+                let node = self.or()?;
+                match self.next() {
+                    Some(Token::RParen) => Ok(node),
+                    _ => Err("expected ')'".into()),
+                }
+            }
+            Some(Token::Ident(ident)) => match ident.as_str() {
+                "on_success" => Ok(Node::Status(Status::OnSuccess)),
+                "on_failure" => Ok(Node::Status(Status::OnFailure)),
+                "always" => Ok(Node::Status(Status::Always)),
+                "branch" => self.comparison(Field::Branch),
+                "event" => self.comparison(Field::Event),
+                other => Err(format!(
+                    "unknown identifier '{other}' (expected branch, event, on_success, on_failure or always)"
+                )),
+            },
+            Some(token) => Err(format!("unexpected {token:?}")),
+            None => Err("unexpected end of expression".into()),
+        }
+    }
+
+    fn comparison(&mut self, field: Field) -> Result<Node, String> {
+        let op = match self.next() {
+            Some(Token::Eq) => Op::Eq,
+            Some(Token::Ne) => Op::Ne,
+            Some(Token::Glob) => Op::Glob,
+            Some(Token::NotGlob) => Op::NotGlob,
+            _ => return Err("expected ==, !=, =~ or !~ after field name".into()),
+        };
+        match self.next() {
+            Some(Token::Str(s)) => Ok(Node::Compare(field, op, s)),
+            _ => Err("expected a quoted string after operator".into()),
+        }
+    }
+}
+
+fn mentions_status(node: &Node) -> bool {
+    match node {
+        Node::Status(_) => true,
+        Node::Compare(..) => false,
+        Node::Not(n) => mentions_status(n),
+        Node::And(a, b) | Node::Or(a, b) => mentions_status(a) || mentions_status(b),
+    }
+}
+
+fn eval(node: &Node, ctx: &WhenContext<'_>) -> bool {
+    match node {
+        Node::Compare(field, op, pattern) => {
+            let value = match field {
+                Field::Branch => ctx.branch,
+                Field::Event => ctx.event,
+            };
+            match op {
+                Op::Eq => value == pattern,
+                Op::Ne => value != pattern,
+                Op::Glob => crate::validation::match_glob_pattern(pattern, value),
+                Op::NotGlob => !crate::validation::match_glob_pattern(pattern, value),
+            }
+        }
+        Node::Status(Status::OnSuccess) => !ctx.failed,
+        Node::Status(Status::OnFailure) => ctx.failed,
+        Node::Status(Status::Always) => true,
+        Node::Not(n) => !eval(n, ctx),
+        Node::And(a, b) => eval(a, ctx) && eval(b, ctx),
+        Node::Or(a, b) => eval(a, ctx) || eval(b, ctx),
+    }
+}
+
+impl WhenExpr {
+    /// Parse a `when` expression.
+    pub fn parse(input: &str) -> Result<Self, String> {
+        let mut parser = Parser {
+            tokens: tokenize(input)?,
+            pos: 0,
+        };
+        if parser.tokens.is_empty() {
+            return Err("empty expression".into());
+        }
+        let root = parser.or()?;
+        if let Some(token) = parser.peek() {
+            return Err(format!("unexpected {token:?} after expression"));
+        }
+        Ok(Self {
+            mentions_status: mentions_status(&root),
+            root,
+        })
+    }
+
+    /// Evaluate against the pipeline context.
+    pub fn matches(&self, ctx: &WhenContext<'_>) -> bool {
+        if !self.mentions_status && ctx.failed {
+            return false;
+        }
+        eval(&self.root, ctx)
+    }
+}
+
+/// Whether any of `changed_files` matches one of the `changes` globs.
+pub fn changes_match(patterns: &[String], changed_files: &[String]) -> bool {
+    changed_files.iter().any(|file| {
+        patterns
+            .iter()
+            .any(|p| crate::validation::match_glob_pattern(p, file))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ctx<'a>(branch: &'a str, event: &'a str, failed: bool) -> WhenContext<'a> {
+        WhenContext {
+            branch,
+            event,
+            failed,
+        }
+    }
+
+    fn matches(expr: &str, ctx: &WhenContext<'_>) -> bool {
+        WhenExpr::parse(expr).unwrap().matches(ctx)
+    }
+
+    #[test]
+    fn branch_equality() {
+        assert!(matches(r#"branch == "main""#, &ctx("main", "push", false)));
+        assert!(!matches(
+            r#"branch == "main""#,
+            &ctx("feature/x", "push", false)
+        ));
+        assert!(matches(
+            "branch != 'main'",
+            &ctx("feature/x", "push", false)
+        ));
+    }
+
+    #[test]
+    fn glob_and_boolean_operators() {
+        let expr = r#"(branch =~ "release/*" || branch == "main") && event != "mr""#;
+        assert!(matches(expr, &ctx("release/1.2", "push", false)));
+        assert!(matches(expr, &ctx("main", "api", false)));
+        assert!(!matches(expr, &ctx("main", "mr", false)));
+        assert!(!matches(expr, &ctx("feature/x", "push", false)));
+        assert!(matches(
+            r#"!(branch !~ "feat*")"#,
+            &ctx("feature", "push", false)
+        ));
+    }
+
+    #[test]
+    fn implicit_on_success() {
+        assert!(!matches(r#"branch == "main""#, &ctx("main", "push", true)));
+    }
+
+    #[test]
+    fn status_keywords() {
+        assert!(matches("on_success", &ctx("main", "push", false)));
+        assert!(!matches("on_success", &ctx("main", "push", true)));
+        assert!(matches("on_failure", &ctx("main", "push", true)));
+        assert!(!matches("on_failure", &ctx("main", "push", false)));
+        assert!(matches("always", &ctx("main", "push", true)));
+        assert!(matches("always", &ctx("main", "push", false)));
+        let expr = r#"on_failure && branch == "main""#;
+        assert!(matches(expr, &ctx("main", "push", true)));
+        assert!(!matches(expr, &ctx("dev", "push", true)));
+    }
+
+    #[test]
+    fn rejects_invalid_expressions() {
+        for bad in [
+            "",
+            "branch",
+            "branch ==",
+            "branch == main",
+            r#"branch == "main"#,
+            r#"tag == "v1""#,
+            r#"(branch == "main""#,
+            r#"branch == "main" &&"#,
+            r#"branch == "main" branch"#,
+            "on_success & on_failure",
+        ] {
+            assert!(WhenExpr::parse(bad).is_err(), "'{bad}' should be rejected");
+        }
+    }
+
+    #[test]
+    fn changes_globs() {
+        let files = vec!["src/main.rs".to_owned(), "README.md".to_owned()];
+        assert!(changes_match(&["src/**".into()], &files));
+        assert!(changes_match(&["*.md".into()], &files));
+        assert!(!changes_match(&["docs/**".into()], &files));
+        assert!(!changes_match(&["src/**".into()], &[]));
+    }
+}
@@ -3,11 +3,14 @@
 
 use chrono::{DateTime, Utc};
 use sqlx::PgPool;
+use tracing::Instrument;
 use uuid::Uuid;
 
+use crate::audit::{AuditEntry, send_audit};
 use crate::error::ApiError;
 use crate::rbac::resolver;
 use crate::rbac::types::Permission;
+use crate::store::AppState;
 
 /// Parameters for creating a new delegation (internal, not the API request type).
 #[derive(Debug)]
@@ -33,6 +36,28 @@ pub struct Delegation {
     pub reason: Option<String>,
     pub created_at: DateTime<Utc>,
     pub revoked_at: Option<DateTime<Utc>>,
+    /// Self-granted emergency access (delegator and delegate are the same user).
+    pub break_glass: bool,
+}
+
+/// Parameters for a self-granted break-glass delegation.
+#[derive(Debug)]
+pub struct BreakGlassParams {
+    pub user_id: Uuid,
+    pub permission: Permission,
+    pub project_id: Uuid,
+    pub reason: String,
+    pub duration_secs: i64,
+}
+
+/// An expired break-glass grant revoked by the reaper.
+#[derive(Debug)]
+pub struct ExpiredBreakGlass {
+    pub id: Uuid,
+    pub delegate_id: Uuid,
+    pub delegate_name: String,
+    pub project_id: Option<Uuid>,
+    pub permission_name: String,
 }
 
 /// Create a new delegation. Validates that the delegator holds the permission.
@@ -131,9 +156,99 @@ pub async fn create_delegation(
         reason: req.reason.clone(),
         created_at: now,
         revoked_at: None,
+        break_glass: false,
+    })
+}
+
+/// Grant `params.permission` to the requester for a fixed window.
+///
+/// Unlike a regular delegation there is no separate delegator: the user grants
+/// themselves, so callers must check eligibility and notify admins. A previous
+/// expired or revoked break-glass grant for the same permission is reused
+/// (the unique grant constraint allows one row per tuple); an active one
+/// yields `Conflict`.
+#[tracing::instrument(skip(pool, valkey, params), fields(user_id = %params.user_id, permission = %params.permission, project_id = %params.project_id), err)]
+pub async fn create_break_glass(
+    pool: &PgPool,
+    valkey: &fred::clients::Pool,
+    params: &BreakGlassParams,
+) -> Result<Delegation, ApiError> {
+    let expires_at = Utc::now() + chrono::Duration::seconds(params.duration_secs);
+
+    let row = sqlx::query!(
+        r#"
+        INSERT INTO delegations
+            (delegator_id, delegate_id, permission_id, project_id, expires_at, reason, break_glass)
+        SELECT $1, $1, p.id, $3, $4, $5, true
+        FROM permissions p WHERE p.name = $2
+        ON CONFLICT ON CONSTRAINT uq_delegations_unique_grant DO UPDATE
+            SET expires_at = EXCLUDED.expires_at,
+                reason = EXCLUDED.reason,
+                created_at = now(),
+                revoked_at = NULL
+            WHERE delegations.break_glass
+              AND (delegations.revoked_at IS NOT NULL OR delegations.expires_at <= now())
+        RETURNING id, permission_id, created_at
+        "#,
+        params.user_id,
+        params.permission.as_str(),
+        params.project_id,
+        expires_at,
+        params.reason,
+    )
+    .fetch_optional(pool)
+    .await?
+    .ok_or_else(|| {
+        ApiError::Conflict("break-glass access for this permission is already active".into())
+    })?;
+
+    let _ = resolver::invalidate_permissions(valkey, params.user_id, Some(params.project_id)).await;
+
+    Ok(Delegation {
+        id: row.id,
+        delegator_id: params.user_id,
+        delegate_id: params.user_id,
+        permission_id: row.permission_id,
+        permission_name: params.permission.as_str().to_owned(),
+        project_id: Some(params.project_id),
+        expires_at: Some(expires_at),
+        reason: Some(params.reason.clone()),
+        created_at: row.created_at,
+        revoked_at: None,
+        break_glass: true,
     })
 }
 
+/// Revoke break-glass grants whose window has passed and drop the affected
+/// permission caches. Returns the revoked grants for auditing.
+///
+/// The resolver already ignores expired delegations; revoking them explicitly
+/// closes the window for cached permission sets and records when it ended.
+#[tracing::instrument(skip(pool, valkey), err)]
+pub async fn expire_break_glass(
+    pool: &PgPool,
+    valkey: &fred::clients::Pool,
+) -> anyhow::Result<Vec<ExpiredBreakGlass>> {
+    let expired = sqlx::query_as!(
+        ExpiredBreakGlass,
+        r#"
+        UPDATE delegations d SET revoked_at = now()
+        FROM users u, permissions p
+        WHERE d.break_glass AND d.revoked_at IS NULL AND d.expires_at <= now()
+          AND u.id = d.delegate_id AND p.id = d.permission_id
+        RETURNING d.id, d.delegate_id, u.name AS delegate_name, d.project_id,
+                  p.name AS permission_name
+        "#,
+    )
+    .fetch_all(pool)
+    .await?;
+
+    for grant in &expired {
+        let _ = resolver::invalidate_permissions(valkey, grant.delegate_id, grant.project_id).await;
+    }
+    Ok(expired)
+}
+
 /// Revoke a delegation by setting `revoked_at`. Returns the delegate's `user_id` for cache invalidation.
 #[tracing::instrument(skip(pool, valkey), fields(%delegation_id), err)]
 pub async fn revoke_delegation(
@@ -159,6 +274,58 @@ pub async fn revoke_delegation(
     Ok(())
 }
 
+/// Background task revoking break-glass grants as soon as their window ends.
+pub async fn run_break_glass_reaper(state: AppState, cancel: tokio_util::sync::CancellationToken) {
+    let mut interval = tokio::time::interval(std::time::Duration::from_secs(30));
+    state.task_registry.register("break_glass_reaper", 120);
+    loop {
+        tokio::select! {
+            () = cancel.cancelled() => {
+                tracing::info!("break-glass reaper shutting down");
+                break;
+            }
+            _ = interval.tick() => {
+                let iter_trace_id = Uuid::new_v4().to_string().replace('-', "");
+                let span = tracing::info_span!(
+                    "task_iteration",
+                    task_name = "break_glass_reaper",
+                    trace_id = %iter_trace_id,
+                    source = "system",
+                );
+                async {
+                    match expire_break_glass(&state.pool, &state.valkey).await {
+                        Ok(expired) => {
+                            for grant in expired {
+                                tracing::info!(delegation_id = %grant.id, user_id = %grant.delegate_id, "break-glass access expired");
+                                send_audit(
+                                    &state.audit_tx,
+                                    AuditEntry {
+                                        actor_id: grant.delegate_id,
+                                        actor_name: grant.delegate_name,
+                                        action: "break_glass.expire".into(),
+                                        resource: "delegation".into(),
+                                        resource_id: Some(grant.id),
+                                        project_id: grant.project_id,
+                                        detail: Some(serde_json::json!({
+                                            "permission": grant.permission_name,
+                                        })),
+                                        ip_addr: None,
+                                    },
+                                );
+                            }
+                            state.task_registry.heartbeat("break_glass_reaper");
+                        }
+                        Err(e) => {
+                            state.task_registry.report_error("break_glass_reaper", &e.to_string());
+                            tracing::error!(error = %e, "break-glass reaper failed");
+                        }
+                    }
+                }.instrument(span).await;
+            }
+        }
+    }
+}
+
 /// List delegations for a user (both granted by and received).
 #[tracing::instrument(skip(pool), fields(%user_id), err)]
 pub async fn list_delegations(
@@ -180,7 +347,8 @@ pub async fn list_delegations(
             d.expires_at,
             d.reason,
             d.created_at,
-            d.revoked_at
+            d.revoked_at,
+            d.break_glass
         FROM delegations d
         JOIN permissions p ON p.id = d.permission_id
         WHERE d.delegator_id = $1 OR d.delegate_id = $1
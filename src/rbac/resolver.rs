@@ -1,7 +1,7 @@
 // Copyright (c) 2026 Steven Hooker. Exclusively licensed to and distributed by AgentSphere GmbH.
 // SPDX-License-Identifier: BUSL-1.1
 
-use std::collections::HashSet;
+use std::collections::{HashMap, HashSet};
 use std::str::FromStr;
 
 use sqlx::PgPool;
@@ -44,91 +44,262 @@ pub async fn effective_permissions(
     user_id: Uuid,
     project_id: Option<Uuid>,
 ) -> anyhow::Result<HashSet<Permission>> {
-    let key = cache_key(user_id, project_id);
-
-    // Check cache
-    if let Some(cached) = valkey::get_cached::<Vec<String>>(valkey, &key).await {
-        let perms = cached
-            .iter()
-            .filter_map(|s| if let Ok(p) = Permission::from_str(s) {
-                Some(p)
-            } else {
-                tracing::warn!(permission = %s, "unparseable permission string in cache, ignoring");
-                None
-            })
-            .collect();
-        return Ok(perms);
+    let mut resolved = effective_permissions_batch(pool, valkey, user_id, &[project_id]).await?;
+    Ok(resolved.remove(&project_id).unwrap_or_default())
+}
+
+/// Resolve effective permissions for several scopes (`None` = global) at once:
+/// one `MGET` for cached entries and a single query for all cache misses.
+/// Same caching semantics as `effective_permissions`.
+#[tracing::instrument(skip(pool, valkey, project_ids), fields(%user_id, scopes = project_ids.len()), err)]
+pub async fn effective_permissions_batch(
+    pool: &PgPool,
+    valkey: &fred::clients::Pool,
+    user_id: Uuid,
+    project_ids: &[Option<Uuid>],
+) -> anyhow::Result<HashMap<Option<Uuid>, HashSet<Permission>>> {
+    let mut scopes: Vec<Option<Uuid>> = Vec::with_capacity(project_ids.len());
+    for pid in project_ids {
+        if !scopes.contains(pid) {
+            scopes.push(*pid);
+        }
     }
 
-    // Cache miss — query DB
-    // Union of:
-    //   1. Global role permissions (user_roles where project_id IS NULL)
-    //   2. Project-scoped role permissions (if project_id provided)
-    //   3. Active delegations (not revoked, not expired)
-    let perm_names: Vec<String> = sqlx::query_scalar!(
-        r#"
-        SELECT DISTINCT p.name as "name!"
-        FROM permissions p
-        WHERE p.id IN (
-            -- Global roles
-            SELECT rp.permission_id
-            FROM user_roles ur
-            JOIN role_permissions rp ON rp.role_id = ur.role_id
-            WHERE ur.user_id = $1
-              AND ur.project_id IS NULL
+    let mut resolved = HashMap::with_capacity(scopes.len());
+    let mut misses = Vec::new();
+    let cached = read_cached_batch(valkey, user_id, &scopes).await;
+    for (scope, hit) in scopes.iter().zip(cached) {
+        match hit {
+            Some(perms) => {
+                resolved.insert(*scope, perms);
+            }
+            None => misses.push(*scope),
+        }
+    }
+    if misses.is_empty() {
+        return Ok(resolved);
+    }
 
-            UNION
+    // Cache miss — query DB for every missing scope in one round trip.
+    let fresh = query_permissions(pool, user_id, &misses).await?;
+
+    // Cache results
+    for (scope, perms) in fresh {
+        let cache_strings: Vec<String> = perms.iter().map(|p| p.as_str().to_owned()).collect();
+        let _ = valkey::set_cached(
+            valkey,
+            &cache_key(user_id, scope),
+            &cache_strings,
+            cache_ttl(),
+        )
+        .await;
+        resolved.insert(scope, perms);
+    }
+
+    Ok(resolved)
+}
 
-            -- Project-scoped roles
-            SELECT rp.permission_id
-            FROM user_roles ur
-            JOIN role_permissions rp ON rp.role_id = ur.role_id
-            WHERE ur.user_id = $1
-              AND ur.project_id = $2
+/// Load permission sets for `scopes` from the database (no caching).
+async fn query_permissions(
+    pool: &PgPool,
+    user_id: Uuid,
+    scopes: &[Option<Uuid>],
+) -> anyhow::Result<HashMap<Option<Uuid>, HashSet<Permission>>> {
+    // Union of:
+    //   1. Global and project-scoped role permissions, including everything
+    //      inherited through `roles.parent_role_id` (transitively)
+    //   2. Active delegations (not revoked, not expired)
+    // The recursive CTE uses UNION, not UNION ALL, so a cycle in the role
+    // hierarchy terminates instead of looping.
+    let miss_projects: Vec<Uuid> = scopes.iter().flatten().copied().collect();
+    let include_global = scopes.contains(&None);
+    let rows = sqlx::query!(
+        r#"
+        WITH RECURSIVE scopes(pid) AS (
+            SELECT unnest($2::uuid[])
+            UNION ALL
+            SELECT NULL::uuid WHERE $3
+        ),
+        granted_roles(pid, role_id) AS (
+            SELECT s.pid, ur.role_id
+            FROM scopes s
+            JOIN user_roles ur
+              ON ur.user_id = $1
+             AND (ur.project_id IS NULL OR ur.project_id = s.pid)
 
             UNION
 
-            -- Active delegations (global)
-            SELECT d.permission_id
-            FROM delegations d
-            WHERE d.delegate_id = $1
-              AND d.project_id IS NULL
-              AND d.revoked_at IS NULL
-              AND (d.expires_at IS NULL OR d.expires_at > now())
+            SELECT g.pid, r.parent_role_id
+            FROM roles r
+            JOIN granted_roles g ON g.role_id = r.id
+            WHERE r.parent_role_id IS NOT NULL
+        ),
+        grants(pid, permission_id) AS (
+            -- Roles (direct + inherited)
+            SELECT g.pid, rp.permission_id
+            FROM granted_roles g
+            JOIN role_permissions rp ON rp.role_id = g.role_id
 
             UNION
 
-            -- Active delegations (project-scoped)
-            SELECT d.permission_id
-            FROM delegations d
-            WHERE d.delegate_id = $1
-              AND d.project_id = $2
-              AND d.revoked_at IS NULL
-              AND (d.expires_at IS NULL OR d.expires_at > now())
+            -- Active delegations (global or project-scoped)
+            SELECT s.pid, d.permission_id
+            FROM scopes s
+            JOIN delegations d
+              ON d.delegate_id = $1
+             AND (d.project_id IS NULL OR d.project_id = s.pid)
+             AND d.revoked_at IS NULL
+             AND (d.expires_at IS NULL OR d.expires_at > now())
         )
+        SELECT DISTINCT gr.pid, p.name
+        FROM grants gr
+        JOIN permissions p ON p.id = gr.permission_id
         "#,
         user_id,
-        project_id,
+        &miss_projects,
+        include_global,
     )
     .fetch_all(pool)
     .await?;
 
-    let mut perms: HashSet<Permission> = perm_names
+    let mut fresh: HashMap<Option<Uuid>, HashSet<Permission>> = scopes
         .iter()
-        .filter_map(|s| Permission::from_str(s).ok())
+        .map(|scope| (*scope, HashSet::new()))
         .collect();
+    for row in rows {
+        if let (Some(perms), Ok(perm)) = (fresh.get_mut(&row.pid), Permission::from_str(&row.name))
+        {
+            perms.insert(perm);
+        }
+    }
 
     // Workspace-derived project permissions: if the project belongs to a
-    // workspace, workspace membership grants implicit project access.
-    if let Some(pid) = project_id {
-        add_workspace_permissions(pool, &mut perms, user_id, pid).await?;
+    // workspace, workspace membership grants implicit project access. The
+    // project owner is treated like a workspace owner, so transferring
+    // ownership moves this access with it.
+    if !miss_projects.is_empty() {
+        let memberships = sqlx::query!(
+            r#"SELECT p.id AS "id!", wm.role AS "role!"
+            FROM workspace_members wm
+            JOIN projects p ON p.workspace_id = wm.workspace_id
+            JOIN workspaces w ON w.id = wm.workspace_id
+            WHERE p.id = ANY($1) AND p.is_active = true AND w.is_active = true
+              AND wm.user_id = $2
+            UNION ALL
+            SELECT p.id, 'owner'
+            FROM projects p
+            WHERE p.id = ANY($1) AND p.is_active = true AND p.owner_id = $2"#,
+            &miss_projects,
+            user_id,
+        )
+        .fetch_all(pool)
+        .await?;
+        for m in memberships {
+            if let Some(perms) = fresh.get_mut(&Some(m.id)) {
+                grant_workspace_role(perms, &m.role);
+            }
+        }
     }
 
-    // Cache result
-    let cache_strings: Vec<String> = perms.iter().map(|p| p.as_str().to_owned()).collect();
-    let _ = valkey::set_cached(valkey, &key, &cache_strings, cache_ttl()).await;
+    Ok(fresh)
+}
+
+/// Cached permission sets for `scopes`, in order (`None` = miss). Valkey errors
+/// and undecodable entries count as misses.
+async fn read_cached_batch(
+    valkey: &fred::clients::Pool,
+    user_id: Uuid,
+    scopes: &[Option<Uuid>],
+) -> Vec<Option<HashSet<Permission>>> {
+    use fred::interfaces::KeysInterface;
+
+    let keys: Vec<String> = scopes.iter().map(|s| cache_key(user_id, *s)).collect();
+    let raw: Vec<Option<String>> = if keys.len() == 1 {
+        // MGET with a single key still replies with a one-element array,
+        // but a plain GET keeps the common single-scope path identical.
+        vec![valkey.get(&keys[0]).await.ok().flatten()]
+    } else {
+        valkey
+            .mget(keys.clone())
+            .await
+            .unwrap_or_else(|_| vec![None; keys.len()])
+    };
+
+    keys.iter()
+        .zip(raw)
+        .map(|(key, value)| {
+            let cached: Vec<String> = match serde_json::from_str(&value?) {
+                Ok(v) => v,
+                Err(e) => {
+                    tracing::warn!(error = %e, %key, "cache deserialization failed, treating as miss");
+                    return None;
+                }
+            };
+            Some(
+                cached
+                    .iter()
+                    .filter_map(|s| {
+                        if let Ok(p) = Permission::from_str(s) {
+                            Some(p)
+                        } else {
+                            tracing::warn!(permission = %s, "unparseable permission string in cache, ignoring");
+                            None
+                        }
+                    })
+                    .collect(),
+            )
+        })
+        .collect()
+}
 
-    Ok(perms)
+/// Whether making `parent_id` the parent of `role_id` would create a cycle,
+/// i.e. `role_id` is `parent_id` itself or one of its ancestors.
+pub async fn role_parent_would_cycle(
+    pool: &PgPool,
+    role_id: Uuid,
+    parent_id: Uuid,
+) -> anyhow::Result<bool> {
+    let cycle: bool = sqlx::query_scalar!(
+        r#"
+        WITH RECURSIVE ancestors(id) AS (
+            SELECT $2::uuid
+            UNION
+            SELECT r.parent_role_id
+            FROM roles r
+            JOIN ancestors a ON a.id = r.id
+            WHERE r.parent_role_id IS NOT NULL
+        )
+        SELECT EXISTS(SELECT 1 FROM ancestors WHERE id = $1) as "exists!"
+        "#,
+        role_id,
+        parent_id,
+    )
+    .fetch_one(pool)
+    .await?;
+    Ok(cycle)
+}
+
+/// Users whose permissions depend on `role_id`: holders of the role itself or
+/// of any role inheriting from it.
+pub async fn users_inheriting_role(pool: &PgPool, role_id: Uuid) -> anyhow::Result<Vec<Uuid>> {
+    let users = sqlx::query_scalar!(
+        r#"
+        WITH RECURSIVE descendants(id) AS (
+            SELECT $1::uuid
+            UNION
+            SELECT r.id
+            FROM roles r
+            JOIN descendants d ON r.parent_role_id = d.id
+        )
+        SELECT DISTINCT ur.user_id
+        FROM user_roles ur
+        JOIN descendants d ON d.id = ur.role_id
+        "#,
+        role_id,
+    )
+    .fetch_all(pool)
+    .await?;
+    Ok(users)
 }
 
 /// Check whether a user has a specific permission, optionally scoped to a project.
@@ -169,7 +340,7 @@ pub async fn has_permission_scoped(
 /// - scopes is empty (backward-compatible unrestricted token)
 /// - scopes contains `"*"` (unrestricted token)
 /// - scopes contains the permission's string representation
-fn scope_allows(token_scopes: Option<&[String]>, perm: Permission) -> bool {
+pub fn scope_allows(token_scopes: Option<&[String]>, perm: Permission) -> bool {
     let Some(scopes) = token_scopes else {
         return true; // session auth
     };
@@ -179,42 +350,62 @@ fn scope_allows(token_scopes: Option<&[String]>, perm: Permission) -> bool {
     scopes.iter().any(|s| s == perm.as_str())
 }
 
-/// Grant implicit project permissions based on workspace membership.
+/// Token scope allowing git clone/fetch.
+pub const SCOPE_REPO_READ: &str = "repo:read";
+/// Token scope allowing git push (implies `repo:read`).
+pub const SCOPE_REPO_WRITE: &str = "repo:write";
+
+/// Permission a `repo:*` scope requires its holder to have when the token is issued.
+pub fn repo_scope_permission(scope: &str) -> Option<Permission> {
+    match scope {
+        SCOPE_REPO_READ => Some(Permission::ProjectRead),
+        SCOPE_REPO_WRITE => Some(Permission::ProjectWrite),
+        _ => None,
+    }
+}
+
+/// Check whether token scopes allow a git read or (`write = true`) push.
+/// Unrestricted tokens and session/SSH auth always pass. `project:read` and
+/// `project:write` are accepted alongside `repo:*` so tokens issued before
+/// repo scopes existed (including agent tokens) keep working.
+pub fn git_scope_allows(token_scopes: Option<&[String]>, write: bool) -> bool {
+    let Some(scopes) = token_scopes else {
+        return true;
+    };
+    if scopes.is_empty() || scopes.iter().any(|s| s == "*") {
+        return true;
+    }
+    let allowed: &[&str] = if write {
+        &[SCOPE_REPO_WRITE, "project:write"]
+    } else {
+        &[
+            SCOPE_REPO_READ,
+            SCOPE_REPO_WRITE,
+            "project:read",
+            "project:write",
+        ]
+    };
+    scopes.iter().any(|s| allowed.contains(&s.as_str()))
+}
+
+/// Implicit project permissions for a workspace member with `role`.
 ///
 /// When a project belongs to a workspace, members of that workspace get
 /// implicit permissions without needing explicit role assignments:
 ///   - workspace owner/admin → `ProjectRead` + `ProjectWrite`
 ///   - workspace member       → `ProjectRead` only
 ///
+/// The project owner is passed in as `"owner"` regardless of workspace
+/// membership.
+///
 /// These grants are added to the user's effective permissions alongside
 /// any explicit role/delegation grants. They are subject to the same
 /// Valkey cache TTL as other permissions.
-async fn add_workspace_permissions(
-    pool: &PgPool,
-    perms: &mut HashSet<Permission>,
-    user_id: Uuid,
-    project_id: Uuid,
-) -> anyhow::Result<()> {
-    let role: Option<String> = sqlx::query_scalar(
-        "SELECT wm.role
-        FROM workspace_members wm
-        JOIN projects p ON p.workspace_id = wm.workspace_id
-        JOIN workspaces w ON w.id = wm.workspace_id
-        WHERE p.id = $1 AND p.is_active = true AND w.is_active = true AND wm.user_id = $2",
-    )
-    .bind(project_id)
-    .bind(user_id)
-    .fetch_optional(pool)
-    .await?;
-
-    if let Some(role) = role {
-        perms.insert(Permission::ProjectRead);
-        if role == "owner" || role == "admin" {
-            perms.insert(Permission::ProjectWrite);
-        }
+fn grant_workspace_role(perms: &mut HashSet<Permission>, role: &str) {
+    perms.insert(Permission::ProjectRead);
+    if role == "owner" || role == "admin" {
+        perms.insert(Permission::ProjectWrite);
     }
-
-    Ok(())
 }
 
 /// Get permissions for a specific role by ID (from `role_permissions` join table).
@@ -300,6 +491,59 @@ mod tests {
         );
     }
 
+    // -- git_scope_allows --
+
+    #[test]
+    fn git_scope_repo_read_cannot_push() {
+        let scopes = vec![SCOPE_REPO_READ.to_string()];
+        assert!(git_scope_allows(Some(&scopes), false));
+        assert!(!git_scope_allows(Some(&scopes), true));
+    }
+
+    #[test]
+    fn git_scope_repo_write_can_clone_and_push() {
+        let scopes = vec![SCOPE_REPO_WRITE.to_string()];
+        assert!(git_scope_allows(Some(&scopes), false));
+        assert!(git_scope_allows(Some(&scopes), true));
+    }
+
+    #[test]
+    fn git_scope_accepts_project_scopes_and_unrestricted() {
+        let read = vec!["project:read".to_string()];
+        assert!(git_scope_allows(Some(&read), false));
+        assert!(!git_scope_allows(Some(&read), true));
+        let write = vec!["project:write".to_string()];
+        assert!(git_scope_allows(Some(&write), true));
+        assert!(git_scope_allows(None, true));
+        assert!(git_scope_allows(Some(&[]), true));
+        assert!(git_scope_allows(Some(&["*".to_string()]), true));
+    }
+
+    #[test]
+    fn git_scope_denies_unrelated_scopes() {
+        let scopes = vec!["observe:write".to_string()];
+        assert!(!git_scope_allows(Some(&scopes), false));
+    }
+
+    // -- grant_workspace_role --
+
+    #[test]
+    fn workspace_member_gets_read_only() {
+        let mut perms = HashSet::new();
+        grant_workspace_role(&mut perms, "member");
+        assert_eq!(perms, HashSet::from([Permission::ProjectRead]));
+    }
+
+    #[test]
+    fn workspace_admin_and_owner_get_write() {
+        for role in ["admin", "owner"] {
+            let mut perms = HashSet::new();
+            grant_workspace_role(&mut perms, role);
+            assert!(perms.contains(&Permission::ProjectRead));
+            assert!(perms.contains(&Permission::ProjectWrite));
+        }
+    }
+
     // -- scope_allows --
 
     #[test]
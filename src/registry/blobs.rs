@@ -104,7 +104,7 @@ pub async fn get_blob(
         // A21: presigned URL redirect (default — avoids loading blobs into memory)
         let presigned = state
             .minio
-            .presign_read(&blob.minio_path, Duration::from_secs(300))
+            .presign_read(&blob.minio_path, Duration::from_mins(5))
             .await?;
         headers.insert("location", header_val(&presigned.uri().to_string()));
         Ok((StatusCode::TEMPORARY_REDIRECT, headers).into_response())
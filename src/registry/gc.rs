@@ -12,7 +12,7 @@ use crate::store::AppState;
 /// - Orphaned blobs (no `blob_links`, older than 24h grace period)
 /// - Expired upload temp files in `MinIO`
 pub async fn run(state: AppState, cancel: tokio_util::sync::CancellationToken) {
-    let mut interval = tokio::time::interval(Duration::from_secs(3600));
+    let mut interval = tokio::time::interval(Duration::from_hours(1));
     state.task_registry.register("registry_gc", 7200);
     loop {
         tokio::select! {
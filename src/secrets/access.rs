@@ -0,0 +1,163 @@
+// Copyright (c) 2026 Steven Hooker. Exclusively licensed to and distributed by AgentSphere GmbH.
+// SPDX-License-Identifier: BUSL-1.1
+
+//! Secret access trail.
+//!
+//! Every decryption that hands a secret to a consumer (pipeline step, agent pod,
+//! deployment, API read) is recorded in `audit_log` as one `secret.access` row
+//! per secret, naming the actor and purpose. Only secret names are recorded —
+//! values never reach this module.
+
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use crate::audit::{AuditEntry, AuditLog, send_audit};
+
+/// Audit action for secrets decrypted on behalf of a workload.
+pub const ACCESS_ACTION: &str = "secret.access";
+
+/// Audit action for secrets read through the API (`GET .../secrets/{name}`).
+pub const READ_ACTION: &str = "secret.read";
+
+/// Why a secret was decrypted.
+#[derive(Debug, Clone, Copy)]
+pub enum AccessPurpose<'a> {
+    /// Injected into a pipeline step pod.
+    PipelineStep { pipeline_id: Uuid, step: &'a str },
+    /// Injected into a pipeline's deploy-test namespace.
+    DeployTest { pipeline_id: Uuid },
+    /// Injected into an agent session pod.
+    AgentSession { session_id: Uuid },
+    /// Written as a K8s Secret for a release.
+    Deploy {
+        release_id: Uuid,
+        environment: &'a str,
+    },
+}
+
+impl AccessPurpose<'_> {
+    fn detail(&self, name: &str) -> serde_json::Value {
+        match *self {
+            Self::PipelineStep { pipeline_id, step } => serde_json::json!({
+                "name": name,
+                "purpose": "pipeline",
+                "pipeline_id": pipeline_id,
+                "step": step,
+            }),
+            Self::DeployTest { pipeline_id } => serde_json::json!({
+                "name": name,
+                "purpose": "deploy_test",
+                "pipeline_id": pipeline_id,
+            }),
+            Self::AgentSession { session_id } => serde_json::json!({
+                "name": name,
+                "purpose": "agent",
+                "session_id": session_id,
+            }),
+            Self::Deploy {
+                release_id,
+                environment,
+            } => serde_json::json!({
+                "name": name,
+                "purpose": "deploy",
+                "release_id": release_id,
+                "environment": environment,
+            }),
+        }
+    }
+}
+
+/// Record access to `names` in `project_id`.
+///
+/// The actor is `actor_id` when known (the pipeline trigger, session owner,
+/// deployer), else the project owner. Failures are logged, never propagated:
+/// the workload has already received the secrets.
+pub async fn record_access<'n>(
+    pool: &PgPool,
+    audit: &AuditLog,
+    project_id: Uuid,
+    actor_id: Option<Uuid>,
+    names: impl IntoIterator<Item = &'n str>,
+    purpose: AccessPurpose<'_>,
+) {
+    let names: Vec<&str> = names.into_iter().collect();
+    if names.is_empty() {
+        return;
+    }
+
+    let actor = match sqlx::query!(
+        "SELECT id, name FROM users \
+         WHERE id = COALESCE($1, (SELECT owner_id FROM projects WHERE id = $2))",
+        actor_id,
+        project_id,
+    )
+    .fetch_optional(pool)
+    .await
+    {
+        Ok(actor) => actor.map(|u| (u.id, u.name)),
+        Err(e) => {
+            tracing::warn!(error = %e, %project_id, "failed to resolve secret access actor");
+            None
+        }
+    };
+    let Some((actor_id, actor_name)) = actor else {
+        tracing::warn!(%project_id, ?purpose, "secret access without resolvable actor not audited");
+        return;
+    };
+
+    for name in names {
+        send_audit(
+            audit,
+            AuditEntry {
+                actor_id,
+                actor_name: actor_name.clone(),
+                action: ACCESS_ACTION.into(),
+                resource: "secret".into(),
+                resource_id: None,
+                project_id: Some(project_id),
+                detail: Some(purpose.detail(name)),
+                ip_addr: None,
+            },
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn pipeline_detail_names_pipeline_and_step() {
+        let pipeline_id = Uuid::new_v4();
+        let detail = AccessPurpose::PipelineStep {
+            pipeline_id,
+            step: "test",
+        }
+        .detail("DB_PASSWORD");
+        assert_eq!(detail["name"], "DB_PASSWORD");
+        assert_eq!(detail["purpose"], "pipeline");
+        assert_eq!(detail["pipeline_id"], pipeline_id.to_string());
+        assert_eq!(detail["step"], "test");
+    }
+
+    #[test]
+    fn detail_never_carries_a_value() {
+        let id = Uuid::new_v4();
+        for purpose in [
+            AccessPurpose::PipelineStep {
+                pipeline_id: id,
+                step: "build",
+            },
+            AccessPurpose::DeployTest { pipeline_id: id },
+            AccessPurpose::AgentSession { session_id: id },
+            AccessPurpose::Deploy {
+                release_id: id,
+                environment: "production",
+            },
+        ] {
+            let detail = purpose.detail("API_KEY");
+            assert!(detail.get("value").is_none());
+            assert_eq!(detail["name"], "API_KEY");
+        }
+    }
+}
@@ -12,8 +12,110 @@ use uuid::Uuid;
 // Master key
 // ---------------------------------------------------------------------------
 
-/// Parse a hex-encoded 32-byte master key (64 hex chars).
-pub fn parse_master_key(hex_str: &str) -> anyhow::Result<[u8; 32]> {
+/// Versioned master keys (keyed ring).
+///
+/// `PLATFORM_MASTER_KEY` holds one or more comma-separated entries, each either a
+/// bare 64-char hex key (version 1) or `{version}:{hex}`, e.g. `2:<new>,1:<old>`.
+/// The highest version encrypts; every loaded version decrypts. To rotate, add a
+/// new version, run the re-encryption admin endpoint, then drop the old entry.
+#[derive(Clone)]
+pub struct MasterKey {
+    /// `(version, key)` pairs, newest version first.
+    keys: Vec<(u8, [u8; 32])>,
+}
+
+impl MasterKey {
+    /// The key new data is encrypted with.
+    pub fn current(&self) -> &[u8; 32] {
+        &self.keys[0].1
+    }
+
+    /// Version of [`Self::current`].
+    pub fn current_version(&self) -> u8 {
+        self.keys[0].0
+    }
+
+    /// All loaded versions, newest first.
+    pub fn versions(&self) -> Vec<u8> {
+        self.keys.iter().map(|(v, _)| *v).collect()
+    }
+
+    /// Encrypt with the current key. Returns `0x02 || version || nonce (12) || ciphertext || tag`.
+    pub fn encrypt(&self, plaintext: &[u8]) -> anyhow::Result<Vec<u8>> {
+        let unversioned = encrypt(plaintext, self.current())?;
+        let mut result = Vec::with_capacity(unversioned.len() + 1);
+        result.push(KEYED_ENCRYPTION_VERSION);
+        result.push(self.current_version());
+        result.extend_from_slice(&unversioned[1..]);
+        Ok(result)
+    }
+
+    /// Decrypt data produced by [`Self::encrypt`] with the key version it names, or
+    /// older unkeyed data (see [`decrypt`]) with whichever loaded key opens it.
+    pub fn decrypt(&self, encrypted: &[u8]) -> anyhow::Result<Vec<u8>> {
+        if let Some(plaintext) = self.decrypt_keyed(encrypted) {
+            return Ok(plaintext);
+        }
+        let mut last_err = None;
+        for (_, key) in &self.keys {
+            match decrypt(encrypted, key, None) {
+                Ok(plaintext) => return Ok(plaintext),
+                Err(e) => last_err = Some(e),
+            }
+        }
+        Err(last_err.unwrap_or_else(|| anyhow::anyhow!("no master key loaded")))
+    }
+
+    /// Whether `encrypted` is not yet wrapped with the current key version.
+    pub fn needs_rewrap(&self, encrypted: &[u8]) -> bool {
+        !(encrypted.get(1) == Some(&self.current_version())
+            && self.decrypt_keyed(encrypted).is_some())
+    }
+
+    fn decrypt_keyed(&self, encrypted: &[u8]) -> Option<Vec<u8>> {
+        // A legacy blob whose nonce happens to start with 0x02 falls through to
+        // the unkeyed path: the GCM tag check fails here.
+        if encrypted.first() != Some(&KEYED_ENCRYPTION_VERSION) || encrypted.len() < 2 + 12 {
+            return None;
+        }
+        let (_, key) = self.keys.iter().find(|(v, _)| *v == encrypted[1])?;
+        decrypt_raw(&encrypted[2..], key).ok()
+    }
+}
+
+impl From<[u8; 32]> for MasterKey {
+    /// A single-key ring at version 1.
+    fn from(key: [u8; 32]) -> Self {
+        Self {
+            keys: vec![(1, key)],
+        }
+    }
+}
+
+impl std::fmt::Debug for MasterKey {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("MasterKey")
+            .field("versions", &self.versions())
+            .finish_non_exhaustive()
+    }
+}
+
+/// Parse a master key ring: comma-separated hex-encoded 32-byte keys (64 hex
+/// chars), each optionally prefixed with `{version}:` (see [`MasterKey`]).
+pub fn parse_master_key(spec: &str) -> anyhow::Result<MasterKey> {
+    let mut keys = Vec::new();
+    for entry in spec.split(',') {
+        let (version, hex_str) = split_key_entry(entry).map_err(|e| anyhow::anyhow!(e))?;
+        if keys.iter().any(|(v, _)| *v == version) {
+            anyhow::bail!("PLATFORM_MASTER_KEY lists version {version} more than once");
+        }
+        keys.push((version, parse_key_bytes(hex_str)?));
+    }
+    keys.sort_by_key(|(v, _)| std::cmp::Reverse(*v));
+    Ok(MasterKey { keys })
+}
+
+fn parse_key_bytes(hex_str: &str) -> anyhow::Result<[u8; 32]> {
     let bytes = hex::decode(hex_str.trim())
         .map_err(|e| anyhow::anyhow!("invalid PLATFORM_MASTER_KEY hex: {e}"))?;
     let key: [u8; 32] = bytes.try_into().map_err(|v: Vec<u8>| {
@@ -22,14 +124,34 @@ pub fn parse_master_key(hex_str: &str) -> anyhow::Result<[u8; 32]> {
     Ok(key)
 }
 
+/// Split a key ring entry into `(version, hex)`. Bare keys are version 1.
+fn split_key_entry(entry: &str) -> Result<(u8, &str), String> {
+    let entry = entry.trim();
+    match entry.split_once(':') {
+        Some((version, hex_str)) => {
+            let version: u8 = version
+                .trim()
+                .parse()
+                .ok()
+                .filter(|&v| v > 0)
+                .ok_or_else(|| format!("invalid key version '{version}' (expected 1-255)"))?;
+            Ok((version, hex_str))
+        }
+        None => Ok((1, entry)),
+    }
+}
+
 /// Validate master key format without panicking.
-/// Returns `Ok(())` if the key is a valid 64-char hex string (32 bytes).
+/// Returns `Ok(())` if every ring entry is a valid 64-char hex string (32 bytes).
 pub fn validate_master_key(key: &str) -> Result<(), String> {
-    let trimmed = key.trim();
-    if trimmed.len() != 64 {
-        return Err(format!("expected 64 hex characters, got {}", trimmed.len()));
+    for entry in key.split(',') {
+        let (_, hex_str) = split_key_entry(entry)?;
+        let trimmed = hex_str.trim();
+        if trimmed.len() != 64 {
+            return Err(format!("expected 64 hex characters, got {}", trimmed.len()));
+        }
+        hex::decode(trimmed).map_err(|e| format!("not valid hex: {e}"))?;
     }
-    hex::decode(trimmed).map_err(|e| format!("not valid hex: {e}"))?;
     Ok(())
 }
 
@@ -51,6 +173,9 @@ pub fn dev_master_key() -> [u8; 32] {
 /// Version byte prepended to encrypted output (S44: multi-key rotation support).
 const ENCRYPTION_VERSION: u8 = 0x01;
 
+/// Version byte for [`MasterKey::encrypt`] output, followed by the key version.
+const KEYED_ENCRYPTION_VERSION: u8 = 0x02;
+
 /// Encrypt plaintext with AES-256-GCM. Returns `0x01 || nonce (12) || ciphertext || tag`.
 ///
 /// The leading version byte (`0x01`) identifies data encrypted with the current format.
@@ -135,6 +260,11 @@ fn decrypt_raw(payload: &[u8], key: &[u8; 32]) -> anyhow::Result<Vec<u8>> {
 // Types
 // ---------------------------------------------------------------------------
 
+/// Secret value stored (encrypted) in the platform database.
+pub const BACKEND_LOCAL: &str = "local";
+/// Secret whose stored value is a `vault://path#key` reference, resolved at read time.
+pub const BACKEND_VAULT: &str = "vault";
+
 #[derive(Debug, Serialize, ts_rs::TS)]
 #[ts(export, rename = "Secret")]
 pub struct SecretMetadata {
@@ -144,11 +274,48 @@ pub struct SecretMetadata {
     pub environment: Option<String>,
     pub name: String,
     pub scope: String,
+    /// `local` or `vault`.
+    pub backend: String,
     pub version: i32,
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
 }
 
+/// Decrypt a stored secret value. For `vault` secrets the stored value is a
+/// reference and the current value is fetched from Vault.
+pub async fn reveal(
+    master_key: &MasterKey,
+    backend: &str,
+    encrypted: &[u8],
+) -> anyhow::Result<String> {
+    let plaintext = master_key.decrypt(encrypted)?;
+    let stored = String::from_utf8(plaintext)
+        .map_err(|e| anyhow::anyhow!("secret value is not valid UTF-8: {e}"))?;
+    materialize(backend, stored).await
+}
+
+async fn materialize(backend: &str, stored: String) -> anyhow::Result<String> {
+    if backend == BACKEND_VAULT {
+        super::vault::read(&stored).await
+    } else {
+        Ok(stored)
+    }
+}
+
+fn check_backend_value(backend: &str, value: &[u8]) -> anyhow::Result<()> {
+    match backend {
+        BACKEND_LOCAL => Ok(()),
+        BACKEND_VAULT => {
+            let reference = std::str::from_utf8(value)
+                .map_err(|_| anyhow::anyhow!("vault reference is not valid UTF-8"))?;
+            super::vault::parse_reference(reference)
+                .map(|_| ())
+                .map_err(|e| anyhow::anyhow!(e))
+        }
+        other => anyhow::bail!("unknown secret backend '{other}'"),
+    }
+}
+
 // ---------------------------------------------------------------------------
 // CRUD
 // ---------------------------------------------------------------------------
@@ -159,8 +326,10 @@ pub struct CreateSecretParams<'a> {
     pub workspace_id: Option<Uuid>,
     pub environment: Option<&'a str>,
     pub name: &'a str,
+    /// The secret value, or a `vault://path#key` reference when `backend` is `vault`.
     pub value: &'a [u8],
     pub scope: &'a str,
+    pub backend: &'a str,
     pub created_by: Uuid,
 }
 
@@ -170,16 +339,18 @@ pub struct CreateSecretParams<'a> {
 #[tracing::instrument(skip(pool, master_key, params), err)]
 pub async fn create_secret(
     pool: &PgPool,
-    master_key: &[u8; 32],
+    master_key: &MasterKey,
     params: CreateSecretParams<'_>,
 ) -> anyhow::Result<SecretMetadata> {
-    let encrypted = encrypt(params.value, master_key)?;
+    check_backend_value(params.backend, params.value)?;
+    let encrypted = master_key.encrypt(params.value)?;
 
     // Use the scoped index for conflict detection
-    let row = sqlx::query!(
+    let meta = sqlx::query_as!(
+        SecretMetadata,
         r#"
-        INSERT INTO secrets (project_id, workspace_id, environment, name, encrypted_value, scope, created_by)
-        VALUES ($1, $2, $3, $4, $5, $6, $7)
+        INSERT INTO secrets (project_id, workspace_id, environment, name, encrypted_value, scope, backend, created_by)
+        VALUES ($1, $2, $3, $4, $5, $6, $7, $8)
         ON CONFLICT (
             COALESCE(workspace_id, '00000000-0000-0000-0000-000000000000'::uuid),
             COALESCE(project_id,   '00000000-0000-0000-0000-000000000000'::uuid),
@@ -189,9 +360,10 @@ pub async fn create_secret(
         DO UPDATE SET
             encrypted_value = EXCLUDED.encrypted_value,
             scope = EXCLUDED.scope,
+            backend = EXCLUDED.backend,
             version = secrets.version + 1,
             updated_at = now()
-        RETURNING id, project_id, workspace_id, environment, name, scope, version,
+        RETURNING id, project_id, workspace_id, environment, name, scope, backend, version,
                   created_at, updated_at
         "#,
         params.project_id,
@@ -200,69 +372,55 @@ pub async fn create_secret(
         params.name,
         encrypted,
         params.scope,
+        params.backend,
         params.created_by,
     )
     .fetch_one(pool)
     .await?;
 
-    Ok(SecretMetadata {
-        id: row.id,
-        project_id: row.project_id,
-        workspace_id: row.workspace_id,
-        environment: row.environment,
-        name: row.name,
-        scope: row.scope,
-        version: row.version,
-        created_at: row.created_at,
-        updated_at: row.updated_at,
-    })
+    Ok(meta)
 }
 
 /// Create or update a global secret (`project_id`, `workspace_id`, `environment` all NULL).
-#[tracing::instrument(skip(pool, master_key, value), fields(%name, %scope), err)]
+#[tracing::instrument(skip(pool, master_key, value), fields(%name, %scope, %backend), err)]
 pub async fn create_global_secret(
     pool: &PgPool,
-    master_key: &[u8; 32],
+    master_key: &MasterKey,
     name: &str,
     value: &[u8],
     scope: &str,
+    backend: &str,
     created_by: Uuid,
 ) -> anyhow::Result<SecretMetadata> {
-    let encrypted = encrypt(value, master_key)?;
+    check_backend_value(backend, value)?;
+    let encrypted = master_key.encrypt(value)?;
 
     // The unique index idx_secrets_global_name handles conflicts for fully-NULL scoping
-    let row = sqlx::query!(
+    let meta = sqlx::query_as!(
+        SecretMetadata,
         r#"
-        INSERT INTO secrets (project_id, workspace_id, environment, name, encrypted_value, scope, created_by)
-        VALUES (NULL, NULL, NULL, $1, $2, $3, $4)
+        INSERT INTO secrets (project_id, workspace_id, environment, name, encrypted_value, scope, backend, created_by)
+        VALUES (NULL, NULL, NULL, $1, $2, $3, $4, $5)
         ON CONFLICT (name) WHERE project_id IS NULL AND workspace_id IS NULL AND environment IS NULL
         DO UPDATE SET
             encrypted_value = EXCLUDED.encrypted_value,
             scope = EXCLUDED.scope,
+            backend = EXCLUDED.backend,
             version = secrets.version + 1,
             updated_at = now()
-        RETURNING id, project_id, workspace_id, environment, name, scope, version,
+        RETURNING id, project_id, workspace_id, environment, name, scope, backend, version,
                   created_at, updated_at
         "#,
         name,
         encrypted,
         scope,
+        backend,
         created_by,
     )
     .fetch_one(pool)
     .await?;
 
-    Ok(SecretMetadata {
-        id: row.id,
-        project_id: row.project_id,
-        workspace_id: row.workspace_id,
-        environment: row.environment,
-        name: row.name,
-        scope: row.scope,
-        version: row.version,
-        created_at: row.created_at,
-        updated_at: row.updated_at,
-    })
+    Ok(meta)
 }
 
 /// Delete a secret by project + name. Returns whether a row was deleted.
@@ -299,10 +457,12 @@ pub async fn list_secrets(
     project_id: Option<Uuid>,
     environment: Option<&str>,
 ) -> anyhow::Result<Vec<SecretMetadata>> {
-    if let Some(pid) = project_id {
-        let rows = sqlx::query!(
+    let rows = if let Some(pid) = project_id {
+        sqlx::query_as!(
+            SecretMetadata,
             r#"
-            SELECT id, project_id, workspace_id, environment, name, scope, version, created_at, updated_at
+            SELECT id, project_id, workspace_id, environment, name, scope, backend, version,
+                   created_at, updated_at
             FROM secrets
             WHERE project_id = $1
               AND ($2::text IS NULL OR environment IS NOT DISTINCT FROM $2)
@@ -312,49 +472,23 @@ pub async fn list_secrets(
             environment,
         )
         .fetch_all(pool)
-        .await?;
-
-        Ok(rows
-            .into_iter()
-            .map(|r| SecretMetadata {
-                id: r.id,
-                project_id: r.project_id,
-                workspace_id: r.workspace_id,
-                environment: r.environment,
-                name: r.name,
-                scope: r.scope,
-                version: r.version,
-                created_at: r.created_at,
-                updated_at: r.updated_at,
-            })
-            .collect())
+        .await?
     } else {
-        let rows = sqlx::query!(
+        sqlx::query_as!(
+            SecretMetadata,
             r#"
-            SELECT id, project_id, workspace_id, environment, name, scope, version, created_at, updated_at
+            SELECT id, project_id, workspace_id, environment, name, scope, backend, version,
+                   created_at, updated_at
             FROM secrets
             WHERE project_id IS NULL AND workspace_id IS NULL AND environment IS NULL
             ORDER BY name
             "#,
         )
         .fetch_all(pool)
-        .await?;
-
-        Ok(rows
-            .into_iter()
-            .map(|r| SecretMetadata {
-                id: r.id,
-                project_id: r.project_id,
-                workspace_id: r.workspace_id,
-                environment: r.environment,
-                name: r.name,
-                scope: r.scope,
-                version: r.version,
-                created_at: r.created_at,
-                updated_at: r.updated_at,
-            })
-            .collect())
-    }
+        .await?
+    };
+
+    Ok(rows)
 }
 
 /// List secret metadata for a workspace. Never returns encrypted values.
@@ -362,9 +496,11 @@ pub async fn list_workspace_secrets(
     pool: &PgPool,
     workspace_id: Uuid,
 ) -> anyhow::Result<Vec<SecretMetadata>> {
-    let rows = sqlx::query!(
+    let rows = sqlx::query_as!(
+        SecretMetadata,
         r#"
-        SELECT id, project_id, workspace_id, environment, name, scope, version, created_at, updated_at
+        SELECT id, project_id, workspace_id, environment, name, scope, backend, version,
+               created_at, updated_at
         FROM secrets
         WHERE workspace_id = $1 AND project_id IS NULL
         ORDER BY name
@@ -374,20 +510,15 @@ pub async fn list_workspace_secrets(
     .fetch_all(pool)
     .await?;
 
-    Ok(rows
-        .into_iter()
-        .map(|r| SecretMetadata {
-            id: r.id,
-            project_id: r.project_id,
-            workspace_id: r.workspace_id,
-            environment: r.environment,
-            name: r.name,
-            scope: r.scope,
-            version: r.version,
-            created_at: r.created_at,
-            updated_at: r.updated_at,
-        })
-        .collect())
+    Ok(rows)
+}
+
+/// Stored secret as read by the resolve functions.
+struct StoredSecret {
+    name: String,
+    encrypted_value: Vec<u8>,
+    scope: String,
+    backend: String,
 }
 
 /// Resolve (decrypt) a secret by name. Internal-only — NOT exposed via API.
@@ -398,14 +529,15 @@ pub async fn list_workspace_secrets(
 #[tracing::instrument(skip(pool, master_key), fields(%project_id, %name, %requested_scope), err)]
 pub async fn resolve_secret(
     pool: &PgPool,
-    master_key: &[u8; 32],
+    master_key: &MasterKey,
     project_id: Uuid,
     name: &str,
     requested_scope: &str,
 ) -> anyhow::Result<String> {
-    let row = sqlx::query!(
+    let row = sqlx::query_as!(
+        StoredSecret,
         r#"
-        SELECT encrypted_value, scope
+        SELECT name, encrypted_value, scope, backend
         FROM secrets
         WHERE (project_id = $1 OR project_id IS NULL)
           AND name = $2
@@ -430,9 +562,7 @@ pub async fn resolve_secret(
         );
     }
 
-    let plaintext = decrypt(&row.encrypted_value, master_key, None)?;
-    String::from_utf8(plaintext)
-        .map_err(|e| anyhow::anyhow!("secret value is not valid UTF-8: {e}"))
+    reveal(master_key, &row.backend, &row.encrypted_value).await
 }
 
 /// Resolve (decrypt) a global secret by name (`project_id IS NULL`).
@@ -440,13 +570,14 @@ pub async fn resolve_secret(
 #[tracing::instrument(skip(pool, master_key), fields(%name, %requested_scope), err)]
 pub async fn resolve_global_secret(
     pool: &PgPool,
-    master_key: &[u8; 32],
+    master_key: &MasterKey,
     name: &str,
     requested_scope: &str,
 ) -> anyhow::Result<String> {
-    let row = sqlx::query!(
+    let row = sqlx::query_as!(
+        StoredSecret,
         r#"
-        SELECT encrypted_value, scope
+        SELECT name, encrypted_value, scope, backend
         FROM secrets
         WHERE project_id IS NULL AND workspace_id IS NULL AND environment IS NULL
           AND name = $1
@@ -465,9 +596,41 @@ pub async fn resolve_global_secret(
         );
     }
 
-    let plaintext = decrypt(&row.encrypted_value, master_key, None)?;
-    String::from_utf8(plaintext)
-        .map_err(|e| anyhow::anyhow!("secret value is not valid UTF-8: {e}"))
+    reveal(master_key, &row.backend, &row.encrypted_value).await
+}
+
+/// Resolve (decrypt) a global secret by id (`project_id IS NULL`).
+/// Enforces scope matching like [`resolve_secret`].
+#[tracing::instrument(skip(pool, master_key), fields(%secret_id, %requested_scope), err)]
+pub async fn resolve_global_secret_by_id(
+    pool: &PgPool,
+    master_key: &MasterKey,
+    secret_id: Uuid,
+    requested_scope: &str,
+) -> anyhow::Result<String> {
+    let row = sqlx::query_as!(
+        StoredSecret,
+        r#"
+        SELECT name, encrypted_value, scope, backend
+        FROM secrets
+        WHERE id = $1
+          AND project_id IS NULL AND workspace_id IS NULL AND environment IS NULL
+        "#,
+        secret_id,
+    )
+    .fetch_optional(pool)
+    .await?
+    .ok_or_else(|| anyhow::anyhow!("global secret {secret_id} not found"))?;
+
+    if row.scope != "all" && row.scope != requested_scope && requested_scope != "all" {
+        anyhow::bail!(
+            "global secret '{}' has scope '{}' but '{requested_scope}' was requested",
+            row.name,
+            row.scope
+        );
+    }
+
+    reveal(master_key, &row.backend, &row.encrypted_value).await
 }
 
 /// Resolve (decrypt) a secret using the full hierarchy (most specific wins):
@@ -481,7 +644,7 @@ pub async fn resolve_global_secret(
 #[tracing::instrument(skip(pool, master_key), fields(%project_id, ?workspace_id, ?environment, %name, %requested_scope), err)]
 pub async fn resolve_secret_hierarchical(
     pool: &PgPool,
-    master_key: &[u8; 32],
+    master_key: &MasterKey,
     project_id: Uuid,
     workspace_id: Option<Uuid>,
     environment: Option<&str>,
@@ -490,10 +653,10 @@ pub async fn resolve_secret_hierarchical(
 ) -> anyhow::Result<String> {
     // Query all matching rows ordered by specificity, take the first one.
     // Specificity: project+env > project > workspace > global
-    let row = sqlx::query!(
+    let row = sqlx::query_as!(
+        StoredSecret,
         r#"
-        SELECT encrypted_value, scope,
-               project_id, workspace_id, environment
+        SELECT name, encrypted_value, scope, backend
         FROM secrets
         WHERE name = $1
           AND (
@@ -532,9 +695,7 @@ pub async fn resolve_secret_hierarchical(
         );
     }
 
-    let plaintext = decrypt(&row.encrypted_value, master_key, None)?;
-    String::from_utf8(plaintext)
-        .map_err(|e| anyhow::anyhow!("secret value is not valid UTF-8: {e}"))
+    reveal(master_key, &row.backend, &row.encrypted_value).await
 }
 
 /// Extract secret names from a template string matching `${{ secrets.NAME }}` patterns.
@@ -567,7 +728,9 @@ fn extract_secret_patterns(template: &str) -> Vec<(usize, usize, String)> {
 }
 
 /// Query and decrypt all secrets for a project matching the given scopes
-/// and environment. Returns `(name, decrypted_value)` pairs.
+/// and environment. Returns `(name, decrypted_value)` pairs. Vault-backed
+/// secrets are resolved live; ones Vault cannot serve are skipped like
+/// undecryptable ones.
 ///
 /// When `environment` is `Some("production")`, matches secrets with that
 /// environment plus env-less (NULL) secrets. When `None`, only matches
@@ -575,14 +738,15 @@ fn extract_secret_patterns(template: &str) -> Vec<(usize, usize, String)> {
 #[tracing::instrument(skip(pool, master_key), fields(%project_id, ?environment), err)]
 pub async fn query_scoped_secrets(
     pool: &PgPool,
-    master_key: &[u8; 32],
+    master_key: &MasterKey,
     project_id: Uuid,
     scopes: &[&str],
     environment: Option<&str>,
 ) -> anyhow::Result<Vec<(String, String)>> {
-    let rows = sqlx::query!(
+    let rows = sqlx::query_as!(
+        StoredSecret,
         r#"
-        SELECT name, encrypted_value
+        SELECT name, encrypted_value, scope, backend
         FROM secrets
         WHERE project_id = $1
           AND scope = ANY($2)
@@ -598,12 +762,17 @@ pub async fn query_scoped_secrets(
 
     let mut result = Vec::with_capacity(rows.len());
     for row in rows {
-        match decrypt(&row.encrypted_value, master_key, None) {
+        match master_key.decrypt(&row.encrypted_value) {
             Ok(plaintext) => {
-                let value = String::from_utf8(plaintext).map_err(|e| {
+                let stored = String::from_utf8(plaintext).map_err(|e| {
                     anyhow::anyhow!("secret '{}' is not valid UTF-8: {e}", row.name)
                 })?;
-                result.push((row.name, value));
+                match materialize(&row.backend, stored).await {
+                    Ok(value) => result.push((row.name, value)),
+                    Err(e) => {
+                        tracing::warn!(secret = %row.name, error = %e, "skipping unresolvable vault secret");
+                    }
+                }
             }
             Err(e) => {
                 tracing::warn!(secret = %row.name, error = %e, "skipping undecryptable secret");
@@ -619,7 +788,7 @@ pub async fn query_scoped_secrets(
 #[tracing::instrument(skip(pool, master_key, template), fields(%project_id, %scope), err)]
 pub async fn resolve_secrets_for_env(
     pool: &PgPool,
-    master_key: &[u8; 32],
+    master_key: &MasterKey,
     project_id: Uuid,
     scope: &str,
     template: &str,
@@ -662,6 +831,337 @@ pub async fn resolve_secrets_for_env(
     Ok(result)
 }
 
+// ---------------------------------------------------------------------------
+// Key rotation
+// ---------------------------------------------------------------------------
+
+/// Columns holding [`MasterKey`] ciphertext. Every table is keyed by `id UUID`.
+#[derive(Debug, Clone, Copy)]
+enum EncryptedColumn {
+    SecretValue,
+    ProviderKey,
+    CliCredential,
+    LlmConfig,
+    TotpSecret,
+    VariableValue,
+    PipelineVariables,
+    MirrorCredential,
+    PushMirrorCredential,
+}
+
+/// A stored ciphertext and the row it belongs to.
+struct EncryptedRow {
+    id: Uuid,
+    encrypted: Vec<u8>,
+}
+
+impl EncryptedColumn {
+    const ALL: [Self; 9] = [
+        Self::SecretValue,
+        Self::ProviderKey,
+        Self::CliCredential,
+        Self::LlmConfig,
+        Self::TotpSecret,
+        Self::VariableValue,
+        Self::PipelineVariables,
+        Self::MirrorCredential,
+        Self::PushMirrorCredential,
+    ];
+
+    fn table(self) -> &'static str {
+        match self {
+            Self::SecretValue => "secrets",
+            Self::ProviderKey => "user_provider_keys",
+            Self::CliCredential => "cli_credentials",
+            Self::LlmConfig => "llm_provider_configs",
+            Self::TotpSecret => "user_totp",
+            Self::VariableValue => "variable_group_entries",
+            Self::PipelineVariables => "pipelines",
+            Self::MirrorCredential => "projects",
+            Self::PushMirrorCredential => "projects",
+        }
+    }
+
+    /// The next `REWRAP_BATCH` non-null ciphertexts with `id > after`, by id.
+    async fn batch(self, pool: &PgPool, after: Uuid) -> Result<Vec<EncryptedRow>, sqlx::Error> {
+        match self {
+            Self::SecretValue => {
+                sqlx::query_as!(
+                    EncryptedRow,
+                    r#"SELECT id, encrypted_value AS "encrypted!" FROM secrets
+                       WHERE id > $1 AND encrypted_value IS NOT NULL ORDER BY id LIMIT $2"#,
+                    after,
+                    REWRAP_BATCH,
+                )
+                .fetch_all(pool)
+                .await
+            }
+            Self::ProviderKey => {
+                sqlx::query_as!(
+                    EncryptedRow,
+                    r#"SELECT id, encrypted_key AS "encrypted!" FROM user_provider_keys
+                       WHERE id > $1 AND encrypted_key IS NOT NULL ORDER BY id LIMIT $2"#,
+                    after,
+                    REWRAP_BATCH,
+                )
+                .fetch_all(pool)
+                .await
+            }
+            Self::CliCredential => {
+                sqlx::query_as!(
+                    EncryptedRow,
+                    r#"SELECT id, encrypted_data AS "encrypted!" FROM cli_credentials
+                       WHERE id > $1 AND encrypted_data IS NOT NULL ORDER BY id LIMIT $2"#,
+                    after,
+                    REWRAP_BATCH,
+                )
+                .fetch_all(pool)
+                .await
+            }
+            Self::LlmConfig => {
+                sqlx::query_as!(
+                    EncryptedRow,
+                    r#"SELECT id, encrypted_config AS "encrypted!" FROM llm_provider_configs
+                       WHERE id > $1 AND encrypted_config IS NOT NULL ORDER BY id LIMIT $2"#,
+                    after,
+                    REWRAP_BATCH,
+                )
+                .fetch_all(pool)
+                .await
+            }
+            Self::TotpSecret => {
+                sqlx::query_as!(
+                    EncryptedRow,
+                    r#"SELECT id, encrypted_secret AS "encrypted!" FROM user_totp
+                       WHERE id > $1 AND encrypted_secret IS NOT NULL ORDER BY id LIMIT $2"#,
+                    after,
+                    REWRAP_BATCH,
+                )
+                .fetch_all(pool)
+                .await
+            }
+            Self::VariableValue => {
+                sqlx::query_as!(
+                    EncryptedRow,
+                    r#"SELECT id, encrypted_value AS "encrypted!" FROM variable_group_entries
+                       WHERE id > $1 AND encrypted_value IS NOT NULL ORDER BY id LIMIT $2"#,
+                    after,
+                    REWRAP_BATCH,
+                )
+                .fetch_all(pool)
+                .await
+            }
+            Self::PipelineVariables => {
+                sqlx::query_as!(
+                    EncryptedRow,
+                    r#"SELECT id, encrypted_variables AS "encrypted!" FROM pipelines
+                       WHERE id > $1 AND encrypted_variables IS NOT NULL ORDER BY id LIMIT $2"#,
+                    after,
+                    REWRAP_BATCH,
+                )
+                .fetch_all(pool)
+                .await
+            }
+            Self::MirrorCredential => {
+                sqlx::query_as!(
+                    EncryptedRow,
+                    r#"SELECT id, mirror_credential AS "encrypted!" FROM projects
+                       WHERE id > $1 AND mirror_credential IS NOT NULL ORDER BY id LIMIT $2"#,
+                    after,
+                    REWRAP_BATCH,
+                )
+                .fetch_all(pool)
+                .await
+            }
+            Self::PushMirrorCredential => {
+                sqlx::query_as!(
+                    EncryptedRow,
+                    r#"SELECT id, push_mirror_credential AS "encrypted!" FROM projects
+                       WHERE id > $1 AND push_mirror_credential IS NOT NULL ORDER BY id LIMIT $2"#,
+                    after,
+                    REWRAP_BATCH,
+                )
+                .fetch_all(pool)
+                .await
+            }
+        }
+    }
+
+    /// Replace `old` with `new` on row `id`. Returns `false` if the row no
+    /// longer holds `old`.
+    async fn swap(
+        self,
+        pool: &PgPool,
+        id: Uuid,
+        new: &[u8],
+        old: &[u8],
+    ) -> Result<bool, sqlx::Error> {
+        let result = match self {
+            Self::SecretValue => {
+                sqlx::query!(
+                    "UPDATE secrets SET encrypted_value = $2 WHERE id = $1 AND encrypted_value = $3",
+                    id,
+                    new,
+                    old,
+                )
+                .execute(pool)
+                .await?
+            }
+            Self::ProviderKey => {
+                sqlx::query!(
+                    "UPDATE user_provider_keys SET encrypted_key = $2 WHERE id = $1 AND encrypted_key = $3",
+                    id,
+                    new,
+                    old,
+                )
+                .execute(pool)
+                .await?
+            }
+            Self::CliCredential => {
+                sqlx::query!(
+                    "UPDATE cli_credentials SET encrypted_data = $2 WHERE id = $1 AND encrypted_data = $3",
+                    id,
+                    new,
+                    old,
+                )
+                .execute(pool)
+                .await?
+            }
+            Self::LlmConfig => {
+                sqlx::query!(
+                    "UPDATE llm_provider_configs SET encrypted_config = $2 WHERE id = $1 AND encrypted_config = $3",
+                    id,
+                    new,
+                    old,
+                )
+                .execute(pool)
+                .await?
+            }
+            Self::TotpSecret => {
+                sqlx::query!(
+                    "UPDATE user_totp SET encrypted_secret = $2 WHERE id = $1 AND encrypted_secret = $3",
+                    id,
+                    new,
+                    old,
+                )
+                .execute(pool)
+                .await?
+            }
+            Self::VariableValue => {
+                sqlx::query!(
+                    "UPDATE variable_group_entries SET encrypted_value = $2 WHERE id = $1 AND encrypted_value = $3",
+                    id,
+                    new,
+                    old,
+                )
+                .execute(pool)
+                .await?
+            }
+            Self::PipelineVariables => {
+                sqlx::query!(
+                    "UPDATE pipelines SET encrypted_variables = $2 WHERE id = $1 AND encrypted_variables = $3",
+                    id,
+                    new,
+                    old,
+                )
+                .execute(pool)
+                .await?
+            }
+            Self::MirrorCredential => {
+                sqlx::query!(
+                    "UPDATE projects SET mirror_credential = $2 WHERE id = $1 AND mirror_credential = $3",
+                    id,
+                    new,
+                    old,
+                )
+                .execute(pool)
+                .await?
+            }
+            Self::PushMirrorCredential => {
+                sqlx::query!(
+                    "UPDATE projects SET push_mirror_credential = $2 WHERE id = $1 AND push_mirror_credential = $3",
+                    id,
+                    new,
+                    old,
+                )
+                .execute(pool)
+                .await?
+            }
+        };
+        Ok(result.rows_affected() == 1)
+    }
+}
+
+/// Rows re-encrypted per batch.
+const REWRAP_BATCH: i64 = 500;
+
+/// Outcome of [`rewrap_all`].
+#[derive(Debug, Default, Serialize, ts_rs::TS)]
+#[ts(export)]
+pub struct RewrapReport {
+    /// Key version everything is now wrapped with.
+    pub key_version: u8,
+    #[ts(type = "number")]
+    pub rewrapped: u64,
+    #[ts(type = "number")]
+    pub unchanged: u64,
+    /// Rows no loaded key could decrypt. Old keys must not be retired while non-zero.
+    #[ts(type = "number")]
+    pub failed: u64,
+}
+
+/// Re-encrypt every stored ciphertext that isn't on the current key version.
+///
+/// Rows are rewritten one at a time with a compare-and-swap on the old value,
+/// so concurrent writes (which already use the current key) are never clobbered
+/// and the platform keeps serving reads throughout. Safe to re-run.
+#[tracing::instrument(skip(pool, master_key), fields(key_version = master_key.current_version()), err)]
+pub async fn rewrap_all(pool: &PgPool, master_key: &MasterKey) -> anyhow::Result<RewrapReport> {
+    let mut report = RewrapReport {
+        key_version: master_key.current_version(),
+        ..RewrapReport::default()
+    };
+
+    for column in EncryptedColumn::ALL {
+        let mut after = Uuid::nil();
+        loop {
+            let rows = column.batch(pool, after).await?;
+            let Some(last) = rows.last() else {
+                break;
+            };
+            after = last.id;
+
+            for EncryptedRow { id, encrypted } in rows {
+                if !master_key.needs_rewrap(&encrypted) {
+                    report.unchanged += 1;
+                    continue;
+                }
+                let rewrapped = match master_key.decrypt(&encrypted) {
+                    Ok(plaintext) => master_key.encrypt(&plaintext)?,
+                    Err(e) => {
+                        tracing::warn!(table = column.table(), %id, error = %e, "cannot decrypt row for rewrap");
+                        report.failed += 1;
+                        continue;
+                    }
+                };
+                if column.swap(pool, id, &rewrapped, &encrypted).await? {
+                    report.rewrapped += 1;
+                } else {
+                    // Rewritten or deleted concurrently — the new value uses the current key
+                    report.unchanged += 1;
+                }
+            }
+        }
+    }
+
+    tracing::info!(
+        rewrapped = report.rewrapped,
+        failed = report.failed,
+        "master key rewrap finished"
+    );
+    Ok(report)
+}
+
 // ---------------------------------------------------------------------------
 // Tests
 // ---------------------------------------------------------------------------
@@ -735,7 +1235,7 @@ mod tests {
     fn parse_master_key_valid() {
         let hex_key = "aa".repeat(32); // 64 hex chars = 32 bytes
         let key = parse_master_key(&hex_key).unwrap();
-        assert_eq!(key, [0xaa; 32]);
+        assert_eq!(*key.current(), [0xaa; 32]);
     }
 
     #[test]
@@ -814,7 +1314,7 @@ mod tests {
     fn parse_master_key_trims_whitespace() {
         let hex_key = format!("  {}  ", "aa".repeat(32));
         let key = parse_master_key(&hex_key).unwrap();
-        assert_eq!(key, [0xaa; 32]);
+        assert_eq!(*key.current(), [0xaa; 32]);
     }
 
     #[test]
@@ -1072,14 +1572,14 @@ mod tests {
     fn parse_master_key_with_leading_trailing_whitespace() {
         let hex_key = format!("\t{}\n", "bb".repeat(32));
         let key = parse_master_key(&hex_key).unwrap();
-        assert_eq!(key, [0xbb; 32]);
+        assert_eq!(*key.current(), [0xbb; 32]);
     }
 
     #[test]
     fn parse_master_key_uppercase_hex() {
         let hex_key = "AA".repeat(32);
         let key = parse_master_key(&hex_key).unwrap();
-        assert_eq!(key, [0xaa; 32]);
+        assert_eq!(*key.current(), [0xaa; 32]);
     }
 
     #[test]
@@ -1261,6 +1761,7 @@ mod tests {
             environment: Some("production".into()),
             name: "DB_URL".into(),
             scope: "pipeline".into(),
+            backend: BACKEND_VAULT.into(),
             version: 3,
             created_at: chrono::Utc::now(),
             updated_at: chrono::Utc::now(),
@@ -1268,6 +1769,7 @@ mod tests {
         let json = serde_json::to_value(&meta).unwrap();
         assert_eq!(json["name"], "DB_URL");
         assert_eq!(json["scope"], "pipeline");
+        assert_eq!(json["backend"], "vault");
         assert_eq!(json["version"], 3);
         assert_eq!(json["environment"], "production");
         assert!(json["workspace_id"].is_null());
@@ -1282,6 +1784,7 @@ mod tests {
             environment: None,
             name: "test".into(),
             scope: "all".into(),
+            backend: BACKEND_LOCAL.into(),
             version: 1,
             created_at: chrono::Utc::now(),
             updated_at: chrono::Utc::now(),
@@ -1291,6 +1794,14 @@ mod tests {
         assert!(debug.contains("test"));
     }
 
+    #[test]
+    fn check_backend_value_requires_vault_reference() {
+        assert!(check_backend_value(BACKEND_LOCAL, b"anything").is_ok());
+        assert!(check_backend_value(BACKEND_VAULT, b"vault://secret/data/app#token").is_ok());
+        assert!(check_backend_value(BACKEND_VAULT, b"plain-value").is_err());
+        assert!(check_backend_value("consul", b"x").is_err());
+    }
+
     #[test]
     fn validate_master_key_valid() {
         let hex_key = "aa".repeat(32);
@@ -1315,4 +1826,112 @@ mod tests {
         let hex_key = format!("  {}  ", "aa".repeat(32));
         assert!(validate_master_key(&hex_key).is_ok());
     }
+
+    // -- Key ring --
+
+    #[test]
+    fn parse_master_key_bare_key_is_version_1() {
+        let key = parse_master_key(&"aa".repeat(32)).unwrap();
+        assert_eq!(key.current_version(), 1);
+        assert_eq!(key.versions(), vec![1]);
+    }
+
+    #[test]
+    fn parse_master_key_ring_newest_version_is_current() {
+        let spec = format!("1:{},3:{}", "aa".repeat(32), "bb".repeat(32));
+        let key = parse_master_key(&spec).unwrap();
+        assert_eq!(key.current_version(), 3);
+        assert_eq!(*key.current(), [0xbb; 32]);
+        assert_eq!(key.versions(), vec![3, 1]);
+    }
+
+    #[test]
+    fn parse_master_key_ring_rejects_duplicate_and_bad_versions() {
+        let dup = format!("2:{},2:{}", "aa".repeat(32), "bb".repeat(32));
+        assert!(
+            parse_master_key(&dup)
+                .unwrap_err()
+                .to_string()
+                .contains("more than once")
+        );
+        let zero = format!("0:{}", "aa".repeat(32));
+        assert!(
+            parse_master_key(&zero)
+                .unwrap_err()
+                .to_string()
+                .contains("key version")
+        );
+        let big = format!("256:{}", "aa".repeat(32));
+        assert!(parse_master_key(&big).is_err());
+    }
+
+    #[test]
+    fn validate_master_key_checks_every_ring_entry() {
+        let ok = format!("2:{}, 1:{}", "aa".repeat(32), "bb".repeat(32));
+        assert!(validate_master_key(&ok).is_ok());
+        let bad = format!("2:{},1:aabb", "aa".repeat(32));
+        assert!(
+            validate_master_key(&bad)
+                .unwrap_err()
+                .contains("64 hex characters")
+        );
+    }
+
+    #[test]
+    fn ring_encrypt_tags_key_version() {
+        let spec = format!("7:{}", "aa".repeat(32));
+        let key = parse_master_key(&spec).unwrap();
+        let encrypted = key.encrypt(b"hello").unwrap();
+        assert_eq!(encrypted[0], KEYED_ENCRYPTION_VERSION);
+        assert_eq!(encrypted[1], 7);
+        assert_eq!(key.decrypt(&encrypted).unwrap(), b"hello");
+    }
+
+    #[test]
+    fn ring_decrypts_data_from_any_loaded_version() {
+        let old = MasterKey::from([1u8; 32]);
+        let keyed = old.encrypt(b"keyed").unwrap();
+        let unkeyed = encrypt(b"unkeyed", &[1u8; 32]).unwrap();
+
+        let spec = format!("2:{},1:{}", hex::encode([2u8; 32]), hex::encode([1u8; 32]));
+        let ring = parse_master_key(&spec).unwrap();
+        assert_eq!(ring.decrypt(&keyed).unwrap(), b"keyed");
+        assert_eq!(ring.decrypt(&unkeyed).unwrap(), b"unkeyed");
+    }
+
+    #[test]
+    fn ring_rewrap_moves_data_to_current_version() {
+        let spec = format!("2:{},1:{}", hex::encode([2u8; 32]), hex::encode([1u8; 32]));
+        let ring = parse_master_key(&spec).unwrap();
+        let old = MasterKey::from([1u8; 32]).encrypt(b"rotate-me").unwrap();
+        assert!(ring.needs_rewrap(&old));
+
+        let rewrapped = ring.encrypt(&ring.decrypt(&old).unwrap()).unwrap();
+        assert!(!ring.needs_rewrap(&rewrapped));
+
+        // Retiring version 1 keeps rewrapped data readable
+        let retired = parse_master_key(&format!("2:{}", hex::encode([2u8; 32]))).unwrap();
+        assert_eq!(retired.decrypt(&rewrapped).unwrap(), b"rotate-me");
+        assert!(retired.decrypt(&old).is_err());
+    }
+
+    #[test]
+    fn ring_unknown_version_fails() {
+        let v3 = parse_master_key(&format!("3:{}", hex::encode([3u8; 32]))).unwrap();
+        let encrypted = v3.encrypt(b"x").unwrap();
+        let v1 = MasterKey::from([3u8; 32]);
+        // Same key bytes but version 3 isn't loaded under that number
+        assert!(v1.decrypt(&encrypted).is_err());
+    }
+
+    #[test]
+    fn master_key_debug_redacts_key_material() {
+        let key = MasterKey::from([0xaa; 32]);
+        let debug = format!("{key:?}");
+        assert!(debug.contains("versions"));
+        assert!(
+            !debug.contains("170"),
+            "key bytes must not be printed: {debug}"
+        );
+    }
 }
@@ -128,7 +128,7 @@ pub fn validate_env_vars<S: std::hash::BuildHasher>(
 #[tracing::instrument(skip(pool, master_key, env_vars), fields(%user_id, %provider_type), err)]
 pub async fn create_config<S: std::hash::BuildHasher>(
     pool: &PgPool,
-    master_key: &[u8; 32],
+    master_key: &engine::MasterKey,
     user_id: Uuid,
     provider_type: &str,
     label: &str,
@@ -146,7 +146,7 @@ pub async fn create_config<S: std::hash::BuildHasher>(
         model: model.map(String::from),
     };
     let plaintext = serde_json::to_vec(&blob)?;
-    let encrypted = engine::encrypt(&plaintext, master_key)?;
+    let encrypted = master_key.encrypt(&plaintext)?;
 
     let id = sqlx::query_scalar!(
         r#"
@@ -170,7 +170,7 @@ pub async fn create_config<S: std::hash::BuildHasher>(
 #[tracing::instrument(skip(pool, master_key, env_vars), fields(%config_id, %user_id), err)]
 pub async fn update_config<S: std::hash::BuildHasher>(
     pool: &PgPool,
-    master_key: &[u8; 32],
+    master_key: &engine::MasterKey,
     config_id: Uuid,
     user_id: Uuid,
     env_vars: &HashMap<String, String, S>,
@@ -200,7 +200,7 @@ pub async fn update_config<S: std::hash::BuildHasher>(
         model: model.map(String::from),
     };
     let plaintext = serde_json::to_vec(&blob)?;
-    let encrypted = engine::encrypt(&plaintext, master_key)?;
+    let encrypted = master_key.encrypt(&plaintext)?;
 
     let result = sqlx::query!(
         r#"
@@ -225,7 +225,7 @@ pub async fn update_config<S: std::hash::BuildHasher>(
 #[tracing::instrument(skip(pool, master_key), fields(%config_id, %user_id), err)]
 pub async fn get_config(
     pool: &PgPool,
-    master_key: &[u8; 32],
+    master_key: &engine::MasterKey,
     config_id: Uuid,
     user_id: Uuid,
 ) -> anyhow::Result<Option<ProviderConfig>> {
@@ -245,7 +245,7 @@ pub async fn get_config(
         return Ok(None);
     };
 
-    let plaintext = engine::decrypt(&row.encrypted_config, master_key, None)?;
+    let plaintext = master_key.decrypt(&row.encrypted_config)?;
     let blob: EncryptedBlob = serde_json::from_slice(&plaintext)
         .map_err(|e| anyhow::anyhow!("failed to parse encrypted config: {e}"))?;
 
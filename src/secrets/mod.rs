@@ -3,8 +3,10 @@
 
 //! Secrets engine: AES-256-GCM encryption, scoped access, and request flow.
 
+pub mod access;
 #[allow(dead_code)]
 pub mod engine;
 pub mod llm_providers;
 pub mod request;
 pub mod user_keys;
+pub mod vault;
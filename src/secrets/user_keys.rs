@@ -29,12 +29,12 @@ pub struct ProviderKeyMetadata {
 #[tracing::instrument(skip(pool, master_key, api_key), fields(%user_id, %provider), err)]
 pub async fn set_user_key(
     pool: &PgPool,
-    master_key: &[u8; 32],
+    master_key: &engine::MasterKey,
     user_id: Uuid,
     provider: &str,
     api_key: &str,
 ) -> anyhow::Result<()> {
-    let encrypted = engine::encrypt(api_key.as_bytes(), master_key)?;
+    let encrypted = master_key.encrypt(api_key.as_bytes())?;
     let suffix = key_suffix(api_key);
 
     sqlx::query!(
@@ -62,7 +62,7 @@ pub async fn set_user_key(
 #[tracing::instrument(skip(pool, master_key), fields(%user_id, %provider), err)]
 pub async fn get_user_key(
     pool: &PgPool,
-    master_key: &[u8; 32],
+    master_key: &engine::MasterKey,
     user_id: Uuid,
     provider: &str,
 ) -> anyhow::Result<Option<String>> {
@@ -76,7 +76,7 @@ pub async fn get_user_key(
 
     match row {
         Some(r) => {
-            let plaintext = engine::decrypt(&r.encrypted_key, master_key, None)?;
+            let plaintext = master_key.decrypt(&r.encrypted_key)?;
             let key = String::from_utf8(plaintext)
                 .map_err(|e| anyhow::anyhow!("provider key is not valid UTF-8: {e}"))?;
             Ok(Some(key))
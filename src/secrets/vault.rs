@@ -0,0 +1,240 @@
+// Copyright (c) 2026 Steven Hooker. Exclusively licensed to and distributed by AgentSphere GmbH.
+// SPDX-License-Identifier: BUSL-1.1
+
+//! `HashiCorp` Vault backend.
+//!
+//! A secret with `backend = 'vault'` stores only an encrypted reference of the
+//! form `vault://path#key`. The value is fetched from the Vault HTTP API when
+//! the secret is resolved and cached in memory for `PLATFORM_VAULT_CACHE_TTL`
+//! seconds — it is never written to the platform database.
+
+use std::collections::HashMap;
+use std::sync::{LazyLock, Mutex, OnceLock};
+use std::time::{Duration, Instant};
+
+use crate::config::Config;
+
+/// Reference prefix for Vault-backed secrets.
+pub const SCHEME: &str = "vault://";
+
+/// A parsed `vault://path#key` reference.
+#[derive(Debug, PartialEq, Eq)]
+pub struct VaultRef<'a> {
+    /// API path below `/v1/`, e.g. `secret/data/myapp`.
+    pub path: &'a str,
+    /// Field within the secret's data.
+    pub key: &'a str,
+}
+
+struct VaultConfig {
+    addr: String,
+    token: String,
+    cache_ttl: Duration,
+    dev_mode: bool,
+}
+
+static CONFIG: OnceLock<VaultConfig> = OnceLock::new();
+
+static CACHE: LazyLock<Mutex<ValueCache>> = LazyLock::new(|| Mutex::new(ValueCache::default()));
+
+static VAULT_CLIENT: LazyLock<reqwest::Client> = LazyLock::new(|| {
+    reqwest::Client::builder()
+        .connect_timeout(Duration::from_secs(5))
+        .timeout(Duration::from_secs(10))
+        .redirect(reqwest::redirect::Policy::none())
+        .build()
+        .expect("failed to build vault HTTP client")
+});
+
+/// Configure the Vault client from `PLATFORM_VAULT_*`. Call once at startup.
+/// Without both an address and a token, `vault://` secrets fail to resolve.
+pub fn configure(config: &Config) {
+    if let (Some(addr), Some(token)) = (&config.vault_addr, &config.vault_token) {
+        CONFIG
+            .set(VaultConfig {
+                addr: addr.trim_end_matches('/').to_owned(),
+                token: token.clone(),
+                cache_ttl: Duration::from_secs(config.vault_cache_ttl_secs),
+                dev_mode: config.dev_mode,
+            })
+            .ok();
+    }
+}
+
+/// Parse and validate a `vault://path#key` reference.
+pub fn parse_reference(reference: &str) -> Result<VaultRef<'_>, String> {
+    let rest = reference
+        .strip_prefix(SCHEME)
+        .ok_or_else(|| format!("vault reference must start with {SCHEME}"))?;
+    let (path, key) = rest
+        .split_once('#')
+        .ok_or("vault reference must be vault://path#key")?;
+
+    let path_ok = !path.is_empty()
+        && path.split('/').all(|seg| {
+            !seg.is_empty()
+                && seg != "."
+                && seg != ".."
+                && seg
+                    .chars()
+                    .all(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.'))
+        });
+    if !path_ok {
+        return Err(format!("invalid vault path '{path}'"));
+    }
+
+    let key_ok = !key.is_empty()
+        && key
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.'));
+    if !key_ok {
+        return Err(format!("invalid vault key '{key}'"));
+    }
+
+    Ok(VaultRef { path, key })
+}
+
+/// Resolve a `vault://path#key` reference to its current value.
+#[tracing::instrument(err)]
+pub async fn read(reference: &str) -> anyhow::Result<String> {
+    let vref = parse_reference(reference).map_err(|e| anyhow::anyhow!(e))?;
+    let cfg = CONFIG.get().ok_or_else(|| {
+        anyhow::anyhow!(
+            "vault is not configured (set PLATFORM_VAULT_ADDR and PLATFORM_VAULT_TOKEN)"
+        )
+    })?;
+
+    if let Some(value) = lock_cache().get(reference, cfg.cache_ttl) {
+        return Ok(value);
+    }
+
+    let url = format!("{}/v1/{}", cfg.addr, vref.path);
+    if !cfg.dev_mode {
+        crate::validation::check_ssrf_url(&url, &["http", "https"])
+            .map_err(|e| anyhow::anyhow!("vault address rejected: {e}"))?;
+    }
+
+    let resp = VAULT_CLIENT
+        .get(&url)
+        .header("X-Vault-Token", &cfg.token)
+        .send()
+        .await?;
+    let status = resp.status();
+    if !status.is_success() {
+        anyhow::bail!("vault returned {status} for '{}'", vref.path);
+    }
+    let body: serde_json::Value = resp.json().await?;
+    let value = extract_value(&body, vref.key)
+        .ok_or_else(|| anyhow::anyhow!("key '{}' not found at '{}'", vref.key, vref.path))?;
+
+    if !cfg.cache_ttl.is_zero() {
+        lock_cache().insert(reference, value.clone());
+    }
+    Ok(value)
+}
+
+/// Pick `key` out of a Vault read response: KV v2 nests the secret under
+/// `data.data`, KV v1 and most other engines put it directly under `data`.
+fn extract_value(body: &serde_json::Value, key: &str) -> Option<String> {
+    let data = &body["data"];
+    let field = data["data"].get(key).or_else(|| data.get(key))?;
+    match field {
+        serde_json::Value::String(s) => Some(s.clone()),
+        serde_json::Value::Number(_) | serde_json::Value::Bool(_) => Some(field.to_string()),
+        _ => None,
+    }
+}
+
+fn lock_cache() -> std::sync::MutexGuard<'static, ValueCache> {
+    CACHE
+        .lock()
+        .unwrap_or_else(std::sync::PoisonError::into_inner)
+}
+
+/// Resolved values keyed by reference.
+#[derive(Default)]
+struct ValueCache {
+    entries: HashMap<String, (Instant, String)>,
+}
+
+impl ValueCache {
+    fn get(&mut self, reference: &str, ttl: Duration) -> Option<String> {
+        let (fetched_at, value) = self.entries.get(reference)?;
+        if fetched_at.elapsed() < ttl {
+            return Some(value.clone());
+        }
+        self.entries.remove(reference);
+        None
+    }
+
+    fn insert(&mut self, reference: &str, value: String) {
+        self.entries
+            .insert(reference.to_owned(), (Instant::now(), value));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_reference_splits_path_and_key() {
+        let vref = parse_reference("vault://secret/data/myapp#db_password").unwrap();
+        assert_eq!(
+            vref,
+            VaultRef {
+                path: "secret/data/myapp",
+                key: "db_password",
+            }
+        );
+    }
+
+    #[test]
+    fn parse_reference_rejects_malformed() {
+        for bad in [
+            "secret/data/myapp#key",
+            "vault://secret/data/myapp",
+            "vault://#key",
+            "vault://secret/data/myapp#",
+            "vault://secret/../sys/seal#key",
+            "vault:///secret#key",
+            "vault://secret/data?x=1#key",
+            "vault://secret#a#b",
+        ] {
+            assert!(parse_reference(bad).is_err(), "{bad} should be rejected");
+        }
+    }
+
+    #[test]
+    fn extract_value_reads_kv_v2_and_v1() {
+        let v2 = serde_json::json!({"data": {"data": {"token": "abc"}, "metadata": {}}});
+        assert_eq!(extract_value(&v2, "token").as_deref(), Some("abc"));
+
+        let v1 = serde_json::json!({"data": {"port": 5432}});
+        assert_eq!(extract_value(&v1, "port").as_deref(), Some("5432"));
+
+        assert!(extract_value(&v2, "missing").is_none());
+        let nested = serde_json::json!({"data": {"obj": {"a": 1}}});
+        assert!(extract_value(&nested, "obj").is_none());
+    }
+
+    #[test]
+    fn cache_expires_after_ttl() {
+        let mut cache = ValueCache::default();
+        cache.insert("vault://a#b", "v".into());
+        assert_eq!(
+            cache.get("vault://a#b", Duration::from_mins(1)).as_deref(),
+            Some("v")
+        );
+        assert!(cache.get("vault://a#b", Duration::ZERO).is_none());
+        // Expired entries are evicted.
+        assert!(cache.get("vault://a#b", Duration::from_mins(1)).is_none());
+    }
+
+    #[tokio::test]
+    async fn read_fails_without_configuration() {
+        // CONFIG is never set in unit tests.
+        let err = read("vault://secret/data/x#y").await.unwrap_err();
+        assert!(err.to_string().contains("not configured"));
+    }
+}
@@ -41,6 +41,10 @@ pub enum PlatformEvent {
         environment: String,
         commit_sha: String,
         image_ref: String,
+        /// User behind the update (pipeline trigger, deploy or rollback
+        /// requester), recorded as the release's `deployed_by`.
+        #[serde(default)]
+        triggered_by: Option<Uuid>,
     },
     /// A deployment was requested via the API (manual trigger).
     DeployRequested {
@@ -192,6 +196,7 @@ pub async fn run(state: AppState, cancel: tokio_util::sync::CancellationToken) {
 // Event dispatch
 // ---------------------------------------------------------------------------
 
+#[allow(clippy::too_many_lines)]
 pub async fn handle_event(state: &AppState, payload: &str) -> anyhow::Result<()> {
     let event: PlatformEvent = serde_json::from_str(payload)?;
     tracing::debug!(?event, "handling platform event");
@@ -219,9 +224,23 @@ pub async fn handle_event(state: &AppState, payload: &str) -> anyhow::Result<()>
             environment,
             commit_sha,
             image_ref,
+            triggered_by,
             ..
         } => {
-            handle_ops_repo_updated(state, project_id, &environment, &commit_sha, &image_ref).await
+            if crate::deployer::freeze::refuses_automatic(&state.pool, project_id, &environment)
+                .await?
+            {
+                return Ok(());
+            }
+            handle_ops_repo_updated(
+                state,
+                project_id,
+                &environment,
+                &commit_sha,
+                &image_ref,
+                triggered_by,
+            )
+            .await
         }
         PlatformEvent::DeployRequested {
             project_id,
@@ -403,6 +422,7 @@ async fn handle_ops_repo_updated(
     environment: &str,
     commit_sha: &str,
     image_ref: &str,
+    triggered_by: Option<Uuid>,
 ) -> anyhow::Result<()> {
     // 1. Read platform.yaml from ops repo (for deploy specs + flags)
     let ops_repo = sqlx::query!(
@@ -478,8 +498,9 @@ async fn handle_ops_repo_updated(
 
     // 4. Create release with strategy + rollout_config
     let release_id = sqlx::query_scalar::<_, Uuid>(
-        "INSERT INTO deploy_releases (target_id, project_id, image_ref, commit_sha, strategy, rollout_config)
-         VALUES ($1, $2, $3, $4, COALESCE($5, (SELECT default_strategy FROM deploy_targets WHERE id = $1)), $6)
+        "INSERT INTO deploy_releases (target_id, project_id, image_ref, commit_sha, strategy, rollout_config, requires_approval, deployed_by)
+         SELECT $1, $2, $3, $4, COALESCE($5, dt.default_strategy), $6, dt.requires_approval, $7
+         FROM deploy_targets dt WHERE dt.id = $1
          RETURNING id",
     )
     .bind(target_id)
@@ -488,6 +509,7 @@ async fn handle_ops_repo_updated(
     .bind(commit_sha)
     .bind(&strategy_override)
     .bind(&rollout_config)
+    .bind(triggered_by)
     .fetch_one(&state.pool)
     .await?;
 
@@ -522,7 +544,7 @@ async fn handle_deploy_requested(
     project_id: Uuid,
     environment: &str,
     image_ref: &str,
-    _requested_by: Option<Uuid>,
+    requested_by: Option<Uuid>,
 ) -> anyhow::Result<()> {
     let ops_repo = sqlx::query!(
         "SELECT id, repo_path, branch FROM ops_repos WHERE project_id = $1",
@@ -560,6 +582,7 @@ async fn handle_deploy_requested(
             environment: environment.into(),
             commit_sha,
             image_ref: image_ref.into(),
+            triggered_by: requested_by,
         },
     )
     .await;
@@ -572,7 +595,7 @@ async fn handle_rollback_requested(
     state: &AppState,
     project_id: Uuid,
     environment: &str,
-    _requested_by: Option<Uuid>,
+    requested_by: Option<Uuid>,
 ) -> anyhow::Result<()> {
     let ops_repo = sqlx::query!(
         "SELECT id, repo_path, branch FROM ops_repos WHERE project_id = $1",
@@ -606,6 +629,7 @@ async fn handle_rollback_requested(
             environment: environment.into(),
             commit_sha: new_sha,
             image_ref: old_image,
+            triggered_by: requested_by,
         },
     )
     .await;
@@ -912,6 +936,7 @@ async fn demo_promote_staging_to_prod(state: &AppState, project_id: Uuid) {
                 environment: "production".into(),
                 commit_sha: new_sha,
                 image_ref,
+                triggered_by: None,
             },
         )
         .await;
@@ -1083,6 +1108,7 @@ mod tests {
                 environment: "production".into(),
                 commit_sha: "abc123".into(),
                 image_ref: "img:v1".into(),
+                triggered_by: None,
             },
             PlatformEvent::DeployRequested {
                 project_id: Uuid::nil(),
@@ -1181,6 +1207,7 @@ mod tests {
                     environment: "prod".into(),
                     commit_sha: "abc".into(),
                     image_ref: "img:v1".into(),
+                    triggered_by: None,
                 },
                 "OpsRepoUpdated",
             ),
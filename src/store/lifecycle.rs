@@ -0,0 +1,228 @@
+// Copyright (c) 2026 Steven Hooker. Exclusively licensed to and distributed by AgentSphere GmbH.
+// SPDX-License-Identifier: BUSL-1.1
+
+//! Retention for build logs, artifacts and telemetry archives in `MinIO`.
+//!
+//! With `object_retention_days` set, these objects are written with an
+//! `expires-at` user metadata entry and pruned by [`run`] once older than the
+//! retention window:
+//! - pipeline logs (`logs/pipelines/<pipeline_id>/`) of pipelines that
+//!   finished before the cutoff; their steps' `log_ref` is cleared
+//! - artifacts whose `expires_at` has passed, with their rows
+//! - Parquet archives (`otel/<signal>/<date>/`) for days before the cutoff
+
+use std::time::Duration;
+
+use chrono::{DateTime, NaiveDate, Utc};
+use tracing::Instrument;
+use uuid::Uuid;
+
+use crate::config::Config;
+use crate::store::AppState;
+
+/// User metadata key holding an object's expiry time (RFC 3339).
+pub const EXPIRES_AT_KEY: &str = "expires-at";
+
+/// Objects removed per category per prune pass.
+const PRUNE_BATCH_SIZE: i64 = 500;
+
+/// Parquet archive signals under `otel/`.
+const OTEL_SIGNALS: &[&str] = &["logs", "traces", "metrics"];
+
+/// When an object written now expires, or `None` if retention is disabled.
+pub fn expires_at(config: &Config) -> Option<DateTime<Utc>> {
+    (config.object_retention_days > 0)
+        .then(|| Utc::now() + chrono::Duration::days(i64::from(config.object_retention_days)))
+}
+
+/// Write an object subject to retention, tagged with its expiry time.
+pub async fn write_expiring(state: &AppState, path: &str, bytes: Vec<u8>) -> opendal::Result<()> {
+    let mut write = state.minio.write_with(path, bytes);
+    if let Some(at) = expires_at(&state.config) {
+        write = write.user_metadata([(EXPIRES_AT_KEY.to_owned(), at.to_rfc3339())]);
+    }
+    write.await?;
+    Ok(())
+}
+
+/// Background task: hourly, delete objects past their retention.
+pub async fn run(state: AppState, cancel: tokio_util::sync::CancellationToken) {
+    let mut interval = tokio::time::interval(Duration::from_hours(1));
+    state.task_registry.register("object_retention", 7200);
+    loop {
+        tokio::select! {
+            () = cancel.cancelled() => {
+                tracing::info!("object retention shutting down");
+                break;
+            }
+            _ = interval.tick() => {
+                let iter_trace_id = Uuid::new_v4().to_string().replace('-', "");
+                let span = tracing::info_span!(
+                    "task_iteration",
+                    task_name = "object_retention",
+                    trace_id = %iter_trace_id,
+                    source = "system",
+                );
+                async {
+                    match prune_expired(&state).await {
+                        Ok(_) => state.task_registry.heartbeat("object_retention"),
+                        Err(e) => {
+                            state.task_registry.report_error("object_retention", &e.to_string());
+                            tracing::error!(error = %e, "object retention failed");
+                        }
+                    }
+                }.instrument(span).await;
+            }
+        }
+    }
+}
+
+/// Delete expired pipeline logs, artifacts and Parquet archives. Returns the
+/// number of objects deleted; a no-op when retention is disabled.
+pub async fn prune_expired(state: &AppState) -> anyhow::Result<usize> {
+    let days = state.config.object_retention_days;
+    if days == 0 {
+        return Ok(0);
+    }
+    let cutoff = Utc::now() - chrono::Duration::days(i64::from(days));
+
+    let mut deleted = prune_pipeline_logs(state, cutoff).await?;
+    deleted += prune_artifacts(state).await?;
+    deleted += prune_parquet(state, cutoff.date_naive()).await?;
+    if deleted > 0 {
+        tracing::info!(deleted, retention_days = days, "pruned expired objects");
+    }
+    Ok(deleted)
+}
+
+async fn prune_pipeline_logs(state: &AppState, cutoff: DateTime<Utc>) -> anyhow::Result<usize> {
+    let pipeline_ids = sqlx::query_scalar!(
+        "SELECT p.id FROM pipelines p
+         WHERE p.finished_at < $1
+           AND EXISTS (SELECT 1 FROM pipeline_steps s
+                       WHERE s.pipeline_id = p.id AND s.log_ref IS NOT NULL)
+         LIMIT $2",
+        cutoff,
+        PRUNE_BATCH_SIZE,
+    )
+    .fetch_all(&state.pool)
+    .await?;
+
+    let mut deleted = 0;
+    for pipeline_id in pipeline_ids {
+        deleted += delete_prefix(state, &format!("logs/pipelines/{pipeline_id}/")).await?;
+        sqlx::query!(
+            "UPDATE pipeline_steps SET log_ref = NULL WHERE pipeline_id = $1",
+            pipeline_id
+        )
+        .execute(&state.pool)
+        .await?;
+    }
+    Ok(deleted)
+}
+
+async fn prune_artifacts(state: &AppState) -> anyhow::Result<usize> {
+    let files = sqlx::query!(
+        "SELECT id, minio_path FROM artifacts
+         WHERE expires_at < now() AND NOT is_directory
+         LIMIT $1",
+        PRUNE_BATCH_SIZE
+    )
+    .fetch_all(&state.pool)
+    .await?;
+
+    let mut deleted = 0;
+    for file in files {
+        let path = file.minio_path;
+        match state.minio.delete(&path).await {
+            Ok(()) => deleted += 1,
+            Err(e) => {
+                // Keep the row so the next pass retries
+                tracing::warn!(error = %e, %path, "failed to delete expired artifact");
+                continue;
+            }
+        }
+        sqlx::query!("DELETE FROM artifacts WHERE id = $1", file.id)
+            .execute(&state.pool)
+            .await?;
+    }
+
+    // Directory rows have no object of their own
+    sqlx::query!(
+        "DELETE FROM artifacts a
+         WHERE a.is_directory AND a.expires_at < now()
+           AND NOT EXISTS (SELECT 1 FROM artifacts c WHERE c.parent_id = a.id)",
+    )
+    .execute(&state.pool)
+    .await?;
+    Ok(deleted)
+}
+
+async fn prune_parquet(state: &AppState, cutoff: NaiveDate) -> anyhow::Result<usize> {
+    let mut deleted = 0;
+    for signal in OTEL_SIGNALS {
+        let prefix = format!("otel/{signal}/");
+        let days = match state.minio.list(&prefix).await {
+            Ok(entries) => entries,
+            Err(e) if e.kind() == opendal::ErrorKind::NotFound => continue,
+            Err(e) => return Err(e.into()),
+        };
+        for entry in days {
+            if archive_day(entry.path()).is_some_and(|day| day < cutoff) {
+                deleted += delete_prefix(state, entry.path()).await?;
+            }
+        }
+    }
+    Ok(deleted)
+}
+
+/// The date of an `otel/<signal>/<date>/` directory.
+fn archive_day(path: &str) -> Option<NaiveDate> {
+    let day = path.strip_suffix('/')?.rsplit('/').next()?;
+    NaiveDate::parse_from_str(day, "%Y-%m-%d").ok()
+}
+
+/// Delete every object directly under `prefix`. Returns the number deleted.
+async fn delete_prefix(state: &AppState, prefix: &str) -> anyhow::Result<usize> {
+    let entries = match state.minio.list(prefix).await {
+        Ok(entries) => entries,
+        Err(e) if e.kind() == opendal::ErrorKind::NotFound => return Ok(0),
+        Err(e) => return Err(e.into()),
+    };
+    let mut deleted = 0;
+    for entry in entries {
+        if entry.path().ends_with('/') {
+            continue;
+        }
+        state.minio.delete(entry.path()).await?;
+        deleted += 1;
+    }
+    Ok(deleted)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn archive_day_parses_date_directories() {
+        assert_eq!(
+            archive_day("otel/logs/2026-03-01/"),
+            NaiveDate::from_ymd_opt(2026, 3, 1)
+        );
+        assert_eq!(archive_day("otel/logs/2026-03-01/logs_x.parquet"), None);
+        assert_eq!(archive_day("otel/logs/latest/"), None);
+    }
+
+    #[test]
+    fn expires_at_disabled_by_default() {
+        assert!(expires_at(&Config::test_default()).is_none());
+        let config = Config {
+            object_retention_days: 30,
+            ..Config::test_default()
+        };
+        let at = expires_at(&config).unwrap();
+        let days = (at - Utc::now()).num_days();
+        assert!((29..=30).contains(&days), "{days}");
+    }
+}
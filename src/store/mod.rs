@@ -6,6 +6,7 @@
 pub mod bootstrap;
 pub mod commands_seed;
 pub mod eventbus;
+pub mod lifecycle;
 pub mod pool;
 pub mod valkey;
 
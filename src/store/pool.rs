@@ -3,27 +3,126 @@
 
 use std::time::Duration;
 
-use sqlx::PgPool;
-use sqlx::postgres::PgPoolOptions;
+use log::LevelFilter;
+use serde::Serialize;
+use sqlx::postgres::{PgConnectOptions, PgPoolOptions};
+use sqlx::{ConnectOptions, PgPool};
+use ts_rs::TS;
+
+use crate::config::Config;
+
+/// Pool sizing and statement logging, from the `PLATFORM_DB_*` settings.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PoolSettings {
+    pub max_connections: u32,
+    /// Never above `max_connections`.
+    pub min_connections: u32,
+    pub acquire_timeout_secs: u64,
+    /// 0 keeps idle connections open.
+    pub idle_timeout_secs: u64,
+    /// 0 turns slow statement logging off.
+    pub slow_query_ms: u64,
+}
+
+impl PoolSettings {
+    pub fn from_config(cfg: &Config) -> Self {
+        Self {
+            max_connections: cfg.db_max_connections,
+            min_connections: cfg.db_min_connections.min(cfg.db_max_connections),
+            acquire_timeout_secs: cfg.db_acquire_timeout_secs,
+            idle_timeout_secs: cfg.db_idle_timeout_secs,
+            slow_query_ms: cfg.db_slow_query_ms,
+        }
+    }
+}
+
+/// Embedded migrations. Static, so the migration list isn't built on the stack.
+static MIGRATOR: sqlx::migrate::Migrator = sqlx::migrate!();
 
 #[tracing::instrument(skip(url), err)]
-pub async fn connect(
-    url: &str,
-    max_connections: u32,
-    acquire_timeout_secs: u64,
-) -> anyhow::Result<PgPool> {
+pub async fn connect(url: &str, settings: &PoolSettings) -> anyhow::Result<PgPool> {
+    let mut connect_options: PgConnectOptions = url.parse()?;
+    connect_options = if settings.slow_query_ms > 0 {
+        connect_options.log_slow_statements(
+            LevelFilter::Warn,
+            Duration::from_millis(settings.slow_query_ms),
+        )
+    } else {
+        connect_options.log_slow_statements(LevelFilter::Off, Duration::ZERO)
+    };
+
     let pool = PgPoolOptions::new()
-        .max_connections(max_connections)
-        .acquire_timeout(Duration::from_secs(acquire_timeout_secs))
-        .idle_timeout(Duration::from_secs(300))
-        .max_lifetime(Duration::from_secs(1800)) // 30 min — recycle stale conns
-        .connect(url)
+        .max_connections(settings.max_connections)
+        .min_connections(settings.min_connections)
+        .acquire_timeout(Duration::from_secs(settings.acquire_timeout_secs))
+        .idle_timeout(
+            (settings.idle_timeout_secs > 0)
+                .then(|| Duration::from_secs(settings.idle_timeout_secs)),
+        )
+        .max_lifetime(Duration::from_mins(30)) // recycle stale conns
+        .connect_with(connect_options)
         .await?;
 
     tracing::info!("connected to postgres");
 
-    sqlx::migrate!().run(&pool).await?;
+    MIGRATOR.run(&pool).await?;
     tracing::info!("migrations applied");
 
     Ok(pool)
 }
+
+/// Point-in-time utilization of the `PostgreSQL` pool. sqlx does not expose
+/// the callers waiting for a connection, so there is no pending count.
+#[derive(Debug, Clone, Copy, Default, Serialize, TS)]
+#[ts(export)]
+pub struct DbPoolStats {
+    /// Open connections, idle or in use.
+    pub size: u32,
+    pub idle: u32,
+    /// Checked out by a request or task. Sitting at `max` means callers are
+    /// waiting for a connection.
+    pub in_use: u32,
+    pub min: u32,
+    pub max: u32,
+}
+
+pub fn stats(pool: &PgPool) -> DbPoolStats {
+    let size = pool.size();
+    let idle = u32::try_from(pool.num_idle()).unwrap_or(u32::MAX);
+    let options = pool.options();
+    DbPoolStats {
+        size,
+        idle,
+        in_use: size.saturating_sub(idle),
+        min: options.get_min_connections(),
+        max: options.get_max_connections(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn min_connections_capped_at_max() {
+        let settings = PoolSettings::from_config(&Config {
+            db_max_connections: 50,
+            db_min_connections: 80,
+            ..Config::test_default()
+        });
+        assert_eq!(settings.max_connections, 50);
+        assert_eq!(settings.min_connections, 50);
+    }
+
+    #[tokio::test]
+    async fn stats_of_unopened_pool() {
+        let pool = PgPoolOptions::new()
+            .max_connections(50)
+            .connect_lazy("postgres://localhost/unused")
+            .unwrap();
+        let stats = stats(&pool);
+        assert_eq!(stats.size, 0);
+        assert_eq!(stats.in_use, 0);
+        assert_eq!((stats.min, stats.max), (0, 50));
+    }
+}
@@ -1,7 +1,7 @@
 // Copyright (c) 2026 Steven Hooker. Exclusively licensed to and distributed by AgentSphere GmbH.
 // SPDX-License-Identifier: BUSL-1.1
 
-use std::net::{IpAddr, ToSocketAddrs};
+use std::net::{IpAddr, SocketAddr, ToSocketAddrs};
 
 use crate::error::ApiError;
 
@@ -90,6 +90,19 @@ pub fn check_labels(labels: &[String]) -> Result<(), ApiError> {
     Ok(())
 }
 
+/// Label colors are `#rrggbb` hex.
+pub fn check_color(value: &str) -> Result<(), ApiError> {
+    let valid = value.len() == 7
+        && value.starts_with('#')
+        && value[1..].chars().all(|c| c.is_ascii_hexdigit());
+    if !valid {
+        return Err(ApiError::BadRequest(
+            "color must be a hex color like #1f883d".into(),
+        ));
+    }
+    Ok(())
+}
+
 pub fn check_lfs_oid(oid: &str) -> Result<(), ApiError> {
     if oid.len() != 64 || !oid.chars().all(|c| c.is_ascii_hexdigit()) {
         return Err(ApiError::BadRequest(
@@ -99,6 +112,100 @@ pub fn check_lfs_oid(oid: &str) -> Result<(), ApiError> {
     Ok(())
 }
 
+/// Password rules configured via `PLATFORM_PASSWORD_*` (see `Config::password_policy`).
+#[derive(Debug, Clone)]
+pub struct PasswordPolicy {
+    pub min_length: usize,
+    pub require_mixed_case: bool,
+    pub require_digit: bool,
+    pub require_symbol: bool,
+    /// Extra rejected passwords, on top of `COMMON_PASSWORDS`.
+    pub denylist: Vec<String>,
+}
+
+impl Default for PasswordPolicy {
+    fn default() -> Self {
+        Self {
+            min_length: 8,
+            require_mixed_case: false,
+            require_digit: false,
+            require_symbol: false,
+            denylist: Vec::new(),
+        }
+    }
+}
+
+/// Passwords that are always rejected (compared case-insensitively).
+const COMMON_PASSWORDS: &[&str] = &[
+    "123456789",
+    "12345678",
+    "1234567890",
+    "1q2w3e4r",
+    "abc12345",
+    "abcd1234",
+    "admin123",
+    "administrator",
+    "baseball",
+    "changeme",
+    "dragon123",
+    "football",
+    "iloveyou",
+    "letmein1",
+    "letmein123",
+    "monkey123",
+    "passw0rd",
+    "password",
+    "password1",
+    "password12",
+    "password123",
+    "password1234",
+    "p@ssw0rd",
+    "qwerty123",
+    "qwertyuiop",
+    "sunshine",
+    "superman",
+    "trustno1",
+    "welcome1",
+    "welcome123",
+];
+
+/// Check a new password against `policy`. All failed rules are listed in a
+/// single `BadRequest` so users can fix them in one go.
+pub fn check_password(password: &str, policy: &PasswordPolicy) -> Result<(), ApiError> {
+    check_length("password", password, 1, 1024)?;
+
+    let mut failed = Vec::new();
+    if password.chars().count() < policy.min_length {
+        failed.push(format!("at least {} characters", policy.min_length));
+    }
+    if policy.require_mixed_case
+        && !(password.chars().any(char::is_uppercase) && password.chars().any(char::is_lowercase))
+    {
+        failed.push("both upper- and lowercase letters".to_owned());
+    }
+    if policy.require_digit && !password.chars().any(|c| c.is_ascii_digit()) {
+        failed.push("a digit".to_owned());
+    }
+    if policy.require_symbol && password.chars().all(char::is_alphanumeric) {
+        failed.push("a symbol".to_owned());
+    }
+    let lower = password.to_lowercase();
+    if COMMON_PASSWORDS.contains(&lower.as_str())
+        || policy.denylist.iter().any(|d| d.to_lowercase() == lower)
+    {
+        failed.push("not a commonly used password".to_owned());
+    }
+
+    if failed.is_empty() {
+        Ok(())
+    } else {
+        Err(ApiError::BadRequest(format!(
+            "password does not meet requirements: {}",
+            failed.join(", ")
+        )))
+    }
+}
+
 /// Check whether an IPv6 address is in the unique-local range (`fc00::/7`).
 fn is_ipv6_unique_local(v6: &std::net::Ipv6Addr) -> bool {
     (v6.segments()[0] & 0xfe00) == 0xfc00
@@ -176,9 +283,9 @@ pub fn check_ssrf_url(url_str: &str, allowed_schemes: &[&str]) -> Result<(), Api
     // S77: DNS rebinding mitigation — resolve hostname to IPs and verify
     // none are private. This catches hostnames that resolve to internal IPs
     // (e.g., custom DNS entries pointing to 10.x or 192.168.x).
-    // Note: this is best-effort. True time-of-check/time-of-use rebinding
-    // (where DNS changes between validation and request) requires pinning
-    // the resolved IP for the HTTP request, which is a separate concern.
+    // Note: this is best-effort. Time-of-check/time-of-use rebinding (DNS
+    // changing between validation and request) is closed at request time by
+    // `resolve_for_connect`, which callers use to pin the connection.
     if let Ok(addrs) = (host, 0u16).to_socket_addrs() {
         for addr in addrs {
             if is_private_ip(addr.ip()) {
@@ -194,6 +301,143 @@ pub fn check_ssrf_url(url_str: &str, allowed_schemes: &[&str]) -> Result<(), Api
     Ok(())
 }
 
+/// Resolve `host` for an outbound connection that is about to be made.
+///
+/// `check_ssrf_url` runs when a URL is saved, but the name can later be
+/// re-pointed at an internal address (DNS rebinding). Callers resolve here
+/// right before connecting and pin the connection to the returned addresses.
+/// `allow_private` is for dev mode only.
+pub async fn resolve_for_connect(
+    host: url::Host<&str>,
+    port: u16,
+    allow_private: bool,
+) -> Result<Vec<SocketAddr>, ApiError> {
+    let addrs: Vec<SocketAddr> = match host {
+        url::Host::Domain(name) => tokio::net::lookup_host((name, port))
+            .await
+            .map_err(|e| ApiError::BadRequest(format!("could not resolve {name}: {e}")))?
+            .collect(),
+        url::Host::Ipv4(ip) => vec![SocketAddr::new(ip.into(), port)],
+        url::Host::Ipv6(ip) => vec![SocketAddr::new(ip.into(), port)],
+    };
+    if addrs.is_empty() {
+        return Err(ApiError::BadRequest(format!("{host} did not resolve")));
+    }
+    if !allow_private && addrs.iter().any(|a| is_private_ip(a.ip())) {
+        return Err(ApiError::BadRequest(
+            "URL hostname resolves to a private/reserved IP address".into(),
+        ));
+    }
+    Ok(addrs)
+}
+
+/// Destinations outbound webhooks may reach (`PLATFORM_WEBHOOK_ALLOWLIST`).
+///
+/// Entries are exact hostnames (`hooks.example.com`), subdomain wildcards
+/// (`*.example.com`, which does not cover `example.com` itself), or networks
+/// in CIDR notation (`203.0.113.0/24`; a bare address is a single host).
+/// A hostname that is not listed by name passes only when every address it
+/// resolves to falls inside a listed network.
+#[derive(Debug, Clone, Default)]
+pub struct EgressAllowlist {
+    hosts: Vec<String>,
+    /// Wildcard suffixes, stored with their leading dot.
+    suffixes: Vec<String>,
+    networks: Vec<ipnetwork::IpNetwork>,
+}
+
+impl EgressAllowlist {
+    /// Parse configured entries; any invalid entry fails the whole list.
+    pub fn parse(entries: &[String]) -> Result<Self, String> {
+        let mut list = Self::default();
+        for entry in entries {
+            let entry = entry.trim().trim_end_matches('.').to_ascii_lowercase();
+            if entry.is_empty() {
+                continue;
+            }
+            if let Ok(network) = entry.parse::<ipnetwork::IpNetwork>() {
+                list.networks.push(network);
+                continue;
+            }
+            match entry.strip_prefix("*.") {
+                Some(suffix) if is_dns_name(suffix) && suffix.contains('.') => {
+                    list.suffixes.push(format!(".{suffix}"));
+                }
+                None if is_dns_name(&entry) => list.hosts.push(entry),
+                _ => return Err(format!("invalid egress allowlist entry: {entry}")),
+            }
+        }
+        Ok(list)
+    }
+
+    /// An empty list places no restriction on destinations.
+    pub fn is_empty(&self) -> bool {
+        self.hosts.is_empty() && self.suffixes.is_empty() && self.networks.is_empty()
+    }
+
+    /// Whether `host` is listed by name, exactly or through a wildcard.
+    pub fn allows_name(&self, host: &str) -> bool {
+        let host = host.trim_end_matches('.').to_ascii_lowercase();
+        self.hosts.contains(&host) || self.suffixes.iter().any(|s| host.ends_with(s.as_str()))
+    }
+
+    /// Whether `ip` falls inside a listed network.
+    pub fn allows_ip(&self, ip: IpAddr) -> bool {
+        let ip = ip.to_canonical();
+        self.networks.iter().any(|n| n.contains(ip))
+    }
+
+    /// Check a destination given its host and the addresses it resolves to.
+    pub fn check(&self, host: &str, addrs: &[IpAddr]) -> Result<(), ApiError> {
+        if self.is_empty()
+            || self.allows_name(host)
+            || (!addrs.is_empty() && addrs.iter().all(|ip| self.allows_ip(*ip)))
+        {
+            Ok(())
+        } else {
+            Err(ApiError::BadRequest(format!(
+                "destination {host} is not on the egress allowlist"
+            )))
+        }
+    }
+}
+
+/// Lowercase DNS name: dot-separated labels of letters, digits and inner hyphens.
+fn is_dns_name(name: &str) -> bool {
+    name.len() <= 253
+        && name.split('.').all(|label| {
+            !label.is_empty()
+                && label.len() <= 63
+                && !label.starts_with('-')
+                && !label.ends_with('-')
+                && label
+                    .bytes()
+                    .all(|b| b.is_ascii_lowercase() || b.is_ascii_digit() || b == b'-')
+        })
+}
+
+/// Check the host of `url_str` against `allowlist`, resolving it when it is
+/// not listed by name. Unresolvable hostnames are rejected.
+pub fn check_egress_url(url_str: &str, allowlist: &EgressAllowlist) -> Result<(), ApiError> {
+    if allowlist.is_empty() {
+        return Ok(());
+    }
+    let parsed =
+        url::Url::parse(url_str).map_err(|_| ApiError::BadRequest("invalid URL".into()))?;
+    let addrs: Vec<IpAddr> = match parsed.host() {
+        Some(url::Host::Ipv4(ip)) => vec![ip.into()],
+        Some(url::Host::Ipv6(ip)) => vec![ip.into()],
+        Some(url::Host::Domain(name)) if !allowlist.allows_name(name) => (name, 0u16)
+            .to_socket_addrs()
+            .map(|addrs| addrs.map(|a| a.ip()).collect())
+            .unwrap_or_default(),
+        Some(url::Host::Domain(_)) => Vec::new(),
+        None => return Err(ApiError::BadRequest("URL must have a host".into())),
+    };
+    let host = parsed.host_str().unwrap_or_default();
+    allowlist.check(host, &addrs)
+}
+
 /// Validates a container image reference.
 ///
 /// Accepts: `registry/image:tag`, `image:tag`, `image@sha256:abc...`,
@@ -340,6 +584,60 @@ mod tests {
     use super::*;
     use rstest::rstest;
 
+    // -----------------------------------------------------------------------
+    // check_password
+    // -----------------------------------------------------------------------
+
+    fn strict_policy() -> PasswordPolicy {
+        PasswordPolicy {
+            min_length: 12,
+            require_mixed_case: true,
+            require_digit: true,
+            require_symbol: true,
+            denylist: vec!["Platform2026!".into()],
+        }
+    }
+
+    #[test]
+    fn password_default_policy_rejects_common() {
+        let err = check_password("Password123", &PasswordPolicy::default()).unwrap_err();
+        assert!(
+            matches!(err, ApiError::BadRequest(ref msg) if msg.contains("commonly used")),
+            "got: {err:?}"
+        );
+        assert!(check_password("testpass123", &PasswordPolicy::default()).is_ok());
+    }
+
+    #[test]
+    fn password_names_every_failed_rule() {
+        let err = check_password("password123", &strict_policy()).unwrap_err();
+        let ApiError::BadRequest(msg) = err else {
+            panic!("expected BadRequest, got {err:?}");
+        };
+        for rule in [
+            "at least 12 characters",
+            "upper- and lowercase",
+            "a symbol",
+            "commonly used",
+        ] {
+            assert!(msg.contains(rule), "missing '{rule}' in: {msg}");
+        }
+        assert!(!msg.contains("a digit"), "digit rule passed: {msg}");
+    }
+
+    #[test]
+    fn password_custom_denylist_case_insensitive() {
+        assert!(check_password("platform2026!", &strict_policy()).is_err());
+        assert!(check_password("Correct-Horse-42", &strict_policy()).is_ok());
+    }
+
+    #[test]
+    fn password_length_bounds() {
+        assert!(check_password("short", &PasswordPolicy::default()).is_err());
+        assert!(check_password(&"a".repeat(1025), &PasswordPolicy::default()).is_err());
+        assert!(check_password("", &PasswordPolicy::default()).is_err());
+    }
+
     // -----------------------------------------------------------------------
     // check_name — boundary & edge-case tests
     // -----------------------------------------------------------------------
@@ -646,6 +944,23 @@ mod tests {
         );
     }
 
+    // -----------------------------------------------------------------------
+    // check_color
+    // -----------------------------------------------------------------------
+
+    #[test]
+    fn color_hex_ok() {
+        assert!(check_color("#1f883d").is_ok());
+        assert!(check_color("#ABCDEF").is_ok());
+    }
+
+    #[test]
+    fn color_invalid_rejected() {
+        for bad in ["", "1f883d", "#1f883", "#1f883dd", "#gggggg", "red"] {
+            assert!(check_color(bad).is_err(), "{bad:?} should be rejected");
+        }
+    }
+
     // -----------------------------------------------------------------------
     // check_url — boundary & edge-case tests
     // -----------------------------------------------------------------------
@@ -1214,6 +1529,96 @@ mod tests {
         assert!(check_ssrf_url("not a url at all", &["http", "https"]).is_err());
     }
 
+    // -----------------------------------------------------------------------
+    // resolve_for_connect
+    // -----------------------------------------------------------------------
+
+    #[tokio::test]
+    async fn resolve_for_connect_rejects_names_resolving_to_loopback() {
+        // A name that passed validation but now points at 127.0.0.1
+        let err = resolve_for_connect(url::Host::Domain("localhost"), 80, false)
+            .await
+            .unwrap_err();
+        assert!(
+            matches!(err, ApiError::BadRequest(ref msg) if msg.contains("private/reserved")),
+            "got: {err:?}"
+        );
+
+        let addrs = resolve_for_connect(url::Host::Domain("localhost"), 8080, true)
+            .await
+            .unwrap();
+        assert!(
+            addrs
+                .iter()
+                .all(|a| a.ip().is_loopback() && a.port() == 8080)
+        );
+    }
+
+    #[tokio::test]
+    async fn resolve_for_connect_checks_ip_literals() {
+        let loopback = url::Host::Ipv4(std::net::Ipv4Addr::LOCALHOST);
+        assert!(resolve_for_connect(loopback, 443, false).await.is_err());
+
+        let public = url::Host::Ipv4(std::net::Ipv4Addr::new(93, 184, 216, 34));
+        let addrs = resolve_for_connect(public, 443, false).await.unwrap();
+        assert_eq!(addrs, vec!["93.184.216.34:443".parse().unwrap()]);
+    }
+
+    // -----------------------------------------------------------------------
+    // EgressAllowlist
+    // -----------------------------------------------------------------------
+
+    fn allowlist(entries: &[&str]) -> EgressAllowlist {
+        EgressAllowlist::parse(&entries.iter().map(|e| (*e).to_owned()).collect::<Vec<_>>())
+            .unwrap()
+    }
+
+    #[test]
+    fn egress_allowlist_matches_hosts_and_wildcards() {
+        let list = allowlist(&["hooks.example.com", "*.partner.example"]);
+        assert!(list.allows_name("hooks.example.com"));
+        assert!(list.allows_name("Hooks.Example.com."));
+        assert!(list.allows_name("a.b.partner.example"));
+        assert!(!list.allows_name("partner.example"));
+        assert!(!list.allows_name("evilpartner.example"));
+        assert!(!list.allows_name("hooks.example.com.evil.com"));
+    }
+
+    #[test]
+    fn egress_allowlist_requires_every_address_in_a_network() {
+        let list = allowlist(&["203.0.113.0/24", "2001:db8::1"]);
+        let ip = |s: &str| s.parse::<IpAddr>().unwrap();
+        assert!(list.check("a.example", &[ip("203.0.113.7")]).is_ok());
+        assert!(list.check("a.example", &[ip("::ffff:203.0.113.7")]).is_ok());
+        assert!(list.check("a.example", &[ip("2001:db8::1")]).is_ok());
+        assert!(
+            list.check("a.example", &[ip("203.0.113.7"), ip("198.51.100.1")])
+                .is_err()
+        );
+        assert!(list.check("a.example", &[]).is_err());
+    }
+
+    #[test]
+    fn egress_allowlist_rejects_public_ip_not_listed() {
+        let list = allowlist(&["hooks.example.com"]);
+        assert!(check_egress_url("https://hooks.example.com/x", &list).is_ok());
+        assert!(check_egress_url("https://93.184.216.34/x", &list).is_err());
+        assert!(check_egress_url("https://[2606:2800:220:1::1]/x", &list).is_err());
+        assert!(check_egress_url("https://93.184.216.34/x", &EgressAllowlist::default()).is_ok());
+    }
+
+    #[rstest]
+    #[case("*")]
+    #[case("*.com")]
+    #[case("foo.*.example.com")]
+    #[case("https://hooks.example.com")]
+    #[case("hooks.example.com:443")]
+    #[case("-bad.example.com")]
+    #[case("10.0.0.0/33")]
+    fn egress_allowlist_rejects_invalid_entries(#[case] entry: &str) {
+        assert!(EgressAllowlist::parse(&[entry.to_owned()]).is_err());
+    }
+
     // -----------------------------------------------------------------------
     // check_pipeline_image — tests
     // -----------------------------------------------------------------------
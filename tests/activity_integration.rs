@@ -0,0 +1,135 @@
+// Copyright (c) 2026 Steven Hooker. Exclusively licensed to and distributed by AgentSphere GmbH.
+// SPDX-License-Identifier: BUSL-1.1
+
+//! Integration tests for the project activity feed.
+
+mod helpers;
+
+use axum::http::StatusCode;
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use helpers::{admin_user_id, create_project, create_user, get_json, test_router, test_state};
+
+#[sqlx::test(migrations = "./migrations")]
+async fn activity_feed_merges_sources_in_order(pool: PgPool) {
+    let (state, admin_token) = test_state(pool.clone()).await;
+    let app = test_router(state);
+    let admin_id = admin_user_id(&pool).await;
+    let project_id = create_project(&app, &admin_token, "activity", "private").await;
+
+    let (status, issue) = helpers::post_json(
+        &app,
+        &admin_token,
+        &format!("/api/projects/{project_id}/issues"),
+        serde_json::json!({ "title": "Login is broken" }),
+    )
+    .await;
+    assert_eq!(status, StatusCode::CREATED, "{issue}");
+    assert_eq!(
+        helpers::wait_for_audit(&pool, "issue.create", 2000).await,
+        1
+    );
+
+    // Later events, timestamped explicitly so the order is deterministic
+    let pipeline_id = Uuid::new_v4();
+    sqlx::query(
+        "INSERT INTO pipelines (id, project_id, trigger, git_ref, status, triggered_by, finished_at)
+         VALUES ($1, $2, 'push', 'refs/heads/main', 'success', $3, now() + interval '1 minute')",
+    )
+    .bind(pipeline_id)
+    .bind(project_id)
+    .bind(admin_id)
+    .execute(&pool)
+    .await
+    .unwrap();
+
+    let target_id = Uuid::new_v4();
+    sqlx::query(
+        "INSERT INTO deploy_targets (id, project_id, name, environment)
+         VALUES ($1, $2, 'staging', 'staging')",
+    )
+    .bind(target_id)
+    .bind(project_id)
+    .execute(&pool)
+    .await
+    .unwrap();
+    let release_id = Uuid::new_v4();
+    sqlx::query(
+        "INSERT INTO deploy_releases (id, target_id, project_id, image_ref, phase, deployed_by, created_at)
+         VALUES ($1, $2, $3, 'app:v2', 'completed', $4, now() + interval '2 minutes')",
+    )
+    .bind(release_id)
+    .bind(target_id)
+    .bind(project_id)
+    .bind(admin_id)
+    .execute(&pool)
+    .await
+    .unwrap();
+
+    // Non-collaboration audit entries stay out of the feed
+    sqlx::query(
+        "INSERT INTO audit_log (actor_id, actor_name, action, resource, project_id)
+         VALUES ($1, 'admin', 'secret.create', 'secret', $2)",
+    )
+    .bind(admin_id)
+    .bind(project_id)
+    .execute(&pool)
+    .await
+    .unwrap();
+
+    let (status, body) = get_json(
+        &app,
+        &admin_token,
+        &format!("/api/projects/{project_id}/activity"),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK, "{body}");
+    assert_eq!(body["total"], 3, "{body}");
+    let items = body["items"].as_array().unwrap();
+
+    assert_eq!(items[0]["kind"], "deployment");
+    assert_eq!(items[0]["action"], "completed");
+    assert_eq!(items[0]["actor_name"], "admin");
+    assert_eq!(items[0]["resource_id"], release_id.to_string());
+    assert_eq!(items[0]["detail"]["environment"], "staging");
+    assert_eq!(items[0]["detail"]["image_ref"], "app:v2");
+
+    assert_eq!(items[1]["kind"], "pipeline");
+    assert_eq!(items[1]["action"], "success");
+    assert_eq!(items[1]["resource_id"], pipeline_id.to_string());
+    assert_eq!(items[1]["detail"]["git_ref"], "refs/heads/main");
+
+    assert_eq!(items[2]["kind"], "issue");
+    assert_eq!(items[2]["action"], "issue.create");
+    assert_eq!(items[2]["resource_id"], issue["id"]);
+    assert_eq!(items[2]["detail"]["number"], issue["number"]);
+    assert_eq!(items[2]["detail"]["title"], "Login is broken");
+
+    // Paging walks the same order
+    let (_, page) = get_json(
+        &app,
+        &admin_token,
+        &format!("/api/projects/{project_id}/activity?limit=1&offset=1"),
+    )
+    .await;
+    assert_eq!(page["total"], 3);
+    assert_eq!(page["items"].as_array().unwrap().len(), 1);
+    assert_eq!(page["items"][0]["kind"], "pipeline");
+}
+
+#[sqlx::test(migrations = "./migrations")]
+async fn activity_feed_requires_project_read(pool: PgPool) {
+    let (state, admin_token) = test_state(pool).await;
+    let app = test_router(state);
+    let project_id = create_project(&app, &admin_token, "activity-private", "private").await;
+    let (_, token) = create_user(&app, &admin_token, "outsider", "outsider@example.com").await;
+
+    let (status, _) = get_json(
+        &app,
+        &token,
+        &format!("/api/projects/{project_id}/activity"),
+    )
+    .await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+}
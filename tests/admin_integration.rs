@@ -932,3 +932,121 @@ async fn duplicate_global_role_returns_conflict(pool: PgPool) {
         "duplicate global role should be rejected"
     );
 }
+
+// ---------------------------------------------------------------------------
+// Bulk user import
+// ---------------------------------------------------------------------------
+
+#[sqlx::test(migrations = "./migrations")]
+async fn import_users_reports_failed_rows(pool: PgPool) {
+    let (state, admin_token) = helpers::test_state(pool.clone()).await;
+    let app = helpers::test_router(state);
+
+    let mut users: Vec<serde_json::Value> = (1..=50)
+        .map(|i| json!({ "name": format!("import-{i}"), "email": format!("import-{i}@example.com") }))
+        .collect();
+    users[0]["roles"] = json!(["developer"]);
+    users[4] = json!({ "name": "bad-email", "email": "not-an-email" });
+    users[9] = json!({ "name": "admin", "email": "someone@example.com" });
+    users[19] = json!({ "name": "wizard", "email": "wizard@example.com", "roles": ["archmage"] });
+    users[29] = json!({ "name": "import-1", "email": "dup@example.com" });
+
+    let (status, body) = helpers::post_json(
+        &app,
+        &admin_token,
+        "/api/admin/users/import",
+        json!({ "users": users }),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK, "{body}");
+    let created = body["created"].as_array().unwrap();
+    let failed = body["failed"].as_array().unwrap();
+    assert_eq!(created.len(), 46);
+    let failed_rows: Vec<u64> = failed.iter().map(|f| f["row"].as_u64().unwrap()).collect();
+    assert_eq!(failed_rows, vec![5, 10, 20, 30]);
+    assert!(
+        failed[1]["error"]
+            .as_str()
+            .unwrap()
+            .contains("already exists")
+    );
+    assert!(failed[2]["error"].as_str().unwrap().contains("archmage"));
+
+    // Temporary password works and roles were assigned
+    let first = &created[0];
+    assert_eq!(first["row"], 1);
+    let (status, login) = helpers::post_json(
+        &app,
+        "",
+        "/api/auth/login",
+        json!({ "name": "import-1", "password": first["temporary_password"] }),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK, "{login}");
+    let user_id: Uuid = first["id"].as_str().unwrap().parse().unwrap();
+    let roles: Vec<String> = sqlx::query_scalar(
+        "SELECT r.name FROM user_roles ur JOIN roles r ON r.id = ur.role_id WHERE ur.user_id = $1",
+    )
+    .bind(user_id)
+    .fetch_all(&pool)
+    .await
+    .unwrap();
+    assert_eq!(roles, vec!["developer"]);
+
+    assert!(helpers::wait_for_audit(&pool, "user.create", 2000).await >= 46);
+}
+
+#[sqlx::test(migrations = "./migrations")]
+async fn import_users_from_csv(pool: PgPool) {
+    use axum::body::Body;
+    use axum::http::Request;
+    use http_body_util::BodyExt;
+    use tower::ServiceExt;
+
+    let (state, admin_token) = helpers::test_state(pool).await;
+    let app = helpers::test_router(state);
+
+    let csv = "name,email,display_name,roles\n\
+               csv-alice,alice@example.com,\"Smith, Alice\",developer;viewer\n\
+               csv-bob,bob@example.com,,\n\
+               csv-carol,carol@example.com\n";
+    let req = Request::builder()
+        .method("POST")
+        .uri("/api/admin/users/import")
+        .header("Authorization", format!("Bearer {admin_token}"))
+        .header("Content-Type", "text/csv")
+        .body(Body::from(csv))
+        .unwrap();
+    let resp = app.clone().oneshot(req).await.unwrap();
+    assert_eq!(resp.status(), StatusCode::OK);
+    let body: serde_json::Value =
+        serde_json::from_slice(&resp.into_body().collect().await.unwrap().to_bytes()).unwrap();
+    assert_eq!(body["created"].as_array().unwrap().len(), 2);
+    assert_eq!(body["failed"][0]["row"], 3);
+
+    let (status, user) = helpers::get_json(
+        &app,
+        &admin_token,
+        &format!("/api/users/{}", body["created"][0]["id"].as_str().unwrap()),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(user["display_name"], "Smith, Alice");
+}
+
+#[sqlx::test(migrations = "./migrations")]
+async fn import_users_requires_admin(pool: PgPool) {
+    let (state, admin_token) = helpers::test_state(pool).await;
+    let app = helpers::test_router(state);
+    let (_, user_token) =
+        helpers::create_user(&app, &admin_token, "plain-user", "plain@example.com").await;
+
+    let (status, _) = helpers::post_json(
+        &app,
+        &user_token,
+        "/api/admin/users/import",
+        json!({ "users": [{ "name": "sneaky", "email": "sneaky@example.com" }] }),
+    )
+    .await;
+    assert_eq!(status, StatusCode::FORBIDDEN);
+}
@@ -1,7 +1,7 @@
 // Copyright (c) 2026 Steven Hooker. Exclusively licensed to and distributed by AgentSphere GmbH.
 // SPDX-License-Identifier: BUSL-1.1
 
-//! Integration tests for alert evaluation — `evaluate_metric`, `fire_alert`, `resolve_alert`, `evaluate_all`.
+//! Integration tests for alert evaluation — `evaluate_metric`, `open_alert_event`, `resolve_alert`, `evaluate_all`.
 
 mod helpers;
 
@@ -9,7 +9,9 @@ use axum::http::StatusCode;
 use chrono::Utc;
 use sqlx::PgPool;
 use std::collections::HashMap;
+use std::net::IpAddr;
 use uuid::Uuid;
+use wiremock::{Mock, MockServer, ResponseTemplate, matchers};
 
 use helpers::{test_router, test_state};
 
@@ -176,7 +178,7 @@ async fn evaluate_metric_unknown_agg_returns_none(pool: PgPool) {
 }
 
 // ---------------------------------------------------------------------------
-// fire_alert / resolve_alert
+// open_alert_event / resolve_alert
 // ---------------------------------------------------------------------------
 
 #[sqlx::test(migrations = "./migrations")]
@@ -192,7 +194,7 @@ async fn fire_and_resolve_alert(pool: PgPool) {
     .await;
 
     // Fire
-    platform::observe::alert::fire_alert(&pool, rule_id, Some(75.0))
+    platform::observe::alert::open_alert_event(&pool, rule_id, Some(75.0), false)
         .await
         .unwrap();
 
@@ -233,6 +235,45 @@ async fn resolve_alert_without_firing_is_noop(pool: PgPool) {
     assert_eq!(count_alert_events(&pool, rule_id).await, 0);
 }
 
+#[sqlx::test(migrations = "./migrations")]
+async fn open_alert_event_deduplicates_open_incident(pool: PgPool) {
+    use platform::observe::alert::{open_alert_event, resolve_alert};
+
+    let rule_id = insert_alert_rule(
+        &pool,
+        "dedup-test",
+        "metric:cpu agg:avg window:60",
+        "gt",
+        Some(50.0),
+        10,
+    )
+    .await;
+
+    // Only the first firing of an open incident is recorded (and notified)
+    assert!(
+        open_alert_event(&pool, rule_id, Some(75.0), false)
+            .await
+            .unwrap()
+    );
+    assert!(
+        !open_alert_event(&pool, rule_id, Some(80.0), false)
+            .await
+            .unwrap()
+    );
+    assert_eq!(count_alert_events(&pool, rule_id).await, 1);
+
+    assert!(resolve_alert(&pool, rule_id).await.unwrap());
+    assert!(!resolve_alert(&pool, rule_id).await.unwrap());
+
+    // A new incident after resolution fires again
+    assert!(
+        open_alert_event(&pool, rule_id, Some(90.0), false)
+            .await
+            .unwrap()
+    );
+    assert_eq!(count_alert_events(&pool, rule_id).await, 2);
+}
+
 // ---------------------------------------------------------------------------
 // evaluate_all (full cycle)
 // ---------------------------------------------------------------------------
@@ -294,7 +335,7 @@ async fn evaluate_all_resolves_when_below_threshold(pool: PgPool) {
     .await;
 
     // Simulate a firing state
-    platform::observe::alert::fire_alert(&pool, rule_id, Some(95.0))
+    platform::observe::alert::open_alert_event(&pool, rule_id, Some(95.0), false)
         .await
         .unwrap();
 
@@ -308,6 +349,7 @@ async fn evaluate_all_resolves_when_below_threshold(pool: PgPool) {
         platform::observe::alert::AlertState {
             first_triggered: Some(Utc::now() - chrono::Duration::seconds(60)),
             firing: true,
+            inhibited: false,
         },
     );
 
@@ -576,7 +618,7 @@ async fn alert_events_with_data(pool: PgPool) {
     .await;
 
     // Insert a firing event
-    platform::observe::alert::fire_alert(&pool, rule_id, Some(75.0))
+    platform::observe::alert::open_alert_event(&pool, rule_id, Some(75.0), false)
         .await
         .unwrap();
 
@@ -597,3 +639,265 @@ async fn alert_events_with_data(pool: PgPool) {
     assert_eq!(status, StatusCode::OK);
     assert!(body["total"].as_i64().unwrap() >= 1);
 }
+
+#[sqlx::test(migrations = "./migrations")]
+async fn create_alert_validates_notify_channels(pool: PgPool) {
+    let (state, admin_token) = test_state(pool.clone()).await;
+    let app = test_router(state);
+
+    let body = |channels: serde_json::Value| {
+        serde_json::json!({
+            "name": "routed",
+            "query": "metric:cpu agg:avg window:60",
+            "condition": "gt",
+            "threshold": 50.0,
+            "notify_channels": channels,
+        })
+    };
+
+    let (status, created) = helpers::post_json(
+        &app,
+        &admin_token,
+        "/api/observe/alerts",
+        body(serde_json::json!([
+            "email:oncall@example.com",
+            "webhook:https://hooks.example.com/alert"
+        ])),
+    )
+    .await;
+    assert_eq!(status, StatusCode::CREATED, "{created}");
+    assert_eq!(created["channels"].as_array().unwrap().len(), 2);
+
+    for bad in [
+        serde_json::json!(["oncall@example.com"]),
+        serde_json::json!(["email:not-an-email"]),
+        serde_json::json!(["webhook:http://localhost/hook"]),
+    ] {
+        let (status, _) =
+            helpers::post_json(&app, &admin_token, "/api/observe/alerts", body(bad.clone())).await;
+        assert_eq!(status, StatusCode::BAD_REQUEST, "{bad}");
+    }
+
+    let id = created["id"].as_str().unwrap();
+    let (status, _) = helpers::patch_json(
+        &app,
+        &admin_token,
+        &format!("/api/observe/alerts/{id}"),
+        serde_json::json!({"notify_channels": ["sms:+15550100"]}),
+    )
+    .await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+}
+
+/// A name other than `localhost` that `/etc/hosts` maps to a loopback
+/// address (e.g. `ip6-localhost`), with that address.
+fn loopback_alias() -> (String, IpAddr) {
+    let hosts = std::fs::read_to_string("/etc/hosts").expect("read /etc/hosts");
+    hosts
+        .lines()
+        .filter_map(|line| {
+            let mut fields = line.split('#').next()?.split_whitespace();
+            let ip: IpAddr = fields.next()?.parse().ok()?;
+            let alias = fields.find(|name| *name != "localhost")?;
+            ip.is_loopback().then(|| (alias.to_owned(), ip))
+        })
+        .next()
+        .expect("/etc/hosts has no loopback alias besides localhost")
+}
+
+/// Fire a rule whose only channel is `webhook:<url>` and give the background
+/// delivery time to run.
+async fn fire_rule_with_webhook(state: &platform::store::AppState, name: &str, url: &str) {
+    let rule_id = insert_alert_rule(
+        &state.pool,
+        name,
+        "metric:cpu_webhook_test agg:avg window:300",
+        "gt",
+        Some(80.0),
+        10,
+    )
+    .await;
+    sqlx::query("UPDATE alert_rules SET notify_channels = $2 WHERE id = $1")
+        .bind(rule_id)
+        .bind(vec![format!("webhook:{url}")])
+        .execute(&state.pool)
+        .await
+        .unwrap();
+    insert_metric(&state.pool, "cpu_webhook_test", 95.0).await;
+
+    let mut alert_states = HashMap::new();
+    platform::observe::alert::evaluate_all(state, &mut alert_states)
+        .await
+        .unwrap();
+    if let Some(s) = alert_states.get_mut(&rule_id) {
+        s.first_triggered = Some(Utc::now() - chrono::Duration::seconds(60));
+    }
+    platform::observe::alert::evaluate_all(state, &mut alert_states)
+        .await
+        .unwrap();
+    assert_eq!(
+        latest_event_status(&state.pool, rule_id).await.as_deref(),
+        Some("firing")
+    );
+    tokio::time::sleep(std::time::Duration::from_millis(500)).await;
+}
+
+/// A webhook channel whose hostname resolves to loopback passes the
+/// save-time URL check, so delivery must resolve the host itself and refuse
+/// the private address. Dev mode reaches the same receiver, which shows the
+/// name and port are otherwise deliverable.
+#[sqlx::test(migrations = "./migrations")]
+async fn alert_webhook_refuses_hostname_resolving_to_loopback(pool: PgPool) {
+    let (alias, ip) = loopback_alias();
+    let listener = std::net::TcpListener::bind((ip, 0)).unwrap();
+    let port = listener.local_addr().unwrap().port();
+    let mock_server = MockServer::builder().listener(listener).start().await;
+    let url = format!("http://{alias}:{port}/alert");
+
+    let (dev_state, _admin_token) = test_state(pool).await;
+    let mut state = dev_state.clone();
+    let mut config = (*state.config).clone();
+    config.dev_mode = false;
+    state.config = std::sync::Arc::new(config);
+
+    let guard = Mock::given(matchers::method("POST"))
+        .and(matchers::path("/alert"))
+        .respond_with(ResponseTemplate::new(200))
+        .expect(0)
+        .mount_as_scoped(&mock_server)
+        .await;
+    fire_rule_with_webhook(&state, "loopback-refused", &url).await;
+    drop(guard);
+
+    Mock::given(matchers::method("POST"))
+        .and(matchers::path("/alert"))
+        .respond_with(ResponseTemplate::new(200))
+        .expect(1)
+        .mount(&mock_server)
+        .await;
+    fire_rule_with_webhook(&dev_state, "loopback-dev", &url).await;
+    mock_server.verify().await;
+}
+
+#[sqlx::test(migrations = "./migrations")]
+async fn evaluate_all_inhibits_lower_severity_rule(pool: PgPool) {
+    let (state, _admin_token) = test_state(pool.clone()).await;
+
+    let node_down = insert_alert_rule(
+        &pool,
+        "node-down",
+        "metric:node_up agg:max window:300",
+        "lt",
+        Some(1.0),
+        10,
+    )
+    .await;
+    let latency = insert_alert_rule(
+        &pool,
+        "high-latency",
+        "metric:latency_ms agg:avg window:300",
+        "gt",
+        Some(100.0),
+        10,
+    )
+    .await;
+    sqlx::query("UPDATE alert_rules SET severity = 'critical' WHERE id = $1")
+        .bind(node_down)
+        .execute(&pool)
+        .await
+        .unwrap();
+    sqlx::query("UPDATE alert_rules SET inhibit_if = '{node-down}' WHERE id = $1")
+        .bind(latency)
+        .execute(&pool)
+        .await
+        .unwrap();
+
+    insert_metric(&pool, "node_up", 0.0).await;
+    insert_metric(&pool, "latency_ms", 500.0).await;
+
+    // Both conditions hold past for_seconds
+    let mut alert_states = HashMap::new();
+    platform::observe::alert::evaluate_all(&state, &mut alert_states)
+        .await
+        .unwrap();
+    for s in alert_states.values_mut() {
+        s.first_triggered = Some(Utc::now() - chrono::Duration::seconds(60));
+    }
+    platform::observe::alert::evaluate_all(&state, &mut alert_states)
+        .await
+        .unwrap();
+
+    assert_eq!(
+        latest_event_status(&pool, node_down).await.as_deref(),
+        Some("firing")
+    );
+    assert_eq!(
+        latest_event_status(&pool, latency).await.as_deref(),
+        Some("inhibited")
+    );
+
+    // Node recovers: its alert resolves and the latency alert is released
+    insert_metric(&pool, "node_up", 1.0).await;
+    sqlx::query(
+        "DELETE FROM metric_samples WHERE series_id IN \
+         (SELECT id FROM metric_series WHERE name = 'node_up') AND value = 0",
+    )
+    .execute(&pool)
+    .await
+    .unwrap();
+    platform::observe::alert::evaluate_all(&state, &mut alert_states)
+        .await
+        .unwrap();
+
+    assert_eq!(
+        latest_event_status(&pool, node_down).await.as_deref(),
+        Some("resolved")
+    );
+    assert_eq!(
+        latest_event_status(&pool, latency).await.as_deref(),
+        Some("firing")
+    );
+    assert_eq!(count_alert_events(&pool, latency).await, 1);
+}
+
+#[sqlx::test(migrations = "./migrations")]
+async fn create_alert_with_inhibit_if(pool: PgPool) {
+    let (state, admin_token) = test_state(pool.clone()).await;
+    let app = test_router(state);
+
+    let (status, body) = helpers::post_json(
+        &app,
+        &admin_token,
+        "/api/observe/alerts",
+        serde_json::json!({
+            "name": "high-latency",
+            "query": "metric:latency_ms agg:avg window:60",
+            "condition": "gt",
+            "threshold": 100.0,
+            "inhibit_if": ["node-down"],
+        }),
+    )
+    .await;
+    assert_eq!(status, StatusCode::CREATED, "{body}");
+    assert_eq!(body["inhibit_if"], serde_json::json!(["node-down"]));
+
+    let id = body["id"].as_str().unwrap();
+    let (status, body) = helpers::patch_json(
+        &app,
+        &admin_token,
+        &format!("/api/observe/alerts/{id}"),
+        serde_json::json!({"inhibit_if": []}),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["inhibit_if"], serde_json::json!([]));
+
+    let (status, _) = helpers::patch_json(
+        &app,
+        &admin_token,
+        &format!("/api/observe/alerts/{id}"),
+        serde_json::json!({"inhibit_if": [""]}),
+    )
+    .await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+}
@@ -340,6 +340,34 @@ async fn create_api_token(pool: PgPool) {
     assert_eq!(body["name"], "test-token");
 }
 
+/// A retried token creation is not replayed: the plaintext token is never
+/// stored, so the retry gets 409 and no second token is created.
+#[sqlx::test(migrations = "./migrations")]
+async fn create_api_token_idempotency_key_not_replayed(pool: PgPool) {
+    let (state, admin_token) = helpers::test_state(pool.clone()).await;
+    let app = helpers::test_router(state);
+    let key = Uuid::new_v4().to_string();
+    let body = serde_json::json!({ "name": "idem-token", "scopes": [] });
+
+    let (status, replayed, first) =
+        helpers::post_json_idempotent(&app, &admin_token, "/api/tokens", &key, body.clone()).await;
+    assert_eq!(status, StatusCode::CREATED, "{first}");
+    assert!(!replayed);
+    assert!(first["token"].as_str().unwrap().starts_with("plat_"));
+
+    let (status, _, second) =
+        helpers::post_json_idempotent(&app, &admin_token, "/api/tokens", &key, body).await;
+    assert_eq!(status, StatusCode::CONFLICT, "{second}");
+    assert!(second.get("token").is_none());
+
+    let count: i64 =
+        sqlx::query_scalar("SELECT COUNT(*) FROM api_tokens WHERE name = 'idem-token'")
+            .fetch_one(&pool)
+            .await
+            .unwrap();
+    assert_eq!(count, 1);
+}
+
 #[sqlx::test(migrations = "./migrations")]
 async fn create_api_token_scope_escalation_blocked(pool: PgPool) {
     let (state, admin_token) = helpers::test_state(pool.clone()).await;
@@ -1087,6 +1115,207 @@ async fn logout_invalidates_session(pool: PgPool) {
     assert_eq!(status, StatusCode::UNAUTHORIZED);
 }
 
+/// Logout ends only the presented session; other sessions stay valid.
+#[sqlx::test(migrations = "./migrations")]
+async fn logout_keeps_other_sessions(pool: PgPool) {
+    let (state, _admin_token) = helpers::test_state(pool).await;
+    let app = helpers::test_router(state);
+
+    let laptop = helpers::admin_login(&app).await;
+    let phone = helpers::admin_login(&app).await;
+
+    let (status, _) =
+        helpers::post_json(&app, &laptop, "/api/auth/logout", serde_json::json!({})).await;
+    assert_eq!(status, StatusCode::OK);
+
+    let (status, _) = helpers::get_json(&app, &laptop, "/api/auth/me").await;
+    assert_eq!(status, StatusCode::UNAUTHORIZED);
+    let (status, _) = helpers::get_json(&app, &phone, "/api/auth/me").await;
+    assert_eq!(status, StatusCode::OK);
+}
+
+/// Logout-all revokes every session of the user.
+#[sqlx::test(migrations = "./migrations")]
+async fn logout_all_revokes_every_session(pool: PgPool) {
+    let (state, _admin_token) = helpers::test_state(pool).await;
+    let app = helpers::test_router(state);
+
+    let laptop = helpers::admin_login(&app).await;
+    let phone = helpers::admin_login(&app).await;
+
+    let (status, body) =
+        helpers::post_json(&app, &laptop, "/api/auth/logout-all", serde_json::json!({})).await;
+    assert_eq!(status, StatusCode::OK);
+    assert!(body["revoked"].as_u64().unwrap() >= 2);
+
+    for token in [&laptop, &phone] {
+        let (status, _) = helpers::get_json(&app, token, "/api/auth/me").await;
+        assert_eq!(status, StatusCode::UNAUTHORIZED);
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Session listing / revocation
+// ---------------------------------------------------------------------------
+
+/// Log in as admin with the given `User-Agent`, returning the session token.
+async fn admin_login_with_agent(app: &axum::Router, user_agent: &str) -> String {
+    let req = Request::builder()
+        .method("POST")
+        .uri("/api/auth/login")
+        .header("Content-Type", "application/json")
+        .header("User-Agent", user_agent)
+        .body(Body::from(
+            serde_json::json!({ "name": "admin", "password": "testpassword" }).to_string(),
+        ))
+        .unwrap();
+
+    let resp = app.clone().oneshot(req).await.unwrap();
+    assert_eq!(resp.status(), StatusCode::OK);
+    let bytes = resp.into_body().collect().await.unwrap().to_bytes();
+    let body: serde_json::Value = serde_json::from_slice(&bytes).unwrap();
+    body["token"].as_str().unwrap().to_owned()
+}
+
+/// Sessions are listed with their user agent and can be revoked one by one.
+#[sqlx::test(migrations = "./migrations")]
+async fn list_and_revoke_sessions(pool: PgPool) {
+    let (state, _admin_token) = helpers::test_state(pool).await;
+    let app = helpers::test_router(state);
+
+    let laptop = admin_login_with_agent(&app, "laptop-browser").await;
+    let phone = admin_login_with_agent(&app, "phone-app").await;
+    let stolen = admin_login_with_agent(&app, "stolen-tablet").await;
+
+    let (status, body) = helpers::get_json(&app, &laptop, "/api/auth/sessions").await;
+    assert_eq!(status, StatusCode::OK);
+    let items = body["items"].as_array().unwrap();
+    let agents: Vec<&str> = items
+        .iter()
+        .filter_map(|s| s["user_agent"].as_str())
+        .collect();
+    for ua in ["laptop-browser", "phone-app", "stolen-tablet"] {
+        assert!(agents.contains(&ua), "missing session for {ua}: {body}");
+    }
+    let current: Vec<_> = items.iter().filter(|s| s["current"] == true).collect();
+    assert_eq!(current.len(), 1);
+    assert_eq!(current[0]["user_agent"], "laptop-browser");
+
+    let stolen_id = items
+        .iter()
+        .find(|s| s["user_agent"] == "stolen-tablet")
+        .and_then(|s| s["id"].as_str())
+        .unwrap();
+    let (status, _) =
+        helpers::delete_json(&app, &laptop, &format!("/api/auth/sessions/{stolen_id}")).await;
+    assert_eq!(status, StatusCode::NO_CONTENT);
+
+    let (status, _) = helpers::get_json(&app, &stolen, "/api/auth/me").await;
+    assert_eq!(status, StatusCode::UNAUTHORIZED);
+    for token in [&laptop, &phone] {
+        let (status, _) = helpers::get_json(&app, token, "/api/auth/me").await;
+        assert_eq!(status, StatusCode::OK);
+    }
+
+    // Already revoked
+    let (status, _) =
+        helpers::delete_json(&app, &laptop, &format!("/api/auth/sessions/{stolen_id}")).await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+}
+
+/// Users cannot revoke each other's sessions.
+#[sqlx::test(migrations = "./migrations")]
+async fn revoke_other_users_session_returns_404(pool: PgPool) {
+    let (state, admin_token) = helpers::test_state(pool).await;
+    let app = helpers::test_router(state);
+
+    let (_uid, user_token) =
+        helpers::create_user(&app, &admin_token, "sess-victim", "sess-victim@example.com").await;
+    let (status, body) = helpers::get_json(&app, &user_token, "/api/auth/sessions").await;
+    assert_eq!(status, StatusCode::OK);
+    let victim_session = body["items"][0]["id"].as_str().unwrap().to_owned();
+
+    let (status, _) = helpers::delete_json(
+        &app,
+        &admin_token,
+        &format!("/api/auth/sessions/{victim_session}"),
+    )
+    .await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+
+    let (status, _) = helpers::get_json(&app, &user_token, "/api/auth/me").await;
+    assert_eq!(status, StatusCode::OK);
+}
+
+// ---------------------------------------------------------------------------
+// Account lockout
+// ---------------------------------------------------------------------------
+
+/// Five wrong passwords lock the account; even the right password is then
+/// refused until an admin unlocks it.
+#[sqlx::test(migrations = "./migrations")]
+async fn repeated_failures_lock_account_until_unlocked(pool: PgPool) {
+    let (state, admin_token) = helpers::test_state(pool).await;
+    let app = helpers::test_router(state);
+
+    let (user_id, _token) =
+        helpers::create_user(&app, &admin_token, "lockme", "lockme@example.com").await;
+
+    for _ in 0..5 {
+        let (status, _) = helpers::post_json(
+            &app,
+            "",
+            "/api/auth/login",
+            serde_json::json!({ "name": "lockme", "password": "not-the-password" }),
+        )
+        .await;
+        assert_eq!(status, StatusCode::UNAUTHORIZED);
+    }
+
+    let correct = serde_json::json!({ "name": "lockme", "password": "testpass123" });
+    let (status, body) = helpers::post_json(&app, "", "/api/auth/login", correct.clone()).await;
+    assert_eq!(status, StatusCode::LOCKED);
+    assert!(
+        body["error"]
+            .as_str()
+            .unwrap()
+            .starts_with("account locked, try again in"),
+        "{body}"
+    );
+
+    let (status, body) = helpers::post_json(
+        &app,
+        &admin_token,
+        &format!("/api/admin/users/{user_id}/unlock"),
+        serde_json::json!({}),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["was_locked"], true);
+
+    let (status, _) = helpers::post_json(&app, "", "/api/auth/login", correct).await;
+    assert_eq!(status, StatusCode::OK);
+}
+
+/// Only admins can unlock accounts.
+#[sqlx::test(migrations = "./migrations")]
+async fn unlock_requires_admin(pool: PgPool) {
+    let (state, admin_token) = helpers::test_state(pool).await;
+    let app = helpers::test_router(state);
+
+    let (user_id, user_token) =
+        helpers::create_user(&app, &admin_token, "unlocker", "unlocker@example.com").await;
+
+    let (status, _) = helpers::post_json(
+        &app,
+        &user_token,
+        &format!("/api/admin/users/{user_id}/unlock"),
+        serde_json::json!({}),
+    )
+    .await;
+    assert_eq!(status, StatusCode::FORBIDDEN);
+}
+
 // ===========================================================================
 // T58: Security header tests
 //
@@ -1248,3 +1477,309 @@ async fn security_headers_on_error_response(pool: PgPool) {
         Some("strict-origin-when-cross-origin"),
     );
 }
+
+// ---------------------------------------------------------------------------
+// API-wide per-token rate limit
+// ---------------------------------------------------------------------------
+
+async fn get_raw(app: &axum::Router, token: &str, path: &str) -> axum::response::Response {
+    let req = Request::builder()
+        .method("GET")
+        .uri(path)
+        .header("Authorization", format!("Bearer {token}"))
+        .body(Body::empty())
+        .unwrap();
+    app.clone().oneshot(req).await.unwrap()
+}
+
+fn with_rate_limit(
+    mut state: platform::store::AppState,
+    per_minute: u64,
+    overrides: Vec<(String, u64)>,
+) -> platform::store::AppState {
+    let mut config = (*state.config).clone();
+    config.api_rate_limit_per_minute = per_minute;
+    config.api_rate_limit_overrides = overrides;
+    state.config = std::sync::Arc::new(config);
+    state
+}
+
+#[sqlx::test(migrations = "./migrations")]
+async fn api_rate_limit_returns_429_with_retry_after(pool: PgPool) {
+    let (state, admin_token) = helpers::test_state(pool).await;
+    let app = helpers::test_router(with_rate_limit(state, 3, vec![]));
+
+    for _ in 0..3 {
+        let resp = get_raw(&app, &admin_token, "/api/auth/me").await;
+        assert_eq!(resp.status(), StatusCode::OK);
+    }
+
+    let resp = get_raw(&app, &admin_token, "/api/auth/me").await;
+    assert_eq!(resp.status(), StatusCode::TOO_MANY_REQUESTS);
+    let retry_after: u64 = resp
+        .headers()
+        .get("retry-after")
+        .and_then(|v| v.to_str().ok())
+        .and_then(|v| v.parse().ok())
+        .expect("429 must carry a numeric Retry-After");
+    assert!(
+        (1..=60).contains(&retry_after),
+        "Retry-After should be within the window, got {retry_after}"
+    );
+    let body: serde_json::Value =
+        serde_json::from_slice(&resp.into_body().collect().await.unwrap().to_bytes()).unwrap();
+    assert_eq!(body["code"], "rate_limited");
+}
+
+#[sqlx::test(migrations = "./migrations")]
+async fn api_rate_limit_is_per_token(pool: PgPool) {
+    let (state, admin_token) = helpers::test_state(pool).await;
+    let app = helpers::test_router(state.clone());
+    let (_id, user_token) =
+        helpers::create_user(&app, &admin_token, "ratelimited", "ratelimited@test.com").await;
+
+    let app = helpers::test_router(with_rate_limit(state, 1, vec![]));
+    assert_eq!(
+        get_raw(&app, &admin_token, "/api/auth/me").await.status(),
+        StatusCode::OK
+    );
+    assert_eq!(
+        get_raw(&app, &admin_token, "/api/auth/me").await.status(),
+        StatusCode::TOO_MANY_REQUESTS
+    );
+    // Another token has its own budget.
+    assert_eq!(
+        get_raw(&app, &user_token, "/api/auth/me").await.status(),
+        StatusCode::OK
+    );
+}
+
+#[sqlx::test(migrations = "./migrations")]
+async fn api_rate_limit_unknown_tokens_share_one_budget(pool: PgPool) {
+    let (state, admin_token) = helpers::test_state(pool).await;
+    let app = helpers::test_router(with_rate_limit(state, 1, vec![]));
+
+    // Each made-up token would otherwise get a fresh counter
+    assert_eq!(
+        get_raw(&app, "plat_bogus_one", "/api/auth/me")
+            .await
+            .status(),
+        StatusCode::UNAUTHORIZED
+    );
+    assert_eq!(
+        get_raw(&app, "plat_bogus_two", "/api/auth/me")
+            .await
+            .status(),
+        StatusCode::TOO_MANY_REQUESTS
+    );
+    // A valid token keeps its own budget
+    assert_eq!(
+        get_raw(&app, &admin_token, "/api/auth/me").await.status(),
+        StatusCode::OK
+    );
+}
+
+#[sqlx::test(migrations = "./migrations")]
+async fn api_rate_limit_route_group_override(pool: PgPool) {
+    let (state, admin_token) = helpers::test_state(pool).await;
+    let app = helpers::test_router(with_rate_limit(
+        state,
+        100,
+        vec![("/api/notifications".into(), 1)],
+    ));
+
+    assert_eq!(
+        get_raw(&app, &admin_token, "/api/notifications")
+            .await
+            .status(),
+        StatusCode::OK
+    );
+    assert_eq!(
+        get_raw(&app, &admin_token, "/api/notifications")
+            .await
+            .status(),
+        StatusCode::TOO_MANY_REQUESTS
+    );
+    // Other routes still use the global default.
+    assert_eq!(
+        get_raw(&app, &admin_token, "/api/auth/me").await.status(),
+        StatusCode::OK
+    );
+}
+
+#[sqlx::test(migrations = "./migrations")]
+async fn api_rate_limit_token_override(pool: PgPool) {
+    let (state, admin_token) = helpers::test_state(pool).await;
+    let admin_app = helpers::test_router(state.clone());
+    let (_id, session) = helpers::create_user(&admin_app, &admin_token, "ci", "ci@test.com").await;
+    let (status, body) = helpers::post_json(
+        &admin_app,
+        &session,
+        "/api/tokens",
+        serde_json::json!({"name": "ci", "scopes": []}),
+    )
+    .await;
+    assert_eq!(status, StatusCode::CREATED, "{body}");
+    let ci_token = body["token"].as_str().unwrap().to_owned();
+    let token_id = Uuid::parse_str(body["id"].as_str().unwrap()).unwrap();
+
+    // Only admins manage overrides
+    let (status, _) = helpers::put_json(
+        &admin_app,
+        &ci_token,
+        &format!("/api/admin/rate-limits/tokens/{token_id}"),
+        serde_json::json!({"requests_per_minute": 20}),
+    )
+    .await;
+    assert_eq!(status, StatusCode::FORBIDDEN);
+    let (status, _) = helpers::put_json(
+        &admin_app,
+        &admin_token,
+        &format!("/api/admin/rate-limits/tokens/{token_id}"),
+        serde_json::json!({"requests_per_minute": -1}),
+    )
+    .await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    let (status, _) = helpers::put_json(
+        &admin_app,
+        &admin_token,
+        &format!("/api/admin/rate-limits/tokens/{}", Uuid::new_v4()),
+        serde_json::json!({"requests_per_minute": 20}),
+    )
+    .await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+
+    // Setting twice replaces the limit
+    for rpm in [5, 20] {
+        let (status, body) = helpers::put_json(
+            &admin_app,
+            &admin_token,
+            &format!("/api/admin/rate-limits/tokens/{token_id}"),
+            serde_json::json!({"requests_per_minute": rpm}),
+        )
+        .await;
+        assert_eq!(status, StatusCode::OK, "{body}");
+        assert_eq!(body["token_id"], token_id.to_string());
+        assert_eq!(body["requests_per_minute"], rpm);
+    }
+    let (_, body) = helpers::get_json(&admin_app, &admin_token, "/api/admin/rate-limits").await;
+    assert_eq!(body["total"], 1);
+
+    // The CI token gets 10x the global default; other tokens do not
+    let app = helpers::test_router(with_rate_limit(state, 2, vec![]));
+    for _ in 0..20 {
+        assert_eq!(
+            get_raw(&app, &ci_token, "/api/auth/me").await.status(),
+            StatusCode::OK
+        );
+    }
+    assert_eq!(
+        get_raw(&app, &ci_token, "/api/auth/me").await.status(),
+        StatusCode::TOO_MANY_REQUESTS
+    );
+    for _ in 0..2 {
+        assert_eq!(
+            get_raw(&app, &admin_token, "/api/auth/me").await.status(),
+            StatusCode::OK
+        );
+    }
+    assert_eq!(
+        get_raw(&app, &admin_token, "/api/auth/me").await.status(),
+        StatusCode::TOO_MANY_REQUESTS
+    );
+
+    let (status, _) = helpers::delete_json(
+        &admin_app,
+        &admin_token,
+        &format!("/api/admin/rate-limits/tokens/{token_id}"),
+    )
+    .await;
+    assert_eq!(status, StatusCode::NO_CONTENT);
+    let (status, _) = helpers::delete_json(
+        &admin_app,
+        &admin_token,
+        &format!("/api/admin/rate-limits/tokens/{token_id}"),
+    )
+    .await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+}
+
+#[sqlx::test(migrations = "./migrations")]
+async fn api_rate_limit_project_override(pool: PgPool) {
+    let (state, admin_token) = helpers::test_state(pool).await;
+    let admin_app = helpers::test_router(state.clone());
+    let project_id =
+        helpers::create_project(&admin_app, &admin_token, "high-volume", "private").await;
+    let (status, body) = helpers::put_json(
+        &admin_app,
+        &admin_token,
+        &format!("/api/admin/rate-limits/projects/{project_id}"),
+        serde_json::json!({"requests_per_minute": 3}),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK, "{body}");
+    assert_eq!(body["project_id"], project_id.to_string());
+    assert!(body["token_id"].is_null());
+
+    let app = helpers::test_router(with_rate_limit(state, 1, vec![]));
+    let project_path = format!("/api/projects/{project_id}");
+    for _ in 0..3 {
+        assert_eq!(
+            get_raw(&app, &admin_token, &project_path).await.status(),
+            StatusCode::OK
+        );
+    }
+    assert_eq!(
+        get_raw(&app, &admin_token, &project_path).await.status(),
+        StatusCode::TOO_MANY_REQUESTS
+    );
+    // Counted separately from, and not raising, the rest of the API
+    assert_eq!(
+        get_raw(&app, &admin_token, "/api/auth/me").await.status(),
+        StatusCode::OK
+    );
+    assert_eq!(
+        get_raw(&app, &admin_token, "/api/auth/me").await.status(),
+        StatusCode::TOO_MANY_REQUESTS
+    );
+}
+
+// ---------------------------------------------------------------------------
+// Client IP behind a trusted proxy
+// ---------------------------------------------------------------------------
+
+#[sqlx::test(migrations = "./migrations")]
+async fn audit_records_forwarded_client_ip(pool: PgPool) {
+    let (mut state, admin_token) = helpers::test_state(pool.clone()).await;
+    let mut config = (*state.config).clone();
+    config.trust_proxy_headers = true;
+    config.trust_proxy_cidrs = vec!["10.0.0.0/8".into()];
+    state.config = std::sync::Arc::new(config);
+    let app = helpers::test_router(state);
+
+    // The load balancer at 10.0.0.1 appends the client it saw; the leftmost
+    // entry was supplied by the client and is not trusted
+    let mut req = Request::builder()
+        .method("POST")
+        .uri("/api/projects")
+        .header("Authorization", format!("Bearer {admin_token}"))
+        .header("Content-Type", "application/json")
+        .header("X-Forwarded-For", "6.6.6.6, 203.0.113.7")
+        .body(Body::from(
+            serde_json::json!({ "name": "proxied", "visibility": "private" }).to_string(),
+        ))
+        .unwrap();
+    let peer: std::net::SocketAddr = "10.0.0.1:40000".parse().unwrap();
+    req.extensions_mut()
+        .insert(axum::extract::ConnectInfo(peer));
+    let resp = app.clone().oneshot(req).await.unwrap();
+    assert_eq!(resp.status(), StatusCode::CREATED);
+
+    assert!(helpers::wait_for_audit(&pool, "project.create", 2000).await > 0);
+    let ip: Option<String> =
+        sqlx::query_scalar("SELECT host(ip_addr) FROM audit_log WHERE action = 'project.create'")
+            .fetch_one(&pool)
+            .await
+            .unwrap();
+    assert_eq!(ip.as_deref(), Some("203.0.113.7"));
+}
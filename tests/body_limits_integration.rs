@@ -0,0 +1,84 @@
+// Copyright (c) 2026 Steven Hooker. Exclusively licensed to and distributed by AgentSphere GmbH.
+// SPDX-License-Identifier: BUSL-1.1
+
+//! Integration tests for per route group request body limits
+//! (`api::router_with_body_limits`).
+
+mod helpers;
+
+use axum::body::Body;
+use axum::http::{Request, StatusCode};
+use sqlx::PgPool;
+use tower::ServiceExt;
+
+const MB: usize = 1024 * 1024;
+
+fn multipart_upload(file_name: &str, content: &[u8]) -> (String, Vec<u8>) {
+    let boundary = "----BodyLimitBoundary";
+    let mut body = Vec::with_capacity(content.len() + 256);
+    body.extend_from_slice(format!("--{boundary}\r\n").as_bytes());
+    body.extend_from_slice(
+        format!("Content-Disposition: form-data; name=\"file\"; filename=\"{file_name}\"\r\n")
+            .as_bytes(),
+    );
+    body.extend_from_slice(b"Content-Type: application/octet-stream\r\n\r\n");
+    body.extend_from_slice(content);
+    body.extend_from_slice(format!("\r\n--{boundary}--\r\n").as_bytes());
+    (format!("multipart/form-data; boundary={boundary}"), body)
+}
+
+#[sqlx::test(migrations = "./migrations")]
+async fn oversized_issue_body_is_rejected(pool: PgPool) {
+    let (state, admin_token) = helpers::test_state(pool).await;
+    let app = helpers::test_router(state);
+    let project_id = helpers::create_project(&app, &admin_token, "limit-issue", "private").await;
+
+    // Sent by hand: the 413 body is plain text, not JSON
+    let body = serde_json::json!({ "title": "huge", "body": "x".repeat(20 * MB) });
+    let req = Request::builder()
+        .method("POST")
+        .uri(format!("/api/projects/{project_id}/issues"))
+        .header("Authorization", format!("Bearer {admin_token}"))
+        .header("Content-Type", "application/json")
+        .body(Body::from(body.to_string()))
+        .unwrap();
+    let resp = app.clone().oneshot(req).await.unwrap();
+    assert_eq!(resp.status(), StatusCode::PAYLOAD_TOO_LARGE);
+
+    // A normal issue still goes through
+    let (status, body) = helpers::post_json(
+        &app,
+        &admin_token,
+        &format!("/api/projects/{project_id}/issues"),
+        serde_json::json!({ "title": "small", "body": "fine" }),
+    )
+    .await;
+    assert_eq!(status, StatusCode::CREATED, "{body}");
+}
+
+#[sqlx::test(migrations = "./migrations")]
+async fn large_release_asset_upload_succeeds(pool: PgPool) {
+    let (state, admin_token) = helpers::test_state(pool).await;
+    let app = helpers::test_router(state);
+    let project_id = helpers::create_project(&app, &admin_token, "limit-asset", "private").await;
+    helpers::post_json(
+        &app,
+        &admin_token,
+        &format!("/api/projects/{project_id}/releases"),
+        serde_json::json!({ "tag_name": "v1.0.0", "name": "Big" }),
+    )
+    .await;
+
+    // Larger than the 10 MB API default
+    let content = vec![7u8; 20 * MB];
+    let (content_type, body) = multipart_upload("big.bin", &content);
+    let req = Request::builder()
+        .method("POST")
+        .uri(format!("/api/projects/{project_id}/releases/v1.0.0/assets"))
+        .header("Authorization", format!("Bearer {admin_token}"))
+        .header("Content-Type", content_type)
+        .body(Body::from(body))
+        .unwrap();
+    let resp = app.clone().oneshot(req).await.unwrap();
+    assert_eq!(resp.status(), StatusCode::CREATED);
+}
@@ -91,6 +91,63 @@ async fn create_command_requires_admin_for_global(pool: PgPool) {
     assert_eq!(status, StatusCode::FORBIDDEN);
 }
 
+/// A token bounded to a project can't touch global commands, even when its
+/// owner is an admin.
+#[sqlx::test(migrations = "./migrations")]
+async fn global_command_rejects_project_bounded_admin_token(pool: PgPool) {
+    let (state, admin_token) = helpers::test_state(pool).await;
+    let app = helpers::test_router(state);
+
+    let project_id = helpers::create_project(&app, &admin_token, "cmd-bound", "private").await;
+    let (status, token_body) = helpers::post_json(
+        &app,
+        &admin_token,
+        "/api/tokens",
+        serde_json::json!({"name": "ci-cmd", "project_id": project_id}),
+    )
+    .await;
+    assert_eq!(status, StatusCode::CREATED, "{token_body}");
+    let ci_token = token_body["token"].as_str().unwrap();
+
+    let (status, cmd) = helpers::post_json(
+        &app,
+        &admin_token,
+        "/api/commands",
+        serde_json::json!({
+            "name": "global-bound",
+            "prompt_template": "Do $ARGUMENTS",
+        }),
+    )
+    .await;
+    assert_eq!(status, StatusCode::CREATED, "{cmd}");
+    let cmd_id = cmd["id"].as_str().unwrap();
+
+    let (status, _) = helpers::post_json(
+        &app,
+        ci_token,
+        "/api/commands",
+        serde_json::json!({
+            "name": "global-escape",
+            "prompt_template": "nope",
+        }),
+    )
+    .await;
+    assert_eq!(status, StatusCode::FORBIDDEN);
+
+    let (status, _) = helpers::put_json(
+        &app,
+        ci_token,
+        &format!("/api/commands/{cmd_id}"),
+        serde_json::json!({ "prompt_template": "hijacked" }),
+    )
+    .await;
+    assert_eq!(status, StatusCode::FORBIDDEN);
+
+    let (status, _) =
+        helpers::delete_json(&app, ci_token, &format!("/api/commands/{cmd_id}")).await;
+    assert_eq!(status, StatusCode::FORBIDDEN);
+}
+
 #[sqlx::test(migrations = "./migrations")]
 async fn list_commands_returns_global(pool: PgPool) {
     let (state, admin_token) = helpers::test_state(pool).await;
@@ -901,7 +901,7 @@ async fn contract_alert_rule_create(pool: PgPool) {
             "condition": "gt",
             "threshold": 100.0,
             "window_seconds": 300,
-            "channels": ["webhook"],
+            "channels": ["webhook:https://hooks.example.com/alerts"],
         }),
     )
     .await;
@@ -1469,7 +1469,7 @@ async fn contract_mcp_observe_alert_get(pool: PgPool) {
             "condition": "gt",
             "threshold": 50.0,
             "window_seconds": 60,
-            "channels": ["webhook"],
+            "channels": ["webhook:https://hooks.example.com/alerts"],
         }),
     )
     .await;
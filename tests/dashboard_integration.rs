@@ -122,6 +122,85 @@ async fn list_audit_log(pool: PgPool) {
     assert!(body["total"].as_i64().unwrap() >= 1);
 }
 
+/// Audit entries are hash-chained; editing a row is flagged by the verifier.
+#[sqlx::test(migrations = "./migrations")]
+async fn audit_log_verify_detects_tampering(pool: PgPool) {
+    let (state, admin_token) = test_state(pool.clone()).await;
+    let app = test_router(state);
+
+    create_project(&app, &admin_token, "chain-a", "private").await;
+    create_project(&app, &admin_token, "chain-b", "private").await;
+    create_project(&app, &admin_token, "chain-c", "private").await;
+    tokio::time::sleep(std::time::Duration::from_millis(200)).await;
+
+    let (status, body) = helpers::get_json(&app, &admin_token, "/api/audit-log/verify").await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["intact"], true, "fresh chain should verify: {body}");
+    assert!(body["checked"].as_i64().unwrap() >= 3);
+    assert!(body["first_break"].is_null());
+
+    // Rewrite the second chained entry behind the application's back.
+    let (tampered_id, tampered_seq): (Uuid, i64) = sqlx::query_as(
+        "SELECT id, seq FROM audit_log WHERE entry_hash IS NOT NULL ORDER BY seq OFFSET 1 LIMIT 1",
+    )
+    .fetch_one(&pool)
+    .await
+    .unwrap();
+    sqlx::query("UPDATE audit_log SET actor_name = 'someone-else' WHERE id = $1")
+        .bind(tampered_id)
+        .execute(&pool)
+        .await
+        .unwrap();
+
+    let (status, body) = helpers::get_json(&app, &admin_token, "/api/audit-log/verify").await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["intact"], false);
+    assert_eq!(body["first_break"]["id"], tampered_id.to_string());
+    assert_eq!(body["first_break"]["seq"], tampered_seq);
+    assert_eq!(body["first_break"]["reason"], "hash_mismatch");
+    assert_eq!(body["checked"], 1);
+}
+
+/// Deleting an entry breaks the link of the entry after it.
+#[sqlx::test(migrations = "./migrations")]
+async fn audit_log_verify_detects_deleted_entry(pool: PgPool) {
+    let (state, admin_token) = test_state(pool.clone()).await;
+    let app = test_router(state);
+
+    create_project(&app, &admin_token, "gap-a", "private").await;
+    create_project(&app, &admin_token, "gap-b", "private").await;
+    create_project(&app, &admin_token, "gap-c", "private").await;
+    tokio::time::sleep(std::time::Duration::from_millis(200)).await;
+
+    let seqs: Vec<(Uuid, i64)> = sqlx::query_as(
+        "SELECT id, seq FROM audit_log WHERE entry_hash IS NOT NULL ORDER BY seq LIMIT 3",
+    )
+    .fetch_all(&pool)
+    .await
+    .unwrap();
+    sqlx::query("DELETE FROM audit_log WHERE id = $1")
+        .bind(seqs[1].0)
+        .execute(&pool)
+        .await
+        .unwrap();
+
+    let (status, body) = helpers::get_json(&app, &admin_token, "/api/audit-log/verify").await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["intact"], false);
+    assert_eq!(body["first_break"]["id"], seqs[2].0.to_string());
+    assert_eq!(body["first_break"]["reason"], "link_mismatch");
+}
+
+#[sqlx::test(migrations = "./migrations")]
+async fn audit_log_verify_requires_admin(pool: PgPool) {
+    let (state, admin_token) = test_state(pool.clone()).await;
+    let app = test_router(state);
+
+    let (_uid, user_token) = create_user(&app, &admin_token, "normie3", "normie3@test.com").await;
+    let (status, _) = helpers::get_json(&app, &user_token, "/api/audit-log/verify").await;
+    assert_eq!(status, StatusCode::FORBIDDEN);
+}
+
 // ---------------------------------------------------------------------------
 // Onboarding status
 // ---------------------------------------------------------------------------
@@ -0,0 +1,261 @@
+// Copyright (c) 2026 Steven Hooker. Exclusively licensed to and distributed by AgentSphere GmbH.
+// SPDX-License-Identifier: BUSL-1.1
+
+//! Integration tests for per-environment deployment approval policies.
+
+#![recursion_limit = "256"]
+
+mod helpers;
+
+use axum::http::StatusCode;
+use serde_json::json;
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use platform::store::AppState;
+
+use helpers::{
+    assign_role, create_project, create_user, get_json, patch_json, post_json, test_router,
+    test_state,
+};
+
+/// Run the reconciler for one wake-up.
+async fn reconcile_once(state: &AppState) {
+    let cancel = tokio_util::sync::CancellationToken::new();
+    let handle = tokio::spawn(platform::deployer::reconciler::run(
+        state.clone(),
+        cancel.clone(),
+    ));
+    state.deploy_notify.notify_one();
+    tokio::time::sleep(std::time::Duration::from_millis(500)).await;
+    cancel.cancel();
+    let _ = tokio::time::timeout(std::time::Duration::from_secs(5), handle).await;
+}
+
+/// `(phase, started)` — the reconciler stamps `started_at` when it claims a release.
+async fn release_state(pool: &PgPool, release_id: Uuid) -> (String, bool) {
+    sqlx::query_as("SELECT phase, started_at IS NOT NULL FROM deploy_releases WHERE id = $1")
+        .bind(release_id)
+        .fetch_one(pool)
+        .await
+        .unwrap()
+}
+
+#[sqlx::test(migrations = "./migrations")]
+async fn production_release_waits_for_second_approver(pool: PgPool) {
+    let (state, admin_token) = test_state(pool.clone()).await;
+    let app = test_router(state.clone());
+    let project_id = create_project(&app, &admin_token, "approvals", "private").await;
+
+    // Production requires approval; staging keeps the default (automatic)
+    let (status, prod) = post_json(
+        &app,
+        &admin_token,
+        &format!("/api/projects/{project_id}/targets"),
+        json!({"name": "production", "environment": "production"}),
+    )
+    .await;
+    assert_eq!(status, StatusCode::CREATED, "{prod}");
+    assert_eq!(prod["requires_approval"], false);
+    let (status, prod) = patch_json(
+        &app,
+        &admin_token,
+        &format!(
+            "/api/projects/{project_id}/targets/{}",
+            prod["id"].as_str().unwrap()
+        ),
+        json!({"requires_approval": true}),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK, "{prod}");
+    assert_eq!(prod["requires_approval"], true);
+
+    let (status, staging) = post_json(
+        &app,
+        &admin_token,
+        &format!("/api/projects/{project_id}/targets"),
+        json!({"name": "staging", "environment": "staging"}),
+    )
+    .await;
+    assert_eq!(status, StatusCode::CREATED, "{staging}");
+    let staging_target = Uuid::parse_str(staging["id"].as_str().unwrap()).unwrap();
+    let staging_release: Uuid = sqlx::query_scalar(
+        "INSERT INTO deploy_releases (target_id, project_id, image_ref, strategy)
+         VALUES ($1, $2, 'app:v2', 'rolling') RETURNING id",
+    )
+    .bind(staging_target)
+    .bind(project_id)
+    .fetch_one(&pool)
+    .await
+    .unwrap();
+
+    let (status, release) = post_json(
+        &app,
+        &admin_token,
+        &format!("/api/projects/{project_id}/deploy-releases"),
+        json!({"image_ref": "app:v2"}),
+    )
+    .await;
+    assert_eq!(status, StatusCode::CREATED, "{release}");
+    assert_eq!(release["phase"], "pending");
+    assert_eq!(release["requires_approval"], true);
+    assert!(release["approved_by"].is_null());
+    let release_id = Uuid::parse_str(release["id"].as_str().unwrap()).unwrap();
+
+    // Staging proceeds; production is left alone
+    reconcile_once(&state).await;
+    assert!(release_state(&pool, staging_release).await.1);
+    assert_eq!(
+        release_state(&pool, release_id).await,
+        ("pending".into(), false)
+    );
+
+    // The deployer cannot approve their own release
+    let approve = format!("/api/projects/{project_id}/deploy-releases/{release_id}/approve");
+    let (status, body) = post_json(&app, &admin_token, &approve, json!({})).await;
+    assert_eq!(status, StatusCode::BAD_REQUEST, "{body}");
+
+    // Approval needs deploy:promote
+    let (viewer_id, viewer_token) =
+        create_user(&app, &admin_token, "viewer", "viewer@example.com").await;
+    assign_role(
+        &app,
+        &admin_token,
+        viewer_id,
+        "viewer",
+        Some(project_id),
+        &pool,
+    )
+    .await;
+    let (status, _) = post_json(&app, &viewer_token, &approve, json!({})).await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+
+    let (ops_id, ops_token) = create_user(&app, &admin_token, "ops", "ops@example.com").await;
+    assign_role(&app, &admin_token, ops_id, "ops", Some(project_id), &pool).await;
+    let (status, body) = post_json(&app, &ops_token, &approve, json!({})).await;
+    assert_eq!(status, StatusCode::OK, "{body}");
+    assert_eq!(body["approved_by"], ops_id.to_string());
+    assert!(body["approved_at"].is_string());
+
+    // Approving twice is rejected
+    let (status, _) = post_json(&app, &ops_token, &approve, json!({})).await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+
+    let (_, history) = get_json(
+        &app,
+        &admin_token,
+        &format!("/api/projects/{project_id}/deploy-releases/{release_id}/history"),
+    )
+    .await;
+    assert!(
+        history["items"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .any(|h| h["action"] == "approved" && h["actor_id"] == ops_id.to_string()),
+        "{history}"
+    );
+
+    reconcile_once(&state).await;
+    assert!(release_state(&pool, release_id).await.1);
+}
+
+#[sqlx::test(migrations = "./migrations")]
+async fn releases_without_policy_need_no_approval(pool: PgPool) {
+    let (state, admin_token) = test_state(pool.clone()).await;
+    let app = test_router(state);
+    let project_id = create_project(&app, &admin_token, "no-approvals", "private").await;
+
+    post_json(
+        &app,
+        &admin_token,
+        &format!("/api/projects/{project_id}/targets"),
+        json!({"name": "production", "environment": "production"}),
+    )
+    .await;
+    let (status, release) = post_json(
+        &app,
+        &admin_token,
+        &format!("/api/projects/{project_id}/deploy-releases"),
+        json!({"image_ref": "app:v1"}),
+    )
+    .await;
+    assert_eq!(status, StatusCode::CREATED, "{release}");
+    assert_eq!(release["requires_approval"], false);
+
+    let (status, _) = post_json(
+        &app,
+        &admin_token,
+        &format!(
+            "/api/projects/{project_id}/deploy-releases/{}/approve",
+            release["id"].as_str().unwrap()
+        ),
+        json!({}),
+    )
+    .await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+}
+
+/// A release created for a pipeline deploy is attributed to the user who
+/// triggered the pipeline, who then cannot approve it.
+#[sqlx::test(migrations = "./migrations")]
+async fn pipeline_release_cannot_be_approved_by_its_trigger(pool: PgPool) {
+    let (state, admin_token) = test_state(pool.clone()).await;
+    let app = test_router(state.clone());
+    let project_id = create_project(&app, &admin_token, "approvals-auto", "private").await;
+    let (status, prod) = post_json(
+        &app,
+        &admin_token,
+        &format!("/api/projects/{project_id}/targets"),
+        json!({"name": "production", "environment": "production"}),
+    )
+    .await;
+    assert_eq!(status, StatusCode::CREATED, "{prod}");
+    let (status, _) = patch_json(
+        &app,
+        &admin_token,
+        &format!(
+            "/api/projects/{project_id}/targets/{}",
+            prod["id"].as_str().unwrap()
+        ),
+        json!({"requires_approval": true}),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+
+    let admin_id: Uuid = sqlx::query_scalar("SELECT id FROM users WHERE name = 'admin'")
+        .fetch_one(&pool)
+        .await
+        .unwrap();
+    platform::store::eventbus::handle_event(
+        &state,
+        &json!({
+            "type": "OpsRepoUpdated",
+            "project_id": project_id,
+            "ops_repo_id": Uuid::new_v4(),
+            "environment": "production",
+            "commit_sha": "abc1234567",
+            "image_ref": "app:v1",
+            "triggered_by": admin_id,
+        })
+        .to_string(),
+    )
+    .await
+    .unwrap();
+    let (release_id, deployed_by): (Uuid, Option<Uuid>) =
+        sqlx::query_as("SELECT id, deployed_by FROM deploy_releases WHERE project_id = $1")
+            .bind(project_id)
+            .fetch_one(&pool)
+            .await
+            .unwrap();
+    assert_eq!(deployed_by, Some(admin_id));
+
+    let (status, body) = post_json(
+        &app,
+        &admin_token,
+        &format!("/api/projects/{project_id}/deploy-releases/{release_id}/approve"),
+        json!({}),
+    )
+    .await;
+    assert_eq!(status, StatusCode::BAD_REQUEST, "{body}");
+}
@@ -0,0 +1,202 @@
+// Copyright (c) 2026 Steven Hooker. Exclusively licensed to and distributed by AgentSphere GmbH.
+// SPDX-License-Identifier: BUSL-1.1
+
+//! Integration tests for the deployment events endpoint: cluster events and
+//! rollout conditions for a release's workloads plus the reconciler log trail.
+
+mod helpers;
+
+use std::time::Duration;
+
+use axum::http::StatusCode;
+use serde_json::{Value, json};
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use helpers::{create_project, create_user, get_json, post_json, test_router, test_state};
+
+async fn create_target(app: &axum::Router, token: &str, project_id: Uuid, env: &str) -> Uuid {
+    let (status, target) = post_json(
+        app,
+        token,
+        &format!("/api/projects/{project_id}/targets"),
+        json!({"name": env, "environment": env}),
+    )
+    .await;
+    assert_eq!(status, StatusCode::CREATED, "{target}");
+    Uuid::parse_str(target["id"].as_str().unwrap()).unwrap()
+}
+
+async fn insert_release(pool: &PgPool, target_id: Uuid, project_id: Uuid, image: &str) -> Uuid {
+    sqlx::query_scalar(
+        "INSERT INTO deploy_releases (target_id, project_id, image_ref, strategy)
+         VALUES ($1, $2, $3, 'rolling') RETURNING id",
+    )
+    .bind(target_id)
+    .bind(project_id)
+    .bind(image)
+    .fetch_one(pool)
+    .await
+    .unwrap()
+}
+
+#[sqlx::test(migrations = "./migrations")]
+async fn events_endpoint_returns_log_trail(pool: PgPool) {
+    let (state, admin_token) = test_state(pool.clone()).await;
+    let app = test_router(state);
+    let project_id = create_project(&app, &admin_token, "events", "private").await;
+    let url = format!("/api/projects/{project_id}/deployments/staging/events");
+
+    let (status, _) = get_json(&app, &admin_token, &url).await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+    let (status, _) = get_json(
+        &app,
+        &admin_token,
+        &format!("/api/projects/{project_id}/deployments/dev/events"),
+    )
+    .await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+
+    // Preview is an environment too, though its path is shared with the
+    // preview lifecycle routes
+    let preview_url = format!("/api/projects/{project_id}/deployments/preview/events");
+    let (status, _) = get_json(&app, &admin_token, &preview_url).await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+    create_target(&app, &admin_token, project_id, "preview").await;
+    let (status, body) = get_json(&app, &admin_token, &preview_url).await;
+    assert_eq!(status, StatusCode::OK, "{body}");
+    assert_eq!(body["environment"], "preview");
+
+    // A target that was never deployed
+    let target_id = create_target(&app, &admin_token, project_id, "staging").await;
+    let (status, body) = get_json(&app, &admin_token, &url).await;
+    assert_eq!(status, StatusCode::OK, "{body}");
+    assert!(body["release_id"].is_null());
+    assert_eq!(body["logs"], json!([]));
+
+    let release_id = insert_release(&pool, target_id, project_id, "app:v1").await;
+    sqlx::query("UPDATE deploy_releases SET phase = 'failed', attempts = 2 WHERE id = $1")
+        .bind(release_id)
+        .execute(&pool)
+        .await
+        .unwrap();
+    for (attempt, level, message) in [
+        (1, "info", "applying app:v1 to namespace events-staging"),
+        (2, "error", "render failed: missing values"),
+    ] {
+        sqlx::query(
+            "INSERT INTO release_logs (release_id, attempt, level, message) VALUES ($1, $2, $3, $4)",
+        )
+        .bind(release_id)
+        .bind(attempt)
+        .bind(level)
+        .bind(message)
+        .execute(&pool)
+        .await
+        .unwrap();
+    }
+
+    let (status, body) = get_json(&app, &admin_token, &url).await;
+    assert_eq!(status, StatusCode::OK, "{body}");
+    assert_eq!(body["release_id"], release_id.to_string());
+    assert_eq!(body["phase"], "failed");
+    assert_eq!(body["attempts"], 2);
+    assert_eq!(body["events"], json!([]));
+    assert!(body["cluster_error"].is_null());
+    let logs = body["logs"].as_array().unwrap();
+    assert_eq!(logs.len(), 2);
+    assert_eq!(logs[1]["attempt"], 2);
+    assert_eq!(logs[1]["level"], "error");
+    assert_eq!(logs[1]["message"], "render failed: missing values");
+
+    // Needs deploy read on the project
+    let (_, outsider) = create_user(&app, &admin_token, "outsider", "outsider@example.com").await;
+    let (status, _) = get_json(&app, &outsider, &url).await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+}
+
+#[sqlx::test(migrations = "./migrations")]
+async fn failing_deploy_shows_image_pull_backoff(pool: PgPool) {
+    let (state, admin_token) = test_state(pool.clone()).await;
+    let app = test_router(state.clone());
+    let project_id = create_project(&app, &admin_token, "pullfail", "private").await;
+    let target_id = create_target(&app, &admin_token, project_id, "staging").await;
+    let release_id = insert_release(
+        &pool,
+        target_id,
+        project_id,
+        "registry.invalid/pullfail/app:v1",
+    )
+    .await;
+
+    let cancel = tokio_util::sync::CancellationToken::new();
+    let reconciler = tokio::spawn(platform::deployer::reconciler::run(
+        state.clone(),
+        cancel.clone(),
+    ));
+    state.deploy_notify.notify_one();
+
+    // The rolling release sits in its health wait while the kubelet backs off
+    let url = format!("/api/projects/{project_id}/deployments/staging/events");
+    let deadline = std::time::Instant::now() + Duration::from_mins(2);
+    let body = loop {
+        let (status, body) = get_json(&app, &admin_token, &url).await;
+        assert_eq!(status, StatusCode::OK, "{body}");
+        let backing_off = body["events"].as_array().unwrap().iter().any(|e: &Value| {
+            e["kind"] == "Pod"
+                && [&e["reason"], &e["message"]]
+                    .iter()
+                    .any(|v| v.as_str().is_some_and(|s| s.contains("ImagePullBackOff")))
+        });
+        if backing_off {
+            break body;
+        }
+        assert!(
+            std::time::Instant::now() < deadline,
+            "no ImagePullBackOff event: {body}"
+        );
+        tokio::time::sleep(Duration::from_secs(3)).await;
+    };
+    cancel.cancel();
+    let _ = tokio::time::timeout(Duration::from_secs(5), reconciler).await;
+
+    assert_eq!(body["release_id"], release_id.to_string());
+    assert!(body["attempts"].as_i64().unwrap() >= 1);
+    assert!(body["cluster_error"].is_null());
+    assert!(
+        body["conditions"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .any(|c| c["deployment"] == "pullfail-staging" && c["condition_type"] == "Available"),
+        "{body}"
+    );
+    let logs: Vec<&str> = body["logs"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .filter_map(|l| l["message"].as_str())
+        .collect();
+    assert!(
+        logs.iter()
+            .any(|m| m.starts_with("applied Deployment/pullfail-staging")),
+        "{logs:?}"
+    );
+    assert!(
+        logs.iter()
+            .any(|m| m.starts_with("waiting up to 300s for deployment pullfail-staging")),
+        "{logs:?}"
+    );
+
+    let slug: String = sqlx::query_scalar("SELECT namespace_slug FROM projects WHERE id = $1")
+        .bind(project_id)
+        .fetch_one(&pool)
+        .await
+        .unwrap();
+    let ns = platform::deployer::reconciler::target_namespace(&state.config, &slug, "staging");
+    let deployments: kube::Api<k8s_openapi::api::apps::v1::Deployment> =
+        kube::Api::namespaced(state.kube.clone(), &ns);
+    let _ = deployments
+        .delete("pullfail-staging", &kube::api::DeleteParams::default())
+        .await;
+}
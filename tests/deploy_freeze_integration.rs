@@ -0,0 +1,237 @@
+// Copyright (c) 2026 Steven Hooker. Exclusively licensed to and distributed by AgentSphere GmbH.
+// SPDX-License-Identifier: BUSL-1.1
+
+//! Integration tests for deployment freeze windows.
+
+#![recursion_limit = "256"]
+
+mod helpers;
+
+use axum::http::StatusCode;
+use chrono::{Duration, Utc};
+use serde_json::json;
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use helpers::{
+    assign_role, create_project, create_user, delete_json, get_json, post_json, test_router,
+    test_state, wait_for_audit,
+};
+
+async fn create_production_target(app: &axum::Router, token: &str, project_id: Uuid) -> Uuid {
+    let (status, body) = post_json(
+        app,
+        token,
+        &format!("/api/projects/{project_id}/targets"),
+        json!({"name": "production", "environment": "production"}),
+    )
+    .await;
+    assert_eq!(status, StatusCode::CREATED, "{body}");
+    Uuid::parse_str(body["id"].as_str().unwrap()).unwrap()
+}
+
+#[sqlx::test(migrations = "./migrations")]
+async fn production_deploy_blocked_during_freeze_unless_forced(pool: PgPool) {
+    let (state, admin_token) = test_state(pool.clone()).await;
+    let app = test_router(state);
+    let project_id = create_project(&app, &admin_token, "freeze", "private").await;
+    create_production_target(&app, &admin_token, project_id).await;
+    let (ops_id, ops_token) = create_user(&app, &admin_token, "ops", "ops@example.com").await;
+    assign_role(&app, &admin_token, ops_id, "ops", Some(project_id), &pool).await;
+
+    let windows = format!("/api/projects/{project_id}/freeze-windows");
+    let now = Utc::now();
+    let (status, window) = post_json(
+        &app,
+        &ops_token,
+        &windows,
+        json!({
+            "environment": "production",
+            "name": "peak hours",
+            "starts_at": now - Duration::hours(1),
+            "ends_at": now + Duration::hours(2),
+        }),
+    )
+    .await;
+    assert_eq!(status, StatusCode::CREATED, "{window}");
+    assert!(window["active_until"].is_string());
+
+    // Staging windows don't affect production
+    let (status, _) = post_json(
+        &app,
+        &ops_token,
+        &windows,
+        json!({"environment": "staging", "name": "never", "cron": "0 0 1 1 *", "duration_minutes": 1}),
+    )
+    .await;
+    assert_eq!(status, StatusCode::CREATED);
+
+    let releases = format!("/api/projects/{project_id}/deploy-releases");
+    let (status, body) =
+        post_json(&app, &ops_token, &releases, json!({"image_ref": "app:v2"})).await;
+    assert_eq!(status, StatusCode::LOCKED, "{body}");
+    let message = body["error"].as_str().unwrap();
+    assert!(
+        message.contains("production is frozen (peak hours) until"),
+        "{message}"
+    );
+
+    // Only admins can force through a freeze
+    let (status, _) = post_json(
+        &app,
+        &ops_token,
+        &format!("{releases}?force=true"),
+        json!({"image_ref": "app:v2"}),
+    )
+    .await;
+    assert_eq!(status, StatusCode::FORBIDDEN);
+
+    let (status, release) = post_json(
+        &app,
+        &admin_token,
+        &format!("{releases}?force=true"),
+        json!({"image_ref": "app:v2"}),
+    )
+    .await;
+    assert_eq!(status, StatusCode::CREATED, "{release}");
+    assert_eq!(
+        wait_for_audit(&pool, "deploy.freeze.override", 2000).await,
+        1
+    );
+
+    // Rollbacks are frozen too
+    let release_id = release["id"].as_str().unwrap();
+    sqlx::query("UPDATE deploy_releases SET phase = 'progressing' WHERE id = $1::uuid")
+        .bind(release_id)
+        .execute(&pool)
+        .await
+        .unwrap();
+    let (status, _) = post_json(
+        &app,
+        &ops_token,
+        &format!("{releases}/{release_id}/rollback"),
+        json!({}),
+    )
+    .await;
+    assert_eq!(status, StatusCode::LOCKED);
+
+    // Lifting the freeze is admin-only
+    let window_url = format!("{windows}/{}", window["id"].as_str().unwrap());
+    let (status, _) = delete_json(&app, &ops_token, &window_url).await;
+    assert_eq!(status, StatusCode::FORBIDDEN);
+    let (status, _) = delete_json(&app, &admin_token, &window_url).await;
+    assert_eq!(status, StatusCode::NO_CONTENT);
+
+    let (status, _) = post_json(
+        &app,
+        &ops_token,
+        &format!("{releases}/{release_id}/rollback"),
+        json!({}),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+}
+
+#[sqlx::test(migrations = "./migrations")]
+async fn recurring_freeze_windows(pool: PgPool) {
+    let (state, admin_token) = test_state(pool.clone()).await;
+    let app = test_router(state);
+    let project_id = create_project(&app, &admin_token, "freeze-cron", "private").await;
+    create_production_target(&app, &admin_token, project_id).await;
+    let windows = format!("/api/projects/{project_id}/freeze-windows");
+
+    // Opens every minute for an hour, so it is open now
+    let (status, body) = post_json(
+        &app,
+        &admin_token,
+        &windows,
+        json!({"environment": "production", "name": "always", "cron": "* * * * *", "duration_minutes": 60}),
+    )
+    .await;
+    assert_eq!(status, StatusCode::CREATED, "{body}");
+
+    let (status, _) = post_json(
+        &app,
+        &admin_token,
+        &format!("/api/projects/{project_id}/deploy-releases"),
+        json!({"image_ref": "app:v3"}),
+    )
+    .await;
+    assert_eq!(status, StatusCode::LOCKED);
+
+    let (status, list) = get_json(&app, &admin_token, &windows).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(list["total"], 1);
+    assert!(list["items"][0]["active_until"].is_string());
+
+    // Malformed windows are rejected
+    for bad in [
+        json!({"environment": "production", "name": "x", "cron": "0 25 * * *", "duration_minutes": 60}),
+        json!({"environment": "production", "name": "x", "cron": "0 9 * * FRI"}),
+        json!({"environment": "prod", "name": "x", "cron": "0 9 * * FRI", "duration_minutes": 60}),
+        json!({"environment": "production", "name": "x",
+               "starts_at": "2026-10-16T17:00:00Z", "ends_at": "2026-10-16T09:00:00Z"}),
+    ] {
+        let (status, _) = post_json(&app, &admin_token, &windows, bad.clone()).await;
+        assert_eq!(status, StatusCode::BAD_REQUEST, "{bad}");
+    }
+}
+
+/// Releases created by the event bus (pipeline deploys) are not created while
+/// the environment is frozen.
+#[sqlx::test(migrations = "./migrations")]
+async fn automatic_release_refused_during_freeze(pool: PgPool) {
+    let (state, admin_token) = test_state(pool.clone()).await;
+    let app = test_router(state.clone());
+    let project_id = create_project(&app, &admin_token, "freeze-auto", "private").await;
+
+    let now = Utc::now();
+    let (status, window) = post_json(
+        &app,
+        &admin_token,
+        &format!("/api/projects/{project_id}/freeze-windows"),
+        json!({
+            "environment": "production",
+            "name": "release week",
+            "starts_at": now - Duration::hours(1),
+            "ends_at": now + Duration::hours(1),
+        }),
+    )
+    .await;
+    assert_eq!(status, StatusCode::CREATED, "{window}");
+
+    let event = json!({
+        "type": "OpsRepoUpdated",
+        "project_id": project_id,
+        "ops_repo_id": Uuid::new_v4(),
+        "environment": "production",
+        "commit_sha": "abc1234567",
+        "image_ref": "app:v1",
+    })
+    .to_string();
+    let count_releases = || async {
+        sqlx::query_scalar::<_, i64>("SELECT COUNT(*) FROM deploy_releases WHERE project_id = $1")
+            .bind(project_id)
+            .fetch_one(&pool)
+            .await
+            .unwrap()
+    };
+
+    platform::store::eventbus::handle_event(&state, &event)
+        .await
+        .unwrap();
+    assert_eq!(count_releases().await, 0);
+
+    let window_id = window["id"].as_str().unwrap();
+    let (status, _) = delete_json(
+        &app,
+        &admin_token,
+        &format!("/api/projects/{project_id}/freeze-windows/{window_id}"),
+    )
+    .await;
+    assert_eq!(status, StatusCode::NO_CONTENT);
+    platform::store::eventbus::handle_event(&state, &event)
+        .await
+        .unwrap();
+    assert_eq!(count_releases().await, 1);
+}
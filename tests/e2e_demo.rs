@@ -896,7 +896,9 @@ async fn demo_otel_ingest_and_query(pool: PgPool) {
     {
         let p = pool.clone();
         let c = cancel.clone();
-        tokio::spawn(platform::observe::ingest::flush_metrics(p, metrics_rx, c));
+        tokio::spawn(platform::observe::ingest::flush_metrics(
+            p, 0, metrics_rx, c,
+        ));
     }
 
     let (project_id, _) = platform::onboarding::demo_project::create_demo_project(&state, admin_id)
@@ -6,6 +6,8 @@
 //! These tests exercise multi-step user journeys spanning ops-repo commits,
 //! the deployer reconciler, staging promotion, and OTLP ingest+query.
 
+#![recursion_limit = "256"]
+
 mod e2e_helpers;
 
 use axum::Router;
@@ -623,6 +625,7 @@ async fn otlp_ingest_and_query_for_project(pool: PgPool) {
     let flush_token = flush_cancel.clone();
     let metrics_handle = tokio::spawn(platform::observe::ingest::flush_metrics(
         flush_pool,
+        0,
         metrics_rx,
         flush_token,
     ));
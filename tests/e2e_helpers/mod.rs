@@ -199,6 +199,9 @@ pub async fn e2e_state_with_api_url(
         minio_access_key: minio_access,
         minio_secret_key: minio_secret,
         minio_insecure,
+        minio_sse: None,
+        minio_sse_kms_key_id: None,
+        object_retention_days: 0,
         master_key: std::env::var("PLATFORM_MASTER_KEY").ok().or(Some(
             "0123456789abcdef0123456789abcdef0123456789abcdef0123456789abcdef".into(),
         )),
@@ -223,6 +226,8 @@ pub async fn e2e_state_with_api_url(
         webauthn_rp_origin: "http://localhost:8080".into(),
         permission_cache_ttl_secs: 300,
         webauthn_rp_name: "Test Platform".into(),
+        passkey_hardware_aaguids: Vec::new(),
+        passkey_attestation_ca: None,
         platform_api_url: platform_api_url.unwrap_or_else(|| {
             std::env::var("PLATFORM_API_URL")
                 .unwrap_or_else(|_| "http://platform.platform.svc.cluster.local:8080".into())
@@ -230,6 +235,7 @@ pub async fn e2e_state_with_api_url(
         platform_namespace: "test-platform".into(),
         ssh_listen: None,
         ssh_host_key_path: "/tmp/test_ssh_host_key".into(),
+        otlp_grpc_listen: None,
         max_cli_subprocesses: 10,
         valkey_agent_host: std::env::var("PLATFORM_VALKEY_AGENT_HOST")
             .unwrap_or_else(|_| "localhost:6379".into()),
@@ -248,8 +254,15 @@ pub async fn e2e_state_with_api_url(
         health_check_interval_secs: 15,
         self_observe_level: "warn".into(),
         session_idle_timeout_secs: 1800,
+        session_max_lifetime_secs: 7200,
+        agent_snapshot_interval_secs: 0,
+        agent_snapshot_keep: 5,
+        vault_addr: None,
+        vault_token: None,
+        vault_cache_ttl_secs: 60,
         preview_proxy_url: std::env::var("PLATFORM_PREVIEW_PROXY_URL").ok(),
         pipeline_max_parallel: 4,
+        pipeline_max_concurrent_per_project: 3,
         mcp_servers_tarball: std::env::var("PLATFORM_MCP_SERVERS_TARBALL").map_or_else(
             |_| "/tmp/mcp-servers.tar.gz".into(),
             std::path::PathBuf::from,
@@ -259,11 +272,26 @@ pub async fn e2e_state_with_api_url(
         gateway_namespace: std::env::var("PLATFORM_GATEWAY_NAMESPACE")
             .unwrap_or_else(|_| "envoy-gateway-system".into()),
         pipeline_timeout_secs: 3600,
+        pipeline_drain_timeout_secs: 20,
+        deploy_health_timeout_secs: 300,
         max_lfs_object_bytes: 5_368_709_120,
         token_max_expiry_days: 365,
         observe_retention_days: 30,
+        observe_log_retention_days: 7,
+        observe_span_retention_days: 7,
+        observe_metric_retention_days: 7,
         master_key_previous: None,
         trust_proxy_cidrs: vec![],
+        password_min_length: 8,
+        password_require_mixed_case: false,
+        password_require_digit: false,
+        password_require_symbol: false,
+        password_denylist: vec![],
+        login_lockout_threshold: 5,
+        login_lockout_window_secs: 900,
+        login_lockout_duration_secs: 900,
+        break_glass_duration_secs: 1800,
+        break_glass_permissions: vec!["deploy:promote".into()],
         runner_image: "platform-runner:v1".into(),
         git_clone_image: "alpine/git:2.47.2".into(),
         kaniko_image: "gcr.io/kaniko-project/executor:v1.23.2-debug".into(),
@@ -273,6 +301,7 @@ pub async fn e2e_state_with_api_url(
         mcp_servers_path: "mcp/servers".into(),
         max_artifact_file_bytes: 50 * 1024 * 1024,
         max_artifact_total_bytes: 500 * 1024 * 1024,
+        pipeline_cache_max_bytes: 10 * 1024 * 1024 * 1024,
         mesh_enabled: std::env::var("PLATFORM_MESH_ENABLED")
             .ok()
             .is_some_and(|v| v == "true"),
@@ -298,9 +327,35 @@ pub async fn e2e_state_with_api_url(
         registry_max_blob_size_bytes: 5_368_709_120,
         db_max_connections: 5,
         db_acquire_timeout_secs: 10,
+        db_min_connections: 0,
+        db_idle_timeout_secs: 300,
+        db_slow_query_ms: 1000,
         valkey_pool_size: 2,
         git_http_timeout_secs: 600,
         request_timeout_secs: 300,
+        webhook_max_concurrent: 50,
+        webhook_max_concurrent_per_project: 10,
+        webhook_allowlist: Vec::new(),
+        manager_session_max_per_user: 10,
+        agent_session_max_per_user: 5,
+        agent_session_max_per_project: 20,
+        observe_buffer_capacity: 10_000,
+        observe_max_series_per_project: 10_000,
+        api_rate_limit_per_minute: 0,
+        api_rate_limit_overrides: Vec::new(),
+        audit_reads: Vec::new(),
+        api_body_limit_bytes: 10 * 1024 * 1024,
+        content_body_limit_bytes: 1024 * 1024,
+        upload_body_limit_bytes: 500 * 1024 * 1024,
+        pod_node_selector: std::collections::BTreeMap::new(),
+        pod_tolerations: Vec::new(),
+        oidc_issuer: None,
+        oidc_client_id: String::new(),
+        oidc_client_secret: None,
+        oidc_redirect_url: "http://localhost:8080/api/auth/oidc/callback".into(),
+        oidc_scopes: "openid email profile".into(),
+        oidc_groups_claim: "groups".into(),
+        oidc_role_map: Vec::new(),
     };
 
     // Registry seed is opt-in — E2E tests that need seeded images should call
@@ -1005,7 +1060,9 @@ pub async fn start_observe_pipeline_server(
     {
         let p = pool.clone();
         let c = cancel.clone();
-        tokio::spawn(platform::observe::ingest::flush_metrics(p, metrics_rx, c));
+        tokio::spawn(platform::observe::ingest::flush_metrics(
+            p, 0, metrics_rx, c,
+        ));
     }
 
     let handle = tokio::spawn(async move {
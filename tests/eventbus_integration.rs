@@ -1,6 +1,8 @@
 // Copyright (c) 2026 Steven Hooker. Exclusively licensed to and distributed by AgentSphere GmbH.
 // SPDX-License-Identifier: BUSL-1.1
 
+#![recursion_limit = "256"]
+
 mod helpers;
 
 use fred::prelude::*;
@@ -1792,6 +1794,7 @@ async fn publish_all_event_variants(pool: PgPool) {
             environment: "production".into(),
             commit_sha: "abc123".into(),
             image_ref: "app:v2".into(),
+            triggered_by: None,
         },
         platform::store::eventbus::PlatformEvent::DeployRequested {
             project_id: Uuid::new_v4(),
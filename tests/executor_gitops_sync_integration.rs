@@ -315,6 +315,11 @@ async fn executor_gitops_sync_basic(pool: PgPool) {
         values_str.contains("gitops-basic"),
         "values should reference project name: {values_str}"
     );
+    // No build step pushed an image, so the tag is deployed as-is
+    assert!(
+        !values_str.contains("@sha256:"),
+        "values should not pin a digest: {values_str}"
+    );
 
     // platform.yaml + deploy/ manifests synced
     assert!(read_file_at_ref(&ops_bare_path, "main", "platform.yaml").is_some());
@@ -614,3 +619,102 @@ deploy:
 
     drop(bare_dir);
 }
+
+// ===========================================================================
+// Test 7: only the project's default branch deploys
+// ===========================================================================
+
+#[sqlx::test(migrations = "./migrations")]
+async fn executor_gitops_sync_follows_default_branch(pool: PgPool) {
+    let (state, admin_token, _server) = helpers::start_pipeline_server(pool).await;
+    let app = helpers::test_router(state.clone());
+    let _executor = ExecutorGuard::spawn(&state);
+
+    let (project_id, bare_path, _work_path, _bd, _wd) = setup_gitops_project(
+        &state,
+        &app,
+        &admin_token,
+        "gitops-develop",
+        GITOPS_PLATFORM_YAML,
+    )
+    .await;
+    sqlx::query("UPDATE projects SET default_branch = 'develop' WHERE id = $1")
+        .bind(project_id)
+        .execute(&state.pool)
+        .await
+        .unwrap();
+    let project_sha = get_head_sha(&bare_path);
+    let (_ops_repo_id, ops_bare_path, _ops_dir) = setup_ops_repo(&state, project_id, "main").await;
+    let ops_sha_before = get_branch_sha(&ops_bare_path, "main");
+
+    // A pipeline on main is no longer a production deploy
+    let (pipeline_id, step_id) = insert_gitops_pipeline(&state, project_id, &project_sha).await;
+    state.pipeline_notify.notify_one();
+    assert_eq!(
+        poll_pipeline_status(&state.pool, pipeline_id, 120).await,
+        "success"
+    );
+    assert_eq!(poll_step_status(&state.pool, step_id, 5).await.0, "success");
+    assert_eq!(get_branch_sha(&ops_bare_path, "main"), ops_sha_before);
+
+    // A pipeline on develop is
+    let (pipeline_id, _) = insert_gitops_pipeline(&state, project_id, &project_sha).await;
+    sqlx::query("UPDATE pipelines SET git_ref = 'refs/heads/develop' WHERE id = $1")
+        .bind(pipeline_id)
+        .execute(&state.pool)
+        .await
+        .unwrap();
+    state.pipeline_notify.notify_one();
+    assert_eq!(
+        poll_pipeline_status(&state.pool, pipeline_id, 120).await,
+        "success"
+    );
+    assert_ne!(get_branch_sha(&ops_bare_path, "main"), ops_sha_before);
+    assert!(read_file_at_ref(&ops_bare_path, "main", "values/production.yaml").is_some());
+}
+
+// ===========================================================================
+// Test: image pinned to the digest the pipeline's build step pushed
+// ===========================================================================
+
+#[sqlx::test(migrations = "./migrations")]
+async fn executor_gitops_sync_pins_pushed_digest(pool: PgPool) {
+    let (state, admin_token, _server) = helpers::start_pipeline_server(pool).await;
+    let app = helpers::test_router(state.clone());
+    let _executor = ExecutorGuard::spawn(&state);
+
+    let (project_id, bare_path, _work_path, _bd, _wd) = setup_gitops_project(
+        &state,
+        &app,
+        &admin_token,
+        "gitops-digest",
+        GITOPS_PLATFORM_YAML,
+    )
+    .await;
+    let project_sha = get_head_sha(&bare_path);
+    let (_ops_repo_id, ops_bare_path, _ops_dir) = setup_ops_repo(&state, project_id, "main").await;
+
+    // What a kaniko step in the same pipeline would have recorded
+    let digest = "sha256:2c26b46b68ffc68ff99b453c1d30413413422d706483bfa0f98a5e886266e7ae";
+    let (pipeline_id, _) = insert_gitops_pipeline(&state, project_id, &project_sha).await;
+    sqlx::query(
+        "INSERT INTO pipeline_image_digests (pipeline_id, repository, digest, step_name)
+         VALUES ($1, 'gitops-digest/app', $2, 'build')",
+    )
+    .bind(pipeline_id)
+    .bind(digest)
+    .execute(&state.pool)
+    .await
+    .unwrap();
+    state.pipeline_notify.notify_one();
+    assert_eq!(
+        poll_pipeline_status(&state.pool, pipeline_id, 120).await,
+        "success"
+    );
+
+    let values_str = read_file_at_ref(&ops_bare_path, "main", "values/production.yaml").unwrap();
+    assert!(
+        values_str.contains(&format!("/gitops-digest/app:{project_sha}@{digest}")),
+        "image_ref should be pinned to the pushed digest: {values_str}"
+    );
+}
@@ -1608,3 +1608,457 @@ async fn executor_step_log_ref_set(pool: PgPool) {
         }
     }
 }
+
+// ===========================================================================
+// Test 33: Orphaned running pipeline (stale heartbeat) is requeued
+// ===========================================================================
+
+#[sqlx::test(migrations = "./migrations")]
+async fn executor_requeues_orphaned_pipeline(pool: PgPool) {
+    let (state, admin_token) = helpers::test_state(pool.clone()).await;
+    let app = helpers::test_router(state.clone());
+    let uid = helpers::admin_user_id(&pool).await;
+
+    let project_id = helpers::create_project(&app, &admin_token, "exec-orphan", "private").await;
+
+    // A replica claimed this pipeline and died five minutes ago mid-step
+    let pipeline_id =
+        helpers::insert_pipeline(&pool, project_id, uid, "running", "refs/heads/main", "push")
+            .await;
+    sqlx::query(
+        "UPDATE pipelines SET started_at = now() - interval '10 minutes',
+                heartbeat_at = now() - interval '5 minutes'
+         WHERE id = $1",
+    )
+    .bind(pipeline_id)
+    .execute(&pool)
+    .await
+    .unwrap();
+    for (order, status, exit_code) in [(0, "success", Some(0)), (1, "running", None)] {
+        sqlx::query(
+            "INSERT INTO pipeline_steps
+                (id, pipeline_id, project_id, name, image, status, step_order, exit_code,
+                 started_at, finished_at)
+             VALUES ($1, $2, $3, $4, 'alpine:3.19', $5, $6, $7, now(),
+                     CASE WHEN $5 = 'success' THEN now() END)",
+        )
+        .bind(Uuid::new_v4())
+        .bind(pipeline_id)
+        .bind(project_id)
+        .bind(format!("step{order}"))
+        .bind(status)
+        .bind(order)
+        .bind(exit_code)
+        .execute(&pool)
+        .await
+        .unwrap();
+    }
+
+    let requeued = platform::pipeline::executor::recover_orphaned_pipelines(&state)
+        .await
+        .unwrap();
+    assert_eq!(requeued, 1);
+
+    let (status, started_at, heartbeat_at): (
+        String,
+        Option<chrono::DateTime<chrono::Utc>>,
+        Option<chrono::DateTime<chrono::Utc>>,
+    ) = sqlx::query_as("SELECT status, started_at, heartbeat_at FROM pipelines WHERE id = $1")
+        .bind(pipeline_id)
+        .fetch_one(&pool)
+        .await
+        .unwrap();
+    assert_eq!(status, "pending");
+    assert!(started_at.is_none());
+    assert!(heartbeat_at.is_none());
+
+    let steps: Vec<(String, Option<i32>, Option<chrono::DateTime<chrono::Utc>>)> = sqlx::query_as(
+        "SELECT status, exit_code, finished_at FROM pipeline_steps
+             WHERE pipeline_id = $1 ORDER BY step_order",
+    )
+    .bind(pipeline_id)
+    .fetch_all(&pool)
+    .await
+    .unwrap();
+    for (status, exit_code, finished_at) in steps {
+        assert_eq!(status, "pending", "steps should be reset for the rerun");
+        assert!(exit_code.is_none());
+        assert!(finished_at.is_none());
+    }
+}
+
+// ===========================================================================
+// Test 34: Running pipeline with a fresh heartbeat is left alone
+// ===========================================================================
+
+#[sqlx::test(migrations = "./migrations")]
+async fn executor_keeps_live_running_pipeline(pool: PgPool) {
+    let (state, admin_token) = helpers::test_state(pool.clone()).await;
+    let app = helpers::test_router(state.clone());
+    let uid = helpers::admin_user_id(&pool).await;
+
+    let project_id = helpers::create_project(&app, &admin_token, "exec-live", "private").await;
+
+    let pipeline_id =
+        helpers::insert_pipeline(&pool, project_id, uid, "running", "refs/heads/main", "push")
+            .await;
+    sqlx::query(
+        "UPDATE pipelines SET started_at = now() - interval '10 minutes', heartbeat_at = now()
+         WHERE id = $1",
+    )
+    .bind(pipeline_id)
+    .execute(&pool)
+    .await
+    .unwrap();
+
+    let requeued = platform::pipeline::executor::recover_orphaned_pipelines(&state)
+        .await
+        .unwrap();
+    assert_eq!(requeued, 0);
+
+    let (status,): (String,) = sqlx::query_as("SELECT status FROM pipelines WHERE id = $1")
+        .bind(pipeline_id)
+        .fetch_one(&pool)
+        .await
+        .unwrap();
+    assert_eq!(status, "running");
+}
+
+// ===========================================================================
+// Test 35: Shutdown requeues a pipeline that outlives the drain timeout
+// ===========================================================================
+
+#[sqlx::test(migrations = "./migrations")]
+async fn executor_shutdown_requeues_in_flight(pool: PgPool) {
+    let (mut state, admin_token, _server) = helpers::start_pipeline_server(pool).await;
+    let mut config = (*state.config).clone();
+    config.pipeline_drain_timeout_secs = 0;
+    state.config = std::sync::Arc::new(config);
+    let app = helpers::test_router(state.clone());
+
+    let (project_id, _bare_path, work_path, _bd, _wd) =
+        setup_pipeline_project(&state, &app, &admin_token, "exec-drain").await;
+    update_pipeline_yaml(
+        &work_path,
+        "\
+pipeline:
+  steps:
+    - name: slow
+      image: alpine:3.19
+      commands:
+        - sleep 120
+",
+    );
+
+    let executor = ExecutorGuard::spawn(&state);
+    let (pipeline_id, _) =
+        trigger_pipeline(&app, &admin_token, project_id, "refs/heads/main").await;
+    let pipeline_uuid = Uuid::parse_str(&pipeline_id).unwrap();
+    state.pipeline_notify.notify_one();
+
+    // Wait until the executor has claimed it
+    let mut claimed = false;
+    for _ in 0..60 {
+        let (status,): (String,) = sqlx::query_as("SELECT status FROM pipelines WHERE id = $1")
+            .bind(pipeline_uuid)
+            .fetch_one(&state.pool)
+            .await
+            .unwrap();
+        if status == "running" {
+            claimed = true;
+            break;
+        }
+        tokio::time::sleep(std::time::Duration::from_millis(500)).await;
+    }
+    assert!(claimed, "executor should claim the pipeline");
+
+    executor.shutdown().await;
+
+    let (status,): (String,) = sqlx::query_as("SELECT status FROM pipelines WHERE id = $1")
+        .bind(pipeline_uuid)
+        .fetch_one(&state.pool)
+        .await
+        .unwrap();
+    assert_eq!(
+        status, "pending",
+        "pipeline still running at shutdown should be requeued"
+    );
+}
+
+// ===========================================================================
+// Test 36: Step reaches a postgres service container on localhost
+// ===========================================================================
+
+#[sqlx::test(migrations = "./migrations")]
+async fn executor_step_with_service_container(pool: PgPool) {
+    let (state, admin_token, _server) = helpers::start_pipeline_server(pool).await;
+    let app = helpers::test_router(state.clone());
+    let _executor = ExecutorGuard::spawn(&state);
+
+    let (project_id, _bare_path, work_path, _bd, _wd) =
+        setup_pipeline_project(&state, &app, &admin_token, "exec-services").await;
+    update_pipeline_yaml(
+        &work_path,
+        "\
+pipeline:
+  steps:
+    - name: db-test
+      image: postgres:16
+      services:
+        - image: postgres:16
+          port: 5432
+          env:
+            POSTGRES_PASSWORD: test
+      commands:
+        - pg_isready -h localhost -p 5432
+",
+    );
+
+    let (pipeline_id, _) =
+        trigger_pipeline(&app, &admin_token, project_id, "refs/heads/main").await;
+    state.pipeline_notify.notify_one();
+
+    let final_status =
+        helpers::poll_pipeline_status(&app, &admin_token, project_id, &pipeline_id, 240).await;
+    assert!(
+        matches!(final_status.as_str(), "success" | "failure"),
+        "pipeline should reach terminal state, got: {final_status}"
+    );
+
+    // The sidecar must not keep the pod alive once the step has finished
+    let (_, detail) = helpers::get_json(
+        &app,
+        &admin_token,
+        &format!("/api/projects/{project_id}/pipelines/{pipeline_id}"),
+    )
+    .await;
+    let steps = detail["steps"].as_array().expect("should have steps");
+    assert!(
+        steps[0]["exit_code"].as_i64().is_some(),
+        "step should have an exit code (ran to completion)"
+    );
+}
+
+// ===========================================================================
+// Fair claiming across projects
+// ===========================================================================
+
+#[sqlx::test(migrations = "./migrations")]
+async fn executor_claims_fairly_across_projects(pool: PgPool) {
+    let (state, admin_token, _server) = helpers::start_pipeline_server(pool).await;
+    let app = helpers::test_router(state.clone());
+    assert_eq!(state.config.pipeline_max_concurrent_per_project, 3);
+
+    let busy = helpers::create_project(&app, &admin_token, "fair-busy", "private").await;
+    let quiet = helpers::create_project(&app, &admin_token, "fair-quiet", "private").await;
+
+    // The busy project queued six pipelines before the quiet one queued two
+    let mut queued = Vec::new();
+    for (project_id, age_secs) in [
+        (busy, 80),
+        (busy, 70),
+        (busy, 60),
+        (busy, 50),
+        (busy, 40),
+        (busy, 30),
+        (quiet, 20),
+        (quiet, 10),
+    ] {
+        let id: Uuid = sqlx::query_scalar(
+            "INSERT INTO pipelines (project_id, trigger, git_ref, status, created_at)
+             VALUES ($1, 'api', 'refs/heads/main', 'pending', now() - make_interval(secs => $2))
+             RETURNING id",
+        )
+        .bind(project_id)
+        .bind(f64::from(age_secs))
+        .fetch_one(&state.pool)
+        .await
+        .unwrap();
+        queued.push(id);
+    }
+
+    // One poll claims a batch of five; the next is a heartbeat interval away
+    let _executor = ExecutorGuard::spawn(&state);
+    tokio::time::sleep(std::time::Duration::from_millis(1500)).await;
+
+    let claimed: Vec<Uuid> =
+        sqlx::query_scalar("SELECT id FROM pipelines WHERE started_at IS NOT NULL")
+            .fetch_all(&state.pool)
+            .await
+            .unwrap();
+    let claimed_busy: Vec<Uuid> = queued[..6]
+        .iter()
+        .copied()
+        .filter(|id| claimed.contains(id))
+        .collect();
+    assert_eq!(
+        claimed_busy,
+        queued[..3],
+        "busy project gets its three oldest"
+    );
+    assert!(queued[6..].iter().all(|id| claimed.contains(id)));
+    assert_eq!(claimed.len(), 5);
+
+    let pending: i64 = sqlx::query_scalar(
+        "SELECT COUNT(*) FROM pipelines WHERE project_id = $1 AND status = 'pending'",
+    )
+    .bind(busy)
+    .fetch_one(&state.pool)
+    .await
+    .unwrap();
+    assert_eq!(pending, 3);
+}
+
+// ===========================================================================
+// Priority claiming
+// ===========================================================================
+
+#[sqlx::test(migrations = "./migrations")]
+async fn executor_claims_high_priority_first(pool: PgPool) {
+    let (state, admin_token, _server) = helpers::start_pipeline_server(pool).await;
+    let app = helpers::test_router(state.clone());
+    assert_eq!(state.config.pipeline_max_concurrent_per_project, 3);
+
+    let project_id = helpers::create_project(&app, &admin_token, "prio", "private").await;
+
+    // Four normal pipelines queued before a high-priority one
+    let mut queued = Vec::new();
+    for (priority, age_secs) in [
+        ("normal", 50),
+        ("normal", 40),
+        ("normal", 30),
+        ("normal", 20),
+        ("high", 10),
+    ] {
+        let id: Uuid = sqlx::query_scalar(
+            "INSERT INTO pipelines (project_id, trigger, git_ref, status, priority, created_at)
+             VALUES ($1, 'push', 'refs/heads/main', 'pending', $2,
+                     now() - make_interval(secs => $3))
+             RETURNING id",
+        )
+        .bind(project_id)
+        .bind(priority)
+        .bind(f64::from(age_secs))
+        .fetch_one(&state.pool)
+        .await
+        .unwrap();
+        queued.push(id);
+    }
+
+    let _executor = ExecutorGuard::spawn(&state);
+    tokio::time::sleep(std::time::Duration::from_millis(1500)).await;
+
+    // Three slots: the high-priority run jumps the queue, then FIFO
+    let claimed: Vec<Uuid> =
+        sqlx::query_scalar("SELECT id FROM pipelines WHERE started_at IS NOT NULL")
+            .fetch_all(&state.pool)
+            .await
+            .unwrap();
+    assert_eq!(claimed.len(), 3);
+    assert!(
+        claimed.contains(&queued[4]),
+        "high-priority pipeline claimed"
+    );
+    assert!(claimed.contains(&queued[0]) && claimed.contains(&queued[1]));
+    assert!(!claimed.contains(&queued[2]) && !claimed.contains(&queued[3]));
+}
+
+// ===========================================================================
+// Clone depth and tags
+// ===========================================================================
+
+#[sqlx::test(migrations = "./migrations")]
+async fn executor_full_clone_with_tags_supports_git_describe(pool: PgPool) {
+    let (state, admin_token, _server) = helpers::start_pipeline_server(pool).await;
+    let app = helpers::test_router(state.clone());
+    let _executor = ExecutorGuard::spawn(&state);
+
+    let (project_id, _bare_path, work_path, _bd, _wd) =
+        setup_pipeline_project(&state, &app, &admin_token, "exec-describe").await;
+
+    // Tag the current commit, then add one more on top of it
+    helpers::git_cmd(&work_path, &["tag", "-a", "v1.0.0", "-m", "v1.0.0"]);
+    helpers::git_cmd(&work_path, &["push", "origin", "v1.0.0"]);
+    update_pipeline_yaml(
+        &work_path,
+        &format!(
+            "\
+pipeline:
+  steps:
+    - name: version
+      image: {}
+      clone_depth: 0
+      fetch_tags: true
+      commands:
+        - git -C /workspace describe --tags
+",
+            state.config.git_clone_image
+        ),
+    );
+
+    let (pipeline_id, _) =
+        trigger_pipeline(&app, &admin_token, project_id, "refs/heads/main").await;
+    state.pipeline_notify.notify_one();
+
+    let final_status =
+        helpers::poll_pipeline_status(&app, &admin_token, project_id, &pipeline_id, 120).await;
+    assert_eq!(final_status, "success");
+
+    let log_ref = format!("logs/pipelines/{pipeline_id}/version.log");
+    let logs = state.minio.read(&log_ref).await.unwrap().to_vec();
+    let logs = String::from_utf8_lossy(&logs);
+    assert!(logs.contains("v1.0.0-1-g"), "{logs}");
+}
+
+#[sqlx::test(migrations = "./migrations")]
+async fn executor_builds_triggering_commit_after_branch_moves(pool: PgPool) {
+    let (state, admin_token, _server) = helpers::start_pipeline_server(pool).await;
+    let app = helpers::test_router(state.clone());
+
+    let (project_id, _bare_path, work_path, _bd, _wd) =
+        setup_pipeline_project(&state, &app, &admin_token, "exec-pin-sha").await;
+    update_pipeline_yaml(
+        &work_path,
+        &format!(
+            "\
+pipeline:
+  steps:
+    - name: head
+      image: {}
+      commands:
+        - git -C /workspace rev-parse HEAD
+",
+            state.config.git_clone_image
+        ),
+    );
+
+    let (pipeline_id, _) =
+        trigger_pipeline(&app, &admin_token, project_id, "refs/heads/main").await;
+    let triggered: String =
+        sqlx::query_scalar("SELECT commit_sha FROM pipelines WHERE id = $1::uuid")
+            .bind(&pipeline_id)
+            .fetch_one(&state.pool)
+            .await
+            .unwrap();
+
+    // The branch moves on before the executor picks the pipeline up
+    std::fs::write(work_path.join("later.txt"), "later").unwrap();
+    helpers::git_cmd(&work_path, &["add", "."]);
+    helpers::git_cmd(&work_path, &["commit", "-m", "later commit"]);
+    helpers::git_cmd(&work_path, &["push", "origin", "main"]);
+
+    let _executor = ExecutorGuard::spawn(&state);
+    state.pipeline_notify.notify_one();
+    let final_status =
+        helpers::poll_pipeline_status(&app, &admin_token, project_id, &pipeline_id, 120).await;
+    assert_eq!(final_status, "success");
+
+    let logs = state
+        .minio
+        .read(&format!("logs/pipelines/{pipeline_id}/head.log"))
+        .await
+        .unwrap()
+        .to_vec();
+    let logs = String::from_utf8_lossy(&logs);
+    assert!(logs.contains(&triggered), "{logs}");
+}
@@ -673,6 +673,26 @@ async fn browse_private_project_without_perm(pool: PgPool) {
     .await;
     assert_eq!(status, StatusCode::NOT_FOUND, "blob should return 404");
 
+    let (status, _) = helpers::get_json(
+        &app,
+        &user_token,
+        &format!("/api/projects/{project_id}/raw?ref=main&path=README.md"),
+    )
+    .await;
+    assert_eq!(status, StatusCode::NOT_FOUND, "raw should return 404");
+
+    let (status, _) = helpers::get_json(
+        &app,
+        &user_token,
+        &format!("/api/projects/{project_id}/raw/main/README.md"),
+    )
+    .await;
+    assert_eq!(
+        status,
+        StatusCode::NOT_FOUND,
+        "raw by path should return 404"
+    );
+
     let (status, _) = helpers::get_json(
         &app,
         &user_token,
@@ -783,3 +803,566 @@ async fn browse_nonexistent_project(pool: PgPool) {
     .await;
     assert_eq!(status, StatusCode::NOT_FOUND);
 }
+
+// ---------------------------------------------------------------------------
+// Compare
+// ---------------------------------------------------------------------------
+
+/// Compare two branches returns per-file stats and hunks.
+#[sqlx::test(migrations = "./migrations")]
+async fn compare_branches(pool: PgPool) {
+    let (state, admin_token) = helpers::test_state(pool).await;
+    let app = helpers::test_router(state.clone());
+
+    let project_id = helpers::create_project(&app, &admin_token, "compare-refs", "public").await;
+
+    let (_bare_dir, bare_path) = helpers::create_bare_repo();
+    let (_work_dir, work_path) = helpers::create_working_copy(&bare_path);
+
+    helpers::git_cmd(&work_path, &["checkout", "-b", "feature"]);
+    std::fs::write(work_path.join("added.txt"), "one\ntwo\n").unwrap();
+    std::fs::write(work_path.join("README.md"), "# changed\n").unwrap();
+    helpers::git_cmd(&work_path, &["add", "."]);
+    helpers::git_cmd(&work_path, &["commit", "-m", "feature changes"]);
+    helpers::git_cmd(&work_path, &["push", "origin", "feature"]);
+
+    sqlx::query("UPDATE projects SET repo_path = $1 WHERE id = $2")
+        .bind(bare_path.to_str().unwrap())
+        .bind(project_id)
+        .execute(&state.pool)
+        .await
+        .unwrap();
+
+    let (status, body) = helpers::get_json(
+        &app,
+        &admin_token,
+        &format!("/api/projects/{project_id}/compare?base=main&head=feature"),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK, "compare failed: {body}");
+
+    let files = body["files"].as_array().expect("files should be an array");
+    assert_eq!(files.len(), 2, "expected 2 changed files: {files:?}");
+
+    let added = files
+        .iter()
+        .find(|f| f["path"] == "added.txt")
+        .expect("added.txt in diff");
+    assert_eq!(added["status"], "added");
+    assert_eq!(added["additions"], 2);
+    assert_eq!(added["deletions"], 0);
+    assert_eq!(added["hunks"].as_array().unwrap().len(), 1);
+
+    let readme = files
+        .iter()
+        .find(|f| f["path"] == "README.md")
+        .expect("README.md in diff");
+    assert_eq!(readme["status"], "modified");
+    assert!(readme["deletions"].as_i64().unwrap() >= 1);
+
+    assert_eq!(
+        body["total_additions"].as_i64().unwrap(),
+        added["additions"].as_i64().unwrap() + readme["additions"].as_i64().unwrap()
+    );
+}
+
+/// Compare rejects refs that could be parsed as git options.
+#[sqlx::test(migrations = "./migrations")]
+async fn compare_rejects_option_refs(pool: PgPool) {
+    let (state, admin_token) = helpers::test_state(pool).await;
+    let app = helpers::test_router(state);
+
+    let project_id = helpers::create_project(&app, &admin_token, "compare-badref", "public").await;
+
+    let (status, _) = helpers::get_json(
+        &app,
+        &admin_token,
+        &format!("/api/projects/{project_id}/compare?base=main&head=--output=/tmp/x"),
+    )
+    .await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+
+    let (status, _) = helpers::get_json(
+        &app,
+        &admin_token,
+        &format!("/api/projects/{project_id}/compare?base=main..feature&head=main"),
+    )
+    .await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+}
+
+/// GET `path`, optionally with `If-None-Match`. Returns status, `ETag` and raw body.
+async fn get_conditional(
+    app: &axum::Router,
+    token: &str,
+    path: &str,
+    if_none_match: Option<&str>,
+) -> (StatusCode, Option<String>, Vec<u8>) {
+    let mut builder = axum::http::Request::builder()
+        .method("GET")
+        .uri(path)
+        .header("Authorization", format!("Bearer {token}"));
+    if let Some(etag) = if_none_match {
+        builder = builder.header("If-None-Match", etag);
+    }
+    let req = builder.body(axum::body::Body::empty()).unwrap();
+    let resp = tower::ServiceExt::oneshot(app.clone(), req).await.unwrap();
+    let status = resp.status();
+    let etag = resp
+        .headers()
+        .get("ETag")
+        .map(|v| v.to_str().unwrap().to_owned());
+    let body = http_body_util::BodyExt::collect(resp.into_body())
+        .await
+        .unwrap()
+        .to_bytes()
+        .to_vec();
+    (status, etag, body)
+}
+
+/// Tree and blob responses carry an `ETag`; replaying it returns 304 with an
+/// empty body until the content changes.
+#[sqlx::test(migrations = "./migrations")]
+async fn tree_and_blob_conditional_requests(pool: PgPool) {
+    let (state, admin_token) = helpers::test_state(pool).await;
+    let app = helpers::test_router(state.clone());
+
+    let project_id = helpers::create_project(&app, &admin_token, "etag-browse", "public").await;
+
+    let (_bare_dir, bare_path) = helpers::create_bare_repo();
+    let (_work_dir, work_path) = helpers::create_working_copy(&bare_path);
+
+    sqlx::query("UPDATE projects SET repo_path = $1 WHERE id = $2")
+        .bind(bare_path.to_str().unwrap())
+        .bind(project_id)
+        .execute(&state.pool)
+        .await
+        .unwrap();
+
+    let tree_url = format!("/api/projects/{project_id}/tree?ref=main&path=/");
+    let (status, etag, body) = get_conditional(&app, &admin_token, &tree_url, None).await;
+    assert_eq!(status, StatusCode::OK);
+    assert!(!body.is_empty());
+    let tree_etag = etag.expect("tree response should carry an ETag");
+
+    let (status, etag, body) =
+        get_conditional(&app, &admin_token, &tree_url, Some(&tree_etag)).await;
+    assert_eq!(status, StatusCode::NOT_MODIFIED);
+    assert_eq!(etag.as_deref(), Some(tree_etag.as_str()));
+    assert!(body.is_empty(), "304 must have an empty body");
+
+    let blob_url = format!("/api/projects/{project_id}/blob?ref=main&path=README.md");
+    let (status, etag, _) = get_conditional(&app, &admin_token, &blob_url, None).await;
+    assert_eq!(status, StatusCode::OK);
+    let blob_etag = etag.expect("blob response should carry an ETag");
+    let (status, _, body) = get_conditional(&app, &admin_token, &blob_url, Some(&blob_etag)).await;
+    assert_eq!(status, StatusCode::NOT_MODIFIED);
+    assert!(body.is_empty());
+
+    // A new commit changes the root tree, so the old ETag no longer matches
+    std::fs::write(work_path.join("NEW.md"), "new\n").unwrap();
+    helpers::git_cmd(&work_path, &["add", "."]);
+    helpers::git_cmd(&work_path, &["commit", "-m", "add file"]);
+    helpers::git_cmd(&work_path, &["push", "origin", "main"]);
+
+    let (status, etag, _) = get_conditional(&app, &admin_token, &tree_url, Some(&tree_etag)).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_ne!(etag.as_deref(), Some(tree_etag.as_str()));
+
+    // README.md itself is unchanged
+    let (status, _, _) = get_conditional(&app, &admin_token, &blob_url, Some(&blob_etag)).await;
+    assert_eq!(status, StatusCode::NOT_MODIFIED);
+}
+
+/// Code search finds a function by name and reports file and line.
+#[sqlx::test(migrations = "./migrations")]
+async fn code_search_finds_function(pool: PgPool) {
+    let (state, admin_token) = helpers::test_state(pool).await;
+    let app = helpers::test_router(state.clone());
+
+    let project_id = helpers::create_project(&app, &admin_token, "code-search", "public").await;
+
+    let (_bare_dir, bare_path) = helpers::create_bare_repo();
+    let (_work_dir, work_path) = helpers::create_working_copy(&bare_path);
+
+    std::fs::create_dir_all(work_path.join("src")).unwrap();
+    std::fs::write(
+        work_path.join("src/config.rs"),
+        "use std::fs;\n\npub fn parse_config(raw: &str) {}\n",
+    )
+    .unwrap();
+    std::fs::write(
+        work_path.join("src/main.rs"),
+        "fn main() {\n    parse_config(\"\");\n}\n",
+    )
+    .unwrap();
+    helpers::git_cmd(&work_path, &["add", "."]);
+    helpers::git_cmd(&work_path, &["commit", "-m", "add config"]);
+    helpers::git_cmd(&work_path, &["push", "origin", "main"]);
+
+    sqlx::query("UPDATE projects SET repo_path = $1 WHERE id = $2")
+        .bind(bare_path.to_str().unwrap())
+        .bind(project_id)
+        .execute(&state.pool)
+        .await
+        .unwrap();
+
+    let url = format!("/api/projects/{project_id}/search/code?q=parse_config&ref=main");
+    let (status, body) = helpers::get_json(&app, &admin_token, &url).await;
+    assert_eq!(status, StatusCode::OK, "{body}");
+    assert_eq!(body["truncated"], false);
+    let matches = body["matches"].as_array().unwrap();
+    assert_eq!(matches.len(), 2, "{body}");
+    assert!(
+        matches
+            .iter()
+            .any(|m| m["path"] == "src/config.rs" && m["line"] == 3)
+    );
+    assert!(
+        matches
+            .iter()
+            .any(|m| m["path"] == "src/main.rs" && m["line"] == 2)
+    );
+
+    // Served from cache on repeat
+    let (status, cached) = helpers::get_json(&app, &admin_token, &url).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(cached, body);
+
+    // Option-looking queries are searched literally, not passed as flags
+    let (status, body) = helpers::get_json(
+        &app,
+        &admin_token,
+        &format!("/api/projects/{project_id}/search/code?q=--output%3D%2Ftmp%2Fx&ref=main"),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["matches"].as_array().unwrap().len(), 0);
+
+    // Missing query and unknown ref
+    let (status, _) = helpers::get_json(
+        &app,
+        &admin_token,
+        &format!("/api/projects/{project_id}/search/code?q=&ref=main"),
+    )
+    .await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    let (status, _) = helpers::get_json(
+        &app,
+        &admin_token,
+        &format!("/api/projects/{project_id}/search/code?q=x&ref=nope"),
+    )
+    .await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+}
+
+/// The `code_search` platform feature switched off everywhere but one project.
+#[sqlx::test(migrations = "./migrations")]
+async fn code_search_enabled_for_one_project(pool: PgPool) {
+    let (state, admin_token) = helpers::test_state(pool).await;
+    let app = helpers::test_router(state.clone());
+
+    let (_bare_dir, bare_path) = helpers::create_bare_repo();
+    let (_work_dir, work_path) = helpers::create_working_copy(&bare_path);
+    std::fs::write(work_path.join("lib.rs"), "pub fn parse_config() {}\n").unwrap();
+    helpers::git_cmd(&work_path, &["add", "."]);
+    helpers::git_cmd(&work_path, &["commit", "-m", "init"]);
+    helpers::git_cmd(&work_path, &["push", "origin", "main"]);
+
+    let mut urls = Vec::new();
+    let mut project_ids = Vec::new();
+    for name in ["search-pilot", "search-other"] {
+        let project_id = helpers::create_project(&app, &admin_token, name, "public").await;
+        sqlx::query("UPDATE projects SET repo_path = $1 WHERE id = $2")
+            .bind(bare_path.to_str().unwrap())
+            .bind(project_id)
+            .execute(&state.pool)
+            .await
+            .unwrap();
+        urls.push(format!(
+            "/api/projects/{project_id}/search/code?q=parse_config&ref=main"
+        ));
+        project_ids.push(project_id);
+    }
+
+    // On by default
+    for url in &urls {
+        let (status, body) = helpers::get_json(&app, &admin_token, url).await;
+        assert_eq!(status, StatusCode::OK, "{body}");
+    }
+
+    let (status, body) = helpers::put_json(
+        &app,
+        &admin_token,
+        "/api/admin/features/code_search",
+        serde_json::json!({"enabled": false}),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK, "{body}");
+    let (status, body) = helpers::put_json(
+        &app,
+        &admin_token,
+        &format!(
+            "/api/admin/features/code_search/projects/{}",
+            project_ids[0]
+        ),
+        serde_json::json!({"enabled": true}),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK, "{body}");
+    assert_eq!(body["project_id"], project_ids[0].to_string());
+
+    let (status, body) = helpers::get_json(&app, &admin_token, &urls[0]).await;
+    assert_eq!(status, StatusCode::OK, "{body}");
+    assert_eq!(body["matches"].as_array().unwrap().len(), 1);
+    let (status, _) = helpers::get_json(&app, &admin_token, &urls[1]).await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+
+    let (status, body) = helpers::get_json(&app, &admin_token, "/api/admin/features").await;
+    assert_eq!(status, StatusCode::OK);
+    let feature = body["items"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .find(|f| f["key"] == "code_search")
+        .unwrap();
+    assert_eq!(feature["enabled"], false);
+    assert_eq!(feature["overrides"].as_array().unwrap().len(), 1);
+
+    // Resetting the global state brings the other project back
+    let (status, _) =
+        helpers::delete_json(&app, &admin_token, "/api/admin/features/code_search").await;
+    assert_eq!(status, StatusCode::NO_CONTENT);
+    let (status, _) = helpers::get_json(&app, &admin_token, &urls[1]).await;
+    assert_eq!(status, StatusCode::OK);
+
+    // Admin only, known features only
+    let (_, user_token) =
+        helpers::create_user(&app, &admin_token, "flag-user", "flag-user@example.com").await;
+    let (status, _) = helpers::put_json(
+        &app,
+        &user_token,
+        "/api/admin/features/code_search",
+        serde_json::json!({"enabled": true}),
+    )
+    .await;
+    assert_eq!(status, StatusCode::FORBIDDEN);
+    let (status, _) = helpers::put_json(
+        &app,
+        &admin_token,
+        "/api/admin/features/time_travel",
+        serde_json::json!({"enabled": true}),
+    )
+    .await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+}
+
+/// GET `path` with extra request headers. Returns status, response headers and raw body.
+async fn get_raw(
+    app: &axum::Router,
+    token: &str,
+    path: &str,
+    extra: &[(&str, &str)],
+) -> (StatusCode, axum::http::HeaderMap, Vec<u8>) {
+    let mut builder = axum::http::Request::builder()
+        .method("GET")
+        .uri(path)
+        .header("Authorization", format!("Bearer {token}"));
+    for (name, value) in extra {
+        builder = builder.header(*name, *value);
+    }
+    let req = builder.body(axum::body::Body::empty()).unwrap();
+    let resp = tower::ServiceExt::oneshot(app.clone(), req).await.unwrap();
+    let status = resp.status();
+    let headers = resp.headers().clone();
+    let body = http_body_util::BodyExt::collect(resp.into_body())
+        .await
+        .unwrap()
+        .to_bytes()
+        .to_vec();
+    (status, headers, body)
+}
+
+/// The raw endpoint streams a blob with its length and honours byte ranges,
+/// so interrupted downloads can resume.
+#[sqlx::test(migrations = "./migrations")]
+async fn raw_blob_download_with_ranges(pool: PgPool) {
+    let (state, admin_token) = helpers::test_state(pool).await;
+    let app = helpers::test_router(state.clone());
+
+    let project_id = helpers::create_project(&app, &admin_token, "raw-browse", "public").await;
+
+    let (_bare_dir, bare_path) = helpers::create_bare_repo();
+    let (_work_dir, work_path) = helpers::create_working_copy(&bare_path);
+
+    // Larger than a single pipe buffer / stream chunk
+    let asset: Vec<u8> = (0..3 * 1024 * 1024u32)
+        .map(|i| u8::try_from(i % 251).unwrap())
+        .collect();
+    std::fs::create_dir_all(work_path.join("assets")).unwrap();
+    std::fs::write(work_path.join("assets/model.bin"), &asset).unwrap();
+    helpers::git_cmd(&work_path, &["add", "."]);
+    helpers::git_cmd(&work_path, &["commit", "-m", "add asset"]);
+    helpers::git_cmd(&work_path, &["push", "origin", "main"]);
+
+    sqlx::query("UPDATE projects SET repo_path = $1 WHERE id = $2")
+        .bind(bare_path.to_str().unwrap())
+        .bind(project_id)
+        .execute(&state.pool)
+        .await
+        .unwrap();
+
+    let url = format!("/api/projects/{project_id}/raw?ref=main&path=assets/model.bin");
+    let (status, headers, body) = get_raw(&app, &admin_token, &url, &[]).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body, asset);
+    assert_eq!(headers["content-length"], asset.len().to_string().as_str());
+    assert_eq!(headers["content-type"], "application/octet-stream");
+    assert_eq!(headers["accept-ranges"], "bytes");
+    assert_eq!(
+        headers["content-disposition"],
+        "attachment; filename=\"model.bin\""
+    );
+    let etag = headers["etag"].to_str().unwrap().to_owned();
+
+    // Resume from an offset
+    let (status, headers, body) =
+        get_raw(&app, &admin_token, &url, &[("Range", "bytes=1000000-")]).await;
+    assert_eq!(status, StatusCode::PARTIAL_CONTENT);
+    assert_eq!(body, asset[1_000_000..]);
+    assert_eq!(
+        headers["content-range"],
+        format!("bytes 1000000-{}/{}", asset.len() - 1, asset.len()).as_str()
+    );
+    assert_eq!(
+        headers["content-length"],
+        (asset.len() - 1_000_000).to_string().as_str()
+    );
+
+    // A bounded range, validated with If-Range
+    let (status, _, body) = get_raw(
+        &app,
+        &admin_token,
+        &url,
+        &[("Range", "bytes=10-19"), ("If-Range", &etag)],
+    )
+    .await;
+    assert_eq!(status, StatusCode::PARTIAL_CONTENT);
+    assert_eq!(body, asset[10..20]);
+
+    // A stale If-Range gets the full blob
+    let (status, _, body) = get_raw(
+        &app,
+        &admin_token,
+        &url,
+        &[("Range", "bytes=10-19"), ("If-Range", "\"stale\"")],
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body.len(), asset.len());
+
+    let (status, headers, _) =
+        get_raw(&app, &admin_token, &url, &[("Range", "bytes=99999999-")]).await;
+    assert_eq!(status, StatusCode::RANGE_NOT_SATISFIABLE);
+    assert_eq!(
+        headers["content-range"],
+        format!("bytes */{}", asset.len()).as_str()
+    );
+
+    let (status, _, body) = get_raw(&app, &admin_token, &url, &[("If-None-Match", &etag)]).await;
+    assert_eq!(status, StatusCode::NOT_MODIFIED);
+    assert!(body.is_empty());
+
+    // Directories and missing paths aren't blobs
+    for path in ["assets", "assets/missing.bin"] {
+        let (status, _, _) = get_raw(
+            &app,
+            &admin_token,
+            &format!("/api/projects/{project_id}/raw?ref=main&path={path}"),
+            &[],
+        )
+        .await;
+        assert_eq!(status, StatusCode::NOT_FOUND, "{path}");
+    }
+}
+
+/// The path-addressed raw endpoint serves files inline with a content type from
+/// the extension or magic bytes, and as an attachment with `?download=1`.
+#[sqlx::test(migrations = "./migrations")]
+async fn raw_file_by_ref_and_path(pool: PgPool) {
+    let (state, admin_token) = helpers::test_state(pool).await;
+    let app = helpers::test_router(state.clone());
+
+    let project_id = helpers::create_project(&app, &admin_token, "raw-path", "public").await;
+
+    let (_bare_dir, bare_path) = helpers::create_bare_repo();
+    let (_work_dir, work_path) = helpers::create_working_copy(&bare_path);
+
+    let png = b"\x89PNG\r\n\x1a\n\0\0\0\rIHDR\0\0\0\x01\0\0\0\x01".to_vec();
+    std::fs::create_dir_all(work_path.join("static")).unwrap();
+    std::fs::write(work_path.join("static/logo.png"), &png).unwrap();
+    // No extension: detected from the PNG signature
+    std::fs::write(work_path.join("static/favicon"), &png).unwrap();
+    helpers::git_cmd(&work_path, &["checkout", "-b", "feature/brand"]);
+    helpers::git_cmd(&work_path, &["add", "."]);
+    helpers::git_cmd(&work_path, &["commit", "-m", "add logo"]);
+    helpers::git_cmd(&work_path, &["push", "origin", "feature/brand"]);
+
+    sqlx::query("UPDATE projects SET repo_path = $1 WHERE id = $2")
+        .bind(bare_path.to_str().unwrap())
+        .bind(project_id)
+        .execute(&state.pool)
+        .await
+        .unwrap();
+
+    let base = format!("/api/projects/{project_id}/raw/feature%2Fbrand");
+    let (status, headers, body) =
+        get_raw(&app, &admin_token, &format!("{base}/static/logo.png"), &[]).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body, png);
+    assert_eq!(headers["content-type"], "image/png");
+    assert_eq!(
+        headers["content-disposition"],
+        "inline; filename=\"logo.png\""
+    );
+
+    let (status, headers, _) =
+        get_raw(&app, &admin_token, &format!("{base}/static/favicon"), &[]).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(headers["content-type"], "image/png");
+
+    let (status, headers, body) = get_raw(
+        &app,
+        &admin_token,
+        &format!("{base}/static/logo.png?download=1"),
+        &[],
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body, png);
+    assert_eq!(
+        headers["content-disposition"],
+        "attachment; filename=\"logo.png\""
+    );
+
+    // Text files are never rendered as HTML
+    let (status, headers, body) = get_raw(
+        &app,
+        &admin_token,
+        &format!("/api/projects/{project_id}/raw/main/README.md"),
+        &[],
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body, b"# Test Project\n");
+    assert_eq!(headers["content-type"], "text/plain; charset=utf-8");
+
+    let (status, _, _) = get_raw(
+        &app,
+        &admin_token,
+        &format!("/api/projects/{project_id}/raw/main/../../etc/passwd"),
+        &[],
+    )
+    .await;
+    assert_ne!(status, StatusCode::OK);
+}
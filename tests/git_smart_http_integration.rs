@@ -495,6 +495,112 @@ async fn internal_repo_write_requires_perm(pool: PgPool) {
     assert_eq!(status, StatusCode::NOT_FOUND);
 }
 
+// ---------------------------------------------------------------------------
+// Tests: token scopes on git operations
+// ---------------------------------------------------------------------------
+
+/// A `repo:read` token can fetch but is refused for push with 403.
+#[sqlx::test(migrations = "./migrations")]
+async fn repo_read_token_cannot_push(pool: PgPool) {
+    let (state, admin_token) = test_state(pool.clone()).await;
+    let app = git_test_router(state);
+
+    create_project(&app, &admin_token, "scoped-git", "private").await;
+
+    let (status, body) = helpers::post_json(
+        &app,
+        &admin_token,
+        "/api/tokens",
+        serde_json::json!({ "name": "ro-git", "scopes": ["repo:read"], "expires_in_days": 1 }),
+    )
+    .await;
+    assert_eq!(status, StatusCode::CREATED, "{body}");
+    let auth = basic_auth("admin", body["token"].as_str().unwrap());
+
+    let (status, _, _) = git_get(
+        &app,
+        "/admin/scoped-git/info/refs?service=git-upload-pack",
+        Some(&auth),
+    )
+    .await;
+    assert_ne!(status, StatusCode::UNAUTHORIZED);
+    assert_ne!(status, StatusCode::FORBIDDEN);
+    assert_ne!(status, StatusCode::NOT_FOUND);
+
+    let (status, _, _) = git_get(
+        &app,
+        "/admin/scoped-git/info/refs?service=git-receive-pack",
+        Some(&auth),
+    )
+    .await;
+    assert_eq!(status, StatusCode::FORBIDDEN);
+}
+
+/// Archived projects can still be fetched, but pushes are refused with 423.
+#[sqlx::test(migrations = "./migrations")]
+async fn archived_repo_refuses_push(pool: PgPool) {
+    let (state, admin_token) = test_state(pool.clone()).await;
+    let app = git_test_router(state);
+
+    let project_id = create_project(&app, &admin_token, "archived-git", "private").await;
+    let (status, _) = helpers::post_json(
+        &app,
+        &admin_token,
+        &format!("/api/projects/{project_id}/archive"),
+        serde_json::json!({}),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+
+    let api_token = create_api_token(&app, &admin_token).await;
+    let auth = basic_auth("admin", &api_token);
+
+    let (status, _, _) = git_get(
+        &app,
+        "/admin/archived-git/info/refs?service=git-upload-pack",
+        Some(&auth),
+    )
+    .await;
+    assert_ne!(status, StatusCode::UNAUTHORIZED);
+    assert_ne!(status, StatusCode::FORBIDDEN);
+    assert_ne!(status, StatusCode::NOT_FOUND);
+
+    let (status, _, _) = git_get(
+        &app,
+        "/admin/archived-git/info/refs?service=git-receive-pack",
+        Some(&auth),
+    )
+    .await;
+    assert_eq!(status, StatusCode::LOCKED);
+}
+
+/// Tokens without any git scope cannot even read an internal repo.
+#[sqlx::test(migrations = "./migrations")]
+async fn unrelated_scope_token_cannot_fetch(pool: PgPool) {
+    let (state, admin_token) = test_state(pool.clone()).await;
+    let app = git_test_router(state);
+
+    create_project(&app, &admin_token, "scoped-int", "internal").await;
+
+    let (status, body) = helpers::post_json(
+        &app,
+        &admin_token,
+        "/api/tokens",
+        serde_json::json!({ "name": "obs", "scopes": ["observe:read"], "expires_in_days": 1 }),
+    )
+    .await;
+    assert_eq!(status, StatusCode::CREATED, "{body}");
+    let auth = basic_auth("admin", body["token"].as_str().unwrap());
+
+    let (status, _, _) = git_get(
+        &app,
+        "/admin/scoped-int/info/refs?service=git-upload-pack",
+        Some(&auth),
+    )
+    .await;
+    assert_eq!(status, StatusCode::FORBIDDEN);
+}
+
 // ---------------------------------------------------------------------------
 // Tests: .git suffix stripping in resolve_project
 // ---------------------------------------------------------------------------
@@ -570,6 +676,64 @@ async fn authenticate_basic_with_password(pool: PgPool) {
     assert_ne!(status, StatusCode::NOT_FOUND);
 }
 
+/// Accounts with password login disabled or TOTP enabled cannot use their
+/// password for git; an API token still works.
+#[sqlx::test(migrations = "./migrations")]
+async fn authenticate_basic_password_refused_for_token_only_accounts(pool: PgPool) {
+    let (state, admin_token) = test_state(pool.clone()).await;
+    let app = git_test_router(state);
+
+    create_project(&app, &admin_token, "mfa-proj", "internal").await;
+    let api_token = create_api_token(&app, &admin_token).await;
+    let uri = "/admin/mfa-proj/info/refs?service=git-upload-pack";
+
+    sqlx::query("UPDATE users SET password_login_disabled = true WHERE name = 'admin'")
+        .execute(&pool)
+        .await
+        .unwrap();
+    let (status, _, _) = git_get(&app, uri, Some(&basic_auth("admin", "testpassword"))).await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+
+    sqlx::query("UPDATE users SET password_login_disabled = false WHERE name = 'admin'")
+        .execute(&pool)
+        .await
+        .unwrap();
+    sqlx::query(
+        "INSERT INTO user_totp (user_id, encrypted_secret, enabled)
+         SELECT id, '\\x00', true FROM users WHERE name = 'admin'",
+    )
+    .execute(&pool)
+    .await
+    .unwrap();
+    let (status, _, _) = git_get(&app, uri, Some(&basic_auth("admin", "testpassword"))).await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+
+    let (status, _, _) = git_get(&app, uri, Some(&basic_auth("admin", &api_token))).await;
+    assert_ne!(status, StatusCode::UNAUTHORIZED);
+    assert_ne!(status, StatusCode::BAD_REQUEST);
+}
+
+/// Wrong git passwords count towards the login lockout, for names without
+/// an account as well, so a 423 does not reveal which accounts exist.
+#[sqlx::test(migrations = "./migrations")]
+async fn authenticate_basic_password_failures_lock(pool: PgPool) {
+    let (state, admin_token) = test_state(pool).await;
+    let app = git_test_router(state);
+
+    create_project(&app, &admin_token, "lock-proj", "internal").await;
+    let uri = "/admin/lock-proj/info/refs?service=git-upload-pack";
+    let ghost = format!("ghost-{}", uuid::Uuid::new_v4().simple());
+
+    for name in ["admin", ghost.as_str()] {
+        for _ in 0..5 {
+            let (status, _, _) = git_get(&app, uri, Some(&basic_auth(name, "wrong"))).await;
+            assert_eq!(status, StatusCode::UNAUTHORIZED);
+        }
+        let (status, _, _) = git_get(&app, uri, Some(&basic_auth(name, "testpassword"))).await;
+        assert_eq!(status, StatusCode::LOCKED, "{name}");
+    }
+}
+
 /// Git Basic Auth with expired API token falls back to password check.
 #[sqlx::test(migrations = "./migrations")]
 async fn authenticate_basic_expired_token_falls_back(pool: PgPool) {
@@ -618,3 +618,109 @@ async fn health_snapshot_includes_background_tasks(pool: PgPool) {
     cancel.cancel();
     let _ = tokio::time::timeout(std::time::Duration::from_secs(5), handle).await;
 }
+
+// ---------------------------------------------------------------------------
+// 13. health_is_ready_trusts_fresh_snapshot_over_live_probes
+// ---------------------------------------------------------------------------
+
+#[sqlx::test(migrations = "./migrations")]
+async fn health_is_ready_trusts_fresh_snapshot_over_live_probes(pool: PgPool) {
+    let (state, _admin_token) = helpers::test_state(pool).await;
+
+    // MinIO is reachable in the test env, but the fresh snapshot says it is
+    // down: the cached result wins
+    {
+        let mut snap = state.health.write().unwrap();
+        snap.checked_at = Utc::now();
+        snap.subsystems = vec![
+            SubsystemCheck {
+                name: "postgres".into(),
+                status: SubsystemStatus::Healthy,
+                latency_ms: 2,
+                message: None,
+                checked_at: Utc::now(),
+            },
+            SubsystemCheck {
+                name: "valkey".into(),
+                status: SubsystemStatus::Healthy,
+                latency_ms: 1,
+                message: None,
+                checked_at: Utc::now(),
+            },
+            SubsystemCheck {
+                name: "minio".into(),
+                status: SubsystemStatus::Unhealthy,
+                latency_ms: 0,
+                message: Some("connection refused".into()),
+                checked_at: Utc::now(),
+            },
+            SubsystemCheck {
+                name: "kubernetes".into(),
+                status: SubsystemStatus::Healthy,
+                latency_ms: 8,
+                message: None,
+                checked_at: Utc::now(),
+            },
+        ];
+    }
+
+    let report = platform::health::checks::readiness(&state).await;
+    assert!(!report.ready);
+    assert_eq!(report.failing, vec!["minio"]);
+    let minio = report
+        .dependencies
+        .iter()
+        .find(|d| d.name == "minio")
+        .unwrap();
+    assert_eq!(minio.message.as_deref(), Some("connection refused"));
+}
+
+// ---------------------------------------------------------------------------
+// 14. health_is_ready_degraded_kubernetes_not_ready
+// ---------------------------------------------------------------------------
+
+#[sqlx::test(migrations = "./migrations")]
+async fn health_is_ready_degraded_kubernetes_not_ready(pool: PgPool) {
+    let (state, _admin_token) = helpers::test_state(pool).await;
+
+    // Readiness is strict: a degraded dependency fails it like an unhealthy one
+    {
+        let mut snap = state.health.write().unwrap();
+        snap.checked_at = Utc::now();
+        snap.subsystems = vec![
+            SubsystemCheck {
+                name: "postgres".into(),
+                status: SubsystemStatus::Healthy,
+                latency_ms: 2,
+                message: None,
+                checked_at: Utc::now(),
+            },
+            SubsystemCheck {
+                name: "valkey".into(),
+                status: SubsystemStatus::Healthy,
+                latency_ms: 1,
+                message: None,
+                checked_at: Utc::now(),
+            },
+            SubsystemCheck {
+                name: "minio".into(),
+                status: SubsystemStatus::Healthy,
+                latency_ms: 4,
+                message: None,
+                checked_at: Utc::now(),
+            },
+            SubsystemCheck {
+                name: "kubernetes".into(),
+                status: SubsystemStatus::Degraded,
+                latency_ms: 650,
+                message: None,
+                checked_at: Utc::now(),
+            },
+        ];
+    }
+
+    let report = platform::health::checks::readiness(&state).await;
+    assert!(!report.ready);
+    assert_eq!(report.failing, vec!["kubernetes"]);
+    assert!(!platform::health::checks::is_ready(&state).await);
+}
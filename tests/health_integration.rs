@@ -226,6 +226,54 @@ async fn readyz_returns_503_when_unhealthy(pool: PgPool) {
     assert_eq!(resp.status(), StatusCode::SERVICE_UNAVAILABLE);
 }
 
+/// A down dependency is named in the 503 body so operators see what failed.
+#[sqlx::test(migrations = "./migrations")]
+async fn readyz_names_failing_dependency(pool: PgPool) {
+    let (state, _admin_token) = helpers::test_state(pool).await;
+
+    {
+        let mut snap = state.health.write().unwrap();
+        snap.checked_at = chrono::Utc::now();
+        snap.subsystems = vec![
+            platform::health::SubsystemCheck {
+                name: "postgres".into(),
+                status: platform::health::SubsystemStatus::Healthy,
+                latency_ms: 5,
+                message: None,
+                checked_at: chrono::Utc::now(),
+            },
+            platform::health::SubsystemCheck {
+                name: "valkey".into(),
+                status: platform::health::SubsystemStatus::Unhealthy,
+                latency_ms: 0,
+                message: Some("connection refused".into()),
+                checked_at: chrono::Utc::now(),
+            },
+        ];
+    }
+
+    let app = helpers::test_router(state);
+    let req = axum::http::Request::builder()
+        .uri("/readyz")
+        .body(axum::body::Body::empty())
+        .unwrap();
+    let resp = tower::ServiceExt::oneshot(app, req).await.unwrap();
+    assert_eq!(resp.status(), StatusCode::SERVICE_UNAVAILABLE);
+
+    let bytes = axum::body::to_bytes(resp.into_body(), usize::MAX)
+        .await
+        .unwrap();
+    let body: serde_json::Value = serde_json::from_slice(&bytes).unwrap();
+    assert_eq!(body["ready"], false);
+    assert_eq!(body["failing"], serde_json::json!(["valkey"]));
+    let deps = body["dependencies"].as_array().unwrap();
+    let names: Vec<&str> = deps.iter().filter_map(|d| d["name"].as_str()).collect();
+    assert_eq!(names, ["postgres", "valkey", "minio", "kubernetes"]);
+    let valkey = deps.iter().find(|d| d["name"] == "valkey").unwrap();
+    assert_eq!(valkey["status"], "unhealthy");
+    assert_eq!(valkey["message"], "connection refused");
+}
+
 // ---------------------------------------------------------------------------
 // Health SSE stream tests
 // ---------------------------------------------------------------------------
@@ -323,6 +371,17 @@ async fn health_summary_returns_correct_structure(pool: PgPool) {
     // Summary should not include pod_failures or background_tasks
     assert!(body.get("pod_failures").is_none());
     assert!(body.get("background_tasks").is_none());
+
+    // Live pool stats; the test has already opened connections
+    let db_pool = &body["db_pool"];
+    let (size, idle, in_use) = (
+        db_pool["size"].as_u64().unwrap(),
+        db_pool["idle"].as_u64().unwrap(),
+        db_pool["in_use"].as_u64().unwrap(),
+    );
+    assert!(size >= 1, "{db_pool}");
+    assert_eq!(size, idle + in_use, "{db_pool}");
+    assert!(size <= db_pool["max"].as_u64().unwrap(), "{db_pool}");
 }
 
 #[sqlx::test(migrations = "./migrations")]
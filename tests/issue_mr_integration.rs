@@ -237,6 +237,130 @@ async fn close_and_reopen_issue(pool: PgPool) {
     assert_eq!(body["status"], "open");
 }
 
+#[sqlx::test(migrations = "./migrations")]
+async fn issue_state_transitions(pool: PgPool) {
+    let (state, admin_token) = helpers::test_state(pool.clone()).await;
+    let app = helpers::test_router(state);
+
+    let project_id = helpers::create_project(&app, &admin_token, "issue-states", "public").await;
+    let issue_path = format!("/api/projects/{project_id}/issues/1");
+    helpers::post_json(
+        &app,
+        &admin_token,
+        &format!("/api/projects/{project_id}/issues"),
+        json!({ "title": "Lifecycle" }),
+    )
+    .await;
+
+    let (status, body) = helpers::patch_json(
+        &app,
+        &admin_token,
+        &issue_path,
+        json!({ "status": "in_progress" }),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["status"], "in_progress");
+
+    // Filter by state
+    let (_, body) = helpers::get_json(
+        &app,
+        &admin_token,
+        &format!("/api/projects/{project_id}/issues?status=in_progress"),
+    )
+    .await;
+    assert_eq!(body["total"], 1);
+
+    let (status, _) = helpers::get_json(
+        &app,
+        &admin_token,
+        &format!("/api/projects/{project_id}/issues?status=bogus"),
+    )
+    .await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+
+    let (status, body) = helpers::patch_json(
+        &app,
+        &admin_token,
+        &issue_path,
+        json!({ "status": "closed" }),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["status"], "closed");
+    assert!(helpers::wait_for_audit(&pool, "issue.close", 2000).await > 0);
+
+    // Closed issues must be reopened before work resumes
+    let (status, _) = helpers::patch_json(
+        &app,
+        &admin_token,
+        &issue_path,
+        json!({ "status": "in_progress" }),
+    )
+    .await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+}
+
+#[sqlx::test(migrations = "./migrations")]
+async fn issue_assign_and_unassign(pool: PgPool) {
+    let (state, admin_token) = helpers::test_state(pool.clone()).await;
+    let app = helpers::test_router(state);
+
+    let project_id = helpers::create_project(&app, &admin_token, "issue-assign", "public").await;
+    let (dev_id, _) =
+        helpers::create_user(&app, &admin_token, "assignee", "assignee@example.com").await;
+    helpers::post_json(
+        &app,
+        &admin_token,
+        &format!("/api/projects/{project_id}/issues"),
+        json!({ "title": "Assign me" }),
+    )
+    .await;
+
+    let assignee_path = format!("/api/projects/{project_id}/issues/1/assignee");
+    let (status, body) = helpers::put_json(
+        &app,
+        &admin_token,
+        &assignee_path,
+        json!({ "assignee_id": dev_id }),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK, "assign failed: {body}");
+    assert_eq!(body["assignee_id"], dev_id.to_string());
+
+    // Assignee gets an in-app notification
+    let (count,): (i64,) = sqlx::query_as(
+        "SELECT COUNT(*) FROM notifications WHERE user_id = $1 AND notification_type = 'issue_assigned'",
+    )
+    .bind(dev_id)
+    .fetch_one(&pool)
+    .await
+    .unwrap();
+    assert_eq!(count, 1);
+
+    let (_, body) = helpers::get_json(
+        &app,
+        &admin_token,
+        &format!("/api/projects/{project_id}/issues?assignee_id={dev_id}"),
+    )
+    .await;
+    assert_eq!(body["total"], 1);
+
+    let (status, body) = helpers::delete_json(&app, &admin_token, &assignee_path).await;
+    assert_eq!(status, StatusCode::OK);
+    assert!(body["assignee_id"].is_null());
+
+    // Unknown users cannot be assigned
+    let (status, _) = helpers::put_json(
+        &app,
+        &admin_token,
+        &assignee_path,
+        json!({ "assignee_id": Uuid::new_v4() }),
+    )
+    .await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+}
+
 #[sqlx::test(migrations = "./migrations")]
 async fn issue_auto_increment_numbers(pool: PgPool) {
     let (state, admin_token) = helpers::test_state(pool).await;
@@ -748,6 +872,62 @@ async fn mr_create_and_list_comments(pool: PgPool) {
     assert_eq!(body["total"], 1);
 }
 
+#[sqlx::test(migrations = "./migrations")]
+async fn mr_create_line_anchored_comment(pool: PgPool) {
+    let (state, admin_token) = helpers::test_state(pool.clone()).await;
+    let app = helpers::test_router(state);
+
+    let project_id = helpers::create_project(&app, &admin_token, "cmt-anchor", "public").await;
+    let admin_id = get_user_id(&app, &admin_token).await;
+    insert_mr(&pool, project_id, admin_id, 1).await;
+
+    let sha = "0123456789abcdef0123456789abcdef01234567";
+    let (status, body) = helpers::post_json(
+        &app,
+        &admin_token,
+        &format!("/api/projects/{project_id}/merge-requests/1/comments"),
+        json!({ "body": "Nit: rename", "file_path": "src/main.rs", "line": 12, "commit_sha": sha }),
+    )
+    .await;
+    assert_eq!(status, StatusCode::CREATED, "create failed: {body}");
+    assert_eq!(body["file_path"], "src/main.rs");
+    assert_eq!(body["line"], 12);
+    assert_eq!(body["commit_sha"], sha);
+    assert_eq!(body["outdated"], false);
+
+    let (status, body) = helpers::get_json(
+        &app,
+        &admin_token,
+        &format!("/api/projects/{project_id}/merge-requests/1/comments"),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["items"][0]["file_path"], "src/main.rs");
+    assert_eq!(body["items"][0]["line"], 12);
+}
+
+#[sqlx::test(migrations = "./migrations")]
+async fn mr_comment_invalid_anchor_rejected(pool: PgPool) {
+    let (state, admin_token) = helpers::test_state(pool.clone()).await;
+    let app = helpers::test_router(state);
+
+    let project_id = helpers::create_project(&app, &admin_token, "cmt-bad-anchor", "public").await;
+    let admin_id = get_user_id(&app, &admin_token).await;
+    insert_mr(&pool, project_id, admin_id, 1).await;
+
+    let path = format!("/api/projects/{project_id}/merge-requests/1/comments");
+    for anchor in [
+        json!({ "body": "x", "file_path": "src/main.rs" }),
+        json!({ "body": "x", "line": 3 }),
+        json!({ "body": "x", "file_path": "src/main.rs", "line": 0 }),
+        json!({ "body": "x", "file_path": "../etc/passwd", "line": 1 }),
+        json!({ "body": "x", "file_path": "a.rs", "line": 1, "commit_sha": "nothex" }),
+    ] {
+        let (status, _) = helpers::post_json(&app, &admin_token, &path, anchor.clone()).await;
+        assert_eq!(status, StatusCode::BAD_REQUEST, "anchor accepted: {anchor}");
+    }
+}
+
 #[sqlx::test(migrations = "./migrations")]
 async fn mr_update_comment_by_author(pool: PgPool) {
     let (state, admin_token) = helpers::test_state(pool.clone()).await;
@@ -3790,3 +3970,233 @@ async fn delete_already_closed_mr(pool: PgPool) {
     .await;
     assert_eq!(status, StatusCode::NO_CONTENT);
 }
+
+// ---------------------------------------------------------------------------
+// Issue auto-close from commit messages
+// ---------------------------------------------------------------------------
+
+fn push_params(
+    project_id: Uuid,
+    user_id: Uuid,
+    repo_path: &std::path::Path,
+    branch: &str,
+    old_sha: &str,
+    new_sha: &str,
+) -> platform::git::hooks::PostReceiveParams {
+    platform::git::hooks::PostReceiveParams {
+        project_id,
+        user_id,
+        user_name: "admin".into(),
+        repo_path: repo_path.to_path_buf(),
+        default_branch: "main".into(),
+        pushed_branches: vec![branch.into()],
+        pushed_tags: vec![],
+        ref_updates: vec![platform::git::hooks::RefUpdate {
+            old_sha: old_sha.into(),
+            new_sha: new_sha.into(),
+            refname: format!("refs/heads/{branch}"),
+        }],
+    }
+}
+
+#[sqlx::test(migrations = "./migrations")]
+async fn push_to_default_branch_closes_referenced_issue(pool: PgPool) {
+    let (state, admin_token) = helpers::test_state(pool.clone()).await;
+    let app = helpers::test_router(state.clone());
+
+    let project_id = helpers::create_project(&app, &admin_token, "auto-close", "public").await;
+    let admin_id = get_user_id(&app, &admin_token).await;
+    for title in ["Referenced on main", "Referenced on branch"] {
+        helpers::post_json(
+            &app,
+            &admin_token,
+            &format!("/api/projects/{project_id}/issues"),
+            json!({ "title": title }),
+        )
+        .await;
+    }
+
+    let (_bare_dir, bare_path) = helpers::create_bare_repo();
+    let (_work_dir, work_path) = helpers::create_working_copy(&bare_path);
+    let base = helpers::git_cmd(&work_path, &["rev-parse", "HEAD"])
+        .trim()
+        .to_owned();
+
+    // A feature branch push must not close anything
+    helpers::git_cmd(&work_path, &["checkout", "-b", "feat"]);
+    std::fs::write(work_path.join("feat.txt"), "feat\n").unwrap();
+    helpers::git_cmd(&work_path, &["add", "."]);
+    helpers::git_cmd(&work_path, &["commit", "-m", "Fixes #2"]);
+    helpers::git_cmd(&work_path, &["push", "origin", "feat"]);
+    let feat_sha = helpers::git_cmd(&work_path, &["rev-parse", "HEAD"])
+        .trim()
+        .to_owned();
+    let params = push_params(project_id, admin_id, &bare_path, "feat", &base, &feat_sha);
+    platform::git::hooks::post_receive(&state, &params)
+        .await
+        .unwrap();
+
+    // Default branch push closes #1
+    helpers::git_cmd(&work_path, &["checkout", "main"]);
+    std::fs::write(work_path.join("fix.txt"), "fix\n").unwrap();
+    helpers::git_cmd(&work_path, &["add", "."]);
+    helpers::git_cmd(
+        &work_path,
+        &["commit", "-m", "Handle empty input\n\nFixes #1"],
+    );
+    helpers::git_cmd(&work_path, &["push", "origin", "main"]);
+    let main_sha = helpers::git_cmd(&work_path, &["rev-parse", "HEAD"])
+        .trim()
+        .to_owned();
+    let params = push_params(project_id, admin_id, &bare_path, "main", &base, &main_sha);
+    platform::git::hooks::post_receive(&state, &params)
+        .await
+        .unwrap();
+
+    let (_, body) = helpers::get_json(
+        &app,
+        &admin_token,
+        &format!("/api/projects/{project_id}/issues/1"),
+    )
+    .await;
+    assert_eq!(body["status"], "closed");
+    let (_, body) = helpers::get_json(
+        &app,
+        &admin_token,
+        &format!("/api/projects/{project_id}/issues/2"),
+    )
+    .await;
+    assert_eq!(body["status"], "open");
+
+    let (_, body) = helpers::get_json(
+        &app,
+        &admin_token,
+        &format!("/api/projects/{project_id}/issues/1/comments"),
+    )
+    .await;
+    assert_eq!(body["total"], 1);
+    let comment = body["items"][0]["body"].as_str().unwrap();
+    assert!(
+        comment.contains(&main_sha[..12]),
+        "comment should link commit: {comment}"
+    );
+}
+
+// ---------------------------------------------------------------------------
+// Labels
+// ---------------------------------------------------------------------------
+
+#[sqlx::test(migrations = "./migrations")]
+async fn label_crud_and_uniqueness(pool: PgPool) {
+    let (state, admin_token) = helpers::test_state(pool).await;
+    let app = helpers::test_router(state);
+
+    let project_id = helpers::create_project(&app, &admin_token, "label-crud", "public").await;
+    let labels_path = format!("/api/projects/{project_id}/labels");
+
+    let (status, body) = helpers::post_json(
+        &app,
+        &admin_token,
+        &labels_path,
+        json!({ "name": "bug", "color": "#d73a4a" }),
+    )
+    .await;
+    assert_eq!(status, StatusCode::CREATED, "create failed: {body}");
+    assert_eq!(body["color"], "#d73a4a");
+    let label_id = body["id"].as_str().unwrap().to_owned();
+
+    let (status, _) =
+        helpers::post_json(&app, &admin_token, &labels_path, json!({ "name": "bug" })).await;
+    assert_eq!(status, StatusCode::CONFLICT);
+
+    let (status, _) = helpers::post_json(
+        &app,
+        &admin_token,
+        &labels_path,
+        json!({ "name": "docs", "color": "blue" }),
+    )
+    .await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+
+    let (status, body) = helpers::patch_json(
+        &app,
+        &admin_token,
+        &format!("{labels_path}/{label_id}"),
+        json!({ "color": "#000000" }),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["color"], "#000000");
+
+    let (status, _) =
+        helpers::delete_json(&app, &admin_token, &format!("{labels_path}/{label_id}")).await;
+    assert_eq!(status, StatusCode::NO_CONTENT);
+
+    let (_, body) = helpers::get_json(&app, &admin_token, &labels_path).await;
+    assert_eq!(body["total"], 0);
+}
+
+#[sqlx::test(migrations = "./migrations")]
+async fn issue_and_mr_label_filters(pool: PgPool) {
+    let (state, admin_token) = helpers::test_state(pool.clone()).await;
+    let app = helpers::test_router(state);
+
+    let project_id = helpers::create_project(&app, &admin_token, "label-filter", "public").await;
+    let admin_id = get_user_id(&app, &admin_token).await;
+
+    for (title, labels) in [("Crash", json!(["bug", "p1"])), ("Docs", json!(["docs"]))] {
+        let (status, body) = helpers::post_json(
+            &app,
+            &admin_token,
+            &format!("/api/projects/{project_id}/issues"),
+            json!({ "title": title, "labels": labels }),
+        )
+        .await;
+        assert_eq!(status, StatusCode::CREATED, "create issue failed: {body}");
+    }
+
+    // Labels used on issues exist as project labels
+    let (_, body) = helpers::get_json(
+        &app,
+        &admin_token,
+        &format!("/api/projects/{project_id}/labels"),
+    )
+    .await;
+    assert_eq!(body["total"], 3);
+
+    let (_, body) = helpers::get_json(
+        &app,
+        &admin_token,
+        &format!("/api/projects/{project_id}/issues?label=bug"),
+    )
+    .await;
+    assert_eq!(body["total"], 1);
+    assert_eq!(body["items"][0]["title"], "Crash");
+    assert_eq!(body["items"][0]["labels"], json!(["bug", "p1"]));
+
+    insert_mr(&pool, project_id, admin_id, 1).await;
+    let (status, body) = helpers::patch_json(
+        &app,
+        &admin_token,
+        &format!("/api/projects/{project_id}/merge-requests/1"),
+        json!({ "labels": ["bug"] }),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK, "label MR failed: {body}");
+    assert_eq!(body["labels"], json!(["bug"]));
+
+    let (_, body) = helpers::get_json(
+        &app,
+        &admin_token,
+        &format!("/api/projects/{project_id}/merge-requests?label=bug"),
+    )
+    .await;
+    assert_eq!(body["total"], 1);
+    let (_, body) = helpers::get_json(
+        &app,
+        &admin_token,
+        &format!("/api/projects/{project_id}/merge-requests?label=docs"),
+    )
+    .await;
+    assert_eq!(body["total"], 0);
+}
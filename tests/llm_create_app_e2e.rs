@@ -340,6 +340,7 @@ async fn llm_create_app_full_flow(pool: PgPool) {
                 name: "ANTHROPIC_API_KEY",
                 value: key.as_bytes(),
                 scope: "agent",
+                backend: platform::secrets::engine::BACKEND_LOCAL,
                 created_by: admin_id.0,
             },
         )
@@ -383,8 +383,8 @@ async fn merge_allowed_ci_success(pool: PgPool) {
     )
     .await;
 
-    // Insert successful pipeline
-    helpers::insert_pipeline(
+    // Insert successful pipeline for the source head
+    let pipeline_id = helpers::insert_pipeline(
         &pool,
         project_id,
         admin_id,
@@ -393,6 +393,12 @@ async fn merge_allowed_ci_success(pool: PgPool) {
         "push",
     )
     .await;
+    sqlx::query("UPDATE pipelines SET commit_sha = $1 WHERE id = $2")
+        .bind(&head_sha)
+        .bind(pipeline_id)
+        .execute(&pool)
+        .await
+        .unwrap();
 
     // Insert MR
     let mr_id = Uuid::new_v4();
@@ -427,6 +433,219 @@ async fn merge_allowed_ci_success(pool: PgPool) {
     );
 }
 
+/// A green pipeline for an earlier head does not satisfy the required checks
+/// once new commits are pushed to the source branch.
+#[sqlx::test(migrations = "./migrations")]
+async fn merge_blocked_when_pipeline_is_for_earlier_head(pool: PgPool) {
+    let (state, admin_token) = helpers::test_state(pool.clone()).await;
+    let app = helpers::test_router(state);
+
+    let project_id = helpers::create_project(&app, &admin_token, "gate-ci-stale", "public").await;
+    let admin_id = helpers::admin_user_id(&pool).await;
+
+    let (_bare_dir, bare_path) = helpers::create_bare_repo();
+    let (_work_dir, work_path) = helpers::create_working_copy(&bare_path);
+    helpers::git_cmd(&work_path, &["checkout", "-b", "feat"]);
+    std::fs::write(work_path.join("feature.txt"), "first").unwrap();
+    helpers::git_cmd(&work_path, &["add", "."]);
+    helpers::git_cmd(&work_path, &["commit", "-m", "first"]);
+    helpers::git_cmd(&work_path, &["push", "origin", "feat"]);
+    sqlx::query("UPDATE projects SET repo_path = $1 WHERE id = $2")
+        .bind(bare_path.to_str().unwrap())
+        .bind(project_id)
+        .execute(&pool)
+        .await
+        .unwrap();
+    let first_sha = helpers::git_cmd(&work_path, &["rev-parse", "HEAD"])
+        .trim()
+        .to_string();
+
+    helpers::insert_branch_protection(
+        &pool,
+        project_id,
+        "main",
+        0,
+        &["merge"],
+        &["ci"],
+        false,
+        false,
+    )
+    .await;
+    helpers::insert_mr(&pool, project_id, admin_id, "feat", "main", 1).await;
+
+    let insert_green_pipeline = |sha: String| {
+        let pool = pool.clone();
+        async move {
+            let id = helpers::insert_pipeline(
+                &pool,
+                project_id,
+                admin_id,
+                "success",
+                "refs/heads/feat",
+                "push",
+            )
+            .await;
+            sqlx::query("UPDATE pipelines SET commit_sha = $1 WHERE id = $2")
+                .bind(sha)
+                .bind(id)
+                .execute(&pool)
+                .await
+                .unwrap();
+        }
+    };
+    insert_green_pipeline(first_sha).await;
+
+    // A new head arrives after the pipeline passed
+    std::fs::write(work_path.join("feature.txt"), "second").unwrap();
+    helpers::git_cmd(&work_path, &["commit", "-am", "second"]);
+    helpers::git_cmd(&work_path, &["push", "origin", "feat"]);
+    let second_sha = helpers::git_cmd(&work_path, &["rev-parse", "HEAD"])
+        .trim()
+        .to_string();
+
+    let merge = format!("/api/projects/{project_id}/merge-requests/1/merge");
+    let (status, body) =
+        helpers::post_json(&app, &admin_token, &merge, serde_json::json!({})).await;
+    assert_eq!(status, StatusCode::BAD_REQUEST, "stale pipeline: {body}");
+    let err = body["error"].as_str().unwrap_or("");
+    assert!(err.contains("missing: ci"), "got: {err}");
+
+    insert_green_pipeline(second_sha).await;
+    let (status, body) =
+        helpers::post_json(&app, &admin_token, &merge, serde_json::json!({})).await;
+    assert_eq!(status, StatusCode::OK, "pipeline for the new head: {body}");
+}
+
+/// A required commit status context gates the merge until it reports success.
+#[sqlx::test(migrations = "./migrations")]
+async fn merge_waits_for_required_status_context(pool: PgPool) {
+    let (state, admin_token) = helpers::test_state(pool.clone()).await;
+    let app = helpers::test_router(state);
+
+    let project_id = helpers::create_project(&app, &admin_token, "gate-status", "public").await;
+    let admin_id = helpers::admin_user_id(&pool).await;
+
+    let (_bare_dir, bare_path) = helpers::create_bare_repo();
+    let (_work_dir, work_path) = helpers::create_working_copy(&bare_path);
+    helpers::git_cmd(&work_path, &["checkout", "-b", "feat"]);
+    std::fs::write(work_path.join("feature.txt"), "status feature").unwrap();
+    helpers::git_cmd(&work_path, &["add", "."]);
+    helpers::git_cmd(&work_path, &["commit", "-m", "add status feature"]);
+    helpers::git_cmd(&work_path, &["push", "origin", "feat"]);
+    sqlx::query("UPDATE projects SET repo_path = $1 WHERE id = $2")
+        .bind(bare_path.to_str().unwrap())
+        .bind(project_id)
+        .execute(&pool)
+        .await
+        .unwrap();
+    let head_sha = helpers::git_cmd(&work_path, &["rev-parse", "HEAD"])
+        .trim()
+        .to_string();
+
+    helpers::insert_branch_protection(
+        &pool,
+        project_id,
+        "main",
+        0,
+        &["merge"],
+        &["ci/build", "ci/test"],
+        false,
+        false,
+    )
+    .await;
+    sqlx::query(
+        "INSERT INTO merge_requests (id, project_id, number, author_id, source_branch, target_branch, title, status, head_sha)
+         VALUES ($1, $2, 1, $3, 'feat', 'main', 'Status MR', 'open', $4)",
+    )
+    .bind(Uuid::new_v4())
+    .bind(project_id)
+    .bind(admin_id)
+    .bind(&head_sha)
+    .execute(&pool)
+    .await
+    .unwrap();
+
+    let statuses = format!("/api/projects/{project_id}/commits/{head_sha}/statuses");
+    let merge = format!("/api/projects/{project_id}/merge-requests/1/merge");
+    for (context, state) in [("ci/build", "pending"), ("ci/test", "success")] {
+        let (status, body) = helpers::post_json(
+            &app,
+            &admin_token,
+            &statuses,
+            serde_json::json!({ "state": state, "context": context }),
+        )
+        .await;
+        assert_eq!(status, StatusCode::CREATED, "{body}");
+    }
+
+    let (status, body) =
+        helpers::post_json(&app, &admin_token, &merge, serde_json::json!({})).await;
+    assert_eq!(status, StatusCode::BAD_REQUEST, "pending check: {body}");
+    let err = body["error"].as_str().unwrap_or("");
+    assert!(err.contains("ci/build (pending)"), "got: {err}");
+    assert!(!err.contains("ci/test"), "got: {err}");
+
+    // Reporting the context again replaces its state
+    let (status, _) = helpers::post_json(
+        &app,
+        &admin_token,
+        &statuses,
+        serde_json::json!({ "state": "success", "context": "ci/build" }),
+    )
+    .await;
+    assert_eq!(status, StatusCode::CREATED);
+    let (status, body) = helpers::get_json(&app, &admin_token, &statuses).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["total"], 2);
+    assert!(
+        body["items"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .all(|s| s["state"] == "success")
+    );
+
+    let (status, body) =
+        helpers::post_json(&app, &admin_token, &merge, serde_json::json!({})).await;
+    assert_eq!(status, StatusCode::OK, "all checks green: {body}");
+}
+
+/// Invalid states and short SHAs are rejected; context defaults to `default`.
+#[sqlx::test(migrations = "./migrations")]
+async fn commit_status_validation(pool: PgPool) {
+    let (state, admin_token) = helpers::test_state(pool.clone()).await;
+    let app = helpers::test_router(state);
+    let project_id = helpers::create_project(&app, &admin_token, "status-valid", "public").await;
+    let sha = "a".repeat(40);
+    let path = format!("/api/projects/{project_id}/commits/{sha}/statuses");
+
+    let (status, _) = helpers::post_json(
+        &app,
+        &admin_token,
+        &path,
+        serde_json::json!({ "state": "done" }),
+    )
+    .await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    let (status, _) = helpers::post_json(
+        &app,
+        &admin_token,
+        &format!("/api/projects/{project_id}/commits/abc123/statuses"),
+        serde_json::json!({ "state": "success" }),
+    )
+    .await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    let (status, body) = helpers::post_json(
+        &app,
+        &admin_token,
+        &path,
+        serde_json::json!({ "state": "error", "target_url": "https://ci.example.com/1" }),
+    )
+    .await;
+    assert_eq!(status, StatusCode::CREATED, "{body}");
+    assert_eq!(body["context"], "default");
+}
+
 // ---------------------------------------------------------------------------
 // T3: Auto-merge enable/disable
 // ---------------------------------------------------------------------------
@@ -1046,3 +1265,270 @@ async fn auto_merge_nonexistent_mr_disable(pool: PgPool) {
     .await;
     assert_eq!(status, StatusCode::NOT_FOUND);
 }
+
+// ---------------------------------------------------------------------------
+// Mergeability dry-run
+// ---------------------------------------------------------------------------
+
+/// Mergeability reports conflicting paths and whether source is behind target.
+#[sqlx::test(migrations = "./migrations")]
+async fn mergeability_reports_conflicts(pool: PgPool) {
+    let (state, admin_token) = helpers::test_state(pool.clone()).await;
+    let app = helpers::test_router(state);
+
+    let project_id = helpers::create_project(&app, &admin_token, "mergeability", "public").await;
+    let admin_id = helpers::admin_user_id(&pool).await;
+
+    let (_bare_dir, bare_path) = helpers::create_bare_repo();
+    let (_work_dir, work_path) = helpers::create_working_copy(&bare_path);
+
+    // Clean feature branch off main
+    helpers::git_cmd(&work_path, &["checkout", "-b", "feat-clean"]);
+    std::fs::write(work_path.join("clean.txt"), "clean\n").unwrap();
+    helpers::git_cmd(&work_path, &["add", "."]);
+    helpers::git_cmd(&work_path, &["commit", "-m", "clean change"]);
+    helpers::git_cmd(&work_path, &["push", "origin", "feat-clean"]);
+
+    // Conflicting branch edits README.md, then main edits it differently
+    helpers::git_cmd(&work_path, &["checkout", "main"]);
+    helpers::git_cmd(&work_path, &["checkout", "-b", "feat-conflict"]);
+    std::fs::write(work_path.join("README.md"), "# from feature\n").unwrap();
+    helpers::git_cmd(&work_path, &["commit", "-am", "feature readme"]);
+    helpers::git_cmd(&work_path, &["push", "origin", "feat-conflict"]);
+
+    helpers::git_cmd(&work_path, &["checkout", "main"]);
+    std::fs::write(work_path.join("README.md"), "# from main\n").unwrap();
+    helpers::git_cmd(&work_path, &["commit", "-am", "main readme"]);
+    helpers::git_cmd(&work_path, &["push", "origin", "main"]);
+
+    sqlx::query("UPDATE projects SET repo_path = $1 WHERE id = $2")
+        .bind(bare_path.to_str().unwrap())
+        .bind(project_id)
+        .execute(&pool)
+        .await
+        .unwrap();
+
+    helpers::insert_mr(&pool, project_id, admin_id, "feat-clean", "main", 1).await;
+    helpers::insert_mr(&pool, project_id, admin_id, "feat-conflict", "main", 2).await;
+
+    let (status, body) = helpers::get_json(
+        &app,
+        &admin_token,
+        &format!("/api/projects/{project_id}/merge-requests/1/mergeability"),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK, "mergeability failed: {body}");
+    assert_eq!(body["can_merge"], true);
+    assert!(body["conflicts"].as_array().unwrap().is_empty());
+    assert_eq!(body["behind_target"], true);
+    assert_eq!(body["behind_by"], 1);
+
+    let (status, body) = helpers::get_json(
+        &app,
+        &admin_token,
+        &format!("/api/projects/{project_id}/merge-requests/2/mergeability"),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK, "mergeability failed: {body}");
+    assert_eq!(body["can_merge"], false);
+    assert_eq!(body["conflicts"], serde_json::json!(["README.md"]));
+
+    // Dry run must not move the target branch
+    let main_sha = helpers::git_cmd(&work_path, &["rev-parse", "main"]);
+    assert_eq!(body["target_sha"].as_str().unwrap(), main_sha.trim());
+}
+
+/// Mergeability on a nonexistent MR returns 404.
+#[sqlx::test(migrations = "./migrations")]
+async fn mergeability_nonexistent_mr(pool: PgPool) {
+    let (state, admin_token) = helpers::test_state(pool).await;
+    let app = helpers::test_router(state);
+
+    let project_id =
+        helpers::create_project(&app, &admin_token, "mergeability-404", "public").await;
+
+    let (status, _) = helpers::get_json(
+        &app,
+        &admin_token,
+        &format!("/api/projects/{project_id}/merge-requests/999/mergeability"),
+    )
+    .await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+}
+
+// ---------------------------------------------------------------------------
+// Merge method validation and squash/rebase results
+// ---------------------------------------------------------------------------
+
+/// Unknown merge method is rejected before any gate or git work.
+#[sqlx::test(migrations = "./migrations")]
+async fn merge_invalid_method_rejected(pool: PgPool) {
+    let (state, admin_token) = helpers::test_state(pool.clone()).await;
+    let app = helpers::test_router(state);
+
+    let project_id = helpers::create_project(&app, &admin_token, "bad-method", "public").await;
+    let admin_id = helpers::admin_user_id(&pool).await;
+    helpers::insert_mr(&pool, project_id, admin_id, "feat", "main", 1).await;
+
+    let (status, body) = helpers::post_json(
+        &app,
+        &admin_token,
+        &format!("/api/projects/{project_id}/merge-requests/1/merge"),
+        serde_json::json!({ "merge_method": "octopus" }),
+    )
+    .await;
+    assert_eq!(status, StatusCode::BAD_REQUEST, "{body}");
+    assert!(body["error"].as_str().unwrap().contains("merge_method"));
+
+    let (status, _) = helpers::put_json(
+        &app,
+        &admin_token,
+        &format!("/api/projects/{project_id}/merge-requests/1/auto-merge"),
+        serde_json::json!({ "merge_method": "octopus" }),
+    )
+    .await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+}
+
+/// Squash merge produces exactly one commit on target, authored by the MR
+/// author, with the MR title and body as message.
+#[sqlx::test(migrations = "./migrations")]
+async fn squash_merge_single_commit_with_mr_message(pool: PgPool) {
+    let (state, admin_token) = helpers::test_state(pool.clone()).await;
+    let app = helpers::test_router(state);
+
+    let project_id = helpers::create_project(&app, &admin_token, "squash-one", "public").await;
+    let admin_id = helpers::admin_user_id(&pool).await;
+
+    let (_bare_dir, bare_path) = helpers::create_bare_repo();
+    let (_work_dir, work_path) = helpers::create_working_copy(&bare_path);
+    let main_before = helpers::git_cmd(&work_path, &["rev-parse", "main"]);
+
+    helpers::git_cmd(&work_path, &["checkout", "-b", "feat-squash-one"]);
+    for i in 0..3 {
+        std::fs::write(work_path.join(format!("f{i}.txt")), "x").unwrap();
+        helpers::git_cmd(&work_path, &["add", "."]);
+        helpers::git_cmd(&work_path, &["commit", "-m", &format!("wip {i}")]);
+    }
+    helpers::git_cmd(&work_path, &["push", "origin", "feat-squash-one"]);
+
+    sqlx::query("UPDATE projects SET repo_path = $1 WHERE id = $2")
+        .bind(bare_path.to_str().unwrap())
+        .bind(project_id)
+        .execute(&pool)
+        .await
+        .unwrap();
+    helpers::insert_mr(&pool, project_id, admin_id, "feat-squash-one", "main", 1).await;
+    sqlx::query(
+        "UPDATE merge_requests SET title = 'Add files', body = 'Three files' WHERE project_id = $1",
+    )
+    .bind(project_id)
+    .execute(&pool)
+    .await
+    .unwrap();
+    sqlx::query(
+        "UPDATE branch_protection_rules SET merge_methods = '{merge,squash}' WHERE project_id = $1 AND pattern = 'main'",
+    )
+    .bind(project_id)
+    .execute(&pool)
+    .await
+    .unwrap();
+
+    let (status, body) = helpers::post_json(
+        &app,
+        &admin_token,
+        &format!("/api/projects/{project_id}/merge-requests/1/merge"),
+        serde_json::json!({ "merge_method": "squash" }),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK, "squash merge failed: {body}");
+
+    let bare = bare_path.to_str().unwrap();
+    let count = helpers::git_cmd(
+        &bare_path,
+        &[
+            "--git-dir",
+            bare,
+            "rev-list",
+            "--count",
+            &format!("{}..main", main_before.trim()),
+        ],
+    );
+    assert_eq!(count.trim(), "1", "squash should add exactly one commit");
+
+    let log = helpers::git_cmd(
+        &bare_path,
+        &["--git-dir", bare, "log", "-1", "--format=%an%n%B", "main"],
+    );
+    let mut lines = log.lines();
+    assert_eq!(lines.next(), Some("admin"));
+    assert_eq!(lines.next(), Some("Add files"));
+    assert!(log.contains("Three files"));
+}
+
+/// Rebase merge replays source onto a diverged target and fast-forwards it.
+#[sqlx::test(migrations = "./migrations")]
+async fn rebase_merge_onto_diverged_target(pool: PgPool) {
+    let (state, admin_token) = helpers::test_state(pool.clone()).await;
+    let app = helpers::test_router(state);
+
+    let project_id = helpers::create_project(&app, &admin_token, "rebase-diverged", "public").await;
+    let admin_id = helpers::admin_user_id(&pool).await;
+
+    let (_bare_dir, bare_path) = helpers::create_bare_repo();
+    let (_work_dir, work_path) = helpers::create_working_copy(&bare_path);
+
+    helpers::git_cmd(&work_path, &["checkout", "-b", "feat-diverged"]);
+    std::fs::write(work_path.join("feature.txt"), "feature").unwrap();
+    helpers::git_cmd(&work_path, &["add", "."]);
+    helpers::git_cmd(&work_path, &["commit", "-m", "feature commit"]);
+    helpers::git_cmd(&work_path, &["push", "origin", "feat-diverged"]);
+
+    helpers::git_cmd(&work_path, &["checkout", "main"]);
+    std::fs::write(work_path.join("main.txt"), "main").unwrap();
+    helpers::git_cmd(&work_path, &["add", "."]);
+    helpers::git_cmd(&work_path, &["commit", "-m", "main commit"]);
+    helpers::git_cmd(&work_path, &["push", "origin", "main"]);
+    let main_before = helpers::git_cmd(&work_path, &["rev-parse", "main"]);
+
+    sqlx::query("UPDATE projects SET repo_path = $1 WHERE id = $2")
+        .bind(bare_path.to_str().unwrap())
+        .bind(project_id)
+        .execute(&pool)
+        .await
+        .unwrap();
+    helpers::insert_branch_protection(
+        &pool,
+        project_id,
+        "main",
+        0,
+        &["merge", "rebase"],
+        &[],
+        false,
+        false,
+    )
+    .await;
+    helpers::insert_mr(&pool, project_id, admin_id, "feat-diverged", "main", 1).await;
+
+    let (status, body) = helpers::post_json(
+        &app,
+        &admin_token,
+        &format!("/api/projects/{project_id}/merge-requests/1/merge"),
+        serde_json::json!({ "merge_method": "rebase" }),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK, "rebase merge failed: {body}");
+
+    let bare = bare_path.to_str().unwrap();
+    let parent = helpers::git_cmd(&bare_path, &["--git-dir", bare, "rev-parse", "main~1"]);
+    assert_eq!(
+        parent.trim(),
+        main_before.trim(),
+        "history must stay linear"
+    );
+    let subject = helpers::git_cmd(
+        &bare_path,
+        &["--git-dir", bare, "log", "-1", "--format=%s", "main"],
+    );
+    assert_eq!(subject.trim(), "feature commit");
+}
@@ -6,6 +6,7 @@ mod helpers;
 use axum::http::StatusCode;
 use sqlx::PgPool;
 use uuid::Uuid;
+use wiremock::{Mock, MockServer, ResponseTemplate, matchers};
 
 // ---------------------------------------------------------------------------
 // E8: Notification Integration Tests (10 tests)
@@ -20,8 +21,8 @@ async fn insert_notification(
     notification_type: &str,
 ) -> Uuid {
     let row: (Uuid,) = sqlx::query_as(
-        "INSERT INTO notifications (user_id, notification_type, subject, channel, status) \
-         VALUES ($1, $2, $3, 'in_app', $4) \
+        "INSERT INTO notifications (user_id, notification_type, subject, channel, status, read_at) \
+         VALUES ($1, $2, $3, 'in_app', $4, CASE WHEN $4 = 'read' THEN now() END) \
          RETURNING id",
     )
     .bind(user_id)
@@ -239,6 +240,103 @@ async fn cannot_mark_other_users_notification(pool: PgPool) {
     assert_eq!(status, StatusCode::NOT_FOUND);
 }
 
+#[sqlx::test(migrations = "./migrations")]
+async fn patch_notification_toggles_read_state(pool: PgPool) {
+    let (state, admin_token) = helpers::test_state(pool.clone()).await;
+    let app = helpers::test_router(state);
+    let admin_id = helpers::admin_user_id(&pool).await;
+
+    let notif_id = insert_notification(&pool, admin_id, "Toggle", "sent", "info").await;
+
+    let (status, body) = helpers::patch_json(
+        &app,
+        &admin_token,
+        &format!("/api/notifications/{notif_id}"),
+        serde_json::json!({ "read": true }),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert!(body["read_at"].is_string());
+    assert_eq!(body["status"], "read");
+
+    // Marking read again is idempotent
+    let (status, again) = helpers::patch_json(
+        &app,
+        &admin_token,
+        &format!("/api/notifications/{notif_id}"),
+        serde_json::json!({ "read": true }),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(again["read_at"], body["read_at"]);
+
+    let (status, body) = helpers::patch_json(
+        &app,
+        &admin_token,
+        &format!("/api/notifications/{notif_id}"),
+        serde_json::json!({ "read": false }),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert!(body["read_at"].is_null());
+    assert_eq!(body["status"], "sent");
+
+    let (_, count) = helpers::get_json(&app, &admin_token, "/api/notifications/unread-count").await;
+    assert_eq!(count["count"], 1);
+}
+
+#[sqlx::test(migrations = "./migrations")]
+async fn read_all_clears_unread_badge(pool: PgPool) {
+    let (state, admin_token) = helpers::test_state(pool.clone()).await;
+    let app = helpers::test_router(state);
+    let admin_id = helpers::admin_user_id(&pool).await;
+
+    for i in 0..3 {
+        insert_notification(&pool, admin_id, &format!("N{i}"), "sent", "info").await;
+    }
+    insert_notification(&pool, admin_id, "Old", "read", "info").await;
+
+    let (_, body) = helpers::get_json(&app, &admin_token, "/api/notifications?unread=true").await;
+    assert_eq!(body["total"], 3);
+    let (_, count) = helpers::get_json(&app, &admin_token, "/api/notifications/unread-count").await;
+    assert_eq!(count["count"], 3);
+
+    let (status, body) = helpers::post_json(
+        &app,
+        &admin_token,
+        "/api/notifications/read-all",
+        serde_json::json!({}),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["updated"], 3);
+
+    let (_, count) = helpers::get_json(&app, &admin_token, "/api/notifications/unread-count").await;
+    assert_eq!(count["count"], 0);
+    let (_, body) = helpers::get_json(&app, &admin_token, "/api/notifications?unread=true").await;
+    assert_eq!(body["total"], 0);
+}
+
+#[sqlx::test(migrations = "./migrations")]
+async fn unread_count_ignores_email_notifications(pool: PgPool) {
+    let (state, admin_token) = helpers::test_state(pool.clone()).await;
+    let app = helpers::test_router(state);
+    let admin_id = helpers::admin_user_id(&pool).await;
+
+    insert_notification(&pool, admin_id, "In app", "sent", "info").await;
+    sqlx::query(
+        "INSERT INTO notifications (user_id, notification_type, subject, channel, status) \
+         VALUES ($1, 'info', 'By email', 'email', 'sent')",
+    )
+    .bind(admin_id)
+    .execute(&pool)
+    .await
+    .unwrap();
+
+    let (_, count) = helpers::get_json(&app, &admin_token, "/api/notifications/unread-count").await;
+    assert_eq!(count["count"], 1);
+}
+
 // ---------------------------------------------------------------------------
 // Dispatch integration tests
 // ---------------------------------------------------------------------------
@@ -471,3 +569,271 @@ async fn on_agent_completed_notifies_user(pool: PgPool) {
 
     assert_eq!(count.0, 1);
 }
+
+// ---------------------------------------------------------------------------
+// Chat channels (Slack / Discord)
+// ---------------------------------------------------------------------------
+
+#[sqlx::test(migrations = "./migrations")]
+async fn chat_channel_crud_hides_url(pool: PgPool) {
+    let (state, admin_token) = helpers::test_state(pool).await;
+    let app = helpers::test_router(state);
+    let project_id = helpers::create_project(&app, &admin_token, "chat-crud", "private").await;
+    let base = format!("/api/projects/{project_id}/chat-channels");
+
+    let (status, body) = helpers::post_json(
+        &app,
+        &admin_token,
+        &base,
+        serde_json::json!({
+            "kind": "slack",
+            "url": "https://hooks.slack.com/services/T0/B0/secret",
+            "events": ["pipeline"],
+        }),
+    )
+    .await;
+    assert_eq!(status, StatusCode::CREATED, "create failed: {body}");
+    assert_eq!(body["kind"], "slack");
+    assert_eq!(body["url_host"], "hooks.slack.com");
+    assert!(!body.to_string().contains("secret"));
+    let channel_id = body["id"].as_str().unwrap().to_owned();
+
+    let (status, body) = helpers::patch_json(
+        &app,
+        &admin_token,
+        &format!("{base}/{channel_id}"),
+        serde_json::json!({ "events": ["pipeline", "deploy"] }),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK, "update failed: {body}");
+    assert_eq!(body["events"], serde_json::json!(["pipeline", "deploy"]));
+
+    let (_, body) = helpers::get_json(&app, &admin_token, &base).await;
+    assert_eq!(body["total"], 1);
+
+    let (status, _) =
+        helpers::delete_json(&app, &admin_token, &format!("{base}/{channel_id}")).await;
+    assert_eq!(status, StatusCode::NO_CONTENT);
+}
+
+#[sqlx::test(migrations = "./migrations")]
+async fn chat_channel_rejects_private_url(pool: PgPool) {
+    let (state, admin_token) = helpers::test_state(pool).await;
+    let app = helpers::test_router(state);
+    let project_id = helpers::create_project(&app, &admin_token, "chat-ssrf", "private").await;
+
+    let (status, _) = helpers::post_json(
+        &app,
+        &admin_token,
+        &format!("/api/projects/{project_id}/chat-channels"),
+        serde_json::json!({
+            "kind": "discord",
+            "url": "https://10.0.0.5/api/webhooks/1/x",
+            "events": ["alert"],
+        }),
+    )
+    .await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+}
+
+/// A failed pipeline posts a red message to the project's Slack channel.
+#[sqlx::test(migrations = "./migrations")]
+async fn failed_pipeline_posts_red_slack_message(pool: PgPool) {
+    let (state, admin_token) = helpers::test_state(pool).await;
+    let app = helpers::test_router(state.clone());
+    let project_id = helpers::create_project(&app, &admin_token, "chat-pipeline", "private").await;
+    let admin_id = helpers::admin_user_id(&state.pool).await;
+
+    let mock_server = MockServer::start().await;
+    Mock::given(matchers::method("POST"))
+        .and(matchers::path("/slack"))
+        .respond_with(ResponseTemplate::new(200))
+        .expect(1)
+        .mount(&mock_server)
+        .await;
+
+    // Inserted directly: the API only accepts public https URLs
+    sqlx::query(
+        "INSERT INTO chat_channels (project_id, kind, url, events) VALUES ($1, 'slack', $2, $3)",
+    )
+    .bind(project_id)
+    .bind(format!("{}/slack", mock_server.uri()))
+    .bind(vec!["pipeline".to_owned()])
+    .execute(&state.pool)
+    .await
+    .unwrap();
+
+    let pipeline_id = helpers::insert_pipeline(
+        &state.pool,
+        project_id,
+        admin_id,
+        "failure",
+        "refs/heads/main",
+        "push",
+    )
+    .await;
+    platform::notify::chat::on_pipeline_finished(&state, project_id, pipeline_id, "failure").await;
+
+    tokio::time::sleep(std::time::Duration::from_millis(500)).await;
+    mock_server.verify().await;
+
+    let requests = mock_server.received_requests().await.unwrap();
+    let payload: serde_json::Value = serde_json::from_slice(&requests[0].body).unwrap();
+    let attachment = &payload["attachments"][0];
+    assert_eq!(attachment["color"], "#d73a49");
+    assert_eq!(attachment["title"], "Pipeline failed: chat-pipeline");
+    assert!(attachment["text"].as_str().unwrap().contains("`main`"));
+}
+
+// ---------------------------------------------------------------------------
+// Email digests
+// ---------------------------------------------------------------------------
+
+#[sqlx::test(migrations = "./migrations")]
+async fn notification_preferences_round_trip(pool: PgPool) {
+    let (state, admin_token) = helpers::test_state(pool).await;
+    let app = helpers::test_router(state);
+
+    let (status, _) = helpers::put_json(
+        &app,
+        &admin_token,
+        "/api/notifications/preferences",
+        serde_json::json!({ "notification_type": "issue_comment", "digest": "hourly" }),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+
+    let (_, body) = helpers::get_json(&app, &admin_token, "/api/notifications/preferences").await;
+    assert_eq!(
+        body,
+        serde_json::json!([{ "notification_type": "issue_comment", "digest": "hourly" }])
+    );
+
+    // Back to immediate removes the preference
+    helpers::put_json(
+        &app,
+        &admin_token,
+        "/api/notifications/preferences",
+        serde_json::json!({ "notification_type": "issue_comment", "digest": "immediate" }),
+    )
+    .await;
+    let (_, body) = helpers::get_json(&app, &admin_token, "/api/notifications/preferences").await;
+    assert_eq!(body, serde_json::json!([]));
+
+    let (status, _) = helpers::put_json(
+        &app,
+        &admin_token,
+        "/api/notifications/preferences",
+        serde_json::json!({ "notification_type": "issue_comment", "digest": "weekly" }),
+    )
+    .await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+}
+
+/// With an hourly digest, ten issue comments queue ten emails that go out as
+/// one digest once the oldest is an hour old, and stay queued if sending fails.
+#[sqlx::test(migrations = "./migrations")]
+async fn hourly_digest_batches_issue_comment_emails(pool: PgPool) {
+    let (state, admin_token) = helpers::test_state(pool.clone()).await;
+    let app = helpers::test_router(state.clone());
+    let admin_id = helpers::admin_user_id(&pool).await;
+
+    let project_id = helpers::create_project(&app, &admin_token, "digest-proj", "private").await;
+    let (commenter_id, commenter_token) =
+        helpers::create_user(&app, &admin_token, "digest-commenter", "dc@example.com").await;
+    helpers::assign_role(
+        &app,
+        &admin_token,
+        commenter_id,
+        "developer",
+        Some(project_id),
+        &pool,
+    )
+    .await;
+
+    helpers::put_json(
+        &app,
+        &admin_token,
+        "/api/notifications/preferences",
+        serde_json::json!({ "notification_type": "issue_comment", "digest": "hourly" }),
+    )
+    .await;
+
+    let (status, _) = helpers::post_json(
+        &app,
+        &admin_token,
+        &format!("/api/projects/{project_id}/issues"),
+        serde_json::json!({ "title": "Noisy issue" }),
+    )
+    .await;
+    assert_eq!(status, StatusCode::CREATED);
+    for i in 0..10 {
+        let (status, body) = helpers::post_json(
+            &app,
+            &commenter_token,
+            &format!("/api/projects/{project_id}/issues/1/comments"),
+            serde_json::json!({ "body": format!("comment {i}") }),
+        )
+        .await;
+        assert_eq!(status, StatusCode::CREATED, "comment failed: {body}");
+    }
+
+    let pending_emails = |status: &'static str| {
+        let pool = pool.clone();
+        async move {
+            let (count,): (i64,) = sqlx::query_as(
+                "SELECT COUNT(*) FROM notifications
+                 WHERE user_id = $1 AND notification_type = 'issue_comment'
+                   AND channel = 'email' AND status = $2",
+            )
+            .bind(admin_id)
+            .bind(status)
+            .fetch_one(&pool)
+            .await
+            .unwrap();
+            count
+        }
+    };
+    assert_eq!(pending_emails("pending").await, 10);
+
+    // Nothing is due yet
+    assert_eq!(
+        platform::notify::digest::flush_due(&state).await.unwrap(),
+        0
+    );
+
+    sqlx::query(
+        "UPDATE notification_digest_queue SET created_at = now() - interval '61 minutes'
+         WHERE user_id = $1",
+    )
+    .bind(admin_id)
+    .execute(&pool)
+    .await
+    .unwrap();
+
+    // A failed send keeps the batch queued for the next flush
+    let mut unreachable = (*state.config).clone();
+    unreachable.smtp_host = Some("127.0.0.1".into());
+    unreachable.smtp_port = 1;
+    let failing = platform::store::AppState {
+        config: std::sync::Arc::new(unreachable),
+        ..state.clone()
+    };
+    assert_eq!(
+        platform::notify::digest::flush_due(&failing).await.unwrap(),
+        1
+    );
+    assert_eq!(pending_emails("pending").await, 10);
+
+    assert_eq!(
+        platform::notify::digest::flush_due(&state).await.unwrap(),
+        1
+    );
+    assert_eq!(pending_emails("sent").await, 10);
+
+    let (queued,): (i64,) = sqlx::query_as("SELECT COUNT(*) FROM notification_digest_queue")
+        .fetch_one(&pool)
+        .await
+        .unwrap();
+    assert_eq!(queued, 0);
+}
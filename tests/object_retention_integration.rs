@@ -0,0 +1,154 @@
+// Copyright (c) 2026 Steven Hooker. Exclusively licensed to and distributed by AgentSphere GmbH.
+// SPDX-License-Identifier: BUSL-1.1
+
+//! Integration tests for `MinIO` object retention: expiry metadata on build
+//! logs and `store::lifecycle::prune_expired`.
+//!
+//! Retention is set to 100 years and the expired fixtures are dated 1920, so
+//! pruning never touches objects written by concurrently running tests.
+
+mod helpers;
+
+use std::sync::Arc;
+
+use platform::store::{AppState, lifecycle};
+use sqlx::PgPool;
+use uuid::Uuid;
+
+const RETENTION_DAYS: u32 = 36_500;
+
+async fn retention_state(pool: PgPool) -> (AppState, String) {
+    let (mut state, token) = helpers::test_state(pool).await;
+    let mut config = (*state.config).clone();
+    config.object_retention_days = RETENTION_DAYS;
+    state.config = Arc::new(config);
+    (state, token)
+}
+
+/// Insert a finished pipeline with one step whose log is stored at `log_ref`.
+async fn insert_pipeline(pool: &PgPool, project_id: Uuid, finished_at: &str) -> (Uuid, String) {
+    let pipeline_id = Uuid::new_v4();
+    sqlx::query(
+        "INSERT INTO pipelines (id, project_id, trigger, git_ref, status, finished_at)
+         VALUES ($1, $2, 'api', 'refs/heads/main', 'success', $3::timestamptz)",
+    )
+    .bind(pipeline_id)
+    .bind(project_id)
+    .bind(finished_at)
+    .execute(pool)
+    .await
+    .unwrap();
+    let log_ref = format!("logs/pipelines/{pipeline_id}/build.log");
+    sqlx::query(
+        "INSERT INTO pipeline_steps (pipeline_id, project_id, step_order, name, image, status,
+                                     log_ref, finished_at)
+         VALUES ($1, $2, 0, 'build', 'alpine', 'success', $3, $4::timestamptz)",
+    )
+    .bind(pipeline_id)
+    .bind(project_id)
+    .bind(&log_ref)
+    .bind(finished_at)
+    .execute(pool)
+    .await
+    .unwrap();
+    (pipeline_id, log_ref)
+}
+
+#[sqlx::test(migrations = "./migrations")]
+async fn expiring_writes_carry_expiry_metadata(pool: PgPool) {
+    let (state, _) = retention_state(pool).await;
+    let path = format!("logs/pipelines/{}/build.log", Uuid::new_v4());
+
+    lifecycle::write_expiring(&state, &path, b"hello".to_vec())
+        .await
+        .unwrap();
+
+    let meta = state.minio.stat(&path).await.unwrap();
+    let expires_at = meta
+        .user_metadata()
+        .and_then(|m| m.get(lifecycle::EXPIRES_AT_KEY))
+        .expect("expires-at metadata");
+    let expires_at: chrono::DateTime<chrono::Utc> = expires_at.parse().unwrap();
+    assert!(expires_at > chrono::Utc::now() + chrono::Duration::days(36_000));
+}
+
+#[sqlx::test(migrations = "./migrations")]
+async fn prune_removes_expired_logs_artifacts_and_archives(pool: PgPool) {
+    let (state, token) = retention_state(pool.clone()).await;
+    let app = helpers::test_router(state.clone());
+    let project_id = helpers::create_project(&app, &token, "retention", "private").await;
+
+    // Pipeline logs
+    let (old_pipeline, old_log) = insert_pipeline(&pool, project_id, "1920-01-01T00:00:00Z").await;
+    let (_, new_log) = insert_pipeline(&pool, project_id, "2026-01-01T00:00:00Z").await;
+    for path in [&old_log, &new_log] {
+        lifecycle::write_expiring(&state, path, b"log".to_vec())
+            .await
+            .unwrap();
+    }
+
+    // An expired artifact
+    let artifact_path = format!("artifacts/{old_pipeline}/{}/report.xml", Uuid::new_v4());
+    state
+        .minio
+        .write(&artifact_path, b"<xml/>".to_vec())
+        .await
+        .unwrap();
+    sqlx::query(
+        "INSERT INTO artifacts (pipeline_id, name, minio_path, expires_at)
+         VALUES ($1, 'report.xml', $2, now() - interval '1 day')",
+    )
+    .bind(old_pipeline)
+    .bind(&artifact_path)
+    .execute(&pool)
+    .await
+    .unwrap();
+
+    // Parquet archives
+    let batch = Uuid::new_v4();
+    let old_archive = format!("otel/logs/1920-01-01/logs_{batch}.parquet");
+    let new_archive = format!("otel/logs/2026-01-01/logs_{batch}.parquet");
+    for path in [&old_archive, &new_archive] {
+        state.minio.write(path, b"PAR1".to_vec()).await.unwrap();
+    }
+
+    let deleted = lifecycle::prune_expired(&state).await.unwrap();
+    assert!(deleted >= 3, "deleted {deleted}");
+
+    assert!(!state.minio.exists(&old_log).await.unwrap());
+    assert!(state.minio.exists(&new_log).await.unwrap());
+    let log_ref: Option<String> =
+        sqlx::query_scalar("SELECT log_ref FROM pipeline_steps WHERE pipeline_id = $1")
+            .bind(old_pipeline)
+            .fetch_one(&pool)
+            .await
+            .unwrap();
+    assert!(log_ref.is_none());
+
+    assert!(!state.minio.exists(&artifact_path).await.unwrap());
+    let artifacts: i64 =
+        sqlx::query_scalar("SELECT count(*) FROM artifacts WHERE pipeline_id = $1")
+            .bind(old_pipeline)
+            .fetch_one(&pool)
+            .await
+            .unwrap();
+    assert_eq!(artifacts, 0);
+
+    assert!(!state.minio.exists(&old_archive).await.unwrap());
+    assert!(state.minio.exists(&new_archive).await.unwrap());
+
+    state.minio.delete(&new_log).await.unwrap();
+    state.minio.delete(&new_archive).await.unwrap();
+}
+
+#[sqlx::test(migrations = "./migrations")]
+async fn prune_is_noop_without_retention(pool: PgPool) {
+    let (state, token) = helpers::test_state(pool.clone()).await;
+    let app = helpers::test_router(state.clone());
+    let project_id = helpers::create_project(&app, &token, "no-retention", "private").await;
+    let (_, log_ref) = insert_pipeline(&pool, project_id, "1920-01-01T00:00:00Z").await;
+    state.minio.write(&log_ref, b"log".to_vec()).await.unwrap();
+
+    assert_eq!(lifecycle::prune_expired(&state).await.unwrap(), 0);
+    assert!(state.minio.exists(&log_ref).await.unwrap());
+}
@@ -308,6 +308,7 @@ async fn ingest_metrics_protobuf(pool: PgPool) {
     let flush_cancel = tokio_util::sync::CancellationToken::new();
     let handle = tokio::spawn(platform::observe::ingest::flush_metrics(
         pool.clone(),
+        0,
         metrics_rx,
         flush_cancel.clone(),
     ));
@@ -432,6 +433,34 @@ async fn otlp_ingest_missing_project_id_forbidden_for_non_admin(pool: PgPool) {
     assert_eq!(status, StatusCode::FORBIDDEN);
 }
 
+/// A project-bounded token can't send system-level OTLP, even when its owner
+/// is an admin.
+#[sqlx::test(migrations = "./migrations")]
+async fn otlp_ingest_missing_project_id_forbidden_for_bounded_token(pool: PgPool) {
+    let (state, admin_token) = test_state(pool.clone()).await;
+
+    let (channels, _spans_rx, _logs_rx, _metrics_rx) = platform::observe::ingest::create_channels();
+    let app = ingest_test_router(state, channels);
+
+    let project_id = helpers::create_project(&app, &admin_token, "otlp-bound", "private").await;
+    let (status, token_body) = helpers::post_json(
+        &app,
+        &admin_token,
+        "/api/tokens",
+        serde_json::json!({"name": "ci-otlp", "project_id": project_id}),
+    )
+    .await;
+    assert_eq!(status, StatusCode::CREATED, "{token_body}");
+    let ci_token = token_body["token"].as_str().unwrap();
+
+    let trace_id: [u8; 16] = [10; 16];
+    let span_id: [u8; 8] = [20; 8];
+    let body = build_trace_request_no_project(&trace_id, span_id);
+
+    let (status, _resp_bytes) = post_protobuf(&app, ci_token, "/v1/traces", body).await;
+    assert_eq!(status, StatusCode::FORBIDDEN);
+}
+
 /// OTLP ingest rejects invalid (non-UUID) `platform.project_id` with 400.
 #[sqlx::test(migrations = "./migrations")]
 async fn otlp_ingest_invalid_project_id_uuid_returns_400(pool: PgPool) {
@@ -771,6 +800,7 @@ async fn ingest_sum_monotonic_metric(pool: PgPool) {
     let flush_cancel = tokio_util::sync::CancellationToken::new();
     let handle = tokio::spawn(platform::observe::ingest::flush_metrics(
         pool.clone(),
+        0,
         metrics_rx,
         flush_cancel.clone(),
     ));
@@ -840,6 +870,7 @@ async fn ingest_sum_non_monotonic_metric(pool: PgPool) {
     let flush_cancel = tokio_util::sync::CancellationToken::new();
     let handle = tokio::spawn(platform::observe::ingest::flush_metrics(
         pool.clone(),
+        0,
         metrics_rx,
         flush_cancel.clone(),
     ));
@@ -906,6 +937,7 @@ async fn ingest_histogram_metric(pool: PgPool) {
     let flush_cancel = tokio_util::sync::CancellationToken::new();
     let handle = tokio::spawn(platform::observe::ingest::flush_metrics(
         pool.clone(),
+        0,
         metrics_rx,
         flush_cancel.clone(),
     ));
@@ -1256,3 +1288,303 @@ async fn ensure_scoped_tokens_nonexistent_project_returns_error(pool: PgPool) {
         "error should mention project not found, got: {err_msg}"
     );
 }
+
+// ---------------------------------------------------------------------------
+// Tests — OTLP/gRPC
+// ---------------------------------------------------------------------------
+
+/// Send a unary gRPC call to the OTLP/gRPC routes; returns the `grpc-status` code.
+async fn grpc_export(
+    state: &platform::store::AppState,
+    channels: platform::observe::ingest::IngestChannels,
+    token: &str,
+    service: &str,
+    message: Vec<u8>,
+) -> String {
+    use axum::body::Body;
+    use axum::http::Request;
+    use http_body_util::BodyExt;
+    use tower::ServiceExt;
+
+    // Length-prefixed message: compression flag + big-endian u32 length.
+    let mut frame = vec![0u8];
+    frame.extend_from_slice(&u32::try_from(message.len()).unwrap().to_be_bytes());
+    frame.extend_from_slice(&message);
+
+    let req = Request::builder()
+        .method("POST")
+        .uri(format!("/{service}/Export"))
+        .header("content-type", "application/grpc")
+        .header("te", "trailers")
+        .header("authorization", format!("Bearer {token}"))
+        .body(Body::from(frame))
+        .unwrap();
+
+    let app = platform::observe::grpc::routes(state, &channels).into_axum_router();
+    let resp = app.oneshot(req).await.unwrap();
+    assert_eq!(resp.status(), StatusCode::OK);
+
+    // Errors are trailers-only responses (status in headers); success sends it in trailers.
+    if let Some(code) = resp.headers().get("grpc-status") {
+        return code.to_str().unwrap().to_owned();
+    }
+    let collected = resp.into_body().collect().await.unwrap();
+    let trailers = collected.trailers().expect("grpc trailers").clone();
+    trailers["grpc-status"].to_str().unwrap().to_owned()
+}
+
+/// A span exported over OTLP/gRPC lands in the traces table.
+#[sqlx::test(migrations = "./migrations")]
+async fn grpc_export_traces_persists_span(pool: PgPool) {
+    let (state, admin_token) = test_state(pool.clone()).await;
+
+    let (channels, spans_rx, _logs_rx, _metrics_rx) = platform::observe::ingest::create_channels();
+    let app = ingest_test_router(state.clone(), channels.clone());
+    let project_id = helpers::create_project(&app, &admin_token, "grpc-proj", "private").await;
+
+    let trace_id: [u8; 16] = [0x42; 16];
+    let body = build_trace_request(&trace_id, [7; 8], project_id);
+    let code = grpc_export(
+        &state,
+        channels,
+        &admin_token,
+        "opentelemetry.proto.collector.trace.v1.TraceService",
+        body,
+    )
+    .await;
+    assert_eq!(code, "0");
+
+    let flush_cancel = tokio_util::sync::CancellationToken::new();
+    let handle = tokio::spawn(platform::observe::ingest::flush_spans(
+        pool.clone(),
+        spans_rx,
+        flush_cancel.clone(),
+    ));
+    flush_cancel.cancel();
+    let _ = handle.await;
+
+    let expected_trace_id = "42".repeat(16);
+    let (status, body) = helpers::get_json(
+        &app,
+        &admin_token,
+        &format!("/api/observe/traces/{expected_trace_id}"),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK, "trace not found: {body}");
+    assert_eq!(body["trace_id"], expected_trace_id);
+}
+
+/// OTLP/gRPC maps auth failures to gRPC status codes.
+#[sqlx::test(migrations = "./migrations")]
+async fn grpc_export_rejects_bad_token_and_unauthorized_project(pool: PgPool) {
+    let (state, admin_token) = test_state(pool.clone()).await;
+
+    let (channels, _spans_rx, _logs_rx, _metrics_rx) = platform::observe::ingest::create_channels();
+    let app = ingest_test_router(state.clone(), channels.clone());
+    let project_id = helpers::create_project(&app, &admin_token, "grpc-auth", "private").await;
+    let (_uid, user_token) =
+        helpers::create_user(&app, &admin_token, "grpc-user", "grpc-user@test.com").await;
+
+    let service = "opentelemetry.proto.collector.logs.v1.LogsService";
+
+    // UNAUTHENTICATED
+    let code = grpc_export(
+        &state,
+        channels.clone(),
+        "not-a-token",
+        service,
+        build_logs_request(project_id),
+    )
+    .await;
+    assert_eq!(code, "16");
+
+    // PERMISSION_DENIED
+    let code = grpc_export(
+        &state,
+        channels,
+        &user_token,
+        service,
+        build_logs_request(project_id),
+    )
+    .await;
+    assert_eq!(code, "7");
+}
+
+// ---------------------------------------------------------------------------
+// Tests — metric series cardinality cap
+// ---------------------------------------------------------------------------
+
+/// New series beyond the per-project cap are dropped; existing series keep ingesting.
+#[sqlx::test(migrations = "./migrations")]
+async fn metric_series_cap_drops_new_series(pool: PgPool) {
+    let (state, admin_token) = test_state(pool.clone()).await;
+    let (channels, _spans_rx, _logs_rx, metrics_rx) = platform::observe::ingest::create_channels();
+    let app = ingest_test_router(state.clone(), channels.clone());
+    let project_id = helpers::create_project(&app, &admin_token, "card-proj", "private").await;
+
+    let record = |request_id: &str, n: u32| platform::observe::store::MetricRecord {
+        name: "http_requests".into(),
+        labels: serde_json::json!({ "request_id": request_id }),
+        metric_type: "gauge".into(),
+        unit: None,
+        project_id: Some(project_id),
+        timestamp: chrono::Utc::now() + chrono::Duration::milliseconds(i64::from(n)),
+        value: f64::from(n),
+    };
+    for (n, id) in (0..).zip(["a", "b", "c", "a"]) {
+        channels.metrics_tx.send(record(id, n)).await.unwrap();
+    }
+
+    let cancel = tokio_util::sync::CancellationToken::new();
+    let handle = tokio::spawn(platform::observe::ingest::flush_metrics(
+        pool.clone(),
+        2,
+        metrics_rx,
+        cancel.clone(),
+    ));
+    cancel.cancel();
+    let _ = handle.await;
+
+    let series: Vec<String> = sqlx::query_scalar(
+        "SELECT labels->>'request_id' FROM metric_series WHERE project_id = $1 ORDER BY 1",
+    )
+    .bind(project_id)
+    .fetch_all(&pool)
+    .await
+    .unwrap();
+    assert_eq!(series, vec!["a", "b"]);
+
+    let samples: i64 = sqlx::query_scalar(
+        "SELECT COUNT(*) FROM metric_samples ms JOIN metric_series s ON s.id = ms.series_id \
+         WHERE s.project_id = $1",
+    )
+    .bind(project_id)
+    .fetch_one(&pool)
+    .await
+    .unwrap();
+    assert_eq!(samples, 3, "both samples of existing series kept");
+    assert!(platform::observe::ingest::metrics_dropped() >= 1);
+
+    let (status, body) = helpers::get_json(
+        &app,
+        &admin_token,
+        &format!("/api/observe/metrics/cardinality?project_id={project_id}"),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK, "{body}");
+    assert_eq!(body["items"][0]["name"], "http_requests");
+    assert_eq!(body["items"][0]["series_count"], 2);
+    assert!(body["metrics_dropped"].as_u64().unwrap() >= 1);
+}
+
+/// The cardinality report is admin-only.
+#[sqlx::test(migrations = "./migrations")]
+async fn metric_cardinality_requires_admin(pool: PgPool) {
+    let (state, admin_token) = test_state(pool.clone()).await;
+    let (channels, _spans_rx, _logs_rx, _metrics_rx) = platform::observe::ingest::create_channels();
+    let app = ingest_test_router(state, channels);
+    let (_uid, user_token) =
+        helpers::create_user(&app, &admin_token, "card-user", "card-user@test.com").await;
+
+    let (status, _) =
+        helpers::get_json(&app, &user_token, "/api/observe/metrics/cardinality").await;
+    assert_eq!(status, StatusCode::FORBIDDEN);
+}
+
+/// Log metric rules count matching logs into a counter series per service.
+#[sqlx::test(migrations = "./migrations")]
+async fn log_metric_rule_counts_matching_logs(pool: PgPool) {
+    let (state, admin_token) = test_state(pool.clone()).await;
+    let (channels, _spans_rx, logs_rx, _metrics_rx) = platform::observe::ingest::create_channels();
+    let app = ingest_test_router(state.clone(), channels.clone());
+    let project_id = helpers::create_project(&app, &admin_token, "logm-proj", "private").await;
+
+    let (status, body) = helpers::post_json(
+        &app,
+        &admin_token,
+        "/api/observe/log-metrics",
+        serde_json::json!({
+            "metric_name": "log_errors_total",
+            "service": "payments",
+            "level": "error",
+        }),
+    )
+    .await;
+    assert_eq!(status, StatusCode::CREATED, "{body}");
+
+    let log = |service: &str, level: &str| platform::observe::store::LogEntryRecord {
+        timestamp: chrono::Utc::now(),
+        trace_id: None,
+        span_id: None,
+        project_id: Some(project_id),
+        session_id: None,
+        user_id: None,
+        service: service.into(),
+        level: level.into(),
+        source: "external".into(),
+        message: "card declined".into(),
+        attributes: None,
+    };
+    for (service, level) in [
+        ("payments", "error"),
+        ("payments", "error"),
+        ("payments", "info"),
+        ("checkout", "error"),
+    ] {
+        channels.logs_tx.send(log(service, level)).await.unwrap();
+    }
+
+    let cancel = tokio_util::sync::CancellationToken::new();
+    let handle = tokio::spawn(platform::observe::ingest::flush_logs(
+        pool.clone(),
+        state.valkey.clone(),
+        logs_rx,
+        cancel.clone(),
+    ));
+    cancel.cancel();
+    let _ = handle.await;
+
+    let rows: Vec<(serde_json::Value, String, Option<f64>)> = sqlx::query_as(
+        "SELECT labels, metric_type, last_value FROM metric_series \
+         WHERE name = 'log_errors_total' AND project_id = $1",
+    )
+    .bind(project_id)
+    .fetch_all(&pool)
+    .await
+    .unwrap();
+    assert_eq!(rows.len(), 1);
+    assert_eq!(rows[0].0, serde_json::json!({ "service": "payments" }));
+    assert_eq!(rows[0].1, "counter");
+    assert_eq!(rows[0].2, Some(2.0));
+}
+
+/// Log metric rule management is admin-only and validates the metric name.
+#[sqlx::test(migrations = "./migrations")]
+async fn log_metric_rules_admin_only_and_validated(pool: PgPool) {
+    let (state, admin_token) = test_state(pool.clone()).await;
+    let (channels, _spans_rx, _logs_rx, _metrics_rx) = platform::observe::ingest::create_channels();
+    let app = ingest_test_router(state, channels);
+    let (_uid, user_token) =
+        helpers::create_user(&app, &admin_token, "logm-user", "logm-user@test.com").await;
+
+    let (status, _) = helpers::get_json(&app, &user_token, "/api/observe/log-metrics").await;
+    assert_eq!(status, StatusCode::FORBIDDEN);
+
+    let (status, _) = helpers::post_json(
+        &app,
+        &admin_token,
+        "/api/observe/log-metrics",
+        serde_json::json!({ "metric_name": "bad-name", "level": "error" }),
+    )
+    .await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+
+    let (status, _) = helpers::post_json(
+        &app,
+        &admin_token,
+        "/api/observe/log-metrics",
+        serde_json::json!({ "metric_name": "everything_total" }),
+    )
+    .await;
+    assert_eq!(status, StatusCode::BAD_REQUEST, "rule without conditions");
+}
@@ -384,6 +384,85 @@ async fn get_trace_not_found(pool: PgPool) {
     assert_eq!(status, StatusCode::NOT_FOUND);
 }
 
+/// Trace logs endpoint returns the trace's logs oldest first with span IDs.
+#[sqlx::test(migrations = "./migrations")]
+async fn get_trace_logs_returns_correlated_logs(pool: PgPool) {
+    let (state, admin_token) = test_state(pool.clone()).await;
+    let app = test_router(state);
+
+    let trace_id = format!("trace-logs-{}", Uuid::new_v4().simple());
+    insert_test_span(&pool, &trace_id, "span-root", "trace-logs-svc").await;
+
+    let now = Utc::now();
+    let log =
+        |offset_ms: i64, span_id: &str, message: &str| platform::observe::store::LogEntryRecord {
+            timestamp: now + chrono::Duration::milliseconds(offset_ms),
+            trace_id: Some(trace_id.clone()),
+            span_id: Some(span_id.into()),
+            project_id: None,
+            session_id: None,
+            user_id: None,
+            service: "trace-logs-svc".into(),
+            level: "info".into(),
+            source: "external".into(),
+            message: message.into(),
+            attributes: None,
+        };
+    platform::observe::store::write_logs(
+        &pool,
+        &[
+            log(20, "span-child", "second"),
+            log(10, "span-root", "first"),
+        ],
+    )
+    .await
+    .unwrap();
+    // Unrelated log must not show up
+    insert_test_log(&pool, "trace-logs-svc", "info", "uncorrelated").await;
+
+    let (status, body) = helpers::get_json(
+        &app,
+        &admin_token,
+        &format!("/api/observe/traces/{trace_id}/logs"),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK, "trace logs failed: {body}");
+    assert_eq!(body["total"], 2);
+    let items = body["items"].as_array().unwrap();
+    assert_eq!(items[0]["message"], "first");
+    assert_eq!(items[0]["span_id"], "span-root");
+    assert_eq!(items[1]["message"], "second");
+    assert_eq!(items[1]["span_id"], "span-child");
+
+    let (status, _) = helpers::get_json(
+        &app,
+        &admin_token,
+        "/api/observe/traces/nonexistent-trace-id-12345/logs",
+    )
+    .await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+}
+
+/// Trace logs of a system-level trace are admin-only, like the trace itself.
+#[sqlx::test(migrations = "./migrations")]
+async fn get_trace_logs_requires_trace_access(pool: PgPool) {
+    let (state, admin_token) = test_state(pool.clone()).await;
+    let app = test_router(state);
+
+    let trace_id = format!("trace-logs-perm-{}", Uuid::new_v4().simple());
+    insert_test_span(&pool, &trace_id, "span-root", "trace-logs-svc").await;
+
+    let (_uid, user_token) =
+        create_user(&app, &admin_token, "trace-logs-user", "tracelogs@test.com").await;
+    let (status, _) = helpers::get_json(
+        &app,
+        &user_token,
+        &format!("/api/observe/traces/{trace_id}/logs"),
+    )
+    .await;
+    assert_eq!(status, StatusCode::FORBIDDEN);
+}
+
 // ---------------------------------------------------------------------------
 // Metric query tests
 // ---------------------------------------------------------------------------
@@ -602,8 +681,8 @@ async fn observe_logs_requires_permission(pool: PgPool) {
 async fn rotate_logs_archives_old_data(pool: PgPool) {
     let (state, _admin_token) = test_state(pool.clone()).await;
 
-    // Insert log with timestamp 72h ago (> 48h cutoff)
-    let old_ts = Utc::now() - chrono::Duration::hours(72);
+    // Insert log with timestamp 8 days ago (> 7 day hot retention)
+    let old_ts = Utc::now() - chrono::Duration::days(8);
     let log = platform::observe::store::LogEntryRecord {
         timestamp: old_ts,
         trace_id: None,
@@ -641,6 +720,141 @@ async fn rotate_logs_archives_old_data(pool: PgPool) {
             .await
             .unwrap();
     assert_eq!(count_after.0, 0, "rotated logs should be deleted");
+
+    // Archived under the date the log was written, not the rotation date
+    let prefix = format!("otel/logs/{}/", old_ts.format("%Y-%m-%d"));
+    let files = state.minio.list(&prefix).await.unwrap();
+    assert!(
+        files.iter().any(|e| e.path().ends_with(".parquet")),
+        "parquet archive missing under {prefix}"
+    );
+}
+
+/// Logs within the hot-retention window stay in Postgres.
+#[sqlx::test(migrations = "./migrations")]
+async fn rotate_logs_keeps_hot_data(pool: PgPool) {
+    let (state, _admin_token) = test_state(pool.clone()).await;
+
+    let log = platform::observe::store::LogEntryRecord {
+        timestamp: Utc::now() - chrono::Duration::days(3),
+        trace_id: None,
+        span_id: None,
+        project_id: None,
+        session_id: None,
+        user_id: None,
+        service: "hot-svc".into(),
+        level: "info".into(),
+        source: "external".into(),
+        message: "recent log".into(),
+        attributes: None,
+    };
+    platform::observe::store::write_logs(&pool, &[log])
+        .await
+        .unwrap();
+
+    let rotated = platform::observe::parquet::rotate_logs(&state)
+        .await
+        .unwrap();
+    assert_eq!(rotated, 0);
+
+    let count: (i64,) =
+        sqlx::query_as("SELECT COUNT(*) FROM log_entries WHERE service = 'hot-svc'")
+            .fetch_one(&pool)
+            .await
+            .unwrap();
+    assert_eq!(count.0, 1);
+}
+
+/// Log search over a range reaching past hot retention includes archived entries.
+#[sqlx::test(migrations = "./migrations")]
+async fn search_logs_includes_archived_entries(pool: PgPool) {
+    let (state, admin_token) = test_state(pool.clone()).await;
+    let app = test_router(state.clone());
+
+    let log = |age: chrono::Duration, message: &str| platform::observe::store::LogEntryRecord {
+        timestamp: Utc::now() - age,
+        trace_id: None,
+        span_id: None,
+        project_id: None,
+        session_id: None,
+        user_id: None,
+        service: "archive-svc".into(),
+        level: "warn".into(),
+        source: "system".into(),
+        message: message.into(),
+        attributes: None,
+    };
+    platform::observe::store::write_logs(
+        &pool,
+        &[
+            log(chrono::Duration::days(10), "cold entry"),
+            log(chrono::Duration::days(9), "other cold entry"),
+            log(chrono::Duration::hours(1), "hot entry"),
+        ],
+    )
+    .await
+    .unwrap();
+    let rotated = platform::observe::parquet::rotate_logs(&state)
+        .await
+        .unwrap();
+    assert_eq!(rotated, 2);
+
+    let (status, body) = helpers::get_json(
+        &app,
+        &admin_token,
+        "/api/observe/logs?service=archive-svc&range=30d",
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK, "{body}");
+    assert_eq!(body["total"], 3);
+    let items = body["items"].as_array().unwrap();
+    let summary: Vec<(&str, bool)> = items
+        .iter()
+        .map(|i| {
+            (
+                i["message"].as_str().unwrap(),
+                i["archived"].as_bool().unwrap(),
+            )
+        })
+        .collect();
+    assert_eq!(
+        summary,
+        vec![
+            ("hot entry", false),
+            ("other cold entry", true),
+            ("cold entry", true),
+        ]
+    );
+    assert_eq!(items[1]["source"], "system");
+
+    // Pagination continues from Postgres into the archive
+    let (_, body) = helpers::get_json(
+        &app,
+        &admin_token,
+        "/api/observe/logs?service=archive-svc&range=30d&limit=1&offset=2",
+    )
+    .await;
+    assert_eq!(body["total"], 3);
+    assert_eq!(body["items"][0]["message"], "cold entry");
+
+    // Filters apply to archived rows too
+    let (_, body) = helpers::get_json(
+        &app,
+        &admin_token,
+        "/api/observe/logs?service=archive-svc&range=30d&q=OTHER",
+    )
+    .await;
+    assert_eq!(body["total"], 1);
+    assert_eq!(body["items"][0]["message"], "other cold entry");
+
+    // Ranges inside hot retention never touch the archive
+    let (_, body) = helpers::get_json(
+        &app,
+        &admin_token,
+        "/api/observe/logs?service=archive-svc&range=7d",
+    )
+    .await;
+    assert_eq!(body["total"], 1);
 }
 
 /// Rotate old spans to parquet.
@@ -648,7 +862,7 @@ async fn rotate_logs_archives_old_data(pool: PgPool) {
 async fn rotate_spans_archives_old_data(pool: PgPool) {
     let (state, _admin_token) = test_state(pool.clone()).await;
 
-    let old_ts = Utc::now() - chrono::Duration::hours(72);
+    let old_ts = Utc::now() - chrono::Duration::days(8);
     let span = platform::observe::store::SpanRecord {
         trace_id: "rot-trace".into(),
         span_id: "rot-span".into(),
@@ -688,8 +902,8 @@ async fn rotate_spans_archives_old_data(pool: PgPool) {
 async fn rotate_metrics_archives_old_data(pool: PgPool) {
     let (state, _admin_token) = test_state(pool.clone()).await;
 
-    // Metric samples > 1h old
-    let old_ts = Utc::now() - chrono::Duration::hours(2);
+    // Metric samples older than the 7 day hot retention
+    let old_ts = Utc::now() - chrono::Duration::days(8);
     let metric = platform::observe::store::MetricRecord {
         name: "rotate_metric_test".into(),
         labels: serde_json::json!({"host": "test"}),
@@ -1433,6 +1647,93 @@ async fn list_traces_empty(pool: PgPool) {
     assert!(body["items"].as_array().unwrap().is_empty());
 }
 
+/// Trace list filters by minimum duration and span attribute.
+#[sqlx::test(migrations = "./migrations")]
+async fn list_traces_by_min_duration_and_attr(pool: PgPool) {
+    let (state, admin_token) = test_state(pool.clone()).await;
+    let app = test_router(state);
+
+    let now = Utc::now();
+    let root = |trace_id: &str, duration_ms: i32, attributes: serde_json::Value| {
+        platform::observe::store::SpanRecord {
+            trace_id: trace_id.into(),
+            span_id: format!("{trace_id}-root"),
+            parent_span_id: None,
+            name: "request".into(),
+            service: "attr-svc".into(),
+            kind: "server".into(),
+            status: "ok".into(),
+            attributes: Some(attributes),
+            events: None,
+            duration_ms: Some(duration_ms),
+            started_at: now,
+            finished_at: Some(now + chrono::Duration::milliseconds(duration_ms.into())),
+            project_id: None,
+            session_id: None,
+            user_id: None,
+        }
+    };
+    platform::observe::store::write_spans(
+        &pool,
+        &[
+            root(
+                "slow-post",
+                1500,
+                serde_json::json!({"http.method": "POST", "http.status_code": 500}),
+            ),
+            root("slow-get", 1500, serde_json::json!({"http.method": "GET"})),
+            root("fast-post", 50, serde_json::json!({"http.method": "POST"})),
+        ],
+    )
+    .await
+    .unwrap();
+
+    let trace_ids = |body: &serde_json::Value| -> Vec<String> {
+        let mut ids: Vec<String> = body["items"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|t| t["trace_id"].as_str().unwrap().to_owned())
+            .collect();
+        ids.sort();
+        ids
+    };
+
+    let (status, body) = helpers::get_json(
+        &app,
+        &admin_token,
+        "/api/observe/traces?min_duration_ms=1000&attr=http.method:POST",
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK, "{body}");
+    assert_eq!(trace_ids(&body), vec!["slow-post"]);
+    assert_eq!(body["total"], 1);
+
+    let (_, body) = helpers::get_json(
+        &app,
+        &admin_token,
+        "/api/observe/traces?min_duration_ms=1000",
+    )
+    .await;
+    assert_eq!(trace_ids(&body), vec!["slow-get", "slow-post"]);
+
+    // Numeric attribute values match their typed form
+    let (_, body) = helpers::get_json(
+        &app,
+        &admin_token,
+        "/api/observe/traces?attr=http.status_code%3D500",
+    )
+    .await;
+    assert_eq!(trace_ids(&body), vec!["slow-post"]);
+
+    let (status, _) =
+        helpers::get_json(&app, &admin_token, "/api/observe/traces?attr=no-separator").await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    let (status, _) =
+        helpers::get_json(&app, &admin_token, "/api/observe/traces?min_duration_ms=-1").await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+}
+
 // ---------------------------------------------------------------------------
 // Trace detail — edge cases
 // ---------------------------------------------------------------------------
@@ -3201,3 +3502,246 @@ async fn metrics_same_project_upserts_series(pool: PgPool) {
     .unwrap();
     assert_eq!(sample_count.0, 2);
 }
+
+/// Hourly-step queries aggregate into buckets, first from raw samples and then,
+/// once the rollup task has run, from `metric_rollups_1h` with the same result.
+#[sqlx::test(migrations = "./migrations")]
+async fn metrics_step_query_served_from_rollups(pool: PgPool) {
+    use chrono::DurationRound;
+    use platform::observe::rollup::{Resolution, rollup_once};
+
+    let (state, admin_token) = test_state(pool.clone()).await;
+    let app = test_router(state);
+
+    let name = format!("rollup_{}", Uuid::new_v4().simple());
+    let hour = (Utc::now() - chrono::Duration::hours(2))
+        .duration_trunc(chrono::Duration::hours(1))
+        .unwrap();
+    let records: Vec<MetricRecord> = [1.0, 5.0, 3.0]
+        .into_iter()
+        .enumerate()
+        .map(|(i, value)| MetricRecord {
+            name: name.clone(),
+            labels: serde_json::json!({"host": "a"}),
+            metric_type: "gauge".into(),
+            unit: None,
+            project_id: None,
+            timestamp: hour + chrono::Duration::minutes(i64::try_from(i).unwrap() + 1),
+            value,
+        })
+        .collect();
+    write_metrics(&pool, &records).await.unwrap();
+
+    let query =
+        |agg: &str| format!("/api/observe/metrics?name={name}&range=24h&step=3600&agg={agg}");
+    let expect = [
+        ("max", 5.0),
+        ("min", 1.0),
+        ("avg", 3.0),
+        ("sum", 9.0),
+        ("count", 3.0),
+    ];
+
+    for pass in ["raw", "rollup"] {
+        if pass == "rollup" {
+            while rollup_once(&pool, Resolution::Hour, 1).await.unwrap() {}
+            let rows: (i64,) = sqlx::query_as("SELECT COUNT(*) FROM metric_rollups_1h")
+                .fetch_one(&pool)
+                .await
+                .unwrap();
+            assert_eq!(rows.0, 1, "one hourly bucket rolled up");
+        }
+        for (agg, value) in expect {
+            let (status, body) = helpers::get_json(&app, &admin_token, &query(agg)).await;
+            assert_eq!(status, StatusCode::OK, "{pass} {agg}: {body}");
+            let points = body[0]["points"].as_array().unwrap();
+            assert_eq!(points.len(), 1, "{pass} {agg}: {body}");
+            assert_eq!(points[0]["value"].as_f64(), Some(value), "{pass} {agg}");
+            assert_eq!(
+                points[0]["timestamp"]
+                    .as_str()
+                    .unwrap()
+                    .parse::<chrono::DateTime<Utc>>()
+                    .unwrap(),
+                hour
+            );
+        }
+    }
+
+    let (status, _) = helpers::get_json(
+        &app,
+        &admin_token,
+        &format!("/api/observe/metrics?name={name}&agg=median"),
+    )
+    .await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+}
+
+/// `step` buckets raw samples into fixed windows and applies `agg` per bucket.
+#[sqlx::test(migrations = "./migrations")]
+async fn metrics_step_and_agg_bucket_raw_samples(pool: PgPool) {
+    use chrono::DurationRound;
+
+    let (state, admin_token) = test_state(pool.clone()).await;
+    let app = test_router(state);
+
+    let name = format!("step_{}", Uuid::new_v4().simple());
+    let minute = (Utc::now() - chrono::Duration::minutes(10))
+        .duration_trunc(chrono::Duration::minutes(1))
+        .unwrap();
+    // Two samples in the first minute, three in the next
+    let samples = [(5, 2.0), (40, 8.0), (65, 1.0), (80, 4.0), (110, 3.0)];
+    let records: Vec<MetricRecord> = samples
+        .iter()
+        .map(|&(secs, value)| MetricRecord {
+            name: name.clone(),
+            labels: serde_json::json!({"host": "a"}),
+            metric_type: "gauge".into(),
+            unit: None,
+            project_id: None,
+            timestamp: minute + chrono::Duration::seconds(secs),
+            value,
+        })
+        .collect();
+    write_metrics(&pool, &records).await.unwrap();
+
+    let values = |body: &serde_json::Value| -> Vec<f64> {
+        body[0]["points"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|p| p["value"].as_f64().unwrap())
+            .collect()
+    };
+
+    let (status, body) = helpers::get_json(
+        &app,
+        &admin_token,
+        &format!("/api/observe/metrics?name={name}&step=60&agg=max"),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK, "{body}");
+    assert_eq!(values(&body), vec![8.0, 4.0]);
+    assert_eq!(
+        body[0]["points"][0]["timestamp"]
+            .as_str()
+            .unwrap()
+            .parse::<chrono::DateTime<Utc>>()
+            .unwrap(),
+        minute
+    );
+
+    let (_, body) = helpers::get_json(
+        &app,
+        &admin_token,
+        &format!("/api/observe/metrics?name={name}&step=60&agg=count"),
+    )
+    .await;
+    assert_eq!(values(&body), vec![2.0, 3.0]);
+
+    // Default aggregation is avg
+    let (_, body) = helpers::get_json(
+        &app,
+        &admin_token,
+        &format!("/api/observe/metrics?name={name}&step=60"),
+    )
+    .await;
+    assert_eq!(values(&body), vec![5.0, 8.0 / 3.0]);
+
+    for step in ["0", "-5", "86401"] {
+        let (status, _) = helpers::get_json(
+            &app,
+            &admin_token,
+            &format!("/api/observe/metrics?name={name}&step={step}"),
+        )
+        .await;
+        assert_eq!(status, StatusCode::BAD_REQUEST, "step={step}");
+    }
+}
+
+/// `rate()` over a counter yields a per-second rate (resets included), and
+/// expression queries are accepted for alert rules.
+#[sqlx::test(migrations = "./migrations")]
+async fn alert_expression_rate_and_arithmetic(pool: PgPool) {
+    let (state, admin_token) = test_state(pool.clone()).await;
+    let app = test_router(state);
+
+    let name = format!("reqs_{}", Uuid::new_v4().simple());
+    let now = Utc::now();
+    // +60, +60, then a reset to 30: increase = 150 over a 5m window
+    let records: Vec<MetricRecord> = [(240, 0.0), (180, 60.0), (120, 120.0), (60, 30.0)]
+        .into_iter()
+        .map(|(ago, value)| MetricRecord {
+            name: name.clone(),
+            labels: serde_json::json!({"route": "/"}),
+            metric_type: "sum".into(),
+            unit: None,
+            project_id: None,
+            timestamp: now - chrono::Duration::seconds(ago),
+            value,
+        })
+        .collect();
+    write_metrics(&pool, &records).await.unwrap();
+
+    let eval = |query: &str| {
+        let encoded = query
+            .replace('[', "%5B")
+            .replace(']', "%5D")
+            .replace('(', "%28")
+            .replace(')', "%29")
+            .replace('+', "%2B")
+            .replace('*', "%2A")
+            .replace(' ', "%20");
+        format!("/api/observe/alerts/evaluate?query={encoded}")
+    };
+
+    let (status, body) =
+        helpers::get_json(&app, &admin_token, &eval(&format!("rate({name}[5m])"))).await;
+    assert_eq!(status, StatusCode::OK, "{body}");
+    assert!(
+        (body["value"].as_f64().unwrap() - 0.5).abs() < 1e-9,
+        "{body}"
+    );
+
+    let (_, body) = helpers::get_json(
+        &app,
+        &admin_token,
+        &eval(&format!("increase({name}[5m]) * 2 + 1")),
+    )
+    .await;
+    assert!(
+        (body["value"].as_f64().unwrap() - 301.0).abs() < 1e-9,
+        "{body}"
+    );
+
+    // Instant selector: latest sample
+    let (_, body) = helpers::get_json(&app, &admin_token, &eval(&name)).await;
+    assert_eq!(body["value"].as_f64(), Some(30.0));
+
+    // No data → null
+    let (_, body) = helpers::get_json(&app, &admin_token, &eval("rate(missing_metric[5m])")).await;
+    assert!(body["value"].is_null(), "{body}");
+
+    let (status, _) = helpers::get_json(&app, &admin_token, &eval("rate(oops")).await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+
+    // Both syntaxes are valid rule queries
+    for query in [
+        format!("rate({name}[5m]) / 60"),
+        format!("metric:{name} agg:max"),
+    ] {
+        let (status, body) = helpers::post_json(
+            &app,
+            &admin_token,
+            "/api/observe/alerts",
+            serde_json::json!({
+                "name": "expr-alert",
+                "query": query,
+                "condition": "gt",
+                "threshold": 1.0,
+            }),
+        )
+        .await;
+        assert_eq!(status, StatusCode::CREATED, "{body}");
+    }
+}
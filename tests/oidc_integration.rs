@@ -0,0 +1,371 @@
+// Copyright (c) 2026 Steven Hooker. Exclusively licensed to and distributed by AgentSphere GmbH.
+// SPDX-License-Identifier: BUSL-1.1
+
+//! Integration tests for OIDC single sign-on against a mock identity
+//! provider (wiremock serving discovery, JWKS and the token endpoint).
+
+mod helpers;
+
+use std::sync::Arc;
+
+use axum::Router;
+use axum::body::Body;
+use axum::http::{Request, StatusCode, header};
+use base64::Engine;
+use base64::engine::general_purpose::URL_SAFE_NO_PAD;
+use ring::rand::SystemRandom;
+use ring::signature::{ECDSA_P256_SHA256_FIXED_SIGNING, EcdsaKeyPair, KeyPair};
+use serde_json::{Value, json};
+use sqlx::PgPool;
+use tower::ServiceExt;
+use uuid::Uuid;
+use wiremock::matchers::{method, path};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+use helpers::{create_user, test_router, test_state};
+
+const CLIENT_ID: &str = "platform";
+
+struct MockIdp {
+    server: MockServer,
+    key: EcdsaKeyPair,
+    rng: SystemRandom,
+}
+
+impl MockIdp {
+    async fn start() -> Self {
+        let server = MockServer::start().await;
+        let rng = SystemRandom::new();
+        let pkcs8 = EcdsaKeyPair::generate_pkcs8(&ECDSA_P256_SHA256_FIXED_SIGNING, &rng).unwrap();
+        let key = EcdsaKeyPair::from_pkcs8(&ECDSA_P256_SHA256_FIXED_SIGNING, pkcs8.as_ref(), &rng)
+            .unwrap();
+
+        let idp = Self { server, key, rng };
+        idp.mount_provider().await;
+        idp
+    }
+
+    /// Discovery document and signing keys.
+    async fn mount_provider(&self) {
+        let issuer = self.issuer();
+        Mock::given(method("GET"))
+            .and(path("/.well-known/openid-configuration"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+                "issuer": issuer,
+                "authorization_endpoint": format!("{issuer}/authorize"),
+                "token_endpoint": format!("{issuer}/token"),
+                "jwks_uri": format!("{issuer}/jwks"),
+            })))
+            .mount(&self.server)
+            .await;
+
+        let point = self.key.public_key().as_ref();
+        Mock::given(method("GET"))
+            .and(path("/jwks"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({"keys": [{
+                "kty": "EC",
+                "kid": "test-key",
+                "use": "sig",
+                "crv": "P-256",
+                "x": URL_SAFE_NO_PAD.encode(&point[1..33]),
+                "y": URL_SAFE_NO_PAD.encode(&point[33..65]),
+            }]})))
+            .mount(&self.server)
+            .await;
+    }
+
+    fn issuer(&self) -> String {
+        self.server.uri()
+    }
+
+    fn claims(&self, sub: &str, email: &str, nonce: &str) -> Value {
+        let now = chrono::Utc::now().timestamp();
+        json!({
+            "iss": self.issuer(),
+            "sub": sub,
+            "aud": CLIENT_ID,
+            "iat": now,
+            "exp": now + 300,
+            "nonce": nonce,
+            "email": email,
+            "email_verified": true,
+        })
+    }
+
+    fn sign(&self, claims: &Value) -> String {
+        let header = json!({"alg": "ES256", "kid": "test-key", "typ": "JWT"});
+        let signed = format!(
+            "{}.{}",
+            URL_SAFE_NO_PAD.encode(header.to_string()),
+            URL_SAFE_NO_PAD.encode(claims.to_string())
+        );
+        let sig = self.key.sign(&self.rng, signed.as_bytes()).unwrap();
+        format!("{signed}.{}", URL_SAFE_NO_PAD.encode(sig.as_ref()))
+    }
+
+    /// Answer the next code exchange with an ID token for `claims`.
+    async fn issue(&self, claims: &Value) {
+        self.server.reset().await;
+        self.mount_provider().await;
+        let id_token = self.sign(claims);
+        Mock::given(method("POST"))
+            .and(path("/token"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+                "access_token": "at",
+                "token_type": "Bearer",
+                "id_token": id_token,
+            })))
+            .mount(&self.server)
+            .await;
+    }
+}
+
+/// Router with SSO pointed at `idp`; `platform-admins` maps to `admin`.
+async fn sso_app(pool: PgPool, idp: &MockIdp) -> (Router, String) {
+    let (mut state, admin_token) = test_state(pool).await;
+    let mut config = (*state.config).clone();
+    config.oidc_issuer = Some(idp.issuer());
+    config.oidc_client_id = CLIENT_ID.into();
+    config.oidc_client_secret = Some("secret".into());
+    config.oidc_role_map = vec![("platform-admins".into(), "admin".into())];
+    state.config = Arc::new(config);
+    (test_router(state), admin_token)
+}
+
+struct StartedLogin {
+    state: String,
+    nonce: String,
+    cookie: String,
+}
+
+async fn start_login(app: &Router) -> StartedLogin {
+    start_flow(app, "/api/auth/oidc/login", "").await
+}
+
+/// Start a flow at `uri`, signed in with `token` if not empty.
+async fn start_flow(app: &Router, uri: &str, token: &str) -> StartedLogin {
+    let mut req = Request::get(uri);
+    if !token.is_empty() {
+        req = req.header(header::AUTHORIZATION, format!("Bearer {token}"));
+    }
+    let req = req.body(Body::empty()).unwrap();
+    let resp = app.clone().oneshot(req).await.unwrap();
+    assert_eq!(resp.status(), StatusCode::SEE_OTHER);
+
+    let location = resp.headers()[header::LOCATION].to_str().unwrap();
+    let url = url::Url::parse(location).unwrap();
+    let param = |name: &str| {
+        url.query_pairs().find(|(k, _)| k == name).map_or_else(
+            || panic!("authorization url has no {name}: {location}"),
+            |(_, v)| v.into_owned(),
+        )
+    };
+    assert_eq!(param("client_id"), CLIENT_ID);
+    assert_eq!(param("code_challenge_method"), "S256");
+
+    let cookie = resp.headers()[header::SET_COOKIE]
+        .to_str()
+        .unwrap()
+        .split(';')
+        .next()
+        .unwrap()
+        .to_owned();
+    StartedLogin {
+        state: param("state"),
+        nonce: param("nonce"),
+        cookie,
+    }
+}
+
+/// Complete the callback; returns the status and the session token, if any.
+async fn finish_login(app: &Router, login: &StartedLogin) -> (StatusCode, Option<String>) {
+    let req = Request::get(format!(
+        "/api/auth/oidc/callback?code=auth-code&state={}",
+        login.state
+    ))
+    .header(header::COOKIE, &login.cookie)
+    .body(Body::empty())
+    .unwrap();
+    let resp = app.clone().oneshot(req).await.unwrap();
+    let session = resp
+        .headers()
+        .get_all(header::SET_COOKIE)
+        .iter()
+        .filter_map(|v| v.to_str().ok())
+        .find_map(|v| v.strip_prefix("session="))
+        .map(|v| v.split(';').next().unwrap().to_owned());
+    (resp.status(), session)
+}
+
+#[sqlx::test(migrations = "./migrations")]
+async fn oidc_login_provisions_user_with_mapped_roles(pool: PgPool) {
+    let idp = MockIdp::start().await;
+    let (app, _) = sso_app(pool.clone(), &idp).await;
+
+    let login = start_login(&app).await;
+    let mut claims = idp.claims("00u-ada", "ada@corp.example.com", &login.nonce);
+    claims["preferred_username"] = "Ada.Lovelace".into();
+    claims["name"] = "Ada Lovelace".into();
+    claims["groups"] = json!(["platform-admins", "eng"]);
+    idp.issue(&claims).await;
+
+    let (status, session) = finish_login(&app, &login).await;
+    assert_eq!(status, StatusCode::SEE_OTHER);
+    let session = session.expect("callback should set a session cookie");
+
+    let (status, me) = helpers::get_json(&app, &session, "/api/auth/me").await;
+    assert_eq!(status, StatusCode::OK, "{me}");
+    assert_eq!(me["name"], "ada.lovelace");
+    assert_eq!(me["display_name"], "Ada Lovelace");
+    assert_eq!(me["email"], "ada@corp.example.com");
+    let user_id = Uuid::parse_str(me["id"].as_str().unwrap()).unwrap();
+
+    let roles: Vec<String> = sqlx::query_scalar(
+        "SELECT r.name FROM user_roles ur JOIN roles r ON r.id = ur.role_id
+         WHERE ur.user_id = $1",
+    )
+    .bind(user_id)
+    .fetch_all(&pool)
+    .await
+    .unwrap();
+    assert_eq!(roles, vec!["admin"]);
+
+    // The subject stays linked even when the provider's email changes
+    let login = start_login(&app).await;
+    idp.issue(&idp.claims("00u-ada", "ada.l@corp.example.com", &login.nonce))
+        .await;
+    let (status, session) = finish_login(&app, &login).await;
+    assert_eq!(status, StatusCode::SEE_OTHER);
+    let (_, me) = helpers::get_json(&app, &session.unwrap(), "/api/auth/me").await;
+    assert_eq!(me["id"], user_id.to_string());
+}
+
+#[sqlx::test(migrations = "./migrations")]
+async fn oidc_login_links_existing_user_by_email(pool: PgPool) {
+    let idp = MockIdp::start().await;
+    let (app, admin_token) = sso_app(pool.clone(), &idp).await;
+    let (user_id, _) = create_user(&app, &admin_token, "grace", "grace@example.com").await;
+
+    let login = start_login(&app).await;
+    idp.issue(&idp.claims("00u-grace", "grace@example.com", &login.nonce))
+        .await;
+    let (status, session) = finish_login(&app, &login).await;
+    assert_eq!(status, StatusCode::SEE_OTHER);
+
+    let (_, me) = helpers::get_json(&app, &session.unwrap(), "/api/auth/me").await;
+    assert_eq!(me["id"], user_id.to_string());
+    assert_eq!(me["name"], "grace");
+
+    let linked: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM oidc_identities WHERE user_id = $1")
+        .bind(user_id)
+        .fetch_one(&pool)
+        .await
+        .unwrap();
+    assert_eq!(linked, 1);
+}
+
+#[sqlx::test(migrations = "./migrations")]
+async fn oidc_login_only_links_verified_emails_of_plain_accounts(pool: PgPool) {
+    let idp = MockIdp::start().await;
+    let (app, admin_token) = sso_app(pool.clone(), &idp).await;
+    let (mallory_id, _) = create_user(&app, &admin_token, "mallory", "mallory@example.com").await;
+    let (grace_id, grace_token) =
+        create_user(&app, &admin_token, "grace", "grace@example.com").await;
+    sqlx::query(
+        "INSERT INTO user_totp (user_id, encrypted_secret, enabled) VALUES ($1, '\\x00', true)",
+    )
+    .bind(grace_id)
+    .execute(&pool)
+    .await
+    .unwrap();
+
+    // No email_verified claim: not an assertion, so nothing is linked
+    let login = start_login(&app).await;
+    let mut claims = idp.claims("00u-evil", "mallory@example.com", &login.nonce);
+    claims.as_object_mut().unwrap().remove("email_verified");
+    idp.issue(&claims).await;
+    let (status, session) = finish_login(&app, &login).await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert!(session.is_none());
+
+    // TOTP accounts are never linked by email
+    let login = start_login(&app).await;
+    idp.issue(&idp.claims("00u-grace", "grace@example.com", &login.nonce))
+        .await;
+    let (status, session) = finish_login(&app, &login).await;
+    assert_eq!(status, StatusCode::CONFLICT);
+    assert!(session.is_none());
+
+    let linked: i64 =
+        sqlx::query_scalar("SELECT COUNT(*) FROM oidc_identities WHERE user_id = ANY($1)")
+            .bind(vec![mallory_id, grace_id])
+            .fetch_one(&pool)
+            .await
+            .unwrap();
+    assert_eq!(linked, 0);
+
+    // ... but their owner can link from a signed-in session
+    let login = start_flow(&app, "/api/auth/oidc/link", &grace_token).await;
+    idp.issue(&idp.claims("00u-grace", "grace@example.com", &login.nonce))
+        .await;
+    let (status, _) = finish_login(&app, &login).await;
+    assert_eq!(status, StatusCode::SEE_OTHER);
+
+    let login = start_login(&app).await;
+    idp.issue(&idp.claims("00u-grace", "grace@example.com", &login.nonce))
+        .await;
+    let (status, session) = finish_login(&app, &login).await;
+    assert_eq!(status, StatusCode::SEE_OTHER);
+    let (_, me) = helpers::get_json(&app, &session.unwrap(), "/api/auth/me").await;
+    assert_eq!(me["id"], grace_id.to_string());
+}
+
+#[sqlx::test(migrations = "./migrations")]
+async fn oidc_callback_rejects_forged_logins(pool: PgPool) {
+    let idp = MockIdp::start().await;
+    let (app, _) = sso_app(pool.clone(), &idp).await;
+
+    // State cookie from another browser
+    let mut login = start_login(&app).await;
+    idp.issue(&idp.claims("00u-eve", "eve@example.com", &login.nonce))
+        .await;
+    login.cookie = "oidc_state=someone-else".into();
+    let (status, session) = finish_login(&app, &login).await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert!(session.is_none());
+
+    // ID token minted for a different login attempt
+    let login = start_login(&app).await;
+    idp.issue(&idp.claims("00u-eve", "eve@example.com", "replayed-nonce"))
+        .await;
+    let (status, session) = finish_login(&app, &login).await;
+    assert_eq!(status, StatusCode::UNAUTHORIZED);
+    assert!(session.is_none());
+
+    // A state can only be redeemed once
+    let (status, _) = finish_login(&app, &login).await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+
+    let users: i64 =
+        sqlx::query_scalar("SELECT COUNT(*) FROM users WHERE email = 'eve@example.com'")
+            .fetch_one(&pool)
+            .await
+            .unwrap();
+    assert_eq!(users, 0);
+}
+
+#[sqlx::test(migrations = "./migrations")]
+async fn oidc_routes_are_not_found_when_unconfigured(pool: PgPool) {
+    let (state, _) = test_state(pool).await;
+    let app = test_router(state);
+
+    let (status, body) = helpers::get_json(&app, "", "/api/auth/oidc/status").await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["enabled"], false);
+
+    let req = Request::get("/api/auth/oidc/login")
+        .body(Body::empty())
+        .unwrap();
+    let resp = app.oneshot(req).await.unwrap();
+    assert_eq!(resp.status(), StatusCode::NOT_FOUND);
+}
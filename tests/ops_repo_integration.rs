@@ -556,3 +556,41 @@ async fn sync_from_project_repo_commit_message() {
 
     let _ = tokio::fs::remove_dir_all(&tmp).await;
 }
+
+// ---------------------------------------------------------------------------
+// fetch_remote
+// ---------------------------------------------------------------------------
+
+/// A remote that answers with a redirect fails the fetch instead of sending
+/// git, and the credential header with it, to a host that was never checked.
+#[tokio::test]
+async fn fetch_remote_does_not_follow_redirects() {
+    use wiremock::{Mock, MockServer, ResponseTemplate, matchers};
+
+    let tmp = std::env::temp_dir().join(format!("platform-test-{}", Uuid::new_v4()));
+    let repo_path = init_ops_repo(&tmp, "test-ops", "main").await.unwrap();
+
+    let target = MockServer::start().await;
+    Mock::given(matchers::any())
+        .respond_with(ResponseTemplate::new(200))
+        .expect(0)
+        .mount(&target)
+        .await;
+    let remote = MockServer::start().await;
+    Mock::given(matchers::any())
+        .respond_with(ResponseTemplate::new(302).insert_header(
+            "Location",
+            format!("{}/ops.git/info/refs?service=git-upload-pack", target.uri()),
+        ))
+        .mount(&remote)
+        .await;
+
+    let remote_url = format!("{}/ops.git", remote.uri().replace("127.0.0.1", "localhost"));
+    let pin = pin_remote(&remote_url, true).await.unwrap();
+    let credential = RemoteCredential::from_secret("ghp_token".into());
+    let result = fetch_remote(&repo_path, &remote_url, Some(&credential), pin.as_ref()).await;
+
+    assert!(result.is_err(), "redirected fetch should fail");
+    target.verify().await;
+    let _ = tokio::fs::remove_dir_all(&tmp).await;
+}
@@ -1057,6 +1057,88 @@ async fn register_multiple_passkeys(pool: PgPool) {
     assert_eq!(keys.len(), 2);
 }
 
+/// List three keys under the names given at registration, rename one, drop a
+/// lost one, and keep the last one while password login is disabled.
+#[sqlx::test(migrations = "./migrations")]
+async fn manage_three_passkeys(pool: PgPool) {
+    let (state, admin_token) = test_state(pool.clone()).await;
+    let app = test_router(state);
+    let (_, me) = helpers::get_json(&app, &admin_token, "/api/auth/me").await;
+    let user_id = Uuid::parse_str(me["id"].as_str().unwrap()).unwrap();
+
+    let mut ids = Vec::new();
+    for _ in 0..3 {
+        let mut authenticator = SoftPasskey::new(true);
+        ids.push(register_passkey_ceremony(&app, &admin_token, &mut authenticator).await);
+    }
+
+    let (status, body) = helpers::get_json(&app, &admin_token, "/api/auth/passkeys").await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["total"], 3);
+    for key in body["items"].as_array().unwrap() {
+        assert_eq!(key["name"], "Ceremony Key", "{key}");
+        assert!(key["created_at"].is_string());
+        assert!(key["last_used_at"].is_null());
+    }
+
+    let (status, _) = helpers::patch_json(
+        &app,
+        &admin_token,
+        &format!("/api/auth/passkeys/{}", ids[0]),
+        serde_json::json!({"name": "YubiKey 5C"}),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    let (_, key) = helpers::get_json(
+        &app,
+        &admin_token,
+        &format!("/api/auth/passkeys/{}", ids[0]),
+    )
+    .await;
+    assert_eq!(key["name"], "YubiKey 5C");
+
+    let (status, _) = helpers::delete_json(
+        &app,
+        &admin_token,
+        &format!("/api/auth/passkeys/{}", ids[2]),
+    )
+    .await;
+    assert_eq!(status, StatusCode::NO_CONTENT);
+    let (_, body) = helpers::get_json(&app, &admin_token, "/api/auth/passkeys").await;
+    assert_eq!(body["total"], 2);
+
+    // Passwordless: keys may go down to one, but not to none
+    let (status, body) = helpers::patch_json(
+        &app,
+        &admin_token,
+        &format!("/api/users/{user_id}"),
+        serde_json::json!({ "password_login_disabled": true }),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK, "{body}");
+    let (status, _) = helpers::delete_json(
+        &app,
+        &admin_token,
+        &format!("/api/auth/passkeys/{}", ids[1]),
+    )
+    .await;
+    assert_eq!(status, StatusCode::NO_CONTENT);
+    let (status, _) = helpers::delete_json(
+        &app,
+        &admin_token,
+        &format!("/api/auth/passkeys/{}", ids[0]),
+    )
+    .await;
+    assert_eq!(status, StatusCode::CONFLICT);
+    let (status, _) = helpers::delete_json(
+        &app,
+        &admin_token,
+        &format!("/api/auth/passkeys/{}", ids[2]),
+    )
+    .await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+}
+
 // ---------------------------------------------------------------------------
 // Full login ceremony tests (SoftPasskey + allowCredentials injection)
 //
@@ -1284,6 +1366,147 @@ async fn login_clone_detection_rejects(pool: PgPool) {
     );
 }
 
+// ---------------------------------------------------------------------------
+// Passkey-only login enforcement
+// ---------------------------------------------------------------------------
+
+/// With `password_login_disabled`, password login is rejected but passkey login works.
+#[sqlx::test(migrations = "./migrations")]
+async fn password_login_disabled_forces_passkey_login(pool: PgPool) {
+    let (state, admin_token) = test_state(pool.clone()).await;
+    let app = test_router(state);
+
+    let (_, me) = helpers::get_json(&app, &admin_token, "/api/auth/me").await;
+    let user_id = Uuid::parse_str(me["id"].as_str().unwrap()).unwrap();
+
+    let mut authenticator = SoftPasskey::new(true);
+    let cred_id = register_passkey_ceremony(&app, &admin_token, &mut authenticator).await;
+
+    let (status, body) = helpers::patch_json(
+        &app,
+        &admin_token,
+        &format!("/api/users/{user_id}"),
+        serde_json::json!({ "password_login_disabled": true }),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK, "set flag failed: {body}");
+
+    let (status, body) = helpers::post_json(
+        &app,
+        "",
+        "/api/auth/login",
+        serde_json::json!({ "name": "admin", "password": "testpassword" }),
+    )
+    .await;
+    assert_eq!(status, StatusCode::BAD_REQUEST, "password login: {body}");
+    assert!(body["token"].is_null());
+
+    let body = login_passkey_ceremony(&app, &pool, user_id, &mut authenticator).await;
+    assert!(body["token"].is_string(), "passkey login should succeed");
+
+    // The only passkey cannot be removed while password login is disabled.
+    let (status, _) =
+        helpers::delete_json(&app, &admin_token, &format!("/api/auth/passkeys/{cred_id}")).await;
+    assert_eq!(status, StatusCode::CONFLICT);
+}
+
+/// Only admins can set the flag, and only for users with a passkey.
+#[sqlx::test(migrations = "./migrations")]
+async fn password_login_disabled_requires_admin_and_passkey(pool: PgPool) {
+    let (state, admin_token) = test_state(pool.clone()).await;
+    let app = test_router(state);
+
+    let (user_id, user_token) =
+        create_user(&app, &admin_token, "pk-only-user", "pkonly@test.com").await;
+
+    let (status, _) = helpers::patch_json(
+        &app,
+        &user_token,
+        &format!("/api/users/{user_id}"),
+        serde_json::json!({ "password_login_disabled": true }),
+    )
+    .await;
+    assert_eq!(status, StatusCode::FORBIDDEN);
+
+    let (status, body) = helpers::patch_json(
+        &app,
+        &admin_token,
+        &format!("/api/users/{user_id}"),
+        serde_json::json!({ "password_login_disabled": true }),
+    )
+    .await;
+    assert_eq!(status, StatusCode::BAD_REQUEST, "no passkey: {body}");
+
+    let (status, _) = helpers::post_json(
+        &app,
+        "",
+        "/api/auth/login",
+        serde_json::json!({ "name": "pk-only-user", "password": "testpass123" }),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK, "flag must not have been set");
+}
+
+/// Disabling password login waits for the user row lock that passkey deletion
+/// holds, so it sees the deletion and refuses instead of locking the user out.
+#[sqlx::test(migrations = "./migrations")]
+async fn password_login_disabled_waits_for_passkey_delete(pool: PgPool) {
+    let (state, admin_token) = test_state(pool.clone()).await;
+    let app = test_router(state);
+
+    let (user_id, _user_token) =
+        create_user(&app, &admin_token, "pk-race-user", "pkrace@test.com").await;
+    sqlx::query(
+        "INSERT INTO passkey_credentials (user_id, credential_id, public_key, name, transports)
+         VALUES ($1, $2, $3, 'Last Key', ARRAY['usb']::text[])",
+    )
+    .bind(user_id)
+    .bind(vec![9u8, 9, 9, 9])
+    .bind(serde_json::to_vec(&serde_json::json!({"type": "public-key"})).unwrap())
+    .execute(&pool)
+    .await
+    .unwrap();
+
+    // Stand in for an in-flight delete_passkey: lock the user row first
+    let mut tx = pool.begin().await.unwrap();
+    sqlx::query("SELECT id FROM users WHERE id = $1 FOR UPDATE")
+        .bind(user_id)
+        .execute(&mut *tx)
+        .await
+        .unwrap();
+
+    let toggle_app = app.clone();
+    let toggle_token = admin_token.clone();
+    let toggle = tokio::spawn(async move {
+        helpers::patch_json(
+            &toggle_app,
+            &toggle_token,
+            &format!("/api/users/{user_id}"),
+            serde_json::json!({ "password_login_disabled": true }),
+        )
+        .await
+    });
+    tokio::time::sleep(std::time::Duration::from_millis(200)).await;
+    assert!(!toggle.is_finished(), "toggle must wait for the row lock");
+
+    sqlx::query("DELETE FROM passkey_credentials WHERE user_id = $1")
+        .bind(user_id)
+        .execute(&mut *tx)
+        .await
+        .unwrap();
+    tx.commit().await.unwrap();
+
+    let (status, body) = toggle.await.unwrap();
+    assert_eq!(status, StatusCode::BAD_REQUEST, "{body}");
+    let disabled: bool =
+        sqlx::query_scalar("SELECT password_login_disabled FROM users WHERE id = $1")
+            .bind(user_id)
+            .fetch_one(&pool)
+            .await
+            .unwrap();
+    assert!(!disabled);
+}
+
 // ---------------------------------------------------------------------------
 // GET /api/auth/passkeys/{id} — individual passkey retrieval
 // ---------------------------------------------------------------------------
@@ -1381,3 +1604,140 @@ async fn get_passkey_unauthenticated(pool: PgPool) {
         helpers::get_json(&app, "bad-token", &format!("/api/auth/passkeys/{fake_id}")).await;
     assert_eq!(status, StatusCode::UNAUTHORIZED);
 }
+
+// ---------------------------------------------------------------------------
+// Attestation and hardware-only policy
+// ---------------------------------------------------------------------------
+
+/// Run a `SoftPasskey` registration and return the complete-register response.
+async fn register_soft_passkey(
+    app: &axum::Router,
+    token: &str,
+    authenticator: &mut SoftPasskey,
+) -> (StatusCode, serde_json::Value) {
+    let (status, body) = helpers::post_json(
+        app,
+        token,
+        "/api/auth/passkeys/register/begin",
+        serde_json::json!({"name": "Soft Key"}),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK, "begin_register failed: {body}");
+    let ccr: CreationChallengeResponse = serde_json::from_value(body).unwrap();
+
+    let origin = Url::parse("http://localhost:8080").unwrap();
+    let reg: RegisterPublicKeyCredential = authenticator
+        .perform_register(origin, ccr.public_key, 60000)
+        .expect("SoftPasskey perform_register");
+    helpers::post_json(
+        app,
+        token,
+        "/api/auth/passkeys/register/complete",
+        serde_json::to_value(&reg).unwrap(),
+    )
+    .await
+}
+
+#[sqlx::test(migrations = "./migrations")]
+async fn hardware_policy_rejects_software_passkey(pool: PgPool) {
+    let (state, admin_token) = test_state(pool.clone()).await;
+    let app = test_router(state.clone());
+    let (user_id, user_token) = create_user(&app, &admin_token, "pk-hw", "pk-hw@test.com").await;
+
+    // Default policy: any authenticator, with what it reported kept on record
+    let mut authenticator = SoftPasskey::new(true);
+    let (status, body) = register_soft_passkey(&app, &user_token, &mut authenticator).await;
+    assert_eq!(status, StatusCode::CREATED, "{body}");
+    assert!(body["attestation_format"].is_string(), "{body}");
+    assert_eq!(body["user_verified"], true);
+    let stored: Option<Vec<u8>> =
+        sqlx::query_scalar("SELECT attestation FROM passkey_credentials WHERE user_id = $1")
+            .bind(user_id)
+            .fetch_one(&pool)
+            .await
+            .unwrap();
+    assert!(stored.is_some_and(|a| !a.is_empty()));
+
+    // Only admins set the policy, and only to a known value
+    let url = format!("/api/admin/users/{user_id}/passkey-policy");
+    let (status, _) = helpers::put_json(
+        &app,
+        &user_token,
+        &url,
+        serde_json::json!({"policy": "any"}),
+    )
+    .await;
+    assert_eq!(status, StatusCode::FORBIDDEN);
+    let (status, _) = helpers::put_json(
+        &app,
+        &admin_token,
+        &url,
+        serde_json::json!({"policy": "yubikey"}),
+    )
+    .await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    let (status, body) = helpers::put_json(
+        &app,
+        &admin_token,
+        &url,
+        serde_json::json!({"policy": "hardware"}),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK, "{body}");
+    assert_eq!(body["policy"], "hardware");
+    assert!(helpers::wait_for_audit(&pool, "user.passkey_policy", 2000).await > 0);
+
+    // Without trusted attestation roots no key can satisfy the policy
+    let (status, body) = helpers::post_json(
+        &app,
+        &user_token,
+        "/api/auth/passkeys/register/begin",
+        serde_json::json!({"name": "Key"}),
+    )
+    .await;
+    assert_eq!(status, StatusCode::BAD_REQUEST, "{body}");
+    assert!(
+        body["error"]
+            .as_str()
+            .is_some_and(|e| e.contains("hardware security key")),
+        "{body}"
+    );
+
+    // Trust one vendor root for a YubiKey model
+    let ca_key = rcgen::KeyPair::generate().unwrap();
+    let mut ca_params = rcgen::CertificateParams::new(Vec::<String>::new()).unwrap();
+    ca_params.is_ca = rcgen::IsCa::Ca(rcgen::BasicConstraints::Unconstrained);
+    let ca_path = std::env::temp_dir().join(format!("fido-roots-{}.pem", Uuid::new_v4()));
+    std::fs::write(&ca_path, ca_params.self_signed(&ca_key).unwrap().pem()).unwrap();
+    let mut config = (*state.config).clone();
+    config.passkey_hardware_aaguids =
+        vec![Uuid::parse_str("fa2b99dc-9e39-4257-8f92-4a30d23c4118").unwrap()];
+    config.passkey_attestation_ca = Some(ca_path.to_string_lossy().into_owned());
+    let mut state = state;
+    state.config = std::sync::Arc::new(config);
+    let app = test_router(state);
+
+    // The challenge asks for a roaming key and direct attestation
+    let (_, ccr) = helpers::post_json(
+        &app,
+        &user_token,
+        "/api/auth/passkeys/register/begin",
+        serde_json::json!({"name": "Key"}),
+    )
+    .await;
+    assert_eq!(ccr["publicKey"]["attestation"], "direct", "{ccr}");
+
+    // A software passkey signs its attestation with a certificate chain of its
+    // own making; it does not chain to the trusted root, so nothing is stored
+    let mut soft = SoftPasskey::new(true);
+    let (status, body) = register_soft_passkey(&app, &user_token, &mut soft).await;
+    assert_eq!(status, StatusCode::BAD_REQUEST, "{body}");
+    let count: i64 =
+        sqlx::query_scalar("SELECT COUNT(*) FROM passkey_credentials WHERE user_id = $1")
+            .bind(user_id)
+            .fetch_one(&pool)
+            .await
+            .unwrap();
+    assert_eq!(count, 1);
+    let _ = std::fs::remove_file(ca_path);
+}
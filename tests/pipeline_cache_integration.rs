@@ -0,0 +1,258 @@
+// Copyright (c) 2026 Steven Hooker. Exclusively licensed to and distributed by AgentSphere GmbH.
+// SPDX-License-Identifier: BUSL-1.1
+
+//! Integration tests for pipeline dependency caches:
+//! `pipeline::cache` key resolution, restore-key lookup, branch fallback,
+//! the size limit and LRU eviction.
+
+mod helpers;
+
+use platform::pipeline::cache::{self, Saved};
+use sha2::{Digest, Sha256};
+use sqlx::PgPool;
+use uuid::Uuid;
+
+const KEY: &str = "cargo-{{ hashFiles('Cargo.lock') }}";
+const MAX: u64 = 1024 * 1024;
+
+async fn save(
+    state: &platform::store::AppState,
+    project_id: Uuid,
+    branch: &str,
+    key: &str,
+    archive: &[u8],
+) -> Saved {
+    cache::save(
+        &state.pool,
+        &state.minio,
+        project_id,
+        branch,
+        key,
+        &mut &archive[..],
+        MAX,
+    )
+    .await
+    .unwrap()
+}
+
+async fn set_last_used(pool: &PgPool, project_id: Uuid, key: &str, minutes_ago: i32) {
+    sqlx::query(
+        "UPDATE pipeline_caches SET last_used_at = now() - make_interval(mins => $3)
+         WHERE project_id = $1 AND cache_key = $2",
+    )
+    .bind(project_id)
+    .bind(key)
+    .bind(minutes_ago)
+    .execute(pool)
+    .await
+    .unwrap();
+}
+
+#[sqlx::test(migrations = "./migrations")]
+async fn new_lockfile_restores_prefix_matched_cache(pool: PgPool) {
+    let (state, token) = helpers::test_state(pool.clone()).await;
+    let app = helpers::test_router(state.clone());
+    let project_id = helpers::create_project(&app, &token, "cache-restore", "private").await;
+
+    let (_bare_dir, bare_path) = helpers::create_bare_repo();
+    let (_work_dir, work_path) = helpers::create_working_copy(&bare_path);
+    std::fs::write(work_path.join("Cargo.lock"), "serde 1.0.200\n").unwrap();
+    helpers::git_cmd(&work_path, &["add", "."]);
+    helpers::git_cmd(&work_path, &["commit", "-m", "lockfile"]);
+    helpers::git_cmd(&work_path, &["push", "origin", "main"]);
+    let old_sha = helpers::git_cmd(&work_path, &["rev-parse", "HEAD"]);
+
+    let old_key = cache::resolve_key(&bare_path, old_sha.trim(), KEY)
+        .await
+        .unwrap();
+    let expected = hex::encode(Sha256::digest(b"serde 1.0.200\n"));
+    assert_eq!(old_key, format!("cargo-{expected}"));
+
+    assert_eq!(
+        save(&state, project_id, "main", &old_key, b"old-archive").await,
+        Saved::Stored(11)
+    );
+    // Keys are immutable
+    assert_eq!(
+        save(&state, project_id, "main", &old_key, b"other").await,
+        Saved::Exists
+    );
+
+    // Exact hit
+    let (entry, exact) = cache::lookup(&pool, project_id, "main", "main", &old_key, &[])
+        .await
+        .unwrap()
+        .unwrap();
+    assert!(exact);
+    assert_eq!(entry.size_bytes, 11);
+
+    // Updating the lockfile changes the key; the old cache is a near-hit
+    std::fs::write(work_path.join("Cargo.lock"), "serde 1.0.201\n").unwrap();
+    helpers::git_cmd(&work_path, &["commit", "-am", "bump serde"]);
+    helpers::git_cmd(&work_path, &["push", "origin", "main"]);
+    let new_key = cache::resolve_key(&bare_path, "refs/heads/main", KEY)
+        .await
+        .unwrap();
+    assert_ne!(new_key, old_key);
+
+    let restore_keys = vec!["npm-".to_owned(), "cargo-".to_owned()];
+    assert!(
+        cache::lookup(&pool, project_id, "main", "main", &new_key, &[])
+            .await
+            .unwrap()
+            .is_none()
+    );
+    let (entry, exact) = cache::lookup(&pool, project_id, "main", "main", &new_key, &restore_keys)
+        .await
+        .unwrap()
+        .unwrap();
+    assert!(!exact);
+    assert_eq!(entry.cache_key, old_key);
+    let archive = state.minio.read(&entry.minio_path).await.unwrap().to_vec();
+    assert_eq!(archive, b"old-archive");
+
+    // Caches are per project
+    let other_id = helpers::create_project(&app, &token, "cache-other", "private").await;
+    assert!(
+        cache::lookup(&pool, other_id, "main", "main", &new_key, &restore_keys)
+            .await
+            .unwrap()
+            .is_none()
+    );
+}
+
+#[sqlx::test(migrations = "./migrations")]
+async fn restore_key_prefers_most_recently_used(pool: PgPool) {
+    let (state, token) = helpers::test_state(pool.clone()).await;
+    let app = helpers::test_router(state.clone());
+    let project_id = helpers::create_project(&app, &token, "cache-recent", "private").await;
+
+    for key in ["deps-aaa", "deps-bbb"] {
+        save(&state, project_id, "main", key, key.as_bytes()).await;
+    }
+    set_last_used(&pool, project_id, "deps-aaa", 5).await;
+    set_last_used(&pool, project_id, "deps-bbb", 60).await;
+
+    let (entry, _) = cache::lookup(
+        &pool,
+        project_id,
+        "main",
+        "main",
+        "deps-ccc",
+        &["deps-".to_owned()],
+    )
+    .await
+    .unwrap()
+    .unwrap();
+    assert_eq!(entry.cache_key, "deps-aaa");
+}
+
+#[sqlx::test(migrations = "./migrations")]
+async fn evicts_least_recently_used_over_limit(pool: PgPool) {
+    let (state, token) = helpers::test_state(pool.clone()).await;
+    let app = helpers::test_router(state.clone());
+    let project_id = helpers::create_project(&app, &token, "cache-evict", "private").await;
+
+    for (key, minutes_ago) in [("k-old", 30), ("k-mid", 20), ("k-new", 10)] {
+        save(&state, project_id, "main", key, &[0; 10]).await;
+        set_last_used(&pool, project_id, key, minutes_ago).await;
+    }
+    let old_path: String =
+        sqlx::query_scalar("SELECT minio_path FROM pipeline_caches WHERE cache_key = 'k-old'")
+            .fetch_one(&pool)
+            .await
+            .unwrap();
+
+    assert_eq!(cache::evict(&pool, &state.minio, 30).await.unwrap(), 0);
+    assert_eq!(cache::evict(&pool, &state.minio, 25).await.unwrap(), 1);
+
+    let keys: Vec<String> =
+        sqlx::query_scalar("SELECT cache_key FROM pipeline_caches ORDER BY cache_key")
+            .fetch_all(&pool)
+            .await
+            .unwrap();
+    assert_eq!(keys, vec!["k-mid", "k-new"]);
+    assert!(!state.minio.exists(&old_path).await.unwrap());
+}
+
+#[sqlx::test(migrations = "./migrations")]
+async fn branch_falls_back_to_default_branch(pool: PgPool) {
+    let (state, token) = helpers::test_state(pool.clone()).await;
+    let app = helpers::test_router(state.clone());
+    let project_id = helpers::create_project(&app, &token, "cache-branch", "private").await;
+
+    save(&state, project_id, "main", "deps-1", b"main-archive").await;
+
+    // A feature branch without its own cache restores the default branch's
+    let (entry, exact) = cache::lookup(&pool, project_id, "feature", "main", "deps-1", &[])
+        .await
+        .unwrap()
+        .unwrap();
+    assert!(exact);
+    assert_eq!(entry.size_bytes, 12);
+
+    // Its own save doesn't touch the default branch's cache
+    assert_eq!(
+        save(&state, project_id, "feature", "deps-1", b"feature").await,
+        Saved::Stored(7)
+    );
+    let (entry, _) = cache::lookup(&pool, project_id, "feature", "main", "deps-1", &[])
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(entry.size_bytes, 7);
+    let (entry, _) = cache::lookup(&pool, project_id, "main", "main", "deps-1", &[])
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(entry.size_bytes, 12);
+
+    // Other branches never see the feature branch's cache
+    save(&state, project_id, "feature", "deps-2", b"feature").await;
+    assert!(
+        cache::lookup(&pool, project_id, "other", "main", "deps-2", &[])
+            .await
+            .unwrap()
+            .is_none()
+    );
+}
+
+#[sqlx::test(migrations = "./migrations")]
+async fn oversized_or_empty_archive_not_saved(pool: PgPool) {
+    let (state, token) = helpers::test_state(pool.clone()).await;
+    let app = helpers::test_router(state.clone());
+    let project_id = helpers::create_project(&app, &token, "cache-limit", "private").await;
+
+    let big = vec![0u8; 3 * 1024 * 1024];
+    assert_eq!(
+        cache::save(
+            &pool,
+            &state.minio,
+            project_id,
+            "main",
+            "big",
+            &mut &big[..],
+            MAX
+        )
+        .await
+        .unwrap(),
+        Saved::TooLarge
+    );
+    assert_eq!(
+        save(&state, project_id, "main", "empty", b"").await,
+        Saved::Empty
+    );
+
+    let count: i64 = sqlx::query_scalar("SELECT count(*) FROM pipeline_caches")
+        .fetch_one(&pool)
+        .await
+        .unwrap();
+    assert_eq!(count, 0);
+    assert!(
+        state
+            .minio
+            .list(&format!("caches/{project_id}/"))
+            .await
+            .map_or(true, |entries| entries.is_empty())
+    );
+}
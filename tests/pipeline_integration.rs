@@ -734,6 +734,94 @@ async fn get_step_logs_step_not_found(pool: PgPool) {
     assert_eq!(status, StatusCode::NOT_FOUND);
 }
 
+#[sqlx::test(migrations = "./migrations")]
+async fn get_pipeline_logs_combined(pool: PgPool) {
+    let (state, admin_token) = test_state(pool.clone()).await;
+    let app = test_router(state.clone());
+    let token = admin_token.clone();
+    let uid = admin_user_id(&pool).await;
+
+    let project_id = create_project(&app, &token, "pl-logs-all", "private").await;
+    let pipeline_id =
+        insert_pipeline(&pool, project_id, uid, "failure", "refs/heads/main", "push").await;
+
+    // build: stored with kubelet timestamps alongside the plain log
+    let build_log = format!("logs/pipelines/{pipeline_id}/build.log");
+    for (path, content) in [
+        (build_log.clone(), "compiling\nlinked\n"),
+        (
+            format!("logs/pipelines/{pipeline_id}/build.ts.log"),
+            "2026-10-15T09:30:00.000000000Z compiling\n2026-10-15T09:30:05.000000000Z linked\n",
+        ),
+    ] {
+        state
+            .minio
+            .write(&path, content.as_bytes().to_vec())
+            .await
+            .unwrap();
+    }
+    insert_step_with_log(&pool, pipeline_id, project_id, "build", &build_log).await;
+
+    // test: only a plain log, stamped with the step's finish time
+    let test_log = format!("logs/pipelines/{pipeline_id}/test-test.log");
+    state
+        .minio
+        .write(&test_log, b"1 failed\n".to_vec())
+        .await
+        .unwrap();
+    let test_step = insert_step(&pool, pipeline_id, project_id, "test", "failure", 1).await;
+    sqlx::query(
+        "UPDATE pipeline_steps SET log_ref = $2, exit_code = 1, duration_ms = 2500,
+                finished_at = '2026-10-15T09:31:00Z' WHERE id = $1",
+    )
+    .bind(test_step)
+    .bind(&test_log)
+    .execute(&pool)
+    .await
+    .unwrap();
+
+    insert_step(&pool, pipeline_id, project_id, "deploy", "skipped", 2).await;
+
+    let url = format!("/api/projects/{project_id}/pipelines/{pipeline_id}/logs");
+    let (status, bytes) = get_bytes(&app, &token, &url).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(
+        String::from_utf8(bytes).unwrap(),
+        "==> [1/3] build (success)\n\
+         2026-10-15T09:30:00.000000000Z compiling\n\
+         2026-10-15T09:30:05.000000000Z linked\n\
+         ==> [2/3] test (failure, exit 1, 2.5s)\n\
+         2026-10-15T09:31:00.000000000Z 1 failed\n\
+         ==> [3/3] deploy (skipped)\n"
+    );
+
+    // Incremental: only newer lines, under their step's header
+    let (status, bytes) =
+        get_bytes(&app, &token, &format!("{url}?since=2026-10-15T09:30:02Z")).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(
+        String::from_utf8(bytes).unwrap(),
+        "==> [1/3] build (success)\n\
+         2026-10-15T09:30:05.000000000Z linked\n\
+         ==> [2/3] test (failure, exit 1, 2.5s)\n\
+         2026-10-15T09:31:00.000000000Z 1 failed\n"
+    );
+    let (status, bytes) =
+        get_bytes(&app, &token, &format!("{url}?since=2026-10-15T09:31:00Z")).await;
+    assert_eq!(status, StatusCode::OK);
+    assert!(bytes.is_empty());
+
+    // The pipeline must belong to the project in the path
+    let other = create_project(&app, &token, "pl-logs-other", "private").await;
+    let (status, _) = get_json(
+        &app,
+        &token,
+        &format!("/api/projects/{other}/pipelines/{pipeline_id}/logs"),
+    )
+    .await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+}
+
 // ===========================================================================
 // Artifacts
 // ===========================================================================
@@ -1016,6 +1104,147 @@ async fn trigger_pipeline_no_repo_path(pool: PgPool) {
     assert_eq!(status, StatusCode::BAD_REQUEST);
 }
 
+#[sqlx::test(migrations = "./migrations")]
+async fn trigger_pipeline_rejects_invalid_variables(pool: PgPool) {
+    let (state, admin_token) = test_state(pool.clone()).await;
+    let app = test_router(state);
+    let token = admin_token.clone();
+
+    let project_id = create_project(&app, &token, "pl-bad-vars", "private").await;
+
+    for variables in [
+        serde_json::json!({ "DEPLOY-ENV": "staging" }),
+        serde_json::json!({ "1ST": "x" }),
+        // COMMIT_SHA is exposed as TRIGGER_COMMIT_SHA, which is also given
+        serde_json::json!({ "COMMIT_SHA": "deadbeef", "TRIGGER_COMMIT_SHA": "x" }),
+    ] {
+        let (status, body) = post_json(
+            &app,
+            &token,
+            &format!("/api/projects/{project_id}/pipelines"),
+            serde_json::json!({ "git_ref": "refs/heads/main", "variables": variables }),
+        )
+        .await;
+        assert_eq!(status, StatusCode::BAD_REQUEST, "{variables}: {body}");
+    }
+}
+
+// ===========================================================================
+// Validate (lint) a definition
+// ===========================================================================
+
+#[sqlx::test(migrations = "./migrations")]
+async fn validate_definition_valid(pool: PgPool) {
+    let (state, admin_token) = test_state(pool.clone()).await;
+    let app = test_router(state);
+    let project_id = create_project(&app, &admin_token, "pl-lint-ok", "private").await;
+
+    let (status, body) = post_json(
+        &app,
+        &admin_token,
+        &format!("/api/projects/{project_id}/pipelines/validate"),
+        serde_json::json!({
+            "content": "pipeline:\n  steps:\n    - name: test\n      image: alpine\n      commands: [echo ok]\n"
+        }),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK, "{body}");
+    assert_eq!(body["valid"], true);
+    assert_eq!(body["errors"], serde_json::json!([]));
+
+    // Nothing was created
+    let count: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM pipelines WHERE project_id = $1")
+        .bind(project_id)
+        .fetch_one(&pool)
+        .await
+        .unwrap();
+    assert_eq!(count, 0);
+}
+
+#[sqlx::test(migrations = "./migrations")]
+async fn validate_definition_reports_errors(pool: PgPool) {
+    let (state, admin_token) = test_state(pool.clone()).await;
+    let app = test_router(state);
+    let project_id = create_project(&app, &admin_token, "pl-lint-bad", "private").await;
+    let content = "\
+pipeline:
+  steps:
+    - name: a
+      image: 'alpine;reboot'
+      depends_on: [b]
+    - name: b
+      image: alpine
+      depends_on: [a]
+      timout: 10
+";
+
+    let (status, body) = post_json(
+        &app,
+        &admin_token,
+        &format!("/api/projects/{project_id}/pipelines/validate"),
+        serde_json::json!({ "content": content }),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK, "{body}");
+    assert_eq!(body["valid"], false);
+    let paths: Vec<&str> = body["errors"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|e| e["path"].as_str().unwrap())
+        .collect();
+    assert_eq!(
+        paths,
+        [
+            "pipeline.steps[1].timout",
+            "pipeline.steps[0]",
+            "pipeline.steps"
+        ],
+        "{body}"
+    );
+    assert!(
+        body["errors"][2]["message"]
+            .as_str()
+            .unwrap()
+            .contains("cycle"),
+        "{body}"
+    );
+
+    // Syntax errors carry a line number
+    let (_, body) = post_json(
+        &app,
+        &admin_token,
+        &format!("/api/projects/{project_id}/pipelines/validate"),
+        serde_json::json!({ "content": "pipeline:\n  steps: [\n" }),
+    )
+    .await;
+    assert_eq!(body["valid"], false);
+    assert!(body["errors"][0]["line"].is_u64(), "{body}");
+}
+
+#[sqlx::test(migrations = "./migrations")]
+async fn validate_definition_private_project_404_for_non_member(pool: PgPool) {
+    let (state, admin_token) = test_state(pool.clone()).await;
+    let app = test_router(state);
+    let project_id = create_project(&app, &admin_token, "pl-lint-priv", "private").await;
+    let (_, user_token) = create_user(
+        &app,
+        &admin_token,
+        "outsider-lint",
+        "outsider-lint@test.com",
+    )
+    .await;
+
+    let (status, _) = post_json(
+        &app,
+        &user_token,
+        &format!("/api/projects/{project_id}/pipelines/validate"),
+        serde_json::json!({ "content": "pipeline:\n  steps: []\n" }),
+    )
+    .await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+}
+
 // ===========================================================================
 // T44: Pipeline trigger edge cases
 // ===========================================================================
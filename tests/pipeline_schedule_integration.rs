@@ -0,0 +1,233 @@
+// Copyright (c) 2026 Steven Hooker. Exclusively licensed to and distributed by AgentSphere GmbH.
+// SPDX-License-Identifier: BUSL-1.1
+
+//! Integration tests for cron-scheduled pipelines: the `pipeline-schedules`
+//! CRUD API and `pipeline::schedule::fire_due_schedules`.
+
+mod helpers;
+
+use axum::http::StatusCode;
+use chrono::{DateTime, Timelike, Utc};
+use serde_json::json;
+use sqlx::PgPool;
+use uuid::Uuid;
+
+const PIPELINE_YAML: &str = "\
+pipeline:
+  steps:
+    - name: nightly
+      image: alpine:3.19
+      commands:
+        - echo nightly
+";
+
+/// Create a project whose repo has a `.platform.yaml` on `main`.
+async fn setup_project(
+    state: &platform::store::AppState,
+    app: &axum::Router,
+    token: &str,
+    name: &str,
+) -> (Uuid, tempfile::TempDir, tempfile::TempDir) {
+    let project_id = helpers::create_project(app, token, name, "private").await;
+
+    let (bare_dir, bare_path) = helpers::create_bare_repo();
+    let (work_dir, work_path) = helpers::create_working_copy(&bare_path);
+    std::fs::write(work_path.join(".platform.yaml"), PIPELINE_YAML).unwrap();
+    helpers::git_cmd(&work_path, &["add", "."]);
+    helpers::git_cmd(&work_path, &["commit", "-m", "add pipeline config"]);
+    helpers::git_cmd(&work_path, &["push", "origin", "main"]);
+
+    sqlx::query("UPDATE projects SET repo_path = $1 WHERE id = $2")
+        .bind(bare_path.to_str().unwrap())
+        .bind(project_id)
+        .execute(&state.pool)
+        .await
+        .unwrap();
+
+    (project_id, bare_dir, work_dir)
+}
+
+/// Make a schedule due now.
+async fn make_due(pool: &PgPool, schedule_id: Uuid) {
+    sqlx::query(
+        "UPDATE pipeline_schedules SET next_run_at = now() - interval '1 minute' WHERE id = $1",
+    )
+    .bind(schedule_id)
+    .execute(pool)
+    .await
+    .unwrap();
+}
+
+async fn scheduled_pipelines(pool: &PgPool, project_id: Uuid) -> Vec<(Uuid, String, String)> {
+    sqlx::query_as(
+        "SELECT id, git_ref, status FROM pipelines
+         WHERE project_id = $1 AND trigger = 'schedule' ORDER BY created_at",
+    )
+    .bind(project_id)
+    .fetch_all(pool)
+    .await
+    .unwrap()
+}
+
+#[sqlx::test(migrations = "./migrations")]
+async fn schedule_crud(pool: PgPool) {
+    let (state, token) = helpers::test_state(pool).await;
+    let app = helpers::test_router(state);
+    let project_id = helpers::create_project(&app, &token, "sched-crud", "private").await;
+    let base = format!("/api/projects/{project_id}/pipeline-schedules");
+
+    let (status, body) =
+        helpers::post_json(&app, &token, &base, json!({"cron": "0 2 * * *"})).await;
+    assert_eq!(status, StatusCode::CREATED, "{body}");
+    assert_eq!(body["git_ref"], "main", "defaults to the default branch");
+    assert_eq!(body["enabled"], true);
+    let next: DateTime<Utc> = body["next_run_at"].as_str().unwrap().parse().unwrap();
+    assert_eq!((next.hour(), next.minute()), (2, 0));
+    assert!(next > Utc::now());
+    let schedule_id = body["id"].as_str().unwrap().to_owned();
+
+    let (status, body) = helpers::get_json(&app, &token, &base).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["total"], 1);
+
+    let (status, body) = helpers::patch_json(
+        &app,
+        &token,
+        &format!("{base}/{schedule_id}"),
+        json!({"enabled": false, "git_ref": "refs/heads/release"}),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK, "{body}");
+    assert_eq!(body["enabled"], false);
+    assert_eq!(body["git_ref"], "release");
+    assert!(
+        body["next_run_at"].is_null(),
+        "disabled schedules never fire"
+    );
+
+    let (status, _) = helpers::delete_json(&app, &token, &format!("{base}/{schedule_id}")).await;
+    assert_eq!(status, StatusCode::NO_CONTENT);
+    let (status, _) = helpers::get_json(&app, &token, &format!("{base}/{schedule_id}")).await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+}
+
+#[sqlx::test(migrations = "./migrations")]
+async fn schedule_rejects_invalid_cron(pool: PgPool) {
+    let (state, token) = helpers::test_state(pool).await;
+    let app = helpers::test_router(state);
+    let project_id = helpers::create_project(&app, &token, "sched-invalid", "private").await;
+    let base = format!("/api/projects/{project_id}/pipeline-schedules");
+
+    for cron in ["", "0 2 * *", "61 * * * *", "0 0 30 2 *"] {
+        let (status, body) = helpers::post_json(&app, &token, &base, json!({"cron": cron})).await;
+        assert_eq!(status, StatusCode::BAD_REQUEST, "cron '{cron}': {body}");
+    }
+    let (status, _) = helpers::post_json(
+        &app,
+        &token,
+        &base,
+        json!({"cron": "0 2 * * *", "git_ref": "main..evil"}),
+    )
+    .await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+}
+
+#[sqlx::test(migrations = "./migrations")]
+async fn schedule_requires_project_write(pool: PgPool) {
+    let (state, admin_token) = helpers::test_state(pool.clone()).await;
+    let app = helpers::test_router(state);
+    let project_id = helpers::create_project(&app, &admin_token, "sched-rbac", "public").await;
+    let (_, viewer_token) =
+        helpers::create_user(&app, &admin_token, "sched-viewer", "sched-viewer@test.com").await;
+    let base = format!("/api/projects/{project_id}/pipeline-schedules");
+
+    let (status, _) = helpers::get_json(&app, &viewer_token, &base).await;
+    assert_eq!(status, StatusCode::OK);
+    let (status, _) =
+        helpers::post_json(&app, &viewer_token, &base, json!({"cron": "0 2 * * *"})).await;
+    assert_eq!(status, StatusCode::FORBIDDEN);
+}
+
+#[sqlx::test(migrations = "./migrations")]
+async fn due_schedule_creates_pending_pipeline_once(pool: PgPool) {
+    let (state, token) = helpers::test_state(pool.clone()).await;
+    let app = helpers::test_router(state.clone());
+    let (project_id, _bd, _wd) = setup_project(&state, &app, &token, "sched-fire").await;
+
+    let (status, body) = helpers::post_json(
+        &app,
+        &token,
+        &format!("/api/projects/{project_id}/pipeline-schedules"),
+        json!({"cron": "0 2 * * *", "git_ref": "main"}),
+    )
+    .await;
+    assert_eq!(status, StatusCode::CREATED, "{body}");
+    let schedule_id = Uuid::parse_str(body["id"].as_str().unwrap()).unwrap();
+
+    // Not due yet
+    let fired = platform::pipeline::schedule::fire_due_schedules(&state)
+        .await
+        .unwrap();
+    assert_eq!(fired, 0);
+
+    make_due(&pool, schedule_id).await;
+    let fired = platform::pipeline::schedule::fire_due_schedules(&state)
+        .await
+        .unwrap();
+    assert_eq!(fired, 1);
+
+    let pipelines = scheduled_pipelines(&pool, project_id).await;
+    assert_eq!(pipelines.len(), 1);
+    assert_eq!(pipelines[0].1, "refs/heads/main");
+    assert_eq!(pipelines[0].2, "pending");
+
+    // The slot was consumed: the schedule moved on to the next 02:00
+    let (next_run_at, last_pipeline_id): (DateTime<Utc>, Option<Uuid>) = sqlx::query_as(
+        "SELECT next_run_at, last_pipeline_id FROM pipeline_schedules WHERE id = $1",
+    )
+    .bind(schedule_id)
+    .fetch_one(&pool)
+    .await
+    .unwrap();
+    assert!(next_run_at > Utc::now());
+    assert_eq!(next_run_at.hour(), 2);
+    assert_eq!(last_pipeline_id, Some(pipelines[0].0));
+    let fired = platform::pipeline::schedule::fire_due_schedules(&state)
+        .await
+        .unwrap();
+    assert_eq!(fired, 0, "a slot fires once");
+}
+
+#[sqlx::test(migrations = "./migrations")]
+async fn due_schedule_skipped_while_last_run_in_progress(pool: PgPool) {
+    let (state, token) = helpers::test_state(pool.clone()).await;
+    let app = helpers::test_router(state.clone());
+    let (project_id, _bd, _wd) = setup_project(&state, &app, &token, "sched-skip").await;
+
+    let (_, body) = helpers::post_json(
+        &app,
+        &token,
+        &format!("/api/projects/{project_id}/pipeline-schedules"),
+        json!({"cron": "*/5 * * * *"}),
+    )
+    .await;
+    let schedule_id = Uuid::parse_str(body["id"].as_str().unwrap()).unwrap();
+
+    make_due(&pool, schedule_id).await;
+    assert_eq!(
+        platform::pipeline::schedule::fire_due_schedules(&state)
+            .await
+            .unwrap(),
+        1
+    );
+
+    // The first run is still pending when the next slot comes round
+    make_due(&pool, schedule_id).await;
+    assert_eq!(
+        platform::pipeline::schedule::fire_due_schedules(&state)
+            .await
+            .unwrap(),
+        0
+    );
+    assert_eq!(scheduled_pipelines(&pool, project_id).await.len(), 1);
+}
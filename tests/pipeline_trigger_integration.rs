@@ -9,6 +9,7 @@
 
 mod helpers;
 
+use std::collections::BTreeMap;
 use std::path::PathBuf;
 use std::process::Command;
 
@@ -16,7 +17,8 @@ use sqlx::PgPool;
 use tempfile::TempDir;
 use uuid::Uuid;
 
-use platform::pipeline::trigger::{self, MrTriggerParams, PushTriggerParams};
+use platform::pipeline::PipelinePriority;
+use platform::pipeline::trigger::{self, ApiTriggerParams, MrTriggerParams, PushTriggerParams};
 
 // ---------------------------------------------------------------------------
 // Test git repo helpers
@@ -179,6 +181,7 @@ async fn on_push_creates_pipeline(pool: PgPool) {
         repo_path: bare_path.clone(),
         branch: "main".into(),
         commit_sha: None,
+        before_sha: None,
     };
 
     let result = trigger::on_push(
@@ -279,6 +282,7 @@ async fn on_push_no_yaml_returns_none(pool: PgPool) {
         repo_path: bare_path,
         branch: "main".into(),
         commit_sha: None,
+        before_sha: None,
     };
 
     let result = trigger::on_push(
@@ -326,6 +330,7 @@ async fn on_push_branch_mismatch_returns_none(pool: PgPool) {
         repo_path: bare_path.clone(),
         branch: "develop".into(),
         commit_sha: None,
+        before_sha: None,
     };
 
     let result = trigger::on_push(
@@ -360,6 +365,7 @@ async fn on_push_nonexistent_repo_returns_none(pool: PgPool) {
         repo_path: PathBuf::from("/tmp/nonexistent-repo-12345"),
         branch: "main".into(),
         commit_sha: None,
+        before_sha: None,
     };
 
     // read_file_at_ref will fail → returns None
@@ -389,6 +395,7 @@ async fn on_push_multi_step_creates_all_steps(pool: PgPool) {
         repo_path: bare_path.clone(),
         branch: "main".into(),
         commit_sha: Some("abc123".into()),
+        before_sha: None,
     };
 
     let result = trigger::on_push(
@@ -437,30 +444,126 @@ async fn on_push_multi_step_creates_all_steps(pool: PgPool) {
     drop(work_dir);
 }
 
+// ---------------------------------------------------------------------------
+// on_push — `when` conditions and changed files are recorded
+// ---------------------------------------------------------------------------
+
+const WHEN_YAML: &str = r#"
+pipeline:
+  steps:
+    - name: test
+      image: alpine:3.19
+      commands:
+        - echo test
+      when:
+        changes: ["src/**"]
+    - name: deploy
+      image: alpine:3.19
+      commands:
+        - echo deploy
+      when: branch == "main"
+"#;
+
+fn git_output(dir: &std::path::Path, args: &[&str]) -> String {
+    let out = Command::new("git")
+        .arg("-C")
+        .arg(dir)
+        .args(["-c", "user.email=test@test.com", "-c", "user.name=Test"])
+        .args(args)
+        .output()
+        .unwrap();
+    assert!(out.status.success(), "git {args:?} failed: {out:?}");
+    String::from_utf8(out.stdout).unwrap().trim().to_owned()
+}
+
+#[sqlx::test(migrations = "./migrations")]
+async fn on_push_records_when_and_changed_files(pool: PgPool) {
+    let _state = helpers::test_state(pool.clone()).await;
+    let (bare_dir, work_dir, bare_path) = create_test_repo_with_pipeline_yaml(WHEN_YAML);
+    let (project_id, user_id) = create_project_with_repo(&pool, bare_path.to_str().unwrap()).await;
+
+    let before = git_output(work_dir.path(), &["rev-parse", "HEAD"]);
+    std::fs::create_dir(work_dir.path().join("src")).unwrap();
+    std::fs::write(work_dir.path().join("src/main.rs"), "fn main() {}\n").unwrap();
+    git_output(work_dir.path(), &["add", "."]);
+    git_output(work_dir.path(), &["commit", "-m", "add src"]);
+    git_output(work_dir.path(), &["push", "origin", "main"]);
+    let after = git_output(work_dir.path(), &["rev-parse", "HEAD"]);
+
+    let params = PushTriggerParams {
+        project_id,
+        user_id,
+        repo_path: bare_path.clone(),
+        branch: "main".into(),
+        commit_sha: Some(after),
+        before_sha: Some(before),
+    };
+    let pipeline_id = trigger::on_push(
+        &pool,
+        &params,
+        "gcr.io/kaniko-project/executor:v1.23.2-debug",
+    )
+    .await
+    .unwrap()
+    .unwrap();
+
+    let changed: Option<Vec<String>> =
+        sqlx::query_scalar("SELECT changed_files FROM pipelines WHERE id = $1")
+            .bind(pipeline_id)
+            .fetch_one(&pool)
+            .await
+            .unwrap();
+    assert_eq!(changed, Some(vec!["src/main.rs".to_owned()]));
+
+    let steps: Vec<(String, Option<serde_json::Value>)> = sqlx::query_as(
+        "SELECT name, condition_when FROM pipeline_steps WHERE pipeline_id = $1 ORDER BY step_order",
+    )
+    .bind(pipeline_id)
+    .fetch_all(&pool)
+    .await
+    .unwrap();
+    assert_eq!(
+        steps[0].1,
+        Some(serde_json::json!({"if": null, "changes": ["src/**"]}))
+    );
+    assert_eq!(steps[1].1, Some(serde_json::json!(r#"branch == "main""#)));
+
+    drop(bare_dir);
+    drop(work_dir);
+}
+
 // ---------------------------------------------------------------------------
 // on_api — happy path
 // ---------------------------------------------------------------------------
 
 #[sqlx::test(migrations = "./migrations")]
 async fn on_api_creates_pipeline(pool: PgPool) {
-    let _state = helpers::test_state(pool.clone()).await;
+    let (state, _) = helpers::test_state(pool.clone()).await;
     let (bare_dir, work_dir, bare_path) = create_test_repo_with_pipeline_yaml(SIMPLE_YAML);
     let (project_id, user_id) = create_project_with_repo(&pool, bare_path.to_str().unwrap()).await;
+    let master_key =
+        platform::secrets::engine::parse_master_key(state.config.master_key.as_deref().unwrap())
+            .unwrap();
+    let variables = BTreeMap::from([("DEPLOY_ENV".to_owned(), "staging".to_owned())]);
 
     let pipeline_id = trigger::on_api(
         &pool,
-        &bare_path,
-        project_id,
-        "refs/heads/main",
-        user_id,
+        &ApiTriggerParams {
+            project_id,
+            user_id,
+            repo_path: bare_path.clone(),
+            git_ref: "refs/heads/main".into(),
+            encrypted_variables: Some(trigger::encrypt_variables(&master_key, &variables).unwrap()),
+            priority: PipelinePriority::High,
+        },
         "gcr.io/kaniko-project/executor:v1.23.2-debug",
     )
     .await
     .unwrap();
 
     // Verify the pipeline row
-    let row: (String, String, String) =
-        sqlx::query_as("SELECT trigger, git_ref, status FROM pipelines WHERE id = $1")
+    let row: (String, String, String, String) =
+        sqlx::query_as("SELECT trigger, git_ref, status, priority FROM pipelines WHERE id = $1")
             .bind(pipeline_id)
             .fetch_one(&pool)
             .await
@@ -469,6 +572,7 @@ async fn on_api_creates_pipeline(pool: PgPool) {
     assert_eq!(row.0, "api");
     assert_eq!(row.1, "refs/heads/main");
     assert_eq!(row.2, "pending");
+    assert_eq!(row.3, "high");
 
     // on_api also resolves the commit SHA from the ref
     let sha_row: (Option<String>,) =
@@ -484,6 +588,19 @@ async fn on_api_creates_pipeline(pool: PgPool) {
     );
     assert_eq!(sha_row.0.unwrap().len(), 40, "SHA should be 40 hex chars");
 
+    // Stored encrypted, not as plaintext
+    let encrypted: Vec<u8> =
+        sqlx::query_scalar("SELECT encrypted_variables FROM pipelines WHERE id = $1")
+            .bind(pipeline_id)
+            .fetch_one(&pool)
+            .await
+            .unwrap();
+    assert!(!String::from_utf8_lossy(&encrypted).contains("staging"));
+    assert_eq!(
+        trigger::decrypt_variables(&master_key, &encrypted).unwrap(),
+        variables
+    );
+
     drop(bare_dir);
     drop(work_dir);
 }
@@ -501,10 +618,14 @@ async fn on_api_bare_branch_name(pool: PgPool) {
     // Pass "main" instead of "refs/heads/main" — on_api strips the prefix for read_file_at_ref
     let pipeline_id = trigger::on_api(
         &pool,
-        &bare_path,
-        project_id,
-        "main",
-        user_id,
+        &ApiTriggerParams {
+            project_id,
+            user_id,
+            repo_path: bare_path.clone(),
+            git_ref: "main".into(),
+            encrypted_variables: None,
+            priority: PipelinePriority::High,
+        },
         "gcr.io/kaniko-project/executor:v1.23.2-debug",
     )
     .await
@@ -531,10 +652,14 @@ async fn on_api_no_yaml_returns_error(pool: PgPool) {
 
     let result = trigger::on_api(
         &pool,
-        std::path::Path::new("/tmp/nonexistent-repo-67890"),
-        Uuid::new_v4(), // project doesn't need to exist for this error path
-        "refs/heads/main",
-        Uuid::new_v4(),
+        &ApiTriggerParams {
+            project_id: Uuid::new_v4(), // project doesn't need to exist for this error path
+            user_id: Uuid::new_v4(),
+            repo_path: PathBuf::from("/tmp/nonexistent-repo-67890"),
+            git_ref: "refs/heads/main".into(),
+            encrypted_variables: None,
+            priority: PipelinePriority::High,
+        },
         "gcr.io/kaniko-project/executor:v1.23.2-debug",
     )
     .await;
@@ -646,6 +771,7 @@ async fn on_push_invalid_yaml_returns_error(pool: PgPool) {
         repo_path: bare_path.clone(),
         branch: "main".into(),
         commit_sha: None,
+        before_sha: None,
     };
 
     let result = trigger::on_push(
@@ -1172,6 +1298,7 @@ async fn on_push_with_version_creates_tag(pool: PgPool) {
         repo_path: bare_path.clone(),
         branch: "main".into(),
         commit_sha: Some(commit_sha),
+        before_sha: None,
     };
 
     let result = trigger::on_push(
@@ -1235,6 +1362,7 @@ async fn on_push_imagebuild_step_type(pool: PgPool) {
         repo_path: bare_path.clone(),
         branch: "main".into(),
         commit_sha: Some("abc12345".into()),
+        before_sha: None,
     };
 
     let result = trigger::on_push(
@@ -1303,6 +1431,7 @@ async fn on_push_gitops_sync_step_type(pool: PgPool) {
         repo_path: bare_path.clone(),
         branch: "main".into(),
         commit_sha: None,
+        before_sha: None,
     };
 
     let result = trigger::on_push(
@@ -1357,6 +1486,7 @@ async fn on_push_deploy_test_step_type(pool: PgPool) {
         repo_path: bare_path.clone(),
         branch: "main".into(),
         commit_sha: None,
+        before_sha: None,
     };
 
     let result = trigger::on_push(
@@ -1410,6 +1540,7 @@ async fn on_push_deploy_watch_step_type(pool: PgPool) {
         repo_path: bare_path.clone(),
         branch: "main".into(),
         commit_sha: None,
+        before_sha: None,
     };
 
     let result = trigger::on_push(
@@ -1467,6 +1598,7 @@ async fn on_push_step_with_depends_on(pool: PgPool) {
         repo_path: bare_path.clone(),
         branch: "main".into(),
         commit_sha: None,
+        before_sha: None,
     };
 
     let result = trigger::on_push(
@@ -1529,6 +1661,7 @@ async fn on_push_step_with_condition(pool: PgPool) {
         repo_path: bare_path.clone(),
         branch: "main".into(),
         commit_sha: None,
+        before_sha: None,
     };
 
     let result = trigger::on_push(
@@ -1776,6 +1909,7 @@ async fn on_push_step_with_artifacts_stored_in_config(pool: PgPool) {
         repo_path: bare_path.clone(),
         branch: "main".into(),
         commit_sha: None,
+        before_sha: None,
     };
 
     let result = trigger::on_push(
@@ -1839,6 +1973,7 @@ async fn on_push_step_without_artifacts_no_config_key(pool: PgPool) {
         repo_path: bare_path.clone(),
         branch: "main".into(),
         commit_sha: None,
+        before_sha: None,
     };
 
     let result = trigger::on_push(
@@ -1868,3 +2003,138 @@ async fn on_push_step_without_artifacts_no_config_key(pool: PgPool) {
     drop(bare_dir);
     drop(work_dir);
 }
+
+// ---------------------------------------------------------------------------
+// post_receive — push auto-triggers pipelines, honouring branches_ignore
+// ---------------------------------------------------------------------------
+
+const IGNORE_FILTERED_YAML: &str = "\
+pipeline:
+  steps:
+    - name: test
+      image: alpine:3.19
+      commands:
+        - echo hello
+  on:
+    push:
+      branches_ignore: [\"wip/*\"]
+";
+
+/// Create `branch` from the current HEAD of the working copy and push it.
+/// Returns the pushed commit SHA.
+fn push_branch(work_path: &std::path::Path, branch: &str) -> String {
+    for args in [
+        &["checkout", "-b", branch][..],
+        &["push", "origin", branch][..],
+    ] {
+        let out = Command::new("git")
+            .arg("-C")
+            .arg(work_path)
+            .args(args)
+            .output()
+            .unwrap();
+        assert!(out.status.success(), "git {args:?} failed: {out:?}");
+    }
+    let out = Command::new("git")
+        .arg("-C")
+        .arg(work_path)
+        .args(["rev-parse", "HEAD"])
+        .output()
+        .unwrap();
+    String::from_utf8_lossy(&out.stdout).trim().to_owned()
+}
+
+fn post_receive_params(
+    project_id: Uuid,
+    user_id: Uuid,
+    bare_path: &std::path::Path,
+    branch: &str,
+    sha: &str,
+) -> platform::git::hooks::PostReceiveParams {
+    platform::git::hooks::PostReceiveParams {
+        project_id,
+        user_id,
+        user_name: "admin".into(),
+        repo_path: bare_path.to_path_buf(),
+        default_branch: "main".into(),
+        pushed_branches: vec![branch.into()],
+        pushed_tags: vec![],
+        ref_updates: vec![platform::git::hooks::RefUpdate {
+            old_sha: "0".repeat(40),
+            new_sha: sha.into(),
+            refname: format!("refs/heads/{branch}"),
+        }],
+    }
+}
+
+async fn push_pipelines(pool: &PgPool, project_id: Uuid) -> Vec<(String, String, Option<String>)> {
+    sqlx::query_as(
+        "SELECT git_ref, status, commit_sha FROM pipelines
+         WHERE project_id = $1 AND trigger = 'push' ORDER BY created_at",
+    )
+    .bind(project_id)
+    .fetch_all(pool)
+    .await
+    .unwrap()
+}
+
+#[sqlx::test(migrations = "./migrations")]
+async fn post_receive_triggers_pending_pipeline(pool: PgPool) {
+    let (state, _token) = helpers::test_state(pool.clone()).await;
+    let (bare_dir, work_dir, bare_path) = create_test_repo_with_pipeline_yaml(SIMPLE_YAML);
+    let (project_id, user_id) = create_project_with_repo(&pool, bare_path.to_str().unwrap()).await;
+
+    let sha = push_branch(work_dir.path(), "feature/login");
+    platform::git::hooks::post_receive(
+        &state,
+        &post_receive_params(project_id, user_id, &bare_path, "feature/login", &sha),
+    )
+    .await
+    .unwrap();
+
+    let pipelines = push_pipelines(&pool, project_id).await;
+    assert_eq!(
+        pipelines.len(),
+        1,
+        "push should create exactly one pipeline"
+    );
+    assert_eq!(pipelines[0].0, "refs/heads/feature/login");
+    assert_eq!(pipelines[0].1, "pending");
+    assert_eq!(pipelines[0].2.as_deref(), Some(sha.as_str()));
+
+    drop(bare_dir);
+    drop(work_dir);
+}
+
+#[sqlx::test(migrations = "./migrations")]
+async fn post_receive_skips_ignored_branch(pool: PgPool) {
+    let (state, _token) = helpers::test_state(pool.clone()).await;
+    let (bare_dir, work_dir, bare_path) = create_test_repo_with_pipeline_yaml(IGNORE_FILTERED_YAML);
+    let (project_id, user_id) = create_project_with_repo(&pool, bare_path.to_str().unwrap()).await;
+
+    let sha = push_branch(work_dir.path(), "wip/spike");
+    platform::git::hooks::post_receive(
+        &state,
+        &post_receive_params(project_id, user_id, &bare_path, "wip/spike", &sha),
+    )
+    .await
+    .unwrap();
+    assert!(
+        push_pipelines(&pool, project_id).await.is_empty(),
+        "ignored branch should not trigger a pipeline"
+    );
+
+    let sha = push_branch(work_dir.path(), "feature/ok");
+    platform::git::hooks::post_receive(
+        &state,
+        &post_receive_params(project_id, user_id, &bare_path, "feature/ok", &sha),
+    )
+    .await
+    .unwrap();
+    let pipelines = push_pipelines(&pool, project_id).await;
+    assert_eq!(pipelines.len(), 1);
+    assert_eq!(pipelines[0].0, "refs/heads/feature/ok");
+
+    drop(bare_dir);
+    drop(work_dir);
+}
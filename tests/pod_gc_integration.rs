@@ -0,0 +1,169 @@
+// Copyright (c) 2026 Steven Hooker. Exclusively licensed to and distributed by AgentSphere GmbH.
+// SPDX-License-Identifier: BUSL-1.1
+
+//! Integration tests for `deployer::pod_gc` — orphaned pod garbage collection
+//! against the Kind cluster.
+
+mod helpers;
+
+use std::collections::BTreeMap;
+
+use k8s_openapi::api::core::v1::{Container, Namespace, Pod, PodSpec};
+use kube::api::{Api, DeleteParams, ObjectMeta, PostParams};
+use sqlx::PgPool;
+use uuid::Uuid;
+
+async fn create_namespace(kube: &kube::Client) -> String {
+    let ns_name = format!("pod-gc-{}", &Uuid::new_v4().to_string()[..8]);
+    let ns_api: Api<Namespace> = Api::all(kube.clone());
+    let ns = Namespace {
+        metadata: ObjectMeta {
+            name: Some(ns_name.clone()),
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+    ns_api
+        .create(&PostParams::default(), &ns)
+        .await
+        .unwrap_or_else(|e| panic!("failed to create namespace {ns_name}: {e}"));
+    ns_name
+}
+
+async fn create_labelled_pod(pods: &Api<Pod>, name: &str, labels: &[(&str, String)]) {
+    let pod = Pod {
+        metadata: ObjectMeta {
+            name: Some(name.into()),
+            labels: Some(
+                labels
+                    .iter()
+                    .map(|(k, v)| ((*k).to_owned(), v.clone()))
+                    .collect::<BTreeMap<_, _>>(),
+            ),
+            ..Default::default()
+        },
+        spec: Some(PodSpec {
+            restart_policy: Some("Never".into()),
+            containers: vec![Container {
+                name: "step".into(),
+                image: Some("alpine:3.19".into()),
+                command: Some(vec!["sleep".into(), "300".into()]),
+                ..Default::default()
+            }],
+            ..Default::default()
+        }),
+        ..Default::default()
+    };
+    pods.create(&PostParams::default(), &pod)
+        .await
+        .unwrap_or_else(|e| panic!("failed to create pod {name}: {e}"));
+}
+
+/// Whether the pod is gone or on its way out.
+async fn is_deleted(pods: &Api<Pod>, name: &str) -> bool {
+    match pods.get_opt(name).await.unwrap() {
+        None => true,
+        Some(pod) => pod.metadata.deletion_timestamp.is_some(),
+    }
+}
+
+#[sqlx::test(migrations = "./migrations")]
+async fn pod_gc_deletes_pods_of_finished_pipelines(pool: PgPool) {
+    let (state, admin_token) = helpers::test_state(pool.clone()).await;
+    let app = helpers::test_router(state.clone());
+    let uid = helpers::admin_user_id(&pool).await;
+    let project_id = helpers::create_project(&app, &admin_token, "pod-gc", "private").await;
+
+    let finished =
+        helpers::insert_pipeline(&pool, project_id, uid, "success", "refs/heads/main", "push")
+            .await;
+    let running =
+        helpers::insert_pipeline(&pool, project_id, uid, "running", "refs/heads/main", "push")
+            .await;
+    // Pipeline row gone, but the project is ours
+    let vanished = Uuid::new_v4();
+    // Neither pipeline nor project known: another instance sharing the cluster
+    let foreign = Uuid::new_v4();
+
+    let ns_name = create_namespace(&state.kube).await;
+    let pods: Api<Pod> = Api::namespaced(state.kube.clone(), &ns_name);
+    for (name, pipeline_id, project) in [
+        ("finished", finished, project_id),
+        ("running", running, project_id),
+        ("vanished", vanished, project_id),
+        ("foreign", foreign, Uuid::new_v4()),
+    ] {
+        create_labelled_pod(
+            &pods,
+            name,
+            &[
+                ("platform.io/pipeline", pipeline_id.to_string()),
+                ("platform.io/project", project.to_string()),
+            ],
+        )
+        .await;
+    }
+
+    let deleted = platform::deployer::pod_gc::collect_orphaned_pods(&state)
+        .await
+        .expect("pod GC should succeed");
+    assert!(deleted >= 2, "expected at least 2 deletions, got {deleted}");
+
+    assert!(
+        is_deleted(&pods, "finished").await,
+        "finished pipeline's pod"
+    );
+    assert!(
+        is_deleted(&pods, "vanished").await,
+        "deleted pipeline's pod"
+    );
+    assert!(
+        !is_deleted(&pods, "running").await,
+        "running pipeline's pod"
+    );
+    assert!(!is_deleted(&pods, "foreign").await, "unknown project's pod");
+
+    let ns_api: Api<Namespace> = Api::all(state.kube.clone());
+    let _ = ns_api.delete(&ns_name, &DeleteParams::default()).await;
+}
+
+#[sqlx::test(migrations = "./migrations")]
+async fn pod_gc_deletes_pods_of_finished_agent_sessions(pool: PgPool) {
+    let (state, admin_token) = helpers::test_state(pool.clone()).await;
+    let app = helpers::test_router(state.clone());
+    let uid = helpers::admin_user_id(&pool).await;
+    let project_id = helpers::create_project(&app, &admin_token, "pod-gc-agent", "private").await;
+
+    let session_id = Uuid::new_v4();
+    sqlx::query(
+        "INSERT INTO agent_sessions (id, project_id, user_id, prompt, status, finished_at)
+         VALUES ($1, $2, $3, 'test', 'completed', now())",
+    )
+    .bind(session_id)
+    .bind(project_id)
+    .bind(uid)
+    .execute(&pool)
+    .await
+    .unwrap();
+
+    let ns_name = create_namespace(&state.kube).await;
+    let pods: Api<Pod> = Api::namespaced(state.kube.clone(), &ns_name);
+    create_labelled_pod(
+        &pods,
+        "agent",
+        &[
+            ("platform.io/component", "agent-session".into()),
+            ("platform.io/session", session_id.to_string()),
+            ("platform.io/project", project_id.to_string()),
+        ],
+    )
+    .await;
+
+    platform::deployer::pod_gc::collect_orphaned_pods(&state)
+        .await
+        .expect("pod GC should succeed");
+    assert!(is_deleted(&pods, "agent").await);
+
+    let ns_api: Api<Namespace> = Api::all(state.kube.clone());
+    let _ = ns_api.delete(&ns_name, &DeleteParams::default()).await;
+}
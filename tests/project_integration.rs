@@ -145,16 +145,76 @@ async fn get_project_by_id(pool: PgPool) {
     assert_eq!(body["name"], "getproj");
 }
 
+#[sqlx::test(migrations = "./migrations")]
+async fn create_project_idempotency_key_replays_response(pool: PgPool) {
+    let (state, admin_token) = helpers::test_state(pool.clone()).await;
+    let app = helpers::test_router(state);
+    let key = Uuid::new_v4().to_string();
+    let body = serde_json::json!({ "name": "idem-project", "setup_infra": false });
+
+    let (status, replayed, first) =
+        helpers::post_json_idempotent(&app, &admin_token, "/api/projects", &key, body.clone())
+            .await;
+    assert_eq!(status, StatusCode::CREATED, "{first}");
+    assert!(!replayed);
+
+    let (status, replayed, second) =
+        helpers::post_json_idempotent(&app, &admin_token, "/api/projects", &key, body).await;
+    assert_eq!(status, StatusCode::CREATED);
+    assert!(
+        replayed,
+        "retry should be served from the idempotency cache"
+    );
+    assert_eq!(first, second);
+
+    let count: i64 =
+        sqlx::query_scalar("SELECT COUNT(*) FROM projects WHERE name = 'idem-project'")
+            .fetch_one(&pool)
+            .await
+            .unwrap();
+    assert_eq!(count, 1);
+}
+
+#[sqlx::test(migrations = "./migrations")]
+async fn idempotency_key_reuse_with_different_body_rejected(pool: PgPool) {
+    let (state, admin_token) = helpers::test_state(pool).await;
+    let app = helpers::test_router(state);
+    let key = Uuid::new_v4().to_string();
+
+    let (status, _, _) = helpers::post_json_idempotent(
+        &app,
+        &admin_token,
+        "/api/projects",
+        &key,
+        serde_json::json!({ "name": "idem-a", "setup_infra": false }),
+    )
+    .await;
+    assert_eq!(status, StatusCode::CREATED);
+
+    let (status, _, body) = helpers::post_json_idempotent(
+        &app,
+        &admin_token,
+        "/api/projects",
+        &key,
+        serde_json::json!({ "name": "idem-b", "setup_infra": false }),
+    )
+    .await;
+    assert_eq!(status, StatusCode::UNPROCESSABLE_ENTITY);
+    assert_eq!(body["code"], "validation_failed");
+}
+
 #[sqlx::test(migrations = "./migrations")]
 async fn get_nonexistent_project(pool: PgPool) {
     let (state, admin_token) = helpers::test_state(pool).await;
     let app = helpers::test_router(state);
 
     let random_id = Uuid::new_v4();
-    let (status, _) =
+    let (status, body) =
         helpers::get_json(&app, &admin_token, &format!("/api/projects/{random_id}")).await;
 
     assert_eq!(status, StatusCode::NOT_FOUND);
+    assert_eq!(body["code"], "not_found");
+    assert!(body["message"].as_str().is_some_and(|m| !m.is_empty()));
 }
 
 #[sqlx::test(migrations = "./migrations")]
@@ -180,6 +240,50 @@ async fn update_project(pool: PgPool) {
     assert_eq!(body["visibility"], "public");
 }
 
+#[sqlx::test(migrations = "./migrations")]
+async fn update_default_branch_moves_repo_head(pool: PgPool) {
+    let (state, admin_token) = helpers::test_state(pool).await;
+    let app = helpers::test_router(state.clone());
+    let project_id = helpers::create_project(&app, &admin_token, "branchproj", "private").await;
+    let repo = state
+        .config
+        .git_repos_path
+        .join("admin")
+        .join("branchproj.git");
+    let path = format!("/api/projects/{project_id}");
+
+    // The branch must exist in a non-empty repo
+    let (status, body) = helpers::patch_json(
+        &app,
+        &admin_token,
+        &path,
+        serde_json::json!({ "default_branch": "develop" }),
+    )
+    .await;
+    assert_eq!(status, StatusCode::BAD_REQUEST, "{body}");
+    let (status, _) = helpers::patch_json(
+        &app,
+        &admin_token,
+        &path,
+        serde_json::json!({ "default_branch": "bad..name" }),
+    )
+    .await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+
+    helpers::git_cmd(&repo, &["branch", "develop", "main"]);
+    let (status, body) = helpers::patch_json(
+        &app,
+        &admin_token,
+        &path,
+        serde_json::json!({ "default_branch": "develop" }),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK, "{body}");
+    assert_eq!(body["default_branch"], "develop");
+    let head = helpers::git_cmd(&repo, &["symbolic-ref", "HEAD"]);
+    assert_eq!(head.trim(), "refs/heads/develop");
+}
+
 #[sqlx::test(migrations = "./migrations")]
 async fn delete_project_soft_delete(pool: PgPool) {
     let (state, admin_token) = helpers::test_state(pool).await;
@@ -414,3 +518,232 @@ async fn recreate_project_after_soft_delete(pool: PgPool) {
     let id2: Uuid = body["id"].as_str().unwrap().parse().unwrap();
     assert_ne!(id1, id2, "new project should have a different id");
 }
+
+// ---------------------------------------------------------------------------
+// Ownership transfer
+// ---------------------------------------------------------------------------
+
+/// Transferring a project moves implicit owner access to the new owner and
+/// removes it from the old one.
+#[sqlx::test(migrations = "./migrations")]
+async fn transfer_project_moves_owner_access(pool: PgPool) {
+    let (state, admin_token) = helpers::test_state(pool.clone()).await;
+    let app = helpers::test_router(state);
+
+    let project_id = helpers::create_project(&app, &admin_token, "handover", "private").await;
+    let (alice_id, alice_token) =
+        helpers::create_user(&app, &admin_token, "alice-xfer", "alice-xfer@test.com").await;
+    let (bob_id, bob_token) =
+        helpers::create_user(&app, &admin_token, "bob-xfer", "bob-xfer@test.com").await;
+
+    // Alice has no access yet (and caches that)
+    let (status, _) =
+        helpers::get_json(&app, &alice_token, &format!("/api/projects/{project_id}")).await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+
+    // Admin transfers to Alice
+    let (status, body) = helpers::post_json(
+        &app,
+        &admin_token,
+        &format!("/api/projects/{project_id}/transfer"),
+        serde_json::json!({ "new_owner_id": alice_id }),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK, "{body}");
+    assert_eq!(body["owner_id"], alice_id.to_string());
+
+    // Alice can now manage it
+    let (status, _) = helpers::patch_json(
+        &app,
+        &alice_token,
+        &format!("/api/projects/{project_id}"),
+        serde_json::json!({ "description": "mine now" }),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+
+    // As current owner, Alice hands it on to Bob
+    let (status, _) = helpers::post_json(
+        &app,
+        &alice_token,
+        &format!("/api/projects/{project_id}/transfer"),
+        serde_json::json!({ "new_owner_id": bob_id }),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+
+    // Alice loses implicit owner access; Bob gains it
+    let (status, _) =
+        helpers::get_json(&app, &alice_token, &format!("/api/projects/{project_id}")).await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+    let (status, _) = helpers::patch_json(
+        &app,
+        &bob_token,
+        &format!("/api/projects/{project_id}"),
+        serde_json::json!({ "description": "bob's" }),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+
+    assert_eq!(
+        helpers::wait_for_audit(&pool, "project.transfer", 2000).await,
+        2
+    );
+}
+
+/// Only the owner or an admin may transfer, and only to an active user.
+#[sqlx::test(migrations = "./migrations")]
+async fn transfer_project_validates_caller_and_target(pool: PgPool) {
+    let (state, admin_token) = helpers::test_state(pool.clone()).await;
+    let app = helpers::test_router(state);
+
+    let project_id = helpers::create_project(&app, &admin_token, "guarded", "public").await;
+    let (user_id, user_token) =
+        helpers::create_user(&app, &admin_token, "not-owner", "not-owner@test.com").await;
+
+    // Non-owner, non-admin cannot transfer
+    let (status, _) = helpers::post_json(
+        &app,
+        &user_token,
+        &format!("/api/projects/{project_id}/transfer"),
+        serde_json::json!({ "new_owner_id": user_id }),
+    )
+    .await;
+    assert_eq!(status, StatusCode::FORBIDDEN);
+
+    // Unknown user
+    let (status, _) = helpers::post_json(
+        &app,
+        &admin_token,
+        &format!("/api/projects/{project_id}/transfer"),
+        serde_json::json!({ "new_owner_id": Uuid::new_v4() }),
+    )
+    .await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+
+    // Deactivated user
+    sqlx::query("UPDATE users SET is_active = false WHERE id = $1")
+        .bind(user_id)
+        .execute(&pool)
+        .await
+        .unwrap();
+    let (status, _) = helpers::post_json(
+        &app,
+        &admin_token,
+        &format!("/api/projects/{project_id}/transfer"),
+        serde_json::json!({ "new_owner_id": user_id }),
+    )
+    .await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+}
+
+// ---------------------------------------------------------------------------
+// Archival
+// ---------------------------------------------------------------------------
+
+/// An archived project stays readable but refuses modification, drops out of
+/// the default listing, and becomes writable again once unarchived.
+#[sqlx::test(migrations = "./migrations")]
+async fn archived_project_is_read_only(pool: PgPool) {
+    let (state, admin_token) = helpers::test_state(pool.clone()).await;
+    let app = helpers::test_router(state);
+
+    let project_id = helpers::create_project(&app, &admin_token, "retired", "private").await;
+
+    let (status, body) = helpers::post_json(
+        &app,
+        &admin_token,
+        &format!("/api/projects/{project_id}/archive"),
+        serde_json::json!({}),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK, "{body}");
+    assert!(body["archived_at"].is_string());
+
+    // Archiving twice conflicts
+    let (status, _) = helpers::post_json(
+        &app,
+        &admin_token,
+        &format!("/api/projects/{project_id}/archive"),
+        serde_json::json!({}),
+    )
+    .await;
+    assert_eq!(status, StatusCode::CONFLICT);
+
+    // Still readable
+    let (status, _) =
+        helpers::get_json(&app, &admin_token, &format!("/api/projects/{project_id}")).await;
+    assert_eq!(status, StatusCode::OK);
+
+    // Not modifiable, and no pipelines
+    let (status, _) = helpers::patch_json(
+        &app,
+        &admin_token,
+        &format!("/api/projects/{project_id}"),
+        serde_json::json!({ "description": "changed" }),
+    )
+    .await;
+    assert_eq!(status, StatusCode::LOCKED);
+    let (status, _) = helpers::post_json(
+        &app,
+        &admin_token,
+        &format!("/api/projects/{project_id}/pipelines"),
+        serde_json::json!({ "git_ref": "refs/heads/main" }),
+    )
+    .await;
+    assert_eq!(status, StatusCode::LOCKED);
+    let (status, _) = helpers::post_json(
+        &app,
+        &admin_token,
+        &format!("/api/projects/{project_id}/issues"),
+        serde_json::json!({ "title": "late bug" }),
+    )
+    .await;
+    assert_eq!(status, StatusCode::LOCKED);
+    let (status, _) = helpers::post_json(
+        &app,
+        &admin_token,
+        &format!("/api/projects/{project_id}/merge-requests/1/merge"),
+        serde_json::json!({}),
+    )
+    .await;
+    assert_eq!(status, StatusCode::LOCKED);
+
+    // Hidden from the default listing, shown on request
+    let (_, body) = helpers::get_json(&app, &admin_token, "/api/projects").await;
+    assert!(
+        body["items"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .all(|p| p["id"] != project_id.to_string())
+    );
+    let (_, body) =
+        helpers::get_json(&app, &admin_token, "/api/projects?include_archived=true").await;
+    assert!(
+        body["items"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .any(|p| p["id"] == project_id.to_string())
+    );
+
+    let (status, body) = helpers::post_json(
+        &app,
+        &admin_token,
+        &format!("/api/projects/{project_id}/unarchive"),
+        serde_json::json!({}),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert!(body["archived_at"].is_null());
+
+    let (status, _) = helpers::patch_json(
+        &app,
+        &admin_token,
+        &format!("/api/projects/{project_id}"),
+        serde_json::json!({ "description": "changed" }),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+}
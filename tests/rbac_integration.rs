@@ -611,3 +611,489 @@ async fn scoped_token_enforces_permission_boundary(pool: PgPool) {
     .await;
     assert_eq!(status, StatusCode::FORBIDDEN);
 }
+
+/// A token bounded to project A can't reach project B or admin endpoints,
+/// even though its owner is an admin, and can't mint an unbounded token.
+#[sqlx::test(migrations = "./migrations")]
+async fn project_bounded_token_cannot_escalate(pool: PgPool) {
+    let (state, admin_token) = helpers::test_state(pool.clone()).await;
+    let app = helpers::test_router(state);
+
+    let project_a = helpers::create_project(&app, &admin_token, "bound-a", "private").await;
+    let project_b = helpers::create_project(&app, &admin_token, "bound-b", "private").await;
+
+    let (status, token_body) = helpers::post_json(
+        &app,
+        &admin_token,
+        "/api/tokens",
+        serde_json::json!({"name": "ci-a", "project_id": project_a}),
+    )
+    .await;
+    assert_eq!(status, StatusCode::CREATED, "{token_body}");
+    let ci_token = token_body["token"].as_str().unwrap();
+
+    let (status, _) =
+        helpers::get_json(&app, ci_token, &format!("/api/projects/{project_a}")).await;
+    assert_eq!(status, StatusCode::OK);
+    let (status, body) = helpers::get_json(&app, ci_token, "/api/projects").await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["total"], 1, "{body}");
+
+    for path in [
+        format!("/api/projects/{project_b}"),
+        format!("/api/projects/{project_b}/issues"),
+        format!("/api/projects/{project_b}/secrets"),
+        "/api/admin/roles".to_owned(),
+        "/api/audit-log".to_owned(),
+        "/api/dashboard/stats".to_owned(),
+    ] {
+        let (status, _) = helpers::get_json(&app, ci_token, &path).await;
+        assert_eq!(status, StatusCode::FORBIDDEN, "{path}");
+    }
+    let (status, _) = helpers::post_json(
+        &app,
+        ci_token,
+        &format!("/api/projects/{project_b}/issues"),
+        serde_json::json!({"title": "cross-project", "body": ""}),
+    )
+    .await;
+    assert_eq!(status, StatusCode::FORBIDDEN);
+
+    // New tokens stay inside the boundary
+    let (status, _) = helpers::post_json(
+        &app,
+        ci_token,
+        "/api/tokens",
+        serde_json::json!({"name": "escape"}),
+    )
+    .await;
+    assert_eq!(status, StatusCode::FORBIDDEN);
+    let (status, _) = helpers::post_json(
+        &app,
+        ci_token,
+        "/api/tokens",
+        serde_json::json!({"name": "sibling", "project_id": project_a}),
+    )
+    .await;
+    assert_eq!(status, StatusCode::CREATED);
+}
+
+// ---------------------------------------------------------------------------
+// Role hierarchy
+// ---------------------------------------------------------------------------
+
+/// Permissions granted to a parent role reach holders of child roles
+/// immediately, without re-listing them on the child.
+#[sqlx::test(migrations = "./migrations")]
+async fn child_role_inherits_parent_permissions(pool: PgPool) {
+    let (state, admin_token) = helpers::test_state(pool.clone()).await;
+    let app = helpers::test_router(state);
+
+    let (status, parent) = helpers::post_json(
+        &app,
+        &admin_token,
+        "/api/admin/roles",
+        serde_json::json!({ "name": "maintainer" }),
+    )
+    .await;
+    assert_eq!(status, StatusCode::CREATED);
+    let parent_id = parent["id"].as_str().unwrap();
+
+    let (status, child) = helpers::post_json(
+        &app,
+        &admin_token,
+        "/api/admin/roles",
+        serde_json::json!({ "name": "lead", "parent_role_id": parent_id }),
+    )
+    .await;
+    assert_eq!(status, StatusCode::CREATED);
+    assert_eq!(child["parent_role_id"], parent["id"]);
+    let child_id = uuid::Uuid::parse_str(child["id"].as_str().unwrap()).unwrap();
+
+    let (user_id, user_token) =
+        helpers::create_user(&app, &admin_token, "leaduser", "lead@test.com").await;
+    let (status, _) = helpers::post_json(
+        &app,
+        &admin_token,
+        &format!("/api/admin/users/{user_id}/roles"),
+        serde_json::json!({ "role_id": child_id }),
+    )
+    .await;
+    assert_eq!(status, StatusCode::CREATED);
+
+    // No permissions anywhere in the chain yet (also primes the cache)
+    let create = serde_json::json!({ "name": "inherited-proj", "visibility": "public" });
+    let (status, _) = helpers::post_json(&app, &user_token, "/api/projects", create.clone()).await;
+    assert_eq!(status, StatusCode::FORBIDDEN);
+
+    // Grant on the parent only
+    let (status, _) = helpers::put_json(
+        &app,
+        &admin_token,
+        &format!("/api/admin/roles/{parent_id}/permissions"),
+        serde_json::json!({ "permissions": ["project:read", "project:write"] }),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+
+    let (status, _) = helpers::post_json(&app, &user_token, "/api/projects", create).await;
+    assert_eq!(status, StatusCode::CREATED);
+}
+
+/// Setting a parent that would make a role its own ancestor is rejected.
+#[sqlx::test(migrations = "./migrations")]
+async fn role_hierarchy_rejects_cycles(pool: PgPool) {
+    let (state, admin_token) = helpers::test_state(pool.clone()).await;
+    let app = helpers::test_router(state);
+
+    let (_, a) = helpers::post_json(
+        &app,
+        &admin_token,
+        "/api/admin/roles",
+        serde_json::json!({ "name": "role-a" }),
+    )
+    .await;
+    let a_id = a["id"].as_str().unwrap();
+    let (_, b) = helpers::post_json(
+        &app,
+        &admin_token,
+        "/api/admin/roles",
+        serde_json::json!({ "name": "role-b", "parent_role_id": a_id }),
+    )
+    .await;
+    let b_id = b["id"].as_str().unwrap();
+
+    let (status, _) = helpers::put_json(
+        &app,
+        &admin_token,
+        &format!("/api/admin/roles/{a_id}/parent"),
+        serde_json::json!({ "parent_role_id": b_id }),
+    )
+    .await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+
+    let (status, _) = helpers::put_json(
+        &app,
+        &admin_token,
+        &format!("/api/admin/roles/{a_id}/parent"),
+        serde_json::json!({ "parent_role_id": a_id }),
+    )
+    .await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+
+    // Clearing the parent works
+    let (status, body) = helpers::put_json(
+        &app,
+        &admin_token,
+        &format!("/api/admin/roles/{b_id}/parent"),
+        serde_json::json!({ "parent_role_id": null }),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert!(body["parent_role_id"].is_null());
+}
+
+/// A batch check resolves global and per-project permissions in one request.
+#[sqlx::test(migrations = "./migrations")]
+async fn batch_permission_check(pool: PgPool) {
+    let (state, admin_token) = helpers::test_state(pool.clone()).await;
+    let app = helpers::test_router(state);
+
+    let project_a = helpers::create_project(&app, &admin_token, "batch-a", "private").await;
+    let project_b = helpers::create_project(&app, &admin_token, "batch-b", "private").await;
+    let (user_id, user_token) =
+        helpers::create_user(&app, &admin_token, "batchuser", "batch@test.com").await;
+    helpers::assign_role(
+        &app,
+        &admin_token,
+        user_id,
+        "developer",
+        Some(project_a),
+        &pool,
+    )
+    .await;
+
+    let (status, body) = helpers::post_json(
+        &app,
+        &user_token,
+        "/api/auth/permissions/check",
+        serde_json::json!({ "checks": [
+            { "permission": "project:write", "project_id": project_a },
+            { "permission": "project:write", "project_id": project_b },
+            { "permission": "project:read", "project_id": project_b },
+            { "permission": "admin:users" },
+        ]}),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    let results = &body["results"];
+    assert_eq!(results[project_a.to_string()]["project:write"], true);
+    assert_eq!(results[project_b.to_string()]["project:write"], false);
+    assert_eq!(results[project_b.to_string()]["project:read"], false);
+    assert_eq!(results["global"]["admin:users"], false);
+
+    // Second call is served from the warmed cache with identical results
+    let (status, cached) = helpers::post_json(
+        &app,
+        &user_token,
+        "/api/auth/permissions/check",
+        serde_json::json!({ "checks": [
+            { "permission": "project:write", "project_id": project_a },
+        ]}),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(
+        cached["results"][project_a.to_string()]["project:write"],
+        true
+    );
+}
+
+/// Unknown permission names and oversized batches are rejected.
+#[sqlx::test(migrations = "./migrations")]
+async fn batch_permission_check_validates_input(pool: PgPool) {
+    let (state, admin_token) = helpers::test_state(pool).await;
+    let app = helpers::test_router(state);
+
+    let (status, _) = helpers::post_json(
+        &app,
+        &admin_token,
+        "/api/auth/permissions/check",
+        serde_json::json!({ "checks": [{ "permission": "nope:nope" }] }),
+    )
+    .await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+
+    let checks: Vec<_> = (0..101)
+        .map(|_| serde_json::json!({ "permission": "project:read" }))
+        .collect();
+    let (status, _) = helpers::post_json(
+        &app,
+        &admin_token,
+        "/api/auth/permissions/check",
+        serde_json::json!({ "checks": checks }),
+    )
+    .await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+}
+
+/// A project member can self-grant `deploy:promote` via break-glass; admins are
+/// notified, audit entries are tagged, and ending the grant removes access.
+#[sqlx::test(migrations = "./migrations")]
+async fn break_glass_grants_temporary_permission(pool: PgPool) {
+    let (state, admin_token) = helpers::test_state(pool.clone()).await;
+    let app = helpers::test_router(state);
+
+    let project = helpers::create_project(&app, &admin_token, "bg-proj", "private").await;
+    let (user_id, user_token) =
+        helpers::create_user(&app, &admin_token, "oncall", "oncall@test.com").await;
+    helpers::assign_role(&app, &admin_token, user_id, "viewer", Some(project), &pool).await;
+
+    let check = serde_json::json!({ "checks": [
+        { "permission": "deploy:promote", "project_id": project },
+    ]});
+    let (_, body) = helpers::post_json(
+        &app,
+        &user_token,
+        "/api/auth/permissions/check",
+        check.clone(),
+    )
+    .await;
+    assert_eq!(
+        body["results"][project.to_string()]["deploy:promote"],
+        false
+    );
+
+    let (status, grant) = helpers::post_json(
+        &app,
+        &user_token,
+        "/api/break-glass",
+        serde_json::json!({
+            "permission": "deploy:promote",
+            "project_id": project,
+            "reason": "prod outage, rollback blocked",
+        }),
+    )
+    .await;
+    assert_eq!(status, StatusCode::CREATED, "{grant}");
+    assert_eq!(grant["break_glass"], true);
+    assert_eq!(grant["delegate_id"], user_id.to_string());
+    let grant_id = grant["id"].as_str().unwrap().to_owned();
+
+    let (_, body) = helpers::post_json(
+        &app,
+        &user_token,
+        "/api/auth/permissions/check",
+        check.clone(),
+    )
+    .await;
+    assert_eq!(body["results"][project.to_string()]["deploy:promote"], true);
+
+    // A second grant while the first is active conflicts
+    let (status, _) = helpers::post_json(
+        &app,
+        &user_token,
+        "/api/break-glass",
+        serde_json::json!({
+            "permission": "deploy:promote",
+            "project_id": project,
+            "reason": "again",
+        }),
+    )
+    .await;
+    assert_eq!(status, StatusCode::CONFLICT);
+
+    // The grant itself is audited and tagged with the grant ID
+    assert_eq!(
+        helpers::wait_for_audit(&pool, "break_glass.grant", 2000).await,
+        1
+    );
+    let tagged: Option<uuid::Uuid> = sqlx::query_scalar(
+        "SELECT break_glass_id FROM audit_log WHERE action = 'break_glass.grant'",
+    )
+    .fetch_one(&pool)
+    .await
+    .unwrap();
+    assert_eq!(tagged.map(|id| id.to_string()), Some(grant_id.clone()));
+
+    // Admins are notified (dispatched in the background)
+    let mut notified = 0i64;
+    for _ in 0..40 {
+        notified = sqlx::query_scalar(
+            "SELECT COUNT(*) FROM notifications WHERE notification_type = 'break_glass'",
+        )
+        .fetch_one(&pool)
+        .await
+        .unwrap();
+        if notified > 0 {
+            break;
+        }
+        tokio::time::sleep(std::time::Duration::from_millis(50)).await;
+    }
+    assert!(notified > 0, "expected admin break-glass notification");
+
+    let (status, _) =
+        helpers::delete_json(&app, &user_token, &format!("/api/break-glass/{grant_id}")).await;
+    assert_eq!(status, StatusCode::NO_CONTENT);
+
+    let (_, body) =
+        helpers::post_json(&app, &user_token, "/api/auth/permissions/check", check).await;
+    assert_eq!(
+        body["results"][project.to_string()]["deploy:promote"],
+        false
+    );
+}
+
+/// Break-glass is limited to configured permissions on projects the caller can read.
+#[sqlx::test(migrations = "./migrations")]
+async fn break_glass_rejects_ineligible_requests(pool: PgPool) {
+    let (state, admin_token) = helpers::test_state(pool.clone()).await;
+    let app = helpers::test_router(state);
+
+    let project = helpers::create_project(&app, &admin_token, "bg-deny", "private").await;
+    let (_, user_token) =
+        helpers::create_user(&app, &admin_token, "outsider", "outsider@test.com").await;
+
+    // Not a member of the project
+    let (status, _) = helpers::post_json(
+        &app,
+        &user_token,
+        "/api/break-glass",
+        serde_json::json!({
+            "permission": "deploy:promote",
+            "project_id": project,
+            "reason": "incident",
+        }),
+    )
+    .await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+
+    // Permission not on the break-glass allowlist
+    let (status, _) = helpers::post_json(
+        &app,
+        &user_token,
+        "/api/break-glass",
+        serde_json::json!({
+            "permission": "admin:users",
+            "project_id": project,
+            "reason": "incident",
+        }),
+    )
+    .await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+
+    // Reason is required
+    let (status, _) = helpers::post_json(
+        &app,
+        &user_token,
+        "/api/break-glass",
+        serde_json::json!({
+            "permission": "deploy:promote",
+            "project_id": project,
+            "reason": "  ",
+        }),
+    )
+    .await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+}
+
+/// The reaper revokes grants whose window has passed.
+#[sqlx::test(migrations = "./migrations")]
+async fn break_glass_expires(pool: PgPool) {
+    let (state, admin_token) = helpers::test_state(pool.clone()).await;
+    let valkey = state.valkey.clone();
+    let app = helpers::test_router(state);
+
+    let project = helpers::create_project(&app, &admin_token, "bg-exp", "private").await;
+    let (user_id, user_token) =
+        helpers::create_user(&app, &admin_token, "expiring", "expiring@test.com").await;
+    helpers::assign_role(&app, &admin_token, user_id, "viewer", Some(project), &pool).await;
+
+    let (status, grant) = helpers::post_json(
+        &app,
+        &user_token,
+        "/api/break-glass",
+        serde_json::json!({
+            "permission": "deploy:promote",
+            "project_id": project,
+            "reason": "incident",
+        }),
+    )
+    .await;
+    assert_eq!(status, StatusCode::CREATED);
+    let grant_id: uuid::Uuid = grant["id"].as_str().unwrap().parse().unwrap();
+
+    sqlx::query("UPDATE delegations SET expires_at = now() - interval '1 second' WHERE id = $1")
+        .bind(grant_id)
+        .execute(&pool)
+        .await
+        .unwrap();
+
+    let expired = platform::rbac::delegation::expire_break_glass(&pool, &valkey)
+        .await
+        .unwrap();
+    assert_eq!(expired.len(), 1);
+    assert_eq!(expired[0].id, grant_id);
+
+    let revoked: bool =
+        sqlx::query_scalar("SELECT revoked_at IS NOT NULL FROM delegations WHERE id = $1")
+            .bind(grant_id)
+            .fetch_one(&pool)
+            .await
+            .unwrap();
+    assert!(revoked);
+
+    // Expired grants can be re-requested
+    let (status, _) = helpers::post_json(
+        &app,
+        &user_token,
+        "/api/break-glass",
+        serde_json::json!({
+            "permission": "deploy:promote",
+            "project_id": project,
+            "reason": "still broken",
+        }),
+    )
+    .await;
+    assert_eq!(status, StatusCode::CREATED);
+}
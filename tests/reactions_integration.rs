@@ -0,0 +1,178 @@
+// Copyright (c) 2026 Steven Hooker. Exclusively licensed to and distributed by AgentSphere GmbH.
+// SPDX-License-Identifier: BUSL-1.1
+
+//! Integration tests for emoji reactions on issues, merge requests and comments.
+
+mod helpers;
+
+use axum::http::StatusCode;
+use serde_json::{Value, json};
+use sqlx::PgPool;
+
+use helpers::{
+    admin_user_id, create_project, create_user, delete_json, get_json, insert_mr, post_json,
+    test_router, test_state,
+};
+
+/// 👍, percent-encoded for the DELETE path.
+const THUMBS_UP_PATH: &str = "%F0%9F%91%8D";
+
+fn counts(reactions: &Value) -> Vec<(String, i64, bool)> {
+    reactions
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|r| {
+            (
+                r["emoji"].as_str().unwrap().to_owned(),
+                r["count"].as_i64().unwrap(),
+                r["reacted"].as_bool().unwrap(),
+            )
+        })
+        .collect()
+}
+
+#[sqlx::test(migrations = "./migrations")]
+async fn comment_reactions_aggregate_per_emoji(pool: PgPool) {
+    let (state, admin_token) = test_state(pool.clone()).await;
+    let app = test_router(state);
+    let project_id = create_project(&app, &admin_token, "reactions", "public").await;
+    let (_, bob_token) = create_user(&app, &admin_token, "bob", "bob@example.com").await;
+
+    let issues = format!("/api/projects/{project_id}/issues");
+    let (status, _) = post_json(&app, &admin_token, &issues, json!({"title": "Flaky test"})).await;
+    assert_eq!(status, StatusCode::CREATED);
+    let (status, comment) = post_json(
+        &app,
+        &admin_token,
+        &format!("{issues}/1/comments"),
+        json!({"body": "Seeing this on main too"}),
+    )
+    .await;
+    assert_eq!(status, StatusCode::CREATED, "{comment}");
+    assert_eq!(comment["reactions"], json!([]));
+    let comment_url = format!("{issues}/1/comments/{}", comment["id"].as_str().unwrap());
+    let reactions_url = format!("{comment_url}/reactions");
+
+    let (status, body) =
+        post_json(&app, &admin_token, &reactions_url, json!({"emoji": "👍"})).await;
+    assert_eq!(status, StatusCode::CREATED, "{body}");
+    assert_eq!(counts(&body), vec![("👍".into(), 1, true)]);
+
+    // The same emoji twice from one user is a no-op
+    let (status, body) =
+        post_json(&app, &admin_token, &reactions_url, json!({"emoji": "👍"})).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(counts(&body), vec![("👍".into(), 1, true)]);
+
+    let (status, _) = post_json(&app, &bob_token, &reactions_url, json!({"emoji": "👍"})).await;
+    assert_eq!(status, StatusCode::CREATED);
+    let (status, _) = post_json(&app, &bob_token, &reactions_url, json!({"emoji": "🎉"})).await;
+    assert_eq!(status, StatusCode::CREATED);
+
+    // Counts show up on the comment itself, from the viewer's perspective
+    let (_, comment) = get_json(&app, &admin_token, &comment_url).await;
+    assert_eq!(
+        counts(&comment["reactions"]),
+        vec![("👍".into(), 2, true), ("🎉".into(), 1, false)]
+    );
+    let (_, list) = get_json(&app, &bob_token, &format!("{issues}/1/comments")).await;
+    assert_eq!(
+        counts(&list["items"][0]["reactions"]),
+        vec![("👍".into(), 2, true), ("🎉".into(), 1, true)]
+    );
+
+    // Removing decrements; removing again is not found
+    let remove_url = format!("{reactions_url}/{THUMBS_UP_PATH}");
+    let (status, body) = delete_json(&app, &admin_token, &remove_url).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(
+        counts(&body),
+        vec![("👍".into(), 1, false), ("🎉".into(), 1, false)]
+    );
+    let (status, _) = delete_json(&app, &admin_token, &remove_url).await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+
+    // Deleting the comment drops its reactions
+    let (status, _) = delete_json(&app, &admin_token, &comment_url).await;
+    assert_eq!(status, StatusCode::NO_CONTENT);
+    let left: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM reactions")
+        .fetch_one(&pool)
+        .await
+        .unwrap();
+    assert_eq!(left, 0);
+}
+
+#[sqlx::test(migrations = "./migrations")]
+async fn issue_and_mr_reactions(pool: PgPool) {
+    let (state, admin_token) = test_state(pool.clone()).await;
+    let app = test_router(state);
+    let project_id = create_project(&app, &admin_token, "reactions", "private").await;
+    let admin_id = admin_user_id(&pool).await;
+    insert_mr(&pool, project_id, admin_id, "feature", "main", 1).await;
+
+    let issues = format!("/api/projects/{project_id}/issues");
+    post_json(&app, &admin_token, &issues, json!({"title": "Dark mode"})).await;
+    let (status, _) = post_json(
+        &app,
+        &admin_token,
+        &format!("{issues}/1/reactions"),
+        json!({"emoji": "❤️"}),
+    )
+    .await;
+    assert_eq!(status, StatusCode::CREATED);
+    let (_, list) = get_json(&app, &admin_token, &issues).await;
+    assert_eq!(
+        counts(&list["items"][0]["reactions"]),
+        vec![("❤️".into(), 1, true)]
+    );
+
+    let mr = format!("/api/projects/{project_id}/merge-requests/1");
+    let (status, _) = post_json(
+        &app,
+        &admin_token,
+        &format!("{mr}/reactions"),
+        json!({"emoji": "🚀"}),
+    )
+    .await;
+    assert_eq!(status, StatusCode::CREATED);
+    let (_, body) = get_json(&app, &admin_token, &mr).await;
+    assert_eq!(counts(&body["reactions"]), vec![("🚀".into(), 1, true)]);
+
+    // Text is not an emoji; unknown targets are not found
+    let (status, _) = post_json(
+        &app,
+        &admin_token,
+        &format!("{mr}/reactions"),
+        json!({"emoji": ":+1:"}),
+    )
+    .await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    let (status, _) = post_json(
+        &app,
+        &admin_token,
+        &format!("/api/projects/{project_id}/merge-requests/9/reactions"),
+        json!({"emoji": "👍"}),
+    )
+    .await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+}
+
+#[sqlx::test(migrations = "./migrations")]
+async fn reactions_require_project_access(pool: PgPool) {
+    let (state, admin_token) = test_state(pool.clone()).await;
+    let app = test_router(state);
+    let project_id = create_project(&app, &admin_token, "secret", "private").await;
+    let (_, eve_token) = create_user(&app, &admin_token, "eve", "eve@example.com").await;
+
+    let issues = format!("/api/projects/{project_id}/issues");
+    post_json(&app, &admin_token, &issues, json!({"title": "Internal"})).await;
+    let (status, _) = post_json(
+        &app,
+        &eve_token,
+        &format!("{issues}/1/reactions"),
+        json!({"emoji": "👍"}),
+    )
+    .await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+}
@@ -0,0 +1,148 @@
+// Copyright (c) 2026 Steven Hooker. Exclusively licensed to and distributed by AgentSphere GmbH.
+// SPDX-License-Identifier: BUSL-1.1
+
+//! Integration tests for issue and merge request full-text search.
+
+mod helpers;
+
+use axum::http::StatusCode;
+use serde_json::Value;
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use helpers::{
+    admin_user_id, create_project, create_user, get_json, insert_mr, post_json, test_router,
+    test_state,
+};
+
+async fn create_issue(app: &axum::Router, token: &str, project_id: Uuid, title: &str, body: &str) {
+    let (status, resp) = post_json(
+        app,
+        token,
+        &format!("/api/projects/{project_id}/issues"),
+        serde_json::json!({ "title": title, "body": body }),
+    )
+    .await;
+    assert_eq!(status, StatusCode::CREATED, "{resp}");
+}
+
+fn titles(body: &Value) -> Vec<&str> {
+    body["items"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|i| i["title"].as_str().unwrap())
+        .collect()
+}
+
+#[sqlx::test(migrations = "./migrations")]
+async fn project_search_ranks_issues_and_mrs(pool: PgPool) {
+    let (state, admin_token) = test_state(pool.clone()).await;
+    let app = test_router(state);
+    let project_id = create_project(&app, &admin_token, "search", "private").await;
+
+    create_issue(
+        &app,
+        &admin_token,
+        project_id,
+        "Login page is slow",
+        "Sometimes the request hits a timeout after 30s.",
+    )
+    .await;
+    create_issue(
+        &app,
+        &admin_token,
+        project_id,
+        "Gateway timeout on deploy",
+        "Deploys fail with a 504 timeout.",
+    )
+    .await;
+    create_issue(
+        &app,
+        &admin_token,
+        project_id,
+        "Dark mode",
+        "Add a theme toggle.",
+    )
+    .await;
+
+    let mr_id = insert_mr(
+        &pool,
+        project_id,
+        admin_user_id(&pool).await,
+        "fix",
+        "main",
+        1,
+    )
+    .await;
+    sqlx::query("UPDATE merge_requests SET title = 'Raise upstream timeouts' WHERE id = $1")
+        .bind(mr_id)
+        .execute(&pool)
+        .await
+        .unwrap();
+
+    let (status, body) = get_json(
+        &app,
+        &admin_token,
+        &format!("/api/projects/{project_id}/search?q=timeout"),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK, "{body}");
+    assert_eq!(body["total"], 3, "{body}");
+    // Title and body hits outrank a body-only hit
+    assert_eq!(titles(&body)[0], "Gateway timeout on deploy");
+    assert_eq!(titles(&body)[2], "Login page is slow");
+    let mr = body["items"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .find(|i| i["kind"] == "mr")
+        .expect("MR should match on its title");
+    assert_eq!(mr["number"], 1);
+    assert_eq!(mr["project_name"], "search");
+    let snippet = body["items"][0]["snippet"].as_str().unwrap();
+    assert!(snippet.contains("**timeout**"), "{snippet}");
+
+    let (_, body) = get_json(
+        &app,
+        &admin_token,
+        &format!("/api/projects/{project_id}/search?q=timeout&type=mr"),
+    )
+    .await;
+    assert_eq!(titles(&body), vec!["Raise upstream timeouts"]);
+
+    let (status, _) = get_json(
+        &app,
+        &admin_token,
+        &format!("/api/projects/{project_id}/search?q=timeout&type=wiki"),
+    )
+    .await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+}
+
+#[sqlx::test(migrations = "./migrations")]
+async fn global_search_only_covers_readable_projects(pool: PgPool) {
+    let (state, admin_token) = test_state(pool.clone()).await;
+    let app = test_router(state);
+    let (_, user_token) = create_user(&app, &admin_token, "searcher", "searcher@example.com").await;
+
+    let public = create_project(&app, &admin_token, "search-public", "public").await;
+    let private = create_project(&app, &admin_token, "search-private", "private").await;
+    create_issue(&app, &admin_token, public, "Public timeout", "").await;
+    create_issue(&app, &admin_token, private, "Private timeout", "").await;
+
+    let (status, body) = get_json(&app, &user_token, "/api/search?q=timeout").await;
+    assert_eq!(status, StatusCode::OK, "{body}");
+    assert_eq!(titles(&body), vec!["Public timeout"]);
+
+    let (_, body) = get_json(&app, &admin_token, "/api/search?q=timeout").await;
+    assert_eq!(body["total"], 2);
+
+    let (status, _) = get_json(
+        &app,
+        &user_token,
+        &format!("/api/projects/{private}/search?q=timeout"),
+    )
+    .await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+}
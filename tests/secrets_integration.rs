@@ -3,6 +3,8 @@
 
 //! Integration tests for secrets and user provider keys APIs.
 
+#![recursion_limit = "256"]
+
 mod helpers;
 
 use axum::http::StatusCode;
@@ -55,6 +57,53 @@ async fn create_and_list_project_secrets(pool: PgPool) {
     assert_eq!(secrets[0]["name"], "DB_PASSWORD");
 }
 
+/// Listing secrets is audited as `secrets.read` only once read auditing is
+/// switched on for secrets.
+#[sqlx::test(migrations = "./migrations")]
+async fn list_project_secrets_audits_read_when_enabled(pool: PgPool) {
+    let (state, admin_token) = test_state(pool.clone()).await;
+    let app = test_router(state.clone());
+    let proj_id = create_project(&app, &admin_token, "sec-audit-reads", "private").await;
+    let path = format!("/api/projects/{proj_id}/secrets");
+
+    let (status, _) = helpers::get_json(&app, &admin_token, &path).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(helpers::wait_for_audit(&pool, "secrets.read", 300).await, 0);
+
+    let mut state = state;
+    let mut config = (*state.config).clone();
+    config.audit_reads = vec!["secrets".into()];
+    state.config = std::sync::Arc::new(config);
+    let app = test_router(state);
+
+    let (status, _) = helpers::get_json(&app, &admin_token, &path).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(
+        helpers::wait_for_audit(&pool, "secrets.read", 2000).await,
+        1
+    );
+    let row = sqlx::query(
+        "SELECT actor_name, resource, project_id FROM audit_log WHERE action = 'secrets.read'",
+    )
+    .fetch_one(&pool)
+    .await
+    .unwrap();
+    assert_eq!(row.get::<String, _>("actor_name"), "admin");
+    assert_eq!(row.get::<String, _>("resource"), "secret");
+    assert_eq!(
+        row.get::<Option<uuid::Uuid>, _>("project_id"),
+        Some(proj_id)
+    );
+
+    // The audit log is not covered by the secrets flag
+    let (status, _) = helpers::get_json(&app, &admin_token, "/api/audit-log").await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(
+        helpers::wait_for_audit(&pool, "audit_log.read", 300).await,
+        0
+    );
+}
+
 /// Delete project secret.
 #[sqlx::test(migrations = "./migrations")]
 async fn delete_project_secret(pool: PgPool) {
@@ -1792,3 +1841,329 @@ async fn secret_request_description_too_long(pool: PgPool) {
     .await;
     assert_eq!(status, StatusCode::BAD_REQUEST);
 }
+
+/// Rotating the master key: rewrap moves secrets to the new version, after
+/// which the old version can be dropped from the ring.
+#[sqlx::test(migrations = "./migrations")]
+async fn master_key_rotation_rewraps_secrets(pool: PgPool) {
+    let (state, admin_token) = test_state(pool.clone()).await;
+    let old_key = state.config.master_key.clone().unwrap();
+    let new_key = "fe".repeat(32);
+    let with_config = |master_key: String| {
+        let mut state = state.clone();
+        let mut config = (*state.config).clone();
+        config.master_key = Some(master_key);
+        state.config = std::sync::Arc::new(config);
+        test_router(state)
+    };
+
+    let app = with_config(old_key.clone());
+    let proj_id = create_project(&app, &admin_token, "sec-rotate", "private").await;
+    let (status, body) = helpers::post_json(
+        &app,
+        &admin_token,
+        &format!("/api/projects/{proj_id}/secrets"),
+        serde_json::json!({ "name": "ROTATED", "value": "before-rotation", "scope": "agent" }),
+    )
+    .await;
+    assert_eq!(status, StatusCode::CREATED, "create secret failed: {body}");
+
+    // Add version 2; old data stays readable while both versions are loaded
+    let app = with_config(format!("2:{new_key},1:{old_key}"));
+    let secret_path = format!("/api/projects/{proj_id}/secrets/ROTATED");
+    let (status, body) = helpers::get_json(&app, &admin_token, &secret_path).await;
+    assert_eq!(
+        status,
+        StatusCode::OK,
+        "read during rotation failed: {body}"
+    );
+    assert_eq!(body["value"], "before-rotation");
+
+    let (status, body) = helpers::post_json(
+        &app,
+        &admin_token,
+        "/api/admin/secrets/rewrap",
+        serde_json::json!({}),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK, "rewrap failed: {body}");
+    assert_eq!(body["key_version"], 2);
+    assert!(body["rewrapped"].as_u64().unwrap() >= 1, "body: {body}");
+    assert_eq!(body["failed"], 0);
+
+    // Re-running is a no-op
+    let (_, body) = helpers::post_json(
+        &app,
+        &admin_token,
+        "/api/admin/secrets/rewrap",
+        serde_json::json!({}),
+    )
+    .await;
+    assert_eq!(body["rewrapped"], 0);
+
+    // Retire version 1
+    let app = with_config(format!("2:{new_key}"));
+    let (status, body) = helpers::get_json(&app, &admin_token, &secret_path).await;
+    assert_eq!(
+        status,
+        StatusCode::OK,
+        "read after retiring old key failed: {body}"
+    );
+    assert_eq!(body["value"], "before-rotation");
+}
+
+/// Only admins can trigger a rewrap.
+#[sqlx::test(migrations = "./migrations")]
+async fn non_admin_cannot_rewrap_secrets(pool: PgPool) {
+    let (state, admin_token) = test_state(pool.clone()).await;
+    let app = test_router(state);
+
+    let (_, user_token) = create_user(&app, &admin_token, "rewrap-user", "rewrap@test.com").await;
+    let (status, _) = helpers::post_json(
+        &app,
+        &user_token,
+        "/api/admin/secrets/rewrap",
+        serde_json::json!({}),
+    )
+    .await;
+    assert_eq!(status, StatusCode::FORBIDDEN);
+}
+
+// ---------------------------------------------------------------------------
+// Secret access log
+// ---------------------------------------------------------------------------
+
+/// API reads and pipeline injections both show up in the secret's access log,
+/// without the value.
+#[sqlx::test(migrations = "./migrations")]
+async fn secret_access_log_records_reads_and_pipeline_use(pool: PgPool) {
+    let (state, admin_token) = test_state(pool.clone()).await;
+    let audit = state.audit_tx.clone();
+    let app = test_router(state);
+
+    let proj_id = create_project(&app, &admin_token, "sec-access-log", "private").await;
+    let (status, _) = helpers::post_json(
+        &app,
+        &admin_token,
+        &format!("/api/projects/{proj_id}/secrets"),
+        serde_json::json!({ "name": "DB_PASSWORD", "value": "hunter2-value", "scope": "all" }),
+    )
+    .await;
+    assert_eq!(status, StatusCode::CREATED);
+
+    let (status, _) = helpers::get_json(
+        &app,
+        &admin_token,
+        &format!("/api/projects/{proj_id}/secrets/DB_PASSWORD"),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+
+    let pipeline_id = uuid::Uuid::new_v4();
+    platform::secrets::access::record_access(
+        &pool,
+        &audit,
+        proj_id,
+        None,
+        ["DB_PASSWORD"],
+        platform::secrets::access::AccessPurpose::PipelineStep {
+            pipeline_id,
+            step: "integration-tests",
+        },
+    )
+    .await;
+    helpers::wait_for_audit(&pool, "secret.access", 2000).await;
+
+    let (status, body) = helpers::get_json(
+        &app,
+        &admin_token,
+        &format!("/api/projects/{proj_id}/secrets/DB_PASSWORD/access-log"),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK, "access log failed: {body}");
+    assert_eq!(body["total"], 2, "body: {body}");
+    let items = body["items"].as_array().unwrap();
+    let pipeline_entry = items
+        .iter()
+        .find(|e| e["action"] == "secret.access")
+        .expect("pipeline access entry");
+    assert_eq!(pipeline_entry["detail"]["purpose"], "pipeline");
+    assert_eq!(
+        pipeline_entry["detail"]["pipeline_id"],
+        pipeline_id.to_string()
+    );
+    assert_eq!(pipeline_entry["detail"]["step"], "integration-tests");
+    let api_entry = items
+        .iter()
+        .find(|e| e["action"] == "secret.read")
+        .expect("api read entry");
+    assert_eq!(api_entry["detail"]["purpose"], "api");
+    assert!(
+        !body.to_string().contains("hunter2-value"),
+        "secret value leaked into audit log: {body}"
+    );
+
+    // Other secrets' logs stay empty
+    let (_, body) = helpers::get_json(
+        &app,
+        &admin_token,
+        &format!("/api/projects/{proj_id}/secrets/OTHER/access-log"),
+    )
+    .await;
+    assert_eq!(body["total"], 0);
+}
+
+/// The access log requires `secret:write`.
+#[sqlx::test(migrations = "./migrations")]
+async fn secret_access_log_requires_write(pool: PgPool) {
+    let (state, admin_token) = test_state(pool.clone()).await;
+    let app = test_router(state);
+
+    let proj_id = create_project(&app, &admin_token, "sec-access-perm", "private").await;
+    let (user_id, token) = create_user(&app, &admin_token, "secaccess", "secaccess@test.com").await;
+    assign_role(&app, &admin_token, user_id, "viewer", Some(proj_id), &pool).await;
+
+    let (status, _) = helpers::get_json(
+        &app,
+        &token,
+        &format!("/api/projects/{proj_id}/secrets/DB_PASSWORD/access-log"),
+    )
+    .await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+}
+
+// ---------------------------------------------------------------------------
+// Vault-backed secrets
+// ---------------------------------------------------------------------------
+
+/// A Vault-backed secret stores only its reference; with no Vault configured
+/// it cannot be resolved.
+#[sqlx::test(migrations = "./migrations")]
+async fn vault_secret_stores_reference_only(pool: PgPool) {
+    let (state, admin_token) = test_state(pool.clone()).await;
+    let master_key =
+        platform::secrets::engine::parse_master_key(state.config.master_key.as_deref().unwrap())
+            .unwrap();
+    let app = test_router(state);
+
+    let proj_id = create_project(&app, &admin_token, "sec-vault1", "private").await;
+
+    let (status, body) = helpers::post_json(
+        &app,
+        &admin_token,
+        &format!("/api/projects/{proj_id}/secrets"),
+        serde_json::json!({
+            "name": "DB_PASSWORD",
+            "value": "vault://secret/data/myapp#db_password",
+            "scope": "pipeline",
+            "backend": "vault",
+        }),
+    )
+    .await;
+    assert_eq!(
+        status,
+        StatusCode::CREATED,
+        "create vault secret failed: {body}"
+    );
+    assert_eq!(body["backend"], "vault");
+
+    let (_, body) = helpers::get_json(
+        &app,
+        &admin_token,
+        &format!("/api/projects/{proj_id}/secrets"),
+    )
+    .await;
+    assert_eq!(body["items"][0]["backend"], "vault");
+
+    let row = sqlx::query("SELECT encrypted_value FROM secrets WHERE name = 'DB_PASSWORD'")
+        .fetch_one(&pool)
+        .await
+        .unwrap();
+    let stored = master_key
+        .decrypt(&row.get::<Vec<u8>, _>("encrypted_value"))
+        .unwrap();
+    assert_eq!(stored, b"vault://secret/data/myapp#db_password");
+
+    // Vault is not configured in tests, so the reference cannot be resolved.
+    let (status, _) = helpers::get_json(
+        &app,
+        &admin_token,
+        &format!("/api/projects/{proj_id}/secrets/DB_PASSWORD?scope=pipeline"),
+    )
+    .await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+
+    // Pipeline injection skips it rather than leaking the reference.
+    let secrets = platform::secrets::engine::query_scoped_secrets(
+        &pool,
+        &master_key,
+        proj_id,
+        &["pipeline", "all"],
+        None,
+    )
+    .await
+    .unwrap();
+    assert!(secrets.is_empty());
+}
+
+/// Vault references are read with the platform's token, so only platform
+/// admins may create them; project writers keep local secrets.
+#[sqlx::test(migrations = "./migrations")]
+async fn vault_secret_requires_platform_admin(pool: PgPool) {
+    let (state, admin_token) = test_state(pool.clone()).await;
+    let app = test_router(state);
+
+    let (user_id, user_token) =
+        create_user(&app, &admin_token, "vault-dev", "vault-dev@test.com").await;
+    assign_role(&app, &admin_token, user_id, "developer", None, &pool).await;
+    let proj_id = create_project(&app, &user_token, "sec-vault3", "private").await;
+
+    let secret = |value: &str, backend: &str| serde_json::json!({"name": "DB_PASSWORD", "value": value, "scope": "all", "backend": backend});
+    let url = format!("/api/projects/{proj_id}/secrets");
+    let (status, _) = helpers::post_json(
+        &app,
+        &user_token,
+        &url,
+        secret("vault://sys/other-team/creds#password", "vault"),
+    )
+    .await;
+    assert_eq!(status, StatusCode::FORBIDDEN);
+
+    let (status, body) =
+        helpers::post_json(&app, &user_token, &url, secret("hunter2", "local")).await;
+    assert_eq!(status, StatusCode::CREATED, "{body}");
+}
+
+/// Malformed references and unknown backends are rejected.
+#[sqlx::test(migrations = "./migrations")]
+async fn vault_secret_validation(pool: PgPool) {
+    let (state, admin_token) = test_state(pool).await;
+    let app = test_router(state);
+
+    let proj_id = create_project(&app, &admin_token, "sec-vault2", "private").await;
+
+    for (value, backend) in [
+        ("plain-value", "vault"),
+        ("vault://secret/data/myapp", "vault"),
+        ("vault://secret/../sys#key", "vault"),
+        ("vault://secret/data/myapp#key", "consul"),
+    ] {
+        let (status, body) = helpers::post_json(
+            &app,
+            &admin_token,
+            &format!("/api/projects/{proj_id}/secrets"),
+            serde_json::json!({
+                "name": "BAD_REF",
+                "value": value,
+                "scope": "all",
+                "backend": backend,
+            }),
+        )
+        .await;
+        assert_eq!(
+            status,
+            StatusCode::BAD_REQUEST,
+            "{backend} {value} should be rejected: {body}"
+        );
+    }
+}
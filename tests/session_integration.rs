@@ -657,6 +657,31 @@ async fn send_message_project_scoped_empty_content(pool: PgPool) {
     assert_eq!(status, StatusCode::BAD_REQUEST);
 }
 
+#[sqlx::test(migrations = "./migrations")]
+async fn send_message_archived_project_locked(pool: PgPool) {
+    let (state, admin_token) = test_state(pool.clone()).await;
+    let app = test_router(state);
+    let admin_id = get_admin_id(&app, &admin_token).await;
+
+    let project_id = create_project(&app, &admin_token, "msg-archived", "private").await;
+    let session_id = insert_session(&pool, project_id, admin_id, "archived", "running").await;
+    sqlx::query("UPDATE projects SET archived_at = now() WHERE id = $1")
+        .bind(project_id)
+        .execute(&pool)
+        .await
+        .unwrap();
+
+    // Even the session owner can't drive a session in an archived project
+    let (status, _) = helpers::post_json(
+        &app,
+        &admin_token,
+        &format!("/api/projects/{project_id}/sessions/{session_id}/message"),
+        serde_json::json!({ "content": "still there?" }),
+    )
+    .await;
+    assert_eq!(status, StatusCode::LOCKED);
+}
+
 // ---------------------------------------------------------------------------
 // Validate provider config edge cases (unit test)
 // ---------------------------------------------------------------------------
@@ -1641,6 +1666,114 @@ async fn reap_idle_sessions_marks_completed(pool: PgPool) {
     );
 }
 
+/// Idleness is measured from `last_activity_at`, not creation: a session inside
+/// its lifetime is reaped once quiet, while a recently active one is kept.
+#[sqlx::test(migrations = "./migrations")]
+async fn reap_idle_sessions_uses_last_activity(pool: PgPool) {
+    let (state, admin_token) = test_state(pool.clone()).await;
+    let app = test_router(state.clone());
+    let admin_id = get_admin_id(&app, &admin_token).await;
+    let project_id = create_project(&app, &admin_token, "reap-activity", "private").await;
+
+    let quiet = insert_session(&pool, project_id, admin_id, "quiet", "running").await;
+    let busy = insert_session(&pool, project_id, admin_id, "busy", "running").await;
+    sqlx::query(
+        "UPDATE agent_sessions SET created_at = NOW() - INTERVAL '40 minutes',
+                last_activity_at = CASE WHEN id = $1 THEN NOW() - INTERVAL '35 minutes'
+                                        ELSE NOW() - INTERVAL '1 minute' END
+         WHERE id IN ($1, $2)",
+    )
+    .bind(quiet)
+    .bind(busy)
+    .execute(&pool)
+    .await
+    .unwrap();
+
+    platform::agent::service::run_reaper_once(&state).await;
+
+    let status = |id: Uuid| {
+        let pool = pool.clone();
+        async move {
+            sqlx::query_scalar::<_, String>("SELECT status FROM agent_sessions WHERE id = $1")
+                .bind(id)
+                .fetch_one(&pool)
+                .await
+                .unwrap()
+        }
+    };
+    assert_eq!(status(quiet).await, "completed");
+    assert_eq!(status(busy).await, "running");
+}
+
+/// The absolute lifetime applies even to sessions that are still active, but
+/// not to manager sessions.
+#[sqlx::test(migrations = "./migrations")]
+async fn reap_idle_sessions_enforces_max_lifetime(pool: PgPool) {
+    let (state, admin_token) = test_state(pool.clone()).await;
+    let app = test_router(state.clone());
+    let admin_id = get_admin_id(&app, &admin_token).await;
+    let project_id = create_project(&app, &admin_token, "reap-lifetime", "private").await;
+
+    let session_id = insert_session(&pool, project_id, admin_id, "old", "running").await;
+    let manager_id = insert_session(&pool, project_id, admin_id, "manager", "running").await;
+    sqlx::query(
+        "UPDATE agent_sessions SET created_at = NOW() - INTERVAL '3 hours', last_activity_at = NOW(),
+                execution_mode = CASE WHEN id = $2 THEN 'manager' ELSE execution_mode END
+         WHERE id IN ($1, $2)",
+    )
+    .bind(session_id)
+    .bind(manager_id)
+    .execute(&pool)
+    .await
+    .unwrap();
+
+    platform::agent::service::run_reaper_once(&state).await;
+
+    let status = |id: Uuid| {
+        let pool = pool.clone();
+        async move {
+            sqlx::query_scalar::<_, String>("SELECT status FROM agent_sessions WHERE id = $1")
+                .bind(id)
+                .fetch_one(&pool)
+                .await
+                .unwrap()
+        }
+    };
+    assert_eq!(status(session_id).await, "completed");
+    assert_eq!(status(manager_id).await, "running");
+}
+
+/// A lifetime of 0 turns the cap off instead of reaping every session.
+#[sqlx::test(migrations = "./migrations")]
+async fn reap_idle_sessions_zero_lifetime_disables_cap(pool: PgPool) {
+    let (mut state, admin_token) = test_state(pool.clone()).await;
+    let app = test_router(state.clone());
+    let admin_id = get_admin_id(&app, &admin_token).await;
+    let project_id = create_project(&app, &admin_token, "reap-no-cap", "private").await;
+    let mut config = (*state.config).clone();
+    config.session_max_lifetime_secs = 0;
+    state.config = std::sync::Arc::new(config);
+
+    let session_id = insert_session(&pool, project_id, admin_id, "old", "running").await;
+    sqlx::query(
+        "UPDATE agent_sessions SET created_at = NOW() - INTERVAL '3 hours', last_activity_at = NOW()
+         WHERE id = $1",
+    )
+    .bind(session_id)
+    .execute(&pool)
+    .await
+    .unwrap();
+
+    platform::agent::service::run_reaper_once(&state).await;
+
+    let status: String = sqlx::query_scalar("SELECT status FROM agent_sessions WHERE id = $1")
+        .bind(session_id)
+        .fetch_one(&pool)
+        .await
+        .unwrap();
+    assert_eq!(status, "running");
+}
+
 // ---------------------------------------------------------------------------
 // Phase 3: Resolve session namespace
 // ---------------------------------------------------------------------------
@@ -5106,3 +5239,225 @@ async fn sse_session_events_different_project_returns_404(pool: PgPool) {
     .await;
     assert_eq!(status, StatusCode::NOT_FOUND);
 }
+
+// ---------------------------------------------------------------------------
+// Session quotas
+// ---------------------------------------------------------------------------
+
+#[sqlx::test(migrations = "./migrations")]
+async fn create_session_rejected_at_user_quota(pool: PgPool) {
+    let (mut state, admin_token) = test_state(pool.clone()).await;
+    let mut config = (*state.config).clone();
+    config.agent_session_max_per_user = 2;
+    state.config = std::sync::Arc::new(config);
+    let app = test_router(state);
+    let admin_id = get_admin_id(&app, &admin_token).await;
+    let project_id = create_project(&app, &admin_token, "sess-quota-user", "private").await;
+
+    let first = insert_session(&pool, project_id, admin_id, "one", "running").await;
+    insert_session(&pool, project_id, admin_id, "two", "pending").await;
+    // Finished sessions do not count
+    insert_session(&pool, project_id, admin_id, "old", "completed").await;
+
+    let (status, body) = helpers::post_json(
+        &app,
+        &admin_token,
+        &format!("/api/projects/{project_id}/sessions"),
+        serde_json::json!({ "prompt": "three" }),
+    )
+    .await;
+    assert_eq!(status, StatusCode::TOO_MANY_REQUESTS);
+    assert!(
+        body["error"].as_str().unwrap().contains("max 2 per user"),
+        "unexpected body: {body}"
+    );
+
+    // Ending a session frees its slot
+    sqlx::query("UPDATE agent_sessions SET status = 'completed' WHERE id = $1")
+        .bind(first)
+        .execute(&pool)
+        .await
+        .unwrap();
+    let (status, _) = helpers::post_json(
+        &app,
+        &admin_token,
+        &format!("/api/projects/{project_id}/sessions"),
+        serde_json::json!({ "prompt": "three" }),
+    )
+    .await;
+    assert_eq!(status, StatusCode::CREATED);
+}
+
+#[sqlx::test(migrations = "./migrations")]
+async fn create_session_rejected_at_project_quota(pool: PgPool) {
+    let (mut state, admin_token) = test_state(pool.clone()).await;
+    let mut config = (*state.config).clone();
+    config.agent_session_max_per_project = 1;
+    state.config = std::sync::Arc::new(config);
+    let app = test_router(state);
+    let project_id = create_project(&app, &admin_token, "sess-quota-proj", "private").await;
+    let (other_id, _) =
+        create_user(&app, &admin_token, "quota-other", "quota-other@test.com").await;
+
+    insert_session(&pool, project_id, other_id, "theirs", "running").await;
+
+    let (status, body) = helpers::post_json(
+        &app,
+        &admin_token,
+        &format!("/api/projects/{project_id}/sessions"),
+        serde_json::json!({ "prompt": "mine" }),
+    )
+    .await;
+    assert_eq!(status, StatusCode::TOO_MANY_REQUESTS);
+    assert!(
+        body["error"]
+            .as_str()
+            .unwrap()
+            .contains("max 1 per project"),
+        "unexpected body: {body}"
+    );
+}
+
+/// A session that fails after its row is inserted gives its quota slot back
+/// right away instead of staying `pending`.
+#[sqlx::test(migrations = "./migrations")]
+async fn failed_session_start_releases_quota(pool: PgPool) {
+    let (state, admin_token) = test_state(pool.clone()).await;
+    let app = test_router(state.clone());
+    let admin_id = get_admin_id(&app, &admin_token).await;
+    let project_id = create_project(&app, &admin_token, "sess-quota-fail", "private").await;
+    // An inactive project passes the insert but fails the next lookup
+    sqlx::query("UPDATE projects SET is_active = false WHERE id = $1")
+        .bind(project_id)
+        .execute(&pool)
+        .await
+        .unwrap();
+
+    let result = platform::agent::service::create_session(
+        &state,
+        admin_id,
+        project_id,
+        "doomed",
+        "claude-code",
+        None,
+        None,
+        platform::agent::AgentRoleName::Dev,
+        None,
+    )
+    .await;
+    assert!(result.is_err());
+
+    let mut status = String::new();
+    for _ in 0..50 {
+        status = sqlx::query_scalar("SELECT status FROM agent_sessions WHERE project_id = $1")
+            .bind(project_id)
+            .fetch_one(&pool)
+            .await
+            .unwrap();
+        if status != "pending" {
+            break;
+        }
+        tokio::time::sleep(std::time::Duration::from_millis(100)).await;
+    }
+    assert_eq!(status, "failed");
+}
+
+// ---------------------------------------------------------------------------
+// Workspace snapshots
+// ---------------------------------------------------------------------------
+
+#[sqlx::test(migrations = "./migrations")]
+async fn list_snapshots_returns_stored_archives(pool: PgPool) {
+    let (state, admin_token) = test_state(pool.clone()).await;
+    let minio = state.minio.clone();
+    let app = test_router(state);
+    let admin_id = get_admin_id(&app, &admin_token).await;
+    let project_id = create_project(&app, &admin_token, "sess-snap-list", "private").await;
+    let session_id = insert_session(&pool, project_id, admin_id, "snap", "running").await;
+
+    let path = format!("/api/projects/{project_id}/sessions/{session_id}/snapshots");
+    let (status, body) = helpers::get_json(&app, &admin_token, &path).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["total"], 0);
+
+    for name in ["20261015T090000Z", "20261015T100000Z"] {
+        minio
+            .write(
+                &format!("agents/{session_id}/snapshots/{name}.tar"),
+                vec![0u8; 512],
+            )
+            .await
+            .unwrap();
+    }
+
+    let (status, body) = helpers::get_json(&app, &admin_token, &path).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["total"], 2);
+    // Newest first
+    assert_eq!(body["items"][0]["name"], "20261015T100000Z");
+    assert_eq!(body["items"][0]["size_bytes"], 512);
+}
+
+#[sqlx::test(migrations = "./migrations")]
+async fn create_session_restore_from_validates_snapshot(pool: PgPool) {
+    let (state, admin_token) = test_state(pool.clone()).await;
+    let app = test_router(state);
+    let admin_id = get_admin_id(&app, &admin_token).await;
+    let project_id = create_project(&app, &admin_token, "sess-snap-restore", "private").await;
+    let other_project = create_project(&app, &admin_token, "sess-snap-other", "private").await;
+    let source = insert_session(&pool, project_id, admin_id, "src", "completed").await;
+    let foreign = insert_session(&pool, other_project, admin_id, "src", "completed").await;
+
+    let path = format!("/api/projects/{project_id}/sessions");
+
+    let (status, _) = helpers::post_json(
+        &app,
+        &admin_token,
+        &path,
+        serde_json::json!({
+            "restore_from": { "session_id": source, "snapshot": "../../etc/passwd" }
+        }),
+    )
+    .await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+
+    let (status, body) = helpers::post_json(
+        &app,
+        &admin_token,
+        &path,
+        serde_json::json!({
+            "restore_from": { "session_id": source, "snapshot": "20261015T090000Z" }
+        }),
+    )
+    .await;
+    assert_eq!(status, StatusCode::NOT_FOUND, "unexpected body: {body}");
+
+    let (status, _) = helpers::post_json(
+        &app,
+        &admin_token,
+        &path,
+        serde_json::json!({
+            "restore_from": { "session_id": foreign, "snapshot": "20261015T090000Z" }
+        }),
+    )
+    .await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+}
+
+#[sqlx::test(migrations = "./migrations")]
+async fn create_snapshot_requires_running_session(pool: PgPool) {
+    let (state, admin_token) = test_state(pool.clone()).await;
+    let app = test_router(state);
+    let admin_id = get_admin_id(&app, &admin_token).await;
+    let project_id = create_project(&app, &admin_token, "sess-snap-stopped", "private").await;
+    let session_id = insert_session(&pool, project_id, admin_id, "done", "completed").await;
+
+    let (status, _) = helpers::post_json(
+        &app,
+        &admin_token,
+        &format!("/api/projects/{project_id}/sessions/{session_id}/snapshots"),
+        serde_json::json!({}),
+    )
+    .await;
+    assert_eq!(status, StatusCode::CONFLICT);
+}
@@ -0,0 +1,143 @@
+// Copyright (c) 2026 Steven Hooker. Exclusively licensed to and distributed by AgentSphere GmbH.
+// SPDX-License-Identifier: BUSL-1.1
+
+//! Integration tests for TOTP enrollment and the login second factor.
+
+mod helpers;
+
+use axum::http::StatusCode;
+use sqlx::PgPool;
+
+use helpers::{create_user, test_router, test_state};
+use platform::auth::totp;
+
+fn code(secret: &[u8], step: i64) -> String {
+    format!("{:06}", totp::code_at(secret, step))
+}
+
+fn now_step() -> i64 {
+    totp::step_at(chrono::Utc::now().timestamp())
+}
+
+async fn login(app: &axum::Router, totp_code: Option<&str>) -> (StatusCode, serde_json::Value) {
+    helpers::post_json(
+        app,
+        "",
+        "/api/auth/login",
+        serde_json::json!({
+            "name": "totp-user",
+            "password": "testpass123",
+            "totp_code": totp_code,
+        }),
+    )
+    .await
+}
+
+/// Enroll, confirm, then login requires a fresh valid code.
+#[sqlx::test(migrations = "./migrations")]
+async fn totp_gates_login_once_enabled(pool: PgPool) {
+    let (state, admin_token) = test_state(pool.clone()).await;
+    let app = test_router(state);
+    let (_, token) = create_user(&app, &admin_token, "totp-user", "totp@test.com").await;
+
+    let (status, body) =
+        helpers::post_json(&app, &token, "/api/auth/totp/enroll", serde_json::json!({})).await;
+    assert_eq!(status, StatusCode::CREATED, "enroll failed: {body}");
+    assert!(
+        body["provisioning_uri"]
+            .as_str()
+            .unwrap()
+            .starts_with("otpauth://totp/")
+    );
+    let secret = data_encoding::BASE32_NOPAD
+        .decode(body["secret"].as_str().unwrap().as_bytes())
+        .unwrap();
+
+    // The secret is stored encrypted, not in the clear.
+    let stored: Vec<u8> =
+        sqlx::query_scalar("SELECT encrypted_secret FROM user_totp WHERE enabled = false")
+            .fetch_one(&pool)
+            .await
+            .unwrap();
+    assert_ne!(stored, secret);
+
+    // Not enforced until confirmed.
+    let (status, _) = login(&app, None).await;
+    assert_eq!(status, StatusCode::OK);
+
+    let step = now_step();
+    let (status, body) = helpers::post_json(
+        &app,
+        &token,
+        "/api/auth/totp/verify",
+        serde_json::json!({ "code": code(&secret, step) }),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK, "verify failed: {body}");
+    assert_eq!(body["enabled"], true);
+
+    let (status, _) = login(&app, None).await;
+    assert_eq!(status, StatusCode::BAD_REQUEST, "missing code");
+
+    let (status, _) = login(&app, Some("000000")).await;
+    assert_eq!(status, StatusCode::UNAUTHORIZED, "wrong code");
+
+    // The confirmation code cannot be replayed; the next step's code is accepted.
+    let (status, _) = login(&app, Some(&code(&secret, step))).await;
+    assert_eq!(status, StatusCode::UNAUTHORIZED, "replayed code");
+
+    let (status, body) = login(&app, Some(&code(&secret, step + 1))).await;
+    assert_eq!(status, StatusCode::OK, "valid code: {body}");
+    assert!(body["token"].is_string());
+}
+
+/// Disabling requires a valid code; afterwards login needs none.
+#[sqlx::test(migrations = "./migrations")]
+async fn totp_disable_requires_code(pool: PgPool) {
+    let (state, admin_token) = test_state(pool).await;
+    let app = test_router(state);
+    let (_, token) = create_user(&app, &admin_token, "totp-user", "totp@test.com").await;
+
+    let (_, body) =
+        helpers::post_json(&app, &token, "/api/auth/totp/enroll", serde_json::json!({})).await;
+    let secret = data_encoding::BASE32_NOPAD
+        .decode(body["secret"].as_str().unwrap().as_bytes())
+        .unwrap();
+    let step = now_step();
+    let (status, _) = helpers::post_json(
+        &app,
+        &token,
+        "/api/auth/totp/verify",
+        serde_json::json!({ "code": code(&secret, step) }),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+
+    // Re-enrolling over an enabled factor is refused.
+    let (status, _) =
+        helpers::post_json(&app, &token, "/api/auth/totp/enroll", serde_json::json!({})).await;
+    assert_eq!(status, StatusCode::CONFLICT);
+
+    let (status, _) = helpers::post_json(
+        &app,
+        &token,
+        "/api/auth/totp/disable",
+        serde_json::json!({ "code": "123456" }),
+    )
+    .await;
+    assert_eq!(status, StatusCode::UNAUTHORIZED);
+
+    let (status, _) = helpers::post_json(
+        &app,
+        &token,
+        "/api/auth/totp/disable",
+        serde_json::json!({ "code": code(&secret, step + 1) }),
+    )
+    .await;
+    assert_eq!(status, StatusCode::NO_CONTENT);
+
+    let (_, body) = helpers::get_json(&app, &token, "/api/auth/totp").await;
+    assert_eq!(body["enabled"], false);
+    let (status, _) = login(&app, None).await;
+    assert_eq!(status, StatusCode::OK);
+}
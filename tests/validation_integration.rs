@@ -412,3 +412,54 @@ async fn create_secret_on_deleted_project(pool: PgPool) {
         "should not create secret on deleted project"
     );
 }
+
+// ---------------------------------------------------------------------------
+// Password policy
+// ---------------------------------------------------------------------------
+
+/// Common passwords are rejected and the configured rules are named in the error.
+#[sqlx::test(migrations = "./migrations")]
+async fn password_policy_rejects_weak_password(pool: PgPool) {
+    let (mut state, admin_token) = helpers::test_state(pool).await;
+    let mut config = (*state.config).clone();
+    config.password_min_length = 12;
+    config.password_require_mixed_case = true;
+    config.password_require_symbol = true;
+    state.config = std::sync::Arc::new(config);
+    let app = helpers::test_router(state);
+
+    let (status, body) = helpers::post_json(
+        &app,
+        &admin_token,
+        "/api/users",
+        serde_json::json!({
+            "name": "weakpw",
+            "email": "weakpw@example.com",
+            "password": "password123"
+        }),
+    )
+    .await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    let msg = body["error"].as_str().unwrap_or_default();
+    for rule in [
+        "at least 12 characters",
+        "upper- and lowercase",
+        "a symbol",
+        "commonly used",
+    ] {
+        assert!(msg.contains(rule), "missing '{rule}' in: {body}");
+    }
+
+    let (status, body) = helpers::post_json(
+        &app,
+        &admin_token,
+        "/api/users",
+        serde_json::json!({
+            "name": "strongpw",
+            "email": "strongpw@example.com",
+            "password": "Correct-Horse-42"
+        }),
+    )
+    .await;
+    assert_eq!(status, StatusCode::CREATED, "{body}");
+}
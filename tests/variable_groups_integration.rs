@@ -0,0 +1,288 @@
+// Copyright (c) 2026 Steven Hooker. Exclusively licensed to and distributed by AgentSphere GmbH.
+// SPDX-License-Identifier: BUSL-1.1
+
+//! Integration tests for pipeline variable groups: the project and admin
+//! `variable-groups` APIs, pipeline references and
+//! `pipeline::variable_groups::resolve`.
+
+mod helpers;
+
+use axum::http::StatusCode;
+use serde_json::json;
+use sqlx::PgPool;
+use uuid::Uuid;
+
+/// The master key `helpers::test_state` configures.
+const TEST_MASTER_KEY: &str = "0123456789abcdef0123456789abcdef0123456789abcdef0123456789abcdef";
+
+const PIPELINE_YAML: &str = "\
+pipeline:
+  variable_groups: [prod-vars]
+  steps:
+    - name: deploy
+      image: alpine:3.19
+      commands:
+        - echo $REGION
+";
+
+/// Create a project whose repo has `yaml` as `.platform.yaml` on `main`.
+async fn setup_project(
+    state: &platform::store::AppState,
+    app: &axum::Router,
+    token: &str,
+    name: &str,
+    yaml: &str,
+) -> (Uuid, tempfile::TempDir, tempfile::TempDir) {
+    let project_id = helpers::create_project(app, token, name, "private").await;
+
+    let (bare_dir, bare_path) = helpers::create_bare_repo();
+    let (work_dir, work_path) = helpers::create_working_copy(&bare_path);
+    std::fs::write(work_path.join(".platform.yaml"), yaml).unwrap();
+    helpers::git_cmd(&work_path, &["add", "."]);
+    helpers::git_cmd(&work_path, &["commit", "-m", "add pipeline config"]);
+    helpers::git_cmd(&work_path, &["push", "origin", "main"]);
+
+    sqlx::query("UPDATE projects SET repo_path = $1 WHERE id = $2")
+        .bind(bare_path.to_str().unwrap())
+        .bind(project_id)
+        .execute(&state.pool)
+        .await
+        .unwrap();
+
+    (project_id, bare_dir, work_dir)
+}
+
+#[sqlx::test(migrations = "./migrations")]
+async fn project_group_crud_hides_masked_values(pool: PgPool) {
+    let (state, token) = helpers::test_state(pool.clone()).await;
+    let app = helpers::test_router(state);
+    let project_id = helpers::create_project(&app, &token, "vg-crud", "private").await;
+    let base = format!("/api/projects/{project_id}/variable-groups");
+
+    let (status, body) = helpers::post_json(
+        &app,
+        &token,
+        &base,
+        json!({
+            "name": "prod-vars",
+            "description": "shared production settings",
+            "variables": [
+                {"name": "REGION", "value": "eu-west-1"},
+                {"name": "DB_PASSWORD", "value": "correct-horse-battery", "masked": true},
+            ],
+        }),
+    )
+    .await;
+    assert_eq!(status, StatusCode::CREATED, "{body}");
+    assert_eq!(body["project_id"], project_id.to_string());
+    let vars = body["variables"].as_array().unwrap();
+    assert_eq!(vars.len(), 2);
+    assert_eq!(vars[0]["name"], "DB_PASSWORD");
+    assert!(
+        vars[0]["value"].is_null(),
+        "masked value must not be returned"
+    );
+    assert_eq!(vars[1]["value"], "eu-west-1");
+
+    // Stored encrypted, never in plain text
+    let (plain, encrypted): (Option<String>, Option<Vec<u8>>) = sqlx::query_as(
+        "SELECT value, encrypted_value FROM variable_group_entries WHERE name = 'DB_PASSWORD'",
+    )
+    .fetch_one(&pool)
+    .await
+    .unwrap();
+    assert!(plain.is_none());
+    assert!(
+        !String::from_utf8_lossy(&encrypted.unwrap()).contains("correct-horse-battery"),
+        "masked value stored in plain text"
+    );
+
+    let (status, _) = helpers::post_json(&app, &token, &base, json!({"name": "prod-vars"})).await;
+    assert_eq!(status, StatusCode::CONFLICT);
+
+    // Omitting a masked value keeps it; REGION is replaced
+    let (status, body) = helpers::put_json(
+        &app,
+        &token,
+        &format!("{base}/prod-vars"),
+        json!({
+            "variables": [
+                {"name": "REGION", "value": "us-east-1"},
+                {"name": "DB_PASSWORD", "masked": true},
+            ],
+        }),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK, "{body}");
+    assert_eq!(body["variables"][1]["value"], "us-east-1");
+    assert_eq!(body["description"], "shared production settings");
+
+    let master_key = platform::secrets::engine::parse_master_key(TEST_MASTER_KEY).unwrap();
+    let resolved = platform::pipeline::variable_groups::resolve(
+        &pool,
+        Some(&master_key),
+        project_id,
+        &["prod-vars".to_owned()],
+    )
+    .await
+    .unwrap();
+    assert!(
+        resolved
+            .vars
+            .contains(&("DB_PASSWORD".into(), "correct-horse-battery".into()))
+    );
+    assert_eq!(resolved.masked, vec!["correct-horse-battery".to_owned()]);
+
+    let (status, body) = helpers::get_json(&app, &token, &base).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["total"], 1);
+
+    let (status, _) = helpers::delete_json(&app, &token, &format!("{base}/prod-vars")).await;
+    assert_eq!(status, StatusCode::NO_CONTENT);
+    let (status, _) = helpers::get_json(&app, &token, &format!("{base}/prod-vars")).await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+}
+
+#[sqlx::test(migrations = "./migrations")]
+async fn group_rejects_invalid_variables(pool: PgPool) {
+    let (state, token) = helpers::test_state(pool).await;
+    let app = helpers::test_router(state);
+    let project_id = helpers::create_project(&app, &token, "vg-invalid", "private").await;
+    let base = format!("/api/projects/{project_id}/variable-groups");
+
+    for body in [
+        json!({"name": "bad name"}),
+        json!({"name": "g", "variables": [{"name": "COMMIT_SHA", "value": "x"}]}),
+        json!({"name": "g", "variables": [{"name": "TOKEN", "value": "short", "masked": true}]}),
+        json!({"name": "g", "variables": [{"name": "TOKEN", "masked": true}]}),
+        json!({"name": "g", "variables": [{"name": "A", "value": "1"}, {"name": "A", "value": "2"}]}),
+    ] {
+        let (status, resp) = helpers::post_json(&app, &token, &base, body.clone()).await;
+        assert_eq!(status, StatusCode::BAD_REQUEST, "{body}: {resp}");
+    }
+}
+
+#[sqlx::test(migrations = "./migrations")]
+async fn group_permissions(pool: PgPool) {
+    let (state, admin_token) = helpers::test_state(pool).await;
+    let app = helpers::test_router(state);
+    let project_id = helpers::create_project(&app, &admin_token, "vg-rbac", "public").await;
+    let (_, viewer_token) =
+        helpers::create_user(&app, &admin_token, "vg-viewer", "vg-viewer@test.com").await;
+
+    let base = format!("/api/projects/{project_id}/variable-groups");
+    let (status, _) = helpers::get_json(&app, &viewer_token, &base).await;
+    assert_eq!(status, StatusCode::OK);
+    let (status, _) =
+        helpers::post_json(&app, &viewer_token, &base, json!({"name": "prod-vars"})).await;
+    assert_eq!(status, StatusCode::FORBIDDEN);
+
+    let (status, _) = helpers::get_json(&app, &viewer_token, "/api/admin/variable-groups").await;
+    assert_eq!(status, StatusCode::FORBIDDEN);
+    let (status, _) = helpers::post_json(
+        &app,
+        &viewer_token,
+        "/api/admin/variable-groups",
+        json!({"name": "shared"}),
+    )
+    .await;
+    assert_eq!(status, StatusCode::FORBIDDEN);
+}
+
+#[sqlx::test(migrations = "./migrations")]
+async fn project_group_shadows_global_group(pool: PgPool) {
+    let (state, token) = helpers::test_state(pool.clone()).await;
+    let app = helpers::test_router(state);
+    let project_id = helpers::create_project(&app, &token, "vg-shadow", "private").await;
+    let other_id = helpers::create_project(&app, &token, "vg-other", "private").await;
+
+    let (status, body) = helpers::post_json(
+        &app,
+        &token,
+        "/api/admin/variable-groups",
+        json!({"name": "prod-vars", "variables": [
+            {"name": "REGION", "value": "global"},
+            {"name": "LOG_LEVEL", "value": "info"},
+        ]}),
+    )
+    .await;
+    assert_eq!(status, StatusCode::CREATED, "{body}");
+    assert!(body["project_id"].is_null());
+    let (status, _) = helpers::post_json(
+        &app,
+        &token,
+        &format!("/api/projects/{project_id}/variable-groups"),
+        json!({"name": "prod-vars", "variables": [{"name": "REGION", "value": "project"}]}),
+    )
+    .await;
+    assert_eq!(status, StatusCode::CREATED);
+
+    let names = ["prod-vars".to_owned()];
+    let own = platform::pipeline::variable_groups::resolve(&pool, None, project_id, &names)
+        .await
+        .unwrap();
+    assert_eq!(own.vars, vec![("REGION".into(), "project".into())]);
+    let other = platform::pipeline::variable_groups::resolve(&pool, None, other_id, &names)
+        .await
+        .unwrap();
+    assert_eq!(
+        other.vars,
+        vec![
+            ("LOG_LEVEL".into(), "info".into()),
+            ("REGION".into(), "global".into())
+        ]
+    );
+}
+
+#[sqlx::test(migrations = "./migrations")]
+async fn pipeline_references_group(pool: PgPool) {
+    let (state, token) = helpers::test_state(pool.clone()).await;
+    let app = helpers::test_router(state.clone());
+    let (project_id, _bd, _wd) =
+        setup_project(&state, &app, &token, "vg-pipeline", PIPELINE_YAML).await;
+    let trigger = format!("/api/projects/{project_id}/pipelines");
+
+    // The group doesn't exist yet
+    let (status, body) = helpers::post_json(
+        &app,
+        &token,
+        &trigger,
+        json!({"git_ref": "refs/heads/main"}),
+    )
+    .await;
+    assert_eq!(status, StatusCode::BAD_REQUEST, "{body}");
+    assert!(
+        body["error"]
+            .as_str()
+            .unwrap()
+            .contains("unknown variable group 'prod-vars'"),
+        "{body}"
+    );
+
+    let (status, _) = helpers::post_json(
+        &app,
+        &token,
+        &format!("/api/projects/{project_id}/variable-groups"),
+        json!({"name": "prod-vars", "variables": [{"name": "REGION", "value": "eu-west-1"}]}),
+    )
+    .await;
+    assert_eq!(status, StatusCode::CREATED);
+
+    let (status, body) = helpers::post_json(
+        &app,
+        &token,
+        &trigger,
+        json!({"git_ref": "refs/heads/main"}),
+    )
+    .await;
+    assert_eq!(status, StatusCode::CREATED, "{body}");
+    let pipeline_id = Uuid::parse_str(body["id"].as_str().unwrap()).unwrap();
+
+    let groups: Vec<String> =
+        sqlx::query_scalar("SELECT variable_groups FROM pipelines WHERE id = $1")
+            .bind(pipeline_id)
+            .fetch_one(&pool)
+            .await
+            .unwrap();
+    assert_eq!(groups, vec!["prod-vars"]);
+}
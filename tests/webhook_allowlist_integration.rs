@@ -0,0 +1,124 @@
+// Copyright (c) 2026 Steven Hooker. Exclusively licensed to and distributed by AgentSphere GmbH.
+// SPDX-License-Identifier: BUSL-1.1
+
+//! Webhook egress allowlist. Kept in its own test binary because the
+//! delivery-time allowlist is process-global.
+
+mod helpers;
+
+use std::sync::Arc;
+use std::time::Duration;
+
+use axum::http::StatusCode;
+use sqlx::PgPool;
+use uuid::Uuid;
+use wiremock::matchers::method;
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+fn allowlist() -> Vec<String> {
+    vec!["localhost".into(), "*.partner.example".into()]
+}
+
+#[sqlx::test(migrations = "./migrations")]
+async fn create_webhook_rejects_destinations_off_the_allowlist(pool: PgPool) {
+    let (mut state, admin_token) = helpers::test_state(pool).await;
+    let mut config = (*state.config).clone();
+    config.webhook_allowlist = allowlist();
+    state.config = Arc::new(config);
+    let app = helpers::test_router(state);
+    let project_id = helpers::create_project(&app, &admin_token, "egress", "private").await;
+    let hooks = format!("/api/projects/{project_id}/webhooks");
+
+    for url in ["https://example.com/hook", "https://93.184.216.34/hook"] {
+        let (status, body) = helpers::post_json(
+            &app,
+            &admin_token,
+            &hooks,
+            serde_json::json!({"url": url, "events": ["push"]}),
+        )
+        .await;
+        assert_eq!(status, StatusCode::BAD_REQUEST, "{url}: {body}");
+        assert!(
+            body["error"].as_str().unwrap().contains("egress allowlist"),
+            "{body}"
+        );
+    }
+
+    let (status, body) = helpers::post_json(
+        &app,
+        &admin_token,
+        &hooks,
+        serde_json::json!({"url": "https://hooks.partner.example/x", "events": ["push"]}),
+    )
+    .await;
+    assert_eq!(status, StatusCode::CREATED, "{body}");
+
+    // Updates are held to the same list
+    let (status, _) = helpers::patch_json(
+        &app,
+        &admin_token,
+        &format!("{hooks}/{}", body["id"].as_str().unwrap()),
+        serde_json::json!({"url": "https://example.com/hook"}),
+    )
+    .await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+}
+
+#[sqlx::test(migrations = "./migrations")]
+async fn delivery_skips_destinations_off_the_allowlist(pool: PgPool) {
+    platform::api::webhooks::set_egress_allowlist(&allowlist());
+    let (state, admin_token) = helpers::test_state(pool).await;
+    let app = helpers::test_router(state.clone());
+    let project_id = helpers::create_project(&app, &admin_token, "egress-dl", "private").await;
+
+    let server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .respond_with(ResponseTemplate::new(200))
+        .mount(&server)
+        .await;
+    let port = server.address().port();
+
+    // Stored directly, as if the URL predates the allowlist
+    for url in [
+        format!("http://localhost:{port}/allowed"),
+        format!("http://127.0.0.1:{port}/blocked"),
+    ] {
+        sqlx::query(
+            "INSERT INTO webhooks (id, project_id, url, events, active) VALUES ($1,$2,$3,$4,true)",
+        )
+        .bind(Uuid::new_v4())
+        .bind(project_id)
+        .bind(&url)
+        .bind(&["issue"][..])
+        .execute(&state.pool)
+        .await
+        .unwrap();
+    }
+
+    platform::api::webhooks::fire_webhooks(
+        &state.pool,
+        project_id,
+        "issue",
+        &serde_json::json!({"action": "created"}),
+        &state.webhook_semaphore,
+    )
+    .await;
+
+    for _ in 0..50 {
+        if !server.received_requests().await.unwrap().is_empty() {
+            break;
+        }
+        tokio::time::sleep(Duration::from_millis(100)).await;
+    }
+    // Give a wrongly allowed delivery time to land as well
+    tokio::time::sleep(Duration::from_millis(300)).await;
+
+    let paths: Vec<String> = server
+        .received_requests()
+        .await
+        .unwrap()
+        .iter()
+        .map(|r| r.url.path().to_owned())
+        .collect();
+    assert_eq!(paths, vec!["/allowed"]);
+}
@@ -563,18 +563,30 @@ async fn test_webhook_endpoint(pool: PgPool) {
     let (state, admin_token) = helpers::test_state(pool.clone()).await;
     let app = helpers::test_router(state);
 
+    let mock_server = MockServer::start().await;
+    Mock::given(matchers::method("POST"))
+        .and(matchers::path("/ok"))
+        .respond_with(ResponseTemplate::new(201).set_body_string("pong"))
+        .expect(1)
+        .mount(&mock_server)
+        .await;
+    Mock::given(matchers::method("POST"))
+        .and(matchers::path("/broken"))
+        .respond_with(ResponseTemplate::new(500).set_body_string("boom"))
+        .mount(&mock_server)
+        .await;
+
     let project_id = helpers::create_project(&app, &admin_token, "wh-test-ep", "public").await;
 
-    // Insert webhook directly to bypass SSRF checks (test endpoint will try to deliver)
+    // Inserted directly to bypass SSRF checks (wiremock binds to 127.0.0.1).
+    // The ping goes out whatever events the webhook subscribes to.
     let wh_id = insert_webhook(
         &pool,
         project_id,
-        "https://example.com/test-delivery",
+        &format!("{}/ok", mock_server.uri()),
         &["push"],
     )
     .await;
-
-    // Test webhook endpoint should return OK (delivery happens async, may fail, but endpoint itself succeeds)
     let (status, body) = helpers::post_json(
         &app,
         &admin_token,
@@ -582,9 +594,56 @@ async fn test_webhook_endpoint(pool: PgPool) {
         serde_json::json!({}),
     )
     .await;
-
     assert_eq!(status, StatusCode::OK, "test webhook failed: {body}");
     assert_eq!(body["ok"], true);
+    assert_eq!(body["status_code"], 201);
+    assert_eq!(body["response_body"], "pong");
+    assert!(body["error"].is_null());
+
+    // Delivered synchronously: the receiver already has it
+    let requests = mock_server.received_requests().await.unwrap();
+    let payload: serde_json::Value = serde_json::from_slice(&requests[0].body).unwrap();
+    assert_eq!(payload["event"], "ping");
+    assert_eq!(payload["webhook_id"], wh_id.to_string());
+
+    // A failing receiver is reported, not turned into an API error
+    let wh_id = insert_webhook(
+        &pool,
+        project_id,
+        &format!("{}/broken", mock_server.uri()),
+        &["push"],
+    )
+    .await;
+    let (status, body) = helpers::post_json(
+        &app,
+        &admin_token,
+        &format!("/api/projects/{project_id}/webhooks/{wh_id}/test"),
+        serde_json::json!({}),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK, "{body}");
+    assert_eq!(body["ok"], false);
+    assert_eq!(body["status_code"], 500);
+    assert_eq!(body["response_body"], "boom");
+
+    // So is one that can't be reached at all
+    let closed = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+    let url = format!("http://{}/hook", closed.local_addr().unwrap());
+    drop(closed);
+    let wh_id = insert_webhook(&pool, project_id, &url, &["push"]).await;
+    let (status, body) = helpers::post_json(
+        &app,
+        &admin_token,
+        &format!("/api/projects/{project_id}/webhooks/{wh_id}/test"),
+        serde_json::json!({}),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK, "{body}");
+    assert_eq!(body["ok"], false);
+    assert!(body["status_code"].is_null());
+    assert!(body["error"].is_string(), "{body}");
+
+    mock_server.verify().await;
 }
 
 #[sqlx::test(migrations = "./migrations")]
@@ -1189,3 +1248,245 @@ async fn webhook_concurrent_limit(pool: PgPool) {
     // We should receive at least some (not all dropped)
     assert!(received > 0, "should receive at least some webhooks, got 0");
 }
+
+// ---------------------------------------------------------------------------
+// Branch / path filters
+// ---------------------------------------------------------------------------
+
+#[sqlx::test(migrations = "./migrations")]
+async fn webhook_filters_round_trip(pool: PgPool) {
+    let (state, admin_token) = helpers::test_state(pool).await;
+    let app = helpers::test_router(state);
+
+    let project_id = helpers::create_project(&app, &admin_token, "wh-filters", "private").await;
+
+    let (status, body) = helpers::post_json(
+        &app,
+        &admin_token,
+        &format!("/api/projects/{project_id}/webhooks"),
+        serde_json::json!({
+            "url": "https://example.com/hook",
+            "events": ["push"],
+            "branch_filter": ["main", "release/*"],
+            "path_filter": ["deploy/*"],
+        }),
+    )
+    .await;
+    assert_eq!(status, StatusCode::CREATED, "create failed: {body}");
+    assert_eq!(
+        body["branch_filter"],
+        serde_json::json!(["main", "release/*"])
+    );
+    assert_eq!(body["path_filter"], serde_json::json!(["deploy/*"]));
+    let wh_id = body["id"].as_str().unwrap();
+
+    // An empty list clears a filter; omitted filters are left untouched
+    let (status, body) = helpers::patch_json(
+        &app,
+        &admin_token,
+        &format!("/api/projects/{project_id}/webhooks/{wh_id}"),
+        serde_json::json!({ "path_filter": [] }),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK, "update failed: {body}");
+    assert_eq!(
+        body["branch_filter"],
+        serde_json::json!(["main", "release/*"])
+    );
+    assert_eq!(body["path_filter"], serde_json::json!([]));
+
+    // Empty patterns are rejected
+    let (status, _) = helpers::patch_json(
+        &app,
+        &admin_token,
+        &format!("/api/projects/{project_id}/webhooks/{wh_id}"),
+        serde_json::json!({ "branch_filter": [""] }),
+    )
+    .await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+}
+
+/// A webhook filtered to `main` ignores feature-branch pushes, and a path
+/// filter ignores pushes that don't touch matching files.
+#[sqlx::test(migrations = "./migrations")]
+async fn push_webhook_respects_branch_and_path_filters(pool: PgPool) {
+    let (state, admin_token) = helpers::test_state(pool).await;
+    let app = helpers::test_router(state.clone());
+
+    let mock_server = MockServer::start().await;
+    Mock::given(matchers::method("POST"))
+        .and(matchers::path("/main-only"))
+        .respond_with(ResponseTemplate::new(200))
+        .expect(1)
+        .mount(&mock_server)
+        .await;
+    Mock::given(matchers::method("POST"))
+        .and(matchers::path("/docs-only"))
+        .respond_with(ResponseTemplate::new(200))
+        .expect(0)
+        .mount(&mock_server)
+        .await;
+
+    let project_id = helpers::create_project(&app, &admin_token, "wh-push-filter", "private").await;
+    let admin_id = helpers::admin_user_id(&state.pool).await;
+
+    let main_only = insert_webhook(
+        &state.pool,
+        project_id,
+        &format!("{}/main-only", mock_server.uri()),
+        &["push"],
+    )
+    .await;
+    let docs_only = insert_webhook(
+        &state.pool,
+        project_id,
+        &format!("{}/docs-only", mock_server.uri()),
+        &["push"],
+    )
+    .await;
+    sqlx::query("UPDATE webhooks SET branch_filter = $2 WHERE id = $1")
+        .bind(main_only)
+        .bind(vec!["main".to_owned()])
+        .execute(&state.pool)
+        .await
+        .unwrap();
+    sqlx::query("UPDATE webhooks SET path_filter = $2 WHERE id = $1")
+        .bind(docs_only)
+        .bind(vec!["docs/*".to_owned()])
+        .execute(&state.pool)
+        .await
+        .unwrap();
+
+    let (_bare_dir, bare_path) = helpers::create_bare_repo();
+    let (_work_dir, work_path) = helpers::create_working_copy(&bare_path);
+    let base = helpers::git_cmd(&work_path, &["rev-parse", "HEAD"])
+        .trim()
+        .to_owned();
+
+    let push = |branch: &str, new_sha: &str| platform::git::hooks::PostReceiveParams {
+        project_id,
+        user_id: admin_id,
+        user_name: "admin".into(),
+        repo_path: bare_path.clone(),
+        default_branch: "main".into(),
+        pushed_branches: vec![branch.into()],
+        pushed_tags: vec![],
+        ref_updates: vec![platform::git::hooks::RefUpdate {
+            old_sha: base.clone(),
+            new_sha: new_sha.into(),
+            refname: format!("refs/heads/{branch}"),
+        }],
+    };
+
+    // Feature branch push: neither webhook matches
+    helpers::git_cmd(&work_path, &["checkout", "-b", "feature/x"]);
+    std::fs::write(work_path.join("feature.rs"), "fn main() {}\n").unwrap();
+    helpers::git_cmd(&work_path, &["add", "."]);
+    helpers::git_cmd(&work_path, &["commit", "-m", "feature work"]);
+    helpers::git_cmd(&work_path, &["push", "origin", "feature/x"]);
+    let feature_sha = helpers::git_cmd(&work_path, &["rev-parse", "HEAD"])
+        .trim()
+        .to_owned();
+    platform::git::hooks::post_receive(&state, &push("feature/x", &feature_sha))
+        .await
+        .unwrap();
+
+    // Main push without docs changes: only the branch-filtered webhook fires
+    helpers::git_cmd(&work_path, &["checkout", "main"]);
+    std::fs::write(work_path.join("src.rs"), "fn lib() {}\n").unwrap();
+    helpers::git_cmd(&work_path, &["add", "."]);
+    helpers::git_cmd(&work_path, &["commit", "-m", "main work"]);
+    helpers::git_cmd(&work_path, &["push", "origin", "main"]);
+    let main_sha = helpers::git_cmd(&work_path, &["rev-parse", "HEAD"])
+        .trim()
+        .to_owned();
+    platform::git::hooks::post_receive(&state, &push("main", &main_sha))
+        .await
+        .unwrap();
+
+    tokio::time::sleep(Duration::from_millis(500)).await;
+    mock_server.verify().await;
+
+    let requests = mock_server.received_requests().await.unwrap();
+    let payload: serde_json::Value = serde_json::from_slice(&requests[0].body).unwrap();
+    assert_eq!(payload["ref"], "refs/heads/main");
+}
+
+/// A burst of events is delivered through the per-project slots: every event
+/// arrives, but never more than the limit at once.
+#[sqlx::test(migrations = "./migrations")]
+async fn webhook_burst_is_delivered_through_bounded_pool(pool: PgPool) {
+    use std::sync::Arc;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    // Receiver that records the highest number of concurrent deliveries
+    #[derive(Default)]
+    struct Counters {
+        in_flight: AtomicUsize,
+        max_in_flight: AtomicUsize,
+        delivered: AtomicUsize,
+    }
+
+    let (state, admin_token) = helpers::test_state(pool).await;
+    let app = helpers::test_router(state.clone());
+    platform::api::webhooks::set_project_concurrency(10);
+    let counters = Arc::new(Counters::default());
+    let receiver = axum::Router::new().route(
+        "/webhook",
+        axum::routing::post({
+            let counters = counters.clone();
+            move || {
+                let counters = counters.clone();
+                async move {
+                    let now = counters.in_flight.fetch_add(1, Ordering::SeqCst) + 1;
+                    counters.max_in_flight.fetch_max(now, Ordering::SeqCst);
+                    tokio::time::sleep(Duration::from_millis(50)).await;
+                    counters.in_flight.fetch_sub(1, Ordering::SeqCst);
+                    counters.delivered.fetch_add(1, Ordering::SeqCst);
+                    StatusCode::OK
+                }
+            }
+        }),
+    );
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap();
+    tokio::spawn(async move { axum::serve(listener, receiver).await.unwrap() });
+
+    let project_id = helpers::create_project(&app, &admin_token, "wh-burst", "private").await;
+    insert_webhook(
+        &state.pool,
+        project_id,
+        &format!("http://{addr}/webhook"),
+        &["issue"],
+    )
+    .await;
+
+    let payload = serde_json::json!({"action": "created"});
+    futures_util::future::join_all((0..100).map(|_| {
+        platform::api::webhooks::fire_webhooks(
+            &state.pool,
+            project_id,
+            "issue",
+            &payload,
+            &state.webhook_semaphore,
+        )
+    }))
+    .await;
+
+    for _ in 0..100 {
+        if counters.delivered.load(Ordering::SeqCst) == 100 {
+            break;
+        }
+        tokio::time::sleep(Duration::from_millis(100)).await;
+    }
+    assert_eq!(
+        counters.delivered.load(Ordering::SeqCst),
+        100,
+        "every event should be delivered, none dropped"
+    );
+    let max = counters.max_in_flight.load(Ordering::SeqCst);
+    assert!(
+        max <= 10,
+        "at most 10 deliveries should be in flight, saw {max}"
+    );
+}
@@ -35,7 +35,7 @@ export function NotificationBell() {
   const toggle = (e: Event) => {
     e.stopPropagation();
     if (!open) {
-      api.get<ListResponse<Notification>>('/api/notifications?limit=5&status=unread')
+      api.get<ListResponse<Notification>>('/api/notifications?limit=5&unread=true')
         .then(r => setNotifications(r.items))
         .catch(e => console.warn('notification count:', e));
     }
@@ -43,11 +43,18 @@ export function NotificationBell() {
   };
 
   const markRead = async (id: string) => {
-    await api.patch(`/api/notifications/${id}/read`, {});
+    await api.patch(`/api/notifications/${id}`, { read: true });
     setNotifications(prev => prev.filter(n => n.id !== id));
     setUnreadCount(prev => Math.max(0, prev - 1));
   };
 
+  const markAllRead = async (e: Event) => {
+    e.stopPropagation();
+    await api.post('/api/notifications/read-all', {});
+    setNotifications([]);
+    setUnreadCount(0);
+  };
+
   const getNotificationHref = (n: Notification): string | null => {
     if (n.ref_type === 'project' && n.ref_id) return `/projects/${n.ref_id}`;
     if (n.ref_type === 'issue' && n.ref_id) return `/projects/${n.ref_id}`;
@@ -67,6 +74,9 @@ export function NotificationBell() {
         <div class="notification-dropdown">
           <div class="notification-dropdown-header">
             <span class="text-sm" style="font-weight:600">Notifications</span>
+            {notifications.length > 0 && (
+              <button class="btn btn-ghost btn-xs" onClick={markAllRead}>Mark all read</button>
+            )}
           </div>
           {notifications.length === 0 ? (
             <div class="notification-empty">No unread notifications</div>
@@ -35,7 +35,7 @@ export function NotificationCenter() {
   const toggle = (e: Event) => {
     e.stopPropagation();
     if (!open) {
-      api.get<ListResponse<Notification>>('/api/notifications?limit=5&status=unread')
+      api.get<ListResponse<Notification>>('/api/notifications?limit=5&unread=true')
         .then(r => setNotifications(r.items))
         .catch(e => console.warn('notifications:', e));
     }
@@ -43,11 +43,18 @@ export function NotificationCenter() {
   };
 
   const markRead = async (id: string) => {
-    await api.patch(`/api/notifications/${id}/read`, {});
+    await api.patch(`/api/notifications/${id}`, { read: true });
     setNotifications(prev => prev.filter(n => n.id !== id));
     setUnreadCount(prev => Math.max(0, prev - 1));
   };
 
+  const markAllRead = async (e: Event) => {
+    e.stopPropagation();
+    await api.post('/api/notifications/read-all', {});
+    setNotifications([]);
+    setUnreadCount(0);
+  };
+
   const getNotificationHref = (n: Notification): string | null => {
     if (n.ref_type === 'project' && n.ref_id) return `/projects/${n.ref_id}`;
     if (n.ref_type === 'issue' && n.ref_id) return `/projects/${n.ref_id}`;
@@ -72,7 +79,12 @@ export function NotificationCenter() {
 
       {open && (
         <div class="notification-center-panel">
-          <div class="notification-center-panel-header">Notifications</div>
+          <div class="notification-center-panel-header">
+            Notifications
+            {notifications.length > 0 && (
+              <button class="btn btn-ghost btn-xs" style="float:right" onClick={markAllRead}>Mark all read</button>
+            )}
+          </div>
           {notifications.length === 0 ? (
             <div class="notification-center-empty">
               <svg width="24" height="24" viewBox="0 0 24 24" fill="none" stroke="var(--text-muted)" stroke-width="1.5" style="margin-bottom:0.5rem">
@@ -7,7 +7,7 @@ import { StagingPromoteBar } from './StagingPromoteBar';
 import { FeatureFlagsPanel } from './FeatureFlagsPanel';
 import {
   FilesTab, IssuesTab, MRsTab, BuildsTab, UiPreviewsTab,
-  DeploymentsTab, SessionsTab, LogsTab, SkillsTab, WebhooksTab,
+  DeploymentsTab, SessionsTab, LogsTab, SkillsTab, WebhooksTab, ActivityTab,
   SettingsTab, SecretsTab, DocsTab, ObserveTab,
 } from './ProjectTabs';
 
@@ -22,14 +22,14 @@ interface DeployRelease {
 }
 
 export const ALL_TABS = [
-  'files', 'issues', 'mrs', 'builds', 'ui', 'docs',
+  'activity', 'files', 'issues', 'mrs', 'builds', 'ui', 'docs',
   'deploys', 'observe', 'sessions', 'skills', 'webhooks', 'settings', 'secrets',
 ] as const;
 
 export type CardTab = typeof ALL_TABS[number] | null;
 
 const TAB_LABELS: Record<string, string> = {
-  files: 'Files', issues: 'Issues', mrs: 'MRs', builds: 'Builds',
+  activity: 'Activity', files: 'Files', issues: 'Issues', mrs: 'MRs', builds: 'Builds',
   ui: 'UI', docs: 'Docs', deploys: 'Deploys', observe: 'Observe',
   sessions: 'Sessions', skills: 'Skills', webhooks: 'Webhooks',
   settings: 'Settings', secrets: 'Secrets',
@@ -212,9 +212,10 @@ export function ProjectCard({ project, initialExpanded, initialTab }: Props) {
 
   const renderTabContent = () => {
     switch (activeTab) {
+      case 'activity': return <ActivityTab projectId={project.id} />;
       case 'files': return <FilesTab projectId={project.id} defaultBranch={projectState.default_branch} />;
       case 'issues': return <IssuesTab projectId={project.id} />;
-      case 'mrs': return <MRsTab projectId={project.id} />;
+      case 'mrs': return <MRsTab projectId={project.id} defaultBranch={projectState.default_branch} />;
       case 'builds': return <BuildsTab projectId={project.id} />;
       case 'ui': return <UiPreviewsTab projectId={project.id} defaultBranch={projectState.default_branch} />;
       case 'docs': return <DocsTab projectId={project.id} defaultBranch={projectState.default_branch} />;
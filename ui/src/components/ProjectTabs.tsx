@@ -6,6 +6,7 @@ import type {
   LogEntry, UiPreviewArtifact, UiPreviewFile, UiPreviewConfig,
   UiPreviewGroup, UiPreviewItem, AgentSession, IframePanel,
   Comment, PipelineDetail as PipelineDetailType, PipelineStep, Artifact,
+  ActivityEntry,
 } from '../lib/types';
 import { timeAgo, duration } from '../lib/format';
 import { Badge } from './Badge';
@@ -38,6 +39,8 @@ function formatBytes(bytes: number): string {
   return `${(bytes / (1024 * 1024)).toFixed(1)} MB`;
 }
 
+const IMAGE_EXT = /\.(png|jpe?g|gif|webp|svg|ico)$/i;
+
 function langFromPath(path: string): string {
   const ext = path.split('.').pop()?.toLowerCase() || '';
   const map: Record<string, string> = {
@@ -264,20 +267,28 @@ export function FilesTab({ projectId, defaultBranch }: { projectId: string; defa
                   <button class="btn btn-ghost btn-xs" onClick={copyFile}>
                     {copied ? 'Copied' : 'Copy'}
                   </button>
+                  <a class="btn btn-ghost btn-xs"
+                    href={`${apiPrefix}/raw${qs({ ref: currentRef, path: blob.path })}`}>Raw</a>
                 </div>
               </div>
-              <div class="repo-content-code">
-                <table class="repo-code-table">
-                  <tbody>
-                    {blobLines.map((line, i) => (
-                      <tr key={i} class="repo-code-row">
-                        <td class="repo-line-no">{i + 1}</td>
-                        <td class="repo-line-code"><pre>{line}</pre></td>
-                      </tr>
-                    ))}
-                  </tbody>
-                </table>
-              </div>
+              {repo === 'app' && IMAGE_EXT.test(blob.path) ? (
+                <div class="repo-content-image">
+                  <img src={`${apiPrefix}/raw/${encodeURIComponent(currentRef)}/${blob.path}`} alt={blob.path} />
+                </div>
+              ) : (
+                <div class="repo-content-code">
+                  <table class="repo-code-table">
+                    <tbody>
+                      {blobLines.map((line, i) => (
+                        <tr key={i} class="repo-code-row">
+                          <td class="repo-line-no">{i + 1}</td>
+                          <td class="repo-line-code"><pre>{line}</pre></td>
+                        </tr>
+                      ))}
+                    </tbody>
+                  </table>
+                </div>
+              )}
             </>
           )}
         </div>
@@ -446,14 +457,14 @@ export function IssuesTab({ projectId }: { projectId: string }) {
 
 /* ---- MRs Tab (with pipeline status) ---- */
 
-export function MRsTab({ projectId }: { projectId: string }) {
+export function MRsTab({ projectId, defaultBranch }: { projectId: string; defaultBranch: string }) {
   const [mrs, setMrs] = useState<MergeRequest[]>([]);
   const [total, setTotal] = useState(0);
   const [offset, setOffset] = useState(0);
   const [status, setStatus] = useState('open');
   const [showCreate, setShowCreate] = useState(false);
   const [branches, setBranches] = useState<BranchInfo[]>([]);
-  const [form, setForm] = useState({ source_branch: '', target_branch: 'main', title: '', body: '' });
+  const [form, setForm] = useState({ source_branch: '', target_branch: defaultBranch, title: '', body: '' });
   const [error, setError] = useState('');
   const [branchPipelines, setBranchPipelines] = useState<Map<string, Pipeline>>(new Map());
 
@@ -487,7 +498,7 @@ export function MRsTab({ projectId }: { projectId: string }) {
     try {
       await api.post(`/api/projects/${projectId}/merge-requests`, form);
       setShowCreate(false);
-      setForm({ source_branch: '', target_branch: 'main', title: '', body: '' });
+      setForm({ source_branch: '', target_branch: defaultBranch, title: '', body: '' });
       load();
     } catch (err: any) { setError(err.message); }
   };
@@ -1527,6 +1538,72 @@ export function WebhooksTab({ projectId }: { projectId: string }) {
   );
 }
 
+/* ---- Activity Tab ---- */
+
+const PIPELINE_VERBS: Record<string, string> = {
+  success: 'succeeded', failure: 'failed', cancelled: 'was cancelled',
+  running: 'started', pending: 'was queued',
+};
+
+/** One-line description, e.g. "merged MR #12" or "deployed to staging". */
+function describeActivity(e: ActivityEntry): string {
+  const d = (e.detail ?? {}) as Record<string, any>;
+  const num = d.number != null ? ` #${d.number}` : '';
+  const title = d.title ? `: ${d.title}` : '';
+  switch (e.kind) {
+    case 'merge_request': {
+      const verb = e.action.split('.').slice(1).join(' ').replace(/_/g, ' ');
+      const past: Record<string, string> = { create: 'opened', merge: 'merged', update: 'updated', delete: 'deleted' };
+      return `${past[verb] ?? verb} MR${num}${title}`;
+    }
+    case 'issue': {
+      const verb = e.action.split('.')[1] ?? e.action;
+      const past: Record<string, string> = {
+        create: 'opened', close: 'closed', update: 'updated', delete: 'deleted',
+        assign: 'assigned', unassign: 'unassigned',
+      };
+      return `${past[verb] ?? verb} issue${num}${title}`;
+    }
+    case 'comment': return `commented${num ? ` on${num}` : ''}`;
+    case 'review': return `reviewed MR${num}`;
+    case 'push': return 'pushed';
+    case 'pipeline': return `pipeline on ${d.git_ref ?? '?'} ${PIPELINE_VERBS[e.action] ?? e.action}`;
+    case 'deployment': return `deployed to ${d.environment ?? d.target ?? '?'} (${e.action})`;
+    default: return e.action.replace(/\./g, ' ');
+  }
+}
+
+export function ActivityTab({ projectId }: { projectId: string }) {
+  const [entries, setEntries] = useState<ActivityEntry[]>([]);
+  const [total, setTotal] = useState(0);
+  const [offset, setOffset] = useState(0);
+
+  useEffect(() => {
+    api.get<ListResponse<ActivityEntry>>(`/api/projects/${projectId}/activity${qs({ limit: 50, offset })}`)
+      .then(r => { setEntries(r.items); setTotal(r.total); })
+      .catch(e => console.warn(e));
+  }, [projectId, offset]);
+
+  return (
+    <div class="card">
+      {entries.length === 0 ? <div class="empty-state">No activity yet</div> : (
+        <div class="activity-feed">
+          {entries.map((e, i) => (
+            <div key={`${e.kind}-${e.resource_id}-${i}`} class="activity-item">
+              <div class="activity-content">
+                {e.actor_name && <span class="activity-actor">{e.actor_name} </span>}
+                <span class="activity-action">{describeActivity(e)}</span>
+                <div class="activity-time">{timeAgo(e.created_at)}</div>
+              </div>
+            </div>
+          ))}
+        </div>
+      )}
+      <Pagination total={total} limit={50} offset={offset} onChange={setOffset} />
+    </div>
+  );
+}
+
 /* ---- Settings Tab ---- */
 
 export function SettingsTab({ project, onUpdate }: { project: Project; onUpdate: (p: Project) => void }) {
@@ -1,5 +1,5 @@
 export class ApiError extends Error {
-  constructor(public status: number, public body: { error: string }) {
+  constructor(public status: number, public body: { error: string; code?: string; message?: string }) {
     super(body.error);
   }
 }
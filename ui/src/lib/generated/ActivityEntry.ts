@@ -0,0 +1,24 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { JsonValue } from "./serde_json/JsonValue";
+
+export type ActivityEntry = { 
+/**
+ * `merge_request`, `issue`, `comment`, `review`, `push`, `pipeline` or
+ * `deployment`.
+ */
+kind: string, 
+/**
+ * Audit action (`mr.merge`, `issue.close`, `git.push`, ...), pipeline
+ * status (`success`, `failure`, ...) or release phase (`completed`, ...).
+ */
+action: string, actor_id: string | null, actor_name: string | null, 
+/**
+ * The merge request, issue, comment, review, pipeline or release.
+ */
+resource_id: string | null, 
+/**
+ * Kind-specific fields: `number`/`title` for merge requests and issues,
+ * `git_ref`/`commit_sha` for pipelines, `environment`/`image_ref` for
+ * deployments.
+ */
+detail: JsonValue, created_at: string, };
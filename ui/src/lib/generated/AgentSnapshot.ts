@@ -0,0 +1,10 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * A stored workspace snapshot of an agent session.
+ */
+export type AgentSnapshot = { 
+/**
+ * Snapshot name, used to restore from it.
+ */
+name: string, size_bytes: number, created_at: string, };
@@ -1,3 +1,3 @@
 // This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
 
-export type AlertRule = { id: string, name: string, description: string | null, query: string, condition: string, threshold: number | null, window_seconds: number, severity: string, channels: Array<string>, project_id: string | null, enabled: boolean, created_at: string, };
+export type AlertRule = { id: string, name: string, description: string | null, query: string, condition: string, threshold: number | null, window_seconds: number, severity: string, channels: Array<string>, inhibit_if: Array<string>, project_id: string | null, enabled: boolean, created_at: string, };
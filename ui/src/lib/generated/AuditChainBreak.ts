@@ -0,0 +1,8 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+export type AuditChainBreak = { id: string, seq: number, created_at: string, 
+/**
+ * `hash_mismatch` (row edited), `link_mismatch` (row removed or inserted)
+ * or `unchained` (row without a hash after chaining started).
+ */
+reason: string, };
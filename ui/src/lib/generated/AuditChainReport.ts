@@ -0,0 +1,11 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { AuditChainBreak } from "./AuditChainBreak";
+
+/**
+ * Result of recomputing the audit log hash chain.
+ */
+export type AuditChainReport = { intact: boolean, 
+/**
+ * Chained entries verified before the first break (or in total when intact).
+ */
+checked: number, first_break: AuditChainBreak | null, };
@@ -1,4 +1,8 @@
 // This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
 import type { JsonValue } from "./serde_json/JsonValue";
 
-export type AuditLogEntry = { id: string, actor_id: string, actor_name: string, action: string, resource: string, resource_id: string | null, project_id: string | null, detail: JsonValue | null, created_at: string, };
+export type AuditLogEntry = { id: string, actor_id: string, actor_name: string, action: string, resource: string, resource_id: string | null, project_id: string | null, detail: JsonValue | null, 
+/**
+ * Break-glass grant the actor was using, if any.
+ */
+break_glass_id: string | null, created_at: string, };
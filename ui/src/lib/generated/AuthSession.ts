@@ -0,0 +1,7 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+export type AuthSession = { id: string, ip_addr: string | null, user_agent: string | null, 
+/**
+ * True for the session making this request.
+ */
+current: boolean, created_at: string, expires_at: string, };
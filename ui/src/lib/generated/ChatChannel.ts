@@ -0,0 +1,7 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+export type ChatChannel = { id: string, project_id: string, kind: string, 
+/**
+ * Host of the incoming-webhook URL; the full URL is a credential and is never returned.
+ */
+url_host: string, events: Array<string>, active: boolean, created_at: string, };
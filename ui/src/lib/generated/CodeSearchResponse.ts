@@ -0,0 +1,12 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { CodeSearchMatch } from "./CodeSearchMatch";
+
+export type CodeSearchResponse = { 
+/**
+ * Commit the search ran against.
+ */
+commit_sha: string, matches: Array<CodeSearchMatch>, 
+/**
+ * More matches exist beyond the result cap.
+ */
+truncated: boolean, };
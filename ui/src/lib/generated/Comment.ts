@@ -1,3 +1,4 @@
 // This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { Reaction } from "./Reaction";
 
-export type Comment = { id: string, author_id: string, body: string, created_at: string, updated_at: string, };
+export type Comment = { id: string, author_id: string, body: string, created_at: string, updated_at: string, reactions: Array<Reaction>, };
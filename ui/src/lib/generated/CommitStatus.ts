@@ -0,0 +1,3 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+export type CommitStatus = { id: string, sha: string, context: string, state: string, description: string | null, target_url: string | null, created_by: string | null, created_at: string, updated_at: string, };
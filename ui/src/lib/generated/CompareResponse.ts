@@ -0,0 +1,4 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { DiffFile } from "./DiffFile";
+
+export type CompareResponse = { base: string, head: string, files: Array<DiffFile>, total_additions: number, total_deletions: number, };
@@ -0,0 +1,16 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * Point-in-time utilization of the `PostgreSQL` pool. sqlx does not expose
+ * the callers waiting for a connection, so there is no pending count.
+ */
+export type DbPoolStats = { 
+/**
+ * Open connections, idle or in use.
+ */
+size: number, idle: number, 
+/**
+ * Checked out by a request or task. Sitting at `max` means callers are
+ * waiting for a connection.
+ */
+in_use: number, min: number, max: number, };
@@ -1,3 +1,7 @@
 // This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
 
-export type Delegation = { id: string, delegator_id: string, delegate_id: string, permission_id: string, permission_name: string, project_id: string | null, expires_at: string | null, reason: string | null, created_at: string, revoked_at: string | null, };
+export type Delegation = { id: string, delegator_id: string, delegate_id: string, permission_id: string, permission_name: string, project_id: string | null, expires_at: string | null, reason: string | null, created_at: string, revoked_at: string | null, 
+/**
+ * Self-granted emergency access (delegator and delegate are the same user).
+ */
+break_glass: boolean, };
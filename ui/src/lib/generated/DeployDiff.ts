@@ -0,0 +1,16 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { ResourceDiff } from "./ResourceDiff";
+
+export type DeployDiff = { environment: string, 
+/**
+ * Image the diff was rendered with.
+ */
+image_ref: string, 
+/**
+ * Image of the target's latest applied release, if any.
+ */
+current_image: string | null, 
+/**
+ * Ops repo branch the manifests were read from, when not the environment's own.
+ */
+manifest_branch: string | null, resources: Array<ResourceDiff>, };
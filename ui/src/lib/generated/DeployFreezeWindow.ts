@@ -0,0 +1,7 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+export type DeployFreezeWindow = { id: string, project_id: string, environment: string, name: string, cron: string | null, duration_minutes: number | null, starts_at: string | null, ends_at: string | null, created_by: string | null, created_at: string, 
+/**
+ * When this window's current opening closes; `None` if it is not open.
+ */
+active_until: string | null, };
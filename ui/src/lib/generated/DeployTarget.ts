@@ -1,3 +1,7 @@
 // This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
 
-export type DeployTarget = { id: string, project_id: string, name: string, environment: string, branch: string | null, branch_slug: string | null, ttl_hours: number | null, expires_at: string | null, default_strategy: string, ops_repo_id: string | null, manifest_path: string | null, hostname: string | null, is_active: boolean, created_at: string, updated_at: string, };
+export type DeployTarget = { id: string, project_id: string, name: string, environment: string, branch: string | null, branch_slug: string | null, ttl_hours: number | null, expires_at: string | null, default_strategy: string, ops_repo_id: string | null, manifest_path: string | null, hostname: string | null, is_active: boolean, 
+/**
+ * New releases wait for a second user's approval before rolling out.
+ */
+requires_approval: boolean, created_at: string, updated_at: string, };
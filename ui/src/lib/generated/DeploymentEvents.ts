@@ -0,0 +1,22 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { ReleaseLog } from "./ReleaseLog";
+import type { RolloutCondition } from "./RolloutCondition";
+import type { WorkloadEvent } from "./WorkloadEvent";
+
+export type DeploymentEvents = { environment: string, 
+/**
+ * The target's latest release; `None` if nothing was ever deployed.
+ */
+release_id: string | null, phase: string | null, attempts: number, conditions: Array<RolloutCondition>, 
+/**
+ * Cluster events for the release's workloads, newest first.
+ */
+events: Array<WorkloadEvent>, 
+/**
+ * Why cluster state could not be read. Logs are returned regardless.
+ */
+cluster_error: string | null, 
+/**
+ * Oldest first.
+ */
+logs: Array<ReleaseLog>, };
@@ -0,0 +1,4 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { DiffHunk } from "./DiffHunk";
+
+export type DiffFile = { path: string, old_path?: string, status: string, additions: number, deletions: number, binary: boolean, hunks: Array<DiffHunk>, };
@@ -0,0 +1,3 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+export type DiffHunk = { header: string, old_start: number, old_lines: number, new_start: number, new_lines: number, lines: Array<string>, };
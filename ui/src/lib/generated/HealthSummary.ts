@@ -1,5 +1,10 @@
 // This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { DbPoolStats } from "./DbPoolStats";
 import type { SubsystemCheck } from "./SubsystemCheck";
 import type { SubsystemStatus } from "./SubsystemStatus";
 
-export type HealthSummary = { overall: SubsystemStatus, subsystems: Array<SubsystemCheck>, uptime_seconds: number, };
+export type HealthSummary = { overall: SubsystemStatus, subsystems: Array<SubsystemCheck>, uptime_seconds: number, 
+/**
+ * Live `PostgreSQL` pool utilization.
+ */
+db_pool: DbPoolStats, };
@@ -0,0 +1,5 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { ImportFailure } from "./ImportFailure";
+import type { ImportedUser } from "./ImportedUser";
+
+export type ImportUsersResponse = { created: Array<ImportedUser>, failed: Array<ImportFailure>, };
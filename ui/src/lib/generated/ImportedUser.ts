@@ -0,0 +1,11 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+export type ImportedUser = { 
+/**
+ * 1-based position in the request (CSV: data row, header excluded).
+ */
+row: number, id: string, name: string, email: string, 
+/**
+ * Shown only in this response; the user should change it after login.
+ */
+temporary_password: string, };
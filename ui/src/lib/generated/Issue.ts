@@ -1,3 +1,4 @@
 // This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { Reaction } from "./Reaction";
 
-export type Issue = { id: string, project_id: string, number: number, author_id: string, title: string, body: string | null, status: string, labels: Array<string>, assignee_id: string | null, created_at: string, updated_at: string, };
+export type Issue = { id: string, project_id: string, number: number, author_id: string, title: string, body: string | null, status: string, labels: Array<string>, assignee_id: string | null, created_at: string, updated_at: string, reactions: Array<Reaction>, };
@@ -0,0 +1,3 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+export type Label = { id: string, project_id: string, name: string, color: string, description: string | null, created_at: string, updated_at: string, };
@@ -1,4 +1,9 @@
 // This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
 import type { JsonValue } from "./serde_json/JsonValue";
 
-export type LogEntry = { id: string, timestamp: string, trace_id: string | null, span_id: string | null, project_id: string | null, session_id: string | null, service: string, level: string, source: string, message: string, attributes: JsonValue | null, };
+export type LogEntry = { id: string, timestamp: string, trace_id: string | null, span_id: string | null, project_id: string | null, session_id: string | null, service: string, level: string, source: string, message: string, attributes: JsonValue | null, 
+/**
+ * True when the entry was read from the Parquet archive in `MinIO`
+ * rather than from Postgres.
+ */
+archived: boolean, };
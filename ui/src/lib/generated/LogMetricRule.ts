@@ -0,0 +1,3 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+export type LogMetricRule = { id: string, metric_name: string, service: string | null, level: string | null, message_contains: string | null, project_id: string | null, enabled: boolean, created_at: string, };
@@ -1,3 +1,4 @@
 // This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { Reaction } from "./Reaction";
 
-export type MergeRequest = { id: string, project_id: string, number: number, author_id: string, source_branch: string, target_branch: string, title: string, body: string | null, status: string, merged_by: string | null, merged_at: string | null, created_at: string, updated_at: string, };
+export type MergeRequest = { id: string, project_id: string, number: number, author_id: string, source_branch: string, target_branch: string, title: string, body: string | null, status: string, merged_by: string | null, merged_at: string | null, labels: Array<string>, created_at: string, updated_at: string, reactions: Array<Reaction>, };
@@ -0,0 +1,3 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+export type MergeabilityResponse = { can_merge: boolean, conflicts: Array<string>, behind_target: boolean, behind_by: number, source_sha: string, target_sha: string, };
@@ -0,0 +1,12 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { MetricCardinality } from "./MetricCardinality";
+
+export type MetricCardinalityResponse = { 
+/**
+ * Highest-cardinality metric names, most series first.
+ */
+items: Array<MetricCardinality>, 
+/**
+ * Samples dropped by the per-project series cap since this replica started.
+ */
+metrics_dropped: number, max_series_per_project: number, };
@@ -0,0 +1,7 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+export type Mirror = { url: string, interval_secs: number, has_credential: boolean, protected_branch_policy: string, last_synced_at: string | null, last_error: string | null, 
+/**
+ * Protected branches left behind upstream by the `keep` policy.
+ */
+diverged: Array<string>, };
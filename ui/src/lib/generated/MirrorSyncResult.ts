@@ -0,0 +1,18 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * Outcome of a mirror sync.
+ */
+export type MirrorSyncResult = { 
+/**
+ * Refs created or moved to match upstream.
+ */
+updated: Array<string>, 
+/**
+ * Refs deleted because upstream no longer has them.
+ */
+deleted: Array<string>, 
+/**
+ * Protected branches kept at their local commit although upstream diverged.
+ */
+diverged: Array<string>, };
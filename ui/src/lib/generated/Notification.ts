@@ -1,3 +1,3 @@
 // This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
 
-export type Notification = { id: string, notification_type: string, subject: string, body: string | null, channel: string, status: string, ref_type: string | null, ref_id: string | null, created_at: string, };
+export type Notification = { id: string, notification_type: string, subject: string, body: string | null, channel: string, status: string, ref_type: string | null, ref_id: string | null, read_at: string | null, created_at: string, };
@@ -0,0 +1,7 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+export type NotificationPreference = { notification_type: string, 
+/**
+ * `immediate` (default), `hourly`, or `daily` email digest.
+ */
+digest: string, };
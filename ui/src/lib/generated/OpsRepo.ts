@@ -1,3 +1,3 @@
 // This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
 
-export type OpsRepo = { id: string, name: string, repo_path: string, branch: string, path: string, created_at: string, };
+export type OpsRepo = { id: string, name: string, repo_path: string, branch: string, path: string, remote_url: string | null, credential_secret_id: string | null, created_at: string, };
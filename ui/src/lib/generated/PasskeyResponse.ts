@@ -1,3 +1,11 @@
 // This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
 
-export type PasskeyResponse = { id: string, name: string, created_at: string, last_used_at: string | null, backup_eligible: boolean, backup_state: boolean, transports: Array<string>, };
+export type PasskeyResponse = { id: string, name: string, created_at: string, last_used_at: string | null, backup_eligible: boolean, backup_state: boolean, transports: Array<string>, 
+/**
+ * Authenticator model, when it disclosed one at registration.
+ */
+aaguid: string | null, 
+/**
+ * Attestation statement format given at registration (`none`, `packed`, ...).
+ */
+attestation_format: string | null, user_verified: boolean, };
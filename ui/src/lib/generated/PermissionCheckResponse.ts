@@ -0,0 +1,7 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+export type PermissionCheckResponse = { 
+/**
+ * Keyed by project ID (or `"global"`), then by permission name.
+ */
+results: { [key in string]: { [key in string]: boolean } }, };
@@ -0,0 +1,19 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * A problem found by [`lint`].
+ */
+export type PipelineLintIssue = { 
+/**
+ * Path to the offending node, e.g. `pipeline.steps[1].image`. `None` when
+ * the YAML parser reports a position instead.
+ */
+path: string | null, 
+/**
+ * 1-based line, for syntax and type errors.
+ */
+line: number | null, 
+/**
+ * 1-based column, for syntax and type errors.
+ */
+column: number | null, message: string, };
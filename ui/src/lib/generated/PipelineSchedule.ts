@@ -0,0 +1,7 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+export type PipelineSchedule = { id: string, project_id: string, cron: string, git_ref: string, enabled: boolean, created_by: string | null, 
+/**
+ * `None` while the schedule is disabled.
+ */
+next_run_at: string | null, last_run_at: string | null, last_pipeline_id: string | null, created_at: string, updated_at: string, };
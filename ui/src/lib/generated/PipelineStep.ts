@@ -1,3 +1,7 @@
 // This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
 
-export type PipelineStep = { id: string, step_order: number, name: string, image: string, status: string, exit_code: number | null, duration_ms: number | null, log_ref: string | null, gate: boolean, depends_on: Array<string>, created_at: string, };
+export type PipelineStep = { id: string, step_order: number, name: string, image: string, status: string, exit_code: number | null, duration_ms: number | null, log_ref: string | null, gate: boolean, depends_on: Array<string>, 
+/**
+ * Why the step was skipped, when `status` is `skipped`.
+ */
+skip_reason: string | null, created_at: string, };
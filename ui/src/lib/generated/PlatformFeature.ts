@@ -0,0 +1,8 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { PlatformFeatureOverride } from "./PlatformFeatureOverride";
+
+export type PlatformFeature = { key: string, description: string, default_enabled: boolean, 
+/**
+ * Global state set by an admin; `null` means the default applies.
+ */
+enabled: boolean | null, overrides: Array<PlatformFeatureOverride>, };
@@ -0,0 +1,6 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * Exactly one of `project_id` and `user_id` is set.
+ */
+export type PlatformFeatureOverride = { id: string, feature: string, project_id: string | null, user_id: string | null, enabled: boolean, created_by: string | null, created_at: string, updated_at: string, };
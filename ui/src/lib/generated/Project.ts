@@ -1,3 +1,7 @@
 // This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
 
-export type Project = { id: string, owner_id: string, workspace_id: string, name: string, display_name: string | null, description: string | null, visibility: string, default_branch: string, namespace_slug: string, agent_image: string | null, is_active: boolean, created_at: string, updated_at: string, };
+export type Project = { id: string, owner_id: string, workspace_id: string, name: string, display_name: string | null, description: string | null, visibility: string, default_branch: string, namespace_slug: string, agent_image: string | null, is_active: boolean, 
+/**
+ * Set while the project is archived (read-only).
+ */
+archived_at: string | null, created_at: string, updated_at: string, };
@@ -0,0 +1,11 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+export type PushMirror = { url: string, has_credential: boolean, 
+/**
+ * Refs are waiting to be pushed (a push is running or will be retried).
+ */
+pending: boolean, 
+/**
+ * Consecutive failed attempts.
+ */
+attempts: number, next_attempt_at: string | null, last_pushed_at: string | null, last_error: string | null, };
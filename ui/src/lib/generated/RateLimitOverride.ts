@@ -0,0 +1,6 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * Exactly one of `project_id` and `token_id` is set.
+ */
+export type RateLimitOverride = { id: string, project_id: string | null, token_id: string | null, requests_per_minute: number, created_by: string | null, created_at: string, updated_at: string, };
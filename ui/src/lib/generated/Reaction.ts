@@ -0,0 +1,10 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * Users who reacted to a target with one emoji.
+ */
+export type Reaction = { emoji: string, count: number, 
+/**
+ * Whether the requesting user is one of them.
+ */
+reacted: boolean, };
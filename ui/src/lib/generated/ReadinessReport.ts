@@ -0,0 +1,11 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { SubsystemCheck } from "./SubsystemCheck";
+
+/**
+ * `/readyz` body: overall readiness plus the check of every required dependency.
+ */
+export type ReadinessReport = { ready: boolean, 
+/**
+ * Names of the dependencies that are not healthy.
+ */
+failing: Array<string>, dependencies: Array<SubsystemCheck>, };
@@ -0,0 +1,14 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * One line of the reconciler's log trail for a release.
+ */
+export type ReleaseLog = { 
+/**
+ * Reconciler attempt the line belongs to, starting at 1.
+ */
+attempt: number, 
+/**
+ * `info`, `warn` or `error`.
+ */
+level: string, message: string, created_at: string, };
@@ -0,0 +1,12 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { ResourceChange } from "./ResourceChange";
+
+/**
+ * How one resource would change if the manifests were applied.
+ */
+export type ResourceDiff = { kind: string, name: string, change: ResourceChange, 
+/**
+ * Unified diff of the live object (`-`) against the dry-run result (`+`), as YAML.
+ * Empty when unchanged.
+ */
+diff: string, };
@@ -0,0 +1,14 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * Outcome of [`rewrap_all`].
+ */
+export type RewrapReport = { 
+/**
+ * Key version everything is now wrapped with.
+ */
+key_version: number, rewrapped: number, unchanged: number, 
+/**
+ * Rows no loaded key could decrypt. Old keys must not be retired while non-zero.
+ */
+failed: number, };
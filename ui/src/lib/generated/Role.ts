@@ -1,3 +1,7 @@
 // This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
 
-export type Role = { id: string, name: string, description: string | null, is_system: boolean, created_at: string, };
+export type Role = { id: string, name: string, description: string | null, is_system: boolean, 
+/**
+ * Role whose permissions this role inherits (transitively).
+ */
+parent_role_id: string | null, created_at: string, };
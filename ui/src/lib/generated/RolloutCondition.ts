@@ -0,0 +1,6 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * A Deployment status condition (`Available`, `Progressing`, `ReplicaFailure`).
+ */
+export type RolloutCondition = { deployment: string, condition_type: string, status: string, reason: string | null, message: string | null, last_update: string | null, };
@@ -0,0 +1,15 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+export type SearchResult = { 
+/**
+ * `issue` or `mr`.
+ */
+kind: string, id: string, project_id: string, project_name: string, number: number, title: string, status: string, 
+/**
+ * Best-matching body fragment with hits wrapped in `**`.
+ */
+snippet: string | null, 
+/**
+ * Relevance; results are ordered by it, highest first.
+ */
+rank: number, updated_at: string, };
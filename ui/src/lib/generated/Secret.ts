@@ -1,3 +1,7 @@
 // This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
 
-export type Secret = { id: string, project_id: string | null, workspace_id: string | null, environment: string | null, name: string, scope: string, version: number, created_at: string, updated_at: string, };
+export type Secret = { id: string, project_id: string | null, workspace_id: string | null, environment: string | null, name: string, scope: string, 
+/**
+ * `local` or `vault`.
+ */
+backend: string, version: number, created_at: string, updated_at: string, };
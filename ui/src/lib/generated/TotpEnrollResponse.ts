@@ -0,0 +1,11 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+export type TotpEnrollResponse = { 
+/**
+ * Base32 secret for manual entry into an authenticator app.
+ */
+secret: string, 
+/**
+ * `otpauth://` URI to render as a QR code.
+ */
+provisioning_uri: string, };
@@ -0,0 +1,8 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { VariableGroupVariable } from "./VariableGroupVariable";
+
+export type VariableGroup = { id: string, 
+/**
+ * `None` for global groups.
+ */
+project_id: string | null, name: string, description: string | null, variables: Array<VariableGroupVariable>, created_by: string | null, created_at: string, updated_at: string, };
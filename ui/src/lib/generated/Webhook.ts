@@ -1,3 +1,11 @@
 // This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
 
-export type Webhook = { id: string, project_id: string, url: string, events: Array<string>, active: boolean, created_at: string, };
+export type Webhook = { id: string, project_id: string, url: string, events: Array<string>, active: boolean, 
+/**
+ * Branch globs a push must match to be delivered (empty = all branches).
+ */
+branch_filter: Array<string>, 
+/**
+ * Path globs at least one changed file must match (empty = all paths).
+ */
+path_filter: Array<string>, created_at: string, };
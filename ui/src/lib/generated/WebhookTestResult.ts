@@ -0,0 +1,22 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * Result of a test delivery (`POST .../webhooks/{wh_id}/test`).
+ */
+export type WebhookTestResult = { 
+/**
+ * The receiver answered with a 2xx status.
+ */
+ok: boolean, 
+/**
+ * The receiver's HTTP status; `None` if it never answered.
+ */
+status_code: number | null, 
+/**
+ * Start of the receiver's response body (at most 4 KiB).
+ */
+response_body: string | null, 
+/**
+ * Why no response was received.
+ */
+error: string | null, duration_ms: number, };
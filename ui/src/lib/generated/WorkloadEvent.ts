@@ -0,0 +1,18 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * A Kubernetes event about one of a release's workloads.
+ */
+export type WorkloadEvent = { 
+/**
+ * Kind of the object the event is about: `Deployment`, `ReplicaSet` or `Pod`.
+ */
+kind: string, name: string, 
+/**
+ * `Normal` or `Warning`.
+ */
+event_type: string, reason: string | null, message: string | null, 
+/**
+ * How many times the event has been seen.
+ */
+count: number, last_seen: string | null, };
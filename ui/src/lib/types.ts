@@ -7,6 +7,9 @@ export type { UserType } from './generated/UserType';
 export type { LoginResponse } from './generated/LoginResponse';
 export type { ApiToken } from './generated/ApiToken';
 export type { CreateTokenResponse } from './generated/CreateTokenResponse';
+export type { RateLimitOverride } from './generated/RateLimitOverride';
+export type { PlatformFeature } from './generated/PlatformFeature';
+export type { PlatformFeatureOverride } from './generated/PlatformFeatureOverride';
 
 // Projects
 export type { Project } from './generated/Project';
@@ -30,6 +33,11 @@ export type { Deployment } from './generated/Deployment';
 export type { DeploymentHistory } from './generated/DeploymentHistory';
 export type { OpsRepo } from './generated/OpsRepo';
 export type { PreviewDeployment } from './generated/PreviewDeployment';
+export type { DeployFreezeWindow } from './generated/DeployFreezeWindow';
+export type { DeploymentEvents } from './generated/DeploymentEvents';
+export type { ReleaseLog } from './generated/ReleaseLog';
+export type { RolloutCondition } from './generated/RolloutCondition';
+export type { WorkloadEvent } from './generated/WorkloadEvent';
 
 // Git Browser
 export type { TreeEntry } from './generated/TreeEntry';
@@ -45,6 +53,7 @@ export type { ServiceAccountResponse } from './generated/ServiceAccountResponse'
 
 // Webhooks
 export type { Webhook } from './generated/Webhook';
+export type { WebhookTestResult } from './generated/WebhookTestResult';
 
 // Observability — Logs & Traces
 export type { LogEntry } from './generated/LogEntry';
@@ -85,6 +94,9 @@ export type { PasskeyLoginResponse } from './generated/PasskeyLoginResponse';
 export type { DashboardStats } from './generated/DashboardStats';
 export type { AuditLogEntry } from './generated/AuditLogEntry';
 export type { OnboardingStatus } from './generated/OnboardingStatus';
+export type { ActivityEntry } from './generated/ActivityEntry';
+export type { SearchResult } from './generated/SearchResult';
+export type { Reaction } from './generated/Reaction';
 
 // Validation
 export type { ValidateKeyResponse } from './generated/ValidateKeyResponse';
@@ -1,6 +1,6 @@
-import { useState } from 'preact/hooks';
+import { useEffect, useState } from 'preact/hooks';
 import { useAuth } from '../lib/auth';
-import { ApiError } from '../lib/api';
+import { api, ApiError } from '../lib/api';
 
 export function Login() {
   const { login, loginWithPasskey } = useAuth();
@@ -8,6 +8,13 @@ export function Login() {
   const [password, setPassword] = useState('');
   const [error, setError] = useState('');
   const [loading, setLoading] = useState(false);
+  const [ssoEnabled, setSsoEnabled] = useState(false);
+
+  useEffect(() => {
+    api.get<{ enabled: boolean }>('/api/auth/oidc/status')
+      .then((res) => setSsoEnabled(res.enabled))
+      .catch(() => setSsoEnabled(false));
+  }, []);
 
   const submit = async (e: Event) => {
     e.preventDefault();
@@ -61,16 +68,22 @@ export function Login() {
             {loading ? 'Signing in...' : 'Sign in'}
           </button>
         </form>
+        {(ssoEnabled || supportsPasskey) && (
+          <div class="login-divider">
+            <span>or</span>
+          </div>
+        )}
+        {ssoEnabled && (
+          <a class="btn btn-ghost" style="width:100%;margin-bottom:0.5rem"
+            href="/api/auth/oidc/login">
+            Sign in with SSO
+          </a>
+        )}
         {supportsPasskey && (
-          <>
-            <div class="login-divider">
-              <span>or</span>
-            </div>
-            <button class="btn btn-ghost" style="width:100%"
-              onClick={handlePasskey} disabled={loading}>
-              Sign in with Passkey
-            </button>
-          </>
+          <button class="btn btn-ghost" style="width:100%"
+            onClick={handlePasskey} disabled={loading}>
+            Sign in with Passkey
+          </button>
         )}
       </div>
     </div>
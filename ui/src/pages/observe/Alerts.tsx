@@ -236,7 +236,7 @@ export function Alerts() {
           <div class="form-group">
             <label>Notification channels (comma-separated)</label>
             <input class="input" value={form.channels}
-              placeholder="email, webhook"
+              placeholder="email:oncall@example.com, webhook:https://hooks.example.com/alerts"
               onInput={(e) => setForm({ ...form, channels: (e.target as HTMLInputElement).value })} />
           </div>
           <div class="form-group">